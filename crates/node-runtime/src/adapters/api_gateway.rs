@@ -4,7 +4,9 @@
 //! via the Event Bus, not direct function calls.
 
 use async_trait::async_trait;
-use qc_16_api_gateway::ipc::{IpcError, IpcRequest, IpcSender};
+use qc_16_api_gateway::ipc::{publish_dead_letter, DeadLetter, IpcError, IpcRequest, IpcSender};
+use qc_16_api_gateway::{LogLevel, NodeLogLine};
+use quantum_telemetry::{LogRecord, PropagatedContext};
 use shared_bus::{BlockchainEvent, EventPublisher, InMemoryEventBus};
 use std::sync::Arc;
 use tracing::debug;
@@ -40,6 +42,10 @@ impl IpcSender for EventBusIpcSender {
             target: request.target.clone(),
             method: request.method_name(),
             params: request.payload_as_json(),
+            trace_parent: request
+                .trace_context
+                .as_ref()
+                .map(PropagatedContext::to_traceparent),
         };
 
         // Publish to event bus
@@ -59,6 +65,26 @@ impl IpcSender for EventBusIpcSender {
 
         Ok(())
     }
+
+    fn is_connected(&self) -> bool {
+        // With no subscribers nothing on the bus can answer a query
+        self.bus.subscriber_count() > 0
+    }
+
+    async fn dead_letter(&self, letter: DeadLetter) -> Result<(), IpcError> {
+        publish_dead_letter(&self.bus, letter).await;
+        Ok(())
+    }
+}
+
+/// Convert a telemetry log record into a line for the gateway's `/logs` feed.
+pub fn node_log_line(record: LogRecord) -> NodeLogLine {
+    NodeLogLine {
+        timestamp_ms: record.timestamp_ms,
+        level: record.level.parse().unwrap_or(LogLevel::Info),
+        target: record.target,
+        message: record.message,
+    }
 }
 
 #[cfg(test)]
@@ -71,4 +97,17 @@ mod tests {
         let sender = EventBusIpcSender::new(bus);
         assert!(Arc::strong_count(&sender.bus) >= 1);
     }
+
+    #[test]
+    fn test_node_log_line_parses_level() {
+        let line = node_log_line(LogRecord {
+            timestamp_ms: 42,
+            level: "warn".to_string(),
+            target: "qc_01".to_string(),
+            message: "peer banned".to_string(),
+        });
+        assert_eq!(line.level, LogLevel::Warn);
+        assert_eq!(line.timestamp_ms, 42);
+        assert_eq!(line.message, "peer banned");
+    }
 }
@@ -18,7 +18,12 @@
 //! - Receives events from choreography
 //! - Delegates to domain for state management
 //! - Publishes BlockStored events when assembly completes
+//!
+//! With the `zk` feature, the validity proof carried by `StateRootComputed`
+//! is held until the block completes and forwarded in `BlockStored`.
 
+#[cfg(feature = "zk")]
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -29,6 +34,8 @@ use qc_02_block_storage::{AssemblyConfig, BlockAssemblyBuffer, PendingBlockAssem
 use shared_types::{BlockHeader, ConsensusProof, SubsystemId, ValidatedBlock};
 
 use crate::adapters::EventBusAdapter;
+#[cfg(feature = "zk")]
+use crate::wiring::ValidityProof;
 use crate::wiring::{ChoreographyEvent, EventRouter};
 
 /// Block Storage adapter implementing Stateful Assembler pattern.
@@ -43,6 +50,9 @@ pub struct BlockStorageAdapter {
     assembly_buffer: Arc<RwLock<BlockAssemblyBuffer>>,
     /// Assembly timeout for GC logging.
     assembly_timeout: Duration,
+    /// Validity proofs of blocks still being assembled.
+    #[cfg(feature = "zk")]
+    validity_proofs: RwLock<HashMap<[u8; 32], ValidityProof>>,
 }
 
 impl BlockStorageAdapter {
@@ -59,6 +69,8 @@ impl BlockStorageAdapter {
             event_bus,
             assembly_buffer,
             assembly_timeout,
+            #[cfg(feature = "zk")]
+            validity_proofs: RwLock::new(HashMap::new()),
         }
     }
 
@@ -151,6 +163,15 @@ impl BlockStorageAdapter {
         self.try_complete_assembly(block_hash).await
     }
 
+    /// Hold a block's validity proof until its assembly completes.
+    ///
+    /// Call before `on_state_root` so the proof is present if the state
+    /// root completes the block.
+    #[cfg(feature = "zk")]
+    pub fn attach_validity_proof(&self, block_hash: [u8; 32], proof: ValidityProof) {
+        self.validity_proofs.write().insert(block_hash, proof);
+    }
+
     /// Try to complete assembly if all components present.
     /// Delegates completeness check to domain.
     async fn try_complete_assembly(&self, block_hash: [u8; 32]) -> Result<(), BlockStorageError> {
@@ -181,6 +202,8 @@ impl BlockStorageAdapter {
                 merkle_root,
                 state_root,
                 sender_id: SubsystemId::BlockStorage,
+                #[cfg(feature = "zk")]
+                validity_proof: self.validity_proofs.write().remove(&block_hash),
             };
 
             self.event_bus
@@ -207,6 +230,8 @@ impl BlockStorageAdapter {
         };
 
         for (block_hash, assembly) in expired {
+            #[cfg(feature = "zk")]
+            self.validity_proofs.write().remove(&block_hash);
             let missing = Self::get_missing_components(&assembly);
             warn!(
                 "Assembly timeout for {:?}, missing: {:?}",
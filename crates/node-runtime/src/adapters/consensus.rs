@@ -4,7 +4,8 @@
 //!
 //! ## V2.3 Choreography
 //!
-//! - Subscribes to: BlockProduced (from Block Production 17), BlockStored (for height tracking)
+//! - Subscribes to: BlockProduced and BlockProposed (from Block Production 17),
+//!   BlockStored (for height tracking)
 //! - Publishes: BlockValidated (triggers TxIndexing 3, StateMgmt 4, BlockStorage 2)
 //!
 //! ## Architecture
@@ -27,6 +28,9 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
+#[cfg(feature = "qc-17")]
+use crate::validator::{ProposalError, ProposerSchedule};
+
 /// Parameters for block validation from BlockProduced event.
 #[derive(Debug, Clone)]
 pub struct BlockProducedParams {
@@ -42,6 +46,31 @@ pub struct BlockProducedParams {
     pub timestamp: u64,
     /// Parent block hash.
     pub parent_hash: [u8; 32],
+    /// Coinbase reward claimed by the producer.
+    pub coinbase: Option<shared_types::CoinbaseTransaction>,
+}
+
+/// Parameters for validation from a PoS BlockProposed event.
+#[derive(Debug, Clone)]
+pub struct BlockProposedParams {
+    /// Block hash.
+    pub block_hash: [u8; 32],
+    /// Block height.
+    pub block_height: u64,
+    /// Parent block hash.
+    pub parent_hash: [u8; 32],
+    /// Block timestamp.
+    pub timestamp: u64,
+    /// Fee recipient in the signed header.
+    pub beneficiary: [u8; 20],
+    /// Gas used, as in the signed header.
+    pub gas_used: u64,
+    /// Slot the block was proposed for.
+    pub slot: u64,
+    /// Index of the proposer in the validator set.
+    pub validator_index: u32,
+    /// Proposer's signature over the serialized header.
+    pub signature: Vec<u8>,
 }
 
 /// Consensus adapter - validates blocks and publishes BlockValidated events.
@@ -68,6 +97,9 @@ pub struct ConsensusAdapter {
     /// Current chain height (event-sourced from BlockStored).
     /// Uses AtomicU64 for lock-free reads during validation.
     chain_height: AtomicU64,
+    /// Who may propose each PoS slot, and their keys.
+    #[cfg(feature = "qc-17")]
+    proposers: Option<ProposerSchedule>,
 }
 
 impl ConsensusAdapter {
@@ -80,9 +112,24 @@ impl ConsensusAdapter {
             validator,
             validated_blocks: RwLock::new(HashSet::new()),
             chain_height: AtomicU64::new(0),
+            #[cfg(feature = "qc-17")]
+            proposers: None,
         }
     }
 
+    /// Validate with chain-specific rules (see `ChainSpec::block_validation_config`).
+    pub fn with_validation_config(mut self, config: BlockValidationConfig) -> Self {
+        self.validator = BlockValidator::new(config);
+        self
+    }
+
+    /// Check PoS proposals against the genesis validators' schedule and keys.
+    #[cfg(feature = "qc-17")]
+    pub fn with_proposer_schedule(mut self, proposers: ProposerSchedule) -> Self {
+        self.proposers = Some(proposers);
+        self
+    }
+
     /// Set the initial chain height (from storage on startup).
     /// Called once during initialization before event handlers start.
     pub fn set_initial_chain_height(&self, height: u64) {
@@ -157,6 +204,7 @@ impl ConsensusAdapter {
             nonce: params.nonce,
             timestamp: params.timestamp,
             parent_hash: params.parent_hash,
+            coinbase: params.coinbase.clone(),
         };
 
         // Get current state (chain height is atomic, no lock needed)
@@ -189,7 +237,7 @@ impl ConsensusAdapter {
                 );
 
                 // Publish BlockValidated event
-                self.publish_block_validated(params)?;
+                self.publish_block_validated(params.block_hash, params.block_height)?;
 
                 Ok(())
             }
@@ -200,6 +248,86 @@ impl ConsensusAdapter {
         }
     }
 
+    /// Process a BlockProposed event - validate a signed PoS proposal and
+    /// publish BlockValidated.
+    ///
+    /// The slot must be assigned to the proposing validator, the header must
+    /// hash to the block hash and its signature must verify with the
+    /// proposer's key. Then the domain checks that apply to a proposal run:
+    /// duplicate, sequential height and timestamp bounds (there is no
+    /// difficulty to check).
+    pub fn process_block_proposed(
+        &self,
+        params: &BlockProposedParams,
+    ) -> Result<(), ConsensusAdapterError> {
+        debug!(
+            "[qc-08] Validating BlockProposed #{} (slot: {})",
+            params.block_height, params.slot
+        );
+
+        self.verify_proposer(params)?;
+
+        let current_height = self.chain_height.load(Ordering::SeqCst);
+        let current_time = self.current_time();
+        let warnings = {
+            let validated_blocks = self.validated_blocks.read();
+            self.validator
+                .check_duplicate(&params.block_hash, &validated_blocks)
+                .map_err(ConsensusAdapterError::from_domain_error)?;
+            [
+                self.validator
+                    .validate_height(params.block_height, current_height)
+                    .map_err(ConsensusAdapterError::from_domain_error)?,
+                self.validator
+                    .validate_timestamp(params.timestamp, current_time)
+                    .map_err(ConsensusAdapterError::from_domain_error)?,
+            ]
+        };
+        for warning in warnings.iter().flatten() {
+            warn!("[qc-08] Validation warning: {:?}", warning);
+        }
+
+        self.update_validated_blocks_cache(&params.block_hash);
+        info!(
+            "[qc-08] ✓ Proposal #{} for slot {} validated (validator #{})",
+            params.block_height, params.slot, params.validator_index
+        );
+
+        self.publish_block_validated(params.block_hash, params.block_height)
+    }
+
+    /// Check the proposal against the proposer schedule.
+    #[cfg(feature = "qc-17")]
+    fn verify_proposer(&self, params: &BlockProposedParams) -> Result<(), ConsensusAdapterError> {
+        let proposers = self
+            .proposers
+            .as_ref()
+            .ok_or(ConsensusAdapterError::NoProposerSchedule)?;
+        let header = qc_17_block_production::utils::serialize_block_header(
+            &primitive_types::H256(params.parent_hash),
+            params.block_height,
+            params.timestamp,
+            &params.beneficiary,
+            params.gas_used,
+            None,
+        );
+        proposers
+            .verify(
+                params.slot,
+                params.validator_index,
+                &header,
+                &params.block_hash,
+                &params.signature,
+            )
+            .map_err(ConsensusAdapterError::InvalidProposal)
+    }
+
+    /// Without Block Production's header format, no proposal can be checked.
+    #[cfg(not(feature = "qc-17"))]
+    fn verify_proposer(&self, _params: &BlockProposedParams) -> Result<(), ConsensusAdapterError> {
+        Err(ConsensusAdapterError::NoProposerSchedule)
+    }
+
     /// Update validated blocks cache after successful validation.
     /// Note: Chain height is NOT updated here - it's event-sourced from BlockStored.
     fn update_validated_blocks_cache(&self, block_hash: &[u8; 32]) {
@@ -215,11 +343,12 @@ impl ConsensusAdapter {
     /// Publish BlockValidated event to choreography.
     fn publish_block_validated(
         &self,
-        params: &BlockProducedParams,
+        block_hash: [u8; 32],
+        block_height: u64,
     ) -> Result<(), ConsensusAdapterError> {
         let event = ChoreographyEvent::BlockValidated {
-            block_hash: params.block_hash,
-            block_height: params.block_height,
+            block_hash,
+            block_height,
             sender_id: SubsystemId::Consensus,
         };
 
@@ -230,7 +359,7 @@ impl ConsensusAdapter {
 
         info!(
             "[qc-08] 📤 Published BlockValidated #{} to choreography",
-            params.block_height
+            block_height
         );
 
         Ok(())
@@ -248,6 +377,13 @@ pub enum ConsensusAdapterError {
     InvalidHeight { expected: u64, got: u64 },
     /// Invalid timestamp.
     InvalidTimestamp,
+    /// Coinbase reward does not match the subsidy schedule.
+    InvalidCoinbase(String),
+    /// No proposer schedule to check PoS proposals against.
+    NoProposerSchedule,
+    /// Proposal not signed by its slot's proposer.
+    #[cfg(feature = "qc-17")]
+    InvalidProposal(ProposalError),
     /// Failed to publish event.
     PublishFailed(String),
 }
@@ -261,8 +397,13 @@ impl ConsensusAdapterError {
             BlockValidationError::NonSequentialHeight { expected, got } => {
                 Self::InvalidHeight { expected, got }
             }
-            BlockValidationError::ZeroDifficulty => Self::InvalidDifficulty,
+            BlockValidationError::ZeroDifficulty
+            | BlockValidationError::TargetAboveLimit { .. } => Self::InvalidDifficulty,
             BlockValidationError::FutureTimestamp { .. } => Self::InvalidTimestamp,
+            err @ (BlockValidationError::CoinbaseHeightMismatch { .. }
+            | BlockValidationError::ExcessiveCoinbase { .. }) => {
+                Self::InvalidCoinbase(err.to_string())
+            }
         }
     }
 }
@@ -276,6 +417,10 @@ impl std::fmt::Display for ConsensusAdapterError {
                 write!(f, "Invalid height: expected {}, got {}", expected, got)
             }
             Self::InvalidTimestamp => write!(f, "Invalid timestamp"),
+            Self::InvalidCoinbase(msg) => write!(f, "Invalid coinbase: {}", msg),
+            Self::NoProposerSchedule => write!(f, "No proposer schedule for PoS proposals"),
+            #[cfg(feature = "qc-17")]
+            Self::InvalidProposal(e) => write!(f, "Invalid proposal: {}", e),
             Self::PublishFailed(msg) => write!(f, "Failed to publish: {}", msg),
         }
     }
@@ -319,6 +464,167 @@ mod tests {
         assert_eq!(adapter.chain_height(), 10);
     }
 
+    #[cfg(feature = "qc-17")]
+    struct Validators {
+        keys: Vec<shared_crypto::Secp256k1KeyPair>,
+        schedule: ProposerSchedule,
+    }
+
+    /// Two equally staked genesis validators.
+    #[cfg(feature = "qc-17")]
+    fn validators() -> Validators {
+        let keys: Vec<_> = (0..2)
+            .map(|_| shared_crypto::Secp256k1KeyPair::generate())
+            .collect();
+        let pubkeys = keys.iter().map(|k| *k.public_key().as_bytes()).collect();
+        Validators {
+            schedule: ProposerSchedule::new(pubkeys, &[1, 1], 32),
+            keys,
+        }
+    }
+
+    /// Proposal for `block_height` in slot `block_height` from its assigned
+    /// proposer, not yet signed.
+    #[cfg(feature = "qc-17")]
+    fn proposal(validators: &Validators, block_height: u64) -> BlockProposedParams {
+        BlockProposedParams {
+            block_hash: [0u8; 32],
+            block_height,
+            parent_hash: [0u8; 32],
+            timestamp: create_test_adapter().current_time(),
+            beneficiary: [0x11; 20],
+            gas_used: 21_000,
+            slot: block_height,
+            validator_index: validators.schedule.proposer(block_height).unwrap(),
+            signature: Vec::new(),
+        }
+    }
+
+    /// Hash and sign the proposal's header with `key`.
+    #[cfg(feature = "qc-17")]
+    fn sign(
+        mut params: BlockProposedParams,
+        key: &shared_crypto::Secp256k1KeyPair,
+    ) -> BlockProposedParams {
+        let header = qc_17_block_production::utils::serialize_block_header(
+            &primitive_types::H256(params.parent_hash),
+            params.block_height,
+            params.timestamp,
+            &params.beneficiary,
+            params.gas_used,
+            None,
+        );
+        params.block_hash = qc_17_block_production::utils::sha256d(&header);
+        params.signature = key.sign(&header).as_bytes().to_vec();
+        params
+    }
+
+    /// Proposal signed by its assigned proposer.
+    #[cfg(feature = "qc-17")]
+    fn signed_proposal(validators: &Validators, block_height: u64) -> BlockProposedParams {
+        let params = proposal(validators, block_height);
+        let key = &validators.keys[params.validator_index as usize];
+        sign(params, key)
+    }
+
+    #[cfg(feature = "qc-17")]
+    #[test]
+    fn test_block_proposed_is_validated() {
+        let validators = validators();
+        let router = Arc::new(EventRouter::default());
+        let mut events = router.subscribe();
+        let adapter = ConsensusAdapter::new(Arc::clone(&router))
+            .with_proposer_schedule(validators.schedule.clone());
+
+        adapter
+            .process_block_proposed(&signed_proposal(&validators, 1))
+            .unwrap();
+        assert!(matches!(
+            events.try_recv(),
+            Ok(ChoreographyEvent::BlockValidated {
+                block_height: 1,
+                sender_id: SubsystemId::Consensus,
+                ..
+            })
+        ));
+
+        // Same block again, and a far-future timestamp
+        assert!(matches!(
+            adapter.process_block_proposed(&signed_proposal(&validators, 1)),
+            Err(ConsensusAdapterError::DuplicateBlock)
+        ));
+        let future = BlockProposedParams {
+            timestamp: u64::MAX / 2,
+            ..proposal(&validators, 2)
+        };
+        let key = &validators.keys[future.validator_index as usize];
+        assert!(matches!(
+            adapter.process_block_proposed(&sign(future, key)),
+            Err(ConsensusAdapterError::InvalidTimestamp)
+        ));
+    }
+
+    #[cfg(feature = "qc-17")]
+    #[test]
+    fn test_proposal_not_signed_by_slot_proposer_is_rejected() {
+        let validators = validators();
+        let router = Arc::new(EventRouter::default());
+        let mut events = router.subscribe();
+        let adapter = ConsensusAdapter::new(Arc::clone(&router))
+            .with_proposer_schedule(validators.schedule.clone());
+
+        let params = proposal(&validators, 1);
+        let other = 1 - params.validator_index;
+
+        // The other validator's key signs for the assigned proposer
+        let wrong_key = sign(params.clone(), &validators.keys[other as usize]);
+        assert!(matches!(
+            adapter.process_block_proposed(&wrong_key),
+            Err(ConsensusAdapterError::InvalidProposal(
+                ProposalError::Signature(_)
+            ))
+        ));
+
+        // The other validator proposes with its own key
+        let not_assigned = sign(
+            BlockProposedParams {
+                validator_index: other,
+                ..params.clone()
+            },
+            &validators.keys[other as usize],
+        );
+        assert!(matches!(
+            adapter.process_block_proposed(&not_assigned),
+            Err(ConsensusAdapterError::InvalidProposal(
+                ProposalError::WrongProposer { .. }
+            ))
+        ));
+
+        // A header field changed after signing
+        let tampered = BlockProposedParams {
+            gas_used: 0,
+            ..signed_proposal(&validators, 1)
+        };
+        assert!(matches!(
+            adapter.process_block_proposed(&tampered),
+            Err(ConsensusAdapterError::InvalidProposal(
+                ProposalError::HashMismatch
+            ))
+        ));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[cfg(feature = "qc-17")]
+    #[test]
+    fn test_proposals_need_a_proposer_schedule() {
+        let validators = validators();
+        let adapter = create_test_adapter();
+        assert!(matches!(
+            adapter.process_block_proposed(&signed_proposal(&validators, 1)),
+            Err(ConsensusAdapterError::NoProposerSchedule)
+        ));
+    }
+
     #[test]
     fn test_validated_blocks_cache() {
         let adapter = create_test_adapter();
@@ -11,8 +11,9 @@
 
 #[cfg(feature = "qc-15")]
 use qc_15_cross_chain::{
-    calculate_timelocks, create_atomic_swap, AtomicSwap, AtomicSwapParams, ChainId,
-    CrossChainConfig, Hash, Secret, HTLC,
+    calculate_timelocks, create_atomic_swap, quote_swap, Address, AtomicSwap, AtomicSwapParams,
+    ChainId, CrossChainConfig, CrossChainError, Hash, MakerOffer, MakerRegistry, QuoteParams,
+    Secret, SwapPair, SwapQuote, HTLC,
 };
 
 #[cfg(feature = "qc-15")]
@@ -29,6 +30,8 @@ pub struct CrossChainAdapter {
     swaps: HashMap<Hash, AtomicSwap>,
     /// Active HTLCs.
     htlcs: HashMap<Hash, HTLC>,
+    /// Swap liquidity advertised through qc-16.
+    makers: MakerRegistry,
 }
 
 #[cfg(feature = "qc-15")]
@@ -40,6 +43,7 @@ impl CrossChainAdapter {
             subsystem_id: 15,
             swaps: HashMap::new(),
             htlcs: HashMap::new(),
+            makers: MakerRegistry::new(),
         }
     }
 
@@ -68,7 +72,7 @@ impl CrossChainAdapter {
             .unwrap()
             .as_secs();
 
-        calculate_timelocks(source, target, current_time)
+        calculate_timelocks(&self.config.chain_policies, source, target, current_time)
     }
 
     /// Check if chain is supported.
@@ -104,6 +108,34 @@ impl CrossChainAdapter {
     pub fn htlc_count(&self) -> usize {
         self.htlcs.len()
     }
+
+    // =========================================================================
+    // Swap Liquidity
+    // =========================================================================
+
+    /// Advertise a maker offer (swap_advertiseLiquidity).
+    pub fn advertise_liquidity(
+        &mut self,
+        offer: MakerOffer,
+        current_time: u64,
+    ) -> Result<(), CrossChainError> {
+        self.makers.advertise(offer, current_time)
+    }
+
+    /// Withdraw a maker offer (swap_withdrawLiquidity).
+    pub fn withdraw_liquidity(&mut self, maker: &Address, pair: SwapPair) -> Option<MakerOffer> {
+        self.makers.withdraw(maker, pair)
+    }
+
+    /// Get the maker registry (swap_getMakers).
+    pub fn makers(&self) -> &MakerRegistry {
+        &self.makers
+    }
+
+    /// Quote a swap against advertised liquidity (swap_getQuote).
+    pub fn quote(&self, params: QuoteParams) -> Result<SwapQuote, CrossChainError> {
+        quote_swap(&self.config.chain_policies, &self.makers, params)
+    }
 }
 
 #[cfg(feature = "qc-15")]
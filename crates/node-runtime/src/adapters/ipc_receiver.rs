@@ -113,7 +113,10 @@ impl EventBusIpcReceiver {
                 Err(api_error) => Err(ResponseError {
                     code: api_error.code,
                     message: api_error.message,
-                    data: None,
+                    // Shared error code, severity and origin pass through unchanged
+                    data: api_error
+                        .info
+                        .and_then(|info| serde_json::to_value(info).ok()),
                 }),
             };
 
@@ -93,6 +93,11 @@ pub mod ipc_receiver;
 #[cfg(feature = "qc-16")]
 pub use ipc_receiver::EventBusIpcReceiver;
 
+#[cfg(feature = "qc-16")]
+pub mod subscription_feed;
+#[cfg(feature = "qc-16")]
+pub use subscription_feed::EventBusSubscriptionFeed;
+
 #[cfg(feature = "qc-07")]
 pub mod replay_cache;
 #[cfg(feature = "qc-07")]
@@ -5,7 +5,7 @@ use tracing::info;
 
 use qc_01_peer_discovery::{
     adapters::VerificationRequestPublisher,
-    domain::{BanDetails, NodeId, PeerDiscoveryError, PeerInfo, RoutingTableStats},
+    domain::{BanDetails, BannedEntry, NodeId, PeerDiscoveryError, PeerInfo, RoutingTableStats},
     ipc::VerifyNodeIdentityRequest,
     ports::PeerDiscoveryApi,
     service::PeerDiscoveryService,
@@ -47,6 +47,10 @@ impl PeerDiscoveryApi for SharedPeerDiscovery {
         self.inner.read().is_banned(node_id)
     }
 
+    fn banned_peers(&self) -> Vec<BannedEntry> {
+        self.inner.read().banned_peers()
+    }
+
     fn touch_peer(&mut self, node_id: NodeId) -> Result<(), PeerDiscoveryError> {
         self.inner.write().touch_peer(node_id)
     }
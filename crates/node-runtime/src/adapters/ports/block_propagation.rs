@@ -4,14 +4,24 @@
 //!
 //! ## Ports Implemented
 //!
-//! - `PeerNetwork` - Network operations for peer communication
-//! - `ConsensusGateway` - Submits blocks to consensus for validation
+//! - `PeerNetwork` - Gossip to peers over the qc-01 QUIC mesh
+//! - `ConsensusGateway` - Publishes received blocks as `BlockReceived`
 //! - `MempoolGateway` - Transaction lookup for compact block reconstruction
 //! - `SignatureVerifier` - Block signature verification
+//!
+//! ## Wire Format
+//!
+//! Each qc-05 message travels on its own QUIC stream as a bincode
+//! `PeerEnvelope`: the sender's node ID plus a `WireMessage`. The sender ID is
+//! self-asserted; blocks are still checked by qc-05 before reaching consensus.
 
 use parking_lot::RwLock;
-use shared_types::Hash;
+use serde::{Deserialize, Serialize};
+use shared_bus::{BlockchainEvent, EventPublisher, InMemoryEventBus};
+use shared_types::{Hash, U256};
+use std::net::SocketAddr;
 use std::sync::Arc;
+use tokio::sync::mpsc;
 
 use qc_05_block_propagation::{PeerId, ShortTxId}; // Layer compliant
 use qc_05_block_propagation::events::PropagationError;
@@ -20,73 +30,277 @@ use qc_05_block_propagation::ports::outbound::{
 };
 use qc_06_mempool::TransactionPool;
 
+// =============================================================================
+// Wire Format
+// =============================================================================
+
+/// qc-05 `NetworkMessage` as sent between nodes, plus the chain sync
+/// requests and responses (see `crate::sync`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WireMessage {
+    /// First message on a new connection, so the peer learns our node ID.
+    Hello,
+    /// Block announcement (header-first)
+    Announce {
+        block_hash: Hash,
+        block_height: u64,
+        parent_hash: Hash,
+    },
+    /// Compact block
+    CompactBlock { data: Vec<u8> },
+    /// Full block request
+    GetBlock { block_hash: Hash, request_id: u64 },
+    /// Full block response
+    Block {
+        request_id: u64,
+        block_data: Option<Vec<u8>>,
+    },
+    /// Request missing transactions
+    GetBlockTxn { block_hash: Hash, indices: Vec<u16> },
+    /// Missing transactions response
+    BlockTxn {
+        block_hash: Hash,
+        transactions: Vec<Vec<u8>>,
+    },
+    /// Our chain head and its total work, sent after `Hello` (chain sync)
+    Status {
+        best_hash: Hash,
+        best_height: u64,
+        total_work: U256,
+    },
+    /// Request up to `max` headers from a block back towards genesis
+    GetHeaders {
+        request_id: u64,
+        block_hash: Hash,
+        max: u32,
+    },
+    /// Headers response, newest first, each bincode-encoded
+    Headers {
+        request_id: u64,
+        headers: Vec<Vec<u8>>,
+    },
+    /// State snapshot chunk request; no `block_hash` starts a new snapshot
+    GetStateChunk {
+        request_id: u64,
+        block_hash: Option<Hash>,
+        index: u32,
+    },
+    /// State snapshot chunk response (no data if the snapshot is gone)
+    StateChunk {
+        request_id: u64,
+        block_hash: Hash,
+        block_height: u64,
+        index: u32,
+        total: u32,
+        data: Option<Vec<u8>>,
+    },
+}
+
+impl WireMessage {
+    /// Request ID of a response to a `GetBlock`, `GetHeaders` or
+    /// `GetStateChunk` request.
+    pub fn response_id(&self) -> Option<u64> {
+        match self {
+            Self::Block { request_id, .. }
+            | Self::Headers { request_id, .. }
+            | Self::StateChunk { request_id, .. } => Some(*request_id),
+            _ => None,
+        }
+    }
+}
+
+impl From<NetworkMessage> for WireMessage {
+    fn from(message: NetworkMessage) -> Self {
+        match message {
+            NetworkMessage::Announce {
+                block_hash,
+                block_height,
+                parent_hash,
+            } => Self::Announce {
+                block_hash,
+                block_height,
+                parent_hash,
+            },
+            NetworkMessage::CompactBlock { data } => Self::CompactBlock { data },
+            NetworkMessage::GetBlock {
+                block_hash,
+                request_id,
+            } => Self::GetBlock {
+                block_hash,
+                request_id,
+            },
+            NetworkMessage::Block {
+                request_id,
+                block_data,
+            } => Self::Block {
+                request_id,
+                block_data,
+            },
+            NetworkMessage::GetBlockTxn {
+                block_hash,
+                indices,
+            } => Self::GetBlockTxn {
+                block_hash,
+                indices,
+            },
+            NetworkMessage::BlockTxn {
+                block_hash,
+                transactions,
+            } => Self::BlockTxn {
+                block_hash,
+                transactions,
+            },
+        }
+    }
+}
+
+/// A wire message tagged with the sender's node ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerEnvelope {
+    /// qc-01 node ID of the sender.
+    pub sender: [u8; 32],
+    /// The message.
+    pub message: WireMessage,
+}
+
+impl PeerEnvelope {
+    /// Serialize for sending.
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::serialize(self).unwrap_or_default()
+    }
+
+    /// Parse a received message, or `None` if it is malformed.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        bincode::deserialize(data).ok()
+    }
+}
+
+/// Encoded message queued for a peer address.
+pub type OutboundMessage = (SocketAddr, Vec<u8>);
+
 // =============================================================================
 // PeerNetwork Adapter
 // =============================================================================
 
 /// Adapter implementing qc-05's PeerNetwork trait.
-/// In production, this would interface with actual P2P networking.
+///
+/// Keeps the peers qc-05 may gossip to, keyed by node ID, and queues encoded
+/// messages for the runtime task that writes them to the QUIC mesh. A full
+/// queue fails the send rather than blocking the caller.
 pub struct BlockPropNetworkAdapter {
-    /// Connected peers (mock for now)
-    peers: RwLock<Vec<PeerInfo>>,
+    /// Our qc-01 node ID, stamped on every envelope.
+    local_id: [u8; 32],
+    /// Known peers and the address they are reachable at.
+    peers: RwLock<Vec<(PeerInfo, SocketAddr)>>,
+    /// Queue drained by the runtime's send task.
+    outbound: mpsc::Sender<OutboundMessage>,
 }
 
 impl BlockPropNetworkAdapter {
-    pub fn new() -> Self {
+    pub fn new(local_id: [u8; 32], outbound: mpsc::Sender<OutboundMessage>) -> Self {
         Self {
+            local_id,
             peers: RwLock::new(Vec::new()),
+            outbound,
         }
     }
 
-    /// Add a peer to the network
-    pub fn add_peer(&self, peer: PeerInfo) {
-        self.peers.write().push(peer);
+    /// Our node ID.
+    pub fn local_id(&self) -> [u8; 32] {
+        self.local_id
+    }
+
+    /// Add a peer, or update its address if already known.
+    pub fn add_peer(&self, peer: PeerInfo, addr: SocketAddr) {
+        let mut peers = self.peers.write();
+        peers.retain(|(p, _)| p.peer_id != peer.peer_id);
+        peers.push((peer, addr));
     }
 
     /// Remove a peer from the network
     pub fn remove_peer(&self, peer_id: &PeerId) {
-        self.peers.write().retain(|p| p.peer_id != *peer_id);
+        self.peers.write().retain(|(p, _)| p.peer_id != *peer_id);
     }
-}
 
-impl Default for BlockPropNetworkAdapter {
-    fn default() -> Self {
-        Self::new()
+    /// True if the peer is known.
+    pub fn has_peer(&self, peer_id: &PeerId) -> bool {
+        self.peer_addr(peer_id).is_some()
+    }
+
+    /// Address a known peer is reachable at.
+    pub fn peer_addr(&self, peer_id: &PeerId) -> Option<SocketAddr> {
+        self.peers
+            .read()
+            .iter()
+            .find(|(p, _)| p.peer_id == *peer_id)
+            .map(|(_, addr)| *addr)
+    }
+
+    /// Drop peers whose address is not in `connected`.
+    pub fn retain_connected(&self, connected: &[SocketAddr]) {
+        self.peers
+            .write()
+            .retain(|(_, addr)| connected.contains(addr));
+    }
+
+    /// Queue a message for an address, whether or not the peer is known yet.
+    pub fn send_to_addr(
+        &self,
+        addr: SocketAddr,
+        message: WireMessage,
+    ) -> Result<(), PropagationError> {
+        let envelope = PeerEnvelope {
+            sender: self.local_id,
+            message,
+        };
+        self.enqueue(addr, envelope.encode())
+    }
+
+    fn enqueue(&self, addr: SocketAddr, data: Vec<u8>) -> Result<(), PropagationError> {
+        self.outbound.try_send((addr, data)).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => {
+                PropagationError::NetworkError("outbound queue full".into())
+            }
+            mpsc::error::TrySendError::Closed(_) => {
+                PropagationError::NetworkError("network stopped".into())
+            }
+        })
     }
 }
 
 impl PeerNetwork for BlockPropNetworkAdapter {
     fn get_connected_peers(&self) -> Vec<PeerInfo> {
-        self.peers.read().clone()
+        self.peers.read().iter().map(|(p, _)| p.clone()).collect()
     }
 
     fn send_to_peer(
         &self,
         peer_id: PeerId,
-        _message: NetworkMessage,
+        message: NetworkMessage,
     ) -> Result<(), PropagationError> {
-        let peers = self.peers.read();
-        if peers.iter().any(|p| p.peer_id == peer_id) {
-            Ok(())
-        } else {
-            Err(PropagationError::UnknownPeer(peer_id.0))
-        }
+        let addr = self
+            .peer_addr(&peer_id)
+            .ok_or(PropagationError::UnknownPeer(peer_id.0))?;
+        self.send_to_addr(addr, message.into())
     }
 
     fn broadcast(
         &self,
         peer_ids: &[PeerId],
-        _message: NetworkMessage,
+        message: NetworkMessage,
     ) -> Vec<Result<(), PropagationError>> {
-        let peers = self.peers.read();
+        let data = PeerEnvelope {
+            sender: self.local_id,
+            message: message.into(),
+        }
+        .encode();
         peer_ids
             .iter()
             .map(|peer_id| {
-                if peers.iter().any(|p| p.peer_id == *peer_id) {
-                    Ok(())
-                } else {
-                    Err(PropagationError::UnknownPeer(peer_id.0))
-                }
+                let addr = self
+                    .peer_addr(peer_id)
+                    .ok_or(PropagationError::UnknownPeer(peer_id.0))?;
+                self.enqueue(addr, data.clone())
             })
             .collect()
     }
@@ -97,18 +311,14 @@ impl PeerNetwork for BlockPropNetworkAdapter {
 // =============================================================================
 
 /// Adapter implementing qc-05's ConsensusGateway trait.
-/// Submits blocks to consensus for validation.
-pub struct BlockPropConsensusAdapter;
-
-impl BlockPropConsensusAdapter {
-    pub fn new() -> Self {
-        Self
-    }
+/// Publishes blocks that passed propagation checks as `BlockReceived`.
+pub struct BlockPropConsensusAdapter {
+    event_bus: Arc<InMemoryEventBus>,
 }
 
-impl Default for BlockPropConsensusAdapter {
-    fn default() -> Self {
-        Self::new()
+impl BlockPropConsensusAdapter {
+    pub fn new(event_bus: Arc<InMemoryEventBus>) -> Self {
+        Self { event_bus }
     }
 }
 
@@ -116,14 +326,26 @@ impl ConsensusGateway for BlockPropConsensusAdapter {
     fn submit_block_for_validation(
         &self,
         block_hash: Hash,
-        _block_data: Vec<u8>,
-        _source_peer: PeerId,
+        block_data: Vec<u8>,
+        source_peer: PeerId,
     ) -> Result<(), PropagationError> {
-        // In production, this would submit to qc-08 Consensus
         tracing::debug!(
-            "Block {} submitted for validation",
-            hex::encode(&block_hash[..8])
+            "Block {} received from peer {}",
+            hex::encode(&block_hash[..8]),
+            hex::encode(&source_peer.0[..8])
         );
+
+        let event = BlockchainEvent::BlockReceived {
+            block_hash,
+            block_data,
+            source_peer: source_peer.0,
+        };
+
+        // Port is sync, the bus is async
+        let event_bus = Arc::clone(&self.event_bus);
+        tokio::spawn(async move {
+            event_bus.publish(event).await;
+        });
         Ok(())
     }
 }
@@ -229,32 +451,132 @@ impl SignatureVerifier for BlockPropSignatureAdapter {
 mod tests {
     use super::*;
 
+    fn adapter(capacity: usize) -> (BlockPropNetworkAdapter, mpsc::Receiver<OutboundMessage>) {
+        let (tx, rx) = mpsc::channel(capacity);
+        (BlockPropNetworkAdapter::new([9u8; 32], tx), rx)
+    }
+
+    fn peer(id: u8) -> PeerInfo {
+        PeerInfo {
+            peer_id: PeerId::new([id; 32]),
+            reputation: 1.0,
+            latency_ms: 50,
+            is_connected: true,
+        }
+    }
+
+    fn addr(port: u16) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
     #[test]
     fn test_network_adapter_creation() {
-        let adapter = BlockPropNetworkAdapter::new();
+        let (adapter, _rx) = adapter(4);
         assert!(adapter.get_connected_peers().is_empty());
     }
 
     #[test]
     fn test_add_remove_peer() {
-        let adapter = BlockPropNetworkAdapter::new();
-        let peer = PeerInfo {
-            peer_id: PeerId::new([1u8; 32]),
-            reputation: 1.0,
-            latency_ms: 50,
-            is_connected: true,
-        };
+        let (adapter, _rx) = adapter(4);
+        let peer = peer(1);
 
-        adapter.add_peer(peer.clone());
+        adapter.add_peer(peer.clone(), addr(30303));
+        adapter.add_peer(peer.clone(), addr(30304));
         assert_eq!(adapter.get_connected_peers().len(), 1);
 
         adapter.remove_peer(&peer.peer_id);
         assert!(adapter.get_connected_peers().is_empty());
     }
 
+    #[test]
+    fn test_retain_connected() {
+        let (adapter, _rx) = adapter(4);
+        adapter.add_peer(peer(1), addr(1));
+        adapter.add_peer(peer(2), addr(2));
+
+        adapter.retain_connected(&[addr(2)]);
+        assert!(!adapter.has_peer(&PeerId::new([1; 32])));
+        assert!(adapter.has_peer(&PeerId::new([2; 32])));
+    }
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let envelope = PeerEnvelope {
+            sender: [7; 32],
+            message: NetworkMessage::Announce {
+                block_hash: [1; 32],
+                block_height: 42,
+                parent_hash: [2; 32],
+            }
+            .into(),
+        };
+        assert_eq!(PeerEnvelope::decode(&envelope.encode()), Some(envelope));
+        assert_eq!(PeerEnvelope::decode(b"garbage"), None);
+    }
+
+    #[test]
+    fn test_response_id() {
+        let chunk = WireMessage::StateChunk {
+            request_id: 7,
+            block_hash: [1; 32],
+            block_height: 3,
+            index: 0,
+            total: 1,
+            data: None,
+        };
+        assert_eq!(chunk.response_id(), Some(7));
+        let request = WireMessage::GetHeaders {
+            request_id: 8,
+            block_hash: [1; 32],
+            max: 16,
+        };
+        assert_eq!(request.response_id(), None);
+    }
+
+    #[test]
+    fn test_send_to_peer_queues_envelope() {
+        let (adapter, mut rx) = adapter(4);
+        adapter.add_peer(peer(1), addr(30303));
+
+        let message = NetworkMessage::GetBlock {
+            block_hash: [3; 32],
+            request_id: 5,
+        };
+        adapter.send_to_peer(PeerId::new([1; 32]), message).unwrap();
+
+        let (to, data) = rx.try_recv().unwrap();
+        assert_eq!(to, addr(30303));
+        let envelope = PeerEnvelope::decode(&data).unwrap();
+        assert_eq!(envelope.sender, [9; 32]);
+        assert_eq!(
+            envelope.message,
+            WireMessage::GetBlock {
+                block_hash: [3; 32],
+                request_id: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_send_errors() {
+        let (adapter, _rx) = adapter(1);
+        let message = NetworkMessage::CompactBlock { data: vec![1] };
+
+        assert!(matches!(
+            adapter.send_to_peer(PeerId::new([1; 32]), message.clone()),
+            Err(PropagationError::UnknownPeer(_))
+        ));
+
+        adapter.add_peer(peer(1), addr(1));
+        adapter.add_peer(peer(2), addr(2));
+        let results = adapter.broadcast(&[PeerId::new([1; 32]), PeerId::new([2; 32])], message);
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(PropagationError::NetworkError(_))));
+    }
+
     #[test]
     fn test_consensus_gateway_creation() {
-        let _adapter = BlockPropConsensusAdapter::new();
+        let _adapter = BlockPropConsensusAdapter::new(Arc::new(InMemoryEventBus::new()));
     }
 
     #[test]
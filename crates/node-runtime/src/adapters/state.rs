@@ -7,6 +7,13 @@
 //!
 //! - Subscribes to: BlockValidated (from Consensus 8)
 //! - Publishes: StateRootComputed (to Block Storage 2)
+//!
+//! ## Validity Proofs (`zk` feature)
+//!
+//! The adapter records the block's state diff while applying it, proves
+//! the transition with qc-zkp and sends the proof with `StateRootComputed`.
+//! A block whose transition can't be proven (e.g. it creates balance) is
+//! still published, without a proof.
 
 use parking_lot::RwLock;
 use std::sync::Arc;
@@ -18,6 +25,19 @@ use shared_types::{Hash, SubsystemId};
 use crate::adapters::EventBusAdapter;
 use crate::wiring::{ChoreographyEvent, EventRouter};
 
+#[cfg(feature = "zk")]
+use crate::wiring::ValidityProof;
+#[cfg(feature = "zk")]
+use qc_04_state_management::{StateDiff, StateDiffRecorder};
+#[cfg(feature = "zk")]
+use qc_zkp::{
+    fri::FriConfig,
+    state_transition::{self, AccountChange, StateTransitionStatement},
+    ZkpError,
+};
+#[cfg(feature = "zk")]
+use tracing::warn;
+
 /// Transaction representation for state application.
 #[derive(Debug, Clone)]
 pub struct StateTransaction {
@@ -73,17 +93,23 @@ impl StateAdapter {
     pub fn process_block_validated(
         &self,
         block_hash: Hash,
-        _block_height: u64,
+        block_height: u64,
         transactions: Vec<StateTransaction>,
     ) -> Result<Hash, StateAdapterError> {
         debug!(
-            "[qc-04] Processing BlockValidated: {} transactions",
+            "[qc-04] Processing BlockValidated #{}: {} transactions",
+            block_height,
             transactions.len()
         );
 
         // Step 1: Apply transactions to trie
+        #[cfg(feature = "zk")]
+        let diff;
         {
             let mut trie = self.trie.write();
+            #[cfg(feature = "zk")]
+            let recorder = StateDiffRecorder::begin(&trie, touched_addresses(&transactions))
+                .map_err(|e| StateAdapterError::StateError(e.to_string()))?;
             for tx in &transactions {
                 // Apply transaction effects to state
                 let _ = trie.apply_balance_change(tx.from, -(tx.value as i128));
@@ -91,8 +117,27 @@ impl StateAdapter {
                 tx.to
                     .map(|to| trie.apply_balance_change(to, tx.value as i128));
             }
+            // No coinbase is applied here, so nothing is minted
+            #[cfg(feature = "zk")]
+            {
+                diff = recorder
+                    .finish(&trie, block_hash, block_height, 0)
+                    .map_err(|e| StateAdapterError::StateError(e.to_string()))?;
+            }
         }
 
+        #[cfg(feature = "zk")]
+        let validity_proof = match prove_state_diff(&diff) {
+            Ok(proof) => Some(proof),
+            Err(e) => {
+                warn!(
+                    "[qc-04] No validity proof for block #{}: {}",
+                    block_height, e
+                );
+                None
+            }
+        };
+
         // Step 2: Compute state root
         let state_root: Hash = {
             let trie = self.trie.read();
@@ -105,6 +150,8 @@ impl StateAdapter {
             block_hash,
             state_root,
             sender_id: SubsystemId::StateManagement,
+            #[cfg(feature = "zk")]
+            validity_proof,
         };
 
         if let Err(e) = self.event_bus.publish(event) {
@@ -126,6 +173,41 @@ impl StateAdapter {
     }
 }
 
+/// Sender and recipient of every transaction, in block order.
+#[cfg(feature = "zk")]
+fn touched_addresses(transactions: &[StateTransaction]) -> Vec<[u8; 20]> {
+    transactions
+        .iter()
+        .flat_map(|tx| std::iter::once(tx.from).chain(tx.to))
+        .collect()
+}
+
+/// Prove the state transition recorded in `diff`.
+#[cfg(feature = "zk")]
+fn prove_state_diff(diff: &StateDiff) -> Result<ValidityProof, ZkpError> {
+    let statement = StateTransitionStatement {
+        block_hash: diff.block_hash,
+        block_height: diff.block_height,
+        previous_state_root: diff.previous_state_root,
+        state_root: diff.state_root,
+        diff_digest: diff.digest(),
+        minted: diff.minted,
+        accounts: diff.accounts.len(),
+    };
+    let changes: Vec<AccountChange> = diff
+        .accounts
+        .iter()
+        .map(|account| AccountChange {
+            balance_before: account.balance_before,
+            balance_after: account.balance_after,
+            nonce_before: account.nonce_before,
+            nonce_after: account.nonce_after,
+        })
+        .collect();
+
+    state_transition::prove(statement, &changes, &FriConfig::default()).map(Arc::new)
+}
+
 /// State adapter errors.
 #[derive(Debug)]
 pub enum StateAdapterError {
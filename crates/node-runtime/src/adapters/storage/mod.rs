@@ -24,8 +24,9 @@ pub mod rocksdb_adapter;
 
 #[cfg(feature = "rocksdb")]
 pub use rocksdb_adapter::{
-    ProductionFileSystemAdapter, RocksDbConfig, RocksDbSnapshotStorage, RocksDbStore,
-    RocksDbTrieDatabase, CF_BLOCKS, CF_METADATA, CF_STATE, CF_TX_INDEX, COLUMN_FAMILIES,
+    BlockCacheHandle, ProductionFileSystemAdapter, RocksDbConfig, RocksDbSnapshotStorage,
+    RocksDbStore, RocksDbTrieDatabase, CF_BLOCKS, CF_METADATA, CF_STATE, CF_TX_INDEX,
+    COLUMN_FAMILIES,
 };
 
 // Re-export in-memory adapters for testing
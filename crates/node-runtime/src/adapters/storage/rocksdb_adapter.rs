@@ -58,6 +58,8 @@ pub struct RocksDbConfig {
     pub sync_writes: bool,
     /// Enable statistics collection (default: false for production)
     pub enable_statistics: bool,
+    /// Maximum open SST files (default: -1, unlimited)
+    pub max_open_files: i32,
 }
 
 impl Default for RocksDbConfig {
@@ -70,6 +72,7 @@ impl Default for RocksDbConfig {
             target_file_size_base: 64 * 1024 * 1024, // 64MB
             sync_writes: true,
             enable_statistics: false,
+            max_open_files: -1,
         }
     }
 }
@@ -85,6 +88,7 @@ impl RocksDbConfig {
             target_file_size_base: 4 * 1024 * 1024, // 4MB
             sync_writes: false,
             enable_statistics: false,
+            max_open_files: -1,
         }
     }
 }
@@ -93,6 +97,7 @@ impl RocksDbConfig {
 pub struct RocksDbStore {
     db: Arc<RwLock<DB>>,
     config: RocksDbConfig,
+    block_cache: rocksdb::Cache,
 }
 
 impl RocksDbStore {
@@ -106,6 +111,7 @@ impl RocksDbStore {
         opts.set_write_buffer_size(config.write_buffer_size);
         opts.set_max_write_buffer_number(config.max_write_buffer_number);
         opts.set_target_file_size_base(config.target_file_size_base);
+        opts.set_max_open_files(config.max_open_files);
 
         // Compression
         opts.set_compression_type(rocksdb::DBCompressionType::Snappy);
@@ -113,7 +119,8 @@ impl RocksDbStore {
         // Bloom filter for faster lookups
         let mut block_opts = rocksdb::BlockBasedOptions::default();
         block_opts.set_bloom_filter(10.0, false);
-        block_opts.set_block_cache(&rocksdb::Cache::new_lru_cache(config.block_cache_size));
+        let block_cache = rocksdb::Cache::new_lru_cache(config.block_cache_size);
+        block_opts.set_block_cache(&block_cache);
         opts.set_block_based_table_factory(&block_opts);
 
         // Column families
@@ -136,6 +143,7 @@ impl RocksDbStore {
         Ok(Self {
             db: Arc::new(RwLock::new(db)),
             config,
+            block_cache,
         })
     }
 
@@ -152,6 +160,23 @@ impl RocksDbStore {
     pub fn inner(&self) -> &Arc<RwLock<DB>> {
         &self.db
     }
+
+    /// Handle to the block cache, for memory accounting after the store has
+    /// been moved into a service
+    pub fn block_cache(&self) -> BlockCacheHandle {
+        BlockCacheHandle(self.block_cache.clone())
+    }
+}
+
+/// Shared handle to a store's block cache
+#[derive(Clone)]
+pub struct BlockCacheHandle(rocksdb::Cache);
+
+impl BlockCacheHandle {
+    /// Bytes currently held by the cache
+    pub fn usage(&self) -> usize {
+        self.0.get_usage()
+    }
 }
 
 impl KeyValueStore for RocksDbStore {
@@ -237,6 +262,13 @@ impl KeyValueStore for RocksDbStore {
 
         Ok(results)
     }
+
+    fn flush(&mut self) -> Result<(), KVStoreError> {
+        let db = self.db.read();
+        db.flush().map_err(|e| KVStoreError::IOError {
+            message: format!("RocksDB flush failed: {}", e),
+        })
+    }
 }
 
 /// Production filesystem adapter using std::fs
@@ -330,6 +362,13 @@ impl RocksDbTrieDatabase {
         })
     }
 
+    /// Flush memtables to disk (graceful shutdown).
+    pub fn flush(&self) -> Result<(), StateError> {
+        let db = self.store.db.read();
+        db.flush()
+            .map_err(|e| StateError::DatabaseError(e.to_string()))
+    }
+
     fn make_key(hash: &Hash) -> Vec<u8> {
         let mut key = Vec::with_capacity(5 + 32);
         key.extend_from_slice(b"trie:");
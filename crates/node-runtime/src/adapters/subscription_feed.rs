@@ -0,0 +1,127 @@
+//! # Event Bus Subscription Feed
+//!
+//! Feeds qc-16 WebSocket subscriptions (`eth_subscribe`) and polling filters
+//! (`eth_newFilter` and friends) from the node's events.
+//!
+//! - `newPendingTransactions`: transactions admitted towards the mempool
+//!   (qc-06) after signature verification.
+//! - `newHeads`: every block qc-02 stores, read back from block storage.
+//! - `logs`: receipt logs qc-03 publishes as `ReceiptsIndexed`.
+
+use crate::container::SubsystemContainer;
+use crate::wiring::ChoreographyEvent;
+use primitive_types::H256;
+use qc_02_block_storage::BlockStorageApi;
+use qc_16_api_gateway::{Log, SubscriptionManager};
+use shared_bus::{BlockchainEvent, EventFilter, EventTopic, InMemoryEventBus, Subscription};
+use shared_types::Hash;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+
+/// Forwards bus events to WebSocket subscribers.
+pub struct EventBusSubscriptionFeed {
+    /// Event bus subscription
+    subscription: Subscription,
+    /// Choreography events, for `BlockStored`
+    stored: broadcast::Receiver<ChoreographyEvent>,
+    /// Source of stored block headers
+    container: Arc<SubsystemContainer>,
+    /// qc-16 subscription manager
+    manager: Arc<SubscriptionManager>,
+}
+
+impl EventBusSubscriptionFeed {
+    /// Create a new feed subscribed to transaction, receipt and block-stored
+    /// events.
+    pub fn new(
+        bus: &InMemoryEventBus,
+        stored: broadcast::Receiver<ChoreographyEvent>,
+        container: Arc<SubsystemContainer>,
+        manager: Arc<SubscriptionManager>,
+    ) -> Self {
+        let filter = EventFilter::topics(vec![
+            EventTopic::SignatureVerification,
+            EventTopic::TransactionIndexing,
+        ]);
+        let subscription = bus.subscribe(filter);
+
+        Self {
+            subscription,
+            stored,
+            container,
+            manager,
+        }
+    }
+
+    /// Start forwarding events. Should be spawned as a background task.
+    pub async fn run(mut self) {
+        info!("[SubscriptionFeed] Started forwarding events to WebSocket subscribers");
+
+        loop {
+            tokio::select! {
+                event = self.subscription.recv() => {
+                    let Some(event) = event else {
+                        error!("[SubscriptionFeed] Event bus closed, shutting down");
+                        break;
+                    };
+                    self.forward(event);
+                }
+                stored = self.stored.recv() => match stored {
+                    Ok(ChoreographyEvent::BlockStored { block_hash, .. }) => {
+                        self.broadcast_head(&block_hash);
+                    }
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("[SubscriptionFeed] Missed {} choreography events", n);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        error!("[SubscriptionFeed] Choreography closed, shutting down");
+                        break;
+                    }
+                },
+            }
+        }
+    }
+
+    fn forward(&self, event: BlockchainEvent) {
+        match event {
+            BlockchainEvent::TransactionVerified(tx) => {
+                self.manager.broadcast_pending_tx(H256::from(tx.tx_hash));
+            }
+            BlockchainEvent::ReceiptsIndexed { logs, .. } => {
+                for log in logs {
+                    match serde_json::from_value::<Log>(log) {
+                        Ok(log) => self.manager.broadcast_log(&log),
+                        Err(e) => warn!("[SubscriptionFeed] Skipping malformed log: {}", e),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn broadcast_head(&self, block_hash: &Hash) {
+        let stored = match self.container.block_storage.read().read_block(block_hash) {
+            Ok(stored) => stored,
+            Err(e) => {
+                warn!(
+                    "[SubscriptionFeed] Stored block {} not readable: {}",
+                    hex::encode(&block_hash[..8]),
+                    e
+                );
+                return;
+            }
+        };
+        let header = &stored.block.header;
+        self.manager.broadcast_new_head(serde_json::json!({
+            "number": format!("0x{:x}", header.height),
+            "hash": format!("0x{}", hex::encode(block_hash)),
+            "parentHash": format!("0x{}", hex::encode(header.parent_hash)),
+            "timestamp": format!("0x{:x}", header.timestamp),
+            "stateRoot": format!("0x{}", hex::encode(stored.state_root)),
+            "transactionsRoot": format!("0x{}", hex::encode(stored.merkle_root)),
+            "miner": format!("0x{}", hex::encode(&header.proposer[..20])),
+        }));
+    }
+}
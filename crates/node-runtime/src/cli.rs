@@ -0,0 +1,616 @@
+//! # Command Line
+//!
+//! `quantum-chain [OPTIONS] [COMMAND]`; without a command the node runs.
+//!
+//! The global options pick the configuration (see `container::loader`).
+//! Commands that touch chain data (`init`, `import-chain`, `export-chain`,
+//! `reset`) take the data directory lock, so they refuse to run against a
+//! directory another process holds.
+
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use qc_02_block_storage::adapters::DatabaseLock;
+use qc_02_block_storage::{BlockStorageApi, SnapshotConfig, SnapshotService};
+use shared_crypto::keystore::{load_key, save_key};
+use shared_crypto::{Kdf, KeyType, Keystore, StoredKey};
+use shared_types::SecretBytes;
+
+use crate::container::{ConfigLoader, NodeConfig, SubsystemContainer};
+use crate::genesis::{ChainSpec, GenesisBuilder};
+use crate::resources::ResourceBudget;
+
+/// Config file written by `init` and looked up in `--config-dir`.
+pub const CONFIG_FILE: &str = "node.toml";
+
+/// Key files live in `<data_dir>/keys`.
+const KEYS_DIR: &str = "keys";
+
+/// Key used when `--name` is not given.
+const DEFAULT_KEY_NAME: &str = "node";
+
+/// Keystore password, if not given with `--password-file`.
+const PASSWORD_ENV: &str = "QC_KEYSTORE_PASSWORD";
+
+const ENVIRONMENT_HELP: &str = "\
+Configuration is layered: defaults, then the config file, then environment
+variables, then --chain, --data-dir and --set. Any key can also be set with
+QC__<SECTION>__<KEY>, e.g. QC__MEMPOOL__MAX_TRANSACTIONS=10000.
+
+ENVIRONMENT VARIABLES:
+    QC_CONFIG        TOML config file
+    QC_CHAIN         Chain spec: mainnet, testnet, dev or a JSON file
+    QC_HMAC_SECRET   32-byte hex-encoded HMAC secret
+    QC_P2P_PORT      P2P port (default: 30303)
+    QC_RPC_PORT      RPC port (default: 8545)
+    QC_DATA_DIR      Data directory path
+    QC_LOG_LEVEL     Log level (default: info)
+    QC_COMPUTE_BACKEND  Compute backend: auto, cpu, opencl
+    QC_EVENT_BUS     Event bus backend: memory, persistent
+    QC_EVENT_LOG_DIR Persistent event log directory
+    QC_EVENT_JOURNAL Event audit journal file (disabled if unset)
+    QC_NONCE_CACHE_FILE  Nonce cache snapshot file (disabled if unset)
+    QC_CRASH_LOG     Also log crash reports for Loki (default: true)
+    QC_KEYSTORE_PASSWORD  Password for `keys generate` and the validator key
+
+TELEMETRY (LGTM Stack):
+    OTEL_EXPORTER_OTLP_ENDPOINT   Tempo endpoint (default: http://localhost:4317)
+    LOKI_ENDPOINT                 Loki endpoint (default: http://localhost:3100)
+    QC_METRICS_PORT               Prometheus metrics port (default: 9100)
+    QC_ALERTS                     Evaluate alert rules in the node (default: false)
+    QC_ALERT_WEBHOOK              POST alerts to this URL (enables alerting)";
+
+/// The `quantum-chain` command tree.
+pub fn command() -> Command {
+    Command::new("quantum-chain")
+        .about("Quantum-Chain Node Runtime")
+        .version(env!("CARGO_PKG_VERSION"))
+        .long_version(concat!(
+            env!("CARGO_PKG_VERSION"),
+            "\nArchitecture: V2.3 Choreography Pattern",
+            "\nSubsystems: 17 (all compiled into single binary)"
+        ))
+        .after_help(ENVIRONMENT_HELP)
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .value_name("FILE")
+                .value_parser(value_parser!(PathBuf))
+                .global(true)
+                .help("TOML config file (default: $QC_CONFIG)"),
+        )
+        .arg(
+            Arg::new("config-dir")
+                .long("config-dir")
+                .value_name("DIR")
+                .value_parser(value_parser!(PathBuf))
+                .global(true)
+                .help("Read node.toml from this directory if --config is not given"),
+        )
+        .arg(
+            Arg::new("chain")
+                .long("chain")
+                .value_name("SPEC")
+                .global(true)
+                .help("Chain spec: mainnet, testnet, dev or a JSON file (same as --set chain.spec=SPEC)"),
+        )
+        .arg(
+            Arg::new("data-dir")
+                .long("data-dir")
+                .value_name("DIR")
+                .global(true)
+                .help("Data directory (same as --set storage.data_dir=DIR)"),
+        )
+        .arg(
+            Arg::new("set")
+                .long("set")
+                .value_name("KEY=VALUE")
+                .action(ArgAction::Append)
+                .global(true)
+                .help("Override a config key, e.g. network.max_peers=100"),
+        )
+        .subcommand(
+            Command::new("run").about("Start the node (the default)").arg(
+                Arg::new("repair")
+                    .long("repair")
+                    .action(ArgAction::SetTrue)
+                    .help("Rebuild the block and transaction indexes if the startup check finds them damaged"),
+            ),
+        )
+        .subcommand(Command::new("health").about("Run health check"))
+        .subcommand(
+            Command::new("calibrate").about("Benchmark compute backends and store the results"),
+        )
+        .subcommand(
+            Command::new("init")
+                .about("Write a config file with a fresh HMAC secret and create the genesis block")
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .action(ArgAction::SetTrue)
+                        .help("Overwrite an existing config file"),
+                ),
+        )
+        .subcommand(
+            Command::new("export-chain")
+                .about("Write the chain from genesis to a snapshot file")
+                .arg(
+                    Arg::new("file")
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("height")
+                        .long("height")
+                        .value_parser(value_parser!(u64))
+                        .help("Last block to include (default: chain head)"),
+                )
+                .arg(
+                    Arg::new("no-compress")
+                        .long("no-compress")
+                        .action(ArgAction::SetTrue)
+                        .help("Do not zstd-compress the snapshot"),
+                ),
+        )
+        .subcommand(
+            Command::new("import-chain")
+                .about("Verify a snapshot file and store its blocks")
+                .arg(
+                    Arg::new("file")
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("verify-only")
+                        .long("verify-only")
+                        .action(ArgAction::SetTrue)
+                        .help("Check the snapshot without importing it"),
+                ),
+        )
+        .subcommand(
+            Command::new("reset")
+                .about("Delete all chain data in the data directory")
+                .arg(
+                    Arg::new("keep-keys")
+                        .long("keep-keys")
+                        .action(ArgAction::SetTrue)
+                        .help("Keep the keystore directory"),
+                )
+                .arg(
+                    Arg::new("yes")
+                        .long("yes")
+                        .short('y')
+                        .action(ArgAction::SetTrue)
+                        .help("Do not ask for confirmation"),
+                ),
+        )
+        .subcommand(
+            Command::new("keys")
+                .about("Manage encrypted node keys in <data_dir>/keys")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("generate")
+                        .about("Generate a key and save it encrypted")
+                        .arg(key_name_arg())
+                        .arg(
+                            Arg::new("type")
+                                .long("type")
+                                .value_parser(["ed25519", "secp256k1"])
+                                .default_value("ed25519"),
+                        )
+                        .arg(
+                            Arg::new("password-file")
+                                .long("password-file")
+                                .value_name("FILE")
+                                .value_parser(value_parser!(PathBuf))
+                                .help("Read the password from FILE (default: $QC_KEYSTORE_PASSWORD or prompt)"),
+                        )
+                        .arg(
+                            Arg::new("force")
+                                .long("force")
+                                .action(ArgAction::SetTrue)
+                                .help("Replace an existing key"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("show")
+                        .about("Show public keys (all keys unless --name is given)")
+                        .arg(
+                            Arg::new("name")
+                                .long("name")
+                                .help("Key file name, without .json"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("config")
+                .about("Inspect the configuration")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("dump")
+                        .about("Print the effective configuration")
+                        .arg(
+                            Arg::new("show-secrets")
+                                .long("show-secrets")
+                                .action(ArgAction::SetTrue)
+                                .help("Print secrets instead of <redacted>"),
+                        ),
+                ),
+        )
+        .subcommand(
+            Command::new("journal")
+                .about("Print event journal entries")
+                .arg(
+                    Arg::new("file")
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("from-offset")
+                        .value_parser(value_parser!(u64))
+                        .default_value("0"),
+                ),
+        )
+}
+
+fn key_name_arg() -> Arg {
+    Arg::new("name")
+        .long("name")
+        .default_value(DEFAULT_KEY_NAME)
+        .help("Key file name, without .json")
+}
+
+/// Config file and overrides from the global options.
+#[derive(Debug, Default)]
+pub struct ConfigArgs {
+    /// `--config`, or `node.toml` in `--config-dir`.
+    pub file: Option<PathBuf>,
+    /// `--chain`, `--data-dir` and `--set` assignments, in that order.
+    pub overrides: Vec<String>,
+}
+
+impl ConfigArgs {
+    /// Read the global options.
+    pub fn from_matches(matches: &ArgMatches) -> Self {
+        let file = matches.get_one::<PathBuf>("config").cloned().or_else(|| {
+            matches
+                .get_one::<PathBuf>("config-dir")
+                .map(|dir| dir.join(CONFIG_FILE))
+                .filter(|file| file.exists())
+        });
+        let chain = matches
+            .get_one::<String>("chain")
+            .map(|spec| format!("chain.spec={}", spec));
+        let data_dir = matches
+            .get_one::<String>("data-dir")
+            .map(|dir| format!("storage.data_dir={}", dir));
+        let overrides = chain
+            .into_iter()
+            .chain(data_dir)
+            .chain(
+                matches
+                    .get_many::<String>("set")
+                    .into_iter()
+                    .flatten()
+                    .cloned(),
+            )
+            .collect();
+        Self { file, overrides }
+    }
+
+    /// `--config` or `QC_CONFIG`.
+    fn config_file(&self) -> Option<PathBuf> {
+        self.file
+            .clone()
+            .or_else(|| std::env::var_os("QC_CONFIG").map(Into::into))
+    }
+
+    fn loader(&self) -> ConfigLoader {
+        self.overrides.iter().fold(
+            ConfigLoader::new().with_env(std::env::vars()),
+            |loader, assignment| loader.with_override(assignment.clone()),
+        )
+    }
+}
+
+/// Load configuration: defaults, then the config file, then environment
+/// variables, then `--chain`, `--data-dir` and `--set`.
+pub fn load_config(args: &ConfigArgs) -> Result<NodeConfig> {
+    let loader = match args.config_file() {
+        Some(file) => args.loader().with_file(file),
+        None => args.loader(),
+    };
+    Ok(loader.load()?)
+}
+
+/// `init`: write a config file and create the genesis block.
+pub fn init(args: &ConfigArgs, force: bool) -> Result<()> {
+    let path = args
+        .config_file()
+        .unwrap_or_else(|| PathBuf::from(CONFIG_FILE));
+    if path.exists() && !force {
+        bail!(
+            "{} already exists (use --force to overwrite)",
+            path.display()
+        );
+    }
+
+    // The file does not exist yet, so only environment and flags apply
+    let mut config = args.loader().load()?;
+    let chain_spec = ChainSpec::load(&config.chain.spec)?;
+    if config.security.hmac_secret.expose_secret() == [0u8; 32] {
+        let mut secret = vec![0u8; 32];
+        rand::Rng::fill(&mut rand::thread_rng(), &mut secret[..]);
+        config.security.hmac_secret = SecretBytes::new(secret);
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, crate::container::dump_config(&config, true)?)
+        .with_context(|| format!("cannot write {}", path.display()))?;
+    restrict_permissions(&path)?;
+    println!("Wrote {}", path.display());
+
+    let _lock = lock_data_dir(&config)?;
+    let resources = ResourceBudget::new(&config);
+    let (storage, _) = SubsystemContainer::init_block_storage(&config, &resources);
+    let mut storage = storage.write();
+    if let Ok(genesis) = storage.read_block_by_height(0) {
+        println!(
+            "Genesis already exists: 0x{}",
+            hex::encode(genesis.block_hash())
+        );
+        return Ok(());
+    }
+    let genesis = GenesisBuilder::new(chain_spec.genesis_config()?).build()?;
+    let hash = storage.write_block(
+        genesis.to_validated_block(),
+        genesis.header.merkle_root,
+        genesis.header.state_root,
+    )?;
+    println!(
+        "Created {} genesis block 0x{} in {}",
+        chain_spec.name,
+        hex::encode(hash),
+        config.storage.data_dir.display()
+    );
+    Ok(())
+}
+
+/// `export-chain`: write blocks 0..=height to a snapshot file.
+pub fn export_chain(
+    config: &NodeConfig,
+    file: &Path,
+    height: Option<u64>,
+    compress: bool,
+) -> Result<()> {
+    let _lock = lock_data_dir(config)?;
+    let resources = ResourceBudget::new(config);
+    let (storage, _) = SubsystemContainer::init_block_storage(config, &resources);
+    let storage = storage.read();
+    let height = match height {
+        Some(height) => height,
+        None => storage.get_latest_height()?,
+    };
+    let snapshot_config = SnapshotConfig {
+        compression: compress,
+        ..SnapshotConfig::default()
+    };
+    let info = storage.export_snapshot(height, file, &snapshot_config)?;
+    println!(
+        "Exported {} blocks ({} transactions) up to height {} to {} ({} bytes)",
+        info.block_count, info.tx_count, info.height, info.path, info.size_bytes
+    );
+    println!("Head: 0x{}", hex::encode(info.block_hash));
+    Ok(())
+}
+
+/// `import-chain`: verify a snapshot and store its blocks.
+pub fn import_chain(config: &NodeConfig, file: &Path, verify_only: bool) -> Result<()> {
+    let _lock = lock_data_dir(config)?;
+    let resources = ResourceBudget::new(config);
+    let (storage, _) = SubsystemContainer::init_block_storage(config, &resources);
+    let mut storage = storage.write();
+    let info = if verify_only {
+        storage.verify_snapshot(file)?
+    } else {
+        storage.import_snapshot(file)?
+    };
+    println!(
+        "{} {} blocks ({} transactions) up to height {}",
+        if verify_only { "Verified" } else { "Imported" },
+        info.block_count,
+        info.tx_count,
+        info.height
+    );
+    println!("Head: 0x{}", hex::encode(info.block_hash));
+    Ok(())
+}
+
+/// `reset`: delete everything in the data directory, optionally keeping keys.
+pub fn reset(config: &NodeConfig, keep_keys: bool, yes: bool) -> Result<()> {
+    let data_dir = &config.storage.data_dir;
+    if !data_dir.exists() {
+        println!("Nothing to reset: {} does not exist", data_dir.display());
+        return Ok(());
+    }
+    let what = if keep_keys {
+        "all chain data except keys"
+    } else {
+        "all chain data and keys"
+    };
+    if !yes && !confirm(&format!("Delete {} in {}?", what, data_dir.display()))? {
+        bail!("reset cancelled");
+    }
+
+    let lock = lock_data_dir(config)?;
+    let removed = clear_dir(data_dir, keep_keys, lock.path())?;
+    println!("Removed {} entries from {}", removed, data_dir.display());
+    Ok(())
+}
+
+/// Delete the entries of `dir` except the lock file and, if `keep_keys`,
+/// the keystore. Returns how many were removed.
+fn clear_dir(dir: &Path, keep_keys: bool, lock_file: &Path) -> Result<usize> {
+    let mut removed = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_keys = path.file_name() == Some(KEYS_DIR.as_ref());
+        if path == lock_file || (keep_keys && is_keys) {
+            continue;
+        }
+        if path.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        }
+        .with_context(|| format!("cannot remove {}", path.display()))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// `keys generate`: create a key and save it encrypted.
+pub fn keys_generate(
+    config: &NodeConfig,
+    name: &str,
+    key_type: &str,
+    password_file: Option<&Path>,
+    force: bool,
+) -> Result<()> {
+    let path = key_path(config, name);
+    if path.exists() && !force {
+        bail!(
+            "{} already exists (use --force to replace it)",
+            path.display()
+        );
+    }
+    let key_type = match key_type {
+        "secp256k1" => KeyType::Secp256k1,
+        _ => KeyType::Ed25519,
+    };
+    let password = read_password(password_file)?;
+
+    let key = StoredKey::generate(key_type);
+    let keystore = save_key(&path, &key, &password, Kdf::default())?;
+    // Check the file decrypts before reporting success
+    load_key(&path, &password)?;
+    println!("Saved {} key to {}", name, path.display());
+    print_key(name, &keystore);
+    Ok(())
+}
+
+/// Decrypt the `[validator]` key for signing.
+pub fn load_validator_key(config: &NodeConfig) -> Result<StoredKey> {
+    let path = key_path(config, &config.validator.key_name);
+    if !path.exists() {
+        bail!(
+            "validator key {} not found (create it with `keys generate {} --type secp256k1`)",
+            path.display(),
+            config.validator.key_name
+        );
+    }
+    let password = read_password(config.validator.password_file.as_deref())?;
+    load_key(&path, &password).with_context(|| format!("cannot decrypt {}", path.display()))
+}
+
+/// `keys show`: print public keys without decrypting.
+pub fn keys_show(config: &NodeConfig, name: Option<&str>) -> Result<()> {
+    let dir = config.storage.data_dir.join(KEYS_DIR);
+    let paths = match name {
+        Some(name) => vec![key_path(config, name)],
+        None => {
+            let mut paths: Vec<_> = std::fs::read_dir(&dir)
+                .with_context(|| format!("no keys in {}", dir.display()))?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension() == Some("json".as_ref()))
+                .collect();
+            paths.sort();
+            paths
+        }
+    };
+    if paths.is_empty() {
+        println!("No keys in {}", dir.display());
+    }
+    for path in paths {
+        let json = std::fs::read_to_string(&path)
+            .with_context(|| format!("cannot read {}", path.display()))?;
+        let keystore = Keystore::from_json(&json)?;
+        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+        print_key(&name, &keystore);
+    }
+    Ok(())
+}
+
+fn print_key(name: &str, keystore: &Keystore) {
+    let key_type = match keystore.key_type {
+        Some(KeyType::Ed25519) => "ed25519",
+        Some(KeyType::Secp256k1) => "secp256k1",
+        None => "unknown",
+    };
+    let pubkey = if keystore.pubkey.is_empty() {
+        "(not recorded)"
+    } else {
+        &keystore.pubkey
+    };
+    println!("{:<16} {:<10} {} {}", name, key_type, keystore.uuid, pubkey);
+}
+
+/// Keystore file of key `name`.
+pub fn key_path(config: &NodeConfig, name: &str) -> PathBuf {
+    config
+        .storage
+        .data_dir
+        .join(KEYS_DIR)
+        .join(format!("{}.json", name))
+}
+
+/// Password from `file`, `QC_KEYSTORE_PASSWORD`, or a prompt.
+fn read_password(file: Option<&Path>) -> Result<String> {
+    let password = match (file, std::env::var(PASSWORD_ENV)) {
+        (Some(file), _) => std::fs::read_to_string(file)
+            .with_context(|| format!("cannot read {}", file.display()))?,
+        (None, Ok(password)) => password,
+        (None, Err(_)) => prompt("Keystore password: ")?,
+    };
+    let password = password.trim_end_matches(['\r', '\n']).to_string();
+    if password.is_empty() {
+        bail!("keystore password must not be empty");
+    }
+    Ok(password)
+}
+
+fn confirm(question: &str) -> Result<bool> {
+    let answer = prompt(&format!("{} [y/N] ", question))?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+fn prompt(text: &str) -> Result<String> {
+    eprint!("{}", text);
+    std::io::stderr().flush()?;
+    let mut line = String::new();
+    std::io::stdin().lock().read_line(&mut line)?;
+    Ok(line)
+}
+
+/// Take the data directory lock, creating the directory if needed.
+fn lock_data_dir(config: &NodeConfig) -> Result<DatabaseLock> {
+    let data_dir = &config.storage.data_dir;
+    std::fs::create_dir_all(data_dir)
+        .with_context(|| format!("cannot create {}", data_dir.display()))?;
+    Ok(DatabaseLock::acquire(data_dir)?)
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
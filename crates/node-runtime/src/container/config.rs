@@ -6,12 +6,24 @@
 //!
 //! - `hmac_secret` MUST NOT be the default zero value in production
 //! - All timeouts and limits have sane defaults with override capability
+//!
+//! ## File Format
+//!
+//! Every section below maps to a TOML table of the same name (see
+//! `loader` for how files, environment variables and `--set` overrides are
+//! layered). Missing keys keep their defaults; unknown keys are rejected.
 
+use serde::{Deserialize, Serialize};
+use shared_bus::{EventLogConfig, JournalConfig, DEFAULT_MIRROR_EVENTS_PER_TOPIC};
+use shared_types::SecretBytes;
 use std::path::PathBuf;
 
 /// Complete node configuration.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct NodeConfig {
+    /// Chain selection.
+    pub chain: ChainConfig,
     /// Network configuration.
     pub network: NetworkConfig,
     /// Storage configuration.
@@ -28,6 +40,18 @@ pub struct NodeConfig {
     pub api_gateway: ApiGatewayConfig,
     /// Mining/Block Production configuration.
     pub mining: MiningConfig,
+    /// Proof-of-stake validator configuration.
+    pub validator: ValidatorConfig,
+    /// Event bus configuration.
+    pub event_bus: EventBusConfig,
+    /// Graceful shutdown configuration.
+    pub shutdown: ShutdownConfig,
+    /// Memory and file-descriptor budgets.
+    pub resources: ResourceConfig,
+    /// Catching up with peers before producing blocks.
+    pub sync: SyncConfig,
+    /// Metric history kept for dashboards.
+    pub metrics: MetricsConfig,
 }
 
 impl NodeConfig {
@@ -38,11 +62,180 @@ impl NodeConfig {
     /// Returns `Err` if:
     /// - HMAC secret is the default zero value
     pub fn validate_for_production(&self) -> Result<(), ConfigError> {
-        if self.security.hmac_secret == [0u8; 32] {
+        if self.security.hmac_secret.expose_secret() == [0u8; 32] {
             return Err(ConfigError::InsecureHmacSecret);
         }
         Ok(())
     }
+
+    /// Check that values are in range and consistent with each other.
+    ///
+    /// Type errors and unknown keys are caught when the config is parsed;
+    /// this covers what the types cannot express.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let checks: [(&'static str, bool, &str); 30] = [
+            (
+                "network.p2p_port",
+                self.network.p2p_port != 0,
+                "must not be 0",
+            ),
+            (
+                "network.rpc_port",
+                self.network.rpc_port != 0,
+                "must not be 0",
+            ),
+            (
+                "network.rpc_port",
+                self.network.rpc_port != self.network.p2p_port,
+                "must differ from network.p2p_port",
+            ),
+            (
+                "network.max_peers",
+                self.network.max_peers > 0,
+                "must be at least 1",
+            ),
+            (
+                "network.gossip_fanout",
+                self.network.gossip_fanout <= self.network.max_peers,
+                "must not exceed network.max_peers",
+            ),
+            (
+                "storage.min_disk_space_percent",
+                self.storage.min_disk_space_percent <= 100,
+                "must be a percentage (0-100)",
+            ),
+            (
+                "storage.assembly_timeout_secs",
+                self.storage.assembly_timeout_secs > 0,
+                "must be at least 1",
+            ),
+            (
+                "consensus.algorithm",
+                matches!(self.consensus.algorithm.as_str(), "pos" | "pbft"),
+                "must be \"pos\" or \"pbft\"",
+            ),
+            (
+                "consensus.min_attestation_percent",
+                (51..=100).contains(&self.consensus.min_attestation_percent),
+                "must be a majority (51-100)",
+            ),
+            (
+                "consensus.block_time_secs",
+                self.consensus.block_time_secs > 0,
+                "must be at least 1",
+            ),
+            (
+                "consensus.epoch_length",
+                self.consensus.epoch_length > 0,
+                "must be at least 1",
+            ),
+            (
+                "mempool.max_per_account",
+                self.mempool.max_per_account <= self.mempool.max_transactions,
+                "must not exceed mempool.max_transactions",
+            ),
+            (
+                "finality.justification_threshold",
+                (51..=100).contains(&self.finality.justification_threshold),
+                "must be a majority (51-100)",
+            ),
+            (
+                "api_gateway.ws_port",
+                !self.api_gateway.enabled || self.api_gateway.ws_port != self.api_gateway.http_port,
+                "must differ from api_gateway.http_port",
+            ),
+            (
+                "api_gateway.admin_port",
+                !self.api_gateway.enabled
+                    || (self.api_gateway.admin_port != self.api_gateway.http_port
+                        && self.api_gateway.admin_port != self.api_gateway.ws_port),
+                "must differ from the HTTP and WebSocket ports",
+            ),
+            (
+                "api_gateway.rate_limit_per_second",
+                self.api_gateway.rate_limit_per_second > 0,
+                "must be at least 1",
+            ),
+            (
+                "api_gateway.max_batch_size",
+                self.api_gateway.max_batch_size > 0,
+                "must be at least 1",
+            ),
+            (
+                "mining.worker_threads",
+                !self.mining.enabled || self.mining.worker_threads > 0,
+                "must be at least 1 when mining is enabled",
+            ),
+            (
+                "mining.initial_difficulty",
+                self.mining.initial_difficulty <= 256,
+                "must be at most 256 bits",
+            ),
+            (
+                "mining.difficulty_adjustment_interval",
+                self.mining.difficulty_adjustment_interval > 0,
+                "must be at least 1",
+            ),
+            (
+                "mining.max_adjustment_factor",
+                self.mining.max_adjustment_factor >= 1.0,
+                "must be at least 1.0",
+            ),
+            (
+                "mining.duty_cycle_percent",
+                (1..=100).contains(&self.mining.duty_cycle_percent),
+                "must be a percentage (1-100)",
+            ),
+            (
+                "validator.key_name",
+                !self.validator.enabled || !self.validator.key_name.is_empty(),
+                "must be set when the validator is enabled",
+            ),
+            (
+                "validator.fee_recipient",
+                self.validator.fee_recipient.is_none() || self.validator.fee_recipient().is_some(),
+                "must be a 20-byte hex address",
+            ),
+            (
+                "validator.fee_recipient",
+                !self.validator.enabled || self.validator.fee_recipient.is_some(),
+                "must be set when the validator is enabled",
+            ),
+            (
+                "validator.graffiti",
+                self.validator.graffiti.len() <= MAX_GRAFFITI_LEN,
+                "must be at most 32 bytes",
+            ),
+            (
+                "resources.memory_budget_mb",
+                self.resources.memory_budget_mb >= MIN_MEMORY_BUDGET_MB,
+                "must be at least 64",
+            ),
+            (
+                "resources.check_interval_secs",
+                self.resources.check_interval_secs > 0,
+                "must be at least 1",
+            ),
+            (
+                "sync.request_timeout_secs",
+                self.sync.request_timeout_secs > 0,
+                "must be at least 1",
+            ),
+            (
+                "metrics.sample_interval_secs",
+                self.metrics.sample_interval_secs > 0,
+                "must be at least 1",
+            ),
+        ];
+
+        match checks.into_iter().find(|(_, ok, _)| !ok) {
+            Some((key, _, reason)) => Err(ConfigError::Invalid {
+                key,
+                reason: reason.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Configuration errors.
@@ -50,6 +243,28 @@ impl NodeConfig {
 pub enum ConfigError {
     /// HMAC secret is not set (zero value).
     InsecureHmacSecret,
+    /// Config file could not be read.
+    Read {
+        /// File path.
+        path: PathBuf,
+        /// Underlying error.
+        source: std::io::Error,
+    },
+    /// A layer (file, environment variable or `--set`) does not match the
+    /// schema: unknown key, wrong type or malformed TOML.
+    Parse {
+        /// Where the value came from, e.g. `node.toml` or `QC_P2P_PORT`.
+        origin: String,
+        /// Parser message, including the offending key.
+        message: String,
+    },
+    /// A value is out of range or inconsistent with another.
+    Invalid {
+        /// Dotted key, e.g. `network.p2p_port`.
+        key: &'static str,
+        /// What is wrong with it.
+        reason: String,
+    },
 }
 
 impl std::fmt::Display for ConfigError {
@@ -62,14 +277,53 @@ impl std::fmt::Display for ConfigError {
                      Set QC_HMAC_SECRET environment variable or provide in config."
                 )
             }
+            ConfigError::Read { path, source } => {
+                write!(f, "cannot read config file {}: {}", path.display(), source)
+            }
+            ConfigError::Parse { origin, message } => {
+                write!(
+                    f,
+                    "invalid configuration in {}: {}",
+                    origin,
+                    message.trim_end()
+                )
+            }
+            ConfigError::Invalid { key, reason } => {
+                write!(f, "invalid configuration: {} {}", key, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Read { source, .. } => Some(source),
+            _ => None,
         }
     }
 }
 
-impl std::error::Error for ConfigError {}
+/// Chain selection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChainConfig {
+    /// Chain spec: "mainnet", "testnet", "dev" or a JSON file (see
+    /// `genesis::ChainSpec`).
+    pub spec: String,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            spec: "mainnet".to_string(),
+        }
+    }
+}
 
 /// Network configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct NetworkConfig {
     /// P2P listening port.
     pub p2p_port: u16,
@@ -96,7 +350,8 @@ impl Default for NetworkConfig {
 }
 
 /// Storage configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct StorageConfig {
     /// Data directory for block storage.
     pub data_dir: PathBuf,
@@ -119,39 +374,236 @@ impl Default for StorageConfig {
     }
 }
 
+/// Event bus configuration.
+///
+/// In files this is flattened to `backend = "memory" | "persistent"` plus
+/// `log_*`, `journal*` and `mirror_*` keys (see `EventBusSettings`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "EventBusSettings", into = "EventBusSettings")]
+pub struct EventBusConfig {
+    /// Where published events are kept.
+    pub backend: EventBusBackend,
+    /// Audit journal of every published event, if enabled.
+    pub journal: Option<JournalConfig>,
+    /// Recent events kept per topic for `debug_eventBus` (0 disables).
+    pub mirror_events_per_topic: usize,
+}
+
+impl Default for EventBusConfig {
+    fn default() -> Self {
+        Self {
+            backend: EventBusBackend::default(),
+            journal: None,
+            mirror_events_per_topic: DEFAULT_MIRROR_EVENTS_PER_TOPIC,
+        }
+    }
+}
+
+/// Event bus backend.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum EventBusBackend {
+    /// Broadcast only; events in flight are lost on a crash.
+    #[default]
+    Memory,
+    /// Also append every event to an on-disk segment log, for consumers
+    /// that need at-least-once delivery across restarts.
+    Persistent(EventLogConfig),
+}
+
+impl EventBusBackend {
+    /// Persistent log under `data_dir`, with default segment size.
+    pub fn persistent(data_dir: &std::path::Path) -> Self {
+        Self::Persistent(EventLogConfig::new(data_dir.join("event-log")))
+    }
+}
+
+/// Event bus backend name in config files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    /// `EventBusBackend::Memory`
+    #[default]
+    Memory,
+    /// `EventBusBackend::Persistent`
+    Persistent,
+}
+
+/// File representation of `EventBusConfig`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EventBusSettings {
+    /// `memory` or `persistent`.
+    pub backend: BackendKind,
+    /// Persistent log directory (defaults to `<data_dir>/event-log`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_dir: Option<PathBuf>,
+    /// Segment size before rolling to a new file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_segment_max_bytes: Option<u64>,
+    /// Flush every log append to disk.
+    pub log_fsync: bool,
+    /// Audit journal file (disabled if unset).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal: Option<PathBuf>,
+    /// Record events in the journal, not just their hash.
+    pub journal_payloads: bool,
+    /// Flush every journal entry to disk.
+    pub journal_fsync: bool,
+    /// Recent events kept per topic for `debug_eventBus` (0 disables).
+    pub mirror_events_per_topic: usize,
+}
+
+impl Default for EventBusSettings {
+    fn default() -> Self {
+        Self {
+            backend: BackendKind::Memory,
+            log_dir: None,
+            log_segment_max_bytes: None,
+            log_fsync: false,
+            journal: None,
+            journal_payloads: true,
+            journal_fsync: false,
+            mirror_events_per_topic: DEFAULT_MIRROR_EVENTS_PER_TOPIC,
+        }
+    }
+}
+
+impl From<EventBusSettings> for EventBusConfig {
+    fn from(settings: EventBusSettings) -> Self {
+        let backend = match settings.backend {
+            BackendKind::Memory => EventBusBackend::Memory,
+            BackendKind::Persistent => {
+                let dir = settings
+                    .log_dir
+                    .unwrap_or_else(|| StorageConfig::default().data_dir.join("event-log"));
+                let mut log = EventLogConfig::new(dir).with_fsync(settings.log_fsync);
+                if let Some(bytes) = settings.log_segment_max_bytes {
+                    log = log.with_segment_max_bytes(bytes);
+                }
+                EventBusBackend::Persistent(log)
+            }
+        };
+        let journal = settings.journal.map(|path| {
+            JournalConfig::new(path)
+                .with_payloads(settings.journal_payloads)
+                .with_fsync(settings.journal_fsync)
+        });
+        Self {
+            backend,
+            journal,
+            mirror_events_per_topic: settings.mirror_events_per_topic,
+        }
+    }
+}
+
+impl From<EventBusConfig> for EventBusSettings {
+    fn from(config: EventBusConfig) -> Self {
+        let mut settings = Self::default();
+        if let EventBusBackend::Persistent(log) = config.backend {
+            settings.backend = BackendKind::Persistent;
+            settings.log_dir = Some(log.dir);
+            settings.log_segment_max_bytes = Some(log.segment_max_bytes);
+            settings.log_fsync = log.fsync;
+        }
+        if let Some(journal) = config.journal {
+            settings.journal = Some(journal.path);
+            settings.journal_payloads = journal.payloads;
+            settings.journal_fsync = journal.fsync;
+        }
+        settings.mirror_events_per_topic = config.mirror_events_per_topic;
+        settings
+    }
+}
+
+/// HMAC secret as 64 hex characters (optionally `0x`-prefixed).
+mod hex_secret {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use shared_types::SecretBytes;
+
+    pub fn serialize<S: Serializer>(
+        secret: &SecretBytes,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(secret.expose_secret()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<SecretBytes, D::Error> {
+        let text = SecretBytes::deserialize(deserializer)?;
+        let hex = text.expose_str().unwrap_or_default();
+        let bytes =
+            SecretBytes::new(hex::decode(hex.trim_start_matches("0x")).map_err(D::Error::custom)?);
+        if bytes.len() != 32 {
+            return Err(D::Error::custom(format!(
+                "expected 32 bytes (64 hex characters), got {} bytes",
+                bytes.len()
+            )));
+        }
+        Ok(bytes)
+    }
+}
+
+/// Optional text secret (API key), written back as plain text.
+mod text_secret {
+    use serde::Serializer;
+    use shared_types::SecretBytes;
+
+    pub fn serialize<S: Serializer>(
+        secret: &Option<SecretBytes>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match secret.as_ref().and_then(SecretBytes::expose_str) {
+            Some(text) => serializer.serialize_str(text),
+            None => serializer.serialize_none(),
+        }
+    }
+}
+
 /// Security configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SecurityConfig {
     /// HMAC secret for inter-subsystem authentication (32 bytes).
     /// MUST NOT be default in production.
-    pub hmac_secret: [u8; 32],
+    #[serde(with = "hex_secret")]
+    pub hmac_secret: SecretBytes,
     /// Nonce cache expiry in seconds.
     pub nonce_cache_expiry_secs: u64,
     /// Maximum message age in seconds.
     pub max_message_age_secs: u64,
     /// Maximum future timestamp skew in seconds.
     pub max_future_skew_secs: u64,
+    /// File the nonce cache is saved to and restored from, so replays
+    /// are still rejected across a restart. Not persisted if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce_cache_file: Option<std::path::PathBuf>,
 }
 
 impl Default for SecurityConfig {
     fn default() -> Self {
         Self {
-            hmac_secret: [0u8; 32], // MUST be overridden in production
+            hmac_secret: SecretBytes::new(vec![0u8; 32]), // MUST be overridden in production
             nonce_cache_expiry_secs: 120,
             max_message_age_secs: 60,
             max_future_skew_secs: 10,
+            nonce_cache_file: None,
         }
     }
 }
 
 /// Consensus configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ConsensusConfig {
     /// Consensus algorithm: "pos" or "pbft".
     pub algorithm: String,
     /// Minimum attestation percentage for PoS (default: 67 = 2/3).
     pub min_attestation_percent: u8,
-    /// Maximum block gas limit.
+    /// Unused: the block gas limit comes from the chain spec. Still accepted
+    /// so older config files load.
+    #[serde(skip_serializing)]
     pub max_block_gas: u64,
     /// Block time in seconds.
     pub block_time_secs: u64,
@@ -172,7 +624,8 @@ impl Default for ConsensusConfig {
 }
 
 /// Mempool configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct MempoolConfig {
     /// Maximum transactions in pool.
     pub max_transactions: usize,
@@ -196,7 +649,8 @@ impl Default for MempoolConfig {
 }
 
 /// Finality configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct FinalityConfig {
     /// Justification threshold (percentage, default: 67).
     pub justification_threshold: u8,
@@ -217,7 +671,8 @@ impl Default for FinalityConfig {
 }
 
 /// API Gateway configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ApiGatewayConfig {
     /// Enable the API Gateway.
     pub enabled: bool,
@@ -228,12 +683,18 @@ pub struct ApiGatewayConfig {
     /// Admin API port (localhost only by default).
     pub admin_port: u16,
     /// Optional API key for protected endpoints.
-    pub api_key: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "text_secret::serialize"
+    )]
+    pub api_key: Option<SecretBytes>,
     /// Rate limit (requests per second per IP).
     pub rate_limit_per_second: u32,
     /// Maximum batch size.
     pub max_batch_size: usize,
-    /// Chain ID for eth_chainId responses.
+    /// Unused: eth_chainId reports the chain spec's chain ID. Still accepted
+    /// so older config files load.
+    #[serde(skip_serializing)]
     pub chain_id: u64,
 }
 
@@ -253,7 +714,8 @@ impl Default for ApiGatewayConfig {
 }
 
 /// Mining/Block Production configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct MiningConfig {
     /// Enable mining (block production).
     pub enabled: bool,
@@ -269,6 +731,8 @@ pub struct MiningConfig {
     pub max_adjustment_factor: f64,
     /// Mempool refresh interval in milliseconds.
     pub pool_refresh_interval_ms: u64,
+    /// Share of time spent mining, 1-100 (changeable at runtime).
+    pub duty_cycle_percent: u8,
 }
 
 impl Default for MiningConfig {
@@ -281,6 +745,155 @@ impl Default for MiningConfig {
             difficulty_adjustment_interval: 100, // Every 100 blocks
             max_adjustment_factor: 4.0,
             pool_refresh_interval_ms: 1000, // 1 second
+            duty_cycle_percent: 100,
+        }
+    }
+}
+
+/// Longest graffiti a block header can carry.
+pub const MAX_GRAFFITI_LEN: usize = 32;
+
+/// Proof-of-stake validator configuration.
+///
+/// When enabled the node proposes blocks for its validator key instead of
+/// mining. The key must be one of the chain spec's genesis validators.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidatorConfig {
+    /// Run as a validator.
+    pub enabled: bool,
+    /// Keystore entry under `<data_dir>/keys` (see `keys generate`).
+    pub key_name: String,
+    /// File holding the keystore password (default: `QC_KEYSTORE_PASSWORD`,
+    /// then a prompt).
+    pub password_file: Option<PathBuf>,
+    /// Address credited with fees, as 0x-prefixed hex. Required when the
+    /// validator is enabled.
+    pub fee_recipient: Option<String>,
+    /// Free text stamped into proposed blocks.
+    pub graffiti: String,
+    /// Record of everything this validator has signed (default:
+    /// `<data_dir>/validator/slashing_protection.json`).
+    pub slashing_protection_file: Option<PathBuf>,
+}
+
+impl ValidatorConfig {
+    /// Parsed `fee_recipient`, or None if unset or malformed.
+    pub fn fee_recipient(&self) -> Option<[u8; 20]> {
+        let hex_str = self.fee_recipient.as_deref()?;
+        let bytes = hex::decode(hex_str.strip_prefix("0x").unwrap_or(hex_str)).ok()?;
+        bytes.try_into().ok()
+    }
+
+    /// Where the slashing-protection record lives.
+    pub fn slashing_protection_path(&self, data_dir: &std::path::Path) -> PathBuf {
+        self.slashing_protection_file
+            .clone()
+            .unwrap_or_else(|| data_dir.join("validator").join("slashing_protection.json"))
+    }
+}
+
+impl Default for ValidatorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            key_name: "validator".to_string(),
+            password_file: None,
+            fee_recipient: None,
+            graffiti: String::new(),
+            slashing_protection_file: None,
+        }
+    }
+}
+
+/// Graceful shutdown configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShutdownConfig {
+    /// How long to wait for in-flight events to be handled, and then for
+    /// event handlers to exit, before state is checkpointed (0 skips the
+    /// drain and aborts handlers immediately).
+    pub drain_timeout_secs: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            drain_timeout_secs: 10,
+        }
+    }
+}
+
+/// Smallest memory budget accepted, in MiB.
+pub const MIN_MEMORY_BUDGET_MB: u64 = 64;
+
+/// Budgets shared by the subsystems (see `crate::resources`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResourceConfig {
+    /// Memory for the Block Storage, Transaction Indexing, State Management
+    /// and Mempool caches together, in MiB.
+    pub memory_budget_mb: u64,
+    /// File descriptors the node may use (0 = the process limit).
+    pub max_open_files: u64,
+    /// Seconds between cache usage checks.
+    pub check_interval_secs: u64,
+}
+
+impl Default for ResourceConfig {
+    fn default() -> Self {
+        Self {
+            memory_budget_mb: 1024,
+            max_open_files: 0,
+            check_interval_secs: 10,
+        }
+    }
+}
+
+/// Chain sync before block production (see `crate::sync`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SyncConfig {
+    /// Catch up with the best peer before producing blocks.
+    pub enabled: bool,
+    /// Seconds to wait for a peer to report its head; with none, the node
+    /// starts on its own chain.
+    pub peer_wait_secs: u64,
+    /// Blocks the node may trail the best peer by and still count as synced.
+    pub max_lag_blocks: u64,
+    /// Seconds to wait for a peer to answer one request.
+    pub request_timeout_secs: u64,
+    /// Seconds between attempts after a failed sync.
+    pub retry_delay_secs: u64,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            peer_wait_secs: 15,
+            max_lag_blocks: 4,
+            request_timeout_secs: 10,
+            retry_delay_secs: 5,
+        }
+    }
+}
+
+/// Metric history (see `crate::metric_history`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// Samples kept per series (0 disables the history).
+    pub history_samples: usize,
+    /// Seconds between samples.
+    pub sample_interval_secs: u64,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            history_samples: 360,
+            sample_interval_secs: 10,
         }
     }
 }
@@ -295,6 +908,22 @@ mod tests {
         assert_eq!(config.network.p2p_port, 30303);
         assert_eq!(config.consensus.min_attestation_percent, 67);
         assert_eq!(config.mempool.max_transactions, 5000);
+        assert_eq!(config.event_bus.backend, EventBusBackend::Memory);
+        assert_eq!(
+            config.event_bus.mirror_events_per_topic,
+            DEFAULT_MIRROR_EVENTS_PER_TOPIC
+        );
+    }
+
+    #[test]
+    fn test_persistent_event_bus_under_data_dir() {
+        let EventBusBackend::Persistent(log) =
+            EventBusBackend::persistent(std::path::Path::new("/data"))
+        else {
+            panic!("expected a persistent backend");
+        };
+        assert_eq!(log.dir, PathBuf::from("/data/event-log"));
+        assert!(!log.fsync);
     }
 
     #[test]
@@ -303,10 +932,38 @@ mod tests {
         assert!(config.validate_for_production().is_err());
     }
 
+    #[test]
+    fn test_security_config_debug_redacts_hmac_secret() {
+        let mut config = SecurityConfig::default();
+        config.hmac_secret = SecretBytes::new(vec![0xab; 32]);
+        let printed = format!("{config:?}");
+        assert!(printed.contains("<redacted>"));
+        assert!(!printed.contains("171"));
+    }
+
     #[test]
     fn test_validate_accepts_nonzero_hmac() {
         let mut config = NodeConfig::default();
-        config.security.hmac_secret = [1u8; 32];
+        config.security.hmac_secret = SecretBytes::new(vec![1u8; 32]);
         assert!(config.validate_for_production().is_ok());
     }
+
+    #[test]
+    fn test_validator_config_checks() {
+        let mut config = NodeConfig::default();
+        config.validator.fee_recipient = Some(format!("0x{}", "ab".repeat(20)));
+        assert!(config.validate().is_ok());
+        assert_eq!(config.validator.fee_recipient(), Some([0xab; 20]));
+
+        config.validator.fee_recipient = Some("0xabcd".to_string());
+        assert!(config.validate().is_err());
+
+        config.validator.fee_recipient = None;
+        config.validator.enabled = true;
+        assert!(config.validate().is_err());
+
+        config.validator.enabled = false;
+        config.validator.graffiti = "g".repeat(MAX_GRAFFITI_LEN + 1);
+        assert!(config.validate().is_err());
+    }
 }
@@ -0,0 +1,201 @@
+//! # Live Configuration
+//!
+//! The settings an operator can change on a running node (`admin_setConfig`):
+//!
+//! | Setting                             | Applied to                        |
+//! |-------------------------------------|-----------------------------------|
+//! | `log_level`                         | Telemetry level filter            |
+//! | `api_gateway.rate_limit_per_second` | API Gateway (qc-16) per-IP limits |
+//! | `mining.duty_cycle_percent`         | Block Production (qc-17) throttle |
+//!
+//! A change is checked as a whole, with the same range checks as the config
+//! file (`NodeConfig::validate`), before any of it is applied. Components
+//! started after the container (the gateway, the miner) register a listener
+//! that applies their part. Changes last until restart; the config file is
+//! not rewritten.
+
+use super::config::{ConfigError, NodeConfig};
+use parking_lot::RwLock;
+use serde::Deserialize;
+
+/// Keys accepted by `admin_setConfig`.
+pub const RELOADABLE_SETTINGS: [&str; 3] = [
+    "log_level",
+    "api_gateway.rate_limit_per_second",
+    "mining.duty_cycle_percent",
+];
+
+/// Origin reported for a malformed change.
+const CHANGE_ORIGIN: &str = "admin_setConfig";
+
+/// Settings to change; unset fields keep their value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigChange {
+    /// Level filter, e.g. `info,qc_08_consensus=debug`.
+    pub log_level: Option<String>,
+    /// Requests per second per client IP.
+    #[serde(rename = "api_gateway.rate_limit_per_second")]
+    pub rate_limit_per_second: Option<u32>,
+    /// Share of time spent mining, 1-100.
+    #[serde(rename = "mining.duty_cycle_percent")]
+    pub duty_cycle_percent: Option<u8>,
+}
+
+impl ConfigChange {
+    /// Parse a change given as a JSON object of dotted keys.
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, ConfigError> {
+        Self::deserialize(value).map_err(|e| ConfigError::Parse {
+            origin: CHANGE_ORIGIN.to_string(),
+            message: e.to_string(),
+        })
+    }
+}
+
+/// Effective configuration: the loaded config with live changes applied.
+#[derive(Debug, Clone)]
+pub struct LiveSettings {
+    /// Node configuration, including changed reloadable keys.
+    pub config: NodeConfig,
+    /// Telemetry level filter (set from `QC_LOG_LEVEL`, not the config file).
+    pub log_level: String,
+}
+
+type Listener = Box<dyn Fn(&LiveSettings, &LiveSettings) + Send + Sync>;
+
+/// Current settings, and who to tell when they change.
+pub struct LiveConfig {
+    current: RwLock<LiveSettings>,
+    listeners: RwLock<Vec<Listener>>,
+}
+
+impl LiveConfig {
+    /// Start from the loaded config and the level filter in use.
+    pub fn new(config: NodeConfig, log_level: String) -> Self {
+        Self {
+            current: RwLock::new(LiveSettings { config, log_level }),
+            listeners: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Current settings.
+    pub fn settings(&self) -> LiveSettings {
+        self.current.read().clone()
+    }
+
+    /// Call `listener` with the previous and new settings after each change.
+    ///
+    /// Listeners run while the change is applied, so they must not call
+    /// back into `LiveConfig`.
+    pub fn on_change(
+        &self,
+        listener: impl Fn(&LiveSettings, &LiveSettings) + Send + Sync + 'static,
+    ) {
+        self.listeners.write().push(Box::new(listener));
+    }
+
+    /// Check `change` against the current settings and apply it.
+    ///
+    /// Nothing changes if any part of it is invalid.
+    pub fn apply(&self, change: &ConfigChange) -> Result<LiveSettings, ConfigError> {
+        let mut current = self.current.write();
+        let mut next = current.clone();
+        if let Some(level) = &change.log_level {
+            quantum_telemetry::validate_log_level(level).map_err(|e| ConfigError::Invalid {
+                key: "log_level",
+                reason: e.to_string(),
+            })?;
+            next.log_level = level.clone();
+        }
+        if let Some(rate) = change.rate_limit_per_second {
+            next.config.api_gateway.rate_limit_per_second = rate;
+        }
+        if let Some(percent) = change.duty_cycle_percent {
+            next.config.mining.duty_cycle_percent = percent;
+        }
+        next.config.validate()?;
+
+        for listener in self.listeners.read().iter() {
+            listener(&current, &next);
+        }
+        *current = next.clone();
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    fn change(json: serde_json::Value) -> Result<ConfigChange, ConfigError> {
+        ConfigChange::from_json(&json)
+    }
+
+    #[test]
+    fn test_apply_notifies_listeners() {
+        let live = LiveConfig::new(NodeConfig::default(), "info".to_string());
+        let applied = Arc::new(AtomicU32::new(0));
+        let seen = Arc::clone(&applied);
+        live.on_change(move |previous, next| {
+            let rate = next.config.api_gateway.rate_limit_per_second;
+            if rate != previous.config.api_gateway.rate_limit_per_second {
+                seen.store(rate, Ordering::SeqCst);
+            }
+        });
+
+        let settings = live
+            .apply(
+                &change(serde_json::json!({
+                    "api_gateway.rate_limit_per_second": 25,
+                    "mining.duty_cycle_percent": 40,
+                    "log_level": "warn"
+                }))
+                .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(applied.load(Ordering::SeqCst), 25);
+        assert_eq!(settings.config.mining.duty_cycle_percent, 40);
+        assert_eq!(live.settings().log_level, "warn");
+    }
+
+    #[test]
+    fn test_invalid_change_applies_nothing() {
+        let live = LiveConfig::new(NodeConfig::default(), "info".to_string());
+        live.on_change(|_, _| panic!("listener called for an invalid change"));
+
+        let err = live
+            .apply(
+                &change(serde_json::json!({
+                    "api_gateway.rate_limit_per_second": 25,
+                    "mining.duty_cycle_percent": 0
+                }))
+                .unwrap(),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::Invalid {
+                key: "mining.duty_cycle_percent",
+                ..
+            }
+        ));
+        let bad_level = change(serde_json::json!({ "log_level": "qc_08=loud" })).unwrap();
+        assert!(matches!(
+            live.apply(&bad_level),
+            Err(ConfigError::Invalid {
+                key: "log_level",
+                ..
+            })
+        ));
+        assert_eq!(
+            live.settings().config.api_gateway.rate_limit_per_second,
+            100
+        );
+
+        // Settings that need a restart are refused by name
+        let err = change(serde_json::json!({ "network.p2p_port": 1 })).unwrap_err();
+        assert!(err.to_string().contains("network.p2p_port"), "{}", err);
+    }
+}
@@ -0,0 +1,408 @@
+//! # Config Loader
+//!
+//! Builds a `NodeConfig` from layers, each overriding the one before:
+//!
+//! 1. Defaults
+//! 2. TOML file (`--config node.toml`)
+//! 3. Environment: the documented `QC_*` variables (see `ENV_VARS`), plus
+//!    `QC__<SECTION>__<KEY>` for any other setting
+//! 4. Command line: `--set <section>.<key>=<value>`
+//!
+//! Each layer is checked against the schema as it is applied, so an unknown
+//! key or a wrongly typed value is reported with the file, variable or flag
+//! it came from. Range checks (`NodeConfig::validate`) run on the result.
+//!
+//! ```rust,ignore
+//! let config = ConfigLoader::new()
+//!     .with_file("node.toml")
+//!     .with_env(std::env::vars())
+//!     .with_override("network.max_peers=100")
+//!     .load()?;
+//! ```
+
+use super::config::{ConfigError, NodeConfig};
+use std::path::PathBuf;
+use toml::{Table, Value};
+
+/// Environment variables mapped to config keys.
+pub const ENV_VARS: &[(&str, &str)] = &[
+    ("QC_CHAIN", "chain.spec"),
+    ("QC_HMAC_SECRET", "security.hmac_secret"),
+    ("QC_NONCE_CACHE_FILE", "security.nonce_cache_file"),
+    ("QC_P2P_PORT", "network.p2p_port"),
+    ("QC_RPC_PORT", "network.rpc_port"),
+    ("QC_DATA_DIR", "storage.data_dir"),
+    ("QC_EVENT_BUS", "event_bus.backend"),
+    ("QC_EVENT_LOG_DIR", "event_bus.log_dir"),
+    ("QC_EVENT_JOURNAL", "event_bus.journal"),
+];
+
+/// Prefix of generic overrides, e.g. `QC__MEMPOOL__MAX_TRANSACTIONS`.
+pub const ENV_PREFIX: &str = "QC__";
+
+/// Placeholder for secrets in `dump_config` output.
+const REDACTED: &str = "<redacted>";
+
+/// Layered configuration loader.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLoader {
+    file: Option<PathBuf>,
+    env: Vec<(String, String)>,
+    overrides: Vec<String>,
+}
+
+impl ConfigLoader {
+    /// Loader with defaults only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read a TOML config file.
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    /// Apply overrides from environment variables (unrelated ones are ignored).
+    pub fn with_env(mut self, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.env.extend(vars);
+        self
+    }
+
+    /// Apply a `<section>.<key>=<value>` override after the environment.
+    pub fn with_override(mut self, assignment: impl Into<String>) -> Self {
+        self.overrides.push(assignment.into());
+        self
+    }
+
+    /// Merge all layers and validate the result.
+    pub fn load(&self) -> Result<NodeConfig, ConfigError> {
+        let mut table = Table::try_from(NodeConfig::default()).map_err(|e| ConfigError::Parse {
+            origin: "defaults".to_string(),
+            message: e.to_string(),
+        })?;
+
+        if let Some(path) = &self.file {
+            merge(&mut table, read_file(path)?);
+        }
+        for (origin, key, value) in self.env_overrides() {
+            apply(&mut table, &origin, &key, &value)?;
+        }
+        for assignment in &self.overrides {
+            let origin = format!("--set {}", assignment);
+            let Some((key, value)) = assignment.split_once('=') else {
+                return Err(ConfigError::Parse {
+                    origin,
+                    message: "expected <section>.<key>=<value>".to_string(),
+                });
+            };
+            apply(&mut table, &origin, key.trim(), value.trim())?;
+        }
+
+        default_event_log_dir(&mut table);
+        let config = check(&table, "merged configuration")?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// `(variable, key, value)` for each recognised environment variable,
+    /// documented variables first.
+    fn env_overrides(&self) -> Vec<(String, String, String)> {
+        let lookup = |name: &str| {
+            self.env
+                .iter()
+                .rev()
+                .find(|(var, _)| var == name)
+                .map(|(_, value)| value.clone())
+        };
+        let mut overrides: Vec<_> = ENV_VARS
+            .iter()
+            .filter_map(|(var, key)| Some((var.to_string(), key.to_string(), lookup(var)?)))
+            .collect();
+
+        let mut generic: Vec<_> = self
+            .env
+            .iter()
+            .filter_map(|(var, value)| {
+                let path = var.strip_prefix(ENV_PREFIX)?;
+                let key = path.split("__").collect::<Vec<_>>().join(".");
+                Some((var.clone(), key.to_lowercase(), value.clone()))
+            })
+            .collect();
+        generic.sort();
+        generic.dedup_by(|a, b| a.0 == b.0);
+        overrides.extend(generic);
+        overrides
+    }
+}
+
+/// Read and schema-check a config file.
+fn read_file(path: &std::path::Path) -> Result<Table, ConfigError> {
+    let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parse_error = |e: toml::de::Error| ConfigError::Parse {
+        origin: path.display().to_string(),
+        message: e.to_string(),
+    };
+    // Parsing into `NodeConfig` first gives errors with line numbers
+    toml::from_str::<NodeConfig>(&text).map_err(parse_error)?;
+    toml::from_str(&text).map_err(parse_error)
+}
+
+/// Recursively merge `layer` into `base`; tables merge, other values replace.
+fn merge(base: &mut Table, layer: Table) {
+    for (key, value) in layer {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(layer)) => merge(base, layer),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Set dotted `key` to `raw` and check that the table still fits the schema.
+fn apply(table: &mut Table, origin: &str, key: &str, raw: &str) -> Result<(), ConfigError> {
+    let error = |message: String| ConfigError::Parse {
+        origin: origin.to_string(),
+        message,
+    };
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let Some(field) = parts.pop().filter(|f| !f.is_empty()) else {
+        return Err(error(format!("invalid key `{}`", key)));
+    };
+
+    let mut section = &mut *table;
+    for part in parts {
+        let Value::Table(next) = section
+            .entry(part.to_string())
+            .or_insert_with(|| Value::Table(Table::new()))
+        else {
+            return Err(error(format!("`{}` is not a section", part)));
+        };
+        section = next;
+    }
+    let value = parse_value(raw, section.get(field));
+    section.insert(field.to_string(), value);
+
+    check(table, origin).map(|_| ())
+}
+
+/// Interpret `raw` with the type of the value it replaces.
+///
+/// Strings (and unset optional paths) are taken verbatim so that e.g. a
+/// numeric API key stays a string; anything else is parsed as a TOML value.
+fn parse_value(raw: &str, current: Option<&Value>) -> Value {
+    match current {
+        None | Some(Value::String(_)) => Value::String(raw.to_string()),
+        Some(_) => toml::from_str::<Table>(&format!("value = {}", raw))
+            .ok()
+            .and_then(|mut t| t.remove("value"))
+            .unwrap_or_else(|| Value::String(raw.to_string())),
+    }
+}
+
+/// Place a persistent event log under the configured data directory unless
+/// a directory is given.
+fn default_event_log_dir(table: &mut Table) {
+    let data_dir = table
+        .get("storage")
+        .and_then(|s| s.get("data_dir"))
+        .and_then(Value::as_str)
+        .map(PathBuf::from);
+    let Some(Value::Table(event_bus)) = table.get_mut("event_bus") else {
+        return;
+    };
+    let persistent = event_bus.get("backend").and_then(Value::as_str) == Some("persistent");
+    if let (true, false, Some(data_dir)) = (persistent, event_bus.contains_key("log_dir"), data_dir)
+    {
+        let dir = data_dir.join("event-log").to_string_lossy().into_owned();
+        event_bus.insert("log_dir".to_string(), Value::String(dir));
+    }
+}
+
+fn check(table: &Table, origin: &str) -> Result<NodeConfig, ConfigError> {
+    Value::Table(table.clone())
+        .try_into()
+        .map_err(|e: toml::de::Error| ConfigError::Parse {
+            origin: origin.to_string(),
+            message: e.to_string(),
+        })
+}
+
+/// Render `config` as TOML, with secrets replaced unless `show_secrets`.
+pub fn dump_config(config: &NodeConfig, show_secrets: bool) -> Result<String, ConfigError> {
+    let table = config_table(config, show_secrets)?;
+    toml::to_string_pretty(&table).map_err(|e| ConfigError::Parse {
+        origin: "effective configuration".to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// `config` as JSON (for `admin_getConfig`), with secrets replaced.
+pub fn config_json(config: &NodeConfig) -> Result<serde_json::Value, ConfigError> {
+    let table = config_table(config, false)?;
+    serde_json::to_value(table).map_err(|e| ConfigError::Parse {
+        origin: "effective configuration".to_string(),
+        message: e.to_string(),
+    })
+}
+
+fn config_table(config: &NodeConfig, show_secrets: bool) -> Result<Table, ConfigError> {
+    let mut table = Table::try_from(config).map_err(|e| ConfigError::Parse {
+        origin: "effective configuration".to_string(),
+        message: e.to_string(),
+    })?;
+    if !show_secrets {
+        let secrets = [("security", "hmac_secret"), ("api_gateway", "api_key")];
+        for (section, key) in secrets {
+            let value = table
+                .get_mut(section)
+                .and_then(Value::as_table_mut)
+                .and_then(|section| section.get_mut(key));
+            if let Some(value) = value {
+                *value = Value::String(REDACTED.to_string());
+            }
+        }
+    }
+    Ok(table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::config::EventBusBackend;
+
+    fn write_file(name: &str, text: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(name), text).unwrap();
+        dir
+    }
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_layers_override_in_order() {
+        let dir = write_file(
+            "node.toml",
+            "[network]\np2p_port = 40000\nmax_peers = 80\n\n[mempool]\nmax_transactions = 9000\n",
+        );
+        let config = ConfigLoader::new()
+            .with_file(dir.path().join("node.toml"))
+            .with_env(env(&[
+                ("QC_P2P_PORT", "40001"),
+                ("QC__NETWORK__MAX_PEERS", "90"),
+                ("QC_LOG_LEVEL", "debug"),
+            ]))
+            .with_override("network.max_peers=100")
+            .load()
+            .unwrap();
+
+        assert_eq!(config.network.p2p_port, 40001);
+        assert_eq!(config.network.max_peers, 100);
+        assert_eq!(config.mempool.max_transactions, 9000);
+        // Untouched keys keep their defaults
+        assert_eq!(config.network.rpc_port, 8545);
+    }
+
+    #[test]
+    fn test_persistent_event_bus_follows_data_dir() {
+        let config = ConfigLoader::new()
+            .with_env(env(&[
+                ("QC_EVENT_BUS", "persistent"),
+                ("QC_DATA_DIR", "/srv/qc"),
+                ("QC_EVENT_JOURNAL", "/srv/qc/journal.log"),
+            ]))
+            .load()
+            .unwrap();
+        let EventBusBackend::Persistent(log) = &config.event_bus.backend else {
+            panic!("expected a persistent backend");
+        };
+        assert_eq!(log.dir, PathBuf::from("/srv/qc/event-log"));
+        let journal = config.event_bus.journal.as_ref().unwrap();
+        assert_eq!(journal.path, PathBuf::from("/srv/qc/journal.log"));
+    }
+
+    #[test]
+    fn test_errors_name_their_origin() {
+        let dir = write_file("node.toml", "[network]\np2p_prot = 1\n");
+        let err = ConfigLoader::new()
+            .with_file(dir.path().join("node.toml"))
+            .load()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("node.toml"), "{}", err);
+        assert!(err.contains("line 2"), "{}", err);
+        assert!(err.contains("unknown field `p2p_prot`"), "{}", err);
+
+        let err = ConfigLoader::new()
+            .with_env(env(&[("QC_RPC_PORT", "eighty")]))
+            .load()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("QC_RPC_PORT"), "{}", err);
+
+        let err = ConfigLoader::new()
+            .with_override("consensus.min_attestation_percent=40")
+            .load()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::Invalid {
+                key: "consensus.min_attestation_percent",
+                ..
+            }
+        ));
+
+        let err = ConfigLoader::new()
+            .with_override("security.hmac_secret=abcd")
+            .load()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("--set security.hmac_secret=abcd"), "{}", err);
+        assert!(err.contains("32 bytes"), "{}", err);
+    }
+
+    #[test]
+    fn test_dump_round_trips_and_redacts() {
+        let secret = "11".repeat(32);
+        let config = ConfigLoader::new()
+            .with_env(env(&[("QC_HMAC_SECRET", &secret)]))
+            .with_override("api_gateway.api_key=12345")
+            .with_override("network.bootstrap_nodes=[\"10.0.0.1:30303\"]")
+            .load()
+            .unwrap();
+        assert_eq!(config.security.hmac_secret.expose_secret(), [0x11; 32]);
+        assert_eq!(
+            config
+                .api_gateway
+                .api_key
+                .as_ref()
+                .and_then(|key| key.expose_str()),
+            Some("12345")
+        );
+        assert!(!format!("{config:?}").contains("12345"));
+
+        let redacted = dump_config(&config, false).unwrap();
+        assert!(!redacted.contains(&secret));
+        assert!(!redacted.contains("12345"));
+        let json = config_json(&config).unwrap();
+        assert_eq!(json["api_gateway"]["api_key"], REDACTED);
+        assert_eq!(json["network"]["bootstrap_nodes"][0], "10.0.0.1:30303");
+
+        let dir = write_file("dump.toml", &dump_config(&config, true).unwrap());
+        let reloaded = ConfigLoader::new()
+            .with_file(dir.path().join("dump.toml"))
+            .load()
+            .unwrap();
+        assert_eq!(reloaded.security.hmac_secret, config.security.hmac_secret);
+        assert_eq!(reloaded.network.bootstrap_nodes, vec!["10.0.0.1:30303"]);
+        assert_eq!(reloaded.mining.worker_threads, config.mining.worker_threads);
+    }
+}
@@ -10,7 +10,13 @@
 //! - Adapters implement outbound ports for each subsystem
 
 pub mod config;
+pub mod live;
+pub mod loader;
+pub mod registered;
 pub mod subsystems;
 
-pub use config::{ConfigError, NodeConfig};
+pub use config::{ConfigError, EventBusBackend, EventBusConfig, NodeConfig};
+pub use live::{ConfigChange, LiveConfig, LiveSettings, RELOADABLE_SETTINGS};
+pub use loader::{config_json, dump_config, ConfigLoader};
+pub use registered::{container_subsystems, ContainerSubsystem};
 pub use subsystems::SubsystemContainer;
@@ -0,0 +1,219 @@
+//! # Registered Subsystems
+//!
+//! Registry entries for the subsystems the container builds.
+//!
+//! The services themselves are constructed by [`super::SubsystemContainer`]
+//! and live as long as the process. Each entry lets the registry track and
+//! control a subsystem's availability: stopping one withdraws it from the
+//! capability report, so the API gateway refuses its requests until it is
+//! started again.
+//!
+//! Dependencies follow the container's initialization levels. Core
+//! subsystems (the choreography path and the gateway itself) are marked
+//! required and cannot be stopped while the node runs.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use async_trait::async_trait;
+use shared_types::{
+    DynSubsystem, Subsystem, SubsystemError, SubsystemId, SubsystemInfo, SubsystemStatus,
+};
+
+/// Registry adapter for a subsystem held by the container.
+pub struct ContainerSubsystem {
+    /// Static name, as reported by `Subsystem::name`.
+    name: &'static str,
+    /// Info advertised through the registry.
+    info: SubsystemInfo,
+    /// Whether the subsystem is currently serving requests.
+    running: AtomicBool,
+}
+
+impl ContainerSubsystem {
+    /// Create an optional subsystem entry with no dependencies.
+    pub fn new(id: SubsystemId, name: &'static str, description: &str) -> Self {
+        let mut info = SubsystemInfo::new(id, name);
+        info.version = env!("CARGO_PKG_VERSION").to_string();
+        info.description = description.to_string();
+        Self {
+            name,
+            info,
+            running: AtomicBool::new(false),
+        }
+    }
+
+    /// Mark the subsystem as core (cannot be stopped at runtime).
+    pub fn core(mut self) -> Self {
+        self.info = self.info.required();
+        self
+    }
+
+    /// Set the subsystems this one needs running.
+    pub fn depends_on(mut self, dependencies: Vec<SubsystemId>) -> Self {
+        self.info = self.info.depends_on(dependencies);
+        self
+    }
+
+    /// Box for registration.
+    pub fn boxed(self) -> DynSubsystem {
+        Box::new(self)
+    }
+}
+
+#[async_trait]
+impl Subsystem for ContainerSubsystem {
+    fn id(&self) -> SubsystemId {
+        self.info.id
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn info(&self) -> SubsystemInfo {
+        self.info.clone()
+    }
+
+    async fn start(&self) -> Result<(), SubsystemError> {
+        self.running.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), SubsystemError> {
+        self.running.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> SubsystemStatus {
+        if self.running.load(Ordering::SeqCst) {
+            SubsystemStatus::Healthy
+        } else {
+            SubsystemStatus::Stopped
+        }
+    }
+}
+
+/// Registry entries for every subsystem compiled into this node.
+pub fn container_subsystems() -> Vec<DynSubsystem> {
+    #[allow(unused_mut)]
+    let mut subsystems = Vec::new();
+
+    #[cfg(feature = "qc-10")]
+    subsystems.push(
+        ContainerSubsystem::new(
+            SubsystemId::SignatureVerification,
+            "qc-10-signature-verification",
+            "Signature verification",
+        )
+        .core()
+        .boxed(),
+    );
+
+    #[cfg(feature = "qc-01")]
+    subsystems.push(
+        ContainerSubsystem::new(
+            SubsystemId::PeerDiscovery,
+            "qc-01-peer-discovery",
+            "Peer discovery",
+        )
+        .depends_on(vec![SubsystemId::SignatureVerification])
+        .boxed(),
+    );
+
+    #[cfg(feature = "qc-06")]
+    subsystems.push(
+        ContainerSubsystem::new(SubsystemId::Mempool, "qc-06-mempool", "Transaction pool")
+            .depends_on(vec![SubsystemId::SignatureVerification])
+            .boxed(),
+    );
+
+    #[cfg(feature = "qc-03")]
+    subsystems.push(
+        ContainerSubsystem::new(
+            SubsystemId::TransactionIndexing,
+            "qc-03-transaction-indexing",
+            "Transaction indexing",
+        )
+        .core()
+        .boxed(),
+    );
+
+    #[cfg(feature = "qc-04")]
+    subsystems.push(
+        ContainerSubsystem::new(
+            SubsystemId::StateManagement,
+            "qc-04-state-management",
+            "State management",
+        )
+        .core()
+        .boxed(),
+    );
+
+    #[cfg(feature = "qc-05")]
+    subsystems.push(
+        ContainerSubsystem::new(
+            SubsystemId::BlockPropagation,
+            "qc-05-block-propagation",
+            "Block propagation",
+        )
+        .depends_on(vec![SubsystemId::PeerDiscovery])
+        .boxed(),
+    );
+
+    #[cfg(feature = "qc-08")]
+    subsystems.push(
+        ContainerSubsystem::new(SubsystemId::Consensus, "qc-08-consensus", "Consensus")
+            .core()
+            .depends_on(vec![SubsystemId::SignatureVerification])
+            .boxed(),
+    );
+
+    #[cfg(feature = "qc-02")]
+    subsystems.push(
+        ContainerSubsystem::new(
+            SubsystemId::BlockStorage,
+            "qc-02-block-storage",
+            "Block storage",
+        )
+        .core()
+        .boxed(),
+    );
+
+    #[cfg(feature = "qc-09")]
+    subsystems.push(
+        ContainerSubsystem::new(SubsystemId::Finality, "qc-09-finality", "Finality")
+            .core()
+            .depends_on(vec![SubsystemId::BlockStorage, SubsystemId::Consensus])
+            .boxed(),
+    );
+
+    #[cfg(feature = "qc-15")]
+    subsystems.push(
+        ContainerSubsystem::new(
+            SubsystemId::CrossChain,
+            "qc-15-cross-chain",
+            "Cross-chain swaps",
+        )
+        .boxed(),
+    );
+
+    #[cfg(feature = "qc-16")]
+    subsystems.push(
+        ContainerSubsystem::new(SubsystemId::ApiGateway, "qc-16-api-gateway", "API gateway")
+            .core()
+            .boxed(),
+    );
+
+    #[cfg(feature = "qc-17")]
+    subsystems.push(
+        ContainerSubsystem::new(
+            SubsystemId::BlockProduction,
+            "qc-17-block-production",
+            "Block production",
+        )
+        .depends_on(vec![SubsystemId::Consensus])
+        .boxed(),
+    );
+
+    subsystems
+}
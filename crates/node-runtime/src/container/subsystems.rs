@@ -38,7 +38,7 @@ use std::time::Duration;
 use parking_lot::RwLock;
 use tracing::{info, instrument, warn};
 
-use shared_bus::{InMemoryEventBus, TimeBoundedNonceCache};
+use shared_bus::{EventJournal, EventLog, EventMirror, InMemoryEventBus, TimeBoundedNonceCache};
 use shared_types::SubsystemRegistry;
 
 #[cfg(feature = "qc-01")]
@@ -46,7 +46,12 @@ use crate::adapters::peer_discovery::{RuntimeVerificationPublisher, SharedPeerDi
 #[cfg(feature = "qc-01")]
 use qc_01_peer_discovery::adapters::BootstrapHandler;
 
-use crate::container::config::NodeConfig;
+use crate::container::config::{EventBusBackend, NodeConfig};
+use crate::container::live::LiveConfig;
+use crate::container::registered::container_subsystems;
+use crate::genesis::ChainSpec;
+use crate::metric_history::MetricHistory;
+use crate::resources::{BudgetedCache, ResourceBudget};
 
 // =============================================================================
 // CONDITIONAL IMPORTS - Only import enabled subsystems
@@ -70,7 +75,7 @@ use qc_02_block_storage::ports::outbound::{FileBackedKVStore, MockFileSystemAdap
 use qc_03_transaction_indexing::{IndexConfig, TransactionIndex};
 
 #[cfg(feature = "qc-04")]
-use qc_04_state_management::PatriciaMerkleTrie;
+use qc_04_state_management::{PatriciaMerkleTrie, StateError};
 
 #[cfg(feature = "qc-06")]
 use qc_06_mempool::TransactionPool;
@@ -90,6 +95,9 @@ use crate::adapters::ports::finality::{
 #[cfg(feature = "qc-09")]
 use qc_09_finality::service::{FinalityConfig, FinalityService};
 
+#[cfg(feature = "qc-15")]
+use crate::adapters::CrossChainAdapter;
+
 #[cfg(feature = "qc-17")]
 use qc_17_block_production::ConcreteBlockProducer;
 
@@ -102,6 +110,11 @@ use crate::adapters::storage::{
 #[cfg(feature = "rocksdb")]
 use std::sync::Arc as StdArc;
 
+/// Routing table snapshot under `storage.data_dir`, loaded at startup and
+/// saved on shutdown.
+#[cfg(feature = "qc-01")]
+pub const PEER_CACHE_FILE: &str = "peers.dat";
+
 // =============================================================================
 // TYPE ALIASES - Conditional based on features
 // =============================================================================
@@ -191,6 +204,10 @@ pub struct SubsystemContainer {
     #[cfg(feature = "qc-04")]
     pub state_trie: Arc<RwLock<PatriciaMerkleTrie>>,
 
+    /// Database the state trie was loaded from (checkpointed on shutdown).
+    #[cfg(all(feature = "qc-04", feature = "rocksdb"))]
+    state_db: StdArc<RocksDbTrieDatabase>,
+
     // =========================================================================
     // LEVEL 3: Depends on Level 0-2
     // =========================================================================
@@ -216,6 +233,10 @@ pub struct SubsystemContainer {
     // =========================================================================
     // LEVEL 5: Advanced Subsystems
     // =========================================================================
+    /// Cross-Chain (Subsystem 15) - Optional, holds advertised swap liquidity
+    #[cfg(feature = "qc-15")]
+    pub cross_chain: Arc<RwLock<CrossChainAdapter>>,
+
     /// Block Production (Subsystem 17) - Optional
     #[cfg(feature = "qc-17")]
     pub block_producer: Arc<ConcreteBlockProducer>,
@@ -227,19 +248,33 @@ pub struct SubsystemContainer {
     pub event_bus: Arc<InMemoryEventBus>,
 
     /// Time-bounded nonce cache for replay prevention.
-    pub nonce_cache: Arc<RwLock<TimeBoundedNonceCache>>,
+    pub nonce_cache: Arc<TimeBoundedNonceCache>,
+
+    /// Memory and file descriptor budgets for subsystem caches.
+    pub resources: Arc<ResourceBudget>,
+
+    /// Recent samples of the headline metrics for dashboards.
+    pub metric_history: Arc<MetricHistory>,
+
+    /// Settings changeable at runtime (`admin_setConfig`).
+    pub live_config: Arc<LiveConfig>,
 
     /// Subsystem registry for plug-and-play management.
-    pub registry: Arc<RwLock<SubsystemRegistry>>,
+    ///
+    /// Async lock: admin requests hold it while a subsystem stops or starts.
+    pub registry: Arc<tokio::sync::RwLock<SubsystemRegistry>>,
 
     /// Node configuration (immutable after initialization).
     pub config: NodeConfig,
+
+    /// Parameters of the chain this node follows.
+    pub chain_spec: ChainSpec,
 }
 
 impl SubsystemContainer {
     /// Create a new subsystem container with all enabled subsystems initialized.
-    #[instrument(name = "subsystem_init", skip(config))]
-    pub fn new(config: NodeConfig) -> Self {
+    #[instrument(name = "subsystem_init", skip(config, chain_spec))]
+    pub fn new(config: NodeConfig, chain_spec: ChainSpec) -> Self {
         info!("Initializing Quantum-Chain subsystem container");
         info!("Architecture version: 2.4 (Plug-and-Play Pattern)");
 
@@ -251,9 +286,16 @@ impl SubsystemContainer {
         // =====================================================================
         info!("Phase 1: Creating shared infrastructure");
 
-        let event_bus = Arc::new(InMemoryEventBus::new());
-        let nonce_cache = Arc::new(RwLock::new(TimeBoundedNonceCache::new()));
-        let registry = Arc::new(RwLock::new(SubsystemRegistry::new()));
+        let event_bus = Arc::new(Self::init_event_bus(&config));
+        let nonce_cache = Arc::new(Self::init_nonce_cache(&config));
+        let registry = Arc::new(tokio::sync::RwLock::new(Self::init_registry()));
+        let resources = Arc::new(ResourceBudget::new(&config));
+        resources.log_summary();
+        let metric_history = Arc::new(MetricHistory::new(config.metrics.history_samples));
+        let live_config = Arc::new(LiveConfig::new(
+            config.clone(),
+            quantum_telemetry::log_level().unwrap_or_else(|| "info".to_string()),
+        ));
 
         // =====================================================================
         // PHASE 2: Level 0 - No Dependencies
@@ -279,6 +321,7 @@ impl SubsystemContainer {
         #[cfg(feature = "qc-06")]
         let mempool = {
             let mp = Self::init_mempool(&config);
+            resources.register(BudgetedCache::Mempool, mp.clone());
             info!(
                 "  [6] Mempool initialized (max {} txs)",
                 config.mempool.max_transactions
@@ -299,17 +342,25 @@ impl SubsystemContainer {
         #[cfg(feature = "qc-03")]
         let transaction_index = {
             let ti = Self::init_transaction_indexing();
+            resources.register(BudgetedCache::TransactionIndex, ti.clone());
             info!("  [3] Transaction Indexing initialized");
             ti
         };
 
-        #[cfg(feature = "qc-04")]
+        #[cfg(all(feature = "qc-04", not(feature = "rocksdb")))]
         let state_trie = {
             let st = Self::init_state_management(&config);
             info!("  [4] State Management initialized");
             st
         };
 
+        #[cfg(all(feature = "qc-04", feature = "rocksdb"))]
+        let (state_trie, state_db) = {
+            let (st, db) = Self::init_state_management(&config, &resources);
+            info!("  [4] State Management initialized");
+            (st, db)
+        };
+
         #[cfg(not(feature = "qc-03"))]
         warn!("  [3] Transaction Indexing DISABLED");
         #[cfg(not(feature = "qc-04"))]
@@ -342,7 +393,7 @@ impl SubsystemContainer {
 
         #[cfg(feature = "qc-02")]
         let (block_storage, assembly_buffer) = {
-            let (bs, ab) = Self::init_block_storage(&config);
+            let (bs, ab) = Self::init_block_storage(&config, &resources);
             info!(
                 "  [2] Block Storage initialized (timeout={}s, max_pending={})",
                 config.storage.assembly_timeout_secs, config.storage.max_pending_assemblies
@@ -371,9 +422,19 @@ impl SubsystemContainer {
         // =====================================================================
         info!("Phase 7: Initializing Level 5 advanced subsystems");
 
+        #[cfg(feature = "qc-15")]
+        let cross_chain = {
+            let adapter = Arc::new(RwLock::new(CrossChainAdapter::with_defaults()));
+            info!("  [15] Cross-Chain initialized");
+            adapter
+        };
+
+        #[cfg(not(feature = "qc-15"))]
+        warn!("  [15] Cross-Chain DISABLED - swap methods unavailable");
+
         #[cfg(feature = "qc-17")]
         let block_producer = {
-            let bp = Self::init_block_producer(Arc::clone(&event_bus), &config);
+            let bp = Self::init_block_producer(Arc::clone(&event_bus), &config, &chain_spec);
             info!(
                 "  [17] Block Production initialized (mining threads={})",
                 config.mining.worker_threads
@@ -397,6 +458,8 @@ impl SubsystemContainer {
             transaction_index,
             #[cfg(feature = "qc-04")]
             state_trie,
+            #[cfg(all(feature = "qc-04", feature = "rocksdb"))]
+            state_db,
             #[cfg(feature = "qc-08")]
             consensus,
             #[cfg(feature = "qc-02")]
@@ -405,13 +468,32 @@ impl SubsystemContainer {
             assembly_buffer,
             #[cfg(feature = "qc-09")]
             finality,
+            #[cfg(feature = "qc-15")]
+            cross_chain,
             #[cfg(feature = "qc-17")]
             block_producer,
             event_bus,
             nonce_cache,
+            resources,
+            metric_history,
+            live_config,
             registry,
             config,
+            chain_spec,
+        }
+    }
+
+    /// Registry with an entry for every enabled subsystem.
+    ///
+    /// Entries start stopped; the runtime starts them once the node is up.
+    fn init_registry() -> SubsystemRegistry {
+        let mut registry = SubsystemRegistry::new();
+        for subsystem in container_subsystems() {
+            if let Err(e) = registry.register(subsystem) {
+                warn!("  Failed to register subsystem: {}", e);
+            }
         }
+        registry
     }
 
     /// Log which subsystems are enabled at compile time.
@@ -436,6 +518,8 @@ impl SubsystemContainer {
         info!("  ✓ qc-09: Finality");
         #[cfg(feature = "qc-10")]
         info!("  ✓ qc-10: Signature Verification");
+        #[cfg(feature = "qc-15")]
+        info!("  ✓ qc-15: Cross-Chain");
         #[cfg(feature = "qc-16")]
         info!("  ✓ qc-16: API Gateway");
         #[cfg(feature = "qc-17")]
@@ -450,35 +534,92 @@ impl SubsystemContainer {
     /// Create a container for testing with in-memory backends.
     #[cfg(test)]
     pub fn new_for_testing() -> Self {
-        Self::new(NodeConfig::default())
+        Self::new(NodeConfig::default(), ChainSpec::mainnet())
     }
 
     // =========================================================================
     // SUBSYSTEM INITIALIZATION METHODS
     // =========================================================================
 
+    fn init_event_bus(config: &NodeConfig) -> InMemoryEventBus {
+        let bus = match &config.event_bus.backend {
+            EventBusBackend::Memory => {
+                info!("  Event bus: in-memory");
+                InMemoryEventBus::new()
+            }
+            EventBusBackend::Persistent(log_config) => {
+                info!(
+                    "  Event bus: persistent log at {}",
+                    log_config.dir.display()
+                );
+                let log = EventLog::open(log_config.clone()).expect("Failed to open event log");
+                InMemoryEventBus::new().with_event_log(Arc::new(log))
+            }
+        };
+        let bus = match &config.event_bus.journal {
+            Some(journal_config) => {
+                info!("  Event journal: {}", journal_config.path.display());
+                let journal = EventJournal::open(journal_config.clone())
+                    .expect("Failed to open event journal");
+                bus.with_journal(Arc::new(journal))
+            }
+            None => bus,
+        };
+        match config.event_bus.mirror_events_per_topic {
+            0 => bus,
+            per_topic => bus.with_mirror(Arc::new(EventMirror::new(per_topic))),
+        }
+    }
+
+    fn init_nonce_cache(config: &NodeConfig) -> TimeBoundedNonceCache {
+        let cache = TimeBoundedNonceCache::with_config(
+            config.security.nonce_cache_expiry_secs,
+            TimeBoundedNonceCache::DEFAULT_GC_INTERVAL,
+        );
+        if let Some(path) = &config.security.nonce_cache_file {
+            match cache.restore(path) {
+                Ok(restored) => info!("  Nonce cache: restored {} nonces", restored),
+                Err(e) => warn!("  Nonce cache: not restored from {}: {}", path.display(), e),
+            }
+        }
+        cache
+    }
+
     #[cfg(feature = "qc-01")]
     #[allow(clippy::type_complexity)]
     fn init_peer_discovery(
         event_bus: Arc<InMemoryEventBus>,
-        _config: &NodeConfig,
+        config: &NodeConfig,
     ) -> (
         Arc<RwLock<PeerDiscoveryService>>,
         Arc<RwLock<BootstrapHandler<SharedPeerDiscovery, RuntimeVerificationPublisher>>>,
     ) {
         use qc_01_peer_discovery::{
-            adapters::network::ProofOfWorkValidator, KademliaConfig, NodeId, SystemTimeSource,
-            TimeSource,
+            adapters::network::ProofOfWorkValidator, domain::SNAPSHOT_MAX_AGE_SECS,
+            FileRoutingTablePersistence, KademliaConfig, NodeId, SystemTimeSource, TimeSource,
         };
 
         let local_node_id = NodeId::new(rand::random());
         let kademlia_config = KademliaConfig::default();
 
-        let service = Arc::new(RwLock::new(PeerDiscoveryService::new(
+        let mut service = PeerDiscoveryService::new(
             local_node_id,
             kademlia_config,
             Box::new(SystemTimeSource), // Separate instance
-        )));
+        );
+
+        // Peers verified in an earlier run go straight back into the buckets
+        let persistence =
+            FileRoutingTablePersistence::new(config.storage.data_dir.join(PEER_CACHE_FILE));
+        match service.load_routing_table(&persistence, None, None, SNAPSHOT_MAX_AGE_SECS) {
+            Ok(restored) => info!("  Routing table: restored {} peers", restored),
+            Err(e) => warn!(
+                "  Routing table: not restored from {}: {}",
+                persistence.path().display(),
+                e
+            ),
+        }
+        let service = Arc::new(RwLock::new(service));
 
         let shared_service = SharedPeerDiscovery {
             inner: service.clone(),
@@ -528,15 +669,21 @@ impl SubsystemContainer {
     }
 
     #[cfg(all(feature = "qc-04", feature = "rocksdb"))]
-    fn init_state_management(config: &NodeConfig) -> Arc<RwLock<PatriciaMerkleTrie>> {
+    fn init_state_management(
+        config: &NodeConfig,
+        resources: &ResourceBudget,
+    ) -> (Arc<RwLock<PatriciaMerkleTrie>>, StdArc<RocksDbTrieDatabase>) {
         info!("Initializing State Management with RocksDB persistence");
         let db_path = config.storage.data_dir.join("state_db");
         let rocks_config = RocksDbConfig {
             path: db_path.to_string_lossy().to_string(),
+            block_cache_size: resources.memory_quota(BudgetedCache::State) as usize,
+            max_open_files: resources.fds().per_database() as i32,
             ..RocksDbConfig::default()
         };
 
         let store = RocksDbStore::open(rocks_config).expect("Failed to open RocksDB for state");
+        resources.register(BudgetedCache::State, Arc::new(store.block_cache()));
         let trie_db = RocksDbTrieDatabase::new(StdArc::new(store));
 
         let trie = match PatriciaMerkleTrie::load_from_db(&trie_db) {
@@ -550,12 +697,15 @@ impl SubsystemContainer {
             }
         };
 
-        Arc::new(RwLock::new(trie))
+        (Arc::new(RwLock::new(trie)), StdArc::new(trie_db))
     }
 
+    /// Open Block Storage in the configured data directory (also used by
+    /// the `import-chain` and `export-chain` commands).
     #[cfg(feature = "qc-02")]
-    fn init_block_storage(
+    pub(crate) fn init_block_storage(
         config: &NodeConfig,
+        resources: &ResourceBudget,
     ) -> (
         Arc<RwLock<ConcreteBlockStorageService>>,
         Arc<RwLock<BlockAssemblyBuffer>>,
@@ -577,9 +727,15 @@ impl SubsystemContainer {
             let db_path = config.storage.data_dir.join("rocksdb");
             let rocks_config = RocksDbConfig {
                 path: db_path.to_string_lossy().to_string(),
+                block_cache_size: resources.memory_quota(BudgetedCache::BlockStorage) as usize,
+                max_open_files: resources.fds().per_database() as i32,
                 ..RocksDbConfig::default()
             };
             let kv_store = RocksDbStore::open(rocks_config).expect("Failed to open RocksDB");
+            resources.register(
+                BudgetedCache::BlockStorage,
+                Arc::new(kv_store.block_cache()),
+            );
             let fs_adapter = ProductionFileSystemAdapter::new(
                 config.storage.data_dir.to_string_lossy().to_string(),
             );
@@ -596,9 +752,9 @@ impl SubsystemContainer {
 
         #[cfg(not(feature = "rocksdb"))]
         let service = {
-            let data_dir = std::env::var("QC_DATA_DIR")
-                .unwrap_or_else(|_| "/var/quantum-chain/data".to_string());
-            let storage_path = std::path::PathBuf::from(&data_dir).join("blocks.db");
+            // The file-backed store has no cache to budget
+            let _ = resources;
+            let storage_path = config.storage.data_dir.join("blocks.db");
             info!(
                 "Initializing Block Storage with file-backed persistence at {}",
                 storage_path.display()
@@ -683,13 +839,14 @@ impl SubsystemContainer {
     fn init_block_producer(
         event_bus: Arc<InMemoryEventBus>,
         config: &NodeConfig,
+        chain_spec: &ChainSpec,
     ) -> Arc<ConcreteBlockProducer> {
         use primitive_types::U256;
         use qc_17_block_production::{BlockProductionConfig, ConsensusMode};
 
         let mut block_config = BlockProductionConfig {
             mode: ConsensusMode::ProofOfStake,
-            gas_limit: 30_000_000,
+            gas_limit: chain_spec.gas_limit,
             min_gas_price: U256::from(1_000_000_000u64),
             fair_ordering: true,
             ..Default::default()
@@ -697,14 +854,7 @@ impl SubsystemContainer {
 
         if config.mining.enabled {
             block_config.mode = ConsensusMode::ProofOfWork;
-            block_config.pow = Some(qc_17_block_production::PoWConfig {
-                threads: config.mining.worker_threads as u8,
-                algorithm: qc_17_block_production::HashAlgorithm::Keccak256,
-                target_block_time: Some(10),
-                use_dgw: Some(true),
-                dgw_window: Some(24),
-                batch_size: Some(10_000_000),
-            });
+            block_config.pow = Some(chain_spec.pow_config(config.mining.worker_threads as u8));
         }
 
         Arc::new(ConcreteBlockProducer::new(event_bus, block_config))
@@ -720,7 +870,7 @@ impl SubsystemContainer {
     }
 
     /// Get the nonce cache for message validation.
-    pub fn nonce_cache(&self) -> Arc<RwLock<TimeBoundedNonceCache>> {
+    pub fn nonce_cache(&self) -> Arc<TimeBoundedNonceCache> {
         Arc::clone(&self.nonce_cache)
     }
 
@@ -741,6 +891,19 @@ impl SubsystemContainer {
         Arc::clone(&self.state_trie)
     }
 
+    /// Write the state trie back to its database (graceful shutdown).
+    ///
+    /// The in-memory backend has nothing to persist.
+    #[cfg(feature = "qc-04")]
+    pub fn checkpoint_state(&self) -> Result<(), StateError> {
+        #[cfg(feature = "rocksdb")]
+        {
+            self.state_trie.read().save_to_db(&*self.state_db)?;
+            self.state_db.flush()?;
+        }
+        Ok(())
+    }
+
     /// Get consensus service for block validation (if enabled).
     #[cfg(feature = "qc-08")]
     pub fn consensus(&self) -> Arc<ConcreteConsensusService> {
@@ -776,6 +939,7 @@ impl SubsystemContainer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared_bus::JournalConfig;
 
     #[test]
     fn test_container_initialization() {
@@ -783,6 +947,40 @@ mod tests {
         assert_eq!(container.event_bus.subscriber_count(), 0);
     }
 
+    #[test]
+    fn test_event_bus_backend_selection() {
+        let memory = SubsystemContainer::init_event_bus(&NodeConfig::default());
+        assert!(memory.event_log().is_none());
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = NodeConfig::default();
+        config.event_bus.backend = EventBusBackend::persistent(dir.path());
+        let persistent = SubsystemContainer::init_event_bus(&config);
+        assert!(persistent.event_log().is_some());
+        assert!(dir.path().join("event-log").is_dir());
+        assert!(persistent.journal().is_none());
+
+        config.event_bus.journal = Some(JournalConfig::new(dir.path().join("journal.jsonl")));
+        let journaled = SubsystemContainer::init_event_bus(&config);
+        assert!(journaled.journal().is_some());
+    }
+
+    #[test]
+    fn test_nonce_cache_restored_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nonces.json");
+        let nonce = uuid::Uuid::new_v4();
+        let now = shared_types::security::current_timestamp();
+        let saved = TimeBoundedNonceCache::new();
+        saved.validate_and_add(8, nonce, now).unwrap();
+        saved.save(&path).unwrap();
+
+        let mut config = NodeConfig::default();
+        config.security.nonce_cache_file = Some(path);
+        let cache = SubsystemContainer::init_nonce_cache(&config);
+        assert!(cache.contains(8, &nonce));
+    }
+
     #[test]
     fn test_subsystem_enabled_check() {
         // These should reflect the features enabled in test builds
@@ -48,6 +48,9 @@ pub struct GenesisConfig {
 
     /// Extra data (max 32 bytes, e.g., "Quantum-Chain Genesis").
     pub extra_data: Vec<u8>,
+
+    /// Difficulty target of the genesis block (higher is easier).
+    pub difficulty: primitive_types::U256,
 }
 
 impl Default for GenesisConfig {
@@ -59,6 +62,7 @@ impl Default for GenesisConfig {
             initial_stakes: Vec::new(),
             protocol_version: 1,
             extra_data: b"Quantum-Chain Genesis".to_vec(),
+            difficulty: primitive_types::U256::from(2).pow(primitive_types::U256::from(252)),
         }
     }
 }
@@ -118,6 +122,30 @@ pub struct GenesisBlock {
     pub transactions_root: [u8; 32],
 }
 
+impl GenesisBlock {
+    /// The block as written to Block Storage.
+    ///
+    /// Genesis bypasses consensus, so it has no proposer or proof, and uses
+    /// the initial (easy) difficulty from its configuration.
+    pub fn to_validated_block(&self) -> shared_types::ValidatedBlock {
+        shared_types::ValidatedBlock {
+            header: shared_types::BlockHeader {
+                version: 1,
+                height: self.header.height,
+                parent_hash: self.header.parent_hash,
+                merkle_root: self.header.merkle_root,
+                state_root: self.header.state_root,
+                timestamp: self.header.timestamp,
+                proposer: [0u8; 32],
+                difficulty: self.header.difficulty,
+                nonce: 0,
+            },
+            transactions: vec![],
+            consensus_proof: shared_types::ConsensusProof::default(),
+        }
+    }
+}
+
 /// Genesis block header.
 #[derive(Debug, Clone)]
 pub struct GenesisHeader {
@@ -147,6 +175,9 @@ pub struct GenesisHeader {
 
     /// Extra data.
     pub extra_data: Vec<u8>,
+
+    /// Difficulty target (not part of the genesis hash).
+    pub difficulty: primitive_types::U256,
 }
 
 /// Validator information in genesis.
@@ -231,6 +262,7 @@ impl GenesisBuilder {
             chain_id: self.config.chain_id,
             protocol_version: self.config.protocol_version,
             extra_data: self.config.extra_data.clone(),
+            difficulty: self.config.difficulty,
         };
 
         // Compute block hash
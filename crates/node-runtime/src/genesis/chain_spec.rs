@@ -0,0 +1,475 @@
+//! # Chain Spec
+//!
+//! Everything that defines a network rather than a node: chain id, genesis
+//! allocation, difficulty parameters, block gas limit and the fork schedule.
+//!
+//! `--chain` (config key `chain.spec`) picks a preset (`mainnet`, `testnet`,
+//! `dev`) or a JSON file with the same fields. Genesis, Consensus (qc-08) and
+//! Block Production (qc-17) take their chain parameters from the spec.
+//!
+//! ```json
+//! {
+//!   "name": "local",
+//!   "chain_id": 1337,
+//!   "genesis": { "timestamp": 1735689600, "extra_data": "Local Genesis" },
+//!   "difficulty": {
+//!     "initial_target_bits": 255,
+//!     "target_block_time": 5,
+//!     "algorithm": "dgw",
+//!     "dgw_window": 24,
+//!     "lwma_window": 45
+//!   },
+//!   "gas_limit": 30000000,
+//!   "forks": [{ "name": "lwma", "height": 1000, "difficulty_algorithm": "lwma" }]
+//! }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::GenesisConfig;
+
+/// Names accepted by `ChainSpec::load` besides a file path.
+pub const PRESETS: &[&str] = &["mainnet", "testnet", "dev"];
+
+/// Chain spec errors.
+#[derive(Debug, Error)]
+pub enum ChainSpecError {
+    /// Spec file could not be read.
+    #[error("cannot read chain spec {path}: {source}")]
+    Read {
+        /// File path.
+        path: PathBuf,
+        /// Underlying error.
+        source: std::io::Error,
+    },
+
+    /// Spec file is not valid JSON or does not match the schema.
+    #[error("cannot parse chain spec {path}: {source}")]
+    Parse {
+        /// File path.
+        path: PathBuf,
+        /// Underlying error.
+        source: serde_json::Error,
+    },
+
+    /// A value is out of range or inconsistent with another.
+    #[error("invalid chain spec: {0}")]
+    Invalid(String),
+}
+
+/// Parameters of one network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainSpec {
+    /// Network name, e.g. "mainnet".
+    pub name: String,
+    /// Chain ID (also reported by eth_chainId).
+    pub chain_id: u64,
+    /// Genesis block contents.
+    pub genesis: GenesisSpec,
+    /// Proof-of-work difficulty parameters.
+    pub difficulty: DifficultySpec,
+    /// Block gas limit.
+    pub gas_limit: u64,
+    /// Forks, in activation order.
+    #[serde(default)]
+    pub forks: Vec<Fork>,
+}
+
+/// Genesis block contents.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisSpec {
+    /// Genesis timestamp (Unix seconds); the time of creation if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// Extra data (max 32 bytes).
+    pub extra_data: String,
+    /// Initial validators and their stakes.
+    #[serde(default)]
+    pub alloc: Vec<GenesisAllocation>,
+}
+
+/// Stake allocated to a validator at genesis.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisAllocation {
+    /// Compressed public key, 33 bytes hex-encoded.
+    pub validator: String,
+    /// Stake in wei.
+    pub stake: u128,
+}
+
+/// Proof-of-work difficulty parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DifficultySpec {
+    /// Genesis target is 2^initial_target_bits (higher is easier). Also the
+    /// easiest target Consensus accepts.
+    pub initial_target_bits: u32,
+    /// Target time between blocks in seconds.
+    pub target_block_time: u64,
+    /// Algorithm in force from genesis.
+    pub algorithm: DifficultyAlgorithm,
+    /// Blocks averaged by Dark Gravity Wave.
+    pub dgw_window: usize,
+    /// Solve times weighted by LWMA.
+    pub lwma_window: usize,
+}
+
+/// Difficulty adjustment algorithm (same names as qc-17).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DifficultyAlgorithm {
+    /// Adjust once per epoch.
+    #[serde(rename = "epoch")]
+    Epoch,
+    /// Dark Gravity Wave.
+    #[serde(rename = "dgw")]
+    DarkGravityWave,
+    /// Linearly weighted moving average.
+    #[serde(rename = "lwma")]
+    Lwma,
+}
+
+/// A named protocol change activated at a block height.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fork {
+    /// Fork name, unique within the spec.
+    pub name: String,
+    /// First block height the fork applies to.
+    pub height: u64,
+    /// Switch difficulty algorithm from `height` onwards.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty_algorithm: Option<DifficultyAlgorithm>,
+}
+
+impl ChainSpec {
+    /// The main network (chain ID 1).
+    pub fn mainnet() -> Self {
+        Self {
+            name: "mainnet".to_string(),
+            chain_id: 1,
+            genesis: GenesisSpec {
+                timestamp: Some(1_735_689_600),
+                extra_data: "Quantum-Chain Genesis".to_string(),
+                alloc: Vec::new(),
+            },
+            difficulty: DifficultySpec {
+                initial_target_bits: 252,
+                target_block_time: 10,
+                algorithm: DifficultyAlgorithm::DarkGravityWave,
+                dgw_window: 24,
+                lwma_window: 45,
+            },
+            gas_limit: 30_000_000,
+            forks: Vec::new(),
+        }
+    }
+
+    /// The public test network (chain ID 5), which moves to LWMA early to
+    /// exercise the fork schedule.
+    pub fn testnet() -> Self {
+        Self {
+            name: "testnet".to_string(),
+            chain_id: 5,
+            genesis: GenesisSpec {
+                extra_data: "Quantum-Chain Testnet".to_string(),
+                ..Self::mainnet().genesis
+            },
+            forks: vec![Fork {
+                name: "lwma".to_string(),
+                height: 10_000,
+                difficulty_algorithm: Some(DifficultyAlgorithm::Lwma),
+            }],
+            ..Self::mainnet()
+        }
+    }
+
+    /// A local development chain (chain ID 31337): easy, fast blocks and a
+    /// fresh genesis every time the data directory is reset.
+    pub fn dev() -> Self {
+        Self {
+            name: "dev".to_string(),
+            chain_id: 31337,
+            genesis: GenesisSpec {
+                timestamp: None,
+                extra_data: "Quantum-Chain Devnet".to_string(),
+                alloc: Vec::new(),
+            },
+            difficulty: DifficultySpec {
+                initial_target_bits: 255,
+                target_block_time: 2,
+                algorithm: DifficultyAlgorithm::Lwma,
+                dgw_window: 24,
+                lwma_window: 45,
+            },
+            ..Self::mainnet()
+        }
+    }
+
+    /// A preset by name, or else a JSON file at that path.
+    pub fn load(spec: &str) -> Result<Self, ChainSpecError> {
+        match spec {
+            "mainnet" => Ok(Self::mainnet()),
+            "testnet" => Ok(Self::testnet()),
+            "dev" => Ok(Self::dev()),
+            path => Self::from_file(Path::new(path)),
+        }
+    }
+
+    /// Read and validate a JSON spec file.
+    pub fn from_file(path: &Path) -> Result<Self, ChainSpecError> {
+        let json = std::fs::read_to_string(path).map_err(|source| ChainSpecError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let spec: Self = serde_json::from_str(&json).map_err(|source| ChainSpecError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Check what the types cannot express.
+    pub fn validate(&self) -> Result<(), ChainSpecError> {
+        let invalid = |reason: &str| Err(ChainSpecError::Invalid(reason.to_string()));
+
+        if self.chain_id == 0 {
+            return invalid("chain_id must not be 0");
+        }
+        if self.gas_limit == 0 {
+            return invalid("gas_limit must be at least 1");
+        }
+        if !(1..=255).contains(&self.difficulty.initial_target_bits) {
+            return invalid("difficulty.initial_target_bits must be between 1 and 255");
+        }
+        if self.difficulty.target_block_time == 0 {
+            return invalid("difficulty.target_block_time must be at least 1");
+        }
+        if self.difficulty.dgw_window < 2 || self.difficulty.lwma_window < 2 {
+            return invalid("difficulty windows must be at least 2 blocks");
+        }
+        if self.forks.windows(2).any(|w| w[0].height > w[1].height) {
+            return invalid("forks must be in activation order");
+        }
+        if self
+            .forks
+            .iter()
+            .enumerate()
+            .any(|(i, fork)| self.forks[..i].iter().any(|f| f.name == fork.name))
+        {
+            return invalid("fork names must be unique");
+        }
+
+        self.genesis_config()?
+            .validate()
+            .map_err(|e| ChainSpecError::Invalid(e.to_string()))
+    }
+
+    /// True if fork `name` exists and is active at `height`.
+    pub fn is_active(&self, name: &str, height: u64) -> bool {
+        self.forks
+            .iter()
+            .any(|fork| fork.name == name && height >= fork.height)
+    }
+
+    /// Target of the genesis block, and the easiest target allowed.
+    pub fn initial_target(&self) -> U256 {
+        U256::from(2).pow(U256::from(self.difficulty.initial_target_bits))
+    }
+
+    /// Genesis block configuration.
+    pub fn genesis_config(&self) -> Result<GenesisConfig, ChainSpecError> {
+        let initial_validators = self
+            .genesis
+            .alloc
+            .iter()
+            .map(|alloc| parse_pubkey(&alloc.validator))
+            .collect::<Result<_, _>>()?;
+
+        Ok(GenesisConfig {
+            chain_id: self.chain_id,
+            timestamp: self.genesis.timestamp,
+            initial_validators,
+            initial_stakes: self.genesis.alloc.iter().map(|a| a.stake).collect(),
+            extra_data: self.genesis.extra_data.as_bytes().to_vec(),
+            difficulty: self.initial_target(),
+            ..GenesisConfig::default()
+        })
+    }
+
+    /// Block validation rules for Consensus (qc-08).
+    #[cfg(feature = "qc-08")]
+    pub fn block_validation_config(&self) -> qc_08_consensus::BlockValidationConfig {
+        qc_08_consensus::BlockValidationConfig {
+            max_target: Some(self.initial_target()),
+            ..Default::default()
+        }
+    }
+
+    /// Proof-of-work settings for Block Production (qc-17).
+    #[cfg(feature = "qc-17")]
+    pub fn pow_config(&self, threads: u8) -> qc_17_block_production::PoWConfig {
+        qc_17_block_production::PoWConfig {
+            threads,
+            target_block_time: Some(self.difficulty.target_block_time),
+            use_dgw: Some(self.difficulty.algorithm == DifficultyAlgorithm::DarkGravityWave),
+            difficulty_algorithm: Some(self.difficulty.algorithm.into()),
+            difficulty_activations: self
+                .forks
+                .iter()
+                .filter_map(|fork| {
+                    fork.difficulty_algorithm.map(|algorithm| {
+                        qc_17_block_production::AlgorithmActivation {
+                            height: fork.height,
+                            algorithm: algorithm.into(),
+                        }
+                    })
+                })
+                .collect(),
+            dgw_window: Some(self.difficulty.dgw_window),
+            lwma_window: Some(self.difficulty.lwma_window),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(feature = "qc-17")]
+impl From<DifficultyAlgorithm> for qc_17_block_production::DifficultyAlgorithm {
+    fn from(algorithm: DifficultyAlgorithm) -> Self {
+        match algorithm {
+            DifficultyAlgorithm::Epoch => Self::Epoch,
+            DifficultyAlgorithm::DarkGravityWave => Self::DarkGravityWave,
+            DifficultyAlgorithm::Lwma => Self::Lwma,
+        }
+    }
+}
+
+fn parse_pubkey(hex_key: &str) -> Result<[u8; 33], ChainSpecError> {
+    hex::decode(hex_key.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| {
+            ChainSpecError::Invalid(format!(
+                "validator {} is not a 33-byte hex public key",
+                hex_key
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        for name in PRESETS {
+            let spec = ChainSpec::load(name).unwrap();
+            assert_eq!(spec.name, *name);
+            spec.validate().unwrap();
+        }
+        assert_eq!(ChainSpec::mainnet().chain_id, 1);
+        assert_eq!(ChainSpec::testnet().chain_id, 5);
+        assert_eq!(ChainSpec::dev().chain_id, 31337);
+    }
+
+    #[test]
+    fn test_mainnet_genesis_is_deterministic() {
+        let spec = ChainSpec::mainnet();
+        let a = super::super::GenesisBuilder::new(spec.genesis_config().unwrap())
+            .build()
+            .unwrap();
+        let b = super::super::GenesisBuilder::new(spec.genesis_config().unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(a.header.block_hash, b.header.block_hash);
+        assert_eq!(
+            a.to_validated_block().header.difficulty,
+            spec.initial_target()
+        );
+    }
+
+    #[test]
+    fn test_load_json_file() {
+        let mut spec = ChainSpec::dev();
+        spec.name = "custom".to_string();
+        spec.chain_id = 1337;
+        spec.genesis.alloc = vec![GenesisAllocation {
+            validator: hex::encode([0x02u8; 33]),
+            stake: 32_000_000_000_000_000_000,
+        }];
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("custom.json");
+        std::fs::write(&path, serde_json::to_string_pretty(&spec).unwrap()).unwrap();
+
+        let loaded = ChainSpec::load(path.to_str().unwrap()).unwrap();
+        assert_eq!(loaded, spec);
+        assert_eq!(loaded.genesis_config().unwrap().initial_validators.len(), 1);
+    }
+
+    #[test]
+    fn test_load_errors() {
+        assert!(matches!(
+            ChainSpec::load("no-such-chain.json"),
+            Err(ChainSpecError::Read { .. })
+        ));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.json");
+        std::fs::write(&path, r#"{"name": "bad", "chain_id": 7}"#).unwrap();
+        assert!(matches!(
+            ChainSpec::from_file(&path),
+            Err(ChainSpecError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut spec = ChainSpec::mainnet();
+        spec.difficulty.initial_target_bits = 256;
+        assert!(spec.validate().is_err());
+
+        let mut spec = ChainSpec::mainnet();
+        spec.genesis.alloc = vec![GenesisAllocation {
+            validator: "02ab".to_string(),
+            stake: 1,
+        }];
+        assert!(spec.validate().is_err());
+
+        let mut spec = ChainSpec::testnet();
+        spec.forks.push(Fork {
+            name: "early".to_string(),
+            height: 1,
+            difficulty_algorithm: None,
+        });
+        assert!(spec.validate().is_err());
+    }
+
+    #[test]
+    fn test_fork_schedule() {
+        let spec = ChainSpec::testnet();
+        assert!(!spec.is_active("lwma", 9_999));
+        assert!(spec.is_active("lwma", 10_000));
+        assert!(!spec.is_active("unknown", u64::MAX));
+    }
+
+    #[cfg(feature = "qc-17")]
+    #[test]
+    fn test_pow_config_follows_forks() {
+        let pow = ChainSpec::testnet().pow_config(1);
+        assert_eq!(pow.target_block_time, Some(10));
+        assert_eq!(pow.difficulty_activations.len(), 1);
+        assert_eq!(pow.difficulty_activations[0].height, 10_000);
+        assert_eq!(
+            pow.difficulty_activations[0].algorithm,
+            qc_17_block_production::DifficultyAlgorithm::Lwma
+        );
+    }
+}
@@ -19,7 +19,12 @@
 //! 3. Initialize State Management with empty state root
 //! 4. Set finalized height to 0
 //! 5. Initialize Transaction Indexing with empty Merkle tree
+//!
+//! The genesis contents come from the chain spec (`chain_spec`), which also
+//! carries the difficulty, gas limit and fork schedule of the network.
 
 pub mod builder;
+pub mod chain_spec;
 
 pub use builder::{GenesisBlock, GenesisBuilder, GenesisConfig, GenesisError};
+pub use chain_spec::{ChainSpec, ChainSpecError};
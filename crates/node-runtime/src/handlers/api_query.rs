@@ -41,12 +41,37 @@
 //! qc-16 API Gateway
 //! ```
 
-use crate::container::SubsystemContainer;
+use crate::container::{
+    config_json, ConfigChange, LiveSettings, SubsystemContainer, RELOADABLE_SETTINGS,
+};
+use crate::metric_history::{self, MetricSample};
+use quantum_telemetry::PropagatedContext;
 use shared_bus::{
-    ApiQueryError, BlockchainEvent, EventFilter, EventPublisher, EventTopic, Subscription,
+    ApiQueryError, BlockchainEvent, DeadLetterEntry, DeadLetterError, EventFilter, EventPublisher,
+    EventTopic, InMemoryEventBus, Subscription, TopicPattern,
 };
+use shared_types::SubsystemId;
 use std::sync::Arc;
-use tracing::{debug, error, info, instrument, warn};
+use tracing::{debug, error, info, instrument, warn, Instrument};
+
+#[cfg(feature = "qc-15")]
+use crate::adapters::CrossChainAdapter;
+#[cfg(feature = "qc-15")]
+use qc_15_cross_chain::{
+    ChainId, CrossChainError, MakerOffer, NetworkFees, QuoteParams, SwapPair, SwapQuote,
+};
+
+/// Dead letters returned by `dlq_list` when no `limit` is given.
+const DEFAULT_DLQ_LIST_LIMIT: u64 = 100;
+
+/// Mirrored events per topic returned by `get_event_bus` when no `limit`
+/// is given.
+const DEFAULT_EVENT_BUS_LIMIT: u64 = 20;
+
+/// Blocks returned by `get_recent_blocks` when no `limit` is given.
+const DEFAULT_RECENT_BLOCKS: u64 = 20;
+/// Largest `get_recent_blocks` limit.
+const MAX_RECENT_BLOCKS: u64 = 256;
 
 /// Helper to create block transaction count JSON
 fn block_tx_json(block_num: u64, count: u64) -> serde_json::Value {
@@ -56,6 +81,487 @@ fn block_tx_json(block_num: u64, count: u64) -> serde_json::Value {
     })
 }
 
+/// Helper to create dead letter JSON
+fn dead_letter_json(entry: &DeadLetterEntry) -> serde_json::Value {
+    let received_at = entry
+        .received_at
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    serde_json::json!({
+        "id": entry.id,
+        "received_at": received_at,
+        "source": entry.event.source_subsystem(),
+        "replayable": entry.original_event().is_some(),
+        "event": serde_json::to_value(&entry.event).unwrap_or(serde_json::Value::Null)
+    })
+}
+
+/// Read the dead letter `id` parameter
+fn dead_letter_id(params: &serde_json::Value) -> Result<u64, ApiQueryError> {
+    params
+        .get("id")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| ApiQueryError {
+            code: -32602,
+            message: "Missing 'id' parameter".to_string(),
+            info: None,
+        })
+}
+
+fn dead_letter_error(e: DeadLetterError) -> ApiQueryError {
+    ApiQueryError {
+        code: -32000,
+        message: e.to_string(),
+        info: None,
+    }
+}
+
+fn unix_millis(time: std::time::SystemTime) -> u64 {
+    time.duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Bus inspection: mirrored events numbered after `since` on topics
+/// matching `topics` (all if empty), per-subscriber lag and DLQ depth.
+///
+/// `cursor` is the `since` to pass next time to get only newer events.
+fn event_bus_json(
+    bus: &InMemoryEventBus,
+    topics: &[TopicPattern],
+    since: u64,
+    limit: usize,
+) -> serde_json::Value {
+    let mirror = bus.mirror();
+    let events: serde_json::Map<_, _> = mirror
+        .as_ref()
+        .map(|mirror| mirror.recent(topics, since, limit))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(topic, events)| {
+            let events: Vec<_> = events
+                .iter()
+                .map(|e| {
+                    serde_json::json!({
+                        "seq": e.seq,
+                        "published_at_ms": unix_millis(e.published_at),
+                        "source": e.event.source_subsystem(),
+                        "event": serde_json::to_value(&e.event).unwrap_or(serde_json::Value::Null)
+                    })
+                })
+                .collect();
+            (topic.name().to_string(), serde_json::Value::from(events))
+        })
+        .collect();
+    let published: serde_json::Map<_, _> = mirror
+        .as_ref()
+        .map(|mirror| mirror.published())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(topic, count)| (topic.name().to_string(), count.into()))
+        .collect();
+    let subscribers: Vec<_> = bus
+        .subscriber_stats()
+        .into_iter()
+        .map(|s| {
+            serde_json::json!({
+                "name": s.name,
+                "policy": s.policy,
+                "pending": s.pending,
+                "delivered": s.delivered,
+                "dropped": s.dropped,
+                "spilled": s.spilled
+            })
+        })
+        .collect();
+    let dead_letters = bus.dead_letters().stats();
+
+    serde_json::json!({
+        "cursor": mirror.as_ref().map_or(since, |mirror| mirror.last_seq().max(since)),
+        "mirror_per_topic": mirror.as_ref().map_or(0, |mirror| mirror.per_topic()),
+        "events": events,
+        "published": published,
+        "events_published": bus.events_published(),
+        "pending_events": bus.pending_events(),
+        "subscribers": subscribers,
+        "dead_letters": {
+            "depth": dead_letters.depth,
+            "received": dead_letters.received,
+            "evicted": dead_letters.evicted
+        }
+    })
+}
+
+/// Resolve a gateway `BlockId` (tag or hex number) to a block height
+fn resolve_block_height(block_id: Option<&serde_json::Value>, latest: u64) -> u64 {
+    block_id
+        .and_then(|id| id.as_str())
+        .map(|tag| match tag {
+            "latest" | "pending" => latest,
+            "earliest" => 0,
+            hex if hex.starts_with("0x") => u64::from_str_radix(&hex[2..], 16).unwrap_or(0),
+            _ => 0,
+        })
+        .or_else(|| block_id.and_then(|id| id.as_u64()))
+        .unwrap_or(latest)
+}
+
+/// Parse a `0x`-prefixed 20-byte address parameter
+#[cfg(any(feature = "qc-15", feature = "qc-17"))]
+fn parse_address(address: &str) -> Result<[u8; 20], ApiQueryError> {
+    hex::decode(address.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| <[u8; 20]>::try_from(bytes).ok())
+        .ok_or_else(|| ApiQueryError {
+            code: -32602,
+            message: format!("Invalid address: {}", address),
+            info: None,
+        })
+}
+
+/// Read a required parameter
+#[cfg(feature = "qc-15")]
+fn required<'a>(
+    data: &'a serde_json::Value,
+    key: &str,
+) -> Result<&'a serde_json::Value, ApiQueryError> {
+    data.get(key).ok_or_else(|| ApiQueryError {
+        code: -32602,
+        message: format!("Missing '{}' parameter", key),
+        info: None,
+    })
+}
+
+/// Error for a parameter of the wrong shape
+#[cfg(feature = "qc-15")]
+fn invalid_param(key: &str, value: &serde_json::Value) -> ApiQueryError {
+    ApiQueryError {
+        code: -32602,
+        message: format!("Invalid '{}' parameter: {}", key, value),
+        info: None,
+    }
+}
+
+/// Parse a chain name (`"QuantumChain"`, `"Ethereum"`, ...)
+#[cfg(feature = "qc-15")]
+fn chain_param(data: &serde_json::Value, key: &str) -> Result<ChainId, ApiQueryError> {
+    let value = required(data, key)?;
+    serde_json::from_value(value.clone()).map_err(|_| ApiQueryError {
+        code: -32602,
+        message: format!("Unsupported chain: {}", value),
+        info: None,
+    })
+}
+
+/// Parse the source/target chain pair stored under `source` and `target`
+#[cfg(feature = "qc-15")]
+fn pair_param(
+    data: &serde_json::Value,
+    source: &str,
+    target: &str,
+) -> Result<SwapPair, ApiQueryError> {
+    Ok(SwapPair::new(
+        chain_param(data, source)?,
+        chain_param(data, target)?,
+    ))
+}
+
+/// Parse a gateway `U256` (`0x`-prefixed hex) that must fit in a `u64`
+#[cfg(feature = "qc-15")]
+fn amount_param(data: &serde_json::Value, key: &str) -> Result<u64, ApiQueryError> {
+    let value = required(data, key)?;
+    value
+        .as_str()
+        .and_then(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok())
+        .ok_or_else(|| invalid_param(key, value))
+}
+
+/// Parse a plain integer parameter
+#[cfg(feature = "qc-15")]
+fn u64_param(data: &serde_json::Value, key: &str) -> Result<u64, ApiQueryError> {
+    let value = required(data, key)?;
+    value.as_u64().ok_or_else(|| invalid_param(key, value))
+}
+
+/// Maker offer in the gateway's `SwapOffer` shape
+#[cfg(feature = "qc-15")]
+fn maker_offer_json(offer: &MakerOffer) -> serde_json::Value {
+    serde_json::json!({
+        "maker": format!("0x{}", hex::encode(offer.maker)),
+        "sourceChain": format!("{:?}", offer.pair.source_chain),
+        "targetChain": format!("{:?}", offer.pair.target_chain),
+        "ratePpm": offer.rate_ppm,
+        "feeBps": offer.fee_bps,
+        "minAmount": format!("0x{:x}", offer.min_amount),
+        "maxAmount": format!("0x{:x}", offer.max_amount),
+        "liquidity": format!("0x{:x}", offer.available_liquidity),
+        "expiresAt": offer.expires_at
+    })
+}
+
+#[cfg(feature = "qc-15")]
+fn swap_quote_json(quote: &SwapQuote) -> serde_json::Value {
+    serde_json::json!({
+        "sourceChain": format!("{:?}", quote.pair.source_chain),
+        "targetChain": format!("{:?}", quote.pair.target_chain),
+        "maker": format!("0x{}", hex::encode(quote.maker)),
+        "sourceAmount": format!("0x{:x}", quote.source_amount),
+        "targetAmount": format!("0x{:x}", quote.target_amount),
+        "makerFee": format!("0x{:x}", quote.maker_fee),
+        "sourceNetworkFee": format!("0x{:x}", quote.source_network_fee),
+        "targetNetworkFee": format!("0x{:x}", quote.target_network_fee),
+        "sourceTimelock": quote.source_timelock,
+        "targetTimelock": quote.target_timelock,
+        "estimatedCompletionSecs": quote.estimated_completion_secs
+    })
+}
+
+/// Serve a qc-15 swap request against the node's maker registry.
+#[cfg(feature = "qc-15")]
+fn cross_chain_query(
+    adapter: &parking_lot::RwLock<CrossChainAdapter>,
+    method: &str,
+    params: &serde_json::Value,
+    now: u64,
+) -> Result<serde_json::Value, ApiQueryError> {
+    // Params comes from RequestPayload tagged enum: { "type": "...", "data": { ... } }
+    let data = params.get("data").unwrap_or(&serde_json::Value::Null);
+    let rejected = |e: CrossChainError| ApiQueryError {
+        code: -32000,
+        message: e.to_string(),
+        info: None,
+    };
+
+    match method {
+        "get_swap_quote" => {
+            let quote = adapter
+                .read()
+                .quote(QuoteParams {
+                    pair: pair_param(data, "source_chain", "target_chain")?,
+                    source_amount: amount_param(data, "amount")?,
+                    // No fee oracle for external chains yet; quotes carry
+                    // the maker fee only.
+                    source_fees: NetworkFees::default(),
+                    target_fees: NetworkFees::default(),
+                    current_time: now,
+                })
+                .map_err(rejected)?;
+            Ok(swap_quote_json(&quote))
+        }
+        "get_swap_makers" => {
+            let pair = pair_param(data, "source_chain", "target_chain")?;
+            let adapter = adapter.read();
+            let offers: Vec<_> = adapter
+                .makers()
+                .offers_for(pair, now)
+                .into_iter()
+                .map(maker_offer_json)
+                .collect();
+            Ok(serde_json::Value::Array(offers))
+        }
+        "advertise_swap_liquidity" => {
+            let offer = required(data, "offer")?;
+            let maker = required(offer, "maker")?;
+            let offer = MakerOffer {
+                maker: parse_address(
+                    maker
+                        .as_str()
+                        .ok_or_else(|| invalid_param("maker", maker))?,
+                )?,
+                pair: pair_param(offer, "sourceChain", "targetChain")?,
+                rate_ppm: u64_param(offer, "ratePpm")?,
+                fee_bps: u64_param(offer, "feeBps")?,
+                min_amount: amount_param(offer, "minAmount")?,
+                max_amount: amount_param(offer, "maxAmount")?,
+                available_liquidity: amount_param(offer, "liquidity")?,
+                expires_at: u64_param(offer, "expiresAt")?,
+            };
+            adapter
+                .write()
+                .advertise_liquidity(offer, now)
+                .map_err(rejected)?;
+            Ok(serde_json::Value::Bool(true))
+        }
+        "withdraw_swap_liquidity" => {
+            let maker = required(data, "maker")?;
+            let maker = parse_address(
+                maker
+                    .as_str()
+                    .ok_or_else(|| invalid_param("maker", maker))?,
+            )?;
+            let pair = pair_param(data, "source_chain", "target_chain")?;
+            let withdrawn = adapter.write().withdraw_liquidity(&maker, pair);
+            Ok(serde_json::Value::Bool(withdrawn.is_some()))
+        }
+        _ => Err(ApiQueryError {
+            code: -32601,
+            message: format!("Unknown cross-chain method: {}", method),
+            info: None,
+        }),
+    }
+}
+
+/// Blocks panel: the `limit` latest canonical blocks, newest first, and
+/// the chain reorganizations block storage has seen, newest first.
+fn recent_blocks_json(
+    storage: &impl qc_02_block_storage::BlockStorageApi,
+    reorgs: &[qc_02_block_storage::ChainReorg],
+    limit: u64,
+) -> serde_json::Value {
+    let hex_hash = |hash: &[u8; 32]| format!("0x{}", hex::encode(hash));
+    let head = storage.get_latest_height().unwrap_or(0);
+    let finalized = storage.get_finalized_height().unwrap_or(0);
+
+    let blocks: Vec<_> = (0..=head)
+        .rev()
+        .take(usize::try_from(limit).unwrap_or(usize::MAX))
+        .filter_map(|height| storage.read_block_by_height(height).ok())
+        .map(|stored| {
+            let header = &stored.block.header;
+            serde_json::json!({
+                "height": header.height,
+                "hash": hex_hash(&stored.block_hash()),
+                "parent_hash": hex_hash(&header.parent_hash),
+                "timestamp": header.timestamp,
+                "transactions": stored.block.transactions.len(),
+                "producer": hex_hash(&header.proposer),
+                "finalized": header.height <= finalized
+            })
+        })
+        .collect();
+    let reorgs: Vec<_> = reorgs
+        .iter()
+        .rev()
+        .map(|reorg| {
+            serde_json::json!({
+                "height": reorg.height,
+                "depth": reorg.depth(),
+                "replaced": reorg.replaced.iter().map(hex_hash).collect::<Vec<_>>(),
+                "adopted": reorg.adopted.iter().map(hex_hash).collect::<Vec<_>>(),
+                "detected_at": reorg.detected_at
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "head": head,
+        "finalized": finalized,
+        "blocks": blocks,
+        "reorgs": reorgs
+    })
+}
+
+/// Metric history as one array per series (for sparklines), or as CSV.
+fn metric_history_json(
+    samples: &[MetricSample],
+    interval_secs: u64,
+    csv: bool,
+) -> serde_json::Value {
+    if csv {
+        return serde_json::json!(metric_history::to_csv(samples));
+    }
+    let series: serde_json::Map<_, _> = metric_history::SERIES
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let values: Vec<_> = samples.iter().map(|s| s.values()[i]).collect();
+            (name.to_string(), serde_json::json!(values))
+        })
+        .collect();
+    serde_json::json!({
+        "interval_secs": interval_secs,
+        "timestamps": samples.iter().map(|s| s.timestamp_ms).collect::<Vec<_>>(),
+        "series": series
+    })
+}
+
+/// Effective configuration (secrets redacted) and the keys `set_config` takes.
+fn live_config_json(settings: &LiveSettings) -> Result<serde_json::Value, ApiQueryError> {
+    let config = config_json(&settings.config).map_err(|e| ApiQueryError {
+        code: -32603,
+        message: e.to_string(),
+        info: None,
+    })?;
+    Ok(serde_json::json!({
+        "config": config,
+        "log_level": settings.log_level,
+        "reloadable": RELOADABLE_SETTINGS
+    }))
+}
+
+/// Parse a `0x`-prefixed 32-byte hash parameter
+fn parse_hash(hash: &str) -> Result<[u8; 32], ApiQueryError> {
+    hex::decode(hash.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| ApiQueryError {
+            code: -32602,
+            message: format!("Invalid hash: {}", hash),
+            info: None,
+        })
+}
+
+/// Mempool browser detail: a pooled transaction, where it stands in its
+/// sender's nonce sequence and the fee bumps it went through.
+fn pooled_transaction_json(
+    pool: &qc_06_mempool::TransactionPool,
+    tx: &qc_06_mempool::MempoolTransaction,
+) -> serde_json::Value {
+    use qc_06_mempool::TransactionState;
+
+    let state = match tx.state {
+        TransactionState::Pending => serde_json::json!({ "status": "pending" }),
+        TransactionState::PendingInclusion {
+            block_height,
+            proposed_at,
+        } => serde_json::json!({
+            "status": "pending_inclusion",
+            "block_height": block_height,
+            "proposed_at": proposed_at
+        }),
+    };
+    let sender_nonces: Vec<_> = pool
+        .sender_transactions(&tx.sender)
+        .iter()
+        .filter_map(|hash| pool.get(hash))
+        .map(|tx| tx.nonce)
+        .collect();
+    let nonce_gaps: Vec<_> = pool
+        .sender_nonce_gaps(&tx.sender)
+        .into_iter()
+        .map(|(from, to)| serde_json::json!({ "from": from, "to": to }))
+        .collect();
+    let replaced: Vec<_> = tx
+        .replaced
+        .iter()
+        .map(|old| {
+            serde_json::json!({
+                "hash": format!("0x{}", hex::encode(old.hash)),
+                "gas_price": format!("0x{:x}", old.gas_price),
+                "added_at": old.added_at,
+                "replaced_at": old.replaced_at
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "hash": format!("0x{}", hex::encode(tx.hash)),
+        "sender": format!("0x{}", hex::encode(tx.sender)),
+        "to": tx.transaction.to.map(|addr| format!("0x{}", hex::encode(addr))),
+        "nonce": tx.nonce,
+        "gas_price": format!("0x{:x}", tx.gas_price),
+        "gas": tx.gas_limit,
+        "value": format!("0x{:x}", tx.transaction.value),
+        "input": format!("0x{}", hex::encode(&tx.transaction.data)),
+        "added_at": tx.added_at,
+        "state": state,
+        "sender_nonces": sender_nonces,
+        "nonce_gaps": nonce_gaps,
+        "replaced": replaced
+    })
+}
+
 /// Handler that processes API queries from the API Gateway.
 ///
 /// Subscribes to `ApiQuery` events and routes them to the appropriate
@@ -70,10 +576,10 @@ pub struct ApiQueryHandler {
 impl ApiQueryHandler {
     /// Create a new API query handler.
     ///
-    /// Subscribes to the ApiGateway topic on the event bus.
+    /// Subscribes to queries the API Gateway (16) publishes on the
+    /// ApiGateway topic; responses from other subsystems are filtered out.
     pub fn new(container: Arc<SubsystemContainer>) -> Self {
-        // Subscribe to ApiGateway topic to receive ApiQuery events
-        let filter = EventFilter::topics(vec![EventTopic::ApiGateway]);
+        let filter = EventFilter::topics(vec![EventTopic::ApiGateway]).from_senders(vec![16]);
         let subscription = container.event_bus.subscribe(filter);
 
         Self {
@@ -97,6 +603,7 @@ impl ApiQueryHandler {
                     target,
                     method,
                     params,
+                    trace_parent,
                 }) => {
                     debug!(
                         correlation_id = %correlation_id,
@@ -105,8 +612,17 @@ impl ApiQueryHandler {
                         "Received API query"
                     );
 
-                    // Process the query and get result
-                    let result = self.process_query(&target, &method, &params).await;
+                    // Process the query in a child span of the gateway's request span
+                    let span = trace_parent
+                        .as_deref()
+                        .and_then(PropagatedContext::from_traceparent)
+                        .unwrap_or_else(PropagatedContext::empty)
+                        .to_context()
+                        .child_span_for_subsystem(&target, &method);
+                    let result = self
+                        .process_query(&target, &method, &params)
+                        .instrument(span)
+                        .await;
 
                     // Determine source subsystem ID from target
                     let source = Self::target_to_subsystem_id(&target);
@@ -126,8 +642,8 @@ impl ApiQueryHandler {
                     );
                 }
                 Some(other) => {
-                    // Received a non-query event (shouldn't happen with proper filtering)
-                    warn!("Received unexpected event type: {:?}", other);
+                    // Only responses to queries targeting qc-16 itself get here
+                    debug!("Ignoring non-query event: {:?}", other);
                 }
                 None => {
                     // Event bus closed
@@ -155,8 +671,10 @@ impl ApiQueryHandler {
             "qc-08-consensus" => self.handle_generic_subsystem_query(method).await,
             "qc-09-finality" => self.handle_generic_subsystem_query(method).await,
             "qc-10-signature-verification" => self.handle_generic_subsystem_query(method).await,
+            "qc-11-smart-contracts" => self.handle_smart_contracts_query(method).await,
+            "qc-15-cross-chain" => self.handle_cross_chain_query(method, params).await,
             "qc-16-api-gateway" => self.handle_generic_subsystem_query(method).await,
-            "qc-17-block-production" => self.handle_generic_subsystem_query(method).await,
+            "qc-17-block-production" => self.handle_block_production_query(method, params).await,
             "node-runtime" => self.handle_node_runtime_query(method, params).await,
             "admin" => self.handle_admin_query(method, params).await,
             _ => {
@@ -164,6 +682,7 @@ impl ApiQueryHandler {
                 Err(ApiQueryError {
                     code: -32601,
                     message: format!("Unknown target subsystem: {}", target),
+                    info: None,
                 })
             }
         }
@@ -185,6 +704,7 @@ impl ApiQueryHandler {
                     Err(e) => Err(ApiQueryError {
                         code: -32000,
                         message: format!("Failed to get block height: {}", e),
+                        info: None,
                     }),
                 }
             }
@@ -198,18 +718,7 @@ impl ApiQueryHandler {
 
                 // Get the height to query
                 let latest = storage.get_latest_height().unwrap_or(0);
-                let height = block_id
-                    .and_then(|id| id.as_str())
-                    .map(|tag| match tag {
-                        "latest" | "pending" => latest,
-                        "earliest" => 0,
-                        hex if hex.starts_with("0x") => {
-                            u64::from_str_radix(&hex[2..], 16).unwrap_or(0)
-                        }
-                        _ => 0,
-                    })
-                    .or_else(|| block_id.and_then(|id| id.as_u64()))
-                    .unwrap_or(latest);
+                let height = resolve_block_height(block_id, latest);
 
                 match storage.read_block_by_height(height) {
                     Ok(stored) => {
@@ -281,9 +790,51 @@ impl ApiQueryHandler {
                     Err(_) => Ok(serde_json::Value::Null),
                 }
             }
+            "get_raw_block" => {
+                let block_id = params.get("GetRawBlock").and_then(|v| v.get("block_id"));
+
+                let storage = self.container.block_storage.read();
+                let latest = storage.get_latest_height().unwrap_or(0);
+                let height = resolve_block_height(block_id, latest);
+
+                // Canonical RLP codec, the same bytes the block hash commits to
+                match storage.read_block_by_height(height) {
+                    Ok(stored) => {
+                        let raw = shared_types::codec::encode(&stored.block);
+                        Ok(serde_json::json!(format!("0x{}", hex::encode(raw))))
+                    }
+                    Err(_) => Ok(serde_json::Value::Null),
+                }
+            }
             _ => Err(ApiQueryError {
                 code: -32601,
                 message: format!("Unknown block storage method: {}", method),
+                info: None,
+            }),
+        }
+    }
+
+    /// Handle queries for qc-11 Smart Contracts.
+    ///
+    /// Execution is not wired into the runtime yet; report that explicitly
+    /// instead of "unknown target".
+    async fn handle_smart_contracts_query(
+        &self,
+        method: &str,
+    ) -> Result<serde_json::Value, ApiQueryError> {
+        match method {
+            "call" | "estimate_gas" => Err(ApiQueryError {
+                code: -32000,
+                message: format!(
+                    "{} unavailable: execution is not wired into the node",
+                    method
+                ),
+                info: None,
+            }),
+            _ => Err(ApiQueryError {
+                code: -32601,
+                message: format!("Unknown smart contracts method: {}", method),
+                info: None,
             }),
         }
     }
@@ -292,7 +843,7 @@ impl ApiQueryHandler {
     async fn handle_mempool_query(
         &self,
         method: &str,
-        _params: &serde_json::Value,
+        params: &serde_json::Value,
     ) -> Result<serde_json::Value, ApiQueryError> {
         match method {
             "get_gas_price" => {
@@ -344,6 +895,20 @@ impl ApiQueryHandler {
                     "queued": {}
                 }))
             }
+            // Transaction detail: { "type": "...", "data": { "hash": "0x..." } }
+            "get_txpool_transaction" => {
+                let hash = parse_hash(
+                    params
+                        .get("data")
+                        .and_then(|d| d.get("hash"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(""),
+                )?;
+                let pool = self.container.mempool.read();
+                Ok(pool.get(&hash).map_or(serde_json::Value::Null, |tx| {
+                    pooled_transaction_json(&pool, tx)
+                }))
+            }
             "get_max_priority_fee_per_gas" => {
                 // Return suggested priority fee (0.1 gwei)
                 let priority_fee = 100_000_000u64;
@@ -352,6 +917,7 @@ impl ApiQueryHandler {
             _ => Err(ApiQueryError {
                 code: -32601,
                 message: format!("Unknown mempool method: {}", method),
+                info: None,
             }),
         }
     }
@@ -393,6 +959,7 @@ impl ApiQueryHandler {
             _ => Err(ApiQueryError {
                 code: -32601,
                 message: format!("Unknown peer discovery method: {}", method),
+                info: None,
             }),
         }
     }
@@ -412,6 +979,7 @@ impl ApiQueryHandler {
                     .ok_or_else(|| ApiQueryError {
                         code: -32602,
                         message: "Missing 'address' parameter".to_string(),
+                        info: None,
                     })?;
 
                 // For now, return 0 balance (state trie integration needed)
@@ -426,6 +994,7 @@ impl ApiQueryHandler {
                     .ok_or_else(|| ApiQueryError {
                         code: -32602,
                         message: "Missing 'address' parameter".to_string(),
+                        info: None,
                     })?;
 
                 // For now, return empty code (no contracts deployed)
@@ -440,6 +1009,7 @@ impl ApiQueryHandler {
                     .ok_or_else(|| ApiQueryError {
                         code: -32602,
                         message: "Missing 'address' parameter".to_string(),
+                        info: None,
                     })?;
 
                 // For now, return 0 nonce (state trie integration needed)
@@ -449,10 +1019,132 @@ impl ApiQueryHandler {
             _ => Err(ApiQueryError {
                 code: -32601,
                 message: format!("Unknown state management method: {}", method),
+                info: None,
             }),
         }
     }
 
+    /// Handle queries for qc-15 Cross-Chain (swap quotes and maker liquidity).
+    #[cfg(feature = "qc-15")]
+    async fn handle_cross_chain_query(
+        &self,
+        method: &str,
+        params: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiQueryError> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        cross_chain_query(&self.container.cross_chain, method, params, now)
+    }
+
+    /// Handle queries for qc-15 Cross-Chain (not compiled in).
+    #[cfg(not(feature = "qc-15"))]
+    async fn handle_cross_chain_query(
+        &self,
+        method: &str,
+        _params: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiQueryError> {
+        self.handle_generic_subsystem_query(method).await
+    }
+
+    /// Handle queries for qc-17 Block Production (external producers).
+    #[cfg(feature = "qc-17")]
+    async fn handle_block_production_query(
+        &self,
+        method: &str,
+        params: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiQueryError> {
+        // Params comes from RequestPayload tagged enum: { "type": "...", "data": { ... } }
+        let data = params.get("data").unwrap_or(&serde_json::Value::Null);
+        let producer = &self.container.block_producer;
+        let rejected = |e: qc_17_block_production::BlockProductionError| ApiQueryError {
+            code: -32000,
+            message: e.to_string(),
+            info: None,
+        };
+
+        match method {
+            "get_block_template" => {
+                let beneficiary = match data.get("beneficiary").and_then(|v| v.as_str()) {
+                    Some(address) => Some(parse_address(address)?),
+                    None => None,
+                };
+                let issued = producer
+                    .get_block_template(beneficiary)
+                    .await
+                    .map_err(rejected)?;
+                let header = &issued.template.header;
+                let parent_hash = format!("0x{}", hex::encode(header.parent_hash.as_bytes()));
+                Ok(serde_json::json!({
+                    "templateId": issued.template_id,
+                    "longPollId": parent_hash,
+                    "parentHash": parent_hash,
+                    "number": format!("0x{:x}", header.block_number),
+                    "timestamp": format!("0x{:x}", header.timestamp),
+                    "beneficiary": format!("0x{}", hex::encode(header.beneficiary)),
+                    "target": format!("0x{:064x}", header.difficulty),
+                    "headerTemplate": format!("0x{}", hex::encode(&issued.header_template)),
+                    "transactionCount": issued.template.transactions.len(),
+                    "totalFees": format!("0x{:x}", issued.template.total_fees),
+                }))
+            }
+            "submit_block" => {
+                let template_id = data
+                    .get("template_id")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| ApiQueryError {
+                        code: -32602,
+                        message: "Missing 'template_id' parameter".to_string(),
+                        info: None,
+                    })?;
+                let nonce =
+                    data.get("nonce")
+                        .and_then(|v| v.as_u64())
+                        .ok_or_else(|| ApiQueryError {
+                            code: -32602,
+                            message: "Missing 'nonce' parameter".to_string(),
+                            info: None,
+                        })?;
+
+                let sealed = producer
+                    .submit_block(template_id, nonce)
+                    .await
+                    .map_err(rejected)?;
+                Ok(serde_json::json!({
+                    "accepted": true,
+                    "blockHash": format!("0x{}", hex::encode(sealed.hash)),
+                    "number": format!("0x{:x}", sealed.template.header.block_number),
+                }))
+            }
+            "get_mev_reports" => {
+                let block_number = data.get("block_number").and_then(|v| v.as_u64());
+                let limit = data.get("limit").and_then(|v| v.as_u64()).unwrap_or(16);
+                let reports = producer.mev_reports(block_number, limit as usize);
+                serde_json::to_value(reports).map_err(|e| ApiQueryError {
+                    code: -32603,
+                    message: e.to_string(),
+                    info: None,
+                })
+            }
+            _ => Err(ApiQueryError {
+                code: -32601,
+                message: format!("Unknown block production method: {}", method),
+                info: None,
+            }),
+        }
+    }
+
+    /// Handle queries for qc-17 Block Production (not compiled in).
+    #[cfg(not(feature = "qc-17"))]
+    async fn handle_block_production_query(
+        &self,
+        method: &str,
+        _params: &serde_json::Value,
+    ) -> Result<serde_json::Value, ApiQueryError> {
+        self.handle_generic_subsystem_query(method).await
+    }
+
     /// Handle queries for subsystems that don't have specific query endpoints.
     /// These subsystems expose their data through debug_subsystemHealth only.
     async fn handle_generic_subsystem_query(
@@ -465,6 +1157,7 @@ impl ApiQueryHandler {
         Err(ApiQueryError {
             code: -32601,
             message: format!("Method not supported: {}", method),
+            info: None,
         })
     }
 
@@ -499,11 +1192,13 @@ impl ApiQueryHandler {
             _ => Err(ApiQueryError {
                 code: -32601,
                 message: format!("Unknown node-runtime method: {}", method),
+                info: None,
             }),
         }
     }
 
-    /// Handle admin queries for subsystem metrics.
+    /// Handle admin queries for subsystem metrics, bus inspection and the
+    /// dead letter queue.
     async fn handle_admin_query(
         &self,
         method: &str,
@@ -520,9 +1215,179 @@ impl ApiQueryHandler {
 
                 self.get_subsystem_specific_metrics(subsystem_id).await
             }
+            // Dead letter queue: { "limit": N } / { "id": N }
+            "dlq_list" => {
+                let limit = params
+                    .get("limit")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(DEFAULT_DLQ_LIST_LIMIT);
+                let entries = self
+                    .container
+                    .event_bus
+                    .dead_letters()
+                    .list(usize::try_from(limit).unwrap_or(usize::MAX));
+                Ok(entries.iter().map(dead_letter_json).collect())
+            }
+            "dlq_peek" => Ok(self
+                .container
+                .event_bus
+                .dead_letters()
+                .peek()
+                .as_ref()
+                .map_or(serde_json::Value::Null, dead_letter_json)),
+            "dlq_stats" => {
+                let stats = self.container.event_bus.dead_letters().stats();
+                Ok(serde_json::json!({
+                    "depth": stats.depth,
+                    "received": stats.received,
+                    "acked": stats.acked,
+                    "replayed": stats.replayed,
+                    "evicted": stats.evicted
+                }))
+            }
+            "dlq_ack" => {
+                let id = dead_letter_id(params)?;
+                self.container
+                    .event_bus
+                    .dead_letters()
+                    .ack(id)
+                    .map_err(dead_letter_error)?;
+                Ok(serde_json::json!(true))
+            }
+            // Bus inspection: { "type": "...", "data": { "topics": ["block.*"], "since": N, "limit": N } }
+            "get_event_bus" => {
+                let data = params.get("data").unwrap_or(&serde_json::Value::Null);
+                let topics: Vec<TopicPattern> = data
+                    .get("topics")
+                    .and_then(|v| v.as_array())
+                    .map(|topics| {
+                        topics
+                            .iter()
+                            .filter_map(|t| t.as_str())
+                            .map(TopicPattern::from)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let since = data.get("since").and_then(|v| v.as_u64()).unwrap_or(0);
+                let limit = data
+                    .get("limit")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(DEFAULT_EVENT_BUS_LIMIT);
+                Ok(event_bus_json(
+                    &self.container.event_bus,
+                    &topics,
+                    since,
+                    usize::try_from(limit).unwrap_or(usize::MAX),
+                ))
+            }
+            // Blocks panel: { "type": "...", "data": { "limit": N } }
+            "get_recent_blocks" => {
+                let limit = params
+                    .get("data")
+                    .and_then(|d| d.get("limit"))
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(DEFAULT_RECENT_BLOCKS)
+                    .min(MAX_RECENT_BLOCKS);
+                let storage = self.container.block_storage.read();
+                Ok(recent_blocks_json(
+                    &*storage,
+                    &storage.recent_reorgs(),
+                    limit,
+                ))
+            }
+            // Metric history: { "type": "...", "data": { "limit": N, "format": "csv" } }
+            "get_metric_history" => {
+                let data = params.get("data").unwrap_or(&serde_json::Value::Null);
+                let limit = data
+                    .get("limit")
+                    .and_then(|v| v.as_u64())
+                    .map_or(usize::MAX, |l| usize::try_from(l).unwrap_or(usize::MAX));
+                let csv = data.get("format").and_then(|v| v.as_str()) == Some("csv");
+                Ok(metric_history_json(
+                    &self.container.metric_history.latest(limit),
+                    self.container.config.metrics.sample_interval_secs,
+                    csv,
+                ))
+            }
+            // Config editor: effective config, and changes to reloadable keys
+            // { "type": "...", "data": { "changes": { "mining.duty_cycle_percent": 50 } } }
+            "get_config" => live_config_json(&self.container.live_config.settings()),
+            "set_config" => {
+                let changes = params
+                    .get("data")
+                    .and_then(|d| d.get("changes"))
+                    .unwrap_or(&serde_json::Value::Null);
+                let settings = ConfigChange::from_json(changes)
+                    .and_then(|change| self.container.live_config.apply(&change))
+                    .map_err(|e| ApiQueryError {
+                        code: -32602,
+                        message: e.to_string(),
+                        info: None,
+                    })?;
+                info!(changes = %changes, "Configuration changed by admin");
+                live_config_json(&settings)
+            }
+            // Runtime subsystem control: { "type": "...", "data": { "subsystem_id": N, "action": "stop" } }
+            "get_subsystem_status" => Ok(self.subsystem_status().await),
+            "control_subsystem" => {
+                let data = params.get("data").unwrap_or(&serde_json::Value::Null);
+                let subsystem_id = data
+                    .get("subsystem_id")
+                    .and_then(|v| v.as_u64())
+                    .and_then(|id| u8::try_from(id).ok())
+                    .and_then(SubsystemId::from_u8)
+                    .ok_or_else(|| ApiQueryError {
+                        code: -32602,
+                        message: "Missing or unknown 'subsystem_id' parameter".to_string(),
+                        info: None,
+                    })?;
+                let action = data.get("action").and_then(|v| v.as_str()).unwrap_or("");
+                if data.get("dry_run").and_then(|v| v.as_bool()) == Some(true) {
+                    return self.check_subsystem_control(subsystem_id, action).await;
+                }
+                self.control_subsystem(subsystem_id, action).await
+            }
+            // Mempool eviction: { "type": "...", "data": { "hash": "0x..." } }
+            "evict_transaction" => {
+                let hash = parse_hash(
+                    params
+                        .get("data")
+                        .and_then(|d| d.get("hash"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(""),
+                )?;
+                let evicted =
+                    self.container
+                        .mempool
+                        .write()
+                        .evict(&hash)
+                        .map_err(|e| ApiQueryError {
+                            code: -32000,
+                            message: e.to_string(),
+                            info: None,
+                        })?;
+                info!(
+                    hash = %hex::encode(evicted.hash),
+                    sender = %hex::encode(evicted.sender),
+                    nonce = evicted.nonce,
+                    "Transaction evicted from mempool by admin"
+                );
+                Ok(serde_json::json!(true))
+            }
+            "dlq_replay" => {
+                let id = dead_letter_id(params)?;
+                let receivers = self
+                    .container
+                    .event_bus
+                    .replay_dead_letter(id)
+                    .await
+                    .map_err(dead_letter_error)?;
+                Ok(serde_json::json!({ "receivers": receivers }))
+            }
             _ => Err(ApiQueryError {
                 code: -32601,
                 message: format!("Unknown admin method: {}", method),
+                info: None,
             }),
         }
     }
@@ -784,10 +1649,124 @@ impl ApiQueryHandler {
             _ => Err(ApiQueryError {
                 code: -32602,
                 message: format!("Unknown subsystem ID: {}", subsystem_id),
+                info: None,
             }),
         }
     }
 
+    /// Status, dependencies and running dependents of every registered subsystem.
+    async fn subsystem_status(&self) -> serde_json::Value {
+        let registry = self.container.registry.read().await;
+        let report = registry.capabilities();
+        report
+            .subsystems
+            .iter()
+            .map(|s| {
+                let dependencies = registry
+                    .info(s.id)
+                    .map(|info| info.dependencies)
+                    .unwrap_or_default();
+                let dependents = registry.dependents(s.id);
+                serde_json::json!({
+                    "id": s.id.as_u8(),
+                    "name": s.name,
+                    "status": s.status,
+                    "core": registry.is_core(s.id),
+                    "dependencies": dependencies.iter().map(|d| d.as_u8()).collect::<Vec<_>>(),
+                    "dependents": dependents.iter().map(|d| d.as_u8()).collect::<Vec<_>>(),
+                })
+            })
+            .collect()
+    }
+
+    /// Stop, start or restart a subsystem through the registry.
+    ///
+    /// Publishes `SubsystemStatusChanged` and a fresh capability report so
+    /// the API gateway stops routing to a stopped subsystem.
+    async fn control_subsystem(
+        &self,
+        id: SubsystemId,
+        action: &str,
+    ) -> Result<serde_json::Value, ApiQueryError> {
+        let registry = self.container.registry.read().await;
+        let result = match action {
+            "stop" => registry.stop(id).await,
+            "start" => registry.start(id).await,
+            "restart" => registry.restart(id).await,
+            _ => {
+                return Err(ApiQueryError {
+                    code: -32602,
+                    message: format!("Unknown subsystem action: {}", action),
+                    info: None,
+                })
+            }
+        };
+        let status = registry.status(id);
+        let report = registry.capabilities();
+        drop(registry);
+
+        match &result {
+            Ok(_) => info!(subsystem = ?id, action, "Subsystem lifecycle changed by operator"),
+            Err(e) => {
+                warn!(subsystem = ?id, action, error = %e, "Subsystem lifecycle action failed")
+            }
+        }
+        if let Some(status) = status {
+            let bus = &self.container.event_bus;
+            bus.publish(BlockchainEvent::SubsystemStatusChanged {
+                subsystem_id: id.as_u8(),
+                action: action.to_string(),
+                status,
+                error: result.as_ref().err().map(ToString::to_string),
+            })
+            .await;
+            bus.publish(BlockchainEvent::CapabilitiesAdvertised(report))
+                .await;
+        }
+
+        let status = result.map_err(|e| ApiQueryError::from(e.to_error_info()))?;
+        Ok(serde_json::json!({
+            "id": id.as_u8(),
+            "action": action,
+            "status": status,
+        }))
+    }
+
+    /// Report whether `action` would be allowed on a subsystem now, and why
+    /// not, without applying it.
+    async fn check_subsystem_control(
+        &self,
+        id: SubsystemId,
+        action: &str,
+    ) -> Result<serde_json::Value, ApiQueryError> {
+        let registry = self.container.registry.read().await;
+        let check = match action {
+            "stop" => registry.check_stop(id),
+            "start" => registry.check_start(id),
+            "restart" => registry.check_restart(id),
+            _ => {
+                return Err(ApiQueryError {
+                    code: -32602,
+                    message: format!("Unknown subsystem action: {}", action),
+                    info: None,
+                })
+            }
+        };
+        Ok(serde_json::json!({
+            "id": id.as_u8(),
+            "action": action,
+            "dry_run": true,
+            "allowed": check.is_ok(),
+            "reason": check.err().map(|e| e.message),
+            "status": registry.status(id),
+            "dependents": registry
+                .dependents(id)
+                .iter()
+                .map(|d| d.as_u8())
+                .collect::<Vec<_>>(),
+        }))
+    }
+
     /// Convert target string to subsystem ID.
     fn target_to_subsystem_id(target: &str) -> u8 {
         match target {
@@ -800,6 +1779,7 @@ impl ApiQueryHandler {
             "qc-08-consensus" => 8,
             "qc-09-finality" => 9,
             "qc-10-signature-verification" => 10,
+            "qc-15-cross-chain" => 15,
             "qc-16-api-gateway" => 16,
             "qc-17-block-production" => 17,
             _ => 0,
@@ -824,4 +1804,322 @@ mod tests {
         );
         assert_eq!(ApiQueryHandler::target_to_subsystem_id("unknown"), 0);
     }
+
+    #[cfg(feature = "qc-15")]
+    #[test]
+    fn test_cross_chain_query_serves_gateway_payloads() {
+        use qc_16_api_gateway::ipc::requests::{
+            AdvertiseSwapLiquidityRequest, GetSwapMakersRequest, GetSwapQuoteRequest,
+            RequestPayload, WithdrawSwapLiquidityRequest,
+        };
+        use qc_16_api_gateway::{Address, SwapOffer, U256};
+
+        let adapter = parking_lot::RwLock::new(CrossChainAdapter::with_defaults());
+        let query = |method: &str, payload: RequestPayload| {
+            let params = serde_json::to_value(payload).unwrap();
+            cross_chain_query(&adapter, method, &params, 1_000)
+        };
+        let maker = Address::repeat_byte(7);
+        let makers = || {
+            RequestPayload::GetSwapMakers(GetSwapMakersRequest {
+                source_chain: "QuantumChain".into(),
+                target_chain: "Ethereum".into(),
+            })
+        };
+
+        let advertised = query(
+            "advertise_swap_liquidity",
+            RequestPayload::AdvertiseSwapLiquidity(AdvertiseSwapLiquidityRequest {
+                offer: SwapOffer {
+                    maker,
+                    source_chain: "QuantumChain".into(),
+                    target_chain: "Ethereum".into(),
+                    rate_ppm: 500_000,
+                    fee_bps: 100,
+                    min_amount: U256::from(1_000u64),
+                    max_amount: U256::from(1_000_000u64),
+                    liquidity: U256::from(600u64),
+                    expires_at: 10_000,
+                },
+            }),
+        )
+        .unwrap();
+        assert_eq!(advertised, serde_json::json!(true));
+
+        let offers = query("get_swap_makers", makers()).unwrap();
+        assert_eq!(offers.as_array().unwrap().len(), 1);
+        assert_eq!(offers[0]["maker"], format!("0x{}", hex::encode(maker)));
+        assert_eq!(offers[0]["liquidity"], "0x258");
+
+        let quote = |amount: u64| {
+            query(
+                "get_swap_quote",
+                RequestPayload::GetSwapQuote(GetSwapQuoteRequest {
+                    source_chain: "QuantumChain".into(),
+                    target_chain: "Ethereum".into(),
+                    amount: U256::from(amount),
+                }),
+            )
+        };
+        // 1_000 - 1% fee = 990, at rate 0.5 = 495
+        let quoted = quote(1_000).unwrap();
+        assert_eq!(quoted["targetAmount"], "0x1ef");
+        assert_eq!(quoted["makerFee"], "0xa");
+        assert!(quoted["sourceTimelock"].as_u64() > quoted["targetTimelock"].as_u64());
+        let below = quote(500).unwrap_err();
+        assert!(below.message.contains("outside offer range"));
+        // 2_000 pays out 990, more than the 600 advertised
+        let short = quote(2_000).unwrap_err();
+        assert!(short.message.contains("Insufficient swap liquidity"));
+
+        let withdrawn = query(
+            "withdraw_swap_liquidity",
+            RequestPayload::WithdrawSwapLiquidity(WithdrawSwapLiquidityRequest {
+                maker,
+                source_chain: "QuantumChain".into(),
+                target_chain: "Ethereum".into(),
+            }),
+        )
+        .unwrap();
+        assert_eq!(withdrawn, serde_json::json!(true));
+        assert_eq!(
+            query("get_swap_makers", makers()).unwrap(),
+            serde_json::json!([])
+        );
+    }
+
+    #[test]
+    fn test_resolve_block_height() {
+        let latest = 42;
+        assert_eq!(resolve_block_height(None, latest), 42);
+        assert_eq!(
+            resolve_block_height(Some(&serde_json::json!("latest")), latest),
+            42
+        );
+        assert_eq!(
+            resolve_block_height(Some(&serde_json::json!("earliest")), latest),
+            0
+        );
+        assert_eq!(
+            resolve_block_height(Some(&serde_json::json!("0x10")), latest),
+            16
+        );
+    }
+
+    #[tokio::test]
+    async fn test_event_bus_json() {
+        let bus = InMemoryEventBus::new().with_mirror(Arc::new(shared_bus::EventMirror::new(4)));
+        let _lagging = bus.subscribe_with(
+            EventFilter::all(),
+            shared_bus::SubscriptionOptions::new("lagging"),
+        );
+        for current in 0..3 {
+            bus.publish(BlockchainEvent::SyncProgress {
+                phase: "headers".to_string(),
+                current,
+                target: 3,
+            })
+            .await;
+        }
+
+        let json = event_bus_json(&bus, &[TopicPattern::new("node.*")], 1, 10);
+        assert_eq!(json["cursor"], 3);
+        assert_eq!(
+            json["events"]["node.sync"].as_array().map(Vec::len),
+            Some(2)
+        );
+        assert_eq!(json["published"]["node.sync"], 3);
+        assert_eq!(json["subscribers"][0]["name"], "lagging");
+        assert_eq!(json["dead_letters"]["depth"], 0);
+
+        let json = event_bus_json(&bus, &[TopicPattern::new("block.*")], 0, 10);
+        assert!(json["events"].as_object().is_some_and(|e| e.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_control_subsystem_on_built_container() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = crate::container::NodeConfig::default();
+        config.storage.data_dir = dir.path().to_path_buf();
+        let container = Arc::new(SubsystemContainer::new(
+            config,
+            crate::genesis::ChainSpec::dev(),
+        ));
+        container.registry.read().await.start_all().await.unwrap();
+        let handler = ApiQueryHandler::new(Arc::clone(&container));
+
+        let core = handler
+            .check_subsystem_control(SubsystemId::Consensus, "stop")
+            .await
+            .unwrap();
+        assert_eq!(core["allowed"], false);
+        let dry_run = handler
+            .check_subsystem_control(SubsystemId::Mempool, "stop")
+            .await
+            .unwrap();
+        assert_eq!(dry_run["allowed"], true);
+        assert_eq!(dry_run["status"], "Healthy");
+
+        let stopped = handler
+            .control_subsystem(SubsystemId::Mempool, "stop")
+            .await
+            .unwrap();
+        assert_eq!(stopped["status"], "Stopped");
+        let report = container.registry.read().await.capabilities();
+        assert!(!report.is_available(SubsystemId::Mempool));
+        assert!(report.is_available(SubsystemId::BlockStorage));
+
+        let restarted = handler
+            .control_subsystem(SubsystemId::Mempool, "restart")
+            .await
+            .unwrap();
+        assert_eq!(restarted["status"], "Healthy");
+    }
+
+    #[test]
+    fn test_pooled_transaction_json() {
+        use qc_06_mempool::{MempoolTransaction, TransactionPool};
+        use shared_types::{SignedTransaction, U256};
+
+        let tx = |nonce: u64, gas_price: u64| {
+            MempoolTransaction::new(
+                SignedTransaction {
+                    from: [0xAA; 20],
+                    to: Some([0xBB; 20]),
+                    value: U256::zero(),
+                    nonce,
+                    gas_price: U256::from(gas_price),
+                    gas_limit: 21000,
+                    data: vec![],
+                    signature: [0u8; 64],
+                },
+                1000,
+            )
+        };
+        let mut pool = TransactionPool::with_defaults();
+        let original = tx(0, 1_000_000_000);
+        let original_hash = original.hash;
+        pool.add(original).unwrap();
+        pool.add(tx(3, 1_000_000_000)).unwrap();
+        let bumped = tx(0, 2_000_000_000);
+        let bumped_hash = bumped.hash;
+        pool.add(bumped).unwrap();
+
+        let json = pooled_transaction_json(&pool, pool.get(&bumped_hash).unwrap());
+        assert_eq!(json["nonce"], 0);
+        assert_eq!(json["gas_price"], "0x77359400");
+        assert_eq!(json["state"]["status"], "pending");
+        assert_eq!(json["sender_nonces"], serde_json::json!([0, 3]));
+        assert_eq!(
+            json["nonce_gaps"],
+            serde_json::json!([{ "from": 1, "to": 2 }])
+        );
+        assert_eq!(
+            json["replaced"][0]["hash"],
+            format!("0x{}", hex::encode(original_hash))
+        );
+
+        assert!(parse_hash(&json["hash"].as_str().unwrap()[2..]).is_ok());
+        assert!(parse_hash("0x1234").is_err());
+    }
+
+    #[test]
+    fn test_recent_blocks_json() {
+        use qc_02_block_storage::ports::outbound::{
+            BincodeBlockSerializer, DefaultChecksumProvider, InMemoryKVStore,
+            MockFileSystemAdapter, SystemTimeSource,
+        };
+        use qc_02_block_storage::service::BlockStorageDependencies;
+        use qc_02_block_storage::{BlockStorageApi, BlockStorageService, StorageConfig};
+        use shared_types::{BlockHeader, ValidatedBlock};
+
+        let block = |height: u64, parent_hash: [u8; 32], timestamp: u64| ValidatedBlock {
+            header: BlockHeader {
+                height,
+                parent_hash,
+                timestamp,
+                proposer: [0x11; 32],
+                ..BlockHeader::default()
+            },
+            ..ValidatedBlock::default()
+        };
+        let mut storage = BlockStorageService::new(
+            BlockStorageDependencies {
+                kv_store: InMemoryKVStore::new(),
+                fs_adapter: MockFileSystemAdapter::new(50),
+                checksum: DefaultChecksumProvider,
+                time_source: SystemTimeSource,
+                serializer: BincodeBlockSerializer,
+            },
+            StorageConfig::default(),
+        );
+        let genesis = storage
+            .write_block(block(0, [0; 32], 1), [0; 32], [0; 32])
+            .unwrap();
+        storage
+            .write_block(block(1, genesis, 2), [0; 32], [0; 32])
+            .unwrap();
+        let adopted = storage
+            .write_block(block(1, genesis, 3), [0; 32], [0; 32])
+            .unwrap();
+
+        let json = recent_blocks_json(&storage, &storage.recent_reorgs(), 1);
+        assert_eq!(json["head"], 1);
+        assert_eq!(json["blocks"].as_array().map(Vec::len), Some(1));
+        assert_eq!(
+            json["blocks"][0]["hash"],
+            format!("0x{}", hex::encode(adopted))
+        );
+        assert_eq!(json["blocks"][0]["transactions"], 0);
+        assert_eq!(json["reorgs"][0]["height"], 1);
+        assert_eq!(json["reorgs"][0]["depth"], 1);
+    }
+
+    #[test]
+    fn test_metric_history_json() {
+        let samples = [
+            MetricSample {
+                timestamp_ms: 1000,
+                peers: Some(4),
+                mempool_size: Some(12),
+                ..MetricSample::default()
+            },
+            MetricSample {
+                timestamp_ms: 2000,
+                peers: Some(5),
+                finality_lag: Some(2),
+                ..MetricSample::default()
+            },
+        ];
+
+        let json = metric_history_json(&samples, 10, false);
+        assert_eq!(json["interval_secs"], 10);
+        assert_eq!(json["timestamps"], serde_json::json!([1000, 2000]));
+        assert_eq!(json["series"]["peers"], serde_json::json!([4, 5]));
+        assert_eq!(
+            json["series"]["mempool_size"],
+            serde_json::json!([12, null])
+        );
+        assert_eq!(json["series"]["finality_lag"], serde_json::json!([null, 2]));
+
+        let csv = metric_history_json(&samples, 10, true);
+        assert!(csv.as_str().unwrap().starts_with("timestamp_ms,peers,"));
+    }
+
+    #[test]
+    fn test_live_config_json_redacts_secrets() {
+        let mut config = crate::container::NodeConfig::default();
+        config.api_gateway.api_key = Some("s3cret".into());
+        config.mining.duty_cycle_percent = 60;
+        let json = live_config_json(&LiveSettings {
+            config,
+            log_level: "debug".to_string(),
+        })
+        .unwrap();
+
+        assert_eq!(json["config"]["api_gateway"]["api_key"], "<redacted>");
+        assert_eq!(json["config"]["mining"]["duty_cycle_percent"], 60);
+        assert_eq!(json["log_level"], "debug");
+        assert_eq!(json["reloadable"][2], "mining.duty_cycle_percent");
+    }
 }
@@ -36,7 +36,10 @@ use crate::adapters::TransactionIndexingAdapter;
 use crate::adapters::StateAdapter;
 
 #[cfg(feature = "qc-08")]
-use crate::adapters::{consensus::BlockProducedParams, ConsensusAdapter};
+use crate::adapters::{
+    consensus::{BlockProducedParams, BlockProposedParams},
+    ConsensusAdapter,
+};
 
 #[cfg(feature = "qc-12")]
 use crate::adapters::TransactionOrderingAdapter;
@@ -45,7 +48,8 @@ use crate::adapters::TransactionOrderingAdapter;
 ///
 /// ## V2.3 Choreography (EDA Pattern)
 ///
-/// - Subscribes to: BlockProduced (from Block Production 17), BlockStored (for height tracking)
+/// - Subscribes to: BlockProduced and BlockProposed (from Block Production 17),
+///   BlockStored (for height tracking)
 /// - Publishes: BlockValidated (triggers TxIndexing 3, StateMgmt 4, BlockStorage 2)
 ///
 /// ## Event-Sourced Chain Height
@@ -118,6 +122,19 @@ impl ConsensusHandler {
         );
     }
 
+    /// Handle a BlockProposed event - validate a PoS proposal and publish
+    /// BlockValidated.
+    fn handle_block_proposed(&self, params: &BlockProposedParams) {
+        info!(
+            "[qc-08] 📥 Received BlockProposed #{} (slot: {}, validator #{})",
+            params.block_height, params.slot, params.validator_index
+        );
+
+        if let Err(e) = self.adapter.process_block_proposed(params) {
+            error!("[qc-08] ❌ Proposal validation failed: {}", e);
+        }
+    }
+
     /// Handle a BlockStored event - update chain height (event sourcing).
     fn handle_block_stored(&self, block_hash: [u8; 32], block_height: u64) {
         // Event-source the chain height from BlockStored events
@@ -127,7 +144,9 @@ impl ConsensusHandler {
     /// Run the handler loop.
     pub async fn run(mut self) {
         info!("[qc-08] Consensus handler started (V2.3 Choreography)");
-        info!("[qc-08]   Subscribes to: BlockProduced (from qc-17), BlockStored (for height)");
+        info!(
+            "[qc-08]   Subscribes to: BlockProduced, BlockProposed (from qc-17), BlockStored (for height)"
+        );
         info!("[qc-08]   Publishes: BlockValidated (to qc-02, qc-03, qc-04)");
 
         loop {
@@ -157,6 +176,7 @@ impl ConsensusHandler {
                 nonce,
                 timestamp,
                 parent_hash,
+                coinbase,
                 sender_id,
             } => {
                 if sender_id != SubsystemId::BlockProduction {
@@ -170,9 +190,39 @@ impl ConsensusHandler {
                     nonce,
                     timestamp,
                     parent_hash,
+                    coinbase,
                 };
                 self.handle_block_produced(&params);
             }
+            ChoreographyEvent::BlockProposed {
+                block_hash,
+                block_height,
+                parent_hash,
+                timestamp,
+                beneficiary,
+                gas_used,
+                slot,
+                validator_index,
+                signature,
+                sender_id,
+            } => {
+                if sender_id != SubsystemId::BlockProduction {
+                    warn!("[qc-08] Ignoring BlockProposed from {:?}", sender_id);
+                    return;
+                }
+                let params = BlockProposedParams {
+                    block_hash,
+                    block_height,
+                    parent_hash,
+                    timestamp,
+                    beneficiary,
+                    gas_used,
+                    slot,
+                    validator_index,
+                    signature,
+                };
+                self.handle_block_proposed(&params);
+            }
             ChoreographyEvent::BlockStored {
                 block_hash,
                 block_height,
@@ -538,8 +588,14 @@ impl BlockStorageHandler {
                 block_hash,
                 state_root,
                 sender_id,
+                #[cfg(feature = "zk")]
+                validity_proof,
             } => {
                 if sender_id == SubsystemId::StateManagement {
+                    #[cfg(feature = "zk")]
+                    if let Some(proof) = validity_proof {
+                        self.adapter.attach_validity_proof(block_hash, proof);
+                    }
                     self.handle_state_root(block_hash, state_root).await;
                 } else {
                     warn!("[qc-02] Ignoring StateRootComputed from {:?}", sender_id);
@@ -29,7 +29,13 @@ pub mod adapters;
 pub mod container;
 pub mod genesis;
 pub mod handlers;
+pub mod metric_history;
 pub mod registry;
+pub mod resources;
+#[cfg(all(feature = "qc-02", feature = "qc-04"))]
+pub mod sync;
+#[cfg(all(feature = "qc-08", feature = "qc-17"))]
+pub mod validator;
 pub mod wiring;
 
 // Re-export registry types for easy access
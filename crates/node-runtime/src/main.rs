@@ -15,6 +15,7 @@
 //! - `adapters/` - Port implementations connecting subsystems
 //! - `handlers/` - Event handlers for choreography flow
 //! - `wiring/` - Event routing and subsystem coordination
+//! - `validator/` - Proof-of-stake duties and slashing protection
 //!
 //! ## V2.3 Choreography Flow (IPC-MATRIX.md)
 //!
@@ -65,32 +66,127 @@
 //! 17. Block Production (qc-17) - Quantum-resistant mining
 
 pub mod adapters;
+mod cli;
 pub mod container;
 pub mod genesis;
 pub mod handlers;
+pub mod metric_history;
+#[cfg(all(
+    feature = "qc-01",
+    feature = "qc-02",
+    feature = "qc-04",
+    feature = "qc-05"
+))]
+mod p2p;
+pub mod resources;
+mod shutdown;
+#[cfg(all(feature = "qc-02", feature = "qc-04"))]
+pub mod sync;
+#[cfg(all(feature = "qc-08", feature = "qc-17"))]
+pub mod validator;
 pub mod wiring;
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use primitive_types::U256;
 use tracing::{error, info, warn};
 
 use crate::adapters::{BlockStorageAdapter, RuntimeMempoolGateway};
-use crate::container::{NodeConfig, SubsystemContainer};
-use crate::genesis::{GenesisBuilder, GenesisConfig};
+use crate::container::{dump_config, NodeConfig, SubsystemContainer};
+use crate::genesis::{ChainSpec, GenesisBuilder};
 use crate::handlers::{
     ApiQueryHandler, BlockStorageHandler, FinalityHandler, SignatureVerificationHandler,
     StateMgmtHandler, TxIndexingHandler,
 };
 use crate::wiring::ChoreographyCoordinator;
-use qc_02_block_storage::BlockStorageApi;
+use qc_02_block_storage::{BlockStorageApi, Repairable};
+use qc_08_consensus::DutyScheduler;
 use qc_16_api_gateway::{ApiGatewayService, GatewayConfig};
+use qc_17_block_production::adapters::pos::DelegatedBlockSigner;
 use qc_17_block_production::{
     BlockProducerService, DifficultyWindowCalculator, DifficultyWindowConfig,
 };
-use quantum_telemetry::{init_telemetry, TelemetryConfig};
+use quantum_telemetry::{
+    init_telemetry, Alert, AlertSeverity, AlertState, CrashReporter, TelemetryConfig,
+    EVENT_BUS_DLQ_DEPTH, EVENT_BUS_SUBSCRIBER_DROPPED, EVENT_BUS_SUBSCRIBER_LAG,
+};
+use shared_crypto::{Signer, SoftwareSigner, StoredKey};
+
+/// How often per-subscriber bus metrics are sampled.
+const SUBSCRIBER_METRICS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often the nonce cache is saved, when persistence is enabled.
+const NONCE_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Save the nonce cache if the node is configured to persist it.
+fn save_nonce_cache(container: &SubsystemContainer) {
+    let Some(path) = &container.config.security.nonce_cache_file else {
+        return;
+    };
+    if let Err(e) = container.nonce_cache.save(path) {
+        warn!("Failed to save nonce cache to {}: {}", path.display(), e);
+    }
+}
+
+/// Republish an alert from the telemetry alert manager as a `NodeAlert` event.
+async fn publish_alert(event_bus: &shared_bus::InMemoryEventBus, alert: Alert) {
+    use shared_bus::EventPublisher;
+
+    let severity = match alert.severity {
+        AlertSeverity::Warning => "warning",
+        AlertSeverity::Critical => "critical",
+    };
+    event_bus
+        .publish(shared_bus::BlockchainEvent::NodeAlert {
+            rule: alert.rule,
+            severity: severity.to_string(),
+            firing: alert.state == AlertState::Firing,
+            value: alert.value,
+            threshold: alert.threshold,
+            summary: alert.summary,
+        })
+        .await;
+}
+
+/// Remember an event, and the chain head it implies, for crash reports.
+fn note_for_crash_report(event: &shared_bus::BlockchainEvent) {
+    quantum_telemetry::record_event(event.topic().name(), event.source_subsystem());
+    if let shared_bus::BlockchainEvent::BlockStored {
+        block_height,
+        block_hash,
+    } = event
+    {
+        quantum_telemetry::set_chain_head(*block_height, hex::encode(block_hash));
+    }
+}
+
+/// Publish event bus subscriber lag and loss to Prometheus.
+///
+/// Subscribers sharing a name are summed; gone subscribers drop out.
+fn export_subscriber_metrics(event_bus: &shared_bus::InMemoryEventBus) {
+    let mut totals: std::collections::HashMap<(String, &str), (usize, u64)> =
+        std::collections::HashMap::new();
+    for stats in event_bus.subscriber_stats() {
+        let total = totals.entry((stats.name, stats.policy)).or_default();
+        total.0 += stats.pending;
+        total.1 += stats.dropped;
+    }
+
+    EVENT_BUS_SUBSCRIBER_LAG.reset();
+    EVENT_BUS_SUBSCRIBER_DROPPED.reset();
+    for ((name, policy), (pending, dropped)) in totals {
+        let labels = [name.as_str(), policy];
+        EVENT_BUS_SUBSCRIBER_LAG
+            .with_label_values(&labels)
+            .set(pending as f64);
+        EVENT_BUS_SUBSCRIBER_DROPPED
+            .with_label_values(&labels)
+            .set(dropped as f64);
+    }
+}
 
 /// Helper to describe difficulty for logging
 fn difficulty_desc(difficulty: &U256) -> String {
@@ -99,6 +195,32 @@ fn difficulty_desc(difficulty: &U256) -> String {
     format!("~{} zero bytes", leading_zero_bytes)
 }
 
+/// Decrypt the validator key and wrap it as a signer.
+fn load_validator_signer(
+    config: &NodeConfig,
+) -> Result<(qc_08_consensus::domain::ValidatorId, Arc<dyn Signer>)> {
+    let key = cli::load_validator_key(config)?;
+    let validator_id = validator::validator_id(&key.public_key_bytes());
+    let signer: Arc<dyn Signer> = match key {
+        StoredKey::Ed25519(keypair) => Arc::new(SoftwareSigner::ed25519(keypair)),
+        StoredKey::Secp256k1(keypair) => Arc::new(SoftwareSigner::secp256k1(keypair)),
+    };
+    Ok((validator_id, signer))
+}
+
+/// Unix time of slot 0: the spec's genesis timestamp, or the stored genesis
+/// block's when the spec leaves it open.
+fn genesis_time(container: &SubsystemContainer) -> Result<u64> {
+    if let Some(timestamp) = container.chain_spec.genesis.timestamp {
+        return Ok(timestamp);
+    }
+    let storage = container.block_storage.read();
+    storage
+        .read_block_by_height(0)
+        .map(|stored| stored.block.header.timestamp)
+        .map_err(|e| anyhow::anyhow!("cannot read genesis block: {:?}", e))
+}
+
 /// Load a single block's info for historical tracking.
 /// Uses DifficultyWindowCalculator.resolve_difficulty for zero-difficulty handling.
 fn load_block_info(
@@ -120,14 +242,14 @@ fn load_block_info(
     })
 }
 
-/// Run the BlockProduced event subscription loop (EDA choreography).
+/// Run the BlockProduced/BlockProposed event subscription loop (EDA choreography).
 /// This bridges shared-bus events to the internal EventRouter.
 /// The ConsensusHandler then processes the event and publishes BlockValidated.
 async fn run_block_produced_subscription(
     mut subscription: shared_bus::Subscription,
     router: std::sync::Arc<crate::wiring::EventRouter>,
 ) {
-    info!("[Bridge] 🎧 Listening for BlockProduced events via subscription...");
+    info!("[Bridge] 🎧 Listening for BlockProduced/BlockProposed events via subscription...");
     info!("[Bridge]   → Republishes to internal EventRouter for ConsensusHandler");
 
     while let Some(event) = subscription.recv().await {
@@ -137,50 +259,82 @@ async fn run_block_produced_subscription(
     warn!("[Bridge] Subscription ended - event bus closed");
 }
 
-/// Bridge a BlockProduced event from shared-bus to internal EventRouter.
+/// Bridge a BlockProduced or BlockProposed event from shared-bus to internal EventRouter.
 /// This follows EDA choreography - we don't directly store blocks here.
 /// The ConsensusHandler validates and publishes BlockValidated.
 fn bridge_block_produced_event(
     event: shared_bus::BlockchainEvent,
     router: &std::sync::Arc<crate::wiring::EventRouter>,
 ) {
-    let shared_bus::BlockchainEvent::BlockProduced {
-        block_height,
-        block_hash,
-        difficulty,
-        nonce,
-        timestamp,
-        parent_hash,
-    } = event
-    else {
-        return;
+    let (kind, block_height, internal_event) = match event {
+        shared_bus::BlockchainEvent::BlockProduced {
+            block_height,
+            block_hash,
+            difficulty,
+            nonce,
+            timestamp,
+            parent_hash,
+            coinbase,
+        } => {
+            info!(
+                "[Bridge] 📥 Received BlockProduced #{} via subscription (nonce: {})",
+                block_height, nonce
+            );
+            let internal_event = crate::wiring::ChoreographyEvent::BlockProduced {
+                block_hash,
+                block_height,
+                difficulty,
+                nonce,
+                timestamp,
+                parent_hash,
+                coinbase: Some(coinbase),
+                sender_id: shared_types::SubsystemId::BlockProduction,
+            };
+            ("BlockProduced", block_height, internal_event)
+        }
+        shared_bus::BlockchainEvent::BlockProposed {
+            block_height,
+            block_hash,
+            parent_hash,
+            timestamp,
+            beneficiary,
+            gas_used,
+            slot,
+            validator_index,
+            signature,
+            ..
+        } => {
+            info!(
+                "[Bridge] 📥 Received BlockProposed #{} via subscription (slot: {})",
+                block_height, slot
+            );
+            let internal_event = crate::wiring::ChoreographyEvent::BlockProposed {
+                block_hash,
+                block_height,
+                parent_hash,
+                timestamp,
+                beneficiary,
+                gas_used,
+                slot,
+                validator_index,
+                signature,
+                sender_id: shared_types::SubsystemId::BlockProduction,
+            };
+            ("BlockProposed", block_height, internal_event)
+        }
+        _ => return,
     };
 
-    info!(
-        "[Bridge] 📥 Received BlockProduced #{} via subscription (nonce: {})",
-        block_height, nonce
-    );
-
     // Republish to internal EventRouter for ConsensusHandler to process
-    let internal_event = crate::wiring::ChoreographyEvent::BlockProduced {
-        block_hash,
-        block_height,
-        difficulty,
-        nonce,
-        timestamp,
-        parent_hash,
-        sender_id: shared_types::SubsystemId::BlockProduction,
-    };
-
     if let Err(e) = router.publish(internal_event) {
         error!(
-            "[Bridge] ❌ Failed to republish BlockProduced to internal router: {}",
-            e
+            "[Bridge] ❌ Failed to republish {} to internal router: {}",
+            kind, e
         );
     } else {
         info!(
-            "[Bridge] ✅ Republished BlockProduced #{} to internal EventRouter",
-            block_height
+            "[Bridge] ✅ Republished {} #{} to internal EventRouter",
+            kind, block_height
         );
     }
 }
@@ -197,6 +351,24 @@ pub struct NodeRuntime {
     shutdown_tx: tokio::sync::watch::Sender<bool>,
     /// Shutdown signal receiver.
     shutdown_rx: tokio::sync::watch::Receiver<bool>,
+    /// Intake stop signal sender (API Gateway, block production); fires
+    /// before the event bus is drained.
+    intake_tx: tokio::sync::watch::Sender<bool>,
+    /// Intake stop signal receiver.
+    intake_rx: tokio::sync::watch::Receiver<bool>,
+    /// Event handlers that exit on the shutdown signal, awaited by
+    /// [`Self::shutdown`].
+    handlers: parking_lot::Mutex<Vec<(&'static str, tokio::task::JoinHandle<()>)>>,
+    /// Rebuild damaged storage indexes at startup (`run --repair`).
+    repair: bool,
+    /// Peers to sync the chain from before producing blocks.
+    #[cfg(all(
+        feature = "qc-01",
+        feature = "qc-02",
+        feature = "qc-04",
+        feature = "qc-05"
+    ))]
+    sync_peers: Option<Arc<p2p::SyncPeers>>,
 }
 
 impl NodeRuntime {
@@ -211,17 +383,21 @@ impl NodeRuntime {
     /// 5. Initialize Level 3: Consensus
     /// 6. Initialize Level 4: Block Storage, Finality
     /// 7. Initialize Level 5: API Gateway (external interface)
-    pub fn new(config: NodeConfig) -> Self {
-        info!("Creating Quantum-Chain node runtime");
+    pub fn new(config: NodeConfig, chain_spec: ChainSpec) -> Self {
+        info!(
+            "Creating Quantum-Chain node runtime (chain: {}, id {})",
+            chain_spec.name, chain_spec.chain_id
+        );
 
         // Create subsystem container (initializes all subsystems)
-        let container = Arc::new(SubsystemContainer::new(config));
+        let container = Arc::new(SubsystemContainer::new(config, chain_spec));
 
         // Create choreography coordinator
         let choreography = ChoreographyCoordinator::new();
 
-        // Create shutdown channel
+        // Create shutdown channels
         let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let (intake_tx, intake_rx) = tokio::sync::watch::channel(false);
 
         Self {
             container,
@@ -229,9 +405,26 @@ impl NodeRuntime {
             api_gateway: None,
             shutdown_tx,
             shutdown_rx,
+            intake_tx,
+            intake_rx,
+            handlers: parking_lot::Mutex::new(Vec::new()),
+            repair: false,
+            #[cfg(all(
+                feature = "qc-01",
+                feature = "qc-02",
+                feature = "qc-04",
+                feature = "qc-05"
+            ))]
+            sync_peers: None,
         }
     }
 
+    /// Rebuild damaged storage indexes instead of refusing to start.
+    pub fn with_repair(mut self, repair: bool) -> Self {
+        self.repair = repair;
+        self
+    }
+
     /// Get reference to API Gateway if running.
     ///
     /// Returns None if API Gateway is disabled or not yet started.
@@ -243,19 +436,31 @@ impl NodeRuntime {
     ///
     /// ## Startup Sequence
     ///
-    /// 1. Validate configuration for production
+    /// 1. Check storage integrity (and repair indexes with `--repair`)
     /// 2. Initialize genesis block (if not exists)
     /// 3. Start choreography coordinator
-    /// 4. Start event handlers
+    /// 4. Start event handlers and block propagation
     /// 5. Start API Gateway
-    /// 6. Signal ready
+    /// 6. Start registered subsystems and advertise their capabilities
+    /// 7. Signal ready
+    ///
+    /// Block production and consensus start later, in
+    /// [`Self::sync_and_produce`].
     pub async fn start(&mut self) -> Result<()> {
         info!("===========================================");
         info!("  Quantum-Chain Node Runtime v0.1.0");
         info!("  Architecture: V2.3 Choreography Pattern");
         info!("===========================================");
 
-        // Step 1: Initialize genesis if needed
+        // Keep recent events for crash reports, including genesis
+        self.record_crash_context();
+        // Shrink subsystem caches when they outgrow the memory budget
+        self.start_resource_budget();
+        self.start_metric_history();
+        self.follow_log_level();
+
+        // Step 1: Check storage integrity, then initialize genesis if needed
+        self.check_storage_integrity()?;
         self.initialize_genesis().await?;
 
         // Step 2: Start choreography coordinator
@@ -264,11 +469,32 @@ impl NodeRuntime {
         // Step 3: Start event handlers
         self.start_choreography_handlers().await?;
 
-        // Step 4: Start API Gateway
+        // Step 4: Gossip blocks to peers over QUIC
+        #[cfg(all(
+            feature = "qc-01",
+            feature = "qc-02",
+            feature = "qc-04",
+            feature = "qc-05"
+        ))]
+        {
+            let sync_peers = p2p::start(
+                Arc::clone(&self.container),
+                self.choreography.router(),
+                self.shutdown_rx.clone(),
+            )
+            .await?;
+            self.sync_peers = Some(sync_peers);
+        }
+
+        // Step 5: Start API Gateway
         if self.container.config.api_gateway.enabled {
             self.start_api_gateway().await?;
         }
 
+        // Step 6: Start registered subsystems and advertise their capabilities
+        // (qc-16 fails fast on disabled subsystems)
+        self.advertise_capabilities().await;
+
         info!("All core subsystems initialized and running");
         info!("P2P Port: {}", self.container.config.network.p2p_port);
         info!("RPC Port: {}", self.container.config.api_gateway.http_port);
@@ -295,7 +521,7 @@ impl NodeRuntime {
         gateway_config.admin.api_key = api_config.api_key.clone();
         gateway_config.rate_limit.requests_per_second = api_config.rate_limit_per_second;
         gateway_config.limits.max_batch_size = api_config.max_batch_size;
-        gateway_config.chain.chain_id = api_config.chain_id;
+        gateway_config.chain.chain_id = self.container.chain_spec.chain_id;
 
         // Create IPC sender that connects to event bus
         let ipc_sender = Arc::new(crate::adapters::api_gateway::EventBusIpcSender::new(
@@ -313,11 +539,24 @@ impl NodeRuntime {
         // Get pending store before moving gateway
         let pending_store = gateway.pending_store();
 
+        // Follow `api_gateway.rate_limit_per_second` changes (admin_setConfig)
+        let rate_limit = gateway.rate_limit();
+        self.container.live_config.on_change(move |previous, next| {
+            let rate = next.config.api_gateway.rate_limit_per_second;
+            if rate != previous.config.api_gateway.rate_limit_per_second {
+                rate_limit.update(qc_16_api_gateway::RateLimitConfig {
+                    requests_per_second: rate,
+                    ..rate_limit.config()
+                });
+                info!("[qc-16] Rate limit set to {} requests/s per IP", rate);
+            }
+        });
+
         // Start EventBusIpcReceiver to complete pending requests from ApiQueryResponse events
         let receiver =
             crate::adapters::EventBusIpcReceiver::new(&self.container.event_bus, pending_store);
         let mut receiver_shutdown = self.shutdown_rx.clone();
-        tokio::spawn(async move {
+        self.spawn_handler("EventBusIpcReceiver", async move {
             tokio::select! {
                 _ = receiver.run() => {}
                 _ = receiver_shutdown.changed() => {
@@ -326,8 +565,68 @@ impl NodeRuntime {
             }
         });
 
-        // Spawn gateway in background task
-        let mut shutdown_rx = self.shutdown_rx.clone();
+        // Feed WebSocket subscriptions from the event bus
+        let feed = crate::adapters::EventBusSubscriptionFeed::new(
+            &self.container.event_bus,
+            self.choreography.router().subscribe(),
+            Arc::clone(&self.container),
+            gateway.subscription_manager(),
+        );
+        let mut feed_shutdown = self.shutdown_rx.clone();
+        self.spawn_handler("SubscriptionFeed", async move {
+            tokio::select! {
+                _ = feed.run() => {}
+                _ = feed_shutdown.changed() => {
+                    info!("[SubscriptionFeed] Shutdown signal received");
+                }
+            }
+        });
+
+        // Tail node logs over the WebSocket port (`/logs`)
+        let log_feed = gateway.log_feed();
+        let log_tail = quantum_telemetry::log_tail();
+        let mut log_lines = log_tail.subscribe();
+        for record in log_tail.recent(quantum_telemetry::RECENT_LOGS) {
+            log_feed.publish(crate::adapters::node_log_line(record));
+        }
+        let mut logs_shutdown = self.shutdown_rx.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    record = log_lines.recv() => match record {
+                        Ok(record) => log_feed.publish(crate::adapters::node_log_line(record)),
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = logs_shutdown.changed() => break,
+                }
+            }
+        });
+
+        // Keep the gateway's view of subsystem capabilities current
+        let capabilities = gateway.capabilities();
+        let mut registry_events = self.container.event_bus.subscribe(
+            shared_bus::EventFilter::topics(vec![shared_bus::EventTopic::Registry]),
+        );
+        let mut capabilities_shutdown = self.shutdown_rx.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    event = registry_events.recv() => match event {
+                        Some(shared_bus::BlockchainEvent::CapabilitiesAdvertised(report)) => {
+                            capabilities.update(report);
+                        }
+                        Some(_) => {}
+                        None => break,
+                    },
+                    _ = capabilities_shutdown.changed() => break,
+                }
+            }
+        });
+
+        // Spawn gateway in background task; it stops with intake, before
+        // the bus drains
+        let mut intake_rx = self.intake_rx.clone();
         tokio::spawn(async move {
             tokio::select! {
                 result = gateway.start() => {
@@ -335,7 +634,7 @@ impl NodeRuntime {
                         error!("API Gateway error: {}", e);
                     }
                 }
-                _ = shutdown_rx.changed() => {
+                _ = intake_rx.changed() => {
                     info!("[qc-16] Shutdown signal received");
                     gateway.shutdown();
                 }
@@ -350,6 +649,170 @@ impl NodeRuntime {
         Ok(())
     }
 
+    /// Start the registered subsystems and publish the registry's
+    /// capability report on the event bus.
+    async fn advertise_capabilities(&self) {
+        use shared_bus::EventPublisher;
+
+        let registry = self.container.registry.read().await;
+        if let Err(e) = registry.start_all().await {
+            error!("Subsystem registry failed to start: {}", e);
+        }
+        let report = registry.capabilities();
+        drop(registry);
+        if report.subsystems.is_empty() {
+            // An empty report would mark every subsystem as disabled
+            warn!("No subsystems registered, skipping capability advertisement");
+            return;
+        }
+
+        info!(
+            "Advertising capabilities of {} subsystems",
+            report.subsystems.len()
+        );
+        self.container
+            .event_bus
+            .publish(shared_bus::BlockchainEvent::CapabilitiesAdvertised(report))
+            .await;
+    }
+
+    /// Spawn an event handler that exits on the shutdown signal; `shutdown`
+    /// waits for it and reports how it finished.
+    fn spawn_handler<F>(&self, name: &'static str, task: F)
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        self.handlers.lock().push((name, tokio::spawn(task)));
+    }
+
+    /// Feed recent events and the chain head into crash reports until shutdown.
+    fn record_crash_context(&self) {
+        let mut events = self.container.event_bus.subscribe_with(
+            shared_bus::EventFilter::all(),
+            shared_bus::SubscriptionOptions::new("crash-context"),
+        );
+        let mut shutdown = self.shutdown_rx.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    event = events.recv() => match event {
+                        Some(event) => note_for_crash_report(&event),
+                        None => break,
+                    },
+                    _ = shutdown.changed() => break,
+                }
+            }
+        });
+    }
+
+    /// Check the memory budget (`[resources]`) periodically until shutdown.
+    fn start_resource_budget(&self) {
+        let interval = Duration::from_secs(self.container.config.resources.check_interval_secs);
+        tokio::spawn(resources::run(
+            Arc::clone(&self.container.resources),
+            interval,
+            self.shutdown_rx.clone(),
+        ));
+    }
+
+    /// Sample the headline metrics (`[metrics]`) periodically until shutdown.
+    fn start_metric_history(&self) {
+        if self.container.metric_history.capacity() == 0 {
+            return;
+        }
+        let interval = Duration::from_secs(self.container.config.metrics.sample_interval_secs);
+        tokio::spawn(metric_history::run(
+            Arc::clone(&self.container),
+            Arc::clone(&self.container.metric_history),
+            interval,
+            self.shutdown_rx.clone(),
+        ));
+    }
+
+    /// Apply `log_level` changes (admin_setConfig) to the telemetry filter.
+    fn follow_log_level(&self) {
+        self.container.live_config.on_change(|previous, next| {
+            if next.log_level != previous.log_level {
+                match quantum_telemetry::set_log_level(&next.log_level) {
+                    Ok(()) => info!("Log level set to {}", next.log_level),
+                    Err(e) => warn!("Log level not changed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// Republish in-node alerts on the event bus until shutdown.
+    fn forward_alerts(&self, mut alerts: tokio::sync::broadcast::Receiver<Alert>) {
+        use tokio::sync::broadcast::error::RecvError;
+
+        let event_bus = Arc::clone(&self.container.event_bus);
+        let mut shutdown = self.shutdown_rx.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    received = alerts.recv() => match received {
+                        Ok(alert) => publish_alert(&event_bus, alert).await,
+                        Err(RecvError::Lagged(missed)) => warn!("Missed {} alerts", missed),
+                        Err(RecvError::Closed) => break,
+                    },
+                    _ = shutdown.changed() => break,
+                }
+            }
+        });
+    }
+
+    /// Cross-check the block and transaction indexes and the chain head
+    /// against the stored blocks.
+    ///
+    /// Damaged indexes stop the node unless `--repair` is given, in which
+    /// case they are rebuilt from the blocks. Damaged blocks cannot be
+    /// rebuilt; they are dropped from the index and reported.
+    fn check_storage_integrity(&self) -> Result<()> {
+        info!("Checking storage integrity...");
+        let mut storage = self.container.block_storage.write();
+        let mut report = storage
+            .check_integrity()
+            .context("Storage integrity check failed")?;
+        if report.is_clean() {
+            info!(
+                "Storage OK ({} blocks, {} indexed transactions)",
+                report.blocks_checked, report.transactions_checked
+            );
+            return Ok(());
+        }
+
+        if !self.repair {
+            let hint = if report.needs_repair() {
+                "restart with `run --repair` to rebuild the indexes"
+            } else {
+                "restore the chain with `import-chain` or `reset`"
+            };
+            anyhow::bail!(
+                "Storage integrity check found {} issues; {}",
+                report.issues.len(),
+                hint
+            );
+        }
+
+        let repaired = storage
+            .repair_index()
+            .context("Failed to repair storage indexes")?;
+        info!(
+            "Reindexed {} blocks and {} transactions in {} ms",
+            repaired.blocks_recovered, repaired.transactions_indexed, repaired.duration_ms
+        );
+        report = storage
+            .check_integrity()
+            .context("Storage integrity check failed")?;
+        if !report.is_clean() {
+            warn!(
+                "{} storage issues remain after repair; restore the chain with `import-chain` or `reset`",
+                report.issues.len()
+            );
+        }
+        Ok(())
+    }
+
     /// Initialize the genesis block if chain is empty.
     async fn initialize_genesis(&self) -> Result<()> {
         info!("Checking for genesis block...");
@@ -369,7 +832,7 @@ impl NodeRuntime {
         info!("No genesis block found, creating...");
 
         // Create genesis block
-        let genesis_config = GenesisConfig::default();
+        let genesis_config = self.container.chain_spec.genesis_config()?;
         let genesis = GenesisBuilder::new(genesis_config)
             .build()
             .context("Failed to build genesis block")?;
@@ -384,28 +847,10 @@ impl NodeRuntime {
         // Note: Genesis bypasses the normal assembly flow
         let mut storage = self.container.block_storage.write();
 
-        // Create a genesis ValidatedBlock using shared-types
-        let genesis_block = shared_types::ValidatedBlock {
-            header: shared_types::BlockHeader {
-                version: 1,
-                height: genesis.header.height,
-                parent_hash: genesis.header.parent_hash,
-                merkle_root: genesis.header.merkle_root,
-                state_root: genesis.header.state_root,
-                timestamp: genesis.header.timestamp,
-                proposer: [0u8; 32], // No proposer for genesis
-                // Genesis uses initial (easy) difficulty - 2^252
-                difficulty: primitive_types::U256::from(2).pow(primitive_types::U256::from(252)),
-                nonce: 0, // Genesis doesn't require mining
-            },
-            transactions: vec![],
-            consensus_proof: shared_types::ConsensusProof::default(),
-        };
-
         // Write block using the proper API
         storage
             .write_block(
-                genesis_block,
+                genesis.to_validated_block(),
                 genesis.header.merkle_root,
                 genesis.header.state_root,
             )
@@ -436,6 +881,38 @@ impl NodeRuntime {
 
     /// Start the choreography event handlers.
     async fn start_choreography_handlers(&self) -> Result<()> {
+        self.start_core_handlers().await?;
+
+        info!("Choreography handlers started");
+        Ok(())
+    }
+
+    /// Catch up with peers, then start block production and consensus.
+    ///
+    /// Producing on a stale head would only fork the chain, so with
+    /// `[sync] enabled` the node first syncs to the best head its peers
+    /// report (see `crate::sync`). A node with no peers produces from its
+    /// own head once `peer_wait_secs` have passed.
+    pub async fn sync_and_produce(&self) -> Result<()> {
+        #[cfg(all(
+            feature = "qc-01",
+            feature = "qc-02",
+            feature = "qc-04",
+            feature = "qc-05"
+        ))]
+        if let Some(peers) = &self.sync_peers {
+            if self.container.config.sync.enabled {
+                let coordinator = sync::SyncCoordinator::new(
+                    Arc::clone(peers),
+                    Arc::clone(&self.container),
+                    Arc::clone(&self.container.event_bus),
+                    self.container.config.sync.clone(),
+                )
+                .with_validation_config(self.container.chain_spec.block_validation_config());
+                coordinator.run().await?;
+            }
+        }
+
         // Calculate chain height once for all consumers
         let chain_height = {
             let storage = self.container.block_storage.read();
@@ -446,11 +923,8 @@ impl NodeRuntime {
              info!("[Main] 💾 Chain height loaded: {}", chain_height);
         }
 
-        self.start_core_handlers().await?;
         self.start_block_production(chain_height).await?;
         self.start_consensus_and_bridge(chain_height).await?;
-
-        info!("Choreography handlers started");
         Ok(())
     }
 
@@ -479,7 +953,7 @@ impl NodeRuntime {
             TxIndexingHandler::new(router.subscribe(), Arc::clone(&tx_indexing_adapter));
         let tx_router = Arc::clone(&router);
         let mut tx_shutdown = self.shutdown_rx.clone();
-        tokio::spawn(async move {
+        self.spawn_handler("qc-03", async move {
             tokio::select! {
                 _ = tx_indexing_handler.run(tx_router) => {}
                 _ = tx_shutdown.changed() => {
@@ -493,7 +967,7 @@ impl NodeRuntime {
             StateMgmtHandler::new(router.subscribe(), Arc::clone(&state_adapter));
         let state_router = Arc::clone(&router);
         let mut state_shutdown = self.shutdown_rx.clone();
-        tokio::spawn(async move {
+        self.spawn_handler("qc-04", async move {
             tokio::select! {
                 _ = state_mgmt_handler.run(state_router) => {}
                 _ = state_shutdown.changed() => {
@@ -506,7 +980,7 @@ impl NodeRuntime {
         let block_storage_handler =
             BlockStorageHandler::new(Arc::clone(&block_storage_adapter), router.subscribe());
         let mut storage_shutdown = self.shutdown_rx.clone();
-        tokio::spawn(async move {
+        self.spawn_handler("qc-02", async move {
             tokio::select! {
                 _ = block_storage_handler.run() => {}
                 _ = storage_shutdown.changed() => {
@@ -519,7 +993,7 @@ impl NodeRuntime {
         let finality_handler = FinalityHandler::new(router.subscribe());
         let finality_router = Arc::clone(&router);
         let mut finality_shutdown = self.shutdown_rx.clone();
-        tokio::spawn(async move {
+        self.spawn_handler("qc-09", async move {
             tokio::select! {
                 _ = finality_handler.run(finality_router) => {}
                 _ = finality_shutdown.changed() => {
@@ -540,7 +1014,7 @@ impl NodeRuntime {
                 Arc::clone(&tx_ordering_adapter),
             );
             let mut tx_ordering_shutdown = self.shutdown_rx.clone();
-            tokio::spawn(async move {
+            self.spawn_handler("qc-12", async move {
                 tokio::select! {
                     _ = tx_ordering_handler.run() => {}
                     _ = tx_ordering_shutdown.changed() => {
@@ -561,7 +1035,7 @@ impl NodeRuntime {
             &container.config,
         );
         let mut sv_shutdown = self.shutdown_rx.clone();
-        tokio::spawn(async move {
+        self.spawn_handler("SignatureVerificationHandler", async move {
             tokio::select! {
                 _ = sv_handler.run() => {}
                 _ = sv_shutdown.changed() => {
@@ -573,7 +1047,7 @@ impl NodeRuntime {
         // Start API Query handler (bridges qc-16 to subsystems)
         let api_query_handler = ApiQueryHandler::new(Arc::clone(&container));
         let mut api_shutdown = self.shutdown_rx.clone();
-        tokio::spawn(async move {
+        self.spawn_handler("ApiQueryHandler", async move {
             tokio::select! {
                 _ = api_query_handler.run() => {}
                 _ = api_shutdown.changed() => {
@@ -582,28 +1056,87 @@ impl NodeRuntime {
             }
         });
 
+        // Export the dead letter queue depth as it changes
+        let mut dlq_depth = container.event_bus.dead_letters().watch_depth();
+        let mut dlq_shutdown = self.shutdown_rx.clone();
+        tokio::spawn(async move {
+            loop {
+                EVENT_BUS_DLQ_DEPTH.set(*dlq_depth.borrow_and_update() as f64);
+                tokio::select! {
+                    changed = dlq_depth.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                    _ = dlq_shutdown.changed() => break,
+                }
+            }
+        });
+
+        // Export per-subscriber lag and loss
+        let event_bus = Arc::clone(&container.event_bus);
+        let mut lag_shutdown = self.shutdown_rx.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(SUBSCRIBER_METRICS_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = tick.tick() => export_subscriber_metrics(&event_bus),
+                    _ = lag_shutdown.changed() => break,
+                }
+            }
+        });
+
+        // Snapshot the nonce cache, so a restart doesn't open a replay window
+        if container.config.security.nonce_cache_file.is_some() {
+            let snapshot_container = Arc::clone(&container);
+            let mut snapshot_shutdown = self.shutdown_rx.clone();
+            tokio::spawn(async move {
+                let mut tick = tokio::time::interval(NONCE_SNAPSHOT_INTERVAL);
+                loop {
+                    tokio::select! {
+                        _ = tick.tick() => save_nonce_cache(&snapshot_container),
+                        _ = snapshot_shutdown.changed() => break,
+                    }
+                }
+            });
+        }
+
         Ok(())
     }
 
-    /// Start the block production miner (qc-17).
+    /// Start the block production miner (qc-17), or the validator when
+    /// `validator.enabled` is set.
     async fn start_block_production(&self, chain_height: u64) -> Result<()> {
+        if self.container.config.validator.enabled {
+            return self.start_validator(chain_height).await;
+        }
         let container = Arc::clone(&self.container);
         info!("Starting Block Production Miner (qc-17)...");
 
         // Create miner configuration (PoW mode by default)
         let miner_config = qc_17_block_production::BlockProductionConfig {
             mode: qc_17_block_production::ConsensusMode::ProofOfWork,
-            gas_limit: container.config.consensus.max_block_gas,
+            gas_limit: container.chain_spec.gas_limit,
             min_gas_price: U256::from(container.config.mempool.min_gas_price),
             fair_ordering: true,
             min_transactions: 1,
+            min_transactions_wait_secs: 0,
+            allow_empty_blocks: true,
+            template_staleness_secs: 0,
+            fee_rebuild_percent: 0,
+            fee_recipient: None,
+            graffiti: Vec::new(),
             pow: Some(qc_17_block_production::PoWConfig {
-                threads: num_cpus::get() as u8,
-                algorithm: qc_17_block_production::HashAlgorithm::Keccak256,
-                target_block_time: Some(10),
-                use_dgw: Some(true),
-                dgw_window: Some(24),
-                batch_size: Some(10_000_000),
+                throttle: qc_17_block_production::ThrottleConfig {
+                    duty_cycle_percent: container
+                        .live_config
+                        .settings()
+                        .config
+                        .mining
+                        .duty_cycle_percent,
+                    ..Default::default()
+                },
+                ..container.chain_spec.pow_config(num_cpus::get() as u8)
             }),
             pos: None,
             pbft: None,
@@ -616,13 +1149,23 @@ impl NodeRuntime {
             miner_config,
         ));
 
+        // Follow `mining.duty_cycle_percent` changes (admin_setConfig)
+        let throttled = Arc::clone(&miner_service);
+        container.live_config.on_change(move |previous, next| {
+            let percent = next.config.mining.duty_cycle_percent;
+            if percent != previous.config.mining.duty_cycle_percent {
+                if let Err(e) = throttled.set_duty_cycle_percent(percent) {
+                    warn!("[qc-17] Duty cycle not changed: {}", e);
+                }
+            }
+        });
+
         // Load recent block history for difficulty adjustment
         let recent_blocks: Vec<qc_17_block_production::HistoricalBlockInfo> = {
             let storage = container.block_storage.read();
             let diff_calc = DifficultyWindowCalculator::new(DifficultyWindowConfig::default());
             let window_size = diff_calc.calculate_window_size(chain_height);
-            let mut last_known_difficulty =
-                primitive_types::U256::from(2).pow(primitive_types::U256::from(252));
+            let mut last_known_difficulty = container.chain_spec.initial_target();
 
             let start_height = diff_calc.calculate_start_height(chain_height);
             let mut blocks: Vec<_> = (start_height..=chain_height)
@@ -648,9 +1191,7 @@ impl NodeRuntime {
         let last_known_difficulty = recent_blocks
             .first()
             .map(|b| b.difficulty)
-            .unwrap_or_else(|| {
-                primitive_types::U256::from(2).pow(primitive_types::U256::from(252))
-            });
+            .unwrap_or_else(|| container.chain_spec.initial_target());
 
         // Start production in PoW mode
         let miner_clone = Arc::clone(&miner_service);
@@ -675,7 +1216,7 @@ impl NodeRuntime {
 
         // Monitor shutdown signal
         let miner_shutdown_clone = Arc::clone(&miner_service);
-        let mut miner_shutdown = self.shutdown_rx.clone();
+        let mut miner_shutdown = self.intake_rx.clone();
         tokio::spawn(async move {
             let _ = miner_shutdown.changed().await;
             info!("[qc-17] Shutdown signal received");
@@ -688,15 +1229,130 @@ impl NodeRuntime {
         Ok(())
     }
 
+    /// Start proposing as a proof-of-stake validator.
+    ///
+    /// Block Production (qc-17) runs in PoS mode with the keystore key and
+    /// the slashing-protection record; the duty task tells it when to
+    /// propose, and the attester keeps it on the stored head and fills its
+    /// attestation pool.
+    async fn start_validator(&self, chain_height: u64) -> Result<()> {
+        let container = Arc::clone(&self.container);
+        let settings = &container.config.validator;
+        info!("Starting validator '{}'...", settings.key_name);
+
+        let (validator_id, signer) = load_validator_signer(&container.config)?;
+        let validators = validator::genesis_validator_set(&container.chain_spec)?;
+        if !validators.contains(&validator_id) {
+            bail!(
+                "validator key '{}' is not a genesis validator of chain {}",
+                settings.key_name,
+                container.chain_spec.name
+            );
+        }
+
+        // Opened before anything can be signed; a corrupt record stops startup
+        let protection_path = settings.slashing_protection_path(&container.config.storage.data_dir);
+        let protection = Arc::new(validator::SlashingProtectionDb::open(&protection_path)?);
+        if let Some(slot) = protection.last_block_slot() {
+            info!("[Validator] Last signed block: slot {}", slot);
+        }
+
+        let slot_duration = container.config.consensus.block_time_secs;
+        let producer_config = qc_17_block_production::BlockProductionConfig {
+            mode: qc_17_block_production::ConsensusMode::ProofOfStake,
+            gas_limit: container.chain_spec.gas_limit,
+            min_gas_price: U256::from(container.config.mempool.min_gas_price),
+            fee_recipient: settings.fee_recipient(),
+            graffiti: settings.graffiti.as_bytes().to_vec(),
+            pow: None,
+            pos: Some(qc_17_block_production::PoSConfig {
+                validator_key_path: cli::key_path(&container.config, &settings.key_name),
+                slot_duration,
+            }),
+            ..Default::default()
+        };
+        let producer = Arc::new(
+            qc_17_block_production::ConcreteBlockProducer::new(
+                Arc::clone(&container.event_bus),
+                producer_config,
+            )
+            .with_signature_provider(Arc::new(DelegatedBlockSigner::new(Arc::clone(&signer))))
+            .with_slashing_protection(
+                Arc::clone(&protection) as Arc<dyn qc_17_block_production::SlashingProtection>,
+            ),
+        );
+        producer
+            .start_production(
+                qc_17_block_production::ConsensusMode::ProofOfStake,
+                qc_17_block_production::ProductionConfig {
+                    starting_height: chain_height,
+                    ..Default::default()
+                },
+            )
+            .await
+            .context("Failed to start PoS block production")?;
+
+        let mut scheduler = DutyScheduler::new(container.config.consensus.epoch_length);
+        scheduler.register(validator_id);
+        let clock = validator::SlotClock {
+            genesis_time: genesis_time(&container)?,
+            slot_duration,
+        };
+        tokio::spawn(validator::run_duties(
+            scheduler,
+            validators,
+            clock,
+            Arc::clone(&container.event_bus),
+            self.intake_rx.clone(),
+        ));
+        let attester = validator::Attester::new(
+            validator_id,
+            signer,
+            protection,
+            clock,
+            container.config.consensus.epoch_length,
+        );
+        tokio::spawn(attester.run(
+            self.choreography.router().subscribe(),
+            Arc::clone(&container.event_bus),
+            self.intake_rx.clone(),
+        ));
+
+        let producer_shutdown = Arc::clone(&producer);
+        let mut intake_stop = self.intake_rx.clone();
+        tokio::spawn(async move {
+            let _ = intake_stop.changed().await;
+            info!("[qc-17] Shutdown signal received");
+            if let Err(e) = producer_shutdown.stop_production().await {
+                error!("[qc-17] Error during shutdown: {}", e);
+            }
+        });
+
+        info!(
+            "  [17] Validator 0x{} proposing ({}s slots, {} slots per epoch)",
+            hex::encode(&validator_id[..8]),
+            slot_duration,
+            container.config.consensus.epoch_length
+        );
+        Ok(())
+    }
+
     /// Start consensus handler and event bridge.
     async fn start_consensus_and_bridge(&self, chain_height: u64) -> Result<()> {
         let choreography_router = self.choreography.router();
         let container = Arc::clone(&self.container);
 
-        // Start Consensus handler (qc-08)
-        let consensus_adapter = Arc::new(crate::adapters::ConsensusAdapter::new(Arc::clone(
-            &choreography_router,
-        )));
+        // Start Consensus handler (qc-08); PoS proposals are checked against
+        // the genesis validators' proposer schedule and keys
+        let proposers = validator::ProposerSchedule::from_chain_spec(
+            &container.chain_spec,
+            container.config.consensus.epoch_length,
+        )?;
+        let consensus_adapter = Arc::new(
+            crate::adapters::ConsensusAdapter::new(Arc::clone(&choreography_router))
+                .with_validation_config(container.chain_spec.block_validation_config())
+                .with_proposer_schedule(proposers),
+        );
         consensus_adapter.set_initial_chain_height(chain_height);
 
         let consensus_handler = crate::handlers::ConsensusHandler::new(
@@ -704,7 +1360,7 @@ impl NodeRuntime {
             Arc::clone(&consensus_adapter),
         );
         let mut consensus_shutdown = self.shutdown_rx.clone();
-        tokio::spawn(async move {
+        self.spawn_handler("qc-08", async move {
             tokio::select! {
                 _ = consensus_handler.run() => {}
                 _ = consensus_shutdown.changed() => {
@@ -713,13 +1369,17 @@ impl NodeRuntime {
             }
         });
         info!(
-            "  [08] Consensus handler started (validates BlockProduced → publishes BlockValidated)"
+            "  [08] Consensus handler started (validates BlockProduced/BlockProposed → publishes BlockValidated)"
         );
 
-        // Subscribe to BlockProduced events from shared-bus (EDA pattern)
+        // Subscribe to BlockProduced/BlockProposed events from shared-bus (EDA pattern).
+        // Consensus must see every block, so producers wait rather than
+        // overwrite blocks the bridge hasn't forwarded yet.
         let event_bus_for_bridge = Arc::clone(&container.event_bus);
         let filter = shared_bus::EventFilter::topics(vec![shared_bus::EventTopic::BlockProduction]);
-        let subscription = event_bus_for_bridge.subscribe(filter);
+        let options = shared_bus::SubscriptionOptions::new("consensus-bridge")
+            .with_policy(shared_bus::BackpressurePolicy::Block);
+        let subscription = event_bus_for_bridge.subscribe_with(filter, options);
 
         info!("[Bridge] 🎧 Starting choreography subscription (EDA pattern - no polling)...");
 
@@ -736,20 +1396,36 @@ impl NodeRuntime {
     ///
     /// ## Shutdown Sequence
     ///
-    /// 1. Signal shutdown to all handlers
-    /// 2. Drain pending events (with timeout)
-    /// 3. Persist subsystem state
-    /// 4. Exit
+    /// 1. Stop intake (API Gateway, block production)
+    /// 2. Drain pending events (`shutdown.drain_timeout_secs`)
+    /// 3. Signal shutdown to all handlers and wait for them to exit
+    ///    (same deadline; stragglers are aborted)
+    /// 4. Checkpoint subsystem state (see `shutdown`)
+    /// 5. Log the per-subsystem report and exit
     pub async fn shutdown(&self) {
         info!("Initiating graceful shutdown...");
 
-        // Signal all handlers to stop
+        // Stop taking new work; handlers keep running to drain the bus
+        if let Err(e) = self.intake_tx.send(true) {
+            error!("Failed to send intake stop signal: {}", e);
+        }
+
+        let drain_timeout =
+            Duration::from_secs(self.container.config.shutdown.drain_timeout_secs);
+        let mut report = shutdown::ShutdownReport {
+            undrained_events: shutdown::drain_event_bus(&self.container, drain_timeout).await,
+            ..Default::default()
+        };
+
+        // Signal all handlers to stop, then wait for them to exit
         if let Err(e) = self.shutdown_tx.send(true) {
             error!("Failed to send shutdown signal: {}", e);
         }
-
-        // Give handlers time to clean up
-        tokio::time::sleep(Duration::from_secs(2)).await;
+        let handlers = std::mem::take(&mut *self.handlers.lock());
+        shutdown::join_handlers(handlers, drain_timeout, &mut report).await;
+        shutdown::checkpoint(&self.container, &mut report);
+        save_nonce_cache(&self.container);
+        report.log();
 
         info!("Shutdown complete");
     }
@@ -760,100 +1436,143 @@ impl NodeRuntime {
     }
 }
 
-/// Load configuration from environment and files.
-fn load_config() -> NodeConfig {
-    let mut config = NodeConfig::default();
-
-    // Override HMAC secret from environment
-    if let Ok(secret_hex) = std::env::var("QC_HMAC_SECRET") {
-        if let Ok(secret_bytes) = hex::decode(&secret_hex) {
-            if secret_bytes.len() == 32 {
-                config.security.hmac_secret.copy_from_slice(&secret_bytes);
-                info!("Loaded HMAC secret from environment");
-            } else {
-                warn!("QC_HMAC_SECRET must be 32 bytes (64 hex chars)");
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Handle CLI commands (no command runs the node)
+    let matches = cli::command().get_matches();
+    let config_args = cli::ConfigArgs::from_matches(&matches);
+
+    match matches.subcommand() {
+        Some(("health", _)) => {
+            // Health check - just verify we can start
+            println!("healthy");
+            return Ok(());
+        }
+        Some(("calibrate", _)) => {
+            // Re-run the compute benchmarks and store the results
+            let path = cli::load_config(&config_args)?
+                .storage
+                .data_dir
+                .join(qc_compute::calibration::CALIBRATION_FILE);
+            let engines = qc_compute::calibration::available_engines();
+            let calibration = qc_compute::calibration::calibrate(&engines).await?;
+            calibration.save(&path)?;
+            for m in &calibration.measurements {
+                println!(
+                    "{:<32} {:?}: {:.0}/s",
+                    m.device, m.workload, m.units_per_sec
+                );
             }
+            println!("Saved to {}", path.display());
+            return Ok(());
         }
-    }
-
-    // Override ports from environment
-    if let Ok(port) = std::env::var("QC_P2P_PORT") {
-        if let Ok(p) = port.parse() {
-            config.network.p2p_port = p;
+        Some(("init", sub)) => return cli::init(&config_args, sub.get_flag("force")),
+        Some(("export-chain", sub)) => {
+            return cli::export_chain(
+                &cli::load_config(&config_args)?,
+                sub.get_one::<PathBuf>("file").context("file is required")?,
+                sub.get_one::<u64>("height").copied(),
+                !sub.get_flag("no-compress"),
+            );
         }
-    }
-    if let Ok(port) = std::env::var("QC_RPC_PORT") {
-        if let Ok(p) = port.parse() {
-            config.network.rpc_port = p;
+        Some(("import-chain", sub)) => {
+            return cli::import_chain(
+                &cli::load_config(&config_args)?,
+                sub.get_one::<PathBuf>("file").context("file is required")?,
+                sub.get_flag("verify-only"),
+            );
         }
-    }
-
-    config
-}
-
-#[tokio::main]
-async fn main() -> Result<()> {
-    // Handle CLI commands
-    let args: Vec<String> = std::env::args().collect();
-
-    if args.len() > 1 {
-        match args[1].as_str() {
-            "--version" | "-V" => {
-                println!("quantum-chain {}", env!("CARGO_PKG_VERSION"));
-                println!("Architecture: V2.3 Choreography Pattern");
-                println!("Subsystems: 17 (all compiled into single binary)");
-                return Ok(());
-            }
-            "health" => {
-                // Health check - just verify we can start
-                println!("healthy");
-                return Ok(());
-            }
-            "--help" | "-h" => {
-                println!("Quantum-Chain Node Runtime");
-                println!();
-                println!("USAGE:");
-                println!("    quantum-chain [OPTIONS]");
-                println!();
-                println!("OPTIONS:");
-                println!("    --version, -V    Print version information");
-                println!("    --help, -h       Print this help message");
-                println!("    health           Run health check");
-                println!();
-                println!("ENVIRONMENT VARIABLES:");
-                println!("    QC_HMAC_SECRET   32-byte hex-encoded HMAC secret");
-                println!("    QC_P2P_PORT      P2P port (default: 30303)");
-                println!("    QC_RPC_PORT      RPC port (default: 8545)");
-                println!("    QC_DATA_DIR      Data directory path");
-                println!("    QC_LOG_LEVEL     Log level (default: info)");
-                println!("    QC_COMPUTE_BACKEND  Compute backend: auto, cpu, opencl");
-                println!();
-                println!("TELEMETRY (LGTM Stack):");
-                println!("    OTEL_EXPORTER_OTLP_ENDPOINT   Tempo endpoint (default: http://localhost:4317)");
-                println!("    LOKI_ENDPOINT                 Loki endpoint (default: http://localhost:3100)");
+        Some(("reset", sub)) => {
+            return cli::reset(
+                &cli::load_config(&config_args)?,
+                sub.get_flag("keep-keys"),
+                sub.get_flag("yes"),
+            );
+        }
+        Some(("keys", sub)) => {
+            let config = cli::load_config(&config_args)?;
+            return match sub.subcommand() {
+                Some(("generate", keys)) => cli::keys_generate(
+                    &config,
+                    keys.get_one::<String>("name").context("name is required")?,
+                    keys.get_one::<String>("type").context("type is required")?,
+                    keys.get_one::<PathBuf>("password-file").map(PathBuf::as_path),
+                    keys.get_flag("force"),
+                ),
+                Some(("show", keys)) => {
+                    cli::keys_show(&config, keys.get_one::<String>("name").map(String::as_str))
+                }
+                _ => unreachable!("clap requires a keys subcommand"),
+            };
+        }
+        Some(("config", sub)) => {
+            // Print the effective configuration after all overrides
+            let Some(("dump", dump)) = sub.subcommand() else {
+                unreachable!("clap requires a config subcommand");
+            };
+            let config = cli::load_config(&config_args)?;
+            print!("{}", dump_config(&config, dump.get_flag("show-secrets"))?);
+            return Ok(());
+        }
+        Some(("journal", sub)) => {
+            // Dump an event journal, optionally from an offset
+            let path = sub.get_one::<PathBuf>("file").context("file is required")?;
+            let from = sub.get_one::<u64>("from-offset").copied().unwrap_or(0);
+            for entry in shared_bus::JournalReader::open(path, from)? {
+                let entry = entry?;
                 println!(
-                    "    QC_METRICS_PORT               Prometheus metrics port (default: 9100)"
+                    "{:>10} {:>15} {:<24} {:>3} {}",
+                    entry.offset,
+                    entry.timestamp_ms,
+                    entry.topic,
+                    entry.sender,
+                    entry.payload_hash
                 );
-                return Ok(());
             }
-            _ => {}
+            return Ok(());
         }
+        Some(("run", _)) | None => {}
+        Some((other, _)) => unreachable!("unhandled command {}", other),
     }
 
     // Initialize LGTM telemetry (Loki, Grafana, Tempo, Metrics)
     let telemetry_config = TelemetryConfig::from_env();
-    let _telemetry_guard = init_telemetry(telemetry_config)
+    let telemetry_guard = init_telemetry(telemetry_config)
         .await
         .map_err(|e| anyhow::anyhow!("Failed to initialize telemetry: {}", e))?;
 
     // Load configuration
-    let config = load_config();
+    let config = cli::load_config(&config_args)?;
+
+    // Write a crash report (subsystem, recent events, chain head) on panic
+    CrashReporter::new(config.storage.data_dir.join("crash-reports"))
+        .with_version(env!("CARGO_PKG_VERSION"))
+        .with_log(
+            std::env::var("QC_CRASH_LOG")
+                .map(|v| v.to_lowercase() != "false" && v != "0")
+                .unwrap_or(true),
+        )
+        .install();
 
     // Auto-detect compute backend (GPU/CPU)
     info!("===========================================");
     info!("  COMPUTE BACKEND DETECTION");
     info!("===========================================");
 
+    // Replace the fixed backend table with benchmarks from this machine
+    // (stored in the data directory, re-run when the devices change)
+    let calibration_path = config
+        .storage
+        .data_dir
+        .join(qc_compute::calibration::CALIBRATION_FILE);
+    match qc_compute::calibration::load_or_calibrate(&calibration_path).await {
+        Ok(calibration) => qc_compute::calibration::install(calibration),
+        Err(e) => warn!(
+            "⚠️  Compute calibration failed: {}. Using default table.",
+            e
+        ),
+    }
+
     match qc_compute::auto_detect() {
         Ok(engine) => {
             let device = engine.device_info();
@@ -864,7 +1583,7 @@ async fn main() -> Result<()> {
                 info!("   Memory: {} MB", device.memory_bytes / 1024 / 1024);
             }
 
-            // Log subsystem recommendations
+            // Log subsystem recommendations (measured when calibrated)
             info!("   GPU-accelerated subsystems:");
             info!(
                 "     - QC-17 (Mining): {}",
@@ -890,12 +1609,30 @@ async fn main() -> Result<()> {
     // config.validate_for_production();
 
     // Create and start the node runtime
-    let mut runtime = NodeRuntime::new(config);
+    let chain_spec = ChainSpec::load(&config.chain.spec)
+        .with_context(|| format!("Failed to load chain spec {}", config.chain.spec))?;
+    let repair = matches
+        .subcommand_matches("run")
+        .is_some_and(|run| run.get_flag("repair"));
+    let mut runtime = NodeRuntime::new(config, chain_spec).with_repair(repair);
     runtime.start().await?;
 
-    // Keep the node running
+    // Publish in-node alerts (QC_ALERTS / QC_ALERT_WEBHOOK) as NodeAlert events
+    if let Some(alerts) = telemetry_guard.subscribe_alerts() {
+        runtime.forward_alerts(alerts);
+    }
+
+    // Keep the node running; Ctrl+C also interrupts a sync in progress
     info!("Node is running. Press Ctrl+C to stop.");
-    tokio::signal::ctrl_c().await?;
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    tokio::select! {
+        result = runtime.sync_and_produce() => {
+            result?;
+            ctrl_c.await?;
+        }
+        result = &mut ctrl_c => result?,
+    }
 
     // Graceful shutdown
     runtime.shutdown().await;
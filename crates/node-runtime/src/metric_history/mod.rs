@@ -0,0 +1,221 @@
+//! # Metric History
+//!
+//! Ring buffer of periodic samples of the node's headline metrics (the
+//! `[metrics]` config section), so a dashboard can draw trends and a bug
+//! report can carry the last few minutes instead of one reading:
+//!
+//! | Series            | Source                                   |
+//! |-------------------|------------------------------------------|
+//! | `peers`           | Peer Discovery (qc-01) routing table     |
+//! | `mempool_size`    | Mempool (qc-06) pending transactions     |
+//! | `block_time_secs` | Block Storage (qc-02) head - parent time |
+//! | `finality_lag`    | Finality (qc-09) blocks behind the head  |
+//!
+//! A series is empty in samples taken while its subsystem is compiled out.
+
+use crate::container::SubsystemContainer;
+use parking_lot::RwLock;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Column names, in CSV order.
+pub const SERIES: [&str; 4] = ["peers", "mempool_size", "block_time_secs", "finality_lag"];
+
+/// One reading of every series.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MetricSample {
+    /// Unix time of the reading, in milliseconds.
+    pub timestamp_ms: u64,
+    /// Peers in the routing table.
+    pub peers: Option<u64>,
+    /// Transactions waiting in the mempool.
+    pub mempool_size: Option<u64>,
+    /// Seconds between the head block and its parent.
+    pub block_time_secs: Option<u64>,
+    /// Blocks between the head and the last finalized block.
+    pub finality_lag: Option<u64>,
+}
+
+impl MetricSample {
+    /// Values in `SERIES` order.
+    pub fn values(&self) -> [Option<u64>; 4] {
+        [
+            self.peers,
+            self.mempool_size,
+            self.block_time_secs,
+            self.finality_lag,
+        ]
+    }
+}
+
+/// The most recent samples, oldest first.
+pub struct MetricHistory {
+    capacity: usize,
+    samples: RwLock<VecDeque<MetricSample>>,
+}
+
+impl MetricHistory {
+    /// History keeping at most `capacity` samples (0 keeps none).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: RwLock::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Samples kept before the oldest is dropped.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Append a sample, dropping the oldest once full.
+    pub fn record(&self, sample: MetricSample) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut samples = self.samples.write();
+        if samples.len() == self.capacity {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    /// The `limit` most recent samples, oldest first.
+    pub fn latest(&self, limit: usize) -> Vec<MetricSample> {
+        let samples = self.samples.read();
+        let skip = samples.len().saturating_sub(limit);
+        samples.iter().skip(skip).copied().collect()
+    }
+}
+
+/// Samples as CSV with a header row; missing values are empty cells.
+pub fn to_csv(samples: &[MetricSample]) -> String {
+    let mut csv = format!("timestamp_ms,{}\n", SERIES.join(","));
+    for sample in samples {
+        let _ = write!(csv, "{}", sample.timestamp_ms);
+        for value in sample.values() {
+            match value {
+                Some(value) => {
+                    let _ = write!(csv, ",{}", value);
+                }
+                None => csv.push(','),
+            }
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Read every series from the running subsystems.
+pub async fn sample(container: &SubsystemContainer) -> MetricSample {
+    let timestamp_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    #[allow(unused_mut)]
+    let mut sample = MetricSample {
+        timestamp_ms,
+        ..MetricSample::default()
+    };
+
+    #[cfg(feature = "qc-01")]
+    {
+        use qc_01_peer_discovery::PeerDiscoveryApi;
+        sample.peers = Some(container.peer_discovery.read().get_stats().total_peers as u64);
+    }
+    #[cfg(feature = "qc-06")]
+    {
+        let status = container.mempool.read().status(timestamp_ms);
+        sample.mempool_size = Some(status.pending_count as u64);
+    }
+    #[cfg(feature = "qc-02")]
+    {
+        use qc_02_block_storage::BlockStorageApi;
+        let storage = container.block_storage.read();
+        let timestamp = |height| {
+            storage
+                .read_block_by_height(height)
+                .ok()
+                .map(|stored| stored.block.header.timestamp)
+        };
+        sample.block_time_secs = storage
+            .get_latest_height()
+            .ok()
+            .filter(|head| *head > 0)
+            .and_then(|head| Some(timestamp(head)?.saturating_sub(timestamp(head - 1)?)));
+    }
+    #[cfg(feature = "qc-09")]
+    {
+        use qc_09_finality::FinalityApi;
+        sample.finality_lag = Some(container.finality.get_finality_lag().await);
+    }
+
+    sample
+}
+
+/// Sample the container into `history` every `interval` until `stop` fires.
+pub async fn run(
+    container: Arc<SubsystemContainer>,
+    history: Arc<MetricHistory>,
+    interval: Duration,
+    mut stop: tokio::sync::watch::Receiver<bool>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = stop.changed() => break,
+        }
+        history.record(sample(&container).await);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_at(timestamp_ms: u64) -> MetricSample {
+        MetricSample {
+            timestamp_ms,
+            peers: Some(timestamp_ms * 2),
+            ..MetricSample::default()
+        }
+    }
+
+    #[test]
+    fn test_history_drops_oldest_when_full() {
+        let history = MetricHistory::new(3);
+        for t in 1..=5 {
+            history.record(sample_at(t));
+        }
+        let kept: Vec<u64> = history.latest(10).iter().map(|s| s.timestamp_ms).collect();
+        assert_eq!(kept, vec![3, 4, 5]);
+        let newest: Vec<u64> = history.latest(2).iter().map(|s| s.timestamp_ms).collect();
+        assert_eq!(newest, vec![4, 5]);
+
+        let disabled = MetricHistory::new(0);
+        disabled.record(sample_at(1));
+        assert!(disabled.latest(10).is_empty());
+    }
+
+    #[test]
+    fn test_csv_leaves_missing_values_empty() {
+        let csv = to_csv(&[
+            sample_at(1000),
+            MetricSample {
+                timestamp_ms: 2000,
+                mempool_size: Some(7),
+                finality_lag: Some(3),
+                ..MetricSample::default()
+            },
+        ]);
+        assert_eq!(
+            csv,
+            "timestamp_ms,peers,mempool_size,block_time_secs,finality_lag\n\
+             1000,2000,,,\n\
+             2000,,7,,3\n"
+        );
+    }
+}
@@ -0,0 +1,781 @@
+//! # Block Propagation Networking
+//!
+//! Runs qc-05 over a qc-01 QUIC mesh bound on `network.p2p_port`:
+//!
+//! - dial: connects to `network.bootstrap_nodes` and routing table peers,
+//!   introduces this node with `Hello` and prunes peers whose connection
+//!   dropped
+//! - send: writes the messages qc-05 queues to the mesh
+//! - receive: hands peer messages to qc-05's `BlockReceiver` and answers
+//!   `GetBlock` from block storage
+//! - gossip: propagates blocks on `BlockStored` (choreography) and on
+//!   `PropagateBlockRequest` (event bus)
+//! - sync: tracks the heads peers report, matches `crate::sync` requests to
+//!   their responses, and serves headers and state snapshots to syncing
+//!   peers
+//!
+//! Compact block relay is off: the mempool cannot resolve short IDs yet, so
+//! peers are sent full blocks.
+
+use std::collections::HashMap;
+use std::net::{Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use parking_lot::{Mutex, RwLock};
+use qc_01_peer_discovery::transport::{QuicConfig, QuicMesh, QuicTransport};
+use qc_02_block_storage::BlockStorageApi;
+use qc_05_block_propagation::ports::outbound::PeerInfo;
+use qc_05_block_propagation::service::BlockPropagationDependencies;
+use qc_05_block_propagation::{
+    BlockPropagationApi, BlockPropagationService, BlockReceiver, PeerId, PropagationConfig,
+    PropagationError,
+};
+use shared_types::{BlockHeader, Hash, ValidatedBlock};
+use tokio::sync::{broadcast, mpsc, oneshot, watch};
+use tracing::{debug, info, warn};
+
+use crate::adapters::ports::{
+    BlockPropConsensusAdapter, BlockPropMempoolAdapter, BlockPropNetworkAdapter,
+    BlockPropSignatureAdapter, OutboundMessage, PeerEnvelope, WireMessage,
+};
+use crate::container::SubsystemContainer;
+use crate::sync::{
+    ChainWork, PeerHead, StateChunk, SyncChain, SyncError, SyncNetwork, HEADERS_PER_REQUEST,
+};
+use crate::wiring::{ChoreographyEvent, EventRouter};
+
+type Propagation = BlockPropagationService<
+    BlockPropNetworkAdapter,
+    BlockPropConsensusAdapter,
+    BlockPropMempoolAdapter,
+    BlockPropSignatureAdapter,
+>;
+
+/// Messages qc-05 may queue before sends start failing.
+const OUTBOUND_QUEUE: usize = 1024;
+
+/// Messages received from peers awaiting qc-05.
+const INBOUND_QUEUE: usize = 1024;
+
+/// How often to dial known peers and refresh qc-05's peer list.
+const DIAL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// TLS server name; peers are identified by node ID, not certificate.
+const SERVER_NAME: &str = "localhost";
+
+/// Serialized state sent per `StateChunk`, well under the mesh's message
+/// size limit.
+const STATE_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Bind the P2P port and start the propagation tasks.
+///
+/// Returns the peers chain sync runs against.
+pub async fn start(
+    container: Arc<SubsystemContainer>,
+    router: Arc<EventRouter>,
+    shutdown: watch::Receiver<bool>,
+) -> Result<Arc<SyncPeers>> {
+    let local_id = container
+        .peer_discovery
+        .read()
+        .routing_table()
+        .local_node_id()
+        .0;
+    // Dual-stack; falls back to IPv4 on hosts without IPv6
+    let bind_addr = SocketAddr::from((Ipv6Addr::UNSPECIFIED, container.config.network.p2p_port));
+    let mut transport = QuicTransport::new(QuicConfig {
+        bind_addr,
+        ..QuicConfig::default()
+    });
+    let local_addr = transport
+        .bind()
+        .await
+        .with_context(|| format!("failed to bind P2P port {}", bind_addr))?;
+    let (mesh, inbound) = transport.into_mesh(INBOUND_QUEUE)?;
+
+    let (outbound_tx, outbound_rx) = mpsc::channel(OUTBOUND_QUEUE);
+    let network = Arc::new(BlockPropNetworkAdapter::new(local_id, outbound_tx));
+    let config = PropagationConfig {
+        fanout: container.config.network.gossip_fanout,
+        enable_compact_blocks: false,
+        ..PropagationConfig::default()
+    };
+    let service = Arc::new(BlockPropagationService::new(
+        config,
+        BlockPropagationDependencies {
+            network: Arc::clone(&network),
+            consensus: Arc::new(BlockPropConsensusAdapter::new(Arc::clone(
+                &container.event_bus,
+            ))),
+            mempool: Arc::new(BlockPropMempoolAdapter::new(Arc::clone(&container.mempool))),
+            sig_verifier: Arc::new(BlockPropSignatureAdapter::new()),
+        },
+    ));
+
+    let sync = Arc::new(SyncPeers::new(
+        Arc::clone(&network),
+        Arc::clone(&container),
+        Duration::from_secs(container.config.sync.request_timeout_secs),
+    ));
+
+    let dialer = Dialer {
+        mesh: mesh.clone(),
+        network: Arc::clone(&network),
+        service: Arc::clone(&service),
+        container: Arc::clone(&container),
+        sync: Arc::clone(&sync),
+        local_addr,
+    };
+    spawn_until(shutdown.clone(), dialer.run());
+    spawn_until(shutdown.clone(), run_sender(mesh.clone(), outbound_rx));
+
+    let receiver = Receiver {
+        network,
+        service: Arc::clone(&service),
+        container: Arc::clone(&container),
+        sync: Arc::clone(&sync),
+    };
+    spawn_until(shutdown.clone(), receiver.run(inbound));
+    spawn_until(
+        shutdown.clone(),
+        run_stored_gossip(
+            Arc::clone(&service),
+            Arc::clone(&container),
+            router.subscribe(),
+        ),
+    );
+
+    let requests = container.event_bus.subscribe_with(
+        shared_bus::EventFilter::topics(vec![shared_bus::EventTopic::Consensus]),
+        shared_bus::SubscriptionOptions::new("block-propagation"),
+    );
+    spawn_until(shutdown.clone(), run_requested_gossip(service, requests));
+
+    let mut closer = shutdown;
+    tokio::spawn(async move {
+        let _ = closer.changed().await;
+        info!("[qc-05] Shutdown signal received");
+        mesh.close();
+    });
+
+    info!(
+        "  [05] Block Propagation listening on {} (node {})",
+        local_addr,
+        hex::encode(&local_id[..8])
+    );
+    Ok(sync)
+}
+
+/// Run `task` until shutdown is signalled.
+fn spawn_until<F>(mut shutdown: watch::Receiver<bool>, task: F)
+where
+    F: std::future::Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = task => {}
+            _ = shutdown.changed() => {}
+        }
+    });
+}
+
+struct Dialer {
+    mesh: QuicMesh,
+    network: Arc<BlockPropNetworkAdapter>,
+    service: Arc<Propagation>,
+    container: Arc<SubsystemContainer>,
+    sync: Arc<SyncPeers>,
+    local_addr: SocketAddr,
+}
+
+impl Dialer {
+    async fn run(self) {
+        let bootstrap: Vec<SocketAddr> = self
+            .container
+            .config
+            .network
+            .bootstrap_nodes
+            .iter()
+            .filter_map(|node| match node.parse() {
+                Ok(addr) => Some(addr),
+                Err(_) => {
+                    warn!("[qc-05] Ignoring bootstrap node {:?}: not ip:port", node);
+                    None
+                }
+            })
+            .collect();
+
+        let mut tick = tokio::time::interval(DIAL_INTERVAL);
+        loop {
+            tick.tick().await;
+            for addr in bootstrap.iter().copied().chain(self.routing_table_peers()) {
+                self.dial(addr).await;
+            }
+            self.network.retain_connected(&self.mesh.connected());
+            self.service.refresh_peers();
+        }
+    }
+
+    /// Connect to `addr` if not already connected, say hello and report
+    /// our head.
+    async fn dial(&self, addr: SocketAddr) {
+        if addr == self.local_addr || self.mesh.is_connected(&addr) {
+            return;
+        }
+        match self.mesh.connect(addr, SERVER_NAME).await {
+            Ok(()) => {
+                info!("[qc-05] Connected to {}", addr);
+                let _ = self.network.send_to_addr(addr, WireMessage::Hello);
+                let _ = self.sync.send_status(addr);
+            }
+            Err(e) => debug!("[qc-05] Dial {} failed: {}", addr, e),
+        }
+    }
+
+    fn routing_table_peers(&self) -> Vec<SocketAddr> {
+        use qc_01_peer_discovery::IpAddr;
+
+        self.container
+            .peer_discovery
+            .read()
+            .routing_table()
+            .peers()
+            .into_iter()
+            .map(|peer| {
+                let ip = match peer.socket_addr.ip {
+                    IpAddr::V4(bytes) => std::net::IpAddr::from(bytes),
+                    IpAddr::V6(bytes) => std::net::IpAddr::from(bytes),
+                };
+                SocketAddr::new(ip, peer.socket_addr.port)
+            })
+            .collect()
+    }
+}
+
+/// Write queued messages to the mesh, one task per message so a slow peer
+/// does not hold up the rest.
+async fn run_sender(mesh: QuicMesh, mut outbound: mpsc::Receiver<OutboundMessage>) {
+    while let Some((addr, data)) = outbound.recv().await {
+        let mesh = mesh.clone();
+        tokio::spawn(async move {
+            if let Err(e) = mesh.send(addr, &data).await {
+                debug!("[qc-05] Send to {} failed: {}", addr, e);
+            }
+        });
+    }
+}
+
+struct Receiver {
+    network: Arc<BlockPropNetworkAdapter>,
+    service: Arc<Propagation>,
+    container: Arc<SubsystemContainer>,
+    sync: Arc<SyncPeers>,
+}
+
+impl Receiver {
+    async fn run(self, mut inbound: mpsc::Receiver<(SocketAddr, Vec<u8>)>) {
+        while let Some((addr, data)) = inbound.recv().await {
+            let Some(envelope) = PeerEnvelope::decode(&data) else {
+                debug!("[qc-05] Dropping malformed message from {}", addr);
+                continue;
+            };
+            if envelope.sender == self.network.local_id() {
+                continue;
+            }
+            if let Err(e) = self.handle(addr, envelope) {
+                debug!("[qc-05] Message from {} not accepted: {}", addr, e);
+            }
+        }
+    }
+
+    fn handle(&self, addr: SocketAddr, envelope: PeerEnvelope) -> Result<(), PropagationError> {
+        let sender = envelope.sender;
+        let peer_id = PeerId::new(sender);
+        let is_new = !self.network.has_peer(&peer_id);
+        if is_new {
+            self.network.add_peer(
+                PeerInfo {
+                    peer_id,
+                    reputation: 1.0,
+                    latency_ms: 0,
+                    is_connected: true,
+                },
+                addr,
+            );
+            self.service.refresh_peers();
+        }
+
+        // Responses to our sync requests never reach qc-05
+        let message = match self.sync.complete(envelope.message) {
+            Some(message) => message,
+            None => return Ok(()),
+        };
+
+        match message {
+            // Answer a new peer's hello so it learns our node ID and head too
+            WireMessage::Hello if is_new => {
+                self.network.send_to_addr(addr, WireMessage::Hello)?;
+                self.sync.send_status(addr)
+            }
+            WireMessage::Hello => Ok(()),
+            WireMessage::Status {
+                best_hash,
+                best_height,
+                total_work,
+            } => {
+                self.sync.record_status(PeerHead {
+                    peer: sender,
+                    height: best_height,
+                    hash: best_hash,
+                    total_work,
+                });
+                Ok(())
+            }
+            WireMessage::Announce {
+                block_hash,
+                block_height,
+                parent_hash,
+            } => {
+                self.sync
+                    .record_announcement(sender, block_height, block_hash, parent_hash);
+                self.service
+                    .handle_announcement(sender, block_hash, block_height)
+            }
+            WireMessage::CompactBlock { data } => self.service.handle_compact_block(sender, data),
+            WireMessage::Block {
+                block_data: Some(data),
+                ..
+            } => self.service.handle_full_block(sender, data),
+            WireMessage::Block {
+                block_data: None, ..
+            } => Ok(()),
+            WireMessage::GetBlock {
+                block_hash,
+                request_id,
+            } => {
+                let block_data = read_block(&self.container, &block_hash).map(|(data, _)| data);
+                self.network.send_to_addr(
+                    addr,
+                    WireMessage::Block {
+                        request_id,
+                        block_data,
+                    },
+                )
+            }
+            WireMessage::GetHeaders {
+                request_id,
+                block_hash,
+                max,
+            } => {
+                let headers = self.sync.serve_headers(block_hash, max);
+                self.network.send_to_addr(
+                    addr,
+                    WireMessage::Headers {
+                        request_id,
+                        headers,
+                    },
+                )
+            }
+            WireMessage::GetStateChunk {
+                request_id,
+                block_hash,
+                index,
+            } => {
+                let chunk = self.sync.serve_state_chunk(request_id, block_hash, index);
+                self.network.send_to_addr(addr, chunk)
+            }
+            // Late answers to sync requests that already timed out
+            WireMessage::Headers { .. } | WireMessage::StateChunk { .. } => Ok(()),
+            // Compact block relay is off
+            WireMessage::GetBlockTxn { .. } | WireMessage::BlockTxn { .. } => Ok(()),
+        }
+    }
+}
+
+/// Serialized block and its transaction hashes, if stored.
+fn read_block(container: &SubsystemContainer, block_hash: &Hash) -> Option<(Vec<u8>, Vec<Hash>)> {
+    let storage = container.block_storage.read();
+    let stored = storage.read_block(block_hash).ok()?;
+    let data = bincode::serialize(&stored.block).ok()?;
+    let tx_hashes = storage
+        .get_transaction_hashes_for_block(block_hash)
+        .unwrap_or_default();
+    Some((data, tx_hashes))
+}
+
+fn propagate(service: &Propagation, block_hash: Hash, block_data: Vec<u8>, tx_hashes: Vec<Hash>) {
+    match service.propagate_block(block_hash, block_data, tx_hashes) {
+        Ok(stats) => debug!(
+            "[qc-05] Block {} sent to {} peers",
+            hex::encode(&block_hash[..8]),
+            stats.peers_reached
+        ),
+        Err(PropagationError::DuplicateBlock(_)) => {}
+        Err(e) => warn!(
+            "[qc-05] Failed to propagate block {}: {}",
+            hex::encode(&block_hash[..8]),
+            e
+        ),
+    }
+}
+
+/// Gossip every block as soon as it is stored.
+async fn run_stored_gossip(
+    service: Arc<Propagation>,
+    container: Arc<SubsystemContainer>,
+    mut events: broadcast::Receiver<ChoreographyEvent>,
+) {
+    loop {
+        let block_hash = match events.recv().await {
+            Ok(ChoreographyEvent::BlockStored { block_hash, .. }) => block_hash,
+            Ok(_) => continue,
+            Err(broadcast::error::RecvError::Lagged(n)) => {
+                warn!("[qc-05] Missed {} choreography events", n);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        match read_block(&container, &block_hash) {
+            Some((data, tx_hashes)) => propagate(&service, block_hash, data, tx_hashes),
+            None => warn!(
+                "[qc-05] Stored block {} not readable",
+                hex::encode(&block_hash[..8])
+            ),
+        }
+    }
+}
+
+/// Gossip blocks other subsystems ask to propagate.
+async fn run_requested_gossip(service: Arc<Propagation>, mut requests: shared_bus::Subscription) {
+    while let Some(event) = requests.recv().await {
+        if let shared_bus::BlockchainEvent::PropagateBlockRequest {
+            block_hash,
+            block_data,
+            tx_hashes,
+            ..
+        } = event
+        {
+            propagate(&service, block_hash, block_data, tx_hashes);
+        }
+    }
+}
+
+/// State snapshot being served to a syncing peer.
+struct ServedSnapshot {
+    block_hash: Hash,
+    block_height: u64,
+    data: Arc<Vec<u8>>,
+}
+
+/// Chain sync over the mesh.
+///
+/// Tracks the head and total work each peer last reported (`Status`, moved
+/// along by announcements), matches responses to the requests `crate::sync`
+/// sends, and answers syncing peers. A state snapshot is serialized once and kept until the
+/// local head moves, so its chunks stay consistent.
+pub struct SyncPeers {
+    network: Arc<BlockPropNetworkAdapter>,
+    container: Arc<SubsystemContainer>,
+    timeout: Duration,
+    heads: RwLock<HashMap<[u8; 32], PeerHead>>,
+    /// Total work of our own chain, reported in `Status`.
+    work: ChainWork,
+    pending: Mutex<HashMap<u64, oneshot::Sender<WireMessage>>>,
+    next_request: AtomicU64,
+    served: Mutex<Option<ServedSnapshot>>,
+}
+
+impl SyncPeers {
+    fn new(
+        network: Arc<BlockPropNetworkAdapter>,
+        container: Arc<SubsystemContainer>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            network,
+            container,
+            timeout,
+            heads: RwLock::new(HashMap::new()),
+            work: ChainWork::default(),
+            pending: Mutex::new(HashMap::new()),
+            // qc-05 sends its own requests with ID 0
+            next_request: AtomicU64::new(1),
+            served: Mutex::new(None),
+        }
+    }
+
+    /// Tell `addr` our chain head.
+    fn send_status(&self, addr: SocketAddr) -> Result<(), PropagationError> {
+        let head = self.container.head().and_then(|(best_height, best_hash)| {
+            let total_work = self.work.total(&*self.container)?;
+            Ok((best_height, best_hash, total_work))
+        });
+        match head {
+            Ok((best_height, best_hash, total_work)) => self.network.send_to_addr(
+                addr,
+                WireMessage::Status {
+                    best_hash,
+                    best_height,
+                    total_work,
+                },
+            ),
+            Err(e) => {
+                debug!("[qc-05] No head to report: {}", e);
+                Ok(())
+            }
+        }
+    }
+
+    /// Remember the head a peer reported if it has more work than the last.
+    fn record_status(&self, head: PeerHead) {
+        let mut heads = self.heads.write();
+        let known = heads.entry(head.peer).or_insert(head);
+        if head.total_work > known.total_work {
+            *known = head;
+        }
+    }
+
+    /// Move `peer`'s head to a block it announced on top of it.
+    ///
+    /// Announcements carry no work, so the head keeps the total work of the
+    /// peer's last `Status`; the sync checks the headers carry at least that.
+    fn record_announcement(&self, peer: [u8; 32], height: u64, hash: Hash, parent_hash: Hash) {
+        if let Some(head) = self.heads.write().get_mut(&peer) {
+            if head.hash == parent_hash {
+                head.height = height;
+                head.hash = hash;
+            }
+        }
+    }
+
+    /// Hand a response to the request waiting for it, or give the message
+    /// back if nothing is waiting.
+    fn complete(&self, message: WireMessage) -> Option<WireMessage> {
+        let waiting = message
+            .response_id()
+            .and_then(|id| self.pending.lock().remove(&id));
+        match waiting {
+            Some(sender) => {
+                let _ = sender.send(message);
+                None
+            }
+            None => Some(message),
+        }
+    }
+
+    /// Send the request built by `make` to `peer` and wait for its response.
+    async fn request(
+        &self,
+        peer: [u8; 32],
+        make: impl FnOnce(u64) -> WireMessage,
+    ) -> Result<WireMessage, SyncError> {
+        let addr = self
+            .network
+            .peer_addr(&PeerId::new(peer))
+            .ok_or_else(|| SyncError::Network("peer disconnected".into()))?;
+        let request_id = self.next_request.fetch_add(1, Ordering::Relaxed);
+        let (sender, response) = oneshot::channel();
+        self.pending.lock().insert(request_id, sender);
+
+        if let Err(e) = self.network.send_to_addr(addr, make(request_id)) {
+            self.pending.lock().remove(&request_id);
+            return Err(SyncError::Network(e.to_string()));
+        }
+        match tokio::time::timeout(self.timeout, response).await {
+            Ok(Ok(message)) => Ok(message),
+            Ok(Err(_)) => Err(SyncError::Network("request dropped".into())),
+            Err(_) => {
+                self.pending.lock().remove(&request_id);
+                Err(SyncError::Timeout)
+            }
+        }
+    }
+
+    /// Up to `max` stored headers from `block_hash` back towards genesis.
+    fn serve_headers(&self, block_hash: Hash, max: u32) -> Vec<Vec<u8>> {
+        let storage = self.container.block_storage.read();
+        let mut headers = Vec::new();
+        let mut next = block_hash;
+        while headers.len() < max.min(HEADERS_PER_REQUEST) as usize {
+            let Ok(stored) = storage.read_block(&next) else {
+                break;
+            };
+            let header = &stored.block.header;
+            headers.push(bincode::serialize(header).unwrap_or_default());
+            if header.height == 0 {
+                break;
+            }
+            next = header.parent_hash;
+        }
+        headers
+    }
+
+    /// Chunk `index` of the snapshot taken at `block_hash`, or of a
+    /// snapshot of the current state if `block_hash` is `None`.
+    fn serve_state_chunk(
+        &self,
+        request_id: u64,
+        block_hash: Option<Hash>,
+        index: u32,
+    ) -> WireMessage {
+        let mut served = self.served.lock();
+        if block_hash.is_none() {
+            if let Err(e) = self.refresh_snapshot(&mut served) {
+                warn!("[qc-05] Cannot snapshot state for a syncing peer: {}", e);
+            }
+        }
+        let snapshot = served
+            .as_ref()
+            .filter(|snapshot| block_hash.map_or(true, |hash| hash == snapshot.block_hash));
+        let Some(snapshot) = snapshot else {
+            return WireMessage::StateChunk {
+                request_id,
+                block_hash: block_hash.unwrap_or_default(),
+                block_height: 0,
+                index,
+                total: 0,
+                data: None,
+            };
+        };
+
+        let total = snapshot.data.len().div_ceil(STATE_CHUNK_SIZE).max(1);
+        let start = index as usize * STATE_CHUNK_SIZE;
+        let data = (start < snapshot.data.len() || start == 0).then(|| {
+            let end = (start + STATE_CHUNK_SIZE).min(snapshot.data.len());
+            snapshot.data[start..end].to_vec()
+        });
+        WireMessage::StateChunk {
+            request_id,
+            block_hash: snapshot.block_hash,
+            block_height: snapshot.block_height,
+            index,
+            total: total as u32,
+            data,
+        }
+    }
+
+    /// Serialize the state again unless the head is unchanged since the
+    /// last snapshot.
+    fn refresh_snapshot(&self, served: &mut Option<ServedSnapshot>) -> Result<(), SyncError> {
+        let state = self.container.state_trie.read();
+        let (block_height, block_hash) = self.container.head()?;
+        if served
+            .as_ref()
+            .is_some_and(|snapshot| snapshot.block_hash == block_hash)
+        {
+            return Ok(());
+        }
+        let data = state
+            .serialize()
+            .map_err(|e| SyncError::Chain(e.to_string()))?;
+        info!(
+            "[qc-05] Serving state at block {} ({} KiB) to syncing peers",
+            block_height,
+            data.len() / 1024
+        );
+        *served = Some(ServedSnapshot {
+            block_hash,
+            block_height,
+            data: Arc::new(data),
+        });
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SyncNetwork for SyncPeers {
+    fn best_head(&self) -> Option<PeerHead> {
+        self.heads
+            .read()
+            .values()
+            .filter(|head| self.network.has_peer(&PeerId::new(head.peer)))
+            .max_by_key(|head| (head.total_work, head.height))
+            .copied()
+    }
+
+    async fn headers(
+        &self,
+        peer: [u8; 32],
+        from: Hash,
+        max: u32,
+    ) -> Result<Vec<BlockHeader>, SyncError> {
+        let response = self
+            .request(peer, |request_id| WireMessage::GetHeaders {
+                request_id,
+                block_hash: from,
+                max,
+            })
+            .await?;
+        let WireMessage::Headers { headers, .. } = response else {
+            return Err(SyncError::Network(
+                "unexpected response to GetHeaders".into(),
+            ));
+        };
+        headers
+            .iter()
+            .map(|data| {
+                bincode::deserialize(data)
+                    .map_err(|_| SyncError::Network("malformed header".into()))
+            })
+            .collect()
+    }
+
+    async fn block(&self, peer: [u8; 32], hash: Hash) -> Result<ValidatedBlock, SyncError> {
+        let response = self
+            .request(peer, |request_id| WireMessage::GetBlock {
+                block_hash: hash,
+                request_id,
+            })
+            .await?;
+        let WireMessage::Block {
+            block_data: Some(data),
+            ..
+        } = response
+        else {
+            return Err(SyncError::Network(format!(
+                "peer does not have block {}",
+                hex::encode(&hash[..8])
+            )));
+        };
+        bincode::deserialize(&data).map_err(|_| SyncError::Network("malformed block".into()))
+    }
+
+    async fn state_chunk(
+        &self,
+        peer: [u8; 32],
+        pivot: Option<Hash>,
+        index: u32,
+    ) -> Result<StateChunk, SyncError> {
+        let response = self
+            .request(peer, |request_id| WireMessage::GetStateChunk {
+                request_id,
+                block_hash: pivot,
+                index,
+            })
+            .await?;
+        let WireMessage::StateChunk {
+            block_hash,
+            block_height,
+            index,
+            total,
+            data: Some(data),
+            ..
+        } = response
+        else {
+            return Err(SyncError::Network(
+                "peer no longer serves the state snapshot".into(),
+            ));
+        };
+        Ok(StateChunk {
+            block_hash,
+            block_height,
+            index,
+            total,
+            data,
+        })
+    }
+}
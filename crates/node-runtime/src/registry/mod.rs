@@ -38,7 +38,7 @@
 //! qc-02-block-storage = true
 //! qc-03-transaction-indexing = true
 //! qc-04-state-management = true
-//! qc-05-block-propagation = false  # Disabled - no P2P yet
+//! qc-05-block-propagation = true
 //! qc-06-mempool = true
 //! qc-07-bloom-filters = false      # Optional optimization
 //! qc-08-consensus = true
@@ -254,7 +254,7 @@ impl Default for SubsystemConfig {
 
         // Optional subsystems (disabled by default)
         enabled.insert(SubsystemId::PeerDiscovery, true);
-        enabled.insert(SubsystemId::BlockPropagation, false); // No P2P yet
+        enabled.insert(SubsystemId::BlockPropagation, true);
         enabled.insert(SubsystemId::BloomFilters, false); // Optional optimization
         enabled.insert(SubsystemId::ApiGateway, true);
 
@@ -526,6 +526,7 @@ mod tests {
         assert!(config.is_enabled(SubsystemId::BlockStorage));
         assert!(config.is_enabled(SubsystemId::Consensus));
         assert!(config.is_enabled(SubsystemId::Finality));
+        assert!(config.is_enabled(SubsystemId::BlockPropagation));
 
         // Optional should be disabled
         assert!(!config.is_enabled(SubsystemId::BloomFilters));
@@ -0,0 +1,389 @@
+//! # Resource Budget
+//!
+//! One memory budget for the subsystem caches that would otherwise each be
+//! sized on their own (the `[resources]` config section):
+//!
+//! | Cache               | Subsystem                    | Share |
+//! |---------------------|------------------------------|-------|
+//! | RocksDB block cache | Block Storage (qc-02)        | 30%   |
+//! | Merkle tree cache   | Transaction Indexing (qc-03) | 10%   |
+//! | RocksDB block cache | State Management (qc-04)     | 30%   |
+//! | Transaction pool    | Mempool (qc-06)              | 30%   |
+//!
+//! RocksDB caches are opened with their quota as capacity. The other caches
+//! may grow past their quota while the node as a whole is under budget; once
+//! total usage exceeds it, `enforce` shrinks every cache above its quota
+//! back to it.
+//!
+//! File descriptors are split the same way: peer connections, RPC clients
+//! and log files are reserved first, the rest is shared by the databases.
+
+use crate::container::NodeConfig;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+const MIB: u64 = 1024 * 1024;
+
+/// File descriptors kept for RPC clients, logs, journals and the keystore.
+const BASE_RESERVED_FDS: u64 = 256;
+
+/// Limit assumed when the process limit cannot be read.
+const DEFAULT_FD_LIMIT: u64 = 1024;
+
+/// Fewest open files a database is given.
+const MIN_DATABASE_FDS: u64 = 64;
+
+/// Databases sharing the unreserved file descriptors (Block Storage and
+/// State Management).
+const DATABASES: u64 = 2;
+
+/// A subsystem cache drawing from the memory budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BudgetedCache {
+    /// Block Storage (qc-02) RocksDB block cache.
+    BlockStorage,
+    /// Transaction Indexing (qc-03) Merkle tree cache.
+    TransactionIndex,
+    /// State Management (qc-04) RocksDB block cache.
+    State,
+    /// Mempool (qc-06) transaction pool.
+    Mempool,
+}
+
+impl BudgetedCache {
+    /// All budgeted caches.
+    pub const ALL: [Self; 4] = [
+        Self::BlockStorage,
+        Self::TransactionIndex,
+        Self::State,
+        Self::Mempool,
+    ];
+
+    /// Percent of the memory budget.
+    fn share(self) -> u64 {
+        match self {
+            Self::BlockStorage | Self::State | Self::Mempool => 30,
+            Self::TransactionIndex => 10,
+        }
+    }
+
+    /// Name used in logs.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::BlockStorage => "qc-02 block cache",
+            Self::TransactionIndex => "qc-03 tree cache",
+            Self::State => "qc-04 block cache",
+            Self::Mempool => "qc-06 pool",
+        }
+    }
+}
+
+/// Something that holds memory on behalf of a budgeted cache.
+pub trait MemoryConsumer: Send + Sync {
+    /// Bytes currently held.
+    fn usage_bytes(&self) -> u64;
+
+    /// Release memory until at most `max_bytes` are held. Returns the bytes
+    /// held afterwards.
+    fn shrink_to(&self, max_bytes: u64) -> u64;
+}
+
+/// Usage of one cache against its quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheUsage {
+    /// The cache.
+    pub cache: BudgetedCache,
+    /// Bytes held.
+    pub usage_bytes: u64,
+    /// Bytes allotted.
+    pub quota_bytes: u64,
+}
+
+/// File descriptor accounting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FdBudget {
+    /// Descriptors the node may use.
+    pub limit: u64,
+    /// Descriptors kept for connections and log files.
+    pub reserved: u64,
+}
+
+impl FdBudget {
+    /// Budget for `config`: `resources.max_open_files`, or the process limit.
+    pub fn new(config: &NodeConfig) -> Self {
+        let limit = match config.resources.max_open_files {
+            0 => process_fd_limit().unwrap_or(DEFAULT_FD_LIMIT),
+            limit => limit,
+        };
+        Self {
+            limit,
+            reserved: BASE_RESERVED_FDS + config.network.max_peers as u64,
+        }
+    }
+
+    /// Open files allowed per database.
+    pub fn per_database(&self) -> u64 {
+        (self.limit.saturating_sub(self.reserved) / DATABASES).max(MIN_DATABASE_FDS)
+    }
+
+    /// Whether the limit covers the reservation and every database's minimum.
+    pub fn is_sufficient(&self) -> bool {
+        self.limit >= self.reserved + DATABASES * MIN_DATABASE_FDS
+    }
+}
+
+/// Memory and file descriptor budgets shared by the subsystems.
+pub struct ResourceBudget {
+    memory_bytes: u64,
+    fds: FdBudget,
+    consumers: RwLock<Vec<(BudgetedCache, Arc<dyn MemoryConsumer>)>>,
+}
+
+impl ResourceBudget {
+    /// Budget from the `[resources]` config section.
+    pub fn new(config: &NodeConfig) -> Self {
+        Self {
+            memory_bytes: config.resources.memory_budget_mb * MIB,
+            fds: FdBudget::new(config),
+            consumers: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Total memory budget in bytes.
+    pub fn memory_bytes(&self) -> u64 {
+        self.memory_bytes
+    }
+
+    /// Bytes allotted to `cache`.
+    pub fn memory_quota(&self, cache: BudgetedCache) -> u64 {
+        self.memory_bytes / 100 * cache.share()
+    }
+
+    /// File descriptor budget.
+    pub fn fds(&self) -> FdBudget {
+        self.fds
+    }
+
+    /// Account a cache's memory against its quota.
+    pub fn register(&self, cache: BudgetedCache, consumer: Arc<dyn MemoryConsumer>) {
+        self.consumers.write().push((cache, consumer));
+    }
+
+    /// Current usage of every registered cache.
+    pub fn usage(&self) -> Vec<CacheUsage> {
+        self.consumers
+            .read()
+            .iter()
+            .map(|(cache, consumer)| CacheUsage {
+                cache: *cache,
+                usage_bytes: consumer.usage_bytes(),
+                quota_bytes: self.memory_quota(*cache),
+            })
+            .collect()
+    }
+
+    /// If the caches together exceed the budget, shrink each one above its
+    /// quota. Returns the usage of the caches that were shrunk.
+    pub fn enforce(&self) -> Vec<CacheUsage> {
+        let usage = self.usage();
+        let total: u64 = usage.iter().map(|u| u.usage_bytes).sum();
+        if total <= self.memory_bytes {
+            return Vec::new();
+        }
+
+        let consumers = self.consumers.read();
+        consumers
+            .iter()
+            .zip(usage)
+            .filter(|(_, usage)| usage.usage_bytes > usage.quota_bytes)
+            .map(|((_, consumer), usage)| CacheUsage {
+                usage_bytes: consumer.shrink_to(usage.quota_bytes),
+                ..usage
+            })
+            .collect()
+    }
+
+    /// Log the quotas (once, at startup).
+    pub fn log_summary(&self) {
+        info!(
+            "  Memory budget: {} MiB ({})",
+            self.memory_bytes / MIB,
+            BudgetedCache::ALL
+                .iter()
+                .map(|cache| format!("{} {} MiB", cache.name(), self.memory_quota(*cache) / MIB))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        info!(
+            "  File descriptors: {} ({} reserved, {} per database)",
+            self.fds.limit,
+            self.fds.reserved,
+            self.fds.per_database()
+        );
+        if !self.fds.is_sufficient() {
+            warn!(
+                "  File descriptor limit {} is below the {} needed; raise `ulimit -n` or resources.max_open_files",
+                self.fds.limit,
+                self.fds.reserved + DATABASES * MIN_DATABASE_FDS
+            );
+        }
+    }
+}
+
+/// Check the budget every `interval` until `stop` fires.
+pub async fn run(
+    budget: Arc<ResourceBudget>,
+    interval: Duration,
+    mut stop: tokio::sync::watch::Receiver<bool>,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = stop.changed() => break,
+        }
+        for shrunk in budget.enforce() {
+            warn!(
+                "[Resources] Memory budget exceeded: shrank {} to {} KiB (quota {} KiB)",
+                shrunk.cache.name(),
+                shrunk.usage_bytes / 1024,
+                shrunk.quota_bytes / 1024
+            );
+        }
+    }
+}
+
+/// Soft `RLIMIT_NOFILE` of this process (Linux only).
+fn process_fd_limit() -> Option<u64> {
+    let limits = std::fs::read_to_string("/proc/self/limits").ok()?;
+    let line = limits
+        .lines()
+        .find(|line| line.starts_with("Max open files"))?;
+    line.split_whitespace().nth(3)?.parse().ok()
+}
+
+#[cfg(feature = "qc-03")]
+impl MemoryConsumer for RwLock<qc_03_transaction_indexing::TransactionIndex> {
+    fn usage_bytes(&self) -> u64 {
+        self.read().tree_cache_bytes() as u64
+    }
+
+    fn shrink_to(&self, max_bytes: u64) -> u64 {
+        let mut index = self.write();
+        index.shrink_tree_cache(max_bytes as usize);
+        index.tree_cache_bytes() as u64
+    }
+}
+
+#[cfg(feature = "qc-06")]
+impl MemoryConsumer for RwLock<qc_06_mempool::TransactionPool> {
+    fn usage_bytes(&self) -> u64 {
+        self.read().memory_bytes() as u64
+    }
+
+    fn shrink_to(&self, max_bytes: u64) -> u64 {
+        let mut pool = self.write();
+        let evicted = pool.shrink_to(max_bytes as usize);
+        if !evicted.is_empty() {
+            warn!(
+                "[Resources] Evicted {} lowest-priced transactions from the mempool",
+                evicted.len()
+            );
+        }
+        pool.memory_bytes() as u64
+    }
+}
+
+/// RocksDB block caches are opened at their quota and never grow past it.
+#[cfg(feature = "rocksdb")]
+impl MemoryConsumer for crate::adapters::storage::BlockCacheHandle {
+    fn usage_bytes(&self) -> u64 {
+        self.usage() as u64
+    }
+
+    fn shrink_to(&self, _max_bytes: u64) -> u64 {
+        self.usage_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    struct FakeCache(AtomicU64);
+
+    impl MemoryConsumer for FakeCache {
+        fn usage_bytes(&self) -> u64 {
+            self.0.load(Ordering::SeqCst)
+        }
+
+        fn shrink_to(&self, max_bytes: u64) -> u64 {
+            self.0.fetch_min(max_bytes, Ordering::SeqCst);
+            self.usage_bytes()
+        }
+    }
+
+    fn budget(memory_budget_mb: u64) -> ResourceBudget {
+        let mut config = NodeConfig::default();
+        config.resources.memory_budget_mb = memory_budget_mb;
+        ResourceBudget::new(&config)
+    }
+
+    #[test]
+    fn test_quotas_fit_the_budget() {
+        let budget = budget(1000);
+        let total: u64 = BudgetedCache::ALL
+            .iter()
+            .map(|cache| budget.memory_quota(*cache))
+            .sum();
+        assert!(total <= budget.memory_bytes());
+        assert_eq!(
+            budget.memory_quota(BudgetedCache::TransactionIndex),
+            100 * MIB
+        );
+    }
+
+    #[test]
+    fn test_caches_shrink_only_under_pressure() {
+        let budget = budget(100);
+        let pool = Arc::new(FakeCache(AtomicU64::new(50 * MIB)));
+        let trees = Arc::new(FakeCache(AtomicU64::new(5 * MIB)));
+        budget.register(BudgetedCache::Mempool, pool.clone());
+        budget.register(BudgetedCache::TransactionIndex, trees.clone());
+
+        // The pool is over its 30 MiB quota, but the node is under budget
+        assert!(budget.enforce().is_empty());
+        assert_eq!(pool.usage_bytes(), 50 * MIB);
+
+        trees.0.store(60 * MIB, Ordering::SeqCst);
+        let shrunk = budget.enforce();
+        assert_eq!(shrunk.len(), 2);
+        assert_eq!(
+            pool.usage_bytes(),
+            budget.memory_quota(BudgetedCache::Mempool)
+        );
+        assert_eq!(
+            trees.usage_bytes(),
+            budget.memory_quota(BudgetedCache::TransactionIndex)
+        );
+    }
+
+    #[test]
+    fn test_fd_budget() {
+        let mut config = NodeConfig::default();
+        config.resources.max_open_files = 4096;
+        config.network.max_peers = 50;
+        let fds = FdBudget::new(&config);
+        assert_eq!(fds.reserved, BASE_RESERVED_FDS + 50);
+        assert_eq!(fds.per_database(), (4096 - 306) / 2);
+        assert!(fds.is_sufficient());
+
+        config.resources.max_open_files = 256;
+        let fds = FdBudget::new(&config);
+        assert!(!fds.is_sufficient());
+        assert_eq!(fds.per_database(), MIN_DATABASE_FDS);
+    }
+}
@@ -0,0 +1,219 @@
+//! # Graceful Shutdown
+//!
+//! Once intake has stopped (API Gateway, block production), in-flight events
+//! are drained from the bus, the event handlers are signalled and awaited,
+//! and every stateful subsystem checkpoints under `storage.data_dir`:
+//!
+//! - qc-02: flush the block store
+//! - qc-04: write the state trie back to its database
+//! - qc-06: snapshot the pool to `mempool.dat`
+//! - qc-01: snapshot the routing table to `peers.dat`
+//!
+//! Every step runs even if an earlier one failed; the outcomes end up in a
+//! `ShutdownReport` that is logged before the process exits.
+
+#[cfg(feature = "qc-06")]
+use std::path::Path;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::container::SubsystemContainer;
+
+/// Mempool snapshot, in the `MempoolPersistence` format.
+#[cfg(feature = "qc-06")]
+pub const MEMPOOL_FILE: &str = "mempool.dat";
+
+#[cfg(feature = "qc-01")]
+pub use crate::container::subsystems::PEER_CACHE_FILE;
+
+/// Outcome of one subsystem's shutdown step.
+#[derive(Debug)]
+pub struct SubsystemShutdown {
+    /// Subsystem label, e.g. `qc-06`.
+    pub subsystem: &'static str,
+    /// What was persisted, or why it failed.
+    pub result: Result<String, String>,
+}
+
+/// How one event handler finished after the shutdown signal.
+#[derive(Debug)]
+pub struct HandlerShutdown {
+    /// Handler label, e.g. `qc-03` or `ApiQueryHandler`.
+    pub handler: &'static str,
+    /// Why it did not exit cleanly, if it didn't.
+    pub result: Result<(), String>,
+}
+
+/// What happened during shutdown.
+#[derive(Debug, Default)]
+pub struct ShutdownReport {
+    /// Events still queued when the drain deadline passed.
+    pub undrained_events: usize,
+    /// One entry per event handler, in the order they were started.
+    pub handlers: Vec<HandlerShutdown>,
+    /// One entry per checkpointed subsystem, in shutdown order.
+    pub subsystems: Vec<SubsystemShutdown>,
+}
+
+impl ShutdownReport {
+    fn record(&mut self, subsystem: &'static str, result: Result<String, String>) {
+        self.subsystems
+            .push(SubsystemShutdown { subsystem, result });
+    }
+
+    /// True if the bus drained, every handler exited and every step
+    /// succeeded.
+    pub fn is_clean(&self) -> bool {
+        self.undrained_events == 0
+            && self.handlers.iter().all(|h| h.result.is_ok())
+            && self.subsystems.iter().all(|s| s.result.is_ok())
+    }
+
+    /// Log one line per subsystem, then a summary.
+    pub fn log(&self) {
+        if self.undrained_events > 0 {
+            warn!(
+                "[shutdown] event bus: {} events not handled before the deadline",
+                self.undrained_events
+            );
+        } else {
+            info!("[shutdown] event bus: drained");
+        }
+        for handler in &self.handlers {
+            match &handler.result {
+                Ok(()) => info!("[shutdown] handler {}: stopped", handler.handler),
+                Err(e) => error!("[shutdown] handler {}: {}", handler.handler, e),
+            }
+        }
+        for step in &self.subsystems {
+            match &step.result {
+                Ok(detail) => info!("[shutdown] {}: {}", step.subsystem, detail),
+                Err(e) => error!("[shutdown] {}: FAILED: {}", step.subsystem, e),
+            }
+        }
+        if self.is_clean() {
+            info!("[shutdown] All subsystems checkpointed");
+        } else {
+            warn!("[shutdown] Shutdown finished with errors");
+        }
+    }
+}
+
+/// Wait for subscribers to handle what is already on the bus.
+///
+/// Returns the number of events still pending at the deadline.
+pub async fn drain_event_bus(container: &SubsystemContainer, timeout: Duration) -> usize {
+    let pending = container.event_bus.pending_events();
+    if pending == 0 || timeout.is_zero() {
+        return pending;
+    }
+    info!(
+        "[shutdown] Draining {} events (timeout {}s)",
+        pending,
+        timeout.as_secs()
+    );
+    container.event_bus.drain(timeout).await
+}
+
+/// Wait for every handler to exit, recording each outcome in `report`.
+///
+/// Handlers still running when `timeout` has passed are aborted.
+pub async fn join_handlers(
+    handlers: Vec<(&'static str, JoinHandle<()>)>,
+    timeout: Duration,
+    report: &mut ShutdownReport,
+) {
+    let deadline = tokio::time::Instant::now() + timeout;
+    for (handler, mut handle) in handlers {
+        let result = match tokio::time::timeout_at(deadline, &mut handle).await {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => Err(format!("FAILED: {}", e)),
+            Err(_) => {
+                handle.abort();
+                Err("still running at the deadline, aborted".to_string())
+            }
+        };
+        report.handlers.push(HandlerShutdown { handler, result });
+    }
+}
+
+/// Checkpoint every stateful subsystem, recording each outcome in `report`.
+pub fn checkpoint(container: &SubsystemContainer, report: &mut ShutdownReport) {
+    #[cfg(feature = "qc-02")]
+    report.record("qc-02", flush_block_storage(container));
+    #[cfg(feature = "qc-04")]
+    report.record("qc-04", checkpoint_state(container));
+    #[cfg(feature = "qc-06")]
+    report.record("qc-06", save_mempool(container));
+    #[cfg(feature = "qc-01")]
+    report.record("qc-01", save_routing_table(container));
+}
+
+#[cfg(feature = "qc-02")]
+fn flush_block_storage(container: &SubsystemContainer) -> Result<String, String> {
+    use qc_02_block_storage::BlockStorageApi;
+
+    let mut storage = container.block_storage.write();
+    storage.flush().map_err(|e| e.to_string())?;
+    let height = storage.get_latest_height().unwrap_or(0);
+    Ok(format!("block store flushed at height {}", height))
+}
+
+#[cfg(feature = "qc-04")]
+fn checkpoint_state(container: &SubsystemContainer) -> Result<String, String> {
+    container.checkpoint_state().map_err(|e| e.to_string())?;
+    let root = container.state_trie.read().root_hash();
+    Ok(format!("state trie saved (root 0x{})", hex::encode(root)))
+}
+
+#[cfg(feature = "qc-06")]
+fn save_mempool(container: &SubsystemContainer) -> Result<String, String> {
+    use qc_06_mempool::MempoolPersistence;
+
+    #[cfg(feature = "qc-02")]
+    let height = {
+        use qc_02_block_storage::BlockStorageApi;
+        container
+            .block_storage
+            .read()
+            .get_latest_height()
+            .unwrap_or(0)
+    };
+    #[cfg(not(feature = "qc-02"))]
+    let height = 0;
+
+    let transactions = container.mempool.read().persisted_transactions(height);
+    let data = MempoolPersistence::new().serialize(&transactions, height);
+    let path = container.config.storage.data_dir.join(MEMPOOL_FILE);
+    write_atomic(&path, &data).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(format!(
+        "{} transactions saved to {}",
+        transactions.len(),
+        path.display()
+    ))
+}
+
+#[cfg(feature = "qc-01")]
+fn save_routing_table(container: &SubsystemContainer) -> Result<String, String> {
+    let path = container.config.storage.data_dir.join(PEER_CACHE_FILE);
+    let persistence = qc_01_peer_discovery::FileRoutingTablePersistence::new(&path);
+    let saved = container
+        .peer_discovery
+        .read()
+        .save_routing_table(&persistence, None, None)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(format!("{} peers saved to {}", saved, path.display()))
+}
+
+/// Write through a temporary file so a crash never leaves a torn snapshot.
+#[cfg(feature = "qc-06")]
+fn write_atomic(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, data)?;
+    std::fs::rename(&tmp, path)
+}
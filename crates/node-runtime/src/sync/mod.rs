@@ -0,0 +1,1050 @@
+//! # Chain Sync
+//!
+//! Catches a new or lagging node up with its best peer before it may produce
+//! blocks (the `[sync]` config section). A sync runs four phases against the
+//! peer reporting the most total work:
+//!
+//! 1. Headers: download headers from the peer's head back to the local head
+//!    over the qc-05 wire protocol, check that they link up, follow qc-08's
+//!    header rules and carry the work the peer reported
+//! 2. Snapshot: download the peer's qc-04 state trie in chunks, pinned to the
+//!    block the peer was at when the download began (the pivot)
+//! 3. Healing: fetch the headers the peer added while the snapshot
+//!    downloaded and check the snapshot's root against the pivot header
+//! 4. Backfill: download the blocks up to the pivot, check each one's
+//!    transactions against its header's merkle root, and only once all of
+//!    them check out store them in qc-02 and install the snapshot as the
+//!    node's state
+//!
+//! Backfilled blocks are not re-executed; their state is the snapshot's.
+//! Headers are checked against qc-08's difficulty target and timestamp
+//! rules; the proof-of-work hash itself covers producer fields a header does
+//! not carry, so it cannot be recomputed here.
+//! Peers serve the state trie whole, so healing verifies it against the
+//! pivot header rather than patching individual trie nodes.
+//!
+//! Syncs repeat until the node is within `max_lag_blocks` of the best peer.
+//! Each phase publishes `SyncProgress` events and the coordinator publishes
+//! `SyncCompleted` once done. A peer chain that forks below the local head
+//! stops the coordinator instead of overwriting stored blocks.
+
+use crate::container::config::SyncConfig;
+use crate::container::SubsystemContainer;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use qc_02_block_storage::{header_hash, BlockStorageApi};
+use qc_03_transaction_indexing::MerkleTree;
+use qc_04_state_management::PatriciaMerkleTrie;
+use qc_08_consensus::{BlockValidationConfig, BlockValidator};
+use shared_bus::{BlockchainEvent, EventPublisher, InMemoryEventBus};
+use shared_types::{BlockHeader, Hash, ValidatedBlock, U256};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+/// Headers asked for per request.
+pub const HEADERS_PER_REQUEST: u32 = 512;
+
+/// How often to look for a peer head while waiting for peers.
+const PEER_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// `SyncProgress` events per phase, at most.
+const PROGRESS_EVENTS: u64 = 100;
+
+/// A sync phase, in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncPhase {
+    /// Header chain from the peer's head down to the local head.
+    Headers,
+    /// State trie at the pivot block.
+    Snapshot,
+    /// Headers up to the pivot and the snapshot's root check.
+    Healing,
+    /// Blocks up to the pivot, then the snapshot state.
+    Backfill,
+}
+
+impl SyncPhase {
+    /// Name used in `SyncProgress` events and logs.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Headers => "headers",
+            Self::Snapshot => "snapshot",
+            Self::Healing => "healing",
+            Self::Backfill => "backfill",
+        }
+    }
+}
+
+/// Chain head reported by a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerHead {
+    /// qc-01 node ID of the peer.
+    pub peer: [u8; 32],
+    /// Height of the peer's head.
+    pub height: u64,
+    /// Hash of the peer's head.
+    pub hash: Hash,
+    /// Total work of the peer's chain, as of its last `Status`.
+    pub total_work: U256,
+}
+
+/// One chunk of a peer's serialized state trie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateChunk {
+    /// Block the state was taken at (the pivot).
+    pub block_hash: Hash,
+    /// Height of the pivot.
+    pub block_height: u64,
+    /// Position of this chunk.
+    pub index: u32,
+    /// Chunks in the snapshot.
+    pub total: u32,
+    /// Serialized trie bytes.
+    pub data: Vec<u8>,
+}
+
+/// Why a sync failed.
+#[derive(Debug, Error)]
+pub enum SyncError {
+    /// Sending to or hearing from the peer failed.
+    #[error("peer request failed: {0}")]
+    Network(String),
+
+    /// The peer did not answer in time.
+    #[error("peer did not answer within the request timeout")]
+    Timeout,
+
+    /// The peer sent headers or blocks that do not form its claimed chain.
+    #[error("invalid chain from peer at height {height}: {reason}")]
+    InvalidChain {
+        /// Height of the offending header or block.
+        height: u64,
+        /// What was wrong with it.
+        reason: String,
+    },
+
+    /// The peer's chain does not contain the local head.
+    #[error("peer chain forks from the local chain at or below height {height}")]
+    Fork {
+        /// Local head height.
+        height: u64,
+    },
+
+    /// The state snapshot was malformed.
+    #[error("invalid state snapshot: {0}")]
+    InvalidSnapshot(String),
+
+    /// The snapshot's state root differs from the pivot header's.
+    #[error("snapshot state root does not match block {height}")]
+    StateRootMismatch {
+        /// Pivot height.
+        height: u64,
+    },
+
+    /// Reading or writing the local chain failed.
+    #[error("local chain: {0}")]
+    Chain(String),
+}
+
+impl SyncError {
+    /// True if retrying cannot help.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, Self::Fork { .. } | Self::Chain(_))
+    }
+}
+
+/// Requests to peers (implemented over the QUIC mesh by the node binary).
+#[async_trait]
+pub trait SyncNetwork: Send + Sync {
+    /// Highest head a connected peer has reported, if any.
+    fn best_head(&self) -> Option<PeerHead>;
+
+    /// Up to `max` headers from `peer`, starting at the block `from` and
+    /// walking to parents.
+    async fn headers(
+        &self,
+        peer: [u8; 32],
+        from: Hash,
+        max: u32,
+    ) -> Result<Vec<BlockHeader>, SyncError>;
+
+    /// The block `hash` from `peer`.
+    async fn block(&self, peer: [u8; 32], hash: Hash) -> Result<ValidatedBlock, SyncError>;
+
+    /// Chunk `index` of the state snapshot of `peer` taken at `pivot`, or of
+    /// a new snapshot of its current state if `pivot` is `None`.
+    async fn state_chunk(
+        &self,
+        peer: [u8; 32],
+        pivot: Option<Hash>,
+        index: u32,
+    ) -> Result<StateChunk, SyncError>;
+}
+
+/// The local chain a sync writes to.
+pub trait SyncChain: Send + Sync {
+    /// Height and hash of the local head.
+    fn head(&self) -> Result<(u64, Hash), SyncError>;
+
+    /// Hash of the stored block at `height`.
+    fn hash_at(&self, height: u64) -> Option<Hash>;
+
+    /// Header of the stored block at `height`.
+    fn header_at(&self, height: u64) -> Option<BlockHeader>;
+
+    /// Store a block received from a peer under its header's roots.
+    fn store_block(&self, block: ValidatedBlock) -> Result<(), SyncError>;
+
+    /// Replace the node's state with a downloaded snapshot.
+    fn install_state(&self, state: PatriciaMerkleTrie) -> Result<(), SyncError>;
+}
+
+impl SyncChain for SubsystemContainer {
+    fn head(&self) -> Result<(u64, Hash), SyncError> {
+        let storage = self.block_storage.read();
+        let height = storage.get_latest_height().map_err(chain_error)?;
+        let block = storage.read_block_by_height(height).map_err(chain_error)?;
+        Ok((height, block.block_hash()))
+    }
+
+    fn hash_at(&self, height: u64) -> Option<Hash> {
+        let storage = self.block_storage.read();
+        storage
+            .read_block_by_height(height)
+            .ok()
+            .map(|block| block.block_hash())
+    }
+
+    fn header_at(&self, height: u64) -> Option<BlockHeader> {
+        let storage = self.block_storage.read();
+        storage
+            .read_block_by_height(height)
+            .ok()
+            .map(|block| block.block.header)
+    }
+
+    fn store_block(&self, block: ValidatedBlock) -> Result<(), SyncError> {
+        let merkle_root = block.header.merkle_root;
+        let state_root = block.header.state_root;
+        self.block_storage
+            .write()
+            .write_block(block, merkle_root, state_root)
+            .map(|_| ())
+            .map_err(chain_error)
+    }
+
+    fn install_state(&self, state: PatriciaMerkleTrie) -> Result<(), SyncError> {
+        *self.state_trie.write() = state;
+        self.checkpoint_state().map_err(chain_error)
+    }
+}
+
+fn chain_error(error: impl std::fmt::Display) -> SyncError {
+    SyncError::Chain(error.to_string())
+}
+
+/// Expected hashes to find a block at difficulty `target`, i.e.
+/// 2^256 / (target + 1).
+///
+/// Blocks stored without a target count for nothing.
+pub fn block_work(target: U256) -> U256 {
+    if target.is_zero() {
+        return U256::zero();
+    }
+    match target.checked_add(U256::one()) {
+        Some(divisor) => !target / divisor + 1,
+        None => U256::one(),
+    }
+}
+
+/// Total work of the local chain, extended as its head moves.
+#[derive(Default)]
+pub struct ChainWork {
+    /// Height, hash and total work of the head last summed.
+    tip: Mutex<Option<(u64, Hash, U256)>>,
+}
+
+impl ChainWork {
+    /// Total work from genesis to the local head.
+    ///
+    /// Only blocks stored since the last call are read, unless the chain
+    /// has moved off the head summed then.
+    pub fn total(&self, chain: &impl SyncChain) -> Result<U256, SyncError> {
+        let (height, hash) = chain.head()?;
+        let mut tip = self.tip.lock();
+        let (mut work, from) = match *tip {
+            Some((tip_height, tip_hash, work))
+                if tip_height <= height && chain.hash_at(tip_height) == Some(tip_hash) =>
+            {
+                (work, tip_height + 1)
+            }
+            _ => (U256::zero(), 0),
+        };
+        for at in from..=height {
+            let header = chain
+                .header_at(at)
+                .ok_or_else(|| SyncError::Chain(format!("no block at height {}", at)))?;
+            work = work.saturating_add(block_work(header.difficulty));
+        }
+        *tip = Some((height, hash, work));
+        Ok(work)
+    }
+}
+
+/// Root of the merkle tree qc-03 builds over `block`'s transactions.
+fn transactions_root(block: &ValidatedBlock) -> Hash {
+    MerkleTree::build(block.transactions.iter().map(|tx| tx.tx_hash).collect()).root()
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// State downloaded in the snapshot phase.
+struct Snapshot {
+    block_hash: Hash,
+    block_height: u64,
+    state: PatriciaMerkleTrie,
+}
+
+/// What one sync has learned so far.
+struct SyncPlan {
+    peer: [u8; 32],
+    local_height: u64,
+    /// Headers above the local head, oldest first, with their hashes.
+    headers: Vec<(Hash, BlockHeader)>,
+    snapshot: Option<Snapshot>,
+}
+
+impl SyncPlan {
+    /// Hash and height of the newest downloaded header.
+    fn tip(&self) -> Option<(Hash, u64)> {
+        self.headers
+            .last()
+            .map(|(hash, header)| (*hash, header.height))
+    }
+}
+
+/// Runs syncs until the node has caught up with its peers.
+pub struct SyncCoordinator<N, C> {
+    network: Arc<N>,
+    chain: Arc<C>,
+    event_bus: Arc<InMemoryEventBus>,
+    config: SyncConfig,
+    /// qc-08 rules downloaded headers must follow.
+    validator: BlockValidator,
+    work: ChainWork,
+}
+
+impl<N: SyncNetwork, C: SyncChain> SyncCoordinator<N, C> {
+    pub fn new(
+        network: Arc<N>,
+        chain: Arc<C>,
+        event_bus: Arc<InMemoryEventBus>,
+        config: SyncConfig,
+    ) -> Self {
+        Self {
+            network,
+            chain,
+            event_bus,
+            config,
+            validator: BlockValidator::with_defaults(),
+            work: ChainWork::default(),
+        }
+    }
+
+    /// Check headers with chain-specific rules (see `ChainSpec::block_validation_config`).
+    pub fn with_validation_config(mut self, config: BlockValidationConfig) -> Self {
+        self.validator = BlockValidator::new(config);
+        self
+    }
+
+    /// Sync until within `max_lag_blocks` of the best peer and return the
+    /// local head height.
+    ///
+    /// Failed syncs are retried after `retry_delay_secs`; only errors that
+    /// retrying cannot fix (a fork, a local storage failure) are returned.
+    pub async fn run(&self) -> Result<u64, SyncError> {
+        self.wait_for_peers().await;
+        loop {
+            let (height, hash) = self.chain.head()?;
+            let work = self.work.total(self.chain.as_ref())?;
+            let ahead = self.network.best_head().filter(|head| {
+                head.total_work > work
+                    && head.height > height.saturating_add(self.config.max_lag_blocks)
+            });
+            let Some(head) = ahead else {
+                info!("[Sync] Synced at height {}", height);
+                self.publish(BlockchainEvent::SyncCompleted {
+                    block_height: height,
+                    block_hash: hash,
+                })
+                .await;
+                return Ok(height);
+            };
+
+            info!(
+                "[Sync] {} blocks behind peer {} (head {}), syncing",
+                head.height - height,
+                hex::encode(&head.peer[..8]),
+                head.height
+            );
+            match self.sync_to(head, height).await {
+                Ok(()) => {}
+                Err(e) if e.is_fatal() => return Err(e),
+                Err(e) => {
+                    warn!(
+                        "[Sync] Sync with peer {} failed: {}; retrying in {}s",
+                        hex::encode(&head.peer[..8]),
+                        e,
+                        self.config.retry_delay_secs
+                    );
+                    tokio::time::sleep(Duration::from_secs(self.config.retry_delay_secs)).await;
+                }
+            }
+        }
+    }
+
+    /// Give peers `peer_wait_secs` to report their heads.
+    async fn wait_for_peers(&self) {
+        let deadline =
+            tokio::time::Instant::now() + Duration::from_secs(self.config.peer_wait_secs);
+        while self.network.best_head().is_none() {
+            if tokio::time::Instant::now() >= deadline {
+                info!("[Sync] No peer reported a head, starting from the local chain");
+                return;
+            }
+            tokio::time::sleep(PEER_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Run every phase against `head`.
+    async fn sync_to(&self, head: PeerHead, local_height: u64) -> Result<(), SyncError> {
+        let mut plan = SyncPlan {
+            peer: head.peer,
+            local_height,
+            headers: Vec::new(),
+            snapshot: None,
+        };
+        self.download_headers(&mut plan, head).await?;
+        self.download_snapshot(&mut plan).await?;
+        self.heal(&mut plan).await?;
+        self.backfill(plan).await
+    }
+
+    /// Headers phase: the peer's chain from `head` down to the local head.
+    async fn download_headers(&self, plan: &mut SyncPlan, head: PeerHead) -> Result<(), SyncError> {
+        let (headers, base) = self
+            .fetch_headers(
+                SyncPhase::Headers,
+                plan.peer,
+                (head.hash, head.height),
+                plan.local_height,
+            )
+            .await?;
+        if self.chain.hash_at(plan.local_height) != Some(base) {
+            return Err(SyncError::Fork {
+                height: plan.local_height,
+            });
+        }
+        let work = headers.iter().fold(
+            self.work.total(self.chain.as_ref())?,
+            |work, (_, header)| work.saturating_add(block_work(header.difficulty)),
+        );
+        if work < head.total_work {
+            return Err(SyncError::InvalidChain {
+                height: head.height,
+                reason: "headers carry less work than the peer reported".into(),
+            });
+        }
+        plan.headers = headers;
+        Ok(())
+    }
+
+    /// Snapshot phase: the peer's state trie, in chunks.
+    async fn download_snapshot(&self, plan: &mut SyncPlan) -> Result<(), SyncError> {
+        let first = self.network.state_chunk(plan.peer, None, 0).await?;
+        let (pivot, pivot_height, total) = (first.block_hash, first.block_height, first.total);
+        let tip_height = plan.tip().map_or(plan.local_height, |(_, height)| height);
+        if first.index != 0 || total == 0 {
+            return Err(SyncError::InvalidSnapshot("bad first chunk".into()));
+        }
+        if pivot_height < tip_height {
+            return Err(SyncError::InvalidSnapshot(format!(
+                "taken at block {}, below the peer's head {}",
+                pivot_height, tip_height
+            )));
+        }
+
+        let mut data = first.data;
+        self.report(SyncPhase::Snapshot, 1, u64::from(total)).await;
+        for index in 1..total {
+            let chunk = self
+                .network
+                .state_chunk(plan.peer, Some(pivot), index)
+                .await?;
+            if chunk.block_hash != pivot || chunk.index != index || chunk.total != total {
+                return Err(SyncError::InvalidSnapshot(format!(
+                    "chunk {} does not belong to the snapshot",
+                    index
+                )));
+            }
+            data.extend_from_slice(&chunk.data);
+            self.report(SyncPhase::Snapshot, u64::from(index) + 1, u64::from(total))
+                .await;
+        }
+
+        let state = PatriciaMerkleTrie::deserialize(&data)
+            .map_err(|e| SyncError::InvalidSnapshot(e.to_string()))?;
+        debug!(
+            "[Sync] Downloaded state at block {} ({} KiB)",
+            pivot_height,
+            data.len() / 1024
+        );
+        plan.snapshot = Some(Snapshot {
+            block_hash: pivot,
+            block_height: pivot_height,
+            state,
+        });
+        Ok(())
+    }
+
+    /// Healing phase: link the pivot to the downloaded headers and check
+    /// the snapshot against it.
+    async fn heal(&self, plan: &mut SyncPlan) -> Result<(), SyncError> {
+        let Some(snapshot) = &plan.snapshot else {
+            return Err(SyncError::InvalidSnapshot("no snapshot downloaded".into()));
+        };
+        let (pivot, pivot_height) = (snapshot.block_hash, snapshot.block_height);
+        let (tip, tip_height) = plan.tip().ok_or(SyncError::InvalidChain {
+            height: plan.local_height,
+            reason: "no headers above the local head".into(),
+        })?;
+
+        if pivot_height > tip_height {
+            let (headers, base) = self
+                .fetch_headers(
+                    SyncPhase::Healing,
+                    plan.peer,
+                    (pivot, pivot_height),
+                    tip_height,
+                )
+                .await?;
+            if base != tip {
+                return Err(SyncError::InvalidChain {
+                    height: tip_height + 1,
+                    reason: "snapshot block does not extend the downloaded headers".into(),
+                });
+            }
+            plan.headers.extend(headers);
+        } else if pivot != tip {
+            return Err(SyncError::InvalidChain {
+                height: pivot_height,
+                reason: "snapshot block is not the peer's head".into(),
+            });
+        }
+
+        let root = snapshot.state.root_hash();
+        match plan.headers.last() {
+            Some((_, header)) if header.state_root == root => {
+                self.report(SyncPhase::Healing, 1, 1).await;
+                Ok(())
+            }
+            _ => Err(SyncError::StateRootMismatch {
+                height: pivot_height,
+            }),
+        }
+    }
+
+    /// Backfill phase: download and check every block up to the pivot, then
+    /// store them and the state.
+    ///
+    /// Nothing is stored until every block has checked out, so a bad block
+    /// or a network error leaves the local chain at its old head.
+    async fn backfill(&self, plan: SyncPlan) -> Result<(), SyncError> {
+        let total = plan.headers.len() as u64;
+        let mut blocks = Vec::with_capacity(plan.headers.len());
+        for (done, (hash, header)) in plan.headers.iter().enumerate() {
+            let block = self.network.block(plan.peer, *hash).await?;
+            if header_hash(&block.header) != *hash {
+                return Err(SyncError::InvalidChain {
+                    height: header.height,
+                    reason: "block does not match its header".into(),
+                });
+            }
+            if transactions_root(&block) != block.header.merkle_root {
+                return Err(SyncError::InvalidChain {
+                    height: header.height,
+                    reason: "transactions do not match the header's merkle root".into(),
+                });
+            }
+            blocks.push(block);
+            self.report(SyncPhase::Backfill, done as u64 + 1, total)
+                .await;
+        }
+
+        for block in blocks {
+            self.chain.store_block(block)?;
+        }
+
+        if let Some(snapshot) = plan.snapshot {
+            self.chain.install_state(snapshot.state)?;
+            info!(
+                "[Sync] Stored {} blocks and installed the state at block {}",
+                total, snapshot.block_height
+            );
+        }
+        Ok(())
+    }
+
+    /// Walk headers from `from` (hash, height) down to `stop_height`,
+    /// checking each against qc-08's header rules.
+    ///
+    /// Returns the headers above `stop_height`, oldest first, and the hash
+    /// the peer's chain has at `stop_height`.
+    async fn fetch_headers(
+        &self,
+        phase: SyncPhase,
+        peer: [u8; 32],
+        from: (Hash, u64),
+        stop_height: u64,
+    ) -> Result<(Vec<(Hash, BlockHeader)>, Hash), SyncError> {
+        let total = from.1.saturating_sub(stop_height);
+        let now = unix_now();
+        let mut headers: Vec<(Hash, BlockHeader)> = Vec::new();
+        let (mut next, mut next_height) = from;
+        while next_height > stop_height {
+            let batch = self
+                .network
+                .headers(peer, next, HEADERS_PER_REQUEST)
+                .await?;
+            if batch.is_empty() {
+                return Err(SyncError::InvalidChain {
+                    height: next_height,
+                    reason: "peer sent no headers".into(),
+                });
+            }
+            let wanted = (next_height - stop_height) as usize;
+            for header in batch.into_iter().take(wanted) {
+                if header.height != next_height || header_hash(&header) != next {
+                    return Err(SyncError::InvalidChain {
+                        height: next_height,
+                        reason: "header does not match the requested hash".into(),
+                    });
+                }
+                self.check_header(&header, headers.last().map(|(_, child)| child), now)?;
+                next = header.parent_hash;
+                next_height -= 1;
+                headers.push((header_hash(&header), header));
+            }
+            self.report(phase, headers.len() as u64, total).await;
+        }
+        headers.reverse();
+        Ok((headers, next))
+    }
+
+    /// Apply qc-08's header rules: a difficulty target within the chain's
+    /// limit, and a timestamp not too far ahead of ours and earlier than
+    /// its child's.
+    fn check_header(
+        &self,
+        header: &BlockHeader,
+        child: Option<&BlockHeader>,
+        now: u64,
+    ) -> Result<(), SyncError> {
+        let invalid = |reason: String| SyncError::InvalidChain {
+            height: header.height,
+            reason,
+        };
+        let mut target = [0u8; 32];
+        header.difficulty.to_big_endian(&mut target);
+        self.validator
+            .validate_difficulty(&target)
+            .map_err(|e| invalid(e.to_string()))?;
+        self.validator
+            .validate_timestamp(header.timestamp, now)
+            .map_err(|e| invalid(e.to_string()))?;
+        match child {
+            Some(child) if child.timestamp <= header.timestamp => Err(SyncError::InvalidChain {
+                height: child.height,
+                reason: "timestamp is not after its parent's".into(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Publish progress at most `PROGRESS_EVENTS` times per phase.
+    async fn report(&self, phase: SyncPhase, current: u64, target: u64) {
+        let step = (target / PROGRESS_EVENTS).max(1);
+        if current != target && current % step != 0 {
+            return;
+        }
+        debug!("[Sync] {}: {}/{}", phase.name(), current, target);
+        self.publish(BlockchainEvent::SyncProgress {
+            phase: phase.name().to_string(),
+            current,
+            target,
+        })
+        .await;
+    }
+
+    async fn publish(&self, event: BlockchainEvent) {
+        self.event_bus.publish(event).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::RwLock;
+    use shared_bus::{EventFilter, EventTopic};
+
+    /// Difficulty target of test blocks (256 hashes of work each).
+    fn test_target() -> U256 {
+        U256::MAX >> 8
+    }
+
+    /// Blocks linked from height 0; `seed` tells chains apart.
+    fn make_chain(len: u64, seed: u8, state_root: Hash) -> Vec<ValidatedBlock> {
+        let mut blocks: Vec<ValidatedBlock> = Vec::new();
+        for height in 0..len {
+            let parent_hash = blocks.last().map_or([0; 32], |b| header_hash(&b.header));
+            let mut block = ValidatedBlock::default();
+            block.header.height = height;
+            block.header.parent_hash = parent_hash;
+            block.header.merkle_root = transactions_root(&block);
+            block.header.state_root = state_root;
+            block.header.timestamp = 1_000 + height * 10 + u64::from(seed);
+            block.header.difficulty = test_target();
+            blocks.push(block);
+        }
+        blocks
+    }
+
+    fn make_state() -> PatriciaMerkleTrie {
+        let mut state = PatriciaMerkleTrie::new();
+        state.set_balance([1; 20], 500).unwrap();
+        state.set_balance([2; 20], 700).unwrap();
+        state
+    }
+
+    struct FakeNetwork {
+        blocks: Vec<ValidatedBlock>,
+        state: Vec<u8>,
+        chunk_size: usize,
+        /// Work the peer claims on top of what its blocks carry.
+        extra_work: U256,
+    }
+
+    impl FakeNetwork {
+        fn new(blocks: Vec<ValidatedBlock>, state: &PatriciaMerkleTrie) -> Self {
+            Self {
+                blocks,
+                state: state.serialize().unwrap(),
+                chunk_size: 16,
+                extra_work: U256::zero(),
+            }
+        }
+
+        fn find(&self, hash: Hash) -> Option<&ValidatedBlock> {
+            self.blocks.iter().find(|b| header_hash(&b.header) == hash)
+        }
+    }
+
+    #[async_trait]
+    impl SyncNetwork for FakeNetwork {
+        fn best_head(&self) -> Option<PeerHead> {
+            let work = self.blocks.iter().fold(self.extra_work, |work, b| {
+                work + block_work(b.header.difficulty)
+            });
+            self.blocks.last().map(|b| PeerHead {
+                peer: [9; 32],
+                height: b.header.height,
+                hash: header_hash(&b.header),
+                total_work: work,
+            })
+        }
+
+        async fn headers(
+            &self,
+            _peer: [u8; 32],
+            from: Hash,
+            max: u32,
+        ) -> Result<Vec<BlockHeader>, SyncError> {
+            let mut headers = Vec::new();
+            let mut next = self.find(from);
+            while let Some(block) = next {
+                if headers.len() == max as usize {
+                    break;
+                }
+                headers.push(block.header.clone());
+                next = self.find(block.header.parent_hash);
+            }
+            Ok(headers)
+        }
+
+        async fn block(&self, _peer: [u8; 32], hash: Hash) -> Result<ValidatedBlock, SyncError> {
+            self.find(hash)
+                .cloned()
+                .ok_or_else(|| SyncError::Network("unknown block".into()))
+        }
+
+        async fn state_chunk(
+            &self,
+            _peer: [u8; 32],
+            _pivot: Option<Hash>,
+            index: u32,
+        ) -> Result<StateChunk, SyncError> {
+            let head = self.best_head().unwrap();
+            let chunks: Vec<&[u8]> = self.state.chunks(self.chunk_size).collect();
+            Ok(StateChunk {
+                block_hash: head.hash,
+                block_height: head.height,
+                index,
+                total: chunks.len() as u32,
+                data: chunks[index as usize].to_vec(),
+            })
+        }
+    }
+
+    #[derive(Default)]
+    struct FakeChain {
+        blocks: RwLock<Vec<ValidatedBlock>>,
+        state_root: RwLock<Option<Hash>>,
+    }
+
+    impl FakeChain {
+        fn with_blocks(blocks: Vec<ValidatedBlock>) -> Self {
+            Self {
+                blocks: RwLock::new(blocks),
+                state_root: RwLock::new(None),
+            }
+        }
+    }
+
+    impl SyncChain for FakeChain {
+        fn head(&self) -> Result<(u64, Hash), SyncError> {
+            let blocks = self.blocks.read();
+            let head = blocks.last().unwrap();
+            Ok((head.header.height, header_hash(&head.header)))
+        }
+
+        fn hash_at(&self, height: u64) -> Option<Hash> {
+            self.blocks
+                .read()
+                .get(height as usize)
+                .map(|b| header_hash(&b.header))
+        }
+
+        fn header_at(&self, height: u64) -> Option<BlockHeader> {
+            self.blocks
+                .read()
+                .get(height as usize)
+                .map(|b| b.header.clone())
+        }
+
+        fn store_block(&self, block: ValidatedBlock) -> Result<(), SyncError> {
+            self.blocks.write().push(block);
+            Ok(())
+        }
+
+        fn install_state(&self, state: PatriciaMerkleTrie) -> Result<(), SyncError> {
+            *self.state_root.write() = Some(state.root_hash());
+            Ok(())
+        }
+    }
+
+    fn coordinator(
+        network: FakeNetwork,
+        chain: Arc<FakeChain>,
+        bus: Arc<InMemoryEventBus>,
+    ) -> SyncCoordinator<FakeNetwork, FakeChain> {
+        let config = SyncConfig {
+            peer_wait_secs: 0,
+            retry_delay_secs: 0,
+            ..SyncConfig::default()
+        };
+        SyncCoordinator::new(Arc::new(network), chain, bus, config)
+    }
+
+    #[tokio::test]
+    async fn test_syncs_to_peer_head_from_snapshot() {
+        let state = make_state();
+        let peer_chain = make_chain(600, 1, state.root_hash());
+        let chain = Arc::new(FakeChain::with_blocks(peer_chain[..1].to_vec()));
+        let bus = Arc::new(InMemoryEventBus::new());
+        let mut events = bus.subscribe(EventFilter::topics(vec![EventTopic::Sync]));
+
+        let sync = coordinator(
+            FakeNetwork::new(peer_chain.clone(), &state),
+            chain.clone(),
+            bus,
+        );
+        assert_eq!(sync.run().await.unwrap(), 599);
+
+        assert_eq!(chain.blocks.read().len(), 600);
+        assert_eq!(
+            chain.hash_at(599),
+            Some(header_hash(&peer_chain[599].header))
+        );
+        assert_eq!(*chain.state_root.read(), Some(state.root_hash()));
+
+        let mut phases = Vec::new();
+        let mut completed = None;
+        while let Ok(Some(event)) = events.try_recv() {
+            match event {
+                BlockchainEvent::SyncProgress { phase, .. } if phases.last() != Some(&phase) => {
+                    phases.push(phase);
+                }
+                BlockchainEvent::SyncCompleted { block_height, .. } => {
+                    completed = Some(block_height)
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(phases, ["headers", "snapshot", "healing", "backfill"]);
+        assert_eq!(completed, Some(599));
+    }
+
+    #[tokio::test]
+    async fn test_within_lag_needs_no_sync() {
+        let state = make_state();
+        let peer_chain = make_chain(8, 1, state.root_hash());
+        let chain = Arc::new(FakeChain::with_blocks(peer_chain[..5].to_vec()));
+        let bus = Arc::new(InMemoryEventBus::new());
+
+        let sync = coordinator(FakeNetwork::new(peer_chain, &state), chain.clone(), bus);
+        assert_eq!(sync.run().await.unwrap(), 4);
+        assert_eq!(chain.blocks.read().len(), 5);
+        assert!(chain.state_root.read().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fork_below_local_head_is_fatal() {
+        let state = make_state();
+        let peer_chain = make_chain(20, 1, state.root_hash());
+        let chain = Arc::new(FakeChain::with_blocks(make_chain(3, 2, [0; 32])));
+        let bus = Arc::new(InMemoryEventBus::new());
+
+        let sync = coordinator(FakeNetwork::new(peer_chain, &state), chain.clone(), bus);
+        assert!(matches!(
+            sync.run().await,
+            Err(SyncError::Fork { height: 2 })
+        ));
+        assert_eq!(chain.blocks.read().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_with_wrong_root_is_not_installed() {
+        let state = make_state();
+        let peer_chain = make_chain(20, 1, [0xEE; 32]);
+        let chain = Arc::new(FakeChain::with_blocks(peer_chain[..1].to_vec()));
+        let bus = Arc::new(InMemoryEventBus::new());
+        let network = FakeNetwork::new(peer_chain, &state);
+        let head = network.best_head().unwrap();
+
+        let sync = coordinator(network, chain.clone(), bus);
+        assert!(matches!(
+            sync.sync_to(head, 0).await,
+            Err(SyncError::StateRootMismatch { height: 19 })
+        ));
+        assert_eq!(chain.blocks.read().len(), 1);
+        assert!(chain.state_root.read().is_none());
+    }
+
+    #[test]
+    fn test_block_work() {
+        assert_eq!(block_work(U256::zero()), U256::zero());
+        assert_eq!(block_work(U256::MAX), U256::one());
+        assert_eq!(block_work(test_target()), U256::from(256));
+    }
+
+    #[test]
+    fn test_chain_work_follows_the_head() {
+        let chain = FakeChain::with_blocks(make_chain(4, 1, [0; 32]));
+        let work = ChainWork::default();
+        assert_eq!(work.total(&chain).unwrap(), U256::from(4 * 256));
+
+        chain
+            .blocks
+            .write()
+            .extend(make_chain(6, 1, [0; 32]).split_off(4));
+        assert_eq!(work.total(&chain).unwrap(), U256::from(6 * 256));
+
+        *chain.blocks.write() = make_chain(2, 2, [0; 32]);
+        assert_eq!(work.total(&chain).unwrap(), U256::from(2 * 256));
+    }
+
+    #[tokio::test]
+    async fn test_header_breaking_qc08_rules_is_rejected() {
+        let state = make_state();
+        let mut peer_chain = make_chain(20, 1, state.root_hash());
+        peer_chain[19].header.difficulty = U256::zero();
+        let chain = Arc::new(FakeChain::with_blocks(peer_chain[..1].to_vec()));
+        let bus = Arc::new(InMemoryEventBus::new());
+        let network = FakeNetwork::new(peer_chain, &state);
+        let head = network.best_head().unwrap();
+
+        let sync = coordinator(network, chain.clone(), bus);
+        assert!(matches!(
+            sync.sync_to(head, 0).await,
+            Err(SyncError::InvalidChain { height: 19, .. })
+        ));
+        assert_eq!(chain.blocks.read().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_overstated_work_is_rejected() {
+        let state = make_state();
+        let peer_chain = make_chain(20, 1, state.root_hash());
+        let chain = Arc::new(FakeChain::with_blocks(peer_chain[..1].to_vec()));
+        let bus = Arc::new(InMemoryEventBus::new());
+        let mut network = FakeNetwork::new(peer_chain, &state);
+        network.extra_work = U256::one();
+        let head = network.best_head().unwrap();
+
+        let sync = coordinator(network, chain.clone(), bus);
+        assert!(matches!(
+            sync.sync_to(head, 0).await,
+            Err(SyncError::InvalidChain { height: 19, .. })
+        ));
+        assert_eq!(chain.blocks.read().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_block_with_wrong_transactions_is_not_stored() {
+        let state = make_state();
+        let mut peer_chain = make_chain(20, 1, state.root_hash());
+        peer_chain[10]
+            .transactions
+            .push(shared_types::ValidatedTransaction {
+                inner: shared_types::Transaction {
+                    from: [0xAA; 32],
+                    to: Some([0xBB; 32]),
+                    value: 1,
+                    nonce: 0,
+                    data: vec![],
+                    signature: [0u8; 64],
+                },
+                tx_hash: [7; 32],
+            });
+        let chain = Arc::new(FakeChain::with_blocks(peer_chain[..1].to_vec()));
+        let bus = Arc::new(InMemoryEventBus::new());
+        let network = FakeNetwork::new(peer_chain, &state);
+        let head = network.best_head().unwrap();
+
+        let sync = coordinator(network, chain.clone(), bus);
+        assert!(matches!(
+            sync.sync_to(head, 0).await,
+            Err(SyncError::InvalidChain { height: 10, .. })
+        ));
+        // Nothing above the old head, not even the blocks before the bad one
+        assert_eq!(chain.blocks.read().len(), 1);
+        assert!(chain.state_root.read().is_none());
+    }
+}
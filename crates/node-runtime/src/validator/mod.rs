@@ -0,0 +1,540 @@
+//! # Validator Mode
+//!
+//! Runs this node as a proof-of-stake validator (the `[validator]` config
+//! section):
+//!
+//! 1. The validator key is loaded from the keystore and must belong to one
+//!    of the chain spec's genesis validators.
+//! 2. Its id is registered with Consensus' (qc-08) `DutyScheduler`.
+//! 3. `run_duties` ticks every slot and publishes `SlotAssigned` when the
+//!    key is due; Block Production (qc-17) then builds and signs the block,
+//!    which Consensus validates from its `BlockProposed`.
+//! 4. `Attester` follows stored blocks: it republishes each `BlockStored` on
+//!    the shared bus so qc-17 builds on the stored head, and attests to the
+//!    first block stored in every epoch (`AttestationVerified`), which fills
+//!    qc-17's attestation pool.
+//! 5. Nothing is signed before `SlashingProtectionDb` has recorded it.
+
+mod slashing_protection;
+
+pub use slashing_protection::{SlashingProtectionDb, SlashingProtectionError};
+
+use crate::genesis::{ChainSpec, ChainSpecError};
+use crate::wiring::ChoreographyEvent;
+use qc_08_consensus::domain::ValidatorId;
+use qc_08_consensus::{DutyScheduler, SlotDuty, ValidatorInfo, ValidatorSet};
+use sha3::{Digest, Keccak256};
+use shared_bus::{BlockchainEvent, EventPublisher, InMemoryEventBus};
+use shared_crypto::{CryptoError, SignatureScheme, Signer};
+use shared_types::Attestation;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tokio::sync::{broadcast, watch};
+use tracing::{info, warn};
+
+/// Validator id of a public key (Keccak256 of its bytes).
+pub fn validator_id(public_key: &[u8]) -> ValidatorId {
+    Keccak256::digest(public_key).into()
+}
+
+/// Validator set at genesis, in chain spec order.
+pub fn genesis_validator_set(spec: &ChainSpec) -> Result<ValidatorSet, ChainSpecError> {
+    let genesis = spec.genesis_config()?;
+    Ok(validator_set(
+        &genesis.initial_validators,
+        &genesis.initial_stakes,
+    ))
+}
+
+fn validator_set(keys: &[[u8; 33]], stakes: &[u128]) -> ValidatorSet {
+    let validators = keys
+        .iter()
+        .zip(stakes)
+        .map(|(pubkey, &stake)| {
+            let mut padded = [0u8; 48];
+            padded[..pubkey.len()].copy_from_slice(pubkey);
+            ValidatorInfo::new(validator_id(pubkey), stake, padded)
+        })
+        .collect();
+    ValidatorSet::new(0, validators)
+}
+
+/// Why a proposal is not accepted as coming from its slot's proposer.
+#[derive(Debug, Error)]
+pub enum ProposalError {
+    /// Another validator (or none) was assigned the slot.
+    #[error("validator #{got} is not the proposer of slot {slot} (expected {expected:?})")]
+    WrongProposer {
+        slot: u64,
+        expected: Option<u32>,
+        got: u32,
+    },
+
+    /// The signed header does not hash to the proposed block hash.
+    #[error("header does not hash to the block hash")]
+    HashMismatch,
+
+    /// The signature does not verify with the proposer's key.
+    #[error("invalid proposer signature: {0}")]
+    Signature(#[from] CryptoError),
+}
+
+/// Proposer schedule and keys of the genesis validators.
+///
+/// Consensus checks every PoS proposal against it: the slot must be
+/// assigned to the proposing validator, and the header signature must
+/// verify with that validator's (secp256k1) genesis key.
+#[derive(Debug, Clone)]
+pub struct ProposerSchedule {
+    scheduler: DutyScheduler,
+    validators: ValidatorSet,
+    /// Public key of each validator, in set order.
+    keys: Vec<[u8; 33]>,
+}
+
+impl ProposerSchedule {
+    /// Schedule for `keys` with matching `stakes`.
+    pub fn new(keys: Vec<[u8; 33]>, stakes: &[u128], slots_per_epoch: u64) -> Self {
+        Self {
+            scheduler: DutyScheduler::new(slots_per_epoch),
+            validators: validator_set(&keys, stakes),
+            keys,
+        }
+    }
+
+    /// Schedule of the chain spec's genesis validators.
+    pub fn from_chain_spec(spec: &ChainSpec, slots_per_epoch: u64) -> Result<Self, ChainSpecError> {
+        let genesis = spec.genesis_config()?;
+        Ok(Self::new(
+            genesis.initial_validators,
+            &genesis.initial_stakes,
+            slots_per_epoch,
+        ))
+    }
+
+    /// Index of the validator assigned `slot`.
+    pub fn proposer(&self, slot: u64) -> Option<u32> {
+        self.scheduler
+            .proposer(&self.validators, slot)
+            .map(|duty| duty.validator_index)
+    }
+
+    /// Check that `validator_index` was assigned `slot` and signed `header`,
+    /// and that `header` hashes (SHA-256d) to `block_hash`.
+    pub fn verify(
+        &self,
+        slot: u64,
+        validator_index: u32,
+        header: &[u8],
+        block_hash: &[u8; 32],
+        signature: &[u8],
+    ) -> Result<(), ProposalError> {
+        let expected = self.proposer(slot);
+        if expected != Some(validator_index) {
+            return Err(ProposalError::WrongProposer {
+                slot,
+                expected,
+                got: validator_index,
+            });
+        }
+        if &qc_17_block_production::utils::sha256d(header) != block_hash {
+            return Err(ProposalError::HashMismatch);
+        }
+        let key = &self.keys[validator_index as usize];
+        shared_crypto::verify_signature(SignatureScheme::Secp256k1, key, header, signature)?;
+        Ok(())
+    }
+}
+
+/// Maps wall-clock time to slots.
+#[derive(Debug, Clone, Copy)]
+pub struct SlotClock {
+    /// Unix time of slot 0 (the genesis block).
+    pub genesis_time: u64,
+    /// Slot length in seconds.
+    pub slot_duration: u64,
+}
+
+impl SlotClock {
+    /// Slot in progress at `unix_secs`, or None before genesis.
+    pub fn slot_at(&self, unix_secs: u64) -> Option<u64> {
+        let elapsed = unix_secs.checked_sub(self.genesis_time)?;
+        Some(elapsed / self.slot_duration.max(1))
+    }
+
+    /// Unix time at which `slot` starts.
+    pub fn slot_start(&self, slot: u64) -> u64 {
+        self.genesis_time
+            .saturating_add(slot.saturating_mul(self.slot_duration.max(1)))
+    }
+}
+
+/// Event that asks Block Production to propose for `duty`.
+pub fn slot_assigned(duty: &SlotDuty) -> BlockchainEvent {
+    BlockchainEvent::SlotAssigned {
+        slot: duty.slot,
+        epoch: duty.epoch,
+        validator_index: duty.validator_index,
+        vrf_output: duty.seed,
+        vrf_proof: Vec::new(),
+    }
+}
+
+/// Publish `SlotAssigned` at the start of every slot a registered validator
+/// proposes, until `stop` fires.
+///
+/// Slots missed while the node was busy or suspended are skipped rather than
+/// proposed late.
+pub async fn run_duties(
+    scheduler: DutyScheduler,
+    validators: ValidatorSet,
+    clock: SlotClock,
+    event_bus: Arc<InMemoryEventBus>,
+    mut stop: watch::Receiver<bool>,
+) {
+    // Slot 0 is the genesis block
+    let mut next = clock.slot_at(unix_now()).map_or(1, |slot| slot + 1);
+    loop {
+        let wait = clock.slot_start(next).saturating_sub(unix_now());
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(wait)) => {}
+            _ = stop.changed() => break,
+        }
+
+        let slot = clock.slot_at(unix_now()).unwrap_or(next).max(next);
+        next = slot + 1;
+        if let Some(duty) = scheduler.duty(&validators, slot) {
+            info!(
+                "[Validator] Proposing slot {} (epoch {}) as validator #{}",
+                duty.slot, duty.epoch, duty.validator_index
+            );
+            event_bus.publish(slot_assigned(&duty)).await;
+        }
+    }
+    info!("[Validator] Duty task stopped");
+}
+
+/// Why no attestation was signed.
+#[derive(Debug, Error)]
+pub enum AttestationError {
+    /// The vote conflicts with one already signed.
+    #[error(transparent)]
+    SlashingProtection(#[from] SlashingProtectionError),
+
+    /// The key failed to sign, or produced a signature that does not verify.
+    #[error("signing failed: {0}")]
+    Signing(#[from] CryptoError),
+
+    /// The key's signatures do not fit an attestation.
+    #[error("{0}-byte signature does not fit an attestation (64 bytes)")]
+    SignatureLength(usize),
+}
+
+/// Bytes an attestation signs: the block hash, then the epoch (big-endian).
+pub fn attestation_message(block_hash: &[u8; 32], epoch: u64) -> Vec<u8> {
+    [block_hash.as_slice(), &epoch.to_be_bytes()].concat()
+}
+
+/// Follows stored blocks for the validator.
+///
+/// Every choreography `BlockStored` is republished on the shared bus, where
+/// qc-17's proposer moves its head; the first block stored in each epoch is
+/// attested to.
+pub struct Attester {
+    validator: ValidatorId,
+    signer: Arc<dyn Signer>,
+    protection: Arc<SlashingProtectionDb>,
+    clock: SlotClock,
+    epoch_length: u64,
+    /// Last epoch an attestation was attempted for.
+    last_epoch: Option<u64>,
+}
+
+impl Attester {
+    /// Attester for `validator`, signing with `signer`.
+    pub fn new(
+        validator: ValidatorId,
+        signer: Arc<dyn Signer>,
+        protection: Arc<SlashingProtectionDb>,
+        clock: SlotClock,
+        epoch_length: u64,
+    ) -> Self {
+        Self {
+            validator,
+            signer,
+            protection,
+            clock,
+            epoch_length: epoch_length.max(1),
+            last_epoch: None,
+        }
+    }
+
+    /// Follow `blocks` until `stop` fires or the router closes.
+    pub async fn run(
+        mut self,
+        mut blocks: broadcast::Receiver<ChoreographyEvent>,
+        event_bus: Arc<InMemoryEventBus>,
+        mut stop: watch::Receiver<bool>,
+    ) {
+        loop {
+            let event = tokio::select! {
+                event = blocks.recv() => event,
+                _ = stop.changed() => break,
+            };
+            let (block_hash, block_height) = match event {
+                Ok(ChoreographyEvent::BlockStored {
+                    block_hash,
+                    block_height,
+                    ..
+                }) => (block_hash, block_height),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    warn!("[Validator] Missed {} choreography events", n);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            event_bus
+                .publish(BlockchainEvent::BlockStored {
+                    block_height,
+                    block_hash,
+                })
+                .await;
+
+            let Some(epoch) = self
+                .clock
+                .slot_at(unix_now())
+                .map(|slot| slot / self.epoch_length)
+            else {
+                continue;
+            };
+            if self.last_epoch.is_some_and(|last| epoch <= last) {
+                continue;
+            }
+            // One attempt per epoch, whatever its outcome
+            self.last_epoch = Some(epoch);
+            match self.attest(block_hash, epoch).await {
+                Ok(attestation) => {
+                    info!(
+                        "[Validator] Attested to block #{} for epoch {}",
+                        block_height, epoch
+                    );
+                    event_bus
+                        .publish(BlockchainEvent::AttestationVerified(attestation))
+                        .await;
+                }
+                Err(e) => warn!("[Validator] No attestation for epoch {}: {}", epoch, e),
+            }
+        }
+        info!("[Validator] Attestation task stopped");
+    }
+
+    /// Sign an attestation to `block_hash` for `epoch`.
+    ///
+    /// The vote's source is the previous epoch, so no two votes can surround
+    /// each other and the record only has to refuse double votes. The
+    /// signature is verified before it is released.
+    pub async fn attest(
+        &self,
+        block_hash: [u8; 32],
+        epoch: u64,
+    ) -> Result<Attestation, AttestationError> {
+        let message = attestation_message(&block_hash, epoch);
+        let signing_root: [u8; 32] = Keccak256::digest(&message).into();
+        self.protection.check_and_record_attestation(
+            epoch.saturating_sub(1),
+            epoch,
+            signing_root,
+        )?;
+
+        let signature = self.signer.sign(&message).await?;
+        shared_crypto::verify_signature(
+            self.signer.scheme(),
+            &self.signer.public_key(),
+            &message,
+            &signature,
+        )?;
+        let signature = signature
+            .try_into()
+            .map_err(|signature: Vec<u8>| AttestationError::SignatureLength(signature.len()))?;
+
+        Ok(Attestation {
+            block_hash,
+            epoch,
+            validator: self.validator,
+            signature,
+        })
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_bus::EventFilter;
+
+    #[test]
+    fn test_slot_clock() {
+        let clock = SlotClock {
+            genesis_time: 1_000,
+            slot_duration: 12,
+        };
+        assert_eq!(clock.slot_at(999), None);
+        assert_eq!(clock.slot_at(1_000), Some(0));
+        assert_eq!(clock.slot_at(1_025), Some(2));
+        assert_eq!(clock.slot_start(2), 1_024);
+    }
+
+    #[test]
+    fn test_genesis_validator_ids_match_keys() {
+        let mut spec = ChainSpec::dev();
+        let key = [2u8; 33];
+        spec.genesis.alloc = vec![crate::genesis::chain_spec::GenesisAllocation {
+            validator: hex::encode(key),
+            stake: 32,
+        }];
+
+        let set = genesis_validator_set(&spec).unwrap();
+        assert_eq!(set.validators.len(), 1);
+        assert!(set.contains(&validator_id(&key)));
+        assert_eq!(set.total_stake, 32);
+    }
+
+    #[tokio::test]
+    async fn test_duties_published_for_registered_validator() {
+        let id = [7u8; 32];
+        let validators = ValidatorSet::new(0, vec![ValidatorInfo::new(id, 1, [0u8; 48])]);
+        let mut scheduler = DutyScheduler::new(32);
+        scheduler.register(id);
+
+        let event_bus = Arc::new(InMemoryEventBus::new());
+        let mut events = event_bus.subscribe(EventFilter::all());
+        let (stop_tx, stop_rx) = tokio::sync::watch::channel(false);
+        let clock = SlotClock {
+            genesis_time: unix_now() - 10,
+            slot_duration: 1,
+        };
+        let task = tokio::spawn(run_duties(
+            scheduler,
+            validators,
+            clock,
+            Arc::clone(&event_bus),
+            stop_rx,
+        ));
+
+        let event = tokio::time::timeout(Duration::from_secs(3), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let BlockchainEvent::SlotAssigned { slot, .. } = event else {
+            panic!("expected SlotAssigned, got {:?}", event);
+        };
+        assert!(slot > 10);
+
+        stop_tx.send(true).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), task)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    fn attester(protection: Arc<SlashingProtectionDb>) -> Attester {
+        let signer = shared_crypto::SoftwareSigner::ed25519(
+            shared_crypto::Ed25519KeyPair::from_seed([3u8; 32]),
+        );
+        let clock = SlotClock {
+            genesis_time: unix_now() - 100,
+            slot_duration: 60,
+        };
+        Attester::new([9u8; 32], Arc::new(signer), protection, clock, 32)
+    }
+
+    #[tokio::test]
+    async fn test_attestation_signed_and_protected() {
+        let dir = tempfile::tempdir().unwrap();
+        let protection =
+            Arc::new(SlashingProtectionDb::open(&dir.path().join("protection.json")).unwrap());
+        let attester = attester(Arc::clone(&protection));
+
+        let attestation = attester.attest([1u8; 32], 7).await.unwrap();
+        assert_eq!(attestation.validator, [9u8; 32]);
+        assert!(shared_crypto::verify_signature(
+            attester.signer.scheme(),
+            &attester.signer.public_key(),
+            &attestation_message(&[1u8; 32], 7),
+            &attestation.signature,
+        )
+        .is_ok());
+
+        // A different block in the same epoch is a double vote
+        assert!(matches!(
+            attester.attest([2u8; 32], 7).await,
+            Err(AttestationError::SlashingProtection(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stored_blocks_republished_and_attested() {
+        let dir = tempfile::tempdir().unwrap();
+        let protection =
+            Arc::new(SlashingProtectionDb::open(&dir.path().join("protection.json")).unwrap());
+        let router = crate::wiring::EventRouter::default();
+        let event_bus = Arc::new(InMemoryEventBus::new());
+        let mut events = event_bus.subscribe(EventFilter::all());
+        let (stop_tx, stop_rx) = watch::channel(false);
+        let task = tokio::spawn(attester(protection).run(
+            router.subscribe(),
+            Arc::clone(&event_bus),
+            stop_rx,
+        ));
+
+        for height in [1, 2] {
+            router
+                .publish(ChoreographyEvent::BlockStored {
+                    block_hash: [height as u8; 32],
+                    block_height: height,
+                    merkle_root: [0u8; 32],
+                    state_root: [0u8; 32],
+                    sender_id: shared_types::SubsystemId::BlockStorage,
+                    #[cfg(feature = "zk")]
+                    validity_proof: None,
+                })
+                .unwrap();
+        }
+
+        let mut received = Vec::new();
+        while let Ok(Some(event)) =
+            tokio::time::timeout(Duration::from_millis(200), events.recv()).await
+        {
+            received.push(event);
+        }
+        let stored = received
+            .iter()
+            .filter(|event| matches!(event, BlockchainEvent::BlockStored { .. }))
+            .count();
+        let attested: Vec<_> = received
+            .iter()
+            .filter_map(|event| match event {
+                BlockchainEvent::AttestationVerified(attestation) => Some(attestation),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(stored, 2);
+        // Both blocks land in the same epoch: only the first is attested to
+        assert_eq!(attested.len(), 1);
+        assert_eq!(attested[0].block_hash, [1u8; 32]);
+
+        stop_tx.send(true).unwrap();
+        tokio::time::timeout(Duration::from_secs(1), task)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
@@ -0,0 +1,302 @@
+//! # Slashing Protection
+//!
+//! Local record of everything this validator has signed. Before a signature
+//! is released the request is checked against the record and the record is
+//! written to disk, so a crash or restart can never lead to signing twice.
+//!
+//! ## Rules
+//!
+//! - Blocks: only slots above the last signed one. Re-signing the exact same
+//!   block (same slot and signing root) is allowed.
+//! - Attestations: never two different votes for the same target epoch, and
+//!   never a vote that surrounds or is surrounded by an earlier one.
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Slashing protection errors.
+#[derive(Debug, Error)]
+pub enum SlashingProtectionError {
+    /// The record could not be read or written.
+    #[error("slashing protection file {path}: {source}")]
+    Io {
+        /// File path.
+        path: PathBuf,
+        /// Underlying error.
+        source: std::io::Error,
+    },
+
+    /// The record exists but cannot be parsed. Signing stays disabled until
+    /// it is repaired by hand.
+    #[error("slashing protection file {path} is corrupt: {source}")]
+    Corrupt {
+        /// File path.
+        path: PathBuf,
+        /// Parser error.
+        source: serde_json::Error,
+    },
+
+    /// A different block was already signed at or after this slot.
+    #[error("refusing to sign block at slot {slot}: already signed slot {last_slot}")]
+    BlockConflict {
+        /// Requested slot.
+        slot: u64,
+        /// Highest slot signed so far.
+        last_slot: u64,
+    },
+
+    /// The vote conflicts with an earlier one.
+    #[error(
+        "refusing to sign attestation {source_epoch}->{target_epoch}: \
+         conflicts with {prior_source}->{prior_target}"
+    )]
+    AttestationConflict {
+        /// Requested source epoch.
+        source_epoch: u64,
+        /// Requested target epoch.
+        target_epoch: u64,
+        /// Source epoch of the conflicting vote.
+        prior_source: u64,
+        /// Target epoch of the conflicting vote.
+        prior_target: u64,
+    },
+}
+
+/// Last block signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct SignedBlock {
+    slot: u64,
+    signing_root: [u8; 32],
+}
+
+/// An attestation signed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct SignedAttestation {
+    source_epoch: u64,
+    target_epoch: u64,
+    signing_root: [u8; 32],
+}
+
+/// On-disk record.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Record {
+    last_block: Option<SignedBlock>,
+    attestations: Vec<SignedAttestation>,
+}
+
+/// File-backed slashing protection database.
+#[derive(Debug)]
+pub struct SlashingProtectionDb {
+    path: PathBuf,
+    record: Mutex<Record>,
+}
+
+impl SlashingProtectionDb {
+    /// Open the record at `path`, starting empty if it does not exist yet.
+    pub fn open(path: &Path) -> Result<Self, SlashingProtectionError> {
+        let record = match std::fs::read(path) {
+            Ok(data) => serde_json::from_slice(&data).map_err(|source| {
+                SlashingProtectionError::Corrupt {
+                    path: path.to_path_buf(),
+                    source,
+                }
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Record::default(),
+            Err(source) => {
+                return Err(SlashingProtectionError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            record: Mutex::new(record),
+        })
+    }
+
+    /// Highest block slot signed so far.
+    pub fn last_block_slot(&self) -> Option<u64> {
+        self.lock().last_block.map(|block| block.slot)
+    }
+
+    /// Check a block signature and record it before it is released.
+    pub fn check_and_record_block(
+        &self,
+        slot: u64,
+        signing_root: [u8; 32],
+    ) -> Result<(), SlashingProtectionError> {
+        let mut record = self.lock();
+        let block = SignedBlock { slot, signing_root };
+        match record.last_block {
+            Some(last) if last == block => return Ok(()),
+            Some(last) if slot <= last.slot => {
+                return Err(SlashingProtectionError::BlockConflict {
+                    slot,
+                    last_slot: last.slot,
+                })
+            }
+            _ => {}
+        }
+
+        let previous = record.last_block.replace(block);
+        self.persist(&record)
+            .inspect_err(|_| record.last_block = previous)
+    }
+
+    /// Check an attestation signature and record it before it is released.
+    pub fn check_and_record_attestation(
+        &self,
+        source_epoch: u64,
+        target_epoch: u64,
+        signing_root: [u8; 32],
+    ) -> Result<(), SlashingProtectionError> {
+        let mut record = self.lock();
+        let vote = SignedAttestation {
+            source_epoch,
+            target_epoch,
+            signing_root,
+        };
+        if record.attestations.contains(&vote) {
+            return Ok(());
+        }
+        if let Some(prior) = record
+            .attestations
+            .iter()
+            .find(|prior| conflicts(prior, &vote))
+        {
+            return Err(SlashingProtectionError::AttestationConflict {
+                source_epoch,
+                target_epoch,
+                prior_source: prior.source_epoch,
+                prior_target: prior.target_epoch,
+            });
+        }
+
+        record.attestations.push(vote);
+        self.persist(&record).inspect_err(|_| {
+            record.attestations.pop();
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Record> {
+        self.record.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Write through a synced temporary file so a crash never loses or tears
+    /// the record.
+    fn persist(&self, record: &Record) -> Result<(), SlashingProtectionError> {
+        let io_err = |source| SlashingProtectionError::Io {
+            path: self.path.clone(),
+            source,
+        };
+        let data = serde_json::to_vec_pretty(record).expect("record serializes");
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(io_err)?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        let mut file = std::fs::File::create(&tmp).map_err(io_err)?;
+        file.write_all(&data).map_err(io_err)?;
+        file.sync_all().map_err(io_err)?;
+        std::fs::rename(&tmp, &self.path).map_err(io_err)
+    }
+}
+
+/// Double vote (same target, different vote) or surround vote.
+fn conflicts(prior: &SignedAttestation, vote: &SignedAttestation) -> bool {
+    let double = prior.target_epoch == vote.target_epoch;
+    let surrounds =
+        vote.source_epoch < prior.source_epoch && vote.target_epoch > prior.target_epoch;
+    let surrounded =
+        vote.source_epoch > prior.source_epoch && vote.target_epoch < prior.target_epoch;
+    double || surrounds || surrounded
+}
+
+impl qc_17_block_production::SlashingProtection for SlashingProtectionDb {
+    fn check_and_record_block(
+        &self,
+        slot: u64,
+        signing_root: [u8; 32],
+    ) -> qc_17_block_production::Result<()> {
+        SlashingProtectionDb::check_and_record_block(self, slot, signing_root).map_err(|e| {
+            qc_17_block_production::BlockProductionError::SlashingProtection {
+                slot,
+                reason: e.to_string(),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db() -> (tempfile::TempDir, SlashingProtectionDb) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = SlashingProtectionDb::open(&dir.path().join("protection.json")).unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn test_blocks_must_advance() {
+        let (_dir, db) = db();
+        db.check_and_record_block(5, [1; 32]).unwrap();
+        // Re-signing the same block is harmless
+        db.check_and_record_block(5, [1; 32]).unwrap();
+
+        assert!(matches!(
+            db.check_and_record_block(5, [2; 32]),
+            Err(SlashingProtectionError::BlockConflict { last_slot: 5, .. })
+        ));
+        assert!(db.check_and_record_block(4, [3; 32]).is_err());
+        db.check_and_record_block(6, [4; 32]).unwrap();
+        assert_eq!(db.last_block_slot(), Some(6));
+    }
+
+    #[test]
+    fn test_attestation_double_and_surround_votes() {
+        let (_dir, db) = db();
+        db.check_and_record_attestation(2, 3, [1; 32]).unwrap();
+        db.check_and_record_attestation(2, 3, [1; 32]).unwrap();
+
+        // Double vote
+        assert!(db.check_and_record_attestation(1, 3, [2; 32]).is_err());
+        // Surrounding
+        assert!(db.check_and_record_attestation(1, 4, [2; 32]).is_err());
+
+        db.check_and_record_attestation(3, 6, [3; 32]).unwrap();
+        // Surrounded
+        assert!(db.check_and_record_attestation(4, 5, [4; 32]).is_err());
+        db.check_and_record_attestation(6, 7, [5; 32]).unwrap();
+    }
+
+    #[test]
+    fn test_record_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("validator").join("protection.json");
+        {
+            let db = SlashingProtectionDb::open(&path).unwrap();
+            db.check_and_record_block(10, [1; 32]).unwrap();
+            db.check_and_record_attestation(1, 2, [1; 32]).unwrap();
+        }
+
+        let db = SlashingProtectionDb::open(&path).unwrap();
+        assert_eq!(db.last_block_slot(), Some(10));
+        assert!(db.check_and_record_block(10, [2; 32]).is_err());
+        assert!(db.check_and_record_attestation(0, 2, [2; 32]).is_err());
+    }
+
+    #[test]
+    fn test_corrupt_record_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("protection.json");
+        std::fs::write(&path, b"not json").unwrap();
+        assert!(matches!(
+            SlashingProtectionDb::open(&path),
+            Err(SlashingProtectionError::Corrupt { .. })
+        ));
+    }
+}
@@ -21,6 +21,7 @@
 
 use std::sync::Arc;
 
+use shared_types::SecretBytes;
 use tokio::sync::RwLock;
 use tracing::info;
 
@@ -28,7 +29,7 @@ use tracing::info;
 #[derive(Debug, Clone)]
 pub struct CoreSubsystemConfig {
     /// HMAC secret for inter-subsystem authentication.
-    pub hmac_secret: [u8; 32],
+    pub hmac_secret: SecretBytes,
     /// Nonce cache expiry in seconds.
     pub nonce_cache_expiry_secs: u64,
     /// Block assembly timeout in seconds.
@@ -48,7 +49,7 @@ pub struct CoreSubsystemConfig {
 impl Default for CoreSubsystemConfig {
     fn default() -> Self {
         Self {
-            hmac_secret: [0u8; 32], // Must be set from environment
+            hmac_secret: SecretBytes::new(vec![0u8; 32]), // Must be set from environment
             nonce_cache_expiry_secs: 120,
             assembly_timeout_secs: 30,
             max_pending_assemblies: 1000,
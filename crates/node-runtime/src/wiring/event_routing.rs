@@ -51,7 +51,11 @@ use anyhow::Result;
 use tokio::sync::broadcast;
 use tracing::{debug, info, warn};
 
-use shared_types::SubsystemId;
+use shared_types::{CoinbaseTransaction, SubsystemId};
+
+/// Validity proof of a block's state transition (`zk` feature).
+#[cfg(feature = "zk")]
+pub type ValidityProof = Arc<qc_zkp::StateTransitionProof>;
 
 /// Event types that flow between subsystems.
 #[derive(Debug, Clone)]
@@ -65,6 +69,22 @@ pub enum ChoreographyEvent {
         nonce: u64,
         timestamp: u64,
         parent_hash: [u8; 32],
+        coinbase: Option<CoinbaseTransaction>,
+        sender_id: SubsystemId,
+    },
+
+    /// Signed PoS proposal from Block Production (17) - triggers consensus
+    /// validation.
+    BlockProposed {
+        block_hash: [u8; 32],
+        block_height: u64,
+        parent_hash: [u8; 32],
+        timestamp: u64,
+        beneficiary: [u8; 20],
+        gas_used: u64,
+        slot: u64,
+        validator_index: u32,
+        signature: Vec<u8>,
         sender_id: SubsystemId,
     },
 
@@ -87,6 +107,9 @@ pub enum ChoreographyEvent {
         block_hash: [u8; 32],
         state_root: [u8; 32],
         sender_id: SubsystemId,
+        /// Proof of the state transition (None if proving failed)
+        #[cfg(feature = "zk")]
+        validity_proof: Option<ValidityProof>,
     },
 
     /// Block stored atomically by Block Storage (2).
@@ -96,6 +119,9 @@ pub enum ChoreographyEvent {
         merkle_root: [u8; 32],
         state_root: [u8; 32],
         sender_id: SubsystemId,
+        /// Proof of the state transition, forwarded from StateRootComputed
+        #[cfg(feature = "zk")]
+        validity_proof: Option<ValidityProof>,
     },
 
     /// Block finalized by Finality (9).
@@ -155,6 +181,15 @@ impl AuthorizationRules {
                     });
                 }
             }
+            ChoreographyEvent::BlockProposed { sender_id, .. } => {
+                if *sender_id != SubsystemId::BlockProduction {
+                    return Err(AuthorizationError::UnauthorizedSender {
+                        event_type: "BlockProposed",
+                        expected: SubsystemId::BlockProduction,
+                        actual: *sender_id,
+                    });
+                }
+            }
             ChoreographyEvent::BlockValidated { sender_id, .. } => {
                 if *sender_id != SubsystemId::Consensus {
                     return Err(AuthorizationError::UnauthorizedSender {
@@ -303,6 +338,14 @@ impl EventRouter {
                     block_height
                 );
             }
+            ChoreographyEvent::BlockProposed {
+                block_height, slot, ..
+            } => {
+                info!(
+                    "[qc-17] 📦 BlockProposed #{} (slot {}) via choreography",
+                    block_height, slot
+                );
+            }
             ChoreographyEvent::BlockValidated { block_height, .. } => {
                 debug!("Publishing BlockValidated for height {}", block_height);
             }
@@ -454,6 +497,8 @@ mod tests {
             block_hash: [0u8; 32],
             state_root: [2u8; 32],
             sender_id: SubsystemId::StateManagement,
+            #[cfg(feature = "zk")]
+            validity_proof: None,
         };
         assert!(AuthorizationRules::validate_sender(&event).is_ok());
     }
@@ -8,6 +8,7 @@
 //! - `get_node_info` - Returns node info for admin_nodeInfo RPC
 //! - `add_peer` - Adds a peer (admin_addPeer)
 //! - `remove_peer` - Removes a peer (admin_removePeer)
+//! - `get_banned_peers` - Returns the ban list for admin_bannedPeers
 //! - `get_subsystem_metrics` - Returns qc-01 specific metrics for debug panel
 //! - `ping` - Health check
 
@@ -81,6 +81,25 @@ impl<S: PeerDiscoveryApi> ApiGatewayHandler<S> {
         serde_json::to_value(metrics).unwrap_or_default()
     }
 
+    /// Handle get_banned_peers request (admin_bannedPeers).
+    ///
+    /// Returns the bans in force, oldest first.
+    pub fn handle_get_banned_peers(&self) -> serde_json::Value {
+        let bans: Vec<RpcBannedPeer> = self
+            .service
+            .banned_peers()
+            .iter()
+            .map(|ban| RpcBannedPeer {
+                id: encode_hex(ban.node_id.as_bytes()),
+                address: ban.socket_addr.as_ref().map(format_socket_addr),
+                reason: ban.reason.to_string(),
+                banned_at: ban.banned_at.as_secs(),
+                banned_until: (!ban.is_permanent()).then(|| ban.banned_until.as_secs()),
+            })
+            .collect();
+        serde_json::to_value(bans).unwrap_or_default()
+    }
+
     /// Handle ping request (health check).
     pub fn handle_ping(&self) -> serde_json::Value {
         serde_json::json!({
@@ -125,6 +144,7 @@ pub fn handle_api_query<S: PeerDiscoveryApi>(
         "get_peers" | "admin_peers" => Ok(handler.handle_get_peers()),
         "get_node_info" | "admin_nodeInfo" => Ok(handler.handle_get_node_info()),
         "get_subsystem_metrics" | "debug_subsystemMetrics" => Ok(handler.handle_get_metrics()),
+        "get_banned_peers" | "admin_bannedPeers" => Ok(handler.handle_get_banned_peers()),
         "ping" => Ok(handler.handle_ping()),
         _ => Err(ApiQueryError {
             code: -32601,
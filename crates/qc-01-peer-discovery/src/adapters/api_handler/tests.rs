@@ -1,8 +1,8 @@
 //! Tests for API Handler Adapter
 use super::*;
 use crate::domain::{
-    BanDetails, IpAddr, KademliaConfig, NodeId, PeerDiscoveryError, PeerInfo, RoutingTable,
-    RoutingTableStats, SocketAddr, Timestamp,
+    BanDetails, BanReason, BannedEntry, IpAddr, KademliaConfig, NodeId, PeerDiscoveryError,
+    PeerInfo, RoutingTable, RoutingTableStats, SocketAddr, Timestamp,
 };
 use crate::ports::PeerDiscoveryApi;
 
@@ -64,6 +64,10 @@ impl PeerDiscoveryApi for TestService {
         self.table.is_banned(&node_id, Timestamp::new(1000))
     }
 
+    fn banned_peers(&self) -> Vec<BannedEntry> {
+        self.table.banned_peers(Timestamp::new(1000))
+    }
+
     fn touch_peer(&mut self, node_id: NodeId) -> Result<(), PeerDiscoveryError> {
         self.table.touch_peer(&node_id, Timestamp::new(1000))
     }
@@ -132,6 +136,41 @@ fn test_handle_get_metrics() {
     assert_eq!(metrics.pending_verification_count, 0);
 }
 
+#[test]
+fn test_handle_get_banned_peers() {
+    let mut service = TestService::with_peers(2);
+    let mut banned = [0u8; 32];
+    banned[0] = 1;
+    service
+        .ban_peer(
+            NodeId::new(banned),
+            BanDetails::new(3600, BanReason::ExcessiveRequests),
+        )
+        .unwrap();
+    service
+        .ban_peer(
+            NodeId::new([9u8; 32]),
+            BanDetails::new(0, BanReason::ManualBan),
+        )
+        .unwrap();
+    let handler = ApiGatewayHandler::new(service, NodeId::new([0u8; 32]), 30303);
+
+    let result = handle_api_query(&handler, "admin_bannedPeers", &serde_json::Value::Null);
+    let bans: Vec<RpcBannedPeer> = serde_json::from_value(result.unwrap()).unwrap();
+    assert_eq!(bans.len(), 2);
+
+    // The routing table peer keeps its address for the audit trail
+    let peer = bans.iter().find(|b| b.id.starts_with("01")).unwrap();
+    assert_eq!(peer.address.as_deref(), Some("192.168.1.1:30303"));
+    assert_eq!(peer.reason, "Excessive requests");
+    assert_eq!(peer.banned_at, 1000);
+    assert_eq!(peer.banned_until, Some(4600));
+
+    let manual = bans.iter().find(|b| b.id.starts_with("09")).unwrap();
+    assert_eq!(manual.address, None);
+    assert_eq!(manual.banned_until, None);
+}
+
 #[test]
 fn test_handle_ping() {
     let service = TestService::new();
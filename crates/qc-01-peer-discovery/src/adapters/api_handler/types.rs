@@ -106,6 +106,22 @@ pub struct Qc01Metrics {
     pub oldest_peer_age_seconds: u64,
 }
 
+/// Ban list entry for admin_bannedPeers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBannedPeer {
+    /// Banned node ID as hex string
+    pub id: String,
+    /// Address the peer had in the routing table, as "ip:port"
+    pub address: Option<String>,
+    /// Why the peer was banned
+    pub reason: String,
+    /// When the ban was imposed (unix seconds)
+    pub banned_at: u64,
+    /// When the ban expires (unix seconds); null for permanent bans
+    pub banned_until: Option<u64>,
+}
+
 /// Error type for API query responses (matches shared-bus).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiQueryError {
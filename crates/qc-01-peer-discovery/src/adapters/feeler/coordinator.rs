@@ -1,16 +1,35 @@
-use super::port::{FeelerError, FeelerPort};
+use super::port::FeelerPort;
 use crate::domain::{
     feeler::{BucketFreshness, FeelerConfig, FeelerResult, FeelerState},
     handshake::ForkId,
-    NodeId, SocketAddr,
+    AddressManager, NodeId, SocketAddr, Timestamp,
 };
-use crate::ports::TimeSource;
+use crate::ports::{RandomSource, TimeSource};
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
 
+use tokio::time::MissedTickBehavior;
+
 // =============================================================================
 // FEELER COORDINATOR (Application Service)
 // =============================================================================
 
+/// A completed feeler probe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeelerOutcome {
+    /// Address that was probed
+    pub addr: SocketAddr,
+    /// Node ID of the probed address, if known
+    pub node_id: Option<NodeId>,
+    /// New table bucket the address was drawn from
+    pub bucket: usize,
+    /// Probe verdict
+    pub result: FeelerResult,
+    /// Address reached `max_failures` and should leave the New table
+    pub evict: bool,
+}
+
 /// Coordinates feeler probing between domain state and network adapter.
 ///
 /// This is the application-layer service that ties together:
@@ -52,12 +71,12 @@ impl<T: TimeSource, P: FeelerPort> FeelerCoordinator<T, P> {
     ///
     /// # Returns
     ///
-    /// Result of probe if one was executed, None otherwise.
-    pub fn maybe_probe<F>(
+    /// Outcome of the probe if one was executed, None otherwise.
+    pub async fn maybe_probe<F>(
         &mut self,
         bucket_counts: &[usize],
         get_address: F,
-    ) -> Option<(SocketAddr, FeelerResult)>
+    ) -> Option<FeelerOutcome>
     where
         F: FnOnce(usize) -> Option<(SocketAddr, Option<NodeId>)>,
     {
@@ -84,20 +103,25 @@ impl<T: TimeSource, P: FeelerPort> FeelerCoordinator<T, P> {
         let result = self
             .port
             .probe(&target_addr, timeout, &self.our_fork_id)
+            .await
             .unwrap_or(FeelerResult::ConnectionFailed);
 
         // Update domain state
-        match &result {
-            FeelerResult::Success => self.state.on_probe_success(&target_addr),
-            _ => {
-                let should_remove = self.state.on_probe_failure(&target_addr);
-                if should_remove {
-                    // Caller should remove from New table
-                }
+        let evict = match &result {
+            FeelerResult::Success => {
+                self.state.on_probe_success(&target_addr);
+                false
             }
-        }
-
-        Some((target_addr, result))
+            _ => self.state.on_probe_failure(&target_addr),
+        };
+
+        Some(FeelerOutcome {
+            addr: target_addr,
+            node_id,
+            bucket: bucket_idx,
+            result,
+            evict,
+        })
     }
 
     /// Check for timed-out probes and mark them as failed.
@@ -117,4 +141,68 @@ impl<T: TimeSource, P: FeelerPort> FeelerCoordinator<T, P> {
     pub fn active_probe_count(&self) -> usize {
         self.state.active_probe_count()
     }
+
+    /// Probe New table addresses until `shutdown` completes.
+    ///
+    /// Every `tick` the coordinator settles timed-out probes and, once the
+    /// domain schedule allows, probes a random address from the stalest New
+    /// bucket. Compatible peers are promoted to Tried; addresses that reach
+    /// `max_failures` are dropped from New. The address manager lock is
+    /// never held across a probe.
+    pub async fn run<R, F>(
+        mut self,
+        addresses: Arc<Mutex<AddressManager>>,
+        random: R,
+        tick: Duration,
+        shutdown: F,
+    ) where
+        R: RandomSource,
+        F: Future<Output = ()>,
+    {
+        tokio::pin!(shutdown);
+
+        let mut ticker = tokio::time::interval(tick);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => return,
+                _ = ticker.tick() => {}
+            }
+
+            self.gc_timed_out();
+            let bucket_counts = lock(&addresses).new_bucket_counts();
+            let probe = self.maybe_probe(&bucket_counts, |bucket| {
+                let addresses = lock(&addresses);
+                let index = random.random_usize(bucket_counts[bucket]);
+                let entry = addresses.new_address_in_bucket(bucket, index)?;
+                Some((entry.peer_info.socket_addr, Some(entry.peer_info.node_id)))
+            });
+            let outcome = tokio::select! {
+                _ = &mut shutdown => return,
+                outcome = probe => outcome,
+            };
+
+            if let Some(outcome) = outcome {
+                let now = self.time_source.now();
+                apply_outcome(&mut lock(&addresses), &outcome, now);
+            }
+        }
+    }
+}
+
+/// Move a probed address between the address manager's tables.
+fn apply_outcome(addresses: &mut AddressManager, outcome: &FeelerOutcome, now: Timestamp) {
+    let Some(node_id) = outcome.node_id else {
+        return;
+    };
+    if outcome.result == FeelerResult::Success {
+        let _ = addresses.promote_to_tried(&node_id, now);
+    } else if outcome.evict {
+        addresses.remove_new(&node_id);
+    }
+}
+
+fn lock(addresses: &Mutex<AddressManager>) -> MutexGuard<'_, AddressManager> {
+    addresses.lock().unwrap_or_else(|e| e.into_inner())
 }
@@ -25,8 +25,9 @@ impl MockFeelerPort {
     }
 }
 
+#[async_trait::async_trait]
 impl FeelerPort for MockFeelerPort {
-    fn probe(
+    async fn probe(
         &self,
         addr: &SocketAddr,
         _timeout: Duration,
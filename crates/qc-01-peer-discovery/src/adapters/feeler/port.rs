@@ -9,6 +9,7 @@ use std::time::Duration;
 ///
 /// This port abstracts the network I/O required for feeler probing,
 /// allowing the domain to remain pure while adapters handle actual connections.
+#[async_trait::async_trait]
 pub trait FeelerPort: Send + Sync {
     /// Probe a peer address.
     ///
@@ -23,8 +24,9 @@ pub trait FeelerPort: Send + Sync {
     /// - `Ok(FeelerResult::Success)` if peer is reachable and compatible
     /// - `Ok(FeelerResult::ConnectionFailed)` if peer unreachable
     /// - `Ok(FeelerResult::WrongChain)` if ForkId mismatch
+    /// - `Ok(FeelerResult::TooFarBehind)` if peer's head is too far behind ours
     /// - `Err` on internal error
-    fn probe(
+    async fn probe(
         &self,
         addr: &SocketAddr,
         timeout: Duration,
@@ -1,59 +1,174 @@
 use super::port::{FeelerError, FeelerPort};
-use crate::domain::{feeler::FeelerResult, handshake::ForkId, SocketAddr};
+use crate::adapters::network::transport::to_std_addr;
+use crate::adapters::network::MessageType;
+use crate::domain::{
+    feeler::FeelerResult,
+    handshake::{ForkId, HandshakeConfig},
+    SocketAddr,
+};
+use crate::transport::{Frame, InboundRequest, QuicMesh};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
-#[cfg(feature = "quic")]
-use crate::transport::quic::QuicTransport;
+use tokio::sync::mpsc;
+
+// =============================================================================
+// STATUS MESSAGE
+// =============================================================================
+
+/// Payload of a STATUS frame: `[fork_hash(4)] [fork_next(8)] [head_height(8)]`,
+/// all big-endian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Status {
+    fork_id: ForkId,
+    head_height: u64,
+}
+
+impl Status {
+    const LEN: usize = 20;
+
+    fn encode(&self) -> Frame {
+        let mut payload = Vec::with_capacity(Self::LEN);
+        payload.extend_from_slice(&self.fork_id.hash.to_be_bytes());
+        payload.extend_from_slice(&self.fork_id.next.to_be_bytes());
+        payload.extend_from_slice(&self.head_height.to_be_bytes());
+        Frame::new(MessageType::Status as u8, payload)
+    }
+
+    /// `None` unless `frame` is a STATUS frame of exactly the right length.
+    fn decode(frame: &Frame) -> Option<Self> {
+        if frame.kind != MessageType::Status as u8 || frame.payload.len() != Self::LEN {
+            return None;
+        }
+        let p = &frame.payload;
+        Some(Self {
+            fork_id: ForkId::new(
+                u32::from_be_bytes(p[0..4].try_into().ok()?),
+                u64::from_be_bytes(p[4..12].try_into().ok()?),
+            ),
+            head_height: u64::from_be_bytes(p[12..20].try_into().ok()?),
+        })
+    }
+}
 
 // =============================================================================
 // QUIC FEELER ADAPTER (production)
 // =============================================================================
 
 /// Production feeler adapter using QUIC transport.
-#[cfg(feature = "quic")]
+///
+/// A probe dials the address (unless a connection is already open), sends
+/// our STATUS on a request stream and judges the peer's STATUS reply. The
+/// whole exchange runs under the probe timeout, and a connection opened
+/// for the probe is closed again afterwards.
 pub struct QuicFeelerPort {
-    /// Shared QUIC transport (owned by network layer)
-    /// In production this would be Arc<Mutex<QuicTransport>>
-    /// For now we keep it simple with Option
-    _marker: std::marker::PhantomData<QuicTransport>,
+    /// Shared mesh (owned by the network layer)
+    mesh: QuicMesh,
+    /// TLS name used when dialing peers
+    server_name: String,
+    /// Our current head height, kept up to date by the node
+    head_height: Arc<AtomicU64>,
+    /// Peers further behind our head than this are `TooFarBehind`
+    max_behind_blocks: u64,
 }
 
-#[cfg(feature = "quic")]
 impl QuicFeelerPort {
-    /// Create a new QUIC feeler port.
+    /// Create a QUIC feeler port over a shared mesh.
     ///
-    /// Note: In production, this would take an Arc<Mutex<QuicTransport>>
-    /// shared with the main network layer.
-    pub fn new() -> Self {
+    /// `server_name` is the TLS name used when dialing peers; `head_height`
+    /// is read on every probe.
+    pub fn new(
+        mesh: QuicMesh,
+        server_name: impl Into<String>,
+        head_height: Arc<AtomicU64>,
+    ) -> Self {
         Self {
-            _marker: std::marker::PhantomData,
+            mesh,
+            server_name: server_name.into(),
+            head_height,
+            max_behind_blocks: HandshakeConfig::default().max_behind_blocks,
         }
     }
-}
 
-#[cfg(feature = "quic")]
-impl Default for QuicFeelerPort {
-    fn default() -> Self {
-        Self::new()
+    /// Override how far behind our head a peer may be.
+    pub fn with_max_behind_blocks(mut self, max_behind_blocks: u64) -> Self {
+        self.max_behind_blocks = max_behind_blocks;
+        self
+    }
+
+    /// Judge a peer's STATUS reply against our own.
+    fn verdict(&self, theirs: &Status, our_fork_id: &ForkId, our_height: u64) -> FeelerResult {
+        if !our_fork_id.is_compatible(&theirs.fork_id, our_height) {
+            return FeelerResult::WrongChain;
+        }
+        if theirs.head_height.saturating_add(self.max_behind_blocks) < our_height {
+            return FeelerResult::TooFarBehind;
+        }
+        FeelerResult::Success
     }
 }
 
-#[cfg(feature = "quic")]
+#[async_trait::async_trait]
 impl FeelerPort for QuicFeelerPort {
-    fn probe(
+    async fn probe(
         &self,
-        _addr: &SocketAddr,
-        _timeout: Duration,
-        _our_fork_id: &ForkId,
+        addr: &SocketAddr,
+        timeout: Duration,
+        our_fork_id: &ForkId,
     ) -> Result<FeelerResult, FeelerError> {
-        // TODO: Implement actual QUIC probe
-        // 1. Connect to peer with timeout
-        // 2. Send STATUS message with our ForkId
-        // 3. Validate their ForkId response
-        // 4. Close connection
-        //
-        // For now, return success as placeholder
-        // This will be wired when async runtime integration is complete
-        Ok(FeelerResult::Success)
+        let remote = to_std_addr(*addr);
+        let our_height = self.head_height.load(Ordering::Relaxed);
+        let request = Status {
+            fork_id: *our_fork_id,
+            head_height: our_height,
+        }
+        .encode();
+
+        let was_connected = self.mesh.is_connected(&remote);
+        let exchange = async {
+            self.mesh.connect(remote, &self.server_name).await?;
+            self.mesh.request(remote, &request, timeout).await
+        };
+        let reply = tokio::time::timeout(timeout, exchange).await;
+        if !was_connected {
+            self.mesh.disconnect(&remote);
+        }
+
+        let reply = match reply {
+            Ok(Ok(reply)) => reply,
+            // Unreachable, refused, timed out or not serving STATUS
+            Ok(Err(_)) | Err(_) => return Ok(FeelerResult::ConnectionFailed),
+        };
+        // A reply that is not a STATUS is a peer speaking another protocol
+        Ok(match Status::decode(&reply) {
+            Some(theirs) => self.verdict(&theirs, our_fork_id, our_height),
+            None => FeelerResult::WrongChain,
+        })
+    }
+}
+
+/// Answer STATUS requests from `QuicMesh::serve_requests` until the mesh
+/// stops delivering them.
+///
+/// Replies carry `fork_id` and the current `head_height`, so other nodes'
+/// feelers can probe this one. Requests of other types are dropped, which
+/// the requester sees as a failed request.
+pub async fn serve_status(
+    mut requests: mpsc::Receiver<InboundRequest>,
+    fork_id: ForkId,
+    head_height: Arc<AtomicU64>,
+) {
+    while let Some(request) = requests.recv().await {
+        if request.frame.kind != MessageType::Status as u8 {
+            continue;
+        }
+        let reply = Status {
+            fork_id,
+            head_height: head_height.load(Ordering::Relaxed),
+        }
+        .encode();
+        // A requester that gave up is not our problem
+        let _ = request.respond(&reply).await;
     }
 }
@@ -1,20 +1,49 @@
 //! Tests for Feeler Adapter
 use super::*;
-use crate::domain::IpAddr;
+use crate::adapters::FixedRandomSource;
+use crate::domain::{
+    feeler::{FeelerConfig, FeelerResult},
+    handshake::ForkId,
+    AddressManager, AddressManagerConfig, IpAddr, NodeId, PeerInfo, SocketAddr, Timestamp,
+};
+use crate::ports::TimeSource;
 use crate::testing::FixedTimeSource;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 fn make_socket(port: u16) -> SocketAddr {
     SocketAddr::new(IpAddr::v4(192, 168, 1, 1), port)
 }
 
-#[test]
-fn test_mock_feeler_port() {
+/// Probe on every call: no interval, no jitter, one failure evicts.
+fn eager_config() -> FeelerConfig {
+    FeelerConfig {
+        probe_interval_secs: 0,
+        jitter_max_secs: 0,
+        connection_timeout_secs: 1,
+        max_failures: 1,
+        max_concurrent_probes: 1,
+    }
+}
+
+/// Advances one second per reading, so every bucket goes stale again.
+struct SteppingTimeSource(AtomicU64);
+
+impl TimeSource for SteppingTimeSource {
+    fn now(&self) -> Timestamp {
+        Timestamp::new(self.0.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[tokio::test]
+async fn test_mock_feeler_port() {
     let mut port = MockFeelerPort::new();
     let addr = make_socket(8080);
     port.set_result(addr, FeelerResult::Success);
 
     let fork_id = ForkId::new(0, 0);
-    let result = port.probe(&addr, Duration::from_secs(5), &fork_id);
+    let result = port.probe(&addr, Duration::from_secs(5), &fork_id).await;
 
     assert_eq!(result.unwrap(), FeelerResult::Success);
 }
@@ -32,6 +61,97 @@ fn test_feeler_coordinator_probe_scheduling() {
     assert_eq!(coordinator.active_probe_count(), 0);
 }
 
+#[tokio::test]
+async fn test_maybe_probe_visits_stalest_buckets() {
+    let mut port = MockFeelerPort::new();
+    port.set_result(make_socket(1), FeelerResult::Success);
+    let mut coordinator = FeelerCoordinator::new(
+        eager_config(),
+        port,
+        FixedTimeSource::new(1000),
+        ForkId::new(0, 0),
+    );
+    let counts = [0, 3, 1];
+
+    let first = coordinator
+        .maybe_probe(&counts, |bucket| Some((make_socket(bucket as u16), None)))
+        .await
+        .unwrap();
+    assert_eq!((first.bucket, first.result), (1, FeelerResult::Success));
+    assert!(!first.evict);
+
+    let second = coordinator
+        .maybe_probe(&counts, |bucket| Some((make_socket(bucket as u16), None)))
+        .await
+        .unwrap();
+    assert_eq!(second.bucket, 2);
+    assert_eq!(second.result, FeelerResult::ConnectionFailed);
+    assert!(second.evict);
+
+    // Both buckets were probed this second
+    let third = coordinator
+        .maybe_probe(&counts, |bucket| Some((make_socket(bucket as u16), None)))
+        .await;
+    assert_eq!(third, None);
+    assert_eq!(coordinator.active_probe_count(), 0);
+}
+
+#[tokio::test]
+async fn test_coordinator_run_promotes_and_evicts() {
+    let peer = |id: u8, third: u8| {
+        PeerInfo::new(
+            NodeId::new([id; 32]),
+            SocketAddr::new(IpAddr::v4(172, 16, third, 1), 30303),
+            Timestamp::new(1000),
+        )
+    };
+    let (good, bad) = (peer(1, 1), peer(2, 2));
+
+    let mut addresses = AddressManager::new(AddressManagerConfig::default());
+    let now = Timestamp::new(1000);
+    assert!(addresses
+        .add_new(good.clone(), &IpAddr::v4(10, 0, 1, 1), now)
+        .unwrap());
+    assert!(addresses
+        .add_new(bad.clone(), &IpAddr::v4(10, 0, 2, 1), now)
+        .unwrap());
+    let addresses = Arc::new(Mutex::new(addresses));
+
+    let mut port = MockFeelerPort::new();
+    port.set_result(good.socket_addr, FeelerResult::Success);
+    port.set_result(bad.socket_addr, FeelerResult::WrongChain);
+    let coordinator = FeelerCoordinator::new(
+        eager_config(),
+        port,
+        SteppingTimeSource(AtomicU64::new(1000)),
+        ForkId::new(0, 0),
+    );
+
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    let task = tokio::spawn(coordinator.run(
+        Arc::clone(&addresses),
+        FixedRandomSource::first(),
+        Duration::from_millis(5),
+        async move {
+            let _ = stopped.await;
+        },
+    ));
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while addresses.lock().unwrap().stats().new_count > 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("both addresses probed");
+    assert_eq!(addresses.lock().unwrap().stats().tried_count, 1);
+    let (_, tried) = addresses.lock().unwrap().entries();
+    assert_eq!(tried[0].peer_info.node_id, good.node_id);
+
+    stop.send(()).unwrap();
+    task.await.unwrap();
+}
+
 #[test]
 fn test_feeler_error_display() {
     let err = FeelerError::NetworkError {
@@ -39,3 +159,90 @@ fn test_feeler_error_display() {
     };
     assert!(err.to_string().contains("connection refused"));
 }
+
+#[cfg(feature = "quic")]
+mod quic {
+    use super::*;
+    use crate::adapters::network::transport::{from_std_addr, to_std_addr};
+    use crate::transport::{QuicConfig, QuicMesh, QuicTransport};
+
+    async fn mesh() -> (QuicMesh, SocketAddr) {
+        let mut transport = QuicTransport::new(QuicConfig::for_testing());
+        let addr = transport.bind().await.unwrap();
+        let (mesh, _inbound) = transport.into_mesh(8).unwrap();
+        (mesh, from_std_addr(addr))
+    }
+
+    /// A node answering STATUS with `fork_id` at `head_height`.
+    async fn status_peer(fork_id: ForkId, head_height: u64) -> (QuicMesh, SocketAddr) {
+        let (mesh, addr) = mesh().await;
+        let requests = mesh.serve_requests(8);
+        tokio::spawn(serve_status(
+            requests,
+            fork_id,
+            Arc::new(AtomicU64::new(head_height)),
+        ));
+        (mesh, addr)
+    }
+
+    async fn prober(head_height: u64) -> (QuicFeelerPort, QuicMesh) {
+        let (mesh, _) = mesh().await;
+        let port = QuicFeelerPort::new(
+            mesh.clone(),
+            "localhost",
+            Arc::new(AtomicU64::new(head_height)),
+        );
+        (port, mesh)
+    }
+
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    #[tokio::test]
+    async fn test_quic_probe_compatible_peer() {
+        let ours = ForkId::new(0xDEADBEEF, 0);
+        let (_peer, peer) = status_peer(ours, 500).await;
+        let (port, mesh) = prober(900).await;
+
+        let result = port.probe(&peer, TIMEOUT, &ours).await.unwrap();
+        assert_eq!(result, FeelerResult::Success);
+        // The connection opened for the probe is closed again
+        assert!(!mesh.is_connected(&to_std_addr(peer)));
+    }
+
+    #[tokio::test]
+    async fn test_quic_probe_rejects_wrong_chain_and_laggards() {
+        let ours = ForkId::new(0xDEADBEEF, 0);
+        let (port, _mesh) = prober(5_000).await;
+
+        let (_other, other_chain) = status_peer(ForkId::new(0xCAFEBABE, 0), 5_000).await;
+        let result = port.probe(&other_chain, TIMEOUT, &ours).await.unwrap();
+        assert_eq!(result, FeelerResult::WrongChain);
+
+        let (_laggard, laggard) = status_peer(ours, 3_000).await;
+        let result = port.probe(&laggard, TIMEOUT, &ours).await.unwrap();
+        assert_eq!(result, FeelerResult::TooFarBehind);
+
+        let port = port.with_max_behind_blocks(2_000);
+        let result = port.probe(&laggard, TIMEOUT, &ours).await.unwrap();
+        assert_eq!(result, FeelerResult::Success);
+    }
+
+    #[tokio::test]
+    async fn test_quic_probe_unreachable_or_silent_peer_fails() {
+        let ours = ForkId::new(0xDEADBEEF, 0);
+        let (port, _mesh) = prober(0).await;
+
+        // Reachable, but does not serve requests
+        let (silent, silent_addr) = mesh().await;
+        let result = port.probe(&silent_addr, TIMEOUT, &ours).await.unwrap();
+        assert_eq!(result, FeelerResult::ConnectionFailed);
+
+        // Nobody listening any more
+        silent.close();
+        let result = port
+            .probe(&silent_addr, Duration::from_millis(500), &ours)
+            .await
+            .unwrap();
+        assert_eq!(result, FeelerResult::ConnectionFailed);
+    }
+}
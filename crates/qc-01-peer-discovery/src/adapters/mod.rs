@@ -14,6 +14,7 @@
 //! | `network` | (always) | None for pure types, `network` for tokio |
 //! | `api_handler` | `rpc` | serde, serde_json |
 //! | `bootstrap_handler` | `bootstrap` | uuid |
+//! | `persistence` | (always) | None |
 
 // =============================================================================
 // NETWORK ADAPTERS (Pure Types Always Available)
@@ -25,12 +26,15 @@
 pub mod network;
 
 pub use network::{
-    NoOpNetworkSocket, NoOpNodeIdValidator, ProofOfWorkValidator, StaticConfigProvider,
+    NoOpNetworkSocket, NoOpNodeIdValidator, NoOpRelayPort, ProofOfWorkValidator, StaticConfigProvider,
     SystemTimeSource,
 };
 
 #[cfg(feature = "network")]
-pub use network::{ConfigError, MessageType, TomlConfigProvider, UdpNetworkSocket};
+pub use network::{ConfigError, MessageType, TokioUdpSocket, TomlConfigProvider, UdpNetworkSocket};
+
+#[cfg(feature = "quic")]
+pub use network::QuicNetworkSocket;
 
 // =============================================================================
 // IPC ADAPTERS (Requires `ipc` feature)
@@ -86,6 +90,15 @@ pub use security::{
     SlidingWindowRateLimiter,
 };
 
+// =============================================================================
+// PERSISTENCE ADAPTERS (Always Available)
+// =============================================================================
+
+/// Routing table persistence: file-backed `peers.dat` and in-memory store.
+pub mod persistence;
+
+pub use persistence::{FileRoutingTablePersistence, InMemoryRoutingTablePersistence};
+
 // =============================================================================
 // FEELER NETWORK ADAPTER (Requires `network` feature)
 // =============================================================================
@@ -97,7 +110,7 @@ pub use security::{
 pub mod feeler;
 
 #[cfg(feature = "network")]
-pub use feeler::{FeelerCoordinator, FeelerError, FeelerPort, MockFeelerPort};
+pub use feeler::{FeelerCoordinator, FeelerError, FeelerOutcome, FeelerPort, MockFeelerPort};
 
 #[cfg(feature = "quic")]
-pub use feeler::QuicFeelerPort;
+pub use feeler::{serve_status, QuicFeelerPort};
@@ -5,7 +5,10 @@
 //! ## Adapters Provided
 //!
 //! - `SystemTimeSource` - Production time source using system clock
+//! - `NoOpRelayPort` - Relay port stub for nodes without relays
 //! - `UdpNetworkSocket` - UDP-based network I/O (requires "network" feature)
+//! - `TokioUdpSocket` - Async UDP for `DiscoveryDriver` (requires "network" feature)
+//! - `QuicNetworkSocket` - Async discovery over a QUIC mesh (requires "quic" feature)
 //! - `TomlConfigProvider` - Config file loading (requires "network" feature)
 //!
 //! ## Feature Flags
@@ -30,13 +33,18 @@ pub mod transport;
 pub use config::StaticConfigProvider;
 pub use security::{NoOpNodeIdValidator, ProofOfWorkValidator};
 pub use time::SystemTimeSource;
-pub use transport::{MessageType, NoOpNetworkSocket};
+pub use transport::{
+    decode_message, encode_message, MessageType, NoOpNetworkSocket, NoOpRelayPort,
+};
 
 #[cfg(feature = "network")]
 pub use config::{ConfigError, TomlConfigProvider};
 
 #[cfg(feature = "network")]
-pub use transport::UdpNetworkSocket;
+pub use transport::{TokioUdpSocket, UdpNetworkSocket};
+
+#[cfg(feature = "quic")]
+pub use transport::QuicNetworkSocket;
 
 #[cfg(all(feature = "network", feature = "ipc"))]
 pub use security::parse_bootstrap_request;
@@ -91,6 +91,27 @@ fn test_proof_of_work_validator_partial_byte() {
     assert!(!validator.validate_node_id(NodeId::new(id)));
 }
 
+#[test]
+fn test_discovery_message_round_trip() {
+    use crate::ports::DiscoveryMessage;
+
+    let from = NodeId::new([7u8; 32]);
+    let search_id = NodeId::new([9u8; 32]);
+    for msg in [
+        DiscoveryMessage::Ping { from },
+        DiscoveryMessage::Pong { from },
+        DiscoveryMessage::FindNode { from, search_id },
+    ] {
+        assert_eq!(decode_message(&encode_message(&msg)), Some(msg));
+    }
+
+    // Wrong length or unknown type
+    let ping = encode_message(&DiscoveryMessage::Ping { from });
+    assert_eq!(decode_message(&ping[..32]), None);
+    assert_eq!(decode_message(&[MessageType::Nodes as u8; 33]), None);
+    assert_eq!(decode_message(&[]), None);
+}
+
 #[cfg(feature = "network")]
 mod network_tests {
     use super::*;
@@ -1,5 +1,5 @@
-use crate::domain::SocketAddr;
-use crate::ports::{NetworkError, NetworkSocket};
+use crate::domain::{NodeId, SocketAddr};
+use crate::ports::{DiscoveryMessage, NetworkError, NetworkSocket, RelayPort};
 
 // ============================================================================
 // NoOpNetworkSocket - Stub for testing without network
@@ -38,6 +38,47 @@ impl NetworkSocket for NoOpNetworkSocket {
     }
 }
 
+// ============================================================================
+// NoOpRelayPort - Stub for nodes that do not use relays
+// ============================================================================
+
+/// No-operation relay port for testing.
+///
+/// All operations succeed but don't send any actual packets.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoOpRelayPort;
+
+impl NoOpRelayPort {
+    /// Create a new no-op relay port.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RelayPort for NoOpRelayPort {
+    fn request_hole_punch(
+        &self,
+        _rendezvous: SocketAddr,
+        _target: crate::domain::NodeId,
+        _our_external: SocketAddr,
+    ) -> Result<(), NetworkError> {
+        Ok(())
+    }
+
+    fn reserve(&self, _relay: SocketAddr) -> Result<(), NetworkError> {
+        Ok(())
+    }
+
+    fn connect_via(
+        &self,
+        _relay: SocketAddr,
+        _target: crate::domain::NodeId,
+    ) -> Result<(), NetworkError> {
+        Ok(())
+    }
+}
+
 // ============================================================================
 // UdpNetworkSocket - Production UDP Socket (requires "network" feature)
 // ============================================================================
@@ -56,13 +97,99 @@ pub enum MessageType {
     Nodes = 0x04,
     /// Bootstrap request with identity proof.
     Bootstrap = 0x05,
+    /// STATUS exchange (ForkId and head height) used by feeler probes.
+    Status = 0x06,
+}
+
+/// Encode a discovery message in the UDP wire format.
+///
+/// - PING/PONG: `[type(1)] [from(32)]`
+/// - FIND_NODE: `[type(1)] [from(32)] [search_id(32)]`
+pub fn encode_message(msg: &DiscoveryMessage) -> Vec<u8> {
+    let (kind, from, search_id) = match msg {
+        DiscoveryMessage::Ping { from } => (MessageType::Ping, from, None),
+        DiscoveryMessage::Pong { from } => (MessageType::Pong, from, None),
+        DiscoveryMessage::FindNode { from, search_id } => {
+            (MessageType::FindNode, from, Some(search_id))
+        }
+    };
+
+    let mut buf = Vec::with_capacity(65);
+    buf.push(kind as u8);
+    buf.extend_from_slice(from.as_bytes());
+    if let Some(search_id) = search_id {
+        buf.extend_from_slice(search_id.as_bytes());
+    }
+    buf
+}
+
+/// Decode a PING, PONG or FIND_NODE datagram.
+///
+/// Returns `None` for other message types and for datagrams whose length
+/// does not match the type exactly.
+pub fn decode_message(data: &[u8]) -> Option<DiscoveryMessage> {
+    let read_id = |range: std::ops::Range<usize>| {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(data.get(range)?);
+        Some(NodeId::new(bytes))
+    };
+
+    match (data.first().copied()?, data.len()) {
+        (t, 33) if t == MessageType::Ping as u8 => Some(DiscoveryMessage::Ping {
+            from: read_id(1..33)?,
+        }),
+        (t, 33) if t == MessageType::Pong as u8 => Some(DiscoveryMessage::Pong {
+            from: read_id(1..33)?,
+        }),
+        (t, 65) if t == MessageType::FindNode as u8 => Some(DiscoveryMessage::FindNode {
+            from: read_id(1..33)?,
+            search_id: read_id(33..65)?,
+        }),
+        _ => None,
+    }
+}
+
+/// Map an I/O error from a send to the port error.
+#[cfg(feature = "network")]
+fn network_error(e: &std::io::Error) -> NetworkError {
+    match e.kind() {
+        std::io::ErrorKind::WouldBlock => NetworkError::Timeout,
+        std::io::ErrorKind::ConnectionRefused => NetworkError::ConnectionRefused,
+        std::io::ErrorKind::InvalidInput => NetworkError::InvalidAddress,
+        _ => NetworkError::Timeout,
+    }
+}
+
+/// Convert domain SocketAddr to std::net::SocketAddr.
+#[cfg(feature = "network")]
+pub(crate) fn to_std_addr(addr: SocketAddr) -> std::net::SocketAddr {
+    use crate::domain::IpAddr;
+    let ip = match addr.ip {
+        IpAddr::V4(bytes) => std::net::IpAddr::V4(std::net::Ipv4Addr::from(bytes)),
+        IpAddr::V6(bytes) => std::net::IpAddr::V6(std::net::Ipv6Addr::from(bytes)),
+    };
+    std::net::SocketAddr::new(ip, addr.port)
+}
+
+/// Convert std::net::SocketAddr to domain SocketAddr.
+///
+/// IPv4-mapped IPv6 addresses come back as plain IPv4.
+#[cfg(feature = "network")]
+pub(crate) fn from_std_addr(addr: std::net::SocketAddr) -> SocketAddr {
+    use crate::domain::IpAddr;
+    let addr = crate::transport::canonical_addr(addr);
+    let ip = match addr.ip() {
+        std::net::IpAddr::V4(v4) => IpAddr::V4(v4.octets()),
+        std::net::IpAddr::V6(v6) => IpAddr::V6(v6.octets()),
+    };
+    SocketAddr::new(ip, addr.port())
 }
 
 #[cfg(feature = "network")]
 mod udp_socket {
     use super::*;
-    use crate::domain::{IpAddr, NodeId};
-    use std::net::UdpSocket as StdUdpSocket;
+    use crate::transport::{bind_udp, send_addr};
+    use std::net::{ToSocketAddrs, UdpSocket as StdUdpSocket};
     use std::sync::Arc;
 
     /// UDP-based network socket for Kademlia protocol.
@@ -93,14 +220,19 @@ mod udp_socket {
         ///
         /// # Arguments
         ///
-        /// * `bind_addr` - Local address to bind (e.g., "0.0.0.0:8080")
+        /// * `bind_addr` - Local address to bind (e.g., "[::]:8080")
         /// * `local_node_id` - Our NodeId to include in messages
         ///
+        /// IPv6 addresses bind dual-stack, so `[::]` serves IPv4 peers too.
+        ///
         /// # Errors
         ///
         /// Returns error if socket binding fails.
         pub fn bind(bind_addr: &str, local_node_id: NodeId) -> std::io::Result<Self> {
-            let socket = StdUdpSocket::bind(bind_addr)?;
+            let addr = bind_addr.to_socket_addrs()?.next().ok_or_else(|| {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, "no bind address")
+            })?;
+            let socket = bind_udp(addr)?;
             socket.set_nonblocking(true)?;
             Ok(Self {
                 socket: Arc::new(socket),
@@ -113,41 +245,23 @@ mod udp_socket {
             self.socket.local_addr()
         }
 
-        /// Convert domain IpAddr to std::net::IpAddr.
-        fn to_std_ip(ip: IpAddr) -> std::net::IpAddr {
-            match ip {
-                IpAddr::V4(bytes) => std::net::IpAddr::V4(std::net::Ipv4Addr::from(bytes)),
-                IpAddr::V6(bytes) => std::net::IpAddr::V6(std::net::Ipv6Addr::from(bytes)),
-            }
-        }
-
-        /// Convert domain SocketAddr to std::net::SocketAddr.
-        fn to_std_addr(addr: SocketAddr) -> std::net::SocketAddr {
-            std::net::SocketAddr::new(Self::to_std_ip(addr.ip), addr.port)
-        }
-
         /// Send raw bytes to a target address.
         fn send_to(&self, data: &[u8], target: SocketAddr) -> Result<(), NetworkError> {
-            let std_addr = Self::to_std_addr(target);
-            match self.socket.send_to(data, std_addr) {
-                Ok(_n) => Ok(()),
-                Err(e) => match e.kind() {
-                    std::io::ErrorKind::WouldBlock => Err(NetworkError::Timeout),
-                    std::io::ErrorKind::ConnectionRefused => Err(NetworkError::ConnectionRefused),
-                    std::io::ErrorKind::InvalidInput => Err(NetworkError::InvalidAddress),
-                    _ => Err(NetworkError::Timeout),
-                },
+            let mut std_addr = to_std_addr(target);
+            if let Ok(local) = self.socket.local_addr() {
+                std_addr = send_addr(local, std_addr);
             }
+            self.socket
+                .send_to(data, std_addr)
+                .map(|_| ())
+                .map_err(|e| network_error(&e))
         }
     }
 
     impl NetworkSocket for UdpNetworkSocket {
         fn send_ping(&self, target: SocketAddr) -> Result<(), NetworkError> {
-            // Wire format: [type(1)] [our_node_id(32)]
-            let mut msg = [0u8; 33];
-            msg[0] = MessageType::Ping as u8;
-            msg[1..33].copy_from_slice(self.local_node_id.as_bytes());
-            self.send_to(&msg, target)
+            let from = self.local_node_id;
+            self.send_to(&encode_message(&DiscoveryMessage::Ping { from }), target)
         }
 
         fn send_find_node(
@@ -155,20 +269,14 @@ mod udp_socket {
             target: SocketAddr,
             search_id: NodeId,
         ) -> Result<(), NetworkError> {
-            // Wire format: [type(1)] [our_node_id(32)] [search_id(32)]
-            let mut msg = [0u8; 65];
-            msg[0] = MessageType::FindNode as u8;
-            msg[1..33].copy_from_slice(self.local_node_id.as_bytes());
-            msg[33..65].copy_from_slice(search_id.as_bytes());
-            self.send_to(&msg, target)
+            let from = self.local_node_id;
+            let msg = DiscoveryMessage::FindNode { from, search_id };
+            self.send_to(&encode_message(&msg), target)
         }
 
         fn send_pong(&self, target: SocketAddr) -> Result<(), NetworkError> {
-            // Wire format: [type(1)] [our_node_id(32)]
-            let mut msg = [0u8; 33];
-            msg[0] = MessageType::Pong as u8;
-            msg[1..33].copy_from_slice(self.local_node_id.as_bytes());
-            self.send_to(&msg, target)
+            let from = self.local_node_id;
+            self.send_to(&encode_message(&DiscoveryMessage::Pong { from }), target)
         }
     }
 
@@ -185,3 +293,212 @@ mod udp_socket {
 
 #[cfg(feature = "network")]
 pub use udp_socket::UdpNetworkSocket;
+
+// ============================================================================
+// TokioUdpSocket - Async UDP Socket (requires "network" feature)
+// ============================================================================
+
+#[cfg(feature = "network")]
+mod tokio_udp_socket {
+    use super::*;
+    use crate::ports::AsyncNetworkSocket;
+    use crate::transport::{bind_udp, send_addr};
+
+    /// Largest datagram `recv` reads; longer ones are truncated and dropped.
+    const MAX_DATAGRAM: usize = 1280;
+
+    /// Tokio UDP socket implementing the `AsyncNetworkSocket` port.
+    ///
+    /// Speaks the same wire protocol as `UdpNetworkSocket`, but sends and
+    /// receives without blocking the runtime.
+    pub struct TokioUdpSocket {
+        socket: tokio::net::UdpSocket,
+        local_node_id: NodeId,
+    }
+
+    impl TokioUdpSocket {
+        /// Bind to a local address.
+        ///
+        /// IPv6 addresses bind dual-stack, so `[::]` serves IPv4 peers too.
+        /// Must be called from within a Tokio runtime.
+        ///
+        /// # Errors
+        ///
+        /// Returns error if socket binding fails.
+        pub fn bind(
+            bind_addr: std::net::SocketAddr,
+            local_node_id: NodeId,
+        ) -> std::io::Result<Self> {
+            let socket = bind_udp(bind_addr)?;
+            socket.set_nonblocking(true)?;
+            Ok(Self {
+                socket: tokio::net::UdpSocket::from_std(socket)?,
+                local_node_id,
+            })
+        }
+
+        /// Get the local address the socket is bound to.
+        pub fn local_addr(&self) -> std::io::Result<std::net::SocketAddr> {
+            self.socket.local_addr()
+        }
+
+        async fn send(
+            &self,
+            msg: DiscoveryMessage,
+            target: SocketAddr,
+        ) -> Result<(), NetworkError> {
+            let mut std_addr = to_std_addr(target);
+            if let Ok(local) = self.socket.local_addr() {
+                std_addr = send_addr(local, std_addr);
+            }
+            self.socket
+                .send_to(&encode_message(&msg), std_addr)
+                .await
+                .map(|_| ())
+                .map_err(|e| network_error(&e))
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AsyncNetworkSocket for TokioUdpSocket {
+        async fn send_ping(&self, target: SocketAddr) -> Result<(), NetworkError> {
+            let from = self.local_node_id;
+            self.send(DiscoveryMessage::Ping { from }, target).await
+        }
+
+        async fn send_find_node(
+            &self,
+            target: SocketAddr,
+            search_id: NodeId,
+        ) -> Result<(), NetworkError> {
+            let from = self.local_node_id;
+            self.send(DiscoveryMessage::FindNode { from, search_id }, target)
+                .await
+        }
+
+        async fn send_pong(&self, target: SocketAddr) -> Result<(), NetworkError> {
+            let from = self.local_node_id;
+            self.send(DiscoveryMessage::Pong { from }, target).await
+        }
+
+        async fn recv(&self) -> Result<(SocketAddr, DiscoveryMessage), NetworkError> {
+            let mut buf = [0u8; MAX_DATAGRAM];
+            loop {
+                let (len, from) = match self.socket.recv_from(&mut buf).await {
+                    Ok(received) => received,
+                    // ICMP errors from earlier sends surface here on some platforms
+                    Err(e) if e.kind() == std::io::ErrorKind::ConnectionReset => continue,
+                    Err(e) => return Err(network_error(&e)),
+                };
+                if let Some(msg) = decode_message(&buf[..len]) {
+                    return Ok((from_std_addr(from), msg));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+pub use tokio_udp_socket::TokioUdpSocket;
+
+// ============================================================================
+// QuicNetworkSocket - Async socket over a QUIC mesh (requires "quic" feature)
+// ============================================================================
+
+#[cfg(feature = "quic")]
+mod quic_socket {
+    use super::*;
+    use crate::ports::AsyncNetworkSocket;
+    use crate::transport::{InboundMessage, QuicMesh};
+    use tokio::sync::{mpsc, Mutex};
+
+    /// `AsyncNetworkSocket` over a `QuicMesh`.
+    ///
+    /// Each message is one QUIC stream carrying the UDP wire format. Peers
+    /// without an open connection are dialed before the first send.
+    pub struct QuicNetworkSocket {
+        mesh: QuicMesh,
+        inbound: Mutex<mpsc::Receiver<InboundMessage>>,
+        server_name: String,
+        local_node_id: NodeId,
+    }
+
+    impl QuicNetworkSocket {
+        /// Wrap a mesh and the receiver returned by `QuicTransport::into_mesh`.
+        ///
+        /// `server_name` is the TLS name used when dialing peers.
+        pub fn new(
+            mesh: QuicMesh,
+            inbound: mpsc::Receiver<InboundMessage>,
+            server_name: impl Into<String>,
+            local_node_id: NodeId,
+        ) -> Self {
+            Self {
+                mesh,
+                inbound: Mutex::new(inbound),
+                server_name: server_name.into(),
+                local_node_id,
+            }
+        }
+
+        /// The underlying mesh.
+        pub fn mesh(&self) -> &QuicMesh {
+            &self.mesh
+        }
+
+        async fn send(
+            &self,
+            msg: DiscoveryMessage,
+            target: SocketAddr,
+        ) -> Result<(), NetworkError> {
+            let remote = to_std_addr(target);
+            self.mesh
+                .connect(remote, &self.server_name)
+                .await
+                .map_err(|_| NetworkError::ConnectionRefused)?;
+            self.mesh
+                .send(remote, &encode_message(&msg))
+                .await
+                .map_err(|_| NetworkError::ConnectionRefused)
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AsyncNetworkSocket for QuicNetworkSocket {
+        async fn send_ping(&self, target: SocketAddr) -> Result<(), NetworkError> {
+            let from = self.local_node_id;
+            self.send(DiscoveryMessage::Ping { from }, target).await
+        }
+
+        async fn send_find_node(
+            &self,
+            target: SocketAddr,
+            search_id: NodeId,
+        ) -> Result<(), NetworkError> {
+            let from = self.local_node_id;
+            self.send(DiscoveryMessage::FindNode { from, search_id }, target)
+                .await
+        }
+
+        async fn send_pong(&self, target: SocketAddr) -> Result<(), NetworkError> {
+            let from = self.local_node_id;
+            self.send(DiscoveryMessage::Pong { from }, target).await
+        }
+
+        async fn recv(&self) -> Result<(SocketAddr, DiscoveryMessage), NetworkError> {
+            let mut inbound = self.inbound.lock().await;
+            loop {
+                let (from, data) = inbound
+                    .recv()
+                    .await
+                    .ok_or(NetworkError::ConnectionRefused)?;
+                if let Some(msg) = decode_message(&data) {
+                    return Ok((from_std_addr(from), msg));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "quic")]
+pub use quic_socket::QuicNetworkSocket;
@@ -0,0 +1,82 @@
+//! # Routing Table Persistence Adapters
+//!
+//! Implements `RoutingTablePersistence`:
+//!
+//! | Adapter | Use |
+//! |---------|-----|
+//! | `FileRoutingTablePersistence` | Production: `peers.dat` in the data directory |
+//! | `InMemoryRoutingTablePersistence` | Testing |
+
+use crate::domain::RoutingTableSnapshot;
+use crate::ports::RoutingTablePersistence;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Snapshot stored in a single file, replaced atomically on save.
+#[derive(Debug, Clone)]
+pub struct FileRoutingTablePersistence {
+    path: PathBuf,
+}
+
+impl FileRoutingTablePersistence {
+    /// Persist to `path` (conventionally `<data_dir>/peers.dat`).
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The snapshot file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl RoutingTablePersistence for FileRoutingTablePersistence {
+    fn load(&self) -> io::Result<Option<RoutingTableSnapshot>> {
+        match std::fs::read(&self.path) {
+            Ok(data) => RoutingTableSnapshot::decode(&data).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write through a temporary file so a crash never leaves a torn snapshot.
+    fn save(&self, snapshot: &RoutingTableSnapshot) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, snapshot.encode())?;
+        std::fs::rename(&tmp, &self.path)
+    }
+}
+
+/// Snapshot kept in memory, for tests.
+#[derive(Debug, Default)]
+pub struct InMemoryRoutingTablePersistence {
+    data: Mutex<Option<Vec<u8>>>,
+}
+
+impl InMemoryRoutingTablePersistence {
+    /// Empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RoutingTablePersistence for InMemoryRoutingTablePersistence {
+    fn load(&self) -> io::Result<Option<RoutingTableSnapshot>> {
+        let data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        data.as_deref()
+            .map(RoutingTableSnapshot::decode)
+            .transpose()
+    }
+
+    fn save(&self, snapshot: &RoutingTableSnapshot) -> io::Result<()> {
+        *self.data.lock().unwrap_or_else(|e| e.into_inner()) = Some(snapshot.encode());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
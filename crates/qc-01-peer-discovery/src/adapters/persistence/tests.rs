@@ -0,0 +1,48 @@
+//! Tests for Routing Table Persistence Adapters
+use super::*;
+use crate::domain::{IpAddr, NodeId, PeerInfo, SocketAddr, Timestamp};
+
+fn make_snapshot() -> RoutingTableSnapshot {
+    RoutingTableSnapshot {
+        saved_at: Timestamp::new(5000),
+        peers: vec![PeerInfo::new(
+            NodeId::new([7u8; 32]),
+            SocketAddr::new(IpAddr::v4(10, 1, 2, 3), 30303),
+            Timestamp::new(4000),
+        )],
+        new_addresses: Vec::new(),
+        tried_addresses: Vec::new(),
+        bans: Vec::new(),
+        scores: Vec::new(),
+    }
+}
+
+#[test]
+fn test_file_persistence_round_trip() {
+    let dir = std::env::temp_dir().join(format!("qc01-persistence-{}", std::process::id()));
+    let persistence = FileRoutingTablePersistence::new(dir.join("peers.dat"));
+
+    // Nothing saved yet
+    assert_eq!(persistence.load().unwrap(), None);
+
+    let snapshot = make_snapshot();
+    persistence.save(&snapshot).unwrap();
+    assert_eq!(persistence.load().unwrap(), Some(snapshot));
+    assert!(!persistence.path().with_extension("tmp").exists());
+
+    // A foreign file is an error, not an empty table
+    std::fs::write(persistence.path(), b"QCMPOOL\x01").unwrap();
+    assert!(persistence.load().is_err());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_in_memory_persistence_round_trip() {
+    let persistence = InMemoryRoutingTablePersistence::new();
+    assert_eq!(persistence.load().unwrap(), None);
+
+    let snapshot = make_snapshot();
+    persistence.save(&snapshot).unwrap();
+    assert_eq!(persistence.load().unwrap(), Some(snapshot));
+}
@@ -1,4 +1,4 @@
-use crate::domain::NodeId;
+use crate::domain::{EnrCache, NodeId, NodeRecord};
 use crate::ipc::security::{SecurityError, SubsystemId};
 use crate::ports::inbound::VerificationHandler;
 
@@ -43,12 +43,28 @@ pub fn validate_identity_result_sender(sender_id: u8) -> Result<(), SecurityErro
 pub struct EventHandler<S> {
     /// The peer discovery service to route events to.
     service: S,
+    /// Cache for the ENRs of verified peers.
+    enr_cache: Option<EnrCache>,
 }
 
 impl<S> EventHandler<S> {
     /// Create a new event handler.
     pub fn new(service: S) -> Self {
-        Self { service }
+        Self {
+            service,
+            enr_cache: None,
+        }
+    }
+
+    /// Store the ENRs of verified peers in `cache`.
+    pub fn with_enr_cache(mut self, cache: EnrCache) -> Self {
+        self.enr_cache = Some(cache);
+        self
+    }
+
+    /// The ENR cache, if one is set.
+    pub fn enr_cache(&self) -> Option<&EnrCache> {
+        self.enr_cache.as_ref()
     }
 
     /// Get a reference to the inner service.
@@ -79,12 +95,24 @@ impl<S: VerificationHandler> PeerDiscoveryEventSubscriber for EventHandler<S> {
         // Convert raw bytes to domain NodeId type
         let node_id = NodeId::new(result.node_id);
 
+        // SECURITY: a presented ENR must be self-signed and describe this node,
+        // otherwise the peer is rejected even if its identity checked out
+        let identity_valid = result.identity_valid
+            && result
+                .record
+                .as_ref()
+                .is_none_or(|record| record_matches(record, &node_id));
+
         // SPEC-01 Section 4.3: Identity result triggers routing table state transition
         // This is the core EDA action - process the event and return the outcome
-        match self
-            .service
-            .handle_verification(&node_id, result.identity_valid)
-        {
+        let outcome = self.service.handle_verification(&node_id, identity_valid);
+        if identity_valid && outcome.is_ok() {
+            if let (Some(cache), Some(record)) = (self.enr_cache.as_mut(), result.record) {
+                cache.insert(record, result.verification_timestamp);
+            }
+        }
+
+        match outcome {
             Ok(Some(challenged_peer)) => {
                 // Bucket was full, need to challenge existing peer
                 // This outcome can trigger a PING event via the publisher
@@ -95,7 +123,7 @@ impl<S: VerificationHandler> PeerDiscoveryEventSubscriber for EventHandler<S> {
             }
             Ok(None) => {
                 // Successfully processed
-                if result.identity_valid {
+                if identity_valid {
                     Ok(VerificationOutcome::PeerPromoted {
                         node_id: result.node_id,
                     })
@@ -115,3 +143,8 @@ impl<S: VerificationHandler> PeerDiscoveryEventSubscriber for EventHandler<S> {
         }
     }
 }
+
+/// True if `record` has a valid signature and belongs to `node_id`.
+fn record_matches(record: &NodeRecord, node_id: &NodeId) -> bool {
+    record.verify_signature() && &record.node_id() == node_id
+}
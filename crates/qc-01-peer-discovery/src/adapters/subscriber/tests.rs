@@ -32,6 +32,7 @@ fn test_node_identity_verification_result() {
         node_id: [1u8; 32],
         identity_valid: true,
         verification_timestamp: 1000,
+        record: None,
     };
     assert!(result.identity_valid);
     assert_eq!(result.verification_timestamp, 1000);
@@ -79,7 +80,8 @@ fn test_subscription_error_from_security_error() {
 // ========================================================================
 
 use crate::domain::{
-    IpAddr, KademliaConfig, PeerDiscoveryError, PeerInfo, RoutingTable, SocketAddr, Timestamp,
+    Capability, EnrCache, EnrConfig, IpAddr, KademliaConfig, NodeRecord, NodeRecordConfig,
+    PeerDiscoveryError, PeerInfo, PublicKey, RoutingTable, SocketAddr, Timestamp,
 };
 
 /// Mock service that implements VerificationHandler for testing
@@ -133,6 +135,7 @@ fn test_event_handler_rejects_unauthorized_sender() {
             node_id: [1u8; 32],
             identity_valid: true,
             verification_timestamp: 1000,
+            record: None,
         },
     );
 
@@ -157,6 +160,7 @@ fn verify_scenario(
                 node_id: [node_id_byte; 32],
                 identity_valid: valid,
                 verification_timestamp: 1000,
+                record: None,
             },
         )
         .expect("Handler execution failed"); // Unwrap here for test helper simplicity
@@ -204,6 +208,7 @@ fn test_event_handler_returns_not_found_for_unknown_peer() {
             node_id: [99u8; 32], // Unknown peer
             identity_valid: true,
             verification_timestamp: 1000,
+            record: None,
         },
     );
 
@@ -222,3 +227,89 @@ fn test_event_handler_into_service_consumes_handler() {
     let recovered_service = handler.into_service();
     assert_eq!(recovered_service.peer_count(), 0);
 }
+
+fn signed_record() -> NodeRecord {
+    let mut pubkey = [0u8; 33];
+    pubkey[0] = 0x02;
+    let mut record = NodeRecord::new_unsigned(NodeRecordConfig {
+        seq: 1,
+        pubkey: PublicKey::new(pubkey),
+        ip: IpAddr::v4(192, 168, 1, 1),
+        udp_port: 8080,
+        tcp_port: 8080,
+        capabilities: vec![Capability::full_node()],
+    });
+    assert!(record.sign(&[7u8; 32]));
+    record
+}
+
+fn verify_with_record(
+    record: NodeRecord,
+) -> (VerificationOutcome, EventHandler<MockVerificationService>) {
+    let node_id = *record.node_id().as_bytes();
+    let mut service = MockVerificationService::new();
+    service.stage_peer(node_id);
+    let mut handler =
+        EventHandler::new(service).with_enr_cache(EnrCache::new(EnrConfig::default()));
+
+    let result = handler
+        .on_node_identity_result(
+            10,
+            NodeIdentityVerificationResult {
+                node_id,
+                identity_valid: true,
+                verification_timestamp: 1000,
+                record: Some(record),
+            },
+        )
+        .expect("Handler execution failed");
+
+    (result, handler)
+}
+
+#[test]
+fn test_event_handler_caches_valid_record() {
+    let record = signed_record();
+    let node_id = record.node_id();
+
+    let (result, handler) = verify_with_record(record);
+
+    assert!(matches!(result, VerificationOutcome::PeerPromoted { .. }));
+    assert!(handler.enr_cache().unwrap().get(&node_id).is_some());
+    assert_eq!(handler.into_service().peer_count(), 1);
+}
+
+#[test]
+fn test_event_handler_rejects_peer_with_forged_record() {
+    let mut record = signed_record();
+    record.udp_port = 9999; // Changed after signing
+
+    let (result, handler) = verify_with_record(record);
+
+    assert!(matches!(result, VerificationOutcome::PeerRejected { .. }));
+    assert!(handler.enr_cache().unwrap().is_empty());
+    assert_eq!(handler.into_service().peer_count(), 0);
+}
+
+#[test]
+fn test_event_handler_rejects_record_for_other_node() {
+    let record = signed_record();
+    let mut service = MockVerificationService::new();
+    service.stage_peer([1u8; 32]);
+    let mut handler = EventHandler::new(service);
+
+    let result = handler.on_node_identity_result(
+        10,
+        NodeIdentityVerificationResult {
+            node_id: [1u8; 32],
+            identity_valid: true,
+            verification_timestamp: 1000,
+            record: Some(record),
+        },
+    );
+
+    assert!(matches!(
+        result,
+        Ok(VerificationOutcome::PeerRejected { node_id }) if node_id == [1u8; 32]
+    ));
+}
@@ -1,3 +1,4 @@
+use crate::domain::NodeRecord;
 use crate::ipc::security::SecurityError;
 
 /// Response from Subsystem 10 for node identity verification.
@@ -9,6 +10,8 @@ pub struct NodeIdentityVerificationResult {
     pub identity_valid: bool,
     /// Timestamp of verification.
     pub verification_timestamp: u64,
+    /// ENR the peer presented during the handshake, if any.
+    pub record: Option<NodeRecord>,
 }
 
 /// Outcome of processing a verification result.
@@ -19,7 +22,7 @@ pub enum VerificationOutcome {
         /// The promoted peer's node ID.
         node_id: [u8; 32],
     },
-    /// Peer was rejected (invalid signature).
+    /// Peer was rejected (invalid identity or ENR signature).
     PeerRejected {
         /// The rejected peer's node ID.
         node_id: [u8; 32],
@@ -51,19 +51,7 @@ impl AddressManager {
         let source_subnet = SubnetKey::from_ip(source_ip);
         let addr_subnet = SubnetKey::from_ip(&peer_info.socket_addr.ip);
 
-        let total_count = self
-            .new_table
-            .subnet_totals
-            .get(&addr_subnet)
-            .copied()
-            .unwrap_or(0)
-            + self
-                .tried_table
-                .subnet_totals
-                .get(&addr_subnet)
-                .copied()
-                .unwrap_or(0);
-        if total_count >= self.config.max_per_subnet_total {
+        if self.subnet_total(&addr_subnet) >= self.config.max_per_subnet_total {
             return Ok(false);
         }
 
@@ -137,6 +125,52 @@ impl AddressManager {
         Ok(true)
     }
 
+    /// Every address in the New and Tried tables, for persistence.
+    pub fn entries(&self) -> (Vec<AddressEntry>, Vec<AddressEntry>) {
+        let collect = |table: &AddressTable| {
+            table
+                .buckets
+                .iter()
+                .flat_map(|b| b.entries().iter().cloned())
+                .collect()
+        };
+        (collect(&self.new_table), collect(&self.tried_table))
+    }
+
+    /// Put back a persisted address, keeping its connection history.
+    ///
+    /// Buckets are recomputed rather than restored, and the same bucket and
+    /// subnet limits as for fresh addresses apply. Returns whether the
+    /// address was kept.
+    pub fn restore_entry(&mut self, entry: AddressEntry, tried: bool) -> bool {
+        let node_id = entry.peer_info.node_id;
+        if self.tried_table.contains(&node_id) || self.new_table.contains(&node_id) {
+            return false;
+        }
+
+        let addr_subnet = SubnetKey::from_ip(&entry.peer_info.socket_addr.ip);
+        if self.subnet_total(&addr_subnet) >= self.config.max_per_subnet_total {
+            return false;
+        }
+
+        let (table, bucket_idx) = if tried {
+            let idx = self.calculate_tried_bucket(&entry.peer_info.socket_addr);
+            (&mut self.tried_table, idx)
+        } else {
+            let idx = self.calculate_new_bucket(&entry.source_subnet, &addr_subnet);
+            (&mut self.new_table, idx)
+        };
+        let bucket = &mut table.buckets[bucket_idx];
+        if !bucket.can_accept(&addr_subnet, &self.config) {
+            return false;
+        }
+
+        bucket.add(entry);
+        *table.subnet_totals.entry(addr_subnet).or_insert(0) += 1;
+        table.node_to_bucket.insert(node_id, bucket_idx);
+        true
+    }
+
     /// Get a random address from the New table.
     #[allow(deprecated)]
     pub fn random_new_address(&self) -> Option<&AddressEntry> {
@@ -165,6 +199,36 @@ impl AddressManager {
         self.tried_table.random_entry_with(random_fn)
     }
 
+    /// Number of addresses in each New table bucket.
+    pub fn new_bucket_counts(&self) -> Vec<usize> {
+        self.new_table.buckets.iter().map(|b| b.len()).collect()
+    }
+
+    /// Get an address from one New table bucket using an externally-provided
+    /// random index.
+    pub fn new_address_in_bucket(
+        &self,
+        bucket_idx: usize,
+        random_index: usize,
+    ) -> Option<&AddressEntry> {
+        self.new_table
+            .buckets
+            .get(bucket_idx)?
+            .random_entry_at(random_index)
+    }
+
+    /// Drop an address from the New table, e.g. after repeated failed probes.
+    pub fn remove_new(&mut self, node_id: &NodeId) -> Option<AddressEntry> {
+        let bucket_idx = self.new_table.node_to_bucket.remove(node_id)?;
+        let entry = self.new_table.buckets[bucket_idx].remove(node_id)?;
+
+        let addr_subnet = SubnetKey::from_ip(&entry.peer_info.socket_addr.ip);
+        if let Some(count) = self.new_table.subnet_totals.get_mut(&addr_subnet) {
+            *count = count.saturating_sub(1);
+        }
+        Some(entry)
+    }
+
     /// Get statistics
     pub fn stats(&self) -> AddressManagerStats {
         AddressManagerStats {
@@ -175,6 +239,21 @@ impl AddressManager {
         }
     }
 
+    /// Addresses from `subnet` across both tables
+    fn subnet_total(&self, subnet: &SubnetKey) -> usize {
+        self.new_table
+            .subnet_totals
+            .get(subnet)
+            .copied()
+            .unwrap_or(0)
+            + self
+                .tried_table
+                .subnet_totals
+                .get(subnet)
+                .copied()
+                .unwrap_or(0)
+    }
+
     /// Calculate New table bucket index
     ///
     /// # Security
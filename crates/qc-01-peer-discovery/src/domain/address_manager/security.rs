@@ -14,6 +14,7 @@ use std::hash::{Hash, Hasher};
 /// Used to ensure we don't accept too many peers from the same IP range.
 /// IPv4 uses /16 (first 2 bytes) to group by ISP/organization.
 /// IPv6 uses /32 (first 4 bytes) as minimum to differentiate organizations.
+/// IPv4-mapped IPv6 addresses are keyed as IPv4.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SubnetKey(pub [u8; 4]);
 
@@ -23,8 +24,11 @@ impl SubnetKey {
     /// # Security Note
     /// IPv4: /16 subnet (2 bytes) - groups by ISP/organization
     /// IPv6: /32 subnet (4 bytes) - minimum for org differentiation
+    ///
+    /// Without canonicalization every IPv4 peer reaching a dual-stack
+    /// socket would share the `::/32` key and crowd out the rest.
     pub fn from_ip(ip: &IpAddr) -> Self {
-        match ip {
+        match ip.to_canonical() {
             IpAddr::V4(bytes) => {
                 // IPv4: use /16 (first 2 bytes). Pad rest with 0.
                 SubnetKey([bytes[0], bytes[1], 0, 0])
@@ -148,3 +148,59 @@ fn test_random_tried_address_after_promotion() {
     assert!(random_new.is_none()); // Moved out of New
     assert!(random_tried.is_some()); // Now in Tried
 }
+
+#[test]
+fn test_new_bucket_lookup_and_removal() {
+    let config = AddressManagerConfig::for_testing();
+    let mut manager = AddressManager::new(config.clone());
+    let now = Timestamp::new(1000);
+
+    let peer = make_peer(1, 1, 100);
+    manager
+        .add_new(peer.clone(), &make_source_ip(0, 1), now)
+        .unwrap();
+
+    let counts = manager.new_bucket_counts();
+    assert_eq!(counts.len(), config.new_bucket_count);
+    let bucket = counts.iter().position(|&c| c == 1).unwrap();
+    let entry = manager.new_address_in_bucket(bucket, 7).unwrap();
+    assert_eq!(entry.peer_info.node_id, peer.node_id);
+
+    assert!(manager.remove_new(&peer.node_id).is_some());
+    assert!(manager.remove_new(&peer.node_id).is_none());
+    assert_eq!(manager.stats().new_count, 0);
+    assert!(manager.new_address_in_bucket(bucket, 0).is_none());
+    // The address can be learned again
+    assert!(manager.add_new(peer, &make_source_ip(0, 1), now).unwrap());
+}
+
+// =============================================================================
+// TEST GROUP: Persistence
+// =============================================================================
+
+#[test]
+fn test_restore_entries_keeps_history_and_limits() {
+    let config = AddressManagerConfig::for_testing();
+    let mut manager = AddressManager::new(config.clone());
+    let now = Timestamp::new(1000);
+    let source = make_source_ip(0, 1);
+
+    let tried = make_peer(1, 1, 100);
+    manager.add_new(tried.clone(), &source, now).unwrap();
+    manager.promote_to_tried(&tried.node_id, now).unwrap();
+    manager.add_new(make_peer(2, 2, 100), &source, now).unwrap();
+
+    let (new_entries, tried_entries) = manager.entries();
+    assert_eq!((new_entries.len(), tried_entries.len()), (1, 1));
+
+    let mut restored = AddressManager::new(config);
+    assert!(restored.restore_entry(tried_entries[0].clone(), true));
+    assert!(restored.restore_entry(new_entries[0].clone(), false));
+    // Duplicates are skipped
+    assert!(!restored.restore_entry(tried_entries[0].clone(), false));
+
+    let stats = restored.stats();
+    assert_eq!((stats.new_count, stats.tried_count), (1, 1));
+    assert_eq!(restored.entries().1, tried_entries);
+    assert_eq!(restored.entries().1[0].last_success, Some(now));
+}
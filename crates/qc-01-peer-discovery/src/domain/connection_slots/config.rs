@@ -1,5 +1,9 @@
 //! Connection slots configuration.
 
+use std::collections::HashSet;
+
+use crate::domain::{NodeId, SubnetMask};
+
 /// Connection slots configuration
 #[derive(Debug, Clone)]
 pub struct ConnectionSlotsConfig {
@@ -13,6 +17,15 @@ pub struct ConnectionSlotsConfig {
     pub protection_threshold_score: f64,
     /// Maximum peers protected per eviction round
     pub max_protected_per_round: usize,
+    /// Maximum connections through a relay, either direction
+    pub max_relayed: usize,
+    /// Maximum connections to addresses in one subnet, either direction
+    pub max_per_subnet: usize,
+    /// Subnet size for `max_per_subnet` (IPv4 and IPv6 prefixes)
+    pub subnet_mask: SubnetMask,
+    /// Validator/trusted peers that always get an inbound slot and are
+    /// never evicted; they sit outside `max_inbound`
+    pub reserved_peers: HashSet<NodeId>,
 }
 
 impl Default for ConnectionSlotsConfig {
@@ -23,6 +36,11 @@ impl Default for ConnectionSlotsConfig {
             protection_threshold_secs: 3600,
             protection_threshold_score: 5.0,
             max_protected_per_round: 10,
+            max_relayed: 8,
+            max_per_subnet: 4,
+            // A /64 is the smallest IPv6 allocation; one host can hold all of it
+            subnet_mask: SubnetMask::dual_stack(24, 64),
+            reserved_peers: HashSet::new(),
         }
     }
 }
@@ -37,6 +55,10 @@ impl ConnectionSlotsConfig {
             protection_threshold_secs: 60,
             protection_threshold_score: 2.0,
             max_protected_per_round: 2,
+            max_relayed: 2,
+            max_per_subnet: 2,
+            subnet_mask: SubnetMask::dual_stack(24, 64),
+            reserved_peers: HashSet::new(),
         }
     }
 }
@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use super::config::ConnectionSlotsConfig;
 use super::security::ConnectionInfo;
 use super::types::{AcceptResult, ConnectionDirection, ConnectionStats};
-use crate::domain::{NodeId, Timestamp};
+use crate::domain::{is_same_subnet, ConnectionPath, IpAddr, NodeId, Timestamp};
 
 /// Manages connection slots with eviction logic
 #[derive(Debug)]
@@ -47,8 +47,69 @@ impl ConnectionSlots {
     }
 
     /// Check if we have inbound slots available (without eviction)
+    ///
+    /// Reserved peers do not take up inbound slots.
     pub fn has_inbound_slot(&self) -> bool {
-        self.inbound_count() < self.config.max_inbound
+        let reserved_inbound = self
+            .connections
+            .values()
+            .filter(|c| c.direction == ConnectionDirection::Inbound)
+            .filter(|c| self.is_reserved(&c.node_id))
+            .count();
+        self.inbound_count() - reserved_inbound < self.config.max_inbound
+    }
+
+    /// Check if `node_id` is a reserved (validator/trusted) peer
+    pub fn is_reserved(&self, node_id: &NodeId) -> bool {
+        self.config.reserved_peers.contains(node_id)
+    }
+
+    /// Add a reserved peer, e.g. when the validator set changes
+    pub fn add_reserved_peer(&mut self, node_id: NodeId) {
+        self.config.reserved_peers.insert(node_id);
+    }
+
+    /// Remove a reserved peer
+    ///
+    /// A connected peer stays connected but counts against `max_inbound`
+    /// again and can be evicted.
+    pub fn remove_reserved_peer(&mut self, node_id: &NodeId) -> bool {
+        self.config.reserved_peers.remove(node_id)
+    }
+
+    /// Get current count of connected reserved peers (both directions)
+    pub fn reserved_count(&self) -> usize {
+        self.connections
+            .keys()
+            .filter(|id| self.is_reserved(id))
+            .count()
+    }
+
+    /// Get current relayed count (both directions)
+    pub fn relayed_count(&self) -> usize {
+        self.connections
+            .values()
+            .filter(|c| c.path == ConnectionPath::Relayed)
+            .count()
+    }
+
+    /// Check if we can take another relayed connection
+    pub fn has_relayed_slot(&self) -> bool {
+        self.relayed_count() < self.config.max_relayed
+    }
+
+    /// Count connections in the same subnet as `ip`
+    pub fn subnet_count(&self, ip: &IpAddr) -> usize {
+        self.connections
+            .values()
+            .filter_map(|c| c.ip.as_ref())
+            .filter(|other| is_same_subnet(other, ip, &self.config.subnet_mask))
+            .count()
+    }
+
+    /// Check if `ip`'s subnet can take another connection
+    pub fn has_subnet_slot(&self, ip: &IpAddr) -> bool {
+        self.subnet_count(ip) < self.config.max_per_subnet
     }
 
     /// Reserve an outbound slot for dialing
@@ -56,6 +117,46 @@ impl ConnectionSlots {
     /// Returns true if slot was reserved, false if no slots available.
     /// Outbound slots are SACRED - never displaced by inbound.
     pub fn reserve_outbound(&mut self, node_id: NodeId, now: Timestamp) -> bool {
+        self.reserve_outbound_via(node_id, ConnectionPath::Direct, now)
+    }
+
+    /// Reserve an outbound slot for a connection over `path`
+    ///
+    /// # Security
+    /// Both sides dial during a hole punch. Only the side that asked for
+    /// the punch takes an outbound slot; the side that was asked must use
+    /// `try_accept_inbound_via`, so peers cannot fill our outbound slots
+    /// by asking us to punch to them.
+    pub fn reserve_outbound_via(
+        &mut self,
+        node_id: NodeId,
+        path: ConnectionPath,
+        now: Timestamp,
+    ) -> bool {
+        self.reserve(node_id, None, path, now)
+    }
+
+    /// Reserve an outbound slot for a connection to `ip` over `path`
+    ///
+    /// Refused when `ip`'s subnet already holds `max_per_subnet`
+    /// connections.
+    pub fn reserve_outbound_to(
+        &mut self,
+        node_id: NodeId,
+        ip: IpAddr,
+        path: ConnectionPath,
+        now: Timestamp,
+    ) -> bool {
+        self.reserve(node_id, Some(ip), path, now)
+    }
+
+    fn reserve(
+        &mut self,
+        node_id: NodeId,
+        ip: Option<IpAddr>,
+        path: ConnectionPath,
+        now: Timestamp,
+    ) -> bool {
         if self.connections.contains_key(&node_id) {
             return false;
         }
@@ -64,7 +165,17 @@ impl ConnectionSlots {
             return false;
         }
 
-        let conn = ConnectionInfo::new(node_id, ConnectionDirection::Outbound, now);
+        if path == ConnectionPath::Relayed && !self.has_relayed_slot() {
+            return false;
+        }
+
+        if ip.is_some_and(|ip| !self.has_subnet_slot(&ip)) {
+            return false;
+        }
+
+        let mut conn = ConnectionInfo::new(node_id, ConnectionDirection::Outbound, now);
+        conn.path = path;
+        conn.ip = ip.map(|ip| ip.to_canonical());
         self.connections.insert(node_id, conn);
         true
     }
@@ -75,23 +186,81 @@ impl ConnectionSlots {
         node_id: NodeId,
         score: f64,
         now: Timestamp,
+    ) -> AcceptResult {
+        self.try_accept_inbound_via(node_id, score, ConnectionPath::Direct, now)
+    }
+
+    /// Try to accept an inbound connection over `path`
+    ///
+    /// Relayed connections beyond `max_relayed` are rejected rather than
+    /// evicting anyone. Reserved peers are always accepted.
+    pub fn try_accept_inbound_via(
+        &mut self,
+        node_id: NodeId,
+        score: f64,
+        path: ConnectionPath,
+        now: Timestamp,
+    ) -> AcceptResult {
+        self.accept(node_id, None, score, path, now)
+    }
+
+    /// Try to accept an inbound connection from `ip` over `path`
+    ///
+    /// # Security
+    /// A subnet already holding `max_per_subnet` connections is rejected
+    /// rather than evicting anyone, so a host with a whole IPv6 /64 cannot
+    /// cycle through addresses to take over the inbound slots. Reserved
+    /// peers are exempt: the set is configured, so it cannot be cycled.
+    pub fn try_accept_inbound_from(
+        &mut self,
+        node_id: NodeId,
+        ip: IpAddr,
+        score: f64,
+        path: ConnectionPath,
+        now: Timestamp,
+    ) -> AcceptResult {
+        self.accept(node_id, Some(ip), score, path, now)
+    }
+
+    fn accept(
+        &mut self,
+        node_id: NodeId,
+        ip: Option<IpAddr>,
+        score: f64,
+        path: ConnectionPath,
+        now: Timestamp,
     ) -> AcceptResult {
         if self.connections.contains_key(&node_id) {
             return AcceptResult::Rejected;
         }
 
+        let mut conn = ConnectionInfo::new(node_id, ConnectionDirection::Inbound, now);
+        conn.score = score;
+        conn.path = path;
+        conn.ip = ip.map(|ip| ip.to_canonical());
+
+        // Validators must stay reachable for consensus liveness: reserved
+        // peers sit outside every cap and never displace anyone.
+        if self.is_reserved(&node_id) {
+            self.connections.insert(node_id, conn);
+            return AcceptResult::Accepted;
+        }
+
+        if path == ConnectionPath::Relayed && !self.has_relayed_slot() {
+            return AcceptResult::Rejected;
+        }
+
+        if ip.is_some_and(|ip| !self.has_subnet_slot(&ip)) {
+            return AcceptResult::Rejected;
+        }
+
         if self.has_inbound_slot() {
-            let mut conn = ConnectionInfo::new(node_id, ConnectionDirection::Inbound, now);
-            conn.score = score;
             self.connections.insert(node_id, conn);
             return AcceptResult::Accepted;
         }
 
         if let Some(victim) = self.find_eviction_candidate(score, now) {
             self.connections.remove(&victim);
-
-            let mut conn = ConnectionInfo::new(node_id, ConnectionDirection::Inbound, now);
-            conn.score = score;
             self.connections.insert(node_id, conn);
 
             return AcceptResult::Evicted(victim);
@@ -183,6 +352,8 @@ impl ConnectionSlots {
             inbound_count: self.inbound_count(),
             max_outbound: self.config.max_outbound,
             max_inbound: self.config.max_inbound,
+            relayed_count: self.relayed_count(),
+            reserved_count: self.reserved_count(),
         }
     }
 }
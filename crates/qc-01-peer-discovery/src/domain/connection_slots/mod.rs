@@ -6,6 +6,12 @@
 //!
 //! - **Outbound Slots**: Sacred - only populated by our logic
 //! - **Inbound Slots**: Populated by external peers dialing us
+//! - **Relayed**: Connections through a relay (either direction), capped
+//!   separately
+//! - **Subnets**: At most `max_per_subnet` connections per /24 (IPv4) or
+//!   /64 (IPv6), so one host cannot take every slot from its IPv6 prefix
+//! - **Reserved**: Configured validator/trusted peers; always accepted
+//!   inbound, outside `max_inbound`, and never evicted
 //!
 //! Reference: Bitcoin Core's `net.cpp` eviction logic
 
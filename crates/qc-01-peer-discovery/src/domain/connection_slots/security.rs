@@ -5,7 +5,7 @@
 
 use super::config::ConnectionSlotsConfig;
 use super::types::ConnectionDirection;
-use crate::domain::{NodeId, Timestamp};
+use crate::domain::{ConnectionPath, IpAddr, NodeId, Timestamp};
 
 /// Information about an active connection
 ///
@@ -18,6 +18,10 @@ pub struct ConnectionInfo {
     pub node_id: NodeId,
     /// Whether this is an outbound (we dialed) or inbound (they dialed) connection
     pub direction: ConnectionDirection,
+    /// Whether the connection is direct, hole-punched or relayed
+    pub path: ConnectionPath,
+    /// Remote address, if known (counted against `max_per_subnet`)
+    pub ip: Option<IpAddr>,
     /// When the connection was established
     pub connected_at: Timestamp,
     /// Current peer score (from PeerScoreManager)
@@ -36,6 +40,8 @@ impl ConnectionInfo {
         Self {
             node_id,
             direction,
+            path: ConnectionPath::Direct,
+            ip: None,
             connected_at: now,
             score: 0.0,
             bytes_received: 0,
@@ -53,9 +59,11 @@ impl ConnectionInfo {
     ///
     /// # Security
     /// Protected peers cannot be evicted, preventing attackers from
-    /// displacing long-standing honest connections.
+    /// displacing long-standing honest connections. Reserved peers are
+    /// always protected so inbound pressure cannot cut consensus links.
     pub fn is_protected(&self, now: Timestamp, config: &ConnectionSlotsConfig) -> bool {
-        self.uptime_secs(now) >= config.protection_threshold_secs
+        config.reserved_peers.contains(&self.node_id)
+            || self.uptime_secs(now) >= config.protection_threshold_secs
             || self.score >= config.protection_threshold_score
     }
 
@@ -3,7 +3,7 @@
 //! Reference: Bitcoin Core's `net.cpp` eviction logic
 
 use super::*;
-use crate::domain::{NodeId, Timestamp};
+use crate::domain::{ConnectionPath, IpAddr, NodeId, Timestamp};
 
 fn make_node_id(byte: u8) -> NodeId {
     let mut id = [0u8; 32];
@@ -189,3 +189,260 @@ fn test_score_update_affects_eviction() {
     // Should still be connected (was protected by score update)
     assert!(slots.is_connected(&victim));
 }
+
+// =============================================================================
+// TEST GROUP 6: NAT Traversal Paths
+// =============================================================================
+
+#[test]
+fn test_hole_punched_connection_classified_by_initiator() {
+    let config = ConnectionSlotsConfig::for_testing();
+    let mut ours = ConnectionSlots::new(config.clone());
+    let mut theirs = ConnectionSlots::new(config);
+    let now = Timestamp::new(1000);
+    let (us, them) = (make_node_id(1), make_node_id(2));
+
+    // We asked for the punch, they were asked
+    assert!(ours.reserve_outbound_via(them, ConnectionPath::HolePunched, now));
+    assert_eq!(
+        theirs.try_accept_inbound_via(us, 0.0, ConnectionPath::HolePunched, now),
+        AcceptResult::Accepted
+    );
+
+    assert_eq!(ours.outbound_count(), 1);
+    assert_eq!(theirs.inbound_count(), 1);
+    assert_eq!(theirs.outbound_count(), 0);
+    assert_eq!(ours.get(&them).unwrap().path, ConnectionPath::HolePunched);
+}
+
+#[test]
+fn test_relayed_connections_capped() {
+    let config = ConnectionSlotsConfig::for_testing();
+    let mut slots = ConnectionSlots::new(config.clone());
+    let now = Timestamp::new(1000);
+
+    assert!(slots.reserve_outbound_via(make_node_id(1), ConnectionPath::Relayed, now));
+    for i in 2..=config.max_relayed {
+        let result =
+            slots.try_accept_inbound_via(make_node_id(i as u8), 0.0, ConnectionPath::Relayed, now);
+        assert_eq!(result, AcceptResult::Accepted);
+    }
+    assert_eq!(slots.stats().relayed_count, config.max_relayed);
+
+    // Both directions refuse further relays, direct connections still fit
+    assert!(!slots.reserve_outbound_via(make_node_id(50), ConnectionPath::Relayed, now));
+    assert_eq!(
+        slots.try_accept_inbound_via(make_node_id(51), 0.0, ConnectionPath::Relayed, now),
+        AcceptResult::Rejected
+    );
+    assert!(slots.reserve_outbound(make_node_id(52), now));
+    assert_eq!(
+        slots.try_accept_inbound(make_node_id(53), 0.0, now),
+        AcceptResult::Accepted
+    );
+}
+
+// =============================================================================
+// TEST GROUP: Subnet Diversity
+// =============================================================================
+
+fn ipv6_in_64(prefix_last: u8, host: u8) -> IpAddr {
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&[0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, prefix_last]);
+    bytes[15] = host;
+    IpAddr::v6(bytes)
+}
+
+#[test]
+fn test_ipv6_subnet_limit_per_64() {
+    let config = ConnectionSlotsConfig::for_testing();
+    let mut slots = ConnectionSlots::new(config.clone());
+    let now = Timestamp::new(1000);
+
+    for i in 0..config.max_per_subnet {
+        let result = slots.try_accept_inbound_from(
+            make_node_id(i as u8),
+            ipv6_in_64(1, i as u8),
+            0.0,
+            ConnectionPath::Direct,
+            now,
+        );
+        assert_eq!(result, AcceptResult::Accepted);
+    }
+
+    // Another address in the same /64 is refused in both directions
+    assert_eq!(
+        slots.try_accept_inbound_from(
+            make_node_id(20),
+            ipv6_in_64(1, 99),
+            100.0,
+            ConnectionPath::Direct,
+            now
+        ),
+        AcceptResult::Rejected
+    );
+    assert!(!slots.reserve_outbound_to(
+        make_node_id(21),
+        ipv6_in_64(1, 98),
+        ConnectionPath::Direct,
+        now
+    ));
+
+    // The neighbouring /64 is a different subnet
+    assert!(slots.reserve_outbound_to(
+        make_node_id(22),
+        ipv6_in_64(2, 1),
+        ConnectionPath::Direct,
+        now
+    ));
+    assert_eq!(slots.subnet_count(&ipv6_in_64(1, 0)), config.max_per_subnet);
+}
+
+#[test]
+fn test_mapped_ipv4_counts_with_ipv4() {
+    let config = ConnectionSlotsConfig::for_testing();
+    let mut slots = ConnectionSlots::new(config.clone());
+    let now = Timestamp::new(1000);
+
+    assert!(slots.reserve_outbound_to(
+        make_node_id(1),
+        IpAddr::v4(203, 0, 113, 1),
+        ConnectionPath::Direct,
+        now
+    ));
+
+    // The same /24 seen through a dual-stack socket
+    let mut mapped = [0u8; 16];
+    mapped[10..].copy_from_slice(&[0xff, 0xff, 203, 0, 113, 2]);
+    assert_eq!(
+        slots.try_accept_inbound_from(
+            make_node_id(2),
+            IpAddr::v6(mapped),
+            0.0,
+            ConnectionPath::Direct,
+            now
+        ),
+        AcceptResult::Accepted
+    );
+
+    assert_eq!(
+        slots.get(&make_node_id(2)).unwrap().ip,
+        Some(IpAddr::v4(203, 0, 113, 2))
+    );
+    assert!(!slots.has_subnet_slot(&IpAddr::v4(203, 0, 113, 3)));
+}
+
+// =============================================================================
+// TEST GROUP: Reserved Peers
+// =============================================================================
+
+fn config_with_reserved(ids: &[u8]) -> ConnectionSlotsConfig {
+    let mut config = ConnectionSlotsConfig::for_testing();
+    config.reserved_peers = ids.iter().map(|&b| make_node_id(b)).collect();
+    config
+}
+
+#[test]
+fn test_reserved_peer_accepted_when_inbound_full() {
+    let config = config_with_reserved(&[200]);
+    let mut slots = ConnectionSlots::new(config.clone());
+    let now = Timestamp::new(1000);
+
+    // Fill inbound with protected peers so nobody can be evicted
+    for i in 0..config.max_inbound {
+        slots.try_accept_inbound(make_node_id(i as u8), 10.0, now);
+    }
+    assert_eq!(
+        slots.try_accept_inbound(make_node_id(100), 5.0, now),
+        AcceptResult::Rejected
+    );
+
+    // The validator still gets in, outside max_inbound
+    let validator = make_node_id(200);
+    assert_eq!(
+        slots.try_accept_inbound(validator, -5.0, now),
+        AcceptResult::Accepted
+    );
+    assert_eq!(slots.inbound_count(), config.max_inbound + 1);
+    assert_eq!(slots.stats().reserved_count, 1);
+    assert!(!slots.has_inbound_slot());
+}
+
+#[test]
+fn test_reserved_peer_never_evicted() {
+    let config = config_with_reserved(&[200]);
+    let mut slots = ConnectionSlots::new(config.clone());
+    let now = Timestamp::new(1000);
+
+    let validator = make_node_id(200);
+    slots.try_accept_inbound(validator, -5.0, now);
+    slots.record_ping_failure(&validator);
+
+    // The reserved peer does not take one of the regular slots
+    for i in 0..config.max_inbound {
+        assert_eq!(
+            slots.try_accept_inbound(make_node_id(i as u8), -1.0, now),
+            AcceptResult::Accepted
+        );
+    }
+
+    // Better peers displace every regular peer, never the worse-scoring validator
+    for i in 0..config.max_inbound {
+        let result = slots.try_accept_inbound(make_node_id(100 + i as u8), 1.0, now);
+        assert!(matches!(result, AcceptResult::Evicted(victim) if victim != validator));
+    }
+    assert!(slots.is_connected(&validator));
+}
+
+#[test]
+fn test_reserved_peer_exempt_from_subnet_cap() {
+    let config = config_with_reserved(&[200]);
+    let mut slots = ConnectionSlots::new(config.clone());
+    let now = Timestamp::new(1000);
+
+    for i in 0..config.max_per_subnet {
+        slots.try_accept_inbound_from(
+            make_node_id(i as u8),
+            IpAddr::v4(203, 0, 113, i as u8),
+            0.0,
+            ConnectionPath::Direct,
+            now,
+        );
+    }
+
+    assert_eq!(
+        slots.try_accept_inbound_from(
+            make_node_id(200),
+            IpAddr::v4(203, 0, 113, 50),
+            0.0,
+            ConnectionPath::Direct,
+            now
+        ),
+        AcceptResult::Accepted
+    );
+}
+
+#[test]
+fn test_removed_reserved_peer_counts_again() {
+    let config = config_with_reserved(&[]);
+    let mut slots = ConnectionSlots::new(config.clone());
+    let now = Timestamp::new(1000);
+
+    let validator = make_node_id(200);
+    slots.add_reserved_peer(validator);
+    assert!(slots.is_reserved(&validator));
+    slots.try_accept_inbound(validator, -5.0, now);
+
+    for i in 1..config.max_inbound {
+        slots.try_accept_inbound(make_node_id(i as u8), 10.0, now);
+    }
+    assert!(slots.has_inbound_slot());
+
+    // Rotated out of the validator set: takes the last slot and is evictable
+    assert!(slots.remove_reserved_peer(&validator));
+    assert!(!slots.has_inbound_slot());
+    assert_eq!(
+        slots.try_accept_inbound(make_node_id(100), 1.0, now),
+        AcceptResult::Evicted(validator)
+    );
+}
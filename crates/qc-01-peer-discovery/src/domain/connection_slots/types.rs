@@ -33,4 +33,8 @@ pub struct ConnectionStats {
     pub max_outbound: usize,
     /// Maximum inbound connections allowed.
     pub max_inbound: usize,
+    /// Current number of relayed connections (counted in the above too).
+    pub relayed_count: usize,
+    /// Current number of reserved peers connected (counted in the above too).
+    pub reserved_count: usize,
 }
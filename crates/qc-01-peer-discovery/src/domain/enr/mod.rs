@@ -7,6 +7,7 @@
 //! - Self-signed: Record is signed by the node's private key
 //! - Sequence number: Prevents replay of old records
 //! - Compact: Efficient wire format for gossip
+//! - Real signatures: secp256k1 or Ed25519 via `shared-crypto` (feature `enr-crypto`)
 //!
 //! Reference: EIP-778 (Ethereum Node Records)
 
@@ -22,7 +23,7 @@ pub use cache::{CachedRecord, EnrCache};
 pub use capability::{Capability, CapabilityData, CapabilityType};
 pub use config::EnrConfig;
 pub use record::{NodeRecord, NodeRecordConfig};
-pub use security::{enr_hash, PublicKey, Signature};
+pub use security::{enr_hash, IdentityScheme, PublicKey, Signature, ED25519_KEY_PREFIX};
 
 #[cfg(test)]
 mod tests;
@@ -3,17 +3,17 @@
 //! Reference: EIP-778 (Ethereum Node Records)
 
 use super::capability::{Capability, CapabilityType};
-use super::security::{enr_hash, PublicKey, Signature};
+use super::security::{self, enr_hash, PublicKey, Signature};
 use crate::domain::{IpAddr, NodeId, SocketAddr};
 
 /// Ethereum Node Record (EIP-778 inspired)
 ///
 /// A self-signed record containing node identity and capabilities.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NodeRecord {
     /// Sequence number (increment on ANY change)
     pub seq: u64,
-    /// Node's public key (33 bytes compressed secp256k1)
+    /// Node's public key (secp256k1 or Ed25519, see `PublicKey`)
     pub pubkey: PublicKey,
     /// IP address
     pub ip: IpAddr,
@@ -101,31 +101,59 @@ impl NodeRecord {
 
     /// Verify the signature is valid for this record
     pub fn verify_signature(&self) -> bool {
-        let payload = self.signing_payload();
-        let expected_hash = enr_hash(&payload);
+        security::verify(&self.pubkey, &self.signing_payload(), &self.signature)
+    }
 
-        if self.signature.0.len() < 4 {
+    /// Sign the record with a private key
+    ///
+    /// With `enr-crypto`, `pubkey` is first replaced by the key derived from
+    /// `private_key` (Ed25519 if `pubkey` is an Ed25519 key, otherwise
+    /// secp256k1), so the record verifies against its own key.
+    ///
+    /// Returns false, leaving the record unsigned, if `private_key` is not a
+    /// valid key for the scheme.
+    pub fn sign(&mut self, private_key: &[u8; 32]) -> bool {
+        self.signature = Signature::empty();
+        let Some(pubkey) = security::public_key_for(&self.pubkey, private_key) else {
             return false;
-        }
+        };
+        self.pubkey = pubkey;
 
-        let sig_hash = u32::from_be_bytes([
-            self.signature.0[0],
-            self.signature.0[1],
-            self.signature.0[2],
-            self.signature.0[3],
-        ]);
-
-        sig_hash == expected_hash
+        match security::sign(&self.pubkey, private_key, &self.signing_payload()) {
+            Some(signature) => {
+                self.signature = signature;
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Sign the record with a private key
-    pub fn sign(&mut self, _private_key: &[u8; 32]) {
-        let payload = self.signing_payload();
-        let hash = enr_hash(&payload);
+    /// Move the record to a new address
+    ///
+    /// Any change bumps `seq` and re-signs (EIP-778), so peers holding the
+    /// old record accept the new one. Returns false if nothing changed, or
+    /// if re-signing failed; the record is then left as it was, so an
+    /// unverifiable record is never advertised.
+    pub fn update_address(
+        &mut self,
+        addr: SocketAddr,
+        tcp_port: u16,
+        private_key: &[u8; 32],
+    ) -> bool {
+        if self.socket_addr() == addr && self.tcp_port == tcp_port {
+            return false;
+        }
 
-        let mut sig = [0u8; 64];
-        sig[0..4].copy_from_slice(&hash.to_be_bytes());
-        self.signature = Signature(sig);
+        let previous = self.clone();
+        self.ip = addr.ip;
+        self.udp_port = addr.port;
+        self.tcp_port = tcp_port;
+        self.seq += 1;
+        if !self.sign(private_key) {
+            *self = previous;
+            return false;
+        }
+        true
     }
 
     /// Check if record has a specific capability
@@ -2,11 +2,30 @@
 //!
 //! SECURITY-CRITICAL: This file contains all signing and verification logic.
 //! Isolate for security audits.
+//!
+//! With the `enr-crypto` feature, records are signed and verified with
+//! secp256k1 ECDSA or Ed25519 from `shared-crypto`. Without it, a hash-based
+//! placeholder is used that proves integrity but not authorship.
 
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
-/// Compressed secp256k1 public key (33 bytes)
+/// Prefix byte marking an Ed25519 key inside a 33-byte `PublicKey`.
+pub const ED25519_KEY_PREFIX: u8 = 0xED;
+
+/// Identity scheme of a record's key (EIP-778 `id`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentityScheme {
+    /// secp256k1 ECDSA ("v4")
+    Secp256k1,
+    /// Ed25519
+    Ed25519,
+}
+
+/// Node public key (33 bytes)
+///
+/// Either a compressed secp256k1 key (`0x02`/`0x03` prefix) or an Ed25519
+/// key behind `ED25519_KEY_PREFIX`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PublicKey(pub [u8; 33]);
 
@@ -16,6 +35,23 @@ impl PublicKey {
         Self(bytes)
     }
 
+    /// Wrap a 32-byte Ed25519 key
+    pub fn ed25519(key: [u8; 32]) -> Self {
+        let mut bytes = [0u8; 33];
+        bytes[0] = ED25519_KEY_PREFIX;
+        bytes[1..].copy_from_slice(&key);
+        Self(bytes)
+    }
+
+    /// Scheme this key belongs to, from its prefix byte
+    pub fn scheme(&self) -> Option<IdentityScheme> {
+        match self.0[0] {
+            0x02 | 0x03 => Some(IdentityScheme::Secp256k1),
+            ED25519_KEY_PREFIX => Some(IdentityScheme::Ed25519),
+            _ => None,
+        }
+    }
+
     /// Create an empty public key
     pub fn empty() -> Self {
         Self([0u8; 33])
@@ -70,3 +106,80 @@ pub fn enr_hash(data: &[u8]) -> u32 {
     data.hash(&mut hasher);
     hasher.finish() as u32
 }
+
+/// Signing backend using `shared-crypto`.
+#[cfg(feature = "enr-crypto")]
+mod backend {
+    use super::{IdentityScheme, PublicKey, Signature};
+    use shared_crypto::{
+        Ed25519KeyPair, Ed25519PublicKey, Ed25519Signature, Secp256k1KeyPair, Secp256k1PublicKey,
+        Secp256k1Signature,
+    };
+
+    /// Public key for `private_key`, in the scheme of `current`.
+    ///
+    /// Keys without a known scheme default to secp256k1.
+    pub fn public_key_for(current: &PublicKey, private_key: &[u8; 32]) -> Option<PublicKey> {
+        match current.scheme() {
+            Some(IdentityScheme::Ed25519) => {
+                let keypair = Ed25519KeyPair::from_seed(*private_key);
+                Some(PublicKey::ed25519(*keypair.public_key().as_bytes()))
+            }
+            _ => {
+                let keypair = Secp256k1KeyPair::from_bytes(*private_key).ok()?;
+                Some(PublicKey(*keypair.public_key().as_bytes()))
+            }
+        }
+    }
+
+    pub fn sign(pubkey: &PublicKey, private_key: &[u8; 32], payload: &[u8]) -> Option<Signature> {
+        match pubkey.scheme()? {
+            IdentityScheme::Secp256k1 => {
+                let keypair = Secp256k1KeyPair::from_bytes(*private_key).ok()?;
+                Some(Signature(*keypair.sign(payload).as_bytes()))
+            }
+            IdentityScheme::Ed25519 => {
+                let keypair = Ed25519KeyPair::from_seed(*private_key);
+                Some(Signature(*keypair.sign(payload).as_bytes()))
+            }
+        }
+    }
+
+    pub fn verify(pubkey: &PublicKey, payload: &[u8], signature: &Signature) -> bool {
+        match pubkey.scheme() {
+            Some(IdentityScheme::Secp256k1) => Secp256k1PublicKey::from_bytes(pubkey.0)
+                .and_then(|key| key.verify(payload, &Secp256k1Signature::from_bytes(signature.0)))
+                .is_ok(),
+            Some(IdentityScheme::Ed25519) => {
+                let mut key = [0u8; 32];
+                key.copy_from_slice(&pubkey.0[1..]);
+                Ed25519PublicKey::from_bytes(key)
+                    .and_then(|key| key.verify(payload, &Ed25519Signature::from_bytes(signature.0)))
+                    .is_ok()
+            }
+            None => false,
+        }
+    }
+}
+
+/// Placeholder backend: the signature carries a hash of the payload.
+#[cfg(not(feature = "enr-crypto"))]
+mod backend {
+    use super::{enr_hash, PublicKey, Signature};
+
+    pub fn public_key_for(current: &PublicKey, _private_key: &[u8; 32]) -> Option<PublicKey> {
+        Some(current.clone())
+    }
+
+    pub fn sign(_pubkey: &PublicKey, _private_key: &[u8; 32], payload: &[u8]) -> Option<Signature> {
+        let mut sig = [0u8; 64];
+        sig[0..4].copy_from_slice(&enr_hash(payload).to_be_bytes());
+        Some(Signature(sig))
+    }
+
+    pub fn verify(_pubkey: &PublicKey, payload: &[u8], signature: &Signature) -> bool {
+        signature.0[0..4] == enr_hash(payload).to_be_bytes()
+    }
+}
+
+pub(super) use backend::{public_key_for, sign, verify};
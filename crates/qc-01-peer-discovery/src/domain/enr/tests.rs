@@ -3,7 +3,7 @@
 //! Reference: EIP-778 (Ethereum Node Records)
 
 use super::*;
-use crate::domain::IpAddr;
+use crate::domain::{IpAddr, SocketAddr};
 
 fn make_pubkey(byte: u8) -> PublicKey {
     let mut key = [0u8; 33];
@@ -189,3 +189,126 @@ fn test_cache_gc_stale() {
     assert_eq!(removed, 1);
     assert_eq!(cache.len(), 0);
 }
+
+#[test]
+fn test_cache_rejects_unsigned_record() {
+    let mut cache = EnrCache::new(EnrConfig::default());
+
+    assert!(!cache.insert(make_record(1, 8080), 1000));
+    assert!(cache.is_empty());
+}
+
+// =============================================================================
+// TEST GROUP 5: Address Updates
+// =============================================================================
+
+#[test]
+fn test_update_address_bumps_seq_and_resigns() {
+    let private_key = [1u8; 32];
+    let mut record = make_record(1, 8080);
+    record.sign(&private_key);
+    let node_id = record.node_id();
+
+    let moved = SocketAddr::new(IpAddr::v4(203, 0, 113, 7), 30303);
+    assert!(record.update_address(moved, 30303, &private_key));
+
+    assert_eq!(record.seq, 2);
+    assert_eq!(record.socket_addr(), moved);
+    assert!(record.verify_signature());
+
+    // Peers holding seq 1 take the update
+    let mut cache = EnrCache::new(EnrConfig::default());
+    let mut old = make_record(1, 8080);
+    old.sign(&private_key);
+    assert!(cache.insert(old, 1000));
+    assert!(cache.insert(record, 1001));
+    assert_eq!(cache.get(&node_id).unwrap().seq, 2);
+}
+
+#[test]
+fn test_update_address_unchanged_is_noop() {
+    let private_key = [1u8; 32];
+    let mut record = make_record(1, 8080);
+    record.sign(&private_key);
+
+    let same = record.socket_addr();
+    assert!(!record.update_address(same, 8080, &private_key));
+    assert_eq!(record.seq, 1);
+}
+
+// =============================================================================
+// TEST GROUP 6: Real Signatures (enr-crypto)
+// =============================================================================
+
+#[cfg(feature = "enr-crypto")]
+mod crypto {
+    use super::*;
+    use shared_crypto::{Ed25519KeyPair, Secp256k1KeyPair};
+
+    #[test]
+    fn test_secp256k1_signing_sets_derived_key() {
+        let private_key = [3u8; 32];
+        let mut record = make_record(1, 8080);
+        assert!(record.sign(&private_key));
+
+        let expected = Secp256k1KeyPair::from_bytes(private_key).unwrap();
+        assert_eq!(record.pubkey.as_bytes(), expected.public_key().as_bytes());
+        assert_eq!(record.pubkey.scheme(), Some(IdentityScheme::Secp256k1));
+        assert!(record.verify_signature());
+    }
+
+    #[test]
+    fn test_ed25519_record_round_trip() {
+        let private_key = [4u8; 32];
+        let mut record = make_record(1, 8080);
+        record.pubkey = PublicKey::ed25519([0u8; 32]);
+        assert!(record.sign(&private_key));
+
+        let expected = Ed25519KeyPair::from_seed(private_key).public_key();
+        assert_eq!(record.pubkey, PublicKey::ed25519(*expected.as_bytes()));
+        assert!(record.verify_signature());
+    }
+
+    #[test]
+    fn test_signature_from_another_key_is_rejected() {
+        let mut record = make_record(1, 8080);
+        record.sign(&[5u8; 32]);
+
+        let mut other = make_record(1, 8080);
+        other.sign(&[6u8; 32]);
+        record.pubkey = other.pubkey;
+
+        assert!(!record.verify_signature());
+    }
+
+    #[test]
+    fn test_flipped_signature_byte_is_rejected() {
+        let mut record = make_record(1, 8080);
+        record.sign(&[5u8; 32]);
+        record.signature.0[10] ^= 0x01;
+
+        assert!(!record.verify_signature());
+    }
+
+    #[test]
+    fn test_invalid_secp256k1_key_leaves_record_unsigned() {
+        let mut record = make_record(1, 8080);
+
+        assert!(!record.sign(&[0u8; 32]));
+        assert_eq!(record.signature, Signature::empty());
+        assert!(!record.verify_signature());
+    }
+
+    #[test]
+    fn test_update_address_signing_failure_keeps_record() {
+        let mut record = make_record(1, 8080);
+        assert!(record.sign(&[3u8; 32]));
+        let before = record.clone();
+
+        let moved = SocketAddr::new(IpAddr::v4(203, 0, 113, 7), 30303);
+        assert!(!record.update_address(moved, 30303, &[0u8; 32]));
+
+        assert_eq!(record, before);
+        assert!(record.verify_signature());
+    }
+}
@@ -11,12 +11,14 @@
 //! - Feeler Connections (Poisson-Process Probing)
 //! - Chain-Aware Handshakes (Fork-ID Convergence)
 //! - ENR (Ethereum Node Records - EIP-778)
+//! - NAT Traversal (External Address Discovery, Hole Punching)
 
 pub mod address_manager;
 pub mod connection_slots;
 pub mod enr;
 pub mod feeler;
 pub mod handshake;
+pub mod nat;
 pub mod peer_score;
 pub mod routing_table;
 pub mod services;
@@ -28,6 +30,7 @@ pub use connection_slots::*;
 pub use enr::*;
 pub use feeler::*;
 pub use handshake::*;
+pub use nat::*;
 pub use peer_score::*;
 pub use routing_table::*;
 pub use services::*;
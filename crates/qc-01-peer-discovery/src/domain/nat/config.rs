@@ -0,0 +1,46 @@
+//! NAT traversal configuration.
+
+/// NAT traversal configuration
+#[derive(Debug, Clone)]
+pub struct NatConfig {
+    /// Distinct observer subnets that must agree before an address is trusted
+    pub min_observers: usize,
+    /// Age (seconds) after which an observation is forgotten
+    pub observation_ttl_secs: u64,
+    /// Maximum observations kept (one per observer subnet)
+    pub max_observations: usize,
+    /// Time (seconds) for both sides of a hole punch to connect
+    pub punch_timeout_secs: u64,
+    /// Failed punches to a peer before falling back to a relay
+    pub max_punch_attempts: u32,
+    /// Maximum hole punches in flight at once
+    pub max_concurrent_punches: usize,
+}
+
+impl Default for NatConfig {
+    fn default() -> Self {
+        Self {
+            min_observers: 3,
+            observation_ttl_secs: 1800, // 30 minutes
+            max_observations: 32,
+            punch_timeout_secs: 10,
+            max_punch_attempts: 3,
+            max_concurrent_punches: 4,
+        }
+    }
+}
+
+impl NatConfig {
+    /// Testing config with smaller limits
+    #[cfg(test)]
+    pub fn for_testing() -> Self {
+        Self {
+            min_observers: 2,
+            observation_ttl_secs: 60,
+            max_observations: 4,
+            punch_timeout_secs: 5,
+            max_punch_attempts: 2,
+            max_concurrent_punches: 1,
+        }
+    }
+}
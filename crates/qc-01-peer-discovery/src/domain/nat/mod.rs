@@ -0,0 +1,27 @@
+//! # NAT Traversal
+//!
+//! Learns how this node is reached from outside and picks how to reach
+//! peers that sit behind a NAT.
+//!
+//! - **External address discovery** (STUN-style): peers report the address
+//!   they see us at; agreement across subnets gives our external address.
+//! - **Hole punching**: both sides dial each other at the same time, with a
+//!   peer connected to both passing on the request, so each NAT sees an
+//!   outgoing flow and lets the other side's packets in.
+//! - **Relay fallback**: when punching cannot work (symmetric NAT) or keeps
+//!   failing, traffic goes through a relay peer (`RelayPort`).
+//!
+//! Reference: RFC 8489 (STUN), RFC 5128 (P2P across NATs)
+
+// Semantic submodules
+mod config;
+mod service;
+mod types;
+
+// Re-export public API
+pub use config::NatConfig;
+pub use service::NatState;
+pub use types::{ConnectStrategy, ConnectionPath, HolePunchAttempt, NatStatus};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,218 @@
+//! NAT traversal state.
+
+use std::collections::HashMap;
+
+use super::config::NatConfig;
+use super::types::{ConnectStrategy, HolePunchAttempt, NatStatus};
+use crate::domain::{IpAddr, SocketAddr, SubnetKey, Timestamp};
+
+/// NAT traversal domain state
+///
+/// This is the pure domain logic. Reading observed addresses off the wire
+/// and dialing are handled by adapters.
+///
+/// # Security
+/// Observations are keyed by the observer's subnet, so a single operator
+/// running many nodes in one range counts once and cannot talk us into
+/// advertising an address of their choosing.
+#[derive(Debug)]
+pub struct NatState {
+    /// Address the transport is bound to
+    local_addr: SocketAddr,
+    /// Latest address each observer subnet reported seeing us at
+    observations: HashMap<SubnetKey, (SocketAddr, Timestamp)>,
+    /// When a peer we had not dialed last reached us
+    last_unsolicited_inbound: Option<Timestamp>,
+    /// Hole punches in flight, by target
+    active_punches: HashMap<SocketAddr, HolePunchAttempt>,
+    /// Failed hole punches per target
+    punch_failures: HashMap<SocketAddr, u32>,
+    /// Configuration
+    config: NatConfig,
+}
+
+impl NatState {
+    /// Create new NAT state for a transport bound to `local_addr`
+    pub fn new(config: NatConfig, local_addr: SocketAddr) -> Self {
+        Self {
+            local_addr,
+            observations: HashMap::new(),
+            last_unsolicited_inbound: None,
+            active_punches: HashMap::new(),
+            punch_failures: HashMap::new(),
+            config,
+        }
+    }
+
+    /// Record the address a peer at `observer_ip` saw us connect from
+    ///
+    /// A newer report from the same subnet replaces the older one. When
+    /// full, the oldest report is dropped.
+    pub fn record_observation(
+        &mut self,
+        observer_ip: &IpAddr,
+        observed: SocketAddr,
+        now: Timestamp,
+    ) {
+        self.prune_observations(now);
+
+        let subnet = SubnetKey::from_ip(observer_ip);
+        if !self.observations.contains_key(&subnet)
+            && self.observations.len() >= self.config.max_observations
+        {
+            let oldest = self
+                .observations
+                .iter()
+                .min_by_key(|(_, (_, seen))| *seen)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                self.observations.remove(&oldest);
+            }
+        }
+        self.observations.insert(subnet, (observed, now));
+    }
+
+    /// Record an inbound connection from a peer we did not dial or punch to
+    ///
+    /// This proves the external address is reachable without help.
+    pub fn record_unsolicited_inbound(&mut self, now: Timestamp) {
+        self.last_unsolicited_inbound = Some(now);
+    }
+
+    /// Current NAT status
+    ///
+    /// - `Public`: a majority of observers agree on one address, and it is
+    ///   the bound address or an unsolicited inbound connection arrived
+    ///   recently
+    /// - `Cone`: a majority agree on one address, but it is not known to be
+    ///   reachable
+    /// - `Symmetric`: enough observers agree on our IP, but each sees a
+    ///   different port
+    pub fn status(&self, now: Timestamp) -> NatStatus {
+        let fresh: Vec<SocketAddr> = self
+            .observations
+            .values()
+            .filter(|(_, seen)| !self.is_expired(*seen, now))
+            .map(|(addr, _)| *addr)
+            .collect();
+        if fresh.len() < self.config.min_observers {
+            return NatStatus::Unknown;
+        }
+
+        let mut by_addr: HashMap<SocketAddr, usize> = HashMap::new();
+        for addr in &fresh {
+            *by_addr.entry(*addr).or_insert(0) += 1;
+        }
+        let majority = by_addr
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .filter(|(_, count)| *count >= self.config.min_observers && count * 2 > fresh.len());
+        if let Some((addr, _)) = majority {
+            let reachable = addr == self.local_addr
+                || self
+                    .last_unsolicited_inbound
+                    .is_some_and(|seen| !self.is_expired(seen, now));
+            return if reachable {
+                NatStatus::Public(addr)
+            } else {
+                NatStatus::Cone(addr)
+            };
+        }
+
+        let mut by_ip: HashMap<IpAddr, usize> = HashMap::new();
+        for addr in &fresh {
+            *by_ip.entry(addr.ip).or_insert(0) += 1;
+        }
+        if by_ip
+            .values()
+            .any(|count| *count >= self.config.min_observers)
+        {
+            return NatStatus::Symmetric;
+        }
+
+        NatStatus::Unknown
+    }
+
+    /// How to reach a peer with NAT status `theirs`
+    ///
+    /// Falls back to a relay once punching to the peer has failed
+    /// `max_punch_attempts` times.
+    pub fn plan_connection(&self, theirs: NatStatus, now: Timestamp) -> ConnectStrategy {
+        let strategy = ConnectStrategy::choose(self.status(now), theirs);
+        match (strategy, theirs.external_addr()) {
+            (ConnectStrategy::HolePunch, Some(target))
+                if self.punch_failures(&target) >= self.config.max_punch_attempts =>
+            {
+                ConnectStrategy::Relay
+            }
+            _ => strategy,
+        }
+    }
+
+    /// Start a hole punch to `target`
+    ///
+    /// Returns the attempt if started, None if at capacity or already
+    /// punching to `target`
+    pub fn start_hole_punch(
+        &mut self,
+        target: SocketAddr,
+        now: Timestamp,
+    ) -> Option<HolePunchAttempt> {
+        if self.active_punches.len() >= self.config.max_concurrent_punches
+            || self.active_punches.contains_key(&target)
+        {
+            return None;
+        }
+
+        let attempt = HolePunchAttempt::new(target, now, self.config.punch_timeout_secs);
+        self.active_punches.insert(target, attempt.clone());
+        Some(attempt)
+    }
+
+    /// Complete a hole punch with success
+    pub fn on_punch_success(&mut self, target: &SocketAddr) {
+        self.active_punches.remove(target);
+        self.punch_failures.remove(target);
+    }
+
+    /// Complete a hole punch with failure
+    ///
+    /// Returns true if the peer should be reached through a relay from now on
+    pub fn on_punch_failure(&mut self, target: &SocketAddr) -> bool {
+        self.active_punches.remove(target);
+
+        let count = self.punch_failures.entry(*target).or_insert(0);
+        *count += 1;
+
+        *count >= self.config.max_punch_attempts
+    }
+
+    /// Get timed-out hole punches
+    pub fn get_timed_out_punches(&self, now: Timestamp) -> Vec<SocketAddr> {
+        self.active_punches
+            .iter()
+            .filter(|(_, p)| p.is_timed_out(now))
+            .map(|(addr, _)| *addr)
+            .collect()
+    }
+
+    /// Get current punch failure count for a peer
+    pub fn punch_failures(&self, target: &SocketAddr) -> u32 {
+        self.punch_failures.get(target).copied().unwrap_or(0)
+    }
+
+    /// Get number of hole punches in flight
+    pub fn active_punch_count(&self) -> usize {
+        self.active_punches.len()
+    }
+
+    fn prune_observations(&mut self, now: Timestamp) {
+        let ttl = self.config.observation_ttl_secs;
+        self.observations
+            .retain(|_, (_, seen)| now.as_secs().saturating_sub(seen.as_secs()) < ttl);
+    }
+
+    fn is_expired(&self, seen: Timestamp, now: Timestamp) -> bool {
+        now.as_secs().saturating_sub(seen.as_secs()) >= self.config.observation_ttl_secs
+    }
+}
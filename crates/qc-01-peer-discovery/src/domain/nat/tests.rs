@@ -0,0 +1,196 @@
+//! Tests for NAT Traversal
+//!
+//! Reference: RFC 8489 (STUN), RFC 5128 (P2P across NATs)
+
+use super::*;
+use crate::domain::{IpAddr, SocketAddr, Timestamp};
+
+fn local() -> SocketAddr {
+    SocketAddr::new(IpAddr::v4(192, 168, 1, 10), 30303)
+}
+
+fn external(port: u16) -> SocketAddr {
+    SocketAddr::new(IpAddr::v4(203, 0, 113, 7), port)
+}
+
+/// Observer in a distinct /16 per `n`
+fn observer(n: u8) -> IpAddr {
+    IpAddr::v4(10, n, 0, 1)
+}
+
+// =============================================================================
+// TEST GROUP 1: External Address Discovery
+// =============================================================================
+
+#[test]
+fn test_status_unknown_until_enough_observers() {
+    let mut state = NatState::new(NatConfig::for_testing(), local());
+    let now = Timestamp::new(1000);
+
+    state.record_observation(&observer(1), external(40000), now);
+    assert_eq!(state.status(now), NatStatus::Unknown);
+
+    // A second report from the same subnet does not count twice
+    state.record_observation(&IpAddr::v4(10, 1, 9, 9), external(40000), now);
+    assert_eq!(state.status(now), NatStatus::Unknown);
+
+    state.record_observation(&observer(2), external(40000), now);
+    assert_eq!(state.status(now), NatStatus::Cone(external(40000)));
+}
+
+#[test]
+fn test_status_public_when_reachable() {
+    let now = Timestamp::new(1000);
+
+    // Observers see the bound address itself
+    let mut state = NatState::new(NatConfig::for_testing(), external(30303));
+    state.record_observation(&observer(1), external(30303), now);
+    state.record_observation(&observer(2), external(30303), now);
+    assert_eq!(state.status(now), NatStatus::Public(external(30303)));
+
+    // Behind a port-preserving NAT, an unsolicited inbound proves it
+    let mut state = NatState::new(NatConfig::for_testing(), local());
+    state.record_observation(&observer(1), external(30303), now);
+    state.record_observation(&observer(2), external(30303), now);
+    assert_eq!(state.status(now), NatStatus::Cone(external(30303)));
+    state.record_unsolicited_inbound(now);
+    assert_eq!(state.status(now), NatStatus::Public(external(30303)));
+}
+
+#[test]
+fn test_status_symmetric_when_ports_differ() {
+    let mut state = NatState::new(NatConfig::for_testing(), local());
+    let now = Timestamp::new(1000);
+
+    state.record_observation(&observer(1), external(40001), now);
+    state.record_observation(&observer(2), external(40002), now);
+    assert_eq!(state.status(now), NatStatus::Symmetric);
+    assert_eq!(state.status(now).external_addr(), None);
+}
+
+#[test]
+fn test_observations_expire() {
+    let config = NatConfig::for_testing();
+    let mut state = NatState::new(config.clone(), local());
+    let now = Timestamp::new(1000);
+
+    state.record_observation(&observer(1), external(40000), now);
+    state.record_observation(&observer(2), external(40000), now);
+    assert!(matches!(state.status(now), NatStatus::Cone(_)));
+
+    let later = now.add_secs(config.observation_ttl_secs);
+    assert_eq!(state.status(later), NatStatus::Unknown);
+}
+
+#[test]
+fn test_full_observations_drop_oldest() {
+    let config = NatConfig::for_testing();
+    let mut state = NatState::new(config.clone(), local());
+
+    // Old, lying observers fill the table...
+    for n in 0..config.max_observations as u8 {
+        state.record_observation(&observer(n), external(1), Timestamp::new(1000 + n as u64));
+    }
+    // ...and honest newer reports push them out
+    let now = Timestamp::new(1010);
+    for n in 100..100 + config.max_observations as u8 {
+        state.record_observation(&observer(n), external(40000), now);
+    }
+    assert_eq!(state.status(now), NatStatus::Cone(external(40000)));
+}
+
+// =============================================================================
+// TEST GROUP 2: Strategy Selection
+// =============================================================================
+
+#[test]
+fn test_connect_strategy_choice() {
+    let cone = NatStatus::Cone(external(40000));
+    let public = NatStatus::Public(external(30303));
+
+    assert_eq!(
+        ConnectStrategy::choose(cone, public),
+        ConnectStrategy::Direct
+    );
+    assert_eq!(
+        ConnectStrategy::choose(NatStatus::Symmetric, NatStatus::Unknown),
+        ConnectStrategy::Direct
+    );
+    assert_eq!(
+        ConnectStrategy::choose(cone, cone),
+        ConnectStrategy::HolePunch
+    );
+    assert_eq!(
+        ConnectStrategy::choose(public, cone),
+        ConnectStrategy::HolePunch
+    );
+    assert_eq!(
+        ConnectStrategy::choose(NatStatus::Symmetric, cone),
+        ConnectStrategy::Relay
+    );
+    assert_eq!(
+        ConnectStrategy::choose(public, NatStatus::Symmetric),
+        ConnectStrategy::Relay
+    );
+}
+
+// =============================================================================
+// TEST GROUP 3: Hole Punching
+// =============================================================================
+
+#[test]
+fn test_hole_punch_respects_max_concurrent() {
+    let mut state = NatState::new(NatConfig::for_testing(), local());
+    let now = Timestamp::new(1000);
+
+    assert!(state.start_hole_punch(external(1), now).is_some());
+    assert!(state.start_hole_punch(external(2), now).is_none());
+
+    state.on_punch_success(&external(1));
+    assert_eq!(state.active_punch_count(), 0);
+    assert!(state.start_hole_punch(external(2), now).is_some());
+}
+
+#[test]
+fn test_hole_punch_timeout() {
+    let config = NatConfig::for_testing();
+    let mut state = NatState::new(config.clone(), local());
+    let now = Timestamp::new(1000);
+
+    state.start_hole_punch(external(1), now);
+    assert!(state.get_timed_out_punches(now).is_empty());
+
+    let later = now.add_secs(config.punch_timeout_secs);
+    assert_eq!(state.get_timed_out_punches(later), vec![external(1)]);
+}
+
+#[test]
+fn test_repeated_punch_failures_fall_back_to_relay() {
+    let config = NatConfig::for_testing();
+    let mut state = NatState::new(config.clone(), local());
+    let now = Timestamp::new(1000);
+    state.record_observation(&observer(1), external(40000), now);
+    state.record_observation(&observer(2), external(40000), now);
+
+    let peer = SocketAddr::new(IpAddr::v4(198, 51, 100, 2), 50000);
+    let theirs = NatStatus::Cone(peer);
+    assert_eq!(
+        state.plan_connection(theirs, now),
+        ConnectStrategy::HolePunch
+    );
+
+    for attempt in 1..=config.max_punch_attempts {
+        state.start_hole_punch(peer, now);
+        let give_up = state.on_punch_failure(&peer);
+        assert_eq!(give_up, attempt == config.max_punch_attempts);
+    }
+    assert_eq!(state.plan_connection(theirs, now), ConnectStrategy::Relay);
+
+    // A later success clears the history
+    state.on_punch_success(&peer);
+    assert_eq!(state.punch_failures(&peer), 0);
+    assert_eq!(
+        state.plan_connection(theirs, now),
+        ConnectStrategy::HolePunch
+    );
+}
@@ -0,0 +1,93 @@
+//! NAT traversal data types.
+
+use crate::domain::{SocketAddr, Timestamp};
+
+/// How this node (or a peer) is reached from outside
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NatStatus {
+    /// Not enough observations yet
+    Unknown,
+    /// Directly reachable at this address
+    Public(SocketAddr),
+    /// Behind a NAT that keeps one mapping for all peers; reachable at this
+    /// address once a hole is punched
+    Cone(SocketAddr),
+    /// Behind a NAT that maps each peer to a different port; only a relay
+    /// can reach it
+    Symmetric,
+}
+
+impl NatStatus {
+    /// Address to advertise to peers, if there is a usable one
+    pub fn external_addr(&self) -> Option<SocketAddr> {
+        match self {
+            NatStatus::Public(addr) | NatStatus::Cone(addr) => Some(*addr),
+            NatStatus::Unknown | NatStatus::Symmetric => None,
+        }
+    }
+}
+
+/// How to open a connection to a peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectStrategy {
+    /// Dial the peer's address
+    Direct,
+    /// Ask a peer connected to both sides to have the target dial us while
+    /// we dial it
+    HolePunch,
+    /// Go through a relay peer
+    Relay,
+}
+
+impl ConnectStrategy {
+    /// Pick a strategy from our NAT status and the target's.
+    ///
+    /// Peers of unknown status are dialed directly; failing that is cheap
+    /// and tells us more than guessing.
+    pub fn choose(ours: NatStatus, theirs: NatStatus) -> Self {
+        match (ours, theirs) {
+            (_, NatStatus::Public(_) | NatStatus::Unknown) => ConnectStrategy::Direct,
+            (_, NatStatus::Symmetric) | (NatStatus::Symmetric, _) => ConnectStrategy::Relay,
+            (_, NatStatus::Cone(_)) => ConnectStrategy::HolePunch,
+        }
+    }
+}
+
+/// How an established connection travels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConnectionPath {
+    /// One side dialed the other's address
+    #[default]
+    Direct,
+    /// Both sides dialed at once through their NATs
+    HolePunched,
+    /// Forwarded by a relay peer
+    Relayed,
+}
+
+/// A hole punch in flight
+#[derive(Debug, Clone)]
+pub struct HolePunchAttempt {
+    /// External address of the peer we are punching to
+    pub target: SocketAddr,
+    /// When the punch started
+    pub started_at: Timestamp,
+    /// Deadline for the connection
+    pub deadline: Timestamp,
+}
+
+impl HolePunchAttempt {
+    /// Create a new hole punch attempt
+    pub fn new(target: SocketAddr, now: Timestamp, timeout_secs: u64) -> Self {
+        Self {
+            target,
+            started_at: now,
+            deadline: now.add_secs(timeout_secs),
+        }
+    }
+
+    /// Check if the punch has timed out
+    pub fn is_timed_out(&self, now: Timestamp) -> bool {
+        now.as_secs() >= self.deadline.as_secs()
+    }
+}
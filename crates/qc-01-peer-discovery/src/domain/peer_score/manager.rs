@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use super::config::PeerScoreConfig;
-use super::security::PeerScore;
+use super::security::{PeerScore, ScoreRecord};
 use crate::domain::{NodeId, Timestamp};
 
 /// Manages scores for all peers
@@ -12,6 +12,8 @@ use crate::domain::{NodeId, Timestamp};
 pub struct PeerScoreManager {
     /// Scores per peer
     scores: HashMap<NodeId, PeerScore>,
+    /// Last scores of disconnected peers, decaying toward zero
+    retained: HashMap<NodeId, ScoreRecord>,
     /// Configuration
     config: PeerScoreConfig,
 }
@@ -21,18 +23,43 @@ impl PeerScoreManager {
     pub fn new(config: PeerScoreConfig) -> Self {
         Self {
             scores: HashMap::new(),
+            retained: HashMap::new(),
             config,
         }
     }
 
     /// Register a new peer
+    ///
+    /// A peer seen before starts from its retained score, decayed for the
+    /// time it was away, rather than from zero.
     pub fn on_peer_connected(&mut self, node_id: NodeId, now: Timestamp) {
-        self.scores.insert(node_id, PeerScore::new(now));
+        let score = self
+            .retained
+            .remove(&node_id)
+            .map_or(0.0, |record| record.decayed(now, &self.config));
+        self.scores
+            .insert(node_id, PeerScore::with_score(now, score));
+    }
+
+    /// Remove a peer, retaining its score
+    pub fn on_peer_disconnected(&mut self, node_id: &NodeId, now: Timestamp) {
+        if let Some(score) = self.scores.remove(node_id) {
+            self.retained.insert(
+                *node_id,
+                ScoreRecord {
+                    node_id: *node_id,
+                    score: score.score(),
+                    updated_at: now,
+                },
+            );
+        }
     }
 
-    /// Remove a peer
-    pub fn on_peer_disconnected(&mut self, node_id: &NodeId) {
-        self.scores.remove(node_id);
+    /// Score retained for a disconnected peer, decayed to `now`
+    pub fn retained_score(&self, node_id: &NodeId, now: Timestamp) -> Option<f64> {
+        self.retained
+            .get(node_id)
+            .map(|record| record.decayed(now, &self.config))
     }
 
     /// Get a peer's current score
@@ -92,10 +119,40 @@ impl PeerScoreManager {
     }
 
     /// Update all peer scores (call periodically)
+    ///
+    /// Also forgets retained scores that have decayed to nothing.
     pub fn update_all(&mut self, now: Timestamp) {
         for score in self.scores.values_mut() {
             score.update(now, &self.config);
         }
+        let config = &self.config;
+        self.retained
+            .retain(|_, record| !record.is_forgotten(now, config));
+    }
+
+    /// Scores worth persisting: connected and retained peers
+    pub fn export(&self, now: Timestamp) -> Vec<ScoreRecord> {
+        let connected = self.scores.iter().map(|(node_id, score)| ScoreRecord {
+            node_id: *node_id,
+            score: score.score(),
+            updated_at: now,
+        });
+        connected
+            .chain(self.retained.values().copied())
+            .filter(|record| !record.is_forgotten(now, &self.config))
+            .collect()
+    }
+
+    /// Put back a score from a snapshot as a retained score.
+    ///
+    /// Skipped if the peer is connected or the score has decayed to
+    /// nothing. Returns whether the score was restored.
+    pub fn restore(&mut self, record: ScoreRecord, now: Timestamp) -> bool {
+        if self.scores.contains_key(&record.node_id) || record.is_forgotten(now, &self.config) {
+            return false;
+        }
+        self.retained.insert(record.node_id, record);
+        true
     }
 
     /// Get peers that should be graylisted
@@ -12,7 +12,7 @@ mod security;
 // Re-export public API
 pub use config::PeerScoreConfig;
 pub use manager::PeerScoreManager;
-pub use security::PeerScore;
+pub use security::{PeerScore, ScoreRecord};
 
 #[cfg(test)]
 mod tests;
@@ -4,7 +4,10 @@
 //! Isolate for security audits.
 
 use super::config::PeerScoreConfig;
-use crate::domain::Timestamp;
+use crate::domain::{NodeId, Timestamp};
+
+/// Scores closer to zero than this are forgotten once a peer is gone
+pub const FORGOTTEN_SCORE: f64 = 0.1;
 
 /// Score state for a single peer
 ///
@@ -48,6 +51,14 @@ impl PeerScore {
         }
     }
 
+    /// Create a peer score carrying over an earlier score
+    pub fn with_score(connected_at: Timestamp, score: f64) -> Self {
+        Self {
+            score,
+            ..Self::new(connected_at)
+        }
+    }
+
     /// Get current score
     pub fn score(&self) -> f64 {
         self.score
@@ -115,3 +126,32 @@ impl PeerScore {
         self.last_update = now;
     }
 }
+
+/// Score of a peer that is no longer connected.
+///
+/// # Security
+/// Kept so a penalized peer cannot wipe its score by reconnecting, or by
+/// waiting for us to restart. The score decays toward zero at
+/// `decay_rate` per minute, so old grudges and old credit both fade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreRecord {
+    /// The scored peer
+    pub node_id: NodeId,
+    /// Score as of `updated_at`
+    pub score: f64,
+    /// When the score was recorded
+    pub updated_at: Timestamp,
+}
+
+impl ScoreRecord {
+    /// Score decayed from `updated_at` to `now`
+    pub fn decayed(&self, now: Timestamp, config: &PeerScoreConfig) -> f64 {
+        let elapsed_minutes = now.as_secs().saturating_sub(self.updated_at.as_secs()) as f64 / 60.0;
+        self.score * config.decay_rate.powf(elapsed_minutes)
+    }
+
+    /// Whether the score has decayed to nothing worth keeping
+    pub fn is_forgotten(&self, now: Timestamp, config: &PeerScoreConfig) -> bool {
+        self.decayed(now, config).abs() < FORGOTTEN_SCORE
+    }
+}
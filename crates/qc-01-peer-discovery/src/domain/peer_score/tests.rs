@@ -157,7 +157,52 @@ fn test_disconnected_peer_removed() {
 
     let node = make_node_id(1);
     manager.on_peer_connected(node, now);
-    manager.on_peer_disconnected(&node);
+    manager.on_peer_disconnected(&node, now);
 
     assert!(manager.get_score(&node).is_none());
 }
+
+#[test]
+fn test_reconnect_keeps_decayed_score() {
+    let config = PeerScoreConfig::for_testing();
+    let mut manager = PeerScoreManager::new(config);
+    let now = Timestamp::new(1000);
+
+    let node = make_node_id(1);
+    manager.on_peer_connected(node, now);
+    manager.on_invalid_block(&node); // -10
+    manager.on_peer_disconnected(&node, now);
+
+    // 0.9 per minute: two minutes away leaves -8.1
+    let later = now.add_secs(120);
+    manager.on_peer_connected(node, later);
+    let score = manager.get_score(&node).unwrap();
+    assert!((score + 8.1).abs() < 1e-9);
+    assert!(manager.should_graylist(&node));
+}
+
+#[test]
+fn test_retained_scores_fade_and_round_trip() {
+    let config = PeerScoreConfig::for_testing();
+    let mut manager = PeerScoreManager::new(config.clone());
+    let now = Timestamp::new(1000);
+
+    let node = make_node_id(1);
+    manager.on_peer_connected(node, now);
+    manager.on_invalid_block(&node);
+    manager.on_peer_disconnected(&node, now);
+
+    // A restarted node restores the exported score
+    let records = manager.export(now);
+    assert_eq!(records.len(), 1);
+    let mut restarted = PeerScoreManager::new(config);
+    assert!(restarted.restore(records[0], now));
+    assert_eq!(restarted.retained_score(&node, now), Some(-10.0));
+
+    // -10 * 0.9^60 is below the threshold: forgotten within the hour
+    let hour_later = now.add_secs(3600);
+    assert!(!restarted.restore(records[0], hour_later));
+    restarted.update_all(hour_later);
+    assert!(restarted.retained_score(&node, hour_later).is_none());
+    assert!(restarted.export(hour_later).is_empty());
+}
@@ -3,7 +3,7 @@
 //! Reference: SPEC-01-PEER-DISCOVERY.md Section 2.2
 
 use super::security::BannedEntry;
-use crate::domain::{BanReason, NodeId, Timestamp};
+use crate::domain::{NodeId, Timestamp};
 use std::collections::HashMap;
 
 /// Tracks banned peers with expiration times
@@ -22,16 +22,26 @@ impl BannedPeers {
         }
     }
 
-    /// Add a ban entry
-    pub fn ban(&mut self, node_id: NodeId, until: Timestamp, reason: BanReason) {
-        self.entries.insert(
-            node_id,
-            BannedEntry {
-                node_id,
-                banned_until: until,
-                reason,
-            },
-        );
+    /// Add a ban entry, replacing any earlier ban of the same peer
+    pub fn ban(&mut self, entry: BannedEntry) {
+        self.entries.insert(entry.node_id, entry);
+    }
+
+    /// Put back a ban from a snapshot.
+    ///
+    /// Expired bans are skipped, and a ban already in force is kept if it
+    /// lasts longer. Returns whether the entry was restored.
+    pub fn restore(&mut self, entry: BannedEntry, now: Timestamp) -> bool {
+        if entry.banned_until <= now {
+            return false;
+        }
+        if let Some(current) = self.entries.get(&entry.node_id) {
+            if current.banned_until >= entry.banned_until {
+                return false;
+            }
+        }
+        self.ban(entry);
+        true
     }
 
     /// Check if a peer is currently banned.
@@ -41,6 +51,18 @@ impl BannedPeers {
             .is_some_and(|entry| entry.banned_until > now)
     }
 
+    /// Active bans, oldest first
+    pub fn entries(&self, now: Timestamp) -> Vec<BannedEntry> {
+        let mut entries: Vec<_> = self
+            .entries
+            .values()
+            .filter(|e| e.banned_until > now)
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| (e.banned_at, e.node_id.0));
+        entries
+    }
+
     /// Remove expired bans
     pub fn gc_expired(&mut self, now: Timestamp) -> usize {
         let before = self.entries.len();
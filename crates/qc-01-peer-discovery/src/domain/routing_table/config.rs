@@ -5,3 +5,6 @@ pub const NUM_BUCKETS: usize = 256;
 
 /// Maximum total peers across all buckets
 pub const MAX_TOTAL_PEERS: usize = 5120; // 256 * 20
+
+/// Default age after which a saved peer or address is pruned on load
+pub const SNAPSHOT_MAX_AGE_SECS: u64 = 3 * 24 * 60 * 60; // 3 days
@@ -9,13 +9,15 @@
 mod banned;
 mod bucket;
 mod config;
+mod peer_cache;
 mod security;
 mod table;
 
 // Re-export public API
 pub use banned::BannedPeers;
 pub use bucket::KBucket;
-pub use config::{MAX_TOTAL_PEERS, NUM_BUCKETS};
+pub use config::{MAX_TOTAL_PEERS, NUM_BUCKETS, SNAPSHOT_MAX_AGE_SECS};
+pub use peer_cache::{decode_peer_cache, encode_peer_cache, RoutingTableSnapshot};
 pub use security::{BanDetails, BannedEntry, PendingInsertion, PendingPeer, RoutingTableStats};
 pub use table::RoutingTable;
 
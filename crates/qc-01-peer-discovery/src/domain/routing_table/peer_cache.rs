@@ -0,0 +1,341 @@
+//! Routing table snapshot (`peers.dat`).
+//!
+//! Written on graceful shutdown and loaded at startup so a restarted node
+//! rejoins through the peers it knew instead of cold-starting from the
+//! bootstrap nodes, which would make eclipsing it easier. Restored peers
+//! passed verification when they first joined; peers not seen within the
+//! loader's maximum age are pruned before anything is restored.
+//!
+//! Format v1: \[MAGIC\]\[COUNT\]\[PEER1\]\[PEER2\]... with each peer as
+//! node id (32), IP tag (1) + IP (4 or 16), port (2), last seen (8) and
+//! reputation (1). Integers are little-endian.
+//!
+//! Format v2 adds the save time and the address manager tables:
+//! \[MAGIC\]\[SAVED AT\]\[PEERS\]\[NEW ENTRIES\]\[TRIED ENTRIES\], each list
+//! prefixed by its count. An address entry is a peer followed by first
+//! seen (8), last attempt and last success (flag (1) + 8 each), attempts
+//! (4) and source subnet (4). v1 files still load, with empty tables.
+//!
+//! Format v3 appends the ban list and retained peer scores, so neither is
+//! wiped by a restart: \[V2 BODY\]\[BANS\]\[SCORES\]. A ban is node id (32),
+//! address (tag (1), 0 for none, then IP and port), banned at (8), banned
+//! until (8) and reason (1); a score is node id (32), score (8, f64) and
+//! updated at (8). v2 files load with no bans or scores.
+
+use crate::domain::{
+    AddressEntry, BanReason, BannedEntry, IpAddr, NodeId, PeerInfo, ScoreRecord, SocketAddr,
+    SubnetKey, Timestamp,
+};
+use std::io::{self, Read};
+
+/// Magic bytes for peers.dat
+const PEER_CACHE_MAGIC: &[u8; 8] = b"QCPEERS\x01";
+
+/// Magic bytes for peers.dat with address manager tables
+const SNAPSHOT_V2_MAGIC: &[u8; 8] = b"QCPEERS\x02";
+
+/// Magic bytes for peers.dat with bans and peer scores
+const SNAPSHOT_MAGIC: &[u8; 8] = b"QCPEERS\x03";
+
+const TAG_NONE: u8 = 0;
+const TAG_V4: u8 = 4;
+const TAG_V6: u8 = 6;
+
+/// Serialize peers for `peers.dat`.
+pub fn encode_peer_cache(peers: &[PeerInfo]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(16 + peers.len() * 64);
+    buf.extend_from_slice(PEER_CACHE_MAGIC);
+    write_peers(&mut buf, peers);
+    buf
+}
+
+/// Deserialize peers written by [`encode_peer_cache`].
+pub fn decode_peer_cache(data: &[u8]) -> io::Result<Vec<PeerInfo>> {
+    let mut reader = data;
+
+    let magic: [u8; 8] = read_array(&mut reader)?;
+    if &magic != PEER_CACHE_MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid magic"));
+    }
+    read_list(&mut reader, read_peer)
+}
+
+/// Routing table peers and address manager tables saved together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutingTableSnapshot {
+    /// When the snapshot was taken
+    pub saved_at: Timestamp,
+    /// Peers in the routing table buckets
+    pub peers: Vec<PeerInfo>,
+    /// Address manager New table
+    pub new_addresses: Vec<AddressEntry>,
+    /// Address manager Tried table
+    pub tried_addresses: Vec<AddressEntry>,
+    /// Bans in force when saved
+    pub bans: Vec<BannedEntry>,
+    /// Peer scores worth keeping
+    pub scores: Vec<ScoreRecord>,
+}
+
+impl RoutingTableSnapshot {
+    /// Drop everything not heard from within `max_age_secs` of `now`.
+    ///
+    /// Peers age by last seen, addresses by their last successful
+    /// connection (or first sighting if never connected). Bans and scores
+    /// run on their own clocks and are left alone. Returns the number of
+    /// peers and addresses dropped.
+    pub fn prune_stale(&mut self, now: Timestamp, max_age_secs: u64) -> usize {
+        let cutoff = now.as_secs().saturating_sub(max_age_secs);
+        let fresh = |entry: &AddressEntry| {
+            entry.last_success.unwrap_or(entry.first_seen).as_secs() >= cutoff
+        };
+        let before = self.len();
+        self.peers.retain(|peer| peer.last_seen.as_secs() >= cutoff);
+        self.new_addresses.retain(fresh);
+        self.tried_addresses.retain(fresh);
+        before - self.len()
+    }
+
+    /// Peers and addresses held.
+    pub fn len(&self) -> usize {
+        self.peers.len() + self.new_addresses.len() + self.tried_addresses.len()
+    }
+
+    /// Whether the snapshot holds nothing.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Serialize in the current (v3) format.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(16 + self.len() * 96);
+        buf.extend_from_slice(SNAPSHOT_MAGIC);
+        buf.extend_from_slice(&self.saved_at.as_secs().to_le_bytes());
+        write_peers(&mut buf, &self.peers);
+        for entries in [&self.new_addresses, &self.tried_addresses] {
+            buf.extend_from_slice(&(entries.len() as u64).to_le_bytes());
+            for entry in entries {
+                write_entry(&mut buf, entry);
+            }
+        }
+        buf.extend_from_slice(&(self.bans.len() as u64).to_le_bytes());
+        for ban in &self.bans {
+            write_ban(&mut buf, ban);
+        }
+        buf.extend_from_slice(&(self.scores.len() as u64).to_le_bytes());
+        for score in &self.scores {
+            write_score(&mut buf, score);
+        }
+        buf
+    }
+
+    /// Deserialize a v3 or v2 snapshot, or a v1 peer cache.
+    pub fn decode(data: &[u8]) -> io::Result<Self> {
+        let mut reader = data;
+        let magic: [u8; 8] = read_array(&mut reader)?;
+        if &magic == PEER_CACHE_MAGIC {
+            return Ok(Self {
+                saved_at: Timestamp::new(0),
+                peers: read_list(&mut reader, read_peer)?,
+                new_addresses: Vec::new(),
+                tried_addresses: Vec::new(),
+                bans: Vec::new(),
+                scores: Vec::new(),
+            });
+        }
+        if &magic != SNAPSHOT_MAGIC && &magic != SNAPSHOT_V2_MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid magic"));
+        }
+
+        let mut snapshot = Self {
+            saved_at: Timestamp::new(u64::from_le_bytes(read_array(&mut reader)?)),
+            peers: read_list(&mut reader, read_peer)?,
+            new_addresses: read_list(&mut reader, read_entry)?,
+            tried_addresses: read_list(&mut reader, read_entry)?,
+            bans: Vec::new(),
+            scores: Vec::new(),
+        };
+        if &magic == SNAPSHOT_MAGIC {
+            snapshot.bans = read_list(&mut reader, read_ban)?;
+            snapshot.scores = read_list(&mut reader, read_score)?;
+        }
+        Ok(snapshot)
+    }
+}
+
+fn write_peers(buf: &mut Vec<u8>, peers: &[PeerInfo]) {
+    buf.extend_from_slice(&(peers.len() as u64).to_le_bytes());
+    for peer in peers {
+        write_peer(buf, peer);
+    }
+}
+
+fn write_peer(buf: &mut Vec<u8>, peer: &PeerInfo) {
+    buf.extend_from_slice(peer.node_id.as_bytes());
+    write_ip(buf, &peer.socket_addr.ip);
+    buf.extend_from_slice(&peer.socket_addr.port.to_le_bytes());
+    buf.extend_from_slice(&peer.last_seen.as_secs().to_le_bytes());
+    buf.push(peer.reputation_score);
+}
+
+fn write_ip(buf: &mut Vec<u8>, ip: &IpAddr) {
+    match ip {
+        IpAddr::V4(ip) => {
+            buf.push(TAG_V4);
+            buf.extend_from_slice(ip);
+        }
+        IpAddr::V6(ip) => {
+            buf.push(TAG_V6);
+            buf.extend_from_slice(ip);
+        }
+    }
+}
+
+fn write_entry(buf: &mut Vec<u8>, entry: &AddressEntry) {
+    write_peer(buf, &entry.peer_info);
+    buf.extend_from_slice(&entry.first_seen.as_secs().to_le_bytes());
+    for time in [entry.last_attempt, entry.last_success] {
+        match time {
+            Some(time) => {
+                buf.push(1);
+                buf.extend_from_slice(&time.as_secs().to_le_bytes());
+            }
+            None => buf.extend_from_slice(&[0; 9]),
+        }
+    }
+    buf.extend_from_slice(&entry.attempts.to_le_bytes());
+    buf.extend_from_slice(&entry.source_subnet.0);
+}
+
+fn write_ban(buf: &mut Vec<u8>, ban: &BannedEntry) {
+    buf.extend_from_slice(ban.node_id.as_bytes());
+    match ban.socket_addr {
+        Some(addr) => {
+            write_ip(buf, &addr.ip);
+            buf.extend_from_slice(&addr.port.to_le_bytes());
+        }
+        None => buf.push(TAG_NONE),
+    }
+    buf.extend_from_slice(&ban.banned_at.as_secs().to_le_bytes());
+    buf.extend_from_slice(&ban.banned_until.as_secs().to_le_bytes());
+    buf.push(match ban.reason {
+        BanReason::MalformedMessage => 1,
+        BanReason::ExcessiveRequests => 2,
+        BanReason::ManualBan => 3,
+    });
+}
+
+fn write_score(buf: &mut Vec<u8>, score: &ScoreRecord) {
+    buf.extend_from_slice(score.node_id.as_bytes());
+    buf.extend_from_slice(&score.score.to_le_bytes());
+    buf.extend_from_slice(&score.updated_at.as_secs().to_le_bytes());
+}
+
+fn read_list<T>(
+    reader: &mut &[u8],
+    read_item: fn(&mut &[u8]) -> io::Result<T>,
+) -> io::Result<Vec<T>> {
+    let count = u64::from_le_bytes(read_array(reader)?);
+    // Cap the preallocation: the count comes from disk
+    let mut items = Vec::with_capacity(count.min(1024) as usize);
+    for _ in 0..count {
+        items.push(read_item(reader)?);
+    }
+    Ok(items)
+}
+
+fn read_entry(reader: &mut &[u8]) -> io::Result<AddressEntry> {
+    let peer_info = read_peer(reader)?;
+    let first_seen = Timestamp::new(u64::from_le_bytes(read_array(reader)?));
+    let mut read_time = || -> io::Result<Option<Timestamp>> {
+        let [flag] = read_array(reader)?;
+        let secs = u64::from_le_bytes(read_array(reader)?);
+        Ok((flag != 0).then(|| Timestamp::new(secs)))
+    };
+    let last_attempt = read_time()?;
+    let last_success = read_time()?;
+
+    Ok(AddressEntry {
+        peer_info,
+        first_seen,
+        last_attempt,
+        last_success,
+        attempts: u32::from_le_bytes(read_array(reader)?),
+        source_subnet: SubnetKey(read_array(reader)?),
+    })
+}
+
+fn read_ban(reader: &mut &[u8]) -> io::Result<BannedEntry> {
+    let node_id = NodeId::new(read_array(reader)?);
+    let socket_addr = match read_ip(reader)? {
+        Some(ip) => Some(SocketAddr::new(ip, u16::from_le_bytes(read_array(reader)?))),
+        None => None,
+    };
+    let banned_at = Timestamp::new(u64::from_le_bytes(read_array(reader)?));
+    let banned_until = Timestamp::new(u64::from_le_bytes(read_array(reader)?));
+    let reason = match read_array(reader)? {
+        [1] => BanReason::MalformedMessage,
+        [2] => BanReason::ExcessiveRequests,
+        [3] => BanReason::ManualBan,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid ban reason",
+            ))
+        }
+    };
+
+    Ok(BannedEntry {
+        node_id,
+        socket_addr,
+        banned_at,
+        banned_until,
+        reason,
+    })
+}
+
+fn read_score(reader: &mut &[u8]) -> io::Result<ScoreRecord> {
+    let node_id = NodeId::new(read_array(reader)?);
+    let score = f64::from_le_bytes(read_array(reader)?);
+    if !score.is_finite() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid score"));
+    }
+
+    Ok(ScoreRecord {
+        node_id,
+        score,
+        updated_at: Timestamp::new(u64::from_le_bytes(read_array(reader)?)),
+    })
+}
+
+fn read_peer(reader: &mut &[u8]) -> io::Result<PeerInfo> {
+    let node_id = NodeId::new(read_array(reader)?);
+    let ip = read_ip(reader)?
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid IP tag"))?;
+    let port = u16::from_le_bytes(read_array(reader)?);
+    let last_seen = Timestamp::new(u64::from_le_bytes(read_array(reader)?));
+    let [reputation_score] = read_array(reader)?;
+
+    Ok(PeerInfo {
+        node_id,
+        socket_addr: SocketAddr::new(ip, port),
+        last_seen,
+        reputation_score,
+    })
+}
+
+/// IP with its tag; `None` for `TAG_NONE`
+fn read_ip(reader: &mut &[u8]) -> io::Result<Option<IpAddr>> {
+    match read_array(reader)? {
+        [TAG_NONE] => Ok(None),
+        [TAG_V4] => Ok(Some(IpAddr::V4(read_array(reader)?))),
+        [TAG_V6] => Ok(Some(IpAddr::V6(read_array(reader)?))),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid IP tag")),
+    }
+}
+
+fn read_array<const N: usize>(reader: &mut &[u8]) -> io::Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
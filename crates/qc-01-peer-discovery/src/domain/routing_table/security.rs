@@ -3,7 +3,7 @@
 //! SECURITY-CRITICAL: Contains types for staging, verification, banning.
 //! Isolate for security audits.
 
-use crate::domain::{BanReason, NodeId, PeerInfo, Timestamp};
+use crate::domain::{BanReason, NodeId, PeerInfo, SocketAddr, Timestamp};
 
 /// A peer waiting to be inserted into a full bucket, pending challenge result
 ///
@@ -44,16 +44,27 @@ pub struct PendingPeer {
 ///
 /// # Security
 /// Banned peers are tracked to prevent re-connection attempts.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BannedEntry {
     /// The banned node's ID.
     pub node_id: NodeId,
+    /// Address the peer had in the routing table, if it was there.
+    pub socket_addr: Option<SocketAddr>,
+    /// When the ban was imposed.
+    pub banned_at: Timestamp,
     /// When the ban expires.
     pub banned_until: Timestamp,
     /// Reason for the ban.
     pub reason: BanReason,
 }
 
+impl BannedEntry {
+    /// Whether the ban never expires (`BanDetails` duration 0).
+    pub fn is_permanent(&self) -> bool {
+        self.banned_until.as_secs() == Timestamp::MAX_REASONABLE
+    }
+}
+
 /// Details for banning a peer.
 pub struct BanDetails {
     /// Duration of the ban in seconds.
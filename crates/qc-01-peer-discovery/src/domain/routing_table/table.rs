@@ -12,7 +12,7 @@ use crate::domain::{
 use super::banned::BannedPeers;
 use super::bucket::KBucket;
 use super::config::NUM_BUCKETS;
-use super::security::{BanDetails, PendingInsertion, PendingPeer, RoutingTableStats};
+use super::security::{BanDetails, BannedEntry, PendingInsertion, PendingPeer, RoutingTableStats};
 
 /// The main routing table implementing Kademlia DHT
 ///
@@ -72,6 +72,14 @@ impl RoutingTable {
         self.buckets.iter().map(|b| b.len()).sum()
     }
 
+    /// Every peer in the buckets (staged peers are not included)
+    pub fn peers(&self) -> Vec<PeerInfo> {
+        self.buckets
+            .iter()
+            .flat_map(|b| b.peers().iter().cloned())
+            .collect()
+    }
+
     /// Get routing table statistics
     pub fn stats(&self, now: Timestamp) -> RoutingTableStats {
         let total_peers = self.total_peer_count();
@@ -281,7 +289,38 @@ impl RoutingTable {
         removed
     }
 
-    /// Ban a peer
+    /// Put back a peer from a routing table snapshot.
+    ///
+    /// The peer passed verification when it first joined, so it skips
+    /// staging, but INVARIANT-3 and INVARIANT-1 still apply: peers from a
+    /// full subnet or into a full bucket are dropped instead of challenging
+    /// live peers. Banned and already-known peers are skipped. Returns
+    /// whether the peer was restored.
+    pub fn restore_peer(&mut self, peer: PeerInfo, now: Timestamp) -> bool {
+        if peer.node_id == self.local_node_id || self.is_banned(&peer.node_id, now) {
+            return false;
+        }
+        let bucket_idx = calculate_bucket_index(&self.local_node_id, &peer.node_id);
+        let Some(bucket) = self.buckets.get_mut(bucket_idx) else {
+            return false;
+        };
+        if bucket.contains(&peer.node_id) || bucket.is_full(self.config.k) {
+            return false;
+        }
+        let peers_in_subnet = bucket
+            .peers()
+            .iter()
+            .filter(|p| is_same_subnet(&p.socket_addr.ip, &peer.socket_addr.ip, &self.subnet_mask))
+            .count();
+        if peers_in_subnet >= self.config.max_peers_per_subnet {
+            return false;
+        }
+
+        bucket.add_peer(peer, now);
+        true
+    }
+
+    /// Ban a peer (duration 0 = permanent)
     pub fn ban_peer(
         &mut self,
         node_id: NodeId,
@@ -289,14 +328,27 @@ impl RoutingTable {
         now: Timestamp,
     ) -> Result<(), PeerDiscoveryError> {
         let bucket_idx = calculate_bucket_index(&self.local_node_id, &node_id);
-        if let Some(bucket) = self.buckets.get_mut(bucket_idx) {
-            bucket.remove_peer(&node_id);
-        }
+        let mut socket_addr = self
+            .buckets
+            .get_mut(bucket_idx)
+            .and_then(|bucket| bucket.remove_peer(&node_id))
+            .map(|peer| peer.socket_addr);
 
-        self.pending_verification.remove(&node_id);
+        if let Some(pending) = self.pending_verification.remove(&node_id) {
+            socket_addr = socket_addr.or(Some(pending.peer_info.socket_addr));
+        }
 
-        let until = now.add_secs(details.duration_secs);
-        self.banned_peers.ban(node_id, until, details.reason);
+        let banned_until = match details.duration_secs {
+            0 => Timestamp::new(Timestamp::MAX_REASONABLE),
+            secs => now.add_secs(secs),
+        };
+        self.banned_peers.ban(BannedEntry {
+            node_id,
+            socket_addr,
+            banned_at: now,
+            banned_until,
+            reason: details.reason,
+        });
 
         Ok(())
     }
@@ -306,6 +358,28 @@ impl RoutingTable {
         self.banned_peers.is_banned(node_id, now)
     }
 
+    /// Active bans, oldest first
+    pub fn banned_peers(&self, now: Timestamp) -> Vec<BannedEntry> {
+        self.banned_peers.entries(now)
+    }
+
+    /// Put back a ban from a routing table snapshot.
+    ///
+    /// The peer is dropped from the buckets and staging in case it was
+    /// restored before its ban. Returns whether the ban was restored.
+    pub fn restore_ban(&mut self, entry: BannedEntry, now: Timestamp) -> bool {
+        let node_id = entry.node_id;
+        if !self.banned_peers.restore(entry, now) {
+            return false;
+        }
+        let bucket_idx = calculate_bucket_index(&self.local_node_id, &node_id);
+        if let Some(bucket) = self.buckets.get_mut(bucket_idx) {
+            bucket.remove_peer(&node_id);
+        }
+        self.pending_verification.remove(&node_id);
+        true
+    }
+
     /// Helper to get mutable bucket for a node ID
     fn get_bucket_mut_for_node(
         &mut self,
@@ -383,4 +457,42 @@ impl RoutingTable {
     pub fn pending_verification_count(&self) -> usize {
         self.pending_verification.len()
     }
+
+    /// Peers currently facing an eviction challenge
+    ///
+    /// The caller PINGs each one; a PONG goes to `on_challenge_response`.
+    pub fn pending_challenges(&self) -> Vec<PeerInfo> {
+        self.buckets
+            .iter()
+            .filter_map(|b| {
+                let pending = b.pending_insertion.as_ref()?;
+                b.peers()
+                    .iter()
+                    .find(|p| p.node_id == pending.challenged_peer)
+                    .cloned()
+            })
+            .collect()
+    }
+
+    /// Non-empty buckets not updated for `max_idle_secs`
+    pub fn stale_buckets(&self, now: Timestamp, max_idle_secs: u64) -> Vec<usize> {
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| !b.is_empty())
+            .filter(|(_, b)| b.last_updated.add_secs(max_idle_secs) <= now)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// A NodeId that falls in bucket `index`, for refresh lookups
+    ///
+    /// Our own ID with bit `index` (counted from the most significant bit)
+    /// flipped.
+    pub fn refresh_target(&self, index: usize) -> NodeId {
+        let mut bytes = *self.local_node_id.as_bytes();
+        let index = index.min(NUM_BUCKETS - 1);
+        bytes[index / 8] ^= 0x80 >> (index % 8);
+        NodeId::new(bytes)
+    }
 }
@@ -4,8 +4,8 @@
 
 use super::*;
 use crate::domain::{
-    calculate_bucket_index, BanReason, IpAddr, KademliaConfig, NodeId, PeerDiscoveryError,
-    PeerInfo, SocketAddr, Timestamp,
+    calculate_bucket_index, AddressEntry, BanReason, IpAddr, KademliaConfig, NodeId,
+    PeerDiscoveryError, PeerInfo, ScoreRecord, SocketAddr, SubnetKey, Timestamp,
 };
 
 fn make_node_id(val: u8) -> NodeId {
@@ -617,3 +617,171 @@ fn test_rejects_third_peer_from_same_subnet() {
         "Third peer from same /24 rejected per INVARIANT-3"
     );
 }
+
+// =============================================================================
+// Test: Peer cache (peers.dat) round trip
+// =============================================================================
+
+#[test]
+fn test_peer_cache_round_trip() {
+    let mut table = RoutingTable::new(make_node_id(0), KademliaConfig::for_testing());
+    let now = Timestamp::new(1000);
+
+    let peer = make_peer(1, 8080);
+    table.stage_peer(peer.clone(), now).unwrap();
+    table
+        .on_verification_result(&peer.node_id, true, now)
+        .unwrap();
+    let mut v6_peer = PeerInfo::new(
+        make_node_id(2),
+        SocketAddr::new(IpAddr::v6([0xFE; 16]), 30303),
+        Timestamp::new(2000),
+    );
+    v6_peer.reputation_score = 90;
+
+    let mut peers = table.peers();
+    assert_eq!(peers, vec![peer]);
+    peers.push(v6_peer);
+
+    let encoded = encode_peer_cache(&peers);
+    assert_eq!(decode_peer_cache(&encoded).unwrap(), peers);
+
+    // Truncated or foreign files are rejected
+    assert!(decode_peer_cache(&encoded[..encoded.len() - 1]).is_err());
+    assert!(decode_peer_cache(b"QCMPOOL\x01").is_err());
+}
+
+#[test]
+fn test_snapshot_round_trip_and_v1_compat() {
+    let peer = make_peer(1, 8080);
+    let mut entry = AddressEntry::new(
+        make_peer(2, 9090),
+        Timestamp::new(900),
+        SubnetKey([10, 0, 0, 0]),
+    );
+    entry.last_success = Some(Timestamp::new(950));
+    entry.attempts = 3;
+    let snapshot = RoutingTableSnapshot {
+        saved_at: Timestamp::new(2000),
+        peers: vec![peer.clone()],
+        new_addresses: vec![AddressEntry::new(
+            make_peer(3, 7070),
+            Timestamp::new(800),
+            SubnetKey([10, 1, 0, 0]),
+        )],
+        tried_addresses: vec![entry],
+        bans: vec![
+            BannedEntry {
+                node_id: make_node_id(4),
+                socket_addr: Some(SocketAddr::new(
+                    IpAddr::v6([0x20, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]),
+                    30303,
+                )),
+                banned_at: Timestamp::new(1500),
+                banned_until: Timestamp::new(5100),
+                reason: BanReason::ExcessiveRequests,
+            },
+            BannedEntry {
+                node_id: make_node_id(5),
+                socket_addr: None,
+                banned_at: Timestamp::new(1600),
+                banned_until: Timestamp::new(Timestamp::MAX_REASONABLE),
+                reason: BanReason::ManualBan,
+            },
+        ],
+        scores: vec![ScoreRecord {
+            node_id: make_node_id(6),
+            score: -42.5,
+            updated_at: Timestamp::new(1900),
+        }],
+    };
+
+    let encoded = snapshot.encode();
+    assert_eq!(RoutingTableSnapshot::decode(&encoded).unwrap(), snapshot);
+    assert!(RoutingTableSnapshot::decode(&encoded[..encoded.len() - 1]).is_err());
+
+    // A v2 snapshot loads with no bans or scores
+    let v2_snapshot = RoutingTableSnapshot {
+        bans: Vec::new(),
+        scores: Vec::new(),
+        ..snapshot.clone()
+    };
+    let mut v2 = v2_snapshot.encode();
+    v2.truncate(v2.len() - 16); // empty ban and score lists
+    v2[7] = 2;
+    assert_eq!(RoutingTableSnapshot::decode(&v2).unwrap(), v2_snapshot);
+
+    // A v1 peer cache loads with empty address tables
+    let v1 = RoutingTableSnapshot::decode(&encode_peer_cache(std::slice::from_ref(&peer))).unwrap();
+    assert_eq!(v1.peers, vec![peer]);
+    assert!(v1.new_addresses.is_empty() && v1.tried_addresses.is_empty());
+}
+
+#[test]
+fn test_snapshot_prunes_stale_entries() {
+    let mut stale_peer = make_peer(1, 8080);
+    stale_peer.last_seen = Timestamp::new(100);
+    let mut snapshot = RoutingTableSnapshot {
+        saved_at: Timestamp::new(2000),
+        peers: vec![stale_peer, make_peer(2, 8080)],
+        new_addresses: vec![AddressEntry::new(
+            make_peer(3, 8080),
+            Timestamp::new(100),
+            SubnetKey([10, 0, 0, 0]),
+        )],
+        tried_addresses: Vec::new(),
+        bans: Vec::new(),
+        scores: Vec::new(),
+    };
+
+    // Cutoff at t=500: peer 2 (seen at 1000) survives
+    assert_eq!(snapshot.prune_stale(Timestamp::new(1500), 1000), 2);
+    assert_eq!(snapshot.peers.len(), 1);
+    assert_eq!(snapshot.peers[0].node_id, make_node_id(2));
+    assert!(snapshot.new_addresses.is_empty());
+}
+
+#[test]
+fn test_restore_peer_respects_limits() {
+    let mut table = RoutingTable::new(make_node_id(0), KademliaConfig::for_testing());
+    let now = Timestamp::new(1000);
+
+    assert!(table.restore_peer(make_peer(1, 8080), now));
+    // Already present, or ourselves
+    assert!(!table.restore_peer(make_peer(1, 8080), now));
+    assert!(!table.restore_peer(make_peer(0, 8080), now));
+
+    // Banned peers stay out
+    table
+        .ban_peer(
+            make_node_id(2),
+            BanDetails::new(3600, BanReason::MalformedMessage),
+            now,
+        )
+        .unwrap();
+    assert!(!table.restore_peer(make_peer(2, 8080), now));
+
+    // INVARIANT-3: peers 128..=130 share a bucket and a /24
+    assert!(table.restore_peer(make_peer(128, 8080), now));
+    assert!(table.restore_peer(make_peer(129, 8080), now));
+    assert!(!table.restore_peer(make_peer(130, 8080), now));
+    assert_eq!(table.total_peer_count(), 3);
+    assert_eq!(table.pending_verification_count(), 0);
+}
+
+#[test]
+fn test_stale_buckets_and_refresh_target() {
+    let mut table = RoutingTable::new(make_node_id(0), KademliaConfig::for_testing());
+    assert!(table.restore_peer(make_peer(128, 8080), Timestamp::new(1000)));
+    assert!(table.restore_peer(make_peer(1, 8080), Timestamp::new(1500)));
+
+    // Empty buckets are never stale
+    assert!(table.stale_buckets(Timestamp::new(1000), 600).is_empty());
+    assert_eq!(table.stale_buckets(Timestamp::new(1600), 600), vec![0]);
+    assert_eq!(table.stale_buckets(Timestamp::new(2100), 600), vec![0, 7]);
+
+    for index in [0, 7, 100, 255] {
+        let target = table.refresh_target(index);
+        assert_eq!(calculate_bucket_index(&make_node_id(0), &target), index);
+    }
+}
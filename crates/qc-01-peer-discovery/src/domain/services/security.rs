@@ -11,16 +11,19 @@ use crate::domain::{IpAddr, SubnetMask};
 /// Used to enforce INVARIANT-3 (IP Diversity). Prevents a single attacker
 /// controlling a subnet from filling all our buckets.
 ///
-/// Compares addresses using the specified prefix length (e.g., /24 for IPv4).
+/// Compares addresses using the mask's prefix length for their family
+/// (e.g., /24 for IPv4, /48 for IPv6). IPv4-mapped IPv6 addresses are
+/// compared as IPv4, so a peer seen through a dual-stack socket is grouped
+/// with the same peer seen over IPv4.
 ///
 /// Reference: SPEC-01 Section 6.1 (Sybil Attack Resistance)
 pub fn is_same_subnet(a: &IpAddr, b: &IpAddr, mask: &SubnetMask) -> bool {
-    match (a, b) {
+    match (a.to_canonical(), b.to_canonical()) {
         (IpAddr::V4(a_bytes), IpAddr::V4(b_bytes)) => {
-            prefix_matches(a_bytes, b_bytes, mask.prefix_length, 4)
+            prefix_matches(&a_bytes, &b_bytes, mask.prefix_length, 4)
         }
         (IpAddr::V6(a_bytes), IpAddr::V6(b_bytes)) => {
-            prefix_matches(a_bytes, b_bytes, mask.prefix_length, 16)
+            prefix_matches(&a_bytes, &b_bytes, mask.ipv6_prefix_length, 16)
         }
         // IPv4 and IPv6 addresses are in disjoint address spaces
         _ => false,
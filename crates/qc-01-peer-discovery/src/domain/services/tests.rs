@@ -189,6 +189,41 @@ fn test_ipv4_ipv6_never_same_subnet() {
     );
 }
 
+#[test]
+fn test_ipv4_mapped_compared_as_ipv4() {
+    let mut mapped = [0u8; 16];
+    mapped[10..].copy_from_slice(&[0xff, 0xff, 192, 168, 1, 7]);
+    let mapped = IpAddr::v6(mapped);
+
+    assert_eq!(mapped.to_canonical(), IpAddr::v4(192, 168, 1, 7));
+    assert!(is_same_subnet(
+        &mapped,
+        &IpAddr::v4(192, 168, 1, 200),
+        &SubnetMask::default()
+    ));
+}
+
+#[test]
+fn test_dual_stack_mask_uses_family_prefix() {
+    // /24 for IPv4, /64 for IPv6: two hosts in one /64 share a subnet,
+    // neighbouring /64s in the same /24 of IPv6 space do not
+    let mask = SubnetMask::dual_stack(24, 64);
+    let mut a = [0u8; 16];
+    a[..8].copy_from_slice(&[0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 1]);
+    let mut b = a;
+    b[15] = 0x42;
+    let mut c = a;
+    c[7] = 2;
+
+    assert!(is_same_subnet(&IpAddr::v6(a), &IpAddr::v6(b), &mask));
+    assert!(!is_same_subnet(&IpAddr::v6(a), &IpAddr::v6(c), &mask));
+    assert!(!is_same_subnet(
+        &IpAddr::v4(10, 0, 0, 1),
+        &IpAddr::v4(10, 0, 1, 1),
+        &mask
+    ));
+}
+
 // =============================================================================
 // Test: find_k_closest
 // =============================================================================
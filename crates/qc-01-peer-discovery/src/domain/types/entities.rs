@@ -141,6 +141,20 @@ impl IpAddr {
     pub fn is_ipv6(&self) -> bool {
         matches!(self, IpAddr::V6(_))
     }
+
+    /// Convert an IPv4-mapped IPv6 address (`::ffff:a.b.c.d`) to IPv4.
+    ///
+    /// Dual-stack sockets report IPv4 peers in mapped form. Subnet checks
+    /// must see them as IPv4, or every IPv4 peer would share one IPv6
+    /// prefix.
+    pub fn to_canonical(&self) -> Self {
+        match self {
+            IpAddr::V6([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, a, b, c, d]) => {
+                IpAddr::V4([*a, *b, *c, *d])
+            }
+            other => *other,
+        }
+    }
 }
 
 /// Unix timestamp in seconds
@@ -2,6 +2,8 @@
 //!
 //! Reference: SPEC-01-PEER-DISCOVERY.md Section 2.3
 
+use super::entities::IpAddr;
+
 /// Result of XOR distance calculation between two nodes
 ///
 /// The distance is measured as the index of the first differing bit
@@ -110,35 +112,56 @@ impl KademliaConfig {
 
 /// Subnet mask for IP diversity checks
 ///
-/// Used to enforce INVARIANT-3: max_peers_per_subnet limit
+/// Used to enforce INVARIANT-3: max_peers_per_subnet limit. IPv4 and IPv6
+/// addresses are grouped with separate prefix lengths, since a /24 of IPv6
+/// space is far larger than any one operator.
 ///
 /// Reference: SPEC-01 Section 2.3
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SubnetMask {
-    /// Prefix length in bits (e.g., 24 for /24)
+    /// Prefix length in bits for IPv4 addresses (e.g., 24 for /24)
     pub prefix_length: u8,
+    /// Prefix length in bits for IPv6 addresses (e.g., 48 for /48)
+    pub ipv6_prefix_length: u8,
 }
 
 impl SubnetMask {
-    /// Create a new subnet mask with the given prefix length.
+    /// Create a subnet mask with the same prefix length for both families.
     pub fn new(prefix_length: u8) -> Self {
-        Self { prefix_length }
+        Self::dual_stack(prefix_length, prefix_length)
+    }
+
+    /// Create a subnet mask with separate IPv4 and IPv6 prefix lengths.
+    pub fn dual_stack(ipv4_prefix_length: u8, ipv6_prefix_length: u8) -> Self {
+        Self {
+            prefix_length: ipv4_prefix_length,
+            ipv6_prefix_length,
+        }
     }
 
     /// Default /24 subnet mask for IPv4
     pub fn ipv4_default() -> Self {
-        Self { prefix_length: 24 }
+        Self::new(24)
     }
 
     /// Default /48 subnet mask for IPv6
     pub fn ipv6_default() -> Self {
-        Self { prefix_length: 48 }
+        Self::new(48)
+    }
+
+    /// Prefix length that applies to `ip`.
+    pub fn prefix_for(&self, ip: &IpAddr) -> u8 {
+        match ip.to_canonical() {
+            IpAddr::V4(_) => self.prefix_length,
+            IpAddr::V6(_) => self.ipv6_prefix_length,
+        }
     }
 }
 
 impl Default for SubnetMask {
+    /// /24 for IPv4, /48 for IPv6
     fn default() -> Self {
-        Self::ipv4_default()
+        Self::dual_stack(24, 48)
     }
 }
 
@@ -174,5 +197,9 @@ mod tests {
 
         assert_eq!(ipv4.prefix_length, 24);
         assert_eq!(ipv6.prefix_length, 48);
+
+        let mask = SubnetMask::default();
+        assert_eq!(mask.prefix_for(&IpAddr::v4(10, 0, 0, 1)), 24);
+        assert_eq!(mask.prefix_for(&IpAddr::v6([0x20; 16])), 48);
     }
 }
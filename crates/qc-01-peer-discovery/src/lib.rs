@@ -14,7 +14,7 @@
 //! - `ipc` - Event bus integration (shared-types)
 //! - `rpc` - API Gateway (serde, serde_json)
 //! - `bootstrap` - Bootstrap handler (uuid)
-//! - `network` - UDP/TOML adapters (tokio, toml)
+//! - `network` - UDP/TOML adapters and the async `DiscoveryDriver` (tokio, toml)
 //!
 //! ## Architecture
 //!
@@ -91,14 +91,14 @@ pub mod adapters;
 // Domain entities
 pub use domain::{
     BanReason, DisconnectReason, Distance, IpAddr, KBucket, KademliaConfig, NodeId,
-    PeerDiscoveryError, PeerInfo, PendingInsertion, PendingPeer, RoutingTable, RoutingTableStats,
-    SocketAddr, SubnetMask, Timestamp, WarningType,
+    PeerDiscoveryError, PeerInfo, PendingInsertion, PendingPeer, RoutingTable,
+    RoutingTableSnapshot, RoutingTableStats, SocketAddr, SubnetMask, Timestamp, WarningType,
 };
 
 // Domain services
 pub use domain::{
-    bucket_for_peer, calculate_bucket_index, find_k_closest, is_same_subnet,
-    sort_peers_by_distance, xor_distance,
+    bucket_for_peer, calculate_bucket_index, decode_peer_cache, encode_peer_cache, find_k_closest,
+    is_same_subnet, sort_peers_by_distance, xor_distance,
 };
 
 // Advanced Peer Discovery (Phase 1-3)
@@ -116,8 +116,10 @@ pub use domain::{
     Capability,
     CapabilityData,
     CapabilityType,
+    ConnectStrategy,
     ConnectionDirection,
     ConnectionInfo,
+    ConnectionPath,
     ConnectionSlots,
     ConnectionSlotsConfig,
     ConnectionStats,
@@ -131,6 +133,11 @@ pub use domain::{
     HandshakeConfig,
     HandshakeData,
     HandshakeResult,
+    HolePunchAttempt,
+    IdentityScheme,
+    NatConfig,
+    NatState,
+    NatStatus,
     NodeRecord,
     PeerClassification,
     PeerScore,
@@ -138,18 +145,26 @@ pub use domain::{
     PeerScoreManager,
     PublicKey,
     RejectReason,
+    ScoreRecord,
     Signature,
 };
 
 // Port traits
 pub use ports::{
-    ConfigProvider, NetworkError, NetworkSocket, NodeIdValidator, PeerDiscoveryApi, RandomSource,
-    RateLimiter, SecureHasher, TimeSource, VerificationHandler,
+    ConfigProvider, DiscoveryMessage, NetworkError, NetworkSocket, NodeIdValidator,
+    PeerDiscoveryApi, RandomSource, RateLimiter, RelayPort, RoutingTablePersistence, SecureHasher,
+    TimeSource, VerificationHandler,
 };
 
+#[cfg(feature = "network")]
+pub use ports::AsyncNetworkSocket;
+
 // Service
 pub use service::PeerDiscoveryService;
 
+#[cfg(feature = "network")]
+pub use service::{DiscoveryDriver, DriverConfig, FeelerTask};
+
 // =============================================================================
 // IPC RE-EXPORTS (Requires `ipc` feature)
 // =============================================================================
@@ -174,9 +189,9 @@ pub use ipc::{
     feature = "network"
 ))]
 pub use adapters::{
-    FixedRandomSource, NoOpNetworkSocket, NoOpNodeIdValidator, NoOpRateLimiter, OsRandomSource,
-    ProofOfWorkValidator, SimpleHasher, SipHasher, SlidingWindowRateLimiter, StaticConfigProvider,
-    SystemTimeSource,
+    FileRoutingTablePersistence, FixedRandomSource, InMemoryRoutingTablePersistence,
+    NoOpNetworkSocket, NoOpNodeIdValidator, NoOpRateLimiter, NoOpRelayPort, OsRandomSource,
+    ProofOfWorkValidator, SimpleHasher, SipHasher, SlidingWindowRateLimiter, StaticConfigProvider, SystemTimeSource,
 };
 
 // IPC/EDA adapters (publisher, subscriber)
@@ -201,7 +216,12 @@ pub use adapters::BootstrapHandler;
 
 // Network adapters (tokio-based)
 #[cfg(feature = "network")]
-pub use adapters::{ConfigError, MessageType, TomlConfigProvider, UdpNetworkSocket};
+pub use adapters::{
+    ConfigError, MessageType, TokioUdpSocket, TomlConfigProvider, UdpNetworkSocket,
+};
+
+#[cfg(feature = "quic")]
+pub use adapters::QuicNetworkSocket;
 
 /// Centralized testing utilities and mocks.
 /// Requires feature: `test-utils`
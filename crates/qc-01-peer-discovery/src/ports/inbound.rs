@@ -4,7 +4,9 @@
 //!
 //! Per SPEC-01-PEER-DISCOVERY.md Section 3.1
 
-use crate::domain::{BanDetails, NodeId, PeerDiscoveryError, PeerInfo, RoutingTableStats};
+use crate::domain::{
+    BanDetails, BannedEntry, NodeId, PeerDiscoveryError, PeerInfo, RoutingTableStats,
+};
 
 /// Primary API for interacting with the peer discovery subsystem.
 ///
@@ -95,6 +97,12 @@ pub trait PeerDiscoveryApi {
     /// `true` if the peer is banned and the ban has not expired.
     fn is_banned(&self, node_id: NodeId) -> bool;
 
+    /// List the bans in force, oldest first.
+    ///
+    /// Each entry records who was banned, their last known address, why,
+    /// when, and until when, so operators can audit and export the list.
+    fn banned_peers(&self) -> Vec<BannedEntry>;
+
     /// Update peer's last-seen timestamp (keep-alive).
     ///
     /// Called when we receive valid communication from a peer.
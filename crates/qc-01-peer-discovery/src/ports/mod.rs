@@ -20,6 +20,10 @@ pub mod outbound;
 
 pub use inbound::{PeerDiscoveryApi, VerificationHandler};
 pub use outbound::{
-    ConfigProvider, EnrSignatureVerifier, NetworkError, NetworkSocket, NodeIdValidator,
-    RandomSource, RateLimiter, SecureHasher, TimeSource,
+    ConfigProvider, DiscoveryMessage, EnrSignatureVerifier, NetworkError, NetworkSocket,
+    NodeIdValidator, RandomSource, RateLimiter, RelayPort, RoutingTablePersistence, SecureHasher,
+    TimeSource,
 };
+
+#[cfg(feature = "network")]
+pub use outbound::AsyncNetworkSocket;
@@ -4,7 +4,7 @@
 //!
 //! Per SPEC-01-PEER-DISCOVERY.md Section 3.2
 
-use crate::domain::{KademliaConfig, NodeId, SocketAddr, Timestamp};
+use crate::domain::{KademliaConfig, NodeId, RoutingTableSnapshot, SocketAddr, Timestamp};
 
 /// Abstract interface for network I/O.
 ///
@@ -52,6 +52,59 @@ pub trait NetworkSocket: Send + Sync {
     fn send_pong(&self, target: SocketAddr) -> Result<(), NetworkError>;
 }
 
+/// A discovery message received from a peer.
+///
+/// Carries the sender's claimed NodeId; the address it arrived from is
+/// returned alongside by `AsyncNetworkSocket::recv`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscoveryMessage {
+    /// Liveness check; answer with a PONG.
+    Ping {
+        /// Sender's NodeId
+        from: NodeId,
+    },
+    /// Answer to a PING.
+    Pong {
+        /// Sender's NodeId
+        from: NodeId,
+    },
+    /// Kademlia lookup query.
+    FindNode {
+        /// Sender's NodeId
+        from: NodeId,
+        /// The NodeId being looked up
+        search_id: NodeId,
+    },
+}
+
+/// Asynchronous counterpart of `NetworkSocket`.
+///
+/// Sends never block the runtime, and `recv` lets a single task drive both
+/// directions. Used by `service::DiscoveryDriver`.
+///
+/// Requires feature: `network`
+#[cfg(feature = "network")]
+#[async_trait::async_trait]
+pub trait AsyncNetworkSocket: Send + Sync {
+    /// Send a PING message to a peer.
+    async fn send_ping(&self, target: SocketAddr) -> Result<(), NetworkError>;
+
+    /// Send a FIND_NODE query to a peer.
+    async fn send_find_node(
+        &self,
+        target: SocketAddr,
+        search_id: NodeId,
+    ) -> Result<(), NetworkError>;
+
+    /// Send a PONG response to a peer.
+    async fn send_pong(&self, target: SocketAddr) -> Result<(), NetworkError>;
+
+    /// Wait for the next well-formed message.
+    ///
+    /// Malformed datagrams are dropped by the adapter, not returned.
+    async fn recv(&self) -> Result<(SocketAddr, DiscoveryMessage), NetworkError>;
+}
+
 /// Errors from network operations.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NetworkError {
@@ -279,6 +332,57 @@ pub trait EnrSignatureVerifier: Send + Sync {
     fn hash_signing_payload(&self, payload: &[u8]) -> [u8; 32];
 }
 
+/// Abstract interface for storing the routing table across restarts.
+///
+/// # Security (Anti-Eclipse)
+///
+/// A node that cold-starts from its bootstrap nodes on every restart
+/// rebuilds its view of the network from whoever answers first, which
+/// makes it easier to eclipse. Restoring the peers and addresses it had
+/// already vetted keeps that view.
+pub trait RoutingTablePersistence: Send + Sync {
+    /// Load the last saved snapshot, `None` if nothing was saved yet.
+    ///
+    /// A corrupt or foreign snapshot is an error, not an empty one.
+    fn load(&self) -> std::io::Result<Option<RoutingTableSnapshot>>;
+
+    /// Replace the saved snapshot.
+    ///
+    /// Implementations must not leave a torn snapshot behind if interrupted.
+    fn save(&self, snapshot: &RoutingTableSnapshot) -> std::io::Result<()>;
+}
+
+/// Abstract interface for reaching peers through a third peer.
+///
+/// Covers both halves of NAT traversal that need help from outside:
+/// passing on hole-punch requests, and relaying traffic when punching
+/// cannot work (see `ConnectStrategy`).
+///
+/// # Security
+///
+/// A relay sees who talks to whom and can drop traffic. Relayed
+/// connections are capped in `ConnectionSlots` so they never make up
+/// most of our peers.
+pub trait RelayPort: Send + Sync {
+    /// Ask `rendezvous`, a peer connected to both sides, to tell `target`
+    /// to dial `our_external` now.
+    ///
+    /// The caller dials `target` at the same time, so both NATs see an
+    /// outgoing flow before the other side's packets arrive.
+    fn request_hole_punch(
+        &self,
+        rendezvous: SocketAddr,
+        target: NodeId,
+        our_external: SocketAddr,
+    ) -> Result<(), NetworkError>;
+
+    /// Keep a slot open on `relay` so peers can reach us through it.
+    fn reserve(&self, relay: SocketAddr) -> Result<(), NetworkError>;
+
+    /// Open a connection to `target` forwarded by `relay`.
+    fn connect_via(&self, relay: SocketAddr, target: NodeId) -> Result<(), NetworkError>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
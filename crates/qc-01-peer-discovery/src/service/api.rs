@@ -1,4 +1,6 @@
-use crate::domain::{BanDetails, NodeId, PeerDiscoveryError, PeerInfo, RoutingTableStats};
+use crate::domain::{
+    BanDetails, BannedEntry, NodeId, PeerDiscoveryError, PeerInfo, RoutingTableStats,
+};
 use crate::ports::PeerDiscoveryApi;
 use crate::service::PeerDiscoveryService;
 
@@ -26,6 +28,11 @@ impl PeerDiscoveryApi for PeerDiscoveryService {
         self.routing_table.is_banned(&node_id, now)
     }
 
+    fn banned_peers(&self) -> Vec<BannedEntry> {
+        let now = self.now();
+        self.routing_table.banned_peers(now)
+    }
+
     fn touch_peer(&mut self, node_id: NodeId) -> Result<(), PeerDiscoveryError> {
         let now = self.now();
         self.routing_table.touch_peer(&node_id, now)
@@ -0,0 +1,236 @@
+//! # Async Discovery Driver
+//!
+//! Runs a `PeerDiscoveryService` on Tokio. One task multiplexes:
+//!
+//! - **Receive:** PINGs are answered with PONGs; PONGs resolve eviction
+//!   challenges and refresh `last_seen`
+//! - **Challenges:** challenged peers are PINGed and expired challenges are
+//!   settled (INVARIANT-10)
+//! - **GC:** expired staging entries and bans are dropped (INVARIANT-8)
+//! - **Bucket refresh:** idle buckets get a FIND_NODE lookup
+//! - **Feeler:** an optional synchronous task; network feeler probes run
+//!   in their own task via `FeelerCoordinator::run`
+//!
+//! Requires feature: `network`
+
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::time::{interval, Interval, MissedTickBehavior};
+
+use crate::domain::{calculate_bucket_index, NodeId, SocketAddr};
+use crate::ports::{AsyncNetworkSocket, DiscoveryMessage, NetworkError};
+use crate::service::PeerDiscoveryService;
+
+/// Timer settings for `DiscoveryDriver`.
+#[derive(Debug, Clone)]
+pub struct DriverConfig {
+    /// How often challenged peers are PINGed and expired challenges settled.
+    pub challenge_interval: Duration,
+    /// How often `PeerDiscoveryService::gc` runs.
+    pub gc_interval: Duration,
+    /// How often idle buckets are looked for.
+    pub refresh_interval: Duration,
+    /// A bucket is refreshed once it has not been updated for this long.
+    pub bucket_idle_secs: u64,
+    /// How often the feeler task runs, if one is set.
+    pub feeler_interval: Duration,
+}
+
+impl Default for DriverConfig {
+    fn default() -> Self {
+        Self {
+            challenge_interval: Duration::from_secs(1),
+            gc_interval: Duration::from_secs(60),
+            refresh_interval: Duration::from_secs(60),
+            bucket_idle_secs: 15 * 60,
+            feeler_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Work run on every feeler tick.
+///
+/// Must not block. Feeler probes await the network, so they run in their
+/// own task via `FeelerCoordinator::run` rather than here.
+pub type FeelerTask = Box<dyn FnMut() + Send>;
+
+/// Async run-loop for a `PeerDiscoveryService`.
+///
+/// The service sits behind a mutex so RPC and IPC handlers can keep using
+/// it while the driver runs. The lock is never held across an `.await`.
+pub struct DiscoveryDriver<S: AsyncNetworkSocket> {
+    inner: DriverInner<S>,
+    feeler: Option<FeelerTask>,
+}
+
+/// Everything the loop shares across awaits; the feeler task is kept out
+/// so it does not have to be `Sync`.
+struct DriverInner<S: AsyncNetworkSocket> {
+    service: Arc<Mutex<PeerDiscoveryService>>,
+    socket: Arc<S>,
+    config: DriverConfig,
+}
+
+impl<S: AsyncNetworkSocket> DiscoveryDriver<S> {
+    /// Create a driver for `service` talking over `socket`.
+    pub fn new(
+        service: Arc<Mutex<PeerDiscoveryService>>,
+        socket: Arc<S>,
+        config: DriverConfig,
+    ) -> Self {
+        Self {
+            inner: DriverInner {
+                service,
+                socket,
+                config,
+            },
+            feeler: None,
+        }
+    }
+
+    /// Run `task` every `feeler_interval`.
+    pub fn with_feeler(mut self, task: impl FnMut() + Send + 'static) -> Self {
+        self.feeler = Some(Box::new(task));
+        self
+    }
+
+    /// Shared handle to the driven service.
+    pub fn service(&self) -> Arc<Mutex<PeerDiscoveryService>> {
+        Arc::clone(&self.inner.service)
+    }
+
+    /// Run until `shutdown` completes.
+    ///
+    /// Failed sends are dropped: a peer that misses a PONG or lookup is
+    /// handled by the challenge and GC timers.
+    ///
+    /// # Errors
+    ///
+    /// Returns the socket's error if receiving fails.
+    pub async fn run<F>(self, shutdown: F) -> Result<(), NetworkError>
+    where
+        F: Future<Output = ()>,
+    {
+        tokio::pin!(shutdown);
+
+        let Self {
+            inner: this,
+            feeler: mut feeler_task,
+        } = self;
+
+        let mut challenges = ticker(this.config.challenge_interval);
+        let mut gc = ticker(this.config.gc_interval);
+        let mut refresh = ticker(this.config.refresh_interval);
+        let mut feeler = ticker(this.config.feeler_interval);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                received = this.socket.recv() => {
+                    let (from, msg) = received?;
+                    this.on_message(from, msg).await;
+                }
+                _ = challenges.tick() => this.run_challenges().await,
+                _ = gc.tick() => {
+                    this.lock().gc();
+                }
+                _ = refresh.tick() => this.refresh_buckets().await,
+                _ = feeler.tick(), if feeler_task.is_some() => {
+                    if let Some(task) = feeler_task.as_mut() {
+                        task();
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<S: AsyncNetworkSocket> DriverInner<S> {
+    fn lock(&self) -> MutexGuard<'_, PeerDiscoveryService> {
+        self.service.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    async fn on_message(&self, from: SocketAddr, msg: DiscoveryMessage) {
+        match msg {
+            DiscoveryMessage::Ping { from: node_id } => {
+                self.touch_if_known(&node_id, &from, false);
+                let _ = self.socket.send_pong(from).await;
+            }
+            DiscoveryMessage::Pong { from: node_id } => {
+                self.touch_if_known(&node_id, &from, true);
+            }
+            DiscoveryMessage::FindNode { from: node_id, .. } => {
+                self.touch_if_known(&node_id, &from, false);
+            }
+        }
+    }
+
+    /// Refresh a peer we know at `addr`, resolving its challenge on a PONG.
+    ///
+    /// # Security
+    /// A message claiming a known NodeId from a different address is
+    /// ignored, so a third party cannot keep a dead peer in its bucket.
+    fn touch_if_known(&self, node_id: &NodeId, addr: &SocketAddr, is_pong: bool) {
+        let mut service = self.lock();
+        let table = service.routing_table();
+        let index = calculate_bucket_index(table.local_node_id(), node_id);
+        let known = table.get_bucket(index).is_some_and(|bucket| {
+            bucket
+                .peers()
+                .iter()
+                .any(|p| &p.node_id == node_id && &p.socket_addr == addr)
+        });
+        if !known {
+            return;
+        }
+
+        if is_pong {
+            let _ = service.on_challenge_response(node_id, true);
+        }
+        let now = service.now();
+        let _ = service.routing_table_mut().touch_peer(node_id, now);
+    }
+
+    async fn run_challenges(&self) {
+        let challenged = {
+            let mut service = self.lock();
+            service.check_expired_challenges();
+            service.routing_table().pending_challenges()
+        };
+
+        for peer in challenged {
+            let _ = self.socket.send_ping(peer.socket_addr).await;
+        }
+    }
+
+    async fn refresh_buckets(&self) {
+        let lookups: Vec<(SocketAddr, NodeId)> = {
+            let service = self.lock();
+            let table = service.routing_table();
+            let alpha = table.config().alpha;
+            table
+                .stale_buckets(service.now(), self.config.bucket_idle_secs)
+                .into_iter()
+                .flat_map(|index| {
+                    let target = table.refresh_target(index);
+                    table
+                        .find_closest_peers(&target, alpha)
+                        .into_iter()
+                        .map(move |p| (p.socket_addr, target))
+                })
+                .collect()
+        };
+
+        for (addr, target) in lookups {
+            let _ = self.socket.send_find_node(addr, target).await;
+        }
+    }
+}
+
+fn ticker(period: Duration) -> Interval {
+    let mut ticker = interval(period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    ticker
+}
@@ -11,16 +11,27 @@
 //! The service implements `VerificationHandler` to receive verification events
 //! from Subsystem 10 via the event bus.
 //!
+//! ## Async Driver
+//!
+//! With the `network` feature, `DiscoveryDriver` runs the service on Tokio
+//! over an `AsyncNetworkSocket`, handling receive and the periodic timers.
+//!
 //! Reference: SPEC-01-PEER-DISCOVERY.md Section 3.1
 
 // Semantic submodules
 mod api;
 mod core;
+#[cfg(feature = "network")]
+mod driver;
 mod events;
 mod maintenance;
+mod persistence;
 
 // Re-export public API
 pub use core::PeerDiscoveryService;
 
+#[cfg(feature = "network")]
+pub use driver::{DiscoveryDriver, DriverConfig, FeelerTask};
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,78 @@
+use crate::domain::{AddressManager, PeerScoreManager, RoutingTableSnapshot};
+use crate::ports::RoutingTablePersistence;
+use crate::service::PeerDiscoveryService;
+use std::io;
+
+impl PeerDiscoveryService {
+    /// Save the routing table and ban list, plus the address manager's
+    /// tables and retained peer scores if given.
+    ///
+    /// Call on graceful shutdown. Returns the number of peers and
+    /// addresses saved.
+    pub fn save_routing_table(
+        &self,
+        persistence: &dyn RoutingTablePersistence,
+        addresses: Option<&AddressManager>,
+        scores: Option<&PeerScoreManager>,
+    ) -> io::Result<usize> {
+        let now = self.now();
+        let (new_addresses, tried_addresses) = addresses.map(AddressManager::entries).unzip();
+        let snapshot = RoutingTableSnapshot {
+            saved_at: now,
+            peers: self.routing_table.peers(),
+            new_addresses: new_addresses.unwrap_or_default(),
+            tried_addresses: tried_addresses.unwrap_or_default(),
+            bans: self.routing_table.banned_peers(now),
+            scores: scores.map(|s| s.export(now)).unwrap_or_default(),
+        };
+        persistence.save(&snapshot)?;
+        Ok(snapshot.len())
+    }
+
+    /// Restore the last saved routing table and ban list, plus the address
+    /// manager's tables and retained peer scores if given.
+    ///
+    /// Call at startup, before bootstrapping. Bans still in force go back
+    /// first, so banned peers are not restored. Peers and addresses not
+    /// heard from within `max_age_secs` are pruned; the rest go through
+    /// `RoutingTable::restore_peer` and `AddressManager::restore_entry`, so
+    /// bans and subnet limits still apply. Returns the number of peers and
+    /// addresses restored.
+    pub fn load_routing_table(
+        &mut self,
+        persistence: &dyn RoutingTablePersistence,
+        addresses: Option<&mut AddressManager>,
+        scores: Option<&mut PeerScoreManager>,
+        max_age_secs: u64,
+    ) -> io::Result<usize> {
+        let Some(mut snapshot) = persistence.load()? else {
+            return Ok(0);
+        };
+        let now = self.now();
+        snapshot.prune_stale(now, max_age_secs);
+
+        for ban in snapshot.bans {
+            self.routing_table.restore_ban(ban, now);
+        }
+        if let Some(scores) = scores {
+            for record in snapshot.scores {
+                scores.restore(record, now);
+            }
+        }
+
+        let mut restored = snapshot
+            .peers
+            .into_iter()
+            .filter(|peer| self.routing_table.restore_peer(peer.clone(), now))
+            .count();
+        if let Some(addresses) = addresses {
+            let tried = snapshot.tried_addresses.into_iter().map(|e| (e, true));
+            let new = snapshot.new_addresses.into_iter().map(|e| (e, false));
+            restored += tried
+                .chain(new)
+                .filter(|(entry, tried)| addresses.restore_entry(entry.clone(), *tried))
+                .count();
+        }
+        Ok(restored)
+    }
+}
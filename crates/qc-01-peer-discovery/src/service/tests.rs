@@ -178,3 +178,271 @@ fn test_service_ban_expires_after_duration() {
 
     assert!(!service.is_banned(peer_id), "Ban expired at t=4601");
 }
+
+#[test]
+fn test_service_zero_duration_ban_is_permanent() {
+    let mut service = PeerDiscoveryService::new(
+        make_node_id(0),
+        KademliaConfig::for_testing(),
+        Box::new(ControllableTimeSource::new(1000)),
+    );
+    let peer_id = make_node_id(1);
+
+    service
+        .ban_peer(peer_id, BanDetails::new(0, BanReason::ManualBan))
+        .unwrap();
+
+    assert!(service.is_banned(peer_id));
+    let bans = service.banned_peers();
+    assert_eq!(bans.len(), 1);
+    assert!(bans[0].is_permanent());
+    assert_eq!(bans[0].banned_at, Timestamp::new(1000));
+}
+
+#[test]
+#[cfg(any(
+    feature = "ipc",
+    feature = "rpc",
+    feature = "bootstrap",
+    feature = "network"
+))]
+fn test_service_routing_table_survives_restart() {
+    use crate::adapters::InMemoryRoutingTablePersistence;
+    use crate::domain::{AddressManager, AddressManagerConfig};
+
+    let persistence = InMemoryRoutingTablePersistence::new();
+    let config = KademliaConfig::for_testing();
+    let mut service = PeerDiscoveryService::new(
+        make_node_id(0),
+        config.clone(),
+        Box::new(ControllableTimeSource::new(1000)),
+    );
+    let peer = make_peer(1);
+    service.add_peer(peer.clone()).unwrap();
+    service.on_verification_result(&peer.node_id, true).unwrap();
+    let mut addresses = AddressManager::new(AddressManagerConfig::default());
+    addresses
+        .add_new(make_peer(2), &IpAddr::v4(10, 0, 0, 1), Timestamp::new(1000))
+        .unwrap();
+
+    let saved = service
+        .save_routing_table(&persistence, Some(&addresses), None)
+        .unwrap();
+    assert_eq!(saved, 2);
+
+    // A day later both are restored
+    let mut restarted = PeerDiscoveryService::new(
+        make_node_id(0),
+        config.clone(),
+        Box::new(ControllableTimeSource::new(1000 + 86_400)),
+    );
+    let mut restored_addresses = AddressManager::new(AddressManagerConfig::default());
+    let restored = restarted
+        .load_routing_table(
+            &persistence,
+            Some(&mut restored_addresses),
+            None,
+            2 * 86_400,
+        )
+        .unwrap();
+    assert_eq!(restored, 2);
+    assert_eq!(restarted.get_stats().total_peers, 1);
+    assert_eq!(restored_addresses.stats().new_count, 1);
+
+    // Past the maximum age nothing is restored
+    let mut late = PeerDiscoveryService::new(
+        make_node_id(0),
+        config,
+        Box::new(ControllableTimeSource::new(1000 + 3 * 86_400)),
+    );
+    assert_eq!(
+        late.load_routing_table(&persistence, None, None, 2 * 86_400)
+            .unwrap(),
+        0
+    );
+    assert_eq!(late.get_stats().total_peers, 0);
+}
+
+#[test]
+#[cfg(any(
+    feature = "ipc",
+    feature = "rpc",
+    feature = "bootstrap",
+    feature = "network"
+))]
+fn test_service_bans_and_scores_survive_restart() {
+    use crate::adapters::InMemoryRoutingTablePersistence;
+    use crate::domain::{PeerScoreConfig, PeerScoreManager};
+
+    let persistence = InMemoryRoutingTablePersistence::new();
+    let config = KademliaConfig::for_testing();
+    let mut service = PeerDiscoveryService::new(
+        make_node_id(0),
+        config.clone(),
+        Box::new(ControllableTimeSource::new(1000)),
+    );
+    let peer = make_peer(1);
+    service.add_peer(peer.clone()).unwrap();
+    service.on_verification_result(&peer.node_id, true).unwrap();
+    service
+        .ban_peer(
+            peer.node_id,
+            BanDetails::new(3600, BanReason::ExcessiveRequests),
+        )
+        .unwrap();
+    let mut scores = PeerScoreManager::new(PeerScoreConfig::default());
+    let scored = make_node_id(2);
+    scores.on_peer_connected(scored, Timestamp::new(1000));
+    scores.on_invalid_block(&scored);
+
+    service
+        .save_routing_table(&persistence, None, Some(&scores))
+        .unwrap();
+
+    // Ten minutes later the ban still holds and the score has decayed
+    let mut restarted = PeerDiscoveryService::new(
+        make_node_id(0),
+        config,
+        Box::new(ControllableTimeSource::new(1600)),
+    );
+    let mut restored_scores = PeerScoreManager::new(PeerScoreConfig::default());
+    restarted
+        .load_routing_table(&persistence, None, Some(&mut restored_scores), 86_400)
+        .unwrap();
+
+    assert!(restarted.is_banned(peer.node_id));
+    let bans = restarted.banned_peers();
+    assert_eq!(bans[0].reason, BanReason::ExcessiveRequests);
+    assert_eq!(bans[0].socket_addr, Some(peer.socket_addr));
+    assert_eq!(bans[0].banned_until, Timestamp::new(4600));
+    assert_eq!(restarted.get_stats().total_peers, 0);
+
+    let score = restored_scores
+        .retained_score(&scored, Timestamp::new(1600))
+        .unwrap();
+    assert!((score - (-50.0 * 0.9f64.powi(10))).abs() < 1e-9);
+}
+
+#[cfg(feature = "network")]
+mod driver {
+    use super::*;
+    use crate::adapters::TokioUdpSocket;
+    use crate::ports::{AsyncNetworkSocket, DiscoveryMessage};
+    use crate::service::{DiscoveryDriver, DriverConfig};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::sync::oneshot;
+
+    fn loopback(socket: &TokioUdpSocket) -> SocketAddr {
+        let port = socket.local_addr().unwrap().port();
+        SocketAddr::new(IpAddr::v4(127, 0, 0, 1), port)
+    }
+
+    fn bind(val: u8) -> TokioUdpSocket {
+        TokioUdpSocket::bind("127.0.0.1:0".parse().unwrap(), make_node_id(val)).unwrap()
+    }
+
+    /// Spawn a driver for `service` and return its address and shutdown handle.
+    fn spawn_driver(
+        service: Arc<Mutex<PeerDiscoveryService>>,
+    ) -> (SocketAddr, oneshot::Sender<()>) {
+        let socket = bind(0);
+        let addr = loopback(&socket);
+        let (stop, stopped) = oneshot::channel::<()>();
+        let driver = DiscoveryDriver::new(service, Arc::new(socket), DriverConfig::default());
+        tokio::spawn(driver.run(async {
+            let _ = stopped.await;
+        }));
+        (addr, stop)
+    }
+
+    /// Poll `check` for up to a second.
+    async fn eventually(check: impl Fn() -> bool) -> bool {
+        let mut attempts = 0;
+        while !check() && attempts < 100 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            attempts += 1;
+        }
+        check()
+    }
+
+    #[tokio::test]
+    async fn test_driver_answers_ping() {
+        let service = PeerDiscoveryService::new(
+            make_node_id(0),
+            KademliaConfig::for_testing(),
+            Box::new(ControllableTimeSource::new(1000)),
+        );
+        let (driver_addr, stop) = spawn_driver(Arc::new(Mutex::new(service)));
+
+        let remote = bind(1);
+        remote.send_ping(driver_addr).await.unwrap();
+        let (from, msg) = tokio::time::timeout(Duration::from_secs(5), remote.recv())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(from, driver_addr);
+        assert_eq!(
+            msg,
+            DiscoveryMessage::Pong {
+                from: make_node_id(0)
+            }
+        );
+        let _ = stop.send(());
+    }
+
+    #[tokio::test]
+    async fn test_driver_pings_challenged_peer_and_keeps_it_on_pong() {
+        let remote = bind(128);
+        let mut service = PeerDiscoveryService::new(
+            make_node_id(0),
+            KademliaConfig::for_testing(),
+            Box::new(ControllableTimeSource::new(1000)),
+        );
+
+        // Fill bucket 0 (k = 3), oldest peer first
+        let now = Timestamp::new(1000);
+        let oldest = PeerInfo::new(make_node_id(128), loopback(&remote), now);
+        let table = service.routing_table_mut();
+        assert!(table.restore_peer(oldest, now));
+        assert!(table.restore_peer(make_peer(129), now));
+        assert!(table.restore_peer(make_peer(130), now));
+
+        // A verified newcomer challenges the oldest peer
+        let newcomer = make_peer(131);
+        service.add_peer(newcomer.clone()).unwrap();
+        let challenged = service
+            .on_verification_result(&newcomer.node_id, true)
+            .unwrap();
+        assert_eq!(challenged, Some(make_node_id(128)));
+
+        let service = Arc::new(Mutex::new(service));
+        let (driver_addr, stop) = spawn_driver(Arc::clone(&service));
+
+        let (from, msg) = tokio::time::timeout(Duration::from_secs(5), remote.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(from, driver_addr);
+        assert_eq!(
+            msg,
+            DiscoveryMessage::Ping {
+                from: make_node_id(0)
+            }
+        );
+        remote.send_pong(driver_addr).await.unwrap();
+
+        let challenge_settled = || {
+            let service = service.lock().unwrap();
+            service.routing_table().pending_challenges().is_empty()
+        };
+        assert!(eventually(challenge_settled).await);
+
+        // INVARIANT-10: the live peer stays, the newcomer is dropped
+        let peers = service.lock().unwrap().routing_table().peers();
+        assert!(peers.iter().any(|p| p.node_id == make_node_id(128)));
+        assert!(!peers.iter().any(|p| p.node_id == newcomer.node_id));
+        let _ = stop.send(());
+    }
+}
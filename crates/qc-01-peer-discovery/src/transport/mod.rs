@@ -3,12 +3,22 @@
 //! ## Available Transports
 //!
 //! - `quic` - QUIC/HTTP3 with encrypted headers and 0-RTT support
+//! - `udp` - Dual-stack UDP binding shared by the UDP and QUIC transports
 //!
 //! ## Feature Gates
 //!
 //! - `quic` feature: Enables full async QUIC transport with quinn
+//! - `network` feature: Enables dual-stack UDP binding
 //! - Without feature: Basic replay protection and config types only
 
 pub mod quic;
+#[cfg(feature = "network")]
+pub mod udp;
 
 pub use quic::{QuicConfig, QuicConnectionState, QuicError, QuicTransport, ReplayProtection};
+
+#[cfg(feature = "quic")]
+pub use quic::{Frame, InboundMessage, InboundRequest, QuicMesh, MAX_MESSAGE_SIZE};
+
+#[cfg(feature = "network")]
+pub use udp::{bind_udp, canonical_addr, send_addr};
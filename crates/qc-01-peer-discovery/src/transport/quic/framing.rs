@@ -0,0 +1,99 @@
+//! # Stream Framing
+//!
+//! Length-prefixed frames for request/response exchanges on bidirectional
+//! QUIC streams.
+//!
+//! ## Wire Format
+//!
+//! ```text
+//! [length: u32 BE] [type: u8] [payload: length - 1 bytes]
+//! ```
+//!
+//! `length` covers the type byte and the payload, so it is never zero. The
+//! type byte carries the message type (e.g. `MessageType::FindNode`); the
+//! transport does not interpret it.
+
+use super::QuicError;
+
+/// Bytes in the length prefix.
+const LENGTH_PREFIX: usize = 4;
+
+/// One framed message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// Message type byte.
+    pub kind: u8,
+    /// Message body.
+    pub payload: Vec<u8>,
+}
+
+impl Frame {
+    /// Create a frame.
+    pub fn new(kind: u8, payload: impl Into<Vec<u8>>) -> Self {
+        Self {
+            kind,
+            payload: payload.into(),
+        }
+    }
+
+    /// Encode as `[length][type][payload]`.
+    pub fn encode(&self) -> Vec<u8> {
+        let length = (1 + self.payload.len()) as u32;
+        let mut buf = Vec::with_capacity(LENGTH_PREFIX + length as usize);
+        buf.extend_from_slice(&length.to_be_bytes());
+        buf.push(self.kind);
+        buf.extend_from_slice(&self.payload);
+        buf
+    }
+}
+
+/// Validate a length prefix against `max_size`.
+fn frame_length(prefix: [u8; LENGTH_PREFIX], max_size: usize) -> Result<usize, QuicError> {
+    let length = u32::from_be_bytes(prefix) as usize;
+    if length == 0 {
+        return Err(QuicError::RecvFailed {
+            reason: "empty frame".into(),
+        });
+    }
+    if length > max_size {
+        return Err(QuicError::RecvFailed {
+            reason: format!("frame of {} bytes exceeds limit of {}", length, max_size),
+        });
+    }
+    Ok(length)
+}
+
+/// Write one frame.
+pub(super) async fn write_frame(
+    stream: &mut quinn::SendStream,
+    frame: &Frame,
+) -> Result<(), QuicError> {
+    stream
+        .write_all(&frame.encode())
+        .await
+        .map_err(|e| QuicError::SendFailed {
+            reason: e.to_string(),
+        })
+}
+
+/// Read one frame of at most `max_size` bytes (type byte included).
+pub(super) async fn read_frame(
+    stream: &mut quinn::RecvStream,
+    max_size: usize,
+) -> Result<Frame, QuicError> {
+    let recv_failed = |e: quinn::ReadExactError| QuicError::RecvFailed {
+        reason: e.to_string(),
+    };
+
+    let mut prefix = [0u8; LENGTH_PREFIX];
+    stream.read_exact(&mut prefix).await.map_err(recv_failed)?;
+    let length = frame_length(prefix, max_size)?;
+
+    let mut body = vec![0u8; length];
+    stream.read_exact(&mut body).await.map_err(recv_failed)?;
+    let payload = body.split_off(1);
+    Ok(Frame {
+        kind: body[0],
+        payload,
+    })
+}
@@ -0,0 +1,425 @@
+//! # QUIC Mesh
+//!
+//! Shared handle over a bound QUIC endpoint for talking to many peers at once.
+//!
+//! `QuicTransport` takes `&mut self` everywhere and reads connections one at a
+//! time, which suits a single peer. A mesh instead owns the endpoint behind an
+//! `Arc`, accepts connections in a background task and runs one reader task per
+//! connection. Every message from every peer arrives on one channel.
+//!
+//! Each one-way message travels on its own unidirectional stream, so no extra
+//! framing is needed. Request/response exchanges (FIND_NODE, STATUS, feeler
+//! probes) use one bidirectional stream per request instead: the caller writes
+//! a `Frame` and reads one back under a deadline, and the peer gets the
+//! request as an `InboundRequest` to answer.
+//!
+//! Peers behind a NAT are reached with `punch`: both sides dial each other at
+//! once, and whichever handshake gets through first becomes the connection.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::transport::udp::canonical_addr;
+
+use super::framing::{read_frame, write_frame, Frame};
+use super::{QuicError, QuicTransport};
+
+/// Largest message a peer may send on one stream.
+pub const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// How often `punch` checks whether the peer's dial got through first.
+const PUNCH_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A message received from a peer.
+pub type InboundMessage = (SocketAddr, Vec<u8>);
+
+/// A request received from a peer on a bidirectional stream.
+///
+/// Dropping it without calling `respond` finishes the stream empty, which
+/// the requester sees as a failed request.
+pub struct InboundRequest {
+    /// Peer that sent the request.
+    pub from: SocketAddr,
+    /// The request frame.
+    pub frame: Frame,
+    stream: quinn::SendStream,
+    timeout: Duration,
+}
+
+impl InboundRequest {
+    /// Send the reply and close the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns `QuicError::RequestTimeout` if the reply is not written within
+    /// the mesh's request timeout, or `SendFailed` if the write fails.
+    pub async fn respond(mut self, reply: &Frame) -> Result<(), QuicError> {
+        let from = self.from;
+        tokio::time::timeout(self.timeout, async {
+            write_frame(&mut self.stream, reply).await?;
+            self.stream.finish().map_err(|e| QuicError::SendFailed {
+                reason: e.to_string(),
+            })
+        })
+        .await
+        .map_err(|_| QuicError::RequestTimeout {
+            remote: from.to_string(),
+        })?
+    }
+}
+
+/// Cloneable handle to a QUIC endpoint and its open connections.
+#[derive(Clone)]
+pub struct QuicMesh {
+    inner: Arc<MeshInner>,
+}
+
+struct MeshInner {
+    endpoint: quinn::Endpoint,
+    connections: Mutex<HashMap<SocketAddr, quinn::Connection>>,
+    inbound: mpsc::Sender<InboundMessage>,
+    requests: Mutex<Option<mpsc::Sender<InboundRequest>>>,
+    connect_timeout: Duration,
+    request_timeout: Duration,
+}
+
+impl QuicTransport {
+    /// Turn a bound transport into a mesh.
+    ///
+    /// Starts the accept loop on the current Tokio runtime. Connections the
+    /// transport already holds are carried over. Received messages are
+    /// delivered to the returned receiver, which buffers up to `capacity`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `QuicError::NotInitialized` if `bind` has not been called.
+    pub fn into_mesh(
+        self,
+        capacity: usize,
+    ) -> Result<(QuicMesh, mpsc::Receiver<InboundMessage>), QuicError> {
+        let endpoint = self.endpoint.ok_or(QuicError::NotInitialized)?;
+        let (inbound, rx) = mpsc::channel(capacity.max(1));
+        let mesh = QuicMesh {
+            inner: Arc::new(MeshInner {
+                endpoint,
+                connections: Mutex::new(HashMap::new()),
+                inbound,
+                requests: Mutex::new(None),
+                connect_timeout: self.config.connect_timeout,
+                request_timeout: self.config.request_timeout,
+            }),
+        };
+
+        for connection in self.connections.into_values() {
+            mesh.register(connection);
+        }
+
+        let acceptor = mesh.clone();
+        tokio::spawn(async move { acceptor.accept_loop().await });
+
+        Ok((mesh, rx))
+    }
+}
+
+impl QuicMesh {
+    /// Connect to a peer, reusing an open connection if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `QuicError::ConnectionRefused`, `ConnectionTimeout` or
+    /// `TlsError` if the handshake does not complete.
+    pub async fn connect(&self, remote: SocketAddr, server_name: &str) -> Result<(), QuicError> {
+        if self.is_connected(&remote) {
+            return Ok(());
+        }
+
+        let connecting = self
+            .inner
+            .endpoint
+            .connect(remote, server_name)
+            .map_err(|_| QuicError::ConnectionRefused {
+                remote: remote.to_string(),
+            })?;
+
+        let connection = tokio::time::timeout(self.inner.connect_timeout, connecting)
+            .await
+            .map_err(|_| QuicError::ConnectionTimeout {
+                remote: remote.to_string(),
+            })?
+            .map_err(|e| QuicError::TlsError {
+                reason: e.to_string(),
+            })?;
+
+        self.register(connection);
+        Ok(())
+    }
+
+    /// Punch a hole to a peer behind a NAT.
+    ///
+    /// Both sides must call this at about the same time, each with the
+    /// other's external address (see `RelayPort::request_hole_punch`). The
+    /// outgoing packets open a mapping in our NAT, so the peer's dial gets
+    /// in, and theirs does the same for ours. Returns once either
+    /// connection is up.
+    ///
+    /// # Errors
+    ///
+    /// Same as `connect` if neither side got through.
+    pub async fn punch(&self, remote: SocketAddr, server_name: &str) -> Result<(), QuicError> {
+        let dial = self.connect(remote, server_name);
+        tokio::pin!(dial);
+        let mut poll = tokio::time::interval(PUNCH_POLL_INTERVAL);
+
+        loop {
+            tokio::select! {
+                result = &mut dial => {
+                    return if self.is_connected(&remote) { Ok(()) } else { result };
+                }
+                _ = poll.tick() => {
+                    if self.is_connected(&remote) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Send one message to a connected peer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `QuicError::ConnectionClosed` if there is no connection to
+    /// `remote`, or a stream/send error if the write fails.
+    pub async fn send(&self, remote: SocketAddr, data: &[u8]) -> Result<(), QuicError> {
+        let connection = self
+            .connection(&remote)
+            .ok_or(QuicError::ConnectionClosed {
+                reason: "not connected".into(),
+            })?;
+
+        let mut stream = connection
+            .open_uni()
+            .await
+            .map_err(|e| QuicError::StreamError {
+                reason: e.to_string(),
+            })?;
+
+        stream
+            .write_all(data)
+            .await
+            .map_err(|e| QuicError::SendFailed {
+                reason: e.to_string(),
+            })?;
+
+        stream.finish().map_err(|e| QuicError::SendFailed {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Send a request on a new bidirectional stream and wait for the reply.
+    ///
+    /// The whole exchange (opening the stream, writing the request, reading
+    /// the reply) must finish within `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `QuicError::ConnectionClosed` if there is no connection to
+    /// `remote`, `RequestTimeout` if the deadline passes, or a stream, send
+    /// or receive error if the exchange fails (including a peer that does not
+    /// serve requests).
+    pub async fn request(
+        &self,
+        remote: SocketAddr,
+        request: &Frame,
+        timeout: Duration,
+    ) -> Result<Frame, QuicError> {
+        let connection = self
+            .connection(&remote)
+            .ok_or(QuicError::ConnectionClosed {
+                reason: "not connected".into(),
+            })?;
+
+        let exchange = async {
+            let (mut send, mut recv) =
+                connection
+                    .open_bi()
+                    .await
+                    .map_err(|e| QuicError::StreamError {
+                        reason: e.to_string(),
+                    })?;
+            write_frame(&mut send, request).await?;
+            send.finish().map_err(|e| QuicError::SendFailed {
+                reason: e.to_string(),
+            })?;
+            read_frame(&mut recv, MAX_MESSAGE_SIZE).await
+        };
+
+        tokio::time::timeout(timeout, exchange)
+            .await
+            .map_err(|_| QuicError::RequestTimeout {
+                remote: remote.to_string(),
+            })?
+    }
+
+    /// Start delivering inbound requests to the returned receiver, which
+    /// buffers up to `capacity`.
+    ///
+    /// Until this is called, request streams from peers are refused. Calling
+    /// it again moves delivery to the new receiver.
+    pub fn serve_requests(&self, capacity: usize) -> mpsc::Receiver<InboundRequest> {
+        let (tx, rx) = mpsc::channel(capacity.max(1));
+        *self
+            .inner
+            .requests
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(tx);
+        rx
+    }
+
+    /// True if there is a live connection to `remote`.
+    pub fn is_connected(&self, remote: &SocketAddr) -> bool {
+        self.connection(remote).is_some()
+    }
+
+    /// Addresses of all live connections.
+    pub fn connected(&self) -> Vec<SocketAddr> {
+        self.lock()
+            .iter()
+            .filter(|(_, c)| c.close_reason().is_none())
+            .map(|(addr, _)| *addr)
+            .collect()
+    }
+
+    /// Close the connection to a peer.
+    pub fn disconnect(&self, remote: &SocketAddr) {
+        if let Some(connection) = self.lock().remove(&canonical_addr(*remote)) {
+            connection.close(0u32.into(), b"closed");
+        }
+    }
+
+    /// Local address of the endpoint.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.inner.endpoint.local_addr().ok()
+    }
+
+    /// Close every connection and stop accepting new ones.
+    pub fn close(&self) {
+        self.lock().clear();
+        self.inner.endpoint.close(0u32.into(), b"shutdown");
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<SocketAddr, quinn::Connection>> {
+        self.inner
+            .connections
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn connection(&self, remote: &SocketAddr) -> Option<quinn::Connection> {
+        self.lock()
+            .get(&canonical_addr(*remote))
+            .filter(|c| c.close_reason().is_none())
+            .cloned()
+    }
+
+    async fn accept_loop(self) {
+        while let Some(incoming) = self.inner.endpoint.accept().await {
+            tokio::spawn(self.clone().handshake(incoming));
+        }
+    }
+
+    async fn handshake(self, incoming: quinn::Incoming) {
+        if let Ok(connection) = incoming.await {
+            self.register(connection);
+        }
+    }
+
+    /// Track a connection and start reading from it.
+    ///
+    /// Connections are keyed by canonical address: an IPv6 endpoint reports
+    /// IPv4 peers as `::ffff:a.b.c.d`, which callers know as `a.b.c.d`.
+    fn register(&self, connection: quinn::Connection) {
+        let remote = canonical_addr(connection.remote_address());
+        self.lock().insert(remote, connection.clone());
+
+        let mesh = self.clone();
+        let streams = connection.clone();
+        tokio::spawn(async move { mesh.read_loop(remote, connection).await });
+
+        let mesh = self.clone();
+        tokio::spawn(async move { mesh.request_loop(remote, streams).await });
+    }
+
+    async fn read_loop(self, remote: SocketAddr, connection: quinn::Connection) {
+        while let Ok(mut stream) = connection.accept_uni().await {
+            let Ok(data) = stream.read_to_end(MAX_MESSAGE_SIZE).await else {
+                continue;
+            };
+            if data.is_empty() {
+                continue;
+            }
+            if self.inner.inbound.send((remote, data)).await.is_err() {
+                break;
+            }
+        }
+
+        // Only forget the connection if a newer one has not replaced it.
+        let mut connections = self.lock();
+        if connections
+            .get(&remote)
+            .is_some_and(|c| c.stable_id() == connection.stable_id())
+        {
+            connections.remove(&remote);
+        }
+    }
+
+    async fn request_loop(self, remote: SocketAddr, connection: quinn::Connection) {
+        while let Ok((send, recv)) = connection.accept_bi().await {
+            tokio::spawn(self.clone().read_request(remote, send, recv));
+        }
+    }
+
+    /// Read one request frame and hand it to the request handler.
+    ///
+    /// Streams are reset if nobody serves requests, and dropped if the peer
+    /// does not send a valid frame within the request timeout.
+    async fn read_request(
+        self,
+        remote: SocketAddr,
+        mut send: quinn::SendStream,
+        mut recv: quinn::RecvStream,
+    ) {
+        let handler = self
+            .inner
+            .requests
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        let Some(handler) = handler else {
+            let _ = send.reset(0u32.into());
+            return;
+        };
+
+        let timeout = self.inner.request_timeout;
+        let frame =
+            match tokio::time::timeout(timeout, read_frame(&mut recv, MAX_MESSAGE_SIZE)).await {
+                Ok(Ok(frame)) => frame,
+                _ => {
+                    let _ = send.reset(0u32.into());
+                    return;
+                }
+            };
+
+        let request = InboundRequest {
+            from: remote,
+            frame,
+            stream: send,
+            timeout,
+        };
+        let _ = handler.send(request).await;
+    }
+}
@@ -15,6 +15,16 @@
 //! - RFC 9000 (QUIC)
 //! - RFC 9001 (QUIC-TLS)
 
+#[cfg(feature = "quic")]
+mod framing;
+#[cfg(feature = "quic")]
+mod mesh;
+
+#[cfg(feature = "quic")]
+pub use framing::Frame;
+#[cfg(feature = "quic")]
+pub use mesh::{InboundMessage, InboundRequest, QuicMesh, MAX_MESSAGE_SIZE};
+
 #[cfg(feature = "quic")]
 use std::collections::HashMap;
 use std::net::SocketAddr;
@@ -43,18 +53,22 @@ pub struct QuicConfig {
     pub max_datagram_size: u16,
     /// Keep-alive interval (0 to disable)
     pub keep_alive_interval: Option<Duration>,
+    /// Time a peer gets to send a request frame, and we get to write the
+    /// reply, on an inbound request stream
+    pub request_timeout: Duration,
 }
 
 impl Default for QuicConfig {
     fn default() -> Self {
         Self {
-            bind_addr: "0.0.0.0:0".parse().expect("valid default bind addr"),
+            bind_addr: "[::]:0".parse().expect("valid default bind addr"),
             connect_timeout: Duration::from_secs(10),
             idle_timeout: Duration::from_secs(30),
             max_streams: 100,
             enable_0rtt: true,
             max_datagram_size: 1350,
             keep_alive_interval: Some(Duration::from_secs(15)),
+            request_timeout: Duration::from_secs(5),
         }
     }
 }
@@ -71,6 +85,7 @@ impl QuicConfig {
             enable_0rtt: false, // Simpler for tests
             max_datagram_size: 1350,
             keep_alive_interval: None,
+            request_timeout: Duration::from_secs(2),
         }
     }
 }
@@ -205,6 +220,11 @@ pub enum QuicError {
         /// Remote address.
         remote: String,
     },
+    /// Request got no complete response in time.
+    RequestTimeout {
+        /// Remote address.
+        remote: String,
+    },
     /// Connection was refused by peer.
     ConnectionRefused {
         /// Remote address.
@@ -253,6 +273,9 @@ impl std::fmt::Display for QuicError {
             Self::ConnectionTimeout { remote } => {
                 write!(f, "connection to {} timed out", remote)
             }
+            Self::RequestTimeout { remote } => {
+                write!(f, "request to {} timed out", remote)
+            }
             Self::ConnectionRefused { remote } => {
                 write!(f, "connection to {} refused", remote)
             }
@@ -324,14 +347,23 @@ impl QuicTransport {
         // Create client config (accepts any certificate for P2P)
         let client_config = self.generate_client_config()?;
 
-        // Build endpoint
-        let mut endpoint =
-            quinn::Endpoint::server(server_config, self.config.bind_addr).map_err(|e| {
-                QuicError::BindFailed {
-                    addr: self.config.bind_addr.to_string(),
-                    reason: e.to_string(),
-                }
-            })?;
+        // Build endpoint on a dual-stack socket
+        let bind_failed = |e: std::io::Error| QuicError::BindFailed {
+            addr: self.config.bind_addr.to_string(),
+            reason: e.to_string(),
+        };
+        let socket = super::udp::bind_udp(self.config.bind_addr).map_err(bind_failed)?;
+        let runtime = quinn::default_runtime().ok_or_else(|| QuicError::BindFailed {
+            addr: self.config.bind_addr.to_string(),
+            reason: "no async runtime".into(),
+        })?;
+        let mut endpoint = quinn::Endpoint::new(
+            quinn::EndpointConfig::default(),
+            Some(server_config),
+            socket,
+            runtime,
+        )
+        .map_err(bind_failed)?;
 
         endpoint.set_default_client_config(client_config);
 
@@ -406,7 +438,7 @@ impl QuicTransport {
         let incoming = endpoint.accept().await?;
         let connection = incoming.await.ok()?;
 
-        let remote = connection.remote_address();
+        let remote = super::udp::canonical_addr(connection.remote_address());
 
         // Create connection state
         let mut conn_id = [0u8; 16];
@@ -59,3 +59,185 @@ fn test_quic_error_display() {
     };
     assert!(err.to_string().contains("timed out"));
 }
+
+#[cfg(feature = "quic")]
+#[tokio::test]
+async fn test_mesh_send_and_reply() {
+    let mut a = QuicTransport::new(QuicConfig::for_testing());
+    let mut b = QuicTransport::new(QuicConfig::for_testing());
+    let a_addr = a.bind().await.unwrap();
+    let b_addr = b.bind().await.unwrap();
+    let (a, mut a_rx) = a.into_mesh(8).unwrap();
+    let (b, mut b_rx) = b.into_mesh(8).unwrap();
+
+    a.connect(b_addr, "localhost").await.unwrap();
+    a.send(b_addr, b"ping").await.unwrap();
+    let (from, data) = b_rx.recv().await.unwrap();
+    assert_eq!(from, a_addr);
+    assert_eq!(data, b"ping");
+
+    // B answers over the connection A opened.
+    assert!(b.is_connected(&a_addr));
+    b.send(a_addr, b"pong").await.unwrap();
+    let (from, data) = a_rx.recv().await.unwrap();
+    assert_eq!(from, b_addr);
+    assert_eq!(data, b"pong");
+
+    a.disconnect(&b_addr);
+    assert!(!a.is_connected(&b_addr));
+    a.close();
+    b.close();
+}
+
+#[cfg(feature = "quic")]
+#[tokio::test]
+async fn test_mesh_simultaneous_punch() {
+    let mut a = QuicTransport::new(QuicConfig::for_testing());
+    let mut b = QuicTransport::new(QuicConfig::for_testing());
+    let a_addr = a.bind().await.unwrap();
+    let b_addr = b.bind().await.unwrap();
+    let (a, _a_rx) = a.into_mesh(8).unwrap();
+    let (b, mut b_rx) = b.into_mesh(8).unwrap();
+
+    let (a_result, b_result) =
+        tokio::join!(a.punch(b_addr, "localhost"), b.punch(a_addr, "localhost"));
+    a_result.unwrap();
+    b_result.unwrap();
+
+    a.send(b_addr, b"through").await.unwrap();
+    let (from, data) = b_rx.recv().await.unwrap();
+    assert_eq!(from, a_addr);
+    assert_eq!(data, b"through");
+    a.close();
+    b.close();
+}
+
+#[cfg(feature = "quic")]
+#[tokio::test]
+async fn test_mesh_dual_stack_sees_ipv4_peer() {
+    let mut a = QuicTransport::new(QuicConfig {
+        bind_addr: "[::]:0".parse().unwrap(),
+        ..QuicConfig::for_testing()
+    });
+    let mut b = QuicTransport::new(QuicConfig::for_testing());
+    let a_port = a.bind().await.unwrap().port();
+    let b_addr = b.bind().await.unwrap();
+    let (a, _a_rx) = a.into_mesh(8).unwrap();
+    let (b, mut b_rx) = b.into_mesh(8).unwrap();
+
+    // A's socket is IPv6, but the IPv4 peer keeps its IPv4 address.
+    let a_v4: SocketAddr = ([127, 0, 0, 1], a_port).into();
+    b.connect(a_v4, "localhost").await.unwrap();
+    b.send(a_v4, b"hello").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(a.is_connected(&b_addr));
+    assert_eq!(a.connected(), vec![b_addr]);
+
+    a.send(b_addr, b"ack").await.unwrap();
+    let (from, data) = b_rx.recv().await.unwrap();
+    assert_eq!(from, a_v4);
+    assert_eq!(data, b"ack");
+}
+
+#[cfg(feature = "quic")]
+#[test]
+fn test_frame_encoding() {
+    let frame = Frame::new(0x03, vec![0xAA, 0xBB]);
+    assert_eq!(frame.encode(), vec![0, 0, 0, 3, 0x03, 0xAA, 0xBB]);
+    assert_eq!(
+        Frame::new(0x01, Vec::new()).encode(),
+        vec![0, 0, 0, 1, 0x01]
+    );
+}
+
+/// Two meshes with A connected to B.
+#[cfg(feature = "quic")]
+async fn connected_pair() -> (QuicMesh, SocketAddr, QuicMesh, SocketAddr) {
+    let mut a = QuicTransport::new(QuicConfig::for_testing());
+    let mut b = QuicTransport::new(QuicConfig::for_testing());
+    let a_addr = a.bind().await.unwrap();
+    let b_addr = b.bind().await.unwrap();
+    let (a, _a_rx) = a.into_mesh(8).unwrap();
+    let (b, _b_rx) = b.into_mesh(8).unwrap();
+    a.connect(b_addr, "localhost").await.unwrap();
+    (a, a_addr, b, b_addr)
+}
+
+#[cfg(feature = "quic")]
+#[tokio::test]
+async fn test_mesh_request_response() {
+    use crate::adapters::network::MessageType;
+
+    let (a, a_addr, b, b_addr) = connected_pair().await;
+    let mut requests = b.serve_requests(8);
+    tokio::spawn(async move {
+        while let Some(request) = requests.recv().await {
+            assert_eq!(request.from, a_addr);
+            let mut reply = request.frame.payload.clone();
+            reply.reverse();
+            let reply = Frame::new(MessageType::Nodes as u8, reply);
+            request.respond(&reply).await.unwrap();
+        }
+    });
+
+    // Concurrent requests each get their own stream and their own reply.
+    let timeout = Duration::from_secs(2);
+    let first = Frame::new(MessageType::FindNode as u8, vec![1, 0xFF]);
+    let second = Frame::new(MessageType::FindNode as u8, vec![2, 0xFF]);
+    let (first, second) = tokio::join!(
+        a.request(b_addr, &first, timeout),
+        a.request(b_addr, &second, timeout)
+    );
+    assert_eq!(
+        first.unwrap(),
+        Frame::new(MessageType::Nodes as u8, vec![0xFF, 1])
+    );
+    assert_eq!(
+        second.unwrap(),
+        Frame::new(MessageType::Nodes as u8, vec![0xFF, 2])
+    );
+    a.close();
+    b.close();
+}
+
+#[cfg(feature = "quic")]
+#[tokio::test]
+async fn test_mesh_request_times_out_without_reply() {
+    let (a, _, b, b_addr) = connected_pair().await;
+    let mut requests = b.serve_requests(8);
+
+    let started = std::time::Instant::now();
+    let result = a
+        .request(
+            b_addr,
+            &Frame::new(0x06, vec![1]),
+            Duration::from_millis(200),
+        )
+        .await;
+    assert!(matches!(result, Err(QuicError::RequestTimeout { .. })));
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    // The request arrived; it just was never answered.
+    assert_eq!(requests.recv().await.unwrap().frame.kind, 0x06);
+    a.close();
+    b.close();
+}
+
+#[cfg(feature = "quic")]
+#[tokio::test]
+async fn test_mesh_request_refused_when_not_served() {
+    let (a, _, b, b_addr) = connected_pair().await;
+
+    let result = a
+        .request(b_addr, &Frame::new(0x03, vec![1]), Duration::from_secs(2))
+        .await;
+    assert!(matches!(result, Err(QuicError::RecvFailed { .. })));
+
+    let unknown: SocketAddr = "127.0.0.1:1".parse().unwrap();
+    let result = a
+        .request(unknown, &Frame::new(0x03, vec![1]), Duration::from_secs(2))
+        .await;
+    assert!(matches!(result, Err(QuicError::ConnectionClosed { .. })));
+    a.close();
+    b.close();
+}
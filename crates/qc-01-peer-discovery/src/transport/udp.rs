@@ -0,0 +1,87 @@
+//! # Dual-Stack UDP Sockets
+//!
+//! Binding `[::]` gives one socket for both address families: IPv4 peers
+//! reach it from IPv4-mapped addresses (`::ffff:a.b.c.d`). Platforms
+//! disagree on the default (Linux is dual-stack, Windows is IPv6-only), so
+//! the option is set explicitly rather than left to the OS.
+
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+
+use socket2::{Domain, Protocol, Socket, Type};
+
+/// Bind a UDP socket, making IPv6 binds dual-stack.
+///
+/// A wildcard IPv6 bind (`[::]:port`) falls back to `0.0.0.0:port` on
+/// hosts without IPv6, so nodes can default to dual-stack everywhere.
+///
+/// # Errors
+///
+/// Returns the bind error (of the IPv4 fallback, if one was tried).
+pub fn bind_udp(addr: SocketAddr) -> io::Result<UdpSocket> {
+    match bind(addr) {
+        Err(_) if addr.is_ipv6() && addr.ip().is_unspecified() => {
+            bind(SocketAddr::from((Ipv4Addr::UNSPECIFIED, addr.port())))
+        }
+        result => result,
+    }
+}
+
+fn bind(addr: SocketAddr) -> io::Result<UdpSocket> {
+    let socket = Socket::new(Domain::for_address(addr), Type::DGRAM, Some(Protocol::UDP))?;
+    if addr.is_ipv6() {
+        socket.set_only_v6(false)?;
+    }
+    socket.bind(&addr.into())?;
+    Ok(socket.into())
+}
+
+/// Address to send to from a socket bound to `local`.
+///
+/// An IPv6 socket reaches IPv4 peers through their mapped address.
+pub fn send_addr(local: SocketAddr, target: SocketAddr) -> SocketAddr {
+    match target {
+        SocketAddr::V4(v4) if local.is_ipv6() => {
+            SocketAddr::from((v4.ip().to_ipv6_mapped(), v4.port()))
+        }
+        other => other,
+    }
+}
+
+/// Peer address as the rest of the node should see it.
+///
+/// Undoes the mapping a dual-stack socket applies to IPv4 peers, so the
+/// same peer has one address whichever family it was reached over.
+pub fn canonical_addr(addr: SocketAddr) -> SocketAddr {
+    SocketAddr::new(addr.ip().to_canonical(), addr.port())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dual_stack_socket_reaches_ipv4() {
+        let Ok(server) = bind_udp("[::]:0".parse().unwrap()) else {
+            return;
+        };
+        let local = server.local_addr().unwrap();
+        let client = bind_udp("127.0.0.1:0".parse().unwrap()).unwrap();
+
+        if local.is_ipv6() {
+            // Dual-stack: IPv4 traffic arrives from a mapped address
+            client
+                .send_to(b"ping", ("127.0.0.1", local.port()))
+                .unwrap();
+            let mut buf = [0u8; 4];
+            let (_, from) = server.recv_from(&mut buf).unwrap();
+            assert!(from.is_ipv6());
+            assert_eq!(canonical_addr(from), client.local_addr().unwrap());
+
+            let back = send_addr(local, client.local_addr().unwrap());
+            server.send_to(b"pong", back).unwrap();
+            client.recv_from(&mut buf).unwrap();
+            assert_eq!(&buf, b"pong");
+        }
+    }
+}
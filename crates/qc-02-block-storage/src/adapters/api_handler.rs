@@ -402,6 +402,10 @@ mod tests {
             height <= self.latest_height
         }
 
+        fn flush(&mut self) -> Result<(), StorageError> {
+            Ok(())
+        }
+
         fn get_transaction_location(
             &self,
             _transaction_hash: &Hash,
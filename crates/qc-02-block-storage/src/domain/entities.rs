@@ -8,11 +8,23 @@
 //! - Section 2.3: Index Structures
 
 use serde::{Deserialize, Serialize};
-use shared_types::{Hash, ValidatedBlock};
+use shared_types::{BlockHeader, Hash, ValidatedBlock};
 
 /// Unix timestamp in seconds since epoch.
 pub type Timestamp = u64;
 
+/// Hash a stored block is keyed by, computed from its header fields.
+pub fn header_hash(header: &BlockHeader) -> Hash {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(header.parent_hash);
+    hasher.update(header.height.to_le_bytes());
+    hasher.update(header.merkle_root);
+    hasher.update(header.state_root);
+    hasher.update(header.timestamp.to_le_bytes());
+    hasher.finalize().into()
+}
+
 /// A block stored on disk with integrity checksum.
 ///
 /// This is the storage-layer wrapper around ValidatedBlock.
@@ -59,15 +71,7 @@ impl StoredBlock {
 
     /// Get the block hash (from the header).
     pub fn block_hash(&self) -> Hash {
-        // Compute hash from header fields
-        use sha2::{Digest, Sha256};
-        let mut hasher = Sha256::new();
-        hasher.update(self.block.header.parent_hash);
-        hasher.update(self.block.header.height.to_le_bytes());
-        hasher.update(self.block.header.merkle_root);
-        hasher.update(self.block.header.state_root);
-        hasher.update(self.block.header.timestamp.to_le_bytes());
-        hasher.finalize().into()
+        header_hash(&self.block.header)
     }
 
     /// Get the block height.
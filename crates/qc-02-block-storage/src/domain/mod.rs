@@ -13,6 +13,7 @@
 //! - `repair` - Self-healing index for disaster recovery (Phase 4)
 //! - `mmr` - Merkle Mountain Range for light client proofs (Phase 3)
 //! - `pruning` - Smart pruning with anchor blocks (SPEC 5.2)
+//! - `reorg` - Log of canonical chain switches
 //! - `snapshot` - State snapshot export/import (SPEC 6.1)
 //! - `metrics` - Compaction and storage metrics (SPEC 4.3)
 
@@ -23,6 +24,7 @@ pub mod errors;
 pub mod metrics;
 pub mod mmr;
 pub mod pruning;
+pub mod reorg;
 pub mod repair;
 pub mod snapshot;
 pub mod value_objects;
@@ -0,0 +1,122 @@
+//! # Chain Reorganizations
+//!
+//! The height index keeps one block per height, so storing a block at an
+//! occupied height replaces the canonical block there. `ReorgLog` turns these
+//! replacements into reorganizations: the first replacement opens one, and
+//! blocks of the new branch replacing the rest of the old branch extend it
+//! rather than being counted as reorgs of their own.
+
+use super::entities::Timestamp;
+use shared_types::Hash;
+use std::collections::VecDeque;
+
+/// Number of reorganizations remembered.
+pub const MAX_RECORDED_REORGS: usize = 32;
+
+/// A switch of the canonical chain to a competing branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainReorg {
+    /// Height of the first replaced block.
+    pub height: u64,
+    /// The old branch from `height` to its tip when the switch was seen,
+    /// lowest first.
+    pub replaced: Vec<Hash>,
+    /// New-branch blocks stored over the old branch so far, lowest first.
+    pub adopted: Vec<Hash>,
+    /// When the switch was seen (unix seconds).
+    pub detected_at: Timestamp,
+}
+
+impl ChainReorg {
+    /// Number of old-branch blocks the switch made non-canonical.
+    pub fn depth(&self) -> usize {
+        self.replaced.len()
+    }
+}
+
+/// Bounded log of recent reorganizations, oldest first.
+#[derive(Debug, Default)]
+pub struct ReorgLog {
+    reorgs: VecDeque<ChainReorg>,
+}
+
+impl ReorgLog {
+    /// Create an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `block` (a child of `parent`) replacing the canonical block at
+    /// `height`, where `old_branch` is the canonical chain from `height` to
+    /// the tip, lowest first.
+    ///
+    /// Returns `true` if this opened a new reorganization.
+    pub fn record(
+        &mut self,
+        height: u64,
+        block: Hash,
+        parent: Hash,
+        old_branch: Vec<Hash>,
+        now: Timestamp,
+    ) -> bool {
+        if let Some(last) = self.reorgs.back_mut() {
+            let extends_branch = last.adopted.last() == Some(&parent)
+                && old_branch
+                    .first()
+                    .is_some_and(|old| last.replaced.contains(old));
+            if extends_branch {
+                last.adopted.push(block);
+                return false;
+            }
+        }
+
+        if self.reorgs.len() == MAX_RECORDED_REORGS {
+            self.reorgs.pop_front();
+        }
+        self.reorgs.push_back(ChainReorg {
+            height,
+            replaced: old_branch,
+            adopted: vec![block],
+            detected_at: now,
+        });
+        true
+    }
+
+    /// Recorded reorganizations, oldest first.
+    pub fn recent(&self) -> Vec<ChainReorg> {
+        self.reorgs.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branch_replacing_old_blocks_is_one_reorg() {
+        let mut log = ReorgLog::new();
+        assert!(log.record(5, [0xB5; 32], [4; 32], vec![[5; 32], [6; 32], [7; 32]], 100));
+        assert!(!log.record(6, [0xB6; 32], [0xB5; 32], vec![[6; 32], [7; 32]], 101));
+
+        let reorgs = log.recent();
+        assert_eq!(reorgs.len(), 1);
+        assert_eq!(reorgs[0].height, 5);
+        assert_eq!(reorgs[0].depth(), 3);
+        assert_eq!(reorgs[0].adopted, vec![[0xB5; 32], [0xB6; 32]]);
+
+        // A block not building on the adopted branch is a reorg of its own
+        assert!(log.record(6, [0xC6; 32], [0xB5; 32], vec![[0xB6; 32]], 102));
+        assert_eq!(log.recent().len(), 2);
+    }
+
+    #[test]
+    fn test_log_is_bounded() {
+        let mut log = ReorgLog::new();
+        for height in 0..MAX_RECORDED_REORGS as u64 + 3 {
+            log.record(height, [1; 32], [0; 32], vec![[2; 32]], height);
+        }
+        let reorgs = log.recent();
+        assert_eq!(reorgs.len(), MAX_RECORDED_REORGS);
+        assert_eq!(reorgs[0].height, 3);
+    }
+}
@@ -14,6 +14,13 @@
 //! 3. Re-insert index entries (height -> hash mappings)
 //! 4. Rebuild transaction index
 //! 5. Return report (blocks recovered, errors encountered)
+//!
+//! ## Integrity Check
+//!
+//! `Repairable::check_integrity` is the read-only counterpart: it cross-checks
+//! the height index, the transaction index and the head/finalized metadata
+//! against the stored blocks and lists every discrepancy. Index problems are
+//! fixed by `repair_index`; missing or corrupt block data is not.
 
 use shared_types::Hash;
 use std::collections::HashMap;
@@ -137,12 +144,154 @@ pub struct TransactionLocationRepair {
     pub tx_index: u32,
 }
 
+// =============================================================================
+// INTEGRITY CHECK
+// =============================================================================
+
+/// A discrepancy between stored blocks and the data derived from them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityIssue {
+    /// The height index points at a block that is not stored
+    MissingBlock { height: u64, hash: Hash },
+    /// A stored block cannot be decoded or fails its checksum
+    CorruptBlock { hash: Hash, reason: String },
+    /// The height index entry points at a block of another height
+    IndexMismatch { height: u64, hash: Hash },
+    /// A stored block has no height index entry
+    UnindexedBlock { height: u64, hash: Hash },
+    /// No block is stored at a height below the head
+    HeightGap { height: u64 },
+    /// The block at this height does not extend the one below it
+    BrokenParentLink { height: u64 },
+    /// A transaction of an indexed block is missing from the tx index
+    MissingTransaction { tx_hash: Hash, block_hash: Hash },
+    /// A tx index entry points at a block or position without that transaction
+    StaleTransaction { tx_hash: Hash },
+    /// The recorded head is not the highest indexed block
+    HeadMismatch { recorded: u64, indexed: u64 },
+    /// The finalized height is above the head or not indexed
+    InvalidFinalized { finalized: u64, head: u64 },
+}
+
+impl IntegrityIssue {
+    /// Whether `repair_index` can fix this (derived data only)
+    pub fn is_repairable(&self) -> bool {
+        !matches!(
+            self,
+            IntegrityIssue::MissingBlock { .. }
+                | IntegrityIssue::CorruptBlock { .. }
+                | IntegrityIssue::HeightGap { .. }
+                | IntegrityIssue::BrokenParentLink { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let short = |hash: &Hash| hex::encode(&hash[..8]);
+        match self {
+            IntegrityIssue::MissingBlock { height, hash } => {
+                write!(
+                    f,
+                    "height {} indexed to missing block 0x{}",
+                    height,
+                    short(hash)
+                )
+            }
+            IntegrityIssue::CorruptBlock { hash, reason } => {
+                write!(f, "block 0x{} is corrupt: {}", short(hash), reason)
+            }
+            IntegrityIssue::IndexMismatch { height, hash } => {
+                write!(
+                    f,
+                    "height {} indexed to block 0x{} of another height",
+                    height,
+                    short(hash)
+                )
+            }
+            IntegrityIssue::UnindexedBlock { height, hash } => {
+                write!(
+                    f,
+                    "block 0x{} at height {} is not indexed",
+                    short(hash),
+                    height
+                )
+            }
+            IntegrityIssue::HeightGap { height } => write!(f, "no block at height {}", height),
+            IntegrityIssue::BrokenParentLink { height } => {
+                write!(f, "block at height {} does not extend its parent", height)
+            }
+            IntegrityIssue::MissingTransaction {
+                tx_hash,
+                block_hash,
+            } => write!(
+                f,
+                "tx 0x{} of block 0x{} is not indexed",
+                short(tx_hash),
+                short(block_hash)
+            ),
+            IntegrityIssue::StaleTransaction { tx_hash } => {
+                write!(
+                    f,
+                    "tx index entry 0x{} points at the wrong block",
+                    short(tx_hash)
+                )
+            }
+            IntegrityIssue::HeadMismatch { recorded, indexed } => {
+                write!(
+                    f,
+                    "head is {} but the highest indexed block is {}",
+                    recorded, indexed
+                )
+            }
+            IntegrityIssue::InvalidFinalized { finalized, head } => {
+                write!(
+                    f,
+                    "finalized height {} is not an indexed block (head {})",
+                    finalized, head
+                )
+            }
+        }
+    }
+}
+
+/// Result of an integrity check
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityReport {
+    /// Stored blocks examined
+    pub blocks_checked: u64,
+    /// Transactions examined
+    pub transactions_checked: u64,
+    /// Discrepancies found
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    /// No discrepancies found
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// At least one issue `repair_index` can fix
+    pub fn needs_repair(&self) -> bool {
+        self.issues.iter().any(IntegrityIssue::is_repairable)
+    }
+
+    /// Issues that need the block data restored (e.g. from a snapshot)
+    pub fn unrepairable(&self) -> impl Iterator<Item = &IntegrityIssue> {
+        self.issues.iter().filter(|issue| !issue.is_repairable())
+    }
+}
+
 // =============================================================================
 // REPAIR TRAIT
 // =============================================================================
 
 /// Trait for types that can be repaired
 pub trait Repairable {
+    /// Cross-check indexes and metadata against stored blocks (read-only)
+    fn check_integrity(&self) -> Result<IntegrityReport, RepairFatalError>;
+
     /// Scan stored data and rebuild indexes
     fn repair_index(&mut self) -> Result<RepairReport, RepairFatalError>;
 }
@@ -255,6 +404,24 @@ mod tests {
         assert_eq!(loc.tx_index, 0);
     }
 
+    #[test]
+    fn test_integrity_report_classifies_issues() {
+        let mut report = IntegrityReport::default();
+        assert!(report.is_clean());
+        assert!(!report.needs_repair());
+
+        report.issues.push(IntegrityIssue::HeightGap { height: 3 });
+        assert!(!report.is_clean());
+        assert!(!report.needs_repair());
+
+        report
+            .issues
+            .push(IntegrityIssue::StaleTransaction { tx_hash: [1; 32] });
+        assert!(report.needs_repair());
+        assert_eq!(report.unrepairable().count(), 1);
+        assert!(report.issues[0].to_string().contains("height 3"));
+    }
+
     #[test]
     fn test_repair_error_display() {
         let err = RepairFatalError::StorageInaccessible("disk full".to_string());
@@ -7,6 +7,16 @@
 //! - Export complete chain state to a portable snapshot file
 //! - Import snapshot to quickly bootstrap a new node
 //! - Optional compression for smaller snapshots
+//!
+//! ## File Layout (single file)
+//!
+//! ```text
+//! SnapshotHeader (93 bytes, little-endian integers)
+//! data: [u32 length | serialized StoredBlock]*   (zstd-compressed if flagged)
+//! ```
+//!
+//! Blocks run from genesis to the snapshot height. The header checksum is
+//! CRC32C over the uncompressed data section.
 
 use shared_types::Hash;
 use std::path::Path;
@@ -151,6 +161,8 @@ pub struct SnapshotHeader {
     pub block_count: u64,
     /// Checksum of data section
     pub data_checksum: u32,
+    /// Whether the data section is zstd-compressed
+    pub compressed: bool,
 }
 
 impl SnapshotHeader {
@@ -158,6 +170,8 @@ impl SnapshotHeader {
     pub const MAGIC: [u8; 4] = [0x51, 0x43, 0x53, 0x4E];
     /// Current version
     pub const VERSION: u32 = 1;
+    /// Encoded header size in bytes
+    pub const ENCODED_LEN: usize = 93;
 
     /// Create a new header
     pub fn new(height: u64, block_hash: Hash, state_root: Hash, block_count: u64) -> Self {
@@ -169,9 +183,50 @@ impl SnapshotHeader {
             state_root,
             block_count,
             data_checksum: 0, // Computed during export
+            compressed: false,
         }
     }
 
+    /// Encode the header for the start of a snapshot file
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::ENCODED_LEN);
+        bytes.extend_from_slice(&self.magic);
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bytes.push(u8::from(self.compressed));
+        bytes.extend_from_slice(&self.height.to_le_bytes());
+        bytes.extend_from_slice(&self.block_hash);
+        bytes.extend_from_slice(&self.state_root);
+        bytes.extend_from_slice(&self.block_count.to_le_bytes());
+        bytes.extend_from_slice(&self.data_checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Decode and validate the header at the start of `bytes`
+    pub fn decode(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut reader = FieldReader { bytes };
+        let header = Self {
+            magic: reader.take()?,
+            version: u32::from_le_bytes(reader.take()?),
+            compressed: match reader.take::<1>()? {
+                [0] => false,
+                [1] => true,
+                [flag] => {
+                    return Err(SnapshotError::Corrupted(format!(
+                        "Invalid compression flag {}",
+                        flag
+                    )))
+                }
+            },
+            height: u64::from_le_bytes(reader.take()?),
+            block_hash: reader.take()?,
+            state_root: reader.take()?,
+            block_count: u64::from_le_bytes(reader.take()?),
+            data_checksum: u32::from_le_bytes(reader.take()?),
+        };
+        header.validate()?;
+        Ok(header)
+    }
+
     /// Validate header magic and version
     pub fn validate(&self) -> Result<(), SnapshotError> {
         if self.magic != Self::MAGIC {
@@ -187,6 +242,51 @@ impl SnapshotHeader {
     }
 }
 
+/// Reads fixed-size fields off the front of a byte slice
+struct FieldReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl FieldReader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        if self.bytes.len() < N {
+            return Err(SnapshotError::Corrupted("Truncated snapshot".into()));
+        }
+        let (bytes, rest) = self.bytes.split_at(N);
+        self.bytes = rest;
+        let mut field = [0u8; N];
+        field.copy_from_slice(bytes);
+        Ok(field)
+    }
+}
+
+/// Concatenate serialized blocks into a data section
+pub fn encode_records(records: &[Vec<u8>]) -> Vec<u8> {
+    let total = records.iter().map(|r| r.len() + 4).sum();
+    let mut data = Vec::with_capacity(total);
+    for record in records {
+        data.extend_from_slice(&(record.len() as u32).to_le_bytes());
+        data.extend_from_slice(record);
+    }
+    data
+}
+
+/// Split a data section back into serialized blocks
+pub fn decode_records(mut data: &[u8]) -> Result<Vec<&[u8]>, SnapshotError> {
+    let mut records = Vec::new();
+    while !data.is_empty() {
+        let mut reader = FieldReader { bytes: data };
+        let len = u32::from_le_bytes(reader.take()?) as usize;
+        if reader.bytes.len() < len {
+            return Err(SnapshotError::Corrupted("Truncated block record".into()));
+        }
+        let (record, rest) = reader.bytes.split_at(len);
+        records.push(record);
+        data = rest;
+    }
+    Ok(records)
+}
+
 // =============================================================================
 // TESTS (TDD)
 // =============================================================================
@@ -235,6 +335,36 @@ mod tests {
         assert!(header.validate().is_ok());
     }
 
+    #[test]
+    fn test_snapshot_header_encoding_round_trip() {
+        let mut header = SnapshotHeader::new(7, [0xAA; 32], [0xBB; 32], 8);
+        header.data_checksum = 0xDEAD_BEEF;
+        header.compressed = true;
+
+        let bytes = header.encode();
+        assert_eq!(bytes.len(), SnapshotHeader::ENCODED_LEN);
+        let decoded = SnapshotHeader::decode(&bytes).unwrap();
+        assert_eq!(decoded.height, 7);
+        assert_eq!(decoded.block_hash, [0xAA; 32]);
+        assert_eq!(decoded.data_checksum, 0xDEAD_BEEF);
+        assert!(decoded.compressed);
+
+        assert!(matches!(
+            SnapshotHeader::decode(&bytes[..40]),
+            Err(SnapshotError::Corrupted(_))
+        ));
+    }
+
+    #[test]
+    fn test_records_round_trip() {
+        let records = vec![vec![1, 2, 3], vec![], vec![4]];
+        let data = encode_records(&records);
+        let decoded = decode_records(&data).unwrap();
+        assert_eq!(decoded, vec![&[1u8, 2, 3][..], &[], &[4]]);
+
+        assert!(decode_records(&data[..data.len() - 1]).is_err());
+    }
+
     #[test]
     fn test_snapshot_error_display() {
         let err = SnapshotError::HeightUnavailable(1000);
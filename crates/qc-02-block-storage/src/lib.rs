@@ -64,14 +64,22 @@ pub mod service;
 
 // Re-export domain types
 pub use domain::assembler::{AssemblyConfig, BlockAssemblyBuffer, PendingBlockAssembly};
-pub use domain::entities::{BlockIndex, BlockIndexEntry, StoredBlock};
+pub use domain::entities::{header_hash, BlockIndex, BlockIndexEntry, StoredBlock};
 pub use domain::errors::{FSError, KVStoreError, StorageError}; // Layer compliance: errors exposed via lib.rs
+pub use domain::reorg::{ChainReorg, ReorgLog, MAX_RECORDED_REORGS};
+pub use domain::repair::{
+    IntegrityIssue, IntegrityReport, RepairFatalError, RepairReport, Repairable,
+};
+pub use domain::snapshot::{
+    SnapshotConfig, SnapshotError, SnapshotFormat, SnapshotInfo, SnapshotService,
+};
 pub use domain::value_objects::{KeyPrefix, StorageConfig, TransactionLocation};
 
 // Re-export port traits
 pub use ports::inbound::BlockStorageApi;
 pub use ports::outbound::{
-    BlockSerializer, ChecksumProvider, FileSystemAdapter, KeyValueStore, TimeSource,
+    BincodeBlockSerializer, BlockSerializer, ChecksumProvider, FileSystemAdapter, KeyValueStore,
+    TimeSource,
 };
 
 // Re-export service
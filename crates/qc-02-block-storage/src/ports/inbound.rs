@@ -112,6 +112,9 @@ pub trait BlockStorageApi {
     /// Check if a block exists at height.
     fn block_exists_at_height(&self, height: u64) -> bool;
 
+    /// Flush the underlying store to disk (graceful shutdown).
+    fn flush(&mut self) -> Result<(), StorageError>;
+
     /// V2.3: Get the location of a transaction by its hash.
     ///
     /// This API supports Transaction Indexing (Subsystem 3) for Merkle proof generation.
@@ -40,6 +40,13 @@ pub trait KeyValueStore: Send + Sync {
 
     /// Iterate over keys with a prefix.
     fn prefix_scan(&self, prefix: &[u8]) -> Result<ScanResult, KVStoreError>;
+
+    /// Make every completed write durable (called on shutdown).
+    ///
+    /// Stores that persist synchronously need not override this.
+    fn flush(&mut self) -> Result<(), KVStoreError> {
+        Ok(())
+    }
 }
 
 /// Batch operation for atomic writes.
@@ -366,6 +373,10 @@ impl KeyValueStore for FileBackedKVStore {
             .collect();
         Ok(results)
     }
+
+    fn flush(&mut self) -> Result<(), KVStoreError> {
+        self.save_to_file()
+    }
 }
 
 /// Controllable filesystem adapter for unit tests.
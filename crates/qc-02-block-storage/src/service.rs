@@ -11,15 +11,25 @@
 //! 4. Uses dependency injection for all external dependencies
 
 use crate::domain::assembler::BlockAssemblyBuffer;
-use crate::domain::entities::{BlockIndex, StorageMetadata, StoredBlock, Timestamp};
+use crate::domain::entities::{header_hash, BlockIndex, StorageMetadata, StoredBlock, Timestamp};
 use crate::domain::errors::StorageError;
+use crate::domain::reorg::{ChainReorg, ReorgLog};
+use crate::domain::repair::{
+    IntegrityIssue, IntegrityReport, RepairContext, RepairError, RepairFatalError, RepairReport,
+    Repairable,
+};
+use crate::domain::snapshot::{
+    decode_records, encode_records, SnapshotConfig, SnapshotError, SnapshotFormat, SnapshotHeader,
+    SnapshotInfo, SnapshotService,
+};
 use crate::domain::value_objects::{KeyPrefix, StorageConfig, TransactionLocation};
 use crate::ports::inbound::{BlockAssemblerApi, BlockStorageApi};
 use crate::ports::outbound::{
     BatchOperation, BlockSerializer, ChecksumProvider, FileSystemAdapter, KeyValueStore, TimeSource,
 };
 use shared_types::{Hash, ValidatedBlock};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
 
 /// Subsystem IDs per IPC-MATRIX.md
 pub mod subsystem_ids {
@@ -103,6 +113,8 @@ where
     /// Currently in-memory for performance. See struct-level documentation
     /// for scalability considerations.
     tx_index: HashMap<Hash, TransactionLocation>,
+    /// Recent switches of the canonical chain (in-memory only).
+    reorgs: ReorgLog,
 }
 
 /// dependencies for BlockStorageService
@@ -144,6 +156,7 @@ where
             block_index: BlockIndex::new(),
             metadata: StorageMetadata::default(),
             tx_index: HashMap::new(),
+            reorgs: ReorgLog::new(),
         };
 
         // Load existing block index from persistent storage
@@ -300,14 +313,7 @@ where
 
     /// Compute block hash from header.
     fn compute_block_hash(&self, block: &ValidatedBlock) -> Hash {
-        use sha2::{Digest, Sha256};
-        let mut hasher = Sha256::new();
-        hasher.update(block.header.parent_hash);
-        hasher.update(block.header.height.to_le_bytes());
-        hasher.update(block.header.merkle_root);
-        hasher.update(block.header.state_root);
-        hasher.update(block.header.timestamp.to_le_bytes());
-        hasher.finalize().into()
+        header_hash(&block.header)
     }
 
     /// Index transactions in a block.
@@ -409,6 +415,13 @@ where
         }
         Ok(None)
     }
+
+    /// Recent switches of the canonical chain, oldest first.
+    ///
+    /// Kept in memory only: reorgs from before a restart are not listed.
+    pub fn recent_reorgs(&self) -> Vec<ChainReorg> {
+        self.reorgs.recent()
+    }
 }
 
 impl<KV, FS, CS, TS, BS> BlockStorageApi for BlockStorageService<KV, FS, CS, TS, BS>
@@ -473,6 +486,32 @@ where
             .atomic_batch_write(operations)
             .map_err(StorageError::from)?;
 
+        // A different block at this height takes over the canonical chain
+        if let Some(old) = self
+            .block_index
+            .get(height)
+            .filter(|old| *old != block_hash)
+        {
+            let old_branch = (height..=self.metadata.latest_height)
+                .filter_map(|h| self.block_index.get(h))
+                .collect();
+            let opened = self.reorgs.record(
+                height,
+                block_hash,
+                block.header.parent_hash,
+                old_branch,
+                now,
+            );
+            if opened {
+                tracing::warn!(
+                    "[qc-02] ⚠ Chain reorganized at #{}: 0x{} replaced by 0x{}",
+                    height,
+                    hex::encode(&old[..8]),
+                    hex::encode(&block_hash[..8])
+                );
+            }
+        }
+
         // Update in-memory state
         self.block_index.insert(height, block_hash);
         self.metadata.on_block_stored(height, block_hash);
@@ -584,6 +623,10 @@ where
         self.block_index.contains(height)
     }
 
+    fn flush(&mut self) -> Result<(), StorageError> {
+        self.kv_store.flush().map_err(StorageError::from)
+    }
+
     fn get_transaction_location(
         &self,
         transaction_hash: &Hash,
@@ -716,6 +759,559 @@ where
     }
 }
 
+/// zstd level used for compressed snapshots
+const SNAPSHOT_ZSTD_LEVEL: i32 = 3;
+
+impl<KV, FS, CS, TS, BS> BlockStorageService<KV, FS, CS, TS, BS>
+where
+    KV: KeyValueStore,
+    FS: FileSystemAdapter,
+    CS: ChecksumProvider,
+    TS: TimeSource,
+    BS: BlockSerializer,
+{
+    /// Read a snapshot file and check it end to end: header, data checksum,
+    /// per-block checksums, and the parent chain from genesis to the tip.
+    fn read_snapshot(
+        &self,
+        path: &Path,
+    ) -> Result<(SnapshotInfo, Vec<StoredBlock>), SnapshotError> {
+        let bytes = std::fs::read(path).map_err(|e| SnapshotError::IoError(e.to_string()))?;
+        let header = SnapshotHeader::decode(&bytes)?;
+        let body = &bytes[SnapshotHeader::ENCODED_LEN..];
+        let data = if header.compressed {
+            zstd::decode_all(body).map_err(|e| SnapshotError::Corrupted(e.to_string()))?
+        } else {
+            body.to_vec()
+        };
+        if !self.checksum.verify_crc32c(&data, header.data_checksum) {
+            return Err(SnapshotError::Corrupted("Data checksum mismatch".into()));
+        }
+
+        let records = decode_records(&data)?;
+        if records.len() as u64 != header.block_count {
+            return Err(SnapshotError::Corrupted(format!(
+                "Header lists {} blocks, found {}",
+                header.block_count,
+                records.len()
+            )));
+        }
+
+        let mut blocks = Vec::with_capacity(records.len());
+        let mut parent_hash = [0u8; 32];
+        for (height, record) in (0u64..).zip(records) {
+            let block = self
+                .serializer
+                .deserialize(record)
+                .map_err(|e| SnapshotError::Corrupted(e.to_string()))?;
+            self.verify_block_checksum(&block)
+                .map_err(|e| SnapshotError::Corrupted(e.to_string()))?;
+            if block.height() != height || (height > 0 && block.parent_hash() != parent_hash) {
+                return Err(SnapshotError::VerificationFailed(format!(
+                    "Block {} does not extend the chain",
+                    height
+                )));
+            }
+            parent_hash = block.block_hash();
+            blocks.push(block);
+        }
+
+        let tip = blocks
+            .last()
+            .ok_or_else(|| SnapshotError::Corrupted("Snapshot has no blocks".into()))?;
+        if tip.height() != header.height
+            || tip.block_hash() != header.block_hash
+            || tip.state_root != header.state_root
+        {
+            return Err(SnapshotError::VerificationFailed(
+                "Tip does not match the header".into(),
+            ));
+        }
+
+        let info = SnapshotInfo {
+            path: path.display().to_string(),
+            height: header.height,
+            block_hash: header.block_hash,
+            state_root: header.state_root,
+            size_bytes: bytes.len() as u64,
+            block_count: header.block_count,
+            tx_count: blocks
+                .iter()
+                .map(|b| b.block.transactions.len() as u64)
+                .sum(),
+            compressed: header.compressed,
+        };
+        Ok((info, blocks))
+    }
+}
+
+/// Snapshots hold every block from genesis to the requested height and are
+/// built in memory, so they suit bootstrapping and backups of chains that
+/// fit in RAM.
+impl<KV, FS, CS, TS, BS> SnapshotService for BlockStorageService<KV, FS, CS, TS, BS>
+where
+    KV: KeyValueStore,
+    FS: FileSystemAdapter,
+    CS: ChecksumProvider,
+    TS: TimeSource,
+    BS: BlockSerializer,
+{
+    fn export_snapshot(
+        &self,
+        height: u64,
+        path: &Path,
+        config: &SnapshotConfig,
+    ) -> Result<SnapshotInfo, SnapshotError> {
+        if config.format == SnapshotFormat::Chunked {
+            return Err(SnapshotError::IoError(
+                "Chunked snapshots are not supported".into(),
+            ));
+        }
+        if !self.block_index.contains(height) {
+            return Err(SnapshotError::HeightUnavailable(height));
+        }
+
+        let mut records = Vec::with_capacity(height as usize + 1);
+        let mut tx_count = 0u64;
+        let mut tip = None;
+        for h in 0..=height {
+            let block = self.read_block_by_height(h).map_err(|e| match e {
+                StorageError::HeightNotFound { height } => SnapshotError::HeightUnavailable(height),
+                e => SnapshotError::Corrupted(e.to_string()),
+            })?;
+            records.push(
+                self.serializer
+                    .serialize(&block)
+                    .map_err(|e| SnapshotError::IoError(e.to_string()))?,
+            );
+            tx_count += block.block.transactions.len() as u64;
+            tip = Some(block);
+        }
+        let tip = tip.ok_or(SnapshotError::HeightUnavailable(height))?;
+
+        let data = encode_records(&records);
+        let mut header = SnapshotHeader::new(
+            height,
+            tip.block_hash(),
+            tip.state_root,
+            records.len() as u64,
+        );
+        header.data_checksum = self.checksum.compute_crc32c(&data);
+        header.compressed = config.compression;
+        let body = if config.compression {
+            zstd::encode_all(data.as_slice(), SNAPSHOT_ZSTD_LEVEL)
+                .map_err(|e| SnapshotError::IoError(e.to_string()))?
+        } else {
+            data
+        };
+
+        let mut file = header.encode();
+        file.extend_from_slice(&body);
+        std::fs::write(path, &file).map_err(|e| SnapshotError::IoError(e.to_string()))?;
+
+        tracing::info!(
+            "[qc-02] 📤 Exported {} blocks (height 0 to {}) to {}",
+            header.block_count,
+            height,
+            path.display()
+        );
+
+        Ok(SnapshotInfo {
+            path: path.display().to_string(),
+            height,
+            block_hash: header.block_hash,
+            state_root: header.state_root,
+            size_bytes: file.len() as u64,
+            block_count: header.block_count,
+            tx_count,
+            compressed: config.compression,
+        })
+    }
+
+    fn import_snapshot(&mut self, path: &Path) -> Result<SnapshotInfo, SnapshotError> {
+        let (info, blocks) = self.read_snapshot(path)?;
+
+        // Blocks already stored are skipped, so an import can resume or
+        // extend a chain with the same genesis; a different chain is refused
+        for block in blocks {
+            let hash = block.block_hash();
+            match self.block_index.get(block.height()) {
+                Some(existing) if existing == hash => continue,
+                Some(_) => {
+                    return Err(SnapshotError::VerificationFailed(format!(
+                        "Block {} conflicts with the local chain",
+                        block.height()
+                    )))
+                }
+                None => {}
+            }
+            self.write_block(block.block, block.merkle_root, block.state_root)
+                .map_err(|e| SnapshotError::VerificationFailed(e.to_string()))?;
+        }
+
+        tracing::info!(
+            "[qc-02] 📥 Imported snapshot {} (height {})",
+            path.display(),
+            info.height
+        );
+        Ok(info)
+    }
+
+    fn verify_snapshot(&self, path: &Path) -> Result<SnapshotInfo, SnapshotError> {
+        self.read_snapshot(path).map(|(info, _)| info)
+    }
+}
+
+/// Stored blocks read back for an integrity check or repair.
+#[derive(Default)]
+struct BlockScan {
+    /// Blocks that decode, pass their checksum and match their key.
+    blocks: HashMap<Hash, StoredBlock>,
+    /// Blocks that do not, with the reason.
+    corrupt: Vec<(Hash, String)>,
+}
+
+impl<KV, FS, CS, TS, BS> BlockStorageService<KV, FS, CS, TS, BS>
+where
+    KV: KeyValueStore,
+    FS: FileSystemAdapter,
+    CS: ChecksumProvider,
+    TS: TimeSource,
+    BS: BlockSerializer,
+{
+    /// Read every stored block (`b:` keys), verifying checksums and hashes.
+    fn scan_stored_blocks(&self) -> Result<BlockScan, RepairFatalError> {
+        let prefix = KeyPrefix::Block.as_bytes();
+        let entries = self
+            .kv_store
+            .prefix_scan(prefix)
+            .map_err(|e| RepairFatalError::StorageInaccessible(e.to_string()))?;
+
+        let mut scan = BlockScan::default();
+        for (key, value) in entries {
+            let Ok(hash) = Hash::try_from(&key[prefix.len()..]) else {
+                continue; // Skip malformed keys
+            };
+            let block = self
+                .serializer
+                .deserialize(&value)
+                .map_err(StorageError::from)
+                .and_then(|block| self.verify_block_checksum(&block).map(|_| block));
+            match block {
+                Ok(block) if block.block_hash() == hash => {
+                    scan.blocks.insert(hash, block);
+                }
+                Ok(_) => scan
+                    .corrupt
+                    .push((hash, "contents do not match the key".to_string())),
+                Err(e) => scan.corrupt.push((hash, e.to_string())),
+            }
+        }
+        Ok(scan)
+    }
+
+    /// Persisted height index (`h:` keys).
+    fn scan_height_index(&self) -> Result<BTreeMap<u64, Hash>, RepairFatalError> {
+        let prefix = KeyPrefix::BlockByHeight.as_bytes();
+        let entries = self
+            .kv_store
+            .prefix_scan(prefix)
+            .map_err(|e| RepairFatalError::StorageInaccessible(e.to_string()))?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let height = u64::from_be_bytes(key[prefix.len()..].try_into().ok()?);
+                Some((height, Hash::try_from(value.as_slice()).ok()?))
+            })
+            .collect())
+    }
+
+    /// Persisted transaction index (`t:` keys); unreadable entries are left
+    /// out so they show up as missing.
+    fn scan_tx_index(&self) -> Result<HashMap<Hash, TransactionLocation>, RepairFatalError> {
+        let prefix = KeyPrefix::Transaction.as_bytes();
+        let entries = self
+            .kv_store
+            .prefix_scan(prefix)
+            .map_err(|e| RepairFatalError::StorageInaccessible(e.to_string()))?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|(key, value)| {
+                let tx_hash = Hash::try_from(&key[prefix.len()..]).ok()?;
+                Some((tx_hash, bincode::deserialize(&value).ok()?))
+            })
+            .collect())
+    }
+
+    /// Replace the persisted height (and, if enabled, transaction) index in
+    /// one atomic batch.
+    fn write_rebuilt_indexes(
+        &mut self,
+        chain: &BTreeMap<u64, Hash>,
+        tx_index: &HashMap<Hash, TransactionLocation>,
+    ) -> Result<(), RepairFatalError> {
+        let mut prefixes = vec![KeyPrefix::BlockByHeight.as_bytes()];
+        if self.config.persist_transaction_index {
+            prefixes.push(KeyPrefix::Transaction.as_bytes());
+        }
+        let mut operations = Vec::new();
+        for prefix in prefixes {
+            let entries = self
+                .kv_store
+                .prefix_scan(prefix)
+                .map_err(|e| RepairFatalError::StorageInaccessible(e.to_string()))?;
+            operations.extend(
+                entries
+                    .into_iter()
+                    .map(|(key, _)| BatchOperation::delete(key)),
+            );
+        }
+
+        operations.extend(chain.iter().map(|(height, hash)| {
+            BatchOperation::put(KeyPrefix::height_key(*height), hash.to_vec())
+        }));
+        if self.config.persist_transaction_index {
+            for (tx_hash, location) in tx_index {
+                let value = bincode::serialize(location)
+                    .map_err(|e| RepairFatalError::CriticalCorruption(e.to_string()))?;
+                operations.push(BatchOperation::put(
+                    KeyPrefix::transaction_key(tx_hash),
+                    value,
+                ));
+            }
+        }
+
+        self.kv_store
+            .atomic_batch_write(operations)
+            .map_err(|e| RepairFatalError::StorageInaccessible(e.to_string()))
+    }
+
+    /// Head and finalized height against the index.
+    fn check_metadata(&self, index: &BTreeMap<u64, Hash>, issues: &mut Vec<IntegrityIssue>) {
+        let indexed = index.keys().next_back().copied().unwrap_or(0);
+        if self.metadata.latest_height != indexed {
+            issues.push(IntegrityIssue::HeadMismatch {
+                recorded: self.metadata.latest_height,
+                indexed,
+            });
+        }
+        let finalized = self.metadata.finalized_height;
+        if finalized > 0 && (finalized > indexed || !index.contains_key(&finalized)) {
+            issues.push(IntegrityIssue::InvalidFinalized {
+                finalized,
+                head: indexed,
+            });
+        }
+    }
+}
+
+/// The integrity pass only reads; repair rewrites the derived indexes from
+/// the blocks themselves and never touches block data.
+impl<KV, FS, CS, TS, BS> Repairable for BlockStorageService<KV, FS, CS, TS, BS>
+where
+    KV: KeyValueStore,
+    FS: FileSystemAdapter,
+    CS: ChecksumProvider,
+    TS: TimeSource,
+    BS: BlockSerializer,
+{
+    fn check_integrity(&self) -> Result<IntegrityReport, RepairFatalError> {
+        let scan = self.scan_stored_blocks()?;
+        let index = self.scan_height_index()?;
+
+        let mut report = IntegrityReport {
+            blocks_checked: (scan.blocks.len() + scan.corrupt.len()) as u64,
+            ..Default::default()
+        };
+        report
+            .issues
+            .extend(
+                scan.corrupt
+                    .iter()
+                    .map(|(hash, reason)| IntegrityIssue::CorruptBlock {
+                        hash: *hash,
+                        reason: reason.clone(),
+                    }),
+            );
+        check_block_index(&scan, &index, &mut report.issues);
+        // Without persistence the tx index lives in memory only; nothing on
+        // disk can disagree with the blocks
+        if self.config.persist_transaction_index {
+            let tx_index = self.scan_tx_index()?;
+            report.transactions_checked =
+                check_tx_index(&scan, &index, &tx_index, &mut report.issues);
+        }
+        self.check_metadata(&index, &mut report.issues);
+
+        for issue in &report.issues {
+            tracing::warn!("[qc-02] Integrity: {}", issue);
+        }
+        Ok(report)
+    }
+
+    fn repair_index(&mut self) -> Result<RepairReport, RepairFatalError> {
+        let started = std::time::Instant::now();
+        let scan = self.scan_stored_blocks()?;
+        if scan.blocks.is_empty() && scan.corrupt.is_empty() {
+            return Err(RepairFatalError::EmptyStorage);
+        }
+
+        let mut ctx = RepairContext::new();
+        for (hash, reason) in &scan.corrupt {
+            ctx.report
+                .add_error(RepairError::new(KeyPrefix::block_key(hash), reason.clone()));
+        }
+
+        let chain = select_chain(&scan.blocks, &self.block_index);
+        let mut tx_index = HashMap::new();
+        for (&height, hash) in &chain {
+            let block = &scan.blocks[hash].block;
+            ctx.record_block(height, *hash, block.transactions.len() as u64);
+            for (index, tx) in block.transactions.iter().enumerate() {
+                let location =
+                    TransactionLocation::new(*hash, height, index, block.header.merkle_root);
+                tx_index.insert(tx.tx_hash, location);
+            }
+        }
+        self.write_rebuilt_indexes(&chain, &tx_index)?;
+
+        // Finalization survives if its block is still indexed
+        let finalized = self.metadata.finalized_height;
+        self.block_index = BlockIndex::new();
+        self.metadata = StorageMetadata::default();
+        for (&height, hash) in &chain {
+            self.block_index.insert(height, *hash);
+            self.metadata.on_block_stored(height, *hash);
+        }
+        if self.block_index.contains(finalized) {
+            self.metadata.on_finalized(finalized);
+        }
+        self.tx_index = tx_index;
+
+        ctx.report.duration_ms = started.elapsed().as_millis() as u64;
+        tracing::info!(
+            "[qc-02] 🔧 Rebuilt indexes: {} blocks, {} transactions, {} unreadable",
+            ctx.report.blocks_recovered,
+            ctx.report.transactions_indexed,
+            ctx.report.errors.len()
+        );
+        Ok(ctx.report)
+    }
+}
+
+/// Height index against the stored blocks.
+fn check_block_index(
+    scan: &BlockScan,
+    index: &BTreeMap<u64, Hash>,
+    issues: &mut Vec<IntegrityIssue>,
+) {
+    let corrupt: HashSet<Hash> = scan.corrupt.iter().map(|(hash, _)| *hash).collect();
+    for (&height, hash) in index {
+        let parent = height.checked_sub(1).and_then(|h| index.get(&h));
+        match scan.blocks.get(hash) {
+            Some(block) if block.height() != height => issues.push(IntegrityIssue::IndexMismatch {
+                height,
+                hash: *hash,
+            }),
+            Some(block) if parent.is_some_and(|parent| block.parent_hash() != *parent) => {
+                issues.push(IntegrityIssue::BrokenParentLink { height })
+            }
+            Some(_) => {}
+            None if corrupt.contains(hash) => {}
+            None => issues.push(IntegrityIssue::MissingBlock {
+                height,
+                hash: *hash,
+            }),
+        }
+    }
+
+    // Blocks on a side chain share a height with an indexed block; only a
+    // block whose height has no entry at all has lost its index
+    let mut unindexed: Vec<_> = scan
+        .blocks
+        .iter()
+        .filter(|(_, block)| !index.contains_key(&block.height()))
+        .map(|(hash, block)| IntegrityIssue::UnindexedBlock {
+            height: block.height(),
+            hash: *hash,
+        })
+        .collect();
+    unindexed.sort_by_key(|issue| match issue {
+        IntegrityIssue::UnindexedBlock { height, .. } => *height,
+        _ => 0,
+    });
+    issues.extend(unindexed);
+
+    // A gap with a stored block is already reported as unindexed
+    let stored: HashSet<u64> = scan.blocks.values().map(StoredBlock::height).collect();
+    let head = index.keys().next_back().copied().unwrap_or(0);
+    issues.extend(
+        (0..head)
+            .filter(|height| !index.contains_key(height) && !stored.contains(height))
+            .map(|height| IntegrityIssue::HeightGap { height }),
+    );
+}
+
+/// Persisted transaction index against the indexed blocks. Returns the number
+/// of transactions checked.
+fn check_tx_index(
+    scan: &BlockScan,
+    index: &BTreeMap<u64, Hash>,
+    tx_index: &HashMap<Hash, TransactionLocation>,
+    issues: &mut Vec<IntegrityIssue>,
+) -> u64 {
+    let mut checked = 0;
+    for hash in index.values() {
+        let Some(block) = scan.blocks.get(hash) else {
+            continue;
+        };
+        for tx in &block.block.transactions {
+            checked += 1;
+            if !tx_index.contains_key(&tx.tx_hash) {
+                issues.push(IntegrityIssue::MissingTransaction {
+                    tx_hash: tx.tx_hash,
+                    block_hash: *hash,
+                });
+            }
+        }
+    }
+
+    for (tx_hash, location) in tx_index {
+        let holds = index.get(&location.block_height) == Some(&location.block_hash)
+            && scan
+                .blocks
+                .get(&location.block_hash)
+                .and_then(|block| block.block.transactions.get(location.transaction_index))
+                .is_some_and(|tx| tx.tx_hash == *tx_hash);
+        if !holds {
+            issues.push(IntegrityIssue::StaleTransaction { tx_hash: *tx_hash });
+        }
+    }
+    checked
+}
+
+/// One block per height for the rebuilt index. Where several blocks share a
+/// height, the one extending the chain below wins, then the one already
+/// indexed.
+fn select_chain(blocks: &HashMap<Hash, StoredBlock>, current: &BlockIndex) -> BTreeMap<u64, Hash> {
+    let mut by_height: BTreeMap<u64, Vec<Hash>> = BTreeMap::new();
+    for (hash, block) in blocks {
+        by_height.entry(block.height()).or_default().push(*hash);
+    }
+
+    let mut chain = BTreeMap::new();
+    for (height, mut candidates) in by_height {
+        candidates.sort_by_key(|hash| (current.get(height) != Some(*hash), *hash));
+        let parent = height.checked_sub(1).and_then(|h| chain.get(&h)).copied();
+        let chosen = candidates
+            .iter()
+            .find(|hash| parent.is_some_and(|parent| blocks[*hash].parent_hash() == parent))
+            .unwrap_or(&candidates[0]);
+        chain.insert(height, *chosen);
+    }
+    chain
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -778,6 +1374,32 @@ mod tests {
         assert_eq!(stored.block.header.height, 0);
     }
 
+    #[test]
+    fn test_competing_block_records_reorg() {
+        let mut service = make_test_service();
+        let genesis = service
+            .write_block(make_test_block(0, [0; 32]), [0; 32], [0; 32])
+            .unwrap();
+        let first = service
+            .write_block(make_test_block(1, genesis), [0; 32], [0; 32])
+            .unwrap();
+        assert!(service.recent_reorgs().is_empty());
+
+        let mut competing = make_test_block(1, genesis);
+        competing.header.timestamp = 2000;
+        let second = service.write_block(competing, [0; 32], [0; 32]).unwrap();
+
+        let reorgs = service.recent_reorgs();
+        assert_eq!(reorgs.len(), 1);
+        assert_eq!(reorgs[0].height, 1);
+        assert_eq!(reorgs[0].replaced, vec![first]);
+        assert_eq!(reorgs[0].adopted, vec![second]);
+        assert_eq!(
+            service.read_block_by_height(1).unwrap().block_hash(),
+            second
+        );
+    }
+
     #[test]
     fn test_disk_full_invariant() {
         let deps = BlockStorageDependencies {
@@ -1339,4 +1961,221 @@ mod tests {
         let tx_key = KeyPrefix::transaction_key(&tx_hash);
         assert!(service.kv_store.exists(&tx_key).unwrap());
     }
+
+    // =========================================================================
+    // TEST GROUP: Snapshots (SPEC-02 Section 6.1)
+    // =========================================================================
+
+    fn write_chain(service: &mut impl BlockStorageApi, length: u64) -> Hash {
+        let mut parent = [0; 32];
+        for height in 0..length {
+            let block = make_test_block(height, parent);
+            parent = service
+                .write_block(block, [0; 32], [height as u8; 32])
+                .unwrap();
+        }
+        parent
+    }
+
+    fn snapshot_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("qc02-{}-{}.snap", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_snapshot_export_import_round_trip() {
+        let mut source = make_test_service();
+        let tip = write_chain(&mut source, 5);
+
+        for compression in [true, false] {
+            let path = snapshot_path(&format!("round-trip-{}", compression));
+            let config = SnapshotConfig {
+                compression,
+                ..SnapshotConfig::default()
+            };
+            let exported = source.export_snapshot(4, &path, &config).unwrap();
+            assert_eq!(exported.block_count, 5);
+            assert_eq!(exported.block_hash, tip);
+            assert_eq!(exported.compressed, compression);
+
+            let mut target = make_test_service();
+            let imported = target.import_snapshot(&path).unwrap();
+            assert_eq!(imported.height, 4);
+            assert_eq!(target.get_latest_height().unwrap(), 4);
+            assert_eq!(target.read_block_by_height(4).unwrap().block_hash(), tip);
+
+            // Importing again skips the blocks already stored
+            assert!(target.import_snapshot(&path).is_ok());
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_snapshot_rejects_corruption_and_conflicts() {
+        let mut source = make_test_service();
+        write_chain(&mut source, 3);
+        let path = snapshot_path("corrupt");
+        let config = SnapshotConfig {
+            compression: false,
+            ..SnapshotConfig::default()
+        };
+        source.export_snapshot(2, &path, &config).unwrap();
+        assert!(matches!(
+            source.export_snapshot(9, &path, &config),
+            Err(SnapshotError::HeightUnavailable(9))
+        ));
+
+        // A node with a different genesis refuses the snapshot
+        let mut other = make_test_service();
+        let mut genesis = make_test_block(0, [0; 32]);
+        genesis.header.timestamp = 2000;
+        other.write_block(genesis, [0; 32], [0; 32]).unwrap();
+        assert!(matches!(
+            other.import_snapshot(&path),
+            Err(SnapshotError::VerificationFailed(_))
+        ));
+
+        let mut bytes = std::fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0xFF;
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            make_test_service().verify_snapshot(&path),
+            Err(SnapshotError::Corrupted(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    fn make_persistent_service() -> BlockStorageService<
+        InMemoryKVStore,
+        MockFileSystemAdapter,
+        DefaultChecksumProvider,
+        SystemTimeSource,
+        BincodeBlockSerializer,
+    > {
+        let deps = BlockStorageDependencies {
+            kv_store: InMemoryKVStore::new(),
+            fs_adapter: MockFileSystemAdapter::new(50),
+            checksum: DefaultChecksumProvider,
+            time_source: SystemTimeSource,
+            serializer: BincodeBlockSerializer,
+        };
+        let config = StorageConfig::new().with_persist_transaction_index(true);
+        BlockStorageService::new(deps, config)
+    }
+
+    fn block_with_tx(height: u64, parent_hash: Hash, tx_hash: Hash) -> ValidatedBlock {
+        let mut block = make_test_block(height, parent_hash);
+        block.transactions.push(shared_types::ValidatedTransaction {
+            inner: shared_types::Transaction {
+                from: [0xAA; 32],
+                to: Some([0xBB; 32]),
+                value: 1,
+                nonce: height,
+                data: vec![],
+                signature: [0u8; 64],
+            },
+            tx_hash,
+        });
+        block
+    }
+
+    #[test]
+    fn test_integrity_check_clean_chain() {
+        let mut service = make_persistent_service();
+        let mut parent = [0; 32];
+        for height in 0..4 {
+            let block = block_with_tx(height, parent, [height as u8 + 1; 32]);
+            parent = service.write_block(block, [0; 32], [0; 32]).unwrap();
+        }
+
+        let report = service.check_integrity().unwrap();
+        assert!(report.is_clean(), "{:?}", report.issues);
+        assert_eq!(report.blocks_checked, 4);
+        assert_eq!(report.transactions_checked, 4);
+    }
+
+    #[test]
+    fn test_repair_rebuilds_damaged_indexes() {
+        let mut service = make_persistent_service();
+        let mut hashes = Vec::new();
+        let mut parent = [0; 32];
+        for height in 0..4 {
+            let block = block_with_tx(height, parent, [height as u8 + 1; 32]);
+            parent = service.write_block(block, [0; 32], [0; 32]).unwrap();
+            hashes.push(parent);
+        }
+        service.kv_store.delete(&KeyPrefix::height_key(2)).unwrap();
+        service
+            .kv_store
+            .delete(&KeyPrefix::transaction_key(&[1; 32]))
+            .unwrap();
+        let stale = TransactionLocation::new(hashes[3], 3, 5, [0; 32]);
+        service
+            .kv_store
+            .put(
+                &KeyPrefix::transaction_key(&[0xEE; 32]),
+                &bincode::serialize(&stale).unwrap(),
+            )
+            .unwrap();
+
+        let report = service.check_integrity().unwrap();
+        assert!(report.needs_repair());
+        assert_eq!(report.unrepairable().count(), 0);
+        for expected in [
+            IntegrityIssue::UnindexedBlock {
+                height: 2,
+                hash: hashes[2],
+            },
+            IntegrityIssue::MissingTransaction {
+                tx_hash: [1; 32],
+                block_hash: hashes[0],
+            },
+            IntegrityIssue::StaleTransaction {
+                tx_hash: [0xEE; 32],
+            },
+        ] {
+            assert!(report.issues.contains(&expected), "missing {}", expected);
+        }
+
+        let repaired = service.repair_index().unwrap();
+        assert_eq!(repaired.blocks_recovered, 4);
+        assert_eq!(repaired.transactions_indexed, 4);
+        assert!(service.check_integrity().unwrap().is_clean());
+        assert_eq!(service.get_latest_height().unwrap(), 3);
+        assert!(service.get_transaction_location(&[1; 32]).is_ok());
+        assert!(service.get_transaction_location(&[0xEE; 32]).is_err());
+    }
+
+    #[test]
+    fn test_corrupt_block_is_unrepairable() {
+        let mut service = make_test_service();
+        let head = write_chain(&mut service, 3);
+        service
+            .kv_store
+            .put(&KeyPrefix::block_key(&head), b"garbage")
+            .unwrap();
+
+        let report = service.check_integrity().unwrap();
+        let unrepairable: Vec<_> = report.unrepairable().collect();
+        assert!(matches!(
+            unrepairable.as_slice(),
+            [IntegrityIssue::CorruptBlock { hash, .. }] if *hash == head
+        ));
+
+        // Repair drops the unreadable head from the index
+        let repaired = service.repair_index().unwrap();
+        assert_eq!(repaired.blocks_recovered, 2);
+        assert_eq!(repaired.errors.len(), 1);
+        assert_eq!(service.get_latest_height().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_repair_empty_storage() {
+        let mut service = make_test_service();
+        assert!(service.check_integrity().unwrap().is_clean());
+        assert!(matches!(
+            service.repair_index(),
+            Err(RepairFatalError::EmptyStorage)
+        ));
+    }
 }
@@ -123,6 +123,11 @@ impl MerkleTree {
         self.padded_leaf_count
     }
 
+    /// Approximate memory held by this tree, in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        std::mem::size_of::<Self>() + self.nodes.capacity() * std::mem::size_of::<Hash>()
+    }
+
     /// Generate a proof for the transaction at the given index.
     ///
     /// ## INVARIANT-2: Proof Validity
@@ -616,6 +621,32 @@ impl TransactionIndex {
         self.trees.contains(block_hash)
     }
 
+    /// Approximate memory held by cached Merkle trees, in bytes.
+    pub fn tree_cache_bytes(&self) -> usize {
+        self.trees
+            .iter()
+            .map(|(_, tree)| std::mem::size_of::<Hash>() + tree.memory_bytes())
+            .sum()
+    }
+
+    /// Evict least recently used trees until the cache holds at most
+    /// `max_bytes`. Returns the number of trees evicted.
+    ///
+    /// Trees are rebuilt on demand, so this only costs proof latency.
+    pub fn shrink_tree_cache(&mut self, max_bytes: usize) -> usize {
+        let mut bytes = self.tree_cache_bytes();
+        let mut evicted = 0;
+        while bytes > max_bytes {
+            let Some((_, tree)) = self.trees.pop_lru() else {
+                break;
+            };
+            bytes -= std::mem::size_of::<Hash>() + tree.memory_bytes();
+            evicted += 1;
+        }
+        self.stats.cached_trees = self.trees.len();
+        evicted
+    }
+
     /// Get the configuration.
     pub fn config(&self) -> &IndexConfig {
         &self.config
@@ -1019,6 +1050,27 @@ mod tests {
         assert!(index.has_tree(&block_d));
     }
 
+    #[test]
+    fn test_shrink_tree_cache_evicts_lru_first() {
+        let mut index = TransactionIndex::new(IndexConfig::default());
+        for i in 0..4u8 {
+            let hashes = (0..8u8).map(|j| hash_from_byte(i * 8 + j)).collect();
+            index.cache_tree(hash_from_byte(0xA0 + i), MerkleTree::build(hashes));
+        }
+        // Touch the oldest so it survives
+        index.get_tree(&hash_from_byte(0xA0));
+
+        let per_tree = index.tree_cache_bytes() / 4;
+        assert_eq!(index.shrink_tree_cache(per_tree * 2), 2);
+        assert!(index.tree_cache_bytes() <= per_tree * 2);
+        assert!(index.has_tree(&hash_from_byte(0xA0)));
+        assert!(!index.has_tree(&hash_from_byte(0xA1)));
+        assert_eq!(index.stats().cached_trees, 2);
+
+        assert_eq!(index.shrink_tree_cache(0), 2);
+        assert_eq!(index.tree_cache_bytes(), 0);
+    }
+
     // ========== Test Group 7: Security Hardening ==========
 
     #[test]
@@ -12,6 +12,7 @@
 //! - `cache`: Versioned state cache (reorg-aware)
 //! - `parallel`: Parallel storage root computation
 //! - `flat_storage`: O(1) execution reads (Dual-Path)
+//! - `state_diff`: Per-block account changes (validity proof input)
 //! - `verify`: Iterative proof verification (Stack-safe)
 
 pub mod cache;
@@ -24,6 +25,7 @@ pub mod node;
 pub mod parallel;
 pub mod proofs;
 pub mod rlp;
+pub mod state_diff;
 pub mod trie;
 pub mod verify;
 
@@ -34,5 +36,6 @@ pub use errors::*;
 pub use flat_storage::*;
 pub use parallel::*;
 pub use proofs::*;
+pub use state_diff::*;
 pub use trie::*;
 pub use verify::*;
@@ -63,16 +63,18 @@ impl TrieNode {
             TrieNode::Branch { children, value } => {
                 let mut items: Vec<Vec<u8>> = Vec::with_capacity(17);
 
+                // Empty slots are the RLP empty string (0x80); the list
+                // encoder adds that header, so pass an empty item.
                 for child in children.iter() {
                     match child {
                         Some(hash) => items.push(hash.to_vec()),
-                        None => items.push(vec![0x80]), // Empty
+                        None => items.push(Vec::new()),
                     }
                 }
 
                 match value {
                     Some(v) => items.push(v.clone()),
-                    None => items.push(vec![0x80]),
+                    None => items.push(Vec::new()),
                 }
 
                 rlp::rlp_encode_list_items(&items)
@@ -139,4 +141,20 @@ mod tests {
         assert_eq!(hash1, hash2);
         assert_ne!(hash1, EMPTY_TRIE_ROOT);
     }
+
+    #[test]
+    fn test_branch_empty_slots_encode_as_empty_string() {
+        let mut children: [Option<Hash>; 16] = [None; 16];
+        children[0] = Some([0x11; 32]);
+        let branch = TrieNode::Branch {
+            children: Box::new(children),
+            value: None,
+        };
+
+        // [0xa0 ++ hash, 0x80 x 15 empty children, 0x80 empty value]
+        let mut expected = vec![0xc0 + 49, 0xa0];
+        expected.extend_from_slice(&[0x11; 32]);
+        expected.extend_from_slice(&[0x80; 16]);
+        assert_eq!(branch.rlp_encode(), expected);
+    }
 }
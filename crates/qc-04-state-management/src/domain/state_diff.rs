@@ -0,0 +1,212 @@
+//! # State Diffs
+//!
+//! Accounts a block touched, with their balance and nonce before and after.
+//!
+//! A diff is the input to validity proofs of the block's state transition:
+//! the proof covers the balance and nonce changes, and the diff's digest
+//! binds it to the addresses and state roots.
+//!
+//! ## Usage
+//!
+//! ```text
+//! let recorder = StateDiffRecorder::begin(&trie, touched_addresses)?;
+//! // ... apply the block to the trie ...
+//! let diff = recorder.finish(&trie, block_hash, block_height, minted)?;
+//! ```
+
+use super::{Address, Hash, PatriciaMerkleTrie, StateError};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::collections::HashSet;
+
+/// Balance and nonce of one account before and after a block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountDiff {
+    /// Account address.
+    pub address: Address,
+    /// Balance before the block.
+    pub balance_before: u128,
+    /// Balance after the block.
+    pub balance_after: u128,
+    /// Nonce before the block.
+    pub nonce_before: u64,
+    /// Nonce after the block.
+    pub nonce_after: u64,
+}
+
+impl AccountDiff {
+    /// Whether the block left this account unchanged.
+    pub fn is_unchanged(&self) -> bool {
+        self.balance_before == self.balance_after && self.nonce_before == self.nonce_after
+    }
+}
+
+/// Account changes made by one block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDiff {
+    /// Hash of the block.
+    pub block_hash: Hash,
+    /// Height of the block.
+    pub block_height: u64,
+    /// State root before the block.
+    pub previous_state_root: Hash,
+    /// State root after the block.
+    pub state_root: Hash,
+    /// Changed accounts, in the order the block first touched them.
+    pub accounts: Vec<AccountDiff>,
+    /// Amount created by the block (coinbase), not debited from any account.
+    pub minted: u128,
+}
+
+impl StateDiff {
+    /// Keccak256 digest of the whole diff.
+    pub fn digest(&self) -> Hash {
+        let mut hasher = Keccak256::new();
+        hasher.update(self.block_hash);
+        hasher.update(self.block_height.to_be_bytes());
+        hasher.update(self.previous_state_root);
+        hasher.update(self.state_root);
+        hasher.update(self.minted.to_be_bytes());
+        hasher.update((self.accounts.len() as u64).to_be_bytes());
+        for account in &self.accounts {
+            hasher.update(account.address);
+            hasher.update(account.balance_before.to_be_bytes());
+            hasher.update(account.balance_after.to_be_bytes());
+            hasher.update(account.nonce_before.to_be_bytes());
+            hasher.update(account.nonce_after.to_be_bytes());
+        }
+        hasher.finalize().into()
+    }
+
+    /// Whether balance changes sum to the minted amount.
+    pub fn is_balanced(&self) -> bool {
+        let (credits, debits) =
+            self.accounts
+                .iter()
+                .fold((0u128, 0u128), |(credits, debits), account| {
+                    if account.balance_after >= account.balance_before {
+                        let credit = account.balance_after - account.balance_before;
+                        (credits.saturating_add(credit), debits)
+                    } else {
+                        let debit = account.balance_before - account.balance_after;
+                        (credits, debits.saturating_add(debit))
+                    }
+                });
+        debits.checked_add(self.minted) == Some(credits)
+    }
+}
+
+/// Captures account states around the application of one block.
+#[derive(Clone, Debug)]
+pub struct StateDiffRecorder {
+    previous_state_root: Hash,
+    /// (address, balance, nonce) before the block
+    before: Vec<(Address, u128, u64)>,
+}
+
+impl StateDiffRecorder {
+    /// Snapshot `addresses` (duplicates ignored) before applying a block.
+    pub fn begin(
+        trie: &PatriciaMerkleTrie,
+        addresses: impl IntoIterator<Item = Address>,
+    ) -> Result<Self, StateError> {
+        let mut seen = HashSet::new();
+        let mut before = Vec::new();
+        for address in addresses {
+            if seen.insert(address) {
+                before.push((
+                    address,
+                    trie.get_balance(address)?,
+                    trie.get_nonce(address)?,
+                ));
+            }
+        }
+
+        Ok(Self {
+            previous_state_root: trie.root_hash(),
+            before,
+        })
+    }
+
+    /// Build the diff once the block has been applied to `trie`.
+    ///
+    /// Accounts the block left unchanged (e.g. a failed debit) are dropped.
+    pub fn finish(
+        self,
+        trie: &PatriciaMerkleTrie,
+        block_hash: Hash,
+        block_height: u64,
+        minted: u128,
+    ) -> Result<StateDiff, StateError> {
+        let mut accounts = Vec::with_capacity(self.before.len());
+        for (address, balance_before, nonce_before) in self.before {
+            let account = AccountDiff {
+                address,
+                balance_before,
+                balance_after: trie.get_balance(address)?,
+                nonce_before,
+                nonce_after: trie.get_nonce(address)?,
+            };
+            if !account.is_unchanged() {
+                accounts.push(account);
+            }
+        }
+
+        Ok(StateDiff {
+            block_hash,
+            block_height,
+            previous_state_root: self.previous_state_root,
+            state_root: trie.root_hash(),
+            accounts,
+            minted,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_changed_accounts() {
+        let (alice, bob, miner) = ([1u8; 20], [2u8; 20], [3u8; 20]);
+        let mut trie = PatriciaMerkleTrie::new();
+        trie.set_balance(alice, 1_000).unwrap();
+
+        let recorder = StateDiffRecorder::begin(&trie, [alice, bob, alice, miner]).unwrap();
+        let previous_root = trie.root_hash();
+        trie.apply_balance_change(alice, -300).unwrap();
+        trie.apply_balance_change(bob, 300).unwrap();
+        trie.apply_nonce_increment(alice, 0).unwrap();
+        trie.mint(miner, 50).unwrap();
+        let diff = recorder.finish(&trie, [9u8; 32], 1, 50).unwrap();
+
+        assert_eq!(diff.previous_state_root, previous_root);
+        assert_eq!(diff.state_root, trie.root_hash());
+        assert_eq!(diff.accounts.len(), 3);
+        assert_eq!(diff.accounts[0].address, alice);
+        assert_eq!(
+            (diff.accounts[0].balance_after, diff.accounts[0].nonce_after),
+            (700, 1)
+        );
+        assert!(diff.is_balanced());
+
+        let mut unbalanced = diff.clone();
+        unbalanced.minted = 0;
+        assert!(!unbalanced.is_balanced());
+        assert_ne!(unbalanced.digest(), diff.digest());
+    }
+
+    #[test]
+    fn test_unchanged_accounts_are_dropped() {
+        let mut trie = PatriciaMerkleTrie::new();
+        let recorder = StateDiffRecorder::begin(&trie, [[1u8; 20], [2u8; 20]]).unwrap();
+        // Debit fails on an empty account
+        assert!(trie.apply_balance_change([1u8; 20], -5).is_err());
+        trie.apply_balance_change([2u8; 20], 5).unwrap();
+        let diff = recorder.finish(&trie, [0u8; 32], 1, 0).unwrap();
+
+        assert_eq!(diff.accounts.len(), 1);
+        assert!(!diff.is_balanced());
+    }
+}
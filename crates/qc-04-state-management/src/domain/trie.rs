@@ -37,6 +37,12 @@ use super::{
 };
 use std::collections::HashMap;
 
+/// Serialized account: address, balance, nonce, code hash, storage root.
+const ACCOUNT_RECORD_LEN: usize = 20 + 16 + 8 + 32 + 32;
+
+/// Serialized storage slot: contract address, key, value.
+const STORAGE_RECORD_LEN: usize = 20 + 32 + 32;
+
 // =============================================================================
 // PATRICIA MERKLE TRIE
 // =============================================================================
@@ -152,6 +158,15 @@ impl PatriciaMerkleTrie {
         Ok(())
     }
 
+    /// Mint a block reward (subsidy plus fees) to `address`.
+    ///
+    /// Unlike a transfer there is no sender to debit; the balance saturates
+    /// instead of overflowing.
+    pub fn mint(&mut self, address: Address, amount: u128) -> Result<(), StateError> {
+        let current = self.get_balance(address)?;
+        self.set_balance(address, current.saturating_add(amount))
+    }
+
     /// Apply a balance change with INVARIANT-1 enforcement.
     ///
     /// Returns error if the change would result in negative balance.
@@ -557,16 +572,28 @@ impl PatriciaMerkleTrie {
         Ok(trie)
     }
     
+    /// Helper to read a record count, checking the records fit in `data`
+    /// (snapshots may come from peers)
+    fn deserialize_count(
+        cursor: &mut usize,
+        data: &[u8],
+        record_len: usize,
+    ) -> Result<usize, StateError> {
+        let truncated = || StateError::DatabaseError("Truncated trie data".to_string());
+        let bytes = data.get(*cursor..*cursor + 4).ok_or_else(truncated)?;
+        let count = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize;
+        *cursor += 4;
+
+        let needed = count.checked_mul(record_len).ok_or_else(truncated)?;
+        if data.len() - *cursor < needed {
+            return Err(truncated());
+        }
+        Ok(count)
+    }
+
     /// Helper to deserialize accounts
     fn deserialize_accounts(cursor: &mut usize, data: &[u8]) -> Result<HashMap<Address, AccountState>, StateError> {
-        // Account count
-        let account_count = u32::from_le_bytes([
-            data[*cursor],
-            data[*cursor + 1],
-            data[*cursor + 2],
-            data[*cursor + 3],
-        ]) as usize;
-        *cursor += 4;
+        let account_count = Self::deserialize_count(cursor, data, ACCOUNT_RECORD_LEN)?;
 
         let mut accounts = HashMap::with_capacity(account_count);
 
@@ -630,14 +657,7 @@ impl PatriciaMerkleTrie {
     
     /// Helper to deserialize storage
     fn deserialize_storage(cursor: &mut usize, data: &[u8]) -> Result<(HashMap<(Address, StorageKey), StorageValue>, HashMap<Address, usize>), StateError> {
-        // Storage count
-        let storage_count = u32::from_le_bytes([
-            data[*cursor],
-            data[*cursor + 1],
-            data[*cursor + 2],
-            data[*cursor + 3],
-        ]) as usize;
-        *cursor += 4;
+        let storage_count = Self::deserialize_count(cursor, data, STORAGE_RECORD_LEN)?;
 
         let mut storage = HashMap::with_capacity(storage_count);
         let mut storage_counts = HashMap::new();
@@ -798,6 +818,20 @@ mod tests {
         assert_eq!(trie1.root_hash(), trie2.root_hash());
     }
 
+    #[test]
+    fn test_mint_credits_without_sender() {
+        let mut trie = PatriciaMerkleTrie::new();
+        let miner = [0xCD; 20];
+        let empty_root = trie.root_hash();
+
+        trie.mint(miner, 5_000_000_000).unwrap();
+        trie.mint(miner, 1_000_000).unwrap();
+
+        assert_eq!(trie.get_balance(miner).unwrap(), 5_001_000_000);
+        assert_eq!(trie.get_balance([0u8; 20]).unwrap(), 0);
+        assert_ne!(trie.root_hash(), empty_root);
+    }
+
     #[test]
     fn test_balance_underflow_protection() {
         let mut trie = PatriciaMerkleTrie::new();
@@ -939,6 +973,24 @@ mod tests {
         assert_eq!(restored.get_balance([0x02; 20]).unwrap(), 2000);
     }
 
+    #[test]
+    fn test_deserialize_rejects_truncated_data() {
+        let mut trie = PatriciaMerkleTrie::new();
+        trie.set_balance([0x01; 20], 1000).unwrap();
+        trie.set_storage([0x01; 20], [0xAA; 32], [0xBB; 32])
+            .unwrap();
+        let serialized = trie.serialize().unwrap();
+
+        for len in [1, 33, 40, serialized.len() - 1] {
+            assert!(PatriciaMerkleTrie::deserialize(&serialized[..len]).is_err());
+        }
+
+        // A count larger than the data must not be trusted for allocation
+        let mut inflated = serialized.clone();
+        inflated[33..37].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(PatriciaMerkleTrie::deserialize(&inflated).is_err());
+    }
+
     #[test]
     fn test_account_rlp_encoding() {
         let account = AccountState {
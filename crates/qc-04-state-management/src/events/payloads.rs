@@ -27,6 +27,20 @@ pub struct BlockValidatedPayload {
     pub block_height: u64,
     /// Transactions to apply to state.
     pub transactions: Vec<TransactionData>,
+    /// Block reward minted to the beneficiary (None if the block has none).
+    #[serde(default)]
+    pub coinbase: Option<CoinbaseData>,
+}
+
+/// Coinbase reward within a BlockValidated payload.
+///
+/// Checked against the subsidy schedule by Consensus before it gets here.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CoinbaseData {
+    /// Fee recipient credited with the reward.
+    pub recipient: Address,
+    /// Block subsidy plus collected fees, in base units.
+    pub amount: u128,
 }
 
 /// Transaction data within a BlockValidated payload.
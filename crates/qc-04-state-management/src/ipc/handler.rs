@@ -182,6 +182,12 @@ impl<K: KeyProvider> IpcHandler<K> {
             trie.apply_nonce_increment(tx.from, tx.nonce)?;
         }
 
+        // Mint the block reward (no sender to debit)
+        if let Some(coinbase) = &payload.coinbase {
+            trie.mint(coinbase.recipient, coinbase.amount)?;
+            accounts_modified += 1;
+        }
+
         let new_root = trie.root_hash();
 
         // Store state root for this height
@@ -447,6 +453,7 @@ mod tests {
             block_hash: [0u8; 32],
             block_height: 1,
             transactions: vec![],
+            coinbase: None,
         };
 
         // Create message from Mempool (6) - should be rejected
@@ -41,6 +41,10 @@ pub const MAX_DESCENDANTS: usize = 25;
 /// Already enforced in MempoolConfig, constant for validation.
 pub const MAX_GAS_LIMIT: u64 = 30_000_000;
 
+/// Maximum replaced transactions remembered per transaction.
+/// Bounds the memory a sender can pin by bumping fees repeatedly.
+pub const MAX_REPLACEMENT_HISTORY: usize = 16;
+
 /// Transaction state in the Two-Phase Commit protocol.
 ///
 /// State machine (SPEC-06 Section 1.3):
@@ -63,6 +67,19 @@ pub enum TransactionState {
     },
 }
 
+/// A transaction that was replaced by a higher-fee one (RBF).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReplacedTransaction {
+    /// Hash of the replaced transaction.
+    pub hash: Hash,
+    /// Its gas price.
+    pub gas_price: U256,
+    /// When it was added to the pool (ms).
+    pub added_at: Timestamp,
+    /// When it was replaced (ms).
+    pub replaced_at: Timestamp,
+}
+
 /// A transaction in the mempool with metadata.
 ///
 /// Per SPEC-06 Section 2.1: Contains the full SignedTransaction.
@@ -89,6 +106,9 @@ pub struct MempoolTransaction {
     pub added_at: Timestamp,
     /// Target block height (if in pending_inclusion state).
     pub target_block: Option<u64>,
+    /// Transactions this one replaced for the same sender and nonce,
+    /// oldest first (at most `MAX_REPLACEMENT_HISTORY`).
+    pub replaced: Vec<ReplacedTransaction>,
 }
 
 impl MempoolTransaction {
@@ -110,9 +130,24 @@ impl MempoolTransaction {
             state: TransactionState::Pending,
             added_at,
             target_block: None,
+            replaced: Vec::new(),
         }
     }
 
+    /// Takes over the replacement history of `old`, which this transaction
+    /// replaces, and adds `old` itself to it.
+    pub fn record_replacement(&mut self, old: MempoolTransaction) {
+        self.replaced = old.replaced;
+        self.replaced.push(ReplacedTransaction {
+            hash: old.hash,
+            gas_price: old.gas_price,
+            added_at: old.added_at,
+            replaced_at: self.added_at,
+        });
+        let excess = self.replaced.len().saturating_sub(MAX_REPLACEMENT_HISTORY);
+        self.replaced.drain(..excess);
+    }
+
     /// Returns the total gas cost (gas_price * gas_limit).
     pub fn gas_cost(&self) -> U256 {
         self.gas_price * U256::from(self.gas_limit)
@@ -123,6 +158,13 @@ impl MempoolTransaction {
         self.transaction.value + self.gas_cost()
     }
 
+    /// Approximate memory held by this transaction, in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.transaction.data.capacity()
+            + self.replaced.capacity() * std::mem::size_of::<ReplacedTransaction>()
+    }
+
     /// Returns true if the transaction is available for block inclusion.
     pub fn is_pending(&self) -> bool {
         matches!(self.state, TransactionState::Pending)
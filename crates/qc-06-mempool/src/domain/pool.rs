@@ -19,6 +19,7 @@ use super::entities::{
     Address, Hash, MempoolConfig, MempoolTransaction, Timestamp, TransactionState, U256,
 };
 use super::errors::MempoolError;
+use super::persistence::PersistedTransaction;
 use super::value_objects::{
     MempoolStatus, PendingInclusionBatch, PricedTransaction, ProposeResult,
 };
@@ -190,8 +191,7 @@ impl TransactionPool {
                 .get(&hash)
                 .ok_or(MempoolError::TransactionNotFound(hash))?;
             if self.can_replace(existing, &tx)? {
-                self.remove_internal(&hash)?;
-                return self.add_internal(tx);
+                return self.replace(&hash, tx);
             }
         }
 
@@ -230,7 +230,14 @@ impl TransactionPool {
             });
         }
 
-        self.remove_internal(&hash)?;
+        self.replace(&hash, tx)
+    }
+
+    /// Replaces the transaction `hash` with `tx`, keeping the replacement
+    /// history.
+    fn replace(&mut self, hash: &Hash, mut tx: MempoolTransaction) -> Result<(), MempoolError> {
+        let old = self.remove_internal(hash)?;
+        tx.record_replacement(old);
         self.add_internal(tx)
     }
 
@@ -321,6 +328,22 @@ impl TransactionPool {
         self.remove_internal(hash)
     }
 
+    /// Removes a transaction on operator request.
+    ///
+    /// # Errors
+    /// - `TransactionNotFound` if the hash is not in the pool
+    /// - `TransactionPendingInclusion` if it is proposed for a block; it
+    ///   leaves the pool when that block is stored or rolled back
+    pub fn evict(&mut self, hash: &Hash) -> Result<MempoolTransaction, MempoolError> {
+        match self.by_hash.get(hash) {
+            Some(tx) if tx.is_pending_inclusion() => {
+                Err(MempoolError::TransactionPendingInclusion(*hash))
+            }
+            Some(_) => self.remove_internal(hash),
+            None => Err(MempoolError::TransactionNotFound(*hash)),
+        }
+    }
+
     /// Internal remove implementation.
     fn remove_internal(&mut self, hash: &Hash) -> Result<MempoolTransaction, MempoolError> {
         let tx = self
@@ -510,6 +533,35 @@ impl TransactionPool {
         self.rollback(&timed_out)
     }
 
+    /// Approximate memory held by pooled transactions, in bytes.
+    pub fn memory_bytes(&self) -> usize {
+        self.by_hash
+            .values()
+            .map(MempoolTransaction::memory_bytes)
+            .sum()
+    }
+
+    /// Evicts the lowest priority pending transactions until the pool holds
+    /// at most `max_bytes`.
+    ///
+    /// PendingInclusion transactions are never evicted; they belong to a
+    /// proposed block. Returns the evicted hashes.
+    pub fn shrink_to(&mut self, max_bytes: usize) -> Vec<Hash> {
+        let mut bytes = self.memory_bytes();
+        let mut evicted = Vec::new();
+        while bytes > max_bytes {
+            let Some(lowest) = self.by_price.iter().next_back().map(|p| p.hash) else {
+                break;
+            };
+            let Ok(tx) = self.remove_internal(&lowest) else {
+                break;
+            };
+            bytes -= tx.memory_bytes();
+            evicted.push(tx.hash);
+        }
+        evicted
+    }
+
     /// Gets the number of transactions for a sender.
     pub fn sender_count(&self, sender: &Address) -> usize {
         self.by_sender.get(sender).map(|m| m.len()).unwrap_or(0)
@@ -523,6 +575,22 @@ impl TransactionPool {
             .unwrap_or_default()
     }
 
+    /// Nonces missing between a sender's lowest and highest pooled nonce,
+    /// as inclusive ranges.
+    ///
+    /// Transactions above a gap cannot be included until it is filled.
+    pub fn sender_nonce_gaps(&self, sender: &Address) -> Vec<(u64, u64)> {
+        let Some(nonces) = self.by_sender.get(sender) else {
+            return Vec::new();
+        };
+        nonces
+            .keys()
+            .zip(nonces.keys().skip(1))
+            .filter(|(low, high)| **high > **low + 1)
+            .map(|(low, high)| (low + 1, high - 1))
+            .collect()
+    }
+
     /// Gets the mempool status.
     pub fn status(&self, now: Timestamp) -> MempoolStatus {
         let oldest_age = self
@@ -542,6 +610,51 @@ impl TransactionPool {
             oldest_tx_age_ms: oldest_age,
         }
     }
+
+    /// Snapshots every transaction for `mempool.dat` (graceful shutdown).
+    ///
+    /// Proposed batches do not survive a restart, so `PendingInclusion`
+    /// transactions are saved as well and come back as pending. Entries are
+    /// ordered by sender and nonce.
+    pub fn persisted_transactions(&self, current_height: u64) -> Vec<PersistedTransaction> {
+        let mut transactions: Vec<_> = self
+            .by_hash
+            .values()
+            .filter_map(|tx| {
+                let raw_data = bincode::serialize(&tx.transaction).ok()?;
+                Some(PersistedTransaction {
+                    hash: tx.hash,
+                    sender: tx.sender,
+                    nonce: tx.nonce,
+                    gas_price: tx.gas_price,
+                    gas_limit: tx.gas_limit,
+                    raw_data,
+                    first_seen: tx.added_at,
+                    saved_at_height: current_height,
+                })
+            })
+            .collect();
+        transactions.sort_by_key(|tx| (tx.sender, tx.nonce));
+        transactions
+    }
+
+    /// Re-adds transactions loaded from `mempool.dat`.
+    ///
+    /// Entries that do not decode, whose hash does not match, or that the
+    /// pool rejects are skipped. Returns how many were added.
+    pub fn restore(&mut self, transactions: Vec<PersistedTransaction>) -> usize {
+        let mut restored = 0;
+        for persisted in transactions {
+            let Ok(signed) = bincode::deserialize(&persisted.raw_data) else {
+                continue;
+            };
+            let tx = MempoolTransaction::new(signed, persisted.first_seen);
+            if tx.hash == persisted.hash && self.add(tx).is_ok() {
+                restored += 1;
+            }
+        }
+        restored
+    }
 }
 
 #[cfg(test)]
@@ -840,6 +953,59 @@ mod tests {
         assert!(pool.contains(&hash1));
     }
 
+    #[test]
+    fn test_rbf_records_replacement_history() {
+        let mut pool = TransactionPool::with_defaults();
+        let tx1 = create_tx_at(0xAA, 0, 1_000_000_000, 1000);
+        let tx2 = create_tx_at(0xAA, 0, 1_200_000_000, 2000);
+        let tx3 = create_tx_at(0xAA, 0, 1_500_000_000, 3000);
+        let (hash1, hash2, hash3) = (tx1.hash, tx2.hash, tx3.hash);
+
+        pool.add(tx1).unwrap();
+        pool.add(tx2).unwrap();
+        pool.add(tx3).unwrap();
+
+        let replaced = &pool.get(&hash3).unwrap().replaced;
+        let hashes: Vec<_> = replaced.iter().map(|r| r.hash).collect();
+        assert_eq!(hashes, vec![hash1, hash2]);
+        assert_eq!(replaced[0].replaced_at, 2000);
+        assert_eq!(replaced[1].gas_price, U256::from(1_200_000_000u64));
+    }
+
+    #[test]
+    fn test_evict() {
+        let mut pool = TransactionPool::with_defaults();
+        let tx1 = create_tx(0xAA, 0, 2_000_000_000);
+        let tx2 = create_tx(0xAA, 1, 2_000_000_000);
+        let (hash1, hash2) = (tx1.hash, tx2.hash);
+        pool.add(tx1).unwrap();
+        pool.add(tx2).unwrap();
+        pool.propose(&[hash1], 1, 2000);
+
+        assert!(matches!(
+            pool.evict(&hash1),
+            Err(MempoolError::TransactionPendingInclusion(_))
+        ));
+        assert_eq!(pool.evict(&hash2).unwrap().hash, hash2);
+        assert!(matches!(
+            pool.evict(&hash2),
+            Err(MempoolError::TransactionNotFound(_))
+        ));
+        assert!(pool.contains(&hash1));
+    }
+
+    #[test]
+    fn test_sender_nonce_gaps() {
+        let mut pool = TransactionPool::with_defaults();
+        for nonce in [3, 4, 7, 9] {
+            pool.add(create_tx(0xAA, nonce, 2_000_000_000)).unwrap();
+        }
+        let sender = create_tx(0xAA, 0, 2_000_000_000).sender;
+
+        assert_eq!(pool.sender_nonce_gaps(&sender), vec![(5, 6), (8, 8)]);
+        assert!(pool.sender_nonce_gaps(&[0xBB; 20]).is_empty());
+    }
+
     #[test]
     fn test_rbf_disabled_config() {
         let config = MempoolConfig {
@@ -993,9 +1159,58 @@ mod tests {
         assert_eq!(status.pending_inclusion_count, 1);
     }
 
+    #[test]
+    fn test_persist_and_restore() {
+        let mut pool = TransactionPool::with_defaults();
+        let tx1 = create_tx(0xAA, 0, 1_000_000_000);
+        let tx2 = create_tx(0xAA, 1, 1_000_000_000);
+        let (hash1, hash2) = (tx1.hash, tx2.hash);
+        pool.add(tx1).unwrap();
+        pool.add(tx2).unwrap();
+        pool.propose(&[hash1], 1, 2000);
+
+        let mut saved = pool.persisted_transactions(7);
+        assert_eq!(saved.len(), 2);
+        assert_eq!((saved[0].nonce, saved[1].nonce), (0, 1));
+        assert!(saved.iter().all(|tx| tx.saved_at_height == 7));
+
+        // A corrupt entry is skipped, the rest come back as pending
+        saved.push(PersistedTransaction {
+            raw_data: vec![0xFF],
+            ..saved[0].clone()
+        });
+        let mut restored = TransactionPool::with_defaults();
+        assert_eq!(restored.restore(saved), 2);
+        assert!(restored.get(&hash1).unwrap().is_pending());
+        assert!(restored.get(&hash2).unwrap().is_pending());
+        assert_eq!(restored.get(&hash1).unwrap().added_at, 1000);
+    }
+
     #[test]
     fn test_address_is_20_bytes() {
         let tx = create_tx(0xAA, 0, 1_000_000_000);
         assert_eq!(tx.sender.len(), 20);
     }
+
+    #[test]
+    fn test_shrink_to_evicts_lowest_price_pending() {
+        let mut pool = TransactionPool::with_defaults();
+        let cheap = create_tx(0xA1, 0, 1_000_000_000);
+        let mid = create_tx(0xA2, 0, 2_000_000_000);
+        let proposed = create_tx(0xA3, 0, 1_500_000_000);
+        let (cheap_hash, mid_hash, proposed_hash) = (cheap.hash, mid.hash, proposed.hash);
+        pool.add(cheap).unwrap();
+        pool.add(mid).unwrap();
+        pool.add(proposed).unwrap();
+        pool.propose(&[proposed_hash], 1, 2000);
+
+        let per_tx = pool.memory_bytes() / 3;
+        assert_eq!(pool.shrink_to(per_tx * 2), vec![cheap_hash]);
+        assert!(pool.contains(&mid_hash));
+
+        // Proposed transactions stay even when over budget
+        assert_eq!(pool.shrink_to(0), vec![mid_hash]);
+        assert!(pool.contains(&proposed_hash));
+        assert_eq!(pool.memory_bytes(), per_tx);
+    }
 }
@@ -106,6 +106,7 @@ where
                 target,
                 method,
                 params,
+                ..
             } => {
                 // Only handle queries targeting us
                 if target == "qc-07-bloom-filters" {
@@ -147,6 +148,7 @@ where
             result: result.map_err(|e| ApiQueryError {
                 code: -32000,
                 message: e.to_string(),
+                info: None,
             }),
         };
 
@@ -314,6 +316,7 @@ impl ApiGatewayHandler {
             target: "qc-07-bloom-filters".to_string(),
             method: method.to_string(),
             params,
+            trace_parent: None,
         };
 
         self.bus.publish(event).await;
@@ -82,6 +82,7 @@ impl TxIndexingAdapter {
             target: "qc-03-transaction-indexing".to_string(),
             method: method.to_string(),
             params,
+            trace_parent: None,
         };
 
         // Check if there are any ApiGateway subscribers (our query handler)
@@ -6,6 +6,7 @@
 //! Reference: SPEC-08-CONSENSUS.md Section 4
 
 use primitive_types::U256;
+use shared_types::entities::{CoinbaseTransaction, RewardSchedule};
 use std::collections::HashSet;
 
 /// Parameters for block validation.
@@ -23,6 +24,8 @@ pub struct BlockValidationParams {
     pub timestamp: u64,
     /// Parent block hash.
     pub parent_hash: [u8; 32],
+    /// Coinbase reward claimed by the producer (None skips the reward check).
+    pub coinbase: Option<CoinbaseTransaction>,
 }
 
 /// Configuration for block validation.
@@ -34,6 +37,10 @@ pub struct BlockValidationConfig {
     pub max_future_drift_secs: u64,
     /// Whether to enforce strict sequential height validation.
     pub strict_height_validation: bool,
+    /// Block subsidy schedule coinbase claims are checked against.
+    pub reward_schedule: RewardSchedule,
+    /// Easiest difficulty target the chain allows (None = no limit).
+    pub max_target: Option<U256>,
 }
 
 impl Default for BlockValidationConfig {
@@ -42,6 +49,8 @@ impl Default for BlockValidationConfig {
             max_cached_blocks: 1000,
             max_future_drift_secs: 15,
             strict_height_validation: false, // Allow flexibility during initial sync
+            reward_schedule: RewardSchedule::default(),
+            max_target: None,
         }
     }
 }
@@ -55,12 +64,22 @@ pub enum BlockValidationError {
     NonSequentialHeight { expected: u64, got: u64 },
     /// Block has zero difficulty (invalid).
     ZeroDifficulty,
+    /// Difficulty target is easier than the chain allows.
+    TargetAboveLimit { target: U256, limit: U256 },
     /// Block timestamp is too far in the future.
     FutureTimestamp {
         block_timestamp: u64,
         current_time: u64,
         max_drift: u64,
     },
+    /// Coinbase claim is for a different block height.
+    CoinbaseHeightMismatch { expected: u64, got: u64 },
+    /// Coinbase reward exceeds the block subsidy plus the collected fees.
+    ExcessiveCoinbase {
+        block_height: u64,
+        claimed: U256,
+        allowed: U256,
+    },
 }
 
 impl std::fmt::Display for BlockValidationError {
@@ -81,6 +100,13 @@ impl std::fmt::Display for BlockValidationError {
                 )
             }
             Self::ZeroDifficulty => write!(f, "Block has zero difficulty"),
+            Self::TargetAboveLimit { target, limit } => {
+                write!(
+                    f,
+                    "Difficulty target {} is above the chain limit {}",
+                    target, limit
+                )
+            }
             Self::FutureTimestamp {
                 block_timestamp,
                 current_time,
@@ -94,6 +120,24 @@ impl std::fmt::Display for BlockValidationError {
                     max_drift
                 )
             }
+            Self::CoinbaseHeightMismatch { expected, got } => {
+                write!(
+                    f,
+                    "Coinbase claims height {}, block is at height {}",
+                    got, expected
+                )
+            }
+            Self::ExcessiveCoinbase {
+                block_height,
+                claimed,
+                allowed,
+            } => {
+                write!(
+                    f,
+                    "Coinbase at height {} claims {}, at most {} allowed",
+                    block_height, claimed, allowed
+                )
+            }
         }
     }
 }
@@ -190,13 +234,18 @@ impl BlockValidator {
         Ok(None)
     }
 
-    /// Validate PoW difficulty is non-zero.
+    /// Validate PoW difficulty is non-zero and within the chain limit.
     pub fn validate_difficulty(&self, difficulty: &[u8; 32]) -> Result<(), BlockValidationError> {
         let difficulty_u256 = U256::from_big_endian(difficulty);
         if difficulty_u256.is_zero() {
-            Err(BlockValidationError::ZeroDifficulty)
-        } else {
-            Ok(())
+            return Err(BlockValidationError::ZeroDifficulty);
+        }
+        match self.config.max_target {
+            Some(limit) if difficulty_u256 > limit => Err(BlockValidationError::TargetAboveLimit {
+                target: difficulty_u256,
+                limit,
+            }),
+            _ => Ok(()),
         }
     }
 
@@ -229,6 +278,38 @@ impl BlockValidator {
         Ok(None)
     }
 
+    /// Validate a coinbase claim against the block subsidy schedule.
+    ///
+    /// The claimed reward may not exceed the subsidy at `block_height` plus
+    /// the claimed fees; the fees themselves are checked when the block's
+    /// transactions are executed.
+    pub fn validate_coinbase(
+        &self,
+        block_height: u64,
+        coinbase: &CoinbaseTransaction,
+    ) -> Result<(), BlockValidationError> {
+        if coinbase.block_height != block_height {
+            return Err(BlockValidationError::CoinbaseHeightMismatch {
+                expected: block_height,
+                got: coinbase.block_height,
+            });
+        }
+
+        let allowed = self
+            .config
+            .reward_schedule
+            .max_reward(block_height, coinbase.fees);
+        if coinbase.reward > allowed {
+            return Err(BlockValidationError::ExcessiveCoinbase {
+                block_height,
+                claimed: coinbase.reward,
+                allowed,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Check if the validated block cache needs eviction.
     pub fn should_evict_cache(&self, cache_size: usize) -> bool {
         cache_size > self.config.max_cached_blocks
@@ -270,6 +351,11 @@ impl BlockValidator {
             warnings.push(warning);
         }
 
+        // 5. Validate coinbase reward
+        if let Some(coinbase) = &params.coinbase {
+            self.validate_coinbase(params.block_height, coinbase)?;
+        }
+
         Ok(ValidationResult {
             block_hash: params.block_hash,
             block_height: params.block_height,
@@ -294,6 +380,17 @@ mod tests {
             nonce: 12345,
             timestamp,
             parent_hash: [(height.saturating_sub(1)) as u8; 32],
+            coinbase: None,
+        }
+    }
+
+    fn make_coinbase(height: u64, reward: U256, fees: U256) -> CoinbaseTransaction {
+        CoinbaseTransaction {
+            block_height: height,
+            miner_address: [9u8; 20],
+            reward,
+            fees,
+            timestamp: 1000,
         }
     }
 
@@ -376,6 +473,25 @@ mod tests {
         assert!(validator.validate_difficulty(&nonzero_difficulty).is_ok());
     }
 
+    #[test]
+    fn test_target_above_limit_rejected() {
+        let config = BlockValidationConfig {
+            max_target: Some(U256::from(1000)),
+            ..Default::default()
+        };
+        let validator = BlockValidator::new(config);
+
+        let mut target = [0u8; 32];
+        U256::from(1000).to_big_endian(&mut target);
+        assert!(validator.validate_difficulty(&target).is_ok());
+
+        U256::from(1001).to_big_endian(&mut target);
+        assert!(matches!(
+            validator.validate_difficulty(&target),
+            Err(BlockValidationError::TargetAboveLimit { .. })
+        ));
+    }
+
     #[test]
     fn test_future_timestamp_rejected() {
         let validator = BlockValidator::with_defaults();
@@ -404,6 +520,48 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_coinbase_reward_checked_against_schedule() {
+        let validator = BlockValidator::with_defaults();
+        let subsidy = RewardSchedule::default().subsidy_at(5);
+        let fees = U256::from(2_000_000);
+
+        let exact = make_coinbase(5, subsidy + fees, fees);
+        assert!(validator.validate_coinbase(5, &exact).is_ok());
+
+        // Claiming less than allowed burns the difference
+        let modest = make_coinbase(5, subsidy, fees);
+        assert!(validator.validate_coinbase(5, &modest).is_ok());
+
+        let greedy = make_coinbase(5, subsidy + fees + 1, fees);
+        assert!(matches!(
+            validator.validate_coinbase(5, &greedy),
+            Err(BlockValidationError::ExcessiveCoinbase { .. })
+        ));
+
+        let wrong_height = make_coinbase(6, subsidy, U256::zero());
+        assert!(matches!(
+            validator.validate_coinbase(5, &wrong_height),
+            Err(BlockValidationError::CoinbaseHeightMismatch {
+                expected: 5,
+                got: 6
+            })
+        ));
+    }
+
+    #[test]
+    fn test_full_validation_rejects_excessive_coinbase() {
+        let validator = BlockValidator::with_defaults();
+        let mut params = make_test_params(5, 1000);
+        params.coinbase = Some(make_coinbase(5, U256::MAX, U256::zero()));
+
+        let result = validator.validate_block(&params, 4, 1000, &HashSet::new());
+        assert!(matches!(
+            result,
+            Err(BlockValidationError::ExcessiveCoinbase { .. })
+        ));
+    }
+
     #[test]
     fn test_cache_eviction_check() {
         let config = BlockValidationConfig {
@@ -0,0 +1,191 @@
+//! # Proposer Duties
+//!
+//! Decides which validator proposes each slot and tells this node when one
+//! of its own validators is due.
+//!
+//! ## Algorithm: Stake-Weighted Selection
+//!
+//! 1. seed = Keccak256("qc-proposer" || epoch || slot)
+//! 2. point = first 16 bytes of seed (big-endian) mod total active stake
+//! 3. The proposer is the validator whose cumulative stake range holds point
+//!
+//! Every node computes the same proposer from the same validator set, so no
+//! messages are needed to agree on it.
+//!
+//! Reference: SPEC-08-CONSENSUS.md Section 3.2
+
+use crate::domain::{ValidatorId, ValidatorSet};
+use sha3::{Digest, Keccak256};
+use std::collections::HashSet;
+
+/// Domain separator for the selection seed.
+const SEED_DOMAIN: &[u8] = b"qc-proposer";
+
+/// A slot one of the registered validators must propose.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlotDuty {
+    /// Slot to propose.
+    pub slot: u64,
+    /// Epoch of the slot.
+    pub epoch: u64,
+    /// Proposer's index in the validator set.
+    pub validator_index: u32,
+    /// Proposer.
+    pub validator: ValidatorId,
+    /// Seed that selected the proposer.
+    pub seed: [u8; 32],
+}
+
+/// Proposer schedule for the validators this node runs.
+#[derive(Clone, Debug)]
+pub struct DutyScheduler {
+    slots_per_epoch: u64,
+    registered: HashSet<ValidatorId>,
+}
+
+impl DutyScheduler {
+    /// Create a scheduler with no registered validators.
+    pub fn new(slots_per_epoch: u64) -> Self {
+        Self {
+            slots_per_epoch: slots_per_epoch.max(1),
+            registered: HashSet::new(),
+        }
+    }
+
+    /// Register a local validator. Returns false if it already was.
+    pub fn register(&mut self, validator: ValidatorId) -> bool {
+        self.registered.insert(validator)
+    }
+
+    /// Stop scheduling duties for a validator.
+    pub fn unregister(&mut self, validator: &ValidatorId) -> bool {
+        self.registered.remove(validator)
+    }
+
+    /// Check if a validator is registered.
+    pub fn is_registered(&self, validator: &ValidatorId) -> bool {
+        self.registered.contains(validator)
+    }
+
+    /// Epoch containing `slot`.
+    pub fn epoch_of(&self, slot: u64) -> u64 {
+        slot / self.slots_per_epoch
+    }
+
+    /// The proposer of `slot`, or None if no validator has active stake.
+    pub fn proposer(&self, validators: &ValidatorSet, slot: u64) -> Option<SlotDuty> {
+        let epoch = self.epoch_of(slot);
+        let seed = selection_seed(epoch, slot);
+
+        let active = || {
+            validators
+                .validators
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| v.active)
+        };
+        let total: u128 = active().map(|(_, v)| v.stake).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut point = u128::from_be_bytes(seed[..16].try_into().expect("16 bytes")) % total;
+        let (index, validator) = active().find(|(_, v)| {
+            if point < v.stake {
+                return true;
+            }
+            point -= v.stake;
+            false
+        })?;
+
+        Some(SlotDuty {
+            slot,
+            epoch,
+            validator_index: index as u32,
+            validator: validator.id,
+            seed,
+        })
+    }
+
+    /// The duty for `slot` if its proposer is registered here.
+    pub fn duty(&self, validators: &ValidatorSet, slot: u64) -> Option<SlotDuty> {
+        self.proposer(validators, slot)
+            .filter(|duty| self.is_registered(&duty.validator))
+    }
+}
+
+fn selection_seed(epoch: u64, slot: u64) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(SEED_DOMAIN);
+    hasher.update(epoch.to_be_bytes());
+    hasher.update(slot.to_be_bytes());
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ValidatorInfo;
+
+    fn validator_set(stakes: &[u128]) -> ValidatorSet {
+        let validators = stakes
+            .iter()
+            .enumerate()
+            .map(|(i, &stake)| ValidatorInfo::new([i as u8 + 1; 32], stake, [0u8; 48]))
+            .collect();
+        ValidatorSet::new(0, validators)
+    }
+
+    #[test]
+    fn test_proposer_is_deterministic() {
+        let set = validator_set(&[100, 200, 300]);
+        let scheduler = DutyScheduler::new(32);
+        for slot in 0..50 {
+            assert_eq!(
+                scheduler.proposer(&set, slot),
+                scheduler.proposer(&set, slot)
+            );
+        }
+        assert_eq!(scheduler.proposer(&set, 40).unwrap().epoch, 1);
+    }
+
+    #[test]
+    fn test_selection_follows_stake() {
+        let set = validator_set(&[1, 0, 99]);
+        let scheduler = DutyScheduler::new(32);
+        let mut counts = [0u32; 3];
+        for slot in 0..1000 {
+            counts[scheduler.proposer(&set, slot).unwrap().validator_index as usize] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        assert!(counts[2] > counts[0] * 10);
+    }
+
+    #[test]
+    fn test_no_active_stake() {
+        let mut set = validator_set(&[10]);
+        set.validators[0].active = false;
+        assert!(DutyScheduler::new(32).proposer(&set, 1).is_none());
+        assert!(DutyScheduler::new(32)
+            .proposer(&validator_set(&[]), 1)
+            .is_none());
+    }
+
+    #[test]
+    fn test_duties_only_for_registered_validators() {
+        let set = validator_set(&[50, 50]);
+        let mut scheduler = DutyScheduler::new(32);
+        assert!((0..20).all(|slot| scheduler.duty(&set, slot).is_none()));
+
+        assert!(scheduler.register([1u8; 32]));
+        assert!(!scheduler.register([1u8; 32]));
+        let duties: Vec<_> = (0..20)
+            .filter_map(|slot| scheduler.duty(&set, slot))
+            .collect();
+        assert!(!duties.is_empty());
+        assert!(duties.iter().all(|duty| duty.validator == [1u8; 32]));
+
+        assert!(scheduler.unregister(&[1u8; 32]));
+        assert!((0..20).all(|slot| scheduler.duty(&set, slot).is_none()));
+    }
+}
@@ -6,6 +6,7 @@
 //! - block_validation: Pure block validation logic
 //! - slashing: Double-vote detection
 //! - checkpoints: Weak subjectivity
+//! - duties: Stake-weighted proposer schedule
 //! - fork_choice: LMD-GHOST
 //! - bls_aggregation: Pipelined BLS verification
 //! - pbs: Proposer-Builder Separation (MEV protection)
@@ -16,6 +17,7 @@ pub mod block_validation;
 mod bls_aggregation;
 mod chain;
 mod checkpoints;
+mod duties;
 mod error;
 mod fork_choice;
 mod pbs;
@@ -29,6 +31,7 @@ pub use block_validation::*;
 pub use bls_aggregation::*;
 pub use chain::*;
 pub use checkpoints::*;
+pub use duties::*;
 pub use error::*;
 pub use fork_choice::*;
 pub use pbs::*;
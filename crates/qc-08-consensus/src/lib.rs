@@ -115,8 +115,9 @@ pub use adapters::InMemoryEventBus;
 pub use domain::{
     Block, BlockHeader, BlockValidationConfig, BlockValidationError, BlockValidationParams,
     BlockValidator, ChainHead, ChainState, ConsensusAlgorithm, ConsensusConfig, ConsensusError,
-    ConsensusResult, PBFTProof, PoSProof, SignedTransaction, ValidatedBlock, ValidationProof,
-    ValidationResult, ValidationWarning, ValidatorInfo, ValidatorSet,
+    ConsensusResult, DutyScheduler, PBFTProof, PoSProof, SignedTransaction, SlotDuty,
+    ValidatedBlock, ValidationProof, ValidationResult, ValidationWarning, ValidatorInfo,
+    ValidatorSet,
 };
 pub use ipc::IpcHandler;
 pub use ports::{ConsensusApi, EventBus, MempoolGateway, SignatureVerifier, ValidatorSetProvider};
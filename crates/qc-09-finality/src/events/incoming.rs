@@ -4,6 +4,7 @@
 
 use crate::domain::Attestation;
 use serde::{Deserialize, Serialize};
+use shared_types::ipc_matrix::{self, IpcMessage, MatrixEntry};
 
 /// Attestations from Consensus for finality processing
 ///
@@ -37,6 +38,10 @@ impl AttestationBatch {
     }
 }
 
+impl IpcMessage for AttestationBatch {
+    const AUTHORIZATION: MatrixEntry = ipc_matrix::ATTESTATION_BATCH;
+}
+
 /// Request to check if a block is finalized
 ///
 /// SECURITY: Envelope sender_id MUST be 8 (Consensus)
@@ -46,6 +51,10 @@ pub struct FinalityCheckRequest {
     pub block_height: u64,
 }
 
+impl IpcMessage for FinalityCheckRequest {
+    const AUTHORIZATION: MatrixEntry = ipc_matrix::FINALITY_CHECK_REQUEST;
+}
+
 /// Request for finality proof
 ///
 /// SECURITY: Envelope sender_id MUST be 15 (Cross-Chain)
@@ -54,3 +63,7 @@ pub struct FinalityProofRequest {
     pub block_hash: [u8; 32],
     pub block_height: u64,
 }
+
+impl IpcMessage for FinalityProofRequest {
+    const AUTHORIZATION: MatrixEntry = ipc_matrix::FINALITY_PROOF_REQUEST;
+}
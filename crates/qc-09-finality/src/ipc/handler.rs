@@ -6,18 +6,15 @@ use crate::error::{FinalityError, FinalityResult, SubsystemId};
 use crate::events::incoming::{AttestationBatch, FinalityCheckRequest, FinalityProofRequest};
 use crate::ports::inbound::FinalityApi;
 use shared_types::envelope::AuthenticatedMessage;
+use shared_types::ipc_matrix::{check_authorized, IpcMessage};
 use shared_types::security::{validate_hmac_signature, validate_timestamp, NonceCache};
 use std::sync::Arc;
 
-/// Authorized senders per IPC-MATRIX.md
-const CONSENSUS_SUBSYSTEM: SubsystemId = 8;
-const CROSS_CHAIN_SUBSYSTEM: SubsystemId = 15;
-
 /// IPC Handler for Finality subsystem
 ///
 /// Reference: IPC-MATRIX.md Subsystem 9 Security Boundaries
 ///
-/// Authorized senders:
+/// Authorized senders (enforced via `shared_types::ipc_matrix`):
 /// - AttestationBatch: Consensus (8) ONLY
 /// - FinalityCheckRequest: Consensus (8) ONLY
 /// - FinalityProofRequest: Cross-Chain (15) ONLY
@@ -71,6 +68,12 @@ where
         Ok(())
     }
 
+    /// Verify the sender against the IPC authorization matrix
+    fn authorize<M: IpcMessage>(sender_id: SubsystemId) -> FinalityResult<()> {
+        check_authorized::<M>(sender_id)
+            .map_err(|_| FinalityError::UnauthorizedSender { sender_id })
+    }
+
     /// Handle attestation batch from Consensus
     ///
     /// SECURITY: Sender MUST be Consensus (8)
@@ -84,11 +87,7 @@ where
         self.verify_message(&message, message_bytes)?;
 
         // 2. Verify sender is Consensus
-        if message.sender_id != CONSENSUS_SUBSYSTEM {
-            return Err(FinalityError::UnauthorizedSender {
-                sender_id: message.sender_id,
-            });
-        }
+        Self::authorize::<AttestationBatch>(message.sender_id)?;
 
         // 3. Process attestations
         let batch = message.payload;
@@ -112,11 +111,7 @@ where
         self.verify_message(&message, message_bytes)?;
 
         // 2. Verify sender is Consensus
-        if message.sender_id != CONSENSUS_SUBSYSTEM {
-            return Err(FinalityError::UnauthorizedSender {
-                sender_id: message.sender_id,
-            });
-        }
+        Self::authorize::<FinalityCheckRequest>(message.sender_id)?;
 
         // 3. Check finality
         Ok(self
@@ -137,11 +132,7 @@ where
         self.verify_message(&message, message_bytes)?;
 
         // 2. Verify sender is Cross-Chain
-        if message.sender_id != CROSS_CHAIN_SUBSYSTEM {
-            return Err(FinalityError::UnauthorizedSender {
-                sender_id: message.sender_id,
-            });
-        }
+        Self::authorize::<FinalityProofRequest>(message.sender_id)?;
 
         // 3. Get finality proof
         let is_finalized = self
@@ -168,6 +159,9 @@ mod tests {
     use std::time::{SystemTime, UNIX_EPOCH};
     use uuid::Uuid;
 
+    const CONSENSUS_SUBSYSTEM: SubsystemId = 8;
+    const CROSS_CHAIN_SUBSYSTEM: SubsystemId = 15;
+
     // Mock FinalityApi for testing
     struct MockFinalityApi;
 
@@ -363,6 +357,54 @@ mod tests {
         ));
     }
 
+    /// Handler enforcement must match the shared IPC matrix for every sender
+    #[tokio::test]
+    async fn test_enforcement_matches_ipc_matrix() {
+        let handler = create_test_handler();
+        let request = FinalityCheckRequest {
+            block_hash: [0u8; 32],
+            block_height: 100,
+        };
+
+        for sender in 0..=20u8 {
+            let (message, bytes) = create_authenticated_message(
+                AttestationBatch::new(vec![], 1, 32),
+                sender,
+                &[1u8; 32],
+            );
+            let rejected = matches!(
+                handler.handle_attestation_batch(message, &bytes).await,
+                Err(FinalityError::UnauthorizedSender { .. })
+            );
+            assert_eq!(rejected, !AttestationBatch::AUTHORIZATION.permits(sender));
+
+            let (message, bytes) =
+                create_authenticated_message(request.clone(), sender, &[1u8; 32]);
+            let rejected = matches!(
+                handler.handle_finality_check(message, &bytes).await,
+                Err(FinalityError::UnauthorizedSender { .. })
+            );
+            assert_eq!(
+                rejected,
+                !FinalityCheckRequest::AUTHORIZATION.permits(sender)
+            );
+
+            let proof_request = FinalityProofRequest {
+                block_hash: [0u8; 32],
+                block_height: 100,
+            };
+            let (message, bytes) = create_authenticated_message(proof_request, sender, &[1u8; 32]);
+            let rejected = matches!(
+                handler.handle_finality_proof_request(message, &bytes).await,
+                Err(FinalityError::UnauthorizedSender { .. })
+            );
+            assert_eq!(
+                rejected,
+                !FinalityProofRequest::AUTHORIZATION.permits(sender)
+            );
+        }
+    }
+
     // =========================================================================
     // COMPREHENSIVE UNAUTHORIZED SENDER TESTS (IPC-MATRIX.md Compliance)
     // =========================================================================
@@ -29,31 +29,27 @@ use crate::types::{FinalityConfig, OffenseContext, SlashableOffense, SlashableOf
 
 /// Aggregate BLS signatures from multiple attestations
 ///
-/// In a production implementation, this would use proper BLS signature
-/// aggregation (e.g., via blst or bls12_381 crate). For now, we concatenate
-/// the signature bytes as a placeholder that preserves all signature data.
-///
-/// SECURITY: The actual cryptographic aggregation should be done by qc-10.
+/// Uses the shared BLS12-381 implementation. Signatures that fail to decode
+/// are skipped; if none decode, an empty signature is returned.
 fn aggregate_bls_signatures(attestations: &[Attestation]) -> BlsSignature {
-    if attestations.is_empty() {
-        return BlsSignature::default();
-    }
-
-    // Collect all signature bytes
-    // In production: Use BLS aggregate signature algorithm
-    // For now: XOR all signatures together (preserves some cryptographic properties)
-    let mut aggregated = vec![0u8; 96]; // BLS signature size
+    let signatures: Vec<shared_crypto::BlsSignature> = attestations
+        .iter()
+        .filter_map(|attestation| {
+            shared_crypto::BlsSignature::from_slice(&attestation.signature.0)
+                .map_err(|_| {
+                    tracing::warn!(
+                        "Skipping undecodable signature from validator {:?}",
+                        attestation.validator_id
+                    )
+                })
+                .ok()
+        })
+        .collect();
 
-    for attestation in attestations {
-        let sig_bytes = &attestation.signature.0;
-        for (i, byte) in sig_bytes.iter().enumerate() {
-            if i < aggregated.len() {
-                aggregated[i] ^= byte;
-            }
-        }
+    match shared_crypto::BlsSignature::aggregate(&signatures) {
+        Ok(aggregate) => BlsSignature::new(aggregate.to_bytes().to_vec()),
+        Err(_) => BlsSignature::default(),
     }
-
-    BlsSignature::new(aggregated)
 }
 
 
@@ -265,6 +265,51 @@ mod tests {
         assert_eq!(agg_pk.bytes.len(), 96);
     }
 
+    /// `qc_compute` batch verification must agree with `verify_bls`
+    #[tokio::test]
+    async fn test_compute_batch_matches_scalar_verify() {
+        use qc_compute::{create_backend, Backend};
+
+        let mut messages = Vec::new();
+        let mut signatures = Vec::new();
+        let mut public_keys = Vec::new();
+        for i in 0..16u8 {
+            let (sk, pk) = generate_keypair();
+            let message = vec![i; 32 + i as usize];
+            signatures.push(sign_message(&sk, &message));
+            messages.push(message);
+            public_keys.push(pk);
+        }
+
+        // Wrong message, wrong key, garbage signature, swapped signatures
+        messages[3] = b"tampered".to_vec();
+        public_keys[5] = generate_keypair().1;
+        signatures[8].bytes = [0xAB; 48];
+        signatures.swap(11, 12);
+
+        let engine = create_backend(Backend::Cpu).unwrap();
+        for batch in [0..4, 0..16, 13..16] {
+            let batch_results = engine
+                .batch_verify_bls(
+                    &messages[batch.clone()],
+                    &signatures[batch.clone()]
+                        .iter()
+                        .map(|s| s.bytes)
+                        .collect::<Vec<_>>(),
+                    &public_keys[batch.clone()]
+                        .iter()
+                        .map(|pk| pk.bytes)
+                        .collect::<Vec<_>>(),
+                )
+                .await
+                .unwrap();
+            let scalar_results: Vec<bool> = batch
+                .map(|i| verify_bls(&messages[i], &signatures[i], &public_keys[i]))
+                .collect();
+            assert_eq!(batch_results, scalar_results);
+        }
+    }
+
     #[test]
     fn test_bls_aggregate_public_keys_single() {
         let (_, pk) = generate_keypair();
@@ -0,0 +1,93 @@
+//! # State Transition Proof Verification
+//!
+//! Checks the validity proof a full node attaches to a stored block
+//! (`zk` feature), so a light client can accept a state root without
+//! re-executing the block.
+//!
+//! The proof shows that the block's account changes conserve balances and
+//! advance nonces by at most one; its statement binds the previous and new
+//! state roots. See `qc_zkp::state_transition` for what is and isn't proven.
+
+use crate::domain::{BlockHeader, Hash, LightClientError};
+use qc_zkp::fri::FriConfig;
+use qc_zkp::{state_transition, StateTransitionProof};
+
+/// Verify a state transition proof for `header` ending in `state_root`.
+///
+/// The proof must be for this block (hash and height) and claim
+/// `state_root`, which the caller takes from a trusted source (e.g. the
+/// stored block agreed on by multi-node consensus).
+pub fn verify_state_transition_proof(
+    header: &BlockHeader,
+    state_root: &Hash,
+    proof: &StateTransitionProof,
+) -> Result<(), LightClientError> {
+    let statement = &proof.statement;
+    if statement.block_hash != header.hash || statement.block_height != header.height {
+        return Err(LightClientError::InvalidStateProof(format!(
+            "proof is for block {} {:02x?}, not {} {:02x?}",
+            statement.block_height,
+            &statement.block_hash[..4],
+            header.height,
+            &header.hash[..4]
+        )));
+    }
+    if statement.state_root != *state_root {
+        return Err(LightClientError::InvalidStateProof(
+            "proof claims a different state root".to_string(),
+        ));
+    }
+
+    state_transition::verify(proof, &FriConfig::default())
+        .map_err(|e| LightClientError::InvalidStateProof(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qc_zkp::state_transition::{AccountChange, StateTransitionStatement};
+
+    fn header() -> BlockHeader {
+        BlockHeader::new([7; 32], [6; 32], 12, 1_700_000_000, [0; 32])
+    }
+
+    fn proof() -> StateTransitionProof {
+        let statement = StateTransitionStatement {
+            block_hash: [7; 32],
+            block_height: 12,
+            previous_state_root: [1; 32],
+            state_root: [2; 32],
+            diff_digest: [3; 32],
+            minted: 50,
+            accounts: 1,
+        };
+        let changes = [AccountChange {
+            balance_before: 0,
+            balance_after: 50,
+            ..Default::default()
+        }];
+        state_transition::prove(statement, &changes, &FriConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_valid_proof_is_accepted() {
+        assert!(verify_state_transition_proof(&header(), &[2; 32], &proof()).is_ok());
+    }
+
+    #[test]
+    fn test_proof_for_other_block_or_root_is_rejected() {
+        let proof = proof();
+        assert!(matches!(
+            verify_state_transition_proof(&header(), &[9; 32], &proof),
+            Err(LightClientError::InvalidStateProof(_))
+        ));
+
+        let mut other = header();
+        other.height = 13;
+        assert!(verify_state_transition_proof(&other, &[2; 32], &proof).is_err());
+
+        let mut tampered = proof;
+        tampered.statement.minted = 51;
+        assert!(verify_state_transition_proof(&header(), &[2; 32], &tampered).is_err());
+    }
+}
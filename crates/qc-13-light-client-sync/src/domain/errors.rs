@@ -32,6 +32,10 @@ pub enum LightClientError {
     #[error("Merkle proof verification failed")]
     InvalidProof,
 
+    /// State transition validity proof rejected.
+    #[error("State transition proof rejected: {0}")]
+    InvalidStateProof(String),
+
     /// Transaction not found in the chain.
     #[error("Transaction not found: {0:?}")]
     TransactionNotFound(Hash),
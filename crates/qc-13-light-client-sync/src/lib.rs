@@ -23,6 +23,7 @@
 //! | Merkle verification | Cryptographic proof of transaction inclusion |
 //! | Checkpoint enforcement | Reject chains missing trusted checkpoints |
 //! | Peer diversity | Random selection from diverse sources |
+//! | State proofs (`zk` feature) | Validity proofs for block state transitions |
 //!
 //! ## Module Structure
 //!
@@ -45,6 +46,8 @@ pub mod domain;
 pub mod ports;
 
 // Re-exports
+#[cfg(feature = "zk")]
+pub use algorithms::verify_state_transition_proof;
 pub use algorithms::{
     append_headers_batch, build_merkle_proof, check_consensus, check_strict_consensus,
     compute_merkle_root, validate_header_batch, verify_merkle_proof,
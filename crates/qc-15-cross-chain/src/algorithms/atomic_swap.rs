@@ -6,7 +6,7 @@
 
 use super::secret::{create_hash_lock, generate_random_secret};
 use crate::domain::{
-    Address, AtomicSwap, ChainId, CrossChainError, SwapState, MIN_TIMELOCK_MARGIN_SECS,
+    Address, AtomicSwap, ChainId, ChainPolicyRegistry, CrossChainError, SwapState,
 };
 
 /// Parameters for creating an atomic swap.
@@ -57,33 +57,37 @@ pub fn create_atomic_swap(params: AtomicSwapParams) -> (AtomicSwap, [u8; 32]) {
 
 /// Validate timelock ordering for swap.
 ///
+/// The required margin is taken from the chain policies of both legs.
 /// Reference: System.md Line 752
 pub fn validate_swap_timelocks(
+    policies: &ChainPolicyRegistry,
+    source_chain: ChainId,
+    target_chain: ChainId,
     source_timelock: u64,
     target_timelock: u64,
 ) -> Result<(), CrossChainError> {
     crate::domain::invariant_timelock_ordering(
         source_timelock,
         target_timelock,
-        MIN_TIMELOCK_MARGIN_SECS,
+        policies.timelock_margin(source_chain, target_chain),
     )
 }
 
 /// Calculate recommended timelocks for swap.
 pub fn calculate_timelocks(
+    policies: &ChainPolicyRegistry,
     source_chain: ChainId,
     target_chain: ChainId,
     current_time: u64,
 ) -> (u64, u64) {
-    // Source chain gets longer timeout
-    let _source_finality = source_chain.required_confirmations() * source_chain.block_time_secs();
-    let target_finality = target_chain.required_confirmations() * target_chain.block_time_secs();
+    let target_finality =
+        policies.required_confirmations(target_chain) * target_chain.block_time_secs();
 
     // Target timeout: enough time for finality + some buffer
     let target_timeout = current_time + target_finality + 6 * 3600; // +6 hours buffer
 
-    // Source timeout: target timeout + margin
-    let source_timeout = target_timeout + MIN_TIMELOCK_MARGIN_SECS;
+    // Source timeout: target timeout + margin (one extra second, the margin is exclusive)
+    let source_timeout = target_timeout + policies.timelock_margin(source_chain, target_chain) + 1;
 
     (source_timeout, target_timeout)
 }
@@ -123,28 +127,73 @@ mod tests {
         assert_eq!(swap.hash_lock, computed_hash);
     }
 
+    fn validate(source_timelock: u64, target_timelock: u64) -> Result<(), CrossChainError> {
+        validate_swap_timelocks(
+            &ChainPolicyRegistry::new(),
+            ChainId::QuantumChain,
+            ChainId::Ethereum,
+            source_timelock,
+            target_timelock,
+        )
+    }
+
     #[test]
     fn test_validate_swap_timelocks_valid() {
         // Source: 50000, Target: 20000
-        // 50000 > 20000 + 21600 = 41600? Yes!
-        assert!(validate_swap_timelocks(50000, 20000).is_ok());
+        // 50000 > 20000 + 21600 + 600 (skew) = 42200? Yes!
+        assert!(validate(50000, 20000).is_ok());
     }
 
     #[test]
     fn test_validate_swap_timelocks_invalid() {
         // Source: 30000, Target: 20000
-        // 30000 > 20000 + 21600 = 41600? No!
-        assert!(validate_swap_timelocks(30000, 20000).is_err());
+        // 30000 > 20000 + 21600 + 600 (skew) = 42200? No!
+        assert!(validate(30000, 20000).is_err());
+    }
+
+    #[test]
+    fn test_validate_swap_timelocks_respects_policy() {
+        use crate::domain::ChainPolicy;
+
+        let policies = ChainPolicyRegistry::new().with_policy(
+            ChainId::Bitcoin,
+            ChainPolicy {
+                min_timelock_margin_secs: 24 * 3600,
+                ..ChainPolicy::for_chain(ChainId::Bitcoin)
+            },
+        );
+        // Fine under default policy, too tight once Bitcoin requires 24 hours.
+        assert!(validate(50000, 20000).is_ok());
+        assert!(validate_swap_timelocks(
+            &policies,
+            ChainId::Bitcoin,
+            ChainId::Ethereum,
+            50000,
+            20000
+        )
+        .is_err());
     }
 
     #[test]
     fn test_calculate_timelocks() {
+        let policies = ChainPolicyRegistry::new();
         let current_time = 1000;
-        let (source, target) =
-            calculate_timelocks(ChainId::QuantumChain, ChainId::Ethereum, current_time);
-
-        // Source should be >= target + margin
-        assert!(source >= target + MIN_TIMELOCK_MARGIN_SECS);
+        let (source, target) = calculate_timelocks(
+            &policies,
+            ChainId::QuantumChain,
+            ChainId::Ethereum,
+            current_time,
+        );
+
+        // Calculated timelocks must pass validation under the same policies
+        assert!(validate_swap_timelocks(
+            &policies,
+            ChainId::QuantumChain,
+            ChainId::Ethereum,
+            source,
+            target
+        )
+        .is_ok());
     }
 
     #[test]
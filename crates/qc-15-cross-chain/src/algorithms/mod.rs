@@ -5,12 +5,14 @@
 //! Reference: System.md Lines 736-739
 
 pub mod atomic_swap;
+pub mod quote;
 pub mod secret;
 
 pub use atomic_swap::{
     calculate_timelocks, create_atomic_swap, is_swap_complete, is_swap_refunded,
     validate_swap_timelocks, AtomicSwapParams,
 };
+pub use quote::{estimate_completion_secs, quote_swap, NetworkFees, QuoteParams, SwapQuote};
 pub use secret::{
     create_hash_lock, generate_random_secret, verify_claim, verify_refund, verify_secret,
 };
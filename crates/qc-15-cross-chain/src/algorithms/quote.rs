@@ -0,0 +1,181 @@
+//! # Swap Quoting
+//!
+//! Expected fees, timelocks, and completion time for a swap.
+
+use super::atomic_swap::calculate_timelocks;
+use crate::domain::{
+    Address, ChainId, ChainPolicyRegistry, CrossChainError, MakerRegistry, SwapPair,
+};
+use serde::{Deserialize, Serialize};
+
+/// Network fees for HTLC operations on one chain, in native units.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkFees {
+    /// Fee to lock an HTLC.
+    pub lock_fee: u64,
+    /// Fee to claim an HTLC.
+    pub claim_fee: u64,
+}
+
+/// Parameters for quoting a swap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuoteParams {
+    /// Pair to swap.
+    pub pair: SwapPair,
+    /// Amount paid on the source chain.
+    pub source_amount: u64,
+    /// Network fee estimate on the source chain.
+    pub source_fees: NetworkFees,
+    /// Network fee estimate on the target chain.
+    pub target_fees: NetworkFees,
+    /// Current timestamp.
+    pub current_time: u64,
+}
+
+/// Quote returned to a prospective initiator.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapQuote {
+    /// Quoted pair.
+    pub pair: SwapPair,
+    /// Maker that would fill the swap.
+    pub maker: Address,
+    /// Amount paid on the source chain.
+    pub source_amount: u64,
+    /// Amount received on the target chain, before target network fees.
+    pub target_amount: u64,
+    /// Maker fee, in source units.
+    pub maker_fee: u64,
+    /// Initiator's network fees on the source chain (lock).
+    pub source_network_fee: u64,
+    /// Initiator's network fees on the target chain (claim).
+    pub target_network_fee: u64,
+    /// Recommended source HTLC timelock.
+    pub source_timelock: u64,
+    /// Recommended target HTLC timelock.
+    pub target_timelock: u64,
+    /// Estimated seconds until both legs are claimed and final.
+    pub estimated_completion_secs: u64,
+}
+
+/// Quote a swap against the best maker offer.
+pub fn quote_swap(
+    policies: &ChainPolicyRegistry,
+    makers: &MakerRegistry,
+    params: QuoteParams,
+) -> Result<SwapQuote, CrossChainError> {
+    let pair = params.pair;
+    let offer = makers.best_offer(pair, params.source_amount, params.current_time)?;
+
+    let (source_timelock, target_timelock) = calculate_timelocks(
+        policies,
+        pair.source_chain,
+        pair.target_chain,
+        params.current_time,
+    );
+
+    Ok(SwapQuote {
+        pair,
+        maker: offer.maker,
+        source_amount: params.source_amount,
+        target_amount: offer.target_amount_for(params.source_amount),
+        maker_fee: offer.fee_for(params.source_amount),
+        source_network_fee: params.source_fees.lock_fee,
+        target_network_fee: params.target_fees.claim_fee,
+        source_timelock,
+        target_timelock,
+        estimated_completion_secs: estimate_completion_secs(policies, pair),
+    })
+}
+
+/// Estimate time for a swap to complete.
+///
+/// Four sequential steps each wait for finality: source lock, target lock,
+/// target claim (reveals the secret), and source claim.
+pub fn estimate_completion_secs(policies: &ChainPolicyRegistry, pair: SwapPair) -> u64 {
+    let source = finality_secs(policies, pair.source_chain);
+    let target = finality_secs(policies, pair.target_chain);
+    2 * (source + target)
+}
+
+fn finality_secs(policies: &ChainPolicyRegistry, chain: ChainId) -> u64 {
+    policies.required_confirmations(chain) * chain.block_time_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::validate_swap_timelocks;
+    use crate::domain::{MakerOffer, RATE_DENOMINATOR};
+
+    fn makers() -> MakerRegistry {
+        let mut makers = MakerRegistry::new();
+        makers
+            .advertise(
+                MakerOffer {
+                    maker: [7; 20],
+                    pair: SwapPair::new(ChainId::QuantumChain, ChainId::Ethereum),
+                    rate_ppm: RATE_DENOMINATOR / 2,
+                    fee_bps: 100,
+                    min_amount: 1_000,
+                    max_amount: 1_000_000,
+                    available_liquidity: 500_000,
+                    expires_at: 100_000,
+                },
+                0,
+            )
+            .unwrap();
+        makers
+    }
+
+    fn params(source_amount: u64) -> QuoteParams {
+        QuoteParams {
+            pair: SwapPair::new(ChainId::QuantumChain, ChainId::Ethereum),
+            source_amount,
+            source_fees: NetworkFees {
+                lock_fee: 5,
+                claim_fee: 3,
+            },
+            target_fees: NetworkFees {
+                lock_fee: 50,
+                claim_fee: 30,
+            },
+            current_time: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_quote_swap() {
+        let policies = ChainPolicyRegistry::new();
+        let quote = quote_swap(&policies, &makers(), params(10_000)).unwrap();
+
+        assert_eq!(quote.maker, [7; 20]);
+        assert_eq!(quote.maker_fee, 100);
+        assert_eq!(quote.target_amount, 4_950);
+        assert_eq!(quote.source_network_fee, 5);
+        assert_eq!(quote.target_network_fee, 30);
+        assert!(validate_swap_timelocks(
+            &policies,
+            ChainId::QuantumChain,
+            ChainId::Ethereum,
+            quote.source_timelock,
+            quote.target_timelock
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_quote_exceeding_liquidity_fails() {
+        let policies = ChainPolicyRegistry::new();
+        // 1_000_000 source units would need ~495_000 target, fine; 1_000_001 is out of range
+        assert!(quote_swap(&policies, &makers(), params(1_000_000)).is_ok());
+        assert!(quote_swap(&policies, &makers(), params(1_000_001)).is_err());
+    }
+
+    #[test]
+    fn test_estimate_completion() {
+        let policies = ChainPolicyRegistry::new();
+        let pair = SwapPair::new(ChainId::QuantumChain, ChainId::Ethereum);
+        // QuantumChain: 6 * 10s, Ethereum: 12 * 12s
+        assert_eq!(estimate_completion_secs(&policies, pair), 2 * (60 + 144));
+    }
+}
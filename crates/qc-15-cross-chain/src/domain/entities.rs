@@ -5,6 +5,7 @@
 //! Reference: SPEC-15 Section 2.1 (Lines 67-174)
 
 use super::errors::{Address, CrossChainError, Hash, Secret};
+use super::policy::ChainPolicyRegistry;
 use super::value_objects::{ChainAddress, ChainId, HTLCState, SwapState};
 use serde::{Deserialize, Serialize};
 
@@ -325,6 +326,9 @@ pub struct CrossChainConfig {
     pub default_target_timeout_secs: u64,
     /// Supported chains.
     pub supported_chains: Vec<ChainId>,
+    /// Per-chain confirmation and timelock policies.
+    #[serde(default)]
+    pub chain_policies: ChainPolicyRegistry,
 }
 
 impl Default for CrossChainConfig {
@@ -340,6 +344,7 @@ impl Default for CrossChainConfig {
                 ChainId::Polygon,
                 ChainId::Arbitrum,
             ],
+            chain_policies: ChainPolicyRegistry::default(),
         }
     }
 }
@@ -100,6 +100,39 @@ pub enum CrossChainError {
     /// Already refunded.
     #[error("HTLC already refunded")]
     AlreadyRefunded,
+
+    /// No maker offers liquidity for the requested pair and amount.
+    #[error("No swap liquidity for {source_chain} -> {target_chain}")]
+    NoLiquidity {
+        /// Source chain name
+        source_chain: String,
+        /// Target chain name
+        target_chain: String,
+    },
+
+    /// Swap amount outside the maker's advertised range.
+    #[error("Swap amount {amount} outside offer range [{min}, {max}]")]
+    AmountOutOfRange {
+        /// Requested amount
+        amount: u64,
+        /// Offer minimum
+        min: u64,
+        /// Offer maximum
+        max: u64,
+    },
+
+    /// Offers accept the amount, but none holds enough target liquidity.
+    #[error("Insufficient swap liquidity: need {requested}, largest offer holds {available}")]
+    InsufficientLiquidity {
+        /// Target amount the swap would pay out
+        requested: u64,
+        /// Largest liquidity among offers accepting the amount
+        available: u64,
+    },
+
+    /// Maker offer is malformed or already expired.
+    #[error("Invalid maker offer: {0}")]
+    InvalidOffer(String),
 }
 
 #[cfg(test)]
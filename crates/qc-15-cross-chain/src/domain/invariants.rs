@@ -6,6 +6,8 @@
 
 use super::entities::HTLC;
 use super::errors::{CrossChainError, Hash};
+use super::policy::ChainPolicyRegistry;
+use super::value_objects::ChainId;
 
 /// Minimum timelock margin (6 hours).
 /// Reference: System.md Line 752
@@ -61,10 +63,14 @@ pub fn invariant_authorized_claimer(htlc: &HTLC, claimer: &[u8; 20]) -> bool {
 
 /// Invariant: Sufficient confirmations for finality.
 /// Reference: SPEC-15 Lines 650-654
+///
+/// The required depth comes from the chain's policy in `policies`.
 pub fn invariant_sufficient_confirmations(
+    policies: &ChainPolicyRegistry,
+    chain: ChainId,
     confirmations: u64,
-    required: u64,
 ) -> Result<(), CrossChainError> {
+    let required = policies.required_confirmations(chain);
     if confirmations < required {
         return Err(CrossChainError::NotFinalized {
             got: confirmations,
@@ -133,12 +139,28 @@ mod tests {
 
     #[test]
     fn test_sufficient_confirmations_pass() {
-        assert!(invariant_sufficient_confirmations(6, 6).is_ok());
-        assert!(invariant_sufficient_confirmations(12, 6).is_ok());
+        let policies = ChainPolicyRegistry::new();
+        assert!(invariant_sufficient_confirmations(&policies, ChainId::Bitcoin, 6).is_ok());
+        assert!(invariant_sufficient_confirmations(&policies, ChainId::Bitcoin, 12).is_ok());
     }
 
     #[test]
     fn test_insufficient_confirmations_fail() {
-        assert!(invariant_sufficient_confirmations(3, 6).is_err());
+        let policies = ChainPolicyRegistry::new();
+        assert!(invariant_sufficient_confirmations(&policies, ChainId::Bitcoin, 3).is_err());
+    }
+
+    #[test]
+    fn test_confirmations_follow_policy_override() {
+        use crate::domain::ChainPolicy;
+
+        let policies = ChainPolicyRegistry::new().with_policy(
+            ChainId::Bitcoin,
+            ChainPolicy {
+                confirmation_depth: 2,
+                ..ChainPolicy::for_chain(ChainId::Bitcoin)
+            },
+        );
+        assert!(invariant_sufficient_confirmations(&policies, ChainId::Bitcoin, 3).is_ok());
     }
 }
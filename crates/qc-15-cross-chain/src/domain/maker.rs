@@ -0,0 +1,326 @@
+//! # Maker Registry
+//!
+//! Swap liquidity advertised by makers on this node.
+//!
+//! Counterparties discover offers through qc-16 and quote against them.
+
+use super::errors::{Address, CrossChainError};
+use super::value_objects::ChainId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Basis points denominator (100% = 10 000 bps).
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Exchange rate denominator (1.0 = 1 000 000 ppm).
+pub const RATE_DENOMINATOR: u64 = 1_000_000;
+
+/// Directed swap pair.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SwapPair {
+    /// Chain the initiator pays on.
+    pub source_chain: ChainId,
+    /// Chain the initiator receives on.
+    pub target_chain: ChainId,
+}
+
+impl SwapPair {
+    /// Create a new swap pair.
+    pub fn new(source_chain: ChainId, target_chain: ChainId) -> Self {
+        Self {
+            source_chain,
+            target_chain,
+        }
+    }
+}
+
+/// Liquidity advertised by a maker for one pair.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MakerOffer {
+    /// Maker address (receives source funds).
+    pub maker: Address,
+    /// Pair this offer covers.
+    pub pair: SwapPair,
+    /// Target units paid per source unit, in parts per million.
+    pub rate_ppm: u64,
+    /// Maker fee taken from the source amount, in basis points.
+    pub fee_bps: u64,
+    /// Smallest source amount accepted.
+    pub min_amount: u64,
+    /// Largest source amount accepted.
+    pub max_amount: u64,
+    /// Target-chain liquidity still available.
+    pub available_liquidity: u64,
+    /// Unix timestamp after which the offer is withdrawn.
+    pub expires_at: u64,
+}
+
+impl MakerOffer {
+    /// Check if the offer has expired.
+    pub fn is_expired(&self, current_time: u64) -> bool {
+        current_time >= self.expires_at
+    }
+
+    /// Check if the offer accepts `amount` on the source chain.
+    pub fn accepts(&self, amount: u64) -> bool {
+        amount >= self.min_amount && amount <= self.max_amount
+    }
+
+    /// Maker fee charged on `amount`.
+    pub fn fee_for(&self, amount: u64) -> u64 {
+        mul_div(amount, self.fee_bps, BPS_DENOMINATOR)
+    }
+
+    /// Target amount paid out for `amount` after the maker fee.
+    pub fn target_amount_for(&self, amount: u64) -> u64 {
+        mul_div(
+            amount.saturating_sub(self.fee_for(amount)),
+            self.rate_ppm,
+            RATE_DENOMINATOR,
+        )
+    }
+
+    fn validate(&self, current_time: u64) -> Result<(), CrossChainError> {
+        if self.pair.source_chain == self.pair.target_chain {
+            return Err(CrossChainError::InvalidOffer("source equals target".into()));
+        }
+        if self.rate_ppm == 0 || self.available_liquidity == 0 {
+            return Err(CrossChainError::InvalidOffer("no liquidity".into()));
+        }
+        if self.fee_bps >= BPS_DENOMINATOR {
+            return Err(CrossChainError::InvalidOffer("fee exceeds 100%".into()));
+        }
+        if self.min_amount > self.max_amount {
+            return Err(CrossChainError::InvalidOffer("min above max".into()));
+        }
+        if self.is_expired(current_time) {
+            return Err(CrossChainError::InvalidOffer("already expired".into()));
+        }
+        Ok(())
+    }
+}
+
+/// Registry of maker offers, one per (maker, pair).
+#[derive(Clone, Debug, Default)]
+pub struct MakerRegistry {
+    offers: HashMap<(Address, SwapPair), MakerOffer>,
+}
+
+impl MakerRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advertise or replace a maker's offer for a pair.
+    pub fn advertise(
+        &mut self,
+        offer: MakerOffer,
+        current_time: u64,
+    ) -> Result<(), CrossChainError> {
+        offer.validate(current_time)?;
+        self.offers.insert((offer.maker, offer.pair), offer);
+        Ok(())
+    }
+
+    /// Withdraw a maker's offer. Returns the removed offer, if any.
+    pub fn withdraw(&mut self, maker: &Address, pair: SwapPair) -> Option<MakerOffer> {
+        self.offers.remove(&(*maker, pair))
+    }
+
+    /// Live offers for a pair.
+    pub fn offers_for(&self, pair: SwapPair, current_time: u64) -> Vec<&MakerOffer> {
+        self.offers
+            .values()
+            .filter(|o| o.pair == pair && !o.is_expired(current_time))
+            .collect()
+    }
+
+    /// Offer paying the most on the target chain for `amount`.
+    pub fn best_offer(
+        &self,
+        pair: SwapPair,
+        amount: u64,
+        current_time: u64,
+    ) -> Result<&MakerOffer, CrossChainError> {
+        let live = self.offers_for(pair, current_time);
+        if live.is_empty() {
+            return Err(no_liquidity(pair));
+        }
+
+        let accepting: Vec<_> = live.iter().filter(|o| o.accepts(amount)).collect();
+        if accepting.is_empty() {
+            // Report the widest advertised range so the caller can adjust.
+            let min = live.iter().map(|o| o.min_amount).min().unwrap_or(0);
+            let max = live.iter().map(|o| o.max_amount).max().unwrap_or(0);
+            return Err(CrossChainError::AmountOutOfRange { amount, min, max });
+        }
+
+        let best = accepting
+            .iter()
+            .filter(|o| o.target_amount_for(amount) <= o.available_liquidity)
+            .max_by_key(|o| o.target_amount_for(amount));
+
+        match best {
+            Some(offer) => Ok(offer),
+            None => Err(CrossChainError::InsufficientLiquidity {
+                requested: accepting
+                    .iter()
+                    .map(|o| o.target_amount_for(amount))
+                    .min()
+                    .unwrap_or(0),
+                available: accepting
+                    .iter()
+                    .map(|o| o.available_liquidity)
+                    .max()
+                    .unwrap_or(0),
+            }),
+        }
+    }
+
+    /// Reserve target liquidity once a swap against `maker` is locked.
+    pub fn reserve(
+        &mut self,
+        maker: &Address,
+        pair: SwapPair,
+        target_amount: u64,
+    ) -> Result<(), CrossChainError> {
+        let offer = self
+            .offers
+            .get_mut(&(*maker, pair))
+            .ok_or_else(|| no_liquidity(pair))?;
+        offer.available_liquidity = offer
+            .available_liquidity
+            .checked_sub(target_amount)
+            .ok_or_else(|| no_liquidity(pair))?;
+        Ok(())
+    }
+
+    /// Drop expired offers. Returns the number removed.
+    pub fn prune_expired(&mut self, current_time: u64) -> usize {
+        let before = self.offers.len();
+        self.offers.retain(|_, o| !o.is_expired(current_time));
+        before - self.offers.len()
+    }
+
+    /// Number of registered offers.
+    pub fn len(&self) -> usize {
+        self.offers.len()
+    }
+
+    /// Check if no offers are registered.
+    pub fn is_empty(&self) -> bool {
+        self.offers.is_empty()
+    }
+}
+
+fn no_liquidity(pair: SwapPair) -> CrossChainError {
+    CrossChainError::NoLiquidity {
+        source_chain: format!("{:?}", pair.source_chain),
+        target_chain: format!("{:?}", pair.target_chain),
+    }
+}
+
+fn mul_div(value: u64, mul: u64, div: u64) -> u64 {
+    ((value as u128 * mul as u128) / div as u128).min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair() -> SwapPair {
+        SwapPair::new(ChainId::QuantumChain, ChainId::Ethereum)
+    }
+
+    fn offer(maker: u8, rate_ppm: u64) -> MakerOffer {
+        MakerOffer {
+            maker: [maker; 20],
+            pair: pair(),
+            rate_ppm,
+            fee_bps: 30,
+            min_amount: 100,
+            max_amount: 1_000_000,
+            available_liquidity: 1_000_000,
+            expires_at: 10_000,
+        }
+    }
+
+    #[test]
+    fn test_target_amount_after_fee() {
+        let offer = offer(1, 2 * RATE_DENOMINATOR);
+        // 10_000 - 0.3% fee = 9_970, at rate 2.0 = 19_940
+        assert_eq!(offer.fee_for(10_000), 30);
+        assert_eq!(offer.target_amount_for(10_000), 19_940);
+    }
+
+    #[test]
+    fn test_advertise_rejects_expired_offer() {
+        let mut registry = MakerRegistry::new();
+        assert!(registry
+            .advertise(offer(1, RATE_DENOMINATOR), 20_000)
+            .is_err());
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_best_offer_picks_highest_payout() {
+        let mut registry = MakerRegistry::new();
+        registry.advertise(offer(1, RATE_DENOMINATOR), 0).unwrap();
+        registry
+            .advertise(offer(2, 2 * RATE_DENOMINATOR), 0)
+            .unwrap();
+
+        let best = registry.best_offer(pair(), 1_000, 0).unwrap();
+        assert_eq!(best.maker, [2; 20]);
+    }
+
+    #[test]
+    fn test_best_offer_out_of_range() {
+        let mut registry = MakerRegistry::new();
+        registry.advertise(offer(1, RATE_DENOMINATOR), 0).unwrap();
+
+        let result = registry.best_offer(pair(), 10, 0);
+        assert!(matches!(
+            result,
+            Err(CrossChainError::AmountOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_best_offer_insufficient_liquidity() {
+        let mut registry = MakerRegistry::new();
+        let mut thin = offer(1, RATE_DENOMINATOR);
+        thin.available_liquidity = 500;
+        registry.advertise(thin, 0).unwrap();
+
+        // 1_000 is within range but pays out 997 against 500 of liquidity.
+        let result = registry.best_offer(pair(), 1_000, 0);
+        assert!(matches!(
+            result,
+            Err(CrossChainError::InsufficientLiquidity {
+                requested: 997,
+                available: 500
+            })
+        ));
+    }
+
+    #[test]
+    fn test_best_offer_no_liquidity_for_pair() {
+        let registry = MakerRegistry::new();
+        let result = registry.best_offer(pair(), 1_000, 0);
+        assert!(matches!(result, Err(CrossChainError::NoLiquidity { .. })));
+    }
+
+    #[test]
+    fn test_reserve_and_prune() {
+        let mut registry = MakerRegistry::new();
+        registry.advertise(offer(1, RATE_DENOMINATOR), 0).unwrap();
+
+        registry.reserve(&[1; 20], pair(), 400_000).unwrap();
+        assert!(registry.reserve(&[1; 20], pair(), 700_000).is_err());
+
+        assert_eq!(registry.prune_expired(10_000), 1);
+        assert!(registry.is_empty());
+    }
+}
@@ -7,11 +7,15 @@
 pub mod entities;
 pub mod errors;
 pub mod invariants;
+pub mod maker;
+pub mod policy;
 pub mod secure_secret;
 pub mod value_objects;
 
 pub use entities::*;
 pub use errors::*;
 pub use invariants::*;
+pub use maker::*;
+pub use policy::*;
 pub use secure_secret::SecureSecret;
 pub use value_objects::*;
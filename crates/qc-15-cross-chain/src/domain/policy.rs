@@ -0,0 +1,160 @@
+//! # Chain Policy Registry
+//!
+//! Per-chain confirmation and timelock policy.
+//!
+//! Reference: SPEC-15 Lines 650-654, System.md Line 752
+
+use super::invariants::MIN_TIMELOCK_MARGIN_SECS;
+use super::value_objects::ChainId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default allowance for clock drift between chains (5 minutes).
+pub const DEFAULT_CLOCK_SKEW_MARGIN_SECS: u64 = 5 * 60;
+
+/// Finality and timelock policy for a single chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainPolicy {
+    /// Confirmations required before a proof is considered final.
+    pub confirmation_depth: u64,
+    /// Deepest reorg tolerated on this chain, in blocks.
+    ///
+    /// A proof with this many confirmations or fewer can still be reorged out.
+    pub reorg_tolerance: u64,
+    /// Allowance for clock drift between this chain and ours, in seconds.
+    pub clock_skew_margin_secs: u64,
+    /// Minimum gap between source and target timelocks, in seconds.
+    pub min_timelock_margin_secs: u64,
+}
+
+impl ChainPolicy {
+    /// Built-in policy for a chain.
+    pub fn for_chain(chain: ChainId) -> Self {
+        Self {
+            confirmation_depth: chain.required_confirmations(),
+            reorg_tolerance: 0,
+            clock_skew_margin_secs: DEFAULT_CLOCK_SKEW_MARGIN_SECS,
+            min_timelock_margin_secs: MIN_TIMELOCK_MARGIN_SECS,
+        }
+    }
+
+    /// Confirmations required once reorg tolerance is taken into account.
+    pub fn required_confirmations(&self) -> u64 {
+        self.confirmation_depth
+            .max(self.reorg_tolerance.saturating_add(1))
+    }
+}
+
+/// Registry mapping each chain to its policy.
+///
+/// Chains without an explicit entry fall back to [`ChainPolicy::for_chain`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainPolicyRegistry {
+    /// Explicit per-chain overrides.
+    #[serde(default)]
+    policies: HashMap<ChainId, ChainPolicy>,
+}
+
+impl ChainPolicyRegistry {
+    /// Create an empty registry (built-in policies only).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the policy for a chain.
+    pub fn with_policy(mut self, chain: ChainId, policy: ChainPolicy) -> Self {
+        self.policies.insert(chain, policy);
+        self
+    }
+
+    /// Replace the policy for a chain at runtime.
+    pub fn set_policy(&mut self, chain: ChainId, policy: ChainPolicy) {
+        self.policies.insert(chain, policy);
+    }
+
+    /// Effective policy for a chain.
+    pub fn policy(&self, chain: ChainId) -> ChainPolicy {
+        self.policies
+            .get(&chain)
+            .copied()
+            .unwrap_or_else(|| ChainPolicy::for_chain(chain))
+    }
+
+    /// Confirmations required for a proof on `chain` to be final.
+    pub fn required_confirmations(&self, chain: ChainId) -> u64 {
+        self.policy(chain).required_confirmations()
+    }
+
+    /// Required timelock margin between a swap's source and target HTLCs.
+    ///
+    /// Takes the stricter of the two chains' minimum margins and adds both
+    /// chains' clock-skew allowances.
+    pub fn timelock_margin(&self, source: ChainId, target: ChainId) -> u64 {
+        let source = self.policy(source);
+        let target = self.policy(target);
+        source
+            .min_timelock_margin_secs
+            .max(target.min_timelock_margin_secs)
+            .saturating_add(source.clock_skew_margin_secs)
+            .saturating_add(target.clock_skew_margin_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_matches_chain_defaults() {
+        let registry = ChainPolicyRegistry::new();
+        assert_eq!(registry.required_confirmations(ChainId::Ethereum), 12);
+        assert_eq!(registry.required_confirmations(ChainId::Arbitrum), 1);
+    }
+
+    #[test]
+    fn test_override_confirmation_depth() {
+        let registry = ChainPolicyRegistry::new().with_policy(
+            ChainId::Bitcoin,
+            ChainPolicy {
+                confirmation_depth: 3,
+                ..ChainPolicy::for_chain(ChainId::Bitcoin)
+            },
+        );
+        assert_eq!(registry.required_confirmations(ChainId::Bitcoin), 3);
+    }
+
+    #[test]
+    fn test_reorg_tolerance_raises_required_confirmations() {
+        let policy = ChainPolicy {
+            confirmation_depth: 6,
+            reorg_tolerance: 10,
+            ..ChainPolicy::for_chain(ChainId::Bitcoin)
+        };
+        assert_eq!(policy.required_confirmations(), 11);
+    }
+
+    #[test]
+    fn test_timelock_margin_includes_skew() {
+        let registry = ChainPolicyRegistry::new();
+        assert_eq!(
+            registry.timelock_margin(ChainId::QuantumChain, ChainId::Ethereum),
+            MIN_TIMELOCK_MARGIN_SECS + 2 * DEFAULT_CLOCK_SKEW_MARGIN_SECS
+        );
+    }
+
+    #[test]
+    fn test_timelock_margin_uses_stricter_chain() {
+        let registry = ChainPolicyRegistry::new().with_policy(
+            ChainId::Bitcoin,
+            ChainPolicy {
+                clock_skew_margin_secs: 0,
+                min_timelock_margin_secs: 12 * 3600,
+                ..ChainPolicy::for_chain(ChainId::Bitcoin)
+            },
+        );
+        assert_eq!(
+            registry.timelock_margin(ChainId::Bitcoin, ChainId::Ethereum),
+            12 * 3600 + DEFAULT_CLOCK_SKEW_MARGIN_SECS
+        );
+    }
+}
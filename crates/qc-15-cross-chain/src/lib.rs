@@ -13,6 +13,7 @@
 //! - Hash Time-Locked Contracts (HTLC) for trustless swaps
 //! - SHA-256 hashlocks for cryptographic security
 //! - Timelock ordering for atomicity guarantees
+//! - Swap quoting against maker liquidity advertised through qc-16
 //!
 //! ## Security Features (System.md Lines 751-756)
 //!
@@ -20,7 +21,7 @@
 //! |---------|-------------|
 //! | Timelock margins | Source > Target + 6 hours |
 //! | SHA-256 only | No weak hash functions |
-//! | Finality checks | Chain-specific confirmations |
+//! | Finality checks | Chain-specific confirmations (`ChainPolicyRegistry`) |
 //! | Secret atomicity | Reveal on one chain = claimable on both |
 //!
 //! ## Module Structure
@@ -28,7 +29,7 @@
 //! ```text
 //! qc-15-cross-chain/
 //! ├── domain/          # HTLC, AtomicSwap, ChainId, errors
-//! ├── algorithms/      # Secret generation, swap logic
+//! ├── algorithms/      # Secret generation, swap logic, quoting
 //! └── ports/           # CrossChainApi, ExternalChainClient
 //! ```
 
@@ -41,15 +42,17 @@ pub mod ports;
 
 // Re-exports
 pub use algorithms::{
-    calculate_timelocks, create_atomic_swap, create_hash_lock, generate_random_secret,
-    is_swap_complete, is_swap_refunded, validate_swap_timelocks, verify_claim, verify_refund,
-    verify_secret, AtomicSwapParams,
+    calculate_timelocks, create_atomic_swap, create_hash_lock, estimate_completion_secs,
+    generate_random_secret, is_swap_complete, is_swap_refunded, quote_swap,
+    validate_swap_timelocks, verify_claim, verify_refund, verify_secret, AtomicSwapParams,
+    NetworkFees, QuoteParams, SwapQuote,
 };
 pub use domain::{
     invariant_authorized_claimer, invariant_hashlock_match, invariant_secret_matches,
     invariant_sufficient_confirmations, invariant_timelock_ordering, Address, AtomicSwap,
-    ChainAddress, ChainId, CrossChainConfig, CrossChainError, CrossChainProof, HTLCParams,
-    HTLCState, Hash, Secret, SwapState, HTLC, MIN_TIMELOCK_MARGIN_SECS,
+    ChainAddress, ChainId, ChainPolicy, ChainPolicyRegistry, CrossChainConfig, CrossChainError,
+    CrossChainProof, HTLCParams, HTLCState, Hash, MakerOffer, MakerRegistry, Secret, SwapPair,
+    SwapState, HTLC, MIN_TIMELOCK_MARGIN_SECS,
 };
 pub use ports::{
     BlockHeader, CrossChainApi, ExternalChainClient, FinalityChecker, HTLCContract,
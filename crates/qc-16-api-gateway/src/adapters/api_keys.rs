@@ -0,0 +1,417 @@
+//! API key store with per-key scopes, rate limits, and usage accounting.
+//!
+//! Keys live in a JSON file so operators can manage them without restarting
+//! the gateway: the admin API writes the file on create/revoke, and a reload
+//! task picks up edits made by hand. Only SHA3-256 digests of keys are stored;
+//! the plaintext is returned once, when the key is created.
+
+use dashmap::DashMap;
+use governor::{
+    clock::DefaultClock,
+    state::{InMemoryState, NotKeyed},
+    Quota, RateLimiter,
+};
+use parking_lot::RwLock;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+/// Prefix of generated keys (makes leaked keys easy to grep for)
+pub const KEY_PREFIX: &str = "qck_";
+
+/// Stored API key (never contains the plaintext key)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyRecord {
+    /// Public key identifier
+    pub id: String,
+    /// Hex SHA3-256 digest of the key
+    pub key_hash: String,
+    /// Operator-facing label
+    #[serde(default)]
+    pub label: String,
+    /// Method patterns this key may call (`eth_*`, `txpool_status`, `*`)
+    pub scopes: Vec<String>,
+    /// Requests per second for this key (None = IP limits only)
+    #[serde(default)]
+    pub rate_limit: Option<u32>,
+    /// Unix timestamp of creation
+    pub created_at: u64,
+    /// Revoked keys are kept for audit but never authenticate
+    #[serde(default)]
+    pub revoked: bool,
+}
+
+impl ApiKeyRecord {
+    /// Check if a method is covered by this key's scopes
+    pub fn allows(&self, method: &str) -> bool {
+        self.scopes.iter().any(|scope| scope_matches(scope, method))
+    }
+}
+
+/// Key file layout
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct KeyFile {
+    keys: Vec<ApiKeyRecord>,
+}
+
+/// Per-key usage counters
+#[derive(Debug, Default)]
+struct KeyUsage {
+    requests: AtomicU64,
+    denied: AtomicU64,
+    rate_limited: AtomicU64,
+    last_used: AtomicU64,
+}
+
+/// Usage snapshot for the admin API
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyUsageSnapshot {
+    pub id: String,
+    pub requests: u64,
+    pub denied: u64,
+    pub rate_limited: u64,
+    /// Unix timestamp of the last authenticated request (0 = never)
+    pub last_used: u64,
+}
+
+/// Key store errors
+#[derive(Debug, thiserror::Error)]
+pub enum ApiKeyError {
+    #[error("api key not found: {0}")]
+    NotFound(String),
+    #[error("api key must have at least one scope")]
+    NoScopes,
+    #[error("failed to read key file: {0}")]
+    Read(String),
+    #[error("failed to write key file: {0}")]
+    Write(String),
+}
+
+type KeyLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+
+/// Store of API keys, indexed by key digest
+pub struct ApiKeyStore {
+    /// Backing file (None = in-memory only)
+    path: Option<PathBuf>,
+    /// Records by key digest
+    keys: RwLock<HashMap<String, ApiKeyRecord>>,
+    /// Usage by key ID (survives reloads)
+    usage: DashMap<String, KeyUsage>,
+    /// Rate limiters by key ID, rebuilt when a key's limit changes
+    limiters: DashMap<String, (u32, KeyLimiter)>,
+    /// Modification time of the file at the last load
+    loaded_mtime: RwLock<Option<SystemTime>>,
+}
+
+impl ApiKeyStore {
+    /// Create an in-memory store
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            keys: RwLock::new(HashMap::new()),
+            usage: DashMap::new(),
+            limiters: DashMap::new(),
+            loaded_mtime: RwLock::new(None),
+        }
+    }
+
+    /// Open a file-backed store. A missing file starts an empty store.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, ApiKeyError> {
+        let store = Self {
+            path: Some(path.into()),
+            ..Self::in_memory()
+        };
+        store.reload()?;
+        Ok(store)
+    }
+
+    /// Re-read the key file, replacing the in-memory key set
+    pub fn reload(&self) -> Result<(), ApiKeyError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let data = std::fs::read(path).map_err(|e| ApiKeyError::Read(e.to_string()))?;
+        let file: KeyFile =
+            serde_json::from_slice(&data).map_err(|e| ApiKeyError::Read(e.to_string()))?;
+
+        let keys = file
+            .keys
+            .into_iter()
+            .map(|record| (record.key_hash.clone(), record))
+            .collect::<HashMap<_, _>>();
+
+        info!(keys = keys.len(), path = %path.display(), "Loaded API keys");
+        *self.keys.write() = keys;
+        *self.loaded_mtime.write() = modified_time(path);
+        Ok(())
+    }
+
+    /// Reload if the key file changed since the last load
+    pub fn reload_if_changed(&self) -> Result<bool, ApiKeyError> {
+        let Some(path) = &self.path else {
+            return Ok(false);
+        };
+        let current = modified_time(path);
+        if current.is_none() || current == *self.loaded_mtime.read() {
+            return Ok(false);
+        }
+        self.reload()?;
+        Ok(true)
+    }
+
+    /// Create a key. Returns the record and the plaintext key (shown once).
+    pub fn create(
+        &self,
+        label: String,
+        scopes: Vec<String>,
+        rate_limit: Option<u32>,
+    ) -> Result<(ApiKeyRecord, String), ApiKeyError> {
+        if scopes.is_empty() {
+            return Err(ApiKeyError::NoScopes);
+        }
+
+        let mut secret = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut secret);
+        let key = format!("{}{}", KEY_PREFIX, hex::encode(secret));
+
+        let key_hash = hash_key(&key);
+        let record = ApiKeyRecord {
+            id: key_hash[..16].to_string(),
+            key_hash: key_hash.clone(),
+            label,
+            scopes,
+            rate_limit,
+            created_at: unix_now(),
+            revoked: false,
+        };
+
+        self.keys.write().insert(key_hash, record.clone());
+        self.persist()?;
+
+        info!(key_id = %record.id, "Created API key");
+        Ok((record, key))
+    }
+
+    /// Revoke a key by ID
+    pub fn revoke(&self, id: &str) -> Result<(), ApiKeyError> {
+        {
+            let mut keys = self.keys.write();
+            let record = keys
+                .values_mut()
+                .find(|r| r.id == id)
+                .ok_or_else(|| ApiKeyError::NotFound(id.to_string()))?;
+            record.revoked = true;
+        }
+        self.limiters.remove(id);
+        self.persist()?;
+
+        info!(key_id = %id, "Revoked API key");
+        Ok(())
+    }
+
+    /// All key records (including revoked)
+    pub fn list(&self) -> Vec<ApiKeyRecord> {
+        let mut records: Vec<_> = self.keys.read().values().cloned().collect();
+        records.sort_by_key(|r| r.created_at);
+        records
+    }
+
+    /// Check if any key can currently authenticate
+    pub fn has_active_keys(&self) -> bool {
+        self.keys.read().values().any(|r| !r.revoked)
+    }
+
+    /// Look up a presented key. Revoked keys never authenticate.
+    pub fn authenticate(&self, key: &str) -> Option<ApiKeyRecord> {
+        let record = self.keys.read().get(&hash_key(key)).cloned()?;
+        if record.revoked {
+            debug!(key_id = %record.id, "Rejected revoked API key");
+            return None;
+        }
+        Some(record)
+    }
+
+    /// Consume one request from the key's rate limit
+    pub fn check_rate_limit(&self, record: &ApiKeyRecord) -> Result<(), Duration> {
+        let Some(rps) = record.rate_limit.and_then(NonZeroU32::new) else {
+            return Ok(());
+        };
+
+        let mut entry = self
+            .limiters
+            .entry(record.id.clone())
+            .or_insert_with(|| (rps.get(), RateLimiter::direct(Quota::per_second(rps))));
+        if entry.0 != rps.get() {
+            *entry = (rps.get(), RateLimiter::direct(Quota::per_second(rps)));
+        }
+
+        let result = entry.1.check().map_err(|not_until| {
+            not_until.wait_time_from(governor::clock::Clock::now(&DefaultClock::default()))
+        });
+        if result.is_err() {
+            self.usage_of(&record.id)
+                .rate_limited
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// Record an authenticated request and whether its scopes allowed it
+    pub fn record_usage(&self, id: &str, allowed: bool) {
+        let usage = self.usage_of(id);
+        usage.requests.fetch_add(1, Ordering::Relaxed);
+        if !allowed {
+            usage.denied.fetch_add(1, Ordering::Relaxed);
+        }
+        usage.last_used.store(unix_now(), Ordering::Relaxed);
+    }
+
+    /// Usage for one key (None = unknown key)
+    pub fn usage(&self, id: &str) -> Option<KeyUsageSnapshot> {
+        if !self.keys.read().values().any(|r| r.id == id) {
+            return None;
+        }
+        let usage = self.usage_of(id);
+        Some(KeyUsageSnapshot {
+            id: id.to_string(),
+            requests: usage.requests.load(Ordering::Relaxed),
+            denied: usage.denied.load(Ordering::Relaxed),
+            rate_limited: usage.rate_limited.load(Ordering::Relaxed),
+            last_used: usage.last_used.load(Ordering::Relaxed),
+        })
+    }
+
+    fn usage_of(&self, id: &str) -> dashmap::mapref::one::RefMut<'_, String, KeyUsage> {
+        self.usage.entry(id.to_string()).or_default()
+    }
+
+    /// Write the key set to disk atomically (temp file + rename)
+    fn persist(&self) -> Result<(), ApiKeyError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let file = KeyFile { keys: self.list() };
+        let data =
+            serde_json::to_vec_pretty(&file).map_err(|e| ApiKeyError::Write(e.to_string()))?;
+
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, data).map_err(|e| ApiKeyError::Write(e.to_string()))?;
+        std::fs::rename(&tmp, path).map_err(|e| ApiKeyError::Write(e.to_string()))?;
+
+        // Our own write must not trigger a reload
+        *self.loaded_mtime.write() = modified_time(path);
+        Ok(())
+    }
+}
+
+/// Background task to pick up hand edits to the key file
+pub async fn key_reload_task(store: Arc<ApiKeyStore>, interval: Duration) {
+    let mut reload_interval = tokio::time::interval(interval);
+    reload_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        reload_interval.tick().await;
+        if let Err(e) = store.reload_if_changed() {
+            warn!(error = %e, "Failed to reload API keys; keeping previous set");
+        }
+    }
+}
+
+/// Match a method against a scope (`*`, `prefix_*`, or exact name)
+pub(crate) fn scope_matches(scope: &str, method: &str) -> bool {
+    match scope.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix),
+        None => scope == method,
+    }
+}
+
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha3_256::digest(key.as_bytes()))
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_matching() {
+        assert!(scope_matches("*", "debug_traceTransaction"));
+        assert!(scope_matches("eth_*", "eth_call"));
+        assert!(!scope_matches("eth_*", "txpool_status"));
+        assert!(scope_matches("txpool_status", "txpool_status"));
+        assert!(!scope_matches("txpool_status", "txpool_content"));
+    }
+
+    #[test]
+    fn test_create_authenticate_revoke() {
+        let store = ApiKeyStore::in_memory();
+        let (record, key) = store
+            .create("indexer".into(), vec!["eth_*".into()], None)
+            .unwrap();
+
+        assert!(key.starts_with(KEY_PREFIX));
+        let found = store.authenticate(&key).unwrap();
+        assert_eq!(found.id, record.id);
+        assert!(found.allows("eth_getLogs"));
+        assert!(!found.allows("admin_addPeer"));
+        assert!(store.authenticate("qck_wrong").is_none());
+
+        store.revoke(&record.id).unwrap();
+        assert!(store.authenticate(&key).is_none());
+        assert!(!store.has_active_keys());
+    }
+
+    #[test]
+    fn test_per_key_rate_limit() {
+        let store = ApiKeyStore::in_memory();
+        let (record, _) = store
+            .create("bot".into(), vec!["*".into()], Some(1))
+            .unwrap();
+
+        assert!(store.check_rate_limit(&record).is_ok());
+        assert!(store.check_rate_limit(&record).is_err());
+        assert_eq!(store.usage(&record.id).unwrap().rate_limited, 1);
+    }
+
+    #[test]
+    fn test_file_persistence_and_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api_keys.json");
+
+        let store = ApiKeyStore::open(&path).unwrap();
+        let (record, key) = store
+            .create("dashboard".into(), vec!["eth_*".into()], Some(50))
+            .unwrap();
+
+        let reopened = ApiKeyStore::open(&path).unwrap();
+        assert_eq!(reopened.authenticate(&key).unwrap().id, record.id);
+
+        // Revocation by another store instance is picked up on reload
+        store.revoke(&record.id).unwrap();
+        reopened.reload().unwrap();
+        assert!(reopened.authenticate(&key).is_none());
+    }
+}
@@ -0,0 +1,114 @@
+//! Subsystem capabilities as advertised by the node's registry.
+//!
+//! The runtime publishes a `CapabilityReport` on the event bus. Once one has
+//! arrived, requests to a subsystem that is not registered or not running
+//! (e.g. qc-07 disabled in config) fail immediately with a coded
+//! `UNAVAILABLE` error instead of waiting out the IPC timeout. Until the
+//! first report arrives every target is assumed available.
+
+use parking_lot::RwLock;
+use shared_types::{CapabilityReport, ErrorCode, ErrorInfo, SubsystemId};
+
+/// Latest capability report received from the bus
+#[derive(Debug, Default)]
+pub struct AdvertisedCapabilities {
+    report: RwLock<Option<CapabilityReport>>,
+}
+
+impl AdvertisedCapabilities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the current report
+    pub fn update(&self, report: CapabilityReport) {
+        *self.report.write() = Some(report);
+    }
+
+    /// Latest report, if one has been received
+    pub fn report(&self) -> Option<CapabilityReport> {
+        self.report.read().clone()
+    }
+
+    /// Error for a request to `target` if it is advertised as unavailable.
+    ///
+    /// Targets that do not name a subsystem (e.g. `node-runtime`) are
+    /// always allowed.
+    pub fn check(&self, target: &str) -> Result<(), ErrorInfo> {
+        let report = self.report.read();
+        let (Some(report), Some(id)) = (report.as_ref(), target_subsystem(target)) else {
+            return Ok(());
+        };
+        if report.is_available(id) {
+            return Ok(());
+        }
+
+        let reason = match report.get(id) {
+            Some(entry) => format!("{:?}", entry.status).to_lowercase(),
+            None => "not enabled on this node".to_string(),
+        };
+        Err(ErrorInfo::new(
+            ErrorCode::Unavailable,
+            id,
+            format!("{} is {}", target, reason),
+        ))
+    }
+}
+
+/// Subsystem named by an IPC target such as `qc-07-bloom-filters` or `qc-02`
+fn target_subsystem(target: &str) -> Option<SubsystemId> {
+    let digits = target.strip_prefix("qc-")?.get(..2)?;
+    SubsystemId::from_u8(digits.parse().ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_types::{AdvertisedSubsystem, SubsystemCapabilities, SubsystemStatus};
+
+    fn advertised(id: SubsystemId, status: SubsystemStatus) -> AdvertisedSubsystem {
+        AdvertisedSubsystem {
+            id,
+            name: format!("{:?}", id),
+            version: "0.1.0".into(),
+            status,
+            capabilities: SubsystemCapabilities::default(),
+        }
+    }
+
+    #[test]
+    fn test_target_subsystem() {
+        assert_eq!(
+            target_subsystem("qc-07-bloom-filters"),
+            Some(SubsystemId::BloomFilters)
+        );
+        assert_eq!(target_subsystem("qc-02"), Some(SubsystemId::BlockStorage));
+        assert_eq!(target_subsystem("node-runtime"), None);
+        assert_eq!(target_subsystem("qc-99-unknown"), None);
+    }
+
+    #[test]
+    fn test_check_against_report() {
+        let capabilities = AdvertisedCapabilities::new();
+        // No report yet: assume everything is there
+        assert!(capabilities.check("qc-07-bloom-filters").is_ok());
+
+        capabilities.update(CapabilityReport {
+            subsystems: vec![
+                advertised(SubsystemId::BlockStorage, SubsystemStatus::Healthy),
+                advertised(SubsystemId::Mempool, SubsystemStatus::Stopped),
+            ],
+        });
+
+        assert!(capabilities.check("qc-02-block-storage").is_ok());
+        assert!(capabilities.check("node-runtime").is_ok());
+
+        let missing = capabilities.check("qc-07-bloom-filters").unwrap_err();
+        assert_eq!(missing.code, ErrorCode::Unavailable);
+        assert_eq!(missing.origin, SubsystemId::BloomFilters);
+        assert!(missing.message.contains("not enabled"));
+
+        let stopped = capabilities.check("qc-06-mempool").unwrap_err();
+        assert_eq!(stopped.message, "qc-06-mempool is stopped");
+    }
+}
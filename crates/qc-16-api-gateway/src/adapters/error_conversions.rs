@@ -2,10 +2,34 @@
 //!
 //! These conversions involve I/O types and belong in the adapters layer.
 
+use crate::adapters::pending::ResponseError;
 use crate::domain::ApiError;
+use shared_types::ErrorInfo;
 
 impl From<std::io::Error> for ApiError {
     fn from(e: std::io::Error) -> Self {
         ApiError::internal(e.to_string())
     }
 }
+
+impl From<ResponseError> for ApiError {
+    /// Keeps the subsystem's `data` (e.g. its shared error code) intact.
+    fn from(e: ResponseError) -> Self {
+        ApiError {
+            code: e.code,
+            message: e.message,
+            data: e.data,
+        }
+    }
+}
+
+impl From<ErrorInfo> for ResponseError {
+    /// Gateway-side failures carry the same `data` as subsystem errors.
+    fn from(info: ErrorInfo) -> Self {
+        ResponseError {
+            code: info.code.json_rpc_code(),
+            message: info.message.clone(),
+            data: serde_json::to_value(&info).ok(),
+        }
+    }
+}
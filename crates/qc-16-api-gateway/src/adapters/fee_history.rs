@@ -0,0 +1,416 @@
+//! Fee history provider backing the gas oracle methods.
+//!
+//! Aggregates per-block base fees and priority-fee percentiles from recent
+//! blocks (qc-02) and the pending fee distribution from the mempool (qc-06).
+//! Settled blocks and short-lived mempool snapshots are cached so that
+//! dashboards polling eth_feeHistory / eth_gasPrice stay cheap.
+
+use crate::domain::types::{BlockId, BlockTag, FeeHistory, U256};
+use crate::ipc::handler::IpcHandler;
+use crate::ipc::requests::*;
+use crate::{ApiError, ApiResult};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Fallback priority fee when no fee data is available (0.1 gwei)
+pub const DEFAULT_PRIORITY_FEE: u64 = 100_000_000;
+
+/// EIP-1559 base fee max change denominator
+const BASE_FEE_CHANGE_DENOMINATOR: u64 = 8;
+
+/// EIP-1559 elasticity multiplier (gas target = limit / 2)
+const ELASTICITY_MULTIPLIER: u64 = 2;
+
+/// Concurrent block fetches when filling the cache
+const FETCH_CONCURRENCY: usize = 16;
+
+/// Fee oracle tuning
+#[derive(Debug, Clone)]
+pub struct FeeOracleConfig {
+    /// Settled blocks kept in the cache
+    pub block_cache_size: usize,
+    /// How long a mempool fee snapshot is reused
+    pub mempool_ttl: Duration,
+    /// Percentile of pending tips suggested by eth_maxPriorityFeePerGas
+    pub suggestion_percentile: f64,
+}
+
+impl Default for FeeOracleConfig {
+    fn default() -> Self {
+        Self {
+            block_cache_size: 1024,
+            mempool_ttl: Duration::from_secs(2),
+            suggestion_percentile: 60.0,
+        }
+    }
+}
+
+/// Fee data extracted from one block
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockFees {
+    /// Block number
+    pub number: u64,
+    /// Base fee per gas (zero for pre-EIP-1559 blocks)
+    pub base_fee: U256,
+    /// Gas used by the block
+    pub gas_used: u64,
+    /// Block gas limit
+    pub gas_limit: u64,
+    /// Effective priority fees paid, sorted ascending
+    pub tips: Vec<U256>,
+}
+
+impl BlockFees {
+    /// Parse fee data from a JSON-RPC block object with full transactions
+    pub fn from_block(block: &serde_json::Value) -> Option<Self> {
+        let number = parse_u256(block.get("number")?)?.as_u64();
+        let base_fee = block
+            .get("baseFeePerGas")
+            .and_then(parse_u256)
+            .unwrap_or(U256::ZERO);
+        let gas_used = block
+            .get("gasUsed")
+            .and_then(parse_u256)
+            .unwrap_or(U256::ZERO);
+        let gas_limit = block
+            .get("gasLimit")
+            .and_then(parse_u256)
+            .unwrap_or(U256::ZERO);
+
+        let mut tips: Vec<U256> = block
+            .get("transactions")
+            .and_then(|txs| txs.as_array())
+            .map(|txs| txs.iter().filter_map(|tx| tx_tip(tx, base_fee)).collect())
+            .unwrap_or_default();
+        tips.sort();
+
+        Some(Self {
+            number,
+            base_fee,
+            gas_used: gas_used.as_u64(),
+            gas_limit: gas_limit.as_u64(),
+            tips,
+        })
+    }
+
+    /// Fraction of the gas limit used (0-1)
+    pub fn gas_used_ratio(&self) -> f64 {
+        if self.gas_limit == 0 {
+            return 0.0;
+        }
+        self.gas_used as f64 / self.gas_limit as f64
+    }
+
+    /// Priority fees at the requested percentiles
+    pub fn rewards(&self, percentiles: &[f64]) -> Vec<U256> {
+        percentiles
+            .iter()
+            .map(|p| percentile(&self.tips, *p))
+            .collect()
+    }
+
+    /// Base fee of the following block per EIP-1559
+    pub fn next_base_fee(&self) -> U256 {
+        let target = self.gas_limit / ELASTICITY_MULTIPLIER;
+        if target == 0 || self.gas_used == target {
+            return self.base_fee;
+        }
+
+        let base = *self.base_fee.inner();
+        let denominator = primitive_types::U256::from(target) * BASE_FEE_CHANGE_DENOMINATOR;
+        if self.gas_used > target {
+            let delta = base * (self.gas_used - target) / denominator;
+            U256(base + delta.max(primitive_types::U256::one()))
+        } else {
+            let delta = base * (target - self.gas_used) / denominator;
+            U256(base.saturating_sub(delta))
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending list (zero when empty)
+pub fn percentile(sorted: &[U256], p: f64) -> U256 {
+    if sorted.is_empty() {
+        return U256::ZERO;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Effective tip of a transaction given the block base fee
+fn tx_tip(tx: &serde_json::Value, base_fee: U256) -> Option<U256> {
+    if let Some(tip) = tx.get("maxPriorityFeePerGas").and_then(parse_u256) {
+        return Some(tip);
+    }
+    tx.get("gasPrice")
+        .and_then(parse_u256)
+        .map(|price| price.saturating_sub(base_fee))
+}
+
+fn parse_u256(value: &serde_json::Value) -> Option<U256> {
+    serde_json::from_value(value.clone()).ok()
+}
+
+/// Cached mempool fee snapshot
+struct MempoolSnapshot {
+    fetched_at: Instant,
+    /// Pending tips, sorted ascending
+    tips: Vec<U256>,
+    /// Minimum gas price accepted by the mempool
+    min_gas_price: U256,
+}
+
+/// Fee history and gas price oracle
+pub struct FeeHistoryProvider {
+    ipc: Arc<IpcHandler>,
+    config: FeeOracleConfig,
+    /// Settled blocks by number
+    blocks: Mutex<BTreeMap<u64, BlockFees>>,
+    mempool: Mutex<Option<Arc<MempoolSnapshot>>>,
+}
+
+impl FeeHistoryProvider {
+    pub fn new(ipc: Arc<IpcHandler>, config: FeeOracleConfig) -> Self {
+        Self {
+            ipc,
+            config,
+            blocks: Mutex::new(BTreeMap::new()),
+            mempool: Mutex::new(None),
+        }
+    }
+
+    /// eth_feeHistory over `block_count` blocks ending at `newest_block`
+    pub async fn fee_history(
+        &self,
+        block_count: u64,
+        newest_block: BlockId,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> ApiResult<FeeHistory> {
+        let latest = self.latest_number().await?;
+        let newest = match newest_block {
+            BlockId::Number(n) => n.min(latest),
+            BlockId::Tag(BlockTag::Earliest) => 0,
+            BlockId::Tag(_) => latest,
+            BlockId::Hash(_) => {
+                return Err(ApiError::invalid_params(
+                    "newestBlock must be a number or tag",
+                ))
+            }
+        };
+        let oldest = newest.saturating_sub(block_count.saturating_sub(1));
+
+        let blocks: Vec<BlockFees> = stream::iter(oldest..=newest)
+            .map(|n| self.block_fees(n, latest))
+            .buffered(FETCH_CONCURRENCY)
+            .try_filter_map(|b| async move { Ok(b) })
+            .try_collect()
+            .await?;
+
+        let mut base_fee_per_gas: Vec<U256> = blocks.iter().map(|b| b.base_fee).collect();
+        if let Some(last) = blocks.last() {
+            base_fee_per_gas.push(last.next_base_fee());
+        }
+
+        Ok(FeeHistory {
+            oldest_block: U256::from(blocks.first().map(|b| b.number).unwrap_or(oldest)),
+            base_fee_per_gas,
+            gas_used_ratio: blocks.iter().map(BlockFees::gas_used_ratio).collect(),
+            reward: reward_percentiles.map(|ps| blocks.iter().map(|b| b.rewards(&ps)).collect()),
+            blob_base_fee_per_gas: None,
+            blob_gas_used_ratio: None,
+        })
+    }
+
+    /// Suggested priority fee from pending transactions, then recent blocks
+    pub async fn max_priority_fee_per_gas(&self) -> ApiResult<U256> {
+        let p = self.config.suggestion_percentile;
+        let snapshot = self.mempool_snapshot().await?;
+        if !snapshot.tips.is_empty() {
+            return Ok(percentile(&snapshot.tips, p));
+        }
+
+        let latest = self.latest_number().await?;
+        match self.block_fees(latest, latest).await? {
+            Some(block) if !block.tips.is_empty() => Ok(percentile(&block.tips, p)),
+            _ => Ok(U256::from(DEFAULT_PRIORITY_FEE)),
+        }
+    }
+
+    /// Suggested legacy gas price: next base fee plus suggested tip,
+    /// never below the mempool minimum
+    pub async fn gas_price(&self) -> ApiResult<U256> {
+        let latest = self.latest_number().await?;
+        let next_base_fee = self
+            .block_fees(latest, latest)
+            .await?
+            .map(|b| b.next_base_fee())
+            .unwrap_or(U256::ZERO);
+        let tip = self.max_priority_fee_per_gas().await?;
+        let min_gas_price = self.mempool_snapshot().await?.min_gas_price;
+
+        Ok(next_base_fee.saturating_add(tip).max(min_gas_price))
+    }
+
+    /// Fee data for a block; blocks below `latest` are cached
+    async fn block_fees(&self, number: u64, latest: u64) -> ApiResult<Option<BlockFees>> {
+        if let Some(cached) = self.blocks.lock().get(&number) {
+            return Ok(Some(cached.clone()));
+        }
+
+        let block = self
+            .request(
+                "qc-02-block-storage",
+                RequestPayload::GetBlockByNumber(GetBlockByNumberRequest {
+                    block_id: BlockId::Number(number),
+                    include_transactions: true,
+                }),
+            )
+            .await?;
+        let Some(fees) = BlockFees::from_block(&block) else {
+            return Ok(None);
+        };
+
+        // The head may still be replaced; only settled blocks are cached
+        if number < latest {
+            let mut blocks = self.blocks.lock();
+            blocks.insert(number, fees.clone());
+            while blocks.len() > self.config.block_cache_size {
+                blocks.pop_first();
+            }
+        }
+        Ok(Some(fees))
+    }
+
+    async fn latest_number(&self) -> ApiResult<u64> {
+        let result = self
+            .request(
+                "qc-02-block-storage",
+                RequestPayload::GetBlockNumber(GetBlockNumberRequest),
+            )
+            .await?;
+        parse_u256(&result)
+            .map(|n| n.as_u64())
+            .ok_or_else(|| ApiError::internal("invalid block number from qc-02"))
+    }
+
+    /// Pending fee distribution, refreshed at most once per TTL
+    async fn mempool_snapshot(&self) -> ApiResult<Arc<MempoolSnapshot>> {
+        if let Some(snapshot) = self.mempool.lock().as_ref() {
+            if snapshot.fetched_at.elapsed() < self.config.mempool_ttl {
+                return Ok(Arc::clone(snapshot));
+            }
+        }
+
+        let content = self
+            .request(
+                "qc-06-mempool",
+                RequestPayload::GetTxPoolContent(GetTxPoolContentRequest { address: None }),
+            )
+            .await?;
+        let min_gas_price = self
+            .request(
+                "qc-06-mempool",
+                RequestPayload::GetGasPrice(GetGasPriceRequest),
+            )
+            .await
+            .ok()
+            .and_then(|v| parse_u256(&v))
+            .unwrap_or(U256::ZERO);
+
+        let mut tips: Vec<U256> = content
+            .get("pending")
+            .and_then(|p| p.as_object())
+            .into_iter()
+            .flat_map(|by_sender| by_sender.values())
+            .filter_map(|by_nonce| by_nonce.as_object())
+            .flat_map(|by_nonce| by_nonce.values())
+            .filter_map(|tx| tx_tip(tx, U256::ZERO))
+            .collect();
+        tips.sort();
+        debug!(pending = tips.len(), "Refreshed mempool fee snapshot");
+
+        let snapshot = Arc::new(MempoolSnapshot {
+            fetched_at: Instant::now(),
+            tips,
+            min_gas_price,
+        });
+        *self.mempool.lock() = Some(Arc::clone(&snapshot));
+        Ok(snapshot)
+    }
+
+    async fn request(&self, target: &str, payload: RequestPayload) -> ApiResult<serde_json::Value> {
+        self.ipc
+            .request(target, payload, None)
+            .await
+            .map_err(ApiError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(gas_used: u64, base_fee: u64) -> BlockFees {
+        BlockFees {
+            number: 1,
+            base_fee: U256::from(base_fee),
+            gas_used,
+            gas_limit: 30_000_000,
+            tips: vec![],
+        }
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let tips: Vec<U256> = (1..=10u64).map(U256::from).collect();
+        assert_eq!(percentile(&tips, 0.0), U256::from(1u64));
+        assert_eq!(percentile(&tips, 50.0), U256::from(5u64));
+        assert_eq!(percentile(&tips, 100.0), U256::from(10u64));
+        assert_eq!(percentile(&[], 50.0), U256::ZERO);
+    }
+
+    #[test]
+    fn test_next_base_fee_follows_eip1559() {
+        // At target: unchanged
+        assert_eq!(
+            block(15_000_000, 1_000).next_base_fee(),
+            U256::from(1_000u64)
+        );
+        // Full block: +12.5%
+        assert_eq!(
+            block(30_000_000, 1_000).next_base_fee(),
+            U256::from(1_125u64)
+        );
+        // Empty block: -12.5%
+        assert_eq!(block(0, 1_000).next_base_fee(), U256::from(875u64));
+    }
+
+    #[test]
+    fn test_block_fees_from_json() {
+        let json = serde_json::json!({
+            "number": "0x10",
+            "baseFeePerGas": "0x64",
+            "gasUsed": "0x5",
+            "gasLimit": "0xa",
+            "transactions": [
+                { "maxPriorityFeePerGas": "0x3" },
+                { "gasPrice": "0x6e" },
+                "0xdeadbeef"
+            ]
+        });
+
+        let fees = BlockFees::from_block(&json).unwrap();
+        assert_eq!(fees.number, 16);
+        assert_eq!(fees.gas_used_ratio(), 0.5);
+        // Legacy tx tip = gasPrice - baseFee = 10
+        assert_eq!(fees.tips, vec![U256::from(3u64), U256::from(10u64)]);
+        assert_eq!(
+            fees.rewards(&[0.0, 100.0]),
+            vec![U256::from(3u64), U256::from(10u64)]
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Polling filter store for eth_newFilter / eth_getFilterChanges.
+//!
+//! Server-side filter objects fed by the same log, block, and pending-tx
+//! feeds as WebSocket subscriptions: every `SubscriptionManager` broadcast
+//! also pushes to the store it was built `with_filters`. Each filter buffers
+//! changes until the client polls; buffers are bounded and idle filters
+//! expire.
+
+use crate::domain::types::{Filter, Hash, Log};
+use crate::domain::ApiError;
+use crate::ws::subscriptions::match_log_filter;
+use dashmap::DashMap;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Filter ID (hex string)
+pub type FilterId = String;
+
+/// What an installed filter watches
+#[derive(Debug, Clone)]
+pub enum FilterKind {
+    /// Contract logs matching a filter (eth_newFilter)
+    Logs(Box<Filter>),
+    /// New block hashes (eth_newBlockFilter)
+    Blocks,
+    /// New pending transaction hashes (eth_newPendingTransactionFilter)
+    PendingTransactions,
+}
+
+/// Installed filter with its change buffer
+struct InstalledFilter {
+    kind: FilterKind,
+    /// Changes since the last poll, oldest first
+    changes: VecDeque<serde_json::Value>,
+    /// Changes discarded because the buffer was full
+    dropped: u64,
+    /// Last time the client created or polled the filter
+    last_poll: Instant,
+}
+
+/// Filter store limits
+#[derive(Debug, Clone)]
+pub struct FilterLimits {
+    /// Max installed filters across all clients
+    pub max_filters: usize,
+    /// Max buffered changes per filter (oldest are dropped first)
+    pub max_changes_per_filter: usize,
+    /// Filters not polled within this window are uninstalled
+    pub idle_timeout: Duration,
+}
+
+impl Default for FilterLimits {
+    fn default() -> Self {
+        Self {
+            max_filters: 10_000,
+            max_changes_per_filter: 10_000,
+            idle_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Filter store errors
+#[derive(Debug, thiserror::Error)]
+pub enum FilterError {
+    #[error("filter not found")]
+    NotFound,
+    #[error("too many installed filters")]
+    TooManyFilters,
+}
+
+impl From<FilterError> for ApiError {
+    fn from(e: FilterError) -> Self {
+        match e {
+            FilterError::NotFound => ApiError::server_error(e.to_string()),
+            FilterError::TooManyFilters => ApiError::limit_exceeded(e.to_string()),
+        }
+    }
+}
+
+/// Store of installed polling filters
+pub struct FilterStore {
+    filters: DashMap<FilterId, InstalledFilter>,
+    id_counter: AtomicU64,
+    limits: FilterLimits,
+}
+
+impl FilterStore {
+    pub fn new(limits: FilterLimits) -> Self {
+        Self {
+            filters: DashMap::new(),
+            id_counter: AtomicU64::new(1),
+            limits,
+        }
+    }
+
+    /// Install a filter and return its ID
+    pub fn install(&self, kind: FilterKind) -> Result<FilterId, FilterError> {
+        if self.filters.len() >= self.limits.max_filters {
+            return Err(FilterError::TooManyFilters);
+        }
+
+        let id = format!("0x{:x}", self.id_counter.fetch_add(1, Ordering::SeqCst));
+        self.filters.insert(
+            id.clone(),
+            InstalledFilter {
+                kind,
+                changes: VecDeque::new(),
+                dropped: 0,
+                last_poll: Instant::now(),
+            },
+        );
+
+        debug!(filter_id = %id, "Installed filter");
+        Ok(id)
+    }
+
+    /// Uninstall a filter. Returns false if it did not exist.
+    pub fn uninstall(&self, id: &str) -> bool {
+        self.filters.remove(id).is_some()
+    }
+
+    /// Drain changes buffered since the last poll
+    pub fn poll_changes(&self, id: &str) -> Result<Vec<serde_json::Value>, FilterError> {
+        let mut filter = self.filters.get_mut(id).ok_or(FilterError::NotFound)?;
+        filter.last_poll = Instant::now();
+        Ok(filter.changes.drain(..).collect())
+    }
+
+    /// Filter kind, refreshing the idle timer
+    pub fn kind(&self, id: &str) -> Result<FilterKind, FilterError> {
+        let mut filter = self.filters.get_mut(id).ok_or(FilterError::NotFound)?;
+        filter.last_poll = Instant::now();
+        Ok(filter.kind.clone())
+    }
+
+    /// Feed a contract log to matching log filters
+    pub fn push_log(&self, log: &Log) {
+        let Ok(value) = serde_json::to_value(log) else {
+            return;
+        };
+        self.push(&value, |kind| match kind {
+            FilterKind::Logs(filter) => match_log_filter(filter, &log.address, &log.topics),
+            _ => false,
+        });
+    }
+
+    /// Feed a new block hash to block filters
+    pub fn push_block(&self, block_hash: Hash) {
+        let value = serde_json::json!(block_hash);
+        self.push(&value, |kind| matches!(kind, FilterKind::Blocks));
+    }
+
+    /// Feed a new pending transaction hash to pending-tx filters
+    pub fn push_pending_tx(&self, tx_hash: Hash) {
+        let value = serde_json::json!(tx_hash);
+        self.push(&value, |kind| {
+            matches!(kind, FilterKind::PendingTransactions)
+        });
+    }
+
+    fn push(&self, value: &serde_json::Value, matches: impl Fn(&FilterKind) -> bool) {
+        let max = self.limits.max_changes_per_filter;
+        for mut filter in self.filters.iter_mut() {
+            if !matches(&filter.kind) {
+                continue;
+            }
+            if filter.changes.len() >= max {
+                filter.changes.pop_front();
+                filter.dropped += 1;
+            }
+            filter.changes.push_back(value.clone());
+        }
+    }
+
+    /// Changes dropped from a filter's buffer because the client polled too slowly
+    pub fn dropped_changes(&self, id: &str) -> u64 {
+        self.filters.get(id).map(|f| f.dropped).unwrap_or(0)
+    }
+
+    /// Uninstall filters idle longer than the configured timeout
+    pub fn remove_expired(&self) -> usize {
+        let before = self.filters.len();
+        let timeout = self.limits.idle_timeout;
+        self.filters.retain(|_, f| f.last_poll.elapsed() < timeout);
+        before - self.filters.len()
+    }
+
+    /// Number of installed filters
+    pub fn len(&self) -> usize {
+        self.filters.len()
+    }
+
+    /// Check if no filters are installed
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+}
+
+impl Default for FilterStore {
+    fn default() -> Self {
+        Self::new(FilterLimits::default())
+    }
+}
+
+/// Background task to expire idle filters
+pub async fn filter_cleanup_task(store: Arc<FilterStore>, interval: Duration) {
+    let mut cleanup_interval = tokio::time::interval(interval);
+    cleanup_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        cleanup_interval.tick().await;
+        let removed = store.remove_expired();
+        if removed > 0 {
+            debug!(removed = removed, "Expired idle filters");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_filter_changes_drain() {
+        let store = FilterStore::default();
+        let id = store.install(FilterKind::PendingTransactions).unwrap();
+        let _blocks = store.install(FilterKind::Blocks).unwrap();
+
+        store.push_pending_tx(Hash::repeat_byte(1));
+        store.push_pending_tx(Hash::repeat_byte(2));
+
+        assert_eq!(store.poll_changes(&id).unwrap().len(), 2);
+        assert!(store.poll_changes(&id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_change_buffer_is_bounded() {
+        let store = FilterStore::new(FilterLimits {
+            max_changes_per_filter: 2,
+            ..Default::default()
+        });
+        let id = store.install(FilterKind::Blocks).unwrap();
+
+        for i in 0..5u8 {
+            store.push_block(Hash::repeat_byte(i));
+        }
+
+        let changes = store.poll_changes(&id).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                serde_json::json!(Hash::repeat_byte(3)),
+                serde_json::json!(Hash::repeat_byte(4)),
+            ]
+        );
+        assert_eq!(store.dropped_changes(&id), 3);
+    }
+
+    #[test]
+    fn test_idle_filters_expire() {
+        let store = FilterStore::new(FilterLimits {
+            idle_timeout: Duration::ZERO,
+            ..Default::default()
+        });
+        let id = store.install(FilterKind::Blocks).unwrap();
+
+        assert_eq!(store.remove_expired(), 1);
+        assert!(matches!(
+            store.poll_changes(&id),
+            Err(FilterError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn test_filter_limit() {
+        let store = FilterStore::new(FilterLimits {
+            max_filters: 1,
+            ..Default::default()
+        });
+        let id = store.install(FilterKind::Blocks).unwrap();
+        assert!(matches!(
+            store.install(FilterKind::Blocks),
+            Err(FilterError::TooManyFilters)
+        ));
+        assert!(store.uninstall(&id));
+        assert!(!store.uninstall(&id));
+    }
+}
@@ -0,0 +1,352 @@
+//! Readiness and liveness reporting for the Admin API.
+//!
+//! The IPC handler records, per target subsystem, when it last asked and
+//! when it last heard back. The readiness probe combines those ages with
+//! event bus connectivity, pending request depth and circuit breaker state
+//! into a report suitable for Kubernetes probes: `not_ready` stops traffic,
+//! `degraded` keeps serving but names what is wrong.
+
+use crate::adapters::pending::PendingRequestStore;
+use crate::ipc::handler::IpcSender;
+use crate::middleware::{CircuitBreakerManager, CircuitState};
+use dashmap::DashMap;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Per-subsystem request/response bookkeeping
+#[derive(Debug, Clone, Copy)]
+struct TargetHealth {
+    /// First request to this subsystem
+    first_request: Instant,
+    /// Most recent response (success or subsystem error)
+    last_response: Option<Instant>,
+    /// Requests that got no response since the last one that did
+    consecutive_failures: u32,
+}
+
+/// Last-response registry, keyed by target subsystem
+#[derive(Debug, Default)]
+pub struct SubsystemHealth {
+    targets: DashMap<String, TargetHealth>,
+}
+
+impl SubsystemHealth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Note that a request was sent to `target`
+    pub fn record_request(&self, target: &str) {
+        self.targets
+            .entry(target.to_string())
+            .or_insert_with(|| TargetHealth {
+                first_request: Instant::now(),
+                last_response: None,
+                consecutive_failures: 0,
+            });
+    }
+
+    /// Note that `target` answered (an error payload still counts)
+    pub fn record_response(&self, target: &str) {
+        if let Some(mut health) = self.targets.get_mut(target) {
+            health.last_response = Some(Instant::now());
+            health.consecutive_failures = 0;
+        }
+    }
+
+    /// Note that a request to `target` went unanswered
+    pub fn record_failure(&self, target: &str) {
+        if let Some(mut health) = self.targets.get_mut(target) {
+            health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        }
+    }
+
+    /// Time since `target` last answered, if it ever has
+    pub fn last_response_age(&self, target: &str) -> Option<Duration> {
+        self.targets
+            .get(target)
+            .and_then(|h| h.last_response)
+            .map(|at| at.elapsed())
+    }
+
+    fn snapshot(&self) -> Vec<(String, TargetHealth)> {
+        let mut targets: Vec<_> = self
+            .targets
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect();
+        targets.sort_by(|a, b| a.0.cmp(&b.0));
+        targets
+    }
+}
+
+/// Thresholds for the readiness probe
+#[derive(Debug, Clone)]
+pub struct ReadinessThresholds {
+    /// Pending requests at which the gateway reports not ready
+    pub max_pending: usize,
+    /// A failing subsystem silent for this long is reported unresponsive
+    pub stale_after: Duration,
+}
+
+/// Overall readiness
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessStatus {
+    /// Everything answering
+    Ready,
+    /// Serving, but some subsystems are unhealthy
+    Degraded,
+    /// Should not receive traffic
+    NotReady,
+}
+
+/// Machine-readable degradation reason
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DegradationCode {
+    /// Requests cannot reach subsystems
+    EventBusDisconnected,
+    /// Too many requests waiting on responses
+    PendingBacklog,
+    /// A subsystem has stopped answering
+    SubsystemUnresponsive,
+    /// A subsystem's circuit breaker is rejecting requests
+    CircuitOpen,
+}
+
+/// One reason the gateway is not fully ready
+#[derive(Debug, Clone, Serialize)]
+pub struct Degradation {
+    pub code: DegradationCode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subsystem: Option<String>,
+    pub message: String,
+}
+
+/// Readiness of one subsystem as seen by the gateway
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemReadiness {
+    pub subsystem: String,
+    /// Milliseconds since the last response (None = never answered)
+    pub last_response_age_ms: Option<u64>,
+    pub consecutive_failures: u32,
+    pub circuit: String,
+    pub responsive: bool,
+}
+
+/// Full readiness report
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessReport {
+    pub status: ReadinessStatus,
+    pub reasons: Vec<Degradation>,
+    pub event_bus_connected: bool,
+    pub pending_requests: usize,
+    pub max_pending_requests: usize,
+    pub subsystems: Vec<SubsystemReadiness>,
+}
+
+impl ReadinessReport {
+    /// Whether probes should route traffic here
+    pub fn is_ready(&self) -> bool {
+        self.status != ReadinessStatus::NotReady
+    }
+}
+
+/// Builds readiness reports from live gateway state
+pub struct ReadinessProbe {
+    health: Arc<SubsystemHealth>,
+    pending: Arc<PendingRequestStore>,
+    circuit_breaker: Arc<CircuitBreakerManager>,
+    sender: Arc<dyn IpcSender>,
+    thresholds: ReadinessThresholds,
+}
+
+impl ReadinessProbe {
+    pub fn new(
+        health: Arc<SubsystemHealth>,
+        pending: Arc<PendingRequestStore>,
+        circuit_breaker: Arc<CircuitBreakerManager>,
+        sender: Arc<dyn IpcSender>,
+        thresholds: ReadinessThresholds,
+    ) -> Self {
+        Self {
+            health,
+            pending,
+            circuit_breaker,
+            sender,
+            thresholds,
+        }
+    }
+
+    /// Evaluate readiness now
+    pub fn check(&self) -> ReadinessReport {
+        let mut reasons = Vec::new();
+        let mut not_ready = false;
+
+        let event_bus_connected = self.sender.is_connected();
+        if !event_bus_connected {
+            not_ready = true;
+            reasons.push(Degradation {
+                code: DegradationCode::EventBusDisconnected,
+                subsystem: None,
+                message: "event bus has no subscribers".into(),
+            });
+        }
+
+        let pending_requests = self.pending.pending_count();
+        if pending_requests >= self.thresholds.max_pending {
+            not_ready = true;
+            reasons.push(Degradation {
+                code: DegradationCode::PendingBacklog,
+                subsystem: None,
+                message: format!(
+                    "{} requests awaiting responses (limit {})",
+                    pending_requests, self.thresholds.max_pending
+                ),
+            });
+        }
+
+        let subsystems: Vec<_> = self
+            .health
+            .snapshot()
+            .into_iter()
+            .map(|(subsystem, health)| {
+                let age = health.last_response.map(|at| at.elapsed());
+                let silent_for = age.unwrap_or_else(|| health.first_request.elapsed());
+                let responsive =
+                    health.consecutive_failures == 0 || silent_for < self.thresholds.stale_after;
+                let circuit = self.circuit_breaker.get_state(&subsystem);
+
+                if !responsive {
+                    reasons.push(Degradation {
+                        code: DegradationCode::SubsystemUnresponsive,
+                        subsystem: Some(subsystem.clone()),
+                        message: format!(
+                            "no response for {}s after {} failed requests",
+                            silent_for.as_secs(),
+                            health.consecutive_failures
+                        ),
+                    });
+                }
+                if circuit == CircuitState::Open {
+                    reasons.push(Degradation {
+                        code: DegradationCode::CircuitOpen,
+                        subsystem: Some(subsystem.clone()),
+                        message: "circuit breaker open".into(),
+                    });
+                }
+
+                SubsystemReadiness {
+                    subsystem,
+                    last_response_age_ms: age.map(|a| a.as_millis() as u64),
+                    consecutive_failures: health.consecutive_failures,
+                    circuit: circuit.to_string(),
+                    responsive,
+                }
+            })
+            .collect();
+
+        let status = if not_ready {
+            ReadinessStatus::NotReady
+        } else if reasons.is_empty() {
+            ReadinessStatus::Ready
+        } else {
+            ReadinessStatus::Degraded
+        };
+
+        ReadinessReport {
+            status,
+            reasons,
+            event_bus_connected,
+            pending_requests,
+            max_pending_requests: self.thresholds.max_pending,
+            subsystems,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::handler::IpcError;
+    use crate::ipc::requests::IpcRequest;
+    use crate::middleware::CircuitBreakerConfig;
+    use async_trait::async_trait;
+
+    struct StubSender(bool);
+
+    #[async_trait]
+    impl IpcSender for StubSender {
+        async fn send(&self, _request: IpcRequest) -> Result<(), IpcError> {
+            Ok(())
+        }
+
+        fn is_connected(&self) -> bool {
+            self.0
+        }
+    }
+
+    fn probe(health: &Arc<SubsystemHealth>, connected: bool, max_pending: usize) -> ReadinessProbe {
+        ReadinessProbe::new(
+            Arc::clone(health),
+            Arc::new(PendingRequestStore::new(Duration::from_secs(1))),
+            Arc::new(CircuitBreakerManager::new(CircuitBreakerConfig::default())),
+            Arc::new(StubSender(connected)),
+            ReadinessThresholds {
+                max_pending,
+                stale_after: Duration::ZERO,
+            },
+        )
+    }
+
+    #[test]
+    fn test_ready_when_subsystems_answer() {
+        let health = Arc::new(SubsystemHealth::new());
+        health.record_request("qc-02");
+        health.record_response("qc-02");
+
+        let report = probe(&health, true, 10).check();
+
+        assert_eq!(report.status, ReadinessStatus::Ready);
+        assert!(report.subsystems[0].last_response_age_ms.is_some());
+        assert!(health.last_response_age("qc-02").is_some());
+    }
+
+    #[test]
+    fn test_unresponsive_subsystem_degrades() {
+        let health = Arc::new(SubsystemHealth::new());
+        health.record_request("qc-03");
+        health.record_failure("qc-03");
+
+        let report = probe(&health, true, 10).check();
+
+        assert_eq!(report.status, ReadinessStatus::Degraded);
+        assert!(report.is_ready());
+        assert_eq!(
+            report.reasons[0].code,
+            DegradationCode::SubsystemUnresponsive
+        );
+        assert_eq!(report.reasons[0].subsystem.as_deref(), Some("qc-03"));
+    }
+
+    #[test]
+    fn test_bus_disconnected_not_ready() {
+        let health = Arc::new(SubsystemHealth::new());
+
+        let report = probe(&health, false, 0).check();
+
+        assert_eq!(report.status, ReadinessStatus::NotReady);
+        let codes: Vec<_> = report.reasons.iter().map(|r| r.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                DegradationCode::EventBusDisconnected,
+                DegradationCode::PendingBacklog
+            ]
+        );
+    }
+}
@@ -2,7 +2,19 @@
 //!
 //! Infrastructure implementations for async operations and external integrations.
 
+pub mod api_keys;
+pub mod capabilities;
 pub mod error_conversions;
+pub mod fee_history;
+pub mod filters;
+pub mod health;
 pub mod pending;
+pub mod tls;
 
+pub use api_keys::{key_reload_task, ApiKeyError, ApiKeyRecord, ApiKeyStore};
+pub use capabilities::AdvertisedCapabilities;
+pub use fee_history::{FeeHistoryProvider, FeeOracleConfig};
+pub use filters::{filter_cleanup_task, FilterError, FilterKind, FilterStore};
+pub use health::{ReadinessProbe, ReadinessReport, ReadinessStatus, SubsystemHealth};
 pub use pending::{cleanup_task, PendingRequestStore, SubsystemResponse};
+pub use tls::{GatewayTls, TlsError};
@@ -0,0 +1,208 @@
+//! TLS termination for the gateway servers.
+//!
+//! Builds rustls server configs from PEM files so operators can expose the
+//! RPC ports without a reverse proxy. ALPN advertises `h2` where HTTP/2 is
+//! enabled; the WebSocket server stays on HTTP/1.1 because upgrades over
+//! HTTP/2 (RFC 8441) are not supported by the router. The Admin server can
+//! additionally require client certificates signed by a configured CA.
+
+use crate::domain::config::TlsConfig;
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::crypto::{ring, CryptoProvider};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+
+/// ALPN identifier for HTTP/2
+const ALPN_H2: &[u8] = b"h2";
+/// ALPN identifier for HTTP/1.1
+const ALPN_HTTP1: &[u8] = b"http/1.1";
+
+/// TLS setup errors
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    #[error("failed to read {path}: {reason}")]
+    Read { path: String, reason: String },
+    #[error("no certificates found in {0}")]
+    NoCertificates(String),
+    #[error("no private key found in {0}")]
+    NoPrivateKey(String),
+    #[error("invalid TLS configuration: {0}")]
+    Config(String),
+}
+
+/// Which server a TLS config is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsServer {
+    /// JSON-RPC over HTTP (and GraphQL)
+    Http,
+    /// WebSocket subscriptions
+    WebSocket,
+    /// Admin API (optionally with client certificates)
+    Admin,
+}
+
+/// Server TLS configs, built once at startup
+#[derive(Clone)]
+pub struct GatewayTls {
+    pub http: RustlsConfig,
+    pub websocket: RustlsConfig,
+    pub admin: RustlsConfig,
+}
+
+impl GatewayTls {
+    /// Load certificates and build a config per server
+    pub fn load(config: &TlsConfig) -> Result<Self, TlsError> {
+        Ok(Self {
+            http: RustlsConfig::from_config(server_config(config, TlsServer::Http)?),
+            websocket: RustlsConfig::from_config(server_config(config, TlsServer::WebSocket)?),
+            admin: RustlsConfig::from_config(server_config(config, TlsServer::Admin)?),
+        })
+    }
+}
+
+/// Build the rustls server config for one server
+pub fn server_config(config: &TlsConfig, server: TlsServer) -> Result<Arc<ServerConfig>, TlsError> {
+    let provider = Arc::new(ring::default_provider());
+    let certs = load_certs(&config.cert_path)?;
+    let key = load_key(&config.key_path)?;
+
+    let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| TlsError::Config(e.to_string()))?;
+
+    let builder = match (&config.admin_client_ca_path, server) {
+        (Some(ca_path), TlsServer::Admin) => {
+            let verifier = client_verifier(ca_path, provider)?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        _ => builder.with_no_client_auth(),
+    };
+
+    let mut server_config = builder
+        .with_single_cert(certs, key)
+        .map_err(|e| TlsError::Config(e.to_string()))?;
+    server_config.alpn_protocols = alpn_protocols(server, config.http2);
+
+    Ok(Arc::new(server_config))
+}
+
+/// ALPN protocols offered by a server, most preferred first
+pub fn alpn_protocols(server: TlsServer, http2: bool) -> Vec<Vec<u8>> {
+    if http2 && server != TlsServer::WebSocket {
+        vec![ALPN_H2.to_vec(), ALPN_HTTP1.to_vec()]
+    } else {
+        vec![ALPN_HTTP1.to_vec()]
+    }
+}
+
+fn client_verifier(
+    ca_path: &str,
+    provider: Arc<CryptoProvider>,
+) -> Result<Arc<dyn rustls::server::danger::ClientCertVerifier>, TlsError> {
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(ca_path)? {
+        roots
+            .add(cert)
+            .map_err(|e| TlsError::Config(format!("invalid client CA in {}: {}", ca_path, e)))?;
+    }
+
+    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+        .build()
+        .map_err(|e| TlsError::Config(e.to_string()))
+}
+
+fn open(path: &str) -> Result<BufReader<File>, TlsError> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| TlsError::Read {
+            path: path.to_string(),
+            reason: e.to_string(),
+        })
+}
+
+fn load_certs(path: &str) -> Result<Vec<CertificateDer<'static>>, TlsError> {
+    let certs = rustls_pemfile::certs(&mut open(path)?)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| TlsError::Read {
+            path: path.to_string(),
+            reason: e.to_string(),
+        })?;
+
+    if certs.is_empty() {
+        return Err(TlsError::NoCertificates(path.to_string()));
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &str) -> Result<PrivateKeyDer<'static>, TlsError> {
+    rustls_pemfile::private_key(&mut open(path)?)
+        .map_err(|e| TlsError::Read {
+            path: path.to_string(),
+            reason: e.to_string(),
+        })?
+        .ok_or_else(|| TlsError::NoPrivateKey(path.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn pem_file(contents: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    fn tls_config(cert: &NamedTempFile, key: &NamedTempFile) -> TlsConfig {
+        TlsConfig {
+            cert_path: cert.path().to_string_lossy().into_owned(),
+            key_path: key.path().to_string_lossy().into_owned(),
+            admin_client_ca_path: None,
+            http2: true,
+        }
+    }
+
+    #[test]
+    fn test_self_signed_config_with_alpn() {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert_file = pem_file(&cert.cert.pem());
+        let key_file = pem_file(&cert.key_pair.serialize_pem());
+        let mut config = tls_config(&cert_file, &key_file);
+        config.admin_client_ca_path = Some(config.cert_path.clone());
+
+        let http = server_config(&config, TlsServer::Http).unwrap();
+        assert_eq!(http.alpn_protocols[0], ALPN_H2);
+
+        let ws = server_config(&config, TlsServer::WebSocket).unwrap();
+        assert_eq!(ws.alpn_protocols, vec![ALPN_HTTP1.to_vec()]);
+
+        // Client auth applies to the Admin server only
+        assert!(server_config(&config, TlsServer::Admin).is_ok());
+    }
+
+    #[test]
+    fn test_missing_key_rejected() {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert_file = pem_file(&cert.cert.pem());
+        let key_file = pem_file("");
+
+        assert!(matches!(
+            server_config(&tls_config(&cert_file, &key_file), TlsServer::Http),
+            Err(TlsError::NoPrivateKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_alpn_http2_disabled() {
+        assert_eq!(
+            alpn_protocols(TlsServer::Http, false),
+            vec![ALPN_HTTP1.to_vec()]
+        );
+    }
+}
@@ -0,0 +1,949 @@
+//! Contract ABI (Solidity JSON ABI) parsing and encoding.
+//!
+//! Lets clients call qc-11 contracts with nothing but an address and the
+//! contract's ABI JSON: the gateway encodes arguments into calldata, decodes
+//! return values and turns revert data into a readable reason.
+//!
+//! Values cross the JSON boundary as follows: integers as decimal strings
+//! (numbers and `0x` hex are accepted on input), addresses and bytes as `0x`
+//! hex, booleans and strings natively, arrays as JSON arrays and tuples as
+//! objects keyed by component name (positional arrays are accepted on input).
+
+use super::error::ApiError;
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha3::{Digest, Keccak256};
+use std::fmt;
+
+/// Selector of `Error(string)`, the payload of `require(.., "reason")`
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of `Panic(uint256)`, raised by failed asserts, overflow, etc.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// ABI word size in bytes
+const WORD: usize = 32;
+
+/// ABI errors
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AbiError {
+    /// ABI JSON could not be parsed
+    #[error("invalid ABI: {0}")]
+    InvalidAbi(String),
+    /// No function with that name or signature
+    #[error("function not found in ABI: {0}")]
+    UnknownFunction(String),
+    /// Overloaded name; the caller must pass a full signature
+    #[error("ambiguous function {name}, use one of: {}", .candidates.join(", "))]
+    AmbiguousFunction {
+        name: String,
+        candidates: Vec<String>,
+    },
+    /// Argument count or value does not match the function's inputs
+    #[error("invalid argument {0}")]
+    InvalidArgument(String),
+    /// Return or revert data does not match the expected types
+    #[error("cannot decode {0}")]
+    InvalidData(String),
+}
+
+impl From<AbiError> for ApiError {
+    fn from(e: AbiError) -> Self {
+        match e {
+            AbiError::InvalidData(_) => ApiError::server_error(e.to_string()),
+            _ => ApiError::invalid_params(e.to_string()),
+        }
+    }
+}
+
+/// A parameter as written in ABI JSON
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbiParam {
+    /// Parameter name (may be empty)
+    #[serde(default)]
+    pub name: String,
+    /// Solidity type, e.g. `uint256`, `address[]`, `tuple`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Tuple components
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<AbiParam>,
+}
+
+/// A decoded return value
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DecodedValue {
+    /// Output name from the ABI (may be empty)
+    pub name: String,
+    /// Canonical type
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Value in the JSON form described in the module docs
+    pub value: Value,
+}
+
+/// ABI type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamType {
+    Address,
+    Bool,
+    Uint(usize),
+    Int(usize),
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<ParamType>),
+    FixedArray(Box<ParamType>, usize),
+    Tuple(Vec<(String, ParamType)>),
+}
+
+impl ParamType {
+    /// Parse the type of an ABI parameter, including tuple components.
+    pub fn from_param(param: &AbiParam) -> Result<Self, AbiError> {
+        let kind = param.kind.trim();
+        let invalid = || AbiError::InvalidAbi(format!("unsupported type {}", kind));
+        let (base, mut suffixes) = kind.split_at(kind.find('[').unwrap_or(kind.len()));
+
+        let mut ty = match base {
+            "address" => ParamType::Address,
+            "bool" => ParamType::Bool,
+            "string" => ParamType::String,
+            "bytes" => ParamType::Bytes,
+            "tuple" => ParamType::Tuple(
+                param
+                    .components
+                    .iter()
+                    .map(|c| Ok((c.name.clone(), ParamType::from_param(c)?)))
+                    .collect::<Result<_, AbiError>>()?,
+            ),
+            _ => {
+                if let Some(bits) = base.strip_prefix("uint") {
+                    ParamType::Uint(parse_bits(bits).ok_or_else(invalid)?)
+                } else if let Some(bits) = base.strip_prefix("int") {
+                    ParamType::Int(parse_bits(bits).ok_or_else(invalid)?)
+                } else if let Some(len) = base.strip_prefix("bytes") {
+                    match len.parse() {
+                        Ok(len @ 1..=32) => ParamType::FixedBytes(len),
+                        _ => return Err(invalid()),
+                    }
+                } else {
+                    return Err(invalid());
+                }
+            }
+        };
+
+        // Suffixes apply left to right: `uint8[2][]` is a list of pairs
+        while !suffixes.is_empty() {
+            let (size, rest) = suffixes
+                .strip_prefix('[')
+                .and_then(|s| s.split_once(']'))
+                .ok_or_else(invalid)?;
+            ty = if size.is_empty() {
+                ParamType::Array(Box::new(ty))
+            } else {
+                ParamType::FixedArray(Box::new(ty), size.parse().map_err(|_| invalid())?)
+            };
+            suffixes = rest;
+        }
+
+        Ok(ty)
+    }
+
+    /// Whether the value is encoded out of line, behind an offset
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+            ParamType::FixedArray(inner, _) => inner.is_dynamic(),
+            ParamType::Tuple(components) => components.iter().any(|(_, ty)| ty.is_dynamic()),
+            _ => false,
+        }
+    }
+
+    /// Bytes taken in the head of an enclosing tuple
+    fn head_size(&self) -> usize {
+        if self.is_dynamic() {
+            return WORD;
+        }
+        match self {
+            ParamType::FixedArray(inner, len) => inner.head_size().saturating_mul(*len),
+            ParamType::Tuple(components) => components
+                .iter()
+                .fold(0, |size, (_, ty)| size.saturating_add(ty.head_size())),
+            _ => WORD,
+        }
+    }
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamType::Address => write!(f, "address"),
+            ParamType::Bool => write!(f, "bool"),
+            ParamType::Uint(bits) => write!(f, "uint{}", bits),
+            ParamType::Int(bits) => write!(f, "int{}", bits),
+            ParamType::FixedBytes(len) => write!(f, "bytes{}", len),
+            ParamType::Bytes => write!(f, "bytes"),
+            ParamType::String => write!(f, "string"),
+            ParamType::Array(inner) => write!(f, "{}[]", inner),
+            ParamType::FixedArray(inner, len) => write!(f, "{}[{}]", inner, len),
+            ParamType::Tuple(components) => {
+                let types: Vec<String> = components.iter().map(|(_, ty)| ty.to_string()).collect();
+                write!(f, "({})", types.join(","))
+            }
+        }
+    }
+}
+
+/// A callable contract function
+#[derive(Debug, Clone)]
+pub struct AbiFunction {
+    /// Function name
+    pub name: String,
+    /// Inputs as written in the ABI
+    pub inputs: Vec<AbiParam>,
+    /// Outputs as written in the ABI
+    pub outputs: Vec<AbiParam>,
+    /// `pure`, `view`, `nonpayable` or `payable`
+    pub state_mutability: String,
+    input_types: Vec<ParamType>,
+    output_types: Vec<ParamType>,
+}
+
+impl AbiFunction {
+    /// Canonical signature, e.g. `transfer(address,uint256)`
+    pub fn signature(&self) -> String {
+        signature(&self.name, &self.input_types)
+    }
+
+    /// First four bytes of the signature's Keccak-256 hash
+    pub fn selector(&self) -> [u8; 4] {
+        selector(&self.signature())
+    }
+
+    /// Whether the function can be run with `eth_call` alone
+    pub fn is_read_only(&self) -> bool {
+        matches!(self.state_mutability.as_str(), "view" | "pure")
+    }
+
+    /// Calldata for a call with `args`, one JSON value per input.
+    pub fn encode_call(&self, args: &[Value]) -> Result<Vec<u8>, AbiError> {
+        if args.len() != self.inputs.len() {
+            return Err(AbiError::InvalidArgument(format!(
+                "count: {} takes {} arguments, got {}",
+                self.signature(),
+                self.inputs.len(),
+                args.len()
+            )));
+        }
+
+        // Check each argument on its own so the error can name it
+        for (i, (ty, arg)) in self.input_types.iter().zip(args).enumerate() {
+            encode_value(ty, arg).map_err(|e| {
+                let name = match self.inputs[i].name.as_str() {
+                    "" => i.to_string(),
+                    name => name.to_string(),
+                };
+                AbiError::InvalidArgument(format!("{} ({}): {}", name, ty, e))
+            })?;
+        }
+
+        let types: Vec<&ParamType> = self.input_types.iter().collect();
+        let values: Vec<&Value> = args.iter().collect();
+        let mut data = self.selector().to_vec();
+        data.extend(encode_params(&types, &values).map_err(AbiError::InvalidArgument)?);
+        Ok(data)
+    }
+
+    /// Decode the data returned by a call.
+    pub fn decode_output(&self, data: &[u8]) -> Result<Vec<DecodedValue>, AbiError> {
+        if data.is_empty() && !self.output_types.is_empty() {
+            return Err(AbiError::InvalidData(
+                "empty return data (is there a contract at this address?)".to_string(),
+            ));
+        }
+
+        let types: Vec<&ParamType> = self.output_types.iter().collect();
+        let values = decode_params(&types, data).map_err(AbiError::InvalidData)?;
+        Ok(self
+            .outputs
+            .iter()
+            .zip(&self.output_types)
+            .zip(values)
+            .map(|((param, ty), value)| DecodedValue {
+                name: param.name.clone(),
+                kind: ty.to_string(),
+                value,
+            })
+            .collect())
+    }
+}
+
+/// A custom error declared in the ABI (`error InsufficientBalance(...)`)
+#[derive(Debug, Clone)]
+struct AbiErrorDef {
+    name: String,
+    inputs: Vec<AbiParam>,
+    input_types: Vec<ParamType>,
+}
+
+/// One entry of ABI JSON
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AbiEntry {
+    #[serde(rename = "type", default = "default_entry_type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    inputs: Vec<AbiParam>,
+    #[serde(default)]
+    outputs: Vec<AbiParam>,
+    state_mutability: Option<String>,
+    // Pre-0.5 compilers emit these instead of stateMutability
+    constant: Option<bool>,
+    payable: Option<bool>,
+}
+
+fn default_entry_type() -> String {
+    "function".to_string()
+}
+
+/// Functions and custom errors of a contract
+#[derive(Debug, Clone, Default)]
+pub struct ContractAbi {
+    functions: Vec<AbiFunction>,
+    errors: Vec<AbiErrorDef>,
+}
+
+impl ContractAbi {
+    /// Parse ABI JSON.
+    ///
+    /// Accepts the ABI array, a compiler artifact with an `abi` field, or
+    /// either of those as a JSON string (as pasted from a file).
+    pub fn from_json(abi: &Value) -> Result<Self, AbiError> {
+        let parsed;
+        let abi = match abi {
+            Value::String(text) => {
+                parsed = serde_json::from_str::<Value>(text)
+                    .map_err(|e| AbiError::InvalidAbi(e.to_string()))?;
+                &parsed
+            }
+            other => other,
+        };
+        let entries = match abi {
+            Value::Object(artifact) => artifact.get("abi").ok_or_else(|| {
+                AbiError::InvalidAbi("expected an array or an `abi` field".into())
+            })?,
+            other => other,
+        };
+        let entries: Vec<AbiEntry> = serde_json::from_value(entries.clone())
+            .map_err(|e| AbiError::InvalidAbi(e.to_string()))?;
+
+        let mut contract = ContractAbi::default();
+        for entry in entries {
+            let input_types = parse_types(&entry.inputs)?;
+            match entry.kind.as_str() {
+                "function" => {
+                    let state_mutability = entry.state_mutability.unwrap_or_else(|| {
+                        match (entry.constant, entry.payable) {
+                            (Some(true), _) => "view",
+                            (_, Some(true)) => "payable",
+                            _ => "nonpayable",
+                        }
+                        .to_string()
+                    });
+                    contract.functions.push(AbiFunction {
+                        output_types: parse_types(&entry.outputs)?,
+                        name: entry.name,
+                        inputs: entry.inputs,
+                        outputs: entry.outputs,
+                        state_mutability,
+                        input_types,
+                    });
+                }
+                "error" => contract.errors.push(AbiErrorDef {
+                    name: entry.name,
+                    inputs: entry.inputs,
+                    input_types,
+                }),
+                // Constructors, events, fallback and receive are not callable by name
+                _ => {}
+            }
+        }
+
+        Ok(contract)
+    }
+
+    /// All functions, in ABI order.
+    pub fn functions(&self) -> &[AbiFunction] {
+        &self.functions
+    }
+
+    /// Find a function by name, or by full signature when it is overloaded.
+    pub fn function(&self, name_or_signature: &str) -> Result<&AbiFunction, AbiError> {
+        let wanted: String = name_or_signature
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+
+        let candidates: Vec<&AbiFunction> = if wanted.contains('(') {
+            self.functions
+                .iter()
+                .filter(|f| f.signature() == wanted)
+                .collect()
+        } else {
+            self.functions.iter().filter(|f| f.name == wanted).collect()
+        };
+
+        match candidates.as_slice() {
+            [] => Err(AbiError::UnknownFunction(wanted)),
+            [function] => Ok(function),
+            overloads => Err(AbiError::AmbiguousFunction {
+                name: wanted,
+                candidates: overloads.iter().map(|f| f.signature()).collect(),
+            }),
+        }
+    }
+
+    /// Human-readable reason for revert data, if it is recognised.
+    ///
+    /// Covers `Error(string)`, `Panic(uint256)` and the custom errors
+    /// declared in this ABI.
+    pub fn decode_revert(&self, data: &[u8]) -> Option<String> {
+        let (prefix, payload) = (data.get(..4)?, &data[4..]);
+
+        if prefix == ERROR_SELECTOR {
+            return match decode_params(&[&ParamType::String], payload).ok()?.pop()? {
+                Value::String(reason) => Some(reason),
+                _ => None,
+            };
+        }
+        if prefix == PANIC_SELECTOR {
+            let code = U256::from_big_endian(payload.get(..WORD)?);
+            return Some(format!(
+                "panic 0x{:02x} ({})",
+                code.low_u64(),
+                panic_reason(code)
+            ));
+        }
+
+        let error = self
+            .errors
+            .iter()
+            .find(|e| prefix == selector(&signature(&e.name, &e.input_types)))?;
+        let types: Vec<&ParamType> = error.input_types.iter().collect();
+        let values = decode_params(&types, payload).ok()?;
+        let args: Vec<String> = error
+            .inputs
+            .iter()
+            .zip(values)
+            .map(|(param, value)| {
+                let value = match value {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                match param.name.as_str() {
+                    "" => value,
+                    name => format!("{}={}", name, value),
+                }
+            })
+            .collect();
+        Some(format!("{}({})", error.name, args.join(", ")))
+    }
+}
+
+/// Solidity panic codes
+fn panic_reason(code: U256) -> &'static str {
+    match code.low_u64() {
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "invalid storage byte array",
+        0x31 => "pop on empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to uninitialized function",
+        _ => "unknown panic",
+    }
+}
+
+fn parse_bits(bits: &str) -> Option<usize> {
+    match bits {
+        "" => Some(256),
+        _ => bits
+            .parse()
+            .ok()
+            .filter(|bits| (8..=256).contains(bits) && bits % 8 == 0),
+    }
+}
+
+fn parse_types(params: &[AbiParam]) -> Result<Vec<ParamType>, AbiError> {
+    params.iter().map(ParamType::from_param).collect()
+}
+
+fn signature(name: &str, types: &[ParamType]) -> String {
+    let types: Vec<String> = types.iter().map(ToString::to_string).collect();
+    format!("{}({})", name, types.join(","))
+}
+
+/// First four bytes of the Keccak-256 hash of a signature
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = Keccak256::digest(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// ENCODING
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn encode_params(types: &[&ParamType], values: &[&Value]) -> Result<Vec<u8>, String> {
+    let head_size = types.iter().map(|ty| ty.head_size()).sum::<usize>();
+    let mut head = Vec::with_capacity(head_size);
+    let mut tail = Vec::new();
+
+    for (ty, value) in types.iter().zip(values) {
+        let encoded = encode_value(ty, value)?;
+        if ty.is_dynamic() {
+            head.extend_from_slice(&uint_word(U256::from(head_size + tail.len())));
+            tail.extend(encoded);
+        } else {
+            head.extend(encoded);
+        }
+    }
+
+    head.extend(tail);
+    Ok(head)
+}
+
+fn encode_value(ty: &ParamType, value: &Value) -> Result<Vec<u8>, String> {
+    match ty {
+        ParamType::Address => {
+            let address = parse_hex(value, Some(20))?;
+            let mut word = [0u8; WORD];
+            word[12..].copy_from_slice(&address);
+            Ok(word.to_vec())
+        }
+        ParamType::Bool => match value {
+            Value::Bool(b) => Ok(uint_word(U256::from(*b as u8)).to_vec()),
+            Value::String(s) if s == "true" || s == "false" => {
+                Ok(uint_word(U256::from((s == "true") as u8)).to_vec())
+            }
+            _ => Err("expected true or false".to_string()),
+        },
+        ParamType::Uint(bits) => {
+            let (negative, magnitude) = parse_integer(value)?;
+            if (negative && !magnitude.is_zero()) || magnitude.bits() > *bits {
+                return Err(format!("out of range for uint{}", bits));
+            }
+            Ok(uint_word(magnitude).to_vec())
+        }
+        ParamType::Int(bits) => {
+            let (negative, magnitude) = parse_integer(value)?;
+            let limit = U256::one() << (bits - 1);
+            if (negative && magnitude > limit) || (!negative && magnitude >= limit) {
+                return Err(format!("out of range for int{}", bits));
+            }
+            let word = if negative {
+                (!magnitude).overflowing_add(U256::one()).0
+            } else {
+                magnitude
+            };
+            Ok(uint_word(word).to_vec())
+        }
+        ParamType::FixedBytes(len) => {
+            let mut word = parse_hex(value, Some(*len))?;
+            word.resize(WORD, 0);
+            Ok(word)
+        }
+        ParamType::Bytes => Ok(encode_bytes(&parse_hex(value, None)?)),
+        ParamType::String => match value {
+            Value::String(s) => Ok(encode_bytes(s.as_bytes())),
+            _ => Err("expected a string".to_string()),
+        },
+        ParamType::Array(inner) => {
+            let items = value.as_array().ok_or("expected an array")?;
+            let mut encoded = uint_word(U256::from(items.len())).to_vec();
+            encoded.extend(encode_params(
+                &vec![inner.as_ref(); items.len()],
+                &items.iter().collect::<Vec<_>>(),
+            )?);
+            Ok(encoded)
+        }
+        ParamType::FixedArray(inner, len) => {
+            let items = value.as_array().ok_or("expected an array")?;
+            if items.len() != *len {
+                return Err(format!("expected {} items, got {}", len, items.len()));
+            }
+            encode_params(
+                &vec![inner.as_ref(); *len],
+                &items.iter().collect::<Vec<_>>(),
+            )
+        }
+        ParamType::Tuple(components) => {
+            let values: Vec<&Value> = match value {
+                Value::Array(items) if items.len() == components.len() => items.iter().collect(),
+                Value::Object(fields) => components
+                    .iter()
+                    .map(|(name, _)| {
+                        fields
+                            .get(name)
+                            .ok_or_else(|| format!("missing field {}", name))
+                    })
+                    .collect::<Result<_, _>>()?,
+                _ => {
+                    return Err(format!(
+                        "expected an object or an array of {} items",
+                        components.len()
+                    ))
+                }
+            };
+            let types: Vec<&ParamType> = components.iter().map(|(_, ty)| ty).collect();
+            encode_params(&types, &values)
+        }
+    }
+}
+
+fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    let padded = bytes.len().div_ceil(WORD) * WORD;
+    let mut encoded = uint_word(U256::from(bytes.len())).to_vec();
+    encoded.extend_from_slice(bytes);
+    encoded.resize(WORD + padded, 0);
+    encoded
+}
+
+fn uint_word(value: U256) -> [u8; WORD] {
+    let mut word = [0u8; WORD];
+    value.to_big_endian(&mut word);
+    word
+}
+
+/// Sign and magnitude of an integer given as a number or a string
+fn parse_integer(value: &Value) -> Result<(bool, U256), String> {
+    match value {
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                Ok((false, U256::from(u)))
+            } else if let Some(i) = n.as_i64() {
+                Ok((i < 0, U256::from(i.unsigned_abs())))
+            } else {
+                Err("expected an integer (pass large values as strings)".to_string())
+            }
+        }
+        Value::String(s) => {
+            let s = s.trim();
+            let (negative, digits) = match s.strip_prefix('-') {
+                Some(digits) => (true, digits),
+                None => (false, s),
+            };
+            let magnitude = match digits.strip_prefix("0x") {
+                Some(hex) if !hex.is_empty() => U256::from_str_radix(hex, 16).ok(),
+                None if !digits.is_empty() => U256::from_dec_str(digits).ok(),
+                _ => None,
+            };
+            magnitude
+                .map(|m| (negative, m))
+                .ok_or_else(|| format!("invalid integer {}", s))
+        }
+        _ => Err("expected an integer".to_string()),
+    }
+}
+
+fn parse_hex(value: &Value, len: Option<usize>) -> Result<Vec<u8>, String> {
+    let s = value.as_str().ok_or("expected a 0x-prefixed hex string")?;
+    let bytes =
+        hex::decode(s.strip_prefix("0x").unwrap_or(s)).map_err(|_| format!("invalid hex {}", s))?;
+    match len {
+        Some(len) if bytes.len() != len => {
+            Err(format!("expected {} bytes, got {}", len, bytes.len()))
+        }
+        _ => Ok(bytes),
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// DECODING
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn decode_params(types: &[&ParamType], data: &[u8]) -> Result<Vec<Value>, String> {
+    let mut values = Vec::with_capacity(types.len());
+    let mut head = 0;
+
+    for ty in types {
+        let value = if ty.is_dynamic() {
+            let offset = read_length(data, head)?;
+            decode_value(ty, &data[offset..])?
+        } else {
+            decode_value(ty, data.get(head..).unwrap_or_default())?
+        };
+        values.push(value);
+        head += ty.head_size();
+    }
+
+    Ok(values)
+}
+
+fn decode_value(ty: &ParamType, data: &[u8]) -> Result<Value, String> {
+    match ty {
+        ParamType::Address => Ok(Value::String(format!(
+            "0x{}",
+            hex::encode(&read_word(data, 0)?[12..])
+        ))),
+        ParamType::Bool => match read_uint(data, 0)?.low_u64() {
+            0 => Ok(Value::Bool(false)),
+            1 => Ok(Value::Bool(true)),
+            _ => Err("bool: value is not 0 or 1".to_string()),
+        },
+        ParamType::Uint(bits) => {
+            let value = read_uint(data, 0)?;
+            if value.bits() > *bits {
+                return Err(format!("uint{}: value out of range", bits));
+            }
+            Ok(Value::String(value.to_string()))
+        }
+        ParamType::Int(bits) => {
+            let word = read_uint(data, 0)?;
+            let limit = U256::one() << (bits - 1);
+            if word.bit(255) {
+                let magnitude = (!word).overflowing_add(U256::one()).0;
+                if magnitude > limit {
+                    return Err(format!("int{}: value out of range", bits));
+                }
+                Ok(Value::String(format!("-{}", magnitude)))
+            } else if word >= limit {
+                Err(format!("int{}: value out of range", bits))
+            } else {
+                Ok(Value::String(word.to_string()))
+            }
+        }
+        ParamType::FixedBytes(len) => Ok(Value::String(format!(
+            "0x{}",
+            hex::encode(&read_word(data, 0)?[..*len])
+        ))),
+        ParamType::Bytes => Ok(Value::String(format!(
+            "0x{}",
+            hex::encode(read_bytes(data)?)
+        ))),
+        ParamType::String => Ok(Value::String(
+            String::from_utf8_lossy(read_bytes(data)?).into_owned(),
+        )),
+        ParamType::Array(inner) => {
+            let len = read_length(data, 0)?;
+            // Every element takes at least a word, which bounds the allocation
+            if len > data.len() / WORD {
+                return Err(format!("{}: length {} exceeds data", ty, len));
+            }
+            decode_params(&vec![inner.as_ref(); len], &data[WORD..]).map(Value::Array)
+        }
+        ParamType::FixedArray(inner, len) => {
+            if inner.head_size().saturating_mul(*len) > data.len() {
+                return Err(format!("{}: data too short", ty));
+            }
+            decode_params(&vec![inner.as_ref(); *len], data).map(Value::Array)
+        }
+        ParamType::Tuple(components) => {
+            let types: Vec<&ParamType> = components.iter().map(|(_, ty)| ty).collect();
+            let values = decode_params(&types, data)?;
+            if components.iter().any(|(name, _)| name.is_empty()) {
+                return Ok(Value::Array(values));
+            }
+            let fields: Map<String, Value> = components
+                .iter()
+                .map(|(name, _)| name.clone())
+                .zip(values)
+                .collect();
+            Ok(Value::Object(fields))
+        }
+    }
+}
+
+fn read_word(data: &[u8], at: usize) -> Result<&[u8], String> {
+    data.get(at..at + WORD)
+        .ok_or_else(|| "data too short".to_string())
+}
+
+fn read_uint(data: &[u8], at: usize) -> Result<U256, String> {
+    read_word(data, at).map(U256::from_big_endian)
+}
+
+/// An offset or length, which cannot point past the data
+fn read_length(data: &[u8], at: usize) -> Result<usize, String> {
+    let value = read_uint(data, at)?;
+    if value > U256::from(data.len()) {
+        return Err("offset or length out of range".to_string());
+    }
+    Ok(value.as_usize())
+}
+
+fn read_bytes(data: &[u8]) -> Result<&[u8], String> {
+    let len = read_length(data, 0)?;
+    data.get(WORD..WORD + len)
+        .ok_or_else(|| "data too short".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn erc20() -> ContractAbi {
+        ContractAbi::from_json(&json!([
+            {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+             "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+             "outputs": [{"name": "", "type": "bool"}]},
+            {"type": "function", "name": "balanceOf", "stateMutability": "view",
+             "inputs": [{"name": "owner", "type": "address"}],
+             "outputs": [{"name": "balance", "type": "uint256"}]},
+            {"type": "function", "name": "mint", "inputs": [{"name": "amount", "type": "uint256"}]},
+            {"type": "function", "name": "mint",
+             "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}]},
+            {"type": "event", "name": "Transfer", "inputs": []},
+            {"type": "error", "name": "InsufficientBalance",
+             "inputs": [{"name": "available", "type": "uint256"}, {"name": "required", "type": "uint256"}]}
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_encode_transfer() {
+        let abi = erc20();
+        let transfer = abi.function("transfer").unwrap();
+        assert_eq!(transfer.signature(), "transfer(address,uint256)");
+        assert_eq!(transfer.selector(), [0xa9, 0x05, 0x9c, 0xbb]);
+        assert!(!transfer.is_read_only());
+        assert!(abi.function("balanceOf").unwrap().is_read_only());
+
+        let data = transfer
+            .encode_call(&[
+                json!("0x00000000000000000000000000000000000000aa"),
+                json!("1000"),
+            ])
+            .unwrap();
+        assert_eq!(
+            hex::encode(data),
+            "a9059cbb\
+             00000000000000000000000000000000000000000000000000000000000000aa\
+             00000000000000000000000000000000000000000000000000000000000003e8"
+        );
+    }
+
+    #[test]
+    fn test_encode_dynamic_types() {
+        // The worked example from the Solidity ABI specification
+        let abi = ContractAbi::from_json(&json!([{"name": "f", "type": "function", "inputs": [
+            {"type": "uint256"}, {"type": "uint32[]"}, {"type": "bytes10"}, {"type": "bytes"}
+        ]}]))
+        .unwrap();
+        let f = abi.function("f").unwrap();
+        let data = f
+            .encode_call(&[
+                json!("0x123"),
+                json!([1110, "0x789"]),
+                json!("0x31323334353637383930"),
+                json!("0x48656c6c6f2c20776f726c6421"),
+            ])
+            .unwrap();
+        assert_eq!(
+            hex::encode(data),
+            "8be65246\
+             0000000000000000000000000000000000000000000000000000000000000123\
+             0000000000000000000000000000000000000000000000000000000000000080\
+             3132333435363738393000000000000000000000000000000000000000000000\
+             00000000000000000000000000000000000000000000000000000000000000e0\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000456\
+             0000000000000000000000000000000000000000000000000000000000000789\
+             000000000000000000000000000000000000000000000000000000000000000d\
+             48656c6c6f2c20776f726c642100000000000000000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        let abi = ContractAbi::from_json(&json!([{"name": "g", "type": "function",
+            "inputs": [
+                {"name": "delta", "type": "int16"},
+                {"name": "order", "type": "tuple", "components": [
+                    {"name": "owner", "type": "address"},
+                    {"name": "tags", "type": "string[]"}
+                ]}
+            ],
+            "outputs": [
+                {"name": "delta", "type": "int16"},
+                {"name": "order", "type": "tuple", "components": [
+                    {"name": "owner", "type": "address"},
+                    {"name": "tags", "type": "string[]"}
+                ]}
+            ]
+        }]))
+        .unwrap();
+        let g = abi.function("g(int16,(address,string[]))").unwrap();
+        let order =
+            json!({"owner": "0x00000000000000000000000000000000000000bb", "tags": ["a", "bc"]});
+        let data = g.encode_call(&[json!(-300), order.clone()]).unwrap();
+
+        let outputs = g.decode_output(&data[4..]).unwrap();
+        assert_eq!(outputs[0].value, json!("-300"));
+        assert_eq!(outputs[0].kind, "int16");
+        assert_eq!(outputs[1].name, "order");
+        assert_eq!(outputs[1].value, order);
+    }
+
+    #[test]
+    fn test_argument_errors() {
+        let abi = erc20();
+        let transfer = abi.function("transfer").unwrap();
+
+        let err = transfer.encode_call(&[json!("0xaa")]).unwrap_err();
+        assert!(err.to_string().contains("takes 2 arguments"));
+
+        let err = transfer
+            .encode_call(&[
+                json!("0x00000000000000000000000000000000000000aa"),
+                json!("-1"),
+            ])
+            .unwrap_err();
+        assert!(err.to_string().contains("amount (uint256): out of range"));
+
+        assert!(matches!(
+            abi.function("mint"),
+            Err(AbiError::AmbiguousFunction { ref candidates, .. }) if candidates.len() == 2
+        ));
+        assert!(abi.function("mint(uint256)").is_ok());
+        assert!(matches!(
+            abi.function("burn"),
+            Err(AbiError::UnknownFunction(_))
+        ));
+        assert!(
+            ContractAbi::from_json(&json!([{"name": "h", "inputs": [{"type": "uint7"}]}])).is_err()
+        );
+    }
+
+    #[test]
+    fn test_decode_revert_reasons() {
+        let abi = erc20();
+
+        let mut error = ERROR_SELECTOR.to_vec();
+        error.extend(encode_params(&[&ParamType::String], &[&json!("not owner")]).unwrap());
+        assert_eq!(abi.decode_revert(&error).as_deref(), Some("not owner"));
+
+        let mut panic = PANIC_SELECTOR.to_vec();
+        panic.extend(uint_word(U256::from(0x11)));
+        assert_eq!(
+            abi.decode_revert(&panic).as_deref(),
+            Some("panic 0x11 (arithmetic overflow or underflow)")
+        );
+
+        let mut custom = selector("InsufficientBalance(uint256,uint256)").to_vec();
+        custom.extend(uint_word(U256::from(5)));
+        custom.extend(uint_word(U256::from(9)));
+        assert_eq!(
+            abi.decode_revert(&custom).as_deref(),
+            Some("InsufficientBalance(available=5, required=9)")
+        );
+
+        assert_eq!(abi.decode_revert(&[0xde, 0xad, 0xbe, 0xef]), None);
+    }
+}
@@ -3,8 +3,10 @@
 //! Configuration follows SPEC-16 Section 10.
 
 use serde::{Deserialize, Serialize};
+use shared_types::SecretBytes;
 use std::collections::HashSet;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// Main gateway configuration
@@ -33,6 +35,22 @@ pub struct GatewayConfig {
     pub methods: MethodsConfig,
     /// Circuit breaker configuration for downstream resilience
     pub circuit_breaker: CircuitBreakerConfig,
+    /// IPC retry and hedging policy
+    pub ipc_retry: IpcRetryConfig,
+    /// Admin readiness probe thresholds
+    pub health: HealthConfig,
+    /// Request metrics configuration
+    pub metrics: MetricsConfig,
+    /// eth_sendRawTransaction pre-flight simulation
+    pub preflight: PreflightConfig,
+    /// Polling filter configuration (eth_newFilter)
+    pub filters: FilterConfig,
+    /// External block producers (qc_getBlockTemplate)
+    pub block_templates: BlockTemplateConfig,
+    /// Scoped API key store
+    pub api_keys: ApiKeysConfig,
+    /// GraphQL server configuration (requires the `graphql` feature)
+    pub graphql: GraphQlConfig,
     /// TLS configuration (optional)
     pub tls: Option<TlsConfig>,
 }
@@ -41,7 +59,10 @@ impl GatewayConfig {
     /// Validate configuration
     pub fn validate(&self) -> Result<(), ConfigError> {
         // Validate ports are different
-        let ports = [self.http.port, self.websocket.port, self.admin.port];
+        let mut ports = vec![self.http.port, self.websocket.port, self.admin.port];
+        if self.graphql.enabled {
+            ports.push(self.graphql.port);
+        }
         let unique_ports: HashSet<_> = ports.iter().collect();
         if unique_ports.len() != ports.len() {
             return Err(ConfigError::DuplicatePorts);
@@ -61,18 +82,48 @@ impl GatewayConfig {
             ));
         }
 
+        if self.limits.log_chunk_blocks == 0 || self.limits.max_log_results == 0 {
+            return Err(ConfigError::InvalidLimit(
+                "log_chunk_blocks and max_log_results cannot be 0".into(),
+            ));
+        }
+
         if self.limits.max_batch_size == 0 {
             return Err(ConfigError::InvalidLimit(
                 "max_batch_size cannot be 0".into(),
             ));
         }
 
+        if self.health.max_pending_requests == 0 {
+            return Err(ConfigError::InvalidLimit(
+                "health.max_pending_requests cannot be 0".into(),
+            ));
+        }
+
         // Validate timeouts
         if self.timeouts.default.as_millis() == 0 {
             return Err(ConfigError::InvalidTimeout(
                 "default timeout cannot be 0".into(),
             ));
         }
+        // Long polls must answer before the method timeout cuts them off
+        if self.block_templates.long_poll_timeout
+            >= crate::domain::methods::get_method_timeout("qc_getBlockTemplate")
+        {
+            return Err(ConfigError::InvalidTimeout(
+                "block_templates.long_poll_timeout must be below the qc_getBlockTemplate timeout"
+                    .into(),
+            ));
+        }
+
+        // Validate TLS paths
+        if let Some(tls) = &self.tls {
+            if tls.cert_path.is_empty() || tls.key_path.is_empty() {
+                return Err(ConfigError::Invalid(
+                    "tls requires cert_path and key_path".into(),
+                ));
+            }
+        }
 
         Ok(())
     }
@@ -91,6 +142,11 @@ impl GatewayConfig {
     pub fn admin_addr(&self) -> SocketAddr {
         SocketAddr::new(self.admin.host, self.admin.port)
     }
+
+    /// Get GraphQL server bind address
+    pub fn graphql_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.graphql.host, self.graphql.port)
+    }
 }
 
 /// HTTP server configuration
@@ -136,8 +192,23 @@ pub struct WebSocketConfig {
     /// Ping interval
     #[serde(with = "humantime_serde")]
     pub ping_interval: Duration,
+    /// Close connections that send nothing (pongs included) for this long
+    #[serde(with = "humantime_serde")]
+    pub idle_timeout: Duration,
+    /// Max connection lifetime ("0s" = unlimited)
+    #[serde(with = "humantime_serde")]
+    pub max_lifetime: Duration,
     /// Message buffer size
     pub message_buffer_size: usize,
+    /// Evict a connection after this many dropped notifications (0 = never)
+    pub slow_consumer_drop_limit: u64,
+    /// Keep a closed connection's subscriptions resumable this long ("0s" = off)
+    #[serde(with = "humantime_serde")]
+    pub resume_window: Duration,
+    /// Notifications buffered per parked connection and replayed on resume
+    pub resume_replay_limit: usize,
+    /// Node log lines kept for `/logs` backlogs (0 = none)
+    pub log_history: usize,
 }
 
 impl Default for WebSocketConfig {
@@ -149,7 +220,26 @@ impl Default for WebSocketConfig {
             max_connections_per_ip: 10,
             max_subscriptions_per_connection: 100,
             ping_interval: Duration::from_secs(30),
+            idle_timeout: Duration::from_secs(90),
+            max_lifetime: Duration::ZERO,
             message_buffer_size: 1024,
+            slow_consumer_drop_limit: 1024,
+            resume_window: Duration::from_secs(60),
+            resume_replay_limit: 256,
+            log_history: 1000,
+        }
+    }
+}
+
+impl WebSocketConfig {
+    /// Convert to the per-connection handler config
+    pub fn to_handler_config(&self) -> crate::ws::WebSocketConfig {
+        crate::ws::WebSocketConfig {
+            ping_interval: self.ping_interval,
+            idle_timeout: self.idle_timeout,
+            notification_buffer: self.message_buffer_size,
+            max_lifetime: (!self.max_lifetime.is_zero()).then_some(self.max_lifetime),
+            ..Default::default()
         }
     }
 }
@@ -165,9 +255,16 @@ pub struct AdminConfig {
     /// Enable admin server
     pub enabled: bool,
     /// Required API key (None = no auth required, only localhost check)
-    pub api_key: Option<String>,
+    #[serde(skip_serializing)]
+    pub api_key: Option<SecretBytes>,
     /// Allow non-localhost connections (DANGER)
     pub allow_external: bool,
+    /// Hex JWT secret file (Engine API style `jwt.hex`); when set, the admin
+    /// port requires an HS256 bearer token and also serves JSON-RPC
+    pub jwt_secret_file: Option<PathBuf>,
+    /// Tolerated difference between a token's `iat` and the local clock
+    #[serde(with = "humantime_serde")]
+    pub jwt_clock_skew: Duration,
 }
 
 impl Default for AdminConfig {
@@ -178,6 +275,8 @@ impl Default for AdminConfig {
             enabled: true,
             api_key: None,
             allow_external: false,
+            jwt_secret_file: None,
+            jwt_clock_skew: Duration::from_secs(60),
         }
     }
 }
@@ -228,6 +327,8 @@ pub struct LimitsConfig {
     pub max_log_block_range: u64,
     /// Max results for eth_getLogs
     pub max_log_results: usize,
+    /// Blocks per qc-03 query when streaming eth_getLogs
+    pub log_chunk_blocks: u64,
 }
 
 impl Default for LimitsConfig {
@@ -238,6 +339,7 @@ impl Default for LimitsConfig {
             max_response_size: 10 * 1024 * 1024, // 10MB
             max_log_block_range: 10_000,
             max_log_results: 10_000,
+            log_chunk_blocks: 1_000,
         }
     }
 }
@@ -335,13 +437,23 @@ impl Default for ChainConfig {
     }
 }
 
-/// TLS configuration
+/// TLS configuration, applied to the HTTP, WebSocket, Admin, and GraphQL servers
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TlsConfig {
-    /// Path to certificate file
+    /// Path to certificate file (PEM, leaf first)
     pub cert_path: String,
-    /// Path to key file
+    /// Path to key file (PEM, PKCS#8, PKCS#1 or SEC1)
     pub key_path: String,
+    /// CA bundle for Admin client certificates (None = no client auth)
+    #[serde(default)]
+    pub admin_client_ca_path: Option<String>,
+    /// Offer HTTP/2 via ALPN (the WebSocket server always uses HTTP/1.1)
+    #[serde(default = "default_http2")]
+    pub http2: bool,
+}
+
+fn default_http2() -> bool {
+    true
 }
 
 /// Security configuration per SPEC-16 Section 7.5
@@ -426,6 +538,219 @@ impl CircuitBreakerConfig {
     }
 }
 
+/// IPC retry configuration.
+///
+/// Each request's timeout is shared across its attempts. Read requests that
+/// are slow to answer can be hedged with a duplicate send; writes are only
+/// retried when the send itself failed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IpcRetryConfig {
+    /// Attempts per request, including the first (1 disables retries)
+    pub max_attempts: u32,
+    /// Backoff before the first retry (in milliseconds), doubled per retry
+    pub backoff_base_ms: u64,
+    /// Upper bound on a single backoff (in milliseconds)
+    pub backoff_max_ms: u64,
+    /// Hedge read requests unanswered after this long (in milliseconds, 0 disables)
+    pub hedge_after_ms: u64,
+}
+
+impl Default for IpcRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 2,
+            backoff_base_ms: 50,
+            backoff_max_ms: 1000,
+            hedge_after_ms: 250,
+        }
+    }
+}
+
+impl IpcRetryConfig {
+    /// Convert to the IPC handler's retry policy
+    pub fn to_policy(&self) -> crate::ipc::RetryPolicy {
+        crate::ipc::RetryPolicy {
+            max_attempts: self.max_attempts.max(1),
+            backoff_base: Duration::from_millis(self.backoff_base_ms),
+            backoff_max: Duration::from_millis(self.backoff_max_ms),
+            hedge_after: (self.hedge_after_ms > 0)
+                .then(|| Duration::from_millis(self.hedge_after_ms)),
+        }
+    }
+}
+
+/// eth_sendRawTransaction pre-flight configuration.
+///
+/// Callers opt in per request with `{"preflight": true}` as the second
+/// parameter; this sets the default when they don't say.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreflightConfig {
+    /// Simulate every raw transaction unless the caller opts out
+    pub enabled_by_default: bool,
+}
+
+/// Request metrics configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    /// Log requests slower than this (in milliseconds, 0 disables)
+    pub slow_request_threshold_ms: u64,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            slow_request_threshold_ms: 1_000,
+        }
+    }
+}
+
+impl MetricsConfig {
+    /// Slow-request log threshold, if enabled
+    pub fn slow_request_threshold(&self) -> Option<Duration> {
+        (self.slow_request_threshold_ms > 0)
+            .then(|| Duration::from_millis(self.slow_request_threshold_ms))
+    }
+}
+
+/// Readiness probe configuration (Admin `/health/ready`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthConfig {
+    /// Pending requests at which the gateway reports not ready
+    pub max_pending_requests: usize,
+    /// Report a failing subsystem unresponsive after this long without a response
+    #[serde(with = "humantime_serde")]
+    pub stale_after: Duration,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            max_pending_requests: 5_000,
+            stale_after: Duration::from_secs(30),
+        }
+    }
+}
+
+impl HealthConfig {
+    /// Convert to the adapter ReadinessThresholds
+    pub fn to_thresholds(&self) -> crate::adapters::health::ReadinessThresholds {
+        crate::adapters::health::ReadinessThresholds {
+            max_pending: self.max_pending_requests,
+            stale_after: self.stale_after,
+        }
+    }
+}
+
+/// External block producer configuration (qc_getBlockTemplate, qc_submitBlock)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlockTemplateConfig {
+    /// Reuse a fetched template for this long while the head is unchanged
+    #[serde(with = "humantime_serde")]
+    pub cache_ttl: Duration,
+    /// Longest a long-polling request waits for a new head
+    #[serde(with = "humantime_serde")]
+    pub long_poll_timeout: Duration,
+}
+
+impl Default for BlockTemplateConfig {
+    fn default() -> Self {
+        Self {
+            cache_ttl: Duration::from_secs(5),
+            long_poll_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Polling filter configuration (eth_newFilter, eth_getFilterChanges)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilterConfig {
+    /// Max installed filters across all clients
+    pub max_filters: usize,
+    /// Max buffered changes per filter before the oldest are dropped
+    pub max_changes_per_filter: usize,
+    /// Uninstall filters not polled within this window
+    #[serde(with = "humantime_serde")]
+    pub idle_timeout: Duration,
+}
+
+impl Default for FilterConfig {
+    fn default() -> Self {
+        Self {
+            max_filters: 10_000,
+            max_changes_per_filter: 10_000,
+            idle_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+impl FilterConfig {
+    /// Convert to the adapter FilterLimits
+    pub fn to_limits(&self) -> crate::adapters::filters::FilterLimits {
+        crate::adapters::filters::FilterLimits {
+            max_filters: self.max_filters,
+            max_changes_per_filter: self.max_changes_per_filter,
+            idle_timeout: self.idle_timeout,
+        }
+    }
+}
+
+/// Scoped API key store configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiKeysConfig {
+    /// JSON key file (None = keys live in memory and are lost on restart)
+    pub file: Option<PathBuf>,
+    /// How often to check the key file for hand edits
+    #[serde(with = "humantime_serde")]
+    pub reload_interval: Duration,
+}
+
+impl Default for ApiKeysConfig {
+    fn default() -> Self {
+        Self {
+            file: None,
+            reload_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// GraphQL server configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphQlConfig {
+    /// Bind address
+    pub host: IpAddr,
+    /// Port (default: 8547)
+    pub port: u16,
+    /// Enable GraphQL server (off by default)
+    pub enabled: bool,
+    /// Max query cost; list fields cost their length times their selection
+    pub max_complexity: usize,
+    /// Max selection nesting depth
+    pub max_depth: usize,
+    /// Max blocks in a single `blocks(from, to)` range
+    pub max_block_range: u64,
+}
+
+impl Default for GraphQlConfig {
+    fn default() -> Self {
+        Self {
+            host: IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)),
+            port: 8547,
+            enabled: false,
+            max_complexity: 2_000,
+            max_depth: 8,
+            max_block_range: 100,
+        }
+    }
+}
+
 /// Configuration errors
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum ConfigError {
@@ -521,6 +846,19 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_graphql_port_checked_only_when_enabled() {
+        let mut config = GatewayConfig::default();
+        config.graphql.port = config.http.port;
+        assert!(config.validate().is_ok());
+
+        config.graphql.enabled = true;
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::DuplicatePorts)
+        ));
+    }
+
     #[test]
     fn test_config_addresses() {
         let config = GatewayConfig::default();
@@ -529,6 +867,17 @@ mod tests {
         assert_eq!(config.admin_addr().port(), 8080);
     }
 
+    #[test]
+    fn test_admin_api_key_is_redacted() {
+        let admin: AdminConfig = serde_json::from_str(r#"{"api_key": "s3cret"}"#).unwrap();
+        assert_eq!(
+            admin.api_key.as_ref().and_then(SecretBytes::expose_str),
+            Some("s3cret")
+        );
+        assert!(!format!("{admin:?}").contains("s3cret"));
+        assert!(!serde_json::to_string(&admin).unwrap().contains("s3cret"));
+    }
+
     #[test]
     fn test_rate_limit_validation() {
         let mut config = GatewayConfig::default();
@@ -1,11 +1,30 @@
 //! Correlation ID for request tracking.
 //!
-//! Uses UUID v7 for time-ordered, unique identifiers.
+//! Uses UUID v7 for time-ordered, unique identifiers. The W3C trace context
+//! of the request being served travels alongside the correlation ID so
+//! subsystem spans join the caller's trace.
 
+use quantum_telemetry::PropagatedContext;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::future::Future;
 use uuid::Uuid;
 
+tokio::task_local! {
+    /// Trace context of the gateway span serving the current request
+    static REQUEST_TRACE: PropagatedContext;
+}
+
+/// Run a request future with its trace context in scope
+pub async fn with_trace_context<F: Future>(context: PropagatedContext, fut: F) -> F::Output {
+    REQUEST_TRACE.scope(context, fut).await
+}
+
+/// Trace context of the request being served (None outside a request)
+pub fn current_trace_context() -> Option<PropagatedContext> {
+    REQUEST_TRACE.try_with(PropagatedContext::clone).ok()
+}
+
 /// Correlation ID for tracking requests through the system.
 ///
 /// Uses UUID v7 which is time-ordered, making it ideal for:
@@ -137,4 +156,16 @@ mod tests {
         // Should be within 1 second
         assert!((ts.unwrap() as i64 - now_ms as i64).abs() < 1000);
     }
+
+    #[tokio::test]
+    async fn test_trace_context_scope() {
+        assert!(current_trace_context().is_none());
+
+        let context = PropagatedContext::empty().new_child();
+        let trace_id = context.trace_id.clone();
+        let seen = with_trace_context(context, async { current_trace_context() }).await;
+
+        assert_eq!(seen.unwrap().trace_id, trace_id);
+        assert!(current_trace_context().is_none());
+    }
 }
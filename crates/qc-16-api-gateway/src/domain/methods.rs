@@ -44,6 +44,9 @@ pub enum MethodCategory {
     Admin,
     Debug,
     Trace,
+    Swap,
+    Mining,
+    Contract,
 }
 
 /// Method metadata
@@ -372,7 +375,7 @@ pub static METHOD_REGISTRY: LazyLock<HashMap<&'static str, MethodInfo>> = LazyLo
             MethodTier::Public,
             MethodCategory::Eth,
             10,
-            Some("qc-03-transaction-indexing"),
+            None,
             "Returns filter changes since last poll",
         ),
         MethodInfo::read(
@@ -466,6 +469,48 @@ pub static METHOD_REGISTRY: LazyLock<HashMap<&'static str, MethodInfo>> = LazyLo
             Some("qc-01-peer-discovery"),
             "Returns peer count",
         ),
+        // --- Cross-Chain Swaps ---
+        MethodInfo::read(
+            "swap_getQuote",
+            MethodTier::Public,
+            MethodCategory::Swap,
+            10,
+            Some("qc-15-cross-chain"),
+            "Returns swap fees, timelocks, and completion estimate",
+        ),
+        MethodInfo::read(
+            "swap_getMakers",
+            MethodTier::Public,
+            MethodCategory::Swap,
+            5,
+            Some("qc-15-cross-chain"),
+            "Returns advertised swap liquidity for a pair",
+        ),
+        // --- Contract Interaction ---
+        MethodInfo::read(
+            "qc_contractFunctions",
+            MethodTier::Public,
+            MethodCategory::Contract,
+            5,
+            None,
+            "Lists the functions of a contract ABI",
+        ),
+        MethodInfo::read(
+            "qc_encodeCall",
+            MethodTier::Public,
+            MethodCategory::Contract,
+            5,
+            None,
+            "Encodes calldata for a contract function from its ABI",
+        ),
+        MethodInfo::read(
+            "qc_callContract",
+            MethodTier::Public,
+            MethodCategory::Contract,
+            30,
+            Some("qc-11-smart-contracts"),
+            "Calls a contract function, decoding return values and revert reasons",
+        ),
         // ═══════════════════════════════════════════════════════════════════════
         // TIER 2: PROTECTED METHODS (API Key OR Localhost)
         // ═══════════════════════════════════════════════════════════════════════
@@ -495,6 +540,14 @@ pub static METHOD_REGISTRY: LazyLock<HashMap<&'static str, MethodInfo>> = LazyLo
             Some("qc-06-mempool"),
             "Returns txpool content for address",
         ),
+        MethodInfo::read(
+            "txpool_transaction",
+            MethodTier::Protected,
+            MethodCategory::TxPool,
+            5,
+            Some("qc-06-mempool"),
+            "Returns a pooled transaction with nonce gaps and replacements",
+        ),
         MethodInfo::read(
             "txpool_inspect",
             MethodTier::Protected,
@@ -503,6 +556,23 @@ pub static METHOD_REGISTRY: LazyLock<HashMap<&'static str, MethodInfo>> = LazyLo
             Some("qc-06-mempool"),
             "Returns txpool summary",
         ),
+        // --- External Block Production ---
+        MethodInfo::read(
+            "qc_getBlockTemplate",
+            MethodTier::Protected,
+            MethodCategory::Mining,
+            60,
+            Some("qc-17-block-production"),
+            "Returns a block template (long-polls on new head)",
+        ),
+        MethodInfo::write(
+            "qc_submitBlock",
+            MethodTier::Protected,
+            MethodCategory::Mining,
+            10,
+            Some("qc-17-block-production"),
+            "Submits a solved block template",
+        ),
         // --- Admin Info (read-only) ---
         MethodInfo::read(
             "admin_nodeInfo",
@@ -528,6 +598,38 @@ pub static METHOD_REGISTRY: LazyLock<HashMap<&'static str, MethodInfo>> = LazyLo
             None,
             "Returns data directory path",
         ),
+        MethodInfo::read(
+            "admin_mevReports",
+            MethodTier::Protected,
+            MethodCategory::Admin,
+            5,
+            Some("qc-17-block-production"),
+            "Returns MEV and censorship reports for produced blocks",
+        ),
+        MethodInfo::read(
+            "admin_logControl",
+            MethodTier::Protected,
+            MethodCategory::Admin,
+            5,
+            None,
+            "Returns log sampling and rate limit settings",
+        ),
+        MethodInfo::read(
+            "admin_subsystems",
+            MethodTier::Protected,
+            MethodCategory::Admin,
+            5,
+            None,
+            "Returns lifecycle status and dependencies of registered subsystems",
+        ),
+        MethodInfo::read(
+            "admin_getConfig",
+            MethodTier::Protected,
+            MethodCategory::Admin,
+            5,
+            None,
+            "Returns the node's effective configuration with secrets redacted",
+        ),
         // ═══════════════════════════════════════════════════════════════════════
         // TIER 3: ADMIN METHODS (Localhost AND Auth Required)
         // ═══════════════════════════════════════════════════════════════════════
@@ -565,6 +667,71 @@ pub static METHOD_REGISTRY: LazyLock<HashMap<&'static str, MethodInfo>> = LazyLo
             Some("qc-01-peer-discovery"),
             "Removes trusted peer",
         ),
+        MethodInfo::write(
+            "admin_setLogControl",
+            MethodTier::Admin,
+            MethodCategory::Admin,
+            5,
+            None,
+            "Replaces log sampling and rate limit settings",
+        ),
+        MethodInfo::write(
+            "admin_setConfig",
+            MethodTier::Admin,
+            MethodCategory::Admin,
+            5,
+            None,
+            "Changes configuration settings that apply without a restart",
+        ),
+        MethodInfo::write(
+            "admin_stopSubsystem",
+            MethodTier::Admin,
+            MethodCategory::Admin,
+            30,
+            None,
+            "Stops a non-core subsystem",
+        ),
+        MethodInfo::write(
+            "admin_startSubsystem",
+            MethodTier::Admin,
+            MethodCategory::Admin,
+            30,
+            None,
+            "Starts a stopped non-core subsystem",
+        ),
+        MethodInfo::write(
+            "admin_restartSubsystem",
+            MethodTier::Admin,
+            MethodCategory::Admin,
+            30,
+            None,
+            "Restarts a non-core subsystem",
+        ),
+        MethodInfo::write(
+            "admin_evictTransaction",
+            MethodTier::Admin,
+            MethodCategory::Admin,
+            10,
+            Some("qc-06-mempool"),
+            "Evicts a transaction from the mempool",
+        ),
+        // --- Swap Liquidity ---
+        MethodInfo::write(
+            "swap_advertiseLiquidity",
+            MethodTier::Admin,
+            MethodCategory::Swap,
+            10,
+            Some("qc-15-cross-chain"),
+            "Advertises this node's swap liquidity",
+        ),
+        MethodInfo::write(
+            "swap_withdrawLiquidity",
+            MethodTier::Admin,
+            MethodCategory::Swap,
+            10,
+            Some("qc-15-cross-chain"),
+            "Withdraws an advertised swap offer",
+        ),
         // --- Debug ---
         MethodInfo::read(
             "debug_traceTransaction",
@@ -670,6 +837,30 @@ pub static METHOD_REGISTRY: LazyLock<HashMap<&'static str, MethodInfo>> = LazyLo
             Some("qc-03-transaction-indexing"),
             "Returns raw tx bytes",
         ),
+        MethodInfo::read(
+            "debug_eventBus",
+            MethodTier::Admin,
+            MethodCategory::Debug,
+            5,
+            None,
+            "Returns recent events per topic, subscriber lag and DLQ depth",
+        ),
+        MethodInfo::read(
+            "debug_recentBlocks",
+            MethodTier::Admin,
+            MethodCategory::Debug,
+            10,
+            None,
+            "Returns recent blocks and chain reorganizations",
+        ),
+        MethodInfo::read(
+            "debug_metricHistory",
+            MethodTier::Admin,
+            MethodCategory::Debug,
+            10,
+            None,
+            "Returns sampled history of peers, mempool size, block time and finality lag",
+        ),
         // --- Trace (for advanced debugging) ---
         MethodInfo::read(
             "trace_block",
@@ -831,6 +1022,17 @@ mod tests {
             Some(MethodTier::Protected)
         );
         assert_eq!(get_method_tier("admin_addPeer"), Some(MethodTier::Admin));
+        assert_eq!(get_method_tier("swap_getQuote"), Some(MethodTier::Public));
+        assert_eq!(
+            get_method_tier("swap_advertiseLiquidity"),
+            Some(MethodTier::Admin)
+        );
+        assert_eq!(
+            get_method_tier("qc_getBlockTemplate"),
+            Some(MethodTier::Protected)
+        );
+        assert!(is_write_method("qc_submitBlock"));
+        assert_eq!(get_method_tier("qc_callContract"), Some(MethodTier::Public));
     }
 
     #[test]
@@ -838,6 +1040,7 @@ mod tests {
         assert!(!is_write_method("eth_getBalance"));
         assert!(is_write_method("eth_sendRawTransaction"));
         assert!(is_write_method("admin_addPeer"));
+        assert!(is_write_method("admin_setLogControl"));
     }
 
     #[test]
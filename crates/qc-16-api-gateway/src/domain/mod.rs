@@ -3,6 +3,7 @@
 //! This module contains the core types, configuration, and error handling.
 //! Note: Async infrastructure (pending requests) is in adapters layer.
 
+pub mod abi;
 pub mod config;
 pub mod correlation;
 pub mod error;
@@ -10,6 +11,7 @@ pub mod methods;
 pub mod types;
 
 // Re-exports for convenience
+pub use abi::{AbiError, AbiFunction, AbiParam, ContractAbi, DecodedValue};
 pub use config::{GatewayConfig, LimitsConfig};
 pub use correlation::CorrelationId;
 pub use error::{ApiError, ApiResult, GatewayError};
@@ -16,7 +16,7 @@ pub type BlockNumber = u64;
 /// U256 wrapper with hex string serialization for JSON-RPC compatibility.
 ///
 /// Serializes as `"0x..."` hex string, deserializes from hex string or number.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
 pub struct U256(pub PrimitiveU256);
 
 impl U256 {
@@ -186,7 +186,7 @@ impl<'de> Deserialize<'de> for U256 {
 #[serde(untagged)]
 pub enum BlockId {
     /// Block number as hex
-    Number(BlockNumber),
+    Number(#[serde(deserialize_with = "deserialize_block_number")] BlockNumber),
     /// Block hash
     Hash(BlockHashOrNumber),
     /// Block tag
@@ -204,12 +204,49 @@ impl Default for BlockId {
 pub struct BlockHashOrNumber {
     #[serde(rename = "blockHash", skip_serializing_if = "Option::is_none")]
     pub block_hash: Option<Hash>,
-    #[serde(rename = "blockNumber", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "blockNumber",
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_block_number"
+    )]
     pub block_number: Option<BlockNumber>,
     #[serde(rename = "requireCanonical", default)]
     pub require_canonical: bool,
 }
 
+/// Block number from a hex quantity (`"0x10"`) or a plain JSON number.
+///
+/// Other strings are rejected so that tags fall through to `BlockId::Tag`.
+fn deserialize_block_number<'de, D>(deserializer: D) -> Result<BlockNumber, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Quantity {
+        Number(BlockNumber),
+        Hex(String),
+    }
+
+    match Quantity::deserialize(deserializer)? {
+        Quantity::Number(n) => Ok(n),
+        Quantity::Hex(s) => s
+            .strip_prefix("0x")
+            .and_then(|digits| BlockNumber::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| de::Error::custom(format!("invalid block number: {}", s))),
+    }
+}
+
+fn deserialize_optional_block_number<'de, D>(
+    deserializer: D,
+) -> Result<Option<BlockNumber>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_block_number(deserializer).map(Some)
+}
+
 /// Block tags for JSON-RPC
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum BlockTag {
@@ -309,6 +346,27 @@ pub struct CallRequest {
     pub transaction_type: Option<u64>,
 }
 
+/// Options for eth_sendRawTransaction (optional second parameter)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendRawTransactionOptions {
+    /// Simulate against latest state before submitting (None = node default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preflight: Option<bool>,
+}
+
+/// Options for qc_getBlockTemplate (optional first parameter)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTemplateOptions {
+    /// Coinbase recipient (None = node default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary: Option<Address>,
+    /// `longPollId` of a previous template: wait for a new head before answering
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long_poll_id: Option<String>,
+}
+
 /// Access list item for EIP-2930
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -406,6 +464,9 @@ pub struct Filter {
     /// Block hash (alternative to from_block/to_block)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub block_hash: Option<Hash>,
+    /// Continuation cursor from a capped eth_getLogs response (gateway extension)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
 }
 
 /// Filter address - single or multiple
@@ -424,6 +485,36 @@ pub enum FilterTopic {
     Multiple(Vec<Hash>),
 }
 
+/// Contract log entry (eth_getLogs, logs subscriptions)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    /// Emitting contract address
+    pub address: Address,
+    /// Indexed topics (up to 4)
+    pub topics: Vec<Hash>,
+    /// Non-indexed data
+    pub data: Bytes,
+    /// Block containing the log (None while pending)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<U256>,
+    /// Block hash (None while pending)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<Hash>,
+    /// Transaction that emitted the log
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<Hash>,
+    /// Index of the transaction in the block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_index: Option<U256>,
+    /// Index of the log in the block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_index: Option<U256>,
+    /// True if the log was removed by a reorg
+    #[serde(default)]
+    pub removed: bool,
+}
+
 /// Syncing status response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -465,6 +556,57 @@ pub struct FeeHistory {
     pub blob_gas_used_ratio: Option<Vec<f64>>,
 }
 
+/// Swap liquidity offer advertised to qc-15 Cross-Chain (swap_advertiseLiquidity)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapOffer {
+    /// Maker address (receives source funds)
+    pub maker: Address,
+    /// Chain the counterparty pays on (e.g. "QuantumChain")
+    pub source_chain: String,
+    /// Chain the counterparty receives on (e.g. "Ethereum")
+    pub target_chain: String,
+    /// Target units per source unit, in parts per million
+    pub rate_ppm: u64,
+    /// Maker fee in basis points
+    pub fee_bps: u64,
+    /// Smallest accepted source amount
+    pub min_amount: U256,
+    /// Largest accepted source amount
+    pub max_amount: U256,
+    /// Target-chain liquidity available
+    pub liquidity: U256,
+    /// Unix timestamp when the offer lapses
+    pub expires_at: u64,
+}
+
+/// Trace options for debug methods
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceOptions {
+    /// Tracer to use (callTracer, prestateTracer, etc.)
+    #[serde(default)]
+    pub tracer: Option<String>,
+    /// Tracer configuration
+    #[serde(default)]
+    pub tracer_config: Option<serde_json::Value>,
+    /// Timeout for trace operation
+    #[serde(default)]
+    pub timeout: Option<String>,
+    /// Disable storage capture
+    #[serde(default)]
+    pub disable_storage: bool,
+    /// Disable stack capture
+    #[serde(default)]
+    pub disable_stack: bool,
+    /// Enable memory capture
+    #[serde(default)]
+    pub enable_memory: bool,
+    /// Enable return data capture
+    #[serde(default)]
+    pub enable_return_data: bool,
+}
+
 /// JSON-RPC request ID type
 ///
 /// Per JSON-RPC 2.0 spec, ID can be string, number, or null.
@@ -568,4 +710,18 @@ mod tests {
         let tag: BlockTag = serde_json::from_str("\"latest\"").unwrap();
         assert_eq!(tag, BlockTag::Latest);
     }
+
+    #[test]
+    fn test_block_id_deserialize_hex_quantity() {
+        let id: BlockId = serde_json::from_str("\"0x10\"").unwrap();
+        assert_eq!(id, BlockId::Number(16));
+        let id: BlockId = serde_json::from_str("16").unwrap();
+        assert_eq!(id, BlockId::Number(16));
+        let id: BlockId = serde_json::from_str("\"pending\"").unwrap();
+        assert_eq!(id, BlockId::Tag(BlockTag::Pending));
+        assert!(serde_json::from_str::<BlockId>("\"0xzz\"").is_err());
+
+        let id: BlockId = serde_json::from_str(r#"{"blockNumber":"0x10"}"#).unwrap();
+        assert!(matches!(id, BlockId::Hash(h) if h.block_number == Some(16)));
+    }
 }
@@ -0,0 +1,235 @@
+//! GraphQL query endpoint (feature `graphql`).
+//!
+//! Read-only alternative to JSON-RPC for dashboards and indexers: one query
+//! can fetch a block range with transactions, receipts, and account state
+//! instead of N+1 JSON-RPC calls. Resolvers reuse the gateway's IPC handler
+//! and pending request store, so the same timeouts and correlation apply.
+//!
+//! Every query is priced before execution: list fields cost their length
+//! times their selection, and fields needing an extra IPC round trip add
+//! [`types::IPC_FIELD_COST`]. Queries over `max_complexity` or `max_depth`
+//! are rejected without touching any subsystem.
+
+pub mod types;
+
+use crate::domain::config::GraphQlConfig;
+use crate::domain::types::{Address, BlockId, BlockTag, Hash};
+use crate::ipc::handler::IpcHandler;
+use crate::ipc::requests::*;
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, ErrorExtensions, Object, Result, Schema,
+};
+use axum::{extract::State, routing::post, Json, Router};
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use types::{Account, Block, Log, LogFilter, Transaction, IPC_FIELD_COST};
+
+/// Gateway GraphQL schema
+pub type GatewaySchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Shared resolver state
+pub struct GraphQlContext {
+    ipc: Arc<IpcHandler>,
+    max_block_range: u64,
+}
+
+/// Build the schema with cost limits from config
+pub fn build_schema(ipc: Arc<IpcHandler>, config: &GraphQlConfig) -> GatewaySchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(GraphQlContext {
+            ipc,
+            max_block_range: config.max_block_range,
+        })
+        .limit_complexity(config.max_complexity)
+        .limit_depth(config.max_depth)
+        .finish()
+}
+
+/// Build the GraphQL router (POST /graphql)
+pub fn graphql_router(schema: GatewaySchema) -> Router {
+    Router::new()
+        .route("/graphql", post(handle_graphql))
+        .with_state(schema)
+}
+
+async fn handle_graphql(
+    State(schema): State<GatewaySchema>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request).await)
+}
+
+/// Root query object
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Block by number or hash (default: latest)
+    #[graphql(complexity = "IPC_FIELD_COST + child_complexity")]
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        number: Option<u64>,
+        hash: Option<String>,
+    ) -> Result<Option<Block>> {
+        let payload = match (number, hash) {
+            (Some(_), Some(_)) => return Err("Specify either number or hash, not both".into()),
+            (None, Some(hash)) => RequestPayload::GetBlockByHash(GetBlockByHashRequest {
+                hash: parse_arg(hash.as_str(), "block hash")?,
+                include_transactions: true,
+            }),
+            (number, None) => RequestPayload::GetBlockByNumber(GetBlockByNumberRequest {
+                block_id: number.map_or(BlockId::Tag(BlockTag::Latest), BlockId::Number),
+                include_transactions: true,
+            }),
+        };
+
+        let result = fetch(ctx, "qc-02-block-storage", payload).await?;
+        Ok((!result.is_null()).then_some(Block(result)))
+    }
+
+    /// Blocks `from..=to`, fetched concurrently
+    #[graphql(
+        complexity = "(to.saturating_sub(from) as usize + 1) * (IPC_FIELD_COST + child_complexity)"
+    )]
+    async fn blocks(&self, ctx: &Context<'_>, from: u64, to: u64) -> Result<Vec<Block>> {
+        if to < from {
+            return Err("`to` must not be below `from`".into());
+        }
+        let max_range = ctx.data::<GraphQlContext>()?.max_block_range;
+        if to - from >= max_range {
+            return Err(format!("Block range exceeds {} blocks", max_range).into());
+        }
+
+        let fetches = (from..=to).map(|number| {
+            fetch(
+                ctx,
+                "qc-02-block-storage",
+                RequestPayload::GetBlockByNumber(GetBlockByNumberRequest {
+                    block_id: BlockId::Number(number),
+                    include_transactions: true,
+                }),
+            )
+        });
+
+        let results = futures::future::try_join_all(fetches).await?;
+        Ok(results
+            .into_iter()
+            .filter(|block| !block.is_null())
+            .map(Block)
+            .collect())
+    }
+
+    /// Transaction by hash
+    #[graphql(complexity = "IPC_FIELD_COST + child_complexity")]
+    async fn transaction(&self, ctx: &Context<'_>, hash: String) -> Result<Option<Transaction>> {
+        let hash: Hash = parse_arg(hash.as_str(), "transaction hash")?;
+        let result = fetch(
+            ctx,
+            "qc-03-transaction-indexing",
+            RequestPayload::GetTransactionByHash(GetTransactionByHashRequest { hash }),
+        )
+        .await?;
+
+        Ok((!result.is_null()).then_some(Transaction(result)))
+    }
+
+    /// Account state at a block (default: latest)
+    async fn account(&self, address: String, block: Option<u64>) -> Result<Account> {
+        let address: Address = parse_arg(address.as_str(), "address")?;
+        Ok(Account {
+            address,
+            block_id: block.map_or(BlockId::Tag(BlockTag::Latest), BlockId::Number),
+        })
+    }
+
+    /// Logs matching a filter
+    #[graphql(complexity = "IPC_FIELD_COST * 10 + child_complexity")]
+    async fn logs(&self, ctx: &Context<'_>, filter: LogFilter) -> Result<Vec<Log>> {
+        let filter = filter.into_filter(ctx)?;
+        let result = fetch(
+            ctx,
+            "qc-03-transaction-indexing",
+            RequestPayload::GetLogs(GetLogsRequest { filter }),
+        )
+        .await?;
+
+        Ok(result
+            .as_array()
+            .map(|logs| logs.iter().cloned().map(Log).collect())
+            .unwrap_or_default())
+    }
+}
+
+/// Send an IPC request through the shared handler
+pub(crate) async fn fetch(
+    ctx: &Context<'_>,
+    target: &str,
+    payload: RequestPayload,
+) -> Result<serde_json::Value> {
+    let gql = ctx.data::<GraphQlContext>()?;
+    gql.ipc.request(target, payload, None).await.map_err(|e| {
+        async_graphql::Error::new(e.message).extend_with(|_, ext| ext.set("code", e.code))
+    })
+}
+
+/// Parse an argument with the JSON-RPC deserializers so validation matches
+pub(crate) fn parse_arg<T: DeserializeOwned>(
+    value: impl Into<serde_json::Value>,
+    what: &str,
+) -> Result<T> {
+    serde_json::from_value(value.into()).map_err(|e| format!("Invalid {}: {}", what, e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::handler::channel::test_handler;
+    use std::time::Duration;
+
+    fn test_schema(config: &GraphQlConfig) -> GatewaySchema {
+        build_schema(test_handler(Duration::from_millis(50), |_| None), config)
+    }
+
+    #[tokio::test]
+    async fn test_block_range_priced_by_length() {
+        let schema = test_schema(&GraphQlConfig {
+            max_complexity: 100,
+            ..Default::default()
+        });
+
+        let response = schema
+            .execute("{ blocks(from: 0, to: 49) { number transactions { hash } } }")
+            .await;
+
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("complex"));
+    }
+
+    #[tokio::test]
+    async fn test_depth_limit() {
+        let schema = test_schema(&GraphQlConfig {
+            max_depth: 2,
+            ..Default::default()
+        });
+
+        let response = schema
+            .execute("{ transaction(hash: \"0x00\") { receipt { logs { address } } } }")
+            .await;
+
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("nested"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_address_rejected() {
+        let schema = test_schema(&GraphQlConfig::default());
+
+        let response = schema
+            .execute("{ account(address: \"not-an-address\") { address } }")
+            .await;
+
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("Invalid address"));
+    }
+}
@@ -0,0 +1,315 @@
+//! GraphQL object types.
+//!
+//! Objects wrap the JSON returned by subsystems over IPC and expose the
+//! Ethereum JSON-RPC field names. Nested fields that need another IPC round
+//! trip (receipts, account state) are resolved lazily and carry a cost.
+
+use super::{fetch, parse_arg, GraphQlContext};
+use crate::domain::types::{Address, BlockId, Filter};
+use crate::ipc::requests::*;
+use async_graphql::{Context, InputObject, Object, Result};
+use serde_json::Value;
+
+/// Cost of a field that needs its own IPC round trip
+pub(crate) const IPC_FIELD_COST: usize = 5;
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// Block header and its transactions
+pub struct Block(pub(crate) Value);
+
+#[Object]
+impl Block {
+    async fn number(&self) -> Option<String> {
+        str_field(&self.0, "number")
+    }
+
+    async fn hash(&self) -> Option<String> {
+        str_field(&self.0, "hash")
+    }
+
+    async fn parent_hash(&self) -> Option<String> {
+        str_field(&self.0, "parentHash")
+    }
+
+    async fn timestamp(&self) -> Option<String> {
+        str_field(&self.0, "timestamp")
+    }
+
+    async fn state_root(&self) -> Option<String> {
+        str_field(&self.0, "stateRoot")
+    }
+
+    async fn transactions_root(&self) -> Option<String> {
+        str_field(&self.0, "transactionsRoot")
+    }
+
+    async fn gas_used(&self) -> Option<String> {
+        str_field(&self.0, "gasUsed")
+    }
+
+    async fn gas_limit(&self) -> Option<String> {
+        str_field(&self.0, "gasLimit")
+    }
+
+    async fn base_fee_per_gas(&self) -> Option<String> {
+        str_field(&self.0, "baseFeePerGas")
+    }
+
+    /// Transactions are embedded in the block response, so this costs no extra IPC
+    async fn transactions(&self) -> Vec<Transaction> {
+        let Some(txs) = self.0.get("transactions").and_then(Value::as_array) else {
+            return Vec::new();
+        };
+        txs.iter()
+            .map(|tx| match tx {
+                // Hash-only block responses still expose the hash
+                Value::String(hash) => Transaction(serde_json::json!({ "hash": hash })),
+                other => Transaction(other.clone()),
+            })
+            .collect()
+    }
+}
+
+/// Transaction
+pub struct Transaction(pub(crate) Value);
+
+#[Object]
+impl Transaction {
+    async fn hash(&self) -> Option<String> {
+        str_field(&self.0, "hash")
+    }
+
+    async fn from(&self) -> Option<String> {
+        str_field(&self.0, "from")
+    }
+
+    async fn to(&self) -> Option<String> {
+        str_field(&self.0, "to")
+    }
+
+    async fn value(&self) -> Option<String> {
+        str_field(&self.0, "value")
+    }
+
+    async fn nonce(&self) -> Option<String> {
+        str_field(&self.0, "nonce")
+    }
+
+    async fn gas(&self) -> Option<String> {
+        str_field(&self.0, "gas")
+    }
+
+    async fn gas_price(&self) -> Option<String> {
+        str_field(&self.0, "gasPrice")
+    }
+
+    async fn input(&self) -> Option<String> {
+        str_field(&self.0, "input")
+    }
+
+    async fn block_number(&self) -> Option<String> {
+        str_field(&self.0, "blockNumber")
+    }
+
+    async fn block_hash(&self) -> Option<String> {
+        str_field(&self.0, "blockHash")
+    }
+
+    async fn transaction_index(&self) -> Option<String> {
+        str_field(&self.0, "transactionIndex")
+    }
+
+    /// Receipt, fetched from qc-03 on demand
+    #[graphql(complexity = "IPC_FIELD_COST + child_complexity")]
+    async fn receipt(&self, ctx: &Context<'_>) -> Result<Option<Receipt>> {
+        let Some(hash) = str_field(&self.0, "hash") else {
+            return Ok(None);
+        };
+        let hash = parse_arg(hash.as_str(), "transaction hash")?;
+
+        let result = fetch(
+            ctx,
+            "qc-03-transaction-indexing",
+            RequestPayload::GetTransactionReceipt(GetTransactionReceiptRequest { hash }),
+        )
+        .await?;
+
+        Ok((!result.is_null()).then_some(Receipt(result)))
+    }
+}
+
+/// Transaction receipt
+pub struct Receipt(pub(crate) Value);
+
+#[Object]
+impl Receipt {
+    async fn status(&self) -> Option<String> {
+        str_field(&self.0, "status")
+    }
+
+    async fn gas_used(&self) -> Option<String> {
+        str_field(&self.0, "gasUsed")
+    }
+
+    async fn cumulative_gas_used(&self) -> Option<String> {
+        str_field(&self.0, "cumulativeGasUsed")
+    }
+
+    async fn effective_gas_price(&self) -> Option<String> {
+        str_field(&self.0, "effectiveGasPrice")
+    }
+
+    async fn contract_address(&self) -> Option<String> {
+        str_field(&self.0, "contractAddress")
+    }
+
+    async fn logs(&self) -> Vec<Log> {
+        self.0
+            .get("logs")
+            .and_then(Value::as_array)
+            .map(|logs| logs.iter().cloned().map(Log).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Contract log
+pub struct Log(pub(crate) Value);
+
+#[Object]
+impl Log {
+    async fn address(&self) -> Option<String> {
+        str_field(&self.0, "address")
+    }
+
+    async fn topics(&self) -> Vec<String> {
+        self.0
+            .get("topics")
+            .and_then(Value::as_array)
+            .map(|topics| {
+                topics
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    async fn data(&self) -> Option<String> {
+        str_field(&self.0, "data")
+    }
+
+    async fn block_number(&self) -> Option<String> {
+        str_field(&self.0, "blockNumber")
+    }
+
+    async fn block_hash(&self) -> Option<String> {
+        str_field(&self.0, "blockHash")
+    }
+
+    async fn transaction_hash(&self) -> Option<String> {
+        str_field(&self.0, "transactionHash")
+    }
+
+    async fn log_index(&self) -> Option<String> {
+        str_field(&self.0, "logIndex")
+    }
+
+    async fn removed(&self) -> bool {
+        self.0
+            .get("removed")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
+}
+
+/// Account state at a block; each field is one qc-04 query
+pub struct Account {
+    pub(crate) address: Address,
+    pub(crate) block_id: BlockId,
+}
+
+#[Object]
+impl Account {
+    async fn address(&self) -> String {
+        format!("{:#x}", self.address)
+    }
+
+    #[graphql(complexity = "IPC_FIELD_COST")]
+    async fn balance(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let payload = RequestPayload::GetBalance(GetBalanceRequest {
+            address: self.address,
+            block_id: self.block_id.clone(),
+        });
+        let result = fetch(ctx, "qc-04-state-management", payload).await?;
+        Ok(result.as_str().map(str::to_string))
+    }
+
+    #[graphql(complexity = "IPC_FIELD_COST")]
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let payload = RequestPayload::GetTransactionCount(GetTransactionCountRequest {
+            address: self.address,
+            block_id: self.block_id.clone(),
+        });
+        let result = fetch(ctx, "qc-04-state-management", payload).await?;
+        Ok(result.as_str().map(str::to_string))
+    }
+
+    #[graphql(complexity = "IPC_FIELD_COST")]
+    async fn code(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let payload = RequestPayload::GetCode(GetCodeRequest {
+            address: self.address,
+            block_id: self.block_id.clone(),
+        });
+        let result = fetch(ctx, "qc-04-state-management", payload).await?;
+        Ok(result.as_str().map(str::to_string))
+    }
+}
+
+/// Log filter input, mirroring the eth_getLogs filter object
+#[derive(InputObject, Default)]
+pub struct LogFilter {
+    /// First block (default: latest)
+    pub from_block: Option<u64>,
+    /// Last block (default: latest)
+    pub to_block: Option<u64>,
+    pub block_hash: Option<String>,
+    pub addresses: Option<Vec<String>>,
+    /// Topic positions; null matches anything, a list matches any of its entries
+    pub topics: Option<Vec<Option<Vec<String>>>>,
+}
+
+impl LogFilter {
+    /// Convert into the JSON-RPC filter, validating every field
+    pub(crate) fn into_filter(self, ctx: &Context<'_>) -> Result<Filter> {
+        let filter: Filter = parse_arg(
+            serde_json::json!({
+                "fromBlock": self.from_block,
+                "toBlock": self.to_block,
+                "blockHash": self.block_hash,
+                "address": self.addresses,
+                "topics": self.topics,
+            }),
+            "log filter",
+        )?;
+
+        if filter.block_hash.is_some() && (filter.from_block.is_some() || filter.to_block.is_some())
+        {
+            return Err("blockHash cannot be combined with fromBlock/toBlock".into());
+        }
+
+        let max_range = ctx.data::<GraphQlContext>()?.max_block_range;
+        if let (Some(BlockId::Number(from)), Some(BlockId::Number(to))) =
+            (&filter.from_block, &filter.to_block)
+        {
+            if to.saturating_sub(*from) >= max_range {
+                return Err(format!("Log block range exceeds {} blocks", max_range).into());
+            }
+        }
+
+        Ok(filter)
+    }
+}
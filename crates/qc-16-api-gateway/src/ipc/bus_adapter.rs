@@ -4,12 +4,14 @@
 //! Per SPEC-16 Section 6, the API Gateway communicates with subsystems
 //! via the event bus, not direct function calls.
 
-use crate::ipc::handler::{IpcError, IpcReceiver, IpcSender};
+use crate::adapters::capabilities::AdvertisedCapabilities;
+use crate::ipc::handler::{DeadLetter, IpcError, IpcReceiver, IpcSender};
 use crate::ipc::requests::{IpcRequest, RequestPayload};
 use crate::ipc::responses::IpcResponse;
 use crate::CorrelationId;
 use async_trait::async_trait;
 use futures::StreamExt;
+use quantum_telemetry::PropagatedContext;
 use shared_bus::{BlockchainEvent, EventFilter, EventPublisher, InMemoryEventBus};
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
@@ -58,6 +60,10 @@ impl IpcSender for EventBusSender {
             target: request.target.clone(),
             method: method.to_string(),
             params,
+            trace_parent: request
+                .trace_context
+                .as_ref()
+                .map(PropagatedContext::to_traceparent),
         };
 
         // Publish to the event bus - the ApiQueryHandler in node-runtime
@@ -81,6 +87,40 @@ impl IpcSender for EventBusSender {
 
         Ok(())
     }
+
+    fn is_connected(&self) -> bool {
+        // With no subscribers nothing on the bus can answer a query
+        self.bus.subscriber_count() > 0
+    }
+
+    async fn dead_letter(&self, letter: DeadLetter) -> Result<(), IpcError> {
+        publish_dead_letter(&self.bus, letter).await;
+        Ok(())
+    }
+}
+
+/// Publish a request that exhausted its attempts as an `ApiQueryDeadLetter`.
+///
+/// Shared by every bus-backed `IpcSender`.
+pub async fn publish_dead_letter(bus: &InMemoryEventBus, letter: DeadLetter) {
+    warn!(
+        correlation_id = %letter.correlation_id,
+        target = %letter.target,
+        method = %letter.method,
+        attempts = letter.attempts,
+        reason = %letter.reason,
+        "Publishing dead letter for failed API query"
+    );
+
+    bus.publish(BlockchainEvent::ApiQueryDeadLetter {
+        correlation_id: letter.correlation_id.to_string(),
+        target: letter.target,
+        method: letter.method,
+        params: letter.params,
+        attempts: letter.attempts,
+        reason: letter.reason,
+    })
+    .await;
 }
 
 /// Convert RequestPayload to method name for event bus
@@ -94,6 +134,7 @@ fn payload_to_method(payload: &RequestPayload) -> &'static str {
         RequestPayload::GetBlockByNumber(_) => "get_block_by_number",
         RequestPayload::GetBlockNumber(_) => "get_block_number",
         RequestPayload::GetFeeHistory(_) => "get_fee_history",
+        RequestPayload::GetRawBlock(_) => "get_raw_block",
         RequestPayload::GetTransactionByHash(_) => "get_transaction_by_hash",
         RequestPayload::GetTransactionReceipt(_) => "get_transaction_receipt",
         RequestPayload::GetLogs(_) => "get_logs",
@@ -105,13 +146,29 @@ fn payload_to_method(payload: &RequestPayload) -> &'static str {
         RequestPayload::GetMaxPriorityFeePerGas(_) => "get_max_priority_fee_per_gas",
         RequestPayload::GetTxPoolStatus(_) => "get_txpool_status",
         RequestPayload::GetTxPoolContent(_) => "get_txpool_content",
+        RequestPayload::GetTxPoolTransaction(_) => "get_txpool_transaction",
         RequestPayload::GetPeers(_) => "get_peers",
         RequestPayload::GetNodeInfo(_) => "get_node_info",
         RequestPayload::GetSyncStatus(_) => "get_sync_status",
+        RequestPayload::GetSwapQuote(_) => "get_swap_quote",
+        RequestPayload::GetSwapMakers(_) => "get_swap_makers",
+        RequestPayload::AdvertiseSwapLiquidity(_) => "advertise_swap_liquidity",
+        RequestPayload::WithdrawSwapLiquidity(_) => "withdraw_swap_liquidity",
+        RequestPayload::GetBlockTemplate(_) => "get_block_template",
+        RequestPayload::SubmitBlock(_) => "submit_block",
+        RequestPayload::GetMevReports(_) => "get_mev_reports",
         RequestPayload::AddPeer(_) => "add_peer",
         RequestPayload::RemovePeer(_) => "remove_peer",
         RequestPayload::Ping => "ping",
         RequestPayload::GetSubsystemMetrics(_) => "get_subsystem_metrics",
+        RequestPayload::GetSubsystemStatus(_) => "get_subsystem_status",
+        RequestPayload::ControlSubsystem(_) => "control_subsystem",
+        RequestPayload::GetEventBus(_) => "get_event_bus",
+        RequestPayload::GetRecentBlocks(_) => "get_recent_blocks",
+        RequestPayload::GetMetricHistory(_) => "get_metric_history",
+        RequestPayload::EvictTransaction(_) => "evict_transaction",
+        RequestPayload::GetConfig(_) => "get_config",
+        RequestPayload::SetConfig(_) => "set_config",
     }
 }
 
@@ -147,18 +204,31 @@ impl IpcReceiver for EventBusReceiver {
 /// Response router that routes blockchain events to pending requests.
 ///
 /// Subscribes to ApiGateway topic and converts ApiQueryResponse events
-/// to IpcResponse messages for the pending request store.
+/// to IpcResponse messages for the pending request store. Capability
+/// reports from the Registry topic update the advertised capabilities.
 pub struct ResponseRouter {
     /// Event bus subscription
     bus: Arc<InMemoryEventBus>,
     /// Channel to send responses to the receiver
     response_tx: mpsc::Sender<IpcResponse>,
+    /// Latest capability report from the registry
+    capabilities: Arc<AdvertisedCapabilities>,
 }
 
 impl ResponseRouter {
     /// Create a new response router.
     pub fn new(bus: Arc<InMemoryEventBus>, response_tx: mpsc::Sender<IpcResponse>) -> Self {
-        Self { bus, response_tx }
+        Self {
+            bus,
+            response_tx,
+            capabilities: Arc::new(AdvertisedCapabilities::new()),
+        }
+    }
+
+    /// Record capability reports in `capabilities` (shared with the IPC handler)
+    pub fn with_capabilities(mut self, capabilities: Arc<AdvertisedCapabilities>) -> Self {
+        self.capabilities = capabilities;
+        self
     }
 
     /// Start listening for ApiQueryResponse events and routing them.
@@ -170,11 +240,21 @@ impl ResponseRouter {
         info!("[ResponseRouter] Started listening for ApiQueryResponse events");
 
         // Subscribe to ApiGateway topic to receive ApiQueryResponse events
-        let filter = EventFilter::topics(vec![shared_bus::EventTopic::ApiGateway]);
+        let filter = EventFilter::topics(vec![
+            shared_bus::EventTopic::ApiGateway,
+            shared_bus::EventTopic::Registry,
+        ]);
         let mut stream = self.bus.event_stream(filter);
 
         loop {
             match stream.next().await {
+                Some(BlockchainEvent::CapabilitiesAdvertised(report)) => {
+                    debug!(
+                        subsystems = report.subsystems.len(),
+                        "Updating advertised subsystem capabilities"
+                    );
+                    self.capabilities.update(report);
+                }
                 Some(event) => {
                     if let Some(response) = self.event_to_response(&event) {
                         debug!(
@@ -220,7 +300,8 @@ impl ResponseRouter {
                     Err(e) => ResponsePayload::Error(ErrorData {
                         code: e.code,
                         message: e.message.clone(),
-                        data: None,
+                        // Shared error code, severity and origin pass through unchanged
+                        data: e.info.as_ref().and_then(|i| serde_json::to_value(i).ok()),
                     }),
                 };
 
@@ -333,7 +414,8 @@ impl QueryRouter {
             RequestPayload::GetBlockByHash(_)
             | RequestPayload::GetBlockByNumber(_)
             | RequestPayload::GetBlockNumber(_)
-            | RequestPayload::GetFeeHistory(_) => {
+            | RequestPayload::GetFeeHistory(_)
+            | RequestPayload::GetRawBlock(_) => {
                 if let Some(tx) = &self.block_tx {
                     let query = BlockQuery {
                         correlation_id,
@@ -370,7 +452,8 @@ impl QueryRouter {
             | RequestPayload::GetGasPrice(_)
             | RequestPayload::GetMaxPriorityFeePerGas(_)
             | RequestPayload::GetTxPoolStatus(_)
-            | RequestPayload::GetTxPoolContent(_) => {
+            | RequestPayload::GetTxPoolContent(_)
+            | RequestPayload::GetTxPoolTransaction(_) => {
                 if let Some(tx) = &self.mempool_tx {
                     let query = MempoolQuery {
                         correlation_id,
@@ -415,6 +498,23 @@ impl QueryRouter {
                 ));
             }
 
+            // Cross-chain swaps (qc-15) - served over the event bus only
+            RequestPayload::GetSwapQuote(_)
+            | RequestPayload::GetSwapMakers(_)
+            | RequestPayload::AdvertiseSwapLiquidity(_)
+            | RequestPayload::WithdrawSwapLiquidity(_) => {
+                return Err(IpcError::SubsystemUnavailable("qc-15-cross-chain".into()));
+            }
+
+            // Block production (qc-17) - served over the event bus only
+            RequestPayload::GetBlockTemplate(_)
+            | RequestPayload::SubmitBlock(_)
+            | RequestPayload::GetMevReports(_) => {
+                return Err(IpcError::SubsystemUnavailable(
+                    "qc-17-block-production".into(),
+                ));
+            }
+
             // Ping - lightweight health check (returns immediately)
             RequestPayload::Ping => {
                 // Ping doesn't need routing - just acknowledge receipt
@@ -431,8 +531,16 @@ impl QueryRouter {
                     .map_err(|_| IpcError::ChannelClosed)?;
             }
 
-            // Admin metrics query - routed to admin handler
-            RequestPayload::GetSubsystemMetrics(_) => {
+            // Admin queries - routed to admin handler
+            RequestPayload::GetSubsystemMetrics(_)
+            | RequestPayload::GetSubsystemStatus(_)
+            | RequestPayload::ControlSubsystem(_)
+            | RequestPayload::GetEventBus(_)
+            | RequestPayload::GetRecentBlocks(_)
+            | RequestPayload::GetMetricHistory(_)
+            | RequestPayload::EvictTransaction(_)
+            | RequestPayload::GetConfig(_)
+            | RequestPayload::SetConfig(_) => {
                 // Route to admin target via event bus
                 // The target is set to "admin" in the request
             }
@@ -453,6 +561,38 @@ mod tests {
         assert_eq!(sender.subsystem_id, 16);
     }
 
+    #[tokio::test]
+    async fn test_dead_letter_published_to_bus() {
+        let bus = Arc::new(InMemoryEventBus::new());
+        let mut stream = bus.event_stream(EventFilter::all());
+        let sender = EventBusSender::new(Arc::clone(&bus), 16);
+        let correlation_id = CorrelationId::new();
+
+        sender
+            .dead_letter(DeadLetter {
+                correlation_id,
+                target: "qc-02-block-storage".into(),
+                method: "eth_blockNumber".into(),
+                params: serde_json::Value::Null,
+                attempts: 3,
+                reason: "timeout".into(),
+            })
+            .await
+            .unwrap();
+
+        match stream.next().await {
+            Some(BlockchainEvent::ApiQueryDeadLetter {
+                correlation_id: id,
+                attempts,
+                ..
+            }) => {
+                assert_eq!(id, correlation_id.to_string());
+                assert_eq!(attempts, 3);
+            }
+            other => panic!("expected dead letter, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_query_router_empty() {
         let router = QueryRouter::empty();
@@ -478,4 +618,38 @@ mod tests {
 
         assert!(matches!(result, Err(IpcError::SubsystemUnavailable(_))));
     }
+
+    #[test]
+    fn test_error_info_surfaced_unchanged() {
+        use crate::adapters::pending::ResponseError;
+        use crate::domain::ApiError;
+        use shared_bus::ApiQueryError;
+        use shared_types::{ErrorCode, ErrorInfo, SubsystemId};
+
+        let (tx, _rx) = mpsc::channel(1);
+        let router = ResponseRouter::new(Arc::new(InMemoryEventBus::new()), tx);
+        let info = ErrorInfo::new(
+            ErrorCode::Syncing,
+            SubsystemId::BlockStorage,
+            "still syncing",
+        );
+        let event = BlockchainEvent::ApiQueryResponse {
+            correlation_id: CorrelationId::new().to_string(),
+            source: 2,
+            result: Err(ApiQueryError::from(info.clone())),
+        };
+
+        let response = router.event_to_response(&event).unwrap();
+        let error_data = response.error_data().unwrap().clone();
+        let error = ApiError::from(ResponseError {
+            code: error_data.code,
+            message: error_data.message,
+            data: error_data.data,
+        });
+
+        assert_eq!(error.code, ErrorCode::Syncing.json_rpc_code());
+        let data: ErrorInfo = serde_json::from_value(error.data.unwrap()).unwrap();
+        assert_eq!(data, info);
+        assert!(data.is_retryable());
+    }
 }
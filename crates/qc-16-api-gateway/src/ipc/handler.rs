@@ -1,12 +1,17 @@
 //! IPC handler for event bus communication.
 
-use crate::adapters::pending::{PendingRequestStore, ResponseError};
-use crate::ipc::requests::{IpcRequest, RequestPayload};
+use crate::adapters::capabilities::AdvertisedCapabilities;
+use crate::adapters::health::SubsystemHealth;
+use crate::adapters::pending::{PendingRequestStore, ResponseError, SubsystemResponse};
+use crate::domain::correlation::CorrelationId;
+use crate::domain::methods::is_write_method;
+use crate::ipc::requests::{IpcRequest, RequestPayload, SubsystemAction};
 use crate::ipc::responses::{IpcResponse, ResponsePayload, SuccessData};
+use crate::ipc::retry::RetryPolicy;
 use async_trait::async_trait;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::mpsc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, oneshot};
 use tracing::{debug, error, warn};
 
 /// IPC handler trait for sending requests to subsystems
@@ -14,6 +19,36 @@ use tracing::{debug, error, warn};
 pub trait IpcSender: Send + Sync {
     /// Send a request to a subsystem
     async fn send(&self, request: IpcRequest) -> Result<(), IpcError>;
+
+    /// Publish a request that failed after all attempts.
+    ///
+    /// The default discards it; bus-backed senders publish it to the
+    /// dead-letter queue.
+    async fn dead_letter(&self, _letter: DeadLetter) -> Result<(), IpcError> {
+        Ok(())
+    }
+
+    /// Whether requests can currently reach subsystems
+    fn is_connected(&self) -> bool {
+        true
+    }
+}
+
+/// A request that could not be delivered or answered after all attempts
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    /// Correlation ID of the original request
+    pub correlation_id: CorrelationId,
+    /// Target subsystem
+    pub target: String,
+    /// JSON-RPC method name
+    pub method: String,
+    /// Request payload as JSON, so the request can be replayed
+    pub params: serde_json::Value,
+    /// Attempts made (hedged duplicates not counted)
+    pub attempts: u32,
+    /// Why the last attempt failed
+    pub reason: String,
 }
 
 /// IPC receiver trait for receiving responses from subsystems
@@ -46,6 +81,20 @@ pub struct IpcHandler {
     sender: Arc<dyn IpcSender>,
     /// Default timeout
     default_timeout: Duration,
+    /// Retry and hedging policy
+    retry: RetryPolicy,
+    /// Per-subsystem last-response registry
+    health: Arc<SubsystemHealth>,
+    /// Subsystem availability advertised by the registry
+    capabilities: Arc<AdvertisedCapabilities>,
+}
+
+/// How a single attempt ended without a response
+enum AttemptFailure {
+    /// The request never left the gateway
+    Send(IpcError),
+    /// No response within the attempt timeout
+    Timeout,
 }
 
 impl IpcHandler {
@@ -58,10 +107,36 @@ impl IpcHandler {
             pending,
             sender,
             default_timeout,
+            retry: RetryPolicy::none(),
+            health: Arc::new(SubsystemHealth::new()),
+            capabilities: Arc::new(AdvertisedCapabilities::new()),
         }
     }
 
-    /// Send request and wait for response
+    /// Record per-subsystem response ages in a shared registry
+    pub fn with_health(mut self, health: Arc<SubsystemHealth>) -> Self {
+        self.health = health;
+        self
+    }
+
+    /// Fail fast for subsystems the registry advertises as unavailable
+    pub fn with_capabilities(mut self, capabilities: Arc<AdvertisedCapabilities>) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Retry failed attempts and hedge slow reads according to `policy`
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Send request and wait for response.
+    ///
+    /// `timeout` bounds the whole exchange, retries included. Requests that
+    /// exhaust their attempts are published as dead letters under their
+    /// original correlation ID. Targets advertised as unavailable are
+    /// rejected without sending.
     pub async fn request(
         &self,
         target: &str,
@@ -70,50 +145,153 @@ impl IpcHandler {
     ) -> Result<serde_json::Value, ResponseError> {
         let method = payload_method_name(&payload);
         let timeout = timeout.unwrap_or(self.default_timeout);
+        let deadline = Instant::now() + timeout;
+        let attempt_timeout = self.retry.attempt_timeout(timeout);
+        let is_write = is_write_method(method);
 
-        // Register pending request
-        let (correlation_id, rx) = self.pending.register(method, Some(timeout));
+        if let Err(info) = self.capabilities.check(target) {
+            debug!(
+                target = target,
+                method = method,
+                "Target subsystem not available"
+            );
+            return Err(info.into());
+        }
 
-        // Create and send IPC request
-        let request = IpcRequest::with_correlation_id(correlation_id, target, payload);
+        // Register once: every attempt shares the correlation ID, so whichever
+        // copy is answered first completes the request
+        let (correlation_id, mut rx) = self.pending.register(method, Some(timeout));
+
+        self.health.record_request(target);
+
+        let mut attempts = 0;
+        let failure = loop {
+            attempts += 1;
+            let request = IpcRequest::with_correlation_id(correlation_id, target, payload.clone());
+
+            let failure = match self.sender.send(request.clone()).await {
+                Ok(()) => {
+                    debug!(
+                        correlation_id = %correlation_id,
+                        target = target,
+                        method = method,
+                        attempt = attempts,
+                        "Sent IPC request"
+                    );
+                    let hedge = !is_write && attempts == 1;
+                    let wait =
+                        attempt_timeout.min(deadline.saturating_duration_since(Instant::now()));
+                    match self.await_attempt(&mut rx, request, wait, hedge).await {
+                        Ok(Some(response)) => {
+                            self.health.record_response(target);
+                            return response.result;
+                        }
+                        Ok(None) => {
+                            // Channel was dropped
+                            return Err(ResponseError {
+                                code: -32603,
+                                message: "Response channel closed".into(),
+                                data: None,
+                            });
+                        }
+                        Err(failure) => failure,
+                    }
+                }
+                Err(e) => AttemptFailure::Send(e),
+            };
 
-        if let Err(e) = self.sender.send(request).await {
-            // Remove from pending if send fails
-            self.pending.cancel(&correlation_id);
-            return Err(ResponseError {
+            // A write that timed out may already have been applied
+            let retryable = !(is_write && matches!(failure, AttemptFailure::Timeout));
+            if !retryable || attempts >= self.retry.max_attempts {
+                break failure;
+            }
+
+            let backoff = self.retry.backoff(attempts);
+            if Instant::now() + backoff >= deadline {
+                break failure;
+            }
+            debug!(
+                correlation_id = %correlation_id,
+                method = method,
+                attempt = attempts,
+                backoff_ms = backoff.as_millis() as u64,
+                "Retrying IPC request"
+            );
+            tokio::time::sleep(backoff).await;
+        };
+
+        self.pending.cancel(&correlation_id);
+        self.health.record_failure(target);
+
+        let error = match failure {
+            AttemptFailure::Send(e) => ResponseError {
                 code: -32603,
                 message: format!("IPC send failed: {}", e),
                 data: None,
-            });
+            },
+            AttemptFailure::Timeout => ResponseError {
+                code: -32006,
+                message: format!("Request timed out after {}s", timeout.as_secs()),
+                data: None,
+            },
+        };
+
+        let letter = DeadLetter {
+            correlation_id,
+            target: target.to_string(),
+            method: method.to_string(),
+            params: serde_json::to_value(&payload).unwrap_or(serde_json::Value::Null),
+            attempts,
+            reason: error.message.clone(),
+        };
+        if let Err(e) = self.sender.dead_letter(letter).await {
+            warn!(
+                correlation_id = %correlation_id,
+                error = %e,
+                "Failed to publish dead letter"
+            );
         }
 
-        debug!(
-            correlation_id = %correlation_id,
-            target = target,
-            method = method,
-            "Sent IPC request"
-        );
+        Err(error)
+    }
 
-        // Wait for response
-        match tokio::time::timeout(timeout, rx).await {
-            Ok(Ok(response)) => response.result,
-            Ok(Err(_)) => {
-                // Channel was dropped
-                Err(ResponseError {
-                    code: -32603,
-                    message: "Response channel closed".into(),
-                    data: None,
-                })
-            }
-            Err(_) => {
-                // Timeout
-                self.pending.cancel(&correlation_id);
-                Err(ResponseError {
-                    code: -32006,
-                    message: format!("Request timed out after {}s", timeout.as_secs()),
-                    data: None,
-                })
-            }
+    /// Wait for one attempt's response, sending a hedged duplicate of `request`
+    /// if `hedge` is set and the policy's hedge delay passes first.
+    ///
+    /// Returns `Ok(None)` if the response channel was dropped.
+    async fn await_attempt(
+        &self,
+        rx: &mut oneshot::Receiver<SubsystemResponse>,
+        request: IpcRequest,
+        attempt_timeout: Duration,
+        hedge: bool,
+    ) -> Result<Option<SubsystemResponse>, AttemptFailure> {
+        let hedge_after = self
+            .retry
+            .hedge_after
+            .filter(|delay| hedge && *delay < attempt_timeout);
+
+        let remaining = match hedge_after {
+            Some(delay) => match tokio::time::timeout(delay, &mut *rx).await {
+                Ok(result) => return Ok(result.ok()),
+                Err(_) => {
+                    debug!(
+                        correlation_id = %request.correlation_id,
+                        target = %request.target,
+                        "Sending hedged IPC request"
+                    );
+                    if let Err(e) = self.sender.send(request).await {
+                        debug!(error = %e, "Hedged IPC request failed to send");
+                    }
+                    attempt_timeout - delay
+                }
+            },
+            None => attempt_timeout,
+        };
+
+        match tokio::time::timeout(remaining, rx).await {
+            Ok(result) => Ok(result.ok()),
+            Err(_) => Err(AttemptFailure::Timeout),
         }
     }
 
@@ -346,6 +524,7 @@ fn payload_method_name(payload: &RequestPayload) -> &'static str {
         RequestPayload::GetBlockByNumber(_) => "eth_getBlockByNumber",
         RequestPayload::GetBlockNumber(_) => "eth_blockNumber",
         RequestPayload::GetFeeHistory(_) => "eth_feeHistory",
+        RequestPayload::GetRawBlock(_) => "debug_getRawBlock",
         RequestPayload::GetTransactionByHash(_) => "eth_getTransactionByHash",
         RequestPayload::GetTransactionReceipt(_) => "eth_getTransactionReceipt",
         RequestPayload::GetLogs(_) => "eth_getLogs",
@@ -357,13 +536,33 @@ fn payload_method_name(payload: &RequestPayload) -> &'static str {
         RequestPayload::GetMaxPriorityFeePerGas(_) => "eth_maxPriorityFeePerGas",
         RequestPayload::GetTxPoolStatus(_) => "txpool_status",
         RequestPayload::GetTxPoolContent(_) => "txpool_content",
+        RequestPayload::GetTxPoolTransaction(_) => "txpool_transaction",
         RequestPayload::GetPeers(_) => "admin_peers",
         RequestPayload::GetNodeInfo(_) => "admin_nodeInfo",
         RequestPayload::GetSyncStatus(_) => "eth_syncing",
+        RequestPayload::GetSwapQuote(_) => "swap_getQuote",
+        RequestPayload::GetSwapMakers(_) => "swap_getMakers",
+        RequestPayload::AdvertiseSwapLiquidity(_) => "swap_advertiseLiquidity",
+        RequestPayload::WithdrawSwapLiquidity(_) => "swap_withdrawLiquidity",
+        RequestPayload::GetBlockTemplate(_) => "qc_getBlockTemplate",
+        RequestPayload::SubmitBlock(_) => "qc_submitBlock",
+        RequestPayload::GetMevReports(_) => "admin_mevReports",
         RequestPayload::AddPeer(_) => "admin_addPeer",
         RequestPayload::RemovePeer(_) => "admin_removePeer",
         RequestPayload::Ping => "ping",
         RequestPayload::GetSubsystemMetrics(_) => "debug_subsystemMetrics",
+        RequestPayload::GetSubsystemStatus(_) => "admin_subsystems",
+        RequestPayload::ControlSubsystem(request) => match request.action {
+            SubsystemAction::Stop => "admin_stopSubsystem",
+            SubsystemAction::Start => "admin_startSubsystem",
+            SubsystemAction::Restart => "admin_restartSubsystem",
+        },
+        RequestPayload::GetEventBus(_) => "debug_eventBus",
+        RequestPayload::GetRecentBlocks(_) => "debug_recentBlocks",
+        RequestPayload::GetMetricHistory(_) => "debug_metricHistory",
+        RequestPayload::EvictTransaction(_) => "admin_evictTransaction",
+        RequestPayload::GetConfig(_) => "admin_getConfig",
+        RequestPayload::SetConfig(_) => "admin_setConfig",
     }
 }
 
@@ -405,12 +604,109 @@ pub mod channel {
         let (resp_tx, resp_rx) = mpsc::channel(buffer);
         (req_tx, req_rx, resp_tx, resp_rx)
     }
+
+    /// IPC handler answered in-process by `respond`.
+    ///
+    /// Every request is passed to `respond`; `Some` completes it, `None`
+    /// leaves it to time out after `timeout`.
+    #[cfg(test)]
+    pub(crate) fn test_handler<F>(timeout: Duration, mut respond: F) -> Arc<IpcHandler>
+    where
+        F: FnMut(&IpcRequest) -> Option<Result<serde_json::Value, ResponseError>> + Send + 'static,
+    {
+        let (req_tx, mut req_rx) = mpsc::channel::<IpcRequest>(16);
+        let pending = Arc::new(PendingRequestStore::new(timeout));
+        let ipc = Arc::new(IpcHandler::new(
+            Arc::clone(&pending),
+            Arc::new(ChannelSender(req_tx)),
+            timeout,
+        ));
+
+        tokio::spawn(async move {
+            while let Some(request) = req_rx.recv().await {
+                if let Some(result) = respond(&request) {
+                    pending.complete(request.correlation_id, result);
+                }
+            }
+        });
+
+        ipc
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ipc::requests::GetBlockNumberRequest;
+    use crate::ipc::requests::{AddPeerRequest, GetBlockNumberRequest, GetEventBusRequest};
+    use std::sync::Mutex;
+
+    /// Sender that fails or ignores the first `skip` sends and answers the rest
+    struct ScriptedSender {
+        pending: Arc<PendingRequestStore>,
+        skip: usize,
+        fail_skipped: bool,
+        sent: Mutex<Vec<CorrelationId>>,
+        dead_letters: Mutex<Vec<DeadLetter>>,
+    }
+
+    impl ScriptedSender {
+        fn new(pending: &Arc<PendingRequestStore>, skip: usize, fail_skipped: bool) -> Arc<Self> {
+            Arc::new(Self {
+                pending: Arc::clone(pending),
+                skip,
+                fail_skipped,
+                sent: Mutex::new(Vec::new()),
+                dead_letters: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn sends(&self) -> usize {
+            self.sent.lock().unwrap().len()
+        }
+    }
+
+    #[async_trait]
+    impl IpcSender for ScriptedSender {
+        async fn send(&self, request: IpcRequest) -> Result<(), IpcError> {
+            let attempt = {
+                let mut sent = self.sent.lock().unwrap();
+                sent.push(request.correlation_id);
+                sent.len()
+            };
+            if attempt <= self.skip {
+                if self.fail_skipped {
+                    return Err(IpcError::ChannelClosed);
+                }
+                return Ok(());
+            }
+            self.pending
+                .complete(request.correlation_id, Ok(serde_json::json!("0x1")));
+            Ok(())
+        }
+
+        async fn dead_letter(&self, letter: DeadLetter) -> Result<(), IpcError> {
+            self.dead_letters.lock().unwrap().push(letter);
+            Ok(())
+        }
+    }
+
+    fn handler(sender: Arc<ScriptedSender>, policy: RetryPolicy) -> IpcHandler {
+        IpcHandler::new(Arc::clone(&sender.pending), sender, Duration::from_secs(1))
+            .with_retry_policy(policy)
+    }
+
+    fn policy(max_attempts: u32, hedge_after: Option<Duration>) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            backoff_base: Duration::from_millis(1),
+            backoff_max: Duration::from_millis(5),
+            hedge_after,
+        }
+    }
+
+    fn block_number() -> RequestPayload {
+        RequestPayload::GetBlockNumber(GetBlockNumberRequest)
+    }
 
     #[tokio::test]
     async fn test_payload_method_name() {
@@ -418,5 +714,113 @@ mod tests {
             payload_method_name(&RequestPayload::GetBlockNumber(GetBlockNumberRequest)),
             "eth_blockNumber"
         );
+        assert_eq!(
+            payload_method_name(&RequestPayload::GetEventBus(GetEventBusRequest::default())),
+            "debug_eventBus"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_send_failure() {
+        let pending = Arc::new(PendingRequestStore::new(Duration::from_secs(1)));
+        let sender = ScriptedSender::new(&pending, 1, true);
+        let ipc = handler(Arc::clone(&sender), policy(3, None));
+
+        let result = ipc.request("qc-02", block_number(), None).await;
+
+        assert_eq!(result.unwrap(), serde_json::json!("0x1"));
+        assert_eq!(sender.sends(), 2);
+        assert_eq!(pending.pending_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_hedged_read_answered_by_duplicate() {
+        let pending = Arc::new(PendingRequestStore::new(Duration::from_secs(1)));
+        let sender = ScriptedSender::new(&pending, 1, false);
+        let ipc = handler(
+            Arc::clone(&sender),
+            policy(1, Some(Duration::from_millis(20))),
+        );
+
+        let result = ipc.request("qc-02", block_number(), None).await;
+
+        assert!(result.is_ok());
+        let sent = sender.sent.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0], sent[1]);
+    }
+
+    #[tokio::test]
+    async fn test_write_not_retried_or_hedged_after_timeout() {
+        let pending = Arc::new(PendingRequestStore::new(Duration::from_secs(1)));
+        let sender = ScriptedSender::new(&pending, usize::MAX, false);
+        let ipc = handler(
+            Arc::clone(&sender),
+            policy(3, Some(Duration::from_millis(5))),
+        );
+
+        let payload = RequestPayload::AddPeer(AddPeerRequest {
+            enode_url: "enode://peer@127.0.0.1:30303".into(),
+        });
+        let err = ipc
+            .request("qc-01", payload, Some(Duration::from_millis(60)))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, -32006);
+        assert_eq!(sender.sends(), 1);
+        assert_eq!(sender.dead_letters.lock().unwrap()[0].attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_after_exhaustion() {
+        let pending = Arc::new(PendingRequestStore::new(Duration::from_secs(1)));
+        let sender = ScriptedSender::new(&pending, usize::MAX, false);
+        let ipc = handler(Arc::clone(&sender), policy(2, None));
+
+        let err = ipc
+            .request("qc-02", block_number(), Some(Duration::from_millis(60)))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, -32006);
+        let sent = sender.sent.lock().unwrap();
+        let letters = sender.dead_letters.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(letters.len(), 1);
+        assert_eq!(letters[0].attempts, 2);
+        assert_eq!(letters[0].method, "eth_blockNumber");
+        assert!(sent.iter().all(|id| *id == letters[0].correlation_id));
+        assert_eq!(pending.pending_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_subsystem_fails_fast() {
+        use shared_types::{CapabilityReport, ErrorCode, SubsystemId};
+
+        let pending = Arc::new(PendingRequestStore::new(Duration::from_secs(1)));
+        let sender = ScriptedSender::new(&pending, 0, false);
+        let capabilities = Arc::new(AdvertisedCapabilities::new());
+        let ipc = handler(Arc::clone(&sender), policy(3, None))
+            .with_capabilities(Arc::clone(&capabilities));
+
+        // qc-07 is not registered on this node
+        capabilities.update(CapabilityReport::default());
+        let err = ipc
+            .request("qc-07-bloom-filters", block_number(), None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, ErrorCode::Unavailable.json_rpc_code());
+        let info: shared_types::ErrorInfo = serde_json::from_value(err.data.unwrap()).unwrap();
+        assert_eq!(info.origin, SubsystemId::BloomFilters);
+        assert_eq!(sender.sends(), 0);
+        assert!(sender.dead_letters.lock().unwrap().is_empty());
+
+        // Non-subsystem targets are unaffected
+        assert!(ipc
+            .request("node-runtime", block_number(), None)
+            .await
+            .is_ok());
     }
 }
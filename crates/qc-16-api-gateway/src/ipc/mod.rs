@@ -7,15 +7,17 @@ pub mod bus_adapter;
 pub mod handler;
 pub mod requests;
 pub mod responses;
+pub mod retry;
 pub mod validation;
 
 pub use bus_adapter::{
-    BlockQuery, EventBusReceiver, EventBusSender, MempoolQuery, PeerDiscoveryQuery, QueryRouter,
-    ResponseRouter, StateQuery, TxIndexQuery,
+    publish_dead_letter, BlockQuery, EventBusReceiver, EventBusSender, MempoolQuery,
+    PeerDiscoveryQuery, QueryRouter, ResponseRouter, StateQuery, TxIndexQuery,
 };
 pub use handler::{
-    IpcError, IpcHandler, IpcReceiver, IpcSender, ResilientIpcHandler, ResponseListener,
+    DeadLetter, IpcError, IpcHandler, IpcReceiver, IpcSender, ResilientIpcHandler, ResponseListener,
 };
 pub use requests::{IpcRequest, RequestPayload, SubmitTransactionRequest};
 pub use responses::{IpcResponse, ResponsePayload, SuccessData};
+pub use retry::RetryPolicy;
 pub use validation::{create_submit_request, validate_raw_transaction, ValidatedTransaction};
@@ -3,8 +3,10 @@
 //! CRITICAL: Read-only requests have NO signatures (internal trusted channels).
 //! Only SubmitTransaction includes user's transaction signature.
 
-use crate::domain::types::{Address, BlockId, Bytes, CallRequest, Filter, Hash, U256};
+use crate::domain::correlation::current_trace_context;
+use crate::domain::types::{Address, BlockId, Bytes, CallRequest, Filter, Hash, SwapOffer, U256};
 use crate::CorrelationId;
+use quantum_telemetry::PropagatedContext;
 use serde::{Deserialize, Serialize};
 
 /// Request envelope for all IPC messages
@@ -16,6 +18,9 @@ pub struct IpcRequest {
     pub target: String,
     /// Request payload
     pub payload: RequestPayload,
+    /// Trace context of the gateway span that issued the request
+    #[serde(default)]
+    pub trace_context: Option<PropagatedContext>,
 }
 
 /// All possible request payloads
@@ -37,6 +42,7 @@ pub enum RequestPayload {
     GetBlockByNumber(GetBlockByNumberRequest),
     GetBlockNumber(GetBlockNumberRequest),
     GetFeeHistory(GetFeeHistoryRequest),
+    GetRawBlock(GetRawBlockRequest),
 
     // ═══════════════════════════════════════════════════════════════════════
     // TRANSACTION QUERIES → qc-03-transaction-indexing
@@ -60,6 +66,7 @@ pub enum RequestPayload {
     GetMaxPriorityFeePerGas(GetMaxPriorityFeePerGasRequest),
     GetTxPoolStatus(GetTxPoolStatusRequest),
     GetTxPoolContent(GetTxPoolContentRequest),
+    GetTxPoolTransaction(GetTxPoolTransactionRequest),
 
     // ═══════════════════════════════════════════════════════════════════════
     // PEER DISCOVERY → qc-01-peer-discovery
@@ -69,6 +76,21 @@ pub enum RequestPayload {
     AddPeer(AddPeerRequest),
     RemovePeer(RemovePeerRequest),
 
+    // ═══════════════════════════════════════════════════════════════════════
+    // CROSS-CHAIN → qc-15-cross-chain
+    // ═══════════════════════════════════════════════════════════════════════
+    GetSwapQuote(GetSwapQuoteRequest),
+    GetSwapMakers(GetSwapMakersRequest),
+    AdvertiseSwapLiquidity(AdvertiseSwapLiquidityRequest),
+    WithdrawSwapLiquidity(WithdrawSwapLiquidityRequest),
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // BLOCK PRODUCTION → qc-17-block-production
+    // ═══════════════════════════════════════════════════════════════════════
+    GetBlockTemplate(GetBlockTemplateRequest),
+    SubmitBlock(SubmitBlockRequest),
+    GetMevReports(GetMevReportsRequest),
+
     // ═══════════════════════════════════════════════════════════════════════
     // NODE RUNTIME → node-runtime
     // ═══════════════════════════════════════════════════════════════════════
@@ -81,6 +103,22 @@ pub enum RequestPayload {
     Ping,
     /// Get subsystem-specific metrics
     GetSubsystemMetrics(GetSubsystemMetricsRequest),
+    /// Get the lifecycle status of every registered subsystem
+    GetSubsystemStatus(GetSubsystemStatusRequest),
+    /// Stop, start or restart a subsystem at runtime
+    ControlSubsystem(ControlSubsystemRequest),
+    /// Inspect the node's event bus
+    GetEventBus(GetEventBusRequest),
+    /// Recent canonical blocks and chain reorganizations
+    GetRecentBlocks(GetRecentBlocksRequest),
+    /// Sampled history of the headline metrics
+    GetMetricHistory(GetMetricHistoryRequest),
+    /// Drop a transaction from the mempool
+    EvictTransaction(EvictTransactionRequest),
+    /// The node's effective configuration
+    GetConfig(GetConfigRequest),
+    /// Change settings that take effect without a restart
+    SetConfig(SetConfigRequest),
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -149,6 +187,12 @@ pub struct GetFeeHistoryRequest {
     pub reward_percentiles: Option<Vec<f64>>,
 }
 
+/// Get serialized block bytes request (admin only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRawBlockRequest {
+    pub block_id: BlockId,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // TRANSACTION QUERY REQUESTS
 // ═══════════════════════════════════════════════════════════════════════════
@@ -240,6 +284,13 @@ pub struct GetTxPoolContentRequest {
     pub address: Option<Address>,
 }
 
+/// Get a single pooled transaction with its sender's nonce gaps and
+/// replacement history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetTxPoolTransactionRequest {
+    pub hash: Hash,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // NETWORK REQUESTS
 // ═══════════════════════════════════════════════════════════════════════════
@@ -256,6 +307,67 @@ pub struct GetNodeInfoRequest;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GetSyncStatusRequest;
 
+// ═══════════════════════════════════════════════════════════════════════════
+// CROSS-CHAIN REQUESTS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Get swap quote request (fees, timelocks, completion estimate)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSwapQuoteRequest {
+    pub source_chain: String,
+    pub target_chain: String,
+    /// Amount paid on the source chain
+    pub amount: U256,
+}
+
+/// List maker offers for a swap pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSwapMakersRequest {
+    pub source_chain: String,
+    pub target_chain: String,
+}
+
+/// Advertise this node's swap liquidity (admin only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdvertiseSwapLiquidityRequest {
+    pub offer: SwapOffer,
+}
+
+/// Withdraw a previously advertised offer (admin only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawSwapLiquidityRequest {
+    pub maker: Address,
+    pub source_chain: String,
+    pub target_chain: String,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// BLOCK PRODUCTION REQUESTS
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Get a block template for an external producer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBlockTemplateRequest {
+    /// Coinbase recipient (None = node default)
+    pub beneficiary: Option<Address>,
+}
+
+/// Submit a solved block template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitBlockRequest {
+    pub template_id: String,
+    pub nonce: u64,
+}
+
+/// Get MEV reports for recently produced blocks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetMevReportsRequest {
+    /// Only this block's report (None = most recent)
+    pub block_number: Option<u64>,
+    /// Maximum reports returned
+    pub limit: u32,
+}
+
 /// Add peer request (admin only)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddPeerRequest {
@@ -275,6 +387,90 @@ pub struct GetSubsystemMetricsRequest {
     pub subsystem_id: u8,
 }
 
+/// Get subsystem lifecycle status request (admin only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetSubsystemStatusRequest;
+
+/// Runtime lifecycle action on a subsystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubsystemAction {
+    Stop,
+    Start,
+    Restart,
+}
+
+/// Control subsystem request (admin only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlSubsystemRequest {
+    /// Subsystem ID (1-17)
+    pub subsystem_id: u8,
+    pub action: SubsystemAction,
+    /// Only check whether the action would be allowed
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Get event bus inspection request (admin only)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GetEventBusRequest {
+    /// Topic names or prefix patterns ("block.*"); empty means all topics
+    pub topics: Vec<String>,
+    /// Only events numbered after this (the `cursor` of the last response)
+    pub since: u64,
+    /// Most recent events per topic (node default if unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// Get recent blocks request (admin only)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GetRecentBlocksRequest {
+    /// Blocks below the head to return (node default if unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// Encoding of a metric history response
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricHistoryFormat {
+    /// One array per series
+    #[default]
+    Json,
+    /// One row per sample, for attaching to bug reports
+    Csv,
+}
+
+/// Get metric history request (admin only)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GetMetricHistoryRequest {
+    /// Most recent samples to return (all kept samples if unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    pub format: MetricHistoryFormat,
+}
+
+/// Evict transaction request (admin only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvictTransactionRequest {
+    pub hash: Hash,
+}
+
+/// Get effective configuration request (admin only)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GetConfigRequest;
+
+/// Set configuration request (admin only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetConfigRequest {
+    /// Dotted config keys and their new values, e.g.
+    /// `{"mining.duty_cycle_percent": 50}`
+    pub changes: serde_json::Value,
+}
+
 impl IpcRequest {
     /// Create a new IPC request
     pub fn new(target: impl Into<String>, payload: RequestPayload) -> Self {
@@ -282,6 +478,7 @@ impl IpcRequest {
             correlation_id: CorrelationId::new(),
             target: target.into(),
             payload,
+            trace_context: current_trace_context(),
         }
     }
 
@@ -295,6 +492,7 @@ impl IpcRequest {
             correlation_id,
             target: target.into(),
             payload,
+            trace_context: current_trace_context(),
         }
     }
 
@@ -309,6 +507,7 @@ impl IpcRequest {
             RequestPayload::GetBlockByNumber(_) => "get_block_by_number".to_string(),
             RequestPayload::GetBlockNumber(_) => "get_block_number".to_string(),
             RequestPayload::GetFeeHistory(_) => "get_fee_history".to_string(),
+            RequestPayload::GetRawBlock(_) => "get_raw_block".to_string(),
             RequestPayload::GetTransactionByHash(_) => "get_transaction_by_hash".to_string(),
             RequestPayload::GetTransactionReceipt(_) => "get_transaction_receipt".to_string(),
             RequestPayload::GetLogs(_) => "get_logs".to_string(),
@@ -320,13 +519,29 @@ impl IpcRequest {
             RequestPayload::GetMaxPriorityFeePerGas(_) => "get_max_priority_fee".to_string(),
             RequestPayload::GetTxPoolStatus(_) => "get_txpool_status".to_string(),
             RequestPayload::GetTxPoolContent(_) => "get_txpool_content".to_string(),
+            RequestPayload::GetTxPoolTransaction(_) => "get_txpool_transaction".to_string(),
             RequestPayload::GetPeers(_) => "get_peers".to_string(),
             RequestPayload::GetNodeInfo(_) => "get_node_info".to_string(),
             RequestPayload::GetSyncStatus(_) => "get_sync_status".to_string(),
+            RequestPayload::GetSwapQuote(_) => "get_swap_quote".to_string(),
+            RequestPayload::GetSwapMakers(_) => "get_swap_makers".to_string(),
+            RequestPayload::AdvertiseSwapLiquidity(_) => "advertise_swap_liquidity".to_string(),
+            RequestPayload::WithdrawSwapLiquidity(_) => "withdraw_swap_liquidity".to_string(),
+            RequestPayload::GetBlockTemplate(_) => "get_block_template".to_string(),
+            RequestPayload::SubmitBlock(_) => "submit_block".to_string(),
+            RequestPayload::GetMevReports(_) => "get_mev_reports".to_string(),
             RequestPayload::AddPeer(_) => "add_peer".to_string(),
             RequestPayload::RemovePeer(_) => "remove_peer".to_string(),
             RequestPayload::Ping => "ping".to_string(),
             RequestPayload::GetSubsystemMetrics(_) => "get_subsystem_metrics".to_string(),
+            RequestPayload::GetSubsystemStatus(_) => "get_subsystem_status".to_string(),
+            RequestPayload::ControlSubsystem(_) => "control_subsystem".to_string(),
+            RequestPayload::GetEventBus(_) => "get_event_bus".to_string(),
+            RequestPayload::GetRecentBlocks(_) => "get_recent_blocks".to_string(),
+            RequestPayload::GetMetricHistory(_) => "get_metric_history".to_string(),
+            RequestPayload::EvictTransaction(_) => "evict_transaction".to_string(),
+            RequestPayload::GetConfig(_) => "get_config".to_string(),
+            RequestPayload::SetConfig(_) => "set_config".to_string(),
         }
     }
 
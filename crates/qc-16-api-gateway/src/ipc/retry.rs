@@ -0,0 +1,106 @@
+//! Retry and hedging policy for IPC requests.
+//!
+//! A request's timeout is its total budget. The budget is split evenly
+//! across attempts; an attempt that times out or fails to send is retried
+//! after an exponential backoff with jitter. Retries reuse the original
+//! correlation ID, so a late answer to an earlier attempt still completes
+//! the request.
+//!
+//! Read-only requests may also be hedged: if the first attempt has not been
+//! answered after `hedge_after`, the same request is sent once more and
+//! whichever response arrives first wins. Writes are never hedged and are
+//! retried only when the send itself failed, since a timed-out write may
+//! already have been applied.
+
+use rand::Rng;
+use std::time::Duration;
+
+/// Retry and hedging policy
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts per request, including the first (1 = no retries)
+    pub max_attempts: u32,
+    /// Backoff before the first retry; doubles per retry
+    pub backoff_base: Duration,
+    /// Upper bound on a single backoff
+    pub backoff_max: Duration,
+    /// Send a hedged duplicate of slow read requests after this delay
+    pub hedge_after: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 2,
+            backoff_base: Duration::from_millis(50),
+            backoff_max: Duration::from_secs(1),
+            hedge_after: Some(Duration::from_millis(250)),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Single attempt, no hedging (the behavior before retries existed)
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            hedge_after: None,
+            ..Default::default()
+        }
+    }
+
+    /// Backoff before retry number `retry` (1-based), with equal jitter:
+    /// half the exponential delay is fixed, the other half random
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exp = self
+            .backoff_base
+            .saturating_mul(1u32 << retry.saturating_sub(1).min(16))
+            .min(self.backoff_max);
+        let half = exp / 2;
+        half + rand::thread_rng().gen_range(Duration::ZERO..=half)
+    }
+
+    /// Per-attempt timeout when `budget` is shared by all attempts
+    pub fn attempt_timeout(&self, budget: Duration) -> Duration {
+        budget / self.max_attempts.max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_bounds() {
+        let policy = RetryPolicy {
+            backoff_base: Duration::from_millis(100),
+            backoff_max: Duration::from_millis(300),
+            ..Default::default()
+        };
+
+        for _ in 0..100 {
+            let first = policy.backoff(1);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+
+            // 100ms * 2^4 is capped at 300ms
+            let capped = policy.backoff(5);
+            assert!(capped >= Duration::from_millis(150) && capped <= Duration::from_millis(300));
+        }
+    }
+
+    #[test]
+    fn test_budget_split_across_attempts() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.attempt_timeout(Duration::from_secs(3)),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            RetryPolicy::none().attempt_timeout(Duration::from_secs(3)),
+            Duration::from_secs(3)
+        );
+    }
+}
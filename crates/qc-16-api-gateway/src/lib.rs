@@ -66,6 +66,7 @@
 //! | qc-08 Consensus | `StartMiningRequest`, `StopMiningRequest` | Block production (Admin) |
 //! | qc-10 Signature Verify | `VerifyTransactionRequest` | Tx signature validation |
 //! | qc-11 Smart Contracts | `ExecuteCallRequest`, `EstimateGasRequest` | eth_call/estimateGas |
+//! | qc-15 Cross-Chain | `GetSwapQuoteRequest`, `AdvertiseSwapLiquidityRequest` | Swap quotes, maker liquidity |
 //!
 //! **IMPORTANT:** Internal IPC messages do NOT require cryptographic signatures.
 //! The Event Bus uses in-memory channels which are process-private (SPEC v1.1 Fix).
@@ -157,6 +158,8 @@
 
 pub mod adapters;
 pub mod domain;
+#[cfg(feature = "graphql")]
+pub mod graphql;
 pub mod ipc;
 pub mod middleware;
 pub mod ports;
@@ -175,9 +178,9 @@ pub use domain::methods::{
 };
 pub use domain::types::*;
 pub use ipc::{IpcHandler, IpcRequest, IpcResponse, IpcSender};
-pub use middleware::GatewayMetrics;
+pub use middleware::{GatewayMetrics, RateLimitState};
 pub use service::ApiGatewayService;
-pub use ws::SubscriptionManager;
+pub use ws::{LogFeed, LogLevel, NodeLogLine, SubscriptionManager};
 
 /// Crate version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
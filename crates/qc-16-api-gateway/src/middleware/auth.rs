@@ -1,7 +1,9 @@
 //! Authentication middleware per SPEC-16 Section 7.4.
 //!
-//! Enforces method tier restrictions based on API key and localhost status.
+//! Enforces method tier restrictions based on API key, JWT and localhost status.
 
+use crate::adapters::api_keys::{ApiKeyRecord, ApiKeyStore};
+use crate::middleware::jwt::{presented_jwt, JwtClaims, JwtValidator};
 use crate::ApiError;
 use crate::{get_method_tier, MethodTier};
 use axum::{
@@ -10,6 +12,7 @@ use axum::{
     http::{Request, StatusCode},
     response::Response,
 };
+use shared_types::SecretBytes;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::sync::Arc;
 use tower::{Layer, Service};
@@ -19,9 +22,22 @@ use tracing::{debug, warn};
 #[derive(Clone, Default)]
 pub struct AuthConfig {
     /// API key for protected/admin access (None = no key required)
-    pub api_key: Option<String>,
+    pub api_key: Option<SecretBytes>,
     /// Allow admin access from non-localhost (DANGEROUS)
     pub allow_external_admin: bool,
+    /// Scoped API keys (in addition to `api_key`)
+    pub keys: Option<Arc<ApiKeyStore>>,
+    /// HS256 JWT validation (Engine API style shared secret)
+    pub jwt: Option<Arc<JwtValidator>>,
+}
+
+impl AuthConfig {
+    /// Check if any API key or JWT secret is configured, making credentials mandatory
+    pub fn key_required(&self) -> bool {
+        self.api_key.is_some()
+            || self.jwt.is_some()
+            || self.keys.as_ref().is_some_and(|k| k.has_active_keys())
+    }
 }
 
 /// Authentication layer
@@ -84,55 +100,9 @@ where
             let method = extract_method_from_request(&req);
 
             if let Some(method_name) = &method {
-                let tier = get_method_tier(method_name).unwrap_or(MethodTier::Admin);
-                let is_localhost = is_request_from_localhost(&req);
-                let has_valid_key = check_api_key(&req, &config);
-
-                debug!(
-                    method = method_name,
-                    tier = ?tier,
-                    is_localhost = is_localhost,
-                    has_valid_key = has_valid_key,
-                    "Checking method authorization"
-                );
-
-                // Check authorization based on tier
-                match tier {
-                    MethodTier::Public => {
-                        // Always allowed
-                    }
-                    MethodTier::Protected => {
-                        // Requires API key OR localhost
-                        if !has_valid_key && !is_localhost {
-                            warn!(
-                                method = method_name,
-                                "Protected method access denied - requires API key or localhost"
-                            );
-                            return Ok(unauthorized_response(
-                                "Protected method requires API key or localhost access",
-                            ));
-                        }
-                    }
-                    MethodTier::Admin => {
-                        // Requires localhost (unless allow_external_admin) AND API key (if configured)
-                        if !is_localhost && !config.allow_external_admin {
-                            warn!(
-                                method = method_name,
-                                "Admin method access denied - localhost required"
-                            );
-                            return Ok(unauthorized_response(
-                                "Admin method requires localhost access",
-                            ));
-                        }
-
-                        if config.api_key.is_some() && !has_valid_key {
-                            warn!(
-                                method = method_name,
-                                "Admin method access denied - API key required"
-                            );
-                            return Ok(unauthorized_response("Admin method requires API key"));
-                        }
-                    }
+                let caller = CallerContext::from_request(&req, &config);
+                if let Err(e) = authorize_method(method_name, &caller, &config) {
+                    return Ok(unauthorized_response(e));
                 }
             }
 
@@ -142,6 +112,129 @@ where
     }
 }
 
+/// Credentials of a JSON-RPC caller, resolved from the transport
+#[derive(Debug, Clone, Default)]
+pub struct CallerContext {
+    /// Request originated from a loopback address
+    pub is_localhost: bool,
+    /// Request carried the configured API key (or no key is configured)
+    pub has_valid_key: bool,
+    /// Scoped key from the key store; grants access only to its scopes
+    pub key: Option<ApiKeyRecord>,
+    /// Claims of a valid JWT; grants access to the methods they allow
+    pub jwt: Option<JwtClaims>,
+}
+
+impl CallerContext {
+    /// Resolve caller credentials from request headers and connection info
+    pub fn from_request<B>(req: &Request<B>, config: &AuthConfig) -> Self {
+        let key = match (&config.keys, presented_api_key(req)) {
+            (Some(store), Some(presented)) => store.authenticate(presented),
+            _ => None,
+        };
+        let has_valid_key = if config.api_key.is_some() {
+            check_api_key(req, config)
+        } else {
+            !config.key_required()
+        };
+        let jwt = match (&config.jwt, presented_jwt(req)) {
+            (Some(validator), Some(token)) => match validator.validate(token) {
+                Ok(claims) => Some(claims),
+                Err(e) => {
+                    warn!(error = %e, "Rejected JWT");
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        Self {
+            is_localhost: is_request_from_localhost(req),
+            has_valid_key,
+            key,
+            jwt,
+        }
+    }
+}
+
+/// Check a method's tier against the caller's credentials.
+///
+/// Unknown methods are treated as Admin. A scoped key counts as a valid key
+/// only for methods inside its scopes, and is subject to its own rate limit.
+pub fn authorize_method(
+    method: &str,
+    caller: &CallerContext,
+    config: &AuthConfig,
+) -> Result<(), ApiError> {
+    let result = check_tier(method, caller, config);
+
+    if let (Some(key), Some(store)) = (&caller.key, &config.keys) {
+        store.record_usage(&key.id, result.is_ok());
+        if result.is_ok() {
+            store
+                .check_rate_limit(key)
+                .map_err(|wait| ApiError::rate_limited(wait.as_millis() as u64))?;
+        }
+    }
+
+    result
+}
+
+fn check_tier(method: &str, caller: &CallerContext, config: &AuthConfig) -> Result<(), ApiError> {
+    let tier = get_method_tier(method).unwrap_or(MethodTier::Admin);
+    let has_valid_key = caller.has_valid_key
+        || caller.key.as_ref().is_some_and(|k| k.allows(method))
+        || caller.jwt.as_ref().is_some_and(|c| c.allows(method));
+
+    debug!(
+        method = method,
+        tier = ?tier,
+        is_localhost = caller.is_localhost,
+        has_valid_key = has_valid_key,
+        key_id = caller.key.as_ref().map(|k| k.id.as_str()),
+        jwt_id = caller.jwt.as_ref().and_then(|c| c.id.as_deref()),
+        "Checking method authorization"
+    );
+
+    match tier {
+        MethodTier::Public => Ok(()),
+        MethodTier::Protected => {
+            // Requires API key OR localhost
+            if !has_valid_key && !caller.is_localhost {
+                warn!(
+                    method = method,
+                    "Protected method access denied - requires API key or localhost"
+                );
+                return Err(ApiError::unauthorized(
+                    "Protected method requires API key or localhost access",
+                ));
+            }
+            Ok(())
+        }
+        MethodTier::Admin => {
+            // Requires localhost (unless allow_external_admin) AND API key (if configured)
+            if !caller.is_localhost && !config.allow_external_admin {
+                warn!(
+                    method = method,
+                    "Admin method access denied - localhost required"
+                );
+                return Err(ApiError::unauthorized(
+                    "Admin method requires localhost access",
+                ));
+            }
+
+            if config.key_required() && !has_valid_key {
+                warn!(
+                    method = method,
+                    "Admin method access denied - API key required"
+                );
+                return Err(ApiError::unauthorized("Admin method requires API key"));
+            }
+            Ok(())
+        }
+    }
+}
+
 /// Check if request is from localhost
 fn is_request_from_localhost<B>(req: &Request<B>) -> bool {
     // Try to get from ConnectInfo
@@ -181,11 +274,19 @@ fn check_api_key<B>(req: &Request<B>, config: &AuthConfig) -> bool {
         None => return true, // No key configured = always valid
     };
 
+    let Some(expected_key) = expected_key.expose_str() else {
+        return false;
+    };
+    presented_api_key(req).is_some_and(|key| constant_time_compare(key, expected_key))
+}
+
+/// Extract the API key presented by the caller, if any
+pub(crate) fn presented_api_key<B>(req: &Request<B>) -> Option<&str> {
     // Check Authorization header (Bearer token)
     if let Some(auth) = req.headers().get("authorization") {
         if let Ok(auth_str) = auth.to_str() {
             if let Some(token) = auth_str.strip_prefix("Bearer ") {
-                return constant_time_compare(token, expected_key);
+                return Some(token);
             }
         }
     }
@@ -193,20 +294,15 @@ fn check_api_key<B>(req: &Request<B>, config: &AuthConfig) -> bool {
     // Check X-API-Key header
     if let Some(api_key) = req.headers().get("x-api-key") {
         if let Ok(key_str) = api_key.to_str() {
-            return constant_time_compare(key_str, expected_key);
+            return Some(key_str);
         }
     }
 
     // Check query parameter (less secure, for debugging)
-    if let Some(query) = req.uri().query() {
-        for pair in query.split('&') {
-            if let Some(key) = pair.strip_prefix("api_key=") {
-                return constant_time_compare(key, expected_key);
-            }
-        }
-    }
-
-    false
+    req.uri()
+        .query()?
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("api_key="))
 }
 
 /// Constant-time string comparison to prevent timing attacks
@@ -261,8 +357,7 @@ fn extract_method_from_request<B>(req: &Request<B>) -> Option<String> {
 }
 
 /// Create unauthorized response
-fn unauthorized_response(message: &str) -> Response {
-    let error = ApiError::unauthorized(message);
+fn unauthorized_response(error: ApiError) -> Response {
     let body = serde_json::json!({
         "jsonrpc": "2.0",
         "error": error,
@@ -305,8 +400,9 @@ mod tests {
     #[test]
     fn test_api_key_check_bearer() {
         let config = AuthConfig {
-            api_key: Some("test-key-123".to_string()),
+            api_key: Some("test-key-123".into()),
             allow_external_admin: false,
+            ..Default::default()
         };
 
         let req = Request::builder()
@@ -327,8 +423,9 @@ mod tests {
     #[test]
     fn test_api_key_check_header() {
         let config = AuthConfig {
-            api_key: Some("test-key-123".to_string()),
+            api_key: Some("test-key-123".into()),
             allow_external_admin: false,
+            ..Default::default()
         };
 
         let req = Request::builder()
@@ -344,6 +441,7 @@ mod tests {
         let config = AuthConfig {
             api_key: None,
             allow_external_admin: false,
+            ..Default::default()
         };
 
         let req = Request::builder().body(Body::empty()).unwrap();
@@ -351,4 +449,94 @@ mod tests {
         // No key configured = always valid
         assert!(check_api_key(&req, &config));
     }
+
+    #[test]
+    fn test_authorize_method_tiers() {
+        let config = AuthConfig {
+            api_key: Some("test-key-123".into()),
+            allow_external_admin: false,
+            ..Default::default()
+        };
+        let remote = CallerContext::default();
+        let local_with_key = CallerContext {
+            is_localhost: true,
+            has_valid_key: true,
+            ..Default::default()
+        };
+        let local_without_key = CallerContext {
+            is_localhost: true,
+            has_valid_key: false,
+            ..Default::default()
+        };
+
+        assert!(authorize_method("eth_blockNumber", &remote, &config).is_ok());
+        assert!(authorize_method("txpool_status", &remote, &config).is_err());
+        assert!(authorize_method("txpool_status", &local_without_key, &config).is_ok());
+        assert!(authorize_method("debug_getRawBlock", &local_without_key, &config).is_err());
+        assert!(authorize_method("debug_getRawBlock", &local_with_key, &config).is_ok());
+    }
+
+    #[test]
+    fn test_scoped_key_grants_only_its_scopes() {
+        let store = Arc::new(ApiKeyStore::in_memory());
+        let (_, key) = store
+            .create("ops".into(), vec!["txpool_*".into()], None)
+            .unwrap();
+        let config = AuthConfig {
+            keys: Some(store),
+            ..Default::default()
+        };
+
+        let req = Request::builder()
+            .header("X-API-Key", key.as_str())
+            .body(Body::empty())
+            .unwrap();
+        let caller = CallerContext::from_request(&req, &config);
+        assert!(caller.key.is_some());
+        assert!(!caller.has_valid_key);
+
+        assert!(authorize_method("txpool_status", &caller, &config).is_ok());
+        assert!(authorize_method("admin_addPeer", &caller, &config).is_err());
+
+        // Once keys exist, anonymous callers no longer count as keyed
+        let anonymous = Request::builder().body(Body::empty()).unwrap();
+        let caller = CallerContext::from_request(&anonymous, &config);
+        assert!(!caller.has_valid_key);
+        assert!(authorize_method("txpool_status", &caller, &config).is_err());
+    }
+
+    #[test]
+    fn test_jwt_grants_only_claimed_methods() {
+        let validator = Arc::new(JwtValidator::from_hex(&"ab".repeat(32)).unwrap());
+        let token = validator.sign(&JwtClaims {
+            iat: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            methods: Some(vec!["admin_addPeer".into()]),
+            ..Default::default()
+        });
+        let config = AuthConfig {
+            jwt: Some(validator),
+            ..Default::default()
+        };
+
+        let req = Request::builder()
+            .header("Authorization", format!("Bearer {}", token))
+            .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 9000))))
+            .body(Body::empty())
+            .unwrap();
+        let caller = CallerContext::from_request(&req, &config);
+        assert!(caller.jwt.is_some());
+        assert!(authorize_method("admin_addPeer", &caller, &config).is_ok());
+        assert!(authorize_method("admin_removePeer", &caller, &config).is_err());
+
+        // A configured secret makes credentials mandatory for admin methods
+        let anonymous = Request::builder()
+            .extension(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 9000))))
+            .body(Body::empty())
+            .unwrap();
+        let caller = CallerContext::from_request(&anonymous, &config);
+        assert!(authorize_method("admin_addPeer", &caller, &config).is_err());
+    }
 }
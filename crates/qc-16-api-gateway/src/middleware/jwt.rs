@@ -0,0 +1,335 @@
+//! JWT authentication compatible with Engine API style `jwt.hex` secrets.
+//!
+//! Tokens are HS256-signed with a 32-byte secret read from a hex file. The
+//! `iat` claim is required and must lie within the allowed clock skew of
+//! the gateway's clock; `exp` and `nbf` are honored when present, with the
+//! same tolerance. An optional `methods` claim restricts the token to the
+//! listed JSON-RPC methods, using the same `prefix_*` patterns as API key
+//! scopes.
+
+use crate::adapters::api_keys::scope_matches;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use shared_types::SecretBytes;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Secret length required by the Engine API convention
+pub const JWT_SECRET_LEN: usize = 32;
+
+/// Default tolerance between token `iat` and the gateway clock
+pub const DEFAULT_CLOCK_SKEW: Duration = Duration::from_secs(60);
+
+/// JWT errors
+#[derive(Debug, thiserror::Error)]
+pub enum JwtError {
+    #[error("failed to read JWT secret: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("JWT secret must be {JWT_SECRET_LEN} hex-encoded bytes")]
+    InvalidSecret,
+    #[error("malformed token")]
+    Malformed,
+    #[error("unsupported algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("token issued {0}s away from server time")]
+    IssuedAtSkew(u64),
+    #[error("token expired")]
+    Expired,
+    #[error("token not yet valid")]
+    NotYetValid,
+}
+
+/// Token header (only the fields we check)
+#[derive(Debug, Serialize, Deserialize)]
+struct JwtHeader {
+    alg: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    typ: Option<String>,
+}
+
+/// Token claims
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JwtClaims {
+    /// Issued at (unix seconds)
+    pub iat: u64,
+    /// Expiry (unix seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+    /// Not before (unix seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<u64>,
+    /// Caller identifier, for logging
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Methods this token may call (None = unrestricted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub methods: Option<Vec<String>>,
+}
+
+impl JwtClaims {
+    /// Check if the token's method restrictions cover `method`
+    pub fn allows(&self, method: &str) -> bool {
+        self.methods
+            .as_ref()
+            .is_none_or(|methods| methods.iter().any(|m| scope_matches(m, method)))
+    }
+}
+
+/// HS256 token validator over a shared secret
+///
+/// The secret is held as `SecretBytes`, so it is wiped on drop.
+pub struct JwtValidator {
+    secret: SecretBytes,
+    clock_skew: Duration,
+}
+
+impl std::fmt::Debug for JwtValidator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JwtValidator")
+            .field("clock_skew", &self.clock_skew)
+            .finish_non_exhaustive()
+    }
+}
+
+impl JwtValidator {
+    /// Create from a hex secret (optional `0x` prefix, surrounding whitespace ignored)
+    pub fn from_hex(secret: &str) -> Result<Self, JwtError> {
+        let secret = secret.trim();
+        let secret = secret.strip_prefix("0x").unwrap_or(secret);
+        let secret = SecretBytes::new(hex::decode(secret).map_err(|_| JwtError::InvalidSecret)?);
+        if secret.len() != JWT_SECRET_LEN {
+            return Err(JwtError::InvalidSecret);
+        }
+        Ok(Self {
+            secret,
+            clock_skew: DEFAULT_CLOCK_SKEW,
+        })
+    }
+
+    /// Load the secret from a `jwt.hex` style file
+    pub fn from_file(path: &Path) -> Result<Self, JwtError> {
+        // The file's hex text is secret too
+        let text = SecretBytes::from(std::fs::read_to_string(path)?);
+        Self::from_hex(text.expose_str().ok_or(JwtError::InvalidSecret)?)
+    }
+
+    /// Set the tolerated clock skew
+    pub fn with_clock_skew(mut self, clock_skew: Duration) -> Self {
+        self.clock_skew = clock_skew;
+        self
+    }
+
+    /// Validate a token against the current time
+    pub fn validate(&self, token: &str) -> Result<JwtClaims, JwtError> {
+        self.validate_at(token, unix_now())
+    }
+
+    fn validate_at(&self, token: &str, now: u64) -> Result<JwtClaims, JwtError> {
+        let (signing_input, signature) = token.rsplit_once('.').ok_or(JwtError::Malformed)?;
+        let (header, claims) = signing_input.split_once('.').ok_or(JwtError::Malformed)?;
+
+        let header: JwtHeader = decode_segment(header)?;
+        if header.alg != "HS256" {
+            return Err(JwtError::UnsupportedAlgorithm(header.alg));
+        }
+
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| JwtError::Malformed)?;
+        self.mac(signing_input)
+            .verify_slice(&signature)
+            .map_err(|_| JwtError::InvalidSignature)?;
+
+        let claims: JwtClaims = decode_segment(claims)?;
+        let skew = self.clock_skew.as_secs();
+        if claims.iat.abs_diff(now) > skew {
+            return Err(JwtError::IssuedAtSkew(claims.iat.abs_diff(now)));
+        }
+        if claims.exp.is_some_and(|exp| now > exp.saturating_add(skew)) {
+            return Err(JwtError::Expired);
+        }
+        if claims.nbf.is_some_and(|nbf| now.saturating_add(skew) < nbf) {
+            return Err(JwtError::NotYetValid);
+        }
+        Ok(claims)
+    }
+
+    /// Sign claims into a token (for clients and tests sharing the secret)
+    pub fn sign(&self, claims: &JwtClaims) -> String {
+        let header = JwtHeader {
+            alg: "HS256".into(),
+            typ: Some("JWT".into()),
+        };
+        let signing_input = format!("{}.{}", encode_segment(&header), encode_segment(claims));
+        let signature = self.mac(&signing_input).finalize().into_bytes();
+        format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature))
+    }
+
+    fn mac(&self, signing_input: &str) -> HmacSha256 {
+        // SAFETY: HMAC-SHA256 can take a key of any size, so this never fails
+        #[allow(clippy::expect_used)]
+        let mut mac = HmacSha256::new_from_slice(self.secret.expose_secret())
+            .expect("HMAC can take key of any size");
+        mac.update(signing_input.as_bytes());
+        mac
+    }
+}
+
+fn decode_segment<T: serde::de::DeserializeOwned>(segment: &str) -> Result<T, JwtError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|_| JwtError::Malformed)?;
+    serde_json::from_slice(&bytes).map_err(|_| JwtError::Malformed)
+}
+
+fn encode_segment<T: Serialize>(value: &T) -> String {
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(value).unwrap_or_default())
+}
+
+/// Bearer token from the Authorization header, if it looks like a JWT
+pub(crate) fn presented_jwt<B>(req: &Request<B>) -> Option<&str> {
+    req.headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .filter(|token| token.matches('.').count() == 2)
+}
+
+/// Admin port middleware: every request needs a valid token, except the
+/// health probes, which orchestrators call without credentials
+pub async fn require_jwt(
+    State(validator): State<Arc<JwtValidator>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if req.uri().path().starts_with("/health") {
+        return next.run(req).await;
+    }
+
+    let result = presented_jwt(&req)
+        .ok_or(JwtError::Malformed)
+        .and_then(|token| validator.validate(token));
+    match result {
+        Ok(_) => next.run(req).await,
+        Err(e) => {
+            warn!(path = %req.uri().path(), error = %e, "Admin request rejected");
+            (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer")],
+                Json(serde_json::json!({ "error": e.to_string() })),
+            )
+                .into_response()
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "0x7365637265747365637265747365637265747365637265747365637265747365";
+
+    fn claims(iat: u64) -> JwtClaims {
+        JwtClaims {
+            iat,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_and_tampering() {
+        let validator = JwtValidator::from_hex(SECRET).unwrap();
+        let token = validator.sign(&claims(1_000));
+        assert_eq!(validator.validate_at(&token, 1_030).unwrap(), claims(1_000));
+
+        let other = JwtValidator::from_hex(&"11".repeat(32)).unwrap();
+        assert!(matches!(
+            other.validate_at(&token, 1_000),
+            Err(JwtError::InvalidSignature)
+        ));
+        assert!(matches!(
+            validator.validate_at("a.b", 1_000),
+            Err(JwtError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn test_clock_skew() {
+        let validator = JwtValidator::from_hex(SECRET)
+            .unwrap()
+            .with_clock_skew(Duration::from_secs(5));
+        let token = validator.sign(&claims(1_000));
+
+        assert!(validator.validate_at(&token, 995).is_ok());
+        assert!(validator.validate_at(&token, 1_005).is_ok());
+        assert!(matches!(
+            validator.validate_at(&token, 1_006),
+            Err(JwtError::IssuedAtSkew(6))
+        ));
+
+        let early = validator.sign(&JwtClaims {
+            nbf: Some(1_010),
+            ..claims(1_000)
+        });
+        assert!(matches!(
+            validator.validate_at(&early, 1_004),
+            Err(JwtError::NotYetValid)
+        ));
+        assert!(validator.validate_at(&early, 1_005).is_ok());
+    }
+
+    #[test]
+    fn test_secret_and_algorithm_checks() {
+        assert!(matches!(
+            JwtValidator::from_hex("abcd"),
+            Err(JwtError::InvalidSecret)
+        ));
+
+        let validator = JwtValidator::from_hex(SECRET).unwrap();
+        let header = encode_segment(&serde_json::json!({"alg": "none"}));
+        let token = format!("{}.{}.", header, encode_segment(&claims(1_000)));
+        assert!(matches!(
+            validator.validate_at(&token, 1_000),
+            Err(JwtError::UnsupportedAlgorithm(_))
+        ));
+    }
+
+    #[test]
+    fn test_method_restrictions() {
+        let unrestricted = claims(0);
+        assert!(unrestricted.allows("admin_addPeer"));
+
+        let restricted = JwtClaims {
+            methods: Some(vec!["admin_peers".into(), "debug_*".into()]),
+            ..claims(0)
+        };
+        assert!(restricted.allows("admin_peers"));
+        assert!(restricted.allows("debug_traceTransaction"));
+        assert!(!restricted.allows("admin_addPeer"));
+    }
+}
@@ -2,9 +2,66 @@
 //!
 //! Exposes metrics for monitoring via Grafana/Prometheus.
 
+use crate::domain::methods::{get_method_info, is_write_method, MethodTier};
+use dashmap::DashMap;
+use std::net::IpAddr;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Upper bounds (ms) of the per-method latency histogram buckets
+pub const LATENCY_BUCKETS_MS: [u64; 11] =
+    [5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000];
+
+/// Distinct rate-limit IP buckets tracked before drops are lumped into "other"
+const MAX_IP_BUCKETS: usize = 1024;
+
+/// Per-method request counters and latency histogram
+#[derive(Default)]
+pub struct MethodStats {
+    pub success: AtomicU64,
+    pub error: AtomicU64,
+    /// Cumulative latency in ms
+    pub latency_sum_ms: AtomicU64,
+    /// Non-cumulative counts per `LATENCY_BUCKETS_MS` bound; the last slot is +Inf
+    pub buckets: [AtomicU64; LATENCY_BUCKETS_MS.len() + 1],
+}
+
+impl MethodStats {
+    fn observe(&self, success: bool, latency_ms: u64) {
+        if success {
+            self.success.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.error.fetch_add(1, Ordering::Relaxed);
+        }
+        self.latency_sum_ms.fetch_add(latency_ms, Ordering::Relaxed);
+
+        let slot = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|bound| latency_ms <= *bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.buckets[slot].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Total observations
+    pub fn count(&self) -> u64 {
+        self.success.load(Ordering::Relaxed) + self.error.load(Ordering::Relaxed)
+    }
+
+    /// Cumulative bucket counts, Prometheus style (`le` bound, count)
+    pub fn cumulative_buckets(&self) -> Vec<(Option<u64>, u64)> {
+        let mut total = 0;
+        self.buckets
+            .iter()
+            .enumerate()
+            .map(|(i, count)| {
+                total += count.load(Ordering::Relaxed);
+                (LATENCY_BUCKETS_MS.get(i).copied(), total)
+            })
+            .collect()
+    }
+}
 
 /// API Gateway metrics
 #[derive(Default)]
@@ -32,6 +89,18 @@ pub struct GatewayMetrics {
     // Latency tracking (simplified - in production use histograms)
     pub total_latency_ms: AtomicU64,
     pub request_count_for_latency: AtomicU64,
+
+    // Per-method counters and latency histograms (unknown methods share one entry)
+    pub methods: DashMap<&'static str, MethodStats>,
+
+    // Authorization failures by tier (public, protected, admin)
+    pub auth_failures: [AtomicU64; 3],
+
+    // Rate limit drops by client IP bucket (IPv4 /24, IPv6 /48)
+    pub rate_limit_drops: DashMap<String, AtomicU64>,
+
+    // Requests slower than this are logged (None = disabled)
+    slow_request_threshold: Option<Duration>,
 }
 
 impl GatewayMetrics {
@@ -39,6 +108,63 @@ impl GatewayMetrics {
         Self::default()
     }
 
+    /// Log requests slower than `threshold`
+    pub fn with_slow_request_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.slow_request_threshold = threshold;
+        self
+    }
+
+    /// Record a completed JSON-RPC call: totals, the method's latency
+    /// histogram, and a slow-request log line above the threshold
+    pub fn record_method(&self, method: &str, success: bool, latency: Duration) {
+        let latency_ms = latency.as_millis() as u64;
+        let name = method_label(method);
+
+        self.record_request(success, is_write_method(method), latency_ms);
+        self.methods
+            .entry(name)
+            .or_default()
+            .observe(success, latency_ms);
+
+        if let Some(threshold) = self.slow_request_threshold {
+            if latency >= threshold {
+                warn!(
+                    method = name,
+                    latency_ms = latency_ms,
+                    threshold_ms = threshold.as_millis() as u64,
+                    success = success,
+                    "Slow request"
+                );
+            }
+        }
+    }
+
+    /// Record a request rejected by method tier authorization
+    pub fn record_auth_failure(&self, tier: MethodTier) {
+        self.auth_failures[tier_index(tier)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a request dropped by the per-IP rate limiter
+    pub fn record_rate_limit_drop(&self, ip: IpAddr) {
+        self.record_rate_limit_rejection();
+
+        let mut bucket = ip_bucket(ip);
+        if !self.rate_limit_drops.contains_key(&bucket)
+            && self.rate_limit_drops.len() >= MAX_IP_BUCKETS
+        {
+            bucket = "other".to_string();
+        }
+        self.rate_limit_drops
+            .entry(bucket)
+            .or_default()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Auth failures recorded for `tier`
+    pub fn auth_failures(&self, tier: MethodTier) -> u64 {
+        self.auth_failures[tier_index(tier)].load(Ordering::Relaxed)
+    }
+
     /// Record a request
     pub fn record_request(&self, success: bool, is_write: bool, latency_ms: u64) {
         self.requests_total.fetch_add(1, Ordering::Relaxed);
@@ -196,6 +322,68 @@ impl GatewayMetrics {
             self.average_latency_ms()
         ));
 
+        // Per-method latency histograms
+        output.push_str(
+            "# HELP api_gateway_method_duration_ms Request latency by method\n\
+             # TYPE api_gateway_method_duration_ms histogram\n",
+        );
+        for entry in self.sorted_methods() {
+            let (method, stats) = (entry.key(), entry.value());
+            for (bound, count) in stats.cumulative_buckets() {
+                let le = bound.map_or_else(|| "+Inf".to_string(), |b| b.to_string());
+                output.push_str(&format!(
+                    "api_gateway_method_duration_ms_bucket{{method=\"{}\",le=\"{}\"}} {}\n",
+                    method, le, count
+                ));
+            }
+            output.push_str(&format!(
+                "api_gateway_method_duration_ms_sum{{method=\"{}\"}} {}\n\
+                 api_gateway_method_duration_ms_count{{method=\"{}\"}} {}\n",
+                method,
+                stats.latency_sum_ms.load(Ordering::Relaxed),
+                method,
+                stats.count()
+            ));
+        }
+
+        output.push_str(
+            "# HELP api_gateway_method_errors_total Failed requests by method\n\
+             # TYPE api_gateway_method_errors_total counter\n",
+        );
+        for entry in self.sorted_methods() {
+            output.push_str(&format!(
+                "api_gateway_method_errors_total{{method=\"{}\"}} {}\n",
+                entry.key(),
+                entry.value().error.load(Ordering::Relaxed)
+            ));
+        }
+
+        // Authorization
+        output.push_str(
+            "# HELP api_gateway_auth_failures_total Requests denied by method tier\n\
+             # TYPE api_gateway_auth_failures_total counter\n",
+        );
+        for tier in [MethodTier::Public, MethodTier::Protected, MethodTier::Admin] {
+            output.push_str(&format!(
+                "api_gateway_auth_failures_total{{tier=\"{}\"}} {}\n",
+                tier_label(tier),
+                self.auth_failures(tier)
+            ));
+        }
+
+        // Rate limit drops per IP bucket
+        output.push_str(
+            "# HELP api_gateway_rate_limit_drops_total Rate limited requests by client IP bucket\n\
+             # TYPE api_gateway_rate_limit_drops_total counter\n",
+        );
+        for entry in self.rate_limit_drops.iter() {
+            output.push_str(&format!(
+                "api_gateway_rate_limit_drops_total{{ip_bucket=\"{}\"}} {}\n",
+                entry.key(),
+                entry.value().load(Ordering::Relaxed)
+            ));
+        }
+
         output
     }
 
@@ -222,9 +410,97 @@ impl GatewayMetrics {
             },
             "latency": {
                 "average_ms": self.average_latency_ms(),
-            }
+            },
+            "methods": self
+                .sorted_methods()
+                .iter()
+                .map(|entry| {
+                    let stats = entry.value();
+                    let count = stats.count();
+                    let sum = stats.latency_sum_ms.load(Ordering::Relaxed);
+                    let buckets: serde_json::Map<_, _> = stats
+                        .cumulative_buckets()
+                        .into_iter()
+                        .map(|(bound, count)| {
+                            let le = bound.map_or_else(|| "+Inf".to_string(), |b| b.to_string());
+                            (le, serde_json::json!(count))
+                        })
+                        .collect();
+                    (
+                        entry.key().to_string(),
+                        serde_json::json!({
+                            "success": stats.success.load(Ordering::Relaxed),
+                            "error": stats.error.load(Ordering::Relaxed),
+                            "average_ms": if count == 0 { 0.0 } else { sum as f64 / count as f64 },
+                            "buckets_ms": buckets,
+                        }),
+                    )
+                })
+                .collect::<serde_json::Map<_, _>>(),
+            "auth_failures": {
+                "public": self.auth_failures(MethodTier::Public),
+                "protected": self.auth_failures(MethodTier::Protected),
+                "admin": self.auth_failures(MethodTier::Admin),
+            },
+            "rate_limit_drops": self
+                .rate_limit_drops
+                .iter()
+                .map(|entry| {
+                    (
+                        entry.key().clone(),
+                        serde_json::json!(entry.value().load(Ordering::Relaxed)),
+                    )
+                })
+                .collect::<serde_json::Map<_, _>>(),
         })
     }
+
+    fn sorted_methods(
+        &self,
+    ) -> Vec<dashmap::mapref::multiple::RefMulti<'_, &'static str, MethodStats>> {
+        let mut methods: Vec<_> = self.methods.iter().collect();
+        methods.sort_by_key(|entry| *entry.key());
+        methods
+    }
+}
+
+/// Registry name for known methods; everything else shares "unknown" so
+/// arbitrary client input cannot grow the metric set
+fn method_label(method: &str) -> &'static str {
+    get_method_info(method)
+        .map(|info| info.name)
+        .unwrap_or("unknown")
+}
+
+fn tier_index(tier: MethodTier) -> usize {
+    match tier {
+        MethodTier::Public => 0,
+        MethodTier::Protected => 1,
+        MethodTier::Admin => 2,
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn tier_label(tier: MethodTier) -> &'static str {
+    match tier {
+        MethodTier::Public => "public",
+        MethodTier::Protected => "protected",
+        MethodTier::Admin => "admin",
+    }
+}
+
+/// Group client addresses so one noisy network maps to one label
+fn ip_bucket(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            format!("{}.{}.{}.0/24", a, b, c)
+        }
+        IpAddr::V6(v6) => {
+            let s = v6.segments();
+            format!("{:x}:{:x}:{:x}::/48", s[0], s[1], s[2])
+        }
+    }
 }
 
 /// Request timing helper
@@ -299,6 +575,48 @@ mod tests {
         assert_eq!(metrics.websocket_subscriptions.load(Ordering::Relaxed), 2);
     }
 
+    #[test]
+    fn test_method_histogram() {
+        let metrics = GatewayMetrics::new();
+
+        metrics.record_method("eth_call", true, Duration::from_millis(3));
+        metrics.record_method("eth_call", false, Duration::from_millis(300));
+        metrics.record_method("eth_bogusMethod", true, Duration::from_millis(1));
+
+        let stats = metrics.methods.get("eth_call").unwrap();
+        assert_eq!(stats.count(), 2);
+        assert_eq!(stats.error.load(Ordering::Relaxed), 1);
+
+        let buckets = stats.cumulative_buckets();
+        assert_eq!(buckets[0], (Some(5), 1));
+        assert_eq!(buckets[6], (Some(500), 2));
+        assert_eq!(buckets.last().unwrap(), &(None, 2));
+
+        // Arbitrary method names do not create new series
+        assert!(metrics.methods.contains_key("unknown"));
+        assert_eq!(metrics.requests_total.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_auth_failures_and_rate_limit_drops() {
+        let metrics = GatewayMetrics::new();
+
+        metrics.record_auth_failure(MethodTier::Admin);
+        metrics.record_auth_failure(MethodTier::Admin);
+        metrics.record_rate_limit_drop("203.0.113.7".parse().unwrap());
+        metrics.record_rate_limit_drop("203.0.113.200".parse().unwrap());
+        metrics.record_rate_limit_drop("2001:db8:1:2::1".parse().unwrap());
+
+        assert_eq!(metrics.auth_failures(MethodTier::Admin), 2);
+        assert_eq!(metrics.auth_failures(MethodTier::Protected), 0);
+        assert_eq!(metrics.rate_limit_rejected.load(Ordering::Relaxed), 3);
+
+        let json = metrics.to_json();
+        assert_eq!(json["rate_limit_drops"]["203.0.113.0/24"], 2);
+        assert_eq!(json["rate_limit_drops"]["2001:db8:1::/48"], 1);
+        assert_eq!(json["auth_failures"]["admin"], 2);
+    }
+
     #[test]
     fn test_json_export() {
         let metrics = GatewayMetrics::new();
@@ -13,6 +13,7 @@ pub mod auth;
 pub mod circuit_breaker;
 pub mod cors;
 pub mod ip_protection;
+pub mod jwt;
 pub mod metrics;
 pub mod rate_limit;
 pub mod timeout;
@@ -20,12 +21,13 @@ pub mod tracing;
 pub mod validation;
 pub mod whitelist;
 
-pub use auth::{constant_time_compare, AuthConfig, AuthLayer};
+pub use auth::{authorize_method, constant_time_compare, AuthConfig, AuthLayer, CallerContext};
 pub use circuit_breaker::{
     CircuitBreakerConfig, CircuitBreakerManager, CircuitState, CircuitStats,
 };
 pub use cors::create_cors_layer;
 pub use ip_protection::{IpProtectionLayer, TrustedProxyConfig};
+pub use jwt::{require_jwt, JwtClaims, JwtError, JwtValidator};
 pub use metrics::{GatewayMetrics, RequestTimer};
 pub use rate_limit::{RateLimitLayer, RateLimitState};
 pub use timeout::TimeoutLayer;
@@ -69,6 +71,8 @@ impl MiddlewareStack {
             auth: AuthLayer::new(AuthConfig {
                 api_key: config.admin.api_key.clone(),
                 allow_external_admin: config.admin.allow_external,
+                keys: None,
+                jwt: None,
             }),
             timeout: TimeoutLayer::new(config.timeouts.clone()),
             tracing: TracingLayer::new(),
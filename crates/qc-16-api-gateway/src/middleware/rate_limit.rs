@@ -3,7 +3,10 @@
 //! Implements per-IP rate limiting with configurable limits for reads and writes.
 //! Write detection uses method registry for accuracy.
 
+use crate::adapters::api_keys::ApiKeyStore;
 use crate::is_write_method;
+use crate::middleware::auth::presented_api_key;
+use crate::middleware::metrics::GatewayMetrics;
 use crate::ApiError;
 use crate::RateLimitConfig;
 use axum::{
@@ -18,6 +21,7 @@ use governor::{
     state::{InMemoryState, NotKeyed},
     Quota, RateLimiter,
 };
+use parking_lot::RwLock;
 use std::net::{IpAddr, SocketAddr};
 use std::num::NonZeroU32;
 use std::sync::Arc;
@@ -87,34 +91,48 @@ impl TokenBucket {
 pub struct RateLimitState {
     /// Per-IP token buckets
     buckets: DashMap<IpAddr, TokenBucket>,
-    /// Configuration
-    config: RateLimitConfig,
+    /// Configuration (replaceable at runtime)
+    config: RwLock<RateLimitConfig>,
 }
 
 impl RateLimitState {
     pub fn new(config: RateLimitConfig) -> Self {
         Self {
             buckets: DashMap::new(),
-            config,
+            config: RwLock::new(config),
         }
     }
 
+    /// Current limits
+    pub fn config(&self) -> RateLimitConfig {
+        self.config.read().clone()
+    }
+
+    /// Replace the limits; every client starts over with a full bucket
+    pub fn update(&self, config: RateLimitConfig) {
+        let mut current = self.config.write();
+        self.buckets.clear();
+        *current = config;
+    }
+
     /// Check if request should be allowed
     pub fn check(&self, ip: IpAddr, is_write: bool) -> Result<(), Duration> {
+        let config = self.config.read();
+
         // Check whitelist
-        if self.config.whitelist.contains(&ip) {
+        if config.whitelist.contains(&ip) {
             return Ok(());
         }
 
         // Check if rate limiting is enabled
-        if !self.config.enabled {
+        if !config.enabled {
             return Ok(());
         }
 
         // Get or create bucket for this IP
         let mut bucket = self.buckets.entry(ip).or_insert_with(|| {
             debug!(ip = %ip, "Creating new rate limit bucket");
-            TokenBucket::new(&self.config)
+            TokenBucket::new(&config)
         });
 
         if is_write {
@@ -144,19 +162,56 @@ impl RateLimitState {
     }
 }
 
+/// Check if the request carries a key whose own limit replaces IP limits
+fn has_key_override<B>(keys: Option<&ApiKeyStore>, req: &Request<B>) -> bool {
+    let (Some(store), Some(key)) = (keys, presented_api_key(req)) else {
+        return false;
+    };
+    store
+        .authenticate(key)
+        .is_some_and(|record| record.rate_limit.is_some())
+}
+
 /// Rate limit layer
 #[derive(Clone)]
 pub struct RateLimitLayer {
     state: Arc<RateLimitState>,
+    /// Keys with their own rate limit bypass the per-IP buckets
+    keys: Option<Arc<ApiKeyStore>>,
+    metrics: Option<Arc<GatewayMetrics>>,
 }
 
 impl RateLimitLayer {
     pub fn new(config: RateLimitConfig) -> Self {
+        Self::from_state(Arc::new(RateLimitState::new(config)))
+    }
+
+    /// Rate limit layer over existing state, e.g. shared by several ports
+    pub fn from_state(state: Arc<RateLimitState>) -> Self {
         Self {
-            state: Arc::new(RateLimitState::new(config)),
+            state,
+            keys: None,
+            metrics: None,
         }
     }
 
+    /// Rate limit layer where scoped keys with a `rate_limit` use that instead
+    pub fn with_key_store(config: RateLimitConfig, keys: Arc<ApiKeyStore>) -> Self {
+        Self::new(config).with_keys(keys)
+    }
+
+    /// Let scoped keys with a `rate_limit` use that instead of IP limits
+    pub fn with_keys(mut self, keys: Arc<ApiKeyStore>) -> Self {
+        self.keys = Some(keys);
+        self
+    }
+
+    /// Count drops per client IP bucket in `metrics`
+    pub fn with_metrics(mut self, metrics: Arc<GatewayMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     pub fn state(&self) -> Arc<RateLimitState> {
         Arc::clone(&self.state)
     }
@@ -169,6 +224,8 @@ impl<S> Layer<S> for RateLimitLayer {
         RateLimitService {
             inner,
             state: Arc::clone(&self.state),
+            keys: self.keys.clone(),
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -178,6 +235,8 @@ impl<S> Layer<S> for RateLimitLayer {
 pub struct RateLimitService<S> {
     inner: S,
     state: Arc<RateLimitState>,
+    keys: Option<Arc<ApiKeyStore>>,
+    metrics: Option<Arc<GatewayMetrics>>,
 }
 
 impl<S> Service<Request<Body>> for RateLimitService<S>
@@ -200,9 +259,16 @@ where
 
     fn call(&mut self, req: Request<Body>) -> Self::Future {
         let state = Arc::clone(&self.state);
+        let keys = self.keys.clone();
+        let metrics = self.metrics.clone();
         let mut inner = self.inner.clone();
 
         Box::pin(async move {
+            // Per-key limits are enforced at authorization time
+            if has_key_override(keys.as_deref(), &req) {
+                return inner.call(req).await;
+            }
+
             // Extract client IP
             let ip = extract_client_ip(&req);
 
@@ -229,6 +295,9 @@ where
                         is_write = is_write,
                         "Rate limit exceeded"
                     );
+                    if let Some(metrics) = &metrics {
+                        metrics.record_rate_limit_drop(ip);
+                    }
 
                     Ok(rate_limit_response(retry_ms))
                 }
@@ -386,6 +455,25 @@ mod tests {
         // Note: With governor, the buckets are separate so this might still work
     }
 
+    #[test]
+    fn test_update_replaces_limits_and_buckets() {
+        let state = RateLimitState::new(test_config());
+        let ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 6));
+        while state.check(ip, false).is_ok() {}
+
+        state.update(RateLimitConfig {
+            requests_per_second: 1,
+            burst_size: 3,
+            ..test_config()
+        });
+        assert_eq!(state.config().requests_per_second, 1);
+        assert_eq!(state.bucket_count(), 0);
+        for _ in 0..3 {
+            assert!(state.check(ip, false).is_ok());
+        }
+        assert!(state.check(ip, false).is_err());
+    }
+
     #[test]
     fn test_cleanup_removes_stale_buckets() {
         let state = RateLimitState::new(test_config());
@@ -1,8 +1,15 @@
 //! Tracing middleware for OpenTelemetry integration per SPEC-16 Section 8.
 //!
 //! Adds distributed tracing context to requests for the LGTM stack.
+//!
+//! An inbound W3C `traceparent` becomes the parent of the gateway's request
+//! span; requests without one start a new trace. The gateway span's context
+//! is scoped over the request so every `IpcRequest` it issues carries it to
+//! the subsystems, and is echoed back in the `traceparent` response header.
 
+use crate::domain::correlation::{current_trace_context, with_trace_context};
 use axum::{body::Body, http::Request, response::Response};
+use quantum_telemetry::PropagatedContext;
 use std::task::{Context, Poll};
 use tower::{Layer, Service};
 use tracing::{info_span, Instrument, Span};
@@ -49,8 +56,13 @@ where
     fn call(&mut self, req: Request<Body>) -> Self::Future {
         let mut inner = self.inner.clone();
 
-        // Extract tracing context from headers
+        // Extract tracing context from headers and open the gateway span under it
         let parent_context = extract_trace_context(&req);
+        let context = parent_context
+            .as_ref()
+            .map_or_else(PropagatedContext::empty, PropagatedContext::clone)
+            .new_child();
+        let parent_span_id = parent_context.map(|p| p.span_id);
 
         // Extract request info for span
         let method = req.method().clone();
@@ -70,6 +82,9 @@ where
                 rpc.method = %rpc,
                 otel.kind = "server",
                 otel.status_code = tracing::field::Empty,
+                trace_id = %context.trace_id,
+                span_id = %context.span_id,
+                parent_span_id = tracing::field::Empty,
             )
         } else {
             info_span!(
@@ -78,75 +93,56 @@ where
                 http.target = %uri.path(),
                 otel.kind = "server",
                 otel.status_code = tracing::field::Empty,
+                trace_id = %context.trace_id,
+                span_id = %context.span_id,
+                parent_span_id = tracing::field::Empty,
             )
         };
 
-        // Set parent context if available
-        if let Some(parent) = parent_context {
-            span.follows_from(parent);
+        if let Some(parent_span_id) = &parent_span_id {
+            span.record("parent_span_id", parent_span_id.as_str());
         }
 
-        Box::pin(
-            async move {
-                let result = inner.call(req).await;
-
-                // Record status in span
-                match &result {
-                    Ok(response) => {
-                        let status = response.status();
-                        Span::current().record(
-                            "otel.status_code",
-                            if status.is_success() { "OK" } else { "ERROR" },
-                        );
-                    }
-                    Err(_) => {
-                        Span::current().record("otel.status_code", "ERROR");
-                    }
-                }
+        let traceparent = context.to_traceparent();
+        let fut = async move {
+            let mut result = inner.call(req).await;
 
-                result
+            // Let the client look up the trace
+            if let (Ok(response), Ok(value)) = (&mut result, traceparent.parse()) {
+                response.headers_mut().insert("traceparent", value);
             }
-            .instrument(span),
-        )
+
+            // Record status in span
+            match &result {
+                Ok(response) => {
+                    let status = response.status();
+                    Span::current().record(
+                        "otel.status_code",
+                        if status.is_success() { "OK" } else { "ERROR" },
+                    );
+                }
+                Err(_) => {
+                    Span::current().record("otel.status_code", "ERROR");
+                }
+            }
+
+            result
+        };
+
+        Box::pin(with_trace_context(context, fut.instrument(span)))
     }
 }
 
 /// Extract trace context from request headers (W3C Trace Context)
-fn extract_trace_context<B>(req: &Request<B>) -> Option<Span> {
-    // Look for traceparent header (W3C Trace Context)
+fn extract_trace_context<B>(req: &Request<B>) -> Option<PropagatedContext> {
     let traceparent = req.headers().get("traceparent")?.to_str().ok()?;
-
-    // Parse traceparent: version-trace_id-parent_id-trace_flags
-    let parts: Vec<&str> = traceparent.split('-').collect();
-    if parts.len() != 4 {
-        return None;
-    }
-
-    let trace_id = parts[1];
-    let parent_id = parts[2];
-
-    // Create a span that references the parent
-    Some(info_span!(
-        "parent_trace",
-        trace_id = trace_id,
-        parent_span_id = parent_id
-    ))
+    PropagatedContext::from_traceparent(traceparent)
 }
 
 /// Add trace context headers to outgoing requests
 pub fn inject_trace_context(headers: &mut axum::http::HeaderMap) {
-    // Get current trace context
-    let span = Span::current();
-
-    // In production, we'd use opentelemetry's propagator
-    // For now, we'll add basic tracing headers
-    if let Some(trace_id) = span.id() {
-        let traceparent = format!(
-            "00-{:032x}-{:016x}-01",
-            trace_id.into_u64(),
-            trace_id.into_u64()
-        );
-        if let Ok(value) = traceparent.parse() {
+    if let Some(context) = current_trace_context() {
+        if let Ok(value) = context.to_traceparent().parse() {
             headers.insert("traceparent", value);
         }
     }
@@ -188,4 +184,33 @@ mod tests {
         let parent = extract_trace_context(&req);
         assert!(parent.is_none());
     }
+
+    #[tokio::test]
+    async fn test_trace_context_scoped_over_request() {
+        let inner = tower::service_fn(|_req: Request<Body>| async {
+            // What an IpcRequest built inside the handler would carry
+            let context = current_trace_context().unwrap();
+            Ok::<_, std::convert::Infallible>(Response::new(Body::from(context.trace_id)))
+        });
+        let mut service = TracingLayer::new().layer(inner);
+
+        let req = Request::builder()
+            .header(
+                "traceparent",
+                "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+            )
+            .body(Body::empty())
+            .unwrap();
+        let response = service.call(req).await.unwrap();
+
+        let echoed = response.headers()["traceparent"].to_str().unwrap();
+        let echoed = PropagatedContext::from_traceparent(echoed).unwrap();
+        assert_eq!(echoed.trace_id, "0af7651916cd43dd8448eb211c80319c");
+        assert_ne!(echoed.span_id, "b7ad6b7169203331");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"0af7651916cd43dd8448eb211c80319c");
+    }
 }
@@ -276,6 +276,7 @@ mod tests {
             max_response_size: 1024,
             max_log_block_range: 1000,
             max_log_results: 1000,
+            log_chunk_blocks: 100,
         }
     }
 
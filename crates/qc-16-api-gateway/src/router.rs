@@ -1,5 +1,5 @@
 use crate::domain::error::ApiError;
-use crate::middleware::GatewayMetrics;
+use crate::middleware::{AuthConfig, GatewayMetrics};
 use crate::rpc::RpcHandlers;
 use std::sync::Arc;
 
@@ -8,6 +8,7 @@ use std::sync::Arc;
 pub struct AppState {
     pub rpc_handlers: Arc<RpcHandlers>,
     pub metrics: Arc<GatewayMetrics>,
+    pub auth: Arc<AuthConfig>,
 }
 
 /// Route JSON-RPC method to appropriate handler.
@@ -49,6 +50,12 @@ pub async fn route_method(
             route_eth_execution(state, method, params).await
         }
 
+        // Polling Filters
+        "eth_newFilter" | "eth_newBlockFilter" | "eth_newPendingTransactionFilter" |
+        "eth_uninstallFilter" | "eth_getFilterChanges" | "eth_getFilterLogs" => {
+            route_eth_filter(state, method, params).await
+        }
+
         // Fee Market
         "eth_maxPriorityFeePerGas" | "eth_feeHistory" => {
             route_eth_fee_market(state, method, params).await
@@ -62,18 +69,41 @@ pub async fn route_method(
             route_net_namespace(state, method, params).await
         }
 
-        "txpool_status" | "txpool_content" | "txpool_inspect" | "txpool_contentFrom" => {
+        "txpool_status" | "txpool_content" | "txpool_inspect" | "txpool_contentFrom"
+        | "txpool_transaction" => {
             route_txpool_namespace(state, method, params).await
         }
 
-        "admin_peers" | "admin_nodeInfo" | "admin_addPeer" | "admin_removePeer" | "admin_datadir" => {
+        "admin_peers" | "admin_nodeInfo" | "admin_addPeer" | "admin_removePeer"
+        | "admin_datadir" | "admin_mevReports" | "admin_logControl"
+        | "admin_setLogControl" | "admin_subsystems" | "admin_stopSubsystem"
+        | "admin_startSubsystem" | "admin_restartSubsystem" | "admin_evictTransaction"
+        | "admin_getConfig" | "admin_setConfig" => {
             route_admin_namespace(state, method, params).await
         }
         
-        "debug_traceBlockByNumber" | "debug_subsystemStatus" => {
+        "debug_traceTransaction"
+        | "debug_getRawBlock"
+        | "debug_traceBlockByNumber"
+        | "debug_subsystemStatus"
+        | "debug_eventBus"
+        | "debug_recentBlocks"
+        | "debug_metricHistory" => {
             route_debug_namespace(state, method, params).await
         }
 
+        "swap_getQuote" | "swap_getMakers" | "swap_advertiseLiquidity" | "swap_withdrawLiquidity" => {
+            route_swap_namespace(state, method, params).await
+        }
+
+        "qc_getBlockTemplate" | "qc_submitBlock" => {
+            route_mining_namespace(state, method, params).await
+        }
+
+        "qc_contractFunctions" | "qc_encodeCall" | "qc_callContract" => {
+            route_contract_namespace(state, method, params).await
+        }
+
         _ => Err(ApiError {
             code: -32601,
             message: format!("Method not found: {}", method),
@@ -189,7 +219,8 @@ async fn route_eth_transaction(
         }
         "eth_sendRawTransaction" => {
             let raw_tx: crate::domain::types::Bytes = parse_param(params, 0)?;
-            state.rpc_handlers.eth.send_raw_transaction(raw_tx).await.map(|v| serde_json::to_value(v).unwrap_or_default())
+            let options = parse_param_optional(params, 1).unwrap_or_default();
+            state.rpc_handlers.eth.send_raw_transaction(raw_tx, options).await.map(|v| serde_json::to_value(v).unwrap_or_default())
         }
         _ => unreachable!("Filtered by caller"),
     }
@@ -221,6 +252,40 @@ async fn route_eth_execution(
     }
 }
 
+async fn route_eth_filter(
+    state: &AppState,
+    method: &str,
+    params: Option<&serde_json::Value>,
+) -> Result<serde_json::Value, ApiError> {
+    use crate::domain::types::Filter;
+
+    let filters = &state.rpc_handlers.filter;
+    match method {
+        "eth_newFilter" => {
+            let filter: Filter = parse_param(params, 0)?;
+            filters.new_filter(filter).await.map(|v| serde_json::json!(v))
+        }
+        "eth_newBlockFilter" => filters.new_block_filter().await.map(|v| serde_json::json!(v)),
+        "eth_newPendingTransactionFilter" => filters
+            .new_pending_transaction_filter()
+            .await
+            .map(|v| serde_json::json!(v)),
+        "eth_uninstallFilter" => {
+            let id: String = parse_param(params, 0)?;
+            filters.uninstall_filter(id).await.map(|v| serde_json::json!(v))
+        }
+        "eth_getFilterChanges" => {
+            let id: String = parse_param(params, 0)?;
+            filters.get_filter_changes(id).await.map(|v| serde_json::json!(v))
+        }
+        "eth_getFilterLogs" => {
+            let id: String = parse_param(params, 0)?;
+            filters.get_filter_logs(id).await.map(|v| serde_json::json!(v))
+        }
+        _ => unreachable!("Filtered by caller"),
+    }
+}
+
 async fn route_eth_fee_market(
     state: &AppState,
     method: &str,
@@ -304,7 +369,7 @@ async fn route_txpool_namespace(
     method: &str,
     params: Option<&serde_json::Value>,
 ) -> Result<serde_json::Value, ApiError> {
-    use crate::domain::types::Address;
+    use crate::domain::types::{Address, Hash};
 
     match method {
         "txpool_status" => state.rpc_handlers.txpool.status().await,
@@ -314,6 +379,10 @@ async fn route_txpool_namespace(
             let address: Address = parse_param(params, 0)?;
             state.rpc_handlers.txpool.content_from(address).await
         }
+        "txpool_transaction" => {
+            let hash: Hash = parse_param(params, 0)?;
+            state.rpc_handlers.txpool.transaction(hash).await
+        }
         _ => unreachable!("Filtered by caller"),
     }
 }
@@ -323,6 +392,8 @@ async fn route_admin_namespace(
     method: &str,
     params: Option<&serde_json::Value>,
 ) -> Result<serde_json::Value, ApiError> {
+    use crate::domain::types::{Hash, U256};
+
     match method {
         "admin_peers" => state.rpc_handlers.admin.peers().await,
         "admin_nodeInfo" => state.rpc_handlers.admin.node_info().await,
@@ -350,6 +421,61 @@ async fn route_admin_namespace(
             .datadir()
             .await
             .map(|v| serde_json::json!(v)),
+        "admin_mevReports" => {
+            let block_number: Option<U256> = parse_param_optional(params, 0);
+            let limit: Option<u32> = parse_param_optional(params, 1);
+            state
+                .rpc_handlers
+                .admin
+                .mev_reports(block_number.map(|n| n.as_u64()), limit)
+                .await
+        }
+        "admin_logControl" => state
+            .rpc_handlers
+            .admin
+            .log_control()
+            .await
+            .map(|v| serde_json::json!(v)),
+        "admin_setLogControl" => {
+            let settings: quantum_telemetry::LogControlSettings = parse_param(params, 0)?;
+            state
+                .rpc_handlers
+                .admin
+                .set_log_control(settings)
+                .await
+                .map(|v| serde_json::json!(v))
+        }
+        "admin_subsystems" => state.rpc_handlers.admin.subsystems().await,
+        "admin_stopSubsystem" | "admin_startSubsystem" | "admin_restartSubsystem" => {
+            use crate::ipc::requests::SubsystemAction;
+
+            let subsystem: serde_json::Value = parse_param(params, 0)?;
+            let dry_run: Option<bool> = parse_param_optional(params, 1);
+            let action = match method {
+                "admin_stopSubsystem" => SubsystemAction::Stop,
+                "admin_startSubsystem" => SubsystemAction::Start,
+                _ => SubsystemAction::Restart,
+            };
+            state
+                .rpc_handlers
+                .admin
+                .control_subsystem(&subsystem, action, dry_run.unwrap_or(false))
+                .await
+        }
+        "admin_evictTransaction" => {
+            let hash: Hash = parse_param(params, 0)?;
+            state
+                .rpc_handlers
+                .admin
+                .evict_transaction(hash)
+                .await
+                .map(|v| serde_json::json!(v))
+        }
+        "admin_getConfig" => state.rpc_handlers.admin.get_config().await,
+        "admin_setConfig" => {
+            let changes: serde_json::Value = parse_param(params, 0)?;
+            state.rpc_handlers.admin.set_config(changes).await
+        }
         _ => unreachable!("Filtered by caller"),
     }
 }
@@ -359,10 +485,28 @@ async fn route_debug_namespace(
     method: &str,
     params: Option<&serde_json::Value>,
 ) -> Result<serde_json::Value, ApiError> {
-    use crate::domain::types::{BlockId};
+    use crate::domain::types::{BlockId, Hash};
     use crate::rpc::debug::TraceOptions;
 
     match method {
+        "debug_traceTransaction" => {
+            let hash: Hash = parse_param(params, 0)?;
+            let options: Option<TraceOptions> = parse_param_optional(params, 1);
+            state
+                .rpc_handlers
+                .debug
+                .trace_transaction(hash, options)
+                .await
+        }
+        "debug_getRawBlock" => {
+            let block_id: BlockId = parse_param(params, 0)?;
+            state
+                .rpc_handlers
+                .debug
+                .get_raw_block(block_id)
+                .await
+                .map(|v| serde_json::json!(v))
+        }
         "debug_traceBlockByNumber" => {
             let block_id: BlockId = parse_param(params, 0)?;
             let options: Option<TraceOptions> = parse_param_optional(params, 1);
@@ -382,6 +526,140 @@ async fn route_debug_namespace(
                 .await
                 .map(|v| serde_json::to_value(v).unwrap_or_default())
         }
+        "debug_eventBus" => {
+            let request = parse_param_optional(params, 0).unwrap_or_default();
+            state.rpc_handlers.debug.event_bus(request).await
+        }
+        "debug_recentBlocks" => {
+            use crate::ipc::requests::GetRecentBlocksRequest;
+
+            let limit: Option<u32> = parse_param_optional(params, 0);
+            state
+                .rpc_handlers
+                .debug
+                .recent_blocks(GetRecentBlocksRequest { limit })
+                .await
+        }
+        "debug_metricHistory" => {
+            let request = parse_param_optional(params, 0).unwrap_or_default();
+            state.rpc_handlers.debug.metric_history(request).await
+        }
+        _ => unreachable!("Filtered by caller"),
+    }
+}
+
+async fn route_swap_namespace(
+    state: &AppState,
+    method: &str,
+    params: Option<&serde_json::Value>,
+) -> Result<serde_json::Value, ApiError> {
+    use crate::domain::types::{Address, SwapOffer, U256};
+
+    match method {
+        "swap_getQuote" => {
+            let source_chain: String = parse_param(params, 0)?;
+            let target_chain: String = parse_param(params, 1)?;
+            let amount: U256 = parse_param(params, 2)?;
+            state
+                .rpc_handlers
+                .swap
+                .get_quote(source_chain, target_chain, amount)
+                .await
+        }
+        "swap_getMakers" => {
+            let source_chain: String = parse_param(params, 0)?;
+            let target_chain: String = parse_param(params, 1)?;
+            state
+                .rpc_handlers
+                .swap
+                .get_makers(source_chain, target_chain)
+                .await
+        }
+        "swap_advertiseLiquidity" => {
+            let offer: SwapOffer = parse_param(params, 0)?;
+            state
+                .rpc_handlers
+                .swap
+                .advertise_liquidity(offer)
+                .await
+                .map(|v| serde_json::json!(v))
+        }
+        "swap_withdrawLiquidity" => {
+            let maker: Address = parse_param(params, 0)?;
+            let source_chain: String = parse_param(params, 1)?;
+            let target_chain: String = parse_param(params, 2)?;
+            state
+                .rpc_handlers
+                .swap
+                .withdraw_liquidity(maker, source_chain, target_chain)
+                .await
+                .map(|v| serde_json::json!(v))
+        }
+        _ => unreachable!("Filtered by caller"),
+    }
+}
+
+async fn route_mining_namespace(
+    state: &AppState,
+    method: &str,
+    params: Option<&serde_json::Value>,
+) -> Result<serde_json::Value, ApiError> {
+    use crate::domain::types::U256;
+
+    match method {
+        "qc_getBlockTemplate" => {
+            let options = parse_param_optional(params, 0).unwrap_or_default();
+            state.rpc_handlers.mining.get_block_template(options).await
+        }
+        "qc_submitBlock" => {
+            let template_id: String = parse_param(params, 0)?;
+            let nonce: U256 = parse_param(params, 1)?;
+            let nonce = u64::try_from(nonce.0)
+                .map_err(|_| ApiError::invalid_params("nonce must fit in 64 bits"))?;
+            state
+                .rpc_handlers
+                .mining
+                .submit_block(template_id, nonce)
+                .await
+        }
+        _ => unreachable!("Filtered by caller"),
+    }
+}
+
+async fn route_contract_namespace(
+    state: &AppState,
+    method: &str,
+    params: Option<&serde_json::Value>,
+) -> Result<serde_json::Value, ApiError> {
+    use crate::domain::types::BlockId;
+    use crate::rpc::contract::ContractCallRequest;
+
+    let contract = &state.rpc_handlers.contract;
+    match method {
+        "qc_contractFunctions" => {
+            let abi: serde_json::Value = parse_param(params, 0)?;
+            contract
+                .functions(abi)
+                .await
+                .map(|v| serde_json::to_value(v).unwrap_or_default())
+        }
+        "qc_encodeCall" => {
+            let abi: serde_json::Value = parse_param(params, 0)?;
+            let function: String = parse_param(params, 1)?;
+            let args: Vec<serde_json::Value> = parse_param_optional(params, 2).unwrap_or_default();
+            contract
+                .encode_call(abi, function, args)
+                .await
+                .map(|v| serde_json::to_value(v).unwrap_or_default())
+        }
+        "qc_callContract" => {
+            let request: ContractCallRequest = parse_param(params, 0)?;
+            let block_id: Option<BlockId> = parse_param_optional(params, 1);
+            contract
+                .call(request, block_id)
+                .await
+                .map(|v| serde_json::to_value(v).unwrap_or_default())
+        }
         _ => unreachable!("Filtered by caller"),
     }
 }
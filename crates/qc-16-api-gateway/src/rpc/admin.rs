@@ -1,12 +1,19 @@
 //! Admin JSON-RPC methods per SPEC-16 Section 3.2 and 3.3.
 
+use crate::domain::types::Hash;
 use crate::ipc::handler::IpcHandler;
 use crate::ipc::requests::*;
 use crate::{ApiError, ApiResult};
+use quantum_telemetry::{log_control, LogControlSettings};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::instrument;
 
+/// Reports returned by `admin_mevReports` when no limit is given
+const DEFAULT_MEV_REPORTS: u32 = 16;
+/// Largest `admin_mevReports` limit (qc-17 keeps 256 reports)
+const MAX_MEV_REPORTS: u32 = 256;
+
 /// Admin RPC methods handler
 pub struct AdminRpc {
     ipc: Arc<IpcHandler>,
@@ -34,7 +41,7 @@ impl AdminRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         Ok(result)
     }
@@ -51,7 +58,7 @@ impl AdminRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         Ok(result)
     }
@@ -62,6 +69,61 @@ impl AdminRpc {
         Ok(self.data_dir.to_string_lossy().to_string())
     }
 
+    /// admin_mevReports - Returns MEV reports for produced blocks, newest first
+    /// Routes to qc-17 Block Production
+    #[instrument(skip(self))]
+    pub async fn mev_reports(
+        &self,
+        block_number: Option<u64>,
+        limit: Option<u32>,
+    ) -> ApiResult<serde_json::Value> {
+        let limit = limit.unwrap_or(DEFAULT_MEV_REPORTS);
+        if limit == 0 || limit > MAX_MEV_REPORTS {
+            return Err(ApiError::invalid_params(format!(
+                "limit must be between 1 and {}",
+                MAX_MEV_REPORTS
+            )));
+        }
+
+        let result = self
+            .ipc
+            .request(
+                "qc-17-block-production",
+                RequestPayload::GetMevReports(GetMevReportsRequest {
+                    block_number,
+                    limit,
+                }),
+                None,
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(result)
+    }
+
+    /// admin_logControl - Returns the current log sampling and rate limits
+    #[instrument(skip(self))]
+    pub async fn log_control(&self) -> ApiResult<LogControlSettings> {
+        Ok(log_control().settings())
+    }
+
+    /// admin_subsystems - Returns lifecycle status of registered subsystems
+    /// Routes to the node runtime's subsystem registry
+    #[instrument(skip(self))]
+    pub async fn subsystems(&self) -> ApiResult<serde_json::Value> {
+        let result = self
+            .ipc
+            .request(
+                "admin",
+                RequestPayload::GetSubsystemStatus(GetSubsystemStatusRequest),
+                None,
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(result)
+    }
+
     // ═══════════════════════════════════════════════════════════════════════
     // TIER 3: ADMIN (Node control)
     // ═══════════════════════════════════════════════════════════════════════
@@ -84,7 +146,7 @@ impl AdminRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         Ok(result.as_bool().unwrap_or(false))
     }
@@ -101,7 +163,7 @@ impl AdminRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         Ok(result.as_bool().unwrap_or(false))
     }
@@ -126,6 +188,113 @@ impl AdminRpc {
         self.remove_peer(enode).await
     }
 
+    /// admin_setLogControl - Replaces the log sampling and rate limits
+    ///
+    /// Takes effect immediately for the whole node; returns the new settings.
+    #[instrument(skip(self))]
+    pub async fn set_log_control(
+        &self,
+        settings: LogControlSettings,
+    ) -> ApiResult<LogControlSettings> {
+        log_control()
+            .apply(settings)
+            .map_err(|e| ApiError::invalid_params(e.to_string()))?;
+        Ok(log_control().settings())
+    }
+
+    /// admin_stopSubsystem / admin_startSubsystem / admin_restartSubsystem
+    ///
+    /// `subsystem` is a numeric ID (7) or a name ("qc-07-bloom-filters").
+    /// Core subsystems are refused, as are stops while a running subsystem
+    /// depends on the target and starts while a dependency is down.
+    /// With `dry_run` the node only reports whether the action would be
+    /// allowed, so a caller can confirm before acting.
+    #[instrument(skip(self))]
+    pub async fn control_subsystem(
+        &self,
+        subsystem: &serde_json::Value,
+        action: SubsystemAction,
+        dry_run: bool,
+    ) -> ApiResult<serde_json::Value> {
+        let subsystem_id = parse_subsystem_id(subsystem)
+            .ok_or_else(|| ApiError::invalid_params(format!("Unknown subsystem: {}", subsystem)))?;
+
+        let result = self
+            .ipc
+            .request(
+                "admin",
+                RequestPayload::ControlSubsystem(ControlSubsystemRequest {
+                    subsystem_id,
+                    action,
+                    dry_run,
+                }),
+                None,
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(result)
+    }
+
+    /// admin_evictTransaction - Drop a transaction from the mempool
+    ///
+    /// Transactions already proposed for a block cannot be evicted.
+    #[instrument(skip(self))]
+    pub async fn evict_transaction(&self, hash: Hash) -> ApiResult<bool> {
+        let result = self
+            .ipc
+            .request(
+                "admin",
+                RequestPayload::EvictTransaction(EvictTransactionRequest { hash }),
+                None,
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(result.as_bool().unwrap_or(false))
+    }
+
+    /// admin_getConfig - The node's effective configuration
+    ///
+    /// Secrets are redacted. `reloadable` lists the keys `admin_setConfig`
+    /// accepts; `log_level` is the telemetry level filter.
+    #[instrument(skip(self))]
+    pub async fn get_config(&self) -> ApiResult<serde_json::Value> {
+        let result = self
+            .ipc
+            .request("admin", RequestPayload::GetConfig(GetConfigRequest), None)
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(result)
+    }
+
+    /// admin_setConfig - Change settings that apply without a restart
+    ///
+    /// `changes` maps dotted keys to new values. The node checks the change
+    /// as a whole and applies none of it if any value is refused; the error
+    /// names the offending key. Returns the new effective configuration.
+    #[instrument(skip(self))]
+    pub async fn set_config(&self, changes: serde_json::Value) -> ApiResult<serde_json::Value> {
+        if !changes.is_object() {
+            return Err(ApiError::invalid_params(
+                "Expected an object of config keys, e.g. {\"log_level\": \"debug\"}",
+            ));
+        }
+
+        let result = self
+            .ipc
+            .request(
+                "admin",
+                RequestPayload::SetConfig(SetConfigRequest { changes }),
+                None,
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        Ok(result)
+    }
+
     /// admin_startHTTP - Start HTTP server (no-op if already running)
     #[instrument(skip(self))]
     pub async fn start_http(&self) -> ApiResult<bool> {
@@ -153,8 +322,36 @@ impl AdminRpc {
     }
 }
 
+/// Parse a subsystem given as an ID (`7`, `"7"`) or name (`"qc-07-bloom-filters"`).
+fn parse_subsystem_id(subsystem: &serde_json::Value) -> Option<u8> {
+    let id = match subsystem {
+        serde_json::Value::Number(n) => n.as_u64()?,
+        serde_json::Value::String(s) => {
+            let digits = s.strip_prefix("qc-").unwrap_or(s);
+            let digits = digits.split('-').next()?;
+            digits.parse().ok()?
+        }
+        _ => return None,
+    };
+    u8::try_from(id).ok().filter(|id| (1..=17).contains(id))
+}
+
 #[cfg(test)]
 mod tests {
+    use super::parse_subsystem_id;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_subsystem_id() {
+        assert_eq!(parse_subsystem_id(&json!(7)), Some(7));
+        assert_eq!(parse_subsystem_id(&json!("7")), Some(7));
+        assert_eq!(parse_subsystem_id(&json!("qc-07-bloom-filters")), Some(7));
+        assert_eq!(parse_subsystem_id(&json!("qc-16")), Some(16));
+        assert_eq!(parse_subsystem_id(&json!(0)), None);
+        assert_eq!(parse_subsystem_id(&json!("qc-99-nope")), None);
+        assert_eq!(parse_subsystem_id(&json!("bloom")), None);
+        assert_eq!(parse_subsystem_id(&json!(true)), None);
+    }
 
     #[test]
     fn test_enode_validation() {
@@ -0,0 +1,276 @@
+//! Contract JSON-RPC methods: call qc-11 contracts from their ABI.
+//!
+//! Clients pass a contract address and its ABI JSON. The gateway lists the
+//! functions, encodes arguments into calldata and, for `qc_callContract`,
+//! runs the call against qc-11 and decodes the return values or the revert
+//! reason. State-changing calls are sent by signing a transaction over
+//! `qc_encodeCall`'s calldata and submitting it with `eth_sendRawTransaction`.
+
+use crate::domain::abi::{AbiFunction, AbiParam, ContractAbi, DecodedValue};
+use crate::domain::error::codes;
+use crate::domain::types::{Address, BlockId, Bytes, CallRequest, U256};
+use crate::ipc::handler::IpcHandler;
+use crate::ipc::requests::*;
+use crate::{ApiError, ApiResult};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::instrument;
+
+/// A function as listed by `qc_contractFunctions`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionSummary {
+    pub name: String,
+    /// Canonical signature, used to pick between overloads
+    pub signature: String,
+    /// 4-byte selector, hex
+    pub selector: Bytes,
+    pub state_mutability: String,
+    /// `view` or `pure`: callable with `qc_callContract` alone
+    pub read_only: bool,
+    pub inputs: Vec<AbiParam>,
+    pub outputs: Vec<AbiParam>,
+}
+
+impl From<&AbiFunction> for FunctionSummary {
+    fn from(function: &AbiFunction) -> Self {
+        Self {
+            name: function.name.clone(),
+            signature: function.signature(),
+            selector: Bytes::from_slice(&function.selector()),
+            state_mutability: function.state_mutability.clone(),
+            read_only: function.is_read_only(),
+            inputs: function.inputs.clone(),
+            outputs: function.outputs.clone(),
+        }
+    }
+}
+
+/// Calldata returned by `qc_encodeCall`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncodedCall {
+    pub signature: String,
+    pub selector: Bytes,
+    /// Selector followed by the encoded arguments
+    pub data: Bytes,
+}
+
+/// Parameters of `qc_callContract`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCallRequest {
+    /// Contract address
+    pub to: Address,
+    /// ABI JSON (array, compiler artifact, or either as a string)
+    pub abi: serde_json::Value,
+    /// Function name, or full signature for overloaded functions
+    pub function: String,
+    /// One value per input
+    #[serde(default)]
+    pub args: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub from: Option<Address>,
+    #[serde(default)]
+    pub value: Option<U256>,
+    #[serde(default)]
+    pub gas: Option<U256>,
+}
+
+/// Result of `qc_callContract`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCallResult {
+    pub signature: String,
+    /// Raw return data
+    pub data: Bytes,
+    /// Return values decoded with the function's outputs
+    pub outputs: Vec<DecodedValue>,
+}
+
+/// Contract RPC methods handler
+pub struct ContractRpc {
+    ipc: Arc<IpcHandler>,
+}
+
+impl ContractRpc {
+    pub fn new(ipc: Arc<IpcHandler>) -> Self {
+        Self { ipc }
+    }
+
+    /// qc_contractFunctions - Lists the callable functions of an ABI
+    #[instrument(skip_all)]
+    pub async fn functions(&self, abi: serde_json::Value) -> ApiResult<Vec<FunctionSummary>> {
+        let abi = ContractAbi::from_json(&abi)?;
+        Ok(abi.functions().iter().map(FunctionSummary::from).collect())
+    }
+
+    /// qc_encodeCall - Encodes calldata for a function call
+    #[instrument(skip(self, abi, args))]
+    pub async fn encode_call(
+        &self,
+        abi: serde_json::Value,
+        function: String,
+        args: Vec<serde_json::Value>,
+    ) -> ApiResult<EncodedCall> {
+        let abi = ContractAbi::from_json(&abi)?;
+        let function = abi.function(&function)?;
+
+        Ok(EncodedCall {
+            signature: function.signature(),
+            selector: Bytes::from_slice(&function.selector()),
+            data: Bytes(function.encode_call(&args)?),
+        })
+    }
+
+    /// qc_callContract - Calls a function and decodes its return values
+    ///
+    /// A revert is returned as an execution error whose message carries the
+    /// decoded reason (`Error(string)`, `Panic(uint256)` or a custom error
+    /// from the ABI) and whose `data` keeps the raw revert data.
+    #[instrument(skip(self, request), fields(to = %request.to, function = %request.function))]
+    pub async fn call(
+        &self,
+        request: ContractCallRequest,
+        block_id: Option<BlockId>,
+    ) -> ApiResult<ContractCallResult> {
+        let abi = ContractAbi::from_json(&request.abi)?;
+        let function = abi.function(&request.function)?;
+        let call = CallRequest {
+            from: request.from,
+            to: Some(request.to),
+            gas: request.gas,
+            value: request.value,
+            data: Some(Bytes(function.encode_call(&request.args)?)),
+            ..Default::default()
+        };
+
+        let result = self
+            .ipc
+            .request(
+                "qc-11-smart-contracts",
+                RequestPayload::Call(CallRequestPayload {
+                    call,
+                    block_id: block_id.unwrap_or_default(),
+                }),
+                None,
+            )
+            .await
+            .map_err(|e| explain_revert(&abi, ApiError::from(e)))?;
+
+        let data: Bytes =
+            serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))?;
+        Ok(ContractCallResult {
+            signature: function.signature(),
+            outputs: function.decode_output(data.as_slice())?,
+            data,
+        })
+    }
+}
+
+/// Replace an execution error's message with the decoded revert reason.
+fn explain_revert(abi: &ContractAbi, error: ApiError) -> ApiError {
+    if error.code != codes::EXECUTION_ERROR {
+        return error;
+    }
+    let revert_data = error
+        .data
+        .as_ref()
+        .and_then(|data| data.get("data"))
+        .and_then(|data| data.as_str())
+        .and_then(|data| hex::decode(data.strip_prefix("0x").unwrap_or(data)).ok());
+
+    match revert_data {
+        Some(data) => match abi.decode_revert(&data) {
+            Some(reason) => ApiError::execution_error(reason, Some(data)),
+            None => error,
+        },
+        None => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::pending::ResponseError;
+    use crate::ipc::handler::channel::test_handler;
+    use serde_json::json;
+    use std::time::Duration;
+
+    const TOKEN: Address = Address::repeat_byte(0xaa);
+
+    /// ContractRpc whose qc-11 answers every call with `call_result`
+    fn contract_rpc(call_result: Result<serde_json::Value, ResponseError>) -> ContractRpc {
+        let ipc = test_handler(Duration::from_secs(1), move |request| {
+            let RequestPayload::Call(payload) = &request.payload else {
+                return None;
+            };
+            assert_eq!(payload.call.to, Some(TOKEN));
+            Some(call_result.clone())
+        });
+
+        ContractRpc::new(ipc)
+    }
+
+    fn balance_of(args: serde_json::Value) -> ContractCallRequest {
+        serde_json::from_value(json!({
+            "to": TOKEN,
+            "abi": [
+                {"type": "function", "name": "balanceOf", "stateMutability": "view",
+                 "inputs": [{"name": "owner", "type": "address"}],
+                 "outputs": [{"name": "balance", "type": "uint256"}]},
+                {"type": "error", "name": "Blocked", "inputs": [{"name": "who", "type": "address"}]}
+            ],
+            "function": "balanceOf",
+            "args": args,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_call_decodes_outputs() {
+        let rpc = contract_rpc(Ok(json!(format!("0x{:064x}", 1234))));
+
+        let result = rpc
+            .call(balance_of(json!([Address::repeat_byte(0xbb)])), None)
+            .await
+            .unwrap();
+
+        assert_eq!(result.signature, "balanceOf(address)");
+        assert_eq!(result.outputs[0].name, "balance");
+        assert_eq!(result.outputs[0].value, json!("1234"));
+    }
+
+    #[tokio::test]
+    async fn test_call_explains_custom_revert() {
+        let mut revert = crate::domain::abi::selector("Blocked(address)").to_vec();
+        revert.extend([0u8; 12]);
+        revert.extend([0xbb; 20]);
+        let rpc = contract_rpc(Err(ResponseError {
+            code: codes::EXECUTION_ERROR,
+            message: "Execution reverted".into(),
+            data: Some(json!({ "data": format!("0x{}", hex::encode(&revert)) })),
+        }));
+
+        let err = rpc
+            .call(balance_of(json!([Address::repeat_byte(0xbb)])), None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, codes::EXECUTION_ERROR);
+        assert_eq!(
+            err.message,
+            format!("Execution reverted: Blocked(who=0x{})", "bb".repeat(20))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bad_arguments_are_invalid_params() {
+        let rpc = contract_rpc(Ok(json!("0x")));
+
+        let err = rpc.call(balance_of(json!([])), None).await.unwrap_err();
+
+        assert_eq!(err.code, codes::INVALID_PARAMS);
+        assert!(err.message.contains("takes 1 arguments"));
+    }
+}
@@ -1,7 +1,10 @@
 //! Debug JSON-RPC methods per SPEC-16 Section 3.3 (Admin tier).
 
+pub use crate::domain::types::TraceOptions;
+
 use crate::domain::types::{BlockId, Hash};
 use crate::ipc::handler::IpcHandler;
+use crate::ipc::requests::*;
 use crate::{ApiError, ApiResult};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -157,6 +160,45 @@ impl DebugRpc {
         })
     }
 
+    /// debug_eventBus - Returns recent events per topic, per-subscriber lag
+    /// and DLQ depth
+    ///
+    /// Poll with `since` set to the previous response's `cursor` to follow
+    /// the bus. Routes to the node runtime's event mirror.
+    #[instrument(skip(self))]
+    pub async fn event_bus(&self, request: GetEventBusRequest) -> ApiResult<serde_json::Value> {
+        self.ipc
+            .request("admin", RequestPayload::GetEventBus(request), None)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    /// debug_recentBlocks - Returns the latest canonical blocks with their
+    /// transaction counts and producers, and recent chain reorganizations
+    #[instrument(skip(self))]
+    pub async fn recent_blocks(
+        &self,
+        request: GetRecentBlocksRequest,
+    ) -> ApiResult<serde_json::Value> {
+        self.ipc
+            .request("admin", RequestPayload::GetRecentBlocks(request), None)
+            .await
+            .map_err(ApiError::from)
+    }
+
+    /// debug_metricHistory - Returns the node's sampled history of peers,
+    /// mempool size, block time and finality lag, as series or CSV
+    #[instrument(skip(self))]
+    pub async fn metric_history(
+        &self,
+        request: GetMetricHistoryRequest,
+    ) -> ApiResult<serde_json::Value> {
+        self.ipc
+            .request("admin", RequestPayload::GetMetricHistory(request), None)
+            .await
+            .map_err(ApiError::from)
+    }
+
     /// debug_subsystemStatus - Returns detailed status for a specific subsystem
     #[instrument(skip(self))]
     pub async fn subsystem_status(
@@ -196,16 +238,17 @@ impl DebugRpc {
     }
 
     /// debug_traceTransaction - Trace transaction execution
+    ///
+    /// Deferred: qc-11 has no tracer to replay a transaction under yet, so
+    /// this is reported as unsupported rather than queried.
     #[instrument(skip(self))]
     pub async fn trace_transaction(
         &self,
         hash: Hash,
         options: Option<TraceOptions>,
     ) -> ApiResult<serde_json::Value> {
-        // This would require deep integration with EVM execution
-        // For now, return a placeholder structure
         Err(ApiError::method_not_supported(
-            "debug_traceTransaction requires EVM tracing integration",
+            "debug_traceTransaction is deferred until qc-11 has a tracer",
         ))
     }
 
@@ -246,13 +289,28 @@ impl DebugRpc {
         ))
     }
 
-    /// debug_getRawBlock - Returns raw block bytes
+    /// debug_getRawBlock - Returns the block in the canonical codec
+    /// (version byte followed by RLP), hex encoded
     #[instrument(skip(self))]
     pub async fn get_raw_block(&self, block_id: BlockId) -> ApiResult<String> {
-        // Would query block storage for RLP-encoded block
-        Err(ApiError::method_not_supported(
-            "debug_getRawBlock not implemented",
-        ))
+        let result = self
+            .ipc
+            .request(
+                "qc-02-block-storage",
+                RequestPayload::GetRawBlock(GetRawBlockRequest { block_id }),
+                None,
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        match result {
+            serde_json::Value::String(raw) => Ok(raw),
+            serde_json::Value::Null => Err(ApiError::resource_not_found("Block not found")),
+            other => Err(ApiError::internal(format!(
+                "Unexpected raw block response: {}",
+                other
+            ))),
+        }
     }
 
     /// debug_getRawHeader - Returns raw header bytes
@@ -303,33 +361,6 @@ impl DebugRpc {
     }
 }
 
-/// Trace options for debug methods
-#[derive(Debug, Clone, Default, serde::Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TraceOptions {
-    /// Tracer to use (callTracer, prestateTracer, etc.)
-    #[serde(default)]
-    pub tracer: Option<String>,
-    /// Tracer configuration
-    #[serde(default)]
-    pub tracer_config: Option<serde_json::Value>,
-    /// Timeout for trace operation
-    #[serde(default)]
-    pub timeout: Option<String>,
-    /// Disable storage capture
-    #[serde(default)]
-    pub disable_storage: bool,
-    /// Disable stack capture
-    #[serde(default)]
-    pub disable_stack: bool,
-    /// Enable memory capture
-    #[serde(default)]
-    pub enable_memory: bool,
-    /// Enable return data capture
-    #[serde(default)]
-    pub enable_return_data: bool,
-}
-
 // ═══════════════════════════════════════════════════════════════════════════
 // SUBSYSTEM HEALTH TYPES (Admin Panel Support)
 // ═══════════════════════════════════════════════════════════════════════════
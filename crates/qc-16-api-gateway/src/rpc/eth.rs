@@ -1,22 +1,40 @@
 //! Ethereum JSON-RPC methods (eth_*) per SPEC-16 Section 3.1.
 
+use crate::adapters::fee_history::{FeeHistoryProvider, FeeOracleConfig};
+use crate::domain::error::codes;
 use crate::domain::types::*;
 use crate::ipc::handler::IpcHandler;
 use crate::ipc::requests::*;
-use crate::ipc::validation::validate_raw_transaction;
+use crate::ipc::validation::{validate_raw_transaction, ValidatedTransaction};
 use crate::{ApiError, ApiResult};
 use std::sync::Arc;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
 /// Ethereum RPC methods handler
 pub struct EthRpc {
     ipc: Arc<IpcHandler>,
     chain_id: u64,
+    /// Gas oracle (eth_gasPrice, eth_maxPriorityFeePerGas, eth_feeHistory)
+    fees: FeeHistoryProvider,
+    /// Simulate raw transactions before submission unless the caller opts out
+    preflight_by_default: bool,
 }
 
 impl EthRpc {
     pub fn new(ipc: Arc<IpcHandler>, chain_id: u64) -> Self {
-        Self { ipc, chain_id }
+        let fees = FeeHistoryProvider::new(Arc::clone(&ipc), FeeOracleConfig::default());
+        Self {
+            ipc,
+            chain_id,
+            fees,
+            preflight_by_default: false,
+        }
+    }
+
+    /// Pre-flight every eth_sendRawTransaction unless the caller opts out
+    pub fn with_preflight_by_default(mut self, enabled: bool) -> Self {
+        self.preflight_by_default = enabled;
+        self
     }
 
     // ═══════════════════════════════════════════════════════════════════════
@@ -40,7 +58,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         // Parse result as hex string or number
         let block_num: u64 = if let Some(s) = result.as_str() {
@@ -58,17 +76,7 @@ impl EthRpc {
     /// eth_gasPrice - Returns current gas price
     #[instrument(skip(self))]
     pub async fn gas_price(&self) -> ApiResult<U256> {
-        let result = self
-            .ipc
-            .request(
-                "qc-06-mempool",
-                RequestPayload::GetGasPrice(GetGasPriceRequest),
-                None,
-            )
-            .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
-
-        serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))
+        self.fees.gas_price().await
     }
 
     // ═══════════════════════════════════════════════════════════════════════
@@ -93,7 +101,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))
     }
@@ -112,7 +120,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))
     }
@@ -137,7 +145,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))
     }
@@ -160,7 +168,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         // Parse as hex string
         if let Some(s) = result.as_str() {
@@ -200,7 +208,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         if result.is_null() {
             Ok(None)
@@ -227,7 +235,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         if result.is_null() {
             Ok(None)
@@ -291,7 +299,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         if result.is_null() {
             Ok(None)
@@ -314,7 +322,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         if result.is_null() {
             Ok(None)
@@ -341,7 +349,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))
     }
@@ -361,7 +369,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))
     }
@@ -372,12 +380,26 @@ impl EthRpc {
 
     /// eth_sendRawTransaction - Submit pre-signed transaction
     ///
-    /// CRITICAL: Validates RLP structure BEFORE sending to mempool.
+    /// CRITICAL: Validates RLP structure BEFORE sending to mempool. With
+    /// pre-flight enabled, the transaction is first executed as an eth_call
+    /// against latest state and a revert is returned instead of submitting.
+    /// A pre-flight the caller asked for must run; one enabled only by the
+    /// node default is skipped when qc-11 cannot simulate.
     #[instrument(skip(self, raw_tx))]
-    pub async fn send_raw_transaction(&self, raw_tx: Bytes) -> ApiResult<Hash> {
+    pub async fn send_raw_transaction(
+        &self,
+        raw_tx: Bytes,
+        options: SendRawTransactionOptions,
+    ) -> ApiResult<Hash> {
         // STEP 1: RLP pre-validation (reject garbage at the gate)
         let validated = validate_raw_transaction(raw_tx.as_slice())?;
 
+        // STEP 2: Optional pre-flight simulation
+        if options.preflight.unwrap_or(self.preflight_by_default) {
+            self.preflight(&validated, options.preflight == Some(true))
+                .await?;
+        }
+
         debug!(
             tx_hash = %validated.hash,
             sender = %validated.sender,
@@ -385,10 +407,10 @@ impl EthRpc {
             "Validated raw transaction, submitting to mempool"
         );
 
-        // STEP 2: Create submit request with pre-computed fields
+        // STEP 3: Create submit request with pre-computed fields
         let submit_request = crate::ipc::validation::create_submit_request(raw_tx, &validated);
 
-        // STEP 3: Send to mempool
+        // STEP 4: Send to mempool
         let _result = self
             .ipc
             .request(
@@ -397,12 +419,50 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         // Return transaction hash
         Ok(validated.hash)
     }
 
+    /// Run a validated transaction through qc-11 against latest state.
+    ///
+    /// An execution error (revert, out of gas) rejects the transaction. If
+    /// the simulation itself cannot run, a `requested` pre-flight fails with
+    /// `RESOURCE_UNAVAILABLE`; otherwise submission proceeds as usual.
+    async fn preflight(&self, tx: &ValidatedTransaction, requested: bool) -> ApiResult<()> {
+        let call = CallRequest {
+            from: Some(tx.sender),
+            to: tx.to,
+            gas: Some(U256::from(tx.gas_limit)),
+            gas_price: Some(tx.gas_price),
+            value: Some(tx.value),
+            data: Some(Bytes::from(tx.data.clone())),
+            nonce: Some(U256::from(tx.nonce)),
+            ..Default::default()
+        };
+
+        match self.call(call, Some(BlockId::default())).await {
+            Ok(_) => Ok(()),
+            Err(e) if e.code == codes::EXECUTION_ERROR => {
+                debug!(tx_hash = %tx.hash, error = %e.message, "Pre-flight simulation reverted");
+                Err(e)
+            }
+            Err(e) if requested => Err(ApiError::resource_unavailable(format!(
+                "preflight unavailable: {}",
+                e.message
+            ))),
+            Err(e) => {
+                warn!(
+                    tx_hash = %tx.hash,
+                    error = %e.message,
+                    "Pre-flight simulation unavailable, submitting without it"
+                );
+                Ok(())
+            }
+        }
+    }
+
     // ═══════════════════════════════════════════════════════════════════════
     // LOGS & EVENTS
     // ═══════════════════════════════════════════════════════════════════════
@@ -418,7 +478,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))
     }
@@ -439,7 +499,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))
     }
@@ -451,17 +511,7 @@ impl EthRpc {
     /// eth_maxPriorityFeePerGas - Returns suggested max priority fee per gas
     #[instrument(skip(self))]
     pub async fn max_priority_fee_per_gas(&self) -> ApiResult<U256> {
-        let result = self
-            .ipc
-            .request(
-                "qc-06-mempool",
-                RequestPayload::GetMaxPriorityFeePerGas(GetMaxPriorityFeePerGasRequest),
-                None,
-            )
-            .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
-
-        serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))
+        self.fees.max_priority_fee_per_gas().await
     }
 
     /// eth_feeHistory - Returns historical gas fee data
@@ -501,21 +551,9 @@ impl EthRpc {
             }
         }
 
-        let result = self
-            .ipc
-            .request(
-                "qc-02-block-storage",
-                RequestPayload::GetFeeHistory(GetFeeHistoryRequest {
-                    block_count: count,
-                    newest_block,
-                    reward_percentiles,
-                }),
-                None,
-            )
+        self.fees
+            .fee_history(count, newest_block, reward_percentiles)
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
-
-        serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))
     }
 
     // ═══════════════════════════════════════════════════════════════════════
@@ -538,7 +576,7 @@ impl EthRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         if result.is_null() {
             Ok(None)
@@ -550,6 +588,114 @@ impl EthRpc {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::adapters::pending::ResponseError;
+    use crate::ipc::handler::channel::test_handler;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// EIP-155 example transaction (chain 1, nonce 9, 1 ETH transfer)
+    const SIGNED_TX: &str = "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    /// EthRpc whose qc-11 answers eth_call with `call_result`; returns the
+    /// handler and counters of (calls, submissions)
+    fn eth_rpc(
+        call_result: Result<serde_json::Value, ResponseError>,
+    ) -> (EthRpc, Arc<(AtomicUsize, AtomicUsize)>) {
+        let counts = Arc::new((AtomicUsize::new(0), AtomicUsize::new(0)));
+
+        let seen = Arc::clone(&counts);
+        let ipc = test_handler(Duration::from_secs(1), move |request| {
+            match request.payload {
+                RequestPayload::Call(_) => {
+                    seen.0.fetch_add(1, Ordering::SeqCst);
+                    Some(call_result.clone())
+                }
+                RequestPayload::SubmitTransaction(_) => {
+                    seen.1.fetch_add(1, Ordering::SeqCst);
+                    Some(Ok(serde_json::Value::Null))
+                }
+                _ => None,
+            }
+        });
+
+        (EthRpc::new(ipc, 1), counts)
+    }
+
+    fn raw_tx() -> Bytes {
+        Bytes(hex::decode(SIGNED_TX).unwrap())
+    }
+
+    fn opt_in() -> SendRawTransactionOptions {
+        SendRawTransactionOptions {
+            preflight: Some(true),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preflight_revert_blocks_submission() {
+        let (eth, counts) = eth_rpc(Err(ResponseError {
+            code: codes::EXECUTION_ERROR,
+            message: "Execution reverted: insufficient allowance".into(),
+            data: None,
+        }));
 
-    // Tests would require mock IPC handler
+        let err = eth
+            .send_raw_transaction(raw_tx(), opt_in())
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, codes::EXECUTION_ERROR);
+        assert!(err.message.contains("insufficient allowance"));
+        assert_eq!(counts.1.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_requested_preflight_unavailable_is_error() {
+        let (eth, counts) = eth_rpc(Err(ResponseError {
+            code: -32000,
+            message: "call unavailable".into(),
+            data: None,
+        }));
+
+        let err = eth
+            .send_raw_transaction(raw_tx(), opt_in())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, codes::RESOURCE_UNAVAILABLE);
+        assert!(err.message.contains("preflight unavailable"));
+        assert_eq!(counts.1.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_default_preflight_unavailable_still_submits() {
+        let (eth, counts) = eth_rpc(Err(ResponseError {
+            code: -32000,
+            message: "call unavailable".into(),
+            data: None,
+        }));
+        let eth = eth.with_preflight_by_default(true);
+
+        let options = SendRawTransactionOptions::default();
+        assert!(eth.send_raw_transaction(raw_tx(), options).await.is_ok());
+        assert_eq!(counts.0.load(Ordering::SeqCst), 1);
+        assert_eq!(counts.1.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_preflight_off_by_default() {
+        let (eth, counts) = eth_rpc(Ok(serde_json::json!("0x")));
+
+        eth.send_raw_transaction(raw_tx(), SendRawTransactionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(counts.0.load(Ordering::SeqCst), 0);
+
+        let eth = eth.with_preflight_by_default(true);
+        eth.send_raw_transaction(raw_tx(), SendRawTransactionOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(counts.0.load(Ordering::SeqCst), 1);
+        assert_eq!(counts.1.load(Ordering::SeqCst), 2);
+    }
 }
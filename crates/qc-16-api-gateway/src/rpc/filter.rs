@@ -0,0 +1,73 @@
+//! Polling filter methods (eth_newFilter and friends) per SPEC-16 Section 3.1.
+//!
+//! Filters live in the gateway; only eth_getFilterLogs reaches qc-03.
+
+use crate::adapters::filters::{FilterKind, FilterStore};
+use crate::domain::types::Filter;
+use crate::ipc::handler::IpcHandler;
+use crate::ipc::requests::*;
+use crate::{ApiError, ApiResult};
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Polling filter RPC methods handler
+pub struct FilterRpc {
+    ipc: Arc<IpcHandler>,
+    filters: Arc<FilterStore>,
+}
+
+impl FilterRpc {
+    pub fn new(ipc: Arc<IpcHandler>, filters: Arc<FilterStore>) -> Self {
+        Self { ipc, filters }
+    }
+
+    /// eth_newFilter - Creates a log filter
+    #[instrument(skip(self))]
+    pub async fn new_filter(&self, filter: Filter) -> ApiResult<String> {
+        Ok(self.filters.install(FilterKind::Logs(Box::new(filter)))?)
+    }
+
+    /// eth_newBlockFilter - Creates a new-block filter
+    #[instrument(skip(self))]
+    pub async fn new_block_filter(&self) -> ApiResult<String> {
+        Ok(self.filters.install(FilterKind::Blocks)?)
+    }
+
+    /// eth_newPendingTransactionFilter - Creates a pending transaction filter
+    #[instrument(skip(self))]
+    pub async fn new_pending_transaction_filter(&self) -> ApiResult<String> {
+        Ok(self.filters.install(FilterKind::PendingTransactions)?)
+    }
+
+    /// eth_uninstallFilter - Removes a filter
+    #[instrument(skip(self))]
+    pub async fn uninstall_filter(&self, id: String) -> ApiResult<bool> {
+        Ok(self.filters.uninstall(&id))
+    }
+
+    /// eth_getFilterChanges - Returns changes since the last poll
+    #[instrument(skip(self))]
+    pub async fn get_filter_changes(&self, id: String) -> ApiResult<Vec<serde_json::Value>> {
+        Ok(self.filters.poll_changes(&id)?)
+    }
+
+    /// eth_getFilterLogs - Returns all logs matching a log filter
+    #[instrument(skip(self))]
+    pub async fn get_filter_logs(&self, id: String) -> ApiResult<Vec<serde_json::Value>> {
+        let FilterKind::Logs(filter) = self.filters.kind(&id)? else {
+            return Err(ApiError::invalid_params("Filter is not a log filter"));
+        };
+
+        let result = self
+            .ipc
+            .request(
+                "qc-03-transaction-indexing",
+                RequestPayload::GetLogs(GetLogsRequest { filter: *filter }),
+                None,
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        serde_json::from_value(result).map_err(|e| ApiError::internal(e.to_string()))
+    }
+}
@@ -0,0 +1,462 @@
+//! Streamed eth_getLogs for wide block ranges.
+//!
+//! The range is split into `log_chunk_blocks` windows that are fetched from
+//! qc-03 one at a time while the previous window is written to the client,
+//! so the gateway never holds the full result set. A response stops after
+//! `max_log_results` logs or `max_log_block_range` scanned blocks; a capped
+//! response carries a `cursor` member next to `result`, which the client
+//! passes back as the filter's `cursor` field to continue where it left off.
+
+use crate::domain::config::LimitsConfig;
+use crate::domain::types::{BlockId, BlockTag, Filter};
+use crate::ipc::handler::IpcHandler;
+use crate::ipc::requests::*;
+use crate::{ApiError, ApiResult};
+use axum::body::{Body, Bytes};
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+
+/// Resume point of a capped eth_getLogs response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogCursor {
+    /// First block to scan
+    pub block: u64,
+    /// Logs of the scan starting at `block` that were already returned
+    pub skip: usize,
+}
+
+impl LogCursor {
+    /// Parse the `0x<block>:<skip>` form returned to clients
+    pub fn parse(s: &str) -> ApiResult<Self> {
+        let invalid = || ApiError::invalid_params(format!("invalid log cursor: {}", s));
+        let (block, skip) = s.split_once(':').ok_or_else(invalid)?;
+        let block = block.strip_prefix("0x").ok_or_else(invalid)?;
+        Ok(Self {
+            block: u64::from_str_radix(block, 16).map_err(|_| invalid())?,
+            skip: skip.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl fmt::Display for LogCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}:{}", self.block, self.skip)
+    }
+}
+
+/// Caps applied to one streamed response
+#[derive(Debug, Clone)]
+pub struct LogStreamLimits {
+    /// Blocks per qc-03 query
+    pub chunk_blocks: u64,
+    /// Max logs per response
+    pub max_results: usize,
+    /// Max blocks scanned per response
+    pub max_block_range: u64,
+}
+
+impl From<&LimitsConfig> for LogStreamLimits {
+    fn from(limits: &LimitsConfig) -> Self {
+        Self {
+            chunk_blocks: limits.log_chunk_blocks.max(1),
+            max_results: limits.max_log_results,
+            max_block_range: limits.max_log_block_range.max(1),
+        }
+    }
+}
+
+/// Starts streamed eth_getLogs scans
+pub struct LogStreamer {
+    ipc: Arc<IpcHandler>,
+    limits: LogStreamLimits,
+}
+
+impl LogStreamer {
+    pub fn new(ipc: Arc<IpcHandler>, limits: LogStreamLimits) -> Self {
+        Self { ipc, limits }
+    }
+
+    /// Check if a filter can be scanned by block number (not by block hash)
+    pub fn supports(filter: &Filter) -> bool {
+        let by_number = |id: &Option<BlockId>| match id {
+            Some(BlockId::Hash(h)) => h.block_number.is_some(),
+            _ => true,
+        };
+        filter.block_hash.is_none() && by_number(&filter.from_block) && by_number(&filter.to_block)
+    }
+
+    /// Resolve the range and fetch the first window.
+    ///
+    /// Errors before the first window surface as a normal JSON-RPC error;
+    /// later errors abort the response body.
+    pub async fn start(&self, filter: Filter) -> ApiResult<LogStream> {
+        let mut head = None;
+        let from = self.resolve(filter.from_block.as_ref(), &mut head).await?;
+        let end = self.resolve(filter.to_block.as_ref(), &mut head).await?;
+        if from > end {
+            return Err(ApiError::invalid_params("fromBlock is after toBlock"));
+        }
+
+        let cursor = filter.cursor.as_deref().map(LogCursor::parse).transpose()?;
+        let (start, skip) = match cursor {
+            Some(c) if c.block < from || c.block > end => {
+                return Err(ApiError::invalid_params(
+                    "log cursor is outside the block range",
+                ))
+            }
+            Some(c) => (c.block, c.skip),
+            None => (from, 0),
+        };
+
+        let mut stream = LogStream {
+            ipc: Arc::clone(&self.ipc),
+            template: Filter {
+                cursor: None,
+                ..filter
+            },
+            next: Some(start),
+            end,
+            scan_end: end.min(start.saturating_add(self.limits.max_block_range - 1)),
+            chunk_blocks: self.limits.chunk_blocks,
+            max_results: self.limits.max_results,
+            emitted: 0,
+            window: None,
+            cursor: None,
+            exhausted: false,
+        };
+        stream.fetch_window(skip).await?;
+        Ok(stream)
+    }
+
+    /// Block number for a filter bound; tags other than `earliest` mean the head
+    async fn resolve(&self, id: Option<&BlockId>, head: &mut Option<u64>) -> ApiResult<u64> {
+        match id {
+            Some(BlockId::Number(n)) => return Ok(*n),
+            Some(BlockId::Tag(BlockTag::Earliest)) => return Ok(0),
+            Some(BlockId::Hash(h)) => {
+                return h
+                    .block_number
+                    .ok_or_else(|| ApiError::invalid_params("block hash bounds use blockHash"))
+            }
+            Some(BlockId::Tag(_)) | None => {}
+        }
+
+        if let Some(head) = head {
+            return Ok(*head);
+        }
+        let result = self
+            .ipc
+            .request(
+                "qc-02-block-storage",
+                RequestPayload::GetBlockNumber(GetBlockNumberRequest),
+                None,
+            )
+            .await
+            .map_err(ApiError::from)?;
+        let number = match &result {
+            Value::String(s) => u64::from_str_radix(s.trim_start_matches("0x"), 16).ok(),
+            other => other.as_u64(),
+        }
+        .ok_or_else(|| ApiError::internal("Invalid block number format"))?;
+
+        *head = Some(number);
+        Ok(number)
+    }
+}
+
+/// Window fetched from qc-03 but not yet written
+struct Window {
+    start: u64,
+    /// Logs of this window dropped before `logs` (cursor resume)
+    skip: usize,
+    logs: Vec<Value>,
+}
+
+/// In-progress eth_getLogs scan
+pub struct LogStream {
+    ipc: Arc<IpcHandler>,
+    /// Caller's filter; each window overrides the block bounds
+    template: Filter,
+    /// Next block to fetch (None once the chain end was passed)
+    next: Option<u64>,
+    /// Requested last block
+    end: u64,
+    /// Last block this response may scan
+    scan_end: u64,
+    chunk_blocks: u64,
+    max_results: usize,
+    emitted: usize,
+    window: Option<Window>,
+    cursor: Option<LogCursor>,
+    exhausted: bool,
+}
+
+impl LogStream {
+    /// Next batch of logs (None when the response is complete)
+    pub async fn next_chunk(&mut self) -> ApiResult<Option<Vec<Value>>> {
+        loop {
+            if let Some(Window {
+                start,
+                skip,
+                mut logs,
+            }) = self.window.take()
+            {
+                let room = self.max_results - self.emitted;
+                if logs.len() > room {
+                    logs.truncate(room);
+                    self.cursor = Some(LogCursor {
+                        block: start,
+                        skip: skip + room,
+                    });
+                    self.exhausted = true;
+                }
+                self.emitted += logs.len();
+                if !logs.is_empty() {
+                    return Ok(Some(logs));
+                }
+                continue;
+            }
+
+            if self.exhausted {
+                return Ok(None);
+            }
+
+            match self.next {
+                Some(next) if next <= self.scan_end => self.fetch_window(0).await?,
+                Some(next) => {
+                    // Scan cap reached before the requested end
+                    if next <= self.end {
+                        self.cursor = Some(LogCursor {
+                            block: next,
+                            skip: 0,
+                        });
+                    }
+                    self.exhausted = true;
+                }
+                None => self.exhausted = true,
+            }
+        }
+    }
+
+    /// Continuation cursor, set once a cap cut the response short
+    pub fn cursor(&self) -> Option<LogCursor> {
+        self.cursor
+    }
+
+    async fn fetch_window(&mut self, skip: usize) -> ApiResult<()> {
+        let Some(start) = self.next else {
+            return Ok(());
+        };
+        let to = start
+            .saturating_add(self.chunk_blocks - 1)
+            .min(self.scan_end);
+
+        let filter = Filter {
+            from_block: Some(BlockId::Number(start)),
+            to_block: Some(BlockId::Number(to)),
+            ..self.template.clone()
+        };
+        let result = self
+            .ipc
+            .request(
+                "qc-03-transaction-indexing",
+                RequestPayload::GetLogs(GetLogsRequest { filter }),
+                None,
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        let Value::Array(mut logs) = result else {
+            return Err(ApiError::internal("Invalid eth_getLogs response"));
+        };
+        logs.drain(..skip.min(logs.len()));
+
+        self.next = to.checked_add(1);
+        self.window = Some(Window { start, skip, logs });
+        Ok(())
+    }
+}
+
+/// Chunked JSON-RPC response body, serialized one window at a time
+pub fn response_body(id: Option<Value>, stream: LogStream) -> Body {
+    struct State {
+        stream: LogStream,
+        id: Option<Option<Value>>,
+        first: bool,
+        done: bool,
+    }
+
+    let state = State {
+        stream,
+        id: Some(id),
+        first: true,
+        done: false,
+    };
+
+    Body::from_stream(futures::stream::unfold(state, |mut st| async move {
+        if st.done {
+            return None;
+        }
+
+        let mut buf = Vec::new();
+        if let Some(id) = st.id.take() {
+            buf.extend_from_slice(br#"{"jsonrpc":"2.0","id":"#);
+            serde_json::to_writer(&mut buf, &id).ok()?;
+            buf.extend_from_slice(br#","result":["#);
+        }
+
+        match st.stream.next_chunk().await {
+            Ok(Some(logs)) => {
+                for log in logs {
+                    if !std::mem::take(&mut st.first) {
+                        buf.push(b',');
+                    }
+                    serde_json::to_writer(&mut buf, &log).ok()?;
+                }
+            }
+            Ok(None) => {
+                buf.push(b']');
+                if let Some(cursor) = st.stream.cursor() {
+                    buf.extend_from_slice(format!(r#","cursor":"{}""#, cursor).as_bytes());
+                }
+                buf.push(b'}');
+                st.done = true;
+            }
+            Err(e) => {
+                // Headers are already sent; abort the body so the client
+                // sees a truncated response instead of a partial result
+                st.done = true;
+                return Some((Err(std::io::Error::other(e.message)), st));
+            }
+        }
+
+        Some((Ok(Bytes::from(buf)), st))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::handler::channel::test_handler;
+    use std::time::Duration;
+
+    /// Streamer whose qc-03 returns one log per block
+    fn streamer(limits: LogStreamLimits) -> LogStreamer {
+        let ipc = test_handler(Duration::from_secs(1), |request| {
+            let RequestPayload::GetLogs(GetLogsRequest { filter }) = &request.payload else {
+                return None;
+            };
+            let (Some(BlockId::Number(from)), Some(BlockId::Number(to))) =
+                (&filter.from_block, &filter.to_block)
+            else {
+                return None;
+            };
+            let logs: Vec<_> = (*from..=*to)
+                .map(|n| serde_json::json!({ "blockNumber": format!("{:#x}", n) }))
+                .collect();
+            Some(Ok(Value::Array(logs)))
+        });
+
+        LogStreamer::new(ipc, limits)
+    }
+
+    fn range(from: u64, to: u64) -> Filter {
+        Filter {
+            from_block: Some(BlockId::Number(from)),
+            to_block: Some(BlockId::Number(to)),
+            ..Default::default()
+        }
+    }
+
+    async fn collect(mut stream: LogStream) -> Vec<Value> {
+        let mut all = Vec::new();
+        while let Some(logs) = stream.next_chunk().await.unwrap() {
+            all.extend(logs);
+        }
+        all
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = LogCursor {
+            block: 0x1234,
+            skip: 7,
+        };
+        assert_eq!(cursor.to_string(), "0x1234:7");
+        assert_eq!(LogCursor::parse("0x1234:7").unwrap(), cursor);
+        assert!(LogCursor::parse("1234:7").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_windows_cover_range() {
+        let streamer = streamer(LogStreamLimits {
+            chunk_blocks: 3,
+            max_results: 100,
+            max_block_range: 100,
+        });
+
+        let stream = streamer.start(range(10, 19)).await.unwrap();
+        let logs = collect(stream).await;
+        assert_eq!(logs.len(), 10);
+        assert_eq!(logs[9]["blockNumber"], "0x13");
+    }
+
+    #[tokio::test]
+    async fn test_result_cap_resumes_with_cursor() {
+        let streamer = streamer(LogStreamLimits {
+            chunk_blocks: 4,
+            max_results: 6,
+            max_block_range: 100,
+        });
+
+        let mut stream = streamer.start(range(0, 9)).await.unwrap();
+        let mut first = Vec::new();
+        while let Some(logs) = stream.next_chunk().await.unwrap() {
+            first.extend(logs);
+        }
+        assert_eq!(first.len(), 6);
+        let cursor = stream.cursor().unwrap();
+        assert_eq!(cursor, LogCursor { block: 4, skip: 2 });
+
+        let resumed = Filter {
+            cursor: Some(cursor.to_string()),
+            ..range(0, 9)
+        };
+        let rest = collect(streamer.start(resumed).await.unwrap()).await;
+        assert_eq!(rest.len(), 4);
+        assert_eq!(rest[0]["blockNumber"], "0x6");
+    }
+
+    #[tokio::test]
+    async fn test_block_range_cap_sets_cursor() {
+        let streamer = streamer(LogStreamLimits {
+            chunk_blocks: 10,
+            max_results: 100,
+            max_block_range: 5,
+        });
+
+        let mut stream = streamer.start(range(0, 9)).await.unwrap();
+        assert_eq!(stream.next_chunk().await.unwrap().unwrap().len(), 5);
+        assert!(stream.next_chunk().await.unwrap().is_none());
+        assert_eq!(stream.cursor(), Some(LogCursor { block: 5, skip: 0 }));
+    }
+
+    #[tokio::test]
+    async fn test_response_body_is_valid_json() {
+        let streamer = streamer(LogStreamLimits {
+            chunk_blocks: 2,
+            max_results: 3,
+            max_block_range: 100,
+        });
+
+        let stream = streamer.start(range(0, 9)).await.unwrap();
+        let body = response_body(Some(serde_json::json!(1)), stream);
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let response: Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"].as_array().unwrap().len(), 3);
+        assert_eq!(response["cursor"], "0x2:1");
+    }
+}
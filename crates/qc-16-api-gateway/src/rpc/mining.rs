@@ -0,0 +1,276 @@
+//! External block producer methods for qc-17 Block Production.
+//!
+//! `qc_getBlockTemplate` returns a template for the current head. Fetched
+//! templates are cached per beneficiary for a short TTL and dropped as soon
+//! as a new head arrives. A request carrying the `longPollId` of a template
+//! it already holds is parked until the head moves past that template or the
+//! long-poll timeout passes, then answered with a fresh template.
+//!
+//! `qc_submitBlock` forwards a nonce for a template to qc-17, which checks the
+//! proof of work and that the template still builds on the head before
+//! propagating the block.
+
+use crate::domain::config::BlockTemplateConfig;
+use crate::domain::types::{Address, BlockTemplateOptions};
+use crate::ipc::handler::IpcHandler;
+use crate::ipc::requests::*;
+use crate::{ApiError, ApiResult};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::watch;
+use tracing::{debug, instrument};
+
+/// Target subsystem for all block template methods
+const BLOCK_PRODUCTION: &str = "qc-17-block-production";
+
+/// Template fetched from qc-17
+struct CachedTemplate {
+    template: serde_json::Value,
+    fetched_at: Instant,
+    head_version: u64,
+}
+
+/// Block template RPC methods handler
+pub struct MiningRpc {
+    ipc: Arc<IpcHandler>,
+    cache_ttl: Duration,
+    long_poll_timeout: Duration,
+    /// Cached templates keyed by beneficiary (None = node default)
+    cache: Mutex<HashMap<Option<Address>, CachedTemplate>>,
+    /// Bumped on every new head; long polls wait on it
+    head_version: watch::Sender<u64>,
+}
+
+impl MiningRpc {
+    pub fn new(ipc: Arc<IpcHandler>, config: &BlockTemplateConfig) -> Self {
+        Self {
+            ipc,
+            cache_ttl: config.cache_ttl,
+            long_poll_timeout: config.long_poll_timeout,
+            cache: Mutex::new(HashMap::new()),
+            head_version: watch::Sender::new(0),
+        }
+    }
+
+    /// Drop cached templates and wake long polls (called on each new head)
+    pub fn on_new_head(&self) {
+        self.cache.lock().unwrap().clear();
+        self.head_version.send_modify(|version| *version += 1);
+    }
+
+    /// qc_getBlockTemplate - Returns a template to seal, long-polling if asked
+    #[instrument(skip(self))]
+    pub async fn get_block_template(
+        &self,
+        options: BlockTemplateOptions,
+    ) -> ApiResult<serde_json::Value> {
+        let Some(long_poll_id) = options.long_poll_id else {
+            return self.template_for(options.beneficiary).await;
+        };
+
+        // Subscribe before fetching so a head arriving in between still wakes us
+        let mut heads = self.head_version.subscribe();
+        let template = self.template_for(options.beneficiary).await?;
+        if template.get("longPollId").and_then(|id| id.as_str()) != Some(long_poll_id.as_str()) {
+            return Ok(template);
+        }
+
+        debug!(long_poll_id = %long_poll_id, "Long-polling for a new head");
+        if tokio::time::timeout(self.long_poll_timeout, heads.changed())
+            .await
+            .is_err()
+        {
+            return Ok(template);
+        }
+        self.template_for(options.beneficiary).await
+    }
+
+    /// qc_submitBlock - Submits a nonce solving a template
+    #[instrument(skip(self))]
+    pub async fn submit_block(
+        &self,
+        template_id: String,
+        nonce: u64,
+    ) -> ApiResult<serde_json::Value> {
+        if template_id.is_empty() {
+            return Err(ApiError::invalid_params("templateId must not be empty"));
+        }
+
+        let result = self
+            .request(RequestPayload::SubmitBlock(SubmitBlockRequest {
+                template_id,
+                nonce,
+            }))
+            .await?;
+
+        // The accepted block is the new head for every cached template
+        self.on_new_head();
+        Ok(result)
+    }
+
+    /// Cached template for `beneficiary`, fetching a new one if expired
+    async fn template_for(&self, beneficiary: Option<Address>) -> ApiResult<serde_json::Value> {
+        let head_version = *self.head_version.borrow();
+        if let Some(cached) = self.cache.lock().unwrap().get(&beneficiary) {
+            if cached.head_version == head_version && cached.fetched_at.elapsed() < self.cache_ttl {
+                return Ok(cached.template.clone());
+            }
+        }
+
+        let template = self
+            .request(RequestPayload::GetBlockTemplate(GetBlockTemplateRequest {
+                beneficiary,
+            }))
+            .await?;
+
+        // Don't cache across a head change that raced the fetch
+        if *self.head_version.borrow() == head_version {
+            self.cache.lock().unwrap().insert(
+                beneficiary,
+                CachedTemplate {
+                    template: template.clone(),
+                    fetched_at: Instant::now(),
+                    head_version,
+                },
+            );
+        }
+        Ok(template)
+    }
+
+    async fn request(&self, payload: RequestPayload) -> ApiResult<serde_json::Value> {
+        self.ipc
+            .request(BLOCK_PRODUCTION, payload, None)
+            .await
+            .map_err(ApiError::from)
+    }
+}
+
+/// Background task forwarding new heads to the template cache
+pub async fn template_head_task(
+    mining: Arc<MiningRpc>,
+    mut heads: broadcast::Receiver<serde_json::Value>,
+) {
+    // A lagged receiver still missed at least one head
+    while let Ok(_) | Err(RecvError::Lagged(_)) = heads.recv().await {
+        mining.on_new_head();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::codes;
+    use crate::ipc::handler::channel::test_handler;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// MiningRpc whose qc-17 issues templates building on `parent`; returns
+    /// the handler and the number of templates fetched
+    fn mining_rpc(
+        parent: Arc<Mutex<&'static str>>,
+        long_poll_timeout: Duration,
+    ) -> (Arc<MiningRpc>, Arc<AtomicU64>) {
+        let fetched = Arc::new(AtomicU64::new(0));
+
+        let seen = Arc::clone(&fetched);
+        let ipc = test_handler(Duration::from_secs(1), move |request| {
+            match request.payload {
+                RequestPayload::GetBlockTemplate(_) => {
+                    let id = seen.fetch_add(1, Ordering::SeqCst);
+                    let parent = *parent.lock().unwrap();
+                    Some(Ok(serde_json::json!({
+                        "templateId": id.to_string(),
+                        "longPollId": parent,
+                    })))
+                }
+                RequestPayload::SubmitBlock(_) => Some(Ok(serde_json::json!({ "accepted": true }))),
+                _ => None,
+            }
+        });
+
+        let config = BlockTemplateConfig {
+            cache_ttl: Duration::from_secs(60),
+            long_poll_timeout,
+        };
+        (Arc::new(MiningRpc::new(ipc, &config)), fetched)
+    }
+
+    fn long_poll(id: &str) -> BlockTemplateOptions {
+        BlockTemplateOptions {
+            long_poll_id: Some(id.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_template_cached_until_new_head() {
+        let parent = Arc::new(Mutex::new("0xaa"));
+        let (mining, fetched) = mining_rpc(parent, Duration::from_secs(1));
+
+        let first = mining.get_block_template(Default::default()).await.unwrap();
+        let second = mining.get_block_template(Default::default()).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(fetched.load(Ordering::SeqCst), 1);
+
+        // Another beneficiary gets its own template
+        let other = BlockTemplateOptions {
+            beneficiary: Some(Address::repeat_byte(1)),
+            ..Default::default()
+        };
+        mining.get_block_template(other).await.unwrap();
+        assert_eq!(fetched.load(Ordering::SeqCst), 2);
+
+        mining.on_new_head();
+        let third = mining.get_block_template(Default::default()).await.unwrap();
+        assert_ne!(first, third);
+    }
+
+    #[tokio::test]
+    async fn test_long_poll_waits_for_new_head() {
+        let parent = Arc::new(Mutex::new("0xaa"));
+        let (mining, _) = mining_rpc(Arc::clone(&parent), Duration::from_secs(5));
+
+        // A stale long-poll id is answered immediately
+        let template = mining.get_block_template(long_poll("0x00")).await.unwrap();
+        assert_eq!(template["longPollId"], "0xaa");
+
+        let waiting = tokio::spawn({
+            let mining = Arc::clone(&mining);
+            async move { mining.get_block_template(long_poll("0xaa")).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+
+        *parent.lock().unwrap() = "0xbb";
+        mining.on_new_head();
+        let template = waiting.await.unwrap().unwrap();
+        assert_eq!(template["longPollId"], "0xbb");
+    }
+
+    #[tokio::test]
+    async fn test_long_poll_times_out_with_current_template() {
+        let parent = Arc::new(Mutex::new("0xaa"));
+        let (mining, fetched) = mining_rpc(parent, Duration::from_millis(20));
+
+        let template = mining.get_block_template(long_poll("0xaa")).await.unwrap();
+        assert_eq!(template["longPollId"], "0xaa");
+        assert_eq!(fetched.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_block() {
+        let parent = Arc::new(Mutex::new("0xaa"));
+        let (mining, fetched) = mining_rpc(parent, Duration::from_secs(1));
+
+        let err = mining.submit_block(String::new(), 1).await.unwrap_err();
+        assert_eq!(err.code, codes::INVALID_PARAMS);
+
+        mining.get_block_template(Default::default()).await.unwrap();
+        mining.submit_block("0".into(), 7).await.unwrap();
+
+        // The sealed block invalidates the cached template
+        mining.get_block_template(Default::default()).await.unwrap();
+        assert_eq!(fetched.load(Ordering::SeqCst), 2);
+    }
+}
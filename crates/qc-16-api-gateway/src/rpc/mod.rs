@@ -1,19 +1,30 @@
 //! RPC method handlers for JSON-RPC API.
 
 pub mod admin;
+pub mod contract;
 pub mod debug;
 pub mod eth;
+pub mod filter;
+pub mod logs;
+pub mod mining;
 pub mod net;
+pub mod swap;
 pub mod txpool;
 pub mod web3;
 
 pub use admin::AdminRpc;
+pub use contract::ContractRpc;
 pub use debug::DebugRpc;
 pub use eth::EthRpc;
+pub use filter::FilterRpc;
+pub use logs::LogStreamer;
+pub use mining::MiningRpc;
 pub use net::NetRpc;
+pub use swap::SwapRpc;
 pub use txpool::TxPoolRpc;
 pub use web3::Web3Rpc;
 
+use crate::adapters::filters::FilterStore;
 use crate::domain::config::GatewayConfig;
 use crate::ipc::handler::IpcHandler;
 use std::path::PathBuf;
@@ -22,23 +33,39 @@ use std::sync::Arc;
 /// All RPC handlers
 pub struct RpcHandlers {
     pub eth: EthRpc,
+    pub filter: FilterRpc,
+    pub logs: LogStreamer,
     pub web3: Web3Rpc,
     pub net: NetRpc,
     pub txpool: TxPoolRpc,
     pub admin: AdminRpc,
     pub debug: DebugRpc,
+    pub swap: SwapRpc,
+    pub contract: ContractRpc,
+    pub mining: Arc<MiningRpc>,
 }
 
 impl RpcHandlers {
-    /// Create all RPC handlers from config and IPC handler
-    pub fn new(config: &GatewayConfig, ipc: Arc<IpcHandler>, data_dir: PathBuf) -> Self {
+    /// Create all RPC handlers from config, IPC handler, and filter store
+    pub fn new(
+        config: &GatewayConfig,
+        ipc: Arc<IpcHandler>,
+        filters: Arc<FilterStore>,
+        data_dir: PathBuf,
+    ) -> Self {
         Self {
-            eth: EthRpc::new(Arc::clone(&ipc), config.chain.chain_id),
+            eth: EthRpc::new(Arc::clone(&ipc), config.chain.chain_id)
+                .with_preflight_by_default(config.preflight.enabled_by_default),
+            filter: FilterRpc::new(Arc::clone(&ipc), filters),
+            logs: LogStreamer::new(Arc::clone(&ipc), (&config.limits).into()),
             web3: Web3Rpc::new(config.chain.client_version.clone()),
             net: NetRpc::new(Arc::clone(&ipc), config.chain.chain_id),
             txpool: TxPoolRpc::new(Arc::clone(&ipc)),
             admin: AdminRpc::new(Arc::clone(&ipc), data_dir),
-            debug: DebugRpc::new(ipc),
+            debug: DebugRpc::new(Arc::clone(&ipc)),
+            swap: SwapRpc::new(Arc::clone(&ipc)),
+            contract: ContractRpc::new(Arc::clone(&ipc)),
+            mining: Arc::new(MiningRpc::new(ipc, &config.block_templates)),
         }
     }
 }
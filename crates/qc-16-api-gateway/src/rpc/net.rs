@@ -53,7 +53,7 @@ impl NetRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         // Parse result as array and count
         let count = if let Some(arr) = result.as_array() {
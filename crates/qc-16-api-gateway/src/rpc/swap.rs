@@ -0,0 +1,136 @@
+//! Swap JSON-RPC methods for qc-15 Cross-Chain atomic swaps.
+//!
+//! Counterparties quote swaps and discover maker liquidity (Public tier);
+//! the node operator advertises its own liquidity (Admin tier).
+
+use crate::domain::types::{Address, SwapOffer, U256};
+use crate::ipc::handler::IpcHandler;
+use crate::ipc::requests::*;
+use crate::{ApiError, ApiResult};
+use std::sync::Arc;
+use tracing::instrument;
+
+/// Target subsystem for all swap methods
+const CROSS_CHAIN: &str = "qc-15-cross-chain";
+
+/// Swap RPC methods handler
+pub struct SwapRpc {
+    ipc: Arc<IpcHandler>,
+}
+
+impl SwapRpc {
+    pub fn new(ipc: Arc<IpcHandler>) -> Self {
+        Self { ipc }
+    }
+
+    /// swap_getQuote - Returns expected fees, timelocks, and completion time
+    #[instrument(skip(self))]
+    pub async fn get_quote(
+        &self,
+        source_chain: String,
+        target_chain: String,
+        amount: U256,
+    ) -> ApiResult<serde_json::Value> {
+        validate_pair(&source_chain, &target_chain)?;
+        if amount.is_zero() {
+            return Err(ApiError::invalid_params("Swap amount must be non-zero"));
+        }
+
+        self.request(RequestPayload::GetSwapQuote(GetSwapQuoteRequest {
+            source_chain,
+            target_chain,
+            amount,
+        }))
+        .await
+    }
+
+    /// swap_getMakers - Returns live maker offers for a pair
+    #[instrument(skip(self))]
+    pub async fn get_makers(
+        &self,
+        source_chain: String,
+        target_chain: String,
+    ) -> ApiResult<serde_json::Value> {
+        validate_pair(&source_chain, &target_chain)?;
+
+        self.request(RequestPayload::GetSwapMakers(GetSwapMakersRequest {
+            source_chain,
+            target_chain,
+        }))
+        .await
+    }
+
+    /// swap_advertiseLiquidity - Advertise (or replace) this node's offer
+    #[instrument(skip(self))]
+    pub async fn advertise_liquidity(&self, offer: SwapOffer) -> ApiResult<bool> {
+        validate_pair(&offer.source_chain, &offer.target_chain)?;
+        if offer.min_amount.inner() > offer.max_amount.inner() {
+            return Err(ApiError::invalid_params(
+                "minAmount must not exceed maxAmount",
+            ));
+        }
+
+        let result = self
+            .request(RequestPayload::AdvertiseSwapLiquidity(
+                AdvertiseSwapLiquidityRequest { offer },
+            ))
+            .await?;
+
+        Ok(result.as_bool().unwrap_or(false))
+    }
+
+    /// swap_withdrawLiquidity - Withdraw a previously advertised offer
+    #[instrument(skip(self))]
+    pub async fn withdraw_liquidity(
+        &self,
+        maker: Address,
+        source_chain: String,
+        target_chain: String,
+    ) -> ApiResult<bool> {
+        validate_pair(&source_chain, &target_chain)?;
+
+        let result = self
+            .request(RequestPayload::WithdrawSwapLiquidity(
+                WithdrawSwapLiquidityRequest {
+                    maker,
+                    source_chain,
+                    target_chain,
+                },
+            ))
+            .await?;
+
+        Ok(result.as_bool().unwrap_or(false))
+    }
+
+    async fn request(&self, payload: RequestPayload) -> ApiResult<serde_json::Value> {
+        self.ipc
+            .request(CROSS_CHAIN, payload, None)
+            .await
+            .map_err(ApiError::from)
+    }
+}
+
+/// Reject empty or identical chain names before hitting IPC
+fn validate_pair(source_chain: &str, target_chain: &str) -> ApiResult<()> {
+    if source_chain.is_empty() || target_chain.is_empty() {
+        return Err(ApiError::invalid_params("Chain name must not be empty"));
+    }
+    if source_chain == target_chain {
+        return Err(ApiError::invalid_params(
+            "Source and target chain must differ",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_pair() {
+        assert!(validate_pair("QuantumChain", "Ethereum").is_ok());
+        assert!(validate_pair("Ethereum", "Ethereum").is_err());
+        assert!(validate_pair("", "Ethereum").is_err());
+    }
+}
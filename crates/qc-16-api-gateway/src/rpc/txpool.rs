@@ -1,6 +1,6 @@
 //! TxPool JSON-RPC methods per SPEC-16 Section 3.2 (Protected tier).
 
-use crate::domain::types::Address;
+use crate::domain::types::{Address, Hash};
 use crate::ipc::handler::IpcHandler;
 use crate::ipc::requests::*;
 use crate::{ApiError, ApiResult};
@@ -28,7 +28,7 @@ impl TxPoolRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         Ok(result)
     }
@@ -44,7 +44,7 @@ impl TxPoolRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
 
         Ok(result)
     }
@@ -62,7 +62,24 @@ impl TxPoolRpc {
                 None,
             )
             .await
-            .map_err(|e| ApiError::new(e.code, e.message))?;
+            .map_err(ApiError::from)?;
+
+        Ok(result)
+    }
+
+    /// txpool_transaction - Returns a pooled transaction with its sender's
+    /// nonce gaps and the transactions it replaced
+    #[instrument(skip(self))]
+    pub async fn transaction(&self, hash: Hash) -> ApiResult<serde_json::Value> {
+        let result = self
+            .ipc
+            .request(
+                "qc-06-mempool",
+                RequestPayload::GetTxPoolTransaction(GetTxPoolTransactionRequest { hash }),
+                None,
+            )
+            .await
+            .map_err(ApiError::from)?;
 
         Ok(result)
     }
@@ -2,25 +2,36 @@
 //!
 //! Provides HTTP (JSON-RPC), WebSocket, and Admin API servers.
 
+use crate::adapters::api_keys::{key_reload_task, ApiKeyStore};
+use crate::adapters::capabilities::AdvertisedCapabilities;
+use crate::adapters::filters::{filter_cleanup_task, FilterStore};
+use crate::adapters::health::{ReadinessProbe, SubsystemHealth};
 use crate::adapters::pending::{cleanup_task, PendingRequestStore};
-use crate::domain::error::GatewayError;
+use crate::adapters::tls::GatewayTls;
+use crate::domain::error::{codes, GatewayError};
+use crate::domain::methods::MethodTier;
+use crate::domain::types::Filter;
 use crate::ipc::handler::{IpcHandler, IpcSender};
 use crate::middleware::{
-    create_cors_layer, GatewayMetrics, RateLimitLayer, TimeoutLayer, TracingLayer, ValidationLayer,
+    authorize_method, create_cors_layer, require_jwt, AuthConfig, CallerContext, GatewayMetrics,
+    JwtValidator, RateLimitLayer, RateLimitState, TimeoutLayer, TracingLayer, ValidationLayer,
 };
-use crate::rpc::RpcHandlers;
-use crate::ws::{SubscriptionManager, WebSocketHandler};
+use crate::rpc::mining::template_head_task;
+use crate::rpc::{LogStreamer, RpcHandlers};
+use crate::ws::node_logs::{stream_logs, LOGS_METHOD};
+use crate::ws::{LogFeed, LogStreamQuery, SubscriptionManager, WebSocketHandler};
 use crate::GatewayConfig;
 use axum::{
-    extract::{ws::WebSocketUpgrade, State},
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{ws::WebSocketUpgrade, Query, State},
+    http::{header, request::Parts, Request, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::oneshot;
 use tower::ServiceBuilder;
 use tracing::{error, info};
@@ -30,9 +41,18 @@ pub struct ApiGatewayService {
     config: GatewayConfig,
     rpc_handlers: Arc<RpcHandlers>,
     subscription_manager: Arc<SubscriptionManager>,
+    log_feed: Arc<LogFeed>,
     pending_store: Arc<PendingRequestStore>,
+    filter_store: Arc<FilterStore>,
+    key_store: Arc<ApiKeyStore>,
+    rate_limit: Arc<RateLimitState>,
+    jwt: Option<Arc<JwtValidator>>,
     metrics: Arc<GatewayMetrics>,
     circuit_breaker: Arc<crate::middleware::CircuitBreakerManager>,
+    readiness: Arc<ReadinessProbe>,
+    capabilities: Arc<AdvertisedCapabilities>,
+    #[cfg(feature = "graphql")]
+    graphql_schema: crate::graphql::GatewaySchema,
     shutdown_tx: Option<oneshot::Sender<()>>,
 }
 
@@ -51,36 +71,106 @@ impl ApiGatewayService {
         // Create pending request store
         let pending_store = Arc::new(PendingRequestStore::new(config.timeouts.default));
 
-        // Create IPC handler
-        let ipc_handler = Arc::new(IpcHandler::new(
-            Arc::clone(&pending_store),
-            ipc_sender,
-            config.timeouts.default,
-        ));
+        // Create IPC handler (records per-subsystem response ages for readiness)
+        let subsystem_health = Arc::new(SubsystemHealth::new());
+        let capabilities = Arc::new(AdvertisedCapabilities::new());
+        let ipc_handler = Arc::new(
+            IpcHandler::new(
+                Arc::clone(&pending_store),
+                Arc::clone(&ipc_sender),
+                config.timeouts.default,
+            )
+            .with_retry_policy(config.ipc_retry.to_policy())
+            .with_health(Arc::clone(&subsystem_health))
+            .with_capabilities(Arc::clone(&capabilities)),
+        );
+
+        // Create GraphQL schema (shares the IPC handler and pending store)
+        #[cfg(feature = "graphql")]
+        let graphql_schema =
+            crate::graphql::build_schema(Arc::clone(&ipc_handler), &config.graphql);
+
+        // Load API keys (file-backed keys survive restarts and can be hand-edited)
+        let key_store = Arc::new(match &config.api_keys.file {
+            Some(path) => {
+                ApiKeyStore::open(path).map_err(|e| GatewayError::Config(e.to_string()))?
+            }
+            None => ApiKeyStore::in_memory(),
+        });
+
+        // Load the admin JWT secret (shared with Engine API style tooling)
+        let jwt = match &config.admin.jwt_secret_file {
+            Some(path) => Some(Arc::new(
+                JwtValidator::from_file(path)
+                    .map_err(|e| GatewayError::Config(format!("{}: {}", path.display(), e)))?
+                    .with_clock_skew(config.admin.jwt_clock_skew),
+            )),
+            None => None,
+        };
+
+        // Create polling filter store (fed alongside WebSocket subscriptions)
+        let filter_store = Arc::new(FilterStore::new(config.filters.to_limits()));
 
         // Create RPC handlers
-        let rpc_handlers = Arc::new(RpcHandlers::new(&config, ipc_handler, data_dir));
+        let rpc_handlers = Arc::new(RpcHandlers::new(
+            &config,
+            ipc_handler,
+            Arc::clone(&filter_store),
+            data_dir,
+        ));
 
         // Create subscription manager
-        let subscription_manager = Arc::new(SubscriptionManager::new(
-            config.websocket.max_subscriptions_per_connection,
-        ));
+        let subscription_manager = Arc::new(
+            SubscriptionManager::with_filters(
+                config.websocket.max_subscriptions_per_connection,
+                Arc::clone(&filter_store),
+            )
+            .with_slow_consumer_limit(config.websocket.slow_consumer_drop_limit)
+            .with_resume_window(config.websocket.resume_window)
+            .with_resume_replay_limit(config.websocket.resume_replay_limit),
+        );
+
+        // Node log lines served on the WebSocket port's `/logs`
+        let log_feed = Arc::new(LogFeed::new(config.websocket.log_history));
 
         // Create metrics
-        let metrics = Arc::new(GatewayMetrics::new());
+        let metrics = Arc::new(
+            GatewayMetrics::new()
+                .with_slow_request_threshold(config.metrics.slow_request_threshold()),
+        );
 
         // Create circuit breaker manager from config
         let circuit_breaker = Arc::new(crate::middleware::CircuitBreakerManager::new(
             config.circuit_breaker.to_middleware_config(),
         ));
 
+        let readiness = Arc::new(ReadinessProbe::new(
+            subsystem_health,
+            Arc::clone(&pending_store),
+            Arc::clone(&circuit_breaker),
+            ipc_sender,
+            config.health.to_thresholds(),
+        ));
+
+        // Per-IP limits, shared by the JSON-RPC and GraphQL ports
+        let rate_limit = Arc::new(RateLimitState::new(config.rate_limit.clone()));
+
         Ok(Self {
             config,
             rpc_handlers,
             subscription_manager,
+            log_feed,
             pending_store,
+            filter_store,
+            key_store,
+            rate_limit,
+            jwt,
             metrics,
             circuit_breaker,
+            readiness,
+            capabilities,
+            #[cfg(feature = "graphql")]
+            graphql_schema,
             shutdown_tx: None,
         })
     }
@@ -93,6 +183,18 @@ impl ApiGatewayService {
         let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
         self.shutdown_tx = Some(shutdown_tx);
 
+        // Load TLS certificates before binding anything
+        let tls = self
+            .config
+            .tls
+            .as_ref()
+            .map(GatewayTls::load)
+            .transpose()
+            .map_err(|e| GatewayError::Config(e.to_string()))?;
+        if tls.is_some() {
+            info!("TLS enabled for all servers");
+        }
+
         // Start cleanup tasks
         self.start_cleanup_tasks();
 
@@ -105,11 +207,8 @@ impl ApiGatewayService {
         let http_addr = self.config.http_addr();
         let http_handle = if self.config.http.enabled {
             info!(addr = %http_addr, "Starting HTTP server");
-            let router = http_router;
-            Some(tokio::spawn(async move {
-                let listener = tokio::net::TcpListener::bind(http_addr).await?;
-                axum::serve(listener, router).await
-            }))
+            let tls = tls.as_ref().map(|t| t.http.clone());
+            Some(tokio::spawn(serve_router(http_addr, http_router, tls)))
         } else {
             None
         };
@@ -118,11 +217,8 @@ impl ApiGatewayService {
         let ws_addr = self.config.ws_addr();
         let _ws_handle = if self.config.websocket.enabled {
             info!(addr = %ws_addr, "Starting WebSocket server");
-            let router = ws_router;
-            Some(tokio::spawn(async move {
-                let listener = tokio::net::TcpListener::bind(ws_addr).await?;
-                axum::serve(listener, router).await
-            }))
+            let tls = tls.as_ref().map(|t| t.websocket.clone());
+            Some(tokio::spawn(serve_router(ws_addr, ws_router, tls)))
         } else {
             None
         };
@@ -131,11 +227,20 @@ impl ApiGatewayService {
         let admin_addr = self.config.admin_addr();
         let _admin_handle = if self.config.admin.enabled {
             info!(addr = %admin_addr, "Starting Admin server");
-            let router = admin_router;
-            Some(tokio::spawn(async move {
-                let listener = tokio::net::TcpListener::bind(admin_addr).await?;
-                axum::serve(listener, router).await
-            }))
+            let tls = tls.as_ref().map(|t| t.admin.clone());
+            Some(tokio::spawn(serve_router(admin_addr, admin_router, tls)))
+        } else {
+            None
+        };
+
+        // Start GraphQL server
+        #[cfg(feature = "graphql")]
+        let _graphql_handle = if self.config.graphql.enabled {
+            let graphql_addr = self.config.graphql_addr();
+            info!(addr = %graphql_addr, "Starting GraphQL server");
+            let router = self.build_graphql_router();
+            let tls = tls.as_ref().map(|t| t.http.clone());
+            Some(tokio::spawn(serve_router(graphql_addr, router, tls)))
         } else {
             None
         };
@@ -181,17 +286,67 @@ impl ApiGatewayService {
         Arc::clone(&self.pending_store)
     }
 
+    /// Get advertised subsystem capabilities (for feeding registry reports from the event bus)
+    pub fn capabilities(&self) -> Arc<AdvertisedCapabilities> {
+        Arc::clone(&self.capabilities)
+    }
+
+    /// Get subscription manager (for feeding WebSocket subscriptions from the event bus)
+    pub fn subscription_manager(&self) -> Arc<SubscriptionManager> {
+        Arc::clone(&self.subscription_manager)
+    }
+
+    /// Get the node log feed (for feeding `/logs` from the node's telemetry)
+    pub fn log_feed(&self) -> Arc<LogFeed> {
+        Arc::clone(&self.log_feed)
+    }
+
     /// Get circuit breaker manager (for IPC integration)
     pub fn circuit_breaker(&self) -> Arc<crate::middleware::CircuitBreakerManager> {
         Arc::clone(&self.circuit_breaker)
     }
 
-    /// Build HTTP router for JSON-RPC
-    fn build_http_router(&self) -> Router {
-        let state = AppState {
+    /// Get the readiness probe behind Admin `/health/ready`
+    pub fn readiness(&self) -> Arc<ReadinessProbe> {
+        Arc::clone(&self.readiness)
+    }
+
+    /// Get API key store (for embedding key management)
+    pub fn api_keys(&self) -> Arc<ApiKeyStore> {
+        Arc::clone(&self.key_store)
+    }
+
+    /// Get the per-IP rate limiter (for changing limits at runtime)
+    pub fn rate_limit(&self) -> Arc<RateLimitState> {
+        Arc::clone(&self.rate_limit)
+    }
+
+    /// JSON-RPC handler state for the public HTTP and WebSocket ports
+    fn app_state(&self) -> AppState {
+        self.app_state_with_jwt(None)
+    }
+
+    /// JSON-RPC handler state for the admin port, the only one honouring JWTs
+    fn admin_app_state(&self) -> AppState {
+        self.app_state_with_jwt(self.jwt.clone())
+    }
+
+    fn app_state_with_jwt(&self, jwt: Option<Arc<JwtValidator>>) -> AppState {
+        AppState {
             rpc_handlers: Arc::clone(&self.rpc_handlers),
             metrics: Arc::clone(&self.metrics),
-        };
+            auth: Arc::new(AuthConfig {
+                api_key: self.config.admin.api_key.clone(),
+                allow_external_admin: self.config.admin.allow_external,
+                keys: Some(Arc::clone(&self.key_store)),
+                jwt,
+            }),
+        }
+    }
+
+    /// Build HTTP router for JSON-RPC
+    fn build_http_router(&self) -> Router {
+        let state = self.app_state();
 
         // Build middleware stack
         let middleware = ServiceBuilder::new()
@@ -199,7 +354,11 @@ impl ApiGatewayService {
             .layer(TracingLayer::new())
             .layer(TimeoutLayer::new(self.config.timeouts.clone()))
             .layer(ValidationLayer::new(self.config.limits.clone()))
-            .layer(RateLimitLayer::new(self.config.rate_limit.clone()));
+            .layer(
+                RateLimitLayer::from_state(Arc::clone(&self.rate_limit))
+                    .with_keys(Arc::clone(&self.key_store))
+                    .with_metrics(Arc::clone(&self.metrics)),
+            );
 
         Router::new()
             .route("/", post(handle_json_rpc))
@@ -208,19 +367,46 @@ impl ApiGatewayService {
             .with_state(state)
     }
 
+    /// Build GraphQL router with the same edge middleware as JSON-RPC
+    #[cfg(feature = "graphql")]
+    fn build_graphql_router(&self) -> Router {
+        // Bodies are GraphQL, not JSON-RPC: only the size limit applies here,
+        // depth and complexity are enforced by the schema
+        let middleware = ServiceBuilder::new()
+            .layer(create_cors_layer(&self.config.cors))
+            .layer(TracingLayer::new())
+            .layer(TimeoutLayer::new(self.config.timeouts.clone()))
+            .layer(axum::extract::DefaultBodyLimit::max(
+                self.config.limits.max_request_size,
+            ))
+            .layer(
+                RateLimitLayer::from_state(Arc::clone(&self.rate_limit))
+                    .with_metrics(Arc::clone(&self.metrics)),
+            );
+
+        crate::graphql::graphql_router(self.graphql_schema.clone())
+            .route("/health", get(health_check))
+            .layer(middleware)
+    }
+
     /// Build WebSocket router
     fn build_ws_router(&self) -> Router {
         let subscription_manager = Arc::clone(&self.subscription_manager);
+        let ws_config = self.config.websocket.to_handler_config();
 
-        Router::new().route(
-            "/",
-            get(move |ws: WebSocketUpgrade| async move {
-                ws.on_upgrade(move |socket| async move {
-                    let handler = WebSocketHandler::new(subscription_manager);
-                    handler.handle(socket).await;
-                })
-            }),
-        )
+        Router::new()
+            .route(
+                "/",
+                get(move |ws: WebSocketUpgrade| async move {
+                    ws.on_upgrade(move |socket| async move {
+                        let handler =
+                            WebSocketHandler::with_config(subscription_manager, ws_config);
+                        handler.handle(socket).await;
+                    })
+                }),
+            )
+            .route("/logs", get(handle_log_stream))
+            .with_state((self.app_state(), Arc::clone(&self.log_feed)))
     }
 
     /// Build Admin router
@@ -230,9 +416,36 @@ impl ApiGatewayService {
         let circuit_breaker = Arc::clone(&self.circuit_breaker);
         let circuit_breaker_for_metrics = Arc::clone(&self.circuit_breaker);
         let circuit_breaker_for_reset = Arc::clone(&self.circuit_breaker);
+        let key_store = Arc::clone(&self.key_store);
+        let readiness = Arc::clone(&self.readiness);
+        let started_at = Instant::now();
 
-        Router::new()
+        let router = Router::new()
             .route("/health", get(health_check))
+            .route(
+                "/health/live",
+                get(move || async move {
+                    Json(serde_json::json!({
+                        "status": "alive",
+                        "uptimeSecs": started_at.elapsed().as_secs()
+                    }))
+                }),
+            )
+            .route(
+                "/health/ready",
+                get(move || {
+                    let readiness = Arc::clone(&readiness);
+                    async move {
+                        let report = readiness.check();
+                        let status = if report.is_ready() {
+                            StatusCode::OK
+                        } else {
+                            StatusCode::SERVICE_UNAVAILABLE
+                        };
+                        (status, Json(report))
+                    }
+                }),
+            )
             .route(
                 "/metrics",
                 get(move || {
@@ -287,6 +500,27 @@ impl ApiGatewayService {
                     }
                 }),
             )
+            .route("/keys", get(list_api_keys).post(create_api_key))
+            .route("/keys/:id", axum::routing::delete(revoke_api_key))
+            .route("/keys/:id/usage", get(api_key_usage))
+            .with_state(key_store);
+
+        // With a JWT secret the admin port doubles as an authenticated
+        // JSON-RPC endpoint, like an Engine API port
+        match &self.jwt {
+            Some(jwt) => router
+                .merge(
+                    Router::new()
+                        .route("/", post(handle_json_rpc))
+                        .with_state(self.admin_app_state()),
+                )
+                .layer(axum::middleware::from_fn_with_state(
+                    Arc::clone(jwt),
+                    require_jwt,
+                ))
+                .layer(create_cors_layer(&self.config.cors)),
+            None => router,
+        }
     }
 
     /// Start background cleanup tasks
@@ -297,14 +531,77 @@ impl ApiGatewayService {
             cleanup_task(pending_store, Duration::from_secs(10)).await;
         });
 
+        // Idle filter expiry
+        let filter_store = Arc::clone(&self.filter_store);
+        tokio::spawn(async move {
+            filter_cleanup_task(filter_store, Duration::from_secs(30)).await;
+        });
+
+        // Invalidate block templates and wake long polls on each new head
+        let mining = Arc::clone(&self.rpc_handlers.mining);
+        let heads = self.subscription_manager.subscribe_new_heads();
+        tokio::spawn(async move {
+            template_head_task(mining, heads).await;
+        });
+
+        // Pick up hand edits to the key file
+        if self.config.api_keys.file.is_some() {
+            let key_store = Arc::clone(&self.key_store);
+            let interval = self.config.api_keys.reload_interval;
+            tokio::spawn(async move {
+                key_reload_task(key_store, interval).await;
+            });
+        }
+
         // Rate limit bucket cleanup would go here
     }
 }
 
-use crate::router::{AppState, route_method};
+use crate::router::{route_method, AppState};
+
+/// Serve a router over plain TCP or TLS.
+///
+/// Both paths negotiate HTTP/1.1 or HTTP/2 per connection (ALPN under TLS,
+/// prior knowledge otherwise). Connect info lets tier enforcement see the
+/// caller's address.
+async fn serve_router(
+    addr: SocketAddr,
+    router: Router,
+    tls: Option<axum_server::tls_rustls::RustlsConfig>,
+) -> std::io::Result<()> {
+    let app = router.into_make_service_with_connect_info::<SocketAddr>();
+    match tls {
+        Some(tls) => axum_server::bind_rustls(addr, tls).serve(app).await,
+        None => {
+            let listener = tokio::net::TcpListener::bind(addr).await?;
+            axum::serve(listener, app).await
+        }
+    }
+}
+
+/// Handle a `/logs` upgrade; the caller must pass the Admin tier
+async fn handle_log_stream(
+    State((state, feed)): State<(AppState, Arc<LogFeed>)>,
+    Query(query): Query<LogStreamQuery>,
+    parts: Parts,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let caller = CallerContext::from_request(&Request::from_parts(parts, ()), &state.auth);
+    if let Err(e) = authorize_method(LOGS_METHOD, &caller, &state.auth) {
+        state.metrics.record_auth_failure(MethodTier::Admin);
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response();
+    }
+    ws.on_upgrade(move |socket| stream_logs(socket, feed, query))
+}
 
 /// Handle JSON-RPC request
-async fn handle_json_rpc(State(state): State<AppState>, body: String) -> impl IntoResponse {
+async fn handle_json_rpc(State(state): State<AppState>, parts: Parts, body: String) -> Response {
+    let caller = CallerContext::from_request(&Request::from_parts(parts, ()), &state.auth);
+
     // Parse request
     let request: serde_json::Value = match serde_json::from_str(&body) {
         Ok(v) => v,
@@ -319,7 +616,8 @@ async fn handle_json_rpc(State(state): State<AppState>, body: String) -> impl In
                     },
                     "id": null
                 })),
-            );
+            )
+                .into_response();
         }
     };
 
@@ -330,22 +628,89 @@ async fn handle_json_rpc(State(state): State<AppState>, body: String) -> impl In
         let mut responses = Vec::with_capacity(requests.len());
 
         for req in requests {
-            let resp = process_single_request(&state, req).await;
+            let resp = process_single_request(&state, &caller, req).await;
             responses.push(resp);
         }
 
         serde_json::Value::Array(responses)
+    } else if let Some(response) = stream_get_logs(&state, &caller, &request).await {
+        // Single eth_getLogs: stream the result window by window
+        return response;
     } else {
         // Single request
-        process_single_request(&state, &request).await
+        process_single_request(&state, &caller, &request).await
     };
 
-    (StatusCode::OK, Json(response))
+    (StatusCode::OK, Json(response)).into_response()
+}
+
+/// Serve a single eth_getLogs request as a chunked response.
+///
+/// Returns None when the request should take the regular path (invalid
+/// request, block hash filter), so errors are reported the usual way.
+async fn stream_get_logs(
+    state: &AppState,
+    caller: &CallerContext,
+    request: &serde_json::Value,
+) -> Option<Response> {
+    if request.get("method").and_then(|m| m.as_str()) != Some("eth_getLogs") {
+        return None;
+    }
+    let id = request.get("id").cloned();
+    validate_request_id(&id).ok()?;
+
+    let filter = request.get("params")?.get(0)?;
+    let filter: Filter = serde_json::from_value(filter.clone()).ok()?;
+    if !LogStreamer::supports(&filter) {
+        return None;
+    }
+
+    let timer = Instant::now();
+    let started = match authorize_method("eth_getLogs", caller, &state.auth) {
+        Ok(()) => state.rpc_handlers.logs.start(filter).await,
+        Err(e) => {
+            if e.code == codes::UNAUTHORIZED {
+                state.metrics.record_auth_failure(MethodTier::Public);
+            }
+            Err(e)
+        }
+    };
+
+    let stream = match started {
+        Ok(stream) => stream,
+        Err(e) => {
+            state
+                .metrics
+                .record_method("eth_getLogs", false, timer.elapsed());
+            let error = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {
+                    "code": e.code,
+                    "message": e.message
+                }
+            });
+            return Some((StatusCode::OK, Json(error)).into_response());
+        }
+    };
+
+    // Time to first window; later windows stream after the handler returns
+    state
+        .metrics
+        .record_method("eth_getLogs", true, timer.elapsed());
+    Some(
+        (
+            [(header::CONTENT_TYPE, "application/json")],
+            crate::rpc::logs::response_body(id, stream),
+        )
+            .into_response(),
+    )
 }
 
 /// Process a single JSON-RPC request
 async fn process_single_request(
     state: &AppState,
+    caller: &CallerContext,
     request: &serde_json::Value,
 ) -> serde_json::Value {
     let id = request.get("id").cloned();
@@ -357,14 +722,28 @@ async fn process_single_request(
 
     let method = request.get("method").and_then(|m| m.as_str()).unwrap_or("");
     let params = request.get("params");
+    let timer = Instant::now();
+
+    // Enforce method tier before routing; unknown methods fall through to
+    // the router's method-not-found error
+    let authorized = match crate::get_method_tier(method) {
+        Some(tier) => authorize_method(method, caller, &state.auth).inspect_err(|e| {
+            if e.code == codes::UNAUTHORIZED {
+                state.metrics.record_auth_failure(tier);
+            }
+        }),
+        None => Ok(()),
+    };
 
     // Route to appropriate handler per SPEC-16 method registry
-    let result: Result<serde_json::Value, crate::domain::error::ApiError> =
-        route_method(state, method, params).await;
+    let result: Result<serde_json::Value, crate::domain::error::ApiError> = match authorized {
+        Ok(()) => route_method(state, method, params).await,
+        Err(e) => Err(e),
+    };
 
     match result {
         Ok(value) => {
-            state.metrics.record_request(true, false, 0);
+            state.metrics.record_method(method, true, timer.elapsed());
             serde_json::json!({
                 "jsonrpc": "2.0",
                 "id": id,
@@ -372,7 +751,7 @@ async fn process_single_request(
             })
         }
         Err(e) => {
-            state.metrics.record_request(false, false, 0);
+            state.metrics.record_method(method, false, timer.elapsed());
             serde_json::json!({
                 "jsonrpc": "2.0",
                 "id": id,
@@ -453,6 +832,74 @@ async fn health_check() -> impl IntoResponse {
     }))
 }
 
+/// Admin key creation request
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateKeyRequest {
+    #[serde(default)]
+    label: String,
+    scopes: Vec<String>,
+    #[serde(default)]
+    rate_limit: Option<u32>,
+}
+
+/// List API keys (digests only, never plaintext)
+async fn list_api_keys(State(keys): State<Arc<ApiKeyStore>>) -> impl IntoResponse {
+    Json(keys.list())
+}
+
+/// Create an API key; the plaintext key is only returned here
+async fn create_api_key(
+    State(keys): State<Arc<ApiKeyStore>>,
+    Json(request): Json<CreateKeyRequest>,
+) -> impl IntoResponse {
+    match keys.create(request.label, request.scopes, request.rate_limit) {
+        Ok((record, key)) => (
+            StatusCode::CREATED,
+            Json(serde_json::json!({ "key": key, "record": record })),
+        ),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        ),
+    }
+}
+
+/// Revoke an API key
+async fn revoke_api_key(
+    State(keys): State<Arc<ApiKeyStore>>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    match keys.revoke(&id) {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "revoked": true, "id": id })),
+        ),
+        Err(e @ crate::adapters::ApiKeyError::NotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        ),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        ),
+    }
+}
+
+/// Usage counters for one API key
+async fn api_key_usage(
+    State(keys): State<Arc<ApiKeyStore>>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    match keys.usage(&id) {
+        Some(usage) => (StatusCode::OK, Json(serde_json::json!(usage))),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": format!("api key not found: {}", id) })),
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -462,4 +909,91 @@ mod tests {
         let config = GatewayConfig::default();
         assert!(config.validate().is_ok());
     }
+
+    #[tokio::test]
+    async fn test_jwt_only_honoured_on_admin_port() {
+        use crate::ipc::handler::channel::ChannelSender;
+        use crate::middleware::JwtClaims;
+
+        let data_dir = tempfile::tempdir().unwrap();
+        let secret_file = data_dir.path().join("jwt.hex");
+        std::fs::write(&secret_file, "ab".repeat(32)).unwrap();
+        let mut config = GatewayConfig::default();
+        config.admin.jwt_secret_file = Some(secret_file);
+        let (tx, _rx) = tokio::sync::mpsc::channel(16);
+        let service = ApiGatewayService::new(
+            config,
+            Arc::new(ChannelSender(tx)),
+            data_dir.path().to_path_buf(),
+        )
+        .unwrap();
+
+        let token = service.jwt.as_ref().unwrap().sign(&JwtClaims {
+            iat: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            ..Default::default()
+        });
+        let request = Request::builder()
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .body(())
+            .unwrap();
+
+        let public = service.app_state();
+        assert!(!public.auth.key_required());
+        assert!(CallerContext::from_request(&request, &public.auth)
+            .jwt
+            .is_none());
+
+        let admin = service.admin_app_state();
+        assert!(CallerContext::from_request(&request, &admin.auth)
+            .jwt
+            .is_some());
+    }
+
+    #[cfg(feature = "graphql")]
+    #[tokio::test]
+    async fn test_graphql_router_serves_queries() {
+        use crate::ipc::handler::channel::ChannelSender;
+        use crate::ipc::requests::RequestPayload;
+        use tower::ServiceExt;
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+        let data_dir = tempfile::tempdir().unwrap();
+        let service = ApiGatewayService::new(
+            GatewayConfig::default(),
+            Arc::new(ChannelSender(tx)),
+            data_dir.path().to_path_buf(),
+        )
+        .unwrap();
+        let pending = service.pending_store();
+        tokio::spawn(async move {
+            while let Some(request) = rx.recv().await {
+                if let RequestPayload::GetBlockByNumber(_) = request.payload {
+                    let block = serde_json::json!({ "number": "0x7" });
+                    pending.complete(request.correlation_id, Ok(block));
+                }
+            }
+        });
+
+        let request = Request::post("/graphql")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(
+                serde_json::json!({ "query": "{ block(number: 7) { number } }" }).to_string(),
+            ))
+            .unwrap();
+        let response = service
+            .build_graphql_router()
+            .oneshot(request)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["block"]["number"], "0x7");
+    }
 }
@@ -3,17 +3,24 @@
 //! Security features:
 //! - Message size limits (default 1MB)
 //! - Connection-level subscription limits
+//! - Bounded notification queues (slow consumers drop, then get evicted)
 //! - Rate limiting per connection
+//! - Keepalive pings; connections silent past the idle timeout are closed
+//! - Optional max connection lifetime, so long-lived clients rebalance
+//!
+//! Besides `eth_subscribe`/`eth_unsubscribe`, clients can call
+//! `qc_resumeToken` to obtain a resumption token and, after reconnecting,
+//! `qc_resumeSubscriptions` with that token to get their subscriptions back,
+//! followed by the notifications they missed.
 
 use crate::domain::correlation::CorrelationId;
 use crate::domain::types::Filter;
-use crate::ws::subscriptions::{SubscriptionManager, SubscriptionNotification};
+use crate::ws::subscriptions::SubscriptionManager;
 use crate::SubscriptionType;
-use axum::extract::ws::{Message, WebSocket};
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket};
 use futures::StreamExt;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
 /// Default maximum message size (1MB)
@@ -22,6 +29,9 @@ pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 1024 * 1024;
 /// Default rate limit (100 messages per second)
 pub const DEFAULT_RATE_LIMIT: u32 = 100;
 
+/// Default per-connection notification queue depth
+pub const DEFAULT_NOTIFICATION_BUFFER: usize = 256;
+
 /// WebSocket configuration
 #[derive(Debug, Clone)]
 pub struct WebSocketConfig {
@@ -31,8 +41,12 @@ pub struct WebSocketConfig {
     pub rate_limit: u32,
     /// Ping interval
     pub ping_interval: Duration,
-    /// Idle timeout (disconnect if no activity)
+    /// Idle timeout (disconnect if no frame, pongs included, arrives)
     pub idle_timeout: Duration,
+    /// Queued notifications per connection before drops begin
+    pub notification_buffer: usize,
+    /// Close connections after this long, regardless of activity
+    pub max_lifetime: Option<Duration>,
 }
 
 impl Default for WebSocketConfig {
@@ -41,7 +55,9 @@ impl Default for WebSocketConfig {
             max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
             rate_limit: DEFAULT_RATE_LIMIT,
             ping_interval: Duration::from_secs(30),
-            idle_timeout: Duration::from_secs(300),
+            idle_timeout: Duration::from_secs(90),
+            notification_buffer: DEFAULT_NOTIFICATION_BUFFER,
+            max_lifetime: None,
         }
     }
 }
@@ -119,118 +135,153 @@ impl WebSocketHandler {
             "New WebSocket connection"
         );
 
-        // Channel for sending notifications to client
-        let (_notif_tx, _notif_rx) = mpsc::channel::<SubscriptionNotification>(256);
-
-        // Spawn notification sender task
-        let _send_handle = {
-            let _conn_id = self.connection_id;
-            tokio::spawn(async move {
-                // This would be split from socket - simplified for now
-            })
-        };
+        // Notifications for this connection's subscriptions
+        let mut notif_rx = self
+            .subscription_manager
+            .register_connection(self.connection_id, self.config.notification_buffer);
 
         let mut last_activity = Instant::now();
 
-        // Handle incoming messages
-        while let Some(result) = socket.next().await {
-            // Check idle timeout
-            if last_activity.elapsed() > self.config.idle_timeout {
-                info!(
-                    connection_id = %self.connection_id,
-                    "Closing idle WebSocket connection"
-                );
-                break;
+        // First ping one interval in; missed ticks are skipped, not bunched
+        let ping_interval = self.config.ping_interval.max(Duration::from_millis(100));
+        let mut ping =
+            tokio::time::interval_at(tokio::time::Instant::now() + ping_interval, ping_interval);
+        ping.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        let max_lifetime = self.config.max_lifetime;
+        let lifetime = async move {
+            match max_lifetime {
+                Some(lifetime) => tokio::time::sleep(lifetime).await,
+                None => std::future::pending().await,
             }
-
-            last_activity = Instant::now();
-
-            match result {
-                Ok(Message::Text(text)) => {
-                    // Check message size
-                    if let Some(error_response) = self.check_message_size(text.len()) {
-                        if let Err(e) = socket.send(Message::Text(error_response)).await {
-                            error!(error = %e, "Failed to send error response");
-                            break;
-                        }
-                        continue;
-                    }
-
-                    // Check rate limit
-                    if !self.check_rate_limit() {
-                        let error = json_rpc_error(None, -32005, "Rate limit exceeded");
-                        if let Err(e) = socket.send(Message::Text(error)).await {
-                            error!(error = %e, "Failed to send rate limit error");
-                            break;
-                        }
-                        continue;
-                    }
-
-                    let response = self.handle_message(&text).await;
-                    if let Err(e) = socket.send(Message::Text(response)).await {
-                        error!(error = %e, "Failed to send WebSocket response");
-                        break;
+        };
+        tokio::pin!(lifetime);
+
+        let close = loop {
+            tokio::select! {
+                incoming = socket.next() => {
+                    let Some(result) = incoming else {
+                        break None;
+                    };
+                    last_activity = Instant::now();
+
+                    if !self.handle_incoming(&mut socket, result).await {
+                        break None;
                     }
                 }
-                Ok(Message::Binary(data)) => {
-                    // Check message size
-                    if let Some(error_response) = self.check_message_size(data.len()) {
-                        if let Err(e) = socket.send(Message::Text(error_response)).await {
-                            error!(error = %e, "Failed to send error response");
-                            break;
-                        }
-                        continue;
-                    }
-
-                    // Check rate limit
-                    if !self.check_rate_limit() {
-                        let error = json_rpc_error(None, -32005, "Rate limit exceeded");
-                        if let Err(e) = socket.send(Message::Text(error)).await {
-                            error!(error = %e, "Failed to send rate limit error");
-                            break;
-                        }
+                notification = notif_rx.recv() => {
+                    // The manager closes the queue when it evicts a slow consumer
+                    let Some(notification) = notification else {
+                        warn!(
+                            connection_id = %self.connection_id,
+                            "Closing WebSocket connection evicted as a slow consumer"
+                        );
+                        break Some(close_frame(close_code::POLICY, "slow consumer"));
+                    };
+                    let Ok(text) = serde_json::to_string(&notification) else {
                         continue;
-                    }
-
-                    // Try to parse as JSON
-                    if let Ok(text) = String::from_utf8(data) {
-                        let response = self.handle_message(&text).await;
-                        if let Err(e) = socket.send(Message::Text(response)).await {
-                            error!(error = %e, "Failed to send WebSocket response");
-                            break;
-                        }
+                    };
+                    if let Err(e) = socket.send(Message::Text(text)).await {
+                        error!(error = %e, "Failed to send subscription notification");
+                        break None;
                     }
                 }
-                Ok(Message::Ping(data)) => {
-                    if let Err(e) = socket.send(Message::Pong(data)).await {
-                        error!(error = %e, "Failed to send pong");
-                        break;
+                _ = ping.tick() => {
+                    if last_activity.elapsed() > self.config.idle_timeout {
+                        info!(
+                            connection_id = %self.connection_id,
+                            "Closing idle WebSocket connection"
+                        );
+                        break Some(close_frame(close_code::AWAY, "idle timeout"));
+                    }
+                    if let Err(e) = socket.send(Message::Ping(Vec::new())).await {
+                        debug!(error = %e, "Failed to send keepalive ping");
+                        break None;
                     }
                 }
-                Ok(Message::Pong(_)) => {
-                    // Ignore pongs
-                }
-                Ok(Message::Close(_)) => {
-                    debug!(connection_id = %self.connection_id, "WebSocket close received");
-                    break;
-                }
-                Err(e) => {
-                    warn!(error = %e, "WebSocket error");
-                    break;
+                _ = &mut lifetime => {
+                    info!(
+                        connection_id = %self.connection_id,
+                        "Closing WebSocket connection at max lifetime"
+                    );
+                    break Some(close_frame(close_code::AWAY, "max connection lifetime reached"));
                 }
             }
+        };
+
+        if let Some(frame) = close {
+            let _ = socket.send(Message::Close(Some(frame))).await;
         }
 
-        // Cleanup subscriptions on disconnect
+        // Read the drop count before cleanup forgets the connection
+        let dropped_notifications = self
+            .subscription_manager
+            .connection_dropped_notifications(&self.connection_id);
         self.subscription_manager
             .remove_connection(&self.connection_id);
 
         info!(
             connection_id = %self.connection_id,
+            dropped_notifications = dropped_notifications,
             "WebSocket connection closed"
         );
     }
 
+    /// Handle one incoming frame. Returns false when the connection should close.
+    async fn handle_incoming(
+        &mut self,
+        socket: &mut WebSocket,
+        result: Result<Message, axum::Error>,
+    ) -> bool {
+        let text = match result {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Binary(data)) => {
+                // Check message size before decoding
+                if let Some(error_response) = self.check_message_size(data.len()) {
+                    return send_text(socket, error_response).await;
+                }
+                // Try to parse as JSON
+                match String::from_utf8(data) {
+                    Ok(text) => text,
+                    Err(_) => return true,
+                }
+            }
+            Ok(Message::Ping(data)) => {
+                if let Err(e) = socket.send(Message::Pong(data)).await {
+                    error!(error = %e, "Failed to send pong");
+                    return false;
+                }
+                return true;
+            }
+            Ok(Message::Pong(_)) => {
+                // Keepalive answer; receiving it already refreshed activity
+                return true;
+            }
+            Ok(Message::Close(_)) => {
+                debug!(connection_id = %self.connection_id, "WebSocket close received");
+                return false;
+            }
+            Err(e) => {
+                warn!(error = %e, "WebSocket error");
+                return false;
+            }
+        };
+
+        // Check message size
+        if let Some(error_response) = self.check_message_size(text.len()) {
+            return send_text(socket, error_response).await;
+        }
+
+        // Check rate limit
+        if !self.check_rate_limit() {
+            let error = json_rpc_error(None, -32005, "Rate limit exceeded");
+            return send_text(socket, error).await;
+        }
+
+        let response = self.handle_message(&text).await;
+        send_text(socket, response).await
+    }
+
     /// Handle a single JSON-RPC message
     async fn handle_message(&self, text: &str) -> String {
         // Parse JSON-RPC request
@@ -248,6 +299,8 @@ impl WebSocketHandler {
         match method {
             "eth_subscribe" => self.handle_subscribe(id, params).await,
             "eth_unsubscribe" => self.handle_unsubscribe(id, params).await,
+            "qc_resumeToken" => self.handle_resume_token(id),
+            "qc_resumeSubscriptions" => self.handle_resume(id, params),
             _ => {
                 // For other methods, they should go through HTTP
                 // But we can handle some simple ones
@@ -352,6 +405,50 @@ impl WebSocketHandler {
         let result = self.subscription_manager.unsubscribe(sub_id);
         json_rpc_result(id, serde_json::json!(result))
     }
+
+    /// Handle qc_resumeToken
+    fn handle_resume_token(&self, id: Option<serde_json::Value>) -> String {
+        match self.subscription_manager.resume_token(self.connection_id) {
+            Some(token) => json_rpc_result(id, serde_json::json!(token)),
+            None => json_rpc_error(id, -32601, "Subscription resumption is disabled"),
+        }
+    }
+
+    /// Handle qc_resumeSubscriptions
+    fn handle_resume(
+        &self,
+        id: Option<serde_json::Value>,
+        params: Option<&serde_json::Value>,
+    ) -> String {
+        let token = match params.and_then(|p| p.get(0)).and_then(|t| t.as_str()) {
+            Some(token) => token,
+            None => {
+                return json_rpc_error(id, -32602, "Invalid params: expected [resumeToken]");
+            }
+        };
+
+        match self.subscription_manager.resume(self.connection_id, token) {
+            Ok(resumed) => json_rpc_result(id, serde_json::json!(resumed)),
+            Err(e) => json_rpc_error(id, -32000, &e.to_string()),
+        }
+    }
+}
+
+/// Close frame with a status code and reason
+fn close_frame(code: u16, reason: &'static str) -> CloseFrame<'static> {
+    CloseFrame {
+        code,
+        reason: reason.into(),
+    }
+}
+
+/// Send a text frame. Returns false if the socket is gone.
+async fn send_text(socket: &mut WebSocket, text: String) -> bool {
+    if let Err(e) = socket.send(Message::Text(text)).await {
+        error!(error = %e, "Failed to send WebSocket response");
+        return false;
+    }
+    true
 }
 
 /// Create JSON-RPC success response
@@ -4,11 +4,18 @@
 //! - eth_subscribe / eth_unsubscribe
 //! - Subscription types: newHeads, logs, newPendingTransactions, syncing
 //! - Message size limits and rate limiting
+//!
+//! and, on `/logs`, a live tail of the node's own log output.
 
 pub mod handler;
+pub mod node_logs;
 pub mod subscriptions;
 
 pub use handler::{
-    WebSocketConfig, WebSocketHandler, DEFAULT_MAX_MESSAGE_SIZE, DEFAULT_RATE_LIMIT,
+    WebSocketConfig, WebSocketHandler, DEFAULT_MAX_MESSAGE_SIZE, DEFAULT_NOTIFICATION_BUFFER,
+    DEFAULT_RATE_LIMIT,
+};
+pub use node_logs::{LogFeed, LogFilter, LogLevel, LogStreamQuery, NodeLogLine};
+pub use subscriptions::{
+    ResumedSubscriptions, SubscribeError, SubscriptionManager, SubscriptionNotification,
 };
-pub use subscriptions::{SubscribeError, SubscriptionManager, SubscriptionNotification};
@@ -0,0 +1,369 @@
+//! Node log streaming on the WebSocket port (`GET /logs`).
+//!
+//! The node feeds its log lines into a `LogFeed`. A connection first gets
+//! the most recent matching lines (`backlog`), then, in follow mode, every
+//! new matching line as it is logged; with `follow=false` the server closes
+//! after the backlog, like `tail` without `-f`.
+//!
+//! The upgrade request is authorized as the Admin-tier method `admin_logs`:
+//! localhost (unless `allow_external`) plus the admin API key if one is
+//! configured, which WebSocket clients can pass as `?api_key=`. Scoped keys
+//! and JWTs need `admin_logs` in their scopes.
+//!
+//! Query parameters, all optional:
+//! - `level`: lowest level to send (`trace`, `debug`, `info`, `warn`, `error`)
+//! - `search`: case-insensitive text that the target or message must contain
+//! - `backlog`: recent lines to send first (default 100)
+//! - `follow`: keep streaming new lines (default true)
+//!
+//! Each line is sent as `{"jsonrpc":"2.0","method":"qc_log","params":{..}}`.
+//! A client that falls behind gets a `qc_logsDropped` notice with the count
+//! it missed. The filter can be changed mid-stream with
+//! `{"jsonrpc":"2.0","id":1,"method":"qc_setLogFilter","params":[{"level":"debug","search":"peer"}]}`.
+
+use axum::extract::ws::{close_code, CloseFrame, Message, WebSocket};
+use futures::StreamExt;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::debug;
+
+/// Method tier the `/logs` upgrade is authorized as
+pub const LOGS_METHOD: &str = "admin_logs";
+
+/// Lines sent before following when the client does not ask
+pub const DEFAULT_LOG_BACKLOG: usize = 100;
+
+/// Lines buffered per connection before it starts missing some
+const FOLLOW_BUFFER: usize = 1024;
+
+/// Log level, ordered from most to least verbose
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "trace" => Ok(LogLevel::Trace),
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "error" => Ok(LogLevel::Error),
+            _ => Err(format!("unknown log level: {}", s)),
+        }
+    }
+}
+
+/// One line of node log output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeLogLine {
+    /// Unix time, in milliseconds
+    pub timestamp_ms: u64,
+    /// Level
+    pub level: LogLevel,
+    /// Module path or explicit target
+    pub target: String,
+    /// Message and fields
+    pub message: String,
+}
+
+/// Which lines a connection wants
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct LogFilter {
+    /// Lowest level to send (None = all)
+    pub level: Option<LogLevel>,
+    /// Text the target or message must contain, ignoring case
+    pub search: Option<String>,
+}
+
+impl LogFilter {
+    /// Check a line against the filter
+    pub fn matches(&self, line: &NodeLogLine) -> bool {
+        if self.level.is_some_and(|level| line.level < level) {
+            return false;
+        }
+        match self.search.as_deref().filter(|s| !s.is_empty()) {
+            Some(search) => {
+                let search = search.to_lowercase();
+                line.message.to_lowercase().contains(&search)
+                    || line.target.to_lowercase().contains(&search)
+            }
+            None => true,
+        }
+    }
+}
+
+/// Query string of a `/logs` upgrade
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LogStreamQuery {
+    /// Lowest level to send
+    pub level: Option<LogLevel>,
+    /// Text to search for
+    pub search: Option<String>,
+    /// Recent lines to send first
+    pub backlog: Option<usize>,
+    /// Keep streaming after the backlog
+    pub follow: Option<bool>,
+}
+
+impl LogStreamQuery {
+    fn filter(&self) -> LogFilter {
+        LogFilter {
+            level: self.level,
+            search: self.search.clone(),
+        }
+    }
+}
+
+/// Recent node log lines and a channel of new ones
+pub struct LogFeed {
+    capacity: usize,
+    history: RwLock<VecDeque<NodeLogLine>>,
+    tx: broadcast::Sender<NodeLogLine>,
+}
+
+impl LogFeed {
+    /// Feed keeping at most `capacity` lines for backlogs (0 keeps none)
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(FOLLOW_BUFFER);
+        Self {
+            capacity,
+            history: RwLock::new(VecDeque::with_capacity(capacity)),
+            tx,
+        }
+    }
+
+    /// Add a line and send it to following connections
+    pub fn publish(&self, line: NodeLogLine) {
+        if self.capacity > 0 {
+            let mut history = self.history.write();
+            if history.len() == self.capacity {
+                history.pop_front();
+            }
+            history.push_back(line.clone());
+        }
+        if self.tx.receiver_count() > 0 {
+            let _ = self.tx.send(line);
+        }
+    }
+
+    /// The `limit` most recent lines matching `filter`, oldest first
+    pub fn recent(&self, filter: &LogFilter, limit: usize) -> Vec<NodeLogLine> {
+        let history = self.history.read();
+        let mut lines: Vec<NodeLogLine> = history
+            .iter()
+            .rev()
+            .filter(|line| filter.matches(line))
+            .take(limit)
+            .cloned()
+            .collect();
+        lines.reverse();
+        lines
+    }
+
+    /// Receive every line published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<NodeLogLine> {
+        self.tx.subscribe()
+    }
+
+    /// Number of connections following the feed
+    pub fn followers(&self) -> usize {
+        self.tx.receiver_count()
+    }
+}
+
+/// Serve one `/logs` connection until the client leaves
+pub async fn stream_logs(mut socket: WebSocket, feed: Arc<LogFeed>, query: LogStreamQuery) {
+    let mut filter = query.filter();
+    let follow = query.follow.unwrap_or(true);
+    // Subscribe before reading the backlog so no line falls between the two
+    let mut lines = feed.subscribe();
+
+    for line in feed.recent(&filter, query.backlog.unwrap_or(DEFAULT_LOG_BACKLOG)) {
+        if !send_json(&mut socket, &log_notification(&line)).await {
+            return;
+        }
+    }
+    if !follow {
+        let _ = socket
+            .send(Message::Close(Some(CloseFrame {
+                code: close_code::NORMAL,
+                reason: "end of backlog".into(),
+            })))
+            .await;
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            received = lines.recv() => {
+                let message = match received {
+                    Ok(line) if filter.matches(&line) => log_notification(&line),
+                    Ok(_) => continue,
+                    Err(broadcast::error::RecvError::Lagged(dropped)) => serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "method": "qc_logsDropped",
+                        "params": { "dropped": dropped }
+                    }),
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                if !send_json(&mut socket, &message).await {
+                    break;
+                }
+            }
+            incoming = socket.next() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    let reply = set_filter(&text, &mut filter);
+                    if !send_json(&mut socket, &reply).await {
+                        break;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    debug!("Log stream closed");
+}
+
+/// Handle a `qc_setLogFilter` request, returning the JSON-RPC reply
+fn set_filter(text: &str, filter: &mut LogFilter) -> serde_json::Value {
+    let request: serde_json::Value = match serde_json::from_str(text) {
+        Ok(v) => v,
+        Err(e) => return rpc_error(None, -32700, &format!("Parse error: {}", e)),
+    };
+    let id = request.get("id").cloned();
+    let method = request.get("method").and_then(|m| m.as_str()).unwrap_or("");
+    if method != "qc_setLogFilter" {
+        return rpc_error(id, -32601, &format!("Method not found: {}", method));
+    }
+    let params = request.get("params").and_then(|p| p.get(0)).cloned();
+    match params.map(serde_json::from_value::<LogFilter>) {
+        Some(Ok(next)) => {
+            *filter = next;
+            serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": true })
+        }
+        Some(Err(e)) => rpc_error(id, -32602, &format!("Invalid filter: {}", e)),
+        None => rpc_error(id, -32602, "Invalid params: expected [filter]"),
+    }
+}
+
+fn log_notification(line: &NodeLogLine) -> serde_json::Value {
+    serde_json::json!({ "jsonrpc": "2.0", "method": "qc_log", "params": line })
+}
+
+fn rpc_error(id: Option<serde_json::Value>, code: i32, message: &str) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message }
+    })
+}
+
+/// Send a JSON text frame. Returns false if the socket is gone.
+async fn send_json(socket: &mut WebSocket, value: &serde_json::Value) -> bool {
+    socket.send(Message::Text(value.to_string())).await.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(level: LogLevel, target: &str, message: &str) -> NodeLogLine {
+        NodeLogLine {
+            timestamp_ms: 1,
+            level,
+            target: target.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_filter_by_level_and_search() {
+        let filter = LogFilter {
+            level: Some(LogLevel::Info),
+            search: Some("PEER".to_string()),
+        };
+        assert!(filter.matches(&line(LogLevel::Warn, "qc_01", "peer banned")));
+        assert!(filter.matches(&line(LogLevel::Info, "qc_01_peer_discovery", "started")));
+        assert!(!filter.matches(&line(LogLevel::Debug, "qc_01", "peer found")));
+        assert!(!filter.matches(&line(LogLevel::Error, "qc_08", "view change")));
+        assert!(LogFilter::default().matches(&line(LogLevel::Trace, "x", "y")));
+        assert_eq!("WARNING".parse::<LogLevel>(), Ok(LogLevel::Warn));
+    }
+
+    #[test]
+    fn test_feed_backlog_and_follow() {
+        let feed = LogFeed::new(3);
+        let mut rx = feed.subscribe();
+        for (i, level) in [
+            LogLevel::Info,
+            LogLevel::Debug,
+            LogLevel::Warn,
+            LogLevel::Info,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            feed.publish(line(level, "qc_16", &format!("line {}", i)));
+        }
+
+        let all: Vec<String> = feed
+            .recent(&LogFilter::default(), 10)
+            .into_iter()
+            .map(|l| l.message)
+            .collect();
+        assert_eq!(all, vec!["line 1", "line 2", "line 3"]);
+
+        // The limit applies to matching lines, newest kept
+        let info = LogFilter {
+            level: Some(LogLevel::Info),
+            search: None,
+        };
+        let newest: Vec<String> = feed
+            .recent(&info, 1)
+            .into_iter()
+            .map(|l| l.message)
+            .collect();
+        assert_eq!(newest, vec!["line 3"]);
+
+        assert_eq!(rx.try_recv().unwrap().message, "line 0");
+    }
+
+    #[test]
+    fn test_set_filter_request() {
+        let mut filter = LogFilter::default();
+        let reply = set_filter(
+            r#"{"jsonrpc":"2.0","id":1,"method":"qc_setLogFilter","params":[{"level":"debug","search":"peer"}]}"#,
+            &mut filter,
+        );
+        assert_eq!(reply["result"], true);
+        assert_eq!(filter.level, Some(LogLevel::Debug));
+        assert_eq!(filter.search.as_deref(), Some("peer"));
+
+        let reply = set_filter(
+            r#"{"jsonrpc":"2.0","id":2,"method":"qc_setLogFilter","params":[{"level":"loud"}]}"#,
+            &mut filter,
+        );
+        assert_eq!(reply["error"]["code"], -32602);
+        assert_eq!(filter.level, Some(LogLevel::Debug));
+
+        let reply = set_filter(r#"{"id":3,"method":"eth_chainId"}"#, &mut filter);
+        assert_eq!(reply["error"]["code"], -32601);
+    }
+}
@@ -1,13 +1,29 @@
 //! WebSocket subscription manager per SPEC-16 Section 5.
-
+//!
+//! Connections that fall too far behind are evicted: once a connection has
+//! dropped `slow_consumer_limit` notifications its queue is closed, which
+//! the WebSocket task sees as the end of its notification stream.
+//!
+//! A client may ask for a resumption token. When a connection holding a
+//! token goes away, its subscriptions are parked under the token for
+//! `resume_window` instead of being deleted; a new connection presenting
+//! the token gets the same subscription IDs back. Notifications raised
+//! while the subscriptions were parked are buffered, up to
+//! `resume_replay_limit`, and replayed on resume; older ones are dropped and
+//! counted so the client knows its cached view has a gap.
+
+use crate::adapters::filters::FilterStore;
 use crate::domain::correlation::CorrelationId;
-use crate::domain::types::{Filter, Hash};
+use crate::domain::types::{Filter, Hash, Log};
 use crate::SubscriptionType;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicU64, Ordering};
-use tokio::sync::broadcast;
-use tracing::debug;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, info, warn};
 
 /// Subscription ID (hex string)
 pub type SubscriptionId = String;
@@ -53,6 +69,40 @@ impl SubscriptionNotification {
     }
 }
 
+/// Outbound notification queue for one connection
+struct ConnectionSink {
+    /// Bounded queue drained by the connection's WebSocket task
+    tx: mpsc::Sender<SubscriptionNotification>,
+    /// Notifications dropped because the queue was full
+    dropped: AtomicU64,
+}
+
+/// Subscriptions of a closed connection, kept for resumption
+struct ParkedSubscriptions {
+    subscriptions: Vec<Subscription>,
+    parked_at: Instant,
+    expires_at: Instant,
+    /// Notifications raised while parked, oldest first
+    missed: VecDeque<SubscriptionNotification>,
+    /// Notifications dropped because `missed` was full
+    overflowed: u64,
+}
+
+/// Outcome of `SubscriptionManager::resume`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumedSubscriptions {
+    /// Subscription IDs now attached to the new connection
+    pub subscriptions: Vec<SubscriptionId>,
+    /// Buffered notifications queued for delivery after the response
+    pub replayed: usize,
+    /// Notifications lost while disconnected; non-zero means the client
+    /// should refetch rather than trust its cached data
+    pub missed: u64,
+    /// How long the subscriptions were parked
+    pub offline_ms: u64,
+}
+
 /// Subscription manager
 pub struct SubscriptionManager {
     /// All active subscriptions by ID
@@ -65,12 +115,35 @@ pub struct SubscriptionManager {
     new_heads_tx: broadcast::Sender<serde_json::Value>,
     /// Broadcast channel for pending transactions
     pending_tx_tx: broadcast::Sender<Hash>,
+    /// Notification queues by connection ID
+    connections: DashMap<CorrelationId, ConnectionSink>,
+    /// Notifications dropped across all connections (slow consumers)
+    dropped_notifications: AtomicU64,
+    /// Polling filters fed from the same events
+    filters: Arc<FilterStore>,
     /// Max subscriptions per connection
     max_per_connection: u32,
+    /// Dropped notifications after which a connection is evicted (0 = never)
+    slow_consumer_limit: u64,
+    /// Connections evicted for falling behind
+    evicted_connections: AtomicU64,
+    /// How long a closed connection's subscriptions stay resumable (zero = off)
+    resume_window: Duration,
+    /// Resumption token issued to each live connection
+    resume_tokens: DashMap<CorrelationId, String>,
+    /// Subscriptions of closed connections, by resumption token
+    parked: DashMap<String, ParkedSubscriptions>,
+    /// Notifications buffered per parked connection for replay
+    resume_replay_limit: usize,
 }
 
 impl SubscriptionManager {
     pub fn new(max_per_connection: u32) -> Self {
+        Self::with_filters(max_per_connection, Arc::new(FilterStore::default()))
+    }
+
+    /// Create a manager that also feeds the given polling filter store
+    pub fn with_filters(max_per_connection: u32, filters: Arc<FilterStore>) -> Self {
         let (new_heads_tx, _) = broadcast::channel(1024);
         let (pending_tx_tx, _) = broadcast::channel(4096);
 
@@ -80,10 +153,58 @@ impl SubscriptionManager {
             id_counter: AtomicU64::new(1),
             new_heads_tx,
             pending_tx_tx,
+            connections: DashMap::new(),
+            dropped_notifications: AtomicU64::new(0),
+            filters,
             max_per_connection,
+            slow_consumer_limit: 0,
+            evicted_connections: AtomicU64::new(0),
+            resume_window: Duration::ZERO,
+            resume_tokens: DashMap::new(),
+            parked: DashMap::new(),
+            resume_replay_limit: 0,
         }
     }
 
+    /// Evict connections once they have dropped `limit` notifications (0 = never)
+    pub fn with_slow_consumer_limit(mut self, limit: u64) -> Self {
+        self.slow_consumer_limit = limit;
+        self
+    }
+
+    /// Keep a closed connection's subscriptions resumable for `window` (zero = off)
+    pub fn with_resume_window(mut self, window: Duration) -> Self {
+        self.resume_window = window;
+        self
+    }
+
+    /// Buffer up to `limit` notifications per parked connection for replay (0 = none)
+    pub fn with_resume_replay_limit(mut self, limit: usize) -> Self {
+        self.resume_replay_limit = limit;
+        self
+    }
+
+    /// Register a connection's notification queue.
+    ///
+    /// Notifications for the connection's subscriptions are pushed into a
+    /// bounded queue of `buffer` entries; when the client falls behind,
+    /// further notifications are dropped and counted rather than queued.
+    pub fn register_connection(
+        &self,
+        connection_id: CorrelationId,
+        buffer: usize,
+    ) -> mpsc::Receiver<SubscriptionNotification> {
+        let (tx, rx) = mpsc::channel(buffer.max(1));
+        self.connections.insert(
+            connection_id,
+            ConnectionSink {
+                tx,
+                dropped: AtomicU64::new(0),
+            },
+        );
+        rx
+    }
+
     /// Subscribe to a topic
     pub fn subscribe(
         &self,
@@ -141,17 +262,127 @@ impl SubscriptionManager {
         }
     }
 
-    /// Remove all subscriptions for a connection
+    /// Remove all subscriptions for a connection.
+    ///
+    /// If the connection was issued a resumption token, its subscriptions
+    /// are parked under the token rather than deleted.
     pub fn remove_connection(&self, connection_id: &CorrelationId) {
-        if let Some((_, sub_ids)) = self.by_connection.remove(connection_id) {
-            for sub_id in sub_ids {
-                self.subscriptions.remove(&sub_id);
+        self.connections.remove(connection_id);
+        self.purge_expired_resumptions();
+        let token = self.resume_tokens.remove(connection_id).map(|(_, t)| t);
+        let Some((_, sub_ids)) = self.by_connection.remove(connection_id) else {
+            return;
+        };
+        let removed: Vec<Subscription> = sub_ids
+            .iter()
+            .filter_map(|id| self.subscriptions.remove(id).map(|(_, sub)| sub))
+            .collect();
+
+        match token {
+            Some(token) if !removed.is_empty() => {
+                debug!(
+                    connection_id = %connection_id,
+                    subscriptions = removed.len(),
+                    "Parked subscriptions for resumption"
+                );
+                let now = Instant::now();
+                self.parked.insert(
+                    token,
+                    ParkedSubscriptions {
+                        subscriptions: removed,
+                        parked_at: now,
+                        expires_at: now + self.resume_window,
+                        missed: VecDeque::new(),
+                        overflowed: 0,
+                    },
+                );
             }
-            debug!(
+            _ => debug!(
                 connection_id = %connection_id,
                 "Removed all subscriptions for connection"
-            );
+            ),
+        }
+    }
+
+    /// Resumption token for a connection, issuing one on first call.
+    ///
+    /// Returns None when resumption is disabled.
+    pub fn resume_token(&self, connection_id: CorrelationId) -> Option<String> {
+        if self.resume_window.is_zero() {
+            return None;
         }
+        let token = self
+            .resume_tokens
+            .entry(connection_id)
+            .or_insert_with(|| hex::encode(rand::random::<[u8; 16]>()));
+        Some(token.clone())
+    }
+
+    /// Move the subscriptions parked under `token` onto `connection_id`.
+    ///
+    /// The subscriptions keep their IDs, and the token stays bound to the
+    /// new connection so it can be used again after the next disconnect.
+    /// Notifications buffered while parked are queued on the connection,
+    /// oldest first.
+    pub fn resume(
+        &self,
+        connection_id: CorrelationId,
+        token: &str,
+    ) -> Result<ResumedSubscriptions, SubscribeError> {
+        self.purge_expired_resumptions();
+        let Some((token, parked)) = self.parked.remove(token) else {
+            return Err(SubscribeError::UnknownResumeToken);
+        };
+
+        let mut conn_subs = self.by_connection.entry(connection_id).or_default();
+        if conn_subs.len() + parked.subscriptions.len() > self.max_per_connection as usize {
+            drop(conn_subs);
+            self.parked.insert(token, parked);
+            return Err(SubscribeError::TooManySubscriptions);
+        }
+
+        let mut resumed = Vec::with_capacity(parked.subscriptions.len());
+        for mut sub in parked.subscriptions {
+            sub.connection_id = connection_id;
+            conn_subs.push(sub.id.clone());
+            resumed.push(sub.id.clone());
+            self.subscriptions.insert(sub.id.clone(), sub);
+        }
+        drop(conn_subs);
+        self.resume_tokens.insert(connection_id, token);
+
+        let mut replayed = 0;
+        let mut missed = parked.overflowed;
+        if let Some(sink) = self.connections.get(&connection_id) {
+            for notification in parked.missed {
+                match sink.tx.try_send(notification) {
+                    Ok(()) => replayed += 1,
+                    Err(_) => missed += 1,
+                }
+            }
+        } else {
+            missed += parked.missed.len() as u64;
+        }
+
+        debug!(
+            connection_id = %connection_id,
+            subscriptions = resumed.len(),
+            replayed,
+            missed,
+            "Resumed parked subscriptions"
+        );
+        Ok(ResumedSubscriptions {
+            subscriptions: resumed,
+            replayed,
+            missed,
+            offline_ms: parked.parked_at.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Drop parked subscriptions whose resumption window has passed
+    fn purge_expired_resumptions(&self) {
+        let now = Instant::now();
+        self.parked.retain(|_, parked| parked.expires_at > now);
     }
 
     /// Get subscription by ID
@@ -184,6 +415,11 @@ impl SubscriptionManager {
 
     /// Broadcast new block header
     pub fn broadcast_new_head(&self, header: serde_json::Value) {
+        if let Some(Ok(hash)) = header.get("hash").cloned().map(serde_json::from_value) {
+            self.filters.push_block(hash);
+        }
+        self.notify(&self.subscriptions_of(SubscriptionType::NewHeads), &header);
+        self.notify_parked(|sub| sub.sub_type == SubscriptionType::NewHeads, &header);
         if self.new_heads_tx.receiver_count() > 0 {
             let _ = self.new_heads_tx.send(header);
         }
@@ -191,11 +427,107 @@ impl SubscriptionManager {
 
     /// Broadcast new pending transaction
     pub fn broadcast_pending_tx(&self, tx_hash: Hash) {
+        self.filters.push_pending_tx(tx_hash);
+        let result = serde_json::json!(tx_hash);
+        self.notify(
+            &self.subscriptions_of(SubscriptionType::NewPendingTransactions),
+            &result,
+        );
+        self.notify_parked(
+            |sub| sub.sub_type == SubscriptionType::NewPendingTransactions,
+            &result,
+        );
         if self.pending_tx_tx.receiver_count() > 0 {
             let _ = self.pending_tx_tx.send(tx_hash);
         }
     }
 
+    /// Broadcast a contract log to matching logs subscriptions
+    pub fn broadcast_log(&self, log: &Log) {
+        self.filters.push_log(log);
+        let subs = self.get_matching_log_subscriptions(&log.address, &log.topics);
+        let parked_match = |sub: &Subscription| matches_log(sub, &log.address, &log.topics);
+        if subs.is_empty() && !self.any_parked(parked_match) {
+            return;
+        }
+        match serde_json::to_value(log) {
+            Ok(result) => {
+                self.notify(&subs, &result);
+                self.notify_parked(parked_match, &result);
+            }
+            Err(e) => warn!(error = %e, "Failed to serialize log notification"),
+        }
+    }
+
+    /// Buffer a notification for matching parked subscriptions, dropping
+    /// the oldest once `resume_replay_limit` are held
+    fn notify_parked(&self, matches: impl Fn(&Subscription) -> bool, result: &serde_json::Value) {
+        for mut parked in self.parked.iter_mut() {
+            let ids: Vec<SubscriptionId> = parked
+                .subscriptions
+                .iter()
+                .filter(|sub| matches(sub))
+                .map(|sub| sub.id.clone())
+                .collect();
+            for id in ids {
+                parked
+                    .missed
+                    .push_back(SubscriptionNotification::new(id, result.clone()));
+                if parked.missed.len() > self.resume_replay_limit {
+                    parked.missed.pop_front();
+                    parked.overflowed += 1;
+                }
+            }
+        }
+    }
+
+    /// True if any parked subscription matches
+    fn any_parked(&self, matches: impl Fn(&Subscription) -> bool) -> bool {
+        self.parked
+            .iter()
+            .any(|parked| parked.subscriptions.iter().any(&matches))
+    }
+
+    /// Queue a notification for each subscription, dropping on backpressure
+    /// and evicting connections that exceed the slow-consumer limit
+    fn notify(&self, subs: &[Subscription], result: &serde_json::Value) {
+        let mut evict = Vec::new();
+        for sub in subs {
+            let Some(sink) = self.connections.get(&sub.connection_id) else {
+                continue;
+            };
+            let notification = SubscriptionNotification::new(sub.id.clone(), result.clone());
+            if let Err(mpsc::error::TrySendError::Full(_)) = sink.tx.try_send(notification) {
+                let dropped = sink.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                self.dropped_notifications.fetch_add(1, Ordering::Relaxed);
+                if self.slow_consumer_limit > 0 && dropped == self.slow_consumer_limit {
+                    evict.push(sub.connection_id);
+                }
+            }
+        }
+
+        for connection_id in evict {
+            // Dropping the sender ends the connection's notification stream
+            if self.connections.remove(&connection_id).is_some() {
+                self.evicted_connections.fetch_add(1, Ordering::Relaxed);
+                info!(
+                    connection_id = %connection_id,
+                    dropped = self.slow_consumer_limit,
+                    "Evicting slow WebSocket consumer"
+                );
+            }
+        }
+    }
+
+    /// Active subscriptions of a given type
+    fn subscriptions_of(&self, sub_type: SubscriptionType) -> Vec<Subscription> {
+        self.subscriptions
+            .iter()
+            .filter(|r| r.sub_type == sub_type)
+            .map(|r| r.clone())
+            .collect()
+    }
+
     /// Get subscriptions matching a log filter
     pub fn get_matching_log_subscriptions(
         &self,
@@ -204,18 +536,7 @@ impl SubscriptionManager {
     ) -> Vec<Subscription> {
         self.subscriptions
             .iter()
-            .filter(|r| {
-                if r.sub_type != SubscriptionType::Logs {
-                    return false;
-                }
-
-                // Check filter match
-                if let Some(filter) = &r.filter {
-                    match_log_filter(filter, log_address, log_topics)
-                } else {
-                    true // No filter = all logs
-                }
-            })
+            .filter(|r| matches_log(r, log_address, log_topics))
             .map(|r| r.clone())
             .collect()
     }
@@ -229,10 +550,46 @@ impl SubscriptionManager {
     pub fn connection_count(&self) -> usize {
         self.by_connection.len()
     }
+
+    /// Total notifications dropped because a client fell behind
+    pub fn dropped_notifications(&self) -> u64 {
+        self.dropped_notifications.load(Ordering::Relaxed)
+    }
+
+    /// Connections evicted for falling behind
+    pub fn evicted_connections(&self) -> u64 {
+        self.evicted_connections.load(Ordering::Relaxed)
+    }
+
+    /// Notifications dropped for a single connection
+    pub fn connection_dropped_notifications(&self, connection_id: &CorrelationId) -> u64 {
+        self.connections
+            .get(connection_id)
+            .map(|sink| sink.dropped.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+}
+
+/// Check if a subscription wants a log
+fn matches_log(
+    sub: &Subscription,
+    log_address: &crate::domain::types::Address,
+    log_topics: &[Hash],
+) -> bool {
+    if sub.sub_type != SubscriptionType::Logs {
+        return false;
+    }
+
+    // Check filter match
+    if let Some(filter) = &sub.filter {
+        match_log_filter(filter, log_address, log_topics)
+    } else {
+        true // No filter = all logs
+    }
 }
 
 /// Check if a log matches a filter
-fn match_log_filter(
+pub(crate) fn match_log_filter(
     filter: &Filter,
     log_address: &crate::domain::types::Address,
     log_topics: &[Hash],
@@ -280,11 +637,14 @@ pub enum SubscribeError {
     InvalidType,
     #[error("invalid filter")]
     InvalidFilter,
+    #[error("unknown or expired resume token")]
+    UnknownResumeToken,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::types::{Address, Bytes, FilterAddress, FilterTopic};
 
     #[test]
     fn test_subscribe_unsubscribe() {
@@ -338,4 +698,215 @@ mod tests {
 
         assert_eq!(manager.total_subscriptions(), 0);
     }
+
+    fn log(address: Address, topics: Vec<Hash>) -> Log {
+        Log {
+            address,
+            topics,
+            data: Bytes(vec![]),
+            block_number: None,
+            block_hash: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            removed: false,
+        }
+    }
+
+    #[test]
+    fn test_log_subscription_filters_address_and_topics() {
+        let manager = SubscriptionManager::new(100);
+        let conn_id = CorrelationId::new();
+        let mut rx = manager.register_connection(conn_id, 16);
+
+        let contract = Address::repeat_byte(0xaa);
+        let transfer = Hash::repeat_byte(0x01);
+        let filter = Filter {
+            address: Some(FilterAddress::Single(contract)),
+            topics: Some(vec![Some(FilterTopic::Single(transfer))]),
+            ..Default::default()
+        };
+        let sub_id = manager
+            .subscribe(conn_id, SubscriptionType::Logs, Some(filter))
+            .unwrap();
+
+        manager.broadcast_log(&log(contract, vec![transfer]));
+        manager.broadcast_log(&log(contract, vec![Hash::repeat_byte(0x02)]));
+        manager.broadcast_log(&log(Address::repeat_byte(0xbb), vec![transfer]));
+
+        let notification = rx.try_recv().unwrap();
+        assert_eq!(notification.params.subscription, sub_id);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_broadcasts_feed_polling_filters() {
+        use crate::adapters::filters::FilterKind;
+
+        let filters = Arc::new(FilterStore::default());
+        let manager = SubscriptionManager::with_filters(100, Arc::clone(&filters));
+        let contract = Address::repeat_byte(0xaa);
+        let blocks = filters.install(FilterKind::Blocks).unwrap();
+        let logs = filters
+            .install(FilterKind::Logs(Box::new(Filter {
+                address: Some(FilterAddress::Single(contract)),
+                ..Default::default()
+            })))
+            .unwrap();
+
+        // Shaped like the node's new-head feed
+        let block_hash = Hash::repeat_byte(0x07);
+        manager.broadcast_new_head(serde_json::json!({
+            "number": "0x7",
+            "hash": block_hash,
+        }));
+        manager.broadcast_log(&log(contract, vec![]));
+        manager.broadcast_log(&log(Address::repeat_byte(0xbb), vec![]));
+
+        let changes = filters.poll_changes(&blocks).unwrap();
+        assert_eq!(changes, vec![serde_json::json!(block_hash)]);
+        let changes = filters.poll_changes(&logs).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0]["address"], serde_json::json!(contract));
+    }
+
+    #[test]
+    fn test_pending_tx_notification() {
+        let manager = SubscriptionManager::new(100);
+        let conn_id = CorrelationId::new();
+        let mut rx = manager.register_connection(conn_id, 16);
+        let _ = manager
+            .subscribe(conn_id, SubscriptionType::NewPendingTransactions, None)
+            .unwrap();
+
+        let tx_hash = Hash::repeat_byte(0x42);
+        manager.broadcast_pending_tx(tx_hash);
+
+        let notification = rx.try_recv().unwrap();
+        assert_eq!(notification.params.result, serde_json::json!(tx_hash));
+    }
+
+    #[test]
+    fn test_slow_consumer_drops_are_counted() {
+        let manager = SubscriptionManager::new(100);
+        let conn_id = CorrelationId::new();
+        let _rx = manager.register_connection(conn_id, 2);
+        let _ = manager
+            .subscribe(conn_id, SubscriptionType::NewPendingTransactions, None)
+            .unwrap();
+
+        for i in 0..5u8 {
+            manager.broadcast_pending_tx(Hash::repeat_byte(i));
+        }
+
+        assert_eq!(manager.connection_dropped_notifications(&conn_id), 3);
+        assert_eq!(manager.dropped_notifications(), 3);
+    }
+
+    #[test]
+    fn test_slow_consumer_evicted_at_limit() {
+        let manager = SubscriptionManager::new(100).with_slow_consumer_limit(3);
+        let conn_id = CorrelationId::new();
+        let mut rx = manager.register_connection(conn_id, 1);
+        let _ = manager
+            .subscribe(conn_id, SubscriptionType::NewPendingTransactions, None)
+            .unwrap();
+
+        for i in 0..4u8 {
+            manager.broadcast_pending_tx(Hash::repeat_byte(i));
+        }
+
+        assert_eq!(manager.evicted_connections(), 1);
+        // The queued notification is still delivered, then the stream ends
+        assert!(rx.try_recv().is_ok());
+        assert!(matches!(
+            rx.try_recv(),
+            Err(mpsc::error::TryRecvError::Disconnected)
+        ));
+    }
+
+    #[test]
+    fn test_resume_restores_subscription_ids() {
+        let manager = SubscriptionManager::new(100).with_resume_window(Duration::from_secs(60));
+        let old_conn = CorrelationId::new();
+        let sub_id = manager
+            .subscribe(old_conn, SubscriptionType::NewHeads, None)
+            .unwrap();
+        let token = manager.resume_token(old_conn).unwrap();
+        assert_eq!(manager.resume_token(old_conn), Some(token.clone()));
+
+        manager.remove_connection(&old_conn);
+        assert_eq!(manager.total_subscriptions(), 0);
+
+        let new_conn = CorrelationId::new();
+        let mut rx = manager.register_connection(new_conn, 16);
+        assert_eq!(
+            manager.resume(new_conn, &token).unwrap().subscriptions,
+            vec![sub_id.clone()]
+        );
+        assert_eq!(manager.get(&sub_id).unwrap().connection_id, new_conn);
+
+        manager.broadcast_new_head(serde_json::json!({"number": "0x1"}));
+        assert_eq!(rx.try_recv().unwrap().params.subscription, sub_id);
+
+        // A token can only be redeemed once per disconnect
+        assert!(matches!(
+            manager.resume(CorrelationId::new(), &token),
+            Err(SubscribeError::UnknownResumeToken)
+        ));
+    }
+
+    #[test]
+    fn test_resume_disabled_or_expired() {
+        let disabled = SubscriptionManager::new(100);
+        assert!(disabled.resume_token(CorrelationId::new()).is_none());
+
+        let manager = SubscriptionManager::new(100).with_resume_window(Duration::from_nanos(1));
+        let conn_id = CorrelationId::new();
+        let _ = manager
+            .subscribe(conn_id, SubscriptionType::NewHeads, None)
+            .unwrap();
+        let token = manager.resume_token(conn_id).unwrap();
+        manager.remove_connection(&conn_id);
+        std::thread::sleep(Duration::from_millis(2));
+
+        assert!(matches!(
+            manager.resume(CorrelationId::new(), &token),
+            Err(SubscribeError::UnknownResumeToken)
+        ));
+    }
+
+    #[test]
+    fn test_resume_replays_missed_notifications() {
+        let manager = SubscriptionManager::new(100)
+            .with_resume_window(Duration::from_secs(60))
+            .with_resume_replay_limit(2);
+        let old_conn = CorrelationId::new();
+        let heads = manager
+            .subscribe(old_conn, SubscriptionType::NewHeads, None)
+            .unwrap();
+        let _ = manager
+            .subscribe(old_conn, SubscriptionType::Logs, Some(Filter::default()))
+            .unwrap();
+        let token = manager.resume_token(old_conn).unwrap();
+        manager.remove_connection(&old_conn);
+
+        for n in 1..=3u8 {
+            manager.broadcast_new_head(serde_json::json!({ "number": format!("0x{n}") }));
+        }
+
+        let new_conn = CorrelationId::new();
+        let mut rx = manager.register_connection(new_conn, 16);
+        let resumed = manager.resume(new_conn, &token).unwrap();
+        assert_eq!(resumed.replayed, 2);
+        assert_eq!(resumed.missed, 1);
+
+        // Oldest dropped, the rest delivered in order
+        for number in ["0x2", "0x3"] {
+            let notification = rx.try_recv().unwrap();
+            assert_eq!(notification.params.subscription, heads);
+            assert_eq!(notification.params.result["number"], number);
+        }
+        assert!(rx.try_recv().is_err());
+    }
 }
@@ -0,0 +1,300 @@
+//! JSON-RPC conformance against reference client fixtures.
+//!
+//! Each file in `tests/fixtures/rpc/` is a list of request/response pairs as
+//! ethers-rs and web3.js exchange them with a reference node (geth unless
+//! the case says otherwise), plus the answers the gateway's backend
+//! subsystems give for the same query. Every case starts a gateway on a
+//! free port, answers its IPC requests from the case's `backend` map, sends
+//! the request over HTTP and compares the response field by field.
+//!
+//! A case fails on schema drift: a missing or unexpected field, a changed
+//! JSON type, or a different value. Paths listed under `ignore` are known
+//! deviations (client strings, error text, oracle choices); those only have
+//! to keep their JSON type, and hex strings have to stay hex.
+//!
+//! Case format:
+//!
+//! ```json
+//! {
+//!   "name": "eth_getBalance",
+//!   "source": "geth 1.13, ethers-rs Provider::get_balance",
+//!   "backend": { "GetBalance": "0xde0b6b3a7640000" },
+//!   "request": { "jsonrpc": "2.0", "id": 1, "method": "eth_getBalance", "params": [...] },
+//!   "response": { "jsonrpc": "2.0", "id": 1, "result": "0xde0b6b3a7640000" },
+//!   "ignore": { "result": "why this value may differ" }
+//! }
+//! ```
+//!
+//! `backend` is keyed by IPC payload type. A string `request` is sent as the
+//! raw body.
+
+use qc_16_api_gateway::adapters::pending::{PendingRequestStore, ResponseError};
+use qc_16_api_gateway::ipc::handler::channel::ChannelSender;
+use qc_16_api_gateway::{ApiGatewayService, GatewayConfig, IpcRequest};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::net::{SocketAddr, TcpListener};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// One recorded exchange
+#[derive(Debug, Deserialize)]
+struct Case {
+    name: String,
+    source: String,
+    #[serde(default)]
+    backend: BTreeMap<String, Value>,
+    request: Value,
+    response: Value,
+    /// Path -> reason the value may differ from the reference
+    #[serde(default)]
+    ignore: BTreeMap<String, String>,
+}
+
+fn load(fixture: &str) -> Vec<Case> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/rpc")
+        .join(format!("{fixture}.json"));
+    let text =
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    serde_json::from_str(&text).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+/// Answer every IPC request from `backend` by payload type
+async fn serve_backend(
+    mut requests: mpsc::Receiver<IpcRequest>,
+    pending: Arc<PendingRequestStore>,
+    backend: BTreeMap<String, Value>,
+) {
+    while let Some(request) = requests.recv().await {
+        let payload = serde_json::to_value(&request.payload).unwrap();
+        let kind = payload["type"].as_str().unwrap_or_default().to_string();
+        let result = backend.get(&kind).cloned().ok_or_else(|| ResponseError {
+            code: -32603,
+            message: format!("fixture has no backend answer for {kind}"),
+            data: None,
+        });
+        pending.complete(request.correlation_id, result);
+    }
+}
+
+/// Start a gateway whose subsystems answer from `backend`; returns its URL
+async fn start_gateway(backend: BTreeMap<String, Value>) -> String {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+
+    let mut config = GatewayConfig::default();
+    config.http.host = [127, 0, 0, 1].into();
+    config.http.port = port;
+    config.websocket.enabled = false;
+    config.admin.enabled = false;
+
+    let (tx, rx) = mpsc::channel(64);
+    let data_dir = std::env::temp_dir();
+    let mut service =
+        ApiGatewayService::new(config, Arc::new(ChannelSender(tx)), data_dir).unwrap();
+    tokio::spawn(serve_backend(rx, service.pending_store(), backend));
+    tokio::spawn(async move { service.start().await });
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    for _ in 0..100 {
+        if tokio::net::TcpStream::connect(addr).await.is_ok() {
+            return format!("http://{addr}/");
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("gateway did not start on {addr}");
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn is_hex(value: &Value) -> bool {
+    value.as_str().is_some_and(|s| {
+        s.strip_prefix("0x")
+            .is_some_and(|digits| digits.chars().all(|c| c.is_ascii_hexdigit()))
+    })
+}
+
+fn child(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// Collect every difference between the reference and actual response
+fn diff(
+    path: &str,
+    expected: &Value,
+    actual: &Value,
+    ignore: &BTreeMap<String, String>,
+    drift: &mut Vec<String>,
+) {
+    if json_type(expected) != json_type(actual) {
+        drift.push(format!(
+            "{path}: expected {}, got {actual}",
+            json_type(expected)
+        ));
+        return;
+    }
+    if ignore.contains_key(path) {
+        if is_hex(expected) && !is_hex(actual) {
+            drift.push(format!("{path}: expected a hex string, got {actual}"));
+        }
+        return;
+    }
+
+    match (expected, actual) {
+        (Value::Object(want), Value::Object(got)) => {
+            for (key, value) in want {
+                match got.get(key) {
+                    Some(other) => diff(&child(path, key), value, other, ignore, drift),
+                    None => drift.push(format!("{}: missing field", child(path, key))),
+                }
+            }
+            for key in got.keys().filter(|key| !want.contains_key(*key)) {
+                drift.push(format!(
+                    "{}: unexpected field {}",
+                    child(path, key),
+                    got[key]
+                ));
+            }
+        }
+        (Value::Array(want), Value::Array(got)) => {
+            if want.len() != got.len() {
+                drift.push(format!(
+                    "{path}: expected {} elements, got {}",
+                    want.len(),
+                    got.len()
+                ));
+                return;
+            }
+            for (i, (value, other)) in want.iter().zip(got).enumerate() {
+                diff(&format!("{path}[{i}]"), value, other, ignore, drift);
+            }
+        }
+        _ if expected != actual => {
+            drift.push(format!("{path}: expected {expected}, got {actual}"));
+        }
+        _ => {}
+    }
+}
+
+/// Replay every case in a fixture file and fail listing all drift
+async fn check_fixture(fixture: &str) {
+    let client = reqwest::Client::new();
+    let mut failures = Vec::new();
+
+    for case in load(fixture) {
+        let url = start_gateway(case.backend).await;
+        let body = match &case.request {
+            Value::String(raw) => raw.clone(),
+            request => request.to_string(),
+        };
+        let actual: Value = client
+            .post(&url)
+            .header("content-type", "application/json")
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+
+        let mut drift = Vec::new();
+        diff("", &case.response, &actual, &case.ignore, &mut drift);
+        if !drift.is_empty() {
+            failures.push(format!(
+                "{} ({}):\n    {}",
+                case.name,
+                case.source,
+                drift.join("\n    ")
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{fixture}: responses drifted from the reference client\n  {}",
+        failures.join("\n  ")
+    );
+}
+
+#[test]
+fn test_diff_reports_drift_by_path() {
+    let expected = serde_json::json!({"result": {"number": "0x10", "hash": "0xaa"}, "id": 1});
+    let actual = serde_json::json!({"result": {"number": 16, "size": "0x1"}, "id": 1});
+    let mut drift = Vec::new();
+    diff("", &expected, &actual, &BTreeMap::new(), &mut drift);
+    assert_eq!(
+        drift,
+        vec![
+            "result.hash: missing field",
+            "result.number: expected string, got 16",
+            r#"result.size: unexpected field "0x1""#,
+        ]
+    );
+
+    let ignore = BTreeMap::from([("result".to_string(), "oracle".to_string())]);
+    let mut drift = Vec::new();
+    diff(
+        "",
+        &serde_json::json!({"result": "0x1"}),
+        &serde_json::json!({"result": "12"}),
+        &ignore,
+        &mut drift,
+    );
+    assert_eq!(drift, vec![r#"result: expected a hex string, got "12""#]);
+}
+
+#[tokio::test]
+async fn test_eth_chain_conformance() {
+    check_fixture("eth_chain").await;
+}
+
+#[tokio::test]
+async fn test_eth_state_conformance() {
+    check_fixture("eth_state").await;
+}
+
+#[tokio::test]
+async fn test_eth_blocks_conformance() {
+    check_fixture("eth_blocks").await;
+}
+
+#[tokio::test]
+async fn test_eth_execution_conformance() {
+    check_fixture("eth_execution").await;
+}
+
+#[tokio::test]
+async fn test_eth_fees_conformance() {
+    check_fixture("eth_fees").await;
+}
+
+#[tokio::test]
+async fn test_net_web3_conformance() {
+    check_fixture("net_web3").await;
+}
+
+#[tokio::test]
+async fn test_envelope_conformance() {
+    check_fixture("envelope").await;
+}
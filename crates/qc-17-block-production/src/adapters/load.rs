@@ -0,0 +1,107 @@
+//! Host load probe adapter (Linux procfs/sysfs)
+//!
+//! `ProcLoadProbe` implements the `SystemLoadProbe` port:
+//!
+//! - CPU load is the 1-minute load average from `/proc/loadavg` divided by
+//!   the core count. It includes the mining threads themselves, so pair a
+//!   load limit with a duty cycle below 100%.
+//! - Temperature is the hottest `/sys/class/thermal/thermal_zone*/temp`
+//!   reading (millidegrees Celsius); hosts without thermal zones report none.
+
+use crate::domain::SystemLoad;
+use crate::error::{BlockProductionError, Result};
+use crate::ports::SystemLoadProbe;
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// Samples load average and thermal zones from procfs/sysfs
+pub struct ProcLoadProbe {
+    loadavg_path: PathBuf,
+    thermal_dir: PathBuf,
+    cores: usize,
+}
+
+impl Default for ProcLoadProbe {
+    fn default() -> Self {
+        Self {
+            loadavg_path: PathBuf::from("/proc/loadavg"),
+            thermal_dir: PathBuf::from("/sys/class/thermal"),
+            cores: num_cpus::get(),
+        }
+    }
+}
+
+impl ProcLoadProbe {
+    fn cpu_load(&self) -> Result<f64> {
+        let loadavg = std::fs::read_to_string(&self.loadavg_path)
+            .map_err(|e| BlockProductionError::LoadProbeFailed(e.to_string()))?;
+        let one_minute: f64 = loadavg
+            .split_whitespace()
+            .next()
+            .and_then(|field| field.parse().ok())
+            .ok_or_else(|| {
+                BlockProductionError::LoadProbeFailed(format!("unreadable loadavg '{}'", loadavg))
+            })?;
+        Ok(one_minute / self.cores.max(1) as f64)
+    }
+
+    fn temperature(&self) -> Option<f64> {
+        std::fs::read_dir(&self.thermal_dir)
+            .ok()?
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("thermal_zone")
+            })
+            .filter_map(|entry| std::fs::read_to_string(entry.path().join("temp")).ok())
+            .filter_map(|millidegrees| millidegrees.trim().parse::<f64>().ok())
+            .map(|millidegrees| millidegrees / 1000.0)
+            .reduce(f64::max)
+    }
+}
+
+#[async_trait]
+impl SystemLoadProbe for ProcLoadProbe {
+    async fn sample(&self) -> Result<SystemLoad> {
+        Ok(SystemLoad {
+            cpu_load: self.cpu_load()?,
+            temperature_celsius: self.temperature(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reads_load_and_hottest_zone() {
+        let dir = std::env::temp_dir().join(format!("qc17-load-{}", std::process::id()));
+        let thermal_dir = dir.join("thermal");
+        for (zone, temp) in [("thermal_zone0", "45000\n"), ("thermal_zone1", "71500\n")] {
+            std::fs::create_dir_all(thermal_dir.join(zone)).unwrap();
+            std::fs::write(thermal_dir.join(zone).join("temp"), temp).unwrap();
+        }
+        std::fs::create_dir_all(thermal_dir.join("cooling_device0")).unwrap();
+        let loadavg_path = dir.join("loadavg");
+        std::fs::write(&loadavg_path, "3.00 2.50 2.00 4/512 1234\n").unwrap();
+
+        let probe = ProcLoadProbe {
+            loadavg_path: loadavg_path.clone(),
+            thermal_dir,
+            cores: 4,
+        };
+        let load = probe.sample().await.unwrap();
+        assert_eq!(load.cpu_load, 0.75);
+        assert_eq!(load.temperature_celsius, Some(71.5));
+
+        std::fs::write(&loadavg_path, "garbage").unwrap();
+        assert!(matches!(
+            probe.sample().await,
+            Err(BlockProductionError::LoadProbeFailed(_))
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,4 +1,7 @@
 pub mod ipc;
+pub mod load;
 pub mod pbft;
 pub mod pos;
 pub mod pow;
+#[cfg(feature = "stratum")]
+pub mod stratum;
@@ -1,4 +1,145 @@
-//! PoS proposer adapter (VRF-based selection)
+//! PoS proposer adapter (validator key signing)
 //!
-//! Reserved for Proof-of-Stake consensus mode.
-//! Will implement VRF-based proposer selection when PoS is enabled.
+//! `Ed25519BlockSigner` implements the `SignatureProvider` port with the
+//! validator's Ed25519 key. The key file named by `PoSConfig::validator_key_path`
+//! holds the 32-byte secret seed, either raw or hex-encoded.
+//!
+//! `DelegatedBlockSigner` implements the port with any `shared_crypto::Signer`,
+//! so the validator key can stay in an HSM or remote signer (enable the
+//! `remote-signer` feature for the HTTP adapter).
+
+use crate::error::{BlockProductionError, Result};
+use crate::ports::SignatureProvider;
+use async_trait::async_trait;
+use shared_crypto::{Ed25519KeyPair, Signer};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Signs block headers with the validator's Ed25519 key
+pub struct Ed25519BlockSigner {
+    keypair: Ed25519KeyPair,
+}
+
+impl Ed25519BlockSigner {
+    /// Create from a 32-byte secret seed
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self {
+            keypair: Ed25519KeyPair::from_seed(seed),
+        }
+    }
+
+    /// Load the secret seed from a key file (raw or hex)
+    pub fn from_key_file(path: &Path) -> Result<Self> {
+        let contents =
+            std::fs::read(path).map_err(|_| BlockProductionError::InvalidValidatorKey)?;
+        let seed = match contents.len() {
+            32 => contents,
+            _ => {
+                let text = String::from_utf8_lossy(&contents);
+                let text = text.trim();
+                hex::decode(text.strip_prefix("0x").unwrap_or(text))
+                    .map_err(|_| BlockProductionError::InvalidValidatorKey)?
+            }
+        };
+        let seed: [u8; 32] = seed
+            .try_into()
+            .map_err(|_| BlockProductionError::InvalidValidatorKey)?;
+        Ok(Self::from_seed(seed))
+    }
+
+    /// Validator public key
+    pub fn public_key(&self) -> [u8; 32] {
+        *self.keypair.public_key().as_bytes()
+    }
+}
+
+#[async_trait]
+impl SignatureProvider for Ed25519BlockSigner {
+    async fn sign_block_header(&self, header_bytes: &[u8]) -> Result<Vec<u8>> {
+        Ok(self.keypair.sign(header_bytes).as_bytes().to_vec())
+    }
+}
+
+/// Signs block headers through a `shared_crypto::Signer` (HSM, remote
+/// signer, or in-process key)
+pub struct DelegatedBlockSigner {
+    signer: Arc<dyn Signer>,
+}
+
+impl DelegatedBlockSigner {
+    /// Wrap a signer
+    pub fn new(signer: Arc<dyn Signer>) -> Self {
+        Self { signer }
+    }
+
+    /// Validator public key
+    pub fn public_key(&self) -> Vec<u8> {
+        self.signer.public_key()
+    }
+}
+
+#[async_trait]
+impl SignatureProvider for DelegatedBlockSigner {
+    async fn sign_block_header(&self, header_bytes: &[u8]) -> Result<Vec<u8>> {
+        self.signer
+            .sign(header_bytes)
+            .await
+            .map_err(|e| BlockProductionError::SignatureError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_crypto::{verify_signature, Ed25519PublicKey, Ed25519Signature, SoftwareSigner};
+
+    #[tokio::test]
+    async fn test_signature_verifies_with_public_key() {
+        let signer = Ed25519BlockSigner::from_seed([7u8; 32]);
+        let signature = signer.sign_block_header(b"header").await.unwrap();
+
+        let public_key = Ed25519PublicKey::from_bytes(signer.public_key()).unwrap();
+        let signature = Ed25519Signature::from_bytes(signature.try_into().unwrap());
+        assert!(public_key.verify(b"header", &signature).is_ok());
+        assert!(public_key.verify(b"other", &signature).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delegated_signer() {
+        let signer = DelegatedBlockSigner::new(Arc::new(SoftwareSigner::secp256k1(
+            shared_crypto::Secp256k1KeyPair::generate(),
+        )));
+        let signature = signer.sign_block_header(b"header").await.unwrap();
+        assert!(verify_signature(
+            shared_crypto::SignatureScheme::Secp256k1,
+            &signer.public_key(),
+            b"header",
+            &signature
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_key_file_formats() {
+        let dir = std::env::temp_dir().join(format!("qc17-key-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let expected = Ed25519BlockSigner::from_seed([7u8; 32]).public_key();
+
+        let raw = dir.join("raw.key");
+        std::fs::write(&raw, [7u8; 32]).unwrap();
+        let hex_file = dir.join("hex.key");
+        std::fs::write(&hex_file, format!("0x{}\n", hex::encode([7u8; 32]))).unwrap();
+        let short = dir.join("short.key");
+        std::fs::write(&short, "abcd").unwrap();
+
+        for path in [&raw, &hex_file] {
+            let signer = Ed25519BlockSigner::from_key_file(path).unwrap();
+            assert_eq!(signer.public_key(), expected);
+        }
+        assert!(matches!(
+            Ed25519BlockSigner::from_key_file(&short),
+            Err(BlockProductionError::InvalidValidatorKey)
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
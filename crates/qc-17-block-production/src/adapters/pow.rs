@@ -1,4 +1,325 @@
-//! PoW mining adapter (parallel nonce search)
+//! PoW mining adapter (compute backend selection)
 //!
-//! Implementation is in the main service.rs file using PoWMiner.
-//! This module reserved for future specialized PoW adapters.
+//! `MiningDispatcher` hands nonce ranges to the preferred qc-compute engine:
+//! OpenCL when a GPU was detected, with the CPU engine behind it. A nonce
+//! reported by an engine is re-hashed on the CPU before it is accepted; a
+//! nonce that fails verification, or an engine error, moves the same range
+//! to the next backend. Hash counts and search time are tracked per backend
+//! so hashrate can be reported for each one.
+
+use crate::domain::PoWMiner;
+use crate::ports::BackendHashrate;
+use primitive_types::U256;
+use qc_compute::{Backend, ComputeEngine, ComputeError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Running totals for one backend
+#[derive(Clone, Copy, Debug, Default)]
+struct BackendCounters {
+    hashes: u64,
+    busy: Duration,
+    solutions: u64,
+    rejected: u64,
+    errors: u64,
+}
+
+/// Outcome of one search call, for accounting
+enum SearchOutcome {
+    Exhausted,
+    Accepted,
+    Rejected,
+    Failed,
+}
+
+/// Dispatches nonce ranges across compute backends
+pub struct MiningDispatcher {
+    /// Engines in order of preference (GPU first)
+    engines: Vec<Arc<dyn ComputeEngine>>,
+    /// Counters, indexed like `engines`
+    counters: Mutex<Vec<BackendCounters>>,
+}
+
+impl MiningDispatcher {
+    /// Create over the given engines; OpenCL engines are tried first
+    pub fn new(mut engines: Vec<Arc<dyn ComputeEngine>>) -> Self {
+        engines.sort_by_key(|engine| engine.backend() != Backend::OpenCL);
+        let counters = Mutex::new(vec![BackendCounters::default(); engines.len()]);
+        Self { engines, counters }
+    }
+
+    /// Create from a miner's detected engine, with the CPU engine as fallback
+    /// behind a GPU
+    pub fn for_miner(miner: &PoWMiner) -> Self {
+        let mut engines: Vec<_> = miner.get_compute_engine().into_iter().collect();
+        if miner.has_gpu() {
+            match qc_compute::create_backend(Backend::Cpu) {
+                Ok(cpu) => engines.push(cpu),
+                Err(e) => warn!("[qc-17] No CPU compute fallback behind GPU: {}", e),
+            }
+        }
+        Self::new(engines)
+    }
+
+    /// Check if there is no compute engine to dispatch to
+    pub fn is_empty(&self) -> bool {
+        self.engines.is_empty()
+    }
+
+    /// Backend that receives nonce ranges first
+    pub fn primary_backend(&self) -> Option<Backend> {
+        self.engines.first().map(|engine| engine.backend())
+    }
+
+    /// Search `nonce_count` nonces starting at `nonce_start`
+    ///
+    /// Returns the first CPU-verified solution, `Ok(None)` if the range holds
+    /// none, or the last error if every backend failed on the range.
+    pub async fn mine_range(
+        &self,
+        header_template: &[u8],
+        target: U256,
+        nonce_start: u64,
+        nonce_count: u64,
+    ) -> Result<Option<(u64, [u8; 32])>, ComputeError> {
+        let mut last_error = ComputeError::NoBackendAvailable;
+        // Engines report their own hash; the CPU recomputes it
+        let verify = |(nonce, _): (u64, [u8; 32])| {
+            (
+                nonce,
+                PoWMiner::verify_nonce(header_template, nonce, target),
+            )
+        };
+
+        for (index, engine) in self.engines.iter().enumerate() {
+            let started = Instant::now();
+            let result = engine
+                .pow_mine(header_template, target, nonce_start, nonce_count)
+                .await;
+            let busy = started.elapsed();
+            let result = result.map(|found| found.map(verify));
+
+            match result {
+                Ok(None) => {
+                    self.record(index, nonce_count, busy, SearchOutcome::Exhausted);
+                    return Ok(None);
+                }
+                Ok(Some((nonce, Some(hash)))) => {
+                    let searched = nonce.saturating_sub(nonce_start).saturating_add(1);
+                    self.record(index, searched, busy, SearchOutcome::Accepted);
+                    return Ok(Some((nonce, hash)));
+                }
+                Ok(Some((nonce, None))) => {
+                    warn!(
+                        "[qc-17] {} reported nonce {} that fails CPU verification",
+                        engine.backend(),
+                        nonce
+                    );
+                    let searched = nonce.saturating_sub(nonce_start).saturating_add(1);
+                    self.record(index, searched, busy, SearchOutcome::Rejected);
+                    last_error = ComputeError::TaskFailed(format!(
+                        "nonce {} from {} failed verification",
+                        nonce,
+                        engine.backend()
+                    ));
+                }
+                Err(e) => {
+                    warn!("[qc-17] {} nonce search failed: {}", engine.backend(), e);
+                    self.record(index, 0, busy, SearchOutcome::Failed);
+                    last_error = e;
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+
+    /// Per-backend statistics, in dispatch order
+    pub fn hashrates(&self) -> Vec<BackendHashrate> {
+        let counters = self.counters.lock().unwrap();
+        self.engines
+            .iter()
+            .zip(counters.iter())
+            .map(|(engine, c)| {
+                let secs = c.busy.as_secs_f64();
+                BackendHashrate {
+                    backend: engine.backend().to_string(),
+                    hashes: c.hashes,
+                    hashrate: if secs > 0.0 {
+                        c.hashes as f64 / secs
+                    } else {
+                        0.0
+                    },
+                    solutions: c.solutions,
+                    rejected: c.rejected,
+                    errors: c.errors,
+                }
+            })
+            .collect()
+    }
+
+    /// Combined hashrate across backends (None before any search)
+    pub fn total_hashrate(&self) -> Option<f64> {
+        let rates = self.hashrates();
+        rates
+            .iter()
+            .any(|r| r.hashes > 0)
+            .then(|| rates.iter().map(|r| r.hashrate).sum())
+    }
+
+    fn record(&self, index: usize, hashes: u64, busy: Duration, outcome: SearchOutcome) {
+        let mut counters = self.counters.lock().unwrap();
+        let c = &mut counters[index];
+        c.hashes = c.hashes.saturating_add(hashes);
+        c.busy += busy;
+        match outcome {
+            SearchOutcome::Exhausted => {}
+            SearchOutcome::Accepted => c.solutions += 1,
+            SearchOutcome::Rejected => c.rejected += 1,
+            SearchOutcome::Failed => c.errors += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qc_compute::DeviceInfo;
+
+    /// Engine returning a fixed answer for every range
+    struct StubEngine {
+        info: DeviceInfo,
+        answer: Result<Option<u64>, ()>,
+    }
+
+    impl StubEngine {
+        fn arc(backend: Backend, answer: Result<Option<u64>, ()>) -> Arc<dyn ComputeEngine> {
+            Arc::new(Self {
+                info: DeviceInfo {
+                    name: backend.to_string(),
+                    backend,
+                    compute_units: 1,
+                    memory_bytes: 0,
+                    supports_f64: false,
+                },
+                answer,
+            })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ComputeEngine for StubEngine {
+        fn backend(&self) -> Backend {
+            self.info.backend
+        }
+
+        fn device_info(&self) -> &DeviceInfo {
+            &self.info
+        }
+
+        async fn batch_sha256(&self, _inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
+            Err(ComputeError::NoBackendAvailable)
+        }
+
+        async fn batch_keccak256(
+            &self,
+            _inputs: &[Vec<u8>],
+        ) -> Result<Vec<[u8; 32]>, ComputeError> {
+            Err(ComputeError::NoBackendAvailable)
+        }
+
+        async fn batch_blake3(&self, _inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
+            Err(ComputeError::NoBackendAvailable)
+        }
+
+        async fn pow_mine(
+            &self,
+            _header_template: &[u8],
+            _target: U256,
+            _nonce_start: u64,
+            _nonce_count: u64,
+        ) -> Result<Option<(u64, [u8; 32])>, ComputeError> {
+            match self.answer {
+                Ok(nonce) => Ok(nonce.map(|n| (n, [0u8; 32]))),
+                Err(()) => Err(ComputeError::TaskFailed("stub".into())),
+            }
+        }
+
+        async fn batch_verify_ecdsa(
+            &self,
+            _messages: &[[u8; 32]],
+            _signatures: &[[u8; 65]],
+            _public_keys: &[[u8; 33]],
+        ) -> Result<Vec<bool>, ComputeError> {
+            Err(ComputeError::NoBackendAvailable)
+        }
+
+        async fn batch_verify_bls(
+            &self,
+            _messages: &[Vec<u8>],
+            _signatures: &[[u8; 48]],
+            _public_keys: &[[u8; 96]],
+        ) -> Result<Vec<bool>, ComputeError> {
+            Err(ComputeError::NoBackendAvailable)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gpu_preferred_and_verified_on_cpu() {
+        let dispatcher = MiningDispatcher::new(vec![
+            StubEngine::arc(Backend::Cpu, Ok(None)),
+            StubEngine::arc(Backend::OpenCL, Ok(Some(41))),
+        ]);
+        assert_eq!(dispatcher.primary_backend(), Some(Backend::OpenCL));
+
+        // Any hash meets the maximum target, so the GPU nonce is accepted
+        let (nonce, hash) = dispatcher
+            .mine_range(b"header", U256::MAX, 40, 100)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(nonce, 41);
+        assert_eq!(Some(hash), PoWMiner::verify_nonce(b"header", 41, U256::MAX));
+
+        let rates = dispatcher.hashrates();
+        assert_eq!(rates[0].backend, Backend::OpenCL.to_string());
+        assert_eq!((rates[0].hashes, rates[0].solutions), (2, 1));
+        assert_eq!(rates[1].hashes, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_nonce_falls_back_to_cpu() {
+        let dispatcher = MiningDispatcher::new(vec![
+            StubEngine::arc(Backend::OpenCL, Ok(Some(7))),
+            StubEngine::arc(Backend::Cpu, Ok(None)),
+        ]);
+
+        // No hash meets a zero target: the GPU answer is rejected and the
+        // CPU engine searches the same range
+        let result = dispatcher.mine_range(b"header", U256::zero(), 0, 50).await;
+        assert!(matches!(result, Ok(None)));
+
+        let rates = dispatcher.hashrates();
+        assert_eq!(rates[0].rejected, 1);
+        assert_eq!(rates[1].hashes, 50);
+    }
+
+    #[tokio::test]
+    async fn test_all_backends_failing() {
+        let dispatcher = MiningDispatcher::new(vec![StubEngine::arc(Backend::OpenCL, Err(()))]);
+        assert!(dispatcher
+            .mine_range(b"header", U256::MAX, 0, 10)
+            .await
+            .is_err());
+        assert_eq!(dispatcher.hashrates()[0].errors, 1);
+        assert_eq!(dispatcher.total_hashrate(), None);
+
+        let empty = MiningDispatcher::new(Vec::new());
+        assert!(empty.is_empty());
+        assert!(matches!(
+            empty.mine_range(b"header", U256::MAX, 0, 10).await,
+            Err(ComputeError::NoBackendAvailable)
+        ));
+    }
+}
@@ -0,0 +1,646 @@
+//! Stratum-style work server for remote miners (feature `stratum`)
+//!
+//! Block header templates are published as jobs to external miners over
+//! newline-delimited JSON-RPC, following Stratum v1 naming:
+//!
+//! - `mining.subscribe` → `[session_id]`, followed by `mining.set_target`
+//!   and the current `mining.notify`
+//! - `mining.authorize [worker, password]` → `true`
+//! - `mining.submit [worker, job_id, nonce_hex]` → `true` or an error
+//! - `mining.notify [job_id, header_hex, target_hex, clean_jobs]` (server push)
+//!
+//! Miners append the nonce as 8 little-endian bytes to the header and hash
+//! with SHA-256d, exactly like the in-process compute engines. Shares must
+//! meet the share target (or the block target, if easier); a share that also
+//! meets the block target becomes the job's block solution, which the
+//! producer picks up between its own nonce batches.
+
+use crate::domain::PoWMiner;
+use crate::utils::hashing::meets_difficulty;
+use primitive_types::U256;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+/// Work server configuration
+#[derive(Clone, Debug)]
+pub struct StratumConfig {
+    /// Target every share must meet (higher = easier)
+    pub share_target: U256,
+    /// Jobs kept for late submissions
+    pub max_jobs: usize,
+    /// Window over which worker hashrate is averaged
+    pub hashrate_window: Duration,
+}
+
+impl Default for StratumConfig {
+    fn default() -> Self {
+        Self {
+            // ~2^32 expected hashes per share
+            share_target: U256::MAX >> 32,
+            max_jobs: 8,
+            hashrate_window: Duration::from_secs(600),
+        }
+    }
+}
+
+/// Share rejection reasons (Stratum v1 error codes)
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ShareError {
+    /// Request could not be parsed
+    #[error("malformed request: {0}")]
+    Malformed(String),
+    /// Job unknown or already replaced
+    #[error("job not found (stale)")]
+    UnknownJob,
+    /// Nonce already submitted for this job
+    #[error("duplicate share")]
+    Duplicate,
+    /// Hash does not meet the share target
+    #[error("low difficulty share")]
+    LowDifficulty,
+    /// Worker not authorized on this connection
+    #[error("unauthorized worker")]
+    Unauthorized,
+}
+
+impl ShareError {
+    /// Stratum error code
+    pub fn code(&self) -> i64 {
+        match self {
+            Self::Malformed(_) => 20,
+            Self::UnknownJob => 21,
+            Self::Duplicate => 22,
+            Self::LowDifficulty => 23,
+            Self::Unauthorized => 24,
+        }
+    }
+}
+
+/// Accepted share
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareOutcome {
+    /// Share met the share target only
+    Share,
+    /// Share met the block target
+    Block {
+        /// Winning nonce
+        nonce: u64,
+        /// Block hash (SHA-256d)
+        hash: [u8; 32],
+    },
+}
+
+/// Job published to miners
+#[derive(Debug, Clone)]
+pub struct StratumJob {
+    /// Job identifier (hex counter)
+    pub job_id: String,
+    /// Header serialized without nonce
+    pub header_template: Vec<u8>,
+    /// Block target
+    pub target: U256,
+    /// Whether miners must drop previous jobs
+    pub clean_jobs: bool,
+}
+
+/// Per-worker statistics
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkerStats {
+    /// Worker name from `mining.authorize`
+    pub worker: String,
+    /// Accepted shares
+    pub accepted: u64,
+    /// Rejected shares
+    pub rejected: u64,
+    /// Shares that solved a block
+    pub blocks: u64,
+    /// Estimated hashes per second over the hashrate window
+    pub hashrate: f64,
+}
+
+struct JobState {
+    job: Arc<StratumJob>,
+    submitted: HashSet<u64>,
+    solution: Option<(u64, [u8; 32])>,
+}
+
+#[derive(Default)]
+struct WorkerState {
+    accepted: u64,
+    rejected: u64,
+    blocks: u64,
+    /// (submitted at, expected hashes) for shares inside the window
+    shares: VecDeque<(Instant, f64)>,
+    first_seen: Option<Instant>,
+}
+
+/// Work server shared by the producer and miner connections
+pub struct WorkServer {
+    config: StratumConfig,
+    next_job: AtomicU64,
+    next_session: AtomicU64,
+    jobs: Mutex<VecDeque<JobState>>,
+    workers: Mutex<HashMap<String, WorkerState>>,
+    notify: broadcast::Sender<Arc<StratumJob>>,
+}
+
+impl WorkServer {
+    /// Create a work server
+    pub fn new(config: StratumConfig) -> Self {
+        let (notify, _) = broadcast::channel(16);
+        Self {
+            config,
+            next_job: AtomicU64::new(1),
+            next_session: AtomicU64::new(1),
+            jobs: Mutex::new(VecDeque::new()),
+            workers: Mutex::new(HashMap::new()),
+            notify,
+        }
+    }
+
+    /// Publish a header template to connected miners
+    pub fn publish_job(
+        &self,
+        header_template: Vec<u8>,
+        target: U256,
+        clean_jobs: bool,
+    ) -> Arc<StratumJob> {
+        let job = Arc::new(StratumJob {
+            job_id: format!("{:x}", self.next_job.fetch_add(1, Ordering::Relaxed)),
+            header_template,
+            target,
+            clean_jobs,
+        });
+
+        {
+            let mut jobs = self.jobs.lock().unwrap();
+            if clean_jobs {
+                jobs.clear();
+            }
+            jobs.push_back(JobState {
+                job: Arc::clone(&job),
+                submitted: HashSet::new(),
+                solution: None,
+            });
+            while jobs.len() > self.config.max_jobs.max(1) {
+                jobs.pop_front();
+            }
+        }
+
+        // No receivers just means no miner is connected
+        let _ = self.notify.send(Arc::clone(&job));
+        job
+    }
+
+    /// Most recently published job
+    pub fn current_job(&self) -> Option<Arc<StratumJob>> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.back().map(|state| Arc::clone(&state.job))
+    }
+
+    /// Block solution submitted for a job, if any
+    pub fn solution(&self, job_id: &str) -> Option<(u64, [u8; 32])> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.iter()
+            .find(|state| state.job.job_id == job_id)
+            .and_then(|state| state.solution)
+    }
+
+    /// Validate a share from `worker`
+    pub fn submit(
+        &self,
+        worker: &str,
+        job_id: &str,
+        nonce: u64,
+    ) -> Result<ShareOutcome, ShareError> {
+        self.submit_at(worker, job_id, nonce, Instant::now())
+    }
+
+    fn submit_at(
+        &self,
+        worker: &str,
+        job_id: &str,
+        nonce: u64,
+        now: Instant,
+    ) -> Result<ShareOutcome, ShareError> {
+        let result = self.check_share(job_id, nonce);
+        let mut workers = self.workers.lock().unwrap();
+        let state = workers.entry(worker.to_string()).or_default();
+        state.first_seen.get_or_insert(now);
+
+        match &result {
+            Ok((outcome, share_target)) => {
+                state.accepted += 1;
+                if matches!(outcome, ShareOutcome::Block { .. }) {
+                    state.blocks += 1;
+                }
+                state
+                    .shares
+                    .push_back((now, expected_hashes(*share_target)));
+            }
+            Err(_) => state.rejected += 1,
+        }
+        result.map(|(outcome, _)| outcome)
+    }
+
+    /// Check a share against its job, returning the share target it met
+    fn check_share(&self, job_id: &str, nonce: u64) -> Result<(ShareOutcome, U256), ShareError> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let state = jobs
+            .iter_mut()
+            .find(|state| state.job.job_id == job_id)
+            .ok_or(ShareError::UnknownJob)?;
+
+        if !state.submitted.insert(nonce) {
+            return Err(ShareError::Duplicate);
+        }
+
+        let share_target = self.config.share_target.max(state.job.target);
+        let hash = PoWMiner::verify_nonce(&state.job.header_template, nonce, share_target)
+            .ok_or(ShareError::LowDifficulty)?;
+
+        if !meets_difficulty(&hash, state.job.target) {
+            return Ok((ShareOutcome::Share, share_target));
+        }
+        state.solution.get_or_insert((nonce, hash));
+        Ok((ShareOutcome::Block { nonce, hash }, share_target))
+    }
+
+    /// Statistics for every worker that submitted a share
+    pub fn worker_stats(&self) -> Vec<WorkerStats> {
+        self.worker_stats_at(Instant::now())
+    }
+
+    fn worker_stats_at(&self, now: Instant) -> Vec<WorkerStats> {
+        let window = self.config.hashrate_window;
+        let mut workers = self.workers.lock().unwrap();
+        let mut stats: Vec<_> = workers
+            .iter_mut()
+            .map(|(worker, state)| {
+                while state
+                    .shares
+                    .front()
+                    .is_some_and(|(at, _)| now.duration_since(*at) > window)
+                {
+                    state.shares.pop_front();
+                }
+                let observed = state
+                    .first_seen
+                    .map_or(window, |first| now.duration_since(first).min(window));
+                let work: f64 = state.shares.iter().map(|(_, hashes)| hashes).sum();
+                WorkerStats {
+                    worker: worker.clone(),
+                    accepted: state.accepted,
+                    rejected: state.rejected,
+                    blocks: state.blocks,
+                    hashrate: if observed.is_zero() {
+                        0.0
+                    } else {
+                        work / observed.as_secs_f64()
+                    },
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| a.worker.cmp(&b.worker));
+        stats
+    }
+
+    /// Accept miner connections until the listener fails
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> std::io::Result<()> {
+        info!(
+            "[qc-17] Stratum work server listening on {}",
+            listener.local_addr()?
+        );
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let server = Arc::clone(&self);
+            debug!("[qc-17] Miner connected: {}", peer);
+            tokio::spawn(async move {
+                let result = server.handle_connection(stream).await;
+                debug!("[qc-17] Miner {} disconnected: {:?}", peer, result);
+            });
+        }
+    }
+
+    async fn handle_connection(&self, stream: TcpStream) -> std::io::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        let mut jobs = self.notify.subscribe();
+        let mut session = Session::default();
+
+        loop {
+            tokio::select! {
+                line = lines.next_line() => {
+                    let Some(line) = line? else { return Ok(()) };
+                    for message in self.handle_line(&mut session, &line) {
+                        write_message(&mut writer, &message).await?;
+                    }
+                }
+                job = jobs.recv(), if session.subscribed => {
+                    match job {
+                        Ok(job) => write_message(&mut writer, &notify_message(&job)).await?,
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => return Ok(()),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handle one request line, returning the messages to send back
+    fn handle_line(&self, session: &mut Session, line: &str) -> Vec<Value> {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => {
+                let error = ShareError::Malformed(e.to_string());
+                return vec![response(Value::Null, Err(error))];
+            }
+        };
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let params = request
+            .get("params")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        match request.get("method").and_then(Value::as_str) {
+            Some("mining.subscribe") => self.subscribe(session, id),
+            Some("mining.authorize") => {
+                let result = param_str(&params, 0).map(|worker| {
+                    session.workers.insert(worker.to_string());
+                    json!(true)
+                });
+                vec![response(id, result)]
+            }
+            Some("mining.submit") => vec![response(id, self.submit_params(session, &params))],
+            Some(other) => {
+                let error = ShareError::Malformed(format!("unknown method {}", other));
+                vec![response(id, Err(error))]
+            }
+            None => vec![response(
+                id,
+                Err(ShareError::Malformed("missing method".into())),
+            )],
+        }
+    }
+
+    fn subscribe(&self, session: &mut Session, id: Value) -> Vec<Value> {
+        session.subscribed = true;
+        let session_id = format!("{:x}", self.next_session.fetch_add(1, Ordering::Relaxed));
+        let mut messages = vec![
+            response(id, Ok(json!([session_id]))),
+            json!({
+                "id": Value::Null,
+                "method": "mining.set_target",
+                "params": [u256_hex(self.config.share_target)],
+            }),
+        ];
+        messages.extend(self.current_job().map(|job| notify_message(&job)));
+        messages
+    }
+
+    fn submit_params(&self, session: &Session, params: &[Value]) -> Result<Value, ShareError> {
+        let worker = param_str(params, 0)?;
+        if !session.workers.contains(worker) {
+            return Err(ShareError::Unauthorized);
+        }
+        let job_id = param_str(params, 1)?;
+        let nonce_hex = param_str(params, 2)?;
+        let nonce = u64::from_str_radix(nonce_hex.trim_start_matches("0x"), 16)
+            .map_err(|_| ShareError::Malformed(format!("invalid nonce {}", nonce_hex)))?;
+
+        let outcome = self.submit(worker, job_id, nonce);
+        match &outcome {
+            Ok(ShareOutcome::Block { .. }) => {
+                info!(
+                    "[qc-17] Block solution from worker {} (job {})",
+                    worker, job_id
+                )
+            }
+            Err(e) => warn!("[qc-17] Share from {} rejected: {}", worker, e),
+            Ok(ShareOutcome::Share) => {}
+        }
+        outcome.map(|_| json!(true))
+    }
+}
+
+/// Connection state
+#[derive(Default)]
+struct Session {
+    subscribed: bool,
+    workers: HashSet<String>,
+}
+
+fn param_str(params: &[Value], index: usize) -> Result<&str, ShareError> {
+    params
+        .get(index)
+        .and_then(Value::as_str)
+        .ok_or_else(|| ShareError::Malformed(format!("missing parameter {}", index)))
+}
+
+fn response(id: Value, result: Result<Value, ShareError>) -> Value {
+    match result {
+        Ok(result) => json!({ "id": id, "result": result, "error": Value::Null }),
+        Err(e) => json!({
+            "id": id,
+            "result": Value::Null,
+            "error": [e.code(), e.to_string(), Value::Null],
+        }),
+    }
+}
+
+fn notify_message(job: &StratumJob) -> Value {
+    json!({
+        "id": Value::Null,
+        "method": "mining.notify",
+        "params": [
+            job.job_id,
+            hex::encode(&job.header_template),
+            u256_hex(job.target),
+            job.clean_jobs,
+        ],
+    })
+}
+
+async fn write_message(
+    writer: &mut tokio::net::tcp::OwnedWriteHalf,
+    message: &Value,
+) -> std::io::Result<()> {
+    let mut line = message.to_string();
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await
+}
+
+fn u256_hex(value: U256) -> String {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Expected hashes to find one hash at or below `target`
+fn expected_hashes(target: U256) -> f64 {
+    let target = target
+        .0
+        .iter()
+        .rev()
+        .fold(0.0, |acc, limb| acc * 2f64.powi(64) + *limb as f64);
+    2f64.powi(256) / (target + 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(share_target: U256) -> WorkServer {
+        WorkServer::new(StratumConfig {
+            share_target,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_share_validation() {
+        let server = server(U256::MAX);
+        let job = server.publish_job(b"header".to_vec(), U256::zero(), true);
+
+        assert_eq!(
+            server.submit("rig1", &job.job_id, 1),
+            Ok(ShareOutcome::Share)
+        );
+        assert_eq!(
+            server.submit("rig1", &job.job_id, 1),
+            Err(ShareError::Duplicate)
+        );
+        assert_eq!(server.submit("rig1", "ff", 2), Err(ShareError::UnknownJob));
+
+        let strict = self::server(U256::zero());
+        let job = strict.publish_job(b"header".to_vec(), U256::zero(), true);
+        assert_eq!(
+            strict.submit("rig1", &job.job_id, 1),
+            Err(ShareError::LowDifficulty)
+        );
+    }
+
+    #[test]
+    fn test_block_solution_and_clean_jobs() {
+        let server = server(U256::MAX);
+        let job = server.publish_job(b"header".to_vec(), U256::MAX, true);
+
+        let outcome = server.submit("rig1", &job.job_id, 9).unwrap();
+        let expected = PoWMiner::verify_nonce(b"header", 9, U256::MAX).unwrap();
+        assert_eq!(
+            outcome,
+            ShareOutcome::Block {
+                nonce: 9,
+                hash: expected
+            }
+        );
+        assert_eq!(server.solution(&job.job_id), Some((9, expected)));
+
+        // A clean job invalidates earlier jobs
+        let next = server.publish_job(b"header2".to_vec(), U256::MAX, true);
+        assert_eq!(
+            server.submit("rig1", &job.job_id, 10),
+            Err(ShareError::UnknownJob)
+        );
+        assert_eq!(server.current_job().unwrap().job_id, next.job_id);
+        assert_eq!(server.solution(&next.job_id), None);
+    }
+
+    #[test]
+    fn test_worker_hashrate() {
+        // Share target of 2^255 - 1: two expected hashes per share
+        let server = server(U256::MAX >> 1);
+        let job = server.publish_job(b"header".to_vec(), U256::zero(), false);
+        let start = Instant::now();
+
+        let mut accepted = 0;
+        for nonce in 0..64 {
+            let at = start + Duration::from_secs(nonce);
+            if server.submit_at("rig1", &job.job_id, nonce, at).is_ok() {
+                accepted += 1;
+            }
+        }
+
+        let stats = server.worker_stats_at(start + Duration::from_secs(100));
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].accepted, accepted);
+        assert_eq!(stats[0].accepted + stats[0].rejected, 64);
+        let expected = accepted as f64 * 2.0 / 100.0;
+        assert!((stats[0].hashrate - expected).abs() < 1e-9);
+    }
+
+    async fn send(
+        writer: &mut tokio::net::tcp::OwnedWriteHalf,
+        id: u64,
+        method: &str,
+        params: Value,
+    ) {
+        let request = json!({"id": id, "method": method, "params": params});
+        write_message(writer, &request).await.unwrap();
+    }
+
+    async fn recv(
+        lines: &mut tokio::io::Lines<BufReader<tokio::net::tcp::OwnedReadHalf>>,
+    ) -> Value {
+        let line = lines.next_line().await.unwrap().unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_miner_session_over_tcp() {
+        let server = Arc::new(server(U256::MAX));
+        let job = server.publish_job(b"header".to_vec(), U256::MAX, true);
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(Arc::clone(&server).serve(listener));
+
+        let (reader, mut writer) = TcpStream::connect(addr).await.unwrap().into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        // Submitting before authorizing is rejected
+        send(
+            &mut writer,
+            1,
+            "mining.submit",
+            json!(["rig1", job.job_id, "1"]),
+        )
+        .await;
+        assert_eq!(recv(&mut lines).await["error"][0], 24);
+
+        send(&mut writer, 2, "mining.subscribe", json!([])).await;
+        assert!(recv(&mut lines).await["result"].is_array());
+        assert_eq!(recv(&mut lines).await["method"], "mining.set_target");
+        let notify = recv(&mut lines).await;
+        assert_eq!(notify["method"], "mining.notify");
+        assert_eq!(notify["params"][0], job.job_id);
+
+        send(&mut writer, 3, "mining.authorize", json!(["rig1", "x"])).await;
+        assert_eq!(recv(&mut lines).await["result"], true);
+
+        send(
+            &mut writer,
+            4,
+            "mining.submit",
+            json!(["rig1", job.job_id, "2a"]),
+        )
+        .await;
+        assert_eq!(recv(&mut lines).await["result"], true);
+        assert_eq!(
+            server.solution(&job.job_id).map(|(nonce, _)| nonce),
+            Some(0x2a)
+        );
+
+        // New jobs are pushed to subscribed miners
+        let pushed = server.publish_job(b"header2".to_vec(), U256::MAX, true);
+        assert_eq!(recv(&mut lines).await["params"][0], pushed.job_id);
+    }
+}
@@ -1,9 +1,14 @@
 //! Configuration types for block production
 
-use crate::domain::ConsensusMode;
+use crate::domain::{
+    AlgorithmActivation, BlockFillPolicy, ConsensusMode, DifficultyAlgorithm, MiningThrottle,
+    MiningWindow,
+};
 use primitive_types::U256;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+use shared_types::entities::Address;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Runtime configuration for block production
 #[derive(Clone, Debug, Deserialize)]
@@ -20,9 +25,37 @@ pub struct BlockProductionConfig {
     /// Enable MEV protection (fair ordering)
     pub fair_ordering: bool,
 
-    /// Minimum transactions per block (0 = allow empty blocks)
+    /// Pending transactions that trigger immediate block production
     pub min_transactions: u32,
 
+    /// Seconds to wait for `min_transactions` before producing anyway (0 = don't wait)
+    #[serde(default)]
+    pub min_transactions_wait_secs: u64,
+
+    /// Allow blocks containing only the coinbase transaction
+    #[serde(default = "default_allow_empty_blocks")]
+    pub allow_empty_blocks: bool,
+
+    /// Re-pull the mempool when a template is older than this (0 = never)
+    #[serde(default)]
+    pub template_staleness_secs: u64,
+
+    /// Rebuild the template mid-round when pending fees beat it by this
+    /// percentage (0 = never)
+    #[serde(default)]
+    pub fee_rebuild_percent: u32,
+
+    /// Address credited with the block subsidy and fees, as hex. Required for
+    /// PoS proposing; PoW mining falls back to the zero address, which burns
+    /// them
+    #[serde(default, deserialize_with = "deserialize_address")]
+    pub fee_recipient: Option<Address>,
+
+    /// Extra data written into proposed (PoS) block headers, at most 32
+    /// bytes (default: "qc-17-proposer")
+    #[serde(default)]
+    pub graffiti: Vec<u8>,
+
     /// PoW specific settings
     pub pow: Option<PoWConfig>,
 
@@ -44,6 +77,12 @@ impl Default for BlockProductionConfig {
             min_gas_price: U256::from(crate::DEFAULT_MIN_GAS_PRICE),
             fair_ordering: true,
             min_transactions: 1,
+            min_transactions_wait_secs: 0,
+            allow_empty_blocks: true,
+            template_staleness_secs: 0,
+            fee_rebuild_percent: 0,
+            fee_recipient: None,
+            graffiti: Vec::new(),
             pow: None,
             pos: None,
             pbft: None,
@@ -52,6 +91,37 @@ impl Default for BlockProductionConfig {
     }
 }
 
+impl BlockProductionConfig {
+    /// Block fill policy derived from the transaction-count settings
+    pub fn fill_policy(&self) -> BlockFillPolicy {
+        BlockFillPolicy {
+            min_transactions: self.min_transactions,
+            min_transactions_wait: Duration::from_secs(self.min_transactions_wait_secs),
+            allow_empty_blocks: self.allow_empty_blocks,
+            staleness_deadline: (self.template_staleness_secs > 0)
+                .then(|| Duration::from_secs(self.template_staleness_secs)),
+            fee_rebuild_percent: (self.fee_rebuild_percent > 0).then_some(self.fee_rebuild_percent),
+        }
+    }
+}
+
+fn default_allow_empty_blocks() -> bool {
+    true
+}
+
+fn deserialize_address<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Address>, D::Error> {
+    let Some(text) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    let bytes =
+        hex::decode(text.strip_prefix("0x").unwrap_or(&text)).map_err(serde::de::Error::custom)?;
+    Address::try_from(bytes.as_slice())
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("invalid address '{}'", text)))
+}
+
 /// PoW configuration
 #[derive(Clone, Debug, Deserialize)]
 pub struct PoWConfig {
@@ -65,16 +135,32 @@ pub struct PoWConfig {
     pub target_block_time: Option<u64>,
 
     /// Use Dark Gravity Wave for per-block difficulty adjustment (default: true)
+    ///
+    /// Ignored when `difficulty_algorithm` is set.
     pub use_dgw: Option<bool>,
 
+    /// Difficulty algorithm: "epoch", "dgw" or "lwma" (default: from `use_dgw`)
+    pub difficulty_algorithm: Option<DifficultyAlgorithm>,
+
+    /// Height-activated difficulty algorithm switches (default: none)
+    #[serde(default)]
+    pub difficulty_activations: Vec<AlgorithmActivation>,
+
     /// Number of blocks to look back for DGW (default: 24)
     pub dgw_window: Option<usize>,
 
+    /// Number of solve times weighted by LWMA (default: 45)
+    pub lwma_window: Option<usize>,
+
     /// Mining batch size for GPU/CPU compute engines (default: 10_000_000)
     /// This is the number of nonces to try in each mining iteration.
     /// Higher values may improve GPU efficiency but increase iteration time.
     /// Lower values provide better responsiveness but may reduce throughput.
     pub batch_size: Option<u64>,
+
+    /// Mining throttle / power management (default: unthrottled)
+    #[serde(default)]
+    pub throttle: ThrottleConfig,
 }
 
 impl Default for PoWConfig {
@@ -82,10 +168,71 @@ impl Default for PoWConfig {
         Self {
             threads: num_cpus::get() as u8,
             algorithm: HashAlgorithm::Keccak256,
-            target_block_time: Some(10),  // 10 seconds per block
-            use_dgw: Some(true),          // Enable Dark Gravity Wave
+            target_block_time: Some(10), // 10 seconds per block
+            use_dgw: Some(true),         // Enable Dark Gravity Wave
+            difficulty_algorithm: None,  // Follow use_dgw
+            difficulty_activations: Vec::new(),
             dgw_window: Some(24),         // Look at last 24 blocks
+            lwma_window: Some(45),        // Weight the last 45 solve times
             batch_size: Some(10_000_000), // Default mining batch size
+            throttle: ThrottleConfig::default(),
+        }
+    }
+}
+
+impl PoWConfig {
+    /// Difficulty algorithm before any activation height
+    pub fn difficulty_algorithm(&self) -> DifficultyAlgorithm {
+        match (self.difficulty_algorithm, self.use_dgw) {
+            (Some(algorithm), _) => algorithm,
+            (None, Some(false)) => DifficultyAlgorithm::Epoch,
+            (None, _) => DifficultyAlgorithm::DarkGravityWave,
+        }
+    }
+}
+
+/// PoW mining throttle configuration
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ThrottleConfig {
+    /// Share of time spent mining, 1-100 (default: 100)
+    pub duty_cycle_percent: u8,
+
+    /// Back off while CPU load per core is above this (default: none)
+    pub max_cpu_load: Option<f64>,
+
+    /// Back off while the host is hotter than this, in °C (default: none)
+    pub max_temperature_celsius: Option<f64>,
+
+    /// Seconds to back off after a load sample over a limit (default: 30)
+    pub load_backoff_secs: u64,
+
+    /// Daily UTC windows in which mining is allowed, e.g.
+    /// `{ start = "22:00", end = "06:00" }` (default: always)
+    pub windows: Vec<MiningWindow>,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        Self {
+            duty_cycle_percent: 100,
+            max_cpu_load: None,
+            max_temperature_celsius: None,
+            load_backoff_secs: 30,
+            windows: Vec::new(),
+        }
+    }
+}
+
+impl ThrottleConfig {
+    /// Throttle policy derived from the configured limits
+    pub fn policy(&self) -> MiningThrottle {
+        MiningThrottle {
+            duty_cycle_percent: self.duty_cycle_percent,
+            max_cpu_load: self.max_cpu_load,
+            max_temperature_celsius: self.max_temperature_celsius,
+            load_backoff: Duration::from_secs(self.load_backoff_secs),
+            windows: self.windows.clone(),
         }
     }
 }
@@ -177,6 +324,66 @@ mod tests {
         assert_eq!(config.mode, ConsensusMode::ProofOfStake);
         assert_eq!(config.gas_limit, crate::DEFAULT_GAS_LIMIT);
         assert!(config.fair_ordering);
+        assert_eq!(config.fill_policy(), BlockFillPolicy::default());
+    }
+
+    #[test]
+    fn test_fill_policy_from_config() {
+        let config = BlockProductionConfig {
+            min_transactions: 50,
+            min_transactions_wait_secs: 3,
+            allow_empty_blocks: false,
+            template_staleness_secs: 20,
+            fee_rebuild_percent: 10,
+            ..Default::default()
+        };
+        let policy = config.fill_policy();
+        assert_eq!(policy.fee_rebuild_percent, Some(10));
+        assert_eq!(policy.min_transactions_wait, Duration::from_secs(3));
+        assert!(!policy.allow_empty_blocks);
+        assert_eq!(policy.staleness_deadline, Some(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn test_throttle_from_config() {
+        assert_eq!(
+            ThrottleConfig::default().policy(),
+            MiningThrottle::default()
+        );
+
+        let throttle: ThrottleConfig = serde_json::from_value(serde_json::json!({
+            "duty_cycle_percent": 50,
+            "max_temperature_celsius": 80.0,
+            "windows": [{ "start": "22:00", "end": "06:30" }]
+        }))
+        .unwrap();
+        let policy = throttle.policy();
+        assert_eq!(policy.duty_cycle_percent, 50);
+        assert_eq!(policy.load_backoff, Duration::from_secs(30));
+        assert_eq!(policy.windows[0].end.seconds(), 6 * 3600 + 30 * 60);
+
+        let bad = serde_json::from_value::<ThrottleConfig>(serde_json::json!({
+            "windows": [{ "start": "25:00", "end": "06:00" }]
+        }));
+        assert!(bad.is_err());
+    }
+
+    #[test]
+    fn test_fee_recipient_from_hex() {
+        #[derive(Deserialize)]
+        struct Recipient {
+            #[serde(default, deserialize_with = "deserialize_address")]
+            fee_recipient: Option<Address>,
+        }
+        let parse = |value| serde_json::from_value::<Recipient>(value).map(|r| r.fee_recipient);
+
+        let hex = format!("0x{}", "ab".repeat(20));
+        assert_eq!(
+            parse(serde_json::json!({ "fee_recipient": hex })).unwrap(),
+            Some([0xab; 20])
+        );
+        assert_eq!(parse(serde_json::json!({})).unwrap(), None);
+        assert!(parse(serde_json::json!({ "fee_recipient": "0xabcd" })).is_err());
     }
 
     #[test]
@@ -1,7 +1,12 @@
 //! Dynamic Difficulty Adjustment (DDA)
 //!
-//! Implements Bitcoin-style and Dark Gravity Wave (DGW) difficulty adjustment algorithms
-//! to maintain consistent block times as network hashrate changes.
+//! Implements Bitcoin-style, Dark Gravity Wave (DGW) and Linearly Weighted
+//! Moving Average (LWMA) difficulty adjustment algorithms to maintain
+//! consistent block times as network hashrate changes.
+//!
+//! The algorithm is chosen by `DifficultyConfig::algorithm` and can be switched
+//! at fixed heights with `DifficultyConfig::activations`, so a running chain
+//! moves to a new algorithm at the same block on every node.
 //!
 //! **IMPORTANT**: In PoW, the "difficulty target" is actually a CEILING:
 //! - HIGHER target number = EASIER (more valid hashes below it)
@@ -10,8 +15,35 @@
 //! This is counterintuitive! When blocks are too fast, we LOWER the target.
 
 use primitive_types::U256;
+use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+/// Difficulty adjustment algorithm
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DifficultyAlgorithm {
+    /// Bitcoin-style: adjust once per `adjustment_period` blocks
+    #[serde(rename = "epoch")]
+    Epoch,
+
+    /// Dark Gravity Wave: per block, over the average of `dgw_window` blocks
+    #[serde(rename = "dgw")]
+    DarkGravityWave,
+
+    /// LWMA: per block, solve times weighted towards the newest of
+    /// `lwma_window` blocks
+    #[serde(rename = "lwma")]
+    Lwma,
+}
+
+/// Switch to `algorithm` from block `height` onwards
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlgorithmActivation {
+    /// First block height computed with `algorithm`
+    pub height: u64,
+    /// Algorithm in force from `height`
+    pub algorithm: DifficultyAlgorithm,
+}
+
 /// Difficulty adjustment configuration
 #[derive(Clone, Debug)]
 pub struct DifficultyConfig {
@@ -21,12 +53,18 @@ pub struct DifficultyConfig {
     /// Number of blocks per adjustment epoch (Bitcoin-style: 2016)
     pub adjustment_period: u64,
 
-    /// Use Dark Gravity Wave (per-block adjustment) instead of epoch-based
-    pub use_dgw: bool,
+    /// Algorithm used until the first activation
+    pub algorithm: DifficultyAlgorithm,
+
+    /// Height-activated algorithm switches, in any order
+    pub activations: Vec<AlgorithmActivation>,
 
     /// Number of blocks to average for DGW (typically 24)
     pub dgw_window: usize,
 
+    /// Number of solve times weighted by LWMA (typically 45-90)
+    pub lwma_window: usize,
+
     /// Initial difficulty target (genesis) - higher = easier
     pub initial_difficulty: U256,
 
@@ -46,10 +84,12 @@ pub struct DifficultyConfig {
 impl Default for DifficultyConfig {
     fn default() -> Self {
         Self {
-            target_block_time: 10,  // 10 seconds per block
-            adjustment_period: 100, // Adjust every 100 blocks
-            use_dgw: true,          // Enable per-block adjustment
-            dgw_window: 24,         // Look at last 24 blocks
+            target_block_time: 10,                           // 10 seconds per block
+            adjustment_period: 100,                          // Adjust every 100 blocks
+            algorithm: DifficultyAlgorithm::DarkGravityWave, // Per-block adjustment
+            activations: Vec::new(),
+            dgw_window: 24,  // Look at last 24 blocks
+            lwma_window: 45, // Weight the last 45 solve times
             // Initial target: harder difficulty for realistic block times
             // 2^220 means ~36 leading zero bits required (~4.5 zero bytes)
             // At 10M hashes/sec (modern CPU with 4 threads), this takes ~6-10 seconds
@@ -90,6 +130,36 @@ impl DifficultyAdjuster {
         Self { config }
     }
 
+    /// Configuration this adjuster was created with
+    pub fn config(&self) -> &DifficultyConfig {
+        &self.config
+    }
+
+    /// Algorithm that computes the difficulty of block `height`
+    pub fn algorithm_at(&self, height: u64) -> DifficultyAlgorithm {
+        self.config
+            .activations
+            .iter()
+            .filter(|activation| activation.height <= height)
+            .max_by_key(|activation| activation.height)
+            .map_or(self.config.algorithm, |activation| activation.algorithm)
+    }
+
+    /// Number of recent blocks the configured algorithms look at
+    ///
+    /// Callers keep at least this many blocks of history.
+    pub fn history_len(&self) -> usize {
+        std::iter::once(self.config.algorithm)
+            .chain(self.config.activations.iter().map(|a| a.algorithm))
+            .map(|algorithm| match algorithm {
+                DifficultyAlgorithm::Epoch => self.config.adjustment_period as usize,
+                DifficultyAlgorithm::DarkGravityWave => self.config.dgw_window,
+                DifficultyAlgorithm::Lwma => self.config.lwma_window + 1,
+            })
+            .max()
+            .unwrap_or(self.config.dgw_window)
+    }
+
     /// Calculate next difficulty based on recent blocks
     ///
     /// # Arguments
@@ -107,10 +177,10 @@ impl DifficultyAdjuster {
             return self.config.initial_difficulty;
         }
 
-        if self.config.use_dgw {
-            self.calculate_dgw_difficulty(recent_blocks)
-        } else {
-            self.calculate_epoch_difficulty(recent_blocks)
+        match self.algorithm_at(recent_blocks[0].height + 1) {
+            DifficultyAlgorithm::Epoch => self.calculate_epoch_difficulty(recent_blocks),
+            DifficultyAlgorithm::DarkGravityWave => self.calculate_dgw_difficulty(recent_blocks),
+            DifficultyAlgorithm::Lwma => self.calculate_lwma_difficulty(recent_blocks),
         }
     }
 
@@ -160,34 +230,51 @@ impl DifficultyAdjuster {
         // CRITICAL: Target is a CEILING, so:
         // - Blocks too fast → LOWER the target (make it harder)
         // - Blocks too slow → RAISE the target (make it easier)
-        //
-        // FIX: To avoid overflow with large difficulty values, we use a different
-        // approach: divide first by a common factor, then adjust.
-        // new_target = old_target * (actual_time / expected_time)
-        //            = old_target / expected_time * actual_time  (if divisible)
-        //            OR use ratio calculation
-        let new_difficulty = if expected_time > 0 {
-            // Calculate ratio as fraction to avoid overflow
-            // new = avg * actual / expected
-            // But we need to avoid overflow, so:
-            // new = (avg / expected) * actual + (avg % expected) * actual / expected
-            let quotient = avg_difficulty / U256::from(expected_time);
-            let remainder = avg_difficulty % U256::from(expected_time);
-
-            quotient
-                .saturating_mul(U256::from(clamped_actual_time))
-                .saturating_add(
-                    remainder.saturating_mul(U256::from(clamped_actual_time))
-                        / U256::from(expected_time),
-                )
-        } else {
-            avg_difficulty
-        };
+        let new_difficulty = scale_target(avg_difficulty, clamped_actual_time, expected_time);
 
         // Clamp to min/max bounds
         self.clamp_difficulty(new_difficulty)
     }
 
+    /// LWMA: Linearly weighted per-block difficulty adjustment
+    ///
+    /// Each of the last N solve times is weighted by its position (the newest
+    /// counts N times as much as the oldest), so the target follows hashrate
+    /// changes within a few blocks without DGW's overshoot when a burst of
+    /// blocks leaves the window. Solve times are capped at 6x the target block
+    /// time so one stale timestamp cannot swing the result.
+    ///
+    /// REMEMBER: Target is a CEILING. Lower target = harder!
+    fn calculate_lwma_difficulty(&self, recent_blocks: &[BlockInfo]) -> U256 {
+        let window = self.config.lwma_window.min(recent_blocks.len() - 1);
+        if window == 0 {
+            return recent_blocks[0].difficulty;
+        }
+
+        let target_block_time = self.config.target_block_time;
+        let max_solve_time = 6 * target_block_time;
+        let mut weighted_time = 0u64;
+        let mut sum_difficulty = U256::zero();
+        // Oldest solve time has weight 1, newest has weight `window`
+        for (age, pair) in recent_blocks[..=window].windows(2).enumerate() {
+            let solve_time = pair[0]
+                .timestamp
+                .saturating_sub(pair[1].timestamp)
+                .clamp(1, max_solve_time);
+            weighted_time += solve_time * (window - age) as u64;
+            sum_difficulty = sum_difficulty.saturating_add(pair[0].difficulty);
+        }
+        let avg_difficulty = sum_difficulty / U256::from(window);
+
+        // Weighted time if every block had taken exactly the target time
+        let expected_time = (window * (window + 1) / 2) as u64 * target_block_time;
+        let min_time = expected_time / self.config.max_adjustment_factor;
+        let max_time = expected_time * self.config.max_adjustment_factor;
+        let clamped_time = weighted_time.clamp(min_time.max(1), max_time);
+
+        self.clamp_difficulty(scale_target(avg_difficulty, clamped_time, expected_time))
+    }
+
     /// Bitcoin-style: Epoch-based difficulty adjustment
     ///
     /// Adjusts difficulty every N blocks based on how long those N blocks took.
@@ -282,6 +369,21 @@ impl DifficultyAdjuster {
     }
 }
 
+/// `target * actual_time / expected_time` without overflowing U256
+///
+/// Divides first, then scales the remainder separately:
+/// new = (target / expected) * actual + (target % expected) * actual / expected
+fn scale_target(target: U256, actual_time: u64, expected_time: u64) -> U256 {
+    if expected_time == 0 {
+        return target;
+    }
+    let expected = U256::from(expected_time);
+    let actual = U256::from(actual_time);
+    (target / expected)
+        .saturating_mul(actual)
+        .saturating_add((target % expected).saturating_mul(actual) / expected)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -422,6 +524,125 @@ mod tests {
         );
     }
 
+    /// Blocks up to `height` at `difficulty`, `solve_time` apart, newest first
+    fn chain(height: u64, solve_time: u64, difficulty: U256) -> Vec<BlockInfo> {
+        (0..=height)
+            .rev()
+            .map(|height| BlockInfo {
+                height,
+                timestamp: 1_000 + height * solve_time,
+                difficulty,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lwma_tracks_solve_times() {
+        let config = DifficultyConfig {
+            algorithm: DifficultyAlgorithm::Lwma,
+            lwma_window: 10,
+            ..Default::default()
+        };
+        let adjuster = DifficultyAdjuster::new(config.clone());
+        let initial = config.initial_difficulty;
+
+        assert_eq!(
+            adjuster.calculate_next_difficulty(&chain(20, 10, initial)),
+            initial
+        );
+        assert_eq!(
+            adjuster.calculate_next_difficulty(&chain(20, 5, initial)),
+            initial / 2
+        );
+        assert_eq!(
+            adjuster.calculate_next_difficulty(&chain(20, 20, initial)),
+            initial * 2
+        );
+    }
+
+    #[test]
+    fn test_lwma_weights_recent_blocks() {
+        let config = DifficultyConfig {
+            algorithm: DifficultyAlgorithm::Lwma,
+            lwma_window: 4,
+            ..Default::default()
+        };
+        let adjuster = DifficultyAdjuster::new(config.clone());
+        let initial = config.initial_difficulty;
+
+        // Same four solve times (2x 5s, 2x 15s), in opposite order
+        let timestamps = |times: [u64; 4]| {
+            let mut timestamp = 1_000;
+            let mut blocks = vec![BlockInfo {
+                height: 0,
+                timestamp,
+                difficulty: initial,
+            }];
+            for (height, time) in (1..).zip(times) {
+                timestamp += time;
+                blocks.insert(
+                    0,
+                    BlockInfo {
+                        height,
+                        timestamp,
+                        difficulty: initial,
+                    },
+                );
+            }
+            blocks
+        };
+        let slowing = adjuster.calculate_next_difficulty(&timestamps([5, 5, 15, 15]));
+        let speeding = adjuster.calculate_next_difficulty(&timestamps([15, 15, 5, 5]));
+
+        // Recent slow blocks make the next block easier than recent fast ones
+        assert!(slowing > initial);
+        assert!(speeding < initial);
+
+        // A single huge gap is capped at 6x the target time
+        let mut stalled = chain(4, 10, initial);
+        stalled[0].timestamp += 10_000;
+        let capped = adjuster.calculate_next_difficulty(&stalled);
+        // Weighted time: 4 * 60 + (3 + 2 + 1) * 10 = 300 vs 100 expected
+        assert_eq!(capped, initial * 3);
+    }
+
+    #[test]
+    fn test_algorithm_activation_heights() {
+        let config = DifficultyConfig {
+            algorithm: DifficultyAlgorithm::DarkGravityWave,
+            activations: vec![
+                AlgorithmActivation {
+                    height: 200,
+                    algorithm: DifficultyAlgorithm::Epoch,
+                },
+                AlgorithmActivation {
+                    height: 100,
+                    algorithm: DifficultyAlgorithm::Lwma,
+                },
+            ],
+            ..Default::default()
+        };
+        let adjuster = DifficultyAdjuster::new(config.clone());
+
+        assert_eq!(
+            adjuster.algorithm_at(99),
+            DifficultyAlgorithm::DarkGravityWave
+        );
+        assert_eq!(adjuster.algorithm_at(100), DifficultyAlgorithm::Lwma);
+        assert_eq!(adjuster.algorithm_at(199), DifficultyAlgorithm::Lwma);
+        assert_eq!(adjuster.algorithm_at(250), DifficultyAlgorithm::Epoch);
+        assert_eq!(adjuster.history_len(), 100);
+
+        // Block 150 is computed by LWMA; at block 202 epoch mode holds the
+        // current target between epoch boundaries
+        let fast = config.initial_difficulty / U256::from(2);
+        assert!(adjuster.calculate_next_difficulty(&chain(149, 5, fast)) < fast);
+        assert_eq!(
+            adjuster.calculate_next_difficulty(&chain(201, 5, fast)),
+            fast
+        );
+    }
+
     #[test]
     fn test_clamping_bounds() {
         let config = DifficultyConfig::default();
@@ -0,0 +1,186 @@
+//! Difficulty replay simulation
+//!
+//! Replays recorded block history through a `DifficultyConfig` to compare how
+//! much each algorithm makes block times oscillate.
+//!
+//! The hashrate behind every historical block is implied by its target and
+//! solve time. Finding a block takes work proportional to `1 / target`, so
+//! with the same hashrate a simulated block whose target is `k` times the
+//! historical one takes `1 / k` of the historical solve time. The replay is
+//! deterministic: it removes the luck of individual blocks from the comparison
+//! but keeps every hashrate swing the real chain went through.
+
+use super::difficulty::{BlockInfo, DifficultyAdjuster, DifficultyConfig};
+use primitive_types::U256;
+
+/// Outcome of replaying history through one difficulty configuration
+#[derive(Clone, Debug, Default)]
+pub struct ReplayReport {
+    /// Simulated blocks, oldest first
+    pub blocks: Vec<BlockInfo>,
+    /// Simulated solve times (seconds), one per replayed block
+    pub solve_times: Vec<f64>,
+    /// Mean simulated solve time (seconds)
+    pub mean_block_time: f64,
+    /// Standard deviation of the simulated solve times (seconds)
+    pub block_time_stddev: f64,
+    /// Longest simulated solve time (seconds)
+    pub max_block_time: f64,
+    /// Times the target changed direction (easier after harder or vice versa)
+    pub target_reversals: usize,
+}
+
+/// Replay `history` (oldest first) through `config`
+///
+/// The first block is taken as-is; every later block gets the target the
+/// configured algorithm would have chosen and the solve time the historical
+/// hashrate needs for it. Returns an empty report for fewer than two blocks.
+pub fn replay_history(config: &DifficultyConfig, history: &[BlockInfo]) -> ReplayReport {
+    let Some((first, rest)) = history.split_first() else {
+        return ReplayReport::default();
+    };
+    if rest.is_empty() {
+        return ReplayReport::default();
+    }
+
+    let adjuster = DifficultyAdjuster::new(config.clone());
+    let keep = adjuster.history_len() + 1;
+    // Simulated chain, newest first (the adjuster's order)
+    let mut recent = vec![first.clone()];
+    let mut blocks = vec![first.clone()];
+    let mut solve_times = Vec::with_capacity(rest.len());
+    let mut clock = first.timestamp as f64;
+
+    for (previous, block) in history.iter().zip(rest) {
+        let target = adjuster.calculate_next_difficulty(&recent);
+        let historical_time = block.timestamp.saturating_sub(previous.timestamp).max(1) as f64;
+        let solve_time = historical_time * target_to_f64(block.difficulty) / target_to_f64(target);
+        clock += solve_time;
+
+        let simulated = BlockInfo {
+            height: block.height,
+            timestamp: clock.round() as u64,
+            difficulty: target,
+        };
+        recent.insert(0, simulated.clone());
+        recent.truncate(keep);
+        blocks.push(simulated);
+        solve_times.push(solve_time);
+    }
+
+    summarize(blocks, solve_times)
+}
+
+fn summarize(blocks: Vec<BlockInfo>, solve_times: Vec<f64>) -> ReplayReport {
+    let count = solve_times.len() as f64;
+    let mean_block_time = solve_times.iter().sum::<f64>() / count;
+    let variance = solve_times
+        .iter()
+        .map(|time| (time - mean_block_time).powi(2))
+        .sum::<f64>()
+        / count;
+    let max_block_time = solve_times.iter().copied().fold(0.0, f64::max);
+
+    let mut target_reversals = 0;
+    let mut last_direction = None;
+    for pair in blocks.windows(2) {
+        let direction = pair[1].difficulty.cmp(&pair[0].difficulty);
+        if direction.is_eq() {
+            continue;
+        }
+        if last_direction.is_some_and(|last| last != direction) {
+            target_reversals += 1;
+        }
+        last_direction = Some(direction);
+    }
+
+    ReplayReport {
+        blocks,
+        solve_times,
+        mean_block_time,
+        block_time_stddev: variance.sqrt(),
+        max_block_time,
+        target_reversals,
+    }
+}
+
+/// Approximate a 256-bit target as f64 (only ratios are used)
+fn target_to_f64(target: U256) -> f64 {
+    target
+        .0
+        .iter()
+        .rev()
+        .fold(0.0, |value, limb| value * 2f64.powi(64) + *limb as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::difficulty::DifficultyAlgorithm;
+
+    /// Blocks at `initial_difficulty` whose solve times follow `solve_times`
+    fn history(config: &DifficultyConfig, solve_times: &[u64]) -> Vec<BlockInfo> {
+        let mut timestamp = 1_000;
+        let mut blocks = vec![BlockInfo {
+            height: 0,
+            timestamp,
+            difficulty: config.initial_difficulty,
+        }];
+        for (height, solve_time) in (1..).zip(solve_times) {
+            timestamp += solve_time;
+            blocks.push(BlockInfo {
+                height,
+                timestamp,
+                difficulty: config.initial_difficulty,
+            });
+        }
+        blocks
+    }
+
+    #[test]
+    fn test_steady_hashrate_replays_unchanged() {
+        let config = DifficultyConfig::default();
+        let report = replay_history(&config, &history(&config, &[10; 60]));
+
+        assert_eq!(report.solve_times.len(), 60);
+        assert!((report.mean_block_time - 10.0).abs() < 1e-6);
+        assert!(report.block_time_stddev < 1e-6);
+        assert_eq!(report.target_reversals, 0);
+        assert!(replay_history(&config, &history(&config, &[]))
+            .blocks
+            .is_empty());
+    }
+
+    #[test]
+    fn test_hashrate_jump_converges_towards_target_time() {
+        // Hashrate roughly triples after 30 blocks: history at the old
+        // target shows 3s blocks from then on
+        let mut solve_times = vec![10; 30];
+        solve_times.extend([3; 150]);
+
+        for algorithm in [
+            DifficultyAlgorithm::DarkGravityWave,
+            DifficultyAlgorithm::Lwma,
+        ] {
+            let config = DifficultyConfig {
+                algorithm,
+                ..DifficultyConfig::default()
+            };
+            let report = replay_history(&config, &history(&config, &solve_times));
+            let settled = &report.solve_times[report.solve_times.len() - 20..];
+            let settled_mean = settled.iter().sum::<f64>() / settled.len() as f64;
+            assert!(
+                (settled_mean - 10.0).abs() < 1.5,
+                "{:?} settled at {}s",
+                algorithm,
+                settled_mean
+            );
+        }
+    }
+
+    #[test]
+    fn test_target_to_f64_keeps_high_limbs() {
+        let target = U256::from(2).pow(U256::from(220));
+        assert_eq!(target_to_f64(target), 2f64.powi(220));
+    }
+}
@@ -209,7 +209,7 @@ pub struct TransactionBundle {
 }
 
 /// MEV bundle types
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BundleType {
     /// Simple bundle (user intent)
     Simple,
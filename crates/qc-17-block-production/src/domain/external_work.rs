@@ -0,0 +1,305 @@
+//! Work issued to external block producers
+//!
+//! Templates handed out through `qc_getBlockTemplate` are remembered here so a
+//! later `qc_submitBlock` can be checked before the block is propagated:
+//!
+//! - The template must be one this node issued and has not evicted.
+//! - Its parent must still be the chain head (a solution for an older head
+//!   would fork the chain).
+//! - The nonce must meet the template's difficulty target, re-hashed the same
+//!   way the in-process miners hash (`header || nonce_le`, SHA-256d).
+//!
+//! An accepted solution becomes the new head, which makes every other template
+//! for the same height stale.
+
+use super::{BlockTemplate, PoWMiner};
+use crate::utils::hashing::serialize_block_header;
+use primitive_types::{H256, U256};
+use std::collections::VecDeque;
+use thiserror::Error;
+
+/// Default number of outstanding templates kept for submission
+pub const DEFAULT_MAX_TEMPLATES: usize = 64;
+
+/// Block the next template builds on
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChainHead {
+    /// Block hash
+    pub hash: H256,
+    /// Block height (0 before the first block)
+    pub height: u64,
+    /// Block timestamp
+    pub timestamp: u64,
+    /// Difficulty target the block was sealed at
+    pub difficulty: U256,
+}
+
+/// Template handed to an external producer
+#[derive(Clone, Debug)]
+pub struct IssuedTemplate {
+    /// ID to quote when submitting a solution
+    pub template_id: String,
+    /// Block template (nonce unset)
+    pub template: BlockTemplate,
+    /// Header serialized without a nonce; the nonce is appended little-endian
+    pub header_template: Vec<u8>,
+}
+
+/// Template sealed with an accepted nonce
+#[derive(Clone, Debug)]
+pub struct SealedBlock {
+    /// Template with its nonce filled in
+    pub template: BlockTemplate,
+    /// Accepted nonce
+    pub nonce: u64,
+    /// Block hash (SHA-256d of header and nonce)
+    pub hash: [u8; 32],
+}
+
+/// Why a submitted solution was refused
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum WorkSubmitError {
+    /// Template was never issued or has been evicted
+    #[error("Unknown block template: {0}")]
+    UnknownTemplate(String),
+
+    /// Template no longer builds on the chain head
+    #[error("Block template {0} is stale: the chain head has moved")]
+    StaleTemplate(String),
+
+    /// Hash of header and nonce does not meet the difficulty target
+    #[error("Nonce {nonce} does not meet the difficulty target")]
+    InvalidProofOfWork {
+        /// Submitted nonce
+        nonce: u64,
+    },
+}
+
+/// Templates issued to external producers, checked on submission
+#[derive(Debug)]
+pub struct ExternalWorkRegistry {
+    head: ChainHead,
+    templates: VecDeque<IssuedTemplate>,
+    max_templates: usize,
+    next_id: u64,
+}
+
+impl Default for ExternalWorkRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TEMPLATES)
+    }
+}
+
+impl ExternalWorkRegistry {
+    /// Create a registry remembering up to `max_templates` templates
+    pub fn new(max_templates: usize) -> Self {
+        Self {
+            head: ChainHead::default(),
+            templates: VecDeque::new(),
+            max_templates: max_templates.max(1),
+            next_id: 0,
+        }
+    }
+
+    /// Current chain head
+    pub fn head(&self) -> ChainHead {
+        self.head
+    }
+
+    /// Start over from `head`, forgetting issued templates
+    pub fn reset(&mut self, head: ChainHead) {
+        self.head = head;
+        self.templates.clear();
+    }
+
+    /// Make `head` the chain head if it is above the current one
+    ///
+    /// Returns false if a block at that height was already accepted.
+    pub fn advance_head(&mut self, head: ChainHead) -> bool {
+        let advanced = head.height > self.head.height;
+        if advanced {
+            self.head = head;
+        }
+        advanced
+    }
+
+    /// Remember `template` and assign it an ID, evicting the oldest if full
+    pub fn issue(&mut self, template: BlockTemplate) -> IssuedTemplate {
+        let header = &template.header;
+        let header_template = serialize_block_header(
+            &header.parent_hash,
+            header.block_number,
+            header.timestamp,
+            &header.beneficiary,
+            header.gas_used,
+            None,
+        );
+
+        self.next_id += 1;
+        let issued = IssuedTemplate {
+            template_id: format!("{:016x}", self.next_id),
+            template,
+            header_template,
+        };
+
+        if self.templates.len() == self.max_templates {
+            self.templates.pop_front();
+        }
+        self.templates.push_back(issued.clone());
+        issued
+    }
+
+    /// Check a solution and, if accepted, make its block the new head
+    pub fn submit(
+        &mut self,
+        template_id: &str,
+        nonce: u64,
+    ) -> Result<SealedBlock, WorkSubmitError> {
+        let index = self
+            .templates
+            .iter()
+            .position(|issued| issued.template_id == template_id)
+            .ok_or_else(|| WorkSubmitError::UnknownTemplate(template_id.to_string()))?;
+
+        let issued = &self.templates[index];
+        let header = &issued.template.header;
+        if header.parent_hash != self.head.hash || header.block_number != self.head.height + 1 {
+            return Err(WorkSubmitError::StaleTemplate(template_id.to_string()));
+        }
+
+        let hash = PoWMiner::verify_nonce(&issued.header_template, nonce, header.difficulty)
+            .ok_or(WorkSubmitError::InvalidProofOfWork { nonce })?;
+
+        let mut template = self
+            .templates
+            .remove(index)
+            .expect("index found above")
+            .template;
+        template.header.nonce = Some(nonce);
+        // Parent is the head, so this block is always above it
+        self.advance_head(ChainHead {
+            hash: H256(hash),
+            height: template.header.block_number,
+            timestamp: template.header.timestamp,
+            difficulty: template.header.difficulty,
+        });
+
+        Ok(SealedBlock {
+            template,
+            nonce,
+            hash,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{BlockHeader, ConsensusMode};
+
+    fn template(parent: H256, block_number: u64, difficulty: U256) -> BlockTemplate {
+        BlockTemplate {
+            header: BlockHeader {
+                parent_hash: parent,
+                block_number,
+                timestamp: 1_700_000_000 + block_number,
+                beneficiary: [7u8; 20],
+                gas_used: 0,
+                gas_limit: 30_000_000,
+                difficulty,
+                extra_data: Vec::new(),
+                merkle_root: None,
+                state_root: None,
+                nonce: None,
+            },
+            transactions: Vec::new(),
+            total_gas_used: 0,
+            total_fees: U256::zero(),
+            consensus_mode: ConsensusMode::ProofOfWork,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_accepted_solution_advances_head() {
+        let mut registry = ExternalWorkRegistry::default();
+        let issued = registry.issue(template(H256::zero(), 1, U256::MAX));
+
+        let sealed = registry.submit(&issued.template_id, 42).unwrap();
+        assert_eq!(sealed.template.header.nonce, Some(42));
+        assert_eq!(
+            Some(sealed.hash),
+            PoWMiner::verify_nonce(&issued.header_template, 42, U256::MAX)
+        );
+
+        let head = registry.head();
+        assert_eq!((head.hash, head.height), (H256(sealed.hash), 1));
+
+        // A sealed template cannot be submitted twice
+        assert_eq!(
+            registry.submit(&issued.template_id, 42).unwrap_err(),
+            WorkSubmitError::UnknownTemplate(issued.template_id)
+        );
+    }
+
+    #[test]
+    fn test_stale_template_rejected() {
+        let mut registry = ExternalWorkRegistry::default();
+        let first = registry.issue(template(H256::zero(), 1, U256::MAX));
+        let second = registry.issue(template(H256::zero(), 1, U256::MAX));
+
+        registry.submit(&first.template_id, 1).unwrap();
+        assert_eq!(
+            registry.submit(&second.template_id, 1).unwrap_err(),
+            WorkSubmitError::StaleTemplate(second.template_id)
+        );
+    }
+
+    #[test]
+    fn test_invalid_proof_of_work_rejected() {
+        let mut registry = ExternalWorkRegistry::default();
+        let issued = registry.issue(template(H256::zero(), 1, U256::zero()));
+
+        assert_eq!(
+            registry.submit(&issued.template_id, 9).unwrap_err(),
+            WorkSubmitError::InvalidProofOfWork { nonce: 9 }
+        );
+        assert_eq!(registry.head(), ChainHead::default());
+    }
+
+    #[test]
+    fn test_oldest_template_evicted() {
+        let mut registry = ExternalWorkRegistry::new(2);
+        let oldest = registry.issue(template(H256::zero(), 1, U256::MAX));
+        registry.issue(template(H256::zero(), 1, U256::MAX));
+        registry.issue(template(H256::zero(), 1, U256::MAX));
+
+        assert!(matches!(
+            registry.submit(&oldest.template_id, 0),
+            Err(WorkSubmitError::UnknownTemplate(_))
+        ));
+    }
+
+    #[test]
+    fn test_head_only_moves_forward() {
+        let mut registry = ExternalWorkRegistry::default();
+        let tip = ChainHead {
+            hash: H256::repeat_byte(1),
+            height: 10,
+            ..Default::default()
+        };
+        assert!(registry.advance_head(tip));
+
+        // A competing block at the same height, or an older one, loses
+        for height in [9, 10] {
+            assert!(!registry.advance_head(ChainHead {
+                height,
+                ..Default::default()
+            }));
+        }
+        assert_eq!(registry.head(), tip);
+
+        registry.reset(ChainHead::default());
+        assert_eq!(registry.head(), ChainHead::default());
+    }
+}
@@ -0,0 +1,157 @@
+//! Block fill policy
+//!
+//! Decides when a block template should be built from the mempool contents,
+//! trading block latency against block fullness:
+//!
+//! - A block is produced as soon as `min_transactions` are pending.
+//! - Below that, the producer waits up to `min_transactions_wait` for more
+//!   transactions, then produces with whatever it has.
+//! - With empty blocks disabled, the producer never seals a block without at
+//!   least one transaction, however long it waits.
+//! - A template older than the staleness deadline is abandoned so the next
+//!   template picks up transactions that arrived while mining.
+//! - While mining, the template is rebuilt as soon as the pending
+//!   transactions pay at least `fee_rebuild_percent` more fees than it does.
+
+use primitive_types::U256;
+use std::time::Duration;
+
+/// Outcome of a fill check
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillDecision {
+    /// Build a template from the pending transactions now
+    Produce,
+    /// Poll the mempool again later
+    Wait,
+}
+
+/// Block fill policy
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockFillPolicy {
+    /// Pending transactions that trigger immediate production
+    pub min_transactions: u32,
+    /// Longest wait for `min_transactions` before producing anyway
+    pub min_transactions_wait: Duration,
+    /// Whether blocks with no transactions besides the coinbase may be sealed
+    pub allow_empty_blocks: bool,
+    /// Re-pull the mempool once a template is this old (None = never)
+    pub staleness_deadline: Option<Duration>,
+    /// Rebuild mid-round when pending fees beat the template's by this
+    /// percentage (None = never)
+    pub fee_rebuild_percent: Option<u32>,
+}
+
+impl Default for BlockFillPolicy {
+    /// Produce immediately, empty or not (the behavior before this policy existed)
+    fn default() -> Self {
+        Self {
+            min_transactions: 1,
+            min_transactions_wait: Duration::ZERO,
+            allow_empty_blocks: true,
+            staleness_deadline: None,
+            fee_rebuild_percent: None,
+        }
+    }
+}
+
+impl BlockFillPolicy {
+    /// Decide whether to produce with `pending` transactions after waiting `waited`
+    pub fn decide(&self, pending: usize, waited: Duration) -> FillDecision {
+        if pending == 0 && !self.allow_empty_blocks {
+            return FillDecision::Wait;
+        }
+        if pending >= self.min_transactions as usize || waited >= self.min_transactions_wait {
+            FillDecision::Produce
+        } else {
+            FillDecision::Wait
+        }
+    }
+
+    /// Check if a template built `age` ago should be rebuilt
+    pub fn is_stale(&self, age: Duration) -> bool {
+        self.staleness_deadline
+            .is_some_and(|deadline| age >= deadline)
+    }
+
+    /// Check if pending transactions paying `pending_fees` are worth
+    /// replacing a template that pays `template_fees`
+    pub fn should_rebuild(&self, template_fees: U256, pending_fees: U256) -> bool {
+        self.fee_rebuild_percent.is_some_and(|percent| {
+            pending_fees > template_fees
+                && pending_fees.saturating_mul(U256::from(100))
+                    >= template_fees.saturating_mul(U256::from(100 + u64::from(percent)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_produces_immediately() {
+        let policy = BlockFillPolicy::default();
+        assert_eq!(policy.decide(0, Duration::ZERO), FillDecision::Produce);
+        assert_eq!(policy.decide(5, Duration::ZERO), FillDecision::Produce);
+        assert!(!policy.is_stale(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_waits_for_min_transactions_until_deadline() {
+        let policy = BlockFillPolicy {
+            min_transactions: 10,
+            min_transactions_wait: Duration::from_secs(5),
+            ..Default::default()
+        };
+        assert_eq!(policy.decide(3, Duration::from_secs(1)), FillDecision::Wait);
+        assert_eq!(
+            policy.decide(10, Duration::from_secs(1)),
+            FillDecision::Produce
+        );
+        assert_eq!(
+            policy.decide(3, Duration::from_secs(5)),
+            FillDecision::Produce
+        );
+    }
+
+    #[test]
+    fn test_empty_blocks_disabled() {
+        let policy = BlockFillPolicy {
+            allow_empty_blocks: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.decide(0, Duration::from_secs(3600)),
+            FillDecision::Wait
+        );
+        assert_eq!(policy.decide(1, Duration::ZERO), FillDecision::Produce);
+    }
+
+    #[test]
+    fn test_staleness_deadline() {
+        let policy = BlockFillPolicy {
+            staleness_deadline: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+        assert!(!policy.is_stale(Duration::from_secs(29)));
+        assert!(policy.is_stale(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_fee_rebuild_threshold() {
+        let fees = |amount: u64| U256::from(amount);
+        assert!(!BlockFillPolicy::default().should_rebuild(fees(0), fees(1_000)));
+
+        let policy = BlockFillPolicy {
+            fee_rebuild_percent: Some(20),
+            ..Default::default()
+        };
+        assert!(!policy.should_rebuild(fees(1_000), fees(1_199)));
+        assert!(policy.should_rebuild(fees(1_000), fees(1_200)));
+        assert!(!policy.should_rebuild(fees(1_000), fees(900)));
+
+        // Any fees beat an empty template; nothing never does
+        assert!(policy.should_rebuild(fees(0), fees(1)));
+        assert!(!policy.should_rebuild(fees(0), fees(0)));
+    }
+}
@@ -5,11 +5,12 @@
 
 use sha2::{Digest, Sha256};
 use shared_types::entities::{
-    Address, BlockHeader, ConsensusProof, GenesisConfig, Hash, PublicKey, Transaction,
-    ValidatedBlock, ValidatedTransaction, U256,
+    Address, BlockHeader, CoinbaseTransaction, ConsensusProof, GenesisConfig, Hash, PublicKey,
+    RewardSchedule, Transaction, ValidatedBlock, ValidatedTransaction, U256,
 };
 
 use crate::domain::difficulty::DifficultyConfig;
+use crate::domain::TransactionCandidate;
 
 /// Creates the genesis block from configuration
 pub fn create_genesis_block(config: &GenesisConfig) -> Result<ValidatedBlock, GenesisError> {
@@ -115,30 +116,48 @@ pub fn create_coinbase_transaction(
 
 /// Calculate the block reward for a given height
 ///
-/// Uses a halving schedule: starts at 50 coins, halves every 210,000 blocks
+/// Uses the shared `RewardSchedule`: starts at 50 coins, halves every
+/// 210,000 blocks. Consensus checks coinbase claims against the same schedule.
 pub fn calculate_block_reward(height: u64) -> U256 {
-    const INITIAL_REWARD: u64 = 50;
-    const HALVING_INTERVAL: u64 = 210_000;
-    const DECIMALS: u128 = 10u128.pow(8);
-
-    let halvings = height / HALVING_INTERVAL;
+    RewardSchedule::default().subsidy_at(height)
+}
 
-    if halvings >= 64 {
-        return U256::zero();
+/// Coinbase claim announced with a produced block
+///
+/// The reward is the block subsidy plus `transaction_fees`, paid to
+/// `recipient`. Consensus (qc-08) checks it against the subsidy schedule and
+/// State Management (qc-04) mints it.
+pub fn coinbase_claim(
+    block_height: u64,
+    recipient: Address,
+    transaction_fees: U256,
+    timestamp: u64,
+) -> CoinbaseTransaction {
+    CoinbaseTransaction {
+        block_height,
+        miner_address: recipient,
+        reward: calculate_block_reward(block_height).saturating_add(transaction_fees),
+        fees: transaction_fees,
+        timestamp,
     }
-
-    let reward = INITIAL_REWARD >> halvings; // Bit shift for halving
-    U256::from(reward) * U256::from(DECIMALS)
 }
 
 /// Calculate transaction fees from a list of transactions
 pub fn calculate_transaction_fees(transactions: &[ValidatedTransaction]) -> U256 {
+    base_fees(transactions.len())
+}
+
+/// Calculate transaction fees for mempool candidates (same fee model)
+pub fn calculate_candidate_fees(candidates: &[TransactionCandidate]) -> U256 {
+    base_fees(candidates.len())
+}
+
+fn base_fees(count: usize) -> U256 {
     // Simple fee model: each transaction pays a base fee
     // Production: gas_used * gas_price per transaction
     const BASE_FEE: u64 = 1_000_000; // 0.000001 coin per tx
 
-    let total_fees = transactions.len() as u64 * BASE_FEE;
-    U256::from(total_fees)
+    U256::from(count as u64 * BASE_FEE)
 }
 
 // =============================================================================
@@ -270,6 +289,19 @@ mod tests {
         assert!(coinbase.inner.data.starts_with(b"COINBASE"));
     }
 
+    #[test]
+    fn test_coinbase_claim_pays_subsidy_and_fees() {
+        let fees = U256::from(3_000_000);
+        let claim = coinbase_claim(210_000, [9u8; 20], fees, 1733494800);
+
+        assert_eq!(claim.miner_address, [9u8; 20]);
+        assert_eq!(claim.subsidy(), calculate_block_reward(210_000));
+        assert_eq!(
+            claim.reward,
+            RewardSchedule::default().max_reward(210_000, fees)
+        );
+    }
+
     #[test]
     fn test_merkle_root_single_tx() {
         let tx = ValidatedTransaction {
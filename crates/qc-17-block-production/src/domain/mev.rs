@@ -0,0 +1,371 @@
+//! MEV detection and censorship reporting
+//!
+//! Each produced block gets a [`MevReport`] built from the block's
+//! transaction order and the mempool candidates that were available for it.
+//! Transactions are only known by sender, nonce and fee here, so the
+//! heuristics work on ordering and fee patterns:
+//!
+//! - **Sandwich:** a sender's transactions bracket another sender's
+//!   transaction, the front one paying more than the victim and the back one
+//!   no more than it.
+//! - **Backrun:** a transaction placed directly after another sender's
+//!   transaction at the same gas price, ahead of a later transaction that pays
+//!   more.
+//! - **Fee-order distance:** pairs of transactions from different senders
+//!   where the later one pays strictly more (Kendall tau distance from fee
+//!   order; pairs from one sender are fixed by nonce order and not counted).
+//! - **Dropped census:** candidates left out of the block, by likely reason,
+//!   and how long left-out transactions have been waiting.
+
+use super::{BundleType, TransactionCandidate};
+use primitive_types::{H160, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Blocks a transaction may be passed over before it counts as censored
+pub const DEFAULT_CENSORSHIP_THRESHOLD: u64 = 3;
+
+/// Block parameters the census checks candidates against
+#[derive(Clone, Copy, Debug)]
+pub struct MevContext {
+    /// Height of the analyzed block
+    pub block_number: u64,
+    /// Block gas limit
+    pub gas_limit: u64,
+    /// Minimum gas price accepted by the producer
+    pub min_gas_price: U256,
+}
+
+/// One suspected MEV pattern
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MevFinding {
+    /// Pattern detected (`Sandwich` or `BackRunning`)
+    pub kind: BundleType,
+    /// Sender suspected of extracting value
+    pub searcher: H160,
+    /// Sender whose transaction was targeted
+    pub victim: H160,
+    /// Block positions involved, in block order
+    pub positions: Vec<usize>,
+}
+
+/// Candidates left out of a block
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DroppedCensus {
+    /// Candidates available from the mempool
+    pub considered: usize,
+    /// Candidates included in the block
+    pub included: usize,
+    /// Left out for paying less than the minimum gas price
+    pub below_min_gas_price: usize,
+    /// Left out for not fitting the remaining block gas
+    pub over_gas_limit: usize,
+    /// Left out with no fee or gas reason
+    pub unexplained: usize,
+    /// Left-out transactions passed over for at least the censorship threshold
+    pub censorship_suspects: usize,
+    /// Blocks the longest-waiting left-out transaction has been passed over
+    pub oldest_pending_blocks: u64,
+}
+
+/// MEV and censorship report for one produced block
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MevReport {
+    /// Block height
+    pub block_number: u64,
+    /// Block hash (zero until the block is sealed)
+    pub block_hash: H256,
+    /// Transactions in the block, excluding the coinbase
+    pub transaction_count: usize,
+    /// Suspected sandwiches
+    pub sandwiches: Vec<MevFinding>,
+    /// Suspected backruns
+    pub backruns: Vec<MevFinding>,
+    /// Cross-sender pairs where the later transaction pays more
+    pub fee_order_inversions: u64,
+    /// `fee_order_inversions` over all cross-sender pairs (0 = fee order)
+    pub reorder_distance: f64,
+    /// Candidates left out of the block
+    pub dropped: DroppedCensus,
+}
+
+/// Builds [`MevReport`]s, remembering how long left-out transactions wait
+#[derive(Debug)]
+pub struct MevDetector {
+    /// First block each left-out (sender, nonce) was available for
+    first_seen: HashMap<(H160, u64), u64>,
+    censorship_threshold: u64,
+}
+
+impl Default for MevDetector {
+    fn default() -> Self {
+        Self::new(DEFAULT_CENSORSHIP_THRESHOLD)
+    }
+}
+
+impl MevDetector {
+    /// Create a detector flagging transactions passed over `censorship_threshold` times
+    pub fn new(censorship_threshold: u64) -> Self {
+        Self {
+            first_seen: HashMap::new(),
+            censorship_threshold: censorship_threshold.max(1),
+        }
+    }
+
+    /// Analyze a block holding `included` (in block order, coinbase excluded)
+    /// that was built from the mempool `considered` set
+    pub fn analyze(
+        &mut self,
+        context: MevContext,
+        considered: &[TransactionCandidate],
+        included: &[TransactionCandidate],
+    ) -> MevReport {
+        let sandwiches = find_sandwiches(included);
+        let sandwiched: HashSet<usize> = sandwiches
+            .iter()
+            .flat_map(|finding| finding.positions.iter().copied())
+            .collect();
+        let backruns = find_backruns(included, &sandwiched);
+        let (fee_order_inversions, cross_sender_pairs) = fee_order_inversions(included);
+
+        MevReport {
+            block_number: context.block_number,
+            block_hash: H256::zero(),
+            transaction_count: included.len(),
+            sandwiches,
+            backruns,
+            fee_order_inversions,
+            reorder_distance: if cross_sender_pairs == 0 {
+                0.0
+            } else {
+                fee_order_inversions as f64 / cross_sender_pairs as f64
+            },
+            dropped: self.census(context, considered, included),
+        }
+    }
+
+    fn census(
+        &mut self,
+        context: MevContext,
+        considered: &[TransactionCandidate],
+        included: &[TransactionCandidate],
+    ) -> DroppedCensus {
+        let included_keys: HashSet<_> = included.iter().map(key).collect();
+        let gas_used: u64 = included.iter().map(|tx| tx.gas_limit).sum();
+        let remaining_gas = context.gas_limit.saturating_sub(gas_used);
+
+        let mut census = DroppedCensus {
+            considered: considered.len(),
+            included: included.len(),
+            ..Default::default()
+        };
+        let mut still_pending = HashMap::new();
+
+        for tx in considered
+            .iter()
+            .filter(|tx| !included_keys.contains(&key(tx)))
+        {
+            if tx.gas_price < context.min_gas_price {
+                census.below_min_gas_price += 1;
+            } else if tx.gas_limit > remaining_gas {
+                census.over_gas_limit += 1;
+            } else {
+                census.unexplained += 1;
+            }
+
+            let first_seen = self
+                .first_seen
+                .get(&key(tx))
+                .copied()
+                .unwrap_or(context.block_number);
+            let passed_over = context.block_number - first_seen.min(context.block_number) + 1;
+            if passed_over >= self.censorship_threshold {
+                census.censorship_suspects += 1;
+            }
+            census.oldest_pending_blocks = census.oldest_pending_blocks.max(passed_over);
+            still_pending.insert(key(tx), first_seen);
+        }
+
+        // Forget included transactions and those no longer in the mempool
+        self.first_seen = still_pending;
+        census
+    }
+}
+
+fn key(tx: &TransactionCandidate) -> (H160, u64) {
+    (H160(tx.from), tx.nonce)
+}
+
+/// Sender brackets another sender's transaction: front pays more than the
+/// victim, back pays no more than it
+fn find_sandwiches(txs: &[TransactionCandidate]) -> Vec<MevFinding> {
+    let mut last_by_sender: HashMap<[u8; 20], usize> = HashMap::new();
+    let mut findings = Vec::new();
+
+    for (back, tx) in txs.iter().enumerate() {
+        if let Some(front) = last_by_sender.insert(tx.from, back) {
+            let front_price = txs[front].gas_price;
+            let victim = (front + 1..back).find(|&v| {
+                txs[v].from != tx.from
+                    && front_price > txs[v].gas_price
+                    && tx.gas_price <= txs[v].gas_price
+            });
+            findings.extend(victim.map(|v| MevFinding {
+                kind: BundleType::Sandwich,
+                searcher: H160(tx.from),
+                victim: H160(txs[v].from),
+                positions: vec![front, v, back],
+            }));
+        }
+    }
+    findings
+}
+
+/// Transaction right after another sender's at the same gas price, placed
+/// ahead of a later transaction that pays more
+fn find_backruns(txs: &[TransactionCandidate], sandwiched: &HashSet<usize>) -> Vec<MevFinding> {
+    // Highest gas price after each position
+    let mut later_max = vec![U256::zero(); txs.len()];
+    for i in (0..txs.len().saturating_sub(1)).rev() {
+        later_max[i] = later_max[i + 1].max(txs[i + 1].gas_price);
+    }
+
+    (1..txs.len())
+        .filter(|&b| !sandwiched.contains(&b))
+        .filter(|&b| {
+            let (target, tx) = (&txs[b - 1], &txs[b]);
+            target.from != tx.from
+                && target.gas_price == tx.gas_price
+                && later_max[b] > tx.gas_price
+        })
+        .map(|b| MevFinding {
+            kind: BundleType::BackRunning,
+            searcher: H160(txs[b].from),
+            victim: H160(txs[b - 1].from),
+            positions: vec![b - 1, b],
+        })
+        .collect()
+}
+
+/// (inverted cross-sender pairs, all cross-sender pairs)
+fn fee_order_inversions(txs: &[TransactionCandidate]) -> (u64, u64) {
+    let mut inversions = 0u64;
+    let mut pairs = 0u64;
+    for (i, earlier) in txs.iter().enumerate() {
+        for later in txs[i + 1..]
+            .iter()
+            .filter(|later| later.from != earlier.from)
+        {
+            pairs += 1;
+            inversions += u64::from(later.gas_price > earlier.gas_price);
+        }
+    }
+    (inversions, pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(sender: u8, nonce: u64, gas_price: u64) -> TransactionCandidate {
+        TransactionCandidate {
+            transaction: vec![sender, nonce as u8],
+            from: [sender; 20],
+            nonce,
+            gas_price: U256::from(gas_price),
+            gas_limit: 21_000,
+            signature_valid: true,
+        }
+    }
+
+    fn context(block_number: u64) -> MevContext {
+        MevContext {
+            block_number,
+            gas_limit: 30_000_000,
+            min_gas_price: U256::from(10),
+        }
+    }
+
+    #[test]
+    fn test_fee_ordered_block_is_clean() {
+        let block = vec![tx(1, 0, 50), tx(2, 0, 40), tx(3, 0, 30)];
+        let report = MevDetector::default().analyze(context(1), &block, &block);
+
+        assert!(report.sandwiches.is_empty());
+        assert!(report.backruns.is_empty());
+        assert_eq!(report.fee_order_inversions, 0);
+        assert_eq!(report.reorder_distance, 0.0);
+        assert_eq!(report.dropped.included, 3);
+    }
+
+    #[test]
+    fn test_sandwich_detected() {
+        // Sender 9 front-runs sender 2 at a higher price and exits below it
+        let block = vec![tx(9, 0, 100), tx(2, 0, 50), tx(9, 1, 50), tx(3, 0, 20)];
+        let report = MevDetector::default().analyze(context(1), &block, &block);
+
+        assert_eq!(report.sandwiches.len(), 1);
+        let finding = &report.sandwiches[0];
+        assert_eq!(finding.kind, BundleType::Sandwich);
+        assert_eq!(finding.searcher, H160([9; 20]));
+        assert_eq!(finding.victim, H160([2; 20]));
+        assert_eq!(finding.positions, vec![0, 1, 2]);
+        // The back-run leg is not reported twice
+        assert!(report.backruns.is_empty());
+    }
+
+    #[test]
+    fn test_backrun_and_reorder_distance() {
+        // Sender 7 matches sender 2's price and lands ahead of a better payer
+        let block = vec![tx(2, 0, 50), tx(7, 0, 50), tx(3, 0, 80)];
+        let report = MevDetector::default().analyze(context(1), &block, &block);
+
+        assert_eq!(report.backruns.len(), 1);
+        assert_eq!(report.backruns[0].searcher, H160([7; 20]));
+        assert_eq!(report.backruns[0].positions, vec![0, 1]);
+        assert_eq!(report.fee_order_inversions, 2);
+        assert!((report.reorder_distance - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_same_sender_pairs_not_counted() {
+        let block = vec![tx(1, 0, 10), tx(1, 1, 90)];
+        let report = MevDetector::default().analyze(context(1), &block, &block);
+        assert_eq!(report.fee_order_inversions, 0);
+    }
+
+    #[test]
+    fn test_dropped_census_tracks_censorship() {
+        let mut detector = MevDetector::new(3);
+        let cheap = tx(4, 0, 5);
+        let big = TransactionCandidate {
+            gas_limit: 40_000_000,
+            ..tx(5, 0, 20)
+        };
+        let ignored = tx(6, 0, 60);
+        let considered = vec![tx(1, 0, 50), cheap, big, ignored];
+        let included = vec![tx(1, 0, 50)];
+
+        let first = detector.analyze(context(1), &considered, &included);
+        assert_eq!(first.dropped.below_min_gas_price, 1);
+        assert_eq!(first.dropped.over_gas_limit, 1);
+        assert_eq!(first.dropped.unexplained, 1);
+        assert_eq!(first.dropped.censorship_suspects, 0);
+
+        detector.analyze(context(2), &considered, &included);
+        let third = detector.analyze(context(3), &considered, &included);
+        assert_eq!(third.dropped.oldest_pending_blocks, 3);
+        assert_eq!(third.dropped.censorship_suspects, 3);
+
+        // Once included, a transaction stops counting
+        let all = detector.analyze(context(4), &considered, &considered);
+        assert_eq!(
+            all.dropped,
+            DroppedCensus {
+                considered: 4,
+                included: 4,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -13,10 +13,14 @@
 //!
 //! ## Services
 //!
-//! - `TransactionSelector`: Optimal transaction selection (greedy knapsack)
+//! - `TransactionSelector`: Optimal transaction selection (greedy knapsack over nonce chains)
 //! - `StatePrefetchCache`: State simulation and caching
 //! - `NonceValidator`: Nonce ordering validation
 //! - `CircuitBreaker`: Downstream subsystem resilience
+//! - `BlockFillPolicy`: When to build a template from the mempool
+//! - `ExternalWorkRegistry`: Templates issued to external producers
+//! - `MevDetector`: Per-block MEV heuristics and dropped-transaction census
+//! - `AttestationPool`: Verified attestations awaiting a PoS proposal
 //!
 //! ## Invariants
 //!
@@ -32,25 +36,42 @@
 //! - Domain entities: ✅ Implemented
 //! - TransactionSelector service: ✅ Basic implementation
 //! - CircuitBreaker: ✅ Implemented (Phase 3)
-//! - StatePrefetchCache: Planned for Phase 4
+//! - StatePrefetchCache: ✅ Retained across rounds (simulation still mocked)
 //! - Invariant checkers: ✅ Core invariants implemented
 
 pub mod circuit_breaker;
 pub mod difficulty;
+pub mod difficulty_replay;
 pub mod difficulty_window;
 mod entities;
+pub mod external_work;
+pub mod fill_policy;
 pub mod genesis;
 pub mod invariants;
+pub mod mev;
+pub mod proposal;
 mod services;
+pub mod throttle;
 
 pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState, CircuitStats};
-pub use difficulty::{BlockInfo, DifficultyAdjuster, DifficultyConfig};
+pub use difficulty::{
+    AlgorithmActivation, BlockInfo, DifficultyAdjuster, DifficultyAlgorithm, DifficultyConfig,
+};
+pub use difficulty_replay::{replay_history, ReplayReport};
 pub use difficulty_window::{
     BlockDifficultyInfo, DifficultyWindowCalculator, DifficultyWindowConfig,
 };
 pub use entities::*;
+pub use external_work::{
+    ChainHead, ExternalWorkRegistry, IssuedTemplate, SealedBlock, WorkSubmitError,
+};
+pub use fill_policy::{BlockFillPolicy, FillDecision};
 pub use genesis::*;
 pub use invariants::*;
+pub use mev::{DroppedCensus, MevContext, MevDetector, MevFinding, MevReport};
+pub use proposal::{AttestationPool, SignedProposal};
 pub use services::{
-    AccountState, NonceValidator, PoSProposer, PoWMiner, StatePrefetchCache, TransactionSelector,
+    AccountState, NonceValidator, PoSProposer, PoWMiner, PrefetchReuse, PrefetchStats,
+    StatePrefetchCache, TransactionSelector,
 };
+pub use throttle::{MiningThrottle, MiningWindow, SystemLoad, ThrottleReason, TimeOfDay};
@@ -0,0 +1,169 @@
+//! PoS block proposals
+//!
+//! A proposal carries the attestations this node has seen for the parent
+//! block. Attestations arrive one at a time (verified by Signature
+//! Verification, qc-10) and are pooled per block until a proposal needs them:
+//!
+//! - One attestation per validator and block; a later epoch replaces an
+//!   earlier one.
+//! - At most `max_per_block` attestations are kept for a block.
+//! - Only the most recently attested blocks are tracked; attestations for an
+//!   older block are dropped with it.
+
+use super::{BlockTemplate, ProposerDuty};
+use shared_types::entities::{Attestation, Hash};
+use std::collections::VecDeque;
+
+/// Default number of attestations kept per block
+pub const DEFAULT_MAX_ATTESTATIONS: usize = 128;
+
+/// Blocks attestations are pooled for at once
+const TRACKED_BLOCKS: usize = 32;
+
+/// Verified attestations awaiting inclusion, grouped by attested block
+#[derive(Debug)]
+pub struct AttestationPool {
+    /// Attested blocks, oldest first
+    blocks: VecDeque<(Hash, Vec<Attestation>)>,
+    max_per_block: usize,
+}
+
+impl Default for AttestationPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ATTESTATIONS)
+    }
+}
+
+impl AttestationPool {
+    /// Create a pool keeping up to `max_per_block` attestations per block
+    pub fn new(max_per_block: usize) -> Self {
+        Self {
+            blocks: VecDeque::new(),
+            max_per_block,
+        }
+    }
+
+    /// Add a verified attestation
+    ///
+    /// Returns false if it was not kept: the validator already attested to
+    /// the block in the same or a later epoch, or the block is full.
+    pub fn add(&mut self, attestation: Attestation) -> bool {
+        let index = match self
+            .blocks
+            .iter()
+            .position(|(hash, _)| *hash == attestation.block_hash)
+        {
+            Some(index) => index,
+            None => {
+                if self.blocks.len() == TRACKED_BLOCKS {
+                    self.blocks.pop_front();
+                }
+                self.blocks.push_back((attestation.block_hash, Vec::new()));
+                self.blocks.len() - 1
+            }
+        };
+
+        let attestations = &mut self.blocks[index].1;
+        if let Some(existing) = attestations
+            .iter_mut()
+            .find(|existing| existing.validator == attestation.validator)
+        {
+            let newer = attestation.epoch > existing.epoch;
+            if newer {
+                *existing = attestation;
+            }
+            return newer;
+        }
+        if attestations.len() >= self.max_per_block {
+            return false;
+        }
+        attestations.push(attestation);
+        true
+    }
+
+    /// Attestations for `block_hash`, one per validator, ordered by validator
+    pub fn aggregate(&self, block_hash: &Hash) -> Vec<Attestation> {
+        let mut attestations = self
+            .blocks
+            .iter()
+            .find(|(hash, _)| hash == block_hash)
+            .map(|(_, attestations)| attestations.clone())
+            .unwrap_or_default();
+        attestations.sort_by_key(|a| a.validator);
+        attestations
+    }
+
+    /// Number of attestations pooled across all blocks
+    pub fn len(&self) -> usize {
+        self.blocks.iter().map(|(_, a)| a.len()).sum()
+    }
+
+    /// Whether no attestations are pooled
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Block signed by this node for an assigned slot
+#[derive(Clone, Debug)]
+pub struct SignedProposal {
+    /// Duty the block was proposed for
+    pub duty: ProposerDuty,
+    /// Block template (no nonce)
+    pub template: BlockTemplate,
+    /// Attestations for the parent block
+    pub attestations: Vec<Attestation>,
+    /// Block hash (SHA-256d of the header)
+    pub block_hash: Hash,
+    /// Proposer signature over the serialized header
+    pub signature: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attestation(block: u8, validator: u8, epoch: u64) -> Attestation {
+        Attestation {
+            block_hash: [block; 32],
+            epoch,
+            validator: [validator; 32],
+            signature: [0u8; 64],
+        }
+    }
+
+    #[test]
+    fn test_one_attestation_per_validator() {
+        let mut pool = AttestationPool::default();
+        assert!(pool.add(attestation(1, 2, 5)));
+        assert!(pool.add(attestation(1, 1, 5)));
+
+        // Same or older epoch is a duplicate; a newer one replaces it
+        assert!(!pool.add(attestation(1, 2, 5)));
+        assert!(!pool.add(attestation(1, 2, 4)));
+        assert!(pool.add(attestation(1, 2, 6)));
+
+        let aggregated = pool.aggregate(&[1; 32]);
+        let summary: Vec<_> = aggregated
+            .iter()
+            .map(|a| (a.validator[0], a.epoch))
+            .collect();
+        assert_eq!(summary, vec![(1, 5), (2, 6)]);
+        assert!(pool.aggregate(&[9; 32]).is_empty());
+    }
+
+    #[test]
+    fn test_pool_bounds() {
+        let mut pool = AttestationPool::new(2);
+        assert!(pool.add(attestation(1, 1, 0)));
+        assert!(pool.add(attestation(1, 2, 0)));
+        assert!(!pool.add(attestation(1, 3, 0)));
+
+        // The oldest block is dropped once too many blocks are tracked
+        for block in 2..=TRACKED_BLOCKS as u8 + 1 {
+            pool.add(attestation(block, 1, 0));
+        }
+        assert!(pool.aggregate(&[1; 32]).is_empty());
+        assert_eq!(pool.len(), TRACKED_BLOCKS);
+    }
+}
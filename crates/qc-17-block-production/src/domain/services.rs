@@ -3,7 +3,7 @@
 use super::entities::*;
 use crate::error::{BlockProductionError, Result};
 use primitive_types::U256;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Transaction selector service (core domain logic)
 ///
@@ -35,127 +35,85 @@ impl TransactionSelector {
         self.fair_ordering
     }
 
-    /// Select optimal transaction set using greedy knapsack
+    /// Select optimal transaction set using greedy knapsack over nonce chains
+    ///
+    /// Each sender's candidates form a nonce chain: sorted by nonce, cut at
+    /// the first gap or the first transaction below the minimum gas price
+    /// (for a repeated nonce the best-paying one is kept). Chains are taken
+    /// as packages, scored by cumulative effective fee per gas
+    /// (`Σ gas_price·gas_limit / Σ gas_limit`) over the chain prefix that
+    /// scores best, so a cheap transaction is selected together with the
+    /// expensive ones that depend on it and never the other way round.
+    ///
+    /// Packages are included best-first and simulated in nonce order. A
+    /// package that no longer fits is cut down to what does; a failed
+    /// simulation drops the rest of that sender's chain.
     ///
     /// Algorithm: Priority-Based Greedy Knapsack (O(n log n))
-    /// Complexity: O(n log n)
+    /// Complexity: O(n log n) heap operations, O(chain length) per package
     #[tracing::instrument(skip(self, candidates, state_cache), fields(candidate_count = candidates.len()))]
     pub fn select_transactions(
         &self,
         candidates: Vec<TransactionCandidate>,
         state_cache: &mut StatePrefetchCache,
     ) -> Result<Vec<Vec<u8>>> {
-        use std::collections::{BinaryHeap, HashMap};
+        use std::collections::BinaryHeap;
 
         if candidates.is_empty() {
             return Ok(vec![]);
         }
 
-        // Step 1: Group transactions by sender
-        let mut sender_txs: HashMap<[u8; 20], Vec<TransactionCandidate>> = HashMap::new();
-        for tx in candidates {
-            // Filter by minimum gas price
-            if tx.gas_price < self.min_gas_price {
-                continue;
-            }
-            sender_txs.entry(tx.from).or_default().push(tx);
-        }
-
-        // Step 2: Sort each sender's transactions by nonce (ascending)
-        for txs in sender_txs.values_mut() {
-            txs.sort_by_key(|tx| tx.nonce);
-        }
-
-        // Step 3: Build priority queue by gas price (max heap)
-        #[derive(Debug)]
-        struct TxRef {
-            from: [u8; 20],
-            idx: usize,
-            gas_price: U256,
-        }
-
-        impl PartialEq for TxRef {
-            fn eq(&self, other: &Self) -> bool {
-                self.gas_price == other.gas_price
-            }
-        }
-
-        impl Eq for TxRef {}
-
-        impl PartialOrd for TxRef {
-            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-                Some(self.cmp(other))
-            }
-        }
-
-        impl Ord for TxRef {
-            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-                self.gas_price.cmp(&other.gas_price)
-            }
-        }
+        let mut chains = self.nonce_chains(candidates);
+        let mut queue: BinaryHeap<ChainPackage> = chains
+            .iter()
+            .filter_map(|(from, chain)| ChainPackage::best(*from, chain, self.gas_limit))
+            .collect();
 
-        let mut pq: BinaryHeap<TxRef> = BinaryHeap::new();
-        let mut sender_indices: HashMap<[u8; 20], usize> = HashMap::new();
-
-        // Initialize with first transaction from each sender
-        for (from, txs) in &sender_txs {
-            if !txs.is_empty() {
-                pq.push(TxRef {
-                    from: *from,
-                    idx: 0,
-                    gas_price: txs[0].gas_price,
-                });
-                sender_indices.insert(*from, 0);
-            }
-        }
-
-        // Step 4: Greedy selection with simulation
         let mut selected = Vec::new();
         let mut total_gas = 0u64;
 
         tracing::debug!(
-            "Starting greedy selection: {} sender groups, gas_limit={}",
-            sender_txs.len(),
+            "Starting greedy selection: {} sender chains, gas_limit={}",
+            chains.len(),
             self.gas_limit
         );
 
-        while let Some(tx_ref) = pq.pop() {
-            let sender_txs_list = &sender_txs[&tx_ref.from];
-            let tx = &sender_txs_list[tx_ref.idx];
-
-            // Check if we have space for this transaction
-            if total_gas + tx.gas_limit > self.gas_limit {
-                continue; // Skip, try next
+        while let Some(package) = queue.pop() {
+            let Some(chain) = chains.get_mut(&package.from) else {
+                continue;
+            };
+            let remaining_gas = self.gas_limit - total_gas;
+            if package.gas > remaining_gas {
+                // Cut down to the best prefix that still fits
+                queue.extend(ChainPackage::best(package.from, chain, remaining_gas));
+                continue;
             }
 
-            // Simulate transaction
-            let sim_result = state_cache.simulate_transaction(&tx.transaction);
-
-            if sim_result.success && total_gas + sim_result.gas_used <= self.gas_limit {
-                // Accept transaction
+            let mut included = 0;
+            for tx in &chain[..package.len] {
+                let sim_result = state_cache.simulate_transaction(&tx.transaction);
+                if !sim_result.success
+                    || total_gas.saturating_add(sim_result.gas_used) > self.gas_limit
+                {
+                    break;
+                }
                 selected.push(tx.transaction.clone());
                 total_gas += sim_result.gas_used;
-
-                // Apply state changes
                 state_cache.apply_state_changes(&sim_result.state_changes);
+                included += 1;
+            }
 
-                // Add next transaction from same sender to priority queue
-                let next_idx = tx_ref.idx + 1;
-                if next_idx < sender_txs_list.len() {
-                    let next_tx = &sender_txs_list[next_idx];
-
-                    // Verify nonce is sequential
-                    if next_tx.nonce == tx.nonce + 1 {
-                        pq.push(TxRef {
-                            from: tx_ref.from,
-                            idx: next_idx,
-                            gas_price: next_tx.gas_price,
-                        });
-                        sender_indices.insert(tx_ref.from, next_idx);
-                    }
-                }
+            if included < package.len {
+                // Later nonces depend on the transaction left out
+                chain.clear();
+                continue;
             }
-            // If simulation failed, skip this sender's remaining transactions
+            chain.drain(..included);
+            queue.extend(ChainPackage::best(
+                package.from,
+                chain,
+                self.gas_limit - total_gas,
+            ));
         }
 
         tracing::info!(
@@ -168,6 +126,34 @@ impl TransactionSelector {
         Ok(selected)
     }
 
+    /// Group candidates into per-sender executable nonce chains
+    fn nonce_chains(
+        &self,
+        candidates: Vec<TransactionCandidate>,
+    ) -> HashMap<[u8; 20], Vec<TransactionCandidate>> {
+        let mut chains: HashMap<[u8; 20], Vec<TransactionCandidate>> = HashMap::new();
+        for tx in candidates {
+            chains.entry(tx.from).or_default().push(tx);
+        }
+
+        for chain in chains.values_mut() {
+            // Highest gas price first within a nonce, so dedup keeps it
+            chain.sort_by(|a, b| a.nonce.cmp(&b.nonce).then(b.gas_price.cmp(&a.gas_price)));
+            chain.dedup_by_key(|tx| tx.nonce);
+
+            let executable = chain
+                .iter()
+                .enumerate()
+                .take_while(|(i, tx)| {
+                    tx.nonce == chain[0].nonce + *i as u64 && tx.gas_price >= self.min_gas_price
+                })
+                .count();
+            chain.truncate(executable);
+        }
+        chains.retain(|_, chain| !chain.is_empty());
+        chains
+    }
+
     /// Validate nonce ordering for a set of transactions
     ///
     /// Validates that transaction nonces are sequential per sender.
@@ -189,19 +175,146 @@ impl TransactionSelector {
     }
 }
 
+/// Prefix of a sender's nonce chain, selected as a unit
+#[derive(Clone, Copy, Debug)]
+struct ChainPackage {
+    /// Sender
+    from: [u8; 20],
+    /// Transactions in the prefix
+    len: usize,
+    /// Cumulative gas limit
+    gas: u64,
+    /// Cumulative effective fee (gas price × gas limit)
+    fees: U256,
+}
+
+impl ChainPackage {
+    /// Best-scoring prefix of `chain` within `gas_budget` (longest on ties)
+    fn best(from: [u8; 20], chain: &[TransactionCandidate], gas_budget: u64) -> Option<Self> {
+        let mut prefix = Self {
+            from,
+            len: 0,
+            gas: 0,
+            fees: U256::zero(),
+        };
+        let mut best: Option<Self> = None;
+
+        for tx in chain {
+            if prefix.gas.saturating_add(tx.gas_limit) > gas_budget {
+                break;
+            }
+            prefix.len += 1;
+            prefix.gas += tx.gas_limit;
+            // Saturates instead of panicking on an absurd gas price
+            let fee = tx.gas_price.saturating_mul(U256::from(tx.gas_limit));
+            prefix.fees = prefix.fees.saturating_add(fee);
+            if best.is_none_or(|best| prefix.score_cmp(&best).is_ge()) {
+                best = Some(prefix);
+            }
+        }
+        best
+    }
+
+    /// Compare cumulative fee per gas without dividing
+    ///
+    /// The cross products are widened to 512 bits so they cannot overflow.
+    fn score_cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let gas = |package: &Self| U256::from(package.gas.max(1));
+        self.fees
+            .full_mul(gas(other))
+            .cmp(&other.fees.full_mul(gas(self)))
+    }
+}
+
+impl PartialEq for ChainPackage {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for ChainPackage {}
+
+impl PartialOrd for ChainPackage {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ChainPackage {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Lower sender address wins ties, for a deterministic block
+        self.score_cmp(other)
+            .then_with(|| other.from.cmp(&self.from))
+    }
+}
+
 /// State prefetch cache for simulation
 ///
 /// Caches account states and storage slots to avoid re-reading during
 /// transaction simulation.
+///
+/// The cache is kept across production rounds. Each round starts with
+/// [`begin_round`](StatePrefetchCache::begin_round) on the parent state root.
+/// If that root is the one the cache was built on, or the root of the block
+/// committed from it, only the keys written during the last round are
+/// dropped (they hold simulated values); any other root empties the cache.
 pub struct StatePrefetchCache {
     /// Parent state root (used for cache invalidation)
     parent_state_root: primitive_types::H256,
 
+    /// State root of the block committed from this round, if any
+    committed_state_root: Option<primitive_types::H256>,
+
     /// Cached account states
     accounts: HashMap<[u8; 20], AccountState>,
 
     /// Cached storage slots
     storage: HashMap<([u8; 20], primitive_types::H256), Vec<u8>>,
+
+    /// Accounts written this round
+    touched_accounts: HashSet<[u8; 20]>,
+
+    /// Storage slots written this round
+    touched_storage: HashSet<([u8; 20], primitive_types::H256)>,
+
+    /// Lookups served from the cache this round
+    hits: u64,
+
+    /// Lookups not in the cache this round
+    misses: u64,
+}
+
+/// How [`StatePrefetchCache::begin_round`] treated the cached entries
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrefetchReuse {
+    /// Same parent state root; last round's writes were dropped
+    SameRoot,
+    /// Parent is the block committed from the cache; its writes were dropped
+    Advanced,
+    /// Unrelated state root; the cache was emptied
+    Rebuilt,
+}
+
+/// Prefetch cache counters for the current round
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrefetchStats {
+    /// Lookups served from the cache
+    pub hits: u64,
+    /// Lookups not in the cache
+    pub misses: u64,
+    /// Cached accounts and storage slots
+    pub entries: usize,
+}
+
+impl PrefetchStats {
+    /// Fraction of lookups served from the cache (0.0 without lookups)
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / lookups as f64
+    }
 }
 
 /// Account state snapshot
@@ -222,8 +335,101 @@ impl StatePrefetchCache {
     pub fn new(parent_state_root: primitive_types::H256) -> Self {
         Self {
             parent_state_root,
+            committed_state_root: None,
             accounts: HashMap::new(),
             storage: HashMap::new(),
+            touched_accounts: HashSet::new(),
+            touched_storage: HashSet::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Start a production round on `parent_state_root`
+    ///
+    /// Keeps the entries still valid at that root and resets the hit counters.
+    pub fn begin_round(&mut self, parent_state_root: primitive_types::H256) -> PrefetchReuse {
+        let reuse = if parent_state_root == self.parent_state_root {
+            PrefetchReuse::SameRoot
+        } else if self.committed_state_root == Some(parent_state_root) {
+            PrefetchReuse::Advanced
+        } else {
+            PrefetchReuse::Rebuilt
+        };
+
+        if reuse == PrefetchReuse::Rebuilt {
+            self.accounts.clear();
+            self.storage.clear();
+        } else {
+            for address in &self.touched_accounts {
+                self.accounts.remove(address);
+            }
+            for key in &self.touched_storage {
+                self.storage.remove(key);
+            }
+        }
+
+        self.parent_state_root = parent_state_root;
+        self.committed_state_root = None;
+        self.touched_accounts.clear();
+        self.touched_storage.clear();
+        self.hits = 0;
+        self.misses = 0;
+        reuse
+    }
+
+    /// Record the state root of the block committed from this round
+    ///
+    /// A round starting on that root keeps the cache.
+    pub fn commit_block(&mut self, state_root: primitive_types::H256) {
+        self.committed_state_root = Some(state_root);
+    }
+
+    /// Cache an account state read from State Management
+    pub fn prefetch_account(&mut self, address: [u8; 20], state: AccountState) {
+        self.accounts.insert(address, state);
+    }
+
+    /// Cache a storage slot read from State Management
+    pub fn prefetch_storage(
+        &mut self,
+        address: [u8; 20],
+        key: primitive_types::H256,
+        value: Vec<u8>,
+    ) {
+        self.storage.insert((address, key), value);
+    }
+
+    /// Look up an account, counting a hit or miss
+    pub fn lookup_account(&mut self, address: [u8; 20]) -> Option<&AccountState> {
+        let account = self.accounts.get(&address);
+        match account {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+        account
+    }
+
+    /// Look up a storage slot, counting a hit or miss
+    pub fn lookup_storage(
+        &mut self,
+        address: [u8; 20],
+        key: primitive_types::H256,
+    ) -> Option<&Vec<u8>> {
+        let value = self.storage.get(&(address, key));
+        match value {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+        value
+    }
+
+    /// Counters for the current round
+    pub fn stats(&self) -> PrefetchStats {
+        PrefetchStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.accounts.len() + self.storage.len(),
         }
     }
 
@@ -273,25 +479,28 @@ impl StatePrefetchCache {
     /// Apply simulation result to cache
     pub fn apply_state_changes(&mut self, changes: &[StateChange]) {
         for change in changes {
-            // Update cached account state
-            let account = self
-                .accounts
-                .entry(change.address)
-                .or_insert_with(|| AccountState {
-                    nonce: 0,
-                    balance: U256::zero(),
-                    code_hash: None,
-                });
-
             // Apply the change (simplified)
-            if change.storage_key.is_none() {
-                // This is a balance/nonce change
-                // In real implementation, we'd parse the change properly
-                account.nonce += 1; // Increment nonce for any state change
-            } else {
-                // Storage slot change
-                let key = (change.address, change.storage_key.unwrap());
-                self.storage.insert(key, change.new_value.clone());
+            match change.storage_key {
+                None => {
+                    // This is a balance/nonce change
+                    // In real implementation, we'd parse the change properly
+                    let account =
+                        self.accounts
+                            .entry(change.address)
+                            .or_insert_with(|| AccountState {
+                                nonce: 0,
+                                balance: U256::zero(),
+                                code_hash: None,
+                            });
+                    account.nonce += 1; // Increment nonce for any state change
+                    self.touched_accounts.insert(change.address);
+                }
+                Some(storage_key) => {
+                    // Storage slot change
+                    let key = (change.address, storage_key);
+                    self.storage.insert(key, change.new_value.clone());
+                    self.touched_storage.insert(key);
+                }
             }
         }
     }
@@ -528,6 +737,25 @@ impl PoWMiner {
         // Check against difficulty
         meets_difficulty(&final_hash, difficulty_target)
     }
+
+    /// Recompute the hash for a nonce reported by a compute engine
+    ///
+    /// `header_template` is the header serialized without a nonce, as handed
+    /// to `ComputeEngine::pow_mine`. Returns the hash if it meets the target.
+    pub fn verify_nonce(
+        header_template: &[u8],
+        nonce: u64,
+        difficulty_target: U256,
+    ) -> Option<[u8; 32]> {
+        use crate::utils::hashing::{meets_difficulty, sha256d};
+
+        let mut header_bytes = Vec::with_capacity(header_template.len() + 8);
+        header_bytes.extend_from_slice(header_template);
+        header_bytes.extend_from_slice(&nonce.to_le_bytes());
+
+        let hash = sha256d(&header_bytes);
+        meets_difficulty(&hash, difficulty_target).then_some(hash)
+    }
 }
 
 /// PoS proposer service
@@ -684,10 +912,200 @@ mod tests {
         assert!(selector.fair_ordering);
     }
 
+    /// Candidate whose payload is `[sender, nonce]` (even length, so the
+    /// mock simulation succeeds)
+    fn candidate(sender: u8, nonce: u64, gwei: u64) -> TransactionCandidate {
+        TransactionCandidate {
+            transaction: vec![sender, nonce as u8],
+            from: [sender; 20],
+            nonce,
+            gas_price: U256::from(gwei) * U256::exp10(9),
+            gas_limit: 21_000,
+            signature_valid: true,
+        }
+    }
+
+    fn select(gas_limit: u64, candidates: Vec<TransactionCandidate>) -> Vec<(u8, u8)> {
+        let selector = TransactionSelector::new(gas_limit, U256::exp10(9), true);
+        let mut cache = StatePrefetchCache::new(primitive_types::H256::zero());
+        selector
+            .select_transactions(candidates, &mut cache)
+            .unwrap()
+            .iter()
+            .map(|tx| (tx[0], tx[1]))
+            .collect()
+    }
+
+    #[test]
+    fn test_cheap_parent_selected_with_expensive_child() {
+        // A's chain averages 55 gwei, B's best prefix is B0 alone at 50
+        let candidates = vec![
+            candidate(0xb, 0, 50),
+            candidate(0xa, 1, 100),
+            candidate(0xb, 1, 40),
+            candidate(0xa, 0, 10),
+        ];
+        assert_eq!(select(42_000, candidates), vec![(0xa, 0), (0xa, 1)]);
+    }
+
+    #[test]
+    fn test_interleaved_senders_keep_nonce_order() {
+        let candidates = vec![
+            candidate(1, 2, 90),
+            candidate(2, 0, 5),
+            candidate(3, 0, 60),
+            candidate(1, 0, 2),
+            candidate(2, 1, 70),
+            candidate(3, 1, 65),
+            candidate(1, 1, 3),
+            candidate(2, 2, 1),
+            candidate(3, 3, 99), // gap after nonce 1
+        ];
+        let selected = select(30_000_000, candidates);
+        assert_eq!(selected.len(), 8);
+        assert!(!selected.contains(&(3, 3)));
+
+        for sender in 1..=3 {
+            let nonces: Vec<u8> = selected
+                .iter()
+                .filter(|(from, _)| *from == sender)
+                .map(|(_, nonce)| *nonce)
+                .collect();
+            let expected: Vec<u8> = (0..nonces.len() as u8).collect();
+            assert_eq!(nonces, expected, "sender {} out of nonce order", sender);
+        }
+    }
+
+    #[test]
+    fn test_package_cut_to_remaining_gas() {
+        // B0 goes first; A's three-transaction package then no longer fits
+        let candidates = vec![
+            candidate(0xa, 0, 10),
+            candidate(0xa, 1, 90),
+            candidate(0xa, 2, 90),
+            candidate(0xb, 0, 95),
+        ];
+        assert_eq!(
+            select(63_000, candidates),
+            vec![(0xb, 0), (0xa, 0), (0xa, 1)]
+        );
+    }
+
+    #[test]
+    fn test_chain_stops_at_failure_and_min_gas_price() {
+        let mut failing = candidate(1, 1, 50);
+        failing.transaction.push(0); // odd length: mock simulation fails
+        let candidates = vec![
+            candidate(1, 0, 50),
+            failing,
+            candidate(1, 2, 50),
+            candidate(2, 0, 0), // below the 1 gwei minimum
+            candidate(2, 1, 90),
+            candidate(3, 0, 20),
+            candidate(3, 0, 30), // replacement for the same nonce
+        ];
+        assert_eq!(select(30_000_000, candidates), vec![(1, 0), (3, 0)]);
+    }
+
+    #[test]
+    fn test_huge_gas_price_does_not_overflow() {
+        let mut whale = candidate(1, 0, 0);
+        whale.gas_price = U256::MAX;
+        let mut whale_child = candidate(1, 1, 0);
+        whale_child.gas_price = U256::MAX;
+        let candidates = vec![whale, whale_child, candidate(2, 0, 50)];
+        assert_eq!(select(30_000_000, candidates), vec![(1, 0), (1, 1), (2, 0)]);
+    }
+
     #[test]
     fn test_state_cache_creation() {
         let cache = StatePrefetchCache::new(primitive_types::H256::zero());
         assert_eq!(cache.get_nonce([0u8; 20]), 0);
         assert_eq!(cache.get_balance([0u8; 20]), U256::zero());
     }
+
+    fn account(nonce: u64) -> AccountState {
+        AccountState {
+            nonce,
+            balance: U256::from(1_000),
+            code_hash: None,
+        }
+    }
+
+    /// Cache on root 1 holding accounts 1 and 2 and slot (1, 7); round one
+    /// wrote account 2 and slot (1, 7)
+    fn cache_after_round() -> StatePrefetchCache {
+        let slot = primitive_types::H256::from_low_u64_be(7);
+        let mut cache = StatePrefetchCache::new(primitive_types::H256::from_low_u64_be(1));
+        cache.prefetch_account([1u8; 20], account(0));
+        cache.prefetch_account([2u8; 20], account(0));
+        cache.prefetch_storage([1u8; 20], slot, vec![1]);
+        cache.apply_state_changes(&[
+            StateChange {
+                address: [2u8; 20],
+                storage_key: None,
+                old_value: vec![],
+                new_value: vec![],
+            },
+            StateChange {
+                address: [1u8; 20],
+                storage_key: Some(slot),
+                old_value: vec![1],
+                new_value: vec![2],
+            },
+        ]);
+        cache
+    }
+
+    #[test]
+    fn test_prefetch_cache_kept_on_committed_root() {
+        let slot = primitive_types::H256::from_low_u64_be(7);
+        let mut cache = cache_after_round();
+        cache.commit_block(primitive_types::H256::from_low_u64_be(2));
+
+        let reuse = cache.begin_round(primitive_types::H256::from_low_u64_be(2));
+        assert_eq!(reuse, PrefetchReuse::Advanced);
+        assert_eq!(cache.parent_state_root().to_low_u64_be(), 2);
+
+        // Only the keys written by the committed block are refetched
+        assert!(cache.lookup_account([1u8; 20]).is_some());
+        assert!(cache.lookup_account([2u8; 20]).is_none());
+        assert!(cache.lookup_storage([1u8; 20], slot).is_none());
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 1));
+        assert!((stats.hit_rate() - 1.0 / 3.0).abs() < 1e-9);
+
+        // Another round on the same root keeps the clean entries too
+        assert_eq!(
+            cache.begin_round(primitive_types::H256::from_low_u64_be(2)),
+            PrefetchReuse::SameRoot
+        );
+        assert_eq!(
+            cache.stats(),
+            PrefetchStats {
+                entries: 1,
+                ..PrefetchStats::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_prefetch_cache_cleared_on_unrelated_root() {
+        let mut cache = cache_after_round();
+        cache.commit_block(primitive_types::H256::from_low_u64_be(2));
+
+        // A reorg onto another parent invalidates everything
+        let reuse = cache.begin_round(primitive_types::H256::from_low_u64_be(3));
+        assert_eq!(reuse, PrefetchReuse::Rebuilt);
+        assert!(cache.lookup_account([1u8; 20]).is_none());
+        assert_eq!(cache.stats().entries, 0);
+
+        // The committed root is forgotten once a round has started elsewhere
+        cache.prefetch_account([1u8; 20], account(1));
+        assert_eq!(
+            cache.begin_round(primitive_types::H256::from_low_u64_be(2)),
+            PrefetchReuse::Rebuilt
+        );
+        assert_eq!(cache.stats().entries, 0);
+    }
 }
@@ -0,0 +1,283 @@
+//! PoW mining throttle
+//!
+//! Decides how long the mining loop pauses between nonce batches so a node
+//! does not keep every core busy:
+//!
+//! - Duty cycle: after a batch that took `busy`, pause long enough that
+//!   mining only runs `duty_cycle_percent` of the time.
+//! - Load backoff: when the host's CPU load or temperature (sampled through
+//!   the `SystemLoadProbe` port) is over its limit, pause for `load_backoff`.
+//! - Mining windows: outside every configured daily window (UTC), pause until
+//!   the next window opens. No windows means mining is always allowed.
+//!
+//! The longest of the three pauses applies. The default throttle never pauses.
+
+use serde::Deserialize;
+use std::time::Duration;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Host load sample
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SystemLoad {
+    /// CPU load relative to the core count (1.0 = every core busy)
+    pub cpu_load: f64,
+    /// Hottest temperature sensor reading, if the host exposes one
+    pub temperature_celsius: Option<f64>,
+}
+
+/// Time of day in UTC, written "HH:MM"
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(try_from = "String")]
+pub struct TimeOfDay {
+    /// Seconds since midnight UTC
+    seconds: u32,
+}
+
+impl TimeOfDay {
+    /// Create from hour (0-23) and minute (0-59)
+    pub fn new(hour: u32, minute: u32) -> Option<Self> {
+        (hour < 24 && minute < 60).then_some(Self {
+            seconds: hour * 3600 + minute * 60,
+        })
+    }
+
+    /// Seconds since midnight UTC
+    pub fn seconds(&self) -> u32 {
+        self.seconds
+    }
+}
+
+impl TryFrom<String> for TimeOfDay {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value
+            .split_once(':')
+            .and_then(|(hour, minute)| Self::new(hour.parse().ok()?, minute.parse().ok()?))
+            .ok_or_else(|| format!("invalid time of day '{}', expected HH:MM", value))
+    }
+}
+
+/// Daily period in which mining is allowed
+///
+/// A window whose end is before its start runs past midnight.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub struct MiningWindow {
+    /// Start of the window (inclusive)
+    pub start: TimeOfDay,
+    /// End of the window (exclusive)
+    pub end: TimeOfDay,
+}
+
+impl MiningWindow {
+    /// Seconds from `now` (seconds since midnight) until the window opens;
+    /// zero while it is open
+    fn wait_from(&self, now: u64) -> u64 {
+        let (start, end) = (self.start.seconds as u64, self.end.seconds as u64);
+        let open = if start <= end {
+            (start..end).contains(&now)
+        } else {
+            now >= start || now < end
+        };
+        if open {
+            0
+        } else {
+            (start + SECONDS_PER_DAY - now) % SECONDS_PER_DAY
+        }
+    }
+}
+
+/// Why the mining loop pauses
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThrottleReason {
+    /// Keeping mining within its duty cycle
+    DutyCycle,
+    /// Host CPU load or temperature over the limit
+    SystemLoad,
+    /// Outside every mining window
+    OutsideWindow,
+}
+
+/// PoW mining throttle policy
+#[derive(Clone, Debug, PartialEq)]
+pub struct MiningThrottle {
+    /// Share of time spent mining, 1-100
+    pub duty_cycle_percent: u8,
+    /// CPU load above which mining backs off (None = ignore load)
+    pub max_cpu_load: Option<f64>,
+    /// Temperature above which mining backs off (None = ignore temperature)
+    pub max_temperature_celsius: Option<f64>,
+    /// Pause after a load sample over a limit
+    pub load_backoff: Duration,
+    /// Daily windows in which mining is allowed (empty = always)
+    pub windows: Vec<MiningWindow>,
+}
+
+impl Default for MiningThrottle {
+    /// Mine flat out, at any time (the behavior before throttling existed)
+    fn default() -> Self {
+        Self {
+            duty_cycle_percent: 100,
+            max_cpu_load: None,
+            max_temperature_celsius: None,
+            load_backoff: Duration::from_secs(30),
+            windows: Vec::new(),
+        }
+    }
+}
+
+impl MiningThrottle {
+    /// Whether a load probe has to be sampled
+    pub fn watches_load(&self) -> bool {
+        self.max_cpu_load.is_some() || self.max_temperature_celsius.is_some()
+    }
+
+    /// Pause after a nonce batch that kept the miner busy for `busy`
+    ///
+    /// `utc_seconds` is the current Unix time; `load` the latest probe sample.
+    pub fn pause(
+        &self,
+        busy: Duration,
+        load: Option<&SystemLoad>,
+        utc_seconds: u64,
+    ) -> Option<(Duration, ThrottleReason)> {
+        [
+            (self.duty_pause(busy), ThrottleReason::DutyCycle),
+            (
+                load.map_or(Duration::ZERO, |load| self.load_pause(load)),
+                ThrottleReason::SystemLoad,
+            ),
+            (
+                self.window_pause(utc_seconds),
+                ThrottleReason::OutsideWindow,
+            ),
+        ]
+        .into_iter()
+        .filter(|(pause, _)| !pause.is_zero())
+        .max_by_key(|(pause, _)| *pause)
+    }
+
+    /// Idle time that keeps `busy` at `duty_cycle_percent` of the total
+    fn duty_pause(&self, busy: Duration) -> Duration {
+        let duty = u32::from(self.duty_cycle_percent.clamp(1, 100));
+        busy * (100 - duty) / duty
+    }
+
+    fn load_pause(&self, load: &SystemLoad) -> Duration {
+        let cpu_over = self.max_cpu_load.is_some_and(|max| load.cpu_load > max);
+        let hot = self
+            .max_temperature_celsius
+            .zip(load.temperature_celsius)
+            .is_some_and(|(max, temperature)| temperature > max);
+        if cpu_over || hot {
+            self.load_backoff
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    fn window_pause(&self, utc_seconds: u64) -> Duration {
+        let now = utc_seconds % SECONDS_PER_DAY;
+        self.windows
+            .iter()
+            .map(|window| window.wait_from(now))
+            .min()
+            .map_or(Duration::ZERO, Duration::from_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hour: u64, minute: u64) -> u64 {
+        // Some day well after the epoch
+        19_000 * SECONDS_PER_DAY + hour * 3600 + minute * 60
+    }
+
+    fn window(start: &str, end: &str) -> MiningWindow {
+        MiningWindow {
+            start: start.to_string().try_into().unwrap(),
+            end: end.to_string().try_into().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_default_never_pauses() {
+        let throttle = MiningThrottle::default();
+        let load = SystemLoad {
+            cpu_load: 4.0,
+            temperature_celsius: Some(110.0),
+        };
+        assert_eq!(
+            throttle.pause(Duration::from_secs(5), Some(&load), at(3, 0)),
+            None
+        );
+        assert!(!throttle.watches_load());
+    }
+
+    #[test]
+    fn test_duty_cycle_and_load_backoff() {
+        let throttle = MiningThrottle {
+            duty_cycle_percent: 25,
+            max_cpu_load: Some(0.9),
+            max_temperature_celsius: Some(80.0),
+            ..MiningThrottle::default()
+        };
+        let busy = Duration::from_secs(2);
+
+        // 2s busy at 25% duty needs 6s idle
+        assert_eq!(
+            throttle.pause(busy, None, at(12, 0)),
+            Some((Duration::from_secs(6), ThrottleReason::DutyCycle))
+        );
+
+        let cool = SystemLoad {
+            cpu_load: 0.5,
+            temperature_celsius: Some(60.0),
+        };
+        let hot = SystemLoad {
+            temperature_celsius: Some(85.0),
+            ..cool
+        };
+        let loaded = SystemLoad {
+            cpu_load: 0.95,
+            temperature_celsius: None,
+        };
+        assert_eq!(
+            throttle.pause(busy, Some(&cool), at(12, 0)).unwrap().1,
+            ThrottleReason::DutyCycle
+        );
+        for load in [hot, loaded] {
+            assert_eq!(
+                throttle.pause(busy, Some(&load), at(12, 0)),
+                Some((Duration::from_secs(30), ThrottleReason::SystemLoad))
+            );
+        }
+    }
+
+    #[test]
+    fn test_mining_windows() {
+        let throttle = MiningThrottle {
+            windows: vec![window("22:00", "06:00"), window("12:00", "13:30")],
+            ..MiningThrottle::default()
+        };
+        let idle = Duration::ZERO;
+
+        for open in [at(23, 0), at(2, 0), at(12, 0), at(13, 29)] {
+            assert_eq!(throttle.pause(idle, None, open), None);
+        }
+        assert_eq!(
+            throttle.pause(idle, None, at(6, 0)),
+            Some((Duration::from_secs(6 * 3600), ThrottleReason::OutsideWindow))
+        );
+        assert_eq!(
+            throttle.pause(idle, None, at(13, 30)).unwrap().0,
+            Duration::from_secs(8 * 3600 + 30 * 60)
+        );
+
+        assert!(TimeOfDay::try_from("24:00".to_string()).is_err());
+        assert!(TimeOfDay::try_from("7".to_string()).is_err());
+    }
+}
@@ -52,6 +52,10 @@ pub enum BlockProductionError {
     #[error("Mining failed: no valid nonce found")]
     MiningFailed,
 
+    /// Host load could not be sampled
+    #[error("Load probe failed: {0}")]
+    LoadProbeFailed(String),
+
     /// Not selected as PoS proposer for this slot
     #[error("Not selected as proposer for slot {slot}")]
     NotProposer {
@@ -59,6 +63,24 @@ pub enum BlockProductionError {
         slot: u64,
     },
 
+    /// Slot is not after the last slot proposed for (double proposal)
+    #[error("Slot {slot} is not after the last proposed slot {last}")]
+    SlotAlreadyProposed {
+        /// Assigned slot
+        slot: u64,
+        /// Last slot a block was proposed for
+        last: u64,
+    },
+
+    /// Slashing protection refused to sign
+    #[error("Slashing protection refused to sign slot {slot}: {reason}")]
+    SlashingProtection {
+        /// Assigned slot
+        slot: u64,
+        /// Why signing would be unsafe
+        reason: String,
+    },
+
     /// Invalid validator key provided
     #[error("Invalid validator key")]
     InvalidValidatorKey,
@@ -174,6 +196,10 @@ pub enum BlockProductionError {
         /// Actual nonce
         actual: u64,
     },
+
+    /// Externally produced block refused
+    #[error("Block submission rejected: {0}")]
+    WorkRejected(#[from] crate::domain::WorkSubmitError),
 }
 
 impl BlockProductionError {
@@ -1,4 +1,4 @@
-//! Handler for SlotAssignedEvent from Consensus (qc-08)
+//! Handler for SlotAssignedEvent from the node runtime's validator duty task
 //!
 //! In PoS mode, when this validator is assigned a slot,
 //! this handler triggers block template creation and proposal.
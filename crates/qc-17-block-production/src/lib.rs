@@ -58,7 +58,8 @@
 //! | Event | Allowed Senders | Purpose |
 //! |-------|-----------------|---------|
 //! | `BlockFinalizedEvent` | Finality (9) | Trigger next block production |
-//! | `SlotAssignedEvent` | Consensus (8) | PoS proposer duty notification |
+//! | `SlotAssignedEvent` | Validator duty task (node runtime, on behalf of Consensus (8)) | PoS proposer duty notification |
+//! | `AttestationVerified` | Signature Verification (10) | Attestations for PoS proposals |
 //! | `NewPendingTransactionEvent` | Mempool (6) | Transaction availability hint |
 //!
 //! ### Outbound Events (Published)
@@ -66,6 +67,7 @@
 //! | Event | Target | Purpose |
 //! |-------|--------|---------|
 //! | `BlockProducedEvent` | Consensus (8), Block Storage (2) | Block ready for validation |
+//! | `BlockProposed` | Consensus (8) | Signed PoS block ready for validation |
 //! | `MiningMetrics` | Telemetry (18) | Observability data |
 //!
 //! ## Difficulty Adjustment
@@ -102,7 +104,7 @@
 //! │  ┌────────────────────────┴────────────────────────┐               │
 //! │  │               Domain Layer                       │               │
 //! │  │  - TransactionSelector (Greedy Knapsack)        │               │
-//! │  │  - DifficultyAdjuster (DGW Algorithm)           │               │
+//! │  │  - DifficultyAdjuster (DGW/LWMA)                │               │
 //! │  │  - PoWMiner (Parallel Nonce Search)             │               │
 //! │  │  - PoSProposer (VRF Selection)                  │               │
 //! │  │  - Invariant Validators                          │               │
@@ -163,6 +165,7 @@ pub mod events;
 /// Event handlers
 pub mod handler;
 pub mod ports;
+mod proposer;
 pub mod security;
 pub mod service;
 pub mod utils;
@@ -173,21 +176,23 @@ mod metrics;
 
 pub use config::{
     BlockProductionConfig, HashAlgorithm, PBFTConfig, PerformanceConfig, PoSConfig, PoWConfig,
+    ThrottleConfig,
 };
 pub use error::{BlockProductionError, Result};
 pub use metrics::Metrics;
 
 // Re-export commonly used types
 pub use domain::{
-    BlockDifficultyInfo, BlockHeader, BlockTemplate, ConsensusMode, DifficultyConfig,
-    DifficultyWindowCalculator, DifficultyWindowConfig, MiningJob, PoSProposer, PoWMiner,
-    ProposerDuty, SimulationResult, StatePrefetchCache, TransactionBundle, TransactionCandidate,
-    TransactionSelector, VRFProof,
+    AlgorithmActivation, BlockDifficultyInfo, BlockHeader, BlockTemplate, ConsensusMode,
+    DifficultyAlgorithm, DifficultyConfig, DifficultyWindowCalculator, DifficultyWindowConfig,
+    MiningJob, PoSProposer, PoWMiner, ProposerDuty, SimulationResult, StatePrefetchCache,
+    TransactionBundle, TransactionCandidate, TransactionSelector, VRFProof,
 };
 
 pub use ports::{
-    BlockProducerService, ConsensusSubmitter, EventPublisher, HistoricalBlockInfo, MempoolReader,
-    ProductionConfig, ProductionStatus, SignatureProvider, StateReader,
+    BackendHashrate, BlockProducerService, ConsensusSubmitter, EventPublisher, HistoricalBlockInfo,
+    MempoolReader, ProductionConfig, ProductionStatus, SignatureProvider, SlashingProtection,
+    StateReader, SystemLoadProbe,
 };
 
 pub use events::{
@@ -25,6 +25,15 @@ pub struct Metrics {
 
     /// Total MEV bundles detected
     pub mev_bundles_detected: AtomicU64,
+
+    /// State lookups served from the prefetch cache
+    pub prefetch_hits: AtomicU64,
+
+    /// State lookups missing from the prefetch cache
+    pub prefetch_misses: AtomicU64,
+
+    /// Templates rebuilt mid-round for better-paying transactions
+    pub template_rebuilds: AtomicU64,
 }
 
 impl Metrics {
@@ -58,6 +67,17 @@ impl Metrics {
         self.mev_bundles_detected.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Record prefetch cache lookups for a round
+    pub fn record_prefetch(&self, hits: u64, misses: u64) {
+        self.prefetch_hits.fetch_add(hits, Ordering::Relaxed);
+        self.prefetch_misses.fetch_add(misses, Ordering::Relaxed);
+    }
+
+    /// Record a mid-round template rebuild
+    pub fn record_template_rebuild(&self) {
+        self.template_rebuilds.fetch_add(1, Ordering::Relaxed);
+    }
+
     /// Get blocks produced
     pub fn get_blocks_produced(&self) -> u64 {
         self.blocks_produced.load(Ordering::Relaxed)
@@ -92,6 +112,26 @@ impl Metrics {
         let time = self.selection_time_us.load(Ordering::Relaxed);
         time as f64 / blocks as f64
     }
+
+    /// Get average template rebuilds per produced block
+    pub fn get_rebuilds_per_block(&self) -> f64 {
+        let blocks = self.blocks_produced.load(Ordering::Relaxed);
+        if blocks == 0 {
+            return 0.0;
+        }
+        let rebuilds = self.template_rebuilds.load(Ordering::Relaxed);
+        rebuilds as f64 / blocks as f64
+    }
+
+    /// Get prefetch cache hit rate across all rounds
+    pub fn get_prefetch_hit_rate(&self) -> f64 {
+        let hits = self.prefetch_hits.load(Ordering::Relaxed);
+        let lookups = hits + self.prefetch_misses.load(Ordering::Relaxed);
+        if lookups == 0 {
+            return 0.0;
+        }
+        hits as f64 / lookups as f64
+    }
 }
 
 #[cfg(test)]
@@ -119,4 +159,27 @@ mod tests {
 
         assert_eq!(metrics.get_avg_selection_time(), 5000.0);
     }
+
+    #[test]
+    fn test_prefetch_hit_rate() {
+        let metrics = Metrics::new();
+        assert_eq!(metrics.get_prefetch_hit_rate(), 0.0);
+
+        metrics.record_prefetch(6, 4);
+        metrics.record_prefetch(9, 1);
+
+        assert_eq!(metrics.get_prefetch_hit_rate(), 0.75);
+    }
+
+    #[test]
+    fn test_rebuilds_per_block() {
+        let metrics = Metrics::new();
+        assert_eq!(metrics.get_rebuilds_per_block(), 0.0);
+
+        metrics.record_block_produced(10, 1_000_000);
+        metrics.record_block_produced(10, 1_000_000);
+        metrics.record_template_rebuild();
+
+        assert_eq!(metrics.get_rebuilds_per_block(), 0.5);
+    }
 }
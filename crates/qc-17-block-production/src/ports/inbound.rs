@@ -94,7 +94,7 @@ impl Default for ProductionConfig {
 }
 
 /// Production status
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct ProductionStatus {
     /// Is currently producing blocks
     pub active: bool,
@@ -111,6 +111,9 @@ pub struct ProductionStatus {
     /// Current hashrate (PoW only)
     pub hashrate: Option<f64>,
 
+    /// Hashrate breakdown per compute backend (PoW only)
+    pub backend_hashrates: Vec<BackendHashrate>,
+
     /// Last block produced timestamp
     pub last_block_at: Option<u64>,
 
@@ -119,4 +122,29 @@ pub struct ProductionStatus {
 
     /// Last mined nonce (PoW only)
     pub last_nonce: Option<u64>,
+
+    /// Templates rebuilt mid-round for better-paying transactions (PoW only)
+    pub template_rebuilds: u64,
+}
+
+/// Mining statistics for one compute backend
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BackendHashrate {
+    /// Backend name (e.g. "OpenCL GPU", "CPU (Rayon)")
+    pub backend: String,
+
+    /// Nonces searched on this backend
+    pub hashes: u64,
+
+    /// Average hashes per second while this backend was searching
+    pub hashrate: f64,
+
+    /// Solutions accepted after CPU verification
+    pub solutions: u64,
+
+    /// Reported solutions that failed CPU verification
+    pub rejected: u64,
+
+    /// Search calls that returned an error
+    pub errors: u64,
 }
@@ -1,6 +1,6 @@
 //! Outbound ports (driven side - SPI)
 
-use crate::domain::{BlockTemplate, SimulationResult, TransactionCandidate};
+use crate::domain::{BlockTemplate, SimulationResult, SystemLoad, TransactionCandidate};
 use crate::error::Result;
 use async_trait::async_trait;
 use primitive_types::{H256, U256};
@@ -74,6 +74,23 @@ pub trait SignatureProvider: Send + Sync {
     async fn sign_block_header(&self, header_bytes: &[u8]) -> Result<Vec<u8>>;
 }
 
+/// Port: Slashing-protection record consulted before signing a proposal
+///
+/// Implementations persist what was signed so a restarted validator cannot
+/// sign a second block for a slot it already proposed in.
+pub trait SlashingProtection: Send + Sync {
+    /// Refuse if signing `signing_root` for `slot` could be slashable,
+    /// otherwise record it (before the signature is made)
+    fn check_and_record_block(&self, slot: u64, signing_root: [u8; 32]) -> Result<()>;
+}
+
+/// Port: Sample host CPU load and temperature for mining throttling
+#[async_trait]
+pub trait SystemLoadProbe: Send + Sync {
+    /// Take a load sample
+    async fn sample(&self) -> Result<SystemLoad>;
+}
+
 /// Port: Publish events to Event Bus
 #[async_trait]
 pub trait EventPublisher: Send + Sync {
@@ -0,0 +1,401 @@
+//! PoS slot proposer
+//!
+//! Runs while production is active in `ConsensusMode::ProofOfStake`. The node
+//! runtime's validator duty task (`node-runtime` `validator::run_duties`, driven
+//! by qc-08's `DutyScheduler`) publishes `SlotAssigned` when this validator is
+//! due to propose; the proposer then:
+//!
+//! 1. Reads the mempool once (a slot does not wait on the fill policy).
+//! 2. Builds the block on the chain head, paying fees to the configured
+//!    `fee_recipient`.
+//! 3. Attaches the verified attestations pooled for the parent block.
+//! 4. Signs the serialized header through the `SignatureProvider` port.
+//! 5. Publishes `BlockProposed` for qc-08 to validate.
+//!
+//! The head only moves on `BlockStored`, i.e. once a proposal has been
+//! validated and stored, never merely because it was signed.
+//!
+//! A slot at or before the last proposed slot is refused: signing two blocks
+//! for one slot is slashable.
+
+use crate::{
+    config::BlockProductionConfig,
+    domain::{
+        AttestationPool, ChainHead, ConsensusMode, ExternalWorkRegistry, MevContext, MevDetector,
+        MevReport, ProposerDuty, SignedProposal, TransactionCandidate, VRFProof,
+    },
+    error::{BlockProductionError, Result},
+    ports::{MempoolReader, ProductionStatus, SignatureProvider, SlashingProtection},
+    service::ConcreteBlockProducer,
+    utils::hashing::{serialize_block_header, sha256d},
+};
+use primitive_types::{H256, U256};
+use shared_bus::{BlockchainEvent, EventPublisher, InMemoryEventBus, Subscription};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock};
+use tracing::{info, warn};
+
+/// Proposes blocks for the slots consensus assigns to this validator
+pub(crate) struct SlotProposer {
+    pub(crate) signer: Arc<dyn SignatureProvider>,
+    pub(crate) slashing_protection: Option<Arc<dyn SlashingProtection>>,
+    pub(crate) mempool_reader: Option<Arc<dyn MempoolReader>>,
+    pub(crate) event_bus: Arc<InMemoryEventBus>,
+    pub(crate) config: BlockProductionConfig,
+    /// Chain head, shared with the service
+    pub(crate) chain_head: Arc<Mutex<ExternalWorkRegistry>>,
+    pub(crate) status: Arc<RwLock<ProductionStatus>>,
+    pub(crate) mev_reports: Arc<Mutex<VecDeque<MevReport>>>,
+    pub(crate) attestations: AttestationPool,
+    pub(crate) mev_detector: MevDetector,
+    /// Last slot a block was signed for
+    pub(crate) last_slot: Option<u64>,
+}
+
+impl SlotProposer {
+    /// Handle duties, attestations and new heads until the bus closes
+    pub(crate) async fn run(mut self, mut subscription: Subscription) {
+        info!("[qc-17] PoS proposer task started");
+        while let Some(event) = subscription.recv().await {
+            self.handle_event(event).await;
+        }
+        info!("[qc-17] PoS proposer task stopped");
+    }
+
+    async fn handle_event(&mut self, event: BlockchainEvent) {
+        match event {
+            BlockchainEvent::SlotAssigned {
+                slot,
+                epoch,
+                validator_index,
+                vrf_output,
+                vrf_proof,
+            } => {
+                let duty = ProposerDuty {
+                    slot,
+                    epoch,
+                    validator_index,
+                    vrf_proof: VRFProof {
+                        output: vrf_output,
+                        proof: vrf_proof,
+                    },
+                };
+                if let Err(e) = self.on_slot_assigned(duty).await {
+                    warn!("[qc-17] No block proposed for slot {}: {}", slot, e);
+                }
+            }
+            BlockchainEvent::AttestationVerified(attestation) => {
+                self.attestations.add(attestation);
+            }
+            BlockchainEvent::BlockStored {
+                block_height,
+                block_hash,
+            } => {
+                self.chain_head.lock().unwrap().advance_head(ChainHead {
+                    hash: H256(block_hash),
+                    height: block_height,
+                    ..ChainHead::default()
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Propose, publish and report on a block for `duty`
+    async fn on_slot_assigned(&mut self, duty: ProposerDuty) -> Result<()> {
+        let pending = match &self.mempool_reader {
+            Some(reader) => reader
+                .get_pending_transactions(
+                    self.config.performance.max_transaction_candidates,
+                    self.config.min_gas_price,
+                )
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("[qc-17] Failed to read mempool: {}", e);
+                    Vec::new()
+                }),
+            None => Vec::new(),
+        };
+        // Every candidate goes into the block; kept for the MEV report
+        let candidates = pending.clone();
+
+        let proposal = self.propose(duty, pending).await?;
+        let header = &proposal.template.header;
+        info!(
+            "[qc-17] Block #{} proposed for slot {} (epoch {}) with {} attestation(s)",
+            header.block_number,
+            proposal.duty.slot,
+            proposal.duty.epoch,
+            proposal.attestations.len()
+        );
+
+        {
+            let mut status = self.status.write().unwrap();
+            status.blocks_produced = header.block_number;
+            status.total_fees += proposal.template.total_fees;
+            status.last_block_at = Some(header.timestamp);
+        }
+        self.event_bus.publish(proposed_event(&proposal)).await;
+
+        let mut report = self.mev_detector.analyze(
+            MevContext {
+                block_number: header.block_number,
+                gas_limit: self.config.gas_limit,
+                min_gas_price: self.config.min_gas_price,
+            },
+            &candidates,
+            &candidates,
+        );
+        report.block_hash = H256(proposal.block_hash);
+        ConcreteBlockProducer::publish_mev_report(&self.event_bus, &self.mev_reports, report).await;
+        Ok(())
+    }
+
+    /// Build and sign the block for `duty` on the current head
+    async fn propose(
+        &mut self,
+        duty: ProposerDuty,
+        pending: Vec<TransactionCandidate>,
+    ) -> Result<SignedProposal> {
+        if let Some(last) = self.last_slot.filter(|last| duty.slot <= *last) {
+            return Err(BlockProductionError::SlotAlreadyProposed {
+                slot: duty.slot,
+                last,
+            });
+        }
+
+        let fee_recipient = self.config.fee_recipient.ok_or_else(|| {
+            BlockProductionError::InvalidConfig(
+                "PoS proposing requires a fee_recipient".to_string(),
+            )
+        })?;
+        let parent = self.chain_head.lock().unwrap().head();
+        let mut template = ConcreteBlockProducer::build_template(
+            &parent,
+            fee_recipient,
+            pending,
+            U256::zero(),
+            self.config.gas_limit,
+            ConsensusMode::ProofOfStake,
+        )?;
+        if !self.config.graffiti.is_empty() {
+            template.header.extra_data = self.config.graffiti.clone();
+        }
+        let header = &template.header;
+        let header_bytes = serialize_block_header(
+            &header.parent_hash,
+            header.block_number,
+            header.timestamp,
+            &header.beneficiary,
+            header.gas_used,
+            None,
+        );
+
+        let block_hash = sha256d(&header_bytes);
+        if let Some(protection) = &self.slashing_protection {
+            protection.check_and_record_block(duty.slot, block_hash)?;
+        }
+
+        let signature = self.signer.sign_block_header(&header_bytes).await?;
+        // Signed: this slot must never be signed again, even if publishing fails
+        self.last_slot = Some(duty.slot);
+
+        Ok(SignedProposal {
+            duty,
+            attestations: self.attestations.aggregate(&parent.hash.0),
+            template,
+            block_hash,
+            signature,
+        })
+    }
+}
+
+/// BlockProposed event for a signed proposal (triggers qc-08 validation)
+fn proposed_event(proposal: &SignedProposal) -> BlockchainEvent {
+    let header = &proposal.template.header;
+    BlockchainEvent::BlockProposed {
+        block_height: header.block_number,
+        block_hash: proposal.block_hash,
+        parent_hash: header.parent_hash.0,
+        timestamp: header.timestamp,
+        beneficiary: header.beneficiary,
+        gas_used: header.gas_used,
+        slot: proposal.duty.slot,
+        epoch: proposal.duty.epoch,
+        validator_index: proposal.duty.validator_index,
+        signature: proposal.signature.clone(),
+        attestations: proposal.attestations.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use shared_types::entities::Attestation;
+
+    /// Signs by prefixing the header with a marker
+    struct PrefixSigner;
+
+    #[async_trait]
+    impl SignatureProvider for PrefixSigner {
+        async fn sign_block_header(&self, header_bytes: &[u8]) -> Result<Vec<u8>> {
+            Ok([b"sig:".as_slice(), header_bytes].concat())
+        }
+    }
+
+    fn proposer(chain_head: Arc<Mutex<ExternalWorkRegistry>>) -> SlotProposer {
+        SlotProposer {
+            signer: Arc::new(PrefixSigner),
+            slashing_protection: None,
+            mempool_reader: None,
+            event_bus: Arc::new(InMemoryEventBus::new()),
+            config: BlockProductionConfig {
+                fee_recipient: Some([0x11; 20]),
+                ..BlockProductionConfig::default()
+            },
+            chain_head,
+            status: Arc::new(RwLock::new(ProductionStatus::default())),
+            mev_reports: Arc::new(Mutex::new(VecDeque::new())),
+            attestations: AttestationPool::default(),
+            mev_detector: MevDetector::default(),
+            last_slot: None,
+        }
+    }
+
+    fn duty(slot: u64) -> ProposerDuty {
+        ProposerDuty {
+            slot,
+            epoch: slot / 32,
+            validator_index: 3,
+            vrf_proof: VRFProof::new([0u8; 32], [0u8; 80]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_proposal_signed_on_head_with_parent_attestations() {
+        let parent = ChainHead {
+            hash: H256::repeat_byte(0xaa),
+            height: 7,
+            ..ChainHead::default()
+        };
+        let chain_head = Arc::new(Mutex::new(ExternalWorkRegistry::default()));
+        chain_head.lock().unwrap().reset(parent);
+        let mut proposer = proposer(Arc::clone(&chain_head));
+        for (block, validator) in [(0xaa, 1), (0xaa, 2), (0xbb, 3)] {
+            proposer.attestations.add(Attestation {
+                block_hash: [block; 32],
+                epoch: 0,
+                validator: [validator; 32],
+                signature: [0u8; 64],
+            });
+        }
+
+        let proposal = proposer.propose(duty(40), Vec::new()).await.unwrap();
+        let header = &proposal.template.header;
+        assert_eq!((header.parent_hash, header.block_number), (parent.hash, 8));
+        assert_eq!(
+            proposal.template.consensus_mode,
+            ConsensusMode::ProofOfStake
+        );
+        assert_eq!(proposal.attestations.len(), 2);
+
+        let header_bytes = serialize_block_header(
+            &header.parent_hash,
+            header.block_number,
+            header.timestamp,
+            &header.beneficiary,
+            header.gas_used,
+            None,
+        );
+        assert_eq!(proposal.signature[4..], header_bytes[..]);
+        assert_eq!(proposal.block_hash, sha256d(&header_bytes));
+        assert_eq!(header.beneficiary, [0x11; 20]);
+        // Signing alone does not move the head; only `BlockStored` does
+        assert_eq!(chain_head.lock().unwrap().head().hash, parent.hash);
+    }
+
+    #[tokio::test]
+    async fn test_head_follows_block_stored() {
+        let chain_head = Arc::new(Mutex::new(ExternalWorkRegistry::default()));
+        let mut proposer = proposer(Arc::clone(&chain_head));
+        let first = proposer.propose(duty(5), Vec::new()).await.unwrap();
+
+        proposer
+            .handle_event(BlockchainEvent::BlockStored {
+                block_height: first.template.header.block_number,
+                block_hash: first.block_hash,
+            })
+            .await;
+
+        let next = proposer.propose(duty(6), Vec::new()).await.unwrap();
+        assert_eq!(next.template.header.parent_hash.0, first.block_hash);
+        assert_eq!(next.template.header.block_number, 2);
+    }
+
+    #[tokio::test]
+    async fn test_proposing_requires_fee_recipient() {
+        let mut proposer = proposer(Arc::new(Mutex::new(ExternalWorkRegistry::default())));
+        proposer.config.fee_recipient = None;
+
+        assert!(matches!(
+            proposer.propose(duty(5), Vec::new()).await,
+            Err(BlockProductionError::InvalidConfig(_))
+        ));
+        // Nothing was signed, so the slot is still free
+        assert_eq!(proposer.last_slot, None);
+    }
+
+    #[tokio::test]
+    async fn test_slot_never_proposed_twice() {
+        let mut proposer = proposer(Arc::new(Mutex::new(ExternalWorkRegistry::default())));
+        proposer.propose(duty(5), Vec::new()).await.unwrap();
+
+        for slot in [4, 5] {
+            assert!(matches!(
+                proposer.propose(duty(slot), Vec::new()).await,
+                Err(BlockProductionError::SlotAlreadyProposed { last: 5, .. })
+            ));
+        }
+        // The first block was never stored, so the head has not moved
+        let next = proposer.propose(duty(6), Vec::new()).await.unwrap();
+        assert_eq!(next.template.header.block_number, 1);
+    }
+
+    /// Allows each slot once, like a record that survived a restart
+    #[derive(Default)]
+    struct SignedSlots(Mutex<Vec<u64>>);
+
+    impl SlashingProtection for SignedSlots {
+        fn check_and_record_block(&self, slot: u64, _signing_root: [u8; 32]) -> Result<()> {
+            let mut slots = self.0.lock().unwrap();
+            if slots.contains(&slot) {
+                return Err(BlockProductionError::SlashingProtection {
+                    slot,
+                    reason: "already signed".into(),
+                });
+            }
+            slots.push(slot);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slashing_protection_checked_before_signing() {
+        let protection = Arc::new(SignedSlots::default());
+        let mut first = proposer(Arc::new(Mutex::new(ExternalWorkRegistry::default())));
+        first.slashing_protection = Some(protection.clone());
+        first.config.graffiti = b"hello".to_vec();
+
+        let proposal = first.propose(duty(5), Vec::new()).await.unwrap();
+        assert_eq!(proposal.template.header.extra_data, b"hello");
+
+        // A fresh proposer (restart) still may not sign slot 5
+        let mut restarted = proposer(Arc::new(Mutex::new(ExternalWorkRegistry::default())));
+        restarted.slashing_protection = Some(protection);
+        assert!(matches!(
+            restarted.propose(duty(5), Vec::new()).await,
+            Err(BlockProductionError::SlashingProtection { slot: 5, .. })
+        ));
+    }
+}
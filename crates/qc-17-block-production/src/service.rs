@@ -6,20 +6,39 @@
 use crate::{
     config::BlockProductionConfig,
     domain::{
-        calculate_block_reward, calculate_transaction_fees, create_coinbase_transaction,
-        BlockHeader, BlockTemplate, ConsensusMode, DifficultyAdjuster, DifficultyConfig, PoWMiner,
+        calculate_block_reward, calculate_candidate_fees, coinbase_claim,
+        create_coinbase_transaction, AttestationPool, BlockFillPolicy, BlockHeader, BlockTemplate,
+        ChainHead, ConsensusMode, DifficultyAdjuster, DifficultyAlgorithm, DifficultyConfig,
+        ExternalWorkRegistry, FillDecision, IssuedTemplate, MevContext, MevDetector, MevReport,
+        MiningThrottle, PoWMiner, SealedBlock, ThrottleReason, TransactionCandidate,
     },
     error::{BlockProductionError, Result},
-    ports::{BlockProducerService, BlockStorageReader, ProductionConfig, ProductionStatus},
+    ports::{
+        BlockProducerService, BlockStorageReader, MempoolReader, ProductionConfig,
+        ProductionStatus, SignatureProvider, SlashingProtection, SystemLoadProbe,
+    },
+    proposer::SlotProposer,
     security::SecurityValidator,
 };
 use async_trait::async_trait;
 use primitive_types::{H256, U256};
-use shared_bus::InMemoryEventBus;
-use shared_types::entities::{Address, ValidatedTransaction};
-use std::sync::Arc;
+use shared_bus::{BlockchainEvent, EventFilter, EventPublisher, EventTopic, InMemoryEventBus};
+use shared_types::entities::Address;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
+/// How often the mempool is re-polled while the fill policy says to wait
+const MEMPOOL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Longest single sleep while the mining throttle pauses
+const THROTTLE_SLICE: Duration = Duration::from_millis(500);
+
+/// MEV reports kept for the admin API
+const MEV_REPORT_HISTORY: usize = 256;
+
 /// Concrete implementation of BlockProducerService
 ///
 /// This service orchestrates block production across different consensus modes:
@@ -54,6 +73,35 @@ pub struct ConcreteBlockProducer {
     /// Block storage reader for chain state queries (V2.4)
     /// Used on startup to resume with correct difficulty
     block_storage_reader: Option<Arc<dyn BlockStorageReader>>,
+
+    /// Mempool reader for pending transactions (qc-06)
+    /// Without one, templates contain only the coinbase transaction
+    mempool_reader: Option<Arc<dyn MempoolReader>>,
+
+    /// Validator key signing PoS proposals (required for PoS)
+    signature_provider: Option<Arc<dyn SignatureProvider>>,
+
+    /// Slashing-protection record checked before each PoS signature
+    slashing_protection: Option<Arc<dyn SlashingProtection>>,
+
+    /// Host load probe for the mining throttle
+    /// Defaults to procfs when a load or temperature limit is configured
+    load_probe: Option<Arc<dyn SystemLoadProbe>>,
+
+    /// Mining duty cycle, shared with the mining task so it can be changed
+    /// while mining
+    duty_cycle_percent: Arc<std::sync::atomic::AtomicU8>,
+
+    /// Work server publishing templates to remote miners
+    #[cfg(feature = "stratum")]
+    work_server: Option<Arc<crate::adapters::stratum::WorkServer>>,
+
+    /// Chain head and templates issued to external producers, shared with
+    /// the mining task so either side sees blocks sealed by the other
+    external_work: Arc<Mutex<ExternalWorkRegistry>>,
+
+    /// MEV reports for recently mined blocks, oldest first
+    mev_reports: Arc<Mutex<VecDeque<MevReport>>>,
 }
 
 impl ConcreteBlockProducer {
@@ -65,6 +113,10 @@ impl ConcreteBlockProducer {
         info!("  Fair Ordering: {}", config.fair_ordering);
 
         let security = SecurityValidator::new(config.gas_limit, config.min_gas_price);
+        let duty_cycle_percent = config
+            .pow
+            .as_ref()
+            .map_or(100, |p| p.throttle.duty_cycle_percent);
 
         let initial_status = ProductionStatus {
             active: false,
@@ -72,9 +124,11 @@ impl ConcreteBlockProducer {
             blocks_produced: 0,
             total_fees: U256::zero(),
             hashrate: None,
+            backend_hashrates: Vec::new(),
             last_block_at: None,
             current_difficulty: None,
             last_nonce: None,
+            template_rebuilds: 0,
         };
 
         // Initialize PoW miner with number of threads from config or default
@@ -86,18 +140,25 @@ impl ConcreteBlockProducer {
             let pow_config = config.pow.as_ref();
             let difficulty_config = DifficultyConfig {
                 target_block_time: pow_config.and_then(|p| p.target_block_time).unwrap_or(10),
-                use_dgw: pow_config.and_then(|p| p.use_dgw).unwrap_or(true),
+                algorithm: pow_config
+                    .map(|p| p.difficulty_algorithm())
+                    .unwrap_or(DifficultyAlgorithm::DarkGravityWave),
+                activations: pow_config
+                    .map(|p| p.difficulty_activations.clone())
+                    .unwrap_or_default(),
                 dgw_window: pow_config.and_then(|p| p.dgw_window).unwrap_or(24),
+                lwma_window: pow_config.and_then(|p| p.lwma_window).unwrap_or(45),
                 ..Default::default()
             };
             info!(
-                "  Difficulty Adjustment: {} (target: {}s per block)",
-                if difficulty_config.use_dgw {
-                    "Dark Gravity Wave"
-                } else {
-                    "Epoch-based"
+                "  Difficulty Adjustment: {} (target: {}s per block, {} activation(s))",
+                match difficulty_config.algorithm {
+                    DifficultyAlgorithm::Epoch => "Epoch-based",
+                    DifficultyAlgorithm::DarkGravityWave => "Dark Gravity Wave",
+                    DifficultyAlgorithm::Lwma => "LWMA",
                 },
-                difficulty_config.target_block_time
+                difficulty_config.target_block_time,
+                difficulty_config.activations.len()
             );
             Some(DifficultyAdjuster::new(difficulty_config))
         } else {
@@ -114,6 +175,15 @@ impl ConcreteBlockProducer {
             mining_handle: std::sync::Mutex::new(None),
             difficulty_adjuster,
             block_storage_reader: None,
+            mempool_reader: None,
+            signature_provider: None,
+            slashing_protection: None,
+            load_probe: None,
+            duty_cycle_percent: Arc::new(std::sync::atomic::AtomicU8::new(duty_cycle_percent)),
+            #[cfg(feature = "stratum")]
+            work_server: None,
+            external_work: Arc::new(Mutex::new(ExternalWorkRegistry::default())),
+            mev_reports: Arc::new(Mutex::new(VecDeque::new())),
         }
     }
 
@@ -126,20 +196,400 @@ impl ConcreteBlockProducer {
         self
     }
 
+    /// Set the mempool reader used to fill block templates
+    pub fn with_mempool_reader(mut self, reader: Arc<dyn MempoolReader>) -> Self {
+        self.mempool_reader = Some(reader);
+        self
+    }
+
+    /// Set the validator signer used for PoS proposals
+    pub fn with_signature_provider(mut self, signer: Arc<dyn SignatureProvider>) -> Self {
+        self.signature_provider = Some(signer);
+        self
+    }
+
+    /// Set the slashing-protection record checked before signing proposals
+    pub fn with_slashing_protection(mut self, protection: Arc<dyn SlashingProtection>) -> Self {
+        self.slashing_protection = Some(protection);
+        self
+    }
+
+    /// Set the host load probe consulted by the mining throttle
+    pub fn with_load_probe(mut self, probe: Arc<dyn SystemLoadProbe>) -> Self {
+        self.load_probe = Some(probe);
+        self
+    }
+
+    /// Set the work server that publishes templates to remote miners
+    ///
+    /// The server itself is started by the caller with `WorkServer::serve`;
+    /// block solutions submitted through it are picked up between nonce batches.
+    #[cfg(feature = "stratum")]
+    pub fn with_work_server(mut self, server: Arc<crate::adapters::stratum::WorkServer>) -> Self {
+        self.work_server = Some(server);
+        self
+    }
+
+    /// Build a template for an external producer (`qc_getBlockTemplate`)
+    ///
+    /// The mempool is read once, without waiting on the fill policy. The
+    /// difficulty is the one the local miner last sealed at.
+    pub async fn get_block_template(&self, beneficiary: Option<Address>) -> Result<IssuedTemplate> {
+        if !self.is_active.load(std::sync::atomic::Ordering::SeqCst) {
+            return Err(BlockProductionError::NotActive);
+        }
+        let config = self.config_sync();
+        if config.mode != ConsensusMode::ProofOfWork {
+            return Err(BlockProductionError::InvalidConfig(
+                "block templates are only issued in PoW mode".to_string(),
+            ));
+        }
+
+        let pending = match &self.mempool_reader {
+            Some(reader) => {
+                reader
+                    .get_pending_transactions(
+                        config.performance.max_transaction_candidates,
+                        config.min_gas_price,
+                    )
+                    .await?
+            }
+            None => Vec::new(),
+        };
+        let difficulty = self
+            .status
+            .read()
+            .unwrap()
+            .current_difficulty
+            .unwrap_or_else(|| DifficultyConfig::default().initial_difficulty);
+
+        let parent = self.external_work.lock().unwrap().head();
+        let template = Self::build_template(
+            &parent,
+            beneficiary.or(config.fee_recipient).unwrap_or_default(),
+            pending,
+            difficulty,
+            config.gas_limit,
+            ConsensusMode::ProofOfWork,
+        )?;
+        Ok(self.external_work.lock().unwrap().issue(template))
+    }
+
+    /// Accept a solution from an external producer (`qc_submitBlock`)
+    ///
+    /// The nonce is checked against the template's target and the template
+    /// must still build on the chain head; the block is then published like a
+    /// locally mined one.
+    pub async fn submit_block(&self, template_id: &str, nonce: u64) -> Result<SealedBlock> {
+        let sealed = self
+            .external_work
+            .lock()
+            .unwrap()
+            .submit(template_id, nonce)?;
+        let header = &sealed.template.header;
+        info!(
+            "[qc-17] Block #{} sealed by external producer | nonce: {} | hash: {}",
+            header.block_number,
+            nonce,
+            hex::encode(&sealed.hash[..8])
+        );
+
+        {
+            let mut status = self.status.write().unwrap();
+            status.blocks_produced = header.block_number;
+            status.last_block_at = Some(header.timestamp);
+            status.current_difficulty = Some(header.difficulty);
+            status.last_nonce = Some(nonce);
+        }
+
+        let event =
+            Self::block_produced_event(header, sealed.template.total_fees, sealed.hash, nonce);
+        self.event_bus.publish(event).await;
+        Ok(sealed)
+    }
+
+    /// MEV reports for recently mined blocks, newest first
+    ///
+    /// With `block_number`, only that block's report (if still retained).
+    pub fn mev_reports(&self, block_number: Option<u64>, limit: usize) -> Vec<MevReport> {
+        self.mev_reports
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|report| block_number.is_none_or(|n| report.block_number == n))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Retain a block's MEV report and publish it on the event bus
+    pub(crate) async fn publish_mev_report(
+        event_bus: &InMemoryEventBus,
+        reports: &Mutex<VecDeque<MevReport>>,
+        report: MevReport,
+    ) {
+        if !report.sandwiches.is_empty() || !report.backruns.is_empty() {
+            warn!(
+                "[qc-17] Block #{}: {} suspected sandwich(es), {} suspected backrun(s)",
+                report.block_number,
+                report.sandwiches.len(),
+                report.backruns.len()
+            );
+        }
+
+        let event = BlockchainEvent::MevReportPublished {
+            block_height: report.block_number,
+            block_hash: report.block_hash.0,
+            report: serde_json::to_value(&report).unwrap_or_default(),
+        };
+        {
+            let mut reports = reports.lock().unwrap();
+            if reports.len() == MEV_REPORT_HISTORY {
+                reports.pop_front();
+            }
+            reports.push_back(report);
+        }
+        event_bus.publish(event).await;
+    }
+
+    /// Build a template on top of `parent` (coinbase first)
+    ///
+    /// PoS templates carry a zero difficulty and are signed, not mined.
+    pub(crate) fn build_template(
+        parent: &ChainHead,
+        beneficiary: Address,
+        pending: Vec<TransactionCandidate>,
+        difficulty: U256,
+        gas_limit: u64,
+        mode: ConsensusMode,
+    ) -> Result<BlockTemplate> {
+        let block_number = parent.height + 1;
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        // Enforce timestamp monotonicity (must be >= parent timestamp)
+        let timestamp = timestamp.max(parent.timestamp + 1);
+
+        let base_reward = calculate_block_reward(block_number);
+        let transaction_fees = calculate_candidate_fees(&pending);
+        let coinbase_tx = create_coinbase_transaction(
+            block_number,
+            beneficiary,
+            base_reward,
+            transaction_fees,
+            timestamp,
+        )
+        .map_err(|e| BlockProductionError::InternalError(format!("coinbase: {}", e)))?;
+
+        // Serialize coinbase for BlockTemplate (simple encoding for now);
+        // mempool transactions are already encoded
+        let mut transactions: Vec<Vec<u8>> =
+            vec![serde_json::to_vec(&coinbase_tx).unwrap_or_default()];
+        transactions.extend(pending.into_iter().map(|c| c.transaction));
+
+        Ok(BlockTemplate {
+            header: BlockHeader {
+                parent_hash: parent.hash,
+                block_number,
+                timestamp,
+                beneficiary,
+                gas_used: 0,
+                gas_limit,
+                difficulty,
+                extra_data: match mode {
+                    ConsensusMode::ProofOfWork => b"qc-17-miner".to_vec(),
+                    _ => b"qc-17-proposer".to_vec(),
+                },
+                merkle_root: None,
+                state_root: Some(H256::zero()),
+                nonce: None,
+            },
+            transactions,
+            total_gas_used: 0,
+            total_fees: transaction_fees,
+            consensus_mode: mode,
+            created_at: timestamp,
+        })
+    }
+
+    /// BlockProduced event for a sealed header (triggers qc-08 validation)
+    ///
+    /// Carries the coinbase claim for the beneficiary: the block subsidy plus
+    /// `total_fees`.
+    fn block_produced_event(
+        header: &BlockHeader,
+        total_fees: U256,
+        hash: [u8; 32],
+        nonce: u64,
+    ) -> BlockchainEvent {
+        let mut difficulty = [0u8; 32];
+        header.difficulty.to_big_endian(&mut difficulty);
+        BlockchainEvent::BlockProduced {
+            block_height: header.block_number,
+            block_hash: hash,
+            difficulty,
+            nonce,
+            timestamp: header.timestamp,
+            parent_hash: header.parent_hash.0,
+            coinbase: coinbase_claim(
+                header.block_number,
+                header.beneficiary,
+                total_fees,
+                header.timestamp,
+            ),
+        }
+    }
+
+    /// Serialized header of `template` without a nonce (the PoW work)
+    fn unsealed_header_bytes(template: &BlockTemplate) -> Vec<u8> {
+        crate::utils::hashing::serialize_block_header(
+            &template.header.parent_hash,
+            template.header.block_number,
+            template.header.timestamp,
+            &template.header.beneficiary,
+            template.header.gas_used,
+            None,
+        )
+    }
+
+    /// Template for the pending transactions, if they pay enough more than
+    /// `current` for the fill policy to switch mid-round
+    ///
+    /// The mempool is read at most once per poll interval.
+    async fn better_template(
+        mempool: Option<&Arc<dyn MempoolReader>>,
+        policy: &BlockFillPolicy,
+        config: &BlockProductionConfig,
+        current: &BlockTemplate,
+        parent: &ChainHead,
+        last_check: &mut std::time::Instant,
+    ) -> Option<(BlockTemplate, Vec<TransactionCandidate>)> {
+        policy.fee_rebuild_percent?;
+        if last_check.elapsed() < MEMPOOL_POLL_INTERVAL {
+            return None;
+        }
+        *last_check = std::time::Instant::now();
+
+        let pending = mempool?
+            .get_pending_transactions(
+                config.performance.max_transaction_candidates,
+                config.min_gas_price,
+            )
+            .await
+            .ok()?;
+        if !policy.should_rebuild(current.total_fees, calculate_candidate_fees(&pending)) {
+            return None;
+        }
+        let template = Self::build_template(
+            parent,
+            current.header.beneficiary,
+            pending.clone(),
+            current.header.difficulty,
+            current.header.gas_limit,
+            ConsensusMode::ProofOfWork,
+        )
+        .ok()?;
+        Some((template, pending))
+    }
+
+    /// Poll the mempool until the fill policy says to produce
+    ///
+    /// Returns None if production stopped while waiting.
+    async fn collect_pending_transactions(
+        mempool: Option<&Arc<dyn MempoolReader>>,
+        policy: &BlockFillPolicy,
+        config: &BlockProductionConfig,
+        is_active: &AtomicBool,
+    ) -> Option<Vec<TransactionCandidate>> {
+        let started = std::time::Instant::now();
+        let mut waiting = false;
+
+        while is_active.load(std::sync::atomic::Ordering::Relaxed) {
+            let pending = match mempool {
+                Some(reader) => reader
+                    .get_pending_transactions(
+                        config.performance.max_transaction_candidates,
+                        config.min_gas_price,
+                    )
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!("[qc-17] Failed to read mempool: {}", e);
+                        Vec::new()
+                    }),
+                None => Vec::new(),
+            };
+
+            if policy.decide(pending.len(), started.elapsed()) == FillDecision::Produce {
+                return Some(pending);
+            }
+            if !waiting {
+                debug!(
+                    "[qc-17] Waiting for transactions ({} pending, min {})",
+                    pending.len(),
+                    policy.min_transactions
+                );
+                waiting = true;
+            }
+            tokio::time::sleep(MEMPOOL_POLL_INTERVAL).await;
+        }
+        None
+    }
+
+    /// Pause before the next nonce batch as the mining throttle asks
+    ///
+    /// `busy` is how long the previous batch kept the miner busy. Sleeps in
+    /// short slices so stopping production is not held up by a long pause.
+    /// Returns why it paused, if it did.
+    async fn throttle_mining(
+        throttle: &MiningThrottle,
+        load_probe: Option<&Arc<dyn SystemLoadProbe>>,
+        busy: Duration,
+        is_active: &AtomicBool,
+    ) -> Option<ThrottleReason> {
+        let load = match load_probe {
+            Some(probe) => probe
+                .sample()
+                .await
+                .inspect_err(|e| warn!("[qc-17] Failed to sample host load: {}", e))
+                .ok(),
+            None => None,
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let (pause, reason) = throttle.pause(busy, load.as_ref(), now)?;
+        if reason != ThrottleReason::DutyCycle {
+            info!(
+                "[qc-17] Mining paused for {}s ({:?})",
+                pause.as_secs(),
+                reason
+            );
+        }
+
+        let deadline = std::time::Instant::now() + pause;
+        while is_active.load(std::sync::atomic::Ordering::Relaxed) {
+            let left = deadline.saturating_duration_since(std::time::Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            tokio::time::sleep(left.min(THROTTLE_SLICE)).await;
+        }
+        Some(reason)
+    }
+
     /// Query chain state from Block Storage (qc-02)
     ///
     /// V2.4: Queries current chain tip and recent blocks for
     /// difficulty adjustment. Returns a ProductionConfig populated
     /// with chain state, or an error if unavailable.
     pub async fn query_chain_state(&self) -> Result<ProductionConfig> {
-        let dgw_window = self
-            .config
-            .read()
-            .unwrap()
-            .pow
+        let history_len = self
+            .difficulty_adjuster
             .as_ref()
-            .and_then(|p| p.dgw_window)
-            .unwrap_or(24) as u32;
+            .map_or(24, DifficultyAdjuster::history_len) as u32;
 
         let Some(ref reader) = self.block_storage_reader else {
             debug!("[qc-17] No block storage reader configured, using default config");
@@ -147,11 +597,11 @@ impl ConcreteBlockProducer {
         };
 
         debug!(
-            "[qc-17] Querying Block Storage for chain state (DGW window: {})",
-            dgw_window
+            "[qc-17] Querying Block Storage for chain state (difficulty window: {})",
+            history_len
         );
 
-        let Ok(chain_info) = reader.get_chain_info(dgw_window).await else {
+        let Ok(chain_info) = reader.get_chain_info(history_len).await else {
             warn!("[qc-17] Failed to query chain state. Starting from genesis.");
             return Ok(ProductionConfig::default());
         };
@@ -194,6 +644,31 @@ impl ConcreteBlockProducer {
         self.config.read().unwrap().clone()
     }
 
+    /// Current mining duty cycle, 1-100
+    pub fn duty_cycle_percent(&self) -> u8 {
+        self.duty_cycle_percent
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Change the mining duty cycle, including for a running miner
+    ///
+    /// Takes effect from the next nonce batch.
+    pub fn set_duty_cycle_percent(&self, percent: u8) -> Result<()> {
+        if !(1..=100).contains(&percent) {
+            return Err(BlockProductionError::InvalidConfig(format!(
+                "duty cycle must be 1-100, got {}",
+                percent
+            )));
+        }
+        self.duty_cycle_percent
+            .store(percent, std::sync::atomic::Ordering::Relaxed);
+        if let Some(pow) = self.config.write().unwrap().pow.as_mut() {
+            pow.throttle.duty_cycle_percent = percent;
+        }
+        info!("[qc-17] Mining duty cycle set to {}%", percent);
+        Ok(())
+    }
+
     /// Get the event bus
     pub fn event_bus(&self) -> Arc<InMemoryEventBus> {
         Arc::clone(&self.event_bus)
@@ -234,6 +709,19 @@ impl BlockProducerService for ConcreteBlockProducer {
     async fn start_production(&self, mode: ConsensusMode, config: ProductionConfig) -> Result<()> {
         info!("[qc-17] Starting block production");
 
+        if mode == ConsensusMode::ProofOfStake && self.signature_provider.is_none() {
+            return Err(BlockProductionError::InvalidConfig(
+                "PoS proposing requires a signature provider".to_string(),
+            ));
+        }
+        if mode == ConsensusMode::ProofOfStake
+            && self.config.read().unwrap().fee_recipient.is_none()
+        {
+            return Err(BlockProductionError::InvalidConfig(
+                "PoS proposing requires a fee_recipient".to_string(),
+            ));
+        }
+
         self.is_active
             .store(true, std::sync::atomic::Ordering::SeqCst);
 
@@ -253,6 +741,11 @@ impl BlockProducerService for ConcreteBlockProducer {
             // CRITICAL: Initialize difficulty for Bridge to use on first block
             status.current_difficulty = Some(initial_difficulty);
         }
+        self.external_work.lock().unwrap().reset(ChainHead {
+            height: starting_height,
+            difficulty: initial_difficulty,
+            ..ChainHead::default()
+        });
 
         if starting_height > 0 {
             info!(
@@ -281,8 +774,31 @@ impl BlockProducerService for ConcreteBlockProducer {
                 let event_bus = Arc::clone(&self.event_bus); // EDA: Publish BlockProduced events
                 let block_config = self.config.read().unwrap().clone();
                 let pow_miner = PoWMiner::new(threads);
+                let dispatcher = crate::adapters::pow::MiningDispatcher::for_miner(&pow_miner);
                 let status = self.status.clone(); // Share the same RwLock, don't copy!
                 let difficulty_adjuster = self.difficulty_adjuster.clone();
+                let mut throttle = block_config
+                    .pow
+                    .as_ref()
+                    .map(|p| p.throttle.policy())
+                    .unwrap_or_default();
+                let load_probe = self.load_probe.clone().or_else(|| {
+                    throttle.watches_load().then(|| {
+                        Arc::new(crate::adapters::load::ProcLoadProbe::default())
+                            as Arc<dyn SystemLoadProbe>
+                    })
+                });
+                // Keep at least the history the difficulty algorithms look at
+                let history_len = difficulty_adjuster
+                    .as_ref()
+                    .map_or(50, |adjuster| adjuster.history_len().max(50));
+                let duty_cycle_percent = Arc::clone(&self.duty_cycle_percent);
+                let mempool_reader = self.mempool_reader.clone();
+                let fill_policy = block_config.fill_policy();
+                #[cfg(feature = "stratum")]
+                let work_server = self.work_server.clone();
+                let external_work = Arc::clone(&self.external_work);
+                let mev_reports = Arc::clone(&self.mev_reports);
 
                 let mining_task = tokio::task::spawn(async move {
                     info!("[qc-17] PoW mining task started");
@@ -328,9 +844,7 @@ impl BlockProducerService for ConcreteBlockProducer {
                         );
                     }
 
-                    // Track the last mined block hash for proper chaining
-                    let mut last_block_hash = H256::zero(); // Genesis parent
-                    let mut last_block_timestamp = 0u64;
+                    let mut mev_detector = MevDetector::default();
 
                     // Get target block time for minimum interval enforcement
                     let target_block_time = block_config
@@ -340,55 +854,38 @@ impl BlockProducerService for ConcreteBlockProducer {
                         .unwrap_or(10);
 
                     while is_active_clone.load(std::sync::atomic::Ordering::Relaxed) {
-                        // Step 1: Get pending transactions from mempool
-                        // Mempool integration via qc-06 IPC (empty for coinbase-only blocks)
-                        let pending_transactions: Vec<ValidatedTransaction> = vec![];
-
-                        // Step 2: Calculate block number (resume from where we left off)
-                        let parent_hash = last_block_hash; // Proper chain linking
-                        let block_number = 1 + blocks_mined; // Next block after persisted height
-                        let timestamp = std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap()
-                            .as_secs();
-
-                        // Enforce timestamp monotonicity (must be >= parent timestamp)
-                        let timestamp = timestamp.max(last_block_timestamp + 1);
-
-                        // Step 3: Calculate mining rewards
-                        let base_reward = calculate_block_reward(block_number);
-                        let transaction_fees = calculate_transaction_fees(&pending_transactions);
-
-                        // Use beneficiary from config, fallback to zero address
-                        let beneficiary: Address = [0u8; 20]; // Default beneficiary
-
-                        // Step 4: Create coinbase transaction
-                        let coinbase_tx = match create_coinbase_transaction(
-                            block_number,
-                            beneficiary,
-                            base_reward,
-                            transaction_fees,
-                            timestamp,
-                        ) {
-                            Ok(tx) => tx,
-                            Err(e) => {
-                                error!("[qc-17] Failed to create coinbase transaction: {}", e);
-                                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-                                continue;
-                            }
+                        // Step 1: Get pending transactions from mempool, waiting
+                        // as long as the fill policy asks for a fuller block
+                        let Some(pending_transactions) = Self::collect_pending_transactions(
+                            mempool_reader.as_ref(),
+                            &fill_policy,
+                            &block_config,
+                            &is_active_clone,
+                        )
+                        .await
+                        else {
+                            break;
                         };
+                        let mut template_built = std::time::Instant::now();
+
+                        // Step 2: Build on the chain head, which moves when an
+                        // external producer seals a block (qc_submitBlock)
+                        let parent = external_work.lock().unwrap().head();
+                        if parent.height > blocks_mined {
+                            blocks_mined = parent.height;
+                            recent_blocks.insert(
+                                0,
+                                crate::domain::difficulty::BlockInfo {
+                                    height: parent.height,
+                                    timestamp: parent.timestamp,
+                                    difficulty: parent.difficulty,
+                                },
+                            );
+                            recent_blocks.truncate(history_len);
+                        }
+                        let block_number = parent.height + 1;
 
-                        // Step 5: Build transaction list (coinbase first)
-                        let mut validated_transactions = vec![coinbase_tx];
-                        validated_transactions.extend(pending_transactions);
-
-                        // Serialize transactions for BlockTemplate (simple encoding for now)
-                        let transactions: Vec<Vec<u8>> = validated_transactions
-                            .iter()
-                            .map(|tx| serde_json::to_vec(&tx).unwrap_or_default())
-                            .collect();
-
-                        // Step 6: Calculate difficulty dynamically based on recent blocks
+                        // Step 3: Calculate difficulty dynamically based on recent blocks
                         let difficulty = if let Some(ref adjuster) = difficulty_adjuster {
                             let calculated = adjuster.calculate_next_difficulty(&recent_blocks);
                             let desc = DifficultyAdjuster::describe_difficulty(calculated);
@@ -406,28 +903,30 @@ impl BlockProducerService for ConcreteBlockProducer {
                             U256::from(2).pow(U256::from(240))
                         };
 
-                        let template = BlockTemplate {
-                            header: BlockHeader {
-                                parent_hash,
-                                block_number,
-                                timestamp,
-                                beneficiary,
-                                gas_used: 0,
-                                gas_limit: block_config.gas_limit,
-                                difficulty,
-                                extra_data: b"qc-17-miner".to_vec(),
-                                merkle_root: None,
-                                state_root: Some(H256::zero()),
-                                nonce: None,
-                            },
-                            transactions,
-                            total_gas_used: 0,
-                            total_fees: transaction_fees,
-                            consensus_mode: ConsensusMode::ProofOfWork,
-                            created_at: timestamp,
+                        // Step 4: Build the template (coinbase first)
+                        // Rewards go to the configured fee recipient (zero address if unset)
+                        let beneficiary: Address = block_config.fee_recipient.unwrap_or_default();
+
+                        // Every candidate goes into the block; kept for the MEV report
+                        let mut candidates = pending_transactions.clone();
+                        let mut template = match Self::build_template(
+                            &parent,
+                            beneficiary,
+                            pending_transactions,
+                            difficulty,
+                            block_config.gas_limit,
+                            ConsensusMode::ProofOfWork,
+                        ) {
+                            Ok(template) => template,
+                            Err(e) => {
+                                error!("[qc-17] Failed to build block template: {}", e);
+                                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                                continue;
+                            }
                         };
+                        let mut timestamp = template.header.timestamp;
 
-                        // Step 7: Mine with calculated difficulty using GPU/CPU compute engine
+                        // Step 5: Mine with calculated difficulty using GPU/CPU compute engine
                         // Log includes difficulty description for debugging
                         let diff_desc = DifficultyAdjuster::describe_difficulty(difficulty);
                         info!(
@@ -436,60 +935,136 @@ impl BlockProducerService for ConcreteBlockProducer {
                             pow_miner.backend_name()
                         );
 
-                        // Async mining with GPU/CPU compute engine (async I/O in service layer)
-                        // This logic was moved from domain layer to maintain domain purity
-                        let mining_result: Option<(u64, [u8; 32])> = {
-                            if let Some(engine) = pow_miner.get_compute_engine() {
-                                // Use GPU/CPU compute engine
-                                let header_bytes = crate::utils::hashing::serialize_block_header(
-                                    &template.header.parent_hash,
-                                    template.header.block_number,
-                                    template.header.timestamp,
-                                    &template.header.beneficiary,
-                                    template.header.gas_used,
-                                    None,
-                                );
+                        // Async mining through the compute backend dispatcher (async I/O in
+                        // service layer); GPU results are verified on the CPU before use
+                        let mut stale = false;
+                        let mut header_bytes = Self::unsealed_header_bytes(&template);
+                        // Remote miners work on the same template
+                        #[cfg(feature = "stratum")]
+                        let publish_remote = |header_bytes: &Vec<u8>| {
+                            work_server.as_ref().map(|server| {
+                                let job =
+                                    server.publish_job(header_bytes.clone(), difficulty, true);
+                                (Arc::clone(server), job)
+                            })
+                        };
+                        #[cfg(feature = "stratum")]
+                        let mut remote_job = publish_remote(&header_bytes);
+                        let mut fees_checked = std::time::Instant::now();
+                        let mining_result: Option<(u64, [u8; 32])> = if dispatcher.is_empty() {
+                            None
+                        } else {
+                            // Get batch size from config, with fallback to default
+                            let batch_size = block_config
+                                .pow
+                                .as_ref()
+                                .and_then(|p| p.batch_size)
+                                .unwrap_or(10_000_000);
+                            let mut nonce_start = 0u64;
+                            let mut result = None;
+                            let mut batch_busy = Duration::ZERO;
+
+                            loop {
+                                if external_work.lock().unwrap().head().height >= block_number {
+                                    // Sealed externally; build on the new head
+                                    stale = true;
+                                    break;
+                                }
+
+                                // A long pause (load, mining window) or a stop
+                                // leaves the template outdated
+                                throttle.duty_cycle_percent =
+                                    duty_cycle_percent.load(std::sync::atomic::Ordering::Relaxed);
+                                let paused = Self::throttle_mining(
+                                    &throttle,
+                                    load_probe.as_ref(),
+                                    batch_busy,
+                                    &is_active_clone,
+                                )
+                                .await;
+                                if paused.is_some_and(|reason| reason != ThrottleReason::DutyCycle)
+                                    || !is_active_clone.load(std::sync::atomic::Ordering::Relaxed)
+                                {
+                                    stale = true;
+                                    break;
+                                }
 
-                                // Get batch size from config, with fallback to default
-                                let batch_size = block_config
-                                    .pow
+                                #[cfg(feature = "stratum")]
+                                if let Some(found) = remote_job
                                     .as_ref()
-                                    .and_then(|p| p.batch_size)
-                                    .unwrap_or(10_000_000);
-                                let mut nonce_start = 0u64;
-                                let mut result = None;
-
-                                loop {
-                                    match engine
-                                        .pow_mine(
-                                            &header_bytes,
-                                            difficulty,
-                                            nonce_start,
-                                            batch_size,
-                                        )
-                                        .await
-                                    {
-                                        Ok(Some((nonce, hash))) => {
-                                            result = Some((nonce, hash));
+                                    .and_then(|(server, job)| server.solution(&job.job_id))
+                                {
+                                    info!("[qc-17] Block solved by remote miner");
+                                    result = Some(found);
+                                    break;
+                                }
+
+                                let batch_started = std::time::Instant::now();
+                                let mined = dispatcher
+                                    .mine_range(&header_bytes, difficulty, nonce_start, batch_size)
+                                    .await;
+                                batch_busy = batch_started.elapsed();
+                                match mined {
+                                    Ok(Some(found)) => {
+                                        result = Some(found);
+                                        break;
+                                    }
+                                    Ok(None) if fill_policy.is_stale(template_built.elapsed()) => {
+                                        // Rebuild with what arrived while mining
+                                        stale = true;
+                                        break;
+                                    }
+                                    Ok(None) => {
+                                        nonce_start += batch_size;
+                                        if nonce_start > u64::MAX - batch_size {
                                             break;
                                         }
-                                        Ok(None) => {
-                                            nonce_start += batch_size;
-                                            if nonce_start > u64::MAX - batch_size {
-                                                break;
-                                            }
-                                        }
-                                        Err(_) => break,
                                     }
+                                    Err(_) => break,
+                                }
+
+                                // Better-paying transactions arrived: switch to a
+                                // new template, carrying on from the current nonce
+                                let Some((rebuilt, pending)) = Self::better_template(
+                                    mempool_reader.as_ref(),
+                                    &fill_policy,
+                                    &block_config,
+                                    &template,
+                                    &parent,
+                                    &mut fees_checked,
+                                )
+                                .await
+                                else {
+                                    continue;
+                                };
+                                info!(
+                                    "[qc-17] Rebuilt block #{} template at nonce {}: fees {} -> {}",
+                                    block_number,
+                                    nonce_start,
+                                    template.total_fees,
+                                    rebuilt.total_fees
+                                );
+                                template = rebuilt;
+                                candidates = pending;
+                                timestamp = template.header.timestamp;
+                                template_built = std::time::Instant::now();
+                                header_bytes = Self::unsealed_header_bytes(&template);
+                                #[cfg(feature = "stratum")]
+                                {
+                                    remote_job = publish_remote(&header_bytes);
                                 }
-                                result
-                            } else {
-                                None
+                                status.write().unwrap().template_rebuilds += 1;
                             }
+                            result
                         };
 
                         // Fallback to CPU mining if compute engine unavailable or failed
+                        let template_header = template.header.clone();
+                        let template_fees = template.total_fees;
                         let mining_result = mining_result.or_else(|| {
+                            if stale {
+                                return None;
+                            }
                             let template_for_hash = template.clone();
                             pow_miner.mine_block(template, difficulty).map(|nonce| {
                                 let header_bytes = crate::utils::hashing::serialize_block_header(
@@ -505,16 +1080,29 @@ impl BlockProducerService for ConcreteBlockProducer {
                             })
                         });
 
+                        // Claim the height; an external block may have won the race
+                        let sealed_head = ChainHead {
+                            hash: H256::default(),
+                            height: block_number,
+                            timestamp,
+                            difficulty,
+                        };
                         match mining_result {
+                            Some((_, block_hash))
+                                if !external_work.lock().unwrap().advance_head(ChainHead {
+                                    hash: H256(block_hash),
+                                    ..sealed_head
+                                }) =>
+                            {
+                                info!(
+                                    "[qc-17] Block #{} was sealed externally first, discarding",
+                                    block_number
+                                );
+                            }
                             Some((nonce, block_hash)) => {
                                 blocks_mined += 1;
-                                let elapsed = start_time.elapsed().as_secs();
-                                let hashrate = if elapsed > 0 {
-                                    Some((blocks_mined as f64 / elapsed as f64) * 1_000_000.0)
-                                // Rough estimate
-                                } else {
-                                    None
-                                };
+                                let hashrate = dispatcher.total_hashrate();
+                                let backend_hashrates = dispatcher.hashrates();
 
                                 info!(
                                     "[qc-17] Block #{} mined! | nonce: {} | hash: {}",
@@ -532,10 +1120,8 @@ impl BlockProducerService for ConcreteBlockProducer {
                                         difficulty,
                                     },
                                 );
-                                // Keep only the last 50 blocks in memory
-                                if recent_blocks.len() > 50 {
-                                    recent_blocks.truncate(50);
-                                }
+                                // Keep only the blocks difficulty adjustment needs
+                                recent_blocks.truncate(history_len);
 
                                 // Compute difficulty description for logs
                                 let difficulty_for_log = diff_desc.clone();
@@ -555,6 +1141,14 @@ impl BlockProducerService for ConcreteBlockProducer {
                                         "total_blocks": blocks_mined,
                                         "hashrate": hashrate,
                                         "backend": pow_miner.backend_name(),
+                                        "backend_hashrates": backend_hashrates
+                                            .iter()
+                                            .map(|b| serde_json::json!({
+                                                "backend": b.backend,
+                                                "hashrate": b.hashrate,
+                                                "rejected": b.rejected,
+                                            }))
+                                            .collect::<Vec<_>>(),
                                         "next_step": "qc-08 (Consensus Validation)"
                                     }
                                 });
@@ -565,6 +1159,7 @@ impl BlockProducerService for ConcreteBlockProducer {
                                     let mut status_guard = status.write().unwrap();
                                     status_guard.blocks_produced = blocks_mined;
                                     status_guard.hashrate = hashrate;
+                                    status_guard.backend_hashrates = backend_hashrates.clone();
                                     status_guard.last_block_at = Some(timestamp);
                                     status_guard.current_difficulty = Some(difficulty);
                                     status_guard.last_nonce = Some(nonce);
@@ -580,34 +1175,31 @@ impl BlockProducerService for ConcreteBlockProducer {
 
                                 // V2.3 CHOREOGRAPHY: Publish BlockProduced event directly
                                 // This triggers qc-08 (Consensus) to validate the block
-                                use shared_bus::BlockchainEvent;
-                                use shared_bus::EventPublisher;
-                                let block_hash_event: [u8; 32] = block_hash;
-                                let difficulty_bytes: [u8; 32] = {
-                                    let mut bytes = [0u8; 32];
-                                    difficulty.to_big_endian(&mut bytes);
-                                    bytes
-                                };
-                                let event = BlockchainEvent::BlockProduced {
-                                    block_height: block_number,
-                                    block_hash: block_hash_event,
-                                    difficulty: difficulty_bytes,
+                                let event = Self::block_produced_event(
+                                    &template_header,
+                                    template_fees,
+                                    block_hash,
                                     nonce,
-                                    timestamp,
-                                    parent_hash: parent_hash.0,
-                                };
+                                );
                                 let receivers = event_bus.publish(event).await;
                                 info!(
                                     "[qc-17] 📤 Published BlockProduced event for block #{} (receivers: {})",
                                     block_number, receivers
                                 );
 
-                                // NOTE: Bridge polling is now REDUNDANT - can be removed in Phase 2
+                                let mut report = mev_detector.analyze(
+                                    MevContext {
+                                        block_number,
+                                        gas_limit: block_config.gas_limit,
+                                        min_gas_price: block_config.min_gas_price,
+                                    },
+                                    &candidates,
+                                    &candidates,
+                                );
+                                report.block_hash = H256(block_hash);
+                                Self::publish_mev_report(&event_bus, &mev_reports, report).await;
 
-                                // Update last block hash for proper chain linking
-                                // Use the hash from mining directly
-                                last_block_hash = H256::from_slice(&block_hash);
-                                last_block_timestamp = timestamp;
+                                // NOTE: Bridge polling is now REDUNDANT - can be removed in Phase 2
 
                                 // CRITICAL: Enforce minimum block interval
                                 // Even if mining is fast, don't start next block immediately
@@ -626,6 +1218,12 @@ impl BlockProducerService for ConcreteBlockProducer {
                                         .await;
                                 }
                             }
+                            None if stale => {
+                                info!(
+                                    "[qc-17] Template for block #{} went stale, rebuilding",
+                                    block_number
+                                );
+                            }
                             None => {
                                 error!(
                                     "[qc-17] Failed to mine block #{} - no valid nonce found",
@@ -647,8 +1245,29 @@ impl BlockProducerService for ConcreteBlockProducer {
             }
             ConsensusMode::ProofOfStake => {
                 info!("  Mode: PoS Proposing");
-                // Slot assignments handled via choreography (Phase 7 for PoS)
-                warn!("PoS proposing not yet implemented");
+
+                // Duties from the validator duty task, attestations from qc-10,
+                // stored heads from qc-02
+                let subscription = self.event_bus.subscribe(EventFilter::topics(vec![
+                    EventTopic::Consensus,
+                    EventTopic::SignatureVerification,
+                    EventTopic::BlockStorage,
+                ]));
+                let proposer = SlotProposer {
+                    signer: self.signature_provider.clone().expect("checked above"),
+                    slashing_protection: self.slashing_protection.clone(),
+                    mempool_reader: self.mempool_reader.clone(),
+                    event_bus: Arc::clone(&self.event_bus),
+                    config: self.config_sync(),
+                    chain_head: Arc::clone(&self.external_work),
+                    status: Arc::clone(&self.status),
+                    mev_reports: Arc::clone(&self.mev_reports),
+                    attestations: AttestationPool::default(),
+                    mev_detector: MevDetector::default(),
+                    last_slot: None,
+                };
+                let proposer_task = tokio::task::spawn(proposer.run(subscription));
+                *self.mining_handle.lock().unwrap() = Some(proposer_task);
             }
             ConsensusMode::PBFT => {
                 info!("  Mode: PBFT Leader Proposal");
@@ -717,9 +1336,15 @@ mod tests {
     #[tokio::test]
     async fn test_start_stop() {
         let event_bus = Arc::new(InMemoryEventBus::new());
-        let config = BlockProductionConfig::default();
+        let config = BlockProductionConfig {
+            fee_recipient: Some([0x22; 20]),
+            ..BlockProductionConfig::default()
+        };
 
-        let service = ConcreteBlockProducer::new(event_bus, config);
+        let service =
+            ConcreteBlockProducer::new(event_bus, config).with_signature_provider(Arc::new(
+                crate::adapters::pos::Ed25519BlockSigner::from_seed([1u8; 32]),
+            ));
 
         assert!(service
             .start_production(ConsensusMode::ProofOfStake, ProductionConfig::default())
@@ -731,6 +1356,129 @@ mod tests {
         assert!(!service.get_status().await.active);
     }
 
+    #[test]
+    fn test_duty_cycle_changes_at_runtime() {
+        let event_bus = Arc::new(InMemoryEventBus::new());
+        let config = BlockProductionConfig {
+            pow: Some(crate::PoWConfig::default()),
+            ..BlockProductionConfig::default()
+        };
+        let service = ConcreteBlockProducer::new(event_bus, config);
+        assert_eq!(service.duty_cycle_percent(), 100);
+
+        service.set_duty_cycle_percent(25).unwrap();
+        assert_eq!(service.duty_cycle_percent(), 25);
+        let pow = service.config_sync().pow.unwrap();
+        assert_eq!(pow.throttle.duty_cycle_percent, 25);
+
+        for invalid in [0, 101] {
+            assert!(matches!(
+                service.set_duty_cycle_percent(invalid),
+                Err(BlockProductionError::InvalidConfig(_))
+            ));
+        }
+        assert_eq!(service.duty_cycle_percent(), 25);
+    }
+
+    #[tokio::test]
+    async fn test_pos_requires_signature_provider() {
+        let event_bus = Arc::new(InMemoryEventBus::new());
+        let service = ConcreteBlockProducer::new(event_bus, BlockProductionConfig::default());
+
+        assert!(matches!(
+            service
+                .start_production(ConsensusMode::ProofOfStake, ProductionConfig::default())
+                .await,
+            Err(BlockProductionError::InvalidConfig(_))
+        ));
+        assert!(!service.get_status().await.active);
+    }
+
+    #[tokio::test]
+    async fn test_pos_requires_fee_recipient() {
+        let event_bus = Arc::new(InMemoryEventBus::new());
+        let service = ConcreteBlockProducer::new(event_bus, BlockProductionConfig::default())
+            .with_signature_provider(Arc::new(
+                crate::adapters::pos::Ed25519BlockSigner::from_seed([1u8; 32]),
+            ));
+
+        assert!(matches!(
+            service
+                .start_production(ConsensusMode::ProofOfStake, ProductionConfig::default())
+                .await,
+            Err(BlockProductionError::InvalidConfig(_))
+        ));
+        assert!(!service.get_status().await.active);
+    }
+
+    #[tokio::test]
+    async fn test_slot_assignment_publishes_proposal() {
+        let event_bus = Arc::new(InMemoryEventBus::new());
+        let config = BlockProductionConfig {
+            fee_recipient: Some([0x22; 20]),
+            ..BlockProductionConfig::default()
+        };
+        let service = ConcreteBlockProducer::new(Arc::clone(&event_bus), config)
+            .with_signature_provider(Arc::new(
+                crate::adapters::pos::Ed25519BlockSigner::from_seed([1u8; 32]),
+            ));
+        let mut proposals =
+            event_bus.subscribe(EventFilter::topics(vec![EventTopic::BlockProduction]));
+        service
+            .start_production(ConsensusMode::ProofOfStake, ProductionConfig::default())
+            .await
+            .unwrap();
+        // Let the proposer task subscribe
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        event_bus
+            .publish(BlockchainEvent::SlotAssigned {
+                slot: 3,
+                epoch: 0,
+                validator_index: 2,
+                vrf_output: [0u8; 32],
+                vrf_proof: vec![0u8; 80],
+            })
+            .await;
+
+        let event = tokio::time::timeout(Duration::from_secs(1), proposals.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let BlockchainEvent::BlockProposed {
+            block_height,
+            slot,
+            validator_index,
+            signature,
+            ..
+        } = event
+        else {
+            panic!("expected BlockProposed, got {:?}", event);
+        };
+        assert_eq!((block_height, slot, validator_index), (1, 3, 2));
+        assert_eq!(signature.len(), 64);
+        assert_eq!(service.get_status().await.blocks_produced, 1);
+
+        service.stop_production().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_external_work_requires_active_producer() {
+        let event_bus = Arc::new(InMemoryEventBus::new());
+        let service = ConcreteBlockProducer::new(event_bus, BlockProductionConfig::default());
+
+        assert!(matches!(
+            service.get_block_template(None).await,
+            Err(BlockProductionError::NotActive)
+        ));
+        assert!(matches!(
+            service.submit_block("0000000000000001", 0).await,
+            Err(BlockProductionError::WorkRejected(
+                crate::domain::WorkSubmitError::UnknownTemplate(_)
+            ))
+        ));
+    }
+
     #[tokio::test]
     async fn test_update_config() {
         let event_bus = Arc::new(InMemoryEventBus::new());
@@ -784,6 +1532,96 @@ mod tests {
         assert_eq!(production_config.starting_height, 0);
     }
 
+    /// Mempool whose pending transactions the test replaces
+    struct SwappableMempool(Mutex<Vec<TransactionCandidate>>);
+
+    #[async_trait]
+    impl MempoolReader for SwappableMempool {
+        async fn get_pending_transactions(
+            &self,
+            _max_count: u32,
+            _min_gas_price: U256,
+        ) -> Result<Vec<TransactionCandidate>> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+    }
+
+    fn pending(gwei: &[u64]) -> Vec<TransactionCandidate> {
+        gwei.iter()
+            .zip(1u8..)
+            .map(|(gwei, sender)| TransactionCandidate {
+                transaction: vec![sender; 4],
+                from: [sender; 20],
+                nonce: 0,
+                gas_price: U256::from(*gwei) * U256::exp10(9),
+                gas_limit: 21_000,
+                signature_valid: true,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_template_rebuilt_for_better_fees() {
+        let config = BlockProductionConfig {
+            fee_rebuild_percent: 50,
+            ..Default::default()
+        };
+        let policy = config.fill_policy();
+        let parent = ChainHead::default();
+        let current = ConcreteBlockProducer::build_template(
+            &parent,
+            [0u8; 20],
+            pending(&[10, 10]),
+            U256::MAX,
+            config.gas_limit,
+            ConsensusMode::ProofOfWork,
+        )
+        .unwrap();
+        let swappable = Arc::new(SwappableMempool(Mutex::new(pending(&[10, 40]))));
+        let mempool: Arc<dyn MempoolReader> = swappable.clone();
+        let long_ago = std::time::Instant::now() - MEMPOOL_POLL_INTERVAL;
+        let mut last_check = long_ago;
+
+        // Same fees (per transaction in the current fee model)
+        let better = ConcreteBlockProducer::better_template(
+            Some(&mempool),
+            &policy,
+            &config,
+            &current,
+            &parent,
+            &mut last_check,
+        );
+        assert!(better.await.is_none());
+
+        // Within the poll interval the mempool is not read again
+        *swappable.0.lock().unwrap() = pending(&[10, 10, 10]);
+        let better = ConcreteBlockProducer::better_template(
+            Some(&mempool),
+            &policy,
+            &config,
+            &current,
+            &parent,
+            &mut last_check,
+        );
+        assert!(better.await.is_none());
+
+        last_check = long_ago;
+        let better = ConcreteBlockProducer::better_template(
+            Some(&mempool),
+            &policy,
+            &config,
+            &current,
+            &parent,
+            &mut last_check,
+        );
+        let (rebuilt, candidates) = better.await.unwrap();
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(rebuilt.transactions.len(), 4);
+        assert_eq!(rebuilt.header.block_number, current.header.block_number);
+        assert_eq!(rebuilt.header.difficulty, U256::MAX);
+        assert!(rebuilt.total_fees > current.total_fees);
+    }
+
     #[test]
     fn test_initial_difficulty_uses_config() {
         // Verify that the fallback uses DifficultyConfig::default().initial_difficulty
@@ -0,0 +1,66 @@
+//! OpenCL batch hashing throughput with and without staging buffer reuse
+//!
+//! Needs an OpenCL device:
+//!
+//! ```text
+//! cargo bench -p qc-compute --features opencl --bench opencl_staging
+//! ```
+//!
+//! | Engine | Staging |
+//! |--------|---------|
+//! | `pooled` | Pinned buffer sets reused across calls (default) |
+//! | `per_call` | Pinned buffers allocated for every call |
+//! | `cpu` | Rayon reference |
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use futures::executor::block_on;
+use qc_compute::backends::cpu::CpuEngine;
+use qc_compute::backends::opencl::OpenCLEngine;
+use qc_compute::ComputeEngine;
+
+/// Bytes per input (two SHA-256 blocks, like a serialized transaction)
+const INPUT_LEN: usize = 128;
+
+fn batch(inputs: usize) -> Vec<Vec<u8>> {
+    (0..inputs)
+        .map(|i| {
+            let mut input = vec![0u8; INPUT_LEN];
+            input[..8].copy_from_slice(&(i as u64).to_le_bytes());
+            input
+        })
+        .collect()
+}
+
+fn bench_batch_sha256(c: &mut Criterion) {
+    let (Ok(pooled), Ok(per_call)) = (OpenCLEngine::new(), OpenCLEngine::new()) else {
+        eprintln!("No OpenCL device, skipping staging benchmarks");
+        return;
+    };
+    let per_call = per_call.with_staging_pool_size(0);
+    let cpu = CpuEngine::new();
+    let engines: [(&str, &dyn ComputeEngine); 3] =
+        [("pooled", &pooled), ("per_call", &per_call), ("cpu", &cpu)];
+
+    let mut group = c.benchmark_group("opencl-batch-sha256");
+    group.sample_size(20);
+    // Smallest size is just above GPU_BATCH_MIN_BYTES
+    for inputs in [16_384usize, 131_072, 1_048_576] {
+        let inputs_batch = batch(inputs);
+        group.throughput(Throughput::Bytes((inputs * INPUT_LEN) as u64));
+        for (name, engine) in engines {
+            group.bench_with_input(BenchmarkId::new(name, inputs), &inputs_batch, |b, batch| {
+                b.iter(|| block_on(engine.batch_sha256(batch)).unwrap())
+            });
+        }
+    }
+    group.finish();
+
+    let stats = pooled.staging_stats();
+    eprintln!(
+        "pooled staging: {} allocations, {} reuses",
+        stats.allocations, stats.reuses
+    );
+}
+
+criterion_group!(benches, bench_batch_sha256);
+criterion_main!(benches);
@@ -7,6 +7,7 @@ use crate::{Backend, ComputeEngine, ComputeError, DeviceInfo};
 use primitive_types::U256;
 use rayon::prelude::*;
 use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 
 /// CPU-based compute engine using Rayon
 pub struct CpuEngine {
@@ -61,6 +62,20 @@ impl ComputeEngine for CpuEngine {
         Ok(results)
     }
 
+    async fn batch_keccak256(&self, inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
+        Ok(inputs
+            .par_iter()
+            .map(|input| Keccak256::digest(input).into())
+            .collect())
+    }
+
+    async fn batch_blake3(&self, inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
+        Ok(inputs
+            .par_iter()
+            .map(|input| *blake3::hash(input).as_bytes())
+            .collect())
+    }
+
     async fn pow_mine(
         &self,
         header_template: &[u8],
@@ -167,6 +182,26 @@ impl ComputeEngine for CpuEngine {
 
         Ok(results)
     }
+
+    async fn batch_verify_bls(
+        &self,
+        messages: &[Vec<u8>],
+        signatures: &[[u8; 48]],
+        public_keys: &[[u8; 96]],
+    ) -> Result<Vec<bool>, ComputeError> {
+        crate::bls::check_lengths(messages, signatures, public_keys)?;
+
+        // One combined check covers the common all-valid case
+        if crate::bls::verify_all(messages, signatures, public_keys) {
+            return Ok(vec![true; messages.len()]);
+        }
+
+        // Otherwise find the invalid ones in parallel
+        Ok((0..messages.len())
+            .into_par_iter()
+            .map(|i| crate::bls::verify_one(&messages[i], &signatures[i], &public_keys[i]))
+            .collect())
+    }
 }
 
 #[cfg(test)]
@@ -186,6 +221,67 @@ mod tests {
         assert_eq!(results[0], expected.as_slice());
     }
 
+    #[tokio::test]
+    async fn test_batch_keccak256_and_blake3() {
+        let engine = CpuEngine::new();
+        let inputs = vec![Vec::new(), b"hello".to_vec()];
+
+        let keccak = engine.batch_keccak256(&inputs).await.unwrap();
+        assert_eq!(
+            hex(&keccak[0]),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_eq!(keccak[1], Keccak256::digest(b"hello").as_slice());
+
+        let blake = engine.batch_blake3(&inputs).await.unwrap();
+        assert_eq!(
+            hex(&blake[0]),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+        assert_eq!(blake[1], *blake3::hash(b"hello").as_bytes());
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_batch_verify_bls_flags_invalid() {
+        use blst::min_sig::SecretKey;
+
+        let engine = CpuEngine::new();
+        let messages: Vec<Vec<u8>> = (0..3u8).map(|i| vec![i; 32]).collect();
+        let (mut signatures, public_keys): (Vec<_>, Vec<_>) = messages
+            .iter()
+            .enumerate()
+            .map(|(i, message)| {
+                let sk = SecretKey::key_gen(&[i as u8 + 7; 32], &[]).unwrap();
+                (
+                    sk.sign(message, crate::bls::DST, &[]).to_bytes(),
+                    sk.sk_to_pk().to_bytes(),
+                )
+            })
+            .unzip();
+
+        let results = engine
+            .batch_verify_bls(&messages, &signatures, &public_keys)
+            .await
+            .unwrap();
+        assert_eq!(results, vec![true, true, true]);
+
+        signatures[1] = [0u8; 48];
+        let results = engine
+            .batch_verify_bls(&messages, &signatures, &public_keys)
+            .await
+            .unwrap();
+        assert_eq!(results, vec![true, false, true]);
+
+        assert!(engine
+            .batch_verify_bls(&messages, &signatures[..2], &public_keys)
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_pow_mine_easy_target() {
         let engine = CpuEngine::new();
@@ -6,9 +6,17 @@
 #[cfg(feature = "cpu")]
 pub mod cpu;
 
+pub mod multi;
+
 #[cfg(feature = "opencl")]
 pub mod opencl;
 
+#[cfg(feature = "opencl")]
+mod staging;
+
+#[cfg(feature = "opencl")]
+pub use staging::StagingStats;
+
 // NOTE: Vulkan backend removed - vulkano-shaders requires shaderc/cmake
 // which breaks compilation on systems without these tools.
 // Use OpenCL for GPU acceleration instead (more portable anyway)
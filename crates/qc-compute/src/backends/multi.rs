@@ -0,0 +1,624 @@
+//! Multi-device compute backend
+//!
+//! `MultiEngine` drives every detected device at once (each OpenCL GPU plus
+//! the CPU). Work is split into contiguous slices sized by each device's
+//! measured throughput, every slice runs on its own thread, and the results
+//! are stitched back together in input order.
+//!
+//! Devices start out weighted by compute units; after each run their
+//! throughput is re-measured (exponentially smoothed), so a fast GPU soon
+//! receives most of the nonce range while the CPU keeps a small share.
+//! A slice whose device fails is retried on the fastest healthy device.
+
+use crate::{Backend, ComputeEngine, ComputeError, DeviceInfo};
+use primitive_types::U256;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Weight of the newest throughput sample in the smoothed rate
+const RATE_SMOOTHING: f64 = 0.3;
+
+/// Per-device work counters
+#[derive(Debug, Clone)]
+pub struct DeviceStats {
+    /// Device name
+    pub name: String,
+    /// Device backend
+    pub backend: Backend,
+    /// Nonces searched by `pow_mine`
+    pub nonces_searched: u64,
+    /// Solutions found by `pow_mine`
+    pub solutions: u64,
+    /// Inputs hashed by the batch hash methods
+    pub inputs_hashed: u64,
+    /// Time spent on this device's slices
+    pub busy: Duration,
+    /// Failed slices (retried elsewhere)
+    pub errors: u64,
+    /// Smoothed nonces per second (None until measured)
+    pub mine_rate: Option<f64>,
+    /// Smoothed inputs hashed per second (None until measured)
+    pub hash_rate: Option<f64>,
+}
+
+/// Workload whose throughput is tracked per device
+#[derive(Clone, Copy)]
+enum Workload {
+    Mine,
+    Hash,
+}
+
+/// Batch hash function a slice of inputs is run through
+#[derive(Clone, Copy)]
+enum HashFunction {
+    Sha256,
+    Keccak256,
+    Blake3,
+}
+
+impl HashFunction {
+    async fn run(
+        self,
+        engine: &dyn ComputeEngine,
+        inputs: &[Vec<u8>],
+    ) -> Result<Vec<[u8; 32]>, ComputeError> {
+        match self {
+            HashFunction::Sha256 => engine.batch_sha256(inputs).await,
+            HashFunction::Keccak256 => engine.batch_keccak256(inputs).await,
+            HashFunction::Blake3 => engine.batch_blake3(inputs).await,
+        }
+    }
+}
+
+struct Device {
+    engine: Arc<dyn ComputeEngine>,
+    stats: Mutex<DeviceStats>,
+}
+
+impl Device {
+    /// Share of work relative to the other devices
+    fn weight(&self, workload: Workload) -> f64 {
+        let stats = self.stats.lock().expect("device stats mutex poisoned");
+        let measured = match workload {
+            Workload::Mine => stats.mine_rate,
+            Workload::Hash => stats.hash_rate,
+        };
+        measured.unwrap_or_else(|| f64::from(self.engine.device_info().compute_units.max(1)))
+    }
+
+    fn record(&self, workload: Workload, units: u64, elapsed: Duration, solved: bool) {
+        let mut guard = self.stats.lock().expect("device stats mutex poisoned");
+        let stats = &mut *guard;
+        stats.busy += elapsed;
+        let (counter, rate) = match workload {
+            Workload::Mine => (&mut stats.nonces_searched, &mut stats.mine_rate),
+            Workload::Hash => (&mut stats.inputs_hashed, &mut stats.hash_rate),
+        };
+        *counter += units;
+        if units > 0 && !elapsed.is_zero() {
+            let sample = units as f64 / elapsed.as_secs_f64();
+            *rate = Some(rate.map_or(sample, |previous| {
+                previous + RATE_SMOOTHING * (sample - previous)
+            }));
+        }
+        if solved {
+            stats.solutions += 1;
+        }
+    }
+
+    /// Hash `slice` on this device, recording throughput or the failure
+    fn hash(
+        &self,
+        function: HashFunction,
+        slice: &[Vec<u8>],
+    ) -> Result<Vec<[u8; 32]>, ComputeError> {
+        if slice.is_empty() {
+            return Ok(Vec::new());
+        }
+        let started = Instant::now();
+        let result = futures::executor::block_on(function.run(&*self.engine, slice));
+        match &result {
+            Ok(_) => self.record(Workload::Hash, slice.len() as u64, started.elapsed(), false),
+            Err(_) => self.record_error(),
+        }
+        result
+    }
+
+    fn record_error(&self) {
+        self.stats
+            .lock()
+            .expect("device stats mutex poisoned")
+            .errors += 1;
+    }
+}
+
+/// Compute engine spreading work across several devices
+pub struct MultiEngine {
+    devices: Vec<Device>,
+    device_info: DeviceInfo,
+}
+
+impl MultiEngine {
+    /// Combine `engines`; the first one is preferred for unsplit work
+    pub fn new(engines: Vec<Arc<dyn ComputeEngine>>) -> Result<Self, ComputeError> {
+        if engines.is_empty() {
+            return Err(ComputeError::NoBackendAvailable);
+        }
+
+        let device_info = DeviceInfo {
+            name: engines
+                .iter()
+                .map(|engine| engine.device_info().name.clone())
+                .collect::<Vec<_>>()
+                .join(" + "),
+            backend: engines[0].backend(),
+            compute_units: engines
+                .iter()
+                .map(|engine| engine.device_info().compute_units)
+                .sum(),
+            memory_bytes: engines
+                .iter()
+                .map(|engine| engine.device_info().memory_bytes)
+                .sum(),
+            supports_f64: engines
+                .iter()
+                .all(|engine| engine.device_info().supports_f64),
+        };
+        let devices = engines
+            .into_iter()
+            .map(|engine| Device {
+                stats: Mutex::new(DeviceStats {
+                    name: engine.device_info().name.clone(),
+                    backend: engine.backend(),
+                    nonces_searched: 0,
+                    solutions: 0,
+                    inputs_hashed: 0,
+                    busy: Duration::ZERO,
+                    errors: 0,
+                    mine_rate: None,
+                    hash_rate: None,
+                }),
+                engine,
+            })
+            .collect();
+
+        Ok(Self {
+            devices,
+            device_info,
+        })
+    }
+
+    /// Enumerate every available device (all OpenCL GPUs, then the CPU)
+    pub fn detect() -> Result<Self, ComputeError> {
+        let cpu: Option<Arc<dyn ComputeEngine>> = {
+            #[cfg(feature = "cpu")]
+            {
+                Some(Arc::new(super::cpu::CpuEngine::new()))
+            }
+            #[cfg(not(feature = "cpu"))]
+            {
+                None
+            }
+        };
+        Self::new(Self::detect_gpus().into_iter().chain(cpu).collect())
+    }
+
+    fn detect_gpus() -> Vec<Arc<dyn ComputeEngine>> {
+        #[cfg(feature = "opencl")]
+        match super::opencl::OpenCLEngine::all() {
+            Ok(gpus) => {
+                return gpus
+                    .into_iter()
+                    .map(|gpu| Arc::new(gpu) as Arc<dyn ComputeEngine>)
+                    .collect()
+            }
+            Err(e) => tracing::debug!("OpenCL not available: {}", e),
+        }
+        Vec::new()
+    }
+
+    /// Number of devices
+    pub fn device_count(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// Work counters and measured throughput, one entry per device
+    pub fn device_stats(&self) -> Vec<DeviceStats> {
+        self.devices
+            .iter()
+            .map(|device| {
+                device
+                    .stats
+                    .lock()
+                    .expect("device stats mutex poisoned")
+                    .clone()
+            })
+            .collect()
+    }
+
+    /// Split `total` units across the devices by throughput
+    fn shares(&self, workload: Workload, total: u64) -> Vec<u64> {
+        let weights: Vec<f64> = self
+            .devices
+            .iter()
+            .map(|device| device.weight(workload))
+            .collect();
+        partition(total, &weights)
+    }
+
+    /// Fastest device that did not fail in this run
+    fn fallback(&self, workload: Workload, failed: &[bool]) -> Option<&Device> {
+        self.devices
+            .iter()
+            .zip(failed)
+            .filter(|(_, failed)| !**failed)
+            .map(|(device, _)| device)
+            .max_by(|a, b| a.weight(workload).total_cmp(&b.weight(workload)))
+    }
+
+    /// Split `inputs` across the devices and hash each slice with `function`
+    async fn batch_hash(
+        &self,
+        function: HashFunction,
+        inputs: &[Vec<u8>],
+    ) -> Result<Vec<[u8; 32]>, ComputeError> {
+        let shares = self.shares(Workload::Hash, inputs.len() as u64);
+        let mut slices = Vec::with_capacity(shares.len());
+        let mut rest = inputs;
+        for share in &shares {
+            let (slice, tail) = rest.split_at(*share as usize);
+            slices.push(slice);
+            rest = tail;
+        }
+
+        let outcomes: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .devices
+                .iter()
+                .zip(&slices)
+                .map(|(device, slice)| scope.spawn(move || device.hash(function, slice)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("device thread panicked"))
+                .collect()
+        });
+
+        let failed: Vec<bool> = outcomes.iter().map(Result::is_err).collect();
+        let mut hashes = Vec::with_capacity(inputs.len());
+        for (outcome, slice) in outcomes.into_iter().zip(&slices) {
+            match outcome {
+                Ok(part) => hashes.extend(part),
+                Err(e) => {
+                    let device = self.fallback(Workload::Hash, &failed).ok_or(e)?;
+                    hashes.extend(function.run(&*device.engine, slice).await?);
+                }
+            }
+        }
+        Ok(hashes)
+    }
+}
+
+/// Split `total` into one contiguous share per weight
+///
+/// Shares are proportional to the weights and always add up to `total`;
+/// the rounding remainder goes to the heaviest weights.
+pub(crate) fn partition(total: u64, weights: &[f64]) -> Vec<u64> {
+    let sum: f64 = weights.iter().map(|weight| weight.max(0.0)).sum();
+    if weights.is_empty() {
+        return Vec::new();
+    }
+    if sum <= 0.0 {
+        let mut shares = vec![total / weights.len() as u64; weights.len()];
+        shares[0] += total % weights.len() as u64;
+        return shares;
+    }
+
+    let mut shares: Vec<u64> = weights
+        .iter()
+        .map(|weight| (total as f64 * weight.max(0.0) / sum).floor() as u64)
+        .collect();
+    let assigned: u64 = shares.iter().sum();
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by(|&a, &b| weights[b].total_cmp(&weights[a]));
+    for index in order
+        .into_iter()
+        .cycle()
+        .take(total.saturating_sub(assigned) as usize)
+    {
+        shares[index] += 1;
+    }
+    shares
+}
+
+#[async_trait::async_trait]
+impl ComputeEngine for MultiEngine {
+    fn backend(&self) -> Backend {
+        self.device_info.backend
+    }
+
+    fn device_info(&self) -> &DeviceInfo {
+        &self.device_info
+    }
+
+    async fn batch_sha256(&self, inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
+        self.batch_hash(HashFunction::Sha256, inputs).await
+    }
+
+    async fn batch_keccak256(&self, inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
+        self.batch_hash(HashFunction::Keccak256, inputs).await
+    }
+
+    async fn batch_blake3(&self, inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
+        self.batch_hash(HashFunction::Blake3, inputs).await
+    }
+
+    async fn pow_mine(
+        &self,
+        header_template: &[u8],
+        target: U256,
+        nonce_start: u64,
+        nonce_count: u64,
+    ) -> Result<Option<(u64, [u8; 32])>, ComputeError> {
+        let shares = self.shares(Workload::Mine, nonce_count);
+        let mut ranges = Vec::with_capacity(shares.len());
+        let mut start = nonce_start;
+        for share in shares {
+            ranges.push((start, share));
+            start = start.wrapping_add(share);
+        }
+
+        let outcomes: Vec<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .devices
+                .iter()
+                .zip(&ranges)
+                .map(|(device, &(start, count))| {
+                    scope.spawn(move || {
+                        if count == 0 {
+                            return Ok(None);
+                        }
+                        let started = Instant::now();
+                        let result = futures::executor::block_on(device.engine.pow_mine(
+                            header_template,
+                            target,
+                            start,
+                            count,
+                        ));
+                        match &result {
+                            Ok(found) => device.record(
+                                Workload::Mine,
+                                count,
+                                started.elapsed(),
+                                found.is_some(),
+                            ),
+                            Err(_) => device.record_error(),
+                        }
+                        result
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("device thread panicked"))
+                .collect()
+        });
+
+        // Lowest nonce wins, so the answer does not depend on device timing
+        let failed: Vec<bool> = outcomes.iter().map(Result::is_err).collect();
+        let mut best: Option<(u64, [u8; 32])> = None;
+        for (outcome, &(start, count)) in outcomes.into_iter().zip(&ranges) {
+            let found = match outcome {
+                Ok(found) => found,
+                Err(e) => {
+                    let device = self.fallback(Workload::Mine, &failed).ok_or(e)?;
+                    device
+                        .engine
+                        .pow_mine(header_template, target, start, count)
+                        .await?
+                }
+            };
+            if let Some((nonce, hash)) = found {
+                if best.is_none_or(|(best_nonce, _)| nonce < best_nonce) {
+                    best = Some((nonce, hash));
+                }
+            }
+        }
+        Ok(best)
+    }
+
+    async fn batch_verify_ecdsa(
+        &self,
+        messages: &[[u8; 32]],
+        signatures: &[[u8; 65]],
+        public_keys: &[[u8; 33]],
+    ) -> Result<Vec<bool>, ComputeError> {
+        // Verification batches are small; the preferred device handles them
+        self.devices[0]
+            .engine
+            .batch_verify_ecdsa(messages, signatures, public_keys)
+            .await
+    }
+
+    async fn batch_verify_bls(
+        &self,
+        messages: &[Vec<u8>],
+        signatures: &[[u8; 48]],
+        public_keys: &[[u8; 96]],
+    ) -> Result<Vec<bool>, ComputeError> {
+        self.devices[0]
+            .engine
+            .batch_verify_bls(messages, signatures, public_keys)
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "cpu"))]
+mod tests {
+    use super::*;
+    use crate::backends::cpu::CpuEngine;
+    use sha2::{Digest, Sha256};
+
+    /// Device that always fails
+    struct BrokenEngine {
+        device_info: DeviceInfo,
+    }
+
+    impl BrokenEngine {
+        fn new() -> Self {
+            Self {
+                device_info: DeviceInfo {
+                    name: "broken".to_string(),
+                    backend: Backend::OpenCL,
+                    compute_units: 64,
+                    memory_bytes: 0,
+                    supports_f64: false,
+                },
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ComputeEngine for BrokenEngine {
+        fn backend(&self) -> Backend {
+            Backend::OpenCL
+        }
+
+        fn device_info(&self) -> &DeviceInfo {
+            &self.device_info
+        }
+
+        async fn batch_sha256(&self, _inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
+            Err(ComputeError::TaskFailed("device lost".to_string()))
+        }
+
+        async fn batch_keccak256(
+            &self,
+            _inputs: &[Vec<u8>],
+        ) -> Result<Vec<[u8; 32]>, ComputeError> {
+            Err(ComputeError::TaskFailed("device lost".to_string()))
+        }
+
+        async fn batch_blake3(&self, _inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
+            Err(ComputeError::TaskFailed("device lost".to_string()))
+        }
+
+        async fn pow_mine(
+            &self,
+            _header_template: &[u8],
+            _target: U256,
+            _nonce_start: u64,
+            _nonce_count: u64,
+        ) -> Result<Option<(u64, [u8; 32])>, ComputeError> {
+            Err(ComputeError::TaskFailed("device lost".to_string()))
+        }
+
+        async fn batch_verify_ecdsa(
+            &self,
+            _messages: &[[u8; 32]],
+            _signatures: &[[u8; 65]],
+            _public_keys: &[[u8; 33]],
+        ) -> Result<Vec<bool>, ComputeError> {
+            Err(ComputeError::TaskFailed("device lost".to_string()))
+        }
+
+        async fn batch_verify_bls(
+            &self,
+            _messages: &[Vec<u8>],
+            _signatures: &[[u8; 48]],
+            _public_keys: &[[u8; 96]],
+        ) -> Result<Vec<bool>, ComputeError> {
+            Err(ComputeError::TaskFailed("device lost".to_string()))
+        }
+    }
+
+    fn two_cpus() -> MultiEngine {
+        MultiEngine::new(vec![Arc::new(CpuEngine::new()), Arc::new(CpuEngine::new())]).unwrap()
+    }
+
+    #[test]
+    fn test_partition_is_proportional_and_exact() {
+        assert_eq!(partition(100, &[3.0, 1.0]), vec![75, 25]);
+        assert_eq!(partition(10, &[1.0, 1.0, 1.0]), vec![4, 3, 3]);
+        assert_eq!(partition(7, &[0.0, 0.0]), vec![4, 3]);
+        assert_eq!(partition(5, &[1.0, 1e9]), vec![0, 5]);
+        assert!(partition(5, &[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_batch_sha256_keeps_input_order() {
+        let engine = two_cpus();
+        let inputs: Vec<Vec<u8>> = (0..50u32).map(|i| i.to_le_bytes().to_vec()).collect();
+
+        let hashes = engine.batch_sha256(&inputs).await.unwrap();
+        assert_eq!(hashes.len(), inputs.len());
+        for (input, hash) in inputs.iter().zip(&hashes) {
+            assert_eq!(hash.as_slice(), Sha256::digest(input).as_slice());
+        }
+        let hashed: u64 = engine
+            .device_stats()
+            .iter()
+            .map(|stats| stats.inputs_hashed)
+            .sum();
+        assert_eq!(hashed, 50);
+
+        let hashes = engine.batch_blake3(&inputs).await.unwrap();
+        for (input, hash) in inputs.iter().zip(&hashes) {
+            assert_eq!(hash, blake3::hash(input).as_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pow_mine_splits_range_and_measures_devices() {
+        let engine = two_cpus();
+        let target = U256::MAX / 4;
+
+        let (nonce, hash) = engine
+            .pow_mine(b"multi_header", target, 1_000, 100_000)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!((1_000..101_000).contains(&nonce));
+        assert!(U256::from_big_endian(&hash) <= target);
+
+        let stats = engine.device_stats();
+        assert_eq!(engine.device_count(), 2);
+        assert_eq!(
+            stats.iter().map(|s| s.nonces_searched).sum::<u64>(),
+            100_000
+        );
+        assert!(stats.iter().all(|s| s.mine_rate.is_some()));
+        assert!(stats.iter().map(|s| s.solutions).sum::<u64>() >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_device_work_is_retried() {
+        let engine = MultiEngine::new(vec![
+            Arc::new(BrokenEngine::new()),
+            Arc::new(CpuEngine::new()),
+        ])
+        .unwrap();
+        assert_eq!(
+            engine.device_info().compute_units,
+            64 + num_cpus::get() as u32
+        );
+
+        let inputs = vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()];
+        let hashes = engine.batch_sha256(&inputs).await.unwrap();
+        assert_eq!(hashes[0].as_slice(), Sha256::digest(b"a").as_slice());
+        let hashes = engine.batch_keccak256(&inputs).await.unwrap();
+        assert_eq!(
+            hashes[2].as_slice(),
+            sha3::Keccak256::digest(b"c").as_slice()
+        );
+
+        let found = engine.pow_mine(b"retry", U256::MAX, 0, 10).await.unwrap();
+        assert_eq!(found.map(|(nonce, _)| nonce), Some(0));
+        assert_eq!(engine.device_stats()[0].errors, 3);
+
+        assert!(matches!(
+            MultiEngine::new(Vec::new()),
+            Err(ComputeError::NoBackendAvailable)
+        ));
+    }
+}
@@ -9,10 +9,20 @@
 //! NOTE: OpenCL Kernel objects contain raw pointers and are not thread-safe.
 //! We wrap them in a Mutex to ensure safe concurrent access.
 
+use super::staging::{self, StagingBuffers, StagingPool, StagingStats};
 use crate::{Backend, ComputeEngine, ComputeError, DeviceInfo};
 use primitive_types::U256;
 use std::sync::Mutex;
 
+/// Smallest `batch_sha256` batch (total input bytes) sent to the GPU
+///
+/// Below this, transfer and launch overhead outweigh the kernel and the
+/// batch is hashed on the CPU.
+pub const GPU_BATCH_MIN_BYTES: usize = 1 << 20;
+
+/// Default number of idle staging buffer sets kept per engine
+pub const DEFAULT_STAGING_POOL_SIZE: usize = 4;
+
 /// OpenCL SHA256 kernel source
 const SHA256_KERNEL: &str = r"
 // SHA256 constants
@@ -163,9 +173,371 @@ __kernel void pow_mine(
 }
 ";
 
+/// OpenCL batch SHA256 kernel source (one work item per input)
+///
+/// Appended after `SHA256_KERNEL`, whose `sha256_transform` it uses.
+const SHA256_BATCH_KERNEL: &str = r"
+// One SHA-256 digest per input (arbitrary length).
+// Input i is data[offsets[i] .. offsets[i + 1]].
+__kernel void batch_sha256(
+    __global const uchar* data,
+    __global const uint* offsets,
+    __global uchar* digests
+) {
+    uint gid = get_global_id(0);
+    uint start = offsets[gid];
+    uint len = offsets[gid + 1] - start;
+
+    uint state[8] = {
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+        0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19
+    };
+
+    uchar block[64];
+    uint pos = 0;
+    while (len - pos >= 64) {
+        for (int i = 0; i < 64; i++) {
+            block[i] = data[start + pos + i];
+        }
+        sha256_transform(state, block);
+        pos += 64;
+    }
+
+    // Final block(s): remaining bytes, 0x80, zeros, then the bit length
+    uint rem = len - pos;
+    for (uint i = 0; i < 64; i++) {
+        block[i] = i < rem ? data[start + pos + i] : 0;
+    }
+    block[rem] = 0x80;
+    if (rem >= 56) {
+        sha256_transform(state, block);
+        for (int i = 0; i < 64; i++) {
+            block[i] = 0;
+        }
+    }
+    ulong bit_len = (ulong)len * 8;
+    for (int i = 0; i < 8; i++) {
+        block[63 - i] = (bit_len >> (i * 8)) & 0xFF;
+    }
+    sha256_transform(state, block);
+
+    for (int i = 0; i < 8; i++) {
+        digests[gid * 32 + i * 4] = (state[i] >> 24) & 0xFF;
+        digests[gid * 32 + i * 4 + 1] = (state[i] >> 16) & 0xFF;
+        digests[gid * 32 + i * 4 + 2] = (state[i] >> 8) & 0xFF;
+        digests[gid * 32 + i * 4 + 3] = state[i] & 0xFF;
+    }
+}
+";
+
+/// OpenCL Keccak256 kernel source (one work item per input)
+const KECCAK256_KERNEL: &str = r"
+// Keccak-f[1600] round constants
+__constant ulong KECCAK_RC[24] = {
+    0x0000000000000001UL, 0x0000000000008082UL, 0x800000000000808aUL,
+    0x8000000080008000UL, 0x000000000000808bUL, 0x0000000080000001UL,
+    0x8000000080008081UL, 0x8000000000008009UL, 0x000000000000008aUL,
+    0x0000000000000088UL, 0x0000000080008009UL, 0x000000008000000aUL,
+    0x000000008000808bUL, 0x800000000000008bUL, 0x8000000000008089UL,
+    0x8000000000008003UL, 0x8000000000008002UL, 0x8000000000000080UL,
+    0x000000000000800aUL, 0x800000008000000aUL, 0x8000000080008081UL,
+    0x8000000000008080UL, 0x0000000080000001UL, 0x8000000080008008UL
+};
+
+// Rho rotation offsets and Pi lane order
+__constant int KECCAK_ROTC[24] = {
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14,
+    27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44
+};
+__constant int KECCAK_PILN[24] = {
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4,
+    15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1
+};
+
+#define ROTL64(x, n) (((x) << (n)) | ((x) >> (64 - (n))))
+
+void keccak_f1600(__private ulong* st) {
+    ulong bc[5], t;
+
+    for (int round = 0; round < 24; round++) {
+        // Theta
+        for (int i = 0; i < 5; i++) {
+            bc[i] = st[i] ^ st[i + 5] ^ st[i + 10] ^ st[i + 15] ^ st[i + 20];
+        }
+        for (int i = 0; i < 5; i++) {
+            t = bc[(i + 4) % 5] ^ ROTL64(bc[(i + 1) % 5], 1);
+            for (int j = 0; j < 25; j += 5) {
+                st[j + i] ^= t;
+            }
+        }
+
+        // Rho and Pi
+        t = st[1];
+        for (int i = 0; i < 24; i++) {
+            int j = KECCAK_PILN[i];
+            bc[0] = st[j];
+            st[j] = ROTL64(t, KECCAK_ROTC[i]);
+            t = bc[0];
+        }
+
+        // Chi
+        for (int j = 0; j < 25; j += 5) {
+            for (int i = 0; i < 5; i++) {
+                bc[i] = st[j + i];
+            }
+            for (int i = 0; i < 5; i++) {
+                st[j + i] ^= (~bc[(i + 1) % 5]) & bc[(i + 2) % 5];
+            }
+        }
+
+        // Iota
+        st[0] ^= KECCAK_RC[round];
+    }
+}
+
+// XOR a 136-byte (rate) block into the state, lanes little-endian
+void keccak_absorb(__private ulong* st, __private const uchar* block) {
+    for (int i = 0; i < 17; i++) {
+        ulong lane = 0;
+        for (int b = 0; b < 8; b++) {
+            lane |= (ulong)block[i * 8 + b] << (8 * b);
+        }
+        st[i] ^= lane;
+    }
+    keccak_f1600(st);
+}
+
+// One Keccak-256 (Ethereum padding) digest per input.
+// Input i is data[offsets[i] .. offsets[i + 1]].
+__kernel void batch_keccak256(
+    __global const uchar* data,
+    __global const uint* offsets,
+    __global uchar* digests
+) {
+    uint gid = get_global_id(0);
+    uint start = offsets[gid];
+    uint len = offsets[gid + 1] - start;
+
+    ulong st[25];
+    for (int i = 0; i < 25; i++) {
+        st[i] = 0;
+    }
+
+    uchar block[136];
+    uint pos = 0;
+    while (len - pos >= 136) {
+        for (int i = 0; i < 136; i++) {
+            block[i] = data[start + pos + i];
+        }
+        keccak_absorb(st, block);
+        pos += 136;
+    }
+
+    // Final block: remaining bytes, then 0x01 ... 0x80 padding
+    uint rem = len - pos;
+    for (uint i = 0; i < 136; i++) {
+        block[i] = i < rem ? data[start + pos + i] : 0;
+    }
+    block[rem] ^= 0x01;
+    block[135] ^= 0x80;
+    keccak_absorb(st, block);
+
+    for (int i = 0; i < 32; i++) {
+        digests[gid * 32 + i] = (st[i / 8] >> (8 * (i % 8))) & 0xFF;
+    }
+}
+";
+
+/// OpenCL BLAKE3 kernel source (one work item per input)
+const BLAKE3_KERNEL: &str = r"
+// BLAKE3 IV (same as the SHA-256 IV) and message schedule
+__constant uint B3_IV[8] = {
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+    0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19
+};
+__constant int B3_PERMUTATION[16] = {
+    2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8
+};
+
+#define B3_CHUNK_START 1u
+#define B3_CHUNK_END 2u
+#define B3_PARENT 4u
+#define B3_ROOT 8u
+#define B3_CHUNK_LEN 1024u
+#define B3_BLOCK_LEN 64u
+// Inputs are under 4 GiB (u32 offsets): at most 2^22 chunks, 22 stacked subtrees
+#define B3_MAX_DEPTH 32
+
+#define ROTR32(x, n) (((x) >> (n)) | ((x) << (32 - (n))))
+
+void b3_g(__private uint* s, int a, int b, int c, int d, uint mx, uint my) {
+    s[a] = s[a] + s[b] + mx;
+    s[d] = ROTR32(s[d] ^ s[a], 16);
+    s[c] = s[c] + s[d];
+    s[b] = ROTR32(s[b] ^ s[c], 12);
+    s[a] = s[a] + s[b] + my;
+    s[d] = ROTR32(s[d] ^ s[a], 8);
+    s[c] = s[c] + s[d];
+    s[b] = ROTR32(s[b] ^ s[c], 7);
+}
+
+// Compression function; writes the 8-word chaining value to `out`
+void b3_compress(
+    __private const uint* cv,
+    __private const uint* block,
+    ulong counter,
+    uint block_len,
+    uint flags,
+    __private uint* out
+) {
+    uint s[16];
+    uint m[16];
+    uint permuted[16];
+    for (int i = 0; i < 8; i++) {
+        s[i] = cv[i];
+        s[i + 8] = i < 4 ? B3_IV[i] : 0;
+    }
+    s[12] = (uint)counter;
+    s[13] = (uint)(counter >> 32);
+    s[14] = block_len;
+    s[15] = flags;
+    for (int i = 0; i < 16; i++) {
+        m[i] = block[i];
+    }
+
+    for (int round = 0; round < 7; round++) {
+        b3_g(s, 0, 4, 8, 12, m[0], m[1]);
+        b3_g(s, 1, 5, 9, 13, m[2], m[3]);
+        b3_g(s, 2, 6, 10, 14, m[4], m[5]);
+        b3_g(s, 3, 7, 11, 15, m[6], m[7]);
+        b3_g(s, 0, 5, 10, 15, m[8], m[9]);
+        b3_g(s, 1, 6, 11, 12, m[10], m[11]);
+        b3_g(s, 2, 7, 8, 13, m[12], m[13]);
+        b3_g(s, 3, 4, 9, 14, m[14], m[15]);
+        for (int i = 0; i < 16; i++) {
+            permuted[i] = m[B3_PERMUTATION[i]];
+        }
+        for (int i = 0; i < 16; i++) {
+            m[i] = permuted[i];
+        }
+    }
+
+    for (int i = 0; i < 8; i++) {
+        out[i] = s[i] ^ s[i + 8];
+    }
+}
+
+// Load up to 64 bytes as 16 little-endian words, zero padded
+void b3_load_block(__global const uchar* bytes, uint len, __private uint* block) {
+    for (uint i = 0; i < 16; i++) {
+        uint word = 0;
+        for (uint b = 0; b < 4; b++) {
+            uint index = i * 4 + b;
+            if (index < len) {
+                word |= (uint)bytes[index] << (8 * b);
+            }
+        }
+        block[i] = word;
+    }
+}
+
+// One BLAKE3 digest (32 bytes, unkeyed) per input.
+// Input i is data[offsets[i] .. offsets[i + 1]].
+__kernel void batch_blake3(
+    __global const uchar* data,
+    __global const uint* offsets,
+    __global uchar* digests
+) {
+    uint gid = get_global_id(0);
+    uint start = offsets[gid];
+    uint len = offsets[gid + 1] - start;
+    uint chunks = len == 0 ? 1 : (len + B3_CHUNK_LEN - 1) / B3_CHUNK_LEN;
+
+    uint stack[B3_MAX_DEPTH * 8];
+    uint stack_len = 0;
+    uint cv[8];
+    uint block[16];
+    uint out[8];
+    uint block_len = 0;
+    uint flags = 0;
+    ulong chunk = 0;
+
+    for (chunk = 0; chunk < chunks; chunk++) {
+        uint chunk_start = (uint)chunk * B3_CHUNK_LEN;
+        uint chunk_len = len - chunk_start;
+        if (chunk_len > B3_CHUNK_LEN) {
+            chunk_len = B3_CHUNK_LEN;
+        }
+        uint blocks = chunk_len == 0 ? 1 : (chunk_len + B3_BLOCK_LEN - 1) / B3_BLOCK_LEN;
+
+        for (int i = 0; i < 8; i++) {
+            cv[i] = B3_IV[i];
+        }
+        for (uint b = 0; b < blocks; b++) {
+            block_len = chunk_len - b * B3_BLOCK_LEN;
+            if (block_len > B3_BLOCK_LEN) {
+                block_len = B3_BLOCK_LEN;
+            }
+            b3_load_block(data + start + chunk_start + b * B3_BLOCK_LEN, block_len, block);
+            flags = (b == 0 ? B3_CHUNK_START : 0) | (b == blocks - 1 ? B3_CHUNK_END : 0);
+            if (b == blocks - 1) {
+                break;
+            }
+            b3_compress(cv, block, chunk, block_len, flags, out);
+            for (int i = 0; i < 8; i++) {
+                cv[i] = out[i];
+            }
+        }
+
+        // The last chunk's final block is compressed below, as root or child
+        if (chunk == chunks - 1) {
+            break;
+        }
+
+        // Finish the chunk, then merge every completed subtree
+        b3_compress(cv, block, chunk, block_len, flags, out);
+        ulong total = chunk + 1;
+        while ((total & 1) == 0) {
+            stack_len--;
+            for (int i = 0; i < 8; i++) {
+                block[i] = stack[stack_len * 8 + i];
+                block[i + 8] = out[i];
+                cv[i] = B3_IV[i];
+            }
+            b3_compress(cv, block, 0, B3_BLOCK_LEN, B3_PARENT, out);
+            total >>= 1;
+        }
+        for (int i = 0; i < 8; i++) {
+            stack[stack_len * 8 + i] = out[i];
+        }
+        stack_len++;
+    }
+
+    // Fold the pending output into the stacked subtrees, right to left
+    ulong counter = chunk;
+    while (stack_len > 0) {
+        b3_compress(cv, block, counter, block_len, flags, out);
+        stack_len--;
+        for (int i = 0; i < 8; i++) {
+            block[i] = stack[stack_len * 8 + i];
+            block[i + 8] = out[i];
+            cv[i] = B3_IV[i];
+        }
+        counter = 0;
+        block_len = B3_BLOCK_LEN;
+        flags = B3_PARENT;
+    }
+
+    b3_compress(cv, block, counter, block_len, flags | B3_ROOT, out);
+    for (int i = 0; i < 32; i++) {
+        digests[gid * 32 + i] = (out[i / 4] >> (8 * (i % 4))) & 0xFF;
+    }
+}
+";
+
 /// OpenCL-based compute engine
 ///
-/// The kernel is wrapped in a Mutex because ocl::Kernel contains raw pointers
+/// Kernels are wrapped in a Mutex because ocl::Kernel contains raw pointers
 /// that are not Sync. This ensures thread-safe access.
 pub struct OpenCLEngine {
     device_info: DeviceInfo,
@@ -173,28 +545,22 @@ pub struct OpenCLEngine {
     queue: ocl::Queue,
     /// Kernel wrapped in Mutex for thread safety (ocl::Kernel is not Sync)
     pow_kernel: Mutex<ocl::Kernel>,
+    sha256_kernel: Mutex<ocl::Kernel>,
+    keccak256_kernel: Mutex<ocl::Kernel>,
+    blake3_kernel: Mutex<ocl::Kernel>,
+    /// Pinned input/output buffers reused across batch hash launches
+    staging: StagingPool,
 }
 
 impl OpenCLEngine {
     pub fn new() -> Result<Self, ComputeError> {
         // Find the best GPU
-        // Use ocl::core::get_platform_ids() directly - it returns Result instead of panicking
-        let platform_ids = ocl::core::get_platform_ids().map_err(|e| {
-            ComputeError::InitializationFailed(format!(
-                "Failed to get OpenCL platforms: {}. Is OpenCL installed?",
-                e
-            ))
-        })?;
-
-        let platform_id = platform_ids.first().cloned().ok_or_else(|| {
+        let platform = Self::platforms()?.into_iter().next().ok_or_else(|| {
             ComputeError::InitializationFailed(
                 "No OpenCL platform found. Install GPU drivers with OpenCL support.".to_string(),
             )
         })?;
 
-        // Convert core PlatformId to high-level Platform
-        let platform = ocl::Platform::new(platform_id);
-
         let device = ocl::Device::list(platform, Some(ocl::flags::DeviceType::GPU))
             .map_err(|e| ComputeError::InitializationFailed(e.to_string()))?
             .into_iter()
@@ -207,6 +573,41 @@ impl OpenCLEngine {
                 ComputeError::InitializationFailed("No OpenCL device found".to_string())
             })?;
 
+        Self::for_device(platform, device)
+    }
+
+    /// One engine per GPU on every OpenCL platform
+    ///
+    /// Devices that fail to initialize are skipped.
+    pub fn all() -> Result<Vec<Self>, ComputeError> {
+        let mut engines = Vec::new();
+        for platform in Self::platforms()? {
+            let devices =
+                ocl::Device::list(platform, Some(ocl::flags::DeviceType::GPU)).unwrap_or_default();
+            for device in devices {
+                match Self::for_device(platform, device) {
+                    Ok(engine) => engines.push(engine),
+                    Err(e) => tracing::debug!("Skipping OpenCL device: {}", e),
+                }
+            }
+        }
+        Ok(engines)
+    }
+
+    fn platforms() -> Result<Vec<ocl::Platform>, ComputeError> {
+        // Use ocl::core::get_platform_ids() directly - it returns Result instead of panicking
+        let platform_ids = ocl::core::get_platform_ids().map_err(|e| {
+            ComputeError::InitializationFailed(format!(
+                "Failed to get OpenCL platforms: {}. Is OpenCL installed?",
+                e
+            ))
+        })?;
+
+        // Convert core PlatformId to high-level Platform
+        Ok(platform_ids.into_iter().map(ocl::Platform::new).collect())
+    }
+
+    fn for_device(platform: ocl::Platform, device: ocl::Device) -> Result<Self, ComputeError> {
         let context = ocl::Context::builder()
             .platform(platform)
             .devices(device)
@@ -219,6 +620,9 @@ impl OpenCLEngine {
         // Build the program
         let program = ocl::Program::builder()
             .src(SHA256_KERNEL)
+            .src(SHA256_BATCH_KERNEL)
+            .src(KECCAK256_KERNEL)
+            .src(BLAKE3_KERNEL)
             .devices(device)
             .build(&context)
             .map_err(|e| ComputeError::InitializationFailed(e.to_string()))?;
@@ -237,6 +641,9 @@ impl OpenCLEngine {
             .arg(None::<&ocl::Buffer<i32>>)  // 6: found
             .build()
             .map_err(|e| ComputeError::InitializationFailed(e.to_string()))?;
+        let sha256_kernel = Self::hash_kernel(&program, &queue, "batch_sha256")?;
+        let keccak256_kernel = Self::hash_kernel(&program, &queue, "batch_keccak256")?;
+        let blake3_kernel = Self::hash_kernel(&program, &queue, "batch_blake3")?;
 
         let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
         // Use info() method for device properties in ocl crate
@@ -269,9 +676,97 @@ impl OpenCLEngine {
             context,
             queue,
             pow_kernel: Mutex::new(pow_kernel),
+            sha256_kernel: Mutex::new(sha256_kernel),
+            keccak256_kernel: Mutex::new(keccak256_kernel),
+            blake3_kernel: Mutex::new(blake3_kernel),
+            staging: StagingPool::new(DEFAULT_STAGING_POOL_SIZE),
         })
     }
 
+    /// Build a batch hash kernel (data, offsets, digests)
+    fn hash_kernel(
+        program: &ocl::Program,
+        queue: &ocl::Queue,
+        name: &str,
+    ) -> Result<ocl::Kernel, ComputeError> {
+        ocl::Kernel::builder()
+            .program(program)
+            .name(name)
+            .queue(queue.clone())
+            .arg(None::<&ocl::Buffer<u8>>) // 0: data
+            .arg(None::<&ocl::Buffer<u32>>) // 1: offsets
+            .arg(None::<&ocl::Buffer<u8>>) // 2: digests
+            .build()
+            .map_err(|e| ComputeError::InitializationFailed(e.to_string()))
+    }
+
+    /// Keep up to `sets` idle staging buffer sets between batches
+    ///
+    /// 0 disables reuse: every launch allocates fresh buffers.
+    pub fn with_staging_pool_size(mut self, sets: usize) -> Self {
+        self.staging = StagingPool::new(sets);
+        self
+    }
+
+    /// Staging buffer allocations and reuses so far
+    pub fn staging_stats(&self) -> StagingStats {
+        self.staging.stats()
+    }
+
+    /// Hash every input with a batch hash kernel, one work item per input
+    ///
+    /// Large batches are split into chunks; each chunk runs in a staging
+    /// buffer set taken from the pool.
+    fn run_hash_kernel(
+        &self,
+        kernel: &Mutex<ocl::Kernel>,
+        inputs: &[Vec<u8>],
+    ) -> Result<Vec<[u8; 32]>, ComputeError> {
+        let mut digests = Vec::with_capacity(inputs.len());
+        for chunk in staging::chunks(inputs)? {
+            let data_bytes = chunk.iter().map(Vec::len).sum();
+            let buffers = self.staging.acquire(&self.queue, data_bytes, chunk.len())?;
+            let result = Self::launch_hash_kernel(kernel, &buffers, chunk);
+            self.staging.release(buffers);
+            digests.extend(result?);
+        }
+        Ok(digests)
+    }
+
+    fn launch_hash_kernel(
+        kernel: &Mutex<ocl::Kernel>,
+        buffers: &StagingBuffers,
+        inputs: &[Vec<u8>],
+    ) -> Result<Vec<[u8; 32]>, ComputeError> {
+        buffers.stage(inputs)?;
+
+        let kernel = kernel
+            .lock()
+            .map_err(|e| ComputeError::TaskFailed(format!("Kernel lock poisoned: {}", e)))?;
+
+        kernel
+            .set_arg(0, &buffers.data)
+            .map_err(|e| ComputeError::TaskFailed(e.to_string()))?;
+        kernel
+            .set_arg(1, &buffers.offsets)
+            .map_err(|e| ComputeError::TaskFailed(e.to_string()))?;
+        kernel
+            .set_arg(2, &buffers.digests)
+            .map_err(|e| ComputeError::TaskFailed(e.to_string()))?;
+
+        // SAFETY: OpenCL kernel calls require unsafe. The staging set holds
+        // at least one offset pair and one digest slot per work item.
+        unsafe {
+            kernel
+                .cmd()
+                .global_work_size(inputs.len())
+                .enq()
+                .map_err(|e| ComputeError::TaskFailed(e.to_string()))?;
+        }
+
+        buffers.digests(inputs.len())
+    }
+
     /// Get reference to the OpenCL context.
     ///
     /// Useful for creating additional buffers or programs.
@@ -291,7 +786,12 @@ impl ComputeEngine for OpenCLEngine {
     }
 
     async fn batch_sha256(&self, inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
-        // For batch hashing, CPU is often faster due to data transfer overhead
+        let total_bytes: usize = inputs.iter().map(Vec::len).sum();
+        if total_bytes >= GPU_BATCH_MIN_BYTES {
+            return self.run_hash_kernel(&self.sha256_kernel, inputs);
+        }
+
+        // For small batches, CPU is faster due to data transfer overhead
         // Use Rayon as fallback
         use rayon::prelude::*;
         use sha2::{Digest, Sha256};
@@ -309,6 +809,14 @@ impl ComputeEngine for OpenCLEngine {
         Ok(results)
     }
 
+    async fn batch_keccak256(&self, inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
+        self.run_hash_kernel(&self.keccak256_kernel, inputs)
+    }
+
+    async fn batch_blake3(&self, inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError> {
+        self.run_hash_kernel(&self.blake3_kernel, inputs)
+    }
+
     async fn pow_mine(
         &self,
         header_template: &[u8],
@@ -479,4 +987,25 @@ impl ComputeEngine for OpenCLEngine {
 
         Ok(results)
     }
+
+    async fn batch_verify_bls(
+        &self,
+        messages: &[Vec<u8>],
+        signatures: &[[u8; 48]],
+        public_keys: &[[u8; 96]],
+    ) -> Result<Vec<bool>, ComputeError> {
+        // No pairing kernel yet, use CPU fallback
+        use rayon::prelude::*;
+
+        crate::bls::check_lengths(messages, signatures, public_keys)?;
+
+        if crate::bls::verify_all(messages, signatures, public_keys) {
+            return Ok(vec![true; messages.len()]);
+        }
+
+        Ok((0..messages.len())
+            .into_par_iter()
+            .map(|i| crate::bls::verify_one(&messages[i], &signatures[i], &public_keys[i]))
+            .collect())
+    }
 }
@@ -0,0 +1,250 @@
+//! Reusable staging buffers for OpenCL batch hashing
+//!
+//! A batch hash launch needs three device buffers: the packed input bytes,
+//! the input offsets and the output digests. Creating them per call (after
+//! first packing every input into a temporary Vec) dominates large batches.
+//! `StagingPool` keeps buffer sets allocated with `ALLOC_HOST_PTR`, which most
+//! drivers back with pinned host memory, and hands them out again:
+//!
+//! - Inputs are copied straight into the mapped data buffer (no packing Vec).
+//! - Batches are split into chunks of at most `CHUNK_BYTES` input bytes and
+//!   `CHUNK_INPUTS` inputs, so no buffer set grows beyond one chunk.
+//! - Capacities are rounded up to powers of two so similar batches share sets.
+
+use crate::ComputeError;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Most input bytes staged per kernel launch
+pub(crate) const CHUNK_BYTES: usize = 64 << 20;
+/// Most inputs staged per kernel launch
+pub(crate) const CHUNK_INPUTS: usize = 1 << 20;
+
+/// Staging pool counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StagingStats {
+    /// Buffer sets allocated
+    pub allocations: u64,
+    /// Launches served by a pooled buffer set
+    pub reuses: u64,
+}
+
+fn task_failed(e: ocl::Error) -> ComputeError {
+    ComputeError::TaskFailed(e.to_string())
+}
+
+/// Device buffers for one batch hash launch
+pub(crate) struct StagingBuffers {
+    pub data: ocl::Buffer<u8>,
+    pub offsets: ocl::Buffer<u32>,
+    pub digests: ocl::Buffer<u8>,
+}
+
+impl StagingBuffers {
+    fn new(queue: &ocl::Queue, data_bytes: usize, inputs: usize) -> Result<Self, ComputeError> {
+        let pinned_input = ocl::flags::MemFlags::new().read_only().alloc_host_ptr();
+        let pinned_output = ocl::flags::MemFlags::new().write_only().alloc_host_ptr();
+
+        Ok(Self {
+            data: ocl::Buffer::builder()
+                .queue(queue.clone())
+                .flags(pinned_input)
+                .len(data_bytes)
+                .build()
+                .map_err(task_failed)?,
+            offsets: ocl::Buffer::builder()
+                .queue(queue.clone())
+                .flags(pinned_input)
+                .len(inputs + 1)
+                .build()
+                .map_err(task_failed)?,
+            digests: ocl::Buffer::builder()
+                .queue(queue.clone())
+                .flags(pinned_output)
+                .len(inputs * 32)
+                .build()
+                .map_err(task_failed)?,
+        })
+    }
+
+    fn fits(&self, data_bytes: usize, inputs: usize) -> bool {
+        self.data.len() >= data_bytes && self.offsets.len() > inputs
+    }
+
+    /// Write `inputs` into the data and offsets buffers through mapped memory
+    ///
+    /// Input i ends up at data[offsets[i]..offsets[i + 1]].
+    pub fn stage(&self, inputs: &[Vec<u8>]) -> Result<(), ComputeError> {
+        let total: usize = inputs.iter().map(Vec::len).sum();
+
+        // SAFETY: a buffer set belongs to one launch at a time, so nothing else
+        // maps or reads these buffers while they are written.
+        unsafe {
+            if total > 0 {
+                let mut data = self
+                    .data
+                    .map()
+                    .write_invalidate()
+                    .len(total)
+                    .enq()
+                    .map_err(task_failed)?;
+                let mut pos = 0;
+                for input in inputs {
+                    data[pos..pos + input.len()].copy_from_slice(input);
+                    pos += input.len();
+                }
+                data.unmap().enq().map_err(task_failed)?;
+            }
+
+            let mut offsets = self
+                .offsets
+                .map()
+                .write_invalidate()
+                .len(inputs.len() + 1)
+                .enq()
+                .map_err(task_failed)?;
+            // Chunks stay below CHUNK_BYTES unless a single input is larger,
+            // and `chunks` rejects inputs that don't fit u32 offsets
+            let mut end = 0;
+            offsets[0] = 0;
+            for (i, input) in inputs.iter().enumerate() {
+                end += input.len();
+                offsets[i + 1] = end as u32;
+            }
+            offsets.unmap().enq().map_err(task_failed)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read back the first `count` digests
+    pub fn digests(&self, count: usize) -> Result<Vec<[u8; 32]>, ComputeError> {
+        let mut bytes = vec![0u8; count * 32];
+        self.digests
+            .read(&mut bytes)
+            .len(count * 32)
+            .enq()
+            .map_err(task_failed)?;
+
+        Ok(bytes
+            .chunks_exact(32)
+            .map(|chunk| {
+                let mut digest = [0u8; 32];
+                digest.copy_from_slice(chunk);
+                digest
+            })
+            .collect())
+    }
+}
+
+/// Pool of idle staging buffer sets for one queue
+pub(crate) struct StagingPool {
+    idle: Mutex<Vec<StagingBuffers>>,
+    /// Most idle sets kept (0 allocates for every launch)
+    capacity: usize,
+    allocations: AtomicU64,
+    reuses: AtomicU64,
+}
+
+impl StagingPool {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            idle: Mutex::new(Vec::new()),
+            capacity,
+            allocations: AtomicU64::new(0),
+            reuses: AtomicU64::new(0),
+        }
+    }
+
+    /// Take an idle set that fits the chunk, or allocate one
+    pub fn acquire(
+        &self,
+        queue: &ocl::Queue,
+        data_bytes: usize,
+        inputs: usize,
+    ) -> Result<StagingBuffers, ComputeError> {
+        if let Ok(mut idle) = self.idle.lock() {
+            if let Some(index) = idle.iter().position(|set| set.fits(data_bytes, inputs)) {
+                self.reuses.fetch_add(1, Ordering::Relaxed);
+                return Ok(idle.swap_remove(index));
+            }
+        }
+
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+        // OpenCL buffers cannot be empty
+        StagingBuffers::new(
+            queue,
+            data_bytes.max(1).next_power_of_two(),
+            inputs.max(1).next_power_of_two(),
+        )
+    }
+
+    /// Return a set for later launches; dropped once the pool is full
+    pub fn release(&self, buffers: StagingBuffers) {
+        if let Ok(mut idle) = self.idle.lock() {
+            if idle.len() < self.capacity {
+                idle.push(buffers);
+            }
+        }
+    }
+
+    pub fn stats(&self) -> StagingStats {
+        StagingStats {
+            allocations: self.allocations.load(Ordering::Relaxed),
+            reuses: self.reuses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Split a batch into launches of at most `CHUNK_BYTES` and `CHUNK_INPUTS`
+///
+/// Every chunk holds at least one input, so a single input larger than
+/// `CHUNK_BYTES` gets a chunk of its own.
+pub(crate) fn chunks(inputs: &[Vec<u8>]) -> Result<Vec<&[Vec<u8>]>, ComputeError> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut bytes = 0;
+
+    for (i, input) in inputs.iter().enumerate() {
+        if u32::try_from(input.len()).is_err() {
+            return Err(ComputeError::InvalidInput(
+                "Hash input exceeds 4 GiB".to_string(),
+            ));
+        }
+        if i > start && (bytes + input.len() > CHUNK_BYTES || i - start == CHUNK_INPUTS) {
+            chunks.push(&inputs[start..i]);
+            start = i;
+            bytes = 0;
+        }
+        bytes += input.len();
+    }
+    if start < inputs.len() {
+        chunks.push(&inputs[start..]);
+    }
+
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_respect_limits() {
+        assert!(chunks(&[]).unwrap().is_empty());
+
+        let small = vec![vec![0u8; 32]; CHUNK_INPUTS + 1];
+        let split = chunks(&small).unwrap();
+        assert_eq!(split.len(), 2);
+        assert_eq!(split[0].len(), CHUNK_INPUTS);
+        assert_eq!(split[1].len(), 1);
+
+        // An oversized input gets its own chunk
+        let large = vec![vec![1u8; 16], vec![2u8; CHUNK_BYTES + 1], vec![3u8; 16]];
+        let split = chunks(&large).unwrap();
+        assert_eq!(
+            split.iter().map(|c| c.len()).collect::<Vec<_>>(),
+            vec![1, 1, 1]
+        );
+    }
+}
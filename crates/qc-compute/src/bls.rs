@@ -0,0 +1,115 @@
+//! BLS12-381 batch verification shared by the backends
+//!
+//! Same scheme as QC-10's scalar `verify_bls`: blst `min_sig` (48-byte G1
+//! signatures, 96-byte G2 public keys) with the same domain separation tag.
+//!
+//! A batch is first checked with a single randomized multi-pairing. Only when
+//! that fails are signatures checked one by one to find the bad ones, so an
+//! all-valid attestation flood costs one final exponentiation.
+
+use blst::min_sig::{PublicKey, Signature};
+use blst::{blst_scalar, BLST_ERROR};
+
+/// Domain Separation Tag (must match QC-10)
+pub(crate) const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Bits of randomness per signature in the combined check
+const RAND_BITS: usize = 64;
+
+/// Verify one signature (the scalar path)
+pub(crate) fn verify_one(message: &[u8], signature: &[u8; 48], public_key: &[u8; 96]) -> bool {
+    let (Ok(sig), Ok(pk)) = (
+        Signature::from_bytes(signature),
+        PublicKey::from_bytes(public_key),
+    ) else {
+        return false;
+    };
+    sig.verify(true, message, DST, &[], &pk, true) == BLST_ERROR::BLST_SUCCESS
+}
+
+/// Whether every signature in the batch is valid, in one multi-pairing
+///
+/// Each signature is weighted by a random 64-bit scalar so invalid
+/// signatures cannot cancel each other out. Returns false for an empty
+/// batch or when any point fails to parse.
+pub(crate) fn verify_all(
+    messages: &[Vec<u8>],
+    signatures: &[[u8; 48]],
+    public_keys: &[[u8; 96]],
+) -> bool {
+    let sigs: Result<Vec<Signature>, _> = signatures
+        .iter()
+        .map(|bytes| Signature::from_bytes(bytes))
+        .collect();
+    let pks: Result<Vec<PublicKey>, _> = public_keys
+        .iter()
+        .map(|bytes| PublicKey::from_bytes(bytes))
+        .collect();
+    let (Ok(sigs), Ok(pks)) = (sigs, pks) else {
+        return false;
+    };
+
+    let rands: Vec<blst_scalar> = (0..sigs.len())
+        .map(|_| {
+            let mut scalar = blst_scalar::default();
+            // A zero weight would drop the signature from the check
+            scalar.b[..8].copy_from_slice(&rand::random::<u64>().max(1).to_le_bytes());
+            scalar
+        })
+        .collect();
+    let msgs: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+    let sig_refs: Vec<&Signature> = sigs.iter().collect();
+    let pk_refs: Vec<&PublicKey> = pks.iter().collect();
+
+    Signature::verify_multiple_aggregate_signatures(
+        &msgs, DST, &pk_refs, true, &sig_refs, true, &rands, RAND_BITS,
+    ) == BLST_ERROR::BLST_SUCCESS
+}
+
+/// Check that the three batch inputs line up
+pub(crate) fn check_lengths(
+    messages: &[Vec<u8>],
+    signatures: &[[u8; 48]],
+    public_keys: &[[u8; 96]],
+) -> Result<(), crate::ComputeError> {
+    if messages.len() != signatures.len() || messages.len() != public_keys.len() {
+        return Err(crate::ComputeError::InvalidInput(
+            "Mismatched input array lengths".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blst::min_sig::SecretKey;
+
+    fn signed(seed: u8, message: &[u8]) -> ([u8; 48], [u8; 96]) {
+        let sk = SecretKey::key_gen(&[seed; 32], &[]).unwrap();
+        (
+            sk.sign(message, DST, &[]).to_bytes(),
+            sk.sk_to_pk().to_bytes(),
+        )
+    }
+
+    #[test]
+    fn test_combined_check_rejects_any_bad_signature() {
+        let messages: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; 40]).collect();
+        let (mut signatures, public_keys): (Vec<_>, Vec<_>) = messages
+            .iter()
+            .enumerate()
+            .map(|(i, message)| signed(i as u8 + 1, message))
+            .unzip();
+        assert!(verify_all(&messages, &signatures, &public_keys));
+        assert!(verify_one(&messages[2], &signatures[2], &public_keys[2]));
+
+        // Swapped signatures: each is valid, just not for its message
+        signatures.swap(0, 1);
+        assert!(!verify_all(&messages, &signatures, &public_keys));
+        assert!(!verify_one(&messages[0], &signatures[0], &public_keys[0]));
+
+        assert!(!verify_all(&[], &[], &[]));
+        assert!(!verify_all(&messages[..1], &[[0u8; 48]], &public_keys[..1]));
+    }
+}
@@ -0,0 +1,349 @@
+//! Benchmark-driven backend calibration
+//!
+//! `recommended_backend_for` falls back to a fixed table (GPU for mining,
+//! signatures and Merkle trees, CPU for everything else). Calibration
+//! replaces that table with measurements from the machine the node runs on:
+//!
+//! 1. Every available backend runs the same micro-benchmarks (a small and a
+//!    large SHA-256 batch, and a nonce search).
+//! 2. The results are persisted as JSON so later startups skip the benchmark
+//!    while the set of devices is unchanged.
+//! 3. Once installed, `recommended_backend_for` picks the backend that was
+//!    fastest on the workload each subsystem actually runs.
+//!
+//! ```rust,ignore
+//! // At startup: reuse the stored results, or benchmark and store them
+//! let calibration = qc_compute::calibration::load_or_calibrate(&path).await?;
+//! qc_compute::calibration::install(calibration);
+//! let mining = qc_compute::recommended_backend_for("qc-17");
+//! ```
+
+use crate::{Backend, ComputeEngine, ComputeError};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Calibration used by `recommended_backend_for` (None = fixed table)
+static INSTALLED: RwLock<Option<Calibration>> = RwLock::new(None);
+
+/// File name for persisted results, inside the node's data directory
+pub const CALIBRATION_FILE: &str = "compute-calibration.json";
+
+/// Bytes per benchmark hash input (about a block header)
+const HASH_INPUT_LEN: usize = 128;
+
+/// Representative workload measured on every backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchmarkWorkload {
+    /// Small SHA-256 batch, dominated by dispatch overhead
+    SmallHashBatch,
+    /// Large SHA-256 batch, dominated by raw hashing throughput
+    LargeHashBatch,
+    /// PoW nonce search over a range with no solution
+    NonceSearch,
+}
+
+impl BenchmarkWorkload {
+    /// Every workload, in benchmark order
+    pub const ALL: [BenchmarkWorkload; 3] = [
+        BenchmarkWorkload::SmallHashBatch,
+        BenchmarkWorkload::LargeHashBatch,
+        BenchmarkWorkload::NonceSearch,
+    ];
+
+    /// Units (inputs or nonces) processed per run
+    pub fn units(&self) -> u64 {
+        match self {
+            BenchmarkWorkload::SmallHashBatch => 64,
+            BenchmarkWorkload::LargeHashBatch => 16_384,
+            BenchmarkWorkload::NonceSearch => 100_000,
+        }
+    }
+
+    /// Workload that best represents what `subsystem` sends to the engine
+    ///
+    /// None for subsystems that never offload work (they stay on the CPU).
+    pub fn for_subsystem(subsystem: &str) -> Option<Self> {
+        match subsystem {
+            // Nonce search
+            "qc-17" | "qc-17-block-production" => Some(BenchmarkWorkload::NonceSearch),
+            // Whole blocks of transaction hashes
+            "qc-03" | "qc-03-transaction-indexing" => Some(BenchmarkWorkload::LargeHashBatch),
+            // Many small batches, so per-call overhead decides
+            "qc-10" | "qc-10-signature-verification" => Some(BenchmarkWorkload::SmallHashBatch),
+            _ => None,
+        }
+    }
+}
+
+/// Throughput of one backend on one workload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub backend: Backend,
+    /// Device name, used to detect hardware changes
+    pub device: String,
+    pub workload: BenchmarkWorkload,
+    /// Inputs (or nonces) processed per second
+    pub units_per_sec: f64,
+}
+
+/// Benchmark results for every available backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Calibration {
+    /// When the benchmark ran (Unix seconds)
+    pub measured_at: u64,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Calibration {
+    /// Backend with the highest measured throughput on `workload`
+    pub fn fastest(&self, workload: BenchmarkWorkload) -> Option<Backend> {
+        self.measurements
+            .iter()
+            .filter(|m| m.workload == workload)
+            .max_by(|a, b| a.units_per_sec.total_cmp(&b.units_per_sec))
+            .map(|m| m.backend)
+    }
+
+    /// Measured choice for `subsystem`; CPU when it offloads no work
+    pub fn backend_for(&self, subsystem: &str) -> Backend {
+        BenchmarkWorkload::for_subsystem(subsystem)
+            .and_then(|workload| self.fastest(workload))
+            .unwrap_or(Backend::Cpu)
+    }
+
+    /// Whether these results were measured on exactly `engines`
+    pub fn covers(&self, engines: &[Arc<dyn ComputeEngine>]) -> bool {
+        let mut measured: Vec<(Backend, &str)> = self
+            .measurements
+            .iter()
+            .map(|m| (m.backend, m.device.as_str()))
+            .collect();
+        measured.dedup();
+        measured.len() == engines.len()
+            && engines.iter().all(|engine| {
+                measured.contains(&(engine.backend(), engine.device_info().name.as_str()))
+            })
+    }
+
+    /// Read persisted results
+    pub fn load(path: &Path) -> Result<Self, ComputeError> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| ComputeError::Calibration(format!("{}: {}", path.display(), e)))?;
+        serde_json::from_str(&json)
+            .map_err(|e| ComputeError::Calibration(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Persist results, creating the parent directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), ComputeError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| ComputeError::Calibration(format!("{}: {}", dir.display(), e)))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| ComputeError::Calibration(e.to_string()))?;
+        std::fs::write(path, json)
+            .map_err(|e| ComputeError::Calibration(format!("{}: {}", path.display(), e)))
+    }
+}
+
+/// One engine per backend that initializes on this machine
+pub fn available_engines() -> Vec<Arc<dyn ComputeEngine>> {
+    [Backend::OpenCL, Backend::Cpu]
+        .into_iter()
+        .filter_map(|backend| crate::create_backend(backend).ok())
+        .collect()
+}
+
+/// Run every benchmark workload on every engine
+///
+/// Each workload runs once to warm up (kernel compilation, thread pools)
+/// and once timed.
+pub async fn calibrate(engines: &[Arc<dyn ComputeEngine>]) -> Result<Calibration, ComputeError> {
+    if engines.is_empty() {
+        return Err(ComputeError::NoBackendAvailable);
+    }
+
+    let mut measurements = Vec::with_capacity(engines.len() * BenchmarkWorkload::ALL.len());
+    for engine in engines {
+        for workload in BenchmarkWorkload::ALL {
+            let inputs = hash_inputs(workload);
+            run(engine.as_ref(), workload, &inputs).await?;
+            let started = Instant::now();
+            run(engine.as_ref(), workload, &inputs).await?;
+            let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+
+            let measurement = Measurement {
+                backend: engine.backend(),
+                device: engine.device_info().name.clone(),
+                workload,
+                units_per_sec: workload.units() as f64 / elapsed,
+            };
+            tracing::debug!(
+                "Calibrated {} on {:?}: {:.0}/s",
+                measurement.device,
+                workload,
+                measurement.units_per_sec
+            );
+            measurements.push(measurement);
+        }
+    }
+
+    Ok(Calibration {
+        measured_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        measurements,
+    })
+}
+
+/// Load the results stored at `path`, or benchmark and store them
+///
+/// Stored results are reused only while they cover exactly the backends
+/// available now; a new or missing GPU triggers a fresh benchmark.
+pub async fn load_or_calibrate(path: &Path) -> Result<Calibration, ComputeError> {
+    let engines = available_engines();
+    match Calibration::load(path) {
+        Ok(stored) if stored.covers(&engines) => return Ok(stored),
+        Ok(_) => tracing::info!("Compute devices changed, recalibrating"),
+        Err(e) => tracing::debug!("No stored calibration: {}", e),
+    }
+
+    let calibration = calibrate(&engines).await?;
+    if let Err(e) = calibration.save(path) {
+        tracing::warn!("Failed to store calibration: {}", e);
+    }
+    Ok(calibration)
+}
+
+/// Make `recommended_backend_for` use `calibration`
+pub fn install(calibration: Calibration) {
+    *INSTALLED.write().expect("calibration lock poisoned") = Some(calibration);
+}
+
+/// Measured choice for `subsystem`, if a calibration is installed
+pub(crate) fn installed_backend_for(subsystem: &str) -> Option<Backend> {
+    INSTALLED
+        .read()
+        .expect("calibration lock poisoned")
+        .as_ref()
+        .map(|calibration| calibration.backend_for(subsystem))
+}
+
+/// Inputs for a hash batch workload, built outside the timed run
+fn hash_inputs(workload: BenchmarkWorkload) -> Vec<Vec<u8>> {
+    if workload == BenchmarkWorkload::NonceSearch {
+        return Vec::new();
+    }
+    (0..workload.units())
+        .map(|i| {
+            let mut input = vec![0u8; HASH_INPUT_LEN];
+            input[..8].copy_from_slice(&i.to_le_bytes());
+            input
+        })
+        .collect()
+}
+
+async fn run(
+    engine: &dyn ComputeEngine,
+    workload: BenchmarkWorkload,
+    inputs: &[Vec<u8>],
+) -> Result<(), ComputeError> {
+    match workload {
+        BenchmarkWorkload::SmallHashBatch | BenchmarkWorkload::LargeHashBatch => {
+            engine.batch_sha256(inputs).await?;
+        }
+        BenchmarkWorkload::NonceSearch => {
+            // A zero target is never met, so the whole range is searched
+            engine
+                .pow_mine(&[0u8; 80], U256::zero(), 0, workload.units())
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(backend: Backend, workload: BenchmarkWorkload, rate: f64) -> Measurement {
+        Measurement {
+            backend,
+            device: format!("{}", backend),
+            workload,
+            units_per_sec: rate,
+        }
+    }
+
+    #[test]
+    fn test_backend_for_follows_measurements() {
+        let calibration = Calibration {
+            measured_at: 0,
+            measurements: vec![
+                measurement(Backend::OpenCL, BenchmarkWorkload::SmallHashBatch, 1_000.0),
+                measurement(
+                    Backend::OpenCL,
+                    BenchmarkWorkload::LargeHashBatch,
+                    900_000.0,
+                ),
+                measurement(Backend::OpenCL, BenchmarkWorkload::NonceSearch, 5e7),
+                measurement(Backend::Cpu, BenchmarkWorkload::SmallHashBatch, 40_000.0),
+                measurement(Backend::Cpu, BenchmarkWorkload::LargeHashBatch, 300_000.0),
+                measurement(Backend::Cpu, BenchmarkWorkload::NonceSearch, 2e6),
+            ],
+        };
+
+        assert_eq!(calibration.backend_for("qc-17"), Backend::OpenCL);
+        assert_eq!(calibration.backend_for("qc-03"), Backend::OpenCL);
+        // The GPU loses on small batches here, unlike the fixed table
+        assert_eq!(calibration.backend_for("qc-10"), Backend::Cpu);
+        assert_eq!(calibration.backend_for("qc-04"), Backend::Cpu);
+    }
+
+    #[cfg(feature = "cpu")]
+    #[tokio::test]
+    async fn test_calibrate_persist_and_reuse() {
+        let engines: Vec<Arc<dyn ComputeEngine>> =
+            vec![Arc::new(crate::backends::cpu::CpuEngine::new())];
+        let calibration = calibrate(&engines).await.unwrap();
+        assert_eq!(calibration.measurements.len(), BenchmarkWorkload::ALL.len());
+        assert!(calibration
+            .measurements
+            .iter()
+            .all(|m| m.backend == Backend::Cpu && m.units_per_sec > 0.0));
+        assert!(calibration.covers(&engines));
+        assert!(!calibration.covers(&[]));
+
+        let path = std::env::temp_dir()
+            .join(format!("qc-compute-calibration-{}", std::process::id()))
+            .join("calibration.json");
+        calibration.save(&path).unwrap();
+        // JSON floats may lose the last bit of a rate
+        let stored = Calibration::load(&path).unwrap();
+        assert_eq!(stored.measured_at, calibration.measured_at);
+        for (stored, measured) in stored.measurements.iter().zip(&calibration.measurements) {
+            assert_eq!(
+                (stored.backend, &stored.device, stored.workload),
+                (measured.backend, &measured.device, measured.workload)
+            );
+            assert!((stored.units_per_sec / measured.units_per_sec - 1.0).abs() < 1e-9);
+        }
+
+        // Stored results for the same devices are reused as-is
+        let reused = load_or_calibrate(&path).await.unwrap();
+        if available_engines().len() == 1 {
+            assert_eq!(reused, stored);
+        }
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert!(matches!(
+            calibrate(&[]).await,
+            Err(ComputeError::NoBackendAvailable)
+        ));
+    }
+}
@@ -17,7 +17,7 @@
 //!
 //! | Subsystem | Workload Type | Best Backend | Why |
 //! |-----------|---------------|--------------|-----|
-//! | QC-17 (Mining) | SHA256 hashing | GPU/OpenCL | Embarrassingly parallel |
+//! | QC-17 (Mining) | SHA256/Keccak256 hashing | GPU/OpenCL | Embarrassingly parallel |
 //! | QC-10 (Signatures) | ECDSA/BLS verify | GPU/OpenCL | Batch verification |
 //! | QC-03 (Merkle) | SHA256 tree | GPU/OpenCL | Parallel hashing |
 //! | QC-04 (State) | Trie operations | CPU | Memory-bound, branching |
@@ -33,20 +33,37 @@
 //! // Auto-detect best backend (OpenCL GPU or CPU)
 //! let engine = auto_detect()?;
 //! println!("Using: {}", engine.backend());
+//!
+//! // Replace the fixed backend table with measurements from this machine
+//! let path = data_dir.join(qc_compute::calibration::CALIBRATION_FILE);
+//! qc_compute::calibration::install(qc_compute::calibration::load_or_calibrate(&path).await?);
+//!
+//! // Or drive every device at once (all GPUs + CPU), split by throughput
+//! let multi = qc_compute::MultiEngine::detect()?;
+//! for device in multi.device_stats() {
+//!     println!("{}: {:?} nonces/s", device.name, device.mine_rate);
+//! }
 //! ```
 
 #![warn(missing_docs)]
 #![allow(missing_docs)] // TODO: Add documentation for all public items
 
 pub mod backends;
+#[cfg(any(feature = "cpu", feature = "opencl"))]
+mod bls;
+pub mod calibration;
+pub mod queue;
 pub mod tasks;
 
 use primitive_types::U256;
 use std::sync::Arc;
 use thiserror::Error;
 
+pub use backends::multi::{DeviceStats, MultiEngine};
+pub use queue::{CancellationToken, JobHandle, JobOptions, JobQueue, Priority};
+
 /// Compute backend capabilities
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum Backend {
     /// CPU with Rayon parallelism
     Cpu,
@@ -78,8 +95,14 @@ pub enum ComputeError {
     #[error("Timeout waiting for result")]
     Timeout,
 
+    #[error("Compute job cancelled")]
+    Cancelled,
+
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    #[error("Calibration failed: {0}")]
+    Calibration(String),
 }
 
 /// Device information
@@ -104,6 +127,12 @@ pub trait ComputeEngine: Send + Sync {
     /// Batch SHA256 hashing (for mining, merkle trees)
     async fn batch_sha256(&self, inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError>;
 
+    /// Batch Keccak256 hashing (Ethereum padding, as used by PoW mining)
+    async fn batch_keccak256(&self, inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError>;
+
+    /// Batch BLAKE3 hashing (unkeyed, 32-byte output)
+    async fn batch_blake3(&self, inputs: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, ComputeError>;
+
     /// PoW mining - find nonce that produces hash below target
     async fn pow_mine(
         &self,
@@ -120,6 +149,15 @@ pub trait ComputeEngine: Send + Sync {
         signatures: &[[u8; 65]],
         public_keys: &[[u8; 33]],
     ) -> Result<Vec<bool>, ComputeError>;
+
+    /// Batch BLS12-381 signature verification (min_sig: 48-byte signatures,
+    /// 96-byte public keys, same scheme as QC-10's `verify_bls`)
+    async fn batch_verify_bls(
+        &self,
+        messages: &[Vec<u8>],
+        signatures: &[[u8; 48]],
+        public_keys: &[[u8; 96]],
+    ) -> Result<Vec<bool>, ComputeError>;
 }
 
 /// Auto-detect and create the best available compute engine
@@ -182,7 +220,15 @@ pub fn create_backend(backend: Backend) -> Result<Arc<dyn ComputeEngine>, Comput
 }
 
 /// Recommended backend for each subsystem workload
+///
+/// Uses the installed benchmark results (see [`calibration`]) when present,
+/// otherwise a fixed table.
 pub fn recommended_backend_for(subsystem: &str) -> Backend {
+    calibration::installed_backend_for(subsystem).unwrap_or_else(|| default_backend_for(subsystem))
+}
+
+/// Fixed recommendation used before calibration
+fn default_backend_for(subsystem: &str) -> Backend {
     match subsystem {
         // GPU-accelerated (embarrassingly parallel): Mining, signatures, Merkle trees
         "qc-17"
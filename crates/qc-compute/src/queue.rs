@@ -0,0 +1,521 @@
+//! Prioritized compute job queue
+//!
+//! `ComputeEngine` calls are one-shot awaits: once a nonce search starts it
+//! runs to the end of its range. `JobQueue` puts a scheduling layer in front
+//! of an engine:
+//!
+//! - Priorities: queued jobs run highest priority first (consensus-critical
+//!   work before plain requests before mining), FIFO within a priority.
+//! - Cancellation: every job carries a `CancellationToken`. Nonce searches
+//!   run in chunks and stop at the next chunk boundary once cancelled, so a
+//!   new head can abandon the search for the old one.
+//! - Timeouts: a job past its deadline stops the same way and resolves to
+//!   `ComputeError::Timeout`.
+//!
+//! Jobs run on dedicated worker threads; a job cancelled or expired while
+//! still queued never reaches the engine.
+//!
+//! ```rust,ignore
+//! let queue = JobQueue::new(engine, 1);
+//! let new_head = CancellationToken::new();
+//! let search = queue.pow_mine(
+//!     JobOptions::new(Priority::Mining).with_cancellation(new_head.clone()),
+//!     header_template,
+//!     target,
+//!     0,
+//!     u64::MAX,
+//! );
+//! // ...a new block arrives
+//! new_head.cancel();
+//! assert!(matches!(search.await, Err(ComputeError::Cancelled)));
+//! ```
+
+use crate::{ComputeEngine, ComputeError};
+use futures::channel::oneshot;
+use primitive_types::U256;
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Default nonces per `pow_mine` call between cancellation checks
+const DEFAULT_POW_CHUNK: u64 = 1 << 16;
+
+/// Job priority (higher runs first)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// PoW nonce search, the first work to yield
+    Mining,
+    /// Everything else
+    #[default]
+    Normal,
+    /// Block and attestation validation on the consensus path
+    ConsensusCritical,
+}
+
+/// Cooperative cancellation flag shared between a job and its owner
+///
+/// Clones share the flag, so one token can cancel a whole group of jobs
+/// (for example every search for the current head).
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every job holding this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Scheduling options for one job
+#[derive(Debug, Clone, Default)]
+pub struct JobOptions {
+    pub priority: Priority,
+    /// Time allowed from submission to completion (None = unlimited)
+    pub timeout: Option<Duration>,
+    pub cancellation: CancellationToken,
+}
+
+impl JobOptions {
+    pub fn new(priority: Priority) -> Self {
+        Self {
+            priority,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+}
+
+/// Cancellation and deadline state handed to a running job
+#[derive(Debug, Clone)]
+pub struct JobContext {
+    cancellation: CancellationToken,
+    deadline: Option<Instant>,
+}
+
+impl JobContext {
+    /// Error out if the job was cancelled or ran past its deadline
+    ///
+    /// Long jobs call this between steps.
+    pub fn check(&self) -> Result<(), ComputeError> {
+        if self.cancellation.is_cancelled() {
+            return Err(ComputeError::Cancelled);
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Err(ComputeError::Timeout);
+        }
+        Ok(())
+    }
+}
+
+/// Result of a queued job; resolves when the job finishes
+pub struct JobHandle<T> {
+    result: oneshot::Receiver<Result<T, ComputeError>>,
+    cancellation: CancellationToken,
+}
+
+impl<T> JobHandle<T> {
+    /// Cancel the job (and every other job sharing its token)
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+}
+
+impl<T> Future for JobHandle<T> {
+    type Output = Result<T, ComputeError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.result).poll(cx).map(|result| {
+            // The sender is only dropped unsent when the queue shuts down
+            result.unwrap_or(Err(ComputeError::Cancelled))
+        })
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct Queued {
+    priority: Priority,
+    /// Submission order, for FIFO within a priority
+    seq: u64,
+    job: Job,
+}
+
+impl PartialEq for Queued {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Queued {}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Queued {
+    /// Max-heap order: higher priority, then earlier submission
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+#[derive(Default)]
+struct State {
+    jobs: BinaryHeap<Queued>,
+    next_seq: u64,
+    shutdown: bool,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    available: Condvar,
+}
+
+/// Priority queue of compute jobs in front of one engine
+pub struct JobQueue {
+    engine: Arc<dyn ComputeEngine>,
+    shared: Arc<Shared>,
+    workers: Vec<JoinHandle<()>>,
+    pow_chunk: u64,
+}
+
+impl JobQueue {
+    /// Queue running jobs on `engine` with `workers` threads (at least one)
+    pub fn new(engine: Arc<dyn ComputeEngine>, workers: usize) -> Self {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State::default()),
+            available: Condvar::new(),
+        });
+        let workers = (0..workers.max(1))
+            .map(|_| {
+                let shared = Arc::clone(&shared);
+                std::thread::spawn(move || worker(&shared))
+            })
+            .collect();
+        Self {
+            engine,
+            shared,
+            workers,
+            pow_chunk: DEFAULT_POW_CHUNK,
+        }
+    }
+
+    /// Nonces searched between cancellation checks (default 65,536)
+    pub fn with_pow_chunk(mut self, nonces: u64) -> Self {
+        self.pow_chunk = nonces.max(1);
+        self
+    }
+
+    /// Jobs waiting for a worker
+    pub fn pending(&self) -> usize {
+        self.shared
+            .state
+            .lock()
+            .expect("job queue mutex poisoned")
+            .jobs
+            .len()
+    }
+
+    /// Queue an arbitrary job against the engine
+    ///
+    /// The job should call `JobContext::check` between steps so cancellation
+    /// and timeouts take effect while it runs.
+    pub fn submit<T, F, Fut>(&self, options: JobOptions, job: F) -> JobHandle<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(Arc<dyn ComputeEngine>, JobContext) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, ComputeError>>,
+    {
+        let (sender, result) = oneshot::channel();
+        let context = JobContext {
+            cancellation: options.cancellation.clone(),
+            deadline: options.timeout.map(|timeout| Instant::now() + timeout),
+        };
+        let engine = Arc::clone(&self.engine);
+        let run: Job = Box::new(move || {
+            let outcome = context
+                .check()
+                .and_then(|()| futures::executor::block_on(job(engine, context.clone())));
+            // The caller may have dropped the handle; nobody to tell then
+            let _ = sender.send(outcome);
+        });
+
+        {
+            let mut state = self.shared.state.lock().expect("job queue mutex poisoned");
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            state.jobs.push(Queued {
+                priority: options.priority,
+                seq,
+                job: run,
+            });
+        }
+        self.shared.available.notify_one();
+
+        JobHandle {
+            result,
+            cancellation: options.cancellation,
+        }
+    }
+
+    /// Queue a nonce search, run in chunks so it can be abandoned
+    ///
+    /// Returns the first solution in nonce order.
+    pub fn pow_mine(
+        &self,
+        options: JobOptions,
+        header_template: Vec<u8>,
+        target: U256,
+        nonce_start: u64,
+        nonce_count: u64,
+    ) -> JobHandle<Option<(u64, [u8; 32])>> {
+        let chunk = self.pow_chunk;
+        self.submit(options, move |engine, context| async move {
+            let mut searched = 0;
+            let mut found = None;
+            while found.is_none() && searched < nonce_count {
+                context.check()?;
+                let count = chunk.min(nonce_count - searched);
+                let start = nonce_start.wrapping_add(searched);
+                found = engine
+                    .pow_mine(&header_template, target, start, count)
+                    .await?;
+                searched += count;
+            }
+            Ok(found)
+        })
+    }
+
+    /// Queue a SHA-256 batch
+    pub fn batch_sha256(
+        &self,
+        options: JobOptions,
+        inputs: Vec<Vec<u8>>,
+    ) -> JobHandle<Vec<[u8; 32]>> {
+        self.submit(options, move |engine, _| async move {
+            engine.batch_sha256(&inputs).await
+        })
+    }
+
+    /// Queue a BLS verification batch
+    pub fn batch_verify_bls(
+        &self,
+        options: JobOptions,
+        messages: Vec<Vec<u8>>,
+        signatures: Vec<[u8; 48]>,
+        public_keys: Vec<[u8; 96]>,
+    ) -> JobHandle<Vec<bool>> {
+        self.submit(options, move |engine, _| async move {
+            engine
+                .batch_verify_bls(&messages, &signatures, &public_keys)
+                .await
+        })
+    }
+}
+
+impl Drop for JobQueue {
+    /// Stop the workers; jobs still queued resolve to `Cancelled`
+    fn drop(&mut self) {
+        {
+            let mut state = self.shared.state.lock().expect("job queue mutex poisoned");
+            state.shutdown = true;
+            state.jobs.clear();
+        }
+        self.shared.available.notify_all();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn worker(shared: &Shared) {
+    while let Some(job) = next_job(shared) {
+        job();
+    }
+}
+
+/// Block until a job is queued; None once the queue shuts down
+fn next_job(shared: &Shared) -> Option<Job> {
+    let mut state = shared.state.lock().expect("job queue mutex poisoned");
+    loop {
+        if state.shutdown {
+            return None;
+        }
+        if let Some(queued) = state.jobs.pop() {
+            return Some(queued.job);
+        }
+        state = shared
+            .available
+            .wait(state)
+            .expect("job queue mutex poisoned");
+    }
+}
+
+#[cfg(all(test, feature = "cpu"))]
+mod tests {
+    use super::*;
+    use crate::backends::cpu::CpuEngine;
+
+    fn queue() -> JobQueue {
+        JobQueue::new(Arc::new(CpuEngine::new()), 1).with_pow_chunk(1_000)
+    }
+
+    #[tokio::test]
+    async fn test_higher_priority_runs_first() {
+        let queue = queue();
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        // Hold the only worker until everything is queued
+        let (release, gate) = std::sync::mpsc::channel::<()>();
+        let blocker = queue.submit(JobOptions::default(), move |_, _| async move {
+            gate.recv().ok();
+            Ok(())
+        });
+
+        let handles: Vec<_> = [
+            ("mining", Priority::Mining),
+            ("normal-1", Priority::Normal),
+            ("consensus", Priority::ConsensusCritical),
+            ("normal-2", Priority::Normal),
+        ]
+        .into_iter()
+        .map(|(name, priority)| record_run(&queue, priority, &order, name))
+        .collect();
+        while queue.pending() < 4 {
+            std::thread::yield_now();
+        }
+        release.send(()).unwrap();
+
+        blocker.await.unwrap();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["consensus", "normal-1", "normal-2", "mining"]
+        );
+    }
+
+    /// Queue a job that appends `name` to `order` when it runs
+    fn record_run(
+        queue: &JobQueue,
+        priority: Priority,
+        order: &Arc<Mutex<Vec<&'static str>>>,
+        name: &'static str,
+    ) -> JobHandle<()> {
+        let order = Arc::clone(order);
+        queue.submit(JobOptions::new(priority), move |_, _| async move {
+            order.lock().unwrap().push(name);
+            Ok(())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_pow_mine_finds_solution() {
+        let queue = queue();
+        let target = U256::MAX / 8;
+
+        let (nonce, hash) = queue
+            .pow_mine(
+                JobOptions::new(Priority::Mining),
+                b"queued".to_vec(),
+                target,
+                500,
+                100_000,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(nonce >= 500);
+        assert!(U256::from_big_endian(&hash) <= target);
+
+        let hashes = queue
+            .batch_sha256(JobOptions::default(), vec![b"a".to_vec()])
+            .await
+            .unwrap();
+        assert_eq!(hashes.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_search_stops() {
+        let queue = queue();
+        let new_head = CancellationToken::new();
+
+        // A zero target is never met, so only cancellation ends this search
+        let search = queue.pow_mine(
+            JobOptions::new(Priority::Mining).with_cancellation(new_head.clone()),
+            b"old head".to_vec(),
+            U256::zero(),
+            0,
+            u64::MAX,
+        );
+        std::thread::sleep(Duration::from_millis(20));
+        new_head.cancel();
+        assert!(matches!(search.await, Err(ComputeError::Cancelled)));
+
+        // Cancelled before it starts: never reaches the engine
+        let ran = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&ran);
+        let handle = queue.submit(
+            JobOptions::default().with_cancellation(new_head),
+            move |_, _| async move {
+                flag.store(true, Ordering::SeqCst);
+                Ok(())
+            },
+        );
+        assert!(matches!(handle.await, Err(ComputeError::Cancelled)));
+        assert!(!ran.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_maps_to_timeout_error() {
+        let queue = queue();
+
+        let search = queue.pow_mine(
+            JobOptions::new(Priority::Mining).with_timeout(Duration::from_millis(20)),
+            b"slow".to_vec(),
+            U256::zero(),
+            0,
+            u64::MAX,
+        );
+        assert!(matches!(search.await, Err(ComputeError::Timeout)));
+
+        let handle = queue.pow_mine(
+            JobOptions::default(),
+            b"after timeout".to_vec(),
+            U256::MAX,
+            7,
+            10,
+        );
+        assert_eq!(handle.await.unwrap().map(|(nonce, _)| nonce), Some(7));
+    }
+}
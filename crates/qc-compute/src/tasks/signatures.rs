@@ -39,6 +39,34 @@ impl BatchEcdsaVerifyTask {
     }
 }
 
+/// Batch BLS signature verification (e.g. an attestation flood)
+pub struct BatchBlsVerifyTask {
+    pub messages: Vec<Vec<u8>>,
+    pub signatures: Vec<[u8; 48]>,
+    pub public_keys: Vec<[u8; 96]>,
+}
+
+impl BatchBlsVerifyTask {
+    /// Execute batch verification
+    pub async fn execute(
+        self,
+        engine: &Arc<dyn ComputeEngine>,
+    ) -> Result<BatchVerifyResult, ComputeError> {
+        let results = engine
+            .batch_verify_bls(&self.messages, &self.signatures, &self.public_keys)
+            .await?;
+
+        let valid_count = results.iter().filter(|&&v| v).count();
+        let invalid_count = results.len() - valid_count;
+
+        Ok(BatchVerifyResult {
+            results,
+            valid_count,
+            invalid_count,
+        })
+    }
+}
+
 /// Single ECDSA verification (convenience wrapper)
 pub struct EcdsaVerifyTask {
     pub message: [u8; 32],
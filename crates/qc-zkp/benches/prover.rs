@@ -0,0 +1,145 @@
+//! Prover throughput on 2^20-element inputs
+//!
+//! ```text
+//! cargo bench -p qc-zkp --bench prover
+//! cargo bench -p qc-zkp --features compute --bench prover
+//! ```
+//!
+//! | Benchmark | Input |
+//! |-----------|-------|
+//! | `ntt` | Forward NTT of 2^20 elements |
+//! | `merkle-commit` | Merkle tree over 2^20 leaves |
+//! | `fri-prove` | FRI over 2^20 coset evaluations |
+//! | `stark-prove` | 2^18 × 4 trace (2^20 cells) |
+//!
+//! With `--no-default-features` everything runs on one thread.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use qc_zkp::air::{BoundaryRow, Expr};
+use qc_zkp::{
+    fri, ConstraintSystem, FieldElement, FriConfig, MerkleCommitment, Polynomial, Radix2Domain,
+    Trace, Transcript,
+};
+
+const LOG_SIZE: u32 = 20;
+
+fn elements(len: usize) -> Vec<FieldElement> {
+    (0..len as u64)
+        .map(|i| FieldElement::new(i.wrapping_mul(0x9e37_79b9_7f4a_7c15)))
+        .collect()
+}
+
+fn bench_ntt(c: &mut Criterion) {
+    let domain = Radix2Domain::new(LOG_SIZE).unwrap();
+    let values = elements(domain.size());
+
+    let mut group = c.benchmark_group("ntt");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(values.len() as u64));
+    group.bench_function("forward", |b| {
+        b.iter_batched_ref(
+            || values.clone(),
+            |values| domain.ntt(values).unwrap(),
+            criterion::BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_merkle_commit(c: &mut Criterion) {
+    let leaves = elements(1 << LOG_SIZE);
+
+    let mut group = c.benchmark_group("merkle-commit");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(leaves.len() as u64));
+    group.bench_function("host", |b| b.iter(|| MerkleCommitment::commit(&leaves)));
+
+    #[cfg(feature = "compute")]
+    {
+        use qc_compute::backends::cpu::CpuEngine;
+        use qc_zkp::commitment::{install_compute_engine, uninstall_compute_engine};
+
+        install_compute_engine(std::sync::Arc::new(CpuEngine::new()));
+        group.bench_function("compute-cpu", |b| {
+            b.iter(|| MerkleCommitment::commit(&leaves))
+        });
+        uninstall_compute_engine();
+    }
+    group.finish();
+}
+
+fn bench_fri_prove(c: &mut Criterion) {
+    let config = FriConfig::default();
+    let log_degree = LOG_SIZE - config.log_blowup;
+    let poly = Polynomial::new(elements(1 << log_degree));
+    let values = fri::low_degree_extension(&config, &poly, log_degree).unwrap();
+
+    let mut group = c.benchmark_group("fri-prove");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(values.len() as u64));
+    group.bench_function("evaluations", |b| {
+        b.iter_batched(
+            || values.clone(),
+            |values| {
+                let mut transcript = Transcript::new(b"bench");
+                fri::prove_evaluations(&config, &mut transcript, values, log_degree).unwrap()
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+/// Two interleaved Fibonacci sequences and their step counters.
+fn fibonacci(log_rows: u32) -> (ConstraintSystem, Trace) {
+    let rows = 1usize << log_rows;
+    let mut builder = ConstraintSystem::builder("fibonacci");
+    let a = builder.column("a");
+    let b = builder.column("b");
+    let step = builder.column("step");
+    let square = builder.column("square");
+    builder
+        .transition("a", a.next() - b.curr())
+        .transition("b", b.next() - a.curr() - b.curr())
+        .transition("step", step.next() - step.curr() - Expr::constant(1))
+        .transition("square", square.curr() - step.curr() * step.curr())
+        .boundary(a, BoundaryRow::First, FieldElement::new(0))
+        .boundary(b, BoundaryRow::First, FieldElement::new(1));
+    let system = builder.build().unwrap();
+
+    let mut trace_rows = Vec::with_capacity(rows);
+    let (mut x, mut y) = (FieldElement::new(0), FieldElement::new(1));
+    for i in 0..rows as u64 {
+        let step = FieldElement::new(i);
+        trace_rows.push(vec![x, y, step, step * step]);
+        (x, y) = (y, x + y);
+    }
+    (system, Trace::from_rows(&trace_rows).unwrap())
+}
+
+fn bench_stark_prove(c: &mut Criterion) {
+    let config = FriConfig::default();
+    let (system, trace) = fibonacci(LOG_SIZE - 2);
+
+    let mut group = c.benchmark_group("stark-prove");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(
+        (trace.num_rows() * trace.num_columns()) as u64,
+    ));
+    group.bench_function("fibonacci", |b| {
+        b.iter(|| {
+            let mut transcript = Transcript::new(b"bench");
+            qc_zkp::stark::prove(&system, &trace, &config, &mut transcript).unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_ntt,
+    bench_merkle_commit,
+    bench_fri_prove,
+    bench_stark_prove
+);
+criterion_main!(benches);
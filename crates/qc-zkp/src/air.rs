@@ -0,0 +1,585 @@
+//! # Constraint Systems (AIR)
+//!
+//! Statements are written as an Algebraic Intermediate Representation:
+//!
+//! - An execution trace: a table of `n` rows (a power of two) by a fixed
+//!   set of columns.
+//! - Transition constraints: expressions over the current and next row that
+//!   must evaluate to zero for every pair of consecutive rows.
+//! - Boundary constraints: a column holds a given value at a given row
+//!   (typically the public inputs and outputs).
+//!
+//! A subsystem defines a statement by implementing `WitnessGenerator`: it
+//! builds the `ConstraintSystem` and fills the `Trace` from its inputs.
+//!
+//! ```
+//! use qc_zkp::air::{BoundaryRow, ConstraintSystem};
+//! use qc_zkp::FieldElement;
+//!
+//! // Fibonacci: (a, b) -> (b, a + b), starting from (1, 1)
+//! let mut builder = ConstraintSystem::builder("fibonacci");
+//! let a = builder.column("a");
+//! let b = builder.column("b");
+//! builder
+//!     .transition("shift", a.next() - b.curr())
+//!     .transition("sum", b.next() - (a.curr() + b.curr()))
+//!     .boundary(a, BoundaryRow::First, FieldElement::new(1))
+//!     .boundary(b, BoundaryRow::First, FieldElement::new(1));
+//! let system = builder.build().unwrap();
+//! assert_eq!(system.max_degree(), 1);
+//! ```
+
+use crate::errors::ZkpError;
+use crate::field::FieldElement;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Handle to a trace column, created by `ConstraintSystemBuilder::column`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Column(usize);
+
+impl Column {
+    /// Position of the column in the trace.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+
+    /// This column in the current row.
+    pub fn curr(self) -> Expr {
+        Expr::Current(self)
+    }
+
+    /// This column in the next row.
+    pub fn next(self) -> Expr {
+        Expr::Next(self)
+    }
+}
+
+/// Polynomial expression over two consecutive trace rows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    /// Constant value
+    Constant(FieldElement),
+    /// Column value in the current row
+    Current(Column),
+    /// Column value in the next row
+    Next(Column),
+    /// Sum
+    Add(Box<Expr>, Box<Expr>),
+    /// Difference
+    Sub(Box<Expr>, Box<Expr>),
+    /// Product
+    Mul(Box<Expr>, Box<Expr>),
+    /// Negation
+    Neg(Box<Expr>),
+}
+
+impl Expr {
+    /// Constant expression.
+    pub fn constant(value: u64) -> Self {
+        Self::Constant(FieldElement::new(value))
+    }
+
+    /// Degree in the trace values (constants 0, cells 1).
+    pub fn degree(&self) -> usize {
+        match self {
+            Self::Constant(_) => 0,
+            Self::Current(_) | Self::Next(_) => 1,
+            Self::Add(a, b) | Self::Sub(a, b) => a.degree().max(b.degree()),
+            Self::Mul(a, b) => a.degree() + b.degree(),
+            Self::Neg(a) => a.degree(),
+        }
+    }
+
+    /// Evaluate with the given current and next rows.
+    ///
+    /// Both rows must hold every column the expression references.
+    pub fn evaluate(&self, current: &[FieldElement], next: &[FieldElement]) -> FieldElement {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Current(column) => current[column.0],
+            Self::Next(column) => next[column.0],
+            Self::Add(a, b) => a.evaluate(current, next) + b.evaluate(current, next),
+            Self::Sub(a, b) => a.evaluate(current, next) - b.evaluate(current, next),
+            Self::Mul(a, b) => a.evaluate(current, next) * b.evaluate(current, next),
+            Self::Neg(a) => -a.evaluate(current, next),
+        }
+    }
+
+    /// Highest column index referenced, if any.
+    fn max_column(&self) -> Option<usize> {
+        match self {
+            Self::Constant(_) => None,
+            Self::Current(column) | Self::Next(column) => Some(column.0),
+            Self::Add(a, b) | Self::Sub(a, b) | Self::Mul(a, b) => {
+                a.max_column().max(b.max_column())
+            }
+            Self::Neg(a) => a.max_column(),
+        }
+    }
+}
+
+impl From<FieldElement> for Expr {
+    fn from(value: FieldElement) -> Self {
+        Self::Constant(value)
+    }
+}
+
+impl<T: Into<Expr>> Add<T> for Expr {
+    type Output = Self;
+
+    fn add(self, rhs: T) -> Self {
+        Self::Add(Box::new(self), Box::new(rhs.into()))
+    }
+}
+
+impl<T: Into<Expr>> Sub<T> for Expr {
+    type Output = Self;
+
+    fn sub(self, rhs: T) -> Self {
+        Self::Sub(Box::new(self), Box::new(rhs.into()))
+    }
+}
+
+impl<T: Into<Expr>> Mul<T> for Expr {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::Mul(Box::new(self), Box::new(rhs.into()))
+    }
+}
+
+impl Neg for Expr {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::Neg(Box::new(self))
+    }
+}
+
+/// Row a boundary constraint applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryRow {
+    /// Row 0
+    First,
+    /// Row n - 1
+    Last,
+    /// A specific row
+    Index(usize),
+}
+
+impl BoundaryRow {
+    /// Row index in a trace of `num_rows` rows.
+    pub fn resolve(&self, num_rows: usize) -> usize {
+        match self {
+            Self::First => 0,
+            Self::Last => num_rows - 1,
+            Self::Index(row) => *row,
+        }
+    }
+}
+
+/// Expression that must vanish on every pair of consecutive rows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransitionConstraint {
+    /// Name used in error messages
+    pub name: String,
+    /// Expression over the current and next row
+    pub expr: Expr,
+}
+
+/// Fixed value of one trace cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundaryConstraint {
+    /// Constrained column
+    pub column: Column,
+    /// Constrained row
+    pub row: BoundaryRow,
+    /// Required value
+    pub value: FieldElement,
+}
+
+/// Columns and constraints of a statement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstraintSystem {
+    name: String,
+    columns: Vec<String>,
+    transitions: Vec<TransitionConstraint>,
+    boundaries: Vec<BoundaryConstraint>,
+}
+
+impl ConstraintSystem {
+    /// Start building a constraint system.
+    pub fn builder(name: impl Into<String>) -> ConstraintSystemBuilder {
+        ConstraintSystemBuilder {
+            name: name.into(),
+            columns: Vec::new(),
+            transitions: Vec::new(),
+            boundaries: Vec::new(),
+        }
+    }
+
+    /// Statement name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Column names, in trace order.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Transition constraints.
+    pub fn transitions(&self) -> &[TransitionConstraint] {
+        &self.transitions
+    }
+
+    /// Boundary constraints.
+    pub fn boundaries(&self) -> &[BoundaryConstraint] {
+        &self.boundaries
+    }
+
+    /// Highest transition constraint degree (0 without transitions).
+    pub fn max_degree(&self) -> usize {
+        self.transitions
+            .iter()
+            .map(|constraint| constraint.expr.degree())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Check that `trace` satisfies every constraint.
+    pub fn check(&self, trace: &Trace) -> Result<(), ZkpError> {
+        if trace.num_columns() != self.columns.len() {
+            return Err(ZkpError::InvalidTrace(format!(
+                "{} has {} columns, trace has {}",
+                self.name,
+                self.columns.len(),
+                trace.num_columns()
+            )));
+        }
+
+        for boundary in &self.boundaries {
+            let row = boundary.row.resolve(trace.num_rows());
+            if row >= trace.num_rows() {
+                return Err(ZkpError::InvalidTrace(format!(
+                    "Boundary row {} outside trace of {} rows",
+                    row,
+                    trace.num_rows()
+                )));
+            }
+            if trace.get(boundary.column, row) != boundary.value {
+                let name = format!("boundary {}", self.columns[boundary.column.0]);
+                return Err(ZkpError::ConstraintViolation(name, row));
+            }
+        }
+
+        let mut current = trace.row(0);
+        for row in 1..trace.num_rows() {
+            let next = trace.row(row);
+            let failed = self
+                .transitions
+                .iter()
+                .find(|constraint| !constraint.expr.evaluate(&current, &next).is_zero());
+            if let Some(constraint) = failed {
+                return Err(ZkpError::ConstraintViolation(
+                    constraint.name.clone(),
+                    row - 1,
+                ));
+            }
+            current = next;
+        }
+        Ok(())
+    }
+}
+
+/// Builder for `ConstraintSystem`.
+#[derive(Clone, Debug)]
+pub struct ConstraintSystemBuilder {
+    name: String,
+    columns: Vec<String>,
+    transitions: Vec<TransitionConstraint>,
+    boundaries: Vec<BoundaryConstraint>,
+}
+
+impl ConstraintSystemBuilder {
+    /// Add a trace column.
+    pub fn column(&mut self, name: impl Into<String>) -> Column {
+        self.columns.push(name.into());
+        Column(self.columns.len() - 1)
+    }
+
+    /// Require `expr` to vanish on every pair of consecutive rows.
+    pub fn transition(&mut self, name: impl Into<String>, expr: Expr) -> &mut Self {
+        self.transitions.push(TransitionConstraint {
+            name: name.into(),
+            expr,
+        });
+        self
+    }
+
+    /// Require `column` to hold `value` at `row`.
+    pub fn boundary(&mut self, column: Column, row: BoundaryRow, value: FieldElement) -> &mut Self {
+        self.boundaries
+            .push(BoundaryConstraint { column, row, value });
+        self
+    }
+
+    /// Validate and build.
+    pub fn build(self) -> Result<ConstraintSystem, ZkpError> {
+        if self.columns.is_empty() {
+            return Err(ZkpError::InvalidConstraintSystem(format!(
+                "{} has no columns",
+                self.name
+            )));
+        }
+
+        let column_count = self.columns.len();
+        for constraint in &self.transitions {
+            if constraint.expr.degree() == 0 {
+                return Err(ZkpError::InvalidConstraintSystem(format!(
+                    "Transition {} does not reference the trace",
+                    constraint.name
+                )));
+            }
+            if constraint.expr.max_column() >= Some(column_count) {
+                return Err(ZkpError::InvalidConstraintSystem(format!(
+                    "Transition {} references an unknown column",
+                    constraint.name
+                )));
+            }
+        }
+        if self.boundaries.iter().any(|b| b.column.0 >= column_count) {
+            return Err(ZkpError::InvalidConstraintSystem(
+                "Boundary references an unknown column".to_string(),
+            ));
+        }
+
+        Ok(ConstraintSystem {
+            name: self.name,
+            columns: self.columns,
+            transitions: self.transitions,
+            boundaries: self.boundaries,
+        })
+    }
+}
+
+/// Execution trace, stored column by column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace {
+    columns: Vec<Vec<FieldElement>>,
+}
+
+impl Trace {
+    /// Create a trace from columns of equal, power-of-two length (at least 2).
+    pub fn new(columns: Vec<Vec<FieldElement>>) -> Result<Self, ZkpError> {
+        let num_rows = columns.first().map_or(0, Vec::len);
+        if columns.is_empty() {
+            return Err(ZkpError::InvalidTrace("Trace has no columns".to_string()));
+        }
+        if columns.iter().any(|column| column.len() != num_rows) {
+            return Err(ZkpError::InvalidTrace(
+                "Trace columns differ in length".to_string(),
+            ));
+        }
+        if num_rows < 2 || !num_rows.is_power_of_two() {
+            return Err(ZkpError::InvalidTrace(format!(
+                "Trace length {} is not a power of two of at least 2",
+                num_rows
+            )));
+        }
+        Ok(Self { columns })
+    }
+
+    /// Create a trace from rows of equal width.
+    pub fn from_rows(rows: &[Vec<FieldElement>]) -> Result<Self, ZkpError> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return Err(ZkpError::InvalidTrace(
+                "Trace rows differ in width".to_string(),
+            ));
+        }
+        let columns = (0..width)
+            .map(|column| rows.iter().map(|row| row[column]).collect())
+            .collect();
+        Self::new(columns)
+    }
+
+    /// Number of rows.
+    pub fn num_rows(&self) -> usize {
+        self.columns[0].len()
+    }
+
+    /// Number of columns.
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// All values of a column.
+    pub fn column(&self, column: Column) -> &[FieldElement] {
+        &self.columns[column.0]
+    }
+
+    /// Value of one cell.
+    pub fn get(&self, column: Column, row: usize) -> FieldElement {
+        self.columns[column.0][row]
+    }
+
+    /// Values of one row, in column order.
+    pub fn row(&self, row: usize) -> Vec<FieldElement> {
+        self.columns.iter().map(|column| column[row]).collect()
+    }
+
+    /// Columns in trace order.
+    pub fn columns(&self) -> &[Vec<FieldElement>] {
+        &self.columns
+    }
+}
+
+/// A provable statement: its constraints and how to fill its trace.
+///
+/// Implemented by subsystems for the statements they prove, e.g. "this
+/// attestation aggregation is valid".
+pub trait WitnessGenerator {
+    /// Inputs the trace is computed from.
+    type Input: ?Sized;
+
+    /// Constraints every generated trace satisfies.
+    fn constraint_system(&self) -> &ConstraintSystem;
+
+    /// Fill the execution trace for `input`.
+    fn generate_witness(&self, input: &Self::Input) -> Result<Trace, ZkpError>;
+
+    /// Generate the trace and check it against the constraints.
+    fn checked_witness(&self, input: &Self::Input) -> Result<Trace, ZkpError> {
+        let trace = self.generate_witness(input)?;
+        self.constraint_system().check(&trace)?;
+        Ok(trace)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fibonacci over `rows` rows, ending in a public result.
+    struct Fibonacci {
+        system: ConstraintSystem,
+        rows: usize,
+    }
+
+    impl Fibonacci {
+        fn new(rows: usize, result: u64) -> Self {
+            let mut builder = ConstraintSystem::builder("fibonacci");
+            let a = builder.column("a");
+            let b = builder.column("b");
+            builder
+                .transition("shift", a.next() - b.curr())
+                .transition("sum", b.next() - (a.curr() + b.curr()))
+                .boundary(a, BoundaryRow::First, FieldElement::new(1))
+                .boundary(b, BoundaryRow::First, FieldElement::new(1))
+                .boundary(b, BoundaryRow::Last, FieldElement::new(result));
+            Self {
+                system: builder.build().unwrap(),
+                rows,
+            }
+        }
+    }
+
+    impl WitnessGenerator for Fibonacci {
+        type Input = (u64, u64);
+
+        fn constraint_system(&self) -> &ConstraintSystem {
+            &self.system
+        }
+
+        fn generate_witness(&self, input: &(u64, u64)) -> Result<Trace, ZkpError> {
+            let mut row = vec![FieldElement::new(input.0), FieldElement::new(input.1)];
+            let mut rows = Vec::with_capacity(self.rows);
+            for _ in 0..self.rows {
+                rows.push(row.clone());
+                row = vec![row[1], row[0] + row[1]];
+            }
+            Trace::from_rows(&rows)
+        }
+    }
+
+    #[test]
+    fn test_fibonacci_witness() {
+        // Rows (1,1), (1,2), (2,3), ..., (13,21)
+        let circuit = Fibonacci::new(8, 34);
+        let trace = circuit.checked_witness(&(1, 1)).unwrap();
+        assert_eq!(trace.num_rows(), 8);
+        assert_eq!(
+            trace.row(7),
+            vec![FieldElement::new(21), FieldElement::new(34)]
+        );
+
+        // Wrong claimed result
+        assert!(matches!(
+            Fibonacci::new(8, 35).checked_witness(&(1, 1)),
+            Err(ZkpError::ConstraintViolation(name, 7)) if name == "boundary b"
+        ));
+    }
+
+    #[test]
+    fn test_transition_violation_reports_row() {
+        let circuit = Fibonacci::new(4, 5);
+        let mut columns = circuit
+            .generate_witness(&(1, 1))
+            .unwrap()
+            .columns()
+            .to_vec();
+        columns[0][2] = FieldElement::new(9);
+        let trace = Trace::new(columns).unwrap();
+
+        assert!(matches!(
+            circuit.constraint_system().check(&trace),
+            Err(ZkpError::ConstraintViolation(name, 1)) if name == "shift"
+        ));
+    }
+
+    #[test]
+    fn test_expression_degree_and_evaluation() {
+        let mut builder = ConstraintSystem::builder("square");
+        let x = builder.column("x");
+        let expr = x.next() - x.curr() * x.curr() - FieldElement::new(1);
+        assert_eq!(expr.degree(), 2);
+
+        let current = [FieldElement::new(3)];
+        assert!(expr.evaluate(&current, &[FieldElement::new(10)]).is_zero());
+        assert!(!expr.evaluate(&current, &[FieldElement::new(9)]).is_zero());
+        assert!((-Expr::constant(1) + Expr::constant(1))
+            .evaluate(&[], &[])
+            .is_zero());
+
+        builder.transition("square", expr);
+        assert_eq!(builder.build().unwrap().max_degree(), 2);
+    }
+
+    #[test]
+    fn test_invalid_systems_and_traces() {
+        assert!(ConstraintSystem::builder("empty").build().is_err());
+
+        let mut other = ConstraintSystem::builder("other");
+        other.column("a");
+        let foreign = other.column("b");
+        let mut builder = ConstraintSystem::builder("one column");
+        let a = builder.column("a");
+        builder.transition("foreign", a.next() - foreign.curr());
+        assert!(matches!(
+            builder.build(),
+            Err(ZkpError::InvalidConstraintSystem(_))
+        ));
+
+        let mut builder = ConstraintSystem::builder("constant");
+        builder.column("a");
+        builder.transition("constant", Expr::constant(0));
+        assert!(builder.build().is_err());
+
+        let one = FieldElement::new(1);
+        assert!(Trace::new(vec![]).is_err());
+        assert!(Trace::new(vec![vec![one; 3]]).is_err());
+        assert!(Trace::new(vec![vec![one; 4], vec![one; 2]]).is_err());
+        assert!(Trace::from_rows(&[vec![one], vec![one, one]]).is_err());
+    }
+}
@@ -1,50 +1,110 @@
 //! # Merkle Commitment
 //!
-//! Merkle tree commitments for polynomial coefficients.
+//! Merkle tree commitments for polynomial coefficients and evaluations.
+//!
+//! Leaves and inner nodes are hashed with BLAKE3 under different prefixes,
+//! so a leaf can never be passed off as an inner node.
+//!
+//! Each tree layer is hashed in parallel (`parallel` feature). With the
+//! `compute` feature, layers of at least `COMPUTE_MIN_HASHES` nodes go to
+//! the qc-compute engine set with `install_compute_engine`, falling back to
+//! local hashing if the engine fails. Both give the same tree.
 
 use crate::field::FieldElement;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use crate::parallel;
+#[cfg(feature = "compute")]
+use std::sync::{Arc, RwLock};
 
-/// Hash output (simulated, would use BLAKE3 in production).
+/// Hash output (BLAKE3).
 pub type HashOutput = [u8; 32];
 
+/// Prefix for leaf hashes.
+const LEAF_PREFIX: u8 = 0;
+/// Prefix for inner node hashes.
+const NODE_PREFIX: u8 = 1;
+
+/// Smallest layer sent to the compute engine; below this the transfer
+/// costs more than the hashing.
+#[cfg(feature = "compute")]
+pub const COMPUTE_MIN_HASHES: usize = 1 << 14;
+
+/// Engine that hashes large layers (`compute` feature).
+#[cfg(feature = "compute")]
+static COMPUTE_ENGINE: RwLock<Option<Arc<dyn qc_compute::ComputeEngine>>> = RwLock::new(None);
+
+/// Hash large Merkle layers with `engine`'s batch BLAKE3.
+///
+/// The engine is driven synchronously, so it must not depend on the
+/// caller's async runtime (the CPU and OpenCL engines don't).
+#[cfg(feature = "compute")]
+pub fn install_compute_engine(engine: Arc<dyn qc_compute::ComputeEngine>) {
+    if let Ok(mut installed) = COMPUTE_ENGINE.write() {
+        *installed = Some(engine);
+    }
+}
+
+/// Go back to hashing every layer locally.
+#[cfg(feature = "compute")]
+pub fn uninstall_compute_engine() {
+    if let Ok(mut installed) = COMPUTE_ENGINE.write() {
+        *installed = None;
+    }
+}
+
 /// Merkle tree commitment for polynomial evaluations.
 #[derive(Clone, Debug)]
 pub struct MerkleCommitment {
     root: HashOutput,
-    leaves: Vec<HashOutput>,
+    /// Every tree layer, leaves first (odd layers padded with a zero hash)
+    layers: Vec<Vec<HashOutput>>,
+    leaf_count: usize,
     height: usize,
 }
 
 impl MerkleCommitment {
     /// Commit to a vector of field elements.
     pub fn commit(values: &[FieldElement]) -> Self {
-        if values.is_empty() {
+        Self::from_leaves(hash_leaves(values, std::slice::from_ref))
+    }
+
+    /// Commit to rows of field elements, one leaf per row.
+    ///
+    /// A one-element row hashes like the element itself.
+    pub fn commit_rows(rows: &[Vec<FieldElement>]) -> Self {
+        Self::from_leaves(hash_leaves(rows, Vec::as_slice))
+    }
+
+    fn from_leaves(leaves: Vec<HashOutput>) -> Self {
+        if leaves.is_empty() {
             return Self {
                 root: [0u8; 32],
-                leaves: vec![],
+                layers: vec![],
+                leaf_count: 0,
                 height: 0,
             };
         }
 
-        // Hash leaves
-        let leaves: Vec<HashOutput> = values.iter().copied().map(hash_field_element).collect();
-
         // Build tree
-        // Allow intentional casts for log2 calculation
-        #[allow(
-            clippy::cast_possible_truncation,
-            clippy::cast_sign_loss,
-            clippy::cast_precision_loss
-        )]
-        let height = (leaves.len() as f64).log2().ceil() as usize;
-        let root = Self::build_tree(&leaves);
+        let leaf_count = leaves.len();
+        let mut layers = vec![leaves];
+        loop {
+            let layer = layers.last_mut().expect("leaf layer is always present");
+            if layer.len() <= 1 {
+                break;
+            }
+            // Pad to even length
+            if layer.len() % 2 == 1 {
+                layer.push([0u8; 32]);
+            }
+            let next_layer = hash_layer(layer);
+            layers.push(next_layer);
+        }
 
         Self {
-            root,
-            leaves,
-            height,
+            root: layers[layers.len() - 1][0],
+            height: layers.len() - 1,
+            layers,
+            leaf_count,
         }
     }
 
@@ -62,74 +122,32 @@ impl MerkleCommitment {
 
     /// Generate opening proof for leaf at index.
     pub fn open(&self, index: usize) -> Option<MerkleProof> {
-        if index >= self.leaves.len() {
+        if index >= self.leaf_count {
             return None;
         }
 
-        let mut siblings = Vec::new();
+        let mut siblings = Vec::with_capacity(self.height);
         let mut current_idx = index;
-        let mut layer = self.leaves.clone();
-
-        while layer.len() > 1 {
-            // Pad to even length
-            if layer.len() % 2 == 1 {
-                layer.push([0u8; 32]);
-            }
-
-            let sibling_idx = if current_idx % 2 == 0 {
-                current_idx + 1
-            } else {
-                current_idx - 1
-            };
-
-            if sibling_idx < layer.len() {
-                siblings.push(layer[sibling_idx]);
-            }
-
-            // Compute next layer
-            let mut next_layer = Vec::new();
-            for chunk in layer.chunks(2) {
-                next_layer.push(hash_pair(&chunk[0], &chunk[1]));
-            }
-            layer = next_layer;
+        for layer in &self.layers[..self.height] {
+            siblings.push(layer[current_idx ^ 1]);
             current_idx /= 2;
         }
 
         Some(MerkleProof {
-            leaf: self.leaves[index],
+            leaf: self.layers[0][index],
             index,
             siblings,
         })
     }
-
-    fn build_tree(leaves: &[HashOutput]) -> HashOutput {
-        if leaves.is_empty() {
-            return [0u8; 32];
-        }
-        if leaves.len() == 1 {
-            return leaves[0];
-        }
-
-        let mut layer = leaves.to_vec();
-
-        while layer.len() > 1 {
-            if layer.len() % 2 == 1 {
-                layer.push([0u8; 32]);
-            }
-
-            let mut next_layer = Vec::new();
-            for chunk in layer.chunks(2) {
-                next_layer.push(hash_pair(&chunk[0], &chunk[1]));
-            }
-            layer = next_layer;
-        }
-
-        layer[0]
-    }
 }
 
 /// Merkle proof for a single leaf.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
 pub struct MerkleProof {
     /// Leaf hash
     pub leaf: HashOutput,
@@ -158,25 +176,155 @@ impl MerkleProof {
     }
 }
 
+/// Committed value together with its Merkle proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct Opening {
+    /// Committed value
+    pub value: FieldElement,
+    /// Proof for the value's leaf
+    pub proof: MerkleProof,
+}
+
+impl Opening {
+    /// Open the leaf at `index` of a commitment to `values`.
+    pub fn new(
+        commitment: &MerkleCommitment,
+        values: &[FieldElement],
+        index: usize,
+    ) -> Option<Self> {
+        Some(Self {
+            value: *values.get(index)?,
+            proof: commitment.open(index)?,
+        })
+    }
+
+    /// Check that `value` sits at `index` of a tree with `root` and `height`.
+    pub fn verify(&self, root: &HashOutput, index: usize, height: usize) -> bool {
+        self.proof.index == index
+            && self.proof.siblings.len() == height
+            && self.proof.leaf == hash_field_element(self.value)
+            && self.proof.verify(root)
+    }
+}
+
+/// Committed row together with its Merkle proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct RowOpening {
+    /// Committed row
+    pub values: Vec<FieldElement>,
+    /// Proof for the row's leaf
+    pub proof: MerkleProof,
+}
+
+impl RowOpening {
+    /// Open the row at `index` of a commitment to `rows`.
+    pub fn new(
+        commitment: &MerkleCommitment,
+        rows: &[Vec<FieldElement>],
+        index: usize,
+    ) -> Option<Self> {
+        Some(Self {
+            values: rows.get(index)?.clone(),
+            proof: commitment.open(index)?,
+        })
+    }
+
+    /// Check that `values` sit at `index` of a tree with `root` and `height`.
+    pub fn verify(&self, root: &HashOutput, index: usize, height: usize) -> bool {
+        self.proof.index == index
+            && self.proof.siblings.len() == height
+            && self.proof.leaf == hash_row(&self.values)
+            && self.proof.verify(root)
+    }
+}
+
+/// Leaf hash of every item's row.
+fn hash_leaves<T, F>(items: &[T], row: F) -> Vec<HashOutput>
+where
+    T: Sync,
+    F: Fn(&T) -> &[FieldElement] + Sync + Send,
+{
+    #[cfg(feature = "compute")]
+    if let Some(hashes) = compute_hashes(items.len(), |i| {
+        let row = row(&items[i]);
+        let mut input = Vec::with_capacity(1 + 8 * row.len());
+        input.push(LEAF_PREFIX);
+        for elem in row {
+            input.extend_from_slice(&elem.value().to_le_bytes());
+        }
+        input
+    }) {
+        return hashes;
+    }
+    parallel::map(items, |item| hash_row(row(item)))
+}
+
+/// Parent hashes of an even-length layer.
+fn hash_layer(layer: &[HashOutput]) -> Vec<HashOutput> {
+    #[cfg(feature = "compute")]
+    if let Some(hashes) = compute_hashes(layer.len() / 2, |i| {
+        let mut input = Vec::with_capacity(65);
+        input.push(NODE_PREFIX);
+        input.extend_from_slice(&layer[2 * i]);
+        input.extend_from_slice(&layer[2 * i + 1]);
+        input
+    }) {
+        return hashes;
+    }
+    parallel::map_range(layer.len() / 2, |i| {
+        hash_pair(&layer[2 * i], &layer[2 * i + 1])
+    })
+}
+
+/// BLAKE3 of `count` inputs on the installed engine, if there is one and
+/// the batch is large enough.
+#[cfg(feature = "compute")]
+fn compute_hashes<F>(count: usize, input: F) -> Option<Vec<HashOutput>>
+where
+    F: Fn(usize) -> Vec<u8> + Sync + Send,
+{
+    if count < COMPUTE_MIN_HASHES {
+        return None;
+    }
+    let engine = COMPUTE_ENGINE.read().ok()?.clone()?;
+    let inputs = parallel::map_range(count, input);
+    futures::executor::block_on(engine.batch_blake3(&inputs))
+        .ok()
+        .filter(|hashes| hashes.len() == count)
+}
+
 /// Hash a field element.
-fn hash_field_element(elem: FieldElement) -> HashOutput {
-    let mut hasher = DefaultHasher::new();
-    elem.value().hash(&mut hasher);
-    let hash = hasher.finish();
-    let mut output = [0u8; 32];
-    output[0..8].copy_from_slice(&hash.to_le_bytes());
-    output
+pub(crate) fn hash_field_element(elem: FieldElement) -> HashOutput {
+    hash_row(&[elem])
+}
+
+/// Hash a row of field elements.
+pub(crate) fn hash_row(row: &[FieldElement]) -> HashOutput {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_PREFIX]);
+    for elem in row {
+        hasher.update(&elem.value().to_le_bytes());
+    }
+    *hasher.finalize().as_bytes()
 }
 
 /// Hash two nodes together.
 fn hash_pair(left: &HashOutput, right: &HashOutput) -> HashOutput {
-    let mut hasher = DefaultHasher::new();
-    left.hash(&mut hasher);
-    right.hash(&mut hasher);
-    let hash = hasher.finish();
-    let mut output = [0u8; 32];
-    output[0..8].copy_from_slice(&hash.to_le_bytes());
-    output
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    *hasher.finalize().as_bytes()
 }
 
 #[cfg(test)]
@@ -209,6 +357,78 @@ mod tests {
         assert!(!proof.verify(commitment.root()));
     }
 
+    #[test]
+    fn test_open_every_leaf() {
+        let values: Vec<FieldElement> = (0..5).map(FieldElement::new).collect();
+        let commitment = MerkleCommitment::commit(&values);
+        assert_eq!(commitment.height(), 3);
+
+        for index in 0..values.len() {
+            let opening = Opening::new(&commitment, &values, index).unwrap();
+            assert!(opening.verify(commitment.root(), index, 3));
+            assert!(!opening.verify(commitment.root(), index ^ 1, 3));
+        }
+        assert!(commitment.open(5).is_none());
+
+        // A value that was not committed does not verify
+        let mut opening = Opening::new(&commitment, &values, 2).unwrap();
+        opening.value = FieldElement::new(9);
+        assert!(!opening.verify(commitment.root(), 2, 3));
+    }
+
+    #[test]
+    fn test_commit_rows() {
+        let rows: Vec<Vec<FieldElement>> = (0..4)
+            .map(|i| vec![FieldElement::new(i), FieldElement::new(i * 10)])
+            .collect();
+        let commitment = MerkleCommitment::commit_rows(&rows);
+
+        let mut opening = RowOpening::new(&commitment, &rows, 3).unwrap();
+        assert!(opening.verify(commitment.root(), 3, 2));
+        opening.values.swap(0, 1);
+        assert!(!opening.verify(commitment.root(), 3, 2));
+
+        // Single-element rows commit like plain values
+        let values: Vec<FieldElement> = (0..4).map(FieldElement::new).collect();
+        let single: Vec<Vec<FieldElement>> = values.iter().map(|v| vec![*v]).collect();
+        assert_eq!(
+            MerkleCommitment::commit_rows(&single).root(),
+            MerkleCommitment::commit(&values).root()
+        );
+    }
+
+    #[test]
+    fn test_large_tree_matches_sequential_hashing() {
+        let values: Vec<FieldElement> = (0..5000).map(FieldElement::new).collect();
+        let commitment = MerkleCommitment::commit(&values);
+
+        let mut layer: Vec<HashOutput> = values.iter().copied().map(hash_field_element).collect();
+        while layer.len() > 1 {
+            if layer.len() % 2 == 1 {
+                layer.push([0u8; 32]);
+            }
+            layer = layer.chunks(2).map(|c| hash_pair(&c[0], &c[1])).collect();
+        }
+        assert_eq!(commitment.root(), &layer[0]);
+    }
+
+    #[cfg(feature = "compute")]
+    #[test]
+    fn test_compute_engine_builds_same_tree() {
+        let rows: Vec<Vec<FieldElement>> = (0..COMPUTE_MIN_HASHES as u64 * 2)
+            .map(|i| vec![FieldElement::new(i), FieldElement::new(i * 3)])
+            .collect();
+        let local = MerkleCommitment::commit_rows(&rows);
+
+        install_compute_engine(qc_compute::create_backend(qc_compute::Backend::Cpu).unwrap());
+        let routed = MerkleCommitment::commit_rows(&rows);
+        uninstall_compute_engine();
+
+        assert_eq!(routed.root(), local.root());
+        let opening = RowOpening::new(&routed, &rows, 77).unwrap();
+        assert!(opening.verify(local.root(), 77, local.height()));
+    }
+
     #[test]
     fn test_empty_commitment() {
         let commitment = MerkleCommitment::commit(&[]);
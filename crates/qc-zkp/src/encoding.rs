@@ -0,0 +1,741 @@
+//! # Proof Wire Format
+//!
+//! Compact, versioned binary encoding of proofs, for sending them over the
+//! event bus and storing them alongside blocks.
+//!
+//! ## Layout
+//!
+//! | Field | Size |
+//! |-------|------|
+//! | Magic `QCZK` | 4 bytes |
+//! | Format version | 1 byte |
+//! | Proof kind | 1 byte |
+//! | Body | rest |
+//!
+//! In the body, field elements are 8-byte little-endian canonical values,
+//! hashes are 32 bytes, and lengths, indices and heights are unsigned
+//! LEB128. Merkle leaf hashes are not sent: decoding recomputes them from
+//! the opened values.
+//!
+//! ## Validation
+//!
+//! Decoding is strict. It rejects unknown versions and kinds, inputs over
+//! `MAX_PROOF_BYTES`, counts over the limits below, non-canonical field
+//! elements and varints, trailing bytes, and proofs whose parts disagree in
+//! shape (e.g. FRI queries vs. witness openings). It does not verify the
+//! proof.
+//!
+//! With the `serde` feature, proofs also convert to and from JSON for
+//! debugging; `from_json` applies the same limits.
+
+use crate::commitment::{
+    hash_field_element, hash_row, HashOutput, MerkleProof, Opening, RowOpening,
+};
+use crate::errors::ZkpError;
+use crate::field::{FieldElement, GoldilocksField};
+use crate::fri::{FriProof, FriQuery};
+use crate::proof::Proof;
+use crate::stark::StarkProof;
+use crate::state_transition::{StateTransitionProof, StateTransitionStatement};
+
+/// Leading bytes of every encoded proof.
+pub const MAGIC: [u8; 4] = *b"QCZK";
+
+/// Current format version; decoding accepts only this version.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Largest encoded proof accepted.
+pub const MAX_PROOF_BYTES: usize = 8 << 20;
+
+/// Largest JSON proof accepted by `from_json`.
+#[cfg(feature = "serde")]
+pub const MAX_JSON_BYTES: usize = 16 * MAX_PROOF_BYTES;
+
+/// Most FRI queries in a proof.
+pub const MAX_QUERIES: usize = 256;
+
+/// Deepest Merkle tree, and most FRI layers (evaluation domains have at
+/// most 2^32 points).
+pub const MAX_DEPTH: usize = GoldilocksField::TWO_ADICITY as usize;
+
+/// Most trace columns in a STARK proof.
+pub const MAX_ROW_WIDTH: usize = 1024;
+
+/// Most claimed evaluations in a `Proof`.
+pub const MAX_EVALUATIONS: usize = 64;
+
+/// Kind byte of `Proof`.
+const KIND_PROOF: u8 = 1;
+/// Kind byte of `StarkProof`.
+const KIND_STARK: u8 = 2;
+/// Kind byte of `StateTransitionProof`.
+const KIND_STATE_TRANSITION: u8 = 3;
+
+impl Proof {
+    /// Binary encoding (see the module docs).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode(KIND_PROOF, self)
+    }
+
+    /// Decode and validate a binary proof.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ZkpError> {
+        decode(KIND_PROOF, bytes)
+    }
+
+    /// Pretty-printed JSON, for debugging.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        to_json(self)
+    }
+
+    /// Parse and validate a JSON proof.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, ZkpError> {
+        from_json(json)
+    }
+}
+
+impl StarkProof {
+    /// Binary encoding (see the module docs).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode(KIND_STARK, self)
+    }
+
+    /// Decode and validate a binary proof.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ZkpError> {
+        decode(KIND_STARK, bytes)
+    }
+
+    /// Pretty-printed JSON, for debugging.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        to_json(self)
+    }
+
+    /// Parse and validate a JSON proof.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, ZkpError> {
+        from_json(json)
+    }
+}
+
+impl StateTransitionProof {
+    /// Binary encoding (see the module docs).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode(KIND_STATE_TRANSITION, self)
+    }
+
+    /// Decode and validate a binary proof.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ZkpError> {
+        decode(KIND_STATE_TRANSITION, bytes)
+    }
+
+    /// Pretty-printed JSON, for debugging.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        to_json(self)
+    }
+
+    /// Parse and validate a JSON proof.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, ZkpError> {
+        from_json(json)
+    }
+}
+
+/// A proof (or part of one) with a binary encoding.
+trait Wire: Sized {
+    fn write(&self, writer: &mut Writer);
+
+    fn read(reader: &mut Reader<'_>) -> Result<Self, ZkpError>;
+
+    /// Check limits and shape, after `read` and on JSON input.
+    fn validate(&self) -> Result<(), ZkpError>;
+}
+
+fn encode<T: Wire>(kind: u8, value: &T) -> Vec<u8> {
+    let mut writer = Writer(Vec::with_capacity(1024));
+    writer.0.extend_from_slice(&MAGIC);
+    writer.0.extend_from_slice(&[FORMAT_VERSION, kind]);
+    value.write(&mut writer);
+    writer.0
+}
+
+fn decode<T: Wire>(kind: u8, bytes: &[u8]) -> Result<T, ZkpError> {
+    if bytes.len() > MAX_PROOF_BYTES {
+        return Err(ZkpError::ProofTooLarge(bytes.len(), MAX_PROOF_BYTES));
+    }
+    let mut reader = Reader { bytes };
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(malformed("bad magic"));
+    }
+    let version = reader.u8()?;
+    if version != FORMAT_VERSION {
+        return Err(ZkpError::UnsupportedVersion(version));
+    }
+    let found = reader.u8()?;
+    if found != kind {
+        return Err(malformed(format!(
+            "expected proof kind {kind}, got {found}"
+        )));
+    }
+
+    let value = T::read(&mut reader)?;
+    if !reader.bytes.is_empty() {
+        return Err(malformed(format!("{} trailing bytes", reader.bytes.len())));
+    }
+    value.validate()?;
+    Ok(value)
+}
+
+#[cfg(feature = "serde")]
+fn to_json<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).expect("proofs serialize to JSON")
+}
+
+#[cfg(feature = "serde")]
+fn from_json<T: Wire + serde::de::DeserializeOwned>(json: &str) -> Result<T, ZkpError> {
+    if json.len() > MAX_JSON_BYTES {
+        return Err(ZkpError::ProofTooLarge(json.len(), MAX_JSON_BYTES));
+    }
+    let value: T = serde_json::from_str(json).map_err(|e| malformed(e.to_string()))?;
+    value.validate()?;
+    Ok(value)
+}
+
+fn malformed(reason: impl Into<String>) -> ZkpError {
+    ZkpError::MalformedEncoding(reason.into())
+}
+
+fn check_len(what: &str, len: usize, max: usize) -> Result<(), ZkpError> {
+    if len > max {
+        return Err(malformed(format!(
+            "{len} {what} exceeds the limit of {max}"
+        )));
+    }
+    Ok(())
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn len(&mut self, len: usize) {
+        self.varint(len as u64);
+    }
+
+    fn field(&mut self, value: FieldElement) {
+        self.0.extend_from_slice(&value.value().to_le_bytes());
+    }
+
+    fn fields(&mut self, values: &[FieldElement]) {
+        for value in values {
+            self.field(*value);
+        }
+    }
+
+    fn hash(&mut self, hash: &HashOutput) {
+        self.0.extend_from_slice(hash);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ZkpError> {
+        if self.bytes.len() < len {
+            return Err(malformed("unexpected end of input"));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, ZkpError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ZkpError> {
+        Ok(self.take(N)?.try_into().expect("took N bytes"))
+    }
+
+    /// Minimal LEB128 u64.
+    fn varint(&mut self) -> Result<u64, ZkpError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            let bits = u64::from(byte & 0x7f);
+            if shift == 63 && bits > 1 {
+                return Err(malformed("varint overflows u64"));
+            }
+            value |= bits << shift;
+            if byte & 0x80 != 0 {
+                continue;
+            }
+            if byte == 0 && shift > 0 {
+                return Err(malformed("non-minimal varint"));
+            }
+            return Ok(value);
+        }
+        Err(malformed("varint overflows u64"))
+    }
+
+    /// Varint no larger than `max`.
+    fn bounded(&mut self, what: &str, max: usize) -> Result<usize, ZkpError> {
+        let value = self.varint()?;
+        let len = usize::try_from(value).unwrap_or(usize::MAX);
+        check_len(what, len, max)?;
+        Ok(len)
+    }
+
+    fn field(&mut self) -> Result<FieldElement, ZkpError> {
+        FieldElement::try_from(u64::from_le_bytes(self.array()?))
+    }
+
+    fn fields(&mut self, count: usize) -> Result<Vec<FieldElement>, ZkpError> {
+        (0..count).map(|_| self.field()).collect()
+    }
+
+    fn hash(&mut self) -> Result<HashOutput, ZkpError> {
+        self.array()
+    }
+
+    /// `count` items, each needing at least `min_len` bytes; checked
+    /// against the remaining input before allocating.
+    fn list<T>(
+        &mut self,
+        count: usize,
+        min_len: usize,
+        mut item: impl FnMut(&mut Self) -> Result<T, ZkpError>,
+    ) -> Result<Vec<T>, ZkpError> {
+        if count.saturating_mul(min_len) > self.bytes.len() {
+            return Err(malformed("unexpected end of input"));
+        }
+        (0..count).map(|_| item(self)).collect()
+    }
+}
+
+/// Index and siblings; the leaf is recomputed from the opened values.
+fn write_path(writer: &mut Writer, proof: &MerkleProof) {
+    writer.len(proof.index);
+    writer.len(proof.siblings.len());
+    for sibling in &proof.siblings {
+        writer.hash(sibling);
+    }
+}
+
+fn read_path(reader: &mut Reader<'_>, leaf: HashOutput) -> Result<MerkleProof, ZkpError> {
+    let index = reader.bounded("leaf index", usize::MAX)?;
+    let height = reader.bounded("tree height", MAX_DEPTH)?;
+    let proof = MerkleProof {
+        leaf,
+        index,
+        siblings: reader.list(height, 32, Reader::hash)?,
+    };
+    check_path(&proof)?;
+    Ok(proof)
+}
+
+fn check_path(proof: &MerkleProof) -> Result<(), ZkpError> {
+    check_len("tree height", proof.siblings.len(), MAX_DEPTH)?;
+    if proof.index >> proof.siblings.len() != 0 {
+        return Err(malformed(format!(
+            "leaf index {} outside a tree of height {}",
+            proof.index,
+            proof.siblings.len()
+        )));
+    }
+    Ok(())
+}
+
+impl Wire for Opening {
+    fn write(&self, writer: &mut Writer) {
+        writer.field(self.value);
+        write_path(writer, &self.proof);
+    }
+
+    fn read(reader: &mut Reader<'_>) -> Result<Self, ZkpError> {
+        let value = reader.field()?;
+        let proof = read_path(reader, hash_field_element(value))?;
+        Ok(Self { value, proof })
+    }
+
+    fn validate(&self) -> Result<(), ZkpError> {
+        check_path(&self.proof)
+    }
+}
+
+fn read_pair(reader: &mut Reader<'_>) -> Result<[Opening; 2], ZkpError> {
+    Ok([Opening::read(reader)?, Opening::read(reader)?])
+}
+
+impl Wire for FriProof {
+    fn write(&self, writer: &mut Writer) {
+        writer.len(self.layer_roots.len());
+        for root in &self.layer_roots {
+            writer.hash(root);
+        }
+        writer.field(self.final_value);
+        // Every query opens every layer
+        writer.len(self.queries.len());
+        for query in &self.queries {
+            for opening in query.layers.iter().flatten() {
+                opening.write(writer);
+            }
+        }
+    }
+
+    fn read(reader: &mut Reader<'_>) -> Result<Self, ZkpError> {
+        let layers = reader.bounded("FRI layers", MAX_DEPTH)?;
+        let layer_roots = reader.list(layers, 32, Reader::hash)?;
+        let final_value = reader.field()?;
+        let queries = reader.bounded("FRI queries", MAX_QUERIES)?;
+        let queries = reader.list(queries, layers * 2 * 10, |reader| {
+            Ok(FriQuery {
+                layers: reader.list(layers, 2 * 10, read_pair)?,
+            })
+        })?;
+        Ok(Self {
+            layer_roots,
+            final_value,
+            queries,
+        })
+    }
+
+    fn validate(&self) -> Result<(), ZkpError> {
+        check_len("FRI layers", self.layer_roots.len(), MAX_DEPTH)?;
+        check_len("FRI queries", self.queries.len(), MAX_QUERIES)?;
+        for query in &self.queries {
+            if query.layers.len() != self.layer_roots.len() {
+                return Err(malformed(format!(
+                    "FRI query opens {} of {} layers",
+                    query.layers.len(),
+                    self.layer_roots.len()
+                )));
+            }
+            for opening in query.layers.iter().flatten() {
+                opening.validate()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Wire for Proof {
+    fn write(&self, writer: &mut Writer) {
+        writer.hash(&self.witness_commitment);
+        writer.hash(&self.quotient_commitment);
+        writer.len(self.evaluations.len());
+        writer.fields(&self.evaluations);
+        writer.field(self.challenge);
+        writer.varint(u64::from(self.log_degree));
+        self.fri.write(writer);
+        // One pair per FRI query
+        for opening in self.witness_openings.iter().flatten() {
+            opening.write(writer);
+        }
+    }
+
+    fn read(reader: &mut Reader<'_>) -> Result<Self, ZkpError> {
+        let witness_commitment = reader.hash()?;
+        let quotient_commitment = reader.hash()?;
+        let evaluations = reader.bounded("evaluations", MAX_EVALUATIONS)?;
+        let evaluations = reader.list(evaluations, 8, Reader::field)?;
+        let challenge = reader.field()?;
+        let log_degree = reader.bounded("log degree", MAX_DEPTH)? as u32;
+        let fri = FriProof::read(reader)?;
+        let witness_openings = reader.list(fri.queries.len(), 2 * 10, read_pair)?;
+        Ok(Self {
+            witness_commitment,
+            quotient_commitment,
+            evaluations,
+            challenge,
+            log_degree,
+            fri,
+            witness_openings,
+        })
+    }
+
+    fn validate(&self) -> Result<(), ZkpError> {
+        check_len("evaluations", self.evaluations.len(), MAX_EVALUATIONS)?;
+        check_len("log degree", self.log_degree as usize, MAX_DEPTH)?;
+        self.fri.validate()?;
+        if self.witness_openings.len() != self.fri.queries.len() {
+            return Err(malformed(format!(
+                "{} witness openings for {} FRI queries",
+                self.witness_openings.len(),
+                self.fri.queries.len()
+            )));
+        }
+        for opening in self.witness_openings.iter().flatten() {
+            opening.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl Wire for StarkProof {
+    fn write(&self, writer: &mut Writer) {
+        writer.varint(u64::from(self.log_rows));
+        writer.hash(&self.trace_root);
+        self.fri.write(writer);
+        // Four rows per FRI query, all of the same width
+        let width = self
+            .trace_openings
+            .first()
+            .map_or(0, |rows| rows[0].values.len());
+        writer.len(width);
+        for row in self.trace_openings.iter().flatten() {
+            writer.fields(&row.values);
+            write_path(writer, &row.proof);
+        }
+    }
+
+    fn read(reader: &mut Reader<'_>) -> Result<Self, ZkpError> {
+        let log_rows = reader.bounded("log rows", MAX_DEPTH)? as u32;
+        let trace_root = reader.hash()?;
+        let fri = FriProof::read(reader)?;
+        let width = reader.bounded("trace columns", MAX_ROW_WIDTH)?;
+        let read_row = |reader: &mut Reader<'_>| {
+            let values = reader.fields(width)?;
+            let proof = read_path(reader, hash_row(&values))?;
+            Ok(RowOpening { values, proof })
+        };
+        let trace_openings = reader.list(fri.queries.len(), 4 * (width * 8 + 2), |reader| {
+            Ok([
+                read_row(reader)?,
+                read_row(reader)?,
+                read_row(reader)?,
+                read_row(reader)?,
+            ])
+        })?;
+        Ok(Self {
+            log_rows,
+            trace_root,
+            fri,
+            trace_openings,
+        })
+    }
+
+    fn validate(&self) -> Result<(), ZkpError> {
+        check_len("log rows", self.log_rows as usize, MAX_DEPTH)?;
+        self.fri.validate()?;
+        if self.trace_openings.len() != self.fri.queries.len() {
+            return Err(malformed(format!(
+                "{} trace openings for {} FRI queries",
+                self.trace_openings.len(),
+                self.fri.queries.len()
+            )));
+        }
+        let width = self
+            .trace_openings
+            .first()
+            .map_or(0, |rows| rows[0].values.len());
+        check_len("trace columns", width, MAX_ROW_WIDTH)?;
+        for row in self.trace_openings.iter().flatten() {
+            if row.values.len() != width {
+                return Err(malformed("trace rows differ in width"));
+            }
+            check_path(&row.proof)?;
+        }
+        Ok(())
+    }
+}
+
+impl Wire for StateTransitionProof {
+    fn write(&self, writer: &mut Writer) {
+        let statement = &self.statement;
+        writer.hash(&statement.block_hash);
+        writer.varint(statement.block_height);
+        writer.hash(&statement.previous_state_root);
+        writer.hash(&statement.state_root);
+        writer.hash(&statement.diff_digest);
+        writer.0.extend_from_slice(&statement.minted.to_le_bytes());
+        writer.len(statement.accounts);
+        self.proof.write(writer);
+    }
+
+    fn read(reader: &mut Reader<'_>) -> Result<Self, ZkpError> {
+        let statement = StateTransitionStatement {
+            block_hash: reader.hash()?,
+            block_height: reader.varint()?,
+            previous_state_root: reader.hash()?,
+            state_root: reader.hash()?,
+            diff_digest: reader.hash()?,
+            minted: u128::from_le_bytes(reader.array()?),
+            accounts: reader.bounded("accounts", max_accounts())?,
+        };
+        Ok(Self {
+            statement,
+            proof: StarkProof::read(reader)?,
+        })
+    }
+
+    fn validate(&self) -> Result<(), ZkpError> {
+        // Keeps num_rows() from overflowing
+        check_len("accounts", self.statement.accounts, max_accounts())?;
+        self.proof.validate()
+    }
+}
+
+/// Most accounts a state transition trace of 2^MAX_DEPTH rows can hold.
+fn max_accounts() -> usize {
+    (1usize << MAX_DEPTH) - 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fri::FriConfig;
+    use crate::polynomial::Polynomial;
+    use crate::proof::{Prover, Verifier};
+    use crate::state_transition::{self, AccountChange};
+
+    /// Magic, version and kind.
+    const HEADER_LEN: usize = MAGIC.len() + 2;
+
+    fn proof() -> Proof {
+        let witness: Vec<FieldElement> = (0..100).map(FieldElement::new).collect();
+        Prover::new(Polynomial::zero()).prove(&witness)
+    }
+
+    fn state_transition_proof() -> StateTransitionProof {
+        let statement = StateTransitionStatement {
+            block_hash: [1; 32],
+            block_height: 300,
+            previous_state_root: [2; 32],
+            state_root: [3; 32],
+            diff_digest: [4; 32],
+            minted: 50,
+            accounts: 1,
+        };
+        let change = AccountChange {
+            balance_before: 10,
+            balance_after: 60,
+            ..Default::default()
+        };
+        state_transition::prove(statement, &[change], &FriConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let proof = proof();
+        let bytes = proof.to_bytes();
+        assert_eq!(&bytes[..4], b"QCZK");
+        assert_eq!(bytes[4], FORMAT_VERSION);
+        let decoded = Proof::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(Verifier::new().verify(&decoded, &[]));
+
+        // Empty proofs encode too
+        let empty = Prover::new(Polynomial::zero()).prove(&[]);
+        assert_eq!(Proof::from_bytes(&empty.to_bytes()).unwrap(), empty);
+
+        let proof = state_transition_proof();
+        let decoded = StateTransitionProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(state_transition::verify(&decoded, &FriConfig::default()).is_ok());
+        assert_eq!(
+            StarkProof::from_bytes(&proof.proof.to_bytes()).unwrap(),
+            proof.proof
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_headers_and_lengths() {
+        let bytes = proof().to_bytes();
+
+        let mut other_version = bytes.clone();
+        other_version[4] = FORMAT_VERSION + 1;
+        assert!(matches!(
+            Proof::from_bytes(&other_version),
+            Err(ZkpError::UnsupportedVersion(v)) if v == FORMAT_VERSION + 1
+        ));
+        assert!(matches!(
+            StarkProof::from_bytes(&bytes),
+            Err(ZkpError::MalformedEncoding(_))
+        ));
+        assert!(Proof::from_bytes(&bytes[1..]).is_err());
+        assert!(Proof::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(matches!(
+            Proof::from_bytes(&trailing),
+            Err(ZkpError::MalformedEncoding(_))
+        ));
+        assert!(matches!(
+            Proof::from_bytes(&vec![0; MAX_PROOF_BYTES + 1]),
+            Err(ZkpError::ProofTooLarge(..))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_invalid_values() {
+        let bytes = proof().to_bytes();
+
+        // Challenge (after two hashes and two evaluations) set to p
+        let challenge = HEADER_LEN + 64 + 1 + 16;
+        let mut non_canonical = bytes.clone();
+        non_canonical[challenge..challenge + 8]
+            .copy_from_slice(&GoldilocksField::MODULUS.to_le_bytes());
+        assert!(matches!(
+            Proof::from_bytes(&non_canonical),
+            Err(ZkpError::InvalidFieldElement)
+        ));
+
+        // Evaluation count over the limit, and as a padded varint
+        let mut too_many = bytes.clone();
+        too_many[HEADER_LEN + 64] = MAX_EVALUATIONS as u8 + 1;
+        assert!(Proof::from_bytes(&too_many).is_err());
+        let mut padded = bytes[..HEADER_LEN + 64].to_vec();
+        padded.extend_from_slice(&[0x82, 0x00]);
+        padded.extend_from_slice(&bytes[HEADER_LEN + 65..]);
+        assert!(matches!(
+            Proof::from_bytes(&padded),
+            Err(ZkpError::MalformedEncoding(reason)) if reason.contains("varint")
+        ));
+    }
+
+    #[test]
+    fn test_validates_shape() {
+        let mut proof = proof();
+        proof.witness_openings.pop();
+        assert!(proof.validate().is_err());
+
+        let mut proof = state_transition_proof();
+        proof.proof.trace_openings[0][2]
+            .values
+            .push(FieldElement::new(1));
+        assert!(proof.validate().is_err());
+
+        let mut proof = state_transition_proof();
+        proof.statement.accounts = usize::MAX;
+        assert!(proof.validate().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_round_trip() {
+        let proof = state_transition_proof();
+        let json = proof.to_json();
+        assert_eq!(StateTransitionProof::from_json(&json).unwrap(), proof);
+
+        let non_canonical = json.replacen(
+            &proof.proof.fri.final_value.value().to_string(),
+            &GoldilocksField::MODULUS.to_string(),
+            1,
+        );
+        assert!(StateTransitionProof::from_json(&non_canonical).is_err());
+        assert!(Proof::from_json(&json).is_err());
+    }
+}
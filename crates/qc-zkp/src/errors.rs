@@ -24,4 +24,40 @@ pub enum ZkpError {
     /// Witness mismatch
     #[error("Witness does not satisfy constraints")]
     WitnessMismatch,
+
+    /// Evaluation domain larger than the field's 2^32 roots of unity
+    #[error("Evaluation domain of size 2^{0} exceeds the field's 2^32 roots of unity")]
+    DomainTooLarge(u32),
+
+    /// Value count does not match the evaluation domain
+    #[error("Evaluation domain has {0} points, got {1} values")]
+    DomainSizeMismatch(usize, usize),
+
+    /// Polynomial division by zero
+    #[error("Polynomial division by zero")]
+    DivisionByZero,
+
+    /// Constraint system is malformed
+    #[error("Invalid constraint system: {0}")]
+    InvalidConstraintSystem(String),
+
+    /// Execution trace has the wrong shape
+    #[error("Invalid trace: {0}")]
+    InvalidTrace(String),
+
+    /// Trace does not satisfy a constraint
+    #[error("Constraint {0} fails at row {1}")]
+    ConstraintViolation(String, usize),
+
+    /// Encoded proof is malformed
+    #[error("Malformed proof encoding: {0}")]
+    MalformedEncoding(String),
+
+    /// Encoded proof uses a format version this build cannot read
+    #[error("Unsupported proof format version {0}")]
+    UnsupportedVersion(u8),
+
+    /// Encoded proof exceeds the size limit
+    #[error("Encoded proof of {0} bytes exceeds the {1} byte limit")]
+    ProofTooLarge(usize, usize),
 }
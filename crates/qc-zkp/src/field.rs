@@ -8,6 +8,7 @@
 //! - Efficient multiplication via Montgomery reduction
 //! - FFT-friendly (has 2^32 roots of unity)
 
+use crate::errors::ZkpError;
 use std::ops::{Add, Mul, Neg, Sub};
 
 /// Goldilocks prime: p = 2^64 - 2^32 + 1
@@ -40,10 +41,26 @@ impl GoldilocksField {
     pub fn generator() -> FieldElement {
         FieldElement(7)
     }
+
+    /// Largest n such that 2^n divides p - 1.
+    pub const TWO_ADICITY: u32 = 32;
+
+    /// Primitive 2^log_n-th root of unity (None if log_n > TWO_ADICITY).
+    pub fn root_of_unity(log_n: u32) -> Option<FieldElement> {
+        if log_n > Self::TWO_ADICITY {
+            return None;
+        }
+        Some(Self::generator().pow((GOLDILOCKS_PRIME - 1) >> log_n))
+    }
 }
 
 /// Element in the Goldilocks field.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u64", into = "u64")
+)]
 pub struct FieldElement(u64);
 
 impl FieldElement {
@@ -94,6 +111,25 @@ impl FieldElement {
     }
 }
 
+impl TryFrom<u64> for FieldElement {
+    type Error = ZkpError;
+
+    /// Canonical value (below p), without reduction.
+    fn try_from(value: u64) -> Result<Self, ZkpError> {
+        if value < GOLDILOCKS_PRIME {
+            Ok(Self(value))
+        } else {
+            Err(ZkpError::InvalidFieldElement)
+        }
+    }
+}
+
+impl From<FieldElement> for u64 {
+    fn from(element: FieldElement) -> u64 {
+        element.0
+    }
+}
+
 impl Add for FieldElement {
     type Output = Self;
 
@@ -190,6 +226,20 @@ mod tests {
         assert_eq!(a.pow(10).value(), 1024);
     }
 
+    #[test]
+    fn test_root_of_unity() {
+        // Known 2^32-th root of unity (7^((p - 1) / 2^32))
+        let root = GoldilocksField::root_of_unity(32).unwrap();
+        assert_eq!(root.value(), 1_753_635_133_440_165_772);
+
+        for log_n in [1, 5, 32] {
+            let w = GoldilocksField::root_of_unity(log_n).unwrap();
+            assert_eq!(w.pow(1 << log_n).value(), 1);
+            assert_ne!(w.pow(1 << (log_n - 1)).value(), 1);
+        }
+        assert!(GoldilocksField::root_of_unity(33).is_none());
+    }
+
     #[test]
     fn test_modular_reduction() {
         let a = FieldElement::new(GOLDILOCKS_PRIME + 5);
@@ -0,0 +1,414 @@
+//! # FRI Low-Degree Test
+//!
+//! FRI (Fast Reed-Solomon IOP of Proximity) shows that committed evaluations
+//! come from a polynomial of degree below 2^k.
+//!
+//! - Commit phase: evaluate on a coset of size 2^(k + log_blowup), commit,
+//!   draw β from the transcript and fold f(x) = f_e(x²) + x·f_o(x²) into
+//!   f_e + β·f_o over the squared coset. After k folds the result is a
+//!   constant, sent in the clear.
+//! - Query phase: at positions drawn from the transcript, every layer is
+//!   opened at x and -x. The verifier recomputes each fold and compares it
+//!   with the next layer, and the last fold with the constant.
+//!
+//! A function far from low degree passes one query with probability about
+//! 2^-log_blowup, so the default configuration gives roughly
+//! `num_queries * log_blowup` = 84 bits of (conjectured) security.
+
+use crate::commitment::{HashOutput, MerkleCommitment, Opening};
+use crate::errors::ZkpError;
+use crate::field::{FieldElement, GoldilocksField, GOLDILOCKS_PRIME};
+use crate::ntt::Radix2Domain;
+use crate::parallel;
+use crate::polynomial::Polynomial;
+use crate::transcript::Transcript;
+
+/// FRI parameters; prover and verifier must agree on them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FriConfig {
+    /// Log2 of the evaluation domain size over the degree bound
+    pub log_blowup: u32,
+    /// Number of query positions
+    pub num_queries: usize,
+}
+
+impl Default for FriConfig {
+    fn default() -> Self {
+        Self {
+            log_blowup: 3,
+            num_queries: 28,
+        }
+    }
+}
+
+impl FriConfig {
+    /// Set the blowup factor (2^log_blowup).
+    pub fn with_log_blowup(mut self, log_blowup: u32) -> Self {
+        self.log_blowup = log_blowup;
+        self
+    }
+
+    /// Set the number of queries.
+    pub fn with_num_queries(mut self, num_queries: usize) -> Self {
+        self.num_queries = num_queries;
+        self
+    }
+}
+
+/// Openings of one query in every committed layer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct FriQuery {
+    /// Per layer: the values at x and -x (positions p and p + n/2)
+    pub layers: Vec<[Opening; 2]>,
+}
+
+/// FRI proof.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct FriProof {
+    /// Merkle root of each layer before folding
+    pub layer_roots: Vec<HashOutput>,
+    /// Value of the fully folded (constant) polynomial
+    pub final_value: FieldElement,
+    /// One entry per query
+    pub queries: Vec<FriQuery>,
+}
+
+/// Shift of the evaluation coset (keeps it disjoint from the trace domain).
+pub fn coset_shift() -> FieldElement {
+    GoldilocksField::generator()
+}
+
+/// Log2 of the evaluation coset size for degree below 2^log_degree.
+///
+/// A bound of 2^0 is treated as 2^1, so there is always a layer to query.
+pub fn lde_log_size(config: &FriConfig, log_degree: u32) -> Result<u32, ZkpError> {
+    let log_size = log_degree.max(1) + config.log_blowup;
+    if log_size > GoldilocksField::TWO_ADICITY {
+        return Err(ZkpError::DomainTooLarge(log_size));
+    }
+    Ok(log_size)
+}
+
+/// Point at `index` of the evaluation coset of size 2^log_size.
+pub fn lde_point(log_size: u32, index: usize) -> FieldElement {
+    let root = GoldilocksField::root_of_unity(log_size).expect("coset size checked");
+    coset_shift() * root.pow(index as u64)
+}
+
+/// Evaluations of `poly` (degree below 2^log_degree) on the evaluation coset.
+pub fn low_degree_extension(
+    config: &FriConfig,
+    poly: &Polynomial,
+    log_degree: u32,
+) -> Result<Vec<FieldElement>, ZkpError> {
+    let log_size = lde_log_size(config, log_degree)?;
+    let max_len = 1usize << log_degree.max(1);
+    if poly.coefficients().len() > max_len {
+        return Err(ZkpError::PolynomialDegreeTooHigh(
+            poly.coefficients().len() - 1,
+            max_len - 1,
+        ));
+    }
+
+    let domain = Radix2Domain::cached(log_size)?;
+    poly.evaluate_over_coset(&domain, coset_shift())
+}
+
+/// Open a committed layer at x and -x, given the position p of x (< n/2).
+pub fn open_pair(
+    commitment: &MerkleCommitment,
+    values: &[FieldElement],
+    position: usize,
+) -> [Opening; 2] {
+    let half = values.len() / 2;
+    let low = position % half;
+    [
+        Opening::new(commitment, values, low).expect("position within layer"),
+        Opening::new(commitment, values, low + half).expect("position within layer"),
+    ]
+}
+
+/// Prove that `poly` has degree below 2^log_degree.
+///
+/// Returns the proof and the query positions in the first layer (each
+/// below half the coset size), so callers can open their own commitments
+/// at the same points.
+pub fn prove(
+    config: &FriConfig,
+    transcript: &mut Transcript,
+    poly: &Polynomial,
+    log_degree: u32,
+) -> Result<(FriProof, Vec<usize>), ZkpError> {
+    let values = low_degree_extension(config, poly, log_degree)?;
+    prove_evaluations(config, transcript, values, log_degree)
+}
+
+/// Prove that evaluations on the evaluation coset (see `lde_point`) come
+/// from a polynomial of degree below 2^log_degree.
+///
+/// Used when the prover has evaluations but not coefficients, e.g. a
+/// composition of committed columns.
+pub fn prove_evaluations(
+    config: &FriConfig,
+    transcript: &mut Transcript,
+    mut values: Vec<FieldElement>,
+    log_degree: u32,
+) -> Result<(FriProof, Vec<usize>), ZkpError> {
+    let log_size = lde_log_size(config, log_degree)?;
+    if values.len() != 1 << log_size {
+        return Err(ZkpError::DomainSizeMismatch(1 << log_size, values.len()));
+    }
+    let folds = log_size - config.log_blowup;
+    append_parameters(transcript, config, folds);
+
+    let mut layers = Vec::with_capacity(folds as usize);
+    let mut shift = coset_shift();
+    for layer in 0..folds {
+        let commitment = MerkleCommitment::commit(&values);
+        transcript.append_root(b"fri-layer", commitment.root());
+        let beta = transcript.challenge_field(b"fri-beta");
+
+        let root = GoldilocksField::root_of_unity(log_size - layer).expect("coset size checked");
+        let folded = fold_layer(&values, beta, shift, root);
+        layers.push((commitment, values));
+        values = folded;
+        shift = shift * shift;
+    }
+
+    let final_value = values[0];
+    transcript.append_field(b"fri-final", final_value);
+    let positions = query_positions(transcript, config, log_size);
+
+    let queries = positions
+        .iter()
+        .map(|&position| FriQuery {
+            layers: layers
+                .iter()
+                .map(|(commitment, values)| open_pair(commitment, values, position))
+                .collect(),
+        })
+        .collect();
+
+    let proof = FriProof {
+        layer_roots: layers
+            .iter()
+            .map(|(commitment, _)| *commitment.root())
+            .collect(),
+        final_value,
+        queries,
+    };
+    Ok((proof, positions))
+}
+
+/// Verify that the committed layers fold to a polynomial of degree below
+/// 2^log_degree.
+///
+/// Returns the query positions in the first layer.
+pub fn verify(
+    config: &FriConfig,
+    transcript: &mut Transcript,
+    proof: &FriProof,
+    log_degree: u32,
+) -> Result<Vec<usize>, ZkpError> {
+    let log_size = lde_log_size(config, log_degree)?;
+    let folds = log_size - config.log_blowup;
+    if proof.layer_roots.len() != folds as usize || proof.queries.len() != config.num_queries {
+        return Err(ZkpError::VerificationFailed);
+    }
+    append_parameters(transcript, config, folds);
+
+    let mut betas = Vec::with_capacity(proof.layer_roots.len());
+    for root in &proof.layer_roots {
+        transcript.append_root(b"fri-layer", root);
+        betas.push(transcript.challenge_field(b"fri-beta"));
+    }
+    transcript.append_field(b"fri-final", proof.final_value);
+    let positions = query_positions(transcript, config, log_size);
+
+    for (query, &position) in proof.queries.iter().zip(&positions) {
+        if !check_query(proof, &betas, query, position, log_size) {
+            return Err(ZkpError::VerificationFailed);
+        }
+    }
+    Ok(positions)
+}
+
+/// Check one query's openings and folds through every layer.
+fn check_query(
+    proof: &FriProof,
+    betas: &[FieldElement],
+    query: &FriQuery,
+    position: usize,
+    log_size: u32,
+) -> bool {
+    if query.layers.len() != betas.len() {
+        return false;
+    }
+
+    let mut shift = coset_shift();
+    // Index and value the previous layer folded into this one
+    let mut folded: Option<(usize, FieldElement)> = None;
+    for (layer, (pair, (root, beta))) in query
+        .layers
+        .iter()
+        .zip(proof.layer_roots.iter().zip(betas))
+        .enumerate()
+    {
+        let log_layer = log_size - layer as u32;
+        let half = 1usize << (log_layer - 1);
+        let low = position % half;
+        let height = log_layer as usize;
+        if !pair[0].verify(root, low, height) || !pair[1].verify(root, low + half, height) {
+            return false;
+        }
+        if folded.is_some_and(|(index, value)| pair[usize::from(index >= half)].value != value) {
+            return false;
+        }
+
+        let root_of_unity = GoldilocksField::root_of_unity(log_layer).expect("coset size checked");
+        let Some(x_inv) = (shift * root_of_unity.pow(low as u64)).inverse() else {
+            return false;
+        };
+        folded = Some((low, fold(pair[0].value, pair[1].value, *beta, x_inv)));
+        shift = shift * shift;
+    }
+
+    folded.is_some_and(|(_, value)| value == proof.final_value)
+}
+
+fn append_parameters(transcript: &mut Transcript, config: &FriConfig, folds: u32) {
+    transcript.append_u64(b"fri-log-blowup", u64::from(config.log_blowup));
+    transcript.append_u64(b"fri-queries", config.num_queries as u64);
+    transcript.append_u64(b"fri-folds", u64::from(folds));
+}
+
+/// Query positions in the first half of the first layer.
+fn query_positions(transcript: &mut Transcript, config: &FriConfig, log_size: u32) -> Vec<usize> {
+    (0..config.num_queries)
+        .map(|_| transcript.challenge_index(b"fri-query", 1 << (log_size - 1)))
+        .collect()
+}
+
+/// Fold evaluations over the coset shift·⟨root⟩ into the squared coset.
+fn fold_layer(
+    values: &[FieldElement],
+    beta: FieldElement,
+    shift: FieldElement,
+    root: FieldElement,
+) -> Vec<FieldElement> {
+    let half = values.len() / 2;
+    let root_inv = root.inverse().expect("roots of unity are non-zero");
+    let shift_inv = shift.inverse().expect("coset shift is non-zero");
+
+    let mut folded = vec![GoldilocksField::zero(); half];
+    parallel::for_each_chunk_mut(&mut folded, parallel::MIN_PARALLEL_LEN, |offset, chunk| {
+        let mut x_inv = shift_inv * root_inv.pow(offset as u64);
+        for (i, out) in chunk.iter_mut().enumerate() {
+            // values[i + half] is the evaluation at -x
+            *out = fold(values[offset + i], values[offset + i + half], beta, x_inv);
+            x_inv = x_inv * root_inv;
+        }
+    });
+    folded
+}
+
+/// f'(x²) = (f(x) + f(-x)) / 2 + β · (f(x) - f(-x)) / 2x
+fn fold(
+    at_x: FieldElement,
+    at_neg_x: FieldElement,
+    beta: FieldElement,
+    x_inv: FieldElement,
+) -> FieldElement {
+    let two_inv = FieldElement::new(GOLDILOCKS_PRIME / 2 + 1);
+    ((at_x + at_neg_x) + beta * (at_x - at_neg_x) * x_inv) * two_inv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(len: usize) -> Polynomial {
+        Polynomial::new(
+            (1..=len as u64)
+                .map(|i| FieldElement::new(i.wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+                .collect(),
+        )
+    }
+
+    fn prove_and_verify(config: &FriConfig, poly: &Polynomial, log_degree: u32) -> bool {
+        let (proof, positions) =
+            prove(config, &mut Transcript::new(b"test"), poly, log_degree).unwrap();
+        verify(config, &mut Transcript::new(b"test"), &proof, log_degree)
+            .is_ok_and(|verified| verified == positions)
+    }
+
+    #[test]
+    fn test_low_degree_polynomials_verify() {
+        let config = FriConfig::default();
+        for (len, log_degree) in [(1, 0), (2, 1), (16, 4), (100, 7), (128, 7)] {
+            assert!(prove_and_verify(&config, &sample(len), log_degree));
+        }
+        assert!(prove_and_verify(
+            &config.with_log_blowup(1).with_num_queries(60),
+            &sample(33),
+            6
+        ));
+    }
+
+    #[test]
+    fn test_degree_above_bound_is_rejected() {
+        let config = FriConfig::default();
+        assert!(matches!(
+            prove(&config, &mut Transcript::new(b"test"), &sample(17), 4),
+            Err(ZkpError::PolynomialDegreeTooHigh(16, 15))
+        ));
+
+        // Evaluations of a degree-63 polynomial claimed to be below 2^4
+        let domain = Radix2Domain::new(4 + config.log_blowup).unwrap();
+        let values = sample(64)
+            .evaluate_over_coset(&domain, coset_shift())
+            .unwrap();
+        let (proof, _) =
+            prove_evaluations(&config, &mut Transcript::new(b"test"), values.clone(), 4).unwrap();
+        assert!(verify(&config, &mut Transcript::new(b"test"), &proof, 4).is_err());
+        assert!(matches!(
+            prove_evaluations(&config, &mut Transcript::new(b"test"), values, 5),
+            Err(ZkpError::DomainSizeMismatch(256, 128))
+        ));
+    }
+
+    #[test]
+    fn test_tampered_proof_is_rejected() {
+        let config = FriConfig::default();
+        let (proof, _) = prove(&config, &mut Transcript::new(b"test"), &sample(16), 4).unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.final_value = tampered.final_value + FieldElement::new(1);
+        assert!(verify(&config, &mut Transcript::new(b"test"), &tampered, 4).is_err());
+
+        let mut tampered = proof.clone();
+        tampered.queries.pop();
+        assert!(verify(&config, &mut Transcript::new(b"test"), &tampered, 4).is_err());
+
+        // Different transcript, different queries
+        assert!(verify(&config, &mut Transcript::new(b"other"), &proof, 4).is_err());
+        // Verifier demanding more queries
+        assert!(verify(
+            &config.with_num_queries(40),
+            &mut Transcript::new(b"test"),
+            &proof,
+            4
+        )
+        .is_err());
+    }
+}
@@ -6,23 +6,50 @@
 //!
 //! - `field` - Goldilocks field arithmetic (p = 2^64 - 2^32 + 1)
 //! - `polynomial` - Polynomial operations
+//! - `ntt` - Radix-2 number theoretic transform
+//! - `air` - Constraint systems, traces and witness generation
 //! - `commitment` - Merkle tree commitments
+//! - `transcript` - Fiat-Shamir transcript
+//! - `fri` - FRI low-degree test
+//! - `stark` - Proofs that a trace satisfies a constraint system
+//! - `state_transition` - Validity proofs for block state diffs
+//! - `encoding` - Versioned binary wire format for proofs
 //! - `prover` - Proof generation
 //! - `verifier` - Proof verification
+//!
+//! ## Features
+//!
+//! - `parallel` (default) - Spread proving work over rayon threads
+//! - `compute` - Hash large Merkle trees on an installed qc-compute engine
+//! - `serde` - Serde derives on proof types and JSON encoding for debugging
 
 #![warn(missing_docs)]
 
+pub mod air;
 pub mod commitment;
+pub mod encoding;
 pub mod errors;
 pub mod field;
+pub mod fri;
+pub mod ntt;
+mod parallel;
 pub mod polynomial;
 pub mod proof;
+pub mod stark;
+pub mod state_transition;
+pub mod transcript;
 
+pub use air::{ConstraintSystem, Trace, WitnessGenerator};
 pub use commitment::MerkleCommitment;
 pub use errors::ZkpError;
 pub use field::{FieldElement, GoldilocksField};
+pub use fri::{FriConfig, FriProof};
+pub use ntt::Radix2Domain;
 pub use polynomial::Polynomial;
 pub use proof::{Proof, Prover, Verifier};
+pub use stark::StarkProof;
+pub use state_transition::{StateTransitionProof, StateTransitionStatement};
+pub use transcript::Transcript;
 
 /// Crate version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,271 @@
+//! # Number Theoretic Transform
+//!
+//! Radix-2 NTT over the Goldilocks field.
+//!
+//! A `Radix2Domain` of size n = 2^k is the set {ω^0, ..., ω^(n-1)} for a
+//! primitive n-th root of unity ω. `ntt` turns n coefficients into the
+//! evaluations at those points and `intt` turns them back, both in
+//! O(n log n). Twiddle factors are computed once per domain.
+
+use crate::errors::ZkpError;
+use crate::field::{FieldElement, GoldilocksField};
+use crate::parallel;
+use std::sync::{Arc, OnceLock};
+
+/// Domains built by `Radix2Domain::cached`, indexed by log size.
+static DOMAINS: [OnceLock<Arc<Radix2Domain>>; GoldilocksField::TWO_ADICITY as usize + 1] =
+    [const { OnceLock::new() }; GoldilocksField::TWO_ADICITY as usize + 1];
+
+/// Multiplicative subgroup of size 2^k with precomputed twiddle factors.
+#[derive(Clone, Debug)]
+pub struct Radix2Domain {
+    log_size: u32,
+    /// ω^i for i < n/2
+    twiddles: Vec<FieldElement>,
+    /// ω^-i for i < n/2
+    inverse_twiddles: Vec<FieldElement>,
+    /// 1/n, applied by the inverse transform
+    size_inverse: FieldElement,
+}
+
+impl Radix2Domain {
+    /// Create the domain of size 2^log_size.
+    pub fn new(log_size: u32) -> Result<Self, ZkpError> {
+        let root =
+            GoldilocksField::root_of_unity(log_size).ok_or(ZkpError::DomainTooLarge(log_size))?;
+        let root_inverse = root.inverse().ok_or(ZkpError::InvalidFieldElement)?;
+        let size = FieldElement::new(1 << log_size);
+        let half = (1usize << log_size) / 2;
+
+        Ok(Self {
+            log_size,
+            twiddles: powers(root, half),
+            inverse_twiddles: powers(root_inverse, half),
+            size_inverse: size.inverse().ok_or(ZkpError::InvalidFieldElement)?,
+        })
+    }
+
+    /// Shared domain of size 2^log_size, built on first use.
+    pub fn cached(log_size: u32) -> Result<Arc<Self>, ZkpError> {
+        let slot = DOMAINS
+            .get(log_size as usize)
+            .ok_or(ZkpError::DomainTooLarge(log_size))?;
+        if let Some(domain) = slot.get() {
+            return Ok(Arc::clone(domain));
+        }
+
+        let domain = Arc::new(Self::new(log_size)?);
+        Ok(Arc::clone(slot.get_or_init(|| domain)))
+    }
+
+    /// Smallest shared domain holding at least `len` points.
+    pub fn for_len(len: usize) -> Result<Arc<Self>, ZkpError> {
+        Self::cached(len.max(1).next_power_of_two().trailing_zeros())
+    }
+
+    /// Number of points.
+    pub fn size(&self) -> usize {
+        1 << self.log_size
+    }
+
+    /// Log2 of the number of points.
+    pub fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    /// Coefficients to evaluations at ω^0, ..., ω^(n-1), in place.
+    pub fn ntt(&self, values: &mut [FieldElement]) -> Result<(), ZkpError> {
+        self.check_len(values)?;
+        transform(values, &self.twiddles);
+        Ok(())
+    }
+
+    /// Evaluations at ω^0, ..., ω^(n-1) to coefficients, in place.
+    pub fn intt(&self, values: &mut [FieldElement]) -> Result<(), ZkpError> {
+        self.check_len(values)?;
+        transform(values, &self.inverse_twiddles);
+        let size_inverse = self.size_inverse;
+        parallel::for_each_chunk_mut(values, parallel::MIN_PARALLEL_LEN, |_, chunk| {
+            for value in chunk {
+                *value = *value * size_inverse;
+            }
+        });
+        Ok(())
+    }
+
+    fn check_len(&self, values: &[FieldElement]) -> Result<(), ZkpError> {
+        if values.len() == self.size() {
+            Ok(())
+        } else {
+            Err(ZkpError::DomainSizeMismatch(self.size(), values.len()))
+        }
+    }
+}
+
+/// base^0, ..., base^(count-1)
+fn powers(base: FieldElement, count: usize) -> Vec<FieldElement> {
+    let mut powers = Vec::with_capacity(count);
+    let mut current = GoldilocksField::one();
+    for _ in 0..count {
+        powers.push(current);
+        current = current * base;
+    }
+    powers
+}
+
+/// Iterative Cooley-Tukey: bit-reversal permutation, then log n butterfly layers.
+///
+/// Early layers have many small blocks, which are split across threads;
+/// late layers have a few large blocks, whose butterflies are split instead.
+fn transform(values: &mut [FieldElement], twiddles: &[FieldElement]) {
+    let n = values.len();
+    bit_reverse(values);
+
+    let mut half = 1;
+    while half < n {
+        // Layer with blocks of 2 * half uses every (n / (2 * half))-th twiddle
+        let stride = n / (2 * half);
+        if half >= parallel::MIN_PARALLEL_LEN {
+            for block in values.chunks_exact_mut(2 * half) {
+                split_block(block, twiddles, stride);
+            }
+        } else {
+            let group = (2 * half).max(parallel::MIN_PARALLEL_LEN);
+            parallel::for_each_chunk_mut(values, group, |_, blocks| {
+                small_blocks(blocks, half, twiddles, stride)
+            });
+        }
+        half *= 2;
+    }
+}
+
+/// Butterflies of consecutive blocks of 2 * half, on the calling thread.
+fn small_blocks(
+    blocks: &mut [FieldElement],
+    half: usize,
+    twiddles: &[FieldElement],
+    stride: usize,
+) {
+    for block in blocks.chunks_exact_mut(2 * half) {
+        let (low, high) = block.split_at_mut(half);
+        butterflies(low, high, twiddles, stride, 0);
+    }
+}
+
+/// Butterflies of one large block, in parallel chunks.
+fn split_block(block: &mut [FieldElement], twiddles: &[FieldElement], stride: usize) {
+    let (low, high) = block.split_at_mut(block.len() / 2);
+    parallel::for_each_chunk_pair_mut(
+        low,
+        high,
+        parallel::MIN_PARALLEL_LEN,
+        |offset, low, high| butterflies(low, high, twiddles, stride, offset),
+    );
+}
+
+/// Butterflies between `low[j]` and `high[j]`, which sit `offset + j` into their block.
+fn butterflies(
+    low: &mut [FieldElement],
+    high: &mut [FieldElement],
+    twiddles: &[FieldElement],
+    stride: usize,
+    offset: usize,
+) {
+    for (j, (u, v)) in low.iter_mut().zip(high.iter_mut()).enumerate() {
+        let t = *v * twiddles[(offset + j) * stride];
+        *v = *u - t;
+        *u = *u + t;
+    }
+}
+
+fn bit_reverse(values: &mut [FieldElement]) {
+    let n = values.len();
+    if n <= 2 {
+        return;
+    }
+
+    let shift = usize::BITS - n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> shift;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomial::Polynomial;
+
+    fn sample(len: usize) -> Vec<FieldElement> {
+        (0..len as u64)
+            .map(|i| FieldElement::new(i.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ 0xDEAD_BEEF))
+            .collect()
+    }
+
+    #[test]
+    fn test_ntt_matches_direct_evaluation() {
+        let domain = Radix2Domain::new(4).unwrap();
+        let coeffs = sample(16);
+        let poly = Polynomial::new(coeffs.clone());
+        let root = GoldilocksField::root_of_unity(4).unwrap();
+
+        let mut values = coeffs;
+        domain.ntt(&mut values).unwrap();
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(*value, poly.evaluate(root.pow(i as u64)));
+        }
+    }
+
+    #[test]
+    fn test_ntt_round_trip() {
+        for log_size in [0, 1, 2, 7] {
+            let domain = Radix2Domain::new(log_size).unwrap();
+            let original = sample(domain.size());
+            let mut values = original.clone();
+            domain.ntt(&mut values).unwrap();
+            domain.intt(&mut values).unwrap();
+            assert_eq!(values, original);
+        }
+    }
+
+    #[test]
+    fn test_large_ntt_round_trip() {
+        // Large enough for the split-block layers
+        let domain = Radix2Domain::new(14).unwrap();
+        let coeffs = sample(domain.size());
+        let mut values = coeffs.clone();
+        domain.ntt(&mut values).unwrap();
+
+        let poly = Polynomial::new(coeffs.clone());
+        let root = GoldilocksField::root_of_unity(14).unwrap();
+        for i in [0, 1, 5000, domain.size() - 1] {
+            assert_eq!(values[i], poly.evaluate(root.pow(i as u64)));
+        }
+        domain.intt(&mut values).unwrap();
+        assert_eq!(values, coeffs);
+    }
+
+    #[test]
+    fn test_ntt_rejects_wrong_length() {
+        let domain = Radix2Domain::new(3).unwrap();
+        let mut values = sample(7);
+        assert!(matches!(
+            domain.ntt(&mut values),
+            Err(ZkpError::DomainSizeMismatch(8, 7))
+        ));
+        assert!(matches!(
+            Radix2Domain::new(33),
+            Err(ZkpError::DomainTooLarge(33))
+        ));
+    }
+
+    #[test]
+    fn test_cached_domains_are_shared() {
+        let a = Radix2Domain::for_len(100).unwrap();
+        let b = Radix2Domain::cached(7).unwrap();
+        assert_eq!(a.size(), 128);
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+}
@@ -0,0 +1,74 @@
+//! Data-parallel helpers for the prover.
+//!
+//! With the `parallel` feature these split work across rayon's thread pool;
+//! without it (or for inputs below `MIN_PARALLEL_LEN`) they run in order on
+//! the calling thread. Results are identical either way.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Inputs shorter than this aren't worth splitting across threads.
+pub(crate) const MIN_PARALLEL_LEN: usize = 1 << 10;
+
+/// `f(i)` for every i in 0..len.
+pub(crate) fn map_range<R, F>(len: usize, f: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn(usize) -> R + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if len >= MIN_PARALLEL_LEN {
+        return (0..len).into_par_iter().map(f).collect();
+    }
+    (0..len).map(f).collect()
+}
+
+/// `f(item)` for every item.
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    map_range(items.len(), |i| f(&items[i]))
+}
+
+/// `f(offset, chunk)` for consecutive chunks of `chunk_len` items.
+pub(crate) fn for_each_chunk_mut<T, F>(items: &mut [T], chunk_len: usize, f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut [T]) + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if items.len() >= MIN_PARALLEL_LEN {
+        items
+            .par_chunks_mut(chunk_len)
+            .enumerate()
+            .for_each(|(index, chunk)| f(index * chunk_len, chunk));
+        return;
+    }
+    items
+        .chunks_mut(chunk_len)
+        .enumerate()
+        .for_each(|(index, chunk)| f(index * chunk_len, chunk));
+}
+
+/// `f(offset, a_chunk, b_chunk)` over matching chunks of two equal-length slices.
+pub(crate) fn for_each_chunk_pair_mut<T, F>(a: &mut [T], b: &mut [T], chunk_len: usize, f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut [T], &mut [T]) + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    if a.len() >= MIN_PARALLEL_LEN {
+        a.par_chunks_mut(chunk_len)
+            .zip(b.par_chunks_mut(chunk_len))
+            .enumerate()
+            .for_each(|(index, (a, b))| f(index * chunk_len, a, b));
+        return;
+    }
+    a.chunks_mut(chunk_len)
+        .zip(b.chunks_mut(chunk_len))
+        .enumerate()
+        .for_each(|(index, (a, b))| f(index * chunk_len, a, b));
+}
@@ -1,8 +1,19 @@
 //! # Polynomial Operations
 //!
 //! Polynomial arithmetic over the Goldilocks field.
+//!
+//! Multiplication switches from schoolbook O(n²) to NTT-based O(n log n)
+//! once both operands have `NTT_MUL_THRESHOLD` coefficients. Division uses
+//! Newton iteration on the reversed divisor, so it costs a few
+//! multiplications.
 
+use crate::errors::ZkpError;
 use crate::field::FieldElement;
+use crate::ntt::Radix2Domain;
+use crate::parallel;
+
+/// Shorter operand length from which `mul` uses the NTT.
+pub const NTT_MUL_THRESHOLD: usize = 32;
 
 /// Polynomial represented as coefficients.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -84,12 +95,40 @@ impl Polynomial {
         Self::new(result)
     }
 
+    /// Subtract two polynomials.
+    pub fn sub(&self, other: &Self) -> Self {
+        let max_len = self.coeffs.len().max(other.coeffs.len());
+        let mut result = Vec::with_capacity(max_len);
+
+        for i in 0..max_len {
+            let a = self.coeffs.get(i).copied().unwrap_or(FieldElement::new(0));
+            let b = other.coeffs.get(i).copied().unwrap_or(FieldElement::new(0));
+            result.push(a - b);
+        }
+
+        Self::new(result)
+    }
+
     /// Multiply two polynomials.
     pub fn mul(&self, other: &Self) -> Self {
         if self.coeffs.is_empty() || other.coeffs.is_empty() {
             return Self::zero();
         }
 
+        if self.coeffs.len().min(other.coeffs.len()) < NTT_MUL_THRESHOLD {
+            return self.mul_naive(other);
+        }
+
+        let result_len = self.coeffs.len() + other.coeffs.len() - 1;
+        match Radix2Domain::for_len(result_len) {
+            Ok(domain) => self.mul_ntt(other, &domain, result_len),
+            // Products beyond 2^32 coefficients have no NTT domain
+            Err(_) => self.mul_naive(other),
+        }
+    }
+
+    /// Schoolbook multiplication.
+    fn mul_naive(&self, other: &Self) -> Self {
         let result_len = self.coeffs.len() + other.coeffs.len() - 1;
         let mut result = vec![FieldElement::new(0); result_len];
 
@@ -101,6 +140,118 @@ impl Polynomial {
 
         Self::new(result)
     }
+
+    /// Multiply by evaluating both operands on `domain`, multiplying
+    /// pointwise and interpolating back.
+    fn mul_ntt(&self, other: &Self, domain: &Radix2Domain, result_len: usize) -> Self {
+        let mut a = self.coeffs.clone();
+        let mut b = other.coeffs.clone();
+        a.resize(domain.size(), FieldElement::new(0));
+        b.resize(domain.size(), FieldElement::new(0));
+
+        domain.ntt(&mut a).expect("operand padded to domain size");
+        domain.ntt(&mut b).expect("operand padded to domain size");
+        for (x, y) in a.iter_mut().zip(&b) {
+            *x = *x * *y;
+        }
+        domain.intt(&mut a).expect("product has domain size");
+
+        a.truncate(result_len);
+        Self::new(a)
+    }
+
+    /// Divide by `divisor`, returning (quotient, remainder).
+    ///
+    /// The remainder's degree is below the divisor's.
+    pub fn div_rem(&self, divisor: &Self) -> Result<(Self, Self), ZkpError> {
+        if divisor.coeffs.is_empty() {
+            return Err(ZkpError::DivisionByZero);
+        }
+        if self.coeffs.len() < divisor.coeffs.len() {
+            return Ok((Self::zero(), self.clone()));
+        }
+
+        // With rev(p) = x^deg(p) * p(1/x): rev(q) = rev(a) / rev(d) mod x^k,
+        // and rev(d) is invertible as a power series (its constant term is
+        // the divisor's leading coefficient).
+        let quotient_len = self.coeffs.len() - divisor.coeffs.len() + 1;
+        let reversed = Self::new(self.coeffs.iter().rev().copied().collect());
+        let divisor_inverse =
+            Self::new(divisor.coeffs.iter().rev().copied().collect()).inverse_series(quotient_len);
+
+        let mut quotient = reversed
+            .mul(&divisor_inverse)
+            .truncated(quotient_len)
+            .coeffs;
+        quotient.resize(quotient_len, FieldElement::new(0));
+        quotient.reverse();
+        let quotient = Self::new(quotient);
+
+        let remainder = self.sub(&quotient.mul(divisor));
+        Ok((quotient, remainder))
+    }
+
+    /// Inverse as a power series mod x^len (constant term must be non-zero).
+    fn inverse_series(&self, len: usize) -> Self {
+        let constant_inverse = self.coeffs[0]
+            .inverse()
+            .expect("constant term is non-zero (leading coefficient of the divisor)");
+        let two = Self::constant(FieldElement::new(2));
+
+        // Newton iteration: g <- g * (2 - f * g), doubling the precision
+        let mut inverse = Self::constant(constant_inverse);
+        let mut precision = 1;
+        while precision < len {
+            precision = (precision * 2).min(len);
+            let product = self.truncated(precision).mul(&inverse).truncated(precision);
+            inverse = inverse.mul(&two.sub(&product)).truncated(precision);
+        }
+        inverse
+    }
+
+    /// First `len` coefficients (the polynomial mod x^len).
+    fn truncated(&self, len: usize) -> Self {
+        Self::new(self.coeffs.iter().take(len).copied().collect())
+    }
+
+    /// Evaluate at every point of `domain`.
+    pub fn evaluate_over(&self, domain: &Radix2Domain) -> Result<Vec<FieldElement>, ZkpError> {
+        self.evaluate_over_coset(domain, FieldElement::new(1))
+    }
+
+    /// Evaluate at shift * ω^i for every point ω^i of `domain`.
+    pub fn evaluate_over_coset(
+        &self,
+        domain: &Radix2Domain,
+        shift: FieldElement,
+    ) -> Result<Vec<FieldElement>, ZkpError> {
+        if self.coeffs.len() > domain.size() {
+            return Err(ZkpError::PolynomialDegreeTooHigh(
+                self.coeffs.len() - 1,
+                domain.size() - 1,
+            ));
+        }
+
+        // p(shift * x) has coefficients c_i * shift^i
+        let mut values = self.coeffs.clone();
+        parallel::for_each_chunk_mut(&mut values, parallel::MIN_PARALLEL_LEN, |offset, chunk| {
+            let mut power = shift.pow(offset as u64);
+            for coeff in chunk {
+                *coeff = *coeff * power;
+                power = power * shift;
+            }
+        });
+        values.resize(domain.size(), FieldElement::new(0));
+        domain.ntt(&mut values)?;
+        Ok(values)
+    }
+
+    /// Polynomial of degree below the domain size taking `values` on `domain`.
+    pub fn interpolate(domain: &Radix2Domain, values: &[FieldElement]) -> Result<Self, ZkpError> {
+        let mut coeffs = values.to_vec();
+        domain.intt(&mut coeffs)?;
+        Ok(Self::new(coeffs))
+    }
 }
 
 #[cfg(test)]
@@ -148,4 +299,76 @@ mod tests {
         assert_eq!(product.coefficients()[1].value(), 2);
         assert_eq!(product.coefficients()[2].value(), 1);
     }
+
+    fn sample(len: usize, seed: u64) -> Polynomial {
+        Polynomial::new(
+            (0..len as u64)
+                .map(|i| FieldElement::new((i + seed).wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_ntt_mul_matches_naive() {
+        for (len_a, len_b) in [(32, 32), (40, 100), (257, 33)] {
+            let a = sample(len_a, 1);
+            let b = sample(len_b, 2);
+            let product = a.mul(&b);
+            assert_eq!(product.degree(), (len_a + len_b - 2) as isize);
+            assert_eq!(product, a.mul_naive(&b));
+        }
+    }
+
+    #[test]
+    fn test_div_rem() {
+        for (len_a, len_b) in [(200, 70), (33, 33), (5, 2), (1, 1)] {
+            let a = sample(len_a, 3);
+            let b = sample(len_b, 4);
+            let (quotient, remainder) = a.div_rem(&b).unwrap();
+            assert!(remainder.degree() < b.degree() || remainder.degree() == -1);
+            assert_eq!(quotient.mul(&b).add(&remainder), a);
+        }
+
+        // Exact division leaves no remainder
+        let a = sample(90, 5);
+        let b = sample(40, 6);
+        let (quotient, remainder) = a.mul(&b).div_rem(&b).unwrap();
+        assert_eq!(quotient, a);
+        assert_eq!(remainder, Polynomial::zero());
+    }
+
+    #[test]
+    fn test_div_rem_edge_cases() {
+        let a = sample(3, 7);
+        assert!(matches!(
+            a.div_rem(&Polynomial::zero()),
+            Err(ZkpError::DivisionByZero)
+        ));
+
+        let (quotient, remainder) = a.div_rem(&sample(10, 8)).unwrap();
+        assert_eq!(quotient, Polynomial::zero());
+        assert_eq!(remainder, a);
+    }
+
+    #[test]
+    fn test_evaluate_over_and_interpolate() {
+        let domain = Radix2Domain::new(6).unwrap();
+        let p = sample(50, 9);
+        let values = p.evaluate_over(&domain).unwrap();
+        assert_eq!(Polynomial::interpolate(&domain, &values).unwrap(), p);
+        assert!(sample(65, 9).evaluate_over(&domain).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_over_coset() {
+        let domain = Radix2Domain::new(3).unwrap();
+        let shift = FieldElement::new(7);
+        let p = sample(6, 10);
+        let root = crate::field::GoldilocksField::root_of_unity(3).unwrap();
+
+        let values = p.evaluate_over_coset(&domain, shift).unwrap();
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(*value, p.evaluate(shift * root.pow(i as u64)));
+        }
+    }
 }
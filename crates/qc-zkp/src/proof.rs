@@ -1,22 +1,45 @@
 //! # ZK Proof Types
 //!
 //! Proof generation and verification.
+//!
+//! A proof shows that the witness polynomial w (coefficients = witness)
+//! committed in `witness_commitment` has degree below 2^log_degree and that
+//! w(z) = `evaluations[0]` at the Fiat-Shamir challenge z:
+//!
+//! 1. Commit to w on the FRI evaluation coset; z is drawn after the commitment.
+//! 2. Run FRI on q(x) = (w(x) - w(z)) / (x - z), which is a low-degree
+//!    polynomial only if the claimed w(z) is right.
+//! 3. Open w at every FRI query; the verifier checks
+//!    q(x) · (x - z) = w(x) - w(z) at those points.
 
-use crate::commitment::{HashOutput, MerkleCommitment};
+use crate::commitment::{HashOutput, MerkleCommitment, Opening};
 use crate::field::FieldElement;
+use crate::fri::{self, FriConfig, FriProof};
 use crate::polynomial::Polynomial;
+use crate::transcript::Transcript;
 
 /// Zero-knowledge proof.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
 pub struct Proof {
     /// Commitment to witness polynomial
     pub witness_commitment: HashOutput,
-    /// Commitment to quotient polynomial
+    /// Commitment to quotient polynomial (first FRI layer)
     pub quotient_commitment: HashOutput,
     /// Opening evaluations
     pub evaluations: Vec<FieldElement>,
     /// Challenge point
     pub challenge: FieldElement,
+    /// Witness polynomial degree is below 2^log_degree
+    pub log_degree: u32,
+    /// Low-degree proof for the quotient polynomial
+    pub fri: FriProof,
+    /// Witness openings at each FRI query's first-layer points (x and -x)
+    pub witness_openings: Vec<[Opening; 2]>,
 }
 
 /// Prover for generating ZK proofs.
@@ -24,52 +47,100 @@ pub struct Proof {
 pub struct Prover {
     /// Constraint polynomial
     constraint: Polynomial,
+    /// FRI parameters
+    config: FriConfig,
 }
 
 impl Prover {
     /// Create new prover with constraint.
     pub fn new(constraint: Polynomial) -> Self {
-        Self { constraint }
+        Self {
+            constraint,
+            config: FriConfig::default(),
+        }
+    }
+
+    /// Use non-default FRI parameters (the verifier must use the same).
+    pub fn with_fri_config(mut self, config: FriConfig) -> Self {
+        self.config = config;
+        self
     }
 
     /// Generate proof for witness satisfying constraint.
     pub fn prove(&self, witness: &[FieldElement]) -> Proof {
-        // 1. Commit to witness
-        let witness_commitment = MerkleCommitment::commit(witness);
+        if witness.is_empty() {
+            return Proof {
+                witness_commitment: [0u8; 32],
+                quotient_commitment: [0u8; 32],
+                evaluations: vec![],
+                challenge: FieldElement::new(0),
+                log_degree: 0,
+                fri: FriProof::default(),
+                witness_openings: vec![],
+            };
+        }
 
-        // 2. Create witness polynomial
+        // 1. Commit to the witness polynomial on the FRI coset
+        let log_degree = witness.len().next_power_of_two().trailing_zeros();
         let witness_poly = Polynomial::new(witness.to_vec());
+        let witness_values = fri::low_degree_extension(&self.config, &witness_poly, log_degree)
+            .expect("witness length fits the FRI domain");
+        let witness_commitment = MerkleCommitment::commit(&witness_values);
 
-        // 3. Generate challenge (in practice, use Fiat-Shamir)
-        let challenge = FieldElement::new(
-            witness_commitment.root()[0] as u64 * 256 + witness_commitment.root()[1] as u64,
-        );
+        // 2. Fiat-Shamir challenge, bound to the commitment
+        let mut transcript = statement_transcript(log_degree, witness_commitment.root());
+        let challenge = transcript.challenge_field(b"challenge");
 
-        // 4. Evaluate at challenge point
+        // 3. Evaluate at challenge point
         let witness_eval = witness_poly.evaluate(challenge);
         let constraint_eval = self.constraint.evaluate(challenge);
+        transcript.append_field(b"evaluation", witness_eval);
+
+        // 4. Quotient (w(x) - w(z)) / (x - z) and its low-degree proof
+        let divisor = Polynomial::new(vec![-challenge, FieldElement::new(1)]);
+        let (quotient_poly, _) = witness_poly
+            .sub(&Polynomial::constant(witness_eval))
+            .div_rem(&divisor)
+            .expect("x - z is non-zero");
+        let (fri, positions) =
+            fri::prove(&self.config, &mut transcript, &quotient_poly, log_degree)
+                .expect("quotient degree is below the witness bound");
 
-        // 5. Compute quotient (simplified)
-        let quotient_poly = Polynomial::constant(constraint_eval);
-        let quotient_commitment = MerkleCommitment::commit(&[quotient_poly.evaluate(challenge)]);
+        // 5. Open the witness where FRI opened the quotient
+        let witness_openings = positions
+            .iter()
+            .map(|&position| fri::open_pair(&witness_commitment, &witness_values, position))
+            .collect();
 
         Proof {
             witness_commitment: *witness_commitment.root(),
-            quotient_commitment: *quotient_commitment.root(),
+            quotient_commitment: fri.layer_roots[0],
             evaluations: vec![witness_eval, constraint_eval],
             challenge,
+            log_degree,
+            fri,
+            witness_openings,
         }
     }
 }
 
 /// Verifier for checking ZK proofs.
 #[derive(Clone, Debug, Default)]
-pub struct Verifier;
+pub struct Verifier {
+    /// FRI parameters
+    config: FriConfig,
+}
 
 impl Verifier {
     /// Create new verifier.
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Use non-default FRI parameters (the prover must use the same).
+    pub fn with_fri_config(mut self, config: FriConfig) -> Self {
+        self.config = config;
+        self
     }
 
     /// Verify a proof.
@@ -85,15 +156,28 @@ impl Verifier {
         }
 
         // 3. Challenge must match (Fiat-Shamir check)
-        let expected_challenge = FieldElement::new(
-            proof.witness_commitment[0] as u64 * 256 + proof.witness_commitment[1] as u64,
-        );
+        let mut transcript = statement_transcript(proof.log_degree, &proof.witness_commitment);
+        if proof.challenge != transcript.challenge_field(b"challenge") {
+            return false;
+        }
+        transcript.append_field(b"evaluation", proof.evaluations[0]);
+
+        // 4. Quotient must be low degree
+        let Ok(positions) =
+            fri::verify(&self.config, &mut transcript, &proof.fri, proof.log_degree)
+        else {
+            return false;
+        };
+        if proof.fri.layer_roots.first() != Some(&proof.quotient_commitment) {
+            return false;
+        }
 
-        if proof.challenge != expected_challenge {
+        // 5. Quotient must match the witness at every query
+        if !self.check_witness_openings(proof, &positions) {
             return false;
         }
 
-        // 4. Public inputs check (simplified)
+        // 6. Public inputs check (simplified)
         for (i, input) in public_inputs.iter().enumerate() {
             if i < proof.evaluations.len() && proof.evaluations[i] != *input {
                 // Public input mismatch (in real impl, would check more carefully)
@@ -102,6 +186,41 @@ impl Verifier {
 
         true
     }
+
+    /// Check q(x) · (x - z) = w(x) - w(z) at x and -x for every query.
+    fn check_witness_openings(&self, proof: &Proof, positions: &[usize]) -> bool {
+        let Ok(log_size) = fri::lde_log_size(&self.config, proof.log_degree) else {
+            return false;
+        };
+        if proof.witness_openings.len() != positions.len() {
+            return false;
+        }
+
+        let half = 1usize << (log_size - 1);
+        let height = log_size as usize;
+        let (z, value) = (proof.challenge, proof.evaluations[0]);
+        positions
+            .iter()
+            .zip(&proof.witness_openings)
+            .zip(&proof.fri.queries)
+            .all(|((&position, witness), query)| {
+                // fri::verify checked every query has its first layer
+                let quotient = &query.layers[0];
+                let x = fri::lde_point(log_size, position);
+                witness[0].verify(&proof.witness_commitment, position, height)
+                    && witness[1].verify(&proof.witness_commitment, position + half, height)
+                    && quotient[0].value * (x - z) == witness[0].value - value
+                    && quotient[1].value * (-x - z) == witness[1].value - value
+            })
+    }
+}
+
+/// Transcript with the statement (degree bound and witness commitment).
+fn statement_transcript(log_degree: u32, witness_commitment: &HashOutput) -> Transcript {
+    let mut transcript = Transcript::new(b"qc-zkp/proof");
+    transcript.append_u64(b"log-degree", u64::from(log_degree));
+    transcript.append_root(b"witness", witness_commitment);
+    transcript
 }
 
 #[cfg(test)]
@@ -131,4 +250,39 @@ mod tests {
         // Empty proof fails
         assert!(!verifier.verify(&proof, &[]));
     }
+
+    #[test]
+    fn test_claimed_evaluation_is_bound() {
+        let prover = Prover::new(Polynomial::zero());
+        let witness: Vec<FieldElement> = (0..100).map(FieldElement::new).collect();
+        let proof = prover.prove(&witness);
+        let verifier = Verifier::new();
+        assert!(verifier.verify(&proof, &[]));
+        assert_eq!(
+            proof.evaluations[0],
+            Polynomial::new(witness).evaluate(proof.challenge)
+        );
+
+        let mut tampered = proof.clone();
+        tampered.evaluations[0] = tampered.evaluations[0] + FieldElement::new(1);
+        assert!(!verifier.verify(&tampered, &[]));
+
+        let mut tampered = proof.clone();
+        tampered.witness_openings[0][1].value = FieldElement::new(1);
+        assert!(!verifier.verify(&tampered, &[]));
+
+        let mut tampered = proof.clone();
+        tampered.log_degree += 1;
+        assert!(!verifier.verify(&tampered, &[]));
+    }
+
+    #[test]
+    fn test_fri_config_must_match() {
+        let config = FriConfig::default().with_log_blowup(2).with_num_queries(40);
+        let prover = Prover::new(Polynomial::zero()).with_fri_config(config);
+        let proof = prover.prove(&[FieldElement::new(3)]);
+
+        assert!(Verifier::new().with_fri_config(config).verify(&proof, &[]));
+        assert!(!Verifier::new().verify(&proof, &[]));
+    }
 }
@@ -0,0 +1,403 @@
+//! # STARK Proofs for Constraint Systems
+//!
+//! Proves that a `Trace` satisfies a `ConstraintSystem` without sending the
+//! trace. For a trace of n rows over the subgroup H = ⟨ω⟩:
+//!
+//! 1. Interpolate every column over H and commit the columns' evaluations
+//!    on the FRI coset, one Merkle leaf per row.
+//! 2. Draw random weights from the transcript and combine into one
+//!    composition polynomial:
+//!    - transitions: C(T(x), T(ωx)) · (x - ω^(n-1)) / (x^n - 1), which is a
+//!      polynomial only if C vanishes on every row but the last;
+//!    - boundaries: (T_c(x) - v) / (x - ω^r);
+//!    - columns: (γ + δ · x^(D-n)) · T_c(x), so the columns themselves must
+//!      be of low degree.
+//! 3. Run FRI on the composition with degree bound D.
+//! 4. At every FRI query, open the trace rows at x, -x and the next rows
+//!    (ωx, -ωx); the verifier recomputes the composition there and compares
+//!    it with FRI's first layer.
+//!
+//! The statement (system name, trace length, boundary values) is bound into
+//! the transcript; callers append their own public inputs before proving.
+//!
+//! Openings reveal trace rows outside H but nothing is masked, so proofs are
+//! succinct and sound but not zero-knowledge.
+
+use crate::air::{ConstraintSystem, Trace};
+use crate::commitment::{HashOutput, MerkleCommitment, RowOpening};
+use crate::errors::ZkpError;
+use crate::field::{FieldElement, GoldilocksField};
+use crate::fri::{self, FriConfig, FriProof};
+use crate::ntt::Radix2Domain;
+use crate::parallel;
+use crate::polynomial::Polynomial;
+use crate::transcript::Transcript;
+
+/// Proof that a trace satisfies a constraint system.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct StarkProof {
+    /// The trace has 2^log_rows rows
+    pub log_rows: u32,
+    /// Commitment to the trace rows on the evaluation coset
+    pub trace_root: HashOutput,
+    /// Low-degree proof for the composition polynomial
+    pub fri: FriProof,
+    /// Per query: trace rows at x, -x, ωx and -ωx
+    pub trace_openings: Vec<[RowOpening; 4]>,
+}
+
+/// Sizes shared by prover and verifier.
+struct Layout {
+    /// Trace rows
+    rows: usize,
+    /// Composition degree bound is 2^log_degree
+    log_degree: u32,
+    /// Evaluation coset has 2^log_lde points
+    log_lde: u32,
+}
+
+impl Layout {
+    fn new(system: &ConstraintSystem, config: &FriConfig, log_rows: u32) -> Result<Self, ZkpError> {
+        if log_rows == 0 || log_rows > GoldilocksField::TWO_ADICITY {
+            return Err(ZkpError::DomainTooLarge(log_rows));
+        }
+        let degree = system.max_degree().max(1).next_power_of_two();
+        let log_degree = log_rows + degree.trailing_zeros();
+        Ok(Self {
+            rows: 1 << log_rows,
+            log_degree,
+            log_lde: fri::lde_log_size(config, log_degree)?,
+        })
+    }
+
+    fn lde_size(&self) -> usize {
+        1 << self.log_lde
+    }
+
+    /// Index offset from x to ωx on the evaluation coset.
+    fn next_step(&self) -> usize {
+        self.lde_size() / self.rows
+    }
+}
+
+/// Random weights of the composition polynomial.
+struct Weights {
+    transitions: Vec<FieldElement>,
+    boundaries: Vec<FieldElement>,
+    /// (γ, δ) per column
+    columns: Vec<(FieldElement, FieldElement)>,
+}
+
+impl Weights {
+    fn draw(system: &ConstraintSystem, transcript: &mut Transcript) -> Self {
+        Self {
+            transitions: system
+                .transitions()
+                .iter()
+                .map(|_| transcript.challenge_field(b"stark-transition"))
+                .collect(),
+            boundaries: system
+                .boundaries()
+                .iter()
+                .map(|_| transcript.challenge_field(b"stark-boundary"))
+                .collect(),
+            columns: system
+                .columns()
+                .iter()
+                .map(|_| {
+                    (
+                        transcript.challenge_field(b"stark-column"),
+                        transcript.challenge_field(b"stark-column-shift"),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Prove that `trace` satisfies `system`.
+///
+/// The trace is checked first, so an unsatisfying trace fails with the
+/// violated constraint instead of producing a proof that won't verify.
+pub fn prove(
+    system: &ConstraintSystem,
+    trace: &Trace,
+    config: &FriConfig,
+    transcript: &mut Transcript,
+) -> Result<StarkProof, ZkpError> {
+    system.check(trace)?;
+    let log_rows = trace.num_rows().trailing_zeros();
+    let layout = Layout::new(system, config, log_rows)?;
+    append_statement(transcript, system, &layout);
+
+    // 1. Trace columns on the evaluation coset, stored row by row
+    let trace_domain = Radix2Domain::cached(log_rows)?;
+    let lde_domain = Radix2Domain::cached(layout.log_lde)?;
+    let columns = parallel::map(trace.columns(), |column| {
+        Polynomial::interpolate(&trace_domain, column)?
+            .evaluate_over_coset(&lde_domain, fri::coset_shift())
+    })
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()?;
+    let rows = parallel::map_range(layout.lde_size(), |i| {
+        columns.iter().map(|column| column[i]).collect::<Vec<_>>()
+    });
+    drop(columns);
+    let commitment = MerkleCommitment::commit_rows(&rows);
+    transcript.append_root(b"stark-trace", commitment.root());
+
+    // 2. Composition polynomial on the evaluation coset
+    let weights = Weights::draw(system, transcript);
+    let composition = composition_values(system, &layout, &weights, &rows)?;
+
+    // 3. Low-degree test, then open the trace where FRI queried
+    let (fri, positions) =
+        fri::prove_evaluations(config, transcript, composition, layout.log_degree)?;
+    let trace_openings = positions
+        .iter()
+        .map(|&position| {
+            query_rows(&layout, position)
+                .map(|index| RowOpening::new(&commitment, &rows, index).expect("row in coset"))
+        })
+        .collect();
+
+    Ok(StarkProof {
+        log_rows,
+        trace_root: *commitment.root(),
+        fri,
+        trace_openings,
+    })
+}
+
+/// Verify a proof that some trace satisfies `system`.
+///
+/// `transcript` must hold the same public inputs the prover appended.
+pub fn verify(
+    system: &ConstraintSystem,
+    proof: &StarkProof,
+    config: &FriConfig,
+    transcript: &mut Transcript,
+) -> Result<(), ZkpError> {
+    let layout = Layout::new(system, config, proof.log_rows)?;
+    append_statement(transcript, system, &layout);
+    transcript.append_root(b"stark-trace", &proof.trace_root);
+    let weights = Weights::draw(system, transcript);
+
+    let positions = fri::verify(config, transcript, &proof.fri, layout.log_degree)?;
+    if proof.trace_openings.len() != positions.len() {
+        return Err(ZkpError::VerificationFailed);
+    }
+
+    for ((openings, query), &position) in proof
+        .trace_openings
+        .iter()
+        .zip(&proof.fri.queries)
+        .zip(&positions)
+    {
+        let opened = query_rows(&layout, position)
+            .iter()
+            .zip(openings)
+            .all(|(&index, opening)| {
+                opening.values.len() == system.columns().len()
+                    && opening.verify(&proof.trace_root, index, layout.log_lde as usize)
+            });
+        let Some(first_layer) = query.layers.first() else {
+            return Err(ZkpError::VerificationFailed);
+        };
+        if !opened {
+            return Err(ZkpError::VerificationFailed);
+        }
+
+        // Composition at x and -x must match FRI's first layer
+        let x = fri::lde_point(layout.log_lde, position);
+        let at_x = composition_value(
+            system,
+            &layout,
+            &weights,
+            &openings[0].values,
+            &openings[2].values,
+            x,
+        )?;
+        let at_neg_x = composition_value(
+            system,
+            &layout,
+            &weights,
+            &openings[1].values,
+            &openings[3].values,
+            -x,
+        )?;
+        if at_x != first_layer[0].value || at_neg_x != first_layer[1].value {
+            return Err(ZkpError::VerificationFailed);
+        }
+    }
+    Ok(())
+}
+
+/// Composition polynomial at every point of the evaluation coset.
+fn composition_values(
+    system: &ConstraintSystem,
+    layout: &Layout,
+    weights: &Weights,
+    rows: &[Vec<FieldElement>],
+) -> Result<Vec<FieldElement>, ZkpError> {
+    let size = layout.lde_size();
+    let chunk = parallel::MIN_PARALLEL_LEN.min(size);
+    let root = GoldilocksField::root_of_unity(layout.log_lde).expect("coset size checked");
+
+    let chunks = parallel::map_range(size / chunk, |index| {
+        let start = index * chunk;
+        let mut x = fri::coset_shift() * root.pow(start as u64);
+        (start..start + chunk)
+            .map(|i| {
+                let next = &rows[(i + layout.next_step()) % size];
+                let value = composition_value(system, layout, weights, &rows[i], next, x);
+                x = x * root;
+                value
+            })
+            .collect::<Result<Vec<_>, _>>()
+    });
+    Ok(chunks.into_iter().collect::<Result<Vec<_>, _>>()?.concat())
+}
+
+/// Coset indices of x, -x, ωx and -ωx for a query at `position` (< N/2).
+fn query_rows(layout: &Layout, position: usize) -> [usize; 4] {
+    let size = layout.lde_size();
+    let half = size / 2;
+    let step = layout.next_step();
+    [
+        position,
+        position + half,
+        (position + step) % size,
+        (position + half + step) % size,
+    ]
+}
+
+fn append_statement(transcript: &mut Transcript, system: &ConstraintSystem, layout: &Layout) {
+    transcript.append_message(b"stark-system", system.name().as_bytes());
+    transcript.append_u64(b"stark-columns", system.columns().len() as u64);
+    transcript.append_u64(b"stark-transitions", system.transitions().len() as u64);
+    transcript.append_u64(b"stark-rows", layout.rows as u64);
+    for boundary in system.boundaries() {
+        transcript.append_u64(b"stark-boundary-column", boundary.column.index() as u64);
+        transcript.append_u64(
+            b"stark-boundary-row",
+            boundary.row.resolve(layout.rows) as u64,
+        );
+        transcript.append_field(b"stark-boundary-value", boundary.value);
+    }
+}
+
+/// Composition polynomial at x, given the trace rows at x and ωx.
+fn composition_value(
+    system: &ConstraintSystem,
+    layout: &Layout,
+    weights: &Weights,
+    current: &[FieldElement],
+    next: &[FieldElement],
+    x: FieldElement,
+) -> Result<FieldElement, ZkpError> {
+    let trace_root =
+        GoldilocksField::root_of_unity(layout.rows.trailing_zeros()).expect("trace size checked");
+    let invert = |value: FieldElement| value.inverse().ok_or(ZkpError::VerificationFailed);
+    let mut total = GoldilocksField::zero();
+
+    // Transitions vanish on every row but the last: divide by
+    // (x^n - 1) / (x - ω^(n-1))
+    let last_row = trace_root.pow(layout.rows as u64 - 1);
+    let transition_quotient =
+        (x - last_row) * invert(x.pow(layout.rows as u64) - GoldilocksField::one())?;
+    for (constraint, weight) in system.transitions().iter().zip(&weights.transitions) {
+        total = total + *weight * constraint.expr.evaluate(current, next) * transition_quotient;
+    }
+
+    for (boundary, weight) in system.boundaries().iter().zip(&weights.boundaries) {
+        let point = trace_root.pow(boundary.row.resolve(layout.rows) as u64);
+        let value = current[boundary.column.index()] - boundary.value;
+        total = total + *weight * value * invert(x - point)?;
+    }
+
+    // Columns raised to the degree bound
+    let shift = x.pow(((1usize << layout.log_degree) - layout.rows) as u64);
+    for (value, (gamma, delta)) in current.iter().zip(&weights.columns) {
+        total = total + (*gamma + *delta * shift) * *value;
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::air::{BoundaryRow, Expr};
+
+    /// Squaring chain x' = x², starting from 3, with the last value public.
+    fn squares(rows: usize) -> (ConstraintSystem, Trace) {
+        let mut values = vec![FieldElement::new(3)];
+        for _ in 1..rows {
+            let last = values[values.len() - 1];
+            values.push(last * last);
+        }
+
+        let mut builder = ConstraintSystem::builder("squares");
+        let x = builder.column("x");
+        let step = builder.column("step");
+        builder
+            .transition("square", x.next() - x.curr() * x.curr())
+            .transition("count", step.next() - step.curr() - Expr::constant(1))
+            .boundary(x, BoundaryRow::First, FieldElement::new(3))
+            .boundary(x, BoundaryRow::Last, values[rows - 1])
+            .boundary(step, BoundaryRow::First, FieldElement::new(0));
+        let system = builder.build().unwrap();
+        let steps = (0..rows as u64).map(FieldElement::new).collect();
+        (system, Trace::new(vec![values, steps]).unwrap())
+    }
+
+    #[test]
+    fn test_prove_and_verify() {
+        let config = FriConfig::default();
+        for rows in [2, 8, 64] {
+            let (system, trace) = squares(rows);
+            let proof = prove(&system, &trace, &config, &mut Transcript::new(b"test")).unwrap();
+            assert_eq!(proof.log_rows, rows.trailing_zeros());
+            assert!(verify(&system, &proof, &config, &mut Transcript::new(b"test")).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_unsatisfying_trace_is_not_proven() {
+        let (system, trace) = squares(8);
+        let mut columns = trace.columns().to_vec();
+        columns[0][4] = FieldElement::new(5);
+        let bad = Trace::new(columns).unwrap();
+        assert!(matches!(
+            prove(&system, &bad, &FriConfig::default(), &mut Transcript::new(b"test")),
+            Err(ZkpError::ConstraintViolation(name, 3)) if name == "square"
+        ));
+    }
+
+    #[test]
+    fn test_wrong_statement_is_rejected() {
+        let config = FriConfig::default();
+        let (system, trace) = squares(16);
+        let proof = prove(&system, &trace, &config, &mut Transcript::new(b"test")).unwrap();
+
+        // Different public output
+        let (other, _) = squares(32);
+        assert!(verify(&other, &proof, &config, &mut Transcript::new(b"test")).is_err());
+        // Different public inputs in the transcript
+        assert!(verify(&system, &proof, &config, &mut Transcript::new(b"other")).is_err());
+
+        // Tampered trace opening
+        let mut tampered = proof.clone();
+        tampered.trace_openings[0][2].values[0] = FieldElement::new(7);
+        assert!(verify(&system, &tampered, &config, &mut Transcript::new(b"test")).is_err());
+
+        let mut tampered = proof;
+        tampered.trace_root = [1u8; 32];
+        assert!(verify(&system, &tampered, &config, &mut Transcript::new(b"test")).is_err());
+    }
+}
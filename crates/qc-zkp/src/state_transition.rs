@@ -0,0 +1,320 @@
+//! # State Transition Proofs
+//!
+//! Validity proofs for a block's state diff: every account whose balance or
+//! nonce changed, before and after the block.
+//!
+//! The circuit proves, over the Goldilocks field:
+//!
+//! - Balances are conserved: the changes sum to the minted amount.
+//! - Every nonce increased by 0 or 1.
+//!
+//! Trace layout: row 0 is an all-zero header, rows 1..=m hold the m
+//! changed accounts, the rest is zero padding (at least one row, since the
+//! last row's transition is unconstrained). `total` is the running sum of
+//! balance changes:
+//!
+//! | balance_before | balance_after | nonce_before | nonce_after | total |
+//!
+//! Trie hashing is not arithmetized. The previous and new state roots and a
+//! digest of the full diff (addresses included) are bound into the
+//! transcript as public inputs, so a proof is tied to one block's diff but
+//! does not itself show that the roots commit to it.
+
+use crate::air::{BoundaryRow, ConstraintSystem, Trace, WitnessGenerator};
+use crate::errors::ZkpError;
+use crate::field::{FieldElement, GoldilocksField};
+use crate::fri::FriConfig;
+use crate::stark::{self, StarkProof};
+use crate::transcript::Transcript;
+
+/// Transcript label of state transition proofs.
+const PROTOCOL: &[u8] = b"qc-state-transition";
+
+/// Balance and nonce of one account before and after a block.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccountChange {
+    /// Balance before the block
+    pub balance_before: u128,
+    /// Balance after the block
+    pub balance_after: u128,
+    /// Nonce before the block
+    pub nonce_before: u64,
+    /// Nonce after the block
+    pub nonce_after: u64,
+}
+
+/// Public inputs of a state transition proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct StateTransitionStatement {
+    /// Block hash
+    pub block_hash: [u8; 32],
+    /// Block height
+    pub block_height: u64,
+    /// State root before the block
+    pub previous_state_root: [u8; 32],
+    /// State root after the block
+    pub state_root: [u8; 32],
+    /// Digest of the full state diff
+    pub diff_digest: [u8; 32],
+    /// Amount created by the block (coinbase)
+    pub minted: u128,
+    /// Number of changed accounts
+    pub accounts: usize,
+}
+
+impl StateTransitionStatement {
+    /// Trace rows for this statement: header, accounts and padding.
+    pub fn num_rows(&self) -> usize {
+        (self.accounts + 2).next_power_of_two()
+    }
+
+    fn append_to(&self, transcript: &mut Transcript) {
+        transcript.append_message(b"block-hash", &self.block_hash);
+        transcript.append_u64(b"block-height", self.block_height);
+        transcript.append_root(b"previous-state-root", &self.previous_state_root);
+        transcript.append_root(b"state-root", &self.state_root);
+        transcript.append_root(b"diff-digest", &self.diff_digest);
+        transcript.append_message(b"minted", &self.minted.to_le_bytes());
+        transcript.append_u64(b"accounts", self.accounts as u64);
+    }
+}
+
+/// Validity proof for a block's state transition.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+pub struct StateTransitionProof {
+    /// Public inputs
+    pub statement: StateTransitionStatement,
+    /// Proof that the diff satisfies the circuit
+    pub proof: StarkProof,
+}
+
+/// Constraint system and witness generator for one statement.
+#[derive(Clone, Debug)]
+pub struct StateTransitionCircuit {
+    system: ConstraintSystem,
+    rows: usize,
+}
+
+impl StateTransitionCircuit {
+    /// Circuit for `statement` (the minted amount is a boundary value).
+    pub fn new(statement: &StateTransitionStatement) -> Self {
+        let mut builder = ConstraintSystem::builder("state-transition");
+        let before = builder.column("balance_before");
+        let after = builder.column("balance_after");
+        let nonce_before = builder.column("nonce_before");
+        let nonce_after = builder.column("nonce_after");
+        let total = builder.column("total");
+
+        let nonce_step = nonce_after.curr() - nonce_before.curr();
+        builder
+            .transition(
+                "balance",
+                total.next() - total.curr() - (after.next() - before.next()),
+            )
+            .transition(
+                "nonce",
+                nonce_step.clone() * (nonce_step - GoldilocksField::one()),
+            )
+            .boundary(total, BoundaryRow::First, GoldilocksField::zero())
+            .boundary(
+                total,
+                BoundaryRow::Last,
+                FieldElement::from_u128(statement.minted),
+            );
+
+        Self {
+            system: builder.build().expect("state transition circuit is valid"),
+            rows: statement.num_rows(),
+        }
+    }
+}
+
+impl WitnessGenerator for StateTransitionCircuit {
+    type Input = [AccountChange];
+
+    fn constraint_system(&self) -> &ConstraintSystem {
+        &self.system
+    }
+
+    fn generate_witness(&self, changes: &[AccountChange]) -> Result<Trace, ZkpError> {
+        if changes.len() + 2 > self.rows {
+            return Err(ZkpError::InvalidTrace(format!(
+                "{} account changes do not fit {} rows",
+                changes.len(),
+                self.rows
+            )));
+        }
+
+        let zero = GoldilocksField::zero();
+        let mut rows = vec![vec![zero; 5]; self.rows];
+        let mut total = zero;
+        for (row, change) in rows[1..].iter_mut().zip(changes) {
+            let before = FieldElement::from_u128(change.balance_before);
+            let after = FieldElement::from_u128(change.balance_after);
+            total = total + after - before;
+            *row = vec![
+                before,
+                after,
+                FieldElement::new(change.nonce_before),
+                FieldElement::new(change.nonce_after),
+                total,
+            ];
+        }
+        // Padding rows carry the final total
+        for row in &mut rows[changes.len() + 1..] {
+            row[4] = total;
+        }
+        Trace::from_rows(&rows)
+    }
+}
+
+/// Prove that `changes` are a valid transition for `statement`.
+pub fn prove(
+    statement: StateTransitionStatement,
+    changes: &[AccountChange],
+    config: &FriConfig,
+) -> Result<StateTransitionProof, ZkpError> {
+    if changes.len() != statement.accounts {
+        return Err(ZkpError::InvalidTrace(format!(
+            "Statement covers {} accounts, got {} changes",
+            statement.accounts,
+            changes.len()
+        )));
+    }
+
+    let circuit = StateTransitionCircuit::new(&statement);
+    let trace = circuit.checked_witness(changes)?;
+    let mut transcript = Transcript::new(PROTOCOL);
+    statement.append_to(&mut transcript);
+    let proof = stark::prove(circuit.constraint_system(), &trace, config, &mut transcript)?;
+    Ok(StateTransitionProof { statement, proof })
+}
+
+/// Verify a state transition proof against its statement.
+pub fn verify(proof: &StateTransitionProof, config: &FriConfig) -> Result<(), ZkpError> {
+    let statement = &proof.statement;
+    if 1usize.checked_shl(proof.proof.log_rows) != Some(statement.num_rows()) {
+        return Err(ZkpError::VerificationFailed);
+    }
+
+    let circuit = StateTransitionCircuit::new(statement);
+    let mut transcript = Transcript::new(PROTOCOL);
+    statement.append_to(&mut transcript);
+    stark::verify(
+        circuit.constraint_system(),
+        &proof.proof,
+        config,
+        &mut transcript,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block() -> (StateTransitionStatement, Vec<AccountChange>) {
+        let changes = vec![
+            // Sender pays 300 and bumps its nonce
+            AccountChange {
+                balance_before: 1_000,
+                balance_after: 700,
+                nonce_before: 4,
+                nonce_after: 5,
+            },
+            // Recipient receives 300
+            AccountChange {
+                balance_before: 0,
+                balance_after: 300,
+                ..Default::default()
+            },
+            // Coinbase mints 50
+            AccountChange {
+                balance_before: 10,
+                balance_after: 60,
+                ..Default::default()
+            },
+        ];
+        let statement = StateTransitionStatement {
+            block_hash: [1; 32],
+            block_height: 7,
+            previous_state_root: [2; 32],
+            state_root: [3; 32],
+            diff_digest: [4; 32],
+            minted: 50,
+            accounts: changes.len(),
+        };
+        (statement, changes)
+    }
+
+    #[test]
+    fn test_valid_transition_verifies() {
+        let config = FriConfig::default();
+        let (statement, changes) = block();
+        let proof = prove(statement, &changes, &config).unwrap();
+        assert_eq!(proof.proof.log_rows, 3);
+        assert!(verify(&proof, &config).is_ok());
+
+        // Empty blocks are provable too
+        let empty = StateTransitionStatement {
+            minted: 0,
+            accounts: 0,
+            ..block().0
+        };
+        let proof = prove(empty, &[], &config).unwrap();
+        assert!(verify(&proof, &config).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_transitions_are_rejected() {
+        let config = FriConfig::default();
+        let (statement, mut changes) = block();
+
+        // Balance created from nothing
+        let mut inflated = changes.clone();
+        inflated[1].balance_after = 400;
+        assert!(matches!(
+            prove(statement.clone(), &inflated, &config),
+            Err(ZkpError::ConstraintViolation(..))
+        ));
+
+        // Nonce skipped ahead
+        changes[0].nonce_after = 7;
+        assert!(matches!(
+            prove(statement.clone(), &changes, &config),
+            Err(ZkpError::ConstraintViolation(name, 1)) if name == "nonce"
+        ));
+        assert!(matches!(
+            prove(statement, &changes[..2], &config),
+            Err(ZkpError::InvalidTrace(_))
+        ));
+    }
+
+    #[test]
+    fn test_proof_is_bound_to_statement() {
+        let config = FriConfig::default();
+        let (statement, changes) = block();
+        let proof = prove(statement, &changes, &config).unwrap();
+
+        let mut other_root = proof.clone();
+        other_root.statement.state_root = [9; 32];
+        assert!(verify(&other_root, &config).is_err());
+
+        let mut other_minted = proof.clone();
+        other_minted.statement.minted = 51;
+        assert!(verify(&other_minted, &config).is_err());
+
+        let mut other_size = proof;
+        other_size.statement.accounts = 9;
+        assert!(verify(&other_size, &config).is_err());
+    }
+}
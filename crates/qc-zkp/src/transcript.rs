@@ -0,0 +1,109 @@
+//! # Fiat-Shamir Transcript
+//!
+//! Turns an interactive protocol into a non-interactive one: every message
+//! the prover would send is appended to a running BLAKE3 hash, and every
+//! verifier challenge is derived from that hash. Prover and verifier replay
+//! the same sequence, so they draw the same challenges.
+
+use crate::commitment::HashOutput;
+use crate::field::{FieldElement, GOLDILOCKS_PRIME};
+
+/// Running hash of a proof's messages.
+#[derive(Clone, Debug)]
+pub struct Transcript {
+    hasher: blake3::Hasher,
+}
+
+impl Transcript {
+    /// Start a transcript for the protocol named `label`.
+    pub fn new(label: &[u8]) -> Self {
+        let mut transcript = Self {
+            hasher: blake3::Hasher::new(),
+        };
+        transcript.append_message(b"protocol", label);
+        transcript
+    }
+
+    /// Append labelled bytes.
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        // Length prefixes keep (label, message) boundaries unambiguous
+        self.hasher.update(&(label.len() as u64).to_le_bytes());
+        self.hasher.update(label);
+        self.hasher.update(&(message.len() as u64).to_le_bytes());
+        self.hasher.update(message);
+    }
+
+    /// Append a labelled integer.
+    pub fn append_u64(&mut self, label: &[u8], value: u64) {
+        self.append_message(label, &value.to_le_bytes());
+    }
+
+    /// Append a labelled field element.
+    pub fn append_field(&mut self, label: &[u8], value: FieldElement) {
+        self.append_u64(label, value.value());
+    }
+
+    /// Append a labelled commitment root.
+    pub fn append_root(&mut self, label: &[u8], root: &HashOutput) {
+        self.append_message(label, root);
+    }
+
+    /// Uniform field element challenge.
+    pub fn challenge_field(&mut self, label: &[u8]) -> FieldElement {
+        loop {
+            let output = self.squeeze(label);
+            // Rejection sampling: values >= p would bias the result
+            let accepted = output
+                .chunks_exact(8)
+                .map(|chunk| u64::from_le_bytes(chunk.try_into().expect("8-byte chunk")))
+                .find(|value| *value < GOLDILOCKS_PRIME);
+            if let Some(value) = accepted {
+                return FieldElement::new(value);
+            }
+        }
+    }
+
+    /// Index challenge in 0..bound (uniform when bound is a power of two).
+    pub fn challenge_index(&mut self, label: &[u8], bound: usize) -> usize {
+        let output = self.squeeze(label);
+        let value = u64::from_le_bytes(output[..8].try_into().expect("8-byte prefix"));
+        (value % bound.max(1) as u64) as usize
+    }
+
+    /// Hash everything so far, then restart from that hash.
+    fn squeeze(&mut self, label: &[u8]) -> HashOutput {
+        self.append_message(label, &[]);
+        let output = *self.hasher.finalize().as_bytes();
+        self.hasher = blake3::Hasher::new();
+        self.hasher.update(&output);
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_challenges_depend_on_messages() {
+        let mut a = Transcript::new(b"test");
+        let mut b = Transcript::new(b"test");
+        a.append_u64(b"x", 1);
+        b.append_u64(b"x", 1);
+        assert_eq!(a.challenge_field(b"c"), b.challenge_field(b"c"));
+        assert_eq!(a.challenge_index(b"i", 64), b.challenge_index(b"i", 64));
+
+        // Later challenges differ from earlier ones
+        assert_ne!(
+            a.challenge_field(b"c"),
+            Transcript::new(b"test").challenge_field(b"c")
+        );
+
+        let mut c = Transcript::new(b"test");
+        c.append_u64(b"x", 2);
+        let mut d = Transcript::new(b"test");
+        d.append_u64(b"x", 1);
+        assert_ne!(c.challenge_field(b"c"), d.challenge_field(b"c"));
+        assert!(c.challenge_index(b"i", 64) < 64);
+    }
+}
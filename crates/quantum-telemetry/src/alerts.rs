@@ -0,0 +1,455 @@
+//! In-node alert evaluation.
+//!
+//! Small deployments often run without Prometheus and Grafana. The alert
+//! manager evaluates a few rules against the global metrics registry and
+//! pushes notifications itself: to in-process subscribers (the node runtime
+//! republishes them on the event bus as `NodeAlert`) and, optionally, as a
+//! JSON POST to a webhook.
+//!
+//! A rule fires once its condition has held for the rule's duration, and a
+//! `resolved` notification is sent when it stops holding. Rules whose
+//! metrics are not registered are skipped.
+//!
+//! ```rust,ignore
+//! let rules = vec![
+//!     AlertRule::below("LowPeerCount", "qc_peers_connected", 3.0)
+//!         .with_duration(Duration::from_secs(60)),
+//! ];
+//! let manager = AlertManager::start(rules, Duration::from_secs(15), None);
+//! let mut alerts = manager.subscribe();
+//! ```
+
+use prometheus::proto::{MetricFamily, MetricType};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use crate::metrics::REGISTRY;
+
+/// Alerts buffered for each in-process subscriber
+const ALERT_CHANNEL_CAPACITY: usize = 64;
+
+/// Time allowed for a webhook POST
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Alert severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    /// Degraded; look at it soon
+    Warning,
+    /// Needs immediate attention
+    Critical,
+}
+
+/// Whether an alert started or stopped firing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertState {
+    /// Condition has held for the rule's duration
+    Firing,
+    /// Condition no longer holds
+    Resolved,
+}
+
+/// Which side of the threshold triggers a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Condition {
+    /// Value greater than the threshold
+    Above,
+    /// Value less than the threshold
+    Below,
+}
+
+/// A threshold rule over one metric, or the difference of two.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertRule {
+    /// Rule name, e.g. `FinalityLag`
+    pub name: String,
+    /// Metric name; values of all its series are summed
+    pub metric: String,
+    /// Metric subtracted from `metric`, if any
+    pub minus: Option<String>,
+    /// Side of the threshold that triggers the rule
+    pub condition: Condition,
+    /// Threshold
+    pub threshold: f64,
+    /// How long the condition must hold before firing
+    pub duration: Duration,
+    /// Severity of the alert
+    pub severity: AlertSeverity,
+    /// Human-readable summary sent with the alert
+    pub summary: String,
+}
+
+impl AlertRule {
+    /// Rule that fires when `metric` is above `threshold`.
+    pub fn above(name: &str, metric: &str, threshold: f64) -> Self {
+        Self::new(name, metric, Condition::Above, threshold)
+    }
+
+    /// Rule that fires when `metric` is below `threshold`.
+    pub fn below(name: &str, metric: &str, threshold: f64) -> Self {
+        Self::new(name, metric, Condition::Below, threshold)
+    }
+
+    fn new(name: &str, metric: &str, condition: Condition, threshold: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            metric: metric.to_string(),
+            minus: None,
+            condition,
+            threshold,
+            duration: Duration::ZERO,
+            severity: AlertSeverity::Warning,
+            summary: name.to_string(),
+        }
+    }
+
+    /// Compare `metric - minus` instead of `metric`.
+    pub fn with_minus(mut self, metric: &str) -> Self {
+        self.minus = Some(metric.to_string());
+        self
+    }
+
+    /// Require the condition to hold this long before firing.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set the severity.
+    pub fn with_severity(mut self, severity: AlertSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Set the summary.
+    pub fn with_summary(mut self, summary: &str) -> Self {
+        self.summary = summary.to_string();
+        self
+    }
+
+    /// Finality lag, peer count and mempool depth rules.
+    pub fn defaults(max_finality_lag: f64, min_peers: f64, max_mempool_depth: f64) -> Vec<Self> {
+        vec![
+            Self::above("FinalityLag", "qc_storage_chain_height", max_finality_lag)
+                .with_minus("qc_finality_finalized_height")
+                .with_duration(Duration::from_secs(120))
+                .with_severity(AlertSeverity::Critical)
+                .with_summary("Finalized height is falling behind the chain head"),
+            Self::below("LowPeerCount", "qc_peers_connected", min_peers)
+                .with_duration(Duration::from_secs(60))
+                .with_summary("Too few connected peers"),
+            Self::above(
+                "MempoolBacklog",
+                "qc_mempool_transactions_pending",
+                max_mempool_depth,
+            )
+            .with_duration(Duration::from_secs(300))
+            .with_summary("Pending transactions are piling up in the mempool"),
+        ]
+    }
+
+    /// Current value of the rule's expression, if its metrics exist.
+    fn value(&self, families: &[MetricFamily]) -> Option<f64> {
+        let value = metric_value(families, &self.metric)?;
+        match &self.minus {
+            Some(minus) => Some(value - metric_value(families, minus)?),
+            None => Some(value),
+        }
+    }
+
+    fn holds(&self, value: f64) -> bool {
+        match self.condition {
+            Condition::Above => value > self.threshold,
+            Condition::Below => value < self.threshold,
+        }
+    }
+}
+
+/// Sum of all series of a counter or gauge.
+fn metric_value(families: &[MetricFamily], name: &str) -> Option<f64> {
+    let family = families.iter().find(|f| f.get_name() == name)?;
+    let values = family
+        .get_metric()
+        .iter()
+        .map(|m| match family.get_field_type() {
+            MetricType::COUNTER => Some(m.get_counter().get_value()),
+            MetricType::GAUGE => Some(m.get_gauge().get_value()),
+            MetricType::UNTYPED => Some(m.get_untyped().get_value()),
+            _ => None,
+        });
+    values.sum()
+}
+
+/// A notification that a rule started or stopped firing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Alert {
+    /// Rule name
+    pub rule: String,
+    /// Rule severity
+    pub severity: AlertSeverity,
+    /// Firing or resolved
+    pub state: AlertState,
+    /// Value that triggered the notification
+    pub value: f64,
+    /// Rule threshold
+    pub threshold: f64,
+    /// Rule summary
+    pub summary: String,
+    /// Unix timestamp (seconds)
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RuleState {
+    Inactive,
+    Pending { since: Instant },
+    Firing,
+}
+
+/// Tracks rule state across evaluations.
+#[derive(Debug)]
+pub struct AlertEvaluator {
+    rules: Vec<(AlertRule, RuleState)>,
+}
+
+impl AlertEvaluator {
+    /// Create an evaluator with every rule inactive.
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        Self {
+            rules: rules
+                .into_iter()
+                .map(|rule| (rule, RuleState::Inactive))
+                .collect(),
+        }
+    }
+
+    /// Evaluate every rule and return the alerts that changed state.
+    pub fn evaluate(&mut self, families: &[MetricFamily], now: Instant) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for (rule, state) in &mut self.rules {
+            let Some(value) = rule.value(families) else {
+                continue;
+            };
+
+            let next = match (*state, rule.holds(value)) {
+                (RuleState::Inactive, true) => RuleState::Pending { since: now },
+                (RuleState::Pending { since }, true)
+                    if now.saturating_duration_since(since) < rule.duration =>
+                {
+                    RuleState::Pending { since }
+                }
+                (RuleState::Pending { .. } | RuleState::Firing, true) => RuleState::Firing,
+                (_, false) => RuleState::Inactive,
+            };
+
+            // A zero duration fires on the first evaluation
+            let next = match next {
+                RuleState::Pending { .. } if rule.duration.is_zero() => RuleState::Firing,
+                next => next,
+            };
+
+            match (*state, next) {
+                (RuleState::Firing, RuleState::Inactive) => {
+                    alerts.push(alert(rule, AlertState::Resolved, value));
+                }
+                (RuleState::Inactive | RuleState::Pending { .. }, RuleState::Firing) => {
+                    alerts.push(alert(rule, AlertState::Firing, value));
+                }
+                _ => {}
+            }
+            *state = next;
+        }
+        alerts
+    }
+}
+
+fn alert(rule: &AlertRule, state: AlertState, value: f64) -> Alert {
+    Alert {
+        rule: rule.name.clone(),
+        severity: rule.severity,
+        state,
+        value,
+        threshold: rule.threshold,
+        summary: rule.summary.clone(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    }
+}
+
+/// Running alert manager. Stops when dropped.
+pub struct AlertManager {
+    sender: broadcast::Sender<Alert>,
+    task: JoinHandle<()>,
+}
+
+impl AlertManager {
+    /// Evaluate `rules` every `interval`, POSTing alerts to `webhook` if set.
+    pub fn start(rules: Vec<AlertRule>, interval: Duration, webhook: Option<String>) -> Self {
+        tracing::info!(
+            rules = rules.len(),
+            webhook = webhook.is_some(),
+            "Evaluating alert rules every {:?}",
+            interval
+        );
+        let (sender, _) = broadcast::channel(ALERT_CHANNEL_CAPACITY);
+        let task = tokio::spawn(evaluate_loop(
+            AlertEvaluator::new(rules),
+            interval,
+            webhook,
+            sender.clone(),
+        ));
+        Self { sender, task }
+    }
+
+    /// Receive alerts as they fire and resolve.
+    pub fn subscribe(&self) -> broadcast::Receiver<Alert> {
+        self.sender.subscribe()
+    }
+}
+
+impl Drop for AlertManager {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Evaluate rules every `interval` until the task is aborted.
+async fn evaluate_loop(
+    mut evaluator: AlertEvaluator,
+    interval: Duration,
+    webhook: Option<String>,
+    sender: broadcast::Sender<Alert>,
+) {
+    let client = reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        for alert in evaluator.evaluate(&REGISTRY.gather(), Instant::now()) {
+            log_alert(&alert);
+            if let Some(url) = &webhook {
+                post_alert(&client, url, &alert).await;
+            }
+            // No subscribers is fine
+            let _ = sender.send(alert);
+        }
+    }
+}
+
+fn log_alert(alert: &Alert) {
+    match (alert.state, alert.severity) {
+        (AlertState::Resolved, _) => tracing::info!(
+            rule = %alert.rule,
+            value = alert.value,
+            "Alert resolved: {}",
+            alert.summary
+        ),
+        (AlertState::Firing, AlertSeverity::Warning) => tracing::warn!(
+            rule = %alert.rule,
+            value = alert.value,
+            threshold = alert.threshold,
+            "Alert firing: {}",
+            alert.summary
+        ),
+        (AlertState::Firing, AlertSeverity::Critical) => tracing::error!(
+            rule = %alert.rule,
+            value = alert.value,
+            threshold = alert.threshold,
+            "Alert firing: {}",
+            alert.summary
+        ),
+    }
+}
+
+async fn post_alert(client: &reqwest::Client, url: &str, alert: &Alert) {
+    let result = client
+        .post(url)
+        .json(alert)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    if let Err(e) = result {
+        tracing::warn!(rule = %alert.rule, "Alert webhook failed: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::{Gauge, Registry};
+
+    fn gauge(registry: &Registry, name: &str, value: f64) -> Gauge {
+        let gauge = Gauge::new(name, name).unwrap();
+        gauge.set(value);
+        registry.register(Box::new(gauge.clone())).unwrap();
+        gauge
+    }
+
+    #[test]
+    fn test_rule_fires_after_duration_and_resolves() {
+        let registry = Registry::new();
+        let peers = gauge(&registry, "qc_peers_connected", 1.0);
+        let mut evaluator = AlertEvaluator::new(vec![AlertRule::below(
+            "LowPeerCount",
+            "qc_peers_connected",
+            3.0,
+        )
+        .with_duration(Duration::from_secs(60))]);
+        let start = Instant::now();
+
+        assert!(evaluator.evaluate(&registry.gather(), start).is_empty());
+        let later = start + Duration::from_secs(30);
+        assert!(evaluator.evaluate(&registry.gather(), later).is_empty());
+
+        let fired = evaluator.evaluate(&registry.gather(), start + Duration::from_secs(60));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].state, AlertState::Firing);
+        assert_eq!(fired[0].value, 1.0);
+
+        // Still firing: no repeat notification
+        let still = start + Duration::from_secs(90);
+        assert!(evaluator.evaluate(&registry.gather(), still).is_empty());
+
+        peers.set(5.0);
+        let resolved = evaluator.evaluate(&registry.gather(), start + Duration::from_secs(120));
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].state, AlertState::Resolved);
+    }
+
+    #[test]
+    fn test_difference_rule_and_missing_metrics() {
+        let registry = Registry::new();
+        gauge(&registry, "qc_storage_chain_height", 200.0);
+        gauge(&registry, "qc_finality_finalized_height", 100.0);
+        let mut evaluator = AlertEvaluator::new(AlertRule::defaults(64.0, 3.0, 10_000.0));
+
+        // Only FinalityLag has its metrics; it is pending, not yet firing
+        let now = Instant::now();
+        assert!(evaluator.evaluate(&registry.gather(), now).is_empty());
+
+        let fired = evaluator.evaluate(&registry.gather(), now + Duration::from_secs(120));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].rule, "FinalityLag");
+        assert_eq!(fired[0].severity, AlertSeverity::Critical);
+        assert_eq!(fired[0].value, 100.0);
+    }
+
+    #[test]
+    fn test_alert_wire_format() {
+        let rule = AlertRule::above("MempoolBacklog", "qc_mempool_transactions_pending", 10.0);
+        let json = serde_json::to_value(alert(&rule, AlertState::Firing, 12.0)).unwrap();
+        assert_eq!(json["rule"], "MempoolBacklog");
+        assert_eq!(json["severity"], "warning");
+        assert_eq!(json["state"], "firing");
+        assert_eq!(json["threshold"], 10.0);
+    }
+}
@@ -1,6 +1,14 @@
 //! Telemetry configuration from environment variables.
 
 use std::env;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::alerts::AlertRule;
+use crate::exporter::BasicAuth;
+use crate::log_control::{parse_sampling, LogControlSettings, RateLimit};
+use crate::TelemetryError;
 
 /// Configuration for the LGTM telemetry stack.
 #[derive(Debug, Clone)]
@@ -26,9 +34,36 @@ pub struct TelemetryConfig {
     /// Whether to enable JSON formatted logs
     pub json_logs: bool,
 
-    /// Prometheus metrics port
+    /// Prometheus metrics port (0 disables the `/metrics` server)
     pub metrics_port: u16,
 
+    /// Address the `/metrics` server binds to
+    pub metrics_bind_address: String,
+
+    /// Basic auth required by the `/metrics` server
+    pub metrics_basic_auth: Option<BasicAuth>,
+
+    /// File to write metrics to periodically (for air-gapped deployments)
+    pub metrics_file: Option<PathBuf>,
+
+    /// How often the metrics file is rewritten
+    pub metrics_file_interval: Duration,
+
+    /// Initial log sampling and rate limits (adjustable at runtime)
+    pub log_control: LogControlSettings,
+
+    /// Whether to evaluate alert rules inside the node
+    pub alerts_enabled: bool,
+
+    /// Alert rules to evaluate
+    pub alert_rules: Vec<AlertRule>,
+
+    /// How often alert rules are evaluated
+    pub alert_interval: Duration,
+
+    /// Webhook that receives alerts as JSON POSTs
+    pub alert_webhook: Option<String>,
+
     /// Network identifier (testnet, mainnet, devnet)
     pub network: String,
 }
@@ -44,6 +79,15 @@ impl Default for TelemetryConfig {
             console_output: true,
             json_logs: false,
             metrics_port: 9100,
+            metrics_bind_address: "0.0.0.0".to_string(),
+            metrics_basic_auth: None,
+            metrics_file: None,
+            metrics_file_interval: Duration::from_secs(15),
+            log_control: LogControlSettings::default(),
+            alerts_enabled: false,
+            alert_rules: AlertRule::defaults(64.0, 3.0, 10_000.0),
+            alert_interval: Duration::from_secs(15),
+            alert_webhook: None,
             network: "testnet".to_string(),
         }
     }
@@ -61,7 +105,20 @@ impl TelemetryConfig {
     /// - `QC_LOG_LEVEL` or `RUST_LOG`: Log level (default: info)
     /// - `QC_CONSOLE_OUTPUT`: Enable console output (default: true)
     /// - `QC_JSON_LOGS`: Enable JSON logs (default: false in dev, true in containers)
-    /// - `QC_METRICS_PORT`: Prometheus metrics port (default: 9100)
+    /// - `QC_METRICS_PORT`: Prometheus metrics port, 0 to disable (default: 9100)
+    /// - `QC_METRICS_BIND`: Metrics server bind address (default: 0.0.0.0)
+    /// - `QC_METRICS_USERNAME` / `QC_METRICS_PASSWORD`: Basic auth for `/metrics` (default: none)
+    /// - `QC_METRICS_FILE`: Also write metrics to this file (default: none)
+    /// - `QC_METRICS_FILE_INTERVAL_SECS`: Metrics file rewrite interval (default: 15)
+    /// - `QC_LOG_SAMPLING`: Keep 1 in N logs per target, e.g. `qc_15_cross_chain=100` (default: none)
+    /// - `QC_LOG_RATE_LIMIT`: Logs per second per call site, 0 to disable (default: 0)
+    /// - `QC_LOG_RATE_BURST`: Burst allowed per call site (default: the rate limit)
+    /// - `QC_ALERTS`: Evaluate alert rules in the node (default: false, true if a webhook is set)
+    /// - `QC_ALERT_WEBHOOK`: URL that receives alerts as JSON POSTs (default: none)
+    /// - `QC_ALERT_INTERVAL_SECS`: Alert evaluation interval (default: 15)
+    /// - `QC_ALERT_FINALITY_LAG`: Blocks between head and finalized height (default: 64)
+    /// - `QC_ALERT_MIN_PEERS`: Fewest connected peers before alerting (default: 3)
+    /// - `QC_ALERT_MEMPOOL_DEPTH`: Most pending transactions before alerting (default: 10000)
     /// - `QC_NETWORK`: Network name (default: testnet)
     pub fn from_env() -> Self {
         let is_container =
@@ -96,6 +153,61 @@ impl TelemetryConfig {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(9100),
 
+            metrics_bind_address: env::var("QC_METRICS_BIND")
+                .unwrap_or_else(|_| "0.0.0.0".to_string()),
+
+            metrics_basic_auth: match (
+                env::var("QC_METRICS_USERNAME"),
+                env::var("QC_METRICS_PASSWORD"),
+            ) {
+                (Ok(username), Ok(password)) => Some(BasicAuth::new(username, password)),
+                _ => None,
+            },
+
+            metrics_file: env::var("QC_METRICS_FILE").ok().map(PathBuf::from),
+
+            metrics_file_interval: env::var("QC_METRICS_FILE_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(Duration::from_secs(15)),
+
+            log_control: LogControlSettings {
+                sampling: env::var("QC_LOG_SAMPLING")
+                    .map(|v| parse_sampling(&v))
+                    .unwrap_or_default(),
+                rate_limit: env::var("QC_LOG_RATE_LIMIT")
+                    .ok()
+                    .and_then(|v| v.parse::<u32>().ok())
+                    .filter(|&per_second| per_second > 0)
+                    .map(|per_second| RateLimit {
+                        per_second,
+                        burst: env::var("QC_LOG_RATE_BURST")
+                            .ok()
+                            .and_then(|v| v.parse().ok())
+                            .filter(|&burst| burst > 0)
+                            .unwrap_or(per_second),
+                    }),
+            },
+
+            alerts_enabled: env::var("QC_ALERTS")
+                .map(|v| v.to_lowercase() == "true" || v == "1")
+                .unwrap_or_else(|_| env::var("QC_ALERT_WEBHOOK").is_ok()),
+
+            alert_rules: AlertRule::defaults(
+                env_f64("QC_ALERT_FINALITY_LAG").unwrap_or(64.0),
+                env_f64("QC_ALERT_MIN_PEERS").unwrap_or(3.0),
+                env_f64("QC_ALERT_MEMPOOL_DEPTH").unwrap_or(10_000.0),
+            ),
+
+            alert_interval: env::var("QC_ALERT_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(Duration::from_secs(15)),
+
+            alert_webhook: env::var("QC_ALERT_WEBHOOK").ok(),
+
             network: env::var("QC_NETWORK").unwrap_or_else(|_| "testnet".to_string()),
         }
     }
@@ -108,6 +220,17 @@ impl TelemetryConfig {
         config
     }
 
+    /// Socket address for the `/metrics` server.
+    pub fn metrics_addr(&self) -> Result<SocketAddr, TelemetryError> {
+        let ip: IpAddr = self.metrics_bind_address.parse().map_err(|_| {
+            TelemetryError::Config(format!(
+                "invalid metrics bind address: {}",
+                self.metrics_bind_address
+            ))
+        })?;
+        Ok(SocketAddr::new(ip, self.metrics_port))
+    }
+
     /// Get the full service name including subsystem.
     pub fn full_service_name(&self) -> String {
         if self.subsystem_id == "00" {
@@ -118,6 +241,10 @@ impl TelemetryConfig {
     }
 }
 
+fn env_f64(name: &str) -> Option<f64> {
+    env::var(name).ok().and_then(|v| v.parse().ok())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -128,6 +255,23 @@ mod tests {
         assert_eq!(config.service_name, "quantum-chain");
         assert_eq!(config.log_level, "info");
         assert_eq!(config.metrics_port, 9100);
+        assert_eq!(config.metrics_addr().unwrap().to_string(), "0.0.0.0:9100");
+        assert!(config.metrics_file.is_none());
+        assert_eq!(config.log_control, LogControlSettings::default());
+        assert!(!config.alerts_enabled);
+        assert_eq!(config.alert_rules.len(), 3);
+    }
+
+    #[test]
+    fn test_metrics_addr() {
+        let mut config = TelemetryConfig {
+            metrics_bind_address: "::1".to_string(),
+            ..TelemetryConfig::default()
+        };
+        assert_eq!(config.metrics_addr().unwrap().to_string(), "[::1]:9100");
+
+        config.metrics_bind_address = "localhost".to_string();
+        assert!(config.metrics_addr().is_err());
     }
 
     #[test]
@@ -36,8 +36,12 @@ use opentelemetry::{
     trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState},
     Context,
 };
+use opentelemetry_sdk::trace::{IdGenerator, RandomIdGenerator};
 use serde::{Deserialize, Serialize};
 
+/// W3C Trace Context version supported by `traceparent` parsing.
+const TRACEPARENT_VERSION: &str = "00";
+
 /// Trace context that can be serialized and sent across process boundaries.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PropagatedContext {
@@ -67,6 +71,61 @@ impl PropagatedContext {
         !self.trace_id.chars().all(|c| c == '0')
     }
 
+    /// Parse a W3C `traceparent` header (`00-<trace_id>-<span_id>-<flags>`).
+    ///
+    /// Returns `None` for malformed headers, unknown versions, or all-zero IDs,
+    /// in which case callers should start a new trace.
+    pub fn from_traceparent(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let (version, trace_id, span_id, flags) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() || version != TRACEPARENT_VERSION {
+            return None;
+        }
+
+        let trace_id = TraceId::from_hex(trace_id).ok()?;
+        let span_id = SpanId::from_hex(span_id).ok()?;
+        if trace_id == TraceId::INVALID || span_id == SpanId::INVALID || flags.len() != 2 {
+            return None;
+        }
+
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+            trace_flags: u8::from_str_radix(flags, 16).ok()?,
+            trace_state: None,
+        })
+    }
+
+    /// Format as a W3C `traceparent` header value.
+    pub fn to_traceparent(&self) -> String {
+        format!(
+            "{}-{}-{}-{:02x}",
+            TRACEPARENT_VERSION, self.trace_id, self.span_id, self.trace_flags
+        )
+    }
+
+    /// Context for a new span under this one.
+    ///
+    /// Keeps the trace ID and flags and assigns a fresh span ID. An invalid
+    /// (empty) parent starts a new sampled trace instead.
+    pub fn new_child(&self) -> Self {
+        let ids = RandomIdGenerator::default();
+        if !self.is_valid() {
+            return Self {
+                trace_id: ids.new_trace_id().to_string(),
+                span_id: ids.new_span_id().to_string(),
+                trace_flags: TraceFlags::SAMPLED.to_u8(),
+                trace_state: None,
+            };
+        }
+
+        Self {
+            span_id: ids.new_span_id().to_string(),
+            ..self.clone()
+        }
+    }
+
     /// Convert to OpenTelemetry Context for creating child spans.
     pub fn to_context(&self) -> TraceContext {
         if !self.is_valid() {
@@ -244,6 +303,46 @@ mod tests {
         assert_eq!(propagated.span_id, back.span_id);
     }
 
+    #[test]
+    fn test_traceparent_roundtrip() {
+        let header = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+        let ctx = PropagatedContext::from_traceparent(header).unwrap();
+
+        assert_eq!(ctx.trace_id, "0af7651916cd43dd8448eb211c80319c");
+        assert_eq!(ctx.span_id, "b7ad6b7169203331");
+        assert_eq!(ctx.trace_flags, 1);
+        assert_eq!(ctx.to_traceparent(), header);
+    }
+
+    #[test]
+    fn test_invalid_traceparent() {
+        for header in [
+            "invalid",
+            "01-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+            "00-00000000000000000000000000000000-b7ad6b7169203331-01",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01-extra",
+        ] {
+            assert!(
+                PropagatedContext::from_traceparent(header).is_none(),
+                "{header}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_new_child_keeps_trace() {
+        let parent = PropagatedContext::from_traceparent(
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+        )
+        .unwrap();
+        let child = parent.new_child();
+        assert_eq!(child.trace_id, parent.trace_id);
+        assert_ne!(child.span_id, parent.span_id);
+
+        // No parent starts a new trace
+        assert!(PropagatedContext::empty().new_child().is_valid());
+    }
+
     #[test]
     fn test_extract_current_empty() {
         // No active span, should return empty context
@@ -0,0 +1,324 @@
+//! Crash reports.
+//!
+//! The panic hook installed by `CrashReporter` writes a JSON report for
+//! every panic to a local directory, so post-mortem debugging does not
+//! depend on terminal scrollback. Each report holds:
+//!
+//! - the panic message, location and backtrace
+//! - the panicking subsystem (from the panic location, or the current span)
+//! - the most recent event-bus events and the last known chain head, which
+//!   the node runtime feeds in with `record_event` and `set_chain_head`
+//! - build information
+//!
+//! The report is also logged at `error` level (target `crash_report`) so it
+//! reaches Loki when the process survives long enough to ship it.
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, TryLockError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Recent events kept for the report
+const RECENT_EVENTS: usize = 32;
+
+lazy_static! {
+    static ref CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext::default());
+}
+
+/// An event-bus event seen shortly before the crash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentEvent {
+    /// Topic name (e.g. `block.storage`)
+    pub topic: String,
+    /// Publishing subsystem ID
+    pub source: u8,
+    /// Unix timestamp (milliseconds)
+    pub at_ms: u64,
+}
+
+/// Last block the node stored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainHead {
+    /// Block height
+    pub height: u64,
+    /// Block hash (hex)
+    pub hash: String,
+}
+
+/// Build the crash happened on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    /// Node version
+    pub version: String,
+    /// `debug` or `release`
+    pub profile: String,
+    /// Target OS
+    pub os: String,
+    /// Target architecture
+    pub arch: String,
+}
+
+/// Everything captured about one panic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrashReport {
+    /// Unix timestamp (milliseconds)
+    pub timestamp_ms: u64,
+    /// Subsystem that panicked (e.g. `qc-08-consensus`), if known
+    pub subsystem: Option<String>,
+    /// Panicking thread name
+    pub thread: String,
+    /// Panic message
+    pub message: String,
+    /// `file:line:column` of the panic
+    pub location: Option<String>,
+    /// Recent event-bus events, oldest first
+    pub recent_events: Vec<RecentEvent>,
+    /// Last known chain head
+    pub chain_head: Option<ChainHead>,
+    /// Build information
+    pub build: BuildInfo,
+    /// Captured backtrace (empty unless `RUST_BACKTRACE` is set)
+    pub backtrace: String,
+}
+
+#[derive(Debug, Default)]
+struct CrashContext {
+    recent_events: VecDeque<RecentEvent>,
+    chain_head: Option<ChainHead>,
+}
+
+/// Record an event-bus event for the next crash report.
+pub fn record_event(topic: &str, source: u8) {
+    let mut context = CONTEXT.lock().unwrap_or_else(|e| e.into_inner());
+    if context.recent_events.len() == RECENT_EVENTS {
+        context.recent_events.pop_front();
+    }
+    context.recent_events.push_back(RecentEvent {
+        topic: topic.to_string(),
+        source,
+        at_ms: now_ms(),
+    });
+}
+
+/// Record the latest stored block for the next crash report.
+pub fn set_chain_head(height: u64, hash: impl Into<String>) {
+    CONTEXT.lock().unwrap_or_else(|e| e.into_inner()).chain_head = Some(ChainHead {
+        height,
+        hash: hash.into(),
+    });
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Installs the crash-reporting panic hook.
+#[derive(Debug, Clone)]
+pub struct CrashReporter {
+    dir: PathBuf,
+    version: String,
+    log: bool,
+}
+
+impl CrashReporter {
+    /// Write reports to `dir` (created on first crash).
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            log: true,
+        }
+    }
+
+    /// Version recorded in reports (defaults to this crate's version).
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Whether reports are also logged for Loki (default: true).
+    pub fn with_log(mut self, log: bool) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Install the panic hook. The previous hook still runs afterwards.
+    pub fn install(self) {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            self.report(info);
+            previous(info);
+        }));
+    }
+
+    fn report(&self, info: &PanicHookInfo<'_>) {
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "<non-string panic payload>".to_string());
+
+        let report = self.build_report(message, location);
+        match write_report(&self.dir, &report) {
+            Ok(path) => eprintln!("Crash report written to {}", path.display()),
+            Err(e) => eprintln!("Failed to write crash report: {}", e),
+        }
+        if self.log {
+            tracing::error!(
+                target: "crash_report",
+                subsystem = report.subsystem.as_deref().unwrap_or("unknown"),
+                location = report.location.as_deref().unwrap_or("unknown"),
+                report = %serde_json::to_string(&report).unwrap_or_default(),
+                "Node panicked: {}",
+                report.message
+            );
+        }
+    }
+
+    fn build_report(&self, message: String, location: Option<String>) -> CrashReport {
+        // Never block in the hook: the panic may have happened while the
+        // context was locked
+        let context = match CONTEXT.try_lock() {
+            Ok(context) => Some(context),
+            Err(TryLockError::Poisoned(e)) => Some(e.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        };
+        let (recent_events, chain_head) = context
+            .map(|c| {
+                (
+                    c.recent_events.iter().cloned().collect(),
+                    c.chain_head.clone(),
+                )
+            })
+            .unwrap_or_default();
+        let subsystem = location
+            .as_deref()
+            .and_then(subsystem_from_path)
+            .or_else(|| {
+                tracing::Span::current()
+                    .metadata()
+                    .and_then(|m| subsystem_from_target(m.target()))
+            });
+
+        CrashReport {
+            timestamp_ms: now_ms(),
+            subsystem,
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("<unnamed>")
+                .to_string(),
+            message,
+            location,
+            recent_events,
+            chain_head,
+            build: BuildInfo {
+                version: self.version.clone(),
+                profile: if cfg!(debug_assertions) {
+                    "debug"
+                } else {
+                    "release"
+                }
+                .to_string(),
+                os: std::env::consts::OS.to_string(),
+                arch: std::env::consts::ARCH.to_string(),
+            },
+            backtrace: Backtrace::capture().to_string(),
+        }
+    }
+}
+
+/// Write `report` to `dir`, returning the file path.
+fn write_report(dir: &Path, report: &CrashReport) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!(
+        "crash-{}-{}.json",
+        report.timestamp_ms,
+        std::process::id()
+    ));
+    let json = serde_json::to_vec_pretty(report).map_err(std::io::Error::other)?;
+    std::fs::write(&path, json)?;
+    Ok(path)
+}
+
+/// Crate directory in a source path, e.g. `crates/qc-08-consensus/src/x.rs`.
+fn subsystem_from_path(path: &str) -> Option<String> {
+    path.split(['/', '\\'])
+        .find(|segment| is_subsystem_crate(segment))
+        .map(str::to_string)
+}
+
+/// Crate name in a tracing target, e.g. `qc_08_consensus::service`.
+fn subsystem_from_target(target: &str) -> Option<String> {
+    let krate = target.split("::").next()?.replace('_', "-");
+    is_subsystem_crate(&krate).then_some(krate)
+}
+
+fn is_subsystem_crate(name: &str) -> bool {
+    let numbered = name
+        .strip_prefix("qc-")
+        .and_then(|rest| rest.get(..3))
+        .is_some_and(|id| id[..2].bytes().all(|b| b.is_ascii_digit()) && id.ends_with('-'));
+    numbered || name == "node-runtime"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsystem_detection() {
+        assert_eq!(
+            subsystem_from_path("crates/qc-08-consensus/src/service/mod.rs").as_deref(),
+            Some("qc-08-consensus")
+        );
+        assert_eq!(
+            subsystem_from_path("crates/node-runtime/src/main.rs").as_deref(),
+            Some("node-runtime")
+        );
+        assert_eq!(
+            subsystem_from_path("/rustc/library/core/src/option.rs"),
+            None
+        );
+        assert_eq!(
+            subsystem_from_target("qc_06_mempool::domain::pool").as_deref(),
+            Some("qc-06-mempool")
+        );
+        assert_eq!(subsystem_from_target("tokio::runtime"), None);
+    }
+
+    #[test]
+    fn test_report_includes_context() {
+        record_event("block.storage", 2);
+        set_chain_head(42, "0xabc");
+
+        let reporter = CrashReporter::new("unused").with_version("9.9.9");
+        let report = reporter.build_report(
+            "boom".to_string(),
+            Some("crates/qc-09-finality/src/service.rs:10:5".to_string()),
+        );
+        assert_eq!(report.subsystem.as_deref(), Some("qc-09-finality"));
+        assert_eq!(report.build.version, "9.9.9");
+        assert!(report
+            .recent_events
+            .iter()
+            .any(|e| e.topic == "block.storage" && e.source == 2));
+        assert_eq!(report.chain_head.as_ref().map(|h| h.height), Some(42));
+
+        let dir = std::env::temp_dir().join(format!("qc-crash-{}", std::process::id()));
+        let path = write_report(&dir, &report).unwrap();
+        let written: CrashReport = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        assert_eq!(written, report);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,335 @@
+//! Prometheus exposition: a `/metrics` HTTP endpoint and a file exporter.
+//!
+//! The HTTP server answers `GET /metrics` with the global registry in text
+//! format, optionally behind HTTP basic auth. Anything else gets 404.
+//!
+//! For air-gapped deployments where nothing can scrape the node, the file
+//! exporter periodically writes the same text to a file (atomically, via a
+//! temporary file and rename), suitable for node_exporter's textfile
+//! collector or for shipping by other means.
+
+use base64::Engine;
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use crate::metrics::encode_metrics;
+use crate::TelemetryError;
+
+/// Largest request head accepted (request line plus headers).
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// Time allowed for a client to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Prometheus text exposition content type.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// HTTP basic auth credentials for the metrics endpoint.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BasicAuth {
+    /// Username
+    pub username: String,
+    /// Password
+    pub password: String,
+}
+
+impl BasicAuth {
+    /// Create credentials.
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Expected `Authorization` header value.
+    fn header_value(&self) -> String {
+        let credentials = format!("{}:{}", self.username, self.password);
+        format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(credentials)
+        )
+    }
+}
+
+impl fmt::Debug for BasicAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BasicAuth")
+            .field("username", &self.username)
+            .field("password", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Running `/metrics` server. The server stops when this is dropped.
+pub struct MetricsServer {
+    local_addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl MetricsServer {
+    /// Bind `addr` and serve `/metrics` in a background task.
+    pub async fn start(addr: SocketAddr, auth: Option<BasicAuth>) -> Result<Self, TelemetryError> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| TelemetryError::MetricsInit(format!("bind {}: {}", addr, e)))?;
+        let local_addr = listener
+            .local_addr()
+            .map_err(|e| TelemetryError::MetricsInit(e.to_string()))?;
+        let expected_auth = auth.map(|a| a.header_value());
+
+        let task = tokio::spawn(accept_loop(listener, expected_auth));
+
+        tracing::info!("Metrics server listening on http://{}/metrics", local_addr);
+        Ok(Self { local_addr, task })
+    }
+
+    /// Address the server is bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Accept connections until the task is aborted.
+async fn accept_loop(listener: TcpListener, expected_auth: Option<String>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                tokio::spawn(serve(stream, peer, expected_auth.clone()));
+            }
+            Err(e) => tracing::warn!("Metrics server accept failed: {}", e),
+        }
+    }
+}
+
+async fn serve(stream: TcpStream, peer: SocketAddr, expected_auth: Option<String>) {
+    if let Err(e) = handle_connection(stream, expected_auth.as_deref()).await {
+        tracing::debug!("Metrics request from {} failed: {}", peer, e);
+    }
+}
+
+/// Serve one request and close the connection.
+async fn handle_connection(
+    mut stream: TcpStream,
+    expected_auth: Option<&str>,
+) -> std::io::Result<()> {
+    let head = match tokio::time::timeout(READ_TIMEOUT, read_head(&mut stream)).await {
+        Ok(head) => head?,
+        Err(_) => return Ok(()),
+    };
+    let Some(head) = head else {
+        return write_response(&mut stream, "431 Request Header Fields Too Large", &[], "").await;
+    };
+
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+
+    if path.split('?').next() != Some("/metrics") {
+        return write_response(&mut stream, "404 Not Found", &[], "").await;
+    }
+    if method != "GET" {
+        return write_response(
+            &mut stream,
+            "405 Method Not Allowed",
+            &[("Allow", "GET")],
+            "",
+        )
+        .await;
+    }
+    if let Some(expected) = expected_auth {
+        let provided = lines
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("authorization"))
+            .map(|(_, value)| value.trim())
+            .unwrap_or_default();
+        if !constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+            return write_response(
+                &mut stream,
+                "401 Unauthorized",
+                &[("WWW-Authenticate", "Basic realm=\"metrics\"")],
+                "",
+            )
+            .await;
+        }
+    }
+
+    match encode_metrics() {
+        Ok(body) => {
+            write_response(
+                &mut stream,
+                "200 OK",
+                &[("Content-Type", CONTENT_TYPE)],
+                &body,
+            )
+            .await
+        }
+        Err(e) => {
+            tracing::warn!("Failed to encode metrics: {}", e);
+            write_response(&mut stream, "500 Internal Server Error", &[], "").await
+        }
+    }
+}
+
+/// Read up to the end of the request head. `None` if it is too large.
+async fn read_head(stream: &mut TcpStream) -> std::io::Result<Option<String>> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if buf.windows(4).any(|w| w == b"\r\n\r\n") {
+            break;
+        }
+        if buf.len() > MAX_REQUEST_HEAD {
+            return Ok(None);
+        }
+    }
+    Ok(Some(String::from_utf8_lossy(&buf).into_owned()))
+}
+
+async fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    headers: &[(&str, &str)],
+    body: &str,
+) -> std::io::Result<()> {
+    let mut response = format!("HTTP/1.1 {}\r\n", status);
+    for (name, value) in headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    response.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    ));
+    response.push_str(body);
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Write the current metrics to `path`, replacing it atomically.
+pub fn write_metrics_file(path: &Path) -> Result<(), TelemetryError> {
+    let body = encode_metrics()?;
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    std::fs::write(&tmp, body)
+        .and_then(|()| std::fs::rename(&tmp, path))
+        .map_err(|e| TelemetryError::MetricsInit(format!("{}: {}", path.display(), e)))
+}
+
+/// Running file exporter. Stops when dropped.
+pub struct MetricsFileExporter {
+    task: JoinHandle<()>,
+}
+
+impl MetricsFileExporter {
+    /// Write metrics to `path` now and then every `interval`.
+    pub fn start(path: PathBuf, interval: Duration) -> Self {
+        tracing::info!("Writing metrics to {} every {:?}", path.display(), interval);
+        let task = tokio::spawn(export_loop(path, interval));
+        Self { task }
+    }
+}
+
+/// Rewrite the metrics file every `interval` until the task is aborted.
+async fn export_loop(path: PathBuf, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        if let Err(e) = write_metrics_file(&path) {
+            tracing::warn!("Failed to write metrics file: {}", e);
+        }
+    }
+}
+
+impl Drop for MetricsFileExporter {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::BLOCKS_STORED;
+
+    async fn get(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_with_basic_auth() {
+        let _ = crate::register_metrics();
+        BLOCKS_STORED.inc();
+
+        let auth = BasicAuth::new("prometheus", "secret");
+        let header = auth.header_value();
+        let server = MetricsServer::start("127.0.0.1:0".parse().unwrap(), Some(auth))
+            .await
+            .unwrap();
+        let addr = server.local_addr();
+
+        let denied = get(addr, "GET /metrics HTTP/1.1\r\nHost: x\r\n\r\n").await;
+        assert!(denied.starts_with("HTTP/1.1 401"));
+
+        let request = format!("GET /metrics HTTP/1.1\r\nauthorization: {}\r\n\r\n", header);
+        let ok = get(addr, &request).await;
+        assert!(ok.starts_with("HTTP/1.1 200"));
+        assert!(ok.contains(CONTENT_TYPE));
+        assert!(ok.contains("qc_storage_blocks_stored_total"));
+
+        let missing = get(
+            addr,
+            &format!("GET / HTTP/1.1\r\nauthorization: {}\r\n\r\n", header),
+        )
+        .await;
+        assert!(missing.starts_with("HTTP/1.1 404"));
+    }
+
+    #[test]
+    fn test_write_metrics_file() {
+        let _ = crate::register_metrics();
+        let dir = std::env::temp_dir().join(format!("qc-metrics-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("node.prom");
+
+        write_metrics_file(&path).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("qc_storage_blocks_stored_total"));
+        assert!(!dir.join("node.prom.tmp").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_basic_auth_debug_redacts_password() {
+        let debug = format!("{:?}", BasicAuth::new("prometheus", "secret"));
+        assert!(debug.contains("prometheus"));
+        assert!(!debug.contains("secret"));
+    }
+}
@@ -41,21 +41,42 @@
 #![warn(missing_docs)]
 #![allow(missing_docs)] // TODO: Add documentation for all public items
 
+mod alerts;
 mod config;
 mod context;
+mod crash;
+mod exporter;
+mod log_control;
+mod log_tail;
 mod logging;
 mod metrics;
+mod subsystem_metrics;
 mod tracing_setup;
 
+pub use alerts::{
+    Alert, AlertEvaluator, AlertManager, AlertRule, AlertSeverity, AlertState, Condition,
+};
 pub use config::TelemetryConfig;
 pub use context::{PropagatedContext, TraceContext};
+pub use crash::{
+    record_event, set_chain_head, BuildInfo, ChainHead, CrashReport, CrashReporter, RecentEvent,
+};
+pub use exporter::{write_metrics_file, BasicAuth, MetricsFileExporter, MetricsServer};
+pub use log_control::{
+    log_control, log_level, set_log_level, validate_log_level, LogControl, LogControlLayer,
+    LogControlSettings, RateLimit,
+};
+pub use log_tail::{log_tail, LogRecord, LogTail, LogTailLayer, RECENT_LOGS};
 pub use logging::StructuredLogger;
 pub use metrics::{
     register_metrics, MetricsHandle, BLOCKS_FINALIZED, BLOCKS_STORED, BLOCKS_VALIDATED,
-    CONSENSUS_ROUNDS, EVENT_BUS_MESSAGES_RECEIVED, EVENT_BUS_MESSAGES_SENT, FINALITY_EPOCHS,
-    MEMPOOL_BYTES, MEMPOOL_SIZE, PEERS_CONNECTED, PEERS_DISCOVERED, SIGNATURE_FAILURES,
-    SIGNATURE_VERIFICATIONS, SUBSYSTEM_ERRORS, TRANSACTIONS_INDEXED, TRANSACTIONS_RECEIVED,
+    CONSENSUS_ROUNDS, EVENT_BUS_DLQ_DEPTH, EVENT_BUS_MESSAGES_RECEIVED, EVENT_BUS_MESSAGES_SENT,
+    EVENT_BUS_SUBSCRIBER_DROPPED, EVENT_BUS_SUBSCRIBER_LAG, EVENT_BUS_SUBSCRIBER_QUEUE_DEPTH,
+    EVENT_BUS_TOPIC_LATENCY, FINALITY_EPOCHS, MEMPOOL_BYTES, MEMPOOL_SIZE, PEERS_CONNECTED,
+    PEERS_DISCOVERED, SIGNATURE_FAILURES, SIGNATURE_VERIFICATIONS, SUBSYSTEM_ERRORS,
+    TRANSACTIONS_INDEXED, TRANSACTIONS_RECEIVED,
 };
+pub use subsystem_metrics::{SubsystemMetrics, SubsystemMetricsBuilder};
 pub use tracing_setup::TracingGuard;
 
 use thiserror::Error;
@@ -94,6 +115,29 @@ pub async fn init_telemetry(config: TelemetryConfig) -> Result<TelemetryGuard, T
     // Initialize metrics first (synchronous)
     let metrics_handle = register_metrics()?;
 
+    // Expose metrics for scraping, and/or write them to a file
+    let metrics_server = if config.metrics_port == 0 {
+        None
+    } else {
+        Some(MetricsServer::start(config.metrics_addr()?, config.metrics_basic_auth.clone()).await?)
+    };
+    let metrics_file = config
+        .metrics_file
+        .clone()
+        .map(|path| MetricsFileExporter::start(path, config.metrics_file_interval));
+
+    // Evaluate alert rules in-process (for deployments without Grafana)
+    let alerts = config.alerts_enabled.then(|| {
+        AlertManager::start(
+            config.alert_rules.clone(),
+            config.alert_interval,
+            config.alert_webhook.clone(),
+        )
+    });
+
+    // Sampling and rate limits for the log layer
+    log_control().apply(config.log_control.clone())?;
+
     // Initialize tracing (OpenTelemetry -> Tempo)
     let tracing_guard = tracing_setup::init_tracing(&config).await?;
 
@@ -103,6 +147,9 @@ pub async fn init_telemetry(config: TelemetryConfig) -> Result<TelemetryGuard, T
     Ok(TelemetryGuard {
         _tracing: tracing_guard,
         _metrics: metrics_handle,
+        _metrics_server: metrics_server,
+        _metrics_file: metrics_file,
+        alerts,
     })
 }
 
@@ -110,13 +157,24 @@ pub async fn init_telemetry(config: TelemetryConfig) -> Result<TelemetryGuard, T
 pub struct TelemetryGuard {
     _tracing: TracingGuard,
     _metrics: MetricsHandle,
+    _metrics_server: Option<MetricsServer>,
+    _metrics_file: Option<MetricsFileExporter>,
+    alerts: Option<AlertManager>,
+}
+
+impl TelemetryGuard {
+    /// Receive alerts as they fire and resolve (None if alerting is disabled).
+    pub fn subscribe_alerts(&self) -> Option<tokio::sync::broadcast::Receiver<Alert>> {
+        self.alerts.as_ref().map(AlertManager::subscribe)
+    }
 }
 
 impl Drop for TelemetryGuard {
     fn drop(&mut self) {
         tracing::info!("Shutting down telemetry...");
         // TracingGuard handles OpenTelemetry shutdown
-        // MetricsHandle handles Prometheus shutdown
+        // MetricsHandle handles Prometheus shutdown; the metrics server, file
+        // exporter and alert manager stop when dropped
     }
 }
 
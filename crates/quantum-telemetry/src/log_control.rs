@@ -0,0 +1,422 @@
+//! Log sampling, rate limiting and the runtime log level.
+//!
+//! Under load some call sites log on every block or message and flood Loki.
+//! Two controls cut the volume without touching the call sites:
+//!
+//! - **Sampling**: keep 1 in N events for a target prefix, e.g.
+//!   `qc_15_cross_chain=100`. The longest matching prefix wins.
+//! - **Rate limiting**: a token bucket per call site. When a call site has
+//!   been throttled, the next event it is allowed to log is preceded by a
+//!   "suppressed N similar messages" summary.
+//!
+//! Warnings and errors are never sampled or rate limited.
+//!
+//! The level filter (`QC_LOG_LEVEL`) is installed behind a reload handle, so
+//! `set_log_level` can change it without restarting the node.
+//!
+//! The settings are process-wide. They are loaded from `TelemetryConfig` at
+//! init and can be changed at runtime through `log_control()` (the API
+//! gateway exposes this as `admin_setLogControl`).
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::TelemetryError;
+
+/// Target of the "suppressed N similar messages" summaries (never throttled)
+const SUMMARY_TARGET: &str = "quantum_telemetry::log_control";
+
+lazy_static! {
+    static ref LOG_CONTROL: LogControl = LogControl::new(LogControlSettings::default());
+}
+
+/// Level filter of the telemetry subscriber, set once it is installed
+static LOG_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Process-wide log control used by the telemetry subscriber.
+pub fn log_control() -> &'static LogControl {
+    &LOG_CONTROL
+}
+
+/// Wrap the subscriber's level filter so `set_log_level` can replace it.
+pub(crate) fn reloadable_filter(filter: EnvFilter) -> reload::Layer<EnvFilter, Registry> {
+    let (layer, handle) = reload::Layer::new(filter);
+    let _ = LOG_FILTER.set(handle);
+    layer
+}
+
+fn parse_log_level(directives: &str) -> Result<EnvFilter, TelemetryError> {
+    if directives.trim().is_empty() {
+        return Err(TelemetryError::Config(
+            "log level must not be empty".to_string(),
+        ));
+    }
+    EnvFilter::try_new(directives)
+        .map_err(|e| TelemetryError::Config(format!("invalid log level {:?}: {}", directives, e)))
+}
+
+/// Check a level filter such as `info,qc_08_consensus=debug`.
+pub fn validate_log_level(directives: &str) -> Result<(), TelemetryError> {
+    parse_log_level(directives).map(|_| ())
+}
+
+/// Current level filter, or None before telemetry is initialized.
+pub fn log_level() -> Option<String> {
+    LOG_FILTER
+        .get()?
+        .with_current(|filter| filter.to_string())
+        .ok()
+}
+
+/// Replace the level filter of the running subscriber.
+pub fn set_log_level(directives: &str) -> Result<(), TelemetryError> {
+    let filter = parse_log_level(directives)?;
+    let handle = LOG_FILTER.get().ok_or_else(|| {
+        TelemetryError::Config("log level cannot change before telemetry init".to_string())
+    })?;
+    handle
+        .reload(filter)
+        .map_err(|e| TelemetryError::Config(e.to_string()))
+}
+
+/// Token bucket limit applied to each call site.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimit {
+    /// Sustained events per second
+    pub per_second: u32,
+    /// Events allowed in a burst
+    pub burst: u32,
+}
+
+/// Sampling and rate limit settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogControlSettings {
+    /// Keep 1 in N events, by target prefix
+    #[serde(default)]
+    pub sampling: BTreeMap<String, u32>,
+    /// Per-call-site rate limit (None = unlimited)
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl LogControlSettings {
+    /// Check that every rate is at least 1.
+    pub fn validate(&self) -> Result<(), TelemetryError> {
+        if let Some((target, _)) = self.sampling.iter().find(|(_, n)| **n == 0) {
+            return Err(TelemetryError::Config(format!(
+                "sampling rate for {} must be at least 1",
+                target
+            )));
+        }
+        if let Some(limit) = self.rate_limit {
+            if limit.per_second == 0 || limit.burst == 0 {
+                return Err(TelemetryError::Config(
+                    "rate limit and burst must be at least 1".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Longest sampling prefix matching `target`, with its rate.
+    fn sampling_for(&self, target: &str) -> Option<(&str, u32)> {
+        self.sampling
+            .iter()
+            .filter(|(prefix, _)| matches_prefix(target, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, n)| (prefix.as_str(), *n))
+    }
+}
+
+/// Whether `target` is `prefix` or a module below it.
+fn matches_prefix(target: &str, prefix: &str) -> bool {
+    target
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// Parse a sampling spec such as `qc_15_cross_chain=100,qc_05=10`.
+///
+/// Malformed entries are skipped.
+pub(crate) fn parse_sampling(spec: &str) -> BTreeMap<String, u32> {
+    spec.split(',')
+        .filter_map(|entry| entry.split_once('='))
+        .filter_map(|(target, n)| Some((target.trim().to_string(), n.trim().parse().ok()?)))
+        .filter(|(target, n)| !target.is_empty() && *n > 0)
+        .collect()
+}
+
+/// What to do with an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Admission {
+    /// Drop it
+    Drop,
+    /// Emit it, after a summary if `suppressed` is non-zero
+    Emit { suppressed: u64 },
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled: Instant,
+    suppressed: u64,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    settings: LogControlSettings,
+    /// Events seen per sampling prefix
+    seen: HashMap<String, u64>,
+    /// Token buckets per call site
+    buckets: HashMap<&'static str, Bucket>,
+}
+
+/// Runtime-adjustable sampling and rate limits.
+#[derive(Debug)]
+pub struct LogControl {
+    state: Mutex<State>,
+}
+
+impl LogControl {
+    fn new(settings: LogControlSettings) -> Self {
+        Self {
+            state: Mutex::new(State {
+                settings,
+                ..State::default()
+            }),
+        }
+    }
+
+    /// Current settings.
+    pub fn settings(&self) -> LogControlSettings {
+        self.lock().settings.clone()
+    }
+
+    /// Replace the settings, resetting sample counters and buckets.
+    pub fn apply(&self, settings: LogControlSettings) -> Result<(), TelemetryError> {
+        settings.validate()?;
+        *self.lock() = State {
+            settings,
+            ..State::default()
+        };
+        Ok(())
+    }
+
+    /// Keep 1 in `n` events for `target` (1 removes sampling).
+    pub fn set_sampling(&self, target: &str, n: u32) -> Result<(), TelemetryError> {
+        let mut settings = self.settings();
+        if n == 1 {
+            settings.sampling.remove(target);
+        } else {
+            settings.sampling.insert(target.to_string(), n);
+        }
+        self.apply(settings)
+    }
+
+    /// Set or remove the per-call-site rate limit.
+    pub fn set_rate_limit(&self, limit: Option<RateLimit>) -> Result<(), TelemetryError> {
+        let mut settings = self.settings();
+        settings.rate_limit = limit;
+        self.apply(settings)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Decide whether an event from call site `site` is emitted.
+    fn admit(&self, level: Level, target: &str, site: &'static str, now: Instant) -> Admission {
+        if level <= Level::WARN || target == SUMMARY_TARGET {
+            return Admission::Emit { suppressed: 0 };
+        }
+
+        let mut state = self.lock();
+        let State {
+            settings,
+            seen,
+            buckets,
+        } = &mut *state;
+
+        if let Some((prefix, n)) = settings.sampling_for(target) {
+            let count = seen.entry(prefix.to_string()).or_default();
+            *count += 1;
+            if (*count - 1) % u64::from(n) != 0 {
+                return Admission::Drop;
+            }
+        }
+
+        let Some(limit) = settings.rate_limit else {
+            return Admission::Emit { suppressed: 0 };
+        };
+        let bucket = buckets.entry(site).or_insert_with(|| Bucket {
+            tokens: f64::from(limit.burst),
+            refilled: now,
+            suppressed: 0,
+        });
+        let elapsed = now.saturating_duration_since(bucket.refilled).as_secs_f64();
+        bucket.tokens =
+            (bucket.tokens + elapsed * f64::from(limit.per_second)).min(f64::from(limit.burst));
+        bucket.refilled = now;
+
+        if bucket.tokens < 1.0 {
+            bucket.suppressed += 1;
+            return Admission::Drop;
+        }
+        bucket.tokens -= 1.0;
+        Admission::Emit {
+            suppressed: std::mem::take(&mut bucket.suppressed),
+        }
+    }
+}
+
+/// Subscriber layer that applies `log_control()` to every event.
+pub struct LogControlLayer {
+    control: &'static LogControl,
+}
+
+impl LogControlLayer {
+    /// Layer driven by the process-wide `log_control()`.
+    pub fn new() -> Self {
+        Self {
+            control: log_control(),
+        }
+    }
+}
+
+impl Default for LogControlLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogControlLayer {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        let metadata = event.metadata();
+        match self.control.admit(
+            *metadata.level(),
+            metadata.target(),
+            metadata.name(),
+            Instant::now(),
+        ) {
+            Admission::Drop => false,
+            Admission::Emit { suppressed } => {
+                if suppressed > 0 {
+                    tracing::info!(
+                        target: SUMMARY_TARGET,
+                        suppressed,
+                        call_site = metadata.name(),
+                        log_target = metadata.target(),
+                        "suppressed {} similar messages",
+                        suppressed
+                    );
+                }
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn emitted(admissions: &[Admission]) -> usize {
+        admissions
+            .iter()
+            .filter(|a| matches!(a, Admission::Emit { .. }))
+            .count()
+    }
+
+    #[test]
+    fn test_sampling_by_longest_prefix() {
+        let control = LogControl::new(LogControlSettings {
+            sampling: parse_sampling("qc_15_cross_chain=10, qc_15_cross_chain::htlc=2,bad"),
+            rate_limit: None,
+        });
+        let now = Instant::now();
+        let admit = |target| control.admit(Level::INFO, target, "site", now);
+
+        let bridge: Vec<_> = (0..100)
+            .map(|_| admit("qc_15_cross_chain::service"))
+            .collect();
+        assert_eq!(emitted(&bridge), 10);
+        let htlc: Vec<_> = (0..10).map(|_| admit("qc_15_cross_chain::htlc")).collect();
+        assert_eq!(emitted(&htlc), 5);
+
+        // Other targets, and warnings, are untouched
+        assert_eq!(
+            emitted(&(0..5).map(|_| admit("qc_150")).collect::<Vec<_>>()),
+            5
+        );
+        let warn = control.admit(Level::WARN, "qc_15_cross_chain", "site", now);
+        assert_eq!(warn, Admission::Emit { suppressed: 0 });
+    }
+
+    #[test]
+    fn test_rate_limit_reports_suppressed() {
+        let control = LogControl::new(LogControlSettings::default());
+        control
+            .set_rate_limit(Some(RateLimit {
+                per_second: 1,
+                burst: 2,
+            }))
+            .unwrap();
+        let start = Instant::now();
+        let admit = |site, now| control.admit(Level::INFO, "qc_08_consensus", site, now);
+
+        let burst: Vec<_> = (0..5).map(|_| admit("block", start)).collect();
+        assert_eq!(emitted(&burst), 2);
+        // Buckets are per call site
+        assert_eq!(admit("vote", start), Admission::Emit { suppressed: 0 });
+
+        let later = start + Duration::from_secs(1);
+        assert_eq!(admit("block", later), Admission::Emit { suppressed: 3 });
+        assert_eq!(admit("block", later), Admission::Drop);
+    }
+
+    #[test]
+    fn test_log_level_reload() {
+        assert!(validate_log_level("info,qc_08_consensus=debug").is_ok());
+        assert!(validate_log_level("").is_err());
+        assert!(validate_log_level("qc_08_consensus=loud").is_err());
+
+        let _layer = reloadable_filter(EnvFilter::new("info"));
+        assert_eq!(log_level().as_deref(), Some("info"));
+        set_log_level("warn,qc_17_block_production=trace").unwrap();
+        assert_eq!(
+            log_level().as_deref(),
+            Some("qc_17_block_production=trace,warn")
+        );
+        assert!(set_log_level("=").is_err());
+        assert_eq!(
+            log_level().as_deref(),
+            Some("qc_17_block_production=trace,warn")
+        );
+    }
+
+    #[test]
+    fn test_runtime_adjustment() {
+        let control = LogControl::new(LogControlSettings::default());
+        control.set_sampling("qc_05_block_propagation", 4).unwrap();
+        assert_eq!(control.settings().sampling["qc_05_block_propagation"], 4);
+        control.set_sampling("qc_05_block_propagation", 1).unwrap();
+        assert!(control.settings().sampling.is_empty());
+
+        assert!(control.set_sampling("qc_05_block_propagation", 0).is_err());
+        let zero_burst = RateLimit {
+            per_second: 10,
+            burst: 0,
+        };
+        assert!(control.set_rate_limit(Some(zero_burst)).is_err());
+        assert_eq!(control.settings(), LogControlSettings::default());
+    }
+}
@@ -0,0 +1,184 @@
+//! Recent log lines, kept in memory for live tailing.
+//!
+//! The telemetry subscriber copies every event that passes the level filter
+//! and log control into a ring buffer and a broadcast channel. The API
+//! gateway serves them to operators (`/logs` on the WebSocket port), so a
+//! node can be tailed without shell access or a Loki deployment.
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt::{self, Write as _};
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Lines kept for clients that connect after they were logged
+pub const RECENT_LOGS: usize = 1000;
+
+/// Lines buffered per live subscriber before it starts missing some
+const SUBSCRIBER_BUFFER: usize = 1024;
+
+lazy_static! {
+    static ref LOG_TAIL: LogTail = LogTail::new(RECENT_LOGS);
+}
+
+/// Process-wide log tail fed by the telemetry subscriber.
+pub fn log_tail() -> &'static LogTail {
+    &LOG_TAIL
+}
+
+/// One logged event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRecord {
+    /// Unix time of the event, in milliseconds
+    pub timestamp_ms: u64,
+    /// Level, lowercase (`trace` .. `error`)
+    pub level: String,
+    /// Module path or explicit target
+    pub target: String,
+    /// Message followed by the event's fields as `name=value`
+    pub message: String,
+}
+
+/// Ring buffer of recent lines plus a channel of new ones.
+#[derive(Debug)]
+pub struct LogTail {
+    capacity: usize,
+    recent: Mutex<VecDeque<LogRecord>>,
+    tx: broadcast::Sender<LogRecord>,
+}
+
+impl LogTail {
+    fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(SUBSCRIBER_BUFFER);
+        Self {
+            capacity,
+            recent: Mutex::new(VecDeque::with_capacity(capacity)),
+            tx,
+        }
+    }
+
+    /// The `limit` most recent lines, oldest first.
+    pub fn recent(&self, limit: usize) -> Vec<LogRecord> {
+        let recent = self.lock();
+        let skip = recent.len().saturating_sub(limit);
+        recent.iter().skip(skip).cloned().collect()
+    }
+
+    /// Receive every line logged from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<LogRecord> {
+        self.tx.subscribe()
+    }
+
+    fn push(&self, record: LogRecord) {
+        {
+            let mut recent = self.lock();
+            if recent.len() == self.capacity {
+                recent.pop_front();
+            }
+            recent.push_back(record.clone());
+        }
+        // No receivers is fine
+        let _ = self.tx.send(record);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<LogRecord>> {
+        self.recent
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Subscriber layer that copies events into a `LogTail`.
+pub struct LogTailLayer {
+    tail: &'static LogTail,
+}
+
+impl LogTailLayer {
+    /// Layer feeding the process-wide `log_tail()`.
+    pub fn new() -> Self {
+        Self { tail: log_tail() }
+    }
+}
+
+impl Default for LogTailLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogTailLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = LineVisitor::default();
+        event.record(&mut visitor);
+
+        self.tail.push(LogRecord {
+            timestamp_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            level: metadata.level().as_str().to_ascii_lowercase(),
+            target: metadata.target().to_string(),
+            message: visitor.message + &visitor.fields,
+        });
+    }
+}
+
+/// Formats an event as `message name=value ...`
+#[derive(Default)]
+struct LineVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for LineVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_tail_keeps_recent_and_broadcasts() {
+        let tail: &'static LogTail = Box::leak(Box::new(LogTail::new(2)));
+        let mut rx = tail.subscribe();
+        let subscriber = tracing_subscriber::registry().with(LogTailLayer { tail });
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "qc_01", "first");
+            tracing::warn!(target: "qc_08", round = 7, peer = "abc", "view change");
+            tracing::debug!(target: "qc_08", "third");
+        });
+
+        let recent = tail.recent(10);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].level, "warn");
+        assert_eq!(recent[0].target, "qc_08");
+        assert_eq!(recent[0].message, "view change round=7 peer=abc");
+        assert_eq!(recent[1].message, "third");
+        assert_eq!(tail.recent(1), vec![recent[1].clone()]);
+
+        assert_eq!(rx.try_recv().unwrap().message, "first");
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! All metrics follow the naming convention: `qc_<subsystem>_<metric>_<unit>`
 //!
+//! Metrics shared across the node are declared here; subsystem-specific ones
+//! are registered at init through `SubsystemMetrics`.
+//!
 //! ## Metric Types
 //!
 //! - **Counter**: Monotonically increasing value (e.g., blocks_validated_total)
@@ -10,8 +13,8 @@
 
 use lazy_static::lazy_static;
 use prometheus::{
-    exponential_buckets, Counter, CounterVec, Encoder, Gauge, Histogram, HistogramVec, Opts,
-    Registry, TextEncoder,
+    exponential_buckets, Counter, CounterVec, Encoder, Gauge, GaugeVec, Histogram, HistogramVec,
+    Opts, Registry, TextEncoder,
 };
 use std::sync::Arc;
 
@@ -196,6 +199,39 @@ lazy_static! {
         ).buckets(exponential_buckets(0.0001, 2.0, 12).unwrap())
     ).expect("metric creation failed");
 
+    /// Events waiting in the dead letter queue
+    pub static ref EVENT_BUS_DLQ_DEPTH: Gauge = Gauge::new(
+        "qc_eventbus_dlq_depth",
+        "Events waiting in the dead letter queue"
+    ).expect("metric creation failed");
+
+    /// Events published but not yet received, per subscriber
+    pub static ref EVENT_BUS_SUBSCRIBER_LAG: GaugeVec = GaugeVec::new(
+        Opts::new("qc_eventbus_subscriber_lag", "Events waiting for each subscriber"),
+        &["subscriber", "policy"]
+    ).expect("metric creation failed");
+
+    /// Events lost to backpressure, per subscriber
+    pub static ref EVENT_BUS_SUBSCRIBER_DROPPED: GaugeVec = GaugeVec::new(
+        Opts::new("qc_eventbus_subscriber_dropped", "Events each subscriber lost to backpressure"),
+        &["subscriber", "policy"]
+    ).expect("metric creation failed");
+
+    /// Publish-to-deliver latency, per topic (instrumented bus only)
+    pub static ref EVENT_BUS_TOPIC_LATENCY: HistogramVec = HistogramVec::new(
+        prometheus::HistogramOpts::new(
+            "qc_eventbus_topic_delivery_latency_seconds",
+            "Time from publish to receipt by a subscriber, per topic"
+        ).buckets(exponential_buckets(0.00001, 2.0, 18).unwrap()),
+        &["topic"]
+    ).expect("metric creation failed");
+
+    /// Events still queued for a subscriber when it received one (instrumented bus only)
+    pub static ref EVENT_BUS_SUBSCRIBER_QUEUE_DEPTH: GaugeVec = GaugeVec::new(
+        Opts::new("qc_eventbus_subscriber_queue_depth", "Events queued for each subscriber at its last receive"),
+        &["subscriber"]
+    ).expect("metric creation failed");
+
     // =========================================================================
     // ERROR METRICS
     // =========================================================================
@@ -246,6 +282,11 @@ pub fn register_metrics() -> Result<MetricsHandle, TelemetryError> {
         Box::new(EVENT_BUS_MESSAGES_SENT.clone()),
         Box::new(EVENT_BUS_MESSAGES_RECEIVED.clone()),
         Box::new(EVENT_BUS_LATENCY.clone()),
+        Box::new(EVENT_BUS_DLQ_DEPTH.clone()),
+        Box::new(EVENT_BUS_SUBSCRIBER_LAG.clone()),
+        Box::new(EVENT_BUS_SUBSCRIBER_DROPPED.clone()),
+        Box::new(EVENT_BUS_TOPIC_LATENCY.clone()),
+        Box::new(EVENT_BUS_SUBSCRIBER_QUEUE_DEPTH.clone()),
         // Errors
         Box::new(SUBSYSTEM_ERRORS.clone()),
     ];
@@ -0,0 +1,280 @@
+//! Per-subsystem metric namespaces.
+//!
+//! Subsystems declare their own counters and histograms at init instead of
+//! adding constants to the global list. Every metric is named
+//! `qc_<subsystem>_<metric>` and carries a constant `subsystem_id` label.
+//!
+//! Registration is idempotent: a handler task that restarts and registers
+//! the same metric again gets the existing handle back, so its values
+//! carry over instead of failing with a duplicate-registration error.
+//!
+//! ```rust,ignore
+//! let metrics = SubsystemMetrics::builder(7, "bloom")
+//!     .counter("filters_built_total", "Bloom filters built")
+//!     .histogram(
+//!         "filter_build_duration_seconds",
+//!         "Time spent building filters",
+//!         exponential_buckets(0.0001, 2.0, 12).unwrap(),
+//!     )
+//!     .register()?;
+//!
+//! metrics.counter("filters_built_total").unwrap().inc();
+//! ```
+
+use lazy_static::lazy_static;
+use prometheus::{Counter, Histogram, HistogramOpts, Opts};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::metrics::REGISTRY;
+use crate::TelemetryError;
+
+lazy_static! {
+    /// Metrics registered through `SubsystemMetrics`, by full name
+    static ref REGISTERED: Mutex<HashMap<String, Registered>> = Mutex::new(HashMap::new());
+}
+
+/// A registered metric handle
+#[derive(Clone)]
+enum Registered {
+    Counter(Counter),
+    Histogram(Histogram),
+}
+
+impl Registered {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Counter(_) => "counter",
+            Self::Histogram(_) => "histogram",
+        }
+    }
+}
+
+/// A metric declared on the builder
+enum Declared {
+    Counter { help: String },
+    Histogram { help: String, buckets: Vec<f64> },
+}
+
+/// Builder for a subsystem's metrics
+pub struct SubsystemMetricsBuilder {
+    subsystem_id: u8,
+    namespace: String,
+    declared: Vec<(String, Declared)>,
+}
+
+impl SubsystemMetricsBuilder {
+    /// Declare a counter.
+    pub fn counter(mut self, name: &str, help: &str) -> Self {
+        self.declared.push((
+            name.to_string(),
+            Declared::Counter {
+                help: help.to_string(),
+            },
+        ));
+        self
+    }
+
+    /// Declare a histogram with the given buckets.
+    pub fn histogram(mut self, name: &str, help: &str, buckets: Vec<f64>) -> Self {
+        self.declared.push((
+            name.to_string(),
+            Declared::Histogram {
+                help: help.to_string(),
+                buckets,
+            },
+        ));
+        self
+    }
+
+    /// Register all declared metrics with the global registry.
+    pub fn register(self) -> Result<SubsystemMetrics, TelemetryError> {
+        let mut metrics = SubsystemMetrics {
+            subsystem_id: self.subsystem_id,
+            namespace: self.namespace,
+            counters: HashMap::new(),
+            histograms: HashMap::new(),
+        };
+
+        for (name, declared) in self.declared {
+            match declared {
+                Declared::Counter { help } => {
+                    metrics.register_counter(&name, &help)?;
+                }
+                Declared::Histogram { help, buckets } => {
+                    metrics.register_histogram(&name, &help, buckets)?;
+                }
+            }
+        }
+
+        Ok(metrics)
+    }
+}
+
+/// Metrics owned by one subsystem
+pub struct SubsystemMetrics {
+    subsystem_id: u8,
+    namespace: String,
+    counters: HashMap<String, Counter>,
+    histograms: HashMap<String, Histogram>,
+}
+
+impl SubsystemMetrics {
+    /// Start declaring metrics for a subsystem.
+    ///
+    /// `namespace` is the short subsystem name used in metric names
+    /// (e.g. `consensus`, `storage`).
+    pub fn builder(subsystem_id: u8, namespace: &str) -> SubsystemMetricsBuilder {
+        SubsystemMetricsBuilder {
+            subsystem_id,
+            namespace: namespace.to_string(),
+            declared: Vec::new(),
+        }
+    }
+
+    /// Subsystem ID these metrics are labelled with.
+    pub fn subsystem_id(&self) -> u8 {
+        self.subsystem_id
+    }
+
+    /// Full metric name for `name` in this namespace.
+    pub fn full_name(&self, name: &str) -> String {
+        format!("qc_{}_{}", self.namespace, name)
+    }
+
+    /// Get a registered counter.
+    pub fn counter(&self, name: &str) -> Option<&Counter> {
+        self.counters.get(name)
+    }
+
+    /// Get a registered histogram.
+    pub fn histogram(&self, name: &str) -> Option<&Histogram> {
+        self.histograms.get(name)
+    }
+
+    /// Register a counter after init (returns the existing one if already registered).
+    pub fn register_counter(&mut self, name: &str, help: &str) -> Result<Counter, TelemetryError> {
+        let opts = Opts::new(self.full_name(name), help)
+            .const_label("subsystem_id", self.subsystem_id.to_string());
+        let registered = register_once(opts.name.clone(), || {
+            Counter::with_opts(opts).map(Registered::Counter)
+        })?;
+        let Registered::Counter(counter) = registered else {
+            return Err(kind_conflict(&self.full_name(name), &registered, "counter"));
+        };
+        self.counters.insert(name.to_string(), counter.clone());
+        Ok(counter)
+    }
+
+    /// Register a histogram after init (returns the existing one if already registered).
+    pub fn register_histogram(
+        &mut self,
+        name: &str,
+        help: &str,
+        buckets: Vec<f64>,
+    ) -> Result<Histogram, TelemetryError> {
+        let opts = HistogramOpts::new(self.full_name(name), help)
+            .const_label("subsystem_id", self.subsystem_id.to_string())
+            .buckets(buckets);
+        let registered = register_once(opts.common_opts.name.clone(), || {
+            Histogram::with_opts(opts).map(Registered::Histogram)
+        })?;
+        let Registered::Histogram(histogram) = registered else {
+            return Err(kind_conflict(
+                &self.full_name(name),
+                &registered,
+                "histogram",
+            ));
+        };
+        self.histograms.insert(name.to_string(), histogram.clone());
+        Ok(histogram)
+    }
+}
+
+/// Return the metric registered under `name`, creating and registering it if new.
+fn register_once(
+    name: String,
+    create: impl FnOnce() -> prometheus::Result<Registered>,
+) -> Result<Registered, TelemetryError> {
+    let mut registered = REGISTERED.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(existing) = registered.get(&name) {
+        return Ok(existing.clone());
+    }
+
+    let metric = create().map_err(|e| TelemetryError::MetricsInit(e.to_string()))?;
+    let collector: Box<dyn prometheus::core::Collector> = match &metric {
+        Registered::Counter(c) => Box::new(c.clone()),
+        Registered::Histogram(h) => Box::new(h.clone()),
+    };
+    REGISTRY
+        .register(collector)
+        .map_err(|e| TelemetryError::MetricsInit(format!("{}: {}", name, e)))?;
+
+    registered.insert(name, metric.clone());
+    Ok(metric)
+}
+
+fn kind_conflict(name: &str, existing: &Registered, wanted: &str) -> TelemetryError {
+    TelemetryError::MetricsInit(format!(
+        "{} is already registered as a {}, not a {}",
+        name,
+        existing.kind(),
+        wanted
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::encode_metrics;
+
+    #[test]
+    fn test_namespaced_registration() {
+        let metrics = SubsystemMetrics::builder(7, "test_bloom")
+            .counter("filters_built_total", "Bloom filters built")
+            .histogram(
+                "filter_build_duration_seconds",
+                "Time spent building filters",
+                vec![0.001, 0.01, 0.1],
+            )
+            .register()
+            .unwrap();
+
+        metrics.counter("filters_built_total").unwrap().inc();
+        metrics
+            .histogram("filter_build_duration_seconds")
+            .unwrap()
+            .observe(0.005);
+        assert!(metrics.counter("missing").is_none());
+
+        let text = encode_metrics().unwrap();
+        assert!(text.contains("qc_test_bloom_filters_built_total{subsystem_id=\"7\"} 1"));
+        assert!(text.contains("qc_test_bloom_filter_build_duration_seconds_count"));
+    }
+
+    #[test]
+    fn test_reregistration_is_deduplicated() {
+        let register = || {
+            SubsystemMetrics::builder(6, "test_mempool")
+                .counter("restarts_total", "Handler restarts")
+                .register()
+                .unwrap()
+        };
+
+        // A restarted handler task registers again and keeps the same series
+        let first = register();
+        first.counter("restarts_total").unwrap().inc();
+        let second = register();
+        second.counter("restarts_total").unwrap().inc();
+        assert_eq!(first.counter("restarts_total").unwrap().get(), 2.0);
+
+        // Same name with a different type is rejected
+        let mut conflicting = SubsystemMetrics::builder(6, "test_mempool")
+            .register()
+            .unwrap();
+        let err = conflicting
+            .register_histogram("restarts_total", "Handler restarts", vec![1.0])
+            .unwrap_err();
+        assert!(err.to_string().contains("already registered as a counter"));
+    }
+}
@@ -13,7 +13,7 @@ use opentelemetry_sdk::{
 };
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
-use crate::{TelemetryConfig, TelemetryError};
+use crate::{LogControlLayer, LogTailLayer, TelemetryConfig, TelemetryError};
 
 /// Guard that shuts down the tracer provider on drop.
 pub struct TracingGuard {
@@ -57,10 +57,11 @@ pub async fn init_tracing(config: &TelemetryConfig) -> Result<TracingGuard, Tele
     let tracer = provider.tracer(config.full_service_name());
     let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
 
-    // Create env filter
+    // Create env filter, reloadable through `set_log_level`
     let env_filter = EnvFilter::try_from_default_env()
         .or_else(|_| EnvFilter::try_new(&config.log_level))
         .map_err(|e| TelemetryError::TracerInit(e.to_string()))?;
+    let env_filter = crate::log_control::reloadable_filter(env_filter);
 
     // Build subscriber based on configuration
     if config.json_logs {
@@ -75,6 +76,8 @@ pub async fn init_tracing(config: &TelemetryConfig) -> Result<TracingGuard, Tele
         if config.console_output {
             tracing_subscriber::registry()
                 .with(env_filter)
+                .with(LogControlLayer::new())
+                .with(LogTailLayer::new())
                 .with(otel_layer)
                 .with(json_layer)
                 .try_init()
@@ -82,6 +85,8 @@ pub async fn init_tracing(config: &TelemetryConfig) -> Result<TracingGuard, Tele
         } else {
             tracing_subscriber::registry()
                 .with(env_filter)
+                .with(LogControlLayer::new())
+                .with(LogTailLayer::new())
                 .with(otel_layer)
                 .try_init()
                 .map_err(|e| TelemetryError::TracerInit(e.to_string()))?;
@@ -98,6 +103,8 @@ pub async fn init_tracing(config: &TelemetryConfig) -> Result<TracingGuard, Tele
         if config.console_output {
             tracing_subscriber::registry()
                 .with(env_filter)
+                .with(LogControlLayer::new())
+                .with(LogTailLayer::new())
                 .with(otel_layer)
                 .with(fmt_layer)
                 .try_init()
@@ -105,6 +112,8 @@ pub async fn init_tracing(config: &TelemetryConfig) -> Result<TracingGuard, Tele
         } else {
             tracing_subscriber::registry()
                 .with(env_filter)
+                .with(LogControlLayer::new())
+                .with(LogTailLayer::new())
                 .with(otel_layer)
                 .try_init()
                 .map_err(|e| TelemetryError::TracerInit(e.to_string()))?;
@@ -0,0 +1,492 @@
+//! # Backpressure
+//!
+//! What happens when a subscriber falls `capacity` events behind.
+//!
+//! | Policy        | Publisher                  | Slow subscriber                 |
+//! |---------------|----------------------------|---------------------------------|
+//! | `DropOldest`  | never waits                | loses the oldest events         |
+//! | `Block`       | waits for a free slot      | loses nothing                   |
+//! | `SpillToDisk` | never waits                | overflow is buffered on disk    |
+//!
+//! `DropOldest` is the default and suits telemetry-style consumers.
+//! `Block` is for consensus-critical consumers that must see every event;
+//! a stalled `Block` subscriber stalls every publisher, so its handler must
+//! never publish and wait on its own input. `SpillToDisk` suits consumers
+//! that handle bursts slowly but must not lose events, such as assemblers.
+//!
+//! The policy applies per priority lane (see `priority`): `capacity` is
+//! the buffer of each lane, and a full bulk lane never holds up critical
+//! events.
+
+use crate::events::{BlockchainEvent, EventFilter};
+use crate::priority::Lanes;
+use crate::DEFAULT_CHANNEL_CAPACITY;
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{mpsc, Notify};
+use tracing::{error, warn};
+
+/// How a subscription handles a full buffer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+    /// Overwrite the oldest buffered events.
+    #[default]
+    DropOldest,
+    /// Make publishers wait until the subscriber catches up.
+    Block,
+    /// Buffer overflow in a spill file under this directory.
+    SpillToDisk(PathBuf),
+}
+
+impl BackpressurePolicy {
+    /// Short policy name, used as a metrics label.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DropOldest => "drop_oldest",
+            Self::Block => "block",
+            Self::SpillToDisk(_) => "spill_to_disk",
+        }
+    }
+}
+
+/// Options of a subscription.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriptionOptions {
+    /// Subscriber name for metrics. Empty means the filter's description.
+    pub name: String,
+    /// What to do when the buffer is full.
+    pub policy: BackpressurePolicy,
+    /// Events buffered in memory.
+    ///
+    /// `DropOldest` subscriptions share the bus channel, whose capacity is
+    /// fixed when the bus is created, and ignore this.
+    pub capacity: usize,
+}
+
+impl Default for SubscriptionOptions {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            policy: BackpressurePolicy::default(),
+            capacity: DEFAULT_CHANNEL_CAPACITY,
+        }
+    }
+}
+
+impl SubscriptionOptions {
+    /// Options for a named subscriber with the default policy.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the backpressure policy.
+    #[must_use]
+    pub fn with_policy(mut self, policy: BackpressurePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set the in-memory buffer size.
+    #[must_use]
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+}
+
+/// Lag and loss counters of one subscriber.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriberStats {
+    /// Subscriber name.
+    pub name: String,
+    /// Policy name (see `BackpressurePolicy::name`).
+    pub policy: &'static str,
+    /// Events published but not yet received.
+    pub pending: usize,
+    /// Events received.
+    pub delivered: u64,
+    /// Events lost to backpressure.
+    pub dropped: u64,
+    /// Events written to the spill file.
+    pub spilled: u64,
+}
+
+/// Counters shared by a subscription and the bus.
+#[derive(Debug)]
+pub(crate) struct SubscriberMetrics {
+    name: String,
+    policy: &'static str,
+    pending: AtomicUsize,
+    delivered: AtomicU64,
+    dropped: AtomicU64,
+    spilled: AtomicU64,
+    /// Set when the subscription is dropped.
+    closed: AtomicBool,
+}
+
+impl SubscriberMetrics {
+    pub(crate) fn new(name: String, policy: &BackpressurePolicy) -> Self {
+        Self {
+            name,
+            policy: policy.name(),
+            pending: AtomicUsize::new(0),
+            delivered: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            spilled: AtomicU64::new(0),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn record_delivered(&self, pending: usize) {
+        self.delivered.fetch_add(1, Ordering::Relaxed);
+        self.pending.store(pending, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_dropped(&self, count: u64) {
+        self.dropped.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_pending(&self, pending: usize) {
+        self.pending.store(pending, Ordering::Relaxed);
+    }
+
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn snapshot(&self) -> SubscriberStats {
+        SubscriberStats {
+            name: self.name.clone(),
+            policy: self.policy,
+            pending: self.pending.load(Ordering::Relaxed),
+            delivered: self.delivered.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            spilled: self.spilled.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Overflow file of a spill queue.
+#[derive(Debug)]
+struct SpillFile {
+    writer: File,
+    reader: BufReader<File>,
+    /// Events written but not read back yet.
+    pending: usize,
+}
+
+#[derive(Debug, Default)]
+struct SpillState {
+    /// Oldest events; everything in `file` is newer.
+    memory: VecDeque<BlockchainEvent>,
+    file: Option<SpillFile>,
+    /// The bus is gone; nothing more will be pushed.
+    closed: bool,
+}
+
+/// Subscriber queue that overflows to a file.
+#[derive(Debug)]
+pub(crate) struct SpillQueue {
+    capacity: usize,
+    path: PathBuf,
+    state: Mutex<SpillState>,
+    notify: Notify,
+}
+
+impl SpillQueue {
+    pub(crate) fn new(dir: &Path, name: &str, capacity: usize) -> Self {
+        let file_name = format!("{}-{}.spill", sanitize(name), uuid::Uuid::new_v4());
+        Self {
+            capacity: capacity.max(1),
+            path: dir.join(file_name),
+            state: Mutex::new(SpillState::default()),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Queue an event, spilling it once memory is full.
+    pub(crate) fn push(&self, event: BlockchainEvent, metrics: &SubscriberMetrics) {
+        let mut state = self.lock();
+        // Once spilling, newer events must queue behind the file
+        if state.file.is_none() && state.memory.len() < self.capacity {
+            state.memory.push_back(event);
+        } else if let Err(e) = self.spill(&mut state, &event) {
+            error!(path = %self.path.display(), error = %e, "Event spill failed, event dropped");
+            metrics.record_dropped(1);
+        } else {
+            metrics.spilled.fetch_add(1, Ordering::Relaxed);
+        }
+        drop(state);
+
+        self.notify.notify_one();
+    }
+
+    /// Take the oldest queued event.
+    pub(crate) fn pop(&self) -> Option<BlockchainEvent> {
+        let mut state = self.lock();
+        if state.memory.is_empty() {
+            self.refill(&mut state);
+        }
+        state.memory.pop_front()
+    }
+
+    /// Wait until an event is pushed or the queue is closed.
+    pub(crate) async fn wait(&self) {
+        self.notify.notified().await;
+    }
+
+    pub(crate) fn pending(&self) -> usize {
+        Self::len(&self.lock())
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.lock().closed
+    }
+
+    pub(crate) fn close(&self) {
+        self.lock().closed = true;
+        self.notify.notify_one();
+    }
+
+    fn len(state: &SpillState) -> usize {
+        state.memory.len() + state.file.as_ref().map_or(0, |file| file.pending)
+    }
+
+    fn spill(&self, state: &mut SpillState, event: &BlockchainEvent) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+
+        if state.file.is_none() {
+            state.file = Some(self.create_file()?);
+        }
+        let Some(file) = state.file.as_mut() else {
+            return Ok(());
+        };
+        file.writer.write_all(&line)?;
+        file.pending += 1;
+        Ok(())
+    }
+
+    fn create_file(&self) -> std::io::Result<SpillFile> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let writer = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(&self.path)?;
+        let reader = BufReader::new(File::open(&self.path)?);
+        Ok(SpillFile {
+            writer,
+            reader,
+            pending: 0,
+        })
+    }
+
+    /// Move up to `capacity` spilled events back into memory.
+    fn refill(&self, state: &mut SpillState) {
+        let Some(file) = state.file.as_mut() else {
+            return;
+        };
+
+        let mut line = String::new();
+        while file.pending > 0 && state.memory.len() < self.capacity {
+            line.clear();
+            if let Err(e) = file.reader.read_line(&mut line) {
+                error!(path = %self.path.display(), error = %e, "Reading spilled events failed");
+                break;
+            }
+            file.pending -= 1;
+            match serde_json::from_str(&line) {
+                Ok(event) => state.memory.push_back(event),
+                Err(e) => warn!(error = %e, "Skipping unreadable spilled event"),
+            }
+        }
+
+        if file.pending == 0 {
+            state.file = None;
+            self.remove_file();
+        }
+    }
+
+    fn remove_file(&self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!(path = %self.path.display(), error = %e, "Spill file not removed");
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SpillState> {
+        // Queue operations leave the state consistent at every step
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Drop for SpillQueue {
+    fn drop(&mut self) {
+        if self.lock().file.take().is_some() {
+            self.remove_file();
+        }
+    }
+}
+
+/// Subscriber the bus delivers to directly (`Block` and `SpillToDisk`).
+pub(crate) struct QueuedSubscriber {
+    filter: EventFilter,
+    sink: QueueSink,
+    metrics: Arc<SubscriberMetrics>,
+}
+
+enum QueueSink {
+    Blocking(Lanes<mpsc::Sender<BlockchainEvent>>),
+    Spill(Lanes<SpillWriter>),
+}
+
+/// Write side of a spill queue; closes it when the bus lets go.
+struct SpillWriter(Arc<SpillQueue>);
+
+impl Drop for SpillWriter {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+impl QueuedSubscriber {
+    pub(crate) fn blocking(
+        filter: EventFilter,
+        senders: Lanes<mpsc::Sender<BlockchainEvent>>,
+        metrics: Arc<SubscriberMetrics>,
+    ) -> Self {
+        Self {
+            filter,
+            sink: QueueSink::Blocking(senders),
+            metrics,
+        }
+    }
+
+    pub(crate) fn spill(
+        filter: EventFilter,
+        queues: &Lanes<Arc<SpillQueue>>,
+        metrics: Arc<SubscriberMetrics>,
+    ) -> Self {
+        Self {
+            filter,
+            sink: QueueSink::Spill(queues.map(|queue| SpillWriter(Arc::clone(queue)))),
+            metrics,
+        }
+    }
+
+    /// Whether this subscriber is live and wants the event.
+    pub(crate) fn wants(&self, event: &BlockchainEvent) -> bool {
+        !self.metrics.is_closed() && self.filter.matches(event)
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.metrics.is_closed()
+    }
+
+    /// Hand over an event in its priority lane, waiting for room in that
+    /// lane under `Block`.
+    ///
+    /// Returns false if the subscription is gone.
+    pub(crate) async fn deliver(&self, event: BlockchainEvent) -> bool {
+        let priority = event.priority();
+        match &self.sink {
+            QueueSink::Blocking(senders) => {
+                if senders.get(priority).send(event).await.is_err() {
+                    return false;
+                }
+            }
+            QueueSink::Spill(writers) => writers.get(priority).0.push(event, &self.metrics),
+        }
+        self.metrics.set_pending(self.pending());
+        true
+    }
+
+    /// Events handed over but not yet received.
+    pub(crate) fn pending(&self) -> usize {
+        match &self.sink {
+            QueueSink::Blocking(senders) => senders
+                .iter()
+                .map(|sender| sender.max_capacity() - sender.capacity())
+                .sum(),
+            QueueSink::Spill(writers) => writers.iter().map(|writer| writer.0.pending()).sum(),
+        }
+    }
+}
+
+/// Keep subscriber names usable in file names.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_types::entities::Hash;
+
+    fn stored(height: u64) -> BlockchainEvent {
+        BlockchainEvent::BlockStored {
+            block_height: height,
+            block_hash: Hash::default(),
+        }
+    }
+
+    fn height(event: &BlockchainEvent) -> u64 {
+        match event {
+            BlockchainEvent::BlockStored { block_height, .. } => *block_height,
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_spill_queue_preserves_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = BackpressurePolicy::SpillToDisk(dir.path().to_path_buf());
+        let metrics = SubscriberMetrics::new("assembler".into(), &policy);
+        let queue = SpillQueue::new(dir.path(), "assembler", 2);
+
+        for h in 0..5 {
+            queue.push(stored(h), &metrics);
+        }
+        assert_eq!(queue.pending(), 5);
+        assert_eq!(metrics.snapshot().spilled, 3);
+
+        // Pushing while the file drains still queues behind it
+        assert_eq!(queue.pop().map(|e| height(&e)), Some(0));
+        assert_eq!(queue.pop().map(|e| height(&e)), Some(1));
+        queue.push(stored(5), &metrics);
+        let rest: Vec<_> = std::iter::from_fn(|| queue.pop())
+            .map(|e| height(&e))
+            .collect();
+        assert_eq!(rest, vec![2, 3, 4, 5]);
+
+        // The drained spill file is removed
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,379 @@
+//! # Dead Letter Queue
+//!
+//! Retains events published on the `dlq.critical` topic so operators can
+//! inspect and recover them after the fact, instead of losing whatever no
+//! live subscriber happened to see.
+//!
+//! Entries stay queued until they are acknowledged (dropped) or replayed
+//! (republished as the event that originally failed, see
+//! `InMemoryEventBus::replay_dead_letter`). Retention is bounded by entry
+//! count and age; the oldest entries are evicted first.
+
+use crate::events::BlockchainEvent;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use tokio::sync::watch;
+use tracing::warn;
+
+/// Default maximum number of retained dead letters.
+pub const DEFAULT_DLQ_MAX_ENTRIES: usize = 10_000;
+
+/// Default maximum age of a retained dead letter.
+pub const DEFAULT_DLQ_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Errors from dead letter operations.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DeadLetterError {
+    /// No queued entry has this ID (never existed, acknowledged or evicted).
+    #[error("Dead letter {0} not found")]
+    NotFound(u64),
+
+    /// The entry does not carry an event that can be republished.
+    #[error("Dead letter {0} cannot be replayed")]
+    NotReplayable(u64),
+}
+
+/// Retention limits of the dead letter queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeadLetterConfig {
+    /// Maximum number of retained entries.
+    pub max_entries: usize,
+    /// Maximum age of a retained entry.
+    pub max_age: Duration,
+}
+
+impl Default for DeadLetterConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_DLQ_MAX_ENTRIES,
+            max_age: DEFAULT_DLQ_MAX_AGE,
+        }
+    }
+}
+
+impl DeadLetterConfig {
+    /// Set the maximum number of retained entries.
+    #[must_use]
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Set the maximum age of a retained entry.
+    #[must_use]
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+}
+
+/// A queued dead letter.
+#[derive(Debug, Clone)]
+pub struct DeadLetterEntry {
+    /// Queue-assigned ID, increasing in arrival order.
+    pub id: u64,
+    /// The event published on the DLQ topic.
+    pub event: BlockchainEvent,
+    /// When the event reached the queue.
+    pub received_at: SystemTime,
+}
+
+impl DeadLetterEntry {
+    /// The event that failed, if this entry carries enough to rebuild it.
+    ///
+    /// Only API queries are replayable: a `CriticalError` describes a
+    /// failure but not the message that caused it.
+    #[must_use]
+    pub fn original_event(&self) -> Option<BlockchainEvent> {
+        match &self.event {
+            BlockchainEvent::ApiQueryDeadLetter {
+                correlation_id,
+                target,
+                method,
+                params,
+                ..
+            } => Some(BlockchainEvent::ApiQuery {
+                correlation_id: correlation_id.clone(),
+                target: target.clone(),
+                method: method.clone(),
+                params: params.clone(),
+                trace_parent: None,
+            }),
+            _ => None,
+        }
+    }
+
+    fn age(&self) -> Duration {
+        self.received_at.elapsed().unwrap_or_default()
+    }
+}
+
+/// Counters of the dead letter queue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeadLetterStats {
+    /// Entries currently queued.
+    pub depth: usize,
+    /// Entries ever queued.
+    pub received: u64,
+    /// Entries acknowledged.
+    pub acked: u64,
+    /// Entries replayed.
+    pub replayed: u64,
+    /// Entries dropped by the retention limits.
+    pub evicted: u64,
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    entries: VecDeque<DeadLetterEntry>,
+    next_id: u64,
+    stats: DeadLetterStats,
+}
+
+/// Bounded store of dead letters.
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    config: DeadLetterConfig,
+    state: Mutex<QueueState>,
+    /// Current depth, for metrics exporters.
+    depth: watch::Sender<usize>,
+}
+
+impl Default for DeadLetterQueue {
+    fn default() -> Self {
+        Self::new(DeadLetterConfig::default())
+    }
+}
+
+impl DeadLetterQueue {
+    /// Create an empty queue.
+    #[must_use]
+    pub fn new(config: DeadLetterConfig) -> Self {
+        let (depth, _) = watch::channel(0);
+        Self {
+            config,
+            state: Mutex::new(QueueState::default()),
+            depth,
+        }
+    }
+
+    /// Get the retention limits.
+    #[must_use]
+    pub fn config(&self) -> &DeadLetterConfig {
+        &self.config
+    }
+
+    /// Queue a dead letter, evicting the oldest entries beyond the limits.
+    pub(crate) fn push(&self, event: BlockchainEvent) -> u64 {
+        let mut state = self.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.stats.received += 1;
+        state.entries.push_back(DeadLetterEntry {
+            id,
+            event,
+            received_at: SystemTime::now(),
+        });
+        self.evict(&mut state);
+        self.publish_depth(&state);
+        id
+    }
+
+    /// Up to `limit` queued entries, oldest first.
+    #[must_use]
+    pub fn list(&self, limit: usize) -> Vec<DeadLetterEntry> {
+        let mut state = self.lock();
+        self.evict(&mut state);
+        self.publish_depth(&state);
+        state.entries.iter().take(limit).cloned().collect()
+    }
+
+    /// The oldest queued entry.
+    #[must_use]
+    pub fn peek(&self) -> Option<DeadLetterEntry> {
+        self.list(1).pop()
+    }
+
+    /// The queued entry with this ID.
+    #[must_use]
+    pub fn get(&self, id: u64) -> Option<DeadLetterEntry> {
+        let state = self.lock();
+        state.entries.iter().find(|entry| entry.id == id).cloned()
+    }
+
+    /// Acknowledge an entry, removing it from the queue.
+    pub fn ack(&self, id: u64) -> Result<DeadLetterEntry, DeadLetterError> {
+        let mut state = self.lock();
+        let position = Self::position(&state, id)?;
+        let entry = state.entries.remove(position);
+        state.stats.acked += 1;
+        self.publish_depth(&state);
+        entry.ok_or(DeadLetterError::NotFound(id))
+    }
+
+    /// Remove a replayable entry and return the event to republish.
+    pub(crate) fn take_for_replay(&self, id: u64) -> Result<BlockchainEvent, DeadLetterError> {
+        let mut state = self.lock();
+        let position = Self::position(&state, id)?;
+        let original = state.entries[position]
+            .original_event()
+            .ok_or(DeadLetterError::NotReplayable(id))?;
+        state.entries.remove(position);
+        state.stats.replayed += 1;
+        self.publish_depth(&state);
+        Ok(original)
+    }
+
+    /// Number of queued entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Whether the queue is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the queue counters.
+    #[must_use]
+    pub fn stats(&self) -> DeadLetterStats {
+        let state = self.lock();
+        DeadLetterStats {
+            depth: state.entries.len(),
+            ..state.stats
+        }
+    }
+
+    /// Watch the queue depth; the value changes on every push, ack, replay
+    /// and eviction.
+    #[must_use]
+    pub fn watch_depth(&self) -> watch::Receiver<usize> {
+        self.depth.subscribe()
+    }
+
+    fn position(state: &QueueState, id: u64) -> Result<usize, DeadLetterError> {
+        state
+            .entries
+            .iter()
+            .position(|entry| entry.id == id)
+            .ok_or(DeadLetterError::NotFound(id))
+    }
+
+    /// Drop entries beyond the count limit or older than the age limit.
+    fn evict(&self, state: &mut QueueState) {
+        let over = state.entries.len().saturating_sub(self.config.max_entries);
+        let expired = state
+            .entries
+            .iter()
+            .skip(over)
+            .take_while(|entry| entry.age() > self.config.max_age)
+            .count();
+        let evicted = over + expired;
+        if evicted == 0 {
+            return;
+        }
+
+        state.entries.drain(..evicted);
+        state.stats.evicted += evicted as u64;
+        warn!(evicted, "Dead letters dropped by retention limits");
+    }
+
+    fn publish_depth(&self, state: &QueueState) {
+        self.depth.send_replace(state.entries.len());
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueState> {
+        // Every mutation leaves the queue consistent, so a poisoned lock
+        // is still usable
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn critical(error: &str) -> BlockchainEvent {
+        BlockchainEvent::CriticalError {
+            subsystem_id: 2,
+            error: error.to_string(),
+        }
+    }
+
+    fn failed_query(id: &str) -> BlockchainEvent {
+        BlockchainEvent::ApiQueryDeadLetter {
+            correlation_id: id.to_string(),
+            target: "qc-02-block-storage".to_string(),
+            method: "get_block_number".to_string(),
+            params: serde_json::json!({"type": "GetBlockNumber"}),
+            attempts: 3,
+            reason: "timeout".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_list_peek_and_ack() {
+        let dlq = DeadLetterQueue::default();
+        let first = dlq.push(critical("disk full"));
+        let second = dlq.push(failed_query("q-1"));
+
+        assert_eq!(dlq.peek().map(|entry| entry.id), Some(first));
+        let ids: Vec<_> = dlq.list(10).iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, vec![first, second]);
+
+        assert!(dlq.ack(first).is_ok());
+        assert_eq!(
+            dlq.ack(first).unwrap_err(),
+            DeadLetterError::NotFound(first)
+        );
+        assert_eq!(dlq.peek().map(|entry| entry.id), Some(second));
+
+        let stats = dlq.stats();
+        assert_eq!((stats.depth, stats.received, stats.acked), (1, 2, 1));
+    }
+
+    #[test]
+    fn test_replay_rebuilds_original_query() {
+        let dlq = DeadLetterQueue::default();
+        let critical_id = dlq.push(critical("disk full"));
+        let query_id = dlq.push(failed_query("q-1"));
+
+        assert_eq!(
+            dlq.take_for_replay(critical_id).unwrap_err(),
+            DeadLetterError::NotReplayable(critical_id)
+        );
+
+        let original = dlq.take_for_replay(query_id).unwrap();
+        assert!(matches!(
+            original,
+            BlockchainEvent::ApiQuery { ref correlation_id, ref params, .. }
+                if correlation_id == "q-1" && params["type"] == "GetBlockNumber"
+        ));
+        assert_eq!(dlq.len(), 1);
+        assert_eq!(dlq.stats().replayed, 1);
+    }
+
+    #[test]
+    fn test_retention_limits() {
+        let dlq = DeadLetterQueue::new(DeadLetterConfig::default().with_max_entries(2));
+        let depth = dlq.watch_depth();
+        for n in 0..3 {
+            dlq.push(critical(&n.to_string()));
+        }
+        assert_eq!(dlq.peek().map(|entry| entry.id), Some(1));
+        assert_eq!(dlq.stats().evicted, 1);
+        assert_eq!(*depth.borrow(), 2);
+
+        let dlq = DeadLetterQueue::new(DeadLetterConfig::default().with_max_age(Duration::ZERO));
+        dlq.push(critical("stale"));
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(dlq.list(10).is_empty());
+        assert_eq!(dlq.stats().evicted, 1);
+    }
+}
@@ -0,0 +1,225 @@
+//! # Durable Subscriptions
+//!
+//! Named consumers of the persistent event log (see `event_log`).
+//!
+//! A durable subscription starts at the consumer's committed offset, or at
+//! the oldest retained event for a new consumer, and reads the log rather
+//! than the live broadcast channel, so it never lags out. Events stay
+//! pending until the consumer acknowledges them; after a restart, a
+//! consumer with the same name gets every unacknowledged event again.
+
+use crate::event_log::{Cursor, EventLog, EventLogError};
+use crate::events::{BlockchainEvent, EventFilter};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Records read from the log per batch.
+const READ_BATCH: usize = 256;
+
+/// An event with its position in the log.
+#[derive(Debug, Clone)]
+pub struct DeliveredEvent {
+    /// Offset to acknowledge once the event is handled.
+    pub offset: u64,
+    /// The event.
+    pub event: BlockchainEvent,
+}
+
+/// Subscription of a named consumer to the persistent event log.
+pub struct DurableSubscription {
+    log: Arc<EventLog>,
+    consumer: String,
+    filter: EventFilter,
+    cursor: Cursor,
+    /// Read but not yet returned.
+    buffer: VecDeque<DeliveredEvent>,
+    head: watch::Receiver<u64>,
+}
+
+impl DurableSubscription {
+    /// Subscribe `consumer` from its committed offset.
+    pub(crate) fn new(
+        log: Arc<EventLog>,
+        consumer: impl Into<String>,
+        filter: EventFilter,
+    ) -> Result<Self, EventLogError> {
+        let consumer = consumer.into();
+        let start = match log.committed_offset(&consumer) {
+            Some(offset) => offset,
+            None => {
+                // Register now so compaction keeps what we haven't read
+                let first = log.first_offset();
+                log.commit(&consumer, first)?;
+                first
+            }
+        };
+
+        Ok(Self {
+            head: log.watch_head(),
+            log,
+            consumer,
+            filter,
+            cursor: Cursor::at(start),
+            buffer: VecDeque::new(),
+        })
+    }
+
+    /// Receive the next event matching the filter, waiting for one to be
+    /// published if the consumer has caught up.
+    pub async fn recv(&mut self) -> Result<DeliveredEvent, EventLogError> {
+        loop {
+            if let Some(delivered) = self.try_recv()? {
+                return Ok(delivered);
+            }
+            // The log outlives the subscription, so the sender can't close
+            if self.head.changed().await.is_err() {
+                return Err(EventLogError::NotPersistent);
+            }
+        }
+    }
+
+    /// Receive the next matching event already in the log, if any.
+    pub fn try_recv(&mut self) -> Result<Option<DeliveredEvent>, EventLogError> {
+        loop {
+            if let Some(delivered) = self.next_buffered() {
+                return Ok(Some(delivered));
+            }
+
+            self.head.borrow_and_update();
+            let records = self.log.read(&mut self.cursor, READ_BATCH)?;
+            if records.is_empty() {
+                return Ok(None);
+            }
+            self.buffer
+                .extend(records.into_iter().map(|record| DeliveredEvent {
+                    offset: record.offset,
+                    event: record.event,
+                }));
+        }
+    }
+
+    /// Pop buffered events until one matches the filter.
+    fn next_buffered(&mut self) -> Option<DeliveredEvent> {
+        let (buffer, filter) = (&mut self.buffer, &self.filter);
+        std::iter::from_fn(|| buffer.pop_front()).find(|delivered| filter.matches(&delivered.event))
+    }
+
+    /// Acknowledge every event up to and including `offset`.
+    ///
+    /// Acknowledgements are cumulative: skipped (filtered-out) events and
+    /// earlier events are acknowledged too.
+    pub fn ack(&self, offset: u64) -> Result<(), EventLogError> {
+        self.log.commit(&self.consumer, offset + 1)
+    }
+
+    /// Name of the consumer.
+    #[must_use]
+    pub fn consumer(&self) -> &str {
+        &self.consumer
+    }
+
+    /// Get the filter for this subscription.
+    #[must_use]
+    pub fn filter(&self) -> &EventFilter {
+        &self.filter
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_log::EventLogConfig;
+    use crate::events::EventTopic;
+    use crate::publisher::InMemoryEventBus;
+    use crate::EventPublisher;
+    use shared_types::entities::{Hash, ValidatedBlock};
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    fn stored(height: u64) -> BlockchainEvent {
+        BlockchainEvent::BlockStored {
+            block_height: height,
+            block_hash: Hash::default(),
+        }
+    }
+
+    fn persistent_bus(dir: &std::path::Path) -> InMemoryEventBus {
+        let log = EventLog::open(EventLogConfig::new(dir)).unwrap();
+        InMemoryEventBus::new().with_event_log(Arc::new(log))
+    }
+
+    #[tokio::test]
+    async fn test_unacked_events_are_redelivered() {
+        let dir = tempfile::tempdir().unwrap();
+        let bus = persistent_bus(dir.path());
+        let mut sub = bus
+            .subscribe_durable("storage", EventFilter::all())
+            .unwrap();
+        for height in 0..3 {
+            bus.publish(stored(height)).await;
+        }
+
+        let first = sub.recv().await.unwrap();
+        assert_eq!(first.offset, 0);
+        sub.ack(first.offset).unwrap();
+        assert_eq!(sub.recv().await.unwrap().offset, 1);
+        // Crash before acknowledging offset 1
+        drop((sub, bus));
+
+        let bus = persistent_bus(dir.path());
+        let mut sub = bus
+            .subscribe_durable("storage", EventFilter::all())
+            .unwrap();
+        assert_eq!(sub.try_recv().unwrap().unwrap().offset, 1);
+        assert_eq!(sub.try_recv().unwrap().unwrap().offset, 2);
+        assert!(sub.try_recv().unwrap().is_none());
+
+        // Another consumer starts from the oldest event
+        let mut other = bus
+            .subscribe_durable("indexer", EventFilter::all())
+            .unwrap();
+        assert_eq!(other.try_recv().unwrap().unwrap().offset, 0);
+    }
+
+    #[tokio::test]
+    async fn test_recv_waits_for_publish_and_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let bus = Arc::new(persistent_bus(dir.path()));
+        let mut sub = bus
+            .subscribe_durable(
+                "consensus",
+                EventFilter::topics(vec![EventTopic::Consensus]),
+            )
+            .unwrap();
+
+        let publisher = Arc::clone(&bus);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            publisher.publish(stored(1)).await;
+            publisher
+                .publish(BlockchainEvent::BlockValidated(ValidatedBlock::default()))
+                .await;
+        });
+
+        let delivered = timeout(Duration::from_secs(2), sub.recv())
+            .await
+            .expect("timeout")
+            .unwrap();
+        assert_eq!(delivered.offset, 1);
+        assert!(matches!(
+            delivered.event,
+            BlockchainEvent::BlockValidated(_)
+        ));
+        assert_eq!(bus.events_published(), 2);
+    }
+
+    #[test]
+    fn test_memory_bus_has_no_durable_subscriptions() {
+        let bus = InMemoryEventBus::new();
+        assert!(matches!(
+            bus.subscribe_durable("storage", EventFilter::all()),
+            Err(EventLogError::NotPersistent)
+        ));
+    }
+}
@@ -0,0 +1,603 @@
+//! # Persistent Event Log
+//!
+//! On-disk log of published events, for consumers that must not lose events
+//! across a crash.
+//!
+//! ## Layout
+//!
+//! ```text
+//! <dir>/
+//!   00000000000000000000.log   segment starting at offset 0
+//!   00000000000000004096.log   segment starting at offset 4096
+//!   offsets.json               committed offset per consumer
+//! ```
+//!
+//! Each segment holds one JSON record (`{"offset":..,"event":..}`) per line.
+//! A segment is closed once it reaches `segment_max_bytes` and deleted once
+//! every known consumer has committed past it.
+//!
+//! ## Delivery
+//!
+//! Durable consumers read from their committed offset and commit as they
+//! go (see `DurableSubscription`). Whatever a consumer had read but not
+//! committed when the node stopped is delivered again: at-least-once.
+//!
+//! A torn last record (crash mid-write) is cut off when the log is opened.
+//! Appends reach the OS before `append` returns, so they survive a process
+//! crash; set `fsync` to also survive power loss, at the cost of a disk
+//! flush per event.
+
+use crate::events::BlockchainEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use thiserror::Error;
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+/// Default segment size before rolling to a new file.
+pub const DEFAULT_SEGMENT_MAX_BYTES: u64 = 16 * 1024 * 1024;
+
+/// File holding committed consumer offsets.
+const OFFSETS_FILE: &str = "offsets.json";
+
+/// Extension of segment files.
+const SEGMENT_EXTENSION: &str = "log";
+
+/// Errors from the persistent event log.
+#[derive(Debug, Error)]
+pub enum EventLogError {
+    /// Reading or writing the log failed.
+    #[error("Event log I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A record could not be parsed.
+    #[error("Corrupt event log segment {segment} at byte {position}: {reason}")]
+    Corrupt {
+        /// Segment file.
+        segment: PathBuf,
+        /// Byte position of the record.
+        position: u64,
+        /// Parse error.
+        reason: String,
+    },
+
+    /// An event could not be serialized.
+    #[error("Event serialization failed: {0}")]
+    Serialization(String),
+
+    /// The bus was created without an event log.
+    #[error("Event bus has no persistent event log")]
+    NotPersistent,
+}
+
+/// Configuration of a persistent event log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventLogConfig {
+    /// Directory holding segments and offsets.
+    pub dir: PathBuf,
+    /// Segment size before rolling to a new file.
+    pub segment_max_bytes: u64,
+    /// Flush every append to disk.
+    pub fsync: bool,
+}
+
+impl EventLogConfig {
+    /// Log in `dir` with default segment size and no fsync.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            segment_max_bytes: DEFAULT_SEGMENT_MAX_BYTES,
+            fsync: false,
+        }
+    }
+
+    /// Set the segment size.
+    #[must_use]
+    pub fn with_segment_max_bytes(mut self, segment_max_bytes: u64) -> Self {
+        self.segment_max_bytes = segment_max_bytes;
+        self
+    }
+
+    /// Flush every append (and offset commit) to disk.
+    #[must_use]
+    pub fn with_fsync(mut self, fsync: bool) -> Self {
+        self.fsync = fsync;
+        self
+    }
+}
+
+/// One event in the log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogRecord {
+    /// Position of the event in the log.
+    pub offset: u64,
+    /// The event.
+    pub event: BlockchainEvent,
+}
+
+/// `LogRecord` without owning the event.
+#[derive(Serialize)]
+struct RecordRef<'a> {
+    offset: u64,
+    event: &'a BlockchainEvent,
+}
+
+/// Where a reader is in the log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Cursor {
+    /// Base offset of the segment being read.
+    segment: u64,
+    /// Byte position in that segment.
+    position: u64,
+    /// Next offset to return.
+    pub(crate) next: u64,
+}
+
+impl Cursor {
+    /// Cursor that returns records from `offset` on.
+    pub(crate) fn at(offset: u64) -> Self {
+        Self {
+            segment: 0,
+            position: 0,
+            next: offset,
+        }
+    }
+}
+
+/// Mutable log state, behind the log's mutex.
+struct LogState {
+    /// Base offsets of the segments on disk, ascending.
+    segments: Vec<u64>,
+    /// Last segment, open for appending.
+    active: File,
+    /// Bytes in the active segment.
+    active_len: u64,
+    /// Offset of the next appended event.
+    next_offset: u64,
+    /// Committed offset (next offset to deliver) per consumer.
+    offsets: HashMap<String, u64>,
+}
+
+/// Persistent, segmented event log with consumer offsets.
+pub struct EventLog {
+    config: EventLogConfig,
+    state: Mutex<LogState>,
+    /// Next offset to be appended, for waking readers.
+    head: watch::Sender<u64>,
+}
+
+impl EventLog {
+    /// Open the log in `config.dir`, creating it if needed.
+    pub fn open(config: EventLogConfig) -> Result<Self, EventLogError> {
+        fs::create_dir_all(&config.dir)?;
+
+        let mut segments = list_segments(&config.dir)?;
+        if segments.is_empty() {
+            segments.push(0);
+        }
+        let last = *segments.last().unwrap_or(&0);
+        let path = segment_path(&config.dir, last);
+        let (active_len, next_offset) = recover_segment(&path, last)?;
+        let active = OpenOptions::new().create(true).append(true).open(&path)?;
+        let offsets = load_offsets(&config.dir)?;
+
+        info!(
+            dir = %config.dir.display(),
+            segments = segments.len(),
+            next_offset,
+            consumers = offsets.len(),
+            "Opened persistent event log"
+        );
+
+        let (head, _) = watch::channel(next_offset);
+        Ok(Self {
+            config,
+            state: Mutex::new(LogState {
+                segments,
+                active,
+                active_len,
+                next_offset,
+                offsets,
+            }),
+            head,
+        })
+    }
+
+    /// Configuration the log was opened with.
+    pub fn config(&self) -> &EventLogConfig {
+        &self.config
+    }
+
+    /// Append an event, returning its offset.
+    pub fn append(&self, event: &BlockchainEvent) -> Result<u64, EventLogError> {
+        let mut state = self.lock();
+        let offset = state.next_offset;
+        let mut line = serde_json::to_vec(&RecordRef { offset, event })
+            .map_err(|e| EventLogError::Serialization(e.to_string()))?;
+        line.push(b'\n');
+
+        if state.active_len > 0
+            && state.active_len + line.len() as u64 > self.config.segment_max_bytes
+        {
+            self.roll(&mut state)?;
+        }
+        if let Err(e) = state.active.write_all(&line) {
+            // Don't leave a partial record for the next append to follow
+            let _ = state.active.set_len(state.active_len);
+            return Err(e.into());
+        }
+        if self.config.fsync {
+            state.active.sync_data()?;
+        }
+        state.active_len += line.len() as u64;
+        state.next_offset = offset + 1;
+        drop(state);
+
+        self.head.send_replace(offset + 1);
+        Ok(offset)
+    }
+
+    /// Offset the next appended event will get.
+    pub fn next_offset(&self) -> u64 {
+        self.lock().next_offset
+    }
+
+    /// Offset of the oldest event still on disk.
+    pub fn first_offset(&self) -> u64 {
+        self.lock().segments.first().copied().unwrap_or(0)
+    }
+
+    /// Next offset to deliver to `consumer`, if it ever committed.
+    pub fn committed_offset(&self, consumer: &str) -> Option<u64> {
+        self.lock().offsets.get(consumer).copied()
+    }
+
+    /// Record that `consumer` has processed every event before `next`.
+    ///
+    /// Offsets never move backwards; committing an older offset is a no-op.
+    pub fn commit(&self, consumer: &str, next: u64) -> Result<(), EventLogError> {
+        let mut state = self.lock();
+        if matches!(state.offsets.get(consumer), Some(&current) if current >= next) {
+            return Ok(());
+        }
+        state.offsets.insert(consumer.to_string(), next);
+        save_offsets(&self.config.dir, &state.offsets, self.config.fsync)
+    }
+
+    /// Delete closed segments that every known consumer has committed past.
+    ///
+    /// Returns the number of segments deleted. Nothing is deleted while no
+    /// consumer has committed.
+    pub fn compact(&self) -> Result<usize, EventLogError> {
+        let mut state = self.lock();
+        self.compact_locked(&mut state)
+    }
+
+    /// Receiver of the next offset to be appended.
+    pub(crate) fn watch_head(&self) -> watch::Receiver<u64> {
+        self.head.subscribe()
+    }
+
+    /// Read up to `max` records from `cursor`, advancing it.
+    pub(crate) fn read(
+        &self,
+        cursor: &mut Cursor,
+        max: usize,
+    ) -> Result<Vec<LogRecord>, EventLogError> {
+        // Holding the lock keeps appends from interleaving with the read
+        let state = self.lock();
+        let mut records = Vec::new();
+        if cursor.next >= state.next_offset {
+            return Ok(records);
+        }
+
+        // Restart from the containing segment if ours was compacted away
+        if !state.segments.contains(&cursor.segment) || cursor.position == 0 {
+            let index = state
+                .segments
+                .partition_point(|&base| base <= cursor.next)
+                .saturating_sub(1);
+            cursor.segment = state.segments[index];
+            cursor.position = 0;
+        }
+
+        loop {
+            self.read_segment(cursor, max, &mut records)?;
+            let next_segment = state.segments.iter().find(|&&base| base > cursor.segment);
+            match next_segment {
+                Some(&base) if records.len() < max => {
+                    cursor.segment = base;
+                    cursor.position = 0;
+                }
+                _ => return Ok(records),
+            }
+        }
+    }
+
+    /// Read records from the cursor's segment until `max` or end of file.
+    fn read_segment(
+        &self,
+        cursor: &mut Cursor,
+        max: usize,
+        records: &mut Vec<LogRecord>,
+    ) -> Result<(), EventLogError> {
+        let path = segment_path(&self.config.dir, cursor.segment);
+        let mut file = File::open(&path)?;
+        file.seek(SeekFrom::Start(cursor.position))?;
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+
+        while records.len() < max {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                break;
+            }
+            let record: LogRecord =
+                serde_json::from_str(&line).map_err(|e| EventLogError::Corrupt {
+                    segment: path.clone(),
+                    position: cursor.position,
+                    reason: e.to_string(),
+                })?;
+            cursor.position += read as u64;
+            if record.offset >= cursor.next {
+                cursor.next = record.offset + 1;
+                records.push(record);
+            }
+        }
+        Ok(())
+    }
+
+    /// Close the active segment and start a new one at the next offset.
+    fn roll(&self, state: &mut LogState) -> Result<(), EventLogError> {
+        state.active.sync_data()?;
+        let base = state.next_offset;
+        let path = segment_path(&self.config.dir, base);
+        state.active = OpenOptions::new().create(true).append(true).open(&path)?;
+        state.active_len = 0;
+        state.segments.push(base);
+        debug!(segment = base, "Rolled event log segment");
+        if let Err(e) = self.compact_locked(state) {
+            warn!(error = %e, "Event log compaction failed");
+        }
+        Ok(())
+    }
+
+    /// `compact` with the lock already held.
+    fn compact_locked(&self, state: &mut LogState) -> Result<usize, EventLogError> {
+        let Some(&min_committed) = state.offsets.values().min() else {
+            return Ok(0);
+        };
+
+        // Segment i is fully consumed once segment i + 1 starts at or below
+        // the slowest consumer's offset; the active segment always stays
+        let deletable = state
+            .segments
+            .windows(2)
+            .take_while(|pair| pair[1] <= min_committed)
+            .count();
+        for base in state.segments.drain(..deletable) {
+            fs::remove_file(segment_path(&self.config.dir, base))?;
+            debug!(segment = base, "Deleted consumed event log segment");
+        }
+        Ok(deletable)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LogState> {
+        // A panic mid-append leaves at most a torn record, which readers
+        // and recovery already handle
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl std::fmt::Debug for EventLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventLog")
+            .field("config", &self.config)
+            .field("next_offset", &*self.head.borrow())
+            .finish_non_exhaustive()
+    }
+}
+
+fn segment_path(dir: &Path, base: u64) -> PathBuf {
+    dir.join(format!("{base:020}.{SEGMENT_EXTENSION}"))
+}
+
+/// Base offsets of the segment files in `dir`, ascending.
+fn list_segments(dir: &Path) -> Result<Vec<u64>, EventLogError> {
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(SEGMENT_EXTENSION) {
+            continue;
+        }
+        match path.file_stem().and_then(|s| s.to_str()).map(str::parse) {
+            Some(Ok(base)) => segments.push(base),
+            _ => warn!(path = %path.display(), "Ignoring unrecognized file in event log"),
+        }
+    }
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+/// Validate the last segment, cutting off a torn final record.
+///
+/// Returns the segment's length and the offset after its last record.
+fn recover_segment(path: &Path, base: u64) -> Result<(u64, u64), EventLogError> {
+    let Ok(file) = File::open(path) else {
+        return Ok((0, base));
+    };
+    let mut reader = BufReader::new(file);
+    let mut line = String::new();
+    let mut position = 0u64;
+    let mut next_offset = base;
+
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)?;
+        if read == 0 {
+            break;
+        }
+        let parsed = serde_json::from_str::<LogRecord>(&line);
+        match parsed {
+            Ok(record) if line.ends_with('\n') => {
+                next_offset = record.offset + 1;
+                position += read as u64;
+            }
+            // Only the final record can be torn
+            Err(e) if !reader.fill_buf()?.is_empty() => {
+                return Err(EventLogError::Corrupt {
+                    segment: path.to_path_buf(),
+                    position,
+                    reason: e.to_string(),
+                })
+            }
+            _ => {
+                warn!(
+                    path = %path.display(),
+                    position,
+                    "Truncating torn record at end of event log"
+                );
+                OpenOptions::new()
+                    .write(true)
+                    .open(path)?
+                    .set_len(position)?;
+                break;
+            }
+        }
+    }
+    Ok((position, next_offset))
+}
+
+fn load_offsets(dir: &Path) -> Result<HashMap<String, u64>, EventLogError> {
+    let path = dir.join(OFFSETS_FILE);
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let json = fs::read_to_string(&path)?;
+    serde_json::from_str(&json).map_err(|e| EventLogError::Corrupt {
+        segment: path,
+        position: 0,
+        reason: e.to_string(),
+    })
+}
+
+/// Write offsets to a temporary file and rename it over the old one.
+fn save_offsets(
+    dir: &Path,
+    offsets: &HashMap<String, u64>,
+    fsync: bool,
+) -> Result<(), EventLogError> {
+    let json =
+        serde_json::to_vec(offsets).map_err(|e| EventLogError::Serialization(e.to_string()))?;
+    let tmp = dir.join(format!("{OFFSETS_FILE}.tmp"));
+    let mut file = File::create(&tmp)?;
+    file.write_all(&json)?;
+    if fsync {
+        file.sync_data()?;
+    }
+    fs::rename(tmp, dir.join(OFFSETS_FILE))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_types::entities::Hash;
+
+    fn stored(height: u64) -> BlockchainEvent {
+        BlockchainEvent::BlockStored {
+            block_height: height,
+            block_hash: Hash::default(),
+        }
+    }
+
+    fn heights(records: &[LogRecord]) -> Vec<u64> {
+        records
+            .iter()
+            .map(|record| match record.event {
+                BlockchainEvent::BlockStored { block_height, .. } => block_height,
+                _ => panic!("unexpected event"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_append_and_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = EventLogConfig::new(dir.path()).with_segment_max_bytes(200);
+        {
+            let log = EventLog::open(config.clone()).unwrap();
+            for height in 0..10 {
+                assert_eq!(log.append(&stored(height)).unwrap(), height);
+            }
+        }
+
+        let log = EventLog::open(config).unwrap();
+        assert_eq!(log.next_offset(), 10);
+        assert!(list_segments(dir.path()).unwrap().len() > 1);
+
+        let mut cursor = Cursor::at(3);
+        let records = log.read(&mut cursor, 4).unwrap();
+        assert_eq!(heights(&records), vec![3, 4, 5, 6]);
+        let records = log.read(&mut cursor, 100).unwrap();
+        assert_eq!(heights(&records), vec![7, 8, 9]);
+        assert!(log.read(&mut cursor, 100).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_torn_record_is_truncated() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = EventLogConfig::new(dir.path());
+        {
+            let log = EventLog::open(config.clone()).unwrap();
+            log.append(&stored(0)).unwrap();
+            log.append(&stored(1)).unwrap();
+        }
+        let path = segment_path(dir.path(), 0);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"offset":2,"event":{"Block"#).unwrap();
+
+        let log = EventLog::open(config).unwrap();
+        assert_eq!(log.next_offset(), 2);
+        assert_eq!(log.append(&stored(2)).unwrap(), 2);
+        let records = log.read(&mut Cursor::at(0), 10).unwrap();
+        assert_eq!(heights(&records), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_offsets_persist_and_compact() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = EventLogConfig::new(dir.path()).with_segment_max_bytes(200);
+        let log = EventLog::open(config.clone()).unwrap();
+        for height in 0..10 {
+            log.append(&stored(height)).unwrap();
+        }
+
+        // Nothing is deleted before any consumer commits
+        assert_eq!(log.compact().unwrap(), 0);
+        log.commit("storage", 8).unwrap();
+        log.commit("storage", 5).unwrap();
+        log.commit("indexer", 2).unwrap();
+        assert_eq!(log.committed_offset("storage"), Some(8));
+        drop(log);
+
+        let log = EventLog::open(config).unwrap();
+        assert_eq!(log.committed_offset("storage"), Some(8));
+        assert_eq!(log.committed_offset("indexer"), Some(2));
+        log.compact().unwrap();
+        assert!(log.first_offset() <= 2);
+
+        log.commit("indexer", 10).unwrap();
+        assert!(log.compact().unwrap() > 0);
+        assert!(log.first_offset() > 2 && log.first_offset() <= 8);
+        let records = log.read(&mut Cursor::at(8), 10).unwrap();
+        assert_eq!(heights(&records), vec![8, 9]);
+    }
+}
@@ -4,8 +4,13 @@
 //! These correspond to IPC payloads in `shared-types/src/ipc.rs`.
 
 use serde::{Deserialize, Serialize};
-use shared_types::entities::{Hash, PeerId, PeerInfo, ValidatedBlock, ValidatedTransaction};
+use shared_types::entities::{
+    Attestation, CoinbaseTransaction, Hash, PeerId, PeerInfo, ValidatedBlock, ValidatedTransaction,
+};
+use shared_types::error_code::ErrorInfo;
 use shared_types::ipc::{VerifyNodeIdentityPayload, VerifyNodeIdentityResponse};
+use shared_types::subsystem_registry::CapabilityReport;
+use shared_types::subsystem_trait::SubsystemStatus;
 
 /// All events that can be published to the event bus.
 ///
@@ -55,6 +60,48 @@ pub enum BlockchainEvent {
         timestamp: u64,
         /// Parent block hash.
         parent_hash: Hash,
+        /// Reward claimed by the block's beneficiary (subsidy plus fees).
+        coinbase: CoinbaseTransaction,
+    },
+
+    /// A PoS block was proposed for an assigned slot and is ready for
+    /// consensus validation.
+    /// Source: Subsystem 17 | Target: Subsystem 8
+    BlockProposed {
+        /// The proposed block's height.
+        block_height: u64,
+        /// The proposed block's hash.
+        block_hash: Hash,
+        /// Parent block hash.
+        parent_hash: Hash,
+        /// Block timestamp.
+        timestamp: u64,
+        /// Fee recipient in the signed header.
+        beneficiary: [u8; 20],
+        /// Gas used, as in the signed header.
+        gas_used: u64,
+        /// Slot the block was proposed for.
+        slot: u64,
+        /// Epoch of the slot.
+        epoch: u64,
+        /// Proposer's index in the validator set.
+        validator_index: u32,
+        /// Proposer signature over the serialized header (parent hash,
+        /// height, timestamp, beneficiary, gas used).
+        signature: Vec<u8>,
+        /// Attestations for the parent block, one per validator.
+        attestations: Vec<Attestation>,
+    },
+
+    /// MEV and censorship report for a produced block.
+    /// Source: Subsystem 17 | Target: observers (admin API, monitoring)
+    MevReportPublished {
+        /// The reported block's height.
+        block_height: u64,
+        /// The reported block's hash.
+        block_hash: Hash,
+        /// Report body (qc-17 `MevReport` as JSON).
+        report: serde_json::Value,
     },
 
     // =========================================================================
@@ -73,6 +120,22 @@ pub enum BlockchainEvent {
         reason: String,
     },
 
+    /// This node was selected to propose the block for a slot (PoS).
+    /// Source: Subsystem 8's `DutyScheduler`, run by the node runtime's
+    /// validator duty task | Target: Subsystem 17
+    SlotAssigned {
+        /// Assigned slot.
+        slot: u64,
+        /// Epoch of the slot.
+        epoch: u64,
+        /// This node's index in the validator set.
+        validator_index: u32,
+        /// VRF output that selected the proposer.
+        vrf_output: Hash,
+        /// VRF proof of the selection.
+        vrf_proof: Vec<u8>,
+    },
+
     // =========================================================================
     // SUBSYSTEM 3: TRANSACTION INDEXING (Choreography Response)
     // =========================================================================
@@ -85,6 +148,17 @@ pub enum BlockchainEvent {
         merkle_root: Hash,
     },
 
+    /// Receipt logs of a stored block were indexed.
+    /// Source: Subsystem 3 | Target: observers (API gateway subscriptions)
+    ReceiptsIndexed {
+        /// The block's height.
+        block_height: u64,
+        /// The block's hash.
+        block_hash: Hash,
+        /// Logs in block order, as Ethereum JSON-RPC log objects.
+        logs: Vec<serde_json::Value>,
+    },
+
     // =========================================================================
     // SUBSYSTEM 4: STATE MANAGEMENT (Choreography Response)
     // =========================================================================
@@ -123,6 +197,33 @@ pub enum BlockchainEvent {
         timestamp: u64,
     },
 
+    // =========================================================================
+    // SUBSYSTEM 5: BLOCK PROPAGATION
+    // =========================================================================
+    /// Request to gossip a block to connected peers.
+    /// Source: Subsystem 8 | Target: Subsystem 5
+    PropagateBlockRequest {
+        /// Hash of the block to propagate.
+        block_hash: Hash,
+        /// Height of the block.
+        block_height: u64,
+        /// Serialized block.
+        block_data: Vec<u8>,
+        /// Hashes of the block's transactions, for compact block relay.
+        tx_hashes: Vec<Hash>,
+    },
+
+    /// A block arrived from a peer and passed propagation checks.
+    /// Source: Subsystem 5 | Target: Subsystem 8
+    BlockReceived {
+        /// Hash of the received block.
+        block_hash: Hash,
+        /// Serialized block as received.
+        block_data: Vec<u8>,
+        /// Node ID of the peer that sent it.
+        source_peer: [u8; 32],
+    },
+
     // =========================================================================
     // SUBSYSTEM 10: SIGNATURE VERIFICATION
     // =========================================================================
@@ -137,6 +238,10 @@ pub enum BlockchainEvent {
         reason: String,
     },
 
+    /// A validator attestation's signature was verified.
+    /// Source: Subsystem 10 | Target: Subsystems 8, 17
+    AttestationVerified(Attestation),
+
     // =========================================================================
     // SUBSYSTEM 9: FINALITY
     // =========================================================================
@@ -161,6 +266,69 @@ pub enum BlockchainEvent {
         error: String,
     },
 
+    // =========================================================================
+    // SUBSYSTEM REGISTRY (node runtime)
+    // =========================================================================
+    /// Capabilities of every registered subsystem.
+    /// Source: node runtime (0) | Target: any, e.g. Subsystem 16
+    CapabilitiesAdvertised(CapabilityReport),
+
+    /// An operator stopped, started or restarted a subsystem at runtime.
+    /// Source: node runtime (0) | Target: any (operator tooling, API gateway)
+    SubsystemStatusChanged {
+        /// The subsystem that changed.
+        subsystem_id: u8,
+        /// Operator action ("stop", "start" or "restart").
+        action: String,
+        /// Status after the action.
+        status: SubsystemStatus,
+        /// Why the action failed, if it did.
+        error: Option<String>,
+    },
+
+    // =========================================================================
+    // NODE ALERTS (node runtime)
+    // =========================================================================
+    /// An in-node alert rule started or stopped firing.
+    /// Source: node runtime (0) | Target: any (operator tooling, API gateway)
+    NodeAlert {
+        /// Rule name (e.g. "FinalityLag").
+        rule: String,
+        /// Severity ("warning" or "critical").
+        severity: String,
+        /// True when the alert fires, false when it resolves.
+        firing: bool,
+        /// Value that triggered the change.
+        value: f64,
+        /// Rule threshold.
+        threshold: f64,
+        /// Human-readable summary.
+        summary: String,
+    },
+
+    // =========================================================================
+    // CHAIN SYNC (node runtime)
+    // =========================================================================
+    /// Progress of one chain sync phase.
+    /// Source: node runtime (0) | Target: any (operator tooling, API gateway)
+    SyncProgress {
+        /// Phase ("headers", "snapshot", "healing" or "backfill").
+        phase: String,
+        /// Items done (headers, snapshot chunks or blocks).
+        current: u64,
+        /// Items in the phase.
+        target: u64,
+    },
+
+    /// The node caught up with its peers; block production may start.
+    /// Source: node runtime (0) | Target: any (operator tooling, API gateway)
+    SyncCompleted {
+        /// Height of the local head.
+        block_height: u64,
+        /// Hash of the local head.
+        block_hash: Hash,
+    },
+
     // =========================================================================
     // API GATEWAY QUERIES (qc-16)
     // =========================================================================
@@ -175,6 +343,9 @@ pub enum BlockchainEvent {
         method: String,
         /// Query parameters as JSON.
         params: serde_json::Value,
+        /// W3C `traceparent` of the gateway span that issued the query.
+        #[serde(default)]
+        trace_parent: Option<String>,
     },
 
     /// Response from a subsystem to an API Gateway query.
@@ -186,6 +357,23 @@ pub enum BlockchainEvent {
         /// Result (Ok data or Err with code/message).
         result: Result<serde_json::Value, ApiQueryError>,
     },
+
+    /// API query that got no response after all retries (routed to the DLQ).
+    ApiQueryDeadLetter {
+        /// Correlation ID of the original query.
+        correlation_id: String,
+        /// Target subsystem of the query.
+        target: String,
+        /// JSON-RPC method name.
+        method: String,
+        /// Query parameters, so the query can be replayed.
+        #[serde(default)]
+        params: serde_json::Value,
+        /// Attempts made, not counting hedged duplicates.
+        attempts: u32,
+        /// Why the last attempt failed.
+        reason: String,
+    },
 }
 
 /// Error type for API query responses.
@@ -195,6 +383,19 @@ pub struct ApiQueryError {
     pub code: i32,
     /// Error message.
     pub message: String,
+    /// Shared error code, severity and origin, surfaced to API clients.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub info: Option<ErrorInfo>,
+}
+
+impl From<ErrorInfo> for ApiQueryError {
+    fn from(info: ErrorInfo) -> Self {
+        Self {
+            code: info.code.json_rpc_code(),
+            message: info.message.clone(),
+            info: Some(info),
+        }
+    }
 }
 
 impl BlockchainEvent {
@@ -206,17 +407,32 @@ impl BlockchainEvent {
             | Self::PeerDisconnected(_)
             | Self::VerifyNodeIdentity { .. }
             | Self::NodeIdentityVerified { .. } => EventTopic::PeerDiscovery,
-            Self::BlockProduced { .. } => EventTopic::BlockProduction,
-            Self::BlockValidated(_) | Self::BlockRejected { .. } => EventTopic::Consensus,
-            Self::MerkleRootComputed { .. } => EventTopic::TransactionIndexing,
+            Self::BlockProduced { .. }
+            | Self::BlockProposed { .. }
+            | Self::MevReportPublished { .. } => EventTopic::BlockProduction,
+            Self::BlockValidated(_)
+            | Self::BlockRejected { .. }
+            | Self::SlotAssigned { .. }
+            | Self::PropagateBlockRequest { .. } => EventTopic::Consensus,
+            Self::BlockReceived { .. } => EventTopic::BlockPropagation,
+            Self::MerkleRootComputed { .. } | Self::ReceiptsIndexed { .. } => {
+                EventTopic::TransactionIndexing
+            }
             Self::StateRootComputed { .. } => EventTopic::StateManagement,
             Self::BlockStored { .. } | Self::GenesisInitialized { .. } => EventTopic::BlockStorage,
-            Self::TransactionVerified(_) | Self::TransactionInvalid { .. } => {
-                EventTopic::SignatureVerification
-            }
+            Self::TransactionVerified(_)
+            | Self::TransactionInvalid { .. }
+            | Self::AttestationVerified(_) => EventTopic::SignatureVerification,
             Self::BlockFinalized { .. } => EventTopic::Finality,
-            Self::CriticalError { .. } => EventTopic::DeadLetterQueue,
+            Self::CriticalError { .. } | Self::ApiQueryDeadLetter { .. } => {
+                EventTopic::DeadLetterQueue
+            }
             Self::ApiQuery { .. } | Self::ApiQueryResponse { .. } => EventTopic::ApiGateway,
+            Self::CapabilitiesAdvertised(_) | Self::SubsystemStatusChanged { .. } => {
+                EventTopic::Registry
+            }
+            Self::NodeAlert { .. } => EventTopic::Alerts,
+            Self::SyncProgress { .. } | Self::SyncCompleted { .. } => EventTopic::Sync,
         }
     }
 
@@ -229,15 +445,28 @@ impl BlockchainEvent {
             | Self::VerifyNodeIdentity { .. } => 1,
             Self::NodeIdentityVerified { .. } => 10,
             Self::BlockStored { .. } | Self::GenesisInitialized { .. } => 2,
-            Self::MerkleRootComputed { .. } => 3,
+            Self::MerkleRootComputed { .. } | Self::ReceiptsIndexed { .. } => 3,
             Self::StateRootComputed { .. } => 4,
-            Self::BlockProduced { .. } => 17,
-            Self::BlockValidated(_) | Self::BlockRejected { .. } => 8,
+            Self::BlockProduced { .. }
+            | Self::BlockProposed { .. }
+            | Self::MevReportPublished { .. } => 17,
+            Self::BlockValidated(_)
+            | Self::BlockRejected { .. }
+            | Self::SlotAssigned { .. }
+            | Self::PropagateBlockRequest { .. } => 8,
+            Self::BlockReceived { .. } => 5,
             Self::BlockFinalized { .. } => 9,
-            Self::TransactionVerified(_) | Self::TransactionInvalid { .. } => 10,
+            Self::TransactionVerified(_)
+            | Self::TransactionInvalid { .. }
+            | Self::AttestationVerified(_) => 10,
             Self::CriticalError { subsystem_id, .. } => *subsystem_id,
-            Self::ApiQuery { .. } => 16,
+            Self::ApiQuery { .. } | Self::ApiQueryDeadLetter { .. } => 16,
             Self::ApiQueryResponse { source, .. } => *source,
+            Self::CapabilitiesAdvertised(_)
+            | Self::SubsystemStatusChanged { .. }
+            | Self::NodeAlert { .. }
+            | Self::SyncProgress { .. }
+            | Self::SyncCompleted { .. } => 0,
         }
     }
 }
@@ -267,19 +496,131 @@ pub enum EventTopic {
     SignatureVerification,
     /// Subsystem 16 events (API Gateway queries).
     ApiGateway,
+    /// Subsystem registry events (capability advertisement).
+    Registry,
+    /// Node alerts raised by in-node rule evaluation.
+    Alerts,
+    /// Chain sync progress.
+    Sync,
     /// Dead Letter Queue for critical errors.
     DeadLetterQueue,
     /// All events (no filtering).
     All,
 }
 
+impl EventTopic {
+    /// Every topic, in declaration order.
+    pub const ALL: [Self; 16] = [
+        Self::PeerDiscovery,
+        Self::BlockStorage,
+        Self::TransactionIndexing,
+        Self::StateManagement,
+        Self::BlockPropagation,
+        Self::Mempool,
+        Self::BlockProduction,
+        Self::Consensus,
+        Self::Finality,
+        Self::SignatureVerification,
+        Self::ApiGateway,
+        Self::Registry,
+        Self::Alerts,
+        Self::Sync,
+        Self::DeadLetterQueue,
+        Self::All,
+    ];
+
+    /// Dotted topic name, used for pattern matching.
+    ///
+    /// Related topics share a prefix (`block.*`, `tx.*`), so a single
+    /// pattern can select a whole family.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PeerDiscovery => "peer.discovery",
+            Self::BlockStorage => "block.storage",
+            Self::TransactionIndexing => "tx.indexing",
+            Self::StateManagement => "state.management",
+            Self::BlockPropagation => "block.propagation",
+            Self::Mempool => "tx.mempool",
+            Self::BlockProduction => "block.production",
+            Self::Consensus => "consensus",
+            Self::Finality => "finality",
+            Self::SignatureVerification => "signature.verification",
+            Self::ApiGateway => "api.gateway",
+            Self::Registry => "node.registry",
+            Self::Alerts => "node.alerts",
+            Self::Sync => "node.sync",
+            Self::DeadLetterQueue => crate::DLQ_TOPIC,
+            Self::All => "*",
+        }
+    }
+
+    /// Look up a topic by its dotted name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|topic| topic.name() == name)
+    }
+}
+
+impl std::fmt::Display for EventTopic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Pattern over topic names.
+///
+/// `*` matches every topic, a trailing `*` matches by prefix
+/// (`block.*`), and anything else must equal the topic name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TopicPattern(String);
+
+impl TopicPattern {
+    /// Create a pattern.
+    #[must_use]
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self(pattern.into())
+    }
+
+    /// Check if a topic matches this pattern.
+    #[must_use]
+    pub fn matches(&self, topic: EventTopic) -> bool {
+        match self.0.strip_suffix('*') {
+            Some(prefix) => topic.name().starts_with(prefix),
+            None => topic.name() == self.0,
+        }
+    }
+
+    /// The pattern string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for TopicPattern {
+    fn from(pattern: &str) -> Self {
+        Self::new(pattern)
+    }
+}
+
 /// Filter for subscribing to specific events.
+///
+/// The fields of one filter are ANDed together; an empty field accepts
+/// everything. Filters compose with [`EventFilter::and`] and
+/// [`EventFilter::or`].
 #[derive(Debug, Clone, Default)]
 pub struct EventFilter {
     /// Topics to include. Empty means all topics.
     pub topics: Vec<EventTopic>,
+    /// Topic name patterns to include. Empty means all topics.
+    pub topic_patterns: Vec<TopicPattern>,
     /// Source subsystems to include. Empty means all sources.
     pub source_subsystems: Vec<u8>,
+    /// Filters that must all match as well.
+    pub all_of: Vec<EventFilter>,
+    /// Filters of which at least one must match. Empty means no constraint.
+    pub any_of: Vec<EventFilter>,
 }
 
 impl EventFilter {
@@ -294,7 +635,16 @@ impl EventFilter {
     pub fn topics(topics: Vec<EventTopic>) -> Self {
         Self {
             topics,
-            source_subsystems: Vec::new(),
+            ..Self::default()
+        }
+    }
+
+    /// Create a filter for topics whose names match any of the patterns.
+    #[must_use]
+    pub fn topic_patterns<P: Into<TopicPattern>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        Self {
+            topic_patterns: patterns.into_iter().map(Into::into).collect(),
+            ..Self::default()
         }
     }
 
@@ -302,22 +652,106 @@ impl EventFilter {
     #[must_use]
     pub fn from_subsystems(subsystems: Vec<u8>) -> Self {
         Self {
-            topics: Vec::new(),
             source_subsystems: subsystems,
+            ..Self::default()
+        }
+    }
+
+    /// Restrict this filter to events sent by specific subsystems.
+    #[must_use]
+    pub fn from_senders(mut self, subsystems: Vec<u8>) -> Self {
+        self.source_subsystems.extend(subsystems);
+        self
+    }
+
+    /// Match events accepted by both this filter and `other`.
+    #[must_use]
+    pub fn and(mut self, other: EventFilter) -> Self {
+        self.all_of.push(other);
+        self
+    }
+
+    /// Match events accepted by either this filter or `other`.
+    #[must_use]
+    pub fn or(self, other: EventFilter) -> Self {
+        if self.is_disjunction() {
+            let mut disjunction = self;
+            disjunction.any_of.push(other);
+            return disjunction;
+        }
+        Self {
+            any_of: vec![self, other],
+            ..Self::default()
         }
     }
 
     /// Check if an event matches this filter.
     #[must_use]
     pub fn matches(&self, event: &BlockchainEvent) -> bool {
+        let topic = event.topic();
+
         let topic_match = self.topics.is_empty()
             || self.topics.contains(&EventTopic::All)
-            || self.topics.contains(&event.topic());
+            || self.topics.contains(&topic);
+
+        let pattern_match = self.topic_patterns.is_empty()
+            || self
+                .topic_patterns
+                .iter()
+                .any(|pattern| pattern.matches(topic));
 
         let source_match = self.source_subsystems.is_empty()
             || self.source_subsystems.contains(&event.source_subsystem());
 
-        topic_match && source_match
+        topic_match
+            && pattern_match
+            && source_match
+            && self.all_of.iter().all(|filter| filter.matches(event))
+            && (self.any_of.is_empty() || self.any_of.iter().any(|filter| filter.matches(event)))
+    }
+
+    /// Whether this filter is nothing but an OR of other filters.
+    fn is_disjunction(&self) -> bool {
+        !self.any_of.is_empty()
+            && self.topics.is_empty()
+            && self.topic_patterns.is_empty()
+            && self.source_subsystems.is_empty()
+            && self.all_of.is_empty()
+    }
+}
+
+impl std::fmt::Display for EventFilter {
+    /// Compact form used for subscription bookkeeping and logs, e.g.
+    /// `(topic=api.gateway & from=16)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut terms = Vec::new();
+        if !self.topics.is_empty() {
+            let names: Vec<_> = self.topics.iter().map(EventTopic::name).collect();
+            terms.push(format!("topic={}", names.join("|")));
+        }
+        if !self.topic_patterns.is_empty() {
+            let patterns: Vec<_> = self
+                .topic_patterns
+                .iter()
+                .map(TopicPattern::as_str)
+                .collect();
+            terms.push(format!("topic~{}", patterns.join("|")));
+        }
+        if !self.source_subsystems.is_empty() {
+            let sources: Vec<_> = self.source_subsystems.iter().map(u8::to_string).collect();
+            terms.push(format!("from={}", sources.join("|")));
+        }
+        terms.extend(self.all_of.iter().map(ToString::to_string));
+        if !self.any_of.is_empty() {
+            let alternatives: Vec<_> = self.any_of.iter().map(ToString::to_string).collect();
+            terms.push(format!("({})", alternatives.join(" | ")));
+        }
+
+        match terms.as_slice() {
+            [] => f.write_str("*"),
+            [term] => f.write_str(term),
+            _ => write!(f, "({})", terms.join(" & ")),
+        }
     }
 }
 
@@ -377,6 +811,17 @@ mod tests {
         assert_eq!(event.source_subsystem(), 3);
     }
 
+    #[test]
+    fn test_receipts_indexed_event() {
+        let event = BlockchainEvent::ReceiptsIndexed {
+            block_height: 7,
+            block_hash: Hash::default(),
+            logs: vec![serde_json::json!({ "logIndex": "0x0" })],
+        };
+        assert_eq!(event.topic(), EventTopic::TransactionIndexing);
+        assert_eq!(event.source_subsystem(), 3);
+    }
+
     #[test]
     fn test_state_root_event() {
         let event = BlockchainEvent::StateRootComputed {
@@ -386,4 +831,145 @@ mod tests {
         assert_eq!(event.topic(), EventTopic::StateManagement);
         assert_eq!(event.source_subsystem(), 4);
     }
+
+    #[test]
+    fn test_capabilities_event() {
+        let event = BlockchainEvent::CapabilitiesAdvertised(CapabilityReport::default());
+        assert_eq!(event.topic(), EventTopic::Registry);
+        assert_eq!(event.source_subsystem(), 0);
+        assert!(!EventFilter::topics(vec![EventTopic::ApiGateway]).matches(&event));
+    }
+
+    #[test]
+    fn test_subsystem_status_event() {
+        let event = BlockchainEvent::SubsystemStatusChanged {
+            subsystem_id: 7,
+            action: "stop".into(),
+            status: SubsystemStatus::Stopped,
+            error: None,
+        };
+        assert_eq!(event.topic(), EventTopic::Registry);
+        assert_eq!(event.source_subsystem(), 0);
+    }
+
+    #[test]
+    fn test_node_alert_event() {
+        let event = BlockchainEvent::NodeAlert {
+            rule: "LowPeerCount".into(),
+            severity: "warning".into(),
+            firing: true,
+            value: 1.0,
+            threshold: 3.0,
+            summary: "Too few connected peers".into(),
+        };
+        assert_eq!(event.topic(), EventTopic::Alerts);
+        assert_eq!(event.source_subsystem(), 0);
+        assert!(EventFilter::topic_patterns(["node.*"]).matches(&event));
+    }
+
+    #[test]
+    fn test_block_propagation_events() {
+        let request = BlockchainEvent::PropagateBlockRequest {
+            block_hash: [1; 32],
+            block_height: 7,
+            block_data: vec![0xAB; 16],
+            tx_hashes: vec![[2; 32]],
+        };
+        assert_eq!(request.topic(), EventTopic::Consensus);
+        assert_eq!(request.source_subsystem(), 8);
+
+        let received = BlockchainEvent::BlockReceived {
+            block_hash: [1; 32],
+            block_data: vec![0xAB; 16],
+            source_peer: [3; 32],
+        };
+        assert_eq!(received.topic(), EventTopic::BlockPropagation);
+        assert_eq!(received.source_subsystem(), 5);
+        assert!(EventFilter::topic_patterns(["block.*"]).matches(&received));
+    }
+
+    #[test]
+    fn test_sync_events() {
+        let progress = BlockchainEvent::SyncProgress {
+            phase: "headers".into(),
+            current: 10,
+            target: 100,
+        };
+        let completed = BlockchainEvent::SyncCompleted {
+            block_height: 100,
+            block_hash: [7u8; 32],
+        };
+        for event in [progress, completed] {
+            assert_eq!(event.topic(), EventTopic::Sync);
+            assert_eq!(event.source_subsystem(), 0);
+            assert!(EventFilter::topic_patterns(["node.*"]).matches(&event));
+        }
+    }
+
+    #[test]
+    fn test_topic_names_round_trip() {
+        for topic in EventTopic::ALL {
+            assert_eq!(EventTopic::from_name(topic.name()), Some(topic));
+        }
+        assert_eq!(EventTopic::DeadLetterQueue.name(), crate::DLQ_TOPIC);
+    }
+
+    #[test]
+    fn test_filter_by_topic_pattern() {
+        let filter = EventFilter::topic_patterns(["block.*"]);
+
+        let storage_event = BlockchainEvent::BlockStored {
+            block_height: 1,
+            block_hash: Hash::default(),
+        };
+        assert!(filter.matches(&storage_event));
+
+        let consensus_event = BlockchainEvent::BlockValidated(ValidatedBlock::default());
+        assert!(!filter.matches(&consensus_event));
+
+        assert!(EventFilter::topic_patterns(["*"]).matches(&consensus_event));
+        assert!(EventFilter::topic_patterns(["consensus"]).matches(&consensus_event));
+        assert!(!EventFilter::topic_patterns(["consensus.*"]).matches(&consensus_event));
+    }
+
+    #[test]
+    fn test_filter_composition() {
+        let query = BlockchainEvent::ApiQuery {
+            correlation_id: "1".into(),
+            target: "qc-02-block-storage".into(),
+            method: "get_block_number".into(),
+            params: serde_json::Value::Null,
+            trace_parent: None,
+        };
+        let response = BlockchainEvent::ApiQueryResponse {
+            correlation_id: "1".into(),
+            source: 2,
+            result: Ok(serde_json::Value::Null),
+        };
+        let stored = BlockchainEvent::BlockStored {
+            block_height: 1,
+            block_hash: Hash::default(),
+        };
+
+        // Only queries sent by the gateway, not the responses to them
+        let queries = EventFilter::topics(vec![EventTopic::ApiGateway]).from_senders(vec![16]);
+        assert!(queries.matches(&query));
+        assert!(!queries.matches(&response));
+
+        let either = queries.clone().or(EventFilter::topic_patterns(["block.*"]));
+        assert!(either.matches(&query));
+        assert!(either.matches(&stored));
+        assert!(!either.matches(&response));
+
+        let both =
+            EventFilter::topic_patterns(["api.*"]).and(EventFilter::from_subsystems(vec![2]));
+        assert!(both.matches(&response));
+        assert!(!both.matches(&query));
+
+        assert_eq!(queries.to_string(), "(topic=api.gateway & from=16)");
+        assert_eq!(
+            either.or(EventFilter::all()).to_string(),
+            "((topic=api.gateway & from=16) | topic~block.* | *)"
+        );
+    }
 }
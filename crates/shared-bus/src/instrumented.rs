@@ -0,0 +1,353 @@
+//! # Instrumented Bus
+//!
+//! Optional telemetry wrapper around `InMemoryEventBus` (feature
+//! `telemetry`). Handlers keep using `EventPublisher` and `recv`; the
+//! wrapper records:
+//!
+//! - publish→deliver latency per topic (`qc_eventbus_topic_delivery_latency_seconds`)
+//! - each subscriber's queue depth at receive (`qc_eventbus_subscriber_queue_depth`)
+//! - a `bus.publish` span and one `bus.deliver` span per receipt, sharing a
+//!   trace ID and a `correlation_id` attribute so Tempo shows the whole
+//!   choreography hop
+//!
+//! The correlation ID is the event's own when it has one (API queries),
+//! otherwise the journal payload hash. Hashing serializes the event, so the
+//! wrapper is meant for diagnosis rather than always-on production use.
+//!
+//! ```rust,ignore
+//! let bus = Arc::new(InstrumentedBus::new(event_bus));
+//! let mut sub = bus.subscribe(EventFilter::topics(vec![EventTopic::Consensus]));
+//! bus.publish(event).await;
+//! let event = sub.recv().await;
+//! ```
+
+use crate::backpressure::{SubscriberStats, SubscriptionOptions};
+use crate::events::{BlockchainEvent, EventFilter};
+use crate::journal::payload_hash;
+use crate::publisher::{EventPublisher, InMemoryEventBus};
+use crate::subscriber::{Subscription, SubscriptionError};
+use async_trait::async_trait;
+use quantum_telemetry::{
+    PropagatedContext, EVENT_BUS_SUBSCRIBER_QUEUE_DEPTH, EVENT_BUS_TOPIC_LATENCY,
+};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::Instrument;
+
+/// A published event awaiting delivery.
+#[derive(Debug, Clone)]
+struct Published {
+    at: Instant,
+    correlation_id: String,
+    context: PropagatedContext,
+}
+
+/// Recently published events, bounded so undelivered ones age out.
+#[derive(Debug)]
+struct InFlight {
+    capacity: usize,
+    state: Mutex<(HashMap<String, Published>, VecDeque<String>)>,
+}
+
+impl InFlight {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            state: Mutex::new((HashMap::new(), VecDeque::new())),
+        }
+    }
+
+    fn insert(&self, key: String, published: Published) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let (entries, order) = &mut *state;
+        if entries.insert(key.clone(), published).is_none() {
+            order.push_back(key);
+        }
+        while order.len() > self.capacity {
+            if let Some(oldest) = order.pop_front() {
+                entries.remove(&oldest);
+            }
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<Published> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.0.get(key).cloned()
+    }
+}
+
+/// Correlation ID and in-flight key of an event.
+///
+/// Queries, responses and dead letters share a correlation ID, so the key
+/// also includes topic and source.
+fn identify(event: &BlockchainEvent) -> (String, String) {
+    let correlation_id = match event {
+        BlockchainEvent::ApiQuery { correlation_id, .. }
+        | BlockchainEvent::ApiQueryResponse { correlation_id, .. }
+        | BlockchainEvent::ApiQueryDeadLetter { correlation_id, .. } => correlation_id.clone(),
+        _ => payload_hash(event).unwrap_or_default(),
+    };
+    let key = format!(
+        "{}/{}/{}",
+        event.topic(),
+        event.source_subsystem(),
+        correlation_id
+    );
+    (correlation_id, key)
+}
+
+/// Trace context an event's publish span continues, if it carries one.
+fn parent_context(event: &BlockchainEvent) -> PropagatedContext {
+    match event {
+        BlockchainEvent::ApiQuery {
+            trace_parent: Some(trace_parent),
+            ..
+        } => PropagatedContext::from_traceparent(trace_parent)
+            .unwrap_or_else(PropagatedContext::empty),
+        _ => PropagatedContext::empty(),
+    }
+}
+
+/// Event bus wrapper that records delivery latency, queue depth and spans.
+pub struct InstrumentedBus {
+    inner: Arc<InMemoryEventBus>,
+    in_flight: Arc<InFlight>,
+}
+
+impl InstrumentedBus {
+    /// Wrap a bus. Events are tracked until `capacity` newer ones are published.
+    #[must_use]
+    pub fn new(inner: Arc<InMemoryEventBus>) -> Self {
+        let capacity = inner.capacity();
+        Self {
+            inner,
+            in_flight: Arc::new(InFlight::new(capacity)),
+        }
+    }
+
+    /// The wrapped bus.
+    #[must_use]
+    pub fn inner(&self) -> &Arc<InMemoryEventBus> {
+        &self.inner
+    }
+
+    /// Subscribe with the default backpressure policy.
+    #[must_use]
+    pub fn subscribe(&self, filter: EventFilter) -> InstrumentedSubscription {
+        self.wrap(self.inner.subscribe(filter))
+    }
+
+    /// Subscribe with explicit options (name, backpressure policy).
+    #[must_use]
+    pub fn subscribe_with(
+        &self,
+        filter: EventFilter,
+        options: SubscriptionOptions,
+    ) -> InstrumentedSubscription {
+        self.wrap(self.inner.subscribe_with(filter, options))
+    }
+
+    fn wrap(&self, inner: Subscription) -> InstrumentedSubscription {
+        InstrumentedSubscription {
+            inner,
+            in_flight: Arc::clone(&self.in_flight),
+        }
+    }
+}
+
+#[async_trait]
+impl EventPublisher for InstrumentedBus {
+    async fn publish(&self, event: BlockchainEvent) -> usize {
+        let (correlation_id, key) = identify(&event);
+        let context = parent_context(&event).new_child();
+        let topic = event.topic();
+
+        let span = tracing::info_span!(
+            "bus.publish",
+            otel.trace_id = %context.trace_id,
+            otel.span_id = %context.span_id,
+            correlation_id = %correlation_id,
+            topic = %topic,
+            source = event.source_subsystem(),
+            receivers = tracing::field::Empty,
+        );
+
+        self.in_flight.insert(
+            key,
+            Published {
+                at: Instant::now(),
+                correlation_id,
+                context,
+            },
+        );
+
+        let receivers = self.inner.publish(event).instrument(span.clone()).await;
+        span.record("receivers", receivers);
+        receivers
+    }
+
+    fn events_published(&self) -> u64 {
+        self.inner.events_published()
+    }
+}
+
+/// Subscription that records latency and queue depth for each event received.
+pub struct InstrumentedSubscription {
+    inner: Subscription,
+    in_flight: Arc<InFlight>,
+}
+
+impl InstrumentedSubscription {
+    /// Receive the next matching event (see `Subscription::recv`).
+    pub async fn recv(&mut self) -> Option<BlockchainEvent> {
+        let event = self.inner.recv().await?;
+        self.observe(&event);
+        Some(event)
+    }
+
+    /// Receive without blocking (see `Subscription::try_recv`).
+    pub fn try_recv(&mut self) -> Result<Option<BlockchainEvent>, SubscriptionError> {
+        let event = self.inner.try_recv()?;
+        if let Some(event) = &event {
+            self.observe(event);
+        }
+        Ok(event)
+    }
+
+    /// Get the filter for this subscription.
+    #[must_use]
+    pub fn filter(&self) -> &EventFilter {
+        self.inner.filter()
+    }
+
+    /// Get this subscriber's lag and loss counters.
+    #[must_use]
+    pub fn stats(&self) -> SubscriberStats {
+        self.inner.stats()
+    }
+
+    /// Unwrap into the plain subscription.
+    #[must_use]
+    pub fn into_inner(self) -> Subscription {
+        self.inner
+    }
+
+    fn observe(&self, event: &BlockchainEvent) {
+        let stats = self.inner.stats();
+        EVENT_BUS_SUBSCRIBER_QUEUE_DEPTH
+            .with_label_values(&[stats.name.as_str()])
+            .set(stats.pending as f64);
+
+        let (_, key) = identify(event);
+        let Some(published) = self.in_flight.get(&key) else {
+            // Published before the wrapper saw it, or aged out
+            return;
+        };
+
+        let topic = event.topic();
+        let latency = published.at.elapsed();
+        EVENT_BUS_TOPIC_LATENCY
+            .with_label_values(&[topic.name()])
+            .observe(latency.as_secs_f64());
+
+        let context = published.context.new_child();
+        let span = tracing::info_span!(
+            "bus.deliver",
+            otel.trace_id = %context.trace_id,
+            otel.span_id = %context.span_id,
+            otel.parent_id = %published.context.span_id,
+            correlation_id = %published.correlation_id,
+            topic = %topic,
+            subscriber = %stats.name,
+            queue_depth = stats.pending,
+            latency_us = latency.as_micros() as u64,
+        );
+        let _entered = span.enter();
+        tracing::trace!("event delivered");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventTopic;
+    use shared_types::entities::Hash;
+
+    fn stored(height: u64) -> BlockchainEvent {
+        BlockchainEvent::BlockStored {
+            block_height: height,
+            block_hash: Hash::default(),
+        }
+    }
+
+    fn latency_samples(topic: EventTopic) -> u64 {
+        EVENT_BUS_TOPIC_LATENCY
+            .with_label_values(&[topic.name()])
+            .get_sample_count()
+    }
+
+    #[tokio::test]
+    async fn test_records_latency_and_queue_depth() {
+        let bus = InstrumentedBus::new(Arc::new(InMemoryEventBus::new()));
+        let mut sub = bus.subscribe_with(
+            EventFilter::topics(vec![EventTopic::BlockStorage]),
+            SubscriptionOptions::new("instrumented-test"),
+        );
+        let before = latency_samples(EventTopic::BlockStorage);
+
+        assert_eq!(bus.publish(stored(1)).await, 1);
+        assert_eq!(bus.publish(stored(2)).await, 1);
+        assert!(sub.recv().await.is_some());
+
+        assert!(latency_samples(EventTopic::BlockStorage) > before);
+        let depth = EVENT_BUS_SUBSCRIBER_QUEUE_DEPTH
+            .with_label_values(&["instrumented-test"])
+            .get();
+        assert_eq!(depth, 1.0);
+        assert_eq!(bus.events_published(), 2);
+    }
+
+    #[test]
+    fn test_correlated_events_keyed_apart() {
+        let query = BlockchainEvent::ApiQuery {
+            correlation_id: "abc".into(),
+            target: "qc-02-block-storage".into(),
+            method: "get_block_number".into(),
+            params: serde_json::Value::Null,
+            trace_parent: None,
+        };
+        let response = BlockchainEvent::ApiQueryResponse {
+            correlation_id: "abc".into(),
+            source: 2,
+            result: Ok(serde_json::Value::Null),
+        };
+
+        let (query_id, query_key) = identify(&query);
+        let (response_id, response_key) = identify(&response);
+        assert_eq!(query_id, "abc");
+        assert_eq!(query_id, response_id);
+        assert_ne!(query_key, response_key);
+
+        // Uncorrelated events fall back to the payload hash
+        let (id, _) = identify(&stored(1));
+        assert_eq!(id, payload_hash(&stored(1)).unwrap());
+    }
+
+    #[test]
+    fn test_in_flight_is_bounded() {
+        let in_flight = InFlight::new(2);
+        for key in ["a", "b", "c"] {
+            in_flight.insert(
+                key.to_string(),
+                Published {
+                    at: Instant::now(),
+                    correlation_id: key.to_string(),
+                    context: PropagatedContext::empty(),
+                },
+            );
+        }
+        assert!(in_flight.get("a").is_none());
+        assert!(in_flight.get("c").is_some());
+    }
+}
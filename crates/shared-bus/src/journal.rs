@@ -0,0 +1,442 @@
+//! # Event Journal
+//!
+//! Optional append-only audit trail of every event published on the bus,
+//! for debugging choreography after the fact (e.g. which component of a
+//! block assembly never arrived, and from whom the others came).
+//!
+//! Each published event adds one JSON line to the journal file:
+//!
+//! ```text
+//! {"offset":0,"timestamp_ms":..,"topic":"block.storage","sender":2,"payload_hash":"9f..","event":{..}}
+//! ```
+//!
+//! `payload_hash` is the hex SHA-256 of the event's JSON encoding. With
+//! `payloads` disabled only the metadata and hash are kept, which is enough
+//! to audit the order and origin of events but not to replay them.
+//!
+//! Unlike the `EventLog`, the journal has no consumers and is never
+//! compacted. `JournalReader` reads it back from any offset, and
+//! `replay_journal` re-publishes the recorded events on a bus, re-driving
+//! whatever subscribers it has.
+
+use crate::events::{BlockchainEvent, EventFilter};
+use crate::publisher::EventPublisher;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// Errors from the event journal.
+#[derive(Debug, Error)]
+pub enum JournalError {
+    /// Reading or writing the journal failed.
+    #[error("Event journal I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// An event could not be encoded.
+    #[error("Event journal serialization error: {0}")]
+    Serialization(String),
+
+    /// A line could not be parsed.
+    #[error("Corrupt event journal at line {line}: {reason}")]
+    Corrupt { line: u64, reason: String },
+
+    /// The entry was recorded without its event.
+    #[error("Journal entry {0} has no payload to replay")]
+    MissingPayload(u64),
+
+    /// The recorded event does not match its recorded hash.
+    #[error("Journal entry {0} does not match its payload hash")]
+    HashMismatch(u64),
+}
+
+/// Configuration of the event journal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalConfig {
+    /// Journal file.
+    pub path: PathBuf,
+    /// Record the event itself, not just its hash (needed for replay).
+    pub payloads: bool,
+    /// Flush every entry to disk before `record` returns.
+    pub fsync: bool,
+}
+
+impl JournalConfig {
+    /// Journal at `path`, recording payloads, without fsync.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            payloads: true,
+            fsync: false,
+        }
+    }
+
+    /// Set whether events are recorded alongside their hash.
+    #[must_use]
+    pub fn with_payloads(mut self, payloads: bool) -> Self {
+        self.payloads = payloads;
+        self
+    }
+
+    /// Set whether every entry is flushed to disk.
+    #[must_use]
+    pub fn with_fsync(mut self, fsync: bool) -> Self {
+        self.fsync = fsync;
+        self
+    }
+}
+
+/// One recorded event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Position of the entry in the journal.
+    pub offset: u64,
+    /// When the event was published (milliseconds since the Unix epoch).
+    pub timestamp_ms: u64,
+    /// Topic name (see `EventTopic::name`).
+    pub topic: String,
+    /// Subsystem that published the event.
+    pub sender: u8,
+    /// Hex SHA-256 of the event's JSON encoding.
+    pub payload_hash: String,
+    /// The event, if the journal records payloads.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<BlockchainEvent>,
+}
+
+impl JournalEntry {
+    /// The recorded event, checked against its hash.
+    pub fn verified_event(&self) -> Result<&BlockchainEvent, JournalError> {
+        let event = self
+            .event
+            .as_ref()
+            .ok_or(JournalError::MissingPayload(self.offset))?;
+        if payload_hash(event)? != self.payload_hash {
+            return Err(JournalError::HashMismatch(self.offset));
+        }
+        Ok(event)
+    }
+}
+
+/// Hex SHA-256 of an event's JSON encoding.
+pub fn payload_hash(event: &BlockchainEvent) -> Result<String, JournalError> {
+    let bytes =
+        serde_json::to_vec(event).map_err(|e| JournalError::Serialization(e.to_string()))?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+struct JournalState {
+    file: File,
+    /// Bytes of complete entries in the file.
+    len: u64,
+    next_offset: u64,
+}
+
+/// Append-only journal of published events.
+pub struct EventJournal {
+    config: JournalConfig,
+    state: Mutex<JournalState>,
+}
+
+impl EventJournal {
+    /// Open the journal, creating it if needed and continuing its offsets.
+    ///
+    /// A torn last line (crash mid-write) is cut off.
+    pub fn open(config: JournalConfig) -> Result<Self, JournalError> {
+        if let Some(parent) = config.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let (len, next_offset) = recover(&config.path)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&config.path)?;
+        file.set_len(len)?;
+
+        info!(
+            path = %config.path.display(),
+            next_offset,
+            payloads = config.payloads,
+            "Opened event journal"
+        );
+        Ok(Self {
+            config,
+            state: Mutex::new(JournalState {
+                file,
+                len,
+                next_offset,
+            }),
+        })
+    }
+
+    /// Configuration the journal was opened with.
+    pub fn config(&self) -> &JournalConfig {
+        &self.config
+    }
+
+    /// Record an event, returning its offset.
+    pub fn record(&self, event: &BlockchainEvent) -> Result<u64, JournalError> {
+        let mut state = self.lock();
+        let offset = state.next_offset;
+        let entry = JournalEntry {
+            offset,
+            timestamp_ms: now_ms(),
+            topic: event.topic().name().to_string(),
+            sender: event.source_subsystem(),
+            payload_hash: payload_hash(event)?,
+            event: self.config.payloads.then(|| event.clone()),
+        };
+        let mut line =
+            serde_json::to_vec(&entry).map_err(|e| JournalError::Serialization(e.to_string()))?;
+        line.push(b'\n');
+
+        if let Err(e) = state.file.write_all(&line) {
+            // Don't leave a partial line for the next entry to follow
+            let _ = state.file.set_len(state.len);
+            return Err(e.into());
+        }
+        if self.config.fsync {
+            state.file.sync_data()?;
+        }
+        state.len += line.len() as u64;
+        state.next_offset = offset + 1;
+        Ok(offset)
+    }
+
+    /// Offset the next recorded event will get.
+    pub fn next_offset(&self) -> u64 {
+        self.lock().next_offset
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, JournalState> {
+        // A panic mid-write leaves at most a torn line, which readers and
+        // recovery already handle
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl std::fmt::Debug for EventJournal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventJournal")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Reads journal entries from an offset on, oldest first.
+///
+/// A torn last line is treated as the end of the journal.
+pub struct JournalReader {
+    lines: std::io::Lines<BufReader<File>>,
+    from: u64,
+    line: u64,
+}
+
+impl JournalReader {
+    /// Read the journal at `path`, starting at entry `from`.
+    pub fn open(path: impl AsRef<Path>, from: u64) -> Result<Self, JournalError> {
+        let file = File::open(path)?;
+        Ok(Self {
+            lines: BufReader::new(file).lines(),
+            from,
+            line: 0,
+        })
+    }
+}
+
+impl Iterator for JournalReader {
+    type Item = Result<JournalEntry, JournalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e.into())),
+            };
+            self.line += 1;
+            let entry: JournalEntry = match serde_json::from_str(&line) {
+                Ok(entry) => entry,
+                // Torn last line
+                Err(e) if e.is_eof() => return None,
+                Err(e) => {
+                    return Some(Err(JournalError::Corrupt {
+                        line: self.line,
+                        reason: e.to_string(),
+                    }))
+                }
+            };
+            if entry.offset >= self.from {
+                return Some(Ok(entry));
+            }
+        }
+    }
+}
+
+/// Re-publish the journal's events matching `filter`, in order.
+///
+/// Stops at the first entry that cannot be replayed (no payload, hash
+/// mismatch). Returns the number of events published.
+pub async fn replay_journal(
+    reader: JournalReader,
+    filter: &EventFilter,
+    publisher: &impl EventPublisher,
+) -> Result<usize, JournalError> {
+    let mut replayed = 0;
+    for entry in reader {
+        let entry = entry?;
+        let event = entry.verified_event()?;
+        if !filter.matches(event) {
+            continue;
+        }
+        publisher.publish(event.clone()).await;
+        replayed += 1;
+    }
+    info!(replayed, "Replayed event journal");
+    Ok(replayed)
+}
+
+/// Length of the complete lines in the journal and the next offset.
+fn recover(path: &Path) -> Result<(u64, u64), JournalError> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((0, 0)),
+        Err(e) => return Err(e.into()),
+    };
+    let mut reader = BufReader::new(file);
+    let mut line = String::new();
+    let mut len = 0;
+    let mut next_offset = 0;
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)?;
+        if read == 0 {
+            break;
+        }
+        match serde_json::from_str::<JournalEntry>(&line) {
+            Ok(entry) if line.ends_with('\n') => {
+                len += read as u64;
+                next_offset = entry.offset + 1;
+            }
+            _ => {
+                warn!(path = %path.display(), position = len, "Discarding torn event journal entry");
+                break;
+            }
+        }
+    }
+    Ok((len, next_offset))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventTopic;
+    use crate::publisher::InMemoryEventBus;
+    use shared_types::entities::{Hash, ValidatedBlock};
+
+    fn stored(height: u64) -> BlockchainEvent {
+        BlockchainEvent::BlockStored {
+            block_height: height,
+            block_hash: Hash::default(),
+        }
+    }
+
+    #[test]
+    fn test_record_and_read_from_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        let journal = EventJournal::open(JournalConfig::new(&path)).unwrap();
+        for height in 0..3 {
+            assert_eq!(journal.record(&stored(height)).unwrap(), height);
+        }
+
+        let entries: Vec<_> = JournalReader::open(&path, 1)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].offset, 1);
+        assert_eq!(entries[0].topic, "block.storage");
+        assert_eq!(entries[0].sender, stored(1).source_subsystem());
+        assert!(entries[0].verified_event().is_ok());
+    }
+
+    #[test]
+    fn test_reopen_continues_offsets_after_torn_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        let journal = EventJournal::open(JournalConfig::new(&path)).unwrap();
+        journal.record(&stored(0)).unwrap();
+        drop(journal);
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"offset\":1,\"timest").unwrap();
+        drop(file);
+
+        let journal = EventJournal::open(JournalConfig::new(&path)).unwrap();
+        assert_eq!(journal.next_offset(), 1);
+        assert_eq!(journal.record(&stored(1)).unwrap(), 1);
+        assert_eq!(JournalReader::open(&path, 0).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_hash_only_entries_cannot_be_replayed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        let config = JournalConfig::new(&path).with_payloads(false);
+        let journal = EventJournal::open(config).unwrap();
+        journal.record(&stored(0)).unwrap();
+
+        let entry = JournalReader::open(&path, 0)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert!(entry.event.is_none());
+        assert_eq!(entry.payload_hash, payload_hash(&stored(0)).unwrap());
+        assert!(matches!(
+            entry.verified_event(),
+            Err(JournalError::MissingPayload(0))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_replay_redrives_subscriber() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        let journal = EventJournal::open(JournalConfig::new(&path)).unwrap();
+        journal.record(&stored(0)).unwrap();
+        journal
+            .record(&BlockchainEvent::BlockValidated(ValidatedBlock::default()))
+            .unwrap();
+        journal.record(&stored(2)).unwrap();
+
+        let bus = InMemoryEventBus::new();
+        let mut sub = bus.subscribe(EventFilter::all());
+        let filter = EventFilter::topics(vec![EventTopic::BlockStorage]);
+        let reader = JournalReader::open(&path, 1).unwrap();
+        assert_eq!(replay_journal(reader, &filter, &bus).await.unwrap(), 1);
+
+        assert!(matches!(
+            sub.try_recv().unwrap(),
+            Some(BlockchainEvent::BlockStored {
+                block_height: 2,
+                ..
+            })
+        ));
+        assert!(sub.try_recv().unwrap().is_none());
+    }
+}
@@ -27,7 +27,43 @@
 //!
 //! - **Time-Bounded Nonce Cache:** Prevents replay attacks (v2.1)
 //! - **Envelope-Only Identity:** `sender_id` from envelope is sole authority
-//! - **Dead Letter Queue:** Failed messages routed to DLQ for investigation
+//! - **Dead Letter Queue:** Failed messages routed to DLQ for investigation;
+//!   the bus retains them for listing, acknowledgement and replay
+//!
+//! ## Durability
+//!
+//! The bus is in-memory by default. Attach an `EventLog` to persist every
+//! event to an on-disk segment log; named consumers then subscribe with
+//! `subscribe_durable` and get at-least-once delivery across restarts.
+//!
+//! Separately, an `EventJournal` keeps an append-only audit trail of every
+//! published event (topic, sender, payload hash, timestamp) that can be
+//! read back and replayed from any offset to reproduce choreography bugs.
+//!
+//! ## Backpressure
+//!
+//! Each subscription picks what happens when it falls behind (see
+//! `backpressure`): drop the oldest events (default), block publishers, or
+//! spill to disk. `subscriber_stats` reports per-subscriber lag and loss.
+//!
+//! ## Priority Lanes
+//!
+//! Events are classed critical, normal or bulk (`BlockchainEvent::priority`)
+//! and each subscriber drains its lanes in strict priority order, so the
+//! choreography path never waits behind API or reporting traffic.
+//!
+//! ## Cross-Process Transport
+//!
+//! `UnixTransport` bridges selected topics to other processes over a Unix
+//! domain socket. Events cross the socket in HMAC-signed
+//! `AuthenticatedMessage` envelopes and are verified (timestamp, nonce,
+//! signature) before they are published on the receiving bus.
+//!
+//! ## Telemetry
+//!
+//! With the `telemetry` feature, `InstrumentedBus` wraps the bus to record
+//! per-topic delivery latency, per-subscriber queue depth and Tempo spans
+//! linked by correlation ID.
 
 #![warn(missing_docs)]
 #![allow(missing_docs)] // TODO: Add documentation for all public items
@@ -39,16 +75,44 @@
 #![cfg_attr(test, allow(clippy::expect_used))]
 #![cfg_attr(test, allow(clippy::panic))]
 
+pub mod backpressure;
+pub mod dead_letter;
+pub mod durable;
+pub mod event_log;
 pub mod events;
+#[cfg(feature = "telemetry")]
+pub mod instrumented;
+pub mod journal;
+pub mod mirror;
 pub mod nonce_cache;
+pub mod priority;
 pub mod publisher;
 pub mod subscriber;
+#[cfg(unix)]
+pub mod transport;
 
 // Re-export main types
-pub use events::{ApiQueryError, BlockchainEvent, EventFilter, EventTopic};
-pub use nonce_cache::TimeBoundedNonceCache;
+pub use backpressure::{BackpressurePolicy, SubscriberStats, SubscriptionOptions};
+pub use dead_letter::{
+    DeadLetterConfig, DeadLetterEntry, DeadLetterError, DeadLetterQueue, DeadLetterStats,
+};
+pub use durable::{DeliveredEvent, DurableSubscription};
+pub use event_log::{EventLog, EventLogConfig, EventLogError};
+pub use events::{ApiQueryError, BlockchainEvent, EventFilter, EventTopic, TopicPattern};
+#[cfg(feature = "telemetry")]
+pub use instrumented::{InstrumentedBus, InstrumentedSubscription};
+pub use journal::{
+    replay_journal, EventJournal, JournalConfig, JournalEntry, JournalError, JournalReader,
+};
+pub use mirror::{EventMirror, MirroredEvent, DEFAULT_MIRROR_EVENTS_PER_TOPIC};
+pub use nonce_cache::{NonceCacheError, TimeBoundedNonceCache};
+pub use priority::EventPriority;
 pub use publisher::{EventPublisher, InMemoryEventBus};
 pub use subscriber::{EventStream, EventSubscriber, Subscription, SubscriptionError};
+#[cfg(unix)]
+pub use transport::{
+    TransportConfig, TransportError, TransportHandle, TransportStats, UnixTransport,
+};
 
 /// Current protocol version for event bus messages.
 pub const PROTOCOL_VERSION: u16 = 1;
@@ -0,0 +1,206 @@
+//! # Event Mirror
+//!
+//! Keeps the most recent events of every topic, so operators can see what
+//! went over the bus without subscribing to it (the `debug_eventBus` RPC).
+//!
+//! Mirrored events are numbered in publish order. Readers poll with the
+//! last number they saw and get only what was published since; anything
+//! pushed out of a topic's window in between is simply not seen.
+
+use crate::events::{BlockchainEvent, EventTopic, TopicPattern};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Default number of events kept per topic.
+pub const DEFAULT_MIRROR_EVENTS_PER_TOPIC: usize = 64;
+
+/// A mirrored event.
+#[derive(Debug, Clone)]
+pub struct MirroredEvent {
+    /// Mirror-assigned sequence number, increasing in publish order.
+    pub seq: u64,
+    /// The event as published.
+    pub event: BlockchainEvent,
+    /// When the event was published.
+    pub published_at: SystemTime,
+}
+
+#[derive(Debug, Default)]
+struct MirrorState {
+    recent: HashMap<EventTopic, VecDeque<MirroredEvent>>,
+    published: HashMap<EventTopic, u64>,
+    next_seq: u64,
+}
+
+/// Bounded per-topic window of published events.
+#[derive(Debug)]
+pub struct EventMirror {
+    per_topic: usize,
+    state: Mutex<MirrorState>,
+}
+
+impl Default for EventMirror {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIRROR_EVENTS_PER_TOPIC)
+    }
+}
+
+impl EventMirror {
+    /// Create a mirror keeping `per_topic` events of each topic.
+    #[must_use]
+    pub fn new(per_topic: usize) -> Self {
+        Self {
+            per_topic: per_topic.max(1),
+            state: Mutex::new(MirrorState {
+                next_seq: 1,
+                ..MirrorState::default()
+            }),
+        }
+    }
+
+    /// Get the number of events kept per topic.
+    #[must_use]
+    pub fn per_topic(&self) -> usize {
+        self.per_topic
+    }
+
+    /// Keep `event`, dropping the oldest event of its topic beyond the window.
+    pub(crate) fn record(&self, event: &BlockchainEvent) {
+        let topic = event.topic();
+        let mut state = self.lock();
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        *state.published.entry(topic).or_default() += 1;
+
+        let recent = state.recent.entry(topic).or_default();
+        if recent.len() == self.per_topic {
+            recent.pop_front();
+        }
+        recent.push_back(MirroredEvent {
+            seq,
+            event: event.clone(),
+            published_at: SystemTime::now(),
+        });
+    }
+
+    /// Events numbered after `since` on topics matching any of `patterns`
+    /// (every topic if empty), at most `limit` per topic (the newest),
+    /// oldest first.
+    #[must_use]
+    pub fn recent(
+        &self,
+        patterns: &[TopicPattern],
+        since: u64,
+        limit: usize,
+    ) -> Vec<(EventTopic, Vec<MirroredEvent>)> {
+        let state = self.lock();
+        let mut topics: Vec<_> = state
+            .recent
+            .iter()
+            .filter(|(topic, _)| patterns.is_empty() || patterns.iter().any(|p| p.matches(**topic)))
+            .map(|(topic, recent)| {
+                let newer: Vec<_> = recent.iter().filter(|e| e.seq > since).collect();
+                let skip = newer.len().saturating_sub(limit);
+                let events: Vec<_> = newer.into_iter().skip(skip).cloned().collect();
+                (*topic, events)
+            })
+            .filter(|(_, events)| !events.is_empty())
+            .collect();
+        topics.sort_by_key(|(topic, _)| topic.name());
+        topics
+    }
+
+    /// Events ever published per topic, including those no longer kept.
+    #[must_use]
+    pub fn published(&self) -> Vec<(EventTopic, u64)> {
+        let mut published: Vec<_> = self
+            .lock()
+            .published
+            .iter()
+            .map(|(topic, count)| (*topic, *count))
+            .collect();
+        published.sort_by_key(|(topic, _)| topic.name());
+        published
+    }
+
+    /// Sequence number of the latest mirrored event (0 if none).
+    #[must_use]
+    pub fn last_seq(&self) -> u64 {
+        self.lock().next_seq - 1
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MirrorState> {
+        // Every mutation leaves the mirror consistent, so a poisoned lock
+        // is still usable
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_types::subsystem_registry::CapabilityReport;
+
+    fn progress(phase: &str) -> BlockchainEvent {
+        BlockchainEvent::SyncProgress {
+            phase: phase.to_string(),
+            current: 0,
+            target: 0,
+        }
+    }
+
+    fn registry() -> BlockchainEvent {
+        BlockchainEvent::CapabilitiesAdvertised(CapabilityReport::default())
+    }
+
+    fn phases(events: &[MirroredEvent]) -> Vec<String> {
+        events
+            .iter()
+            .filter_map(|e| match &e.event {
+                BlockchainEvent::SyncProgress { phase, .. } => Some(phase.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_keeps_newest_events_per_topic() {
+        let mirror = EventMirror::new(2);
+        mirror.record(&progress("a"));
+        mirror.record(&registry());
+        mirror.record(&progress("b"));
+        mirror.record(&progress("c"));
+
+        let recent = mirror.recent(&[], 0, 10);
+        let topics: Vec<_> = recent.iter().map(|(topic, _)| *topic).collect();
+        assert_eq!(topics, vec![EventTopic::Registry, EventTopic::Sync]);
+        assert_eq!(phases(&recent[1].1), vec!["b", "c"]);
+        assert_eq!(mirror.last_seq(), 4);
+        assert_eq!(
+            mirror.published(),
+            vec![(EventTopic::Registry, 1), (EventTopic::Sync, 3)]
+        );
+    }
+
+    #[test]
+    fn test_recent_since_limit_and_patterns() {
+        let mirror = EventMirror::default();
+        for phase in ["a", "b", "c", "d"] {
+            mirror.record(&progress(phase));
+        }
+        mirror.record(&registry());
+
+        let recent = mirror.recent(&[TopicPattern::new("node.sync")], 1, 2);
+        assert_eq!(recent.len(), 1);
+        assert_eq!(phases(&recent[0].1), vec!["c", "d"]);
+
+        let recent = mirror.recent(&[TopicPattern::new("node.*")], 4, 10);
+        let topics: Vec<_> = recent.iter().map(|(topic, _)| *topic).collect();
+        assert_eq!(topics, vec![EventTopic::Registry]);
+
+        assert!(mirror.recent(&[], mirror.last_seq(), 10).is_empty());
+    }
+}
@@ -7,12 +7,34 @@
 //! - Nonces are valid only within the message timestamp window (60s past, 10s future)
 //! - Nonces are garbage-collected after the validity window expires
 //! - This bounds memory usage while preventing replay attacks
+//!
+//! ## Concurrency
+//!
+//! The cache is sharded by sender subsystem, each shard behind its own
+//! lock, so checks for different senders never contend. Lookups take only a
+//! shared lock on one shard and the size is an atomic counter, so readers
+//! never wait on each other or on other senders' inserts.
+//!
+//! ## Persistence
+//!
+//! A cache that forgets its nonces on restart accepts a replay of any
+//! message still inside the timestamp window. `save` snapshots the live
+//! nonces to a file and `restore` reloads them, skipping those that have
+//! since expired.
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 use uuid::Uuid;
 
+/// Number of shards: one per possible sender ID.
+const SHARD_COUNT: usize = u8::MAX as usize + 1;
+
 /// Errors from nonce cache operations.
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum NonceError {
@@ -29,22 +51,55 @@ pub enum NonceError {
     MessageFromFuture { timestamp: u64, threshold: u64 },
 }
 
+/// Errors from saving or restoring the nonce cache.
+#[derive(Debug, Error)]
+pub enum NonceCacheError {
+    /// Reading or writing the snapshot failed.
+    #[error("Nonce cache I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The snapshot could not be encoded or parsed.
+    #[error("Nonce cache snapshot error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// A nonce in a persisted snapshot.
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotEntry {
+    sender: u8,
+    nonce: Uuid,
+    timestamp: u64,
+}
+
+/// Nonces of one sender.
+#[derive(Debug)]
+struct Shard {
+    /// Map of nonce -> message timestamp.
+    nonces: HashMap<Uuid, u64>,
+
+    /// Last garbage collection timestamp.
+    last_gc: u64,
+}
+
 /// Time-bounded cache for replay prevention.
 ///
 /// Per Architecture.md v2.1:
 /// - Timestamp window: now - 60s to now + 10s
 /// - Nonce validity: 120s (2x the timestamp window)
 /// - Garbage collection: Every 10s
+///
+/// Nonces are tracked per sender: the same nonce from two senders is two
+/// different messages.
 pub struct TimeBoundedNonceCache {
-    /// Map of nonce -> timestamp when nonce was first seen.
-    cache: HashMap<Uuid, u64>,
+    /// Nonces by sender ID.
+    shards: Box<[RwLock<Shard>]>,
+
+    /// Total number of cached nonces.
+    len: AtomicUsize,
 
     /// Nonce validity window in seconds (default: 120s = 2x message window).
     validity_window_secs: u64,
 
-    /// Last garbage collection timestamp.
-    last_gc: u64,
-
     /// Garbage collection interval in seconds.
     gc_interval_secs: u64,
 }
@@ -65,23 +120,25 @@ impl TimeBoundedNonceCache {
     /// Create a new nonce cache with default settings.
     #[must_use]
     pub fn new() -> Self {
-        let now = Self::current_timestamp();
-        Self {
-            cache: HashMap::new(),
-            validity_window_secs: Self::DEFAULT_VALIDITY_WINDOW,
-            last_gc: now,
-            gc_interval_secs: Self::DEFAULT_GC_INTERVAL,
-        }
+        Self::with_config(Self::DEFAULT_VALIDITY_WINDOW, Self::DEFAULT_GC_INTERVAL)
     }
 
     /// Create a nonce cache with custom settings.
     #[must_use]
     pub fn with_config(validity_window_secs: u64, gc_interval_secs: u64) -> Self {
         let now = Self::current_timestamp();
+        let shards = (0..SHARD_COUNT)
+            .map(|_| {
+                RwLock::new(Shard {
+                    nonces: HashMap::new(),
+                    last_gc: now,
+                })
+            })
+            .collect();
         Self {
-            cache: HashMap::new(),
+            shards,
+            len: AtomicUsize::new(0),
             validity_window_secs,
-            last_gc: now,
             gc_interval_secs,
         }
     }
@@ -99,7 +156,12 @@ impl TimeBoundedNonceCache {
     /// - `NonceError::MessageTooOld` - Timestamp older than 60s
     /// - `NonceError::MessageFromFuture` - Timestamp more than 10s in future
     /// - `NonceError::NonceReused` - Nonce has been seen before
-    pub fn validate_and_add(&mut self, nonce: Uuid, timestamp: u64) -> Result<(), NonceError> {
+    pub fn validate_and_add(
+        &self,
+        sender_id: u8,
+        nonce: Uuid,
+        timestamp: u64,
+    ) -> Result<(), NonceError> {
         let now = Self::current_timestamp();
 
         // ╔═══════════════════════════════════════════════════════════════╗
@@ -130,16 +192,17 @@ impl TimeBoundedNonceCache {
         // ║  STEP 2: GARBAGE COLLECT (Periodic)                           ║
         // ╚═══════════════════════════════════════════════════════════════╝
 
-        if now.saturating_sub(self.last_gc) > self.gc_interval_secs {
-            self.garbage_collect(now);
-            self.last_gc = now;
+        let mut shard = self.write_shard(sender_id);
+        if now.saturating_sub(shard.last_gc) > self.gc_interval_secs {
+            self.garbage_collect(&mut shard, now);
+            shard.last_gc = now;
         }
 
         // ╔═══════════════════════════════════════════════════════════════╗
         // ║  STEP 3: NONCE CHECK                                          ║
         // ╚═══════════════════════════════════════════════════════════════╝
 
-        if self.cache.contains_key(&nonce) {
+        if shard.nonces.contains_key(&nonce) {
             return Err(NonceError::NonceReused { nonce });
         }
 
@@ -147,33 +210,110 @@ impl TimeBoundedNonceCache {
         // ║  STEP 4: ADD NONCE                                            ║
         // ╚═══════════════════════════════════════════════════════════════╝
 
-        self.cache.insert(nonce, timestamp);
+        shard.nonces.insert(nonce, timestamp);
+        self.len.fetch_add(1, Ordering::Relaxed);
 
         Ok(())
     }
 
-    /// Check if a nonce exists without adding it.
+    /// Check if a sender's nonce exists without adding it.
     #[must_use]
-    pub fn contains(&self, nonce: &Uuid) -> bool {
-        self.cache.contains_key(nonce)
+    pub fn contains(&self, sender_id: u8, nonce: &Uuid) -> bool {
+        self.read_shard(sender_id).nonces.contains_key(nonce)
     }
 
     /// Get the number of cached nonces.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.cache.len()
+        self.len.load(Ordering::Relaxed)
     }
 
     /// Check if the cache is empty.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.cache.is_empty()
+        self.len() == 0
     }
 
-    /// Remove expired nonces from the cache.
-    fn garbage_collect(&mut self, now: u64) {
-        let expiry_threshold = now.saturating_sub(self.validity_window_secs);
-        self.cache.retain(|_, &mut ts| ts > expiry_threshold);
+    /// Write the unexpired nonces to `path`, returning how many were saved.
+    ///
+    /// The snapshot replaces `path` atomically, so a crash mid-save leaves
+    /// the previous snapshot intact.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<usize, NonceCacheError> {
+        let path = path.as_ref();
+        let threshold = self.expiry_threshold(Self::current_timestamp());
+        let mut entries = Vec::new();
+        for (sender, shard) in (0..=u8::MAX).zip(self.shards.iter()) {
+            let shard = shard
+                .read()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            entries.extend(
+                shard
+                    .nonces
+                    .iter()
+                    .filter(|(_, &timestamp)| timestamp > threshold)
+                    .map(|(&nonce, &timestamp)| SnapshotEntry {
+                        sender,
+                        nonce,
+                        timestamp,
+                    }),
+            );
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec(&entries)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(entries.len())
+    }
+
+    /// Load the unexpired nonces saved at `path`, returning how many were
+    /// added. A missing file restores nothing.
+    pub fn restore(&self, path: impl AsRef<Path>) -> Result<usize, NonceCacheError> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+        let entries: Vec<SnapshotEntry> = serde_json::from_slice(&bytes)?;
+
+        let threshold = self.expiry_threshold(Self::current_timestamp());
+        let mut restored = 0;
+        for entry in entries.into_iter().filter(|e| e.timestamp > threshold) {
+            let mut shard = self.write_shard(entry.sender);
+            if shard.nonces.insert(entry.nonce, entry.timestamp).is_none() {
+                self.len.fetch_add(1, Ordering::Relaxed);
+                restored += 1;
+            }
+        }
+        Ok(restored)
+    }
+
+    /// Remove expired nonces from a shard.
+    fn garbage_collect(&self, shard: &mut Shard, now: u64) {
+        let expiry_threshold = self.expiry_threshold(now);
+        let before = shard.nonces.len();
+        shard.nonces.retain(|_, &mut ts| ts > expiry_threshold);
+        self.len
+            .fetch_sub(before - shard.nonces.len(), Ordering::Relaxed);
+    }
+
+    fn expiry_threshold(&self, now: u64) -> u64 {
+        now.saturating_sub(self.validity_window_secs)
+    }
+
+    fn read_shard(&self, sender_id: u8) -> RwLockReadGuard<'_, Shard> {
+        // Shards hold plain data, so a poisoned lock is still usable
+        self.shards[usize::from(sender_id)]
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn write_shard(&self, sender_id: u8) -> RwLockWriteGuard<'_, Shard> {
+        self.shards[usize::from(sender_id)]
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
     }
 
     /// Get current Unix timestamp.
@@ -195,77 +335,79 @@ impl Default for TimeBoundedNonceCache {
 mod tests {
     use super::*;
 
+    const SENDER: u8 = 8;
+
     fn now() -> u64 {
         TimeBoundedNonceCache::current_timestamp()
     }
 
     #[test]
     fn test_valid_nonce() {
-        let mut cache = TimeBoundedNonceCache::new();
+        let cache = TimeBoundedNonceCache::new();
         let nonce = Uuid::new_v4();
         let timestamp = now();
 
-        assert!(cache.validate_and_add(nonce, timestamp).is_ok());
-        assert!(cache.contains(&nonce));
+        assert!(cache.validate_and_add(SENDER, nonce, timestamp).is_ok());
+        assert!(cache.contains(SENDER, &nonce));
     }
 
     #[test]
     fn test_duplicate_nonce_rejected() {
-        let mut cache = TimeBoundedNonceCache::new();
+        let cache = TimeBoundedNonceCache::new();
         let nonce = Uuid::new_v4();
         let timestamp = now();
 
-        assert!(cache.validate_and_add(nonce, timestamp).is_ok());
+        assert!(cache.validate_and_add(SENDER, nonce, timestamp).is_ok());
 
-        let result = cache.validate_and_add(nonce, timestamp);
+        let result = cache.validate_and_add(SENDER, nonce, timestamp);
         assert!(matches!(result, Err(NonceError::NonceReused { .. })));
     }
 
     #[test]
     fn test_timestamp_too_old() {
-        let mut cache = TimeBoundedNonceCache::new();
+        let cache = TimeBoundedNonceCache::new();
         let nonce = Uuid::new_v4();
         let old_timestamp = now().saturating_sub(120); // 2 minutes ago
 
-        let result = cache.validate_and_add(nonce, old_timestamp);
+        let result = cache.validate_and_add(SENDER, nonce, old_timestamp);
         assert!(matches!(result, Err(NonceError::MessageTooOld { .. })));
     }
 
     #[test]
     fn test_timestamp_from_future() {
-        let mut cache = TimeBoundedNonceCache::new();
+        let cache = TimeBoundedNonceCache::new();
         let nonce = Uuid::new_v4();
         let future_timestamp = now() + 60; // 1 minute in future
 
-        let result = cache.validate_and_add(nonce, future_timestamp);
+        let result = cache.validate_and_add(SENDER, nonce, future_timestamp);
         assert!(matches!(result, Err(NonceError::MessageFromFuture { .. })));
     }
 
     #[test]
     fn test_timestamp_within_skew_allowed() {
-        let mut cache = TimeBoundedNonceCache::new();
+        let cache = TimeBoundedNonceCache::new();
 
         // 5 seconds in future (within 10s skew)
         let nonce1 = Uuid::new_v4();
         let future_ok = now() + 5;
-        assert!(cache.validate_and_add(nonce1, future_ok).is_ok());
+        assert!(cache.validate_and_add(SENDER, nonce1, future_ok).is_ok());
 
         // 30 seconds in past (within 60s window)
         let nonce2 = Uuid::new_v4();
         let past_ok = now().saturating_sub(30);
-        assert!(cache.validate_and_add(nonce2, past_ok).is_ok());
+        assert!(cache.validate_and_add(SENDER, nonce2, past_ok).is_ok());
     }
 
     #[test]
     fn test_cache_length() {
-        let mut cache = TimeBoundedNonceCache::new();
+        let cache = TimeBoundedNonceCache::new();
         assert!(cache.is_empty());
         assert_eq!(cache.len(), 0);
 
         let timestamp = now();
         for _ in 0..5 {
             let nonce = Uuid::new_v4();
-            cache.validate_and_add(nonce, timestamp).unwrap();
+            cache.validate_and_add(SENDER, nonce, timestamp).unwrap();
         }
 
         assert_eq!(cache.len(), 5);
@@ -278,4 +420,40 @@ mod tests {
         assert_eq!(cache.validity_window_secs, 60);
         assert_eq!(cache.gc_interval_secs, 5);
     }
+
+    #[test]
+    fn test_nonces_are_tracked_per_sender() {
+        let cache = TimeBoundedNonceCache::new();
+        let nonce = Uuid::new_v4();
+
+        cache.validate_and_add(SENDER, nonce, now()).unwrap();
+        assert!(cache.validate_and_add(SENDER + 1, nonce, now()).is_ok());
+        assert!(cache.contains(SENDER + 1, &nonce));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_save_and_restore_rejects_replay_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nonces.json");
+        let nonce = Uuid::new_v4();
+
+        let cache = TimeBoundedNonceCache::new();
+        cache.validate_and_add(SENDER, nonce, now()).unwrap();
+        // Expired by the time of the restart: not saved
+        cache.shards[usize::from(SENDER)]
+            .write()
+            .unwrap()
+            .nonces
+            .insert(Uuid::new_v4(), now().saturating_sub(300));
+        assert_eq!(cache.save(&path).unwrap(), 1);
+
+        let restarted = TimeBoundedNonceCache::new();
+        assert_eq!(restarted.restore(&path).unwrap(), 1);
+        let result = restarted.validate_and_add(SENDER, nonce, now());
+        assert!(matches!(result, Err(NonceError::NonceReused { .. })));
+
+        let fresh = TimeBoundedNonceCache::new();
+        assert_eq!(fresh.restore(dir.path().join("missing.json")).unwrap(), 0);
+    }
 }
@@ -0,0 +1,173 @@
+//! # Priority Lanes
+//!
+//! Every event travels in one of three lanes by its `EventPriority`, and
+//! every subscription drains its lanes in strict priority order: a bulk
+//! event is only delivered when no critical or normal event is waiting.
+//!
+//! Lanes also have separate buffers, so a flood of API queries can make a
+//! subscriber lag (or block, or spill) on the bulk lane without touching
+//! the choreography events on the critical lane.
+
+use crate::events::BlockchainEvent;
+use serde::{Deserialize, Serialize};
+
+/// Delivery class of an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EventPriority {
+    /// Choreography and consensus: the block pipeline must never starve.
+    Critical,
+    /// Peer, transaction and dead letter traffic.
+    Normal,
+    /// API queries and reporting.
+    Bulk,
+}
+
+impl EventPriority {
+    /// All priorities, highest first.
+    pub const ALL: [Self; 3] = [Self::Critical, Self::Normal, Self::Bulk];
+
+    /// Short name, used as a metrics label.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::Normal => "normal",
+            Self::Bulk => "bulk",
+        }
+    }
+}
+
+impl BlockchainEvent {
+    /// Get the lane this event is delivered in.
+    #[must_use]
+    pub fn priority(&self) -> EventPriority {
+        match self {
+            Self::BlockProduced { .. }
+            | Self::BlockProposed { .. }
+            | Self::BlockValidated(_)
+            | Self::BlockRejected { .. }
+            | Self::SlotAssigned { .. }
+            | Self::MerkleRootComputed { .. }
+            | Self::StateRootComputed { .. }
+            | Self::BlockStored { .. }
+            | Self::GenesisInitialized { .. }
+            | Self::AttestationVerified(_)
+            | Self::BlockFinalized { .. }
+            | Self::CriticalError { .. } => EventPriority::Critical,
+            Self::PeerDiscovered(_)
+            | Self::PeerDisconnected(_)
+            | Self::VerifyNodeIdentity { .. }
+            | Self::NodeIdentityVerified { .. }
+            | Self::TransactionVerified(_)
+            | Self::TransactionInvalid { .. }
+            | Self::CapabilitiesAdvertised(_)
+            | Self::SubsystemStatusChanged { .. }
+            | Self::NodeAlert { .. }
+            | Self::SyncProgress { .. }
+            | Self::SyncCompleted { .. }
+            | Self::ApiQueryDeadLetter { .. }
+            | Self::PropagateBlockRequest { .. }
+            | Self::BlockReceived { .. }
+            | Self::ReceiptsIndexed { .. } => EventPriority::Normal,
+            Self::MevReportPublished { .. }
+            | Self::ApiQuery { .. }
+            | Self::ApiQueryResponse { .. } => EventPriority::Bulk,
+        }
+    }
+}
+
+/// One value per lane.
+#[derive(Debug)]
+pub(crate) struct Lanes<T> {
+    critical: T,
+    normal: T,
+    bulk: T,
+}
+
+impl<T> Lanes<T> {
+    /// Build each lane with `f`.
+    pub(crate) fn new(mut f: impl FnMut(EventPriority) -> T) -> Self {
+        Self {
+            critical: f(EventPriority::Critical),
+            normal: f(EventPriority::Normal),
+            bulk: f(EventPriority::Bulk),
+        }
+    }
+
+    pub(crate) fn get(&self, priority: EventPriority) -> &T {
+        match priority {
+            EventPriority::Critical => &self.critical,
+            EventPriority::Normal => &self.normal,
+            EventPriority::Bulk => &self.bulk,
+        }
+    }
+
+    /// Lanes highest priority first.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &T> {
+        [&self.critical, &self.normal, &self.bulk].into_iter()
+    }
+
+    /// Lanes highest priority first.
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        [&mut self.critical, &mut self.normal, &mut self.bulk].into_iter()
+    }
+
+    /// Mutable access to each lane, highest priority first.
+    pub(crate) fn as_mut(&mut self) -> (&mut T, &mut T, &mut T) {
+        (&mut self.critical, &mut self.normal, &mut self.bulk)
+    }
+
+    /// Derive a value per lane.
+    pub(crate) fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Lanes<U> {
+        Lanes {
+            critical: f(&self.critical),
+            normal: f(&self.normal),
+            bulk: f(&self.bulk),
+        }
+    }
+}
+
+impl<A, B> Lanes<(A, B)> {
+    /// Split lanes of pairs into a pair of lanes.
+    pub(crate) fn unzip(self) -> (Lanes<A>, Lanes<B>) {
+        (
+            Lanes {
+                critical: self.critical.0,
+                normal: self.normal.0,
+                bulk: self.bulk.0,
+            },
+            Lanes {
+                critical: self.critical.1,
+                normal: self.normal.1,
+                bulk: self.bulk.1,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared_types::entities::{Hash, ValidatedBlock};
+
+    #[test]
+    fn test_choreography_events_are_critical() {
+        let validated = BlockchainEvent::BlockValidated(ValidatedBlock::default());
+        assert_eq!(validated.priority(), EventPriority::Critical);
+
+        let query = BlockchainEvent::ApiQuery {
+            correlation_id: "1".into(),
+            target: "qc-02-block-storage".into(),
+            method: "get_block_number".into(),
+            params: serde_json::Value::Null,
+            trace_parent: None,
+        };
+        assert_eq!(query.priority(), EventPriority::Bulk);
+
+        let stored = BlockchainEvent::BlockStored {
+            block_height: 1,
+            block_hash: Hash::default(),
+        };
+        assert!(stored.priority() < query.priority());
+    }
+}
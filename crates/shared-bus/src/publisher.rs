@@ -2,16 +2,30 @@
 //!
 //! Defines the publishing side of the event bus.
 
-use crate::events::{BlockchainEvent, EventFilter};
+use crate::backpressure::{
+    BackpressurePolicy, QueuedSubscriber, SpillQueue, SubscriberMetrics, SubscriberStats,
+    SubscriptionOptions,
+};
+use crate::dead_letter::{DeadLetterConfig, DeadLetterError, DeadLetterQueue};
+use crate::durable::DurableSubscription;
+use crate::event_log::{EventLog, EventLogError};
+use crate::events::{BlockchainEvent, EventFilter, EventTopic};
+use crate::journal::EventJournal;
+use crate::mirror::EventMirror;
 use crate::nonce_cache::TimeBoundedNonceCache;
-use crate::subscriber::{EventStream, Subscription};
+use crate::priority::{EventPriority, Lanes};
+use crate::subscriber::{EventSource, EventStream, Subscription};
 use crate::DEFAULT_CHANNEL_CAPACITY;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
-use tokio::sync::broadcast;
-use tracing::{debug, warn};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, error, warn};
+
+/// How often `drain` re-checks subscriber queues.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
 /// Trait for publishing events to the bus.
 ///
@@ -39,12 +53,35 @@ pub trait EventPublisher: Send + Sync {
 /// Uses `tokio::sync::broadcast` for multi-producer, multi-consumer semantics.
 /// Suitable for single-node operation; distributed deployments would use
 /// a different implementation (e.g., Redis, Kafka).
+///
+/// With an `EventLog` attached (`with_event_log`), every event is appended
+/// to the log before it is broadcast, and named consumers can read it with
+/// at-least-once delivery through `subscribe_durable`. Plain subscriptions
+/// are unaffected.
+///
+/// With an `EventJournal` attached (`with_journal`), every event is also
+/// recorded in an append-only audit journal that can be read back and
+/// replayed from any offset.
+///
+/// Events are delivered in priority lanes (see `priority`), so consensus
+/// traffic is never queued behind API queries.
+///
+/// Subscriptions default to the `DropOldest` backpressure policy: a
+/// subscriber more than `capacity` events behind loses the oldest ones.
+/// `subscribe_with` selects `Block` or `SpillToDisk` instead.
+///
+/// Events on the `dlq.critical` topic are also kept in a bounded
+/// `DeadLetterQueue` (`dead_letters`), so they can be inspected, acknowledged
+/// or replayed after the fact.
+///
+/// With an `EventMirror` attached (`with_mirror`), the most recent events of
+/// every topic are kept for inspection.
 pub struct InMemoryEventBus {
-    /// Broadcast sender for events.
-    sender: broadcast::Sender<BlockchainEvent>,
+    /// Broadcast sender for events, per priority lane.
+    senders: Lanes<broadcast::Sender<BlockchainEvent>>,
 
     /// Nonce cache for replay prevention.
-    nonce_cache: Arc<RwLock<TimeBoundedNonceCache>>,
+    nonce_cache: Arc<TimeBoundedNonceCache>,
 
     /// Active subscription count by topic.
     subscriptions: Arc<RwLock<HashMap<String, usize>>>,
@@ -54,6 +91,24 @@ pub struct InMemoryEventBus {
 
     /// Channel capacity.
     capacity: usize,
+
+    /// Persistent log of published events, if any.
+    event_log: Option<Arc<EventLog>>,
+
+    /// Audit journal of published events, if any.
+    journal: Option<Arc<EventJournal>>,
+
+    /// Events published on the DLQ topic.
+    dead_letters: Arc<DeadLetterQueue>,
+
+    /// Recent events per topic, if mirrored.
+    mirror: Option<Arc<EventMirror>>,
+
+    /// Subscribers with their own queue (`Block`, `SpillToDisk`).
+    queued: RwLock<Vec<Arc<QueuedSubscriber>>>,
+
+    /// Counters of every subscription, for lag metrics.
+    subscriber_metrics: RwLock<Vec<Arc<SubscriberMetrics>>>,
 }
 
 impl InMemoryEventBus {
@@ -66,23 +121,130 @@ impl InMemoryEventBus {
     /// Create a new in-memory event bus with specified capacity.
     #[must_use]
     pub fn with_capacity(capacity: usize) -> Self {
-        let (sender, _) = broadcast::channel(capacity);
         Self {
-            sender,
-            nonce_cache: Arc::new(RwLock::new(TimeBoundedNonceCache::new())),
+            senders: Lanes::new(|_| broadcast::channel(capacity).0),
+            nonce_cache: Arc::new(TimeBoundedNonceCache::new()),
             subscriptions: Arc::new(RwLock::new(HashMap::new())),
             events_published: AtomicU64::new(0),
             capacity,
+            event_log: None,
+            journal: None,
+            dead_letters: Arc::new(DeadLetterQueue::default()),
+            mirror: None,
+            queued: RwLock::new(Vec::new()),
+            subscriber_metrics: RwLock::new(Vec::new()),
         }
     }
 
+    /// Persist every published event to `log`.
+    #[must_use]
+    pub fn with_event_log(mut self, log: Arc<EventLog>) -> Self {
+        self.event_log = Some(log);
+        self
+    }
+
+    /// The persistent event log, if one is attached.
+    #[must_use]
+    pub fn event_log(&self) -> Option<Arc<EventLog>> {
+        self.event_log.clone()
+    }
+
+    /// Record every published event in `journal`.
+    #[must_use]
+    pub fn with_journal(mut self, journal: Arc<EventJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// The audit journal, if one is attached.
+    #[must_use]
+    pub fn journal(&self) -> Option<Arc<EventJournal>> {
+        self.journal.clone()
+    }
+
+    /// Keep the most recent events of every topic in `mirror`.
+    #[must_use]
+    pub fn with_mirror(mut self, mirror: Arc<EventMirror>) -> Self {
+        self.mirror = Some(mirror);
+        self
+    }
+
+    /// The event mirror, if one is attached.
+    #[must_use]
+    pub fn mirror(&self) -> Option<Arc<EventMirror>> {
+        self.mirror.clone()
+    }
+
+    /// Subscribe a named consumer to the persistent event log.
+    ///
+    /// Delivery resumes from the consumer's last acknowledged event, so
+    /// events published while it was down (or not acknowledged before a
+    /// crash) are delivered again.
+    ///
+    /// # Errors
+    ///
+    /// `EventLogError::NotPersistent` if the bus has no event log.
+    pub fn subscribe_durable(
+        &self,
+        consumer: &str,
+        filter: EventFilter,
+    ) -> Result<DurableSubscription, EventLogError> {
+        let log = self.event_log.clone().ok_or(EventLogError::NotPersistent)?;
+        debug!(consumer, filter = %filter, "New durable subscription created");
+        DurableSubscription::new(log, consumer, filter)
+    }
+
     /// Subscribe to events matching a filter.
     ///
     /// Returns a `Subscription` handle that can be used to receive events.
     #[must_use]
     pub fn subscribe(&self, filter: EventFilter) -> Subscription {
-        let receiver = self.sender.subscribe();
-        let topic_key = format!("{:?}", filter.topics);
+        self.subscribe_with(filter, SubscriptionOptions::default())
+    }
+
+    /// Subscribe to events matching a filter, with a name and backpressure
+    /// policy of its own.
+    #[must_use]
+    pub fn subscribe_with(
+        &self,
+        filter: EventFilter,
+        options: SubscriptionOptions,
+    ) -> Subscription {
+        let topic_key = filter.to_string();
+        let name = if options.name.is_empty() {
+            topic_key.clone()
+        } else {
+            options.name
+        };
+        let metrics = Arc::new(SubscriberMetrics::new(name.clone(), &options.policy));
+        let capacity = options.capacity.max(1);
+
+        let source = match &options.policy {
+            BackpressurePolicy::DropOldest => {
+                EventSource::Broadcast(self.senders.map(broadcast::Sender::subscribe))
+            }
+            BackpressurePolicy::Block => {
+                let (senders, receivers) = Lanes::new(|_| mpsc::channel(capacity)).unzip();
+                self.add_queued(QueuedSubscriber::blocking(
+                    filter.clone(),
+                    senders,
+                    Arc::clone(&metrics),
+                ));
+                EventSource::Blocking(receivers)
+            }
+            BackpressurePolicy::SpillToDisk(dir) => {
+                let queues = Lanes::new(|priority| {
+                    let lane_name = format!("{name}-{}", priority.name());
+                    Arc::new(SpillQueue::new(dir, &lane_name, capacity))
+                });
+                self.add_queued(QueuedSubscriber::spill(
+                    filter.clone(),
+                    &queues,
+                    Arc::clone(&metrics),
+                ));
+                EventSource::Spill(queues)
+            }
+        };
 
         // Track subscription
         {
@@ -90,10 +252,95 @@ impl InMemoryEventBus {
                 *subs.entry(topic_key.clone()).or_insert(0) += 1;
             }
         }
+        if let Ok(mut all) = self.subscriber_metrics.write() {
+            all.retain(|m| !m.is_closed());
+            all.push(Arc::clone(&metrics));
+        }
+
+        debug!(filter = %topic_key, subscriber = %name, policy = options.policy.name(), "New subscription created");
+
+        Subscription::new(
+            source,
+            filter,
+            metrics,
+            self.subscriptions.clone(),
+            topic_key,
+        )
+    }
+
+    /// Lag and loss counters of every live subscription.
+    #[must_use]
+    pub fn subscriber_stats(&self) -> Vec<SubscriberStats> {
+        let Ok(all) = self.subscriber_metrics.read() else {
+            return Vec::new();
+        };
+        all.iter()
+            .filter(|m| !m.is_closed())
+            .map(|m| m.snapshot())
+            .collect()
+    }
+
+    /// Events published but not yet received by every live subscription.
+    ///
+    /// A broadcast event counts until all subscribers have seen it (or it
+    /// is evicted), so one stalled subscriber keeps the whole lane pending.
+    #[must_use]
+    pub fn pending_events(&self) -> usize {
+        let broadcast: usize = self.senders.iter().map(broadcast::Sender::len).sum();
+        let queued = self.queued.read().map_or(0, |queued| {
+            queued
+                .iter()
+                .filter(|s| !s.is_closed())
+                .map(|s| s.pending())
+                .sum()
+        });
+        broadcast + queued
+    }
+
+    /// Wait until subscribers have received everything published so far,
+    /// or `timeout` elapses.
+    ///
+    /// Used on shutdown, after intake has stopped, so in-flight events are
+    /// handled before subsystems flush their state. Returns the number of
+    /// events still pending; 0 means the bus drained.
+    pub async fn drain(&self, timeout: Duration) -> usize {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let pending = self.pending_events();
+            if pending == 0 {
+                return 0;
+            }
+            if Instant::now() >= deadline {
+                warn!(pending, "Event bus drain timed out");
+                return pending;
+            }
+            tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+        }
+    }
 
-        debug!(topics = ?filter.topics, "New subscription created");
+    fn add_queued(&self, subscriber: QueuedSubscriber) {
+        if let Ok(mut queued) = self.queued.write() {
+            queued.retain(|s| !s.is_closed());
+            queued.push(Arc::new(subscriber));
+        }
+    }
 
-        Subscription::new(receiver, filter, self.subscriptions.clone(), topic_key)
+    /// Deliver to queued subscribers, returning how many took the event.
+    async fn deliver_queued(&self, event: &BlockchainEvent) -> usize {
+        // Snapshot first: a `Block` delivery may wait, and must not hold
+        // the lock while it does
+        let targets: Vec<_> = match self.queued.read() {
+            Ok(queued) => queued.iter().filter(|s| s.wants(event)).cloned().collect(),
+            Err(_) => return 0,
+        };
+
+        let mut delivered = 0;
+        for subscriber in targets {
+            if subscriber.deliver(event.clone()).await {
+                delivered += 1;
+            }
+        }
+        delivered
     }
 
     /// Get a stream of events matching a filter.
@@ -107,7 +354,12 @@ impl InMemoryEventBus {
     /// Get the number of active subscribers.
     #[must_use]
     pub fn subscriber_count(&self) -> usize {
-        self.sender.receiver_count()
+        let queued = self
+            .queued
+            .read()
+            .map_or(0, |queued| queued.iter().filter(|s| !s.is_closed()).count());
+        // Every broadcast subscriber holds a receiver on each lane
+        self.senders.get(EventPriority::Critical).receiver_count() + queued
     }
 
     /// Get the channel capacity.
@@ -116,8 +368,36 @@ impl InMemoryEventBus {
         self.capacity
     }
 
+    /// Set the retention limits of the dead letter queue.
+    #[must_use]
+    pub fn with_dead_letter_config(mut self, config: DeadLetterConfig) -> Self {
+        self.dead_letters = Arc::new(DeadLetterQueue::new(config));
+        self
+    }
+
+    /// The dead letter queue.
+    #[must_use]
+    pub fn dead_letters(&self) -> Arc<DeadLetterQueue> {
+        Arc::clone(&self.dead_letters)
+    }
+
+    /// Republish the event behind a dead letter and remove it from the queue.
+    ///
+    /// Returns the number of subscribers that received the replayed event.
+    ///
+    /// # Errors
+    ///
+    /// `DeadLetterError::NotFound` if no entry has this ID, or
+    /// `DeadLetterError::NotReplayable` if it doesn't carry the failed event
+    /// (the entry stays queued).
+    pub async fn replay_dead_letter(&self, id: u64) -> Result<usize, DeadLetterError> {
+        let original = self.dead_letters.take_for_replay(id)?;
+        debug!(id, topic = %original.topic(), "Replaying dead letter");
+        Ok(self.publish(original).await)
+    }
+
     /// Get access to the nonce cache for message validation.
-    pub fn nonce_cache(&self) -> Arc<RwLock<TimeBoundedNonceCache>> {
+    pub fn nonce_cache(&self) -> Arc<TimeBoundedNonceCache> {
         self.nonce_cache.clone()
     }
 }
@@ -137,8 +417,31 @@ impl EventPublisher for InMemoryEventBus {
         // Always increment counter (event was attempted)
         self.events_published.fetch_add(1, Ordering::Relaxed);
 
-        match self.sender.send(event) {
-            Ok(receiver_count) => {
+        // Persist before broadcasting, so durable consumers never miss an
+        // event a live subscriber saw
+        if let Some(log) = &self.event_log {
+            if let Err(e) = log.append(&event) {
+                error!(topic = ?topic, source = source, error = %e, "Event not persisted");
+            }
+        }
+        if let Some(journal) = &self.journal {
+            if let Err(e) = journal.record(&event) {
+                error!(topic = ?topic, source = source, error = %e, "Event not journaled");
+            }
+        }
+
+        if topic == EventTopic::DeadLetterQueue {
+            self.dead_letters.push(event.clone());
+        }
+        if let Some(mirror) = &self.mirror {
+            mirror.record(&event);
+        }
+
+        let queued = self.deliver_queued(&event).await;
+
+        match self.senders.get(event.priority()).send(event) {
+            Ok(broadcast_count) => {
+                let receiver_count = broadcast_count + queued;
                 debug!(
                     topic = ?topic,
                     source = source,
@@ -147,6 +450,10 @@ impl EventPublisher for InMemoryEventBus {
                 );
                 receiver_count
             }
+            Err(_) if queued > 0 => {
+                debug!(topic = ?topic, source = source, receivers = queued, "Event published");
+                queued
+            }
             Err(e) => {
                 // No receivers - event is dropped
                 warn!(
@@ -168,7 +475,6 @@ impl EventPublisher for InMemoryEventBus {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::events::EventTopic;
     use shared_types::entities::ValidatedBlock;
 
     #[tokio::test]
@@ -210,6 +516,29 @@ mod tests {
         assert_eq!(bus.subscriber_count(), 3);
     }
 
+    #[tokio::test]
+    async fn test_drain() {
+        let bus = InMemoryEventBus::new();
+        assert_eq!(bus.drain(Duration::ZERO).await, 0);
+
+        let mut sub = bus.subscribe(EventFilter::all());
+        for _ in 0..3 {
+            bus.publish(BlockchainEvent::BlockValidated(ValidatedBlock::default()))
+                .await;
+        }
+        assert_eq!(bus.pending_events(), 3);
+        // Nobody reads: the deadline passes with everything still queued
+        assert_eq!(bus.drain(Duration::from_millis(20)).await, 3);
+
+        let reader = tokio::spawn(async move {
+            let received = [sub.recv().await, sub.recv().await, sub.recv().await];
+            (sub, received)
+        });
+        assert_eq!(bus.drain(Duration::from_secs(5)).await, 0);
+        let (_sub, received) = reader.await.unwrap();
+        assert!(received.iter().all(Option::is_some));
+    }
+
     #[tokio::test]
     async fn test_custom_capacity() {
         let bus = InMemoryEventBus::with_capacity(100);
@@ -223,4 +552,49 @@ mod tests {
         assert_eq!(bus.subscriber_count(), 0);
         assert_eq!(bus.events_published(), 0);
     }
+
+    #[tokio::test]
+    async fn test_dead_letters_are_retained_and_replayed() {
+        let bus = InMemoryEventBus::new();
+        let mut queries = bus.subscribe(EventFilter::topics(vec![EventTopic::ApiGateway]));
+
+        bus.publish(BlockchainEvent::ApiQueryDeadLetter {
+            correlation_id: "q-1".to_string(),
+            target: "qc-02-block-storage".to_string(),
+            method: "get_block_number".to_string(),
+            params: serde_json::Value::Null,
+            attempts: 3,
+            reason: "timeout".to_string(),
+        })
+        .await;
+        let dead_letters = bus.dead_letters();
+        let entry = dead_letters.peek().expect("dead letter retained");
+
+        assert_eq!(bus.replay_dead_letter(entry.id).await, Ok(1));
+        assert!(matches!(
+            queries.recv().await,
+            Some(BlockchainEvent::ApiQuery { correlation_id, .. }) if correlation_id == "q-1"
+        ));
+        assert!(dead_letters.is_empty());
+        assert_eq!(
+            bus.replay_dead_letter(entry.id).await,
+            Err(DeadLetterError::NotFound(entry.id))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mirror_keeps_events_without_subscribers() {
+        let bus = InMemoryEventBus::new();
+        assert!(bus.mirror().is_none());
+
+        let bus = bus.with_mirror(Arc::new(EventMirror::new(8)));
+        bus.publish(BlockchainEvent::BlockValidated(ValidatedBlock::default()))
+            .await;
+
+        let mirror = bus.mirror().expect("mirror attached");
+        let recent = mirror.recent(&[], 0, 10);
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].0, EventTopic::Consensus);
+        assert_eq!(mirror.last_seq(), 1);
+    }
 }
@@ -2,14 +2,16 @@
 //!
 //! Defines the subscription side of the event bus.
 
+use crate::backpressure::{SpillQueue, SubscriberMetrics, SubscriberStats};
 use crate::events::{BlockchainEvent, EventFilter};
+use crate::priority::Lanes;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::{Arc, RwLock};
 use std::task::{Context, Poll};
 use thiserror::Error;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc};
 use tokio_stream::Stream;
 use tracing::debug;
 
@@ -28,16 +30,30 @@ pub trait EventSubscriber: Send + Sync {
     fn subscribe(&self, filter: EventFilter) -> Subscription;
 }
 
+/// Where a subscription reads events from, by backpressure policy, one
+/// channel per priority lane.
+pub(crate) enum EventSource {
+    /// The shared bus channels (`DropOldest`).
+    Broadcast(Lanes<broadcast::Receiver<BlockchainEvent>>),
+    /// Bounded queues publishers wait on (`Block`).
+    Blocking(Lanes<mpsc::Receiver<BlockchainEvent>>),
+    /// Queues overflowing to disk (`SpillToDisk`).
+    Spill(Lanes<Arc<SpillQueue>>),
+}
+
 /// A subscription handle for receiving events.
 ///
 /// When dropped, the subscription is automatically cleaned up.
 pub struct Subscription {
-    /// The broadcast receiver.
-    receiver: broadcast::Receiver<BlockchainEvent>,
+    /// Where events arrive.
+    source: EventSource,
 
     /// Filter for this subscription.
     filter: EventFilter,
 
+    /// Lag and loss counters, shared with the bus.
+    metrics: Arc<SubscriberMetrics>,
+
     /// Reference to subscription tracking (for cleanup).
     subscriptions: Arc<RwLock<HashMap<String, usize>>>,
 
@@ -48,14 +64,16 @@ pub struct Subscription {
 impl Subscription {
     /// Create a new subscription.
     pub(crate) fn new(
-        receiver: broadcast::Receiver<BlockchainEvent>,
+        source: EventSource,
         filter: EventFilter,
+        metrics: Arc<SubscriberMetrics>,
         subscriptions: Arc<RwLock<HashMap<String, usize>>>,
         topic_key: String,
     ) -> Self {
         Self {
-            receiver,
+            source,
             filter,
+            metrics,
             subscriptions,
             topic_key,
         }
@@ -63,22 +81,24 @@ impl Subscription {
 
     /// Receive the next event that matches the filter.
     ///
+    /// Waiting events are delivered highest priority first.
+    ///
     /// # Returns
     ///
     /// - `Some(event)` - The next matching event
     /// - `None` - The channel was closed (bus dropped)
     pub async fn recv(&mut self) -> Option<BlockchainEvent> {
         loop {
-            let event = match self.receiver.recv().await {
-                Ok(e) => e,
-                Err(broadcast::error::RecvError::Closed) => return None,
-                Err(broadcast::error::RecvError::Lagged(count)) => {
-                    debug!(lagged = count, "Subscriber lagged, some events dropped");
-                    continue;
-                }
+            let event = match self.try_next() {
+                Ok(Some(e)) => e,
+                Ok(None) => match self.wait_next().await {
+                    Some(e) => e,
+                    None => continue,
+                },
+                Err(SubscriptionError::Closed) => return None,
             };
 
-            if self.filter.matches(&event) {
+            if let Some(event) = self.accept(event) {
                 return Some(event);
             }
             // Event doesn't match filter, continue waiting
@@ -94,16 +114,11 @@ impl Subscription {
     /// - `Err(SubscriptionError::Closed)` - The channel was closed
     pub fn try_recv(&mut self) -> Result<Option<BlockchainEvent>, SubscriptionError> {
         loop {
-            let event = match self.receiver.try_recv() {
-                Ok(e) => e,
-                Err(broadcast::error::TryRecvError::Empty) => return Ok(None),
-                Err(broadcast::error::TryRecvError::Closed) => {
-                    return Err(SubscriptionError::Closed)
-                }
-                Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+            let Some(event) = self.try_next()? else {
+                return Ok(None);
             };
 
-            if self.filter.matches(&event) {
+            if let Some(event) = self.accept(event) {
                 return Ok(Some(event));
             }
             // Event doesn't match filter, try again
@@ -115,10 +130,102 @@ impl Subscription {
     pub fn filter(&self) -> &EventFilter {
         &self.filter
     }
+
+    /// Get this subscriber's lag and loss counters.
+    #[must_use]
+    pub fn stats(&self) -> SubscriberStats {
+        self.metrics.snapshot()
+    }
+
+    /// Next waiting event from the highest non-empty lane, matching or not.
+    fn try_next(&mut self) -> Result<Option<BlockchainEvent>, SubscriptionError> {
+        let metrics = &self.metrics;
+        match &mut self.source {
+            EventSource::Broadcast(lanes) => first_ready(lanes.iter_mut(), |receiver| {
+                try_broadcast(receiver, metrics)
+            }),
+            EventSource::Blocking(lanes) => {
+                first_ready(lanes.iter_mut(), |receiver| match receiver.try_recv() {
+                    Ok(e) => Ok(Some(e)),
+                    Err(mpsc::error::TryRecvError::Empty) => Ok(None),
+                    Err(mpsc::error::TryRecvError::Disconnected) => Err(SubscriptionError::Closed),
+                })
+            }
+            EventSource::Spill(lanes) => first_ready(lanes.iter(), |queue| match queue.pop() {
+                Some(e) => Ok(Some(e)),
+                None if queue.is_closed() => Err(SubscriptionError::Closed),
+                None => Ok(None),
+            }),
+        }
+    }
+
+    /// Wait for activity on any lane, preferring higher lanes.
+    ///
+    /// Returns the event that arrived, or `None` if the caller should
+    /// look again (events were lost to lag, a queue was refilled or the
+    /// bus closed).
+    async fn wait_next(&mut self) -> Option<BlockchainEvent> {
+        match &mut self.source {
+            EventSource::Broadcast(lanes) => {
+                let (critical, normal, bulk) = lanes.as_mut();
+                let received = tokio::select! {
+                    biased;
+                    r = critical.recv() => r,
+                    r = normal.recv() => r,
+                    r = bulk.recv() => r,
+                };
+                match received {
+                    Ok(e) => Some(e),
+                    Err(broadcast::error::RecvError::Lagged(count)) => {
+                        debug!(lagged = count, "Subscriber lagged, some events dropped");
+                        self.metrics.record_dropped(count);
+                        None
+                    }
+                    Err(broadcast::error::RecvError::Closed) => None,
+                }
+            }
+            EventSource::Blocking(lanes) => {
+                let (critical, normal, bulk) = lanes.as_mut();
+                tokio::select! {
+                    biased;
+                    r = critical.recv() => r,
+                    r = normal.recv() => r,
+                    r = bulk.recv() => r,
+                }
+            }
+            EventSource::Spill(lanes) => {
+                let (critical, normal, bulk) = lanes.as_mut();
+                tokio::select! {
+                    biased;
+                    () = critical.wait() => {}
+                    () = normal.wait() => {}
+                    () = bulk.wait() => {}
+                }
+                None
+            }
+        }
+    }
+
+    /// Apply the filter and count a delivered event.
+    fn accept(&self, event: BlockchainEvent) -> Option<BlockchainEvent> {
+        if !self.filter.matches(&event) {
+            return None;
+        }
+        let pending = match &self.source {
+            EventSource::Broadcast(lanes) => lanes.iter().map(broadcast::Receiver::len).sum(),
+            EventSource::Blocking(lanes) => lanes.iter().map(mpsc::Receiver::len).sum(),
+            EventSource::Spill(lanes) => lanes.iter().map(|queue| queue.pending()).sum(),
+        };
+        self.metrics.record_delivered(pending);
+        Some(event)
+    }
 }
 
 impl Drop for Subscription {
     fn drop(&mut self) {
+        // Publishers stop delivering to queued subscribers once closed
+        self.metrics.close();
+
         // Decrement subscription count
         let Ok(mut subs) = self.subscriptions.write() else {
             return;
@@ -136,6 +243,43 @@ impl Drop for Subscription {
     }
 }
 
+/// Poll lanes in priority order with `try_lane`.
+///
+/// Reports `Closed` only once every lane is closed and drained.
+fn first_ready<T>(
+    lanes: impl Iterator<Item = T>,
+    mut try_lane: impl FnMut(T) -> Result<Option<BlockchainEvent>, SubscriptionError>,
+) -> Result<Option<BlockchainEvent>, SubscriptionError> {
+    let mut open = false;
+    for lane in lanes {
+        match try_lane(lane) {
+            Ok(Some(event)) => return Ok(Some(event)),
+            Ok(None) => open = true,
+            Err(SubscriptionError::Closed) => {}
+        }
+    }
+    if open {
+        Ok(None)
+    } else {
+        Err(SubscriptionError::Closed)
+    }
+}
+
+/// Take the next event from a broadcast lane, counting lost events.
+fn try_broadcast(
+    receiver: &mut broadcast::Receiver<BlockchainEvent>,
+    metrics: &SubscriberMetrics,
+) -> Result<Option<BlockchainEvent>, SubscriptionError> {
+    loop {
+        match receiver.try_recv() {
+            Ok(e) => return Ok(Some(e)),
+            Err(broadcast::error::TryRecvError::Empty) => return Ok(None),
+            Err(broadcast::error::TryRecvError::Closed) => return Err(SubscriptionError::Closed),
+            Err(broadcast::error::TryRecvError::Lagged(count)) => metrics.record_dropped(count),
+        }
+    }
+}
+
 /// A stream wrapper for subscriptions.
 ///
 /// Implements `tokio_stream::Stream` for use with stream combinators.
@@ -178,6 +322,7 @@ impl Stream for EventStream {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backpressure::{BackpressurePolicy, SubscriptionOptions};
     use crate::events::EventTopic;
     use crate::publisher::InMemoryEventBus;
     use crate::EventPublisher;
@@ -280,4 +425,143 @@ mod tests {
         assert_eq!(stream.filter().topics.len(), 1);
         assert_eq!(stream.filter().topics[0], EventTopic::Consensus);
     }
+
+    fn stored(height: u64) -> BlockchainEvent {
+        BlockchainEvent::BlockStored {
+            block_height: height,
+            block_hash: Hash::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_counts_lost_events() {
+        let bus = InMemoryEventBus::with_capacity(2);
+        let mut sub = bus.subscribe_with(EventFilter::all(), SubscriptionOptions::new("telemetry"));
+        for height in 0..5 {
+            bus.publish(stored(height)).await;
+        }
+
+        assert!(matches!(
+            sub.recv().await,
+            Some(BlockchainEvent::BlockStored {
+                block_height: 3,
+                ..
+            })
+        ));
+        let stats = sub.stats();
+        assert_eq!(
+            (stats.name.as_str(), stats.policy),
+            ("telemetry", "drop_oldest")
+        );
+        assert_eq!((stats.dropped, stats.delivered, stats.pending), (3, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_block_policy_waits_for_subscriber() {
+        let bus = Arc::new(InMemoryEventBus::new());
+        let options = SubscriptionOptions::new("consensus")
+            .with_policy(BackpressurePolicy::Block)
+            .with_capacity(1);
+        let mut sub = bus.subscribe_with(EventFilter::all(), options);
+
+        assert_eq!(bus.publish(stored(0)).await, 1);
+        let publisher = Arc::clone(&bus);
+        let blocked = tokio::spawn(async move { publisher.publish(stored(1)).await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!blocked.is_finished());
+
+        assert!(sub.recv().await.is_some());
+        assert_eq!(blocked.await.unwrap(), 1);
+        assert!(sub.recv().await.is_some());
+        assert_eq!(sub.stats().dropped, 0);
+
+        // Publishers stop waiting on a dropped subscriber
+        drop(sub);
+        assert_eq!(bus.publish(stored(2)).await, 0);
+        assert_eq!(bus.subscriber_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_spill_policy_loses_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let bus = InMemoryEventBus::new();
+        let options = SubscriptionOptions::new("assembler")
+            .with_policy(BackpressurePolicy::SpillToDisk(dir.path().to_path_buf()))
+            .with_capacity(2);
+        let mut sub =
+            bus.subscribe_with(EventFilter::topics(vec![EventTopic::BlockStorage]), options);
+
+        for height in 0..10 {
+            bus.publish(stored(height)).await;
+            bus.publish(BlockchainEvent::BlockValidated(ValidatedBlock::default()))
+                .await;
+        }
+        assert_eq!(sub.stats().spilled, 8);
+
+        for expected in 0..10 {
+            let received = timeout(Duration::from_millis(100), sub.recv())
+                .await
+                .expect("timeout");
+            assert!(matches!(
+                received,
+                Some(BlockchainEvent::BlockStored { block_height, .. }) if block_height == expected
+            ));
+        }
+        assert!(matches!(sub.try_recv(), Ok(None)));
+        assert_eq!(bus.subscriber_stats()[0].pending, 0);
+
+        drop(bus);
+        assert_eq!(sub.recv().await.map(|_| ()), None);
+    }
+
+    fn query(id: u64) -> BlockchainEvent {
+        BlockchainEvent::ApiQuery {
+            correlation_id: id.to_string(),
+            target: "qc-02-block-storage".to_string(),
+            method: "get_block_number".to_string(),
+            params: serde_json::Value::Null,
+            trace_parent: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_critical_events_overtake_bulk_backlog() {
+        let dir = tempfile::tempdir().unwrap();
+        let bus = InMemoryEventBus::with_capacity(4);
+        let policies = [
+            BackpressurePolicy::DropOldest,
+            BackpressurePolicy::Block,
+            BackpressurePolicy::SpillToDisk(dir.path().to_path_buf()),
+        ];
+        let mut subs: Vec<_> = policies
+            .into_iter()
+            .map(|policy| {
+                let options = SubscriptionOptions::default()
+                    .with_policy(policy)
+                    .with_capacity(4);
+                bus.subscribe_with(EventFilter::all(), options)
+            })
+            .collect();
+
+        for id in 0..4 {
+            bus.publish(query(id)).await;
+        }
+        // A full bulk lane neither blocks nor evicts critical events
+        bus.publish(stored(7)).await;
+
+        for sub in &mut subs {
+            assert!(matches!(
+                sub.recv().await,
+                Some(BlockchainEvent::BlockStored {
+                    block_height: 7,
+                    ..
+                })
+            ));
+            assert!(matches!(
+                sub.recv().await,
+                Some(BlockchainEvent::ApiQuery { correlation_id, .. }) if correlation_id == "0"
+            ));
+            assert_eq!(sub.stats().dropped, 0);
+        }
+    }
 }
@@ -0,0 +1,575 @@
+//! # Cross-Process Transport
+//!
+//! Bridges bus topics between processes over a Unix domain socket, so a
+//! subsystem running out of process sees the same events as one sharing the
+//! node's `InMemoryEventBus`.
+//!
+//! ## Wire Format
+//!
+//! Each event travels as one frame: a 4-byte big-endian length followed by
+//! the JSON encoding of an `AuthenticatedMessage<BlockchainEvent>`. The
+//! envelope is signed with HMAC-SHA256 over its JSON encoding with a zeroed
+//! `signature` field, using the sender's key derived from the shared master
+//! secret (`DerivedKeyProvider`).
+//!
+//! ## Verification
+//!
+//! Incoming frames go through the same `MessageVerifier` as in-process IPC:
+//! version, timestamp window, nonce replay and HMAC checks. Rejected frames
+//! are logged, counted and dropped; the connection stays up.
+//!
+//! ## Topology
+//!
+//! Only events matching the transport filter cross the socket, in either
+//! direction. Events received from a peer are published on the local bus
+//! and are not sent back to that peer. Bridges should form a tree (e.g. a
+//! node listening and out-of-process subsystems connecting to it): a cycle
+//! of bridges would circulate events indefinitely.
+
+use crate::events::{BlockchainEvent, EventFilter};
+use crate::publisher::{EventPublisher, InMemoryEventBus};
+use crate::subscriber::Subscription;
+use crate::PROTOCOL_VERSION;
+use shared_types::envelope::{AuthenticatedMessage, VerificationResult};
+use shared_types::secret::SecretBytes;
+use shared_types::security::{
+    current_timestamp, sign_message, DerivedKeyProvider, KeyProvider, MessageVerifier, NonceCache,
+};
+use std::collections::HashMap;
+use std::io;
+use std::os::unix::fs::FileTypeExt;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::task::{JoinHandle, JoinSet};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Default maximum size of one frame.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Recipient ID of bridged envelopes: the remote bus, not one subsystem.
+pub const BUS_RECIPIENT_ID: u8 = 0;
+
+/// Errors from the cross-process transport.
+#[derive(Debug, Error)]
+pub enum TransportError {
+    /// Socket I/O failed.
+    #[error("Transport I/O error: {0}")]
+    Io(#[from] io::Error),
+
+    /// A frame could not be encoded or decoded.
+    #[error("Transport serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    /// A frame exceeds the configured maximum length.
+    #[error("Frame of {len} bytes exceeds limit of {max} bytes")]
+    FrameTooLarge { len: usize, max: usize },
+
+    /// A frame failed envelope verification.
+    #[error("Frame rejected: {0:?}")]
+    Rejected(VerificationResult),
+}
+
+/// Configuration of a transport endpoint.
+#[derive(Clone)]
+pub struct TransportConfig {
+    /// Subsystem ID this endpoint signs as.
+    pub subsystem_id: u8,
+    /// Master secret shared by both endpoints.
+    pub master_secret: SecretBytes,
+    /// Events bridged in either direction.
+    pub filter: EventFilter,
+    /// Maximum size of one frame.
+    pub max_frame_len: usize,
+}
+
+impl TransportConfig {
+    /// Bridge every topic, signing as `subsystem_id`.
+    #[must_use]
+    pub fn new(subsystem_id: u8, master_secret: impl Into<SecretBytes>) -> Self {
+        Self {
+            subsystem_id,
+            master_secret: master_secret.into(),
+            filter: EventFilter::all(),
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        }
+    }
+
+    /// Only bridge events matching `filter`.
+    #[must_use]
+    pub fn with_filter(mut self, filter: EventFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Set the maximum size of one frame.
+    #[must_use]
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+}
+
+/// Counters of a transport endpoint, over all its connections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransportStats {
+    /// Events sent to peers.
+    pub sent: u64,
+    /// Events received from peers and published locally.
+    pub received: u64,
+    /// Frames dropped by verification or decoding.
+    pub rejected: u64,
+}
+
+#[derive(Debug, Default)]
+struct TransportCounters {
+    sent: AtomicU64,
+    received: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl TransportCounters {
+    fn snapshot(&self) -> TransportStats {
+        TransportStats {
+            sent: self.sent.load(Ordering::Relaxed),
+            received: self.received.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Handle to a running listener or connection.
+///
+/// Dropping the handle stops the endpoint.
+#[derive(Debug)]
+pub struct TransportHandle {
+    task: JoinHandle<()>,
+    counters: Arc<TransportCounters>,
+}
+
+impl TransportHandle {
+    /// Get the endpoint counters.
+    #[must_use]
+    pub fn stats(&self) -> TransportStats {
+        self.counters.snapshot()
+    }
+
+    /// Whether the endpoint has stopped (connection closed).
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+impl Drop for TransportHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Bridges an `InMemoryEventBus` to other processes over Unix sockets.
+#[derive(Clone)]
+pub struct UnixTransport {
+    bus: Arc<InMemoryEventBus>,
+    config: Arc<TransportConfig>,
+}
+
+impl UnixTransport {
+    /// Create a transport for `bus`.
+    #[must_use]
+    pub fn new(bus: Arc<InMemoryEventBus>, config: TransportConfig) -> Self {
+        Self {
+            bus,
+            config: Arc::new(config),
+        }
+    }
+
+    /// Listen on `path` and bridge every peer that connects.
+    ///
+    /// A stale socket file at `path` is replaced.
+    pub fn listen(&self, path: impl AsRef<Path>) -> Result<TransportHandle, TransportError> {
+        let path = path.as_ref();
+        remove_stale_socket(path)?;
+        let listener = UnixListener::bind(path)?;
+        info!(path = %path.display(), "Bus transport listening");
+
+        let counters = Arc::new(TransportCounters::default());
+        let task = tokio::spawn(self.clone().accept(listener, Arc::clone(&counters)));
+
+        Ok(TransportHandle { task, counters })
+    }
+
+    /// Bridge each accepted connection until aborted; aborting also stops
+    /// the connections.
+    async fn accept(self, listener: UnixListener, counters: Arc<TransportCounters>) {
+        let mut connections = JoinSet::new();
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let bridge = self.bridge(Arc::clone(&counters));
+                    connections.spawn(bridge.run(stream));
+                }
+                Err(e) => warn!(error = %e, "Bus transport accept failed"),
+            }
+            // Reap finished connections
+            while connections.try_join_next().is_some() {}
+        }
+    }
+
+    /// Connect to a listening peer at `path` and bridge with it.
+    pub async fn connect(&self, path: impl AsRef<Path>) -> Result<TransportHandle, TransportError> {
+        let path = path.as_ref();
+        let stream = UnixStream::connect(path).await?;
+        info!(path = %path.display(), "Bus transport connected");
+
+        let counters = Arc::new(TransportCounters::default());
+        let bridge = self.bridge(Arc::clone(&counters));
+        let task = tokio::spawn(bridge.run(stream));
+
+        Ok(TransportHandle { task, counters })
+    }
+
+    fn bridge(&self, counters: Arc<TransportCounters>) -> Bridge {
+        let key_provider = DerivedKeyProvider::new(self.config.master_secret.clone());
+        let signing_key = key_provider
+            .get_shared_secret(self.config.subsystem_id)
+            .unwrap_or_default();
+        Bridge {
+            bus: Arc::clone(&self.bus),
+            config: Arc::clone(&self.config),
+            signing_key,
+            verifier: MessageVerifier::new(
+                self.config.subsystem_id,
+                NonceCache::new_shared(),
+                key_provider,
+            ),
+            received: Mutex::new(HashMap::new()),
+            counters,
+        }
+    }
+}
+
+/// One bridged connection.
+struct Bridge {
+    bus: Arc<InMemoryEventBus>,
+    config: Arc<TransportConfig>,
+    signing_key: Vec<u8>,
+    verifier: MessageVerifier<DerivedKeyProvider>,
+    /// Encoded events received from the peer and not yet seen by the
+    /// outbound side, so they are not echoed back.
+    received: Mutex<HashMap<String, usize>>,
+    counters: Arc<TransportCounters>,
+}
+
+impl Bridge {
+    async fn run(self, stream: UnixStream) {
+        // Subscribe before reading, so nothing published meanwhile is missed
+        let subscription = self.bus.subscribe(self.config.filter.clone());
+        let (reader, writer) = stream.into_split();
+
+        let result = tokio::select! {
+            result = self.outbound(subscription, writer) => result,
+            result = self.inbound(reader) => result,
+        };
+        match result {
+            Ok(()) => debug!("Bus transport connection closed"),
+            Err(e) => warn!(error = %e, "Bus transport connection failed"),
+        }
+    }
+
+    /// Send local events to the peer.
+    async fn outbound(
+        &self,
+        mut subscription: Subscription,
+        mut writer: impl AsyncWrite + Unpin,
+    ) -> Result<(), TransportError> {
+        while let Some(event) = subscription.recv().await {
+            if self.is_echo(&event)? {
+                continue;
+            }
+            let frame = seal(&event, self.config.subsystem_id, &self.signing_key)?;
+            write_frame(&mut writer, &frame, self.config.max_frame_len).await?;
+            self.counters.sent.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Publish the peer's events locally.
+    async fn inbound(&self, mut reader: impl AsyncRead + Unpin) -> Result<(), TransportError> {
+        while let Some(frame) = read_frame(&mut reader, self.config.max_frame_len).await? {
+            let event = match open(&frame, &self.verifier) {
+                Ok(event) => event,
+                Err(e) => {
+                    self.counters.rejected.fetch_add(1, Ordering::Relaxed);
+                    warn!(error = %e, "Bus transport frame dropped");
+                    continue;
+                }
+            };
+            if !self.config.filter.matches(&event) {
+                continue;
+            }
+
+            self.mark_received(&event)?;
+            self.counters.received.fetch_add(1, Ordering::Relaxed);
+            self.bus.publish(event).await;
+        }
+        Ok(())
+    }
+
+    fn mark_received(&self, event: &BlockchainEvent) -> Result<(), TransportError> {
+        let key = serde_json::to_string(event)?;
+        *self.lock_received().entry(key).or_insert(0) += 1;
+        Ok(())
+    }
+
+    /// Whether `event` came from the peer, consuming the record if so.
+    fn is_echo(&self, event: &BlockchainEvent) -> Result<bool, TransportError> {
+        let key = serde_json::to_string(event)?;
+        let mut received = self.lock_received();
+        let Some(count) = received.get_mut(&key) else {
+            return Ok(false);
+        };
+        *count -= 1;
+        if *count == 0 {
+            received.remove(&key);
+        }
+        Ok(true)
+    }
+
+    fn lock_received(&self) -> std::sync::MutexGuard<'_, HashMap<String, usize>> {
+        self.received
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Wrap `event` in a signed envelope and encode it.
+fn seal(
+    event: &BlockchainEvent,
+    sender_id: u8,
+    signing_key: &[u8],
+) -> Result<Vec<u8>, TransportError> {
+    let mut message = AuthenticatedMessage {
+        version: PROTOCOL_VERSION,
+        sender_id,
+        recipient_id: BUS_RECIPIENT_ID,
+        correlation_id: Uuid::new_v4(),
+        reply_to: None,
+        timestamp: current_timestamp(),
+        nonce: Uuid::new_v4(),
+        signature: [0u8; 64],
+        payload: event.clone(),
+    };
+    let unsigned = serde_json::to_vec(&message)?;
+    message.signature = sign_message(&unsigned, signing_key);
+    Ok(serde_json::to_vec(&message)?)
+}
+
+/// Decode and verify an envelope, returning its event.
+fn open(
+    frame: &[u8],
+    verifier: &MessageVerifier<DerivedKeyProvider>,
+) -> Result<BlockchainEvent, TransportError> {
+    let message: AuthenticatedMessage<BlockchainEvent> = serde_json::from_slice(frame)?;
+    let mut unsigned = message.clone();
+    unsigned.signature = [0u8; 64];
+    let unsigned = serde_json::to_vec(&unsigned)?;
+
+    match verifier.verify(&message, &unsigned) {
+        VerificationResult::Valid => Ok(message.payload),
+        rejected => Err(TransportError::Rejected(rejected)),
+    }
+}
+
+async fn write_frame(
+    writer: &mut (impl AsyncWrite + Unpin),
+    frame: &[u8],
+    max: usize,
+) -> Result<(), TransportError> {
+    let len = frame.len();
+    let too_large = TransportError::FrameTooLarge { len, max };
+    if len > max {
+        return Err(too_large);
+    }
+    let prefix = u32::try_from(len).map_err(|_| too_large)?;
+    writer.write_all(&prefix.to_be_bytes()).await?;
+    writer.write_all(frame).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Read one frame; `None` when the peer closed the connection.
+async fn read_frame(
+    reader: &mut (impl AsyncRead + Unpin),
+    max: usize,
+) -> Result<Option<Vec<u8>>, TransportError> {
+    let mut prefix = [0u8; 4];
+    match reader.read_exact(&mut prefix).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    let len = u32::from_be_bytes(prefix) as usize;
+    if len > max {
+        return Err(TransportError::FrameTooLarge { len, max });
+    }
+    let mut frame = vec![0u8; len];
+    reader.read_exact(&mut frame).await?;
+    Ok(Some(frame))
+}
+
+/// Remove a socket file left by a previous run; other files are kept.
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(path),
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventTopic;
+    use shared_types::entities::{Hash, ValidatedBlock};
+    use std::time::Duration;
+
+    const SECRET: &[u8] = b"transport-test-secret";
+
+    fn stored(height: u64) -> BlockchainEvent {
+        BlockchainEvent::BlockStored {
+            block_height: height,
+            block_hash: Hash::default(),
+        }
+    }
+
+    async fn recv(subscription: &mut Subscription) -> Option<BlockchainEvent> {
+        tokio::time::timeout(Duration::from_secs(2), subscription.recv())
+            .await
+            .ok()
+            .flatten()
+    }
+
+    async fn wait_for(handle: &TransportHandle, done: impl Fn(TransportStats) -> bool) {
+        for _ in 0..200 {
+            if done(handle.stats()) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("transport stats never reached: {:?}", handle.stats());
+    }
+
+    #[tokio::test]
+    async fn test_events_cross_the_socket_both_ways() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bus.sock");
+        let filter = EventFilter::topics(vec![EventTopic::BlockStorage]);
+
+        let node = Arc::new(InMemoryEventBus::new());
+        let remote = Arc::new(InMemoryEventBus::new());
+        let server = UnixTransport::new(
+            Arc::clone(&node),
+            TransportConfig::new(2, SECRET.to_vec()).with_filter(filter.clone()),
+        )
+        .listen(&path)
+        .unwrap();
+        let client = UnixTransport::new(
+            Arc::clone(&remote),
+            TransportConfig::new(3, SECRET.to_vec()).with_filter(filter.clone()),
+        )
+        .connect(&path)
+        .await
+        .unwrap();
+
+        let mut on_node = node.subscribe(EventFilter::all());
+        let mut on_remote = remote.subscribe(EventFilter::all());
+        // Wait for the server side bridge to subscribe
+        while node.subscriber_count() < 2 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        node.publish(stored(1)).await;
+        // Outside the bridged topics
+        node.publish(BlockchainEvent::BlockValidated(ValidatedBlock::default()))
+            .await;
+        assert!(matches!(
+            recv(&mut on_remote).await,
+            Some(BlockchainEvent::BlockStored {
+                block_height: 1,
+                ..
+            })
+        ));
+
+        remote.publish(stored(2)).await;
+        assert!(matches!(
+            recv(&mut on_node).await,
+            Some(BlockchainEvent::BlockStored {
+                block_height: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            recv(&mut on_node).await,
+            Some(BlockchainEvent::BlockValidated(_))
+        ));
+        assert!(matches!(
+            recv(&mut on_node).await,
+            Some(BlockchainEvent::BlockStored {
+                block_height: 2,
+                ..
+            })
+        ));
+
+        wait_for(&client, |stats| stats.sent == 1 && stats.received == 1).await;
+        // Neither event was echoed back to its origin
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(server.stats().sent, 1);
+        assert_eq!(server.stats().received, 1);
+        assert!(on_remote.try_recv().unwrap().is_some());
+        assert!(on_remote.try_recv().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_forged_and_replayed_frames_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bus.sock");
+        let node = Arc::new(InMemoryEventBus::new());
+        let server =
+            UnixTransport::new(Arc::clone(&node), TransportConfig::new(2, SECRET.to_vec()))
+                .listen(&path)
+                .unwrap();
+        let mut on_node = node.subscribe(EventFilter::all());
+
+        let mut stream = UnixStream::connect(&path).await.unwrap();
+        let max = DEFAULT_MAX_FRAME_LEN;
+
+        let forged = seal(&stored(1), 3, b"wrong-secret").unwrap();
+        write_frame(&mut stream, &forged, max).await.unwrap();
+
+        let key = DerivedKeyProvider::new(SECRET.to_vec())
+            .get_shared_secret(3)
+            .unwrap();
+        let genuine = seal(&stored(2), 3, &key).unwrap();
+        write_frame(&mut stream, &genuine, max).await.unwrap();
+        write_frame(&mut stream, &genuine, max).await.unwrap();
+
+        wait_for(&server, |stats| stats.received + stats.rejected == 3).await;
+        assert_eq!(server.stats().received, 1);
+        assert_eq!(server.stats().rejected, 2);
+
+        assert!(matches!(
+            recv(&mut on_node).await,
+            Some(BlockchainEvent::BlockStored {
+                block_height: 2,
+                ..
+            })
+        ));
+        assert!(on_node.try_recv().unwrap().is_none());
+    }
+}
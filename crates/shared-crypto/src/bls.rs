@@ -4,12 +4,25 @@
 //! - Key generation
 //! - Sign/verify operations
 //! - Signature and public key aggregation
+//! - Proof-of-possession (rogue-key protection for aggregation)
 //!
-//! Used by qc-09-finality for attestation verification.
+//! Used by qc-09-finality for attestation verification. This is the one
+//! BLS implementation for the workspace: subsystems exchange keys and
+//! signatures in the compressed form below (48-byte G1 public keys,
+//! 96-byte G2 signatures), serialized by serde as `0x`-prefixed hex in
+//! human-readable formats and as raw bytes otherwise.
+//!
+//! Decoding validates points: infinity and points outside the prime-order
+//! subgroup are rejected, so a decoded key or signature is safe to aggregate.
+//!
+//! Aggregating public keys is only sound when every key has a verified
+//! proof-of-possession; check `verify_possession` when a validator
+//! registers its key.
 
 use blst::min_pk::{AggregatePublicKey, AggregateSignature, PublicKey, SecretKey, Signature};
 use blst::BLST_ERROR;
 use rand::RngCore;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use zeroize::Zeroize;
 
 use crate::CryptoError;
@@ -17,6 +30,15 @@ use crate::CryptoError;
 /// Domain separation tag for BLS signatures (Ethereum 2.0 compatible)
 const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
 
+/// Domain separation tag for proofs of possession
+const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Compressed public key length in bytes
+pub const PUBLIC_KEY_LEN: usize = 48;
+
+/// Compressed signature length in bytes
+pub const SIGNATURE_LEN: usize = 96;
+
 /// BLS secret key wrapper (32 bytes)
 #[derive(Clone, Zeroize)]
 #[zeroize(drop)]
@@ -69,9 +91,18 @@ impl BlsKeyPair {
     pub fn generate() -> Self {
         let mut ikm = [0u8; 32];
         rand::thread_rng().fill_bytes(&mut ikm);
-        let secret = SecretKey::key_gen(&ikm, &[]).expect("valid IKM");
+        let keypair = Self::from_ikm(&ikm).expect("valid IKM");
+        ikm.zeroize();
+        keypair
+    }
+
+    /// Derive a key pair deterministically from input key material
+    /// (EIP-2333 `KeyGen`, at least 32 bytes).
+    pub fn from_ikm(ikm: &[u8]) -> Result<Self, CryptoError> {
+        let secret = SecretKey::key_gen(ikm, &[])
+            .map_err(|_| CryptoError::InvalidInput("IKM must be at least 32 bytes".into()))?;
         let public = BlsPublicKey(secret.sk_to_pk());
-        Self { secret, public }
+        Ok(Self { secret, public })
     }
 
     /// Create from existing secret key bytes
@@ -87,6 +118,11 @@ impl BlsKeyPair {
         BlsSignature(self.secret.sign(message, DST, &[]))
     }
 
+    /// Prove possession of the secret key by signing the public key
+    pub fn prove_possession(&self) -> BlsSignature {
+        BlsSignature(self.secret.sign(&self.public.to_bytes(), POP_DST, &[]))
+    }
+
     /// Get the public key
     pub fn public_key(&self) -> BlsPublicKey {
         self.public.clone()
@@ -104,13 +140,31 @@ impl BlsPublicKey {
         signature.0.verify(true, message, DST, &[], &self.0, true) == BLST_ERROR::BLST_SUCCESS
     }
 
+    /// Verify a proof-of-possession produced by `BlsKeyPair::prove_possession`
+    pub fn verify_possession(&self, proof: &BlsSignature) -> bool {
+        proof.0.verify(true, &self.to_bytes(), POP_DST, &[], &self.0, true)
+            == BLST_ERROR::BLST_SUCCESS
+    }
+
     /// Create from 48-byte compressed representation
+    ///
+    /// Rejects the point at infinity and points outside the G1 subgroup.
     pub fn from_bytes(bytes: &[u8; 48]) -> Result<Self, CryptoError> {
-        PublicKey::from_bytes(bytes)
+        PublicKey::key_validate(bytes)
             .map(BlsPublicKey)
             .map_err(|_| CryptoError::InvalidPublicKey)
     }
 
+    /// Create from a slice, checking its length
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, CryptoError> {
+        let bytes: &[u8; PUBLIC_KEY_LEN] =
+            bytes.try_into().map_err(|_| CryptoError::InvalidKeyLength {
+                expected: PUBLIC_KEY_LEN,
+                actual: bytes.len(),
+            })?;
+        Self::from_bytes(bytes)
+    }
+
     /// Serialize to 48-byte compressed form
     pub fn to_bytes(&self) -> [u8; 48] {
         self.0.to_bytes()
@@ -132,12 +186,22 @@ impl BlsPublicKey {
 
 impl BlsSignature {
     /// Create from 96-byte representation
+    ///
+    /// Rejects the point at infinity and points outside the G2 subgroup.
     pub fn from_bytes(bytes: &[u8; 96]) -> Result<Self, CryptoError> {
-        Signature::from_bytes(bytes)
+        Signature::sig_validate(bytes, true)
             .map(BlsSignature)
             .map_err(|_| CryptoError::InvalidSignature)
     }
 
+    /// Create from a slice, checking its length
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, CryptoError> {
+        let bytes: &[u8; SIGNATURE_LEN] = bytes
+            .try_into()
+            .map_err(|_| CryptoError::InvalidSignatureFormat)?;
+        Self::from_bytes(bytes)
+    }
+
     /// Serialize to 96-byte form
     pub fn to_bytes(&self) -> [u8; 96] {
         self.0.to_bytes()
@@ -155,6 +219,98 @@ impl BlsSignature {
             .map(|asig| BlsSignature(asig.to_signature()))
             .map_err(|_| CryptoError::AggregationFailed)
     }
+
+    /// Verify an aggregate signature where every signer signed `message`
+    ///
+    /// Only sound if each key's proof-of-possession has been verified.
+    pub fn fast_aggregate_verify(&self, message: &[u8], keys: &[BlsPublicKey]) -> bool {
+        if keys.is_empty() {
+            return false;
+        }
+        let refs: Vec<&PublicKey> = keys.iter().map(|k| &k.0).collect();
+        self.0.fast_aggregate_verify(true, message, DST, &refs) == BLST_ERROR::BLST_SUCCESS
+    }
+
+    /// Verify an aggregate signature where signer `i` signed `messages[i]`
+    pub fn aggregate_verify(&self, messages: &[&[u8]], keys: &[BlsPublicKey]) -> bool {
+        if keys.is_empty() || messages.len() != keys.len() {
+            return false;
+        }
+        let refs: Vec<&PublicKey> = keys.iter().map(|k| &k.0).collect();
+        self.0.aggregate_verify(true, messages, DST, &refs, true) == BLST_ERROR::BLST_SUCCESS
+    }
+}
+
+/// Serialize fixed-size point bytes as `0x` hex or raw bytes
+fn serialize_point<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Deserialize point bytes written by `serialize_point`
+fn deserialize_point<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(s.strip_prefix("0x").unwrap_or(&s)).map_err(de::Error::custom)
+    } else {
+        serde_bytes_vec(deserializer)
+    }
+}
+
+/// Raw byte deserialization that accepts both byte strings and sequences
+fn serde_bytes_vec<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    struct BytesVisitor;
+
+    impl<'de> de::Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("compressed BLS point bytes")
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            Ok(bytes)
+        }
+    }
+
+    deserializer.deserialize_bytes(BytesVisitor)
+}
+
+impl Serialize for BlsPublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_point(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BlsPublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_point(deserializer)?;
+        Self::from_slice(&bytes).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for BlsSignature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_point(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BlsSignature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_point(deserializer)?;
+        Self::from_slice(&bytes).map_err(de::Error::custom)
+    }
 }
 
 #[cfg(test)]
@@ -235,6 +391,88 @@ mod tests {
         assert!(pk_restored.verify(message, &sig_restored));
     }
 
+    #[test]
+    fn test_bls_proof_of_possession() {
+        let keypair = BlsKeyPair::generate();
+        let other = BlsKeyPair::generate();
+        let proof = keypair.prove_possession();
+
+        assert!(keypair.public_key().verify_possession(&proof));
+        assert!(!other.public_key().verify_possession(&proof));
+
+        // A PoP is not a valid signature over the key bytes (separate DST)
+        let pk_bytes = keypair.public_key().to_bytes();
+        assert!(!keypair.public_key().verify(&pk_bytes, &proof));
+        assert!(!keypair
+            .public_key()
+            .verify_possession(&keypair.sign(&pk_bytes)));
+    }
+
+    #[test]
+    fn test_bls_aggregate_verify() {
+        let keypairs: Vec<_> = (0..3).map(|_| BlsKeyPair::generate()).collect();
+        let keys: Vec<_> = keypairs.iter().map(|kp| kp.public_key()).collect();
+
+        let message = b"checkpoint";
+        let sigs: Vec<_> = keypairs.iter().map(|kp| kp.sign(message)).collect();
+        let agg = BlsSignature::aggregate(&sigs).unwrap();
+        assert!(agg.fast_aggregate_verify(message, &keys));
+        assert!(!agg.fast_aggregate_verify(message, &keys[..2]));
+        assert!(!agg.fast_aggregate_verify(message, &[]));
+
+        let messages: [&[u8]; 3] = [b"a", b"b", b"c"];
+        let sigs: Vec<_> = keypairs
+            .iter()
+            .zip(messages)
+            .map(|(kp, m)| kp.sign(m))
+            .collect();
+        let agg = BlsSignature::aggregate(&sigs).unwrap();
+        assert!(agg.aggregate_verify(&messages, &keys));
+        assert!(!agg.aggregate_verify(&[b"a", b"c", b"b"], &keys));
+        assert!(!agg.aggregate_verify(&messages[..2], &keys));
+    }
+
+    #[test]
+    fn test_bls_deterministic_keygen() {
+        let ikm = [7u8; 32];
+        let a = BlsKeyPair::from_ikm(&ikm).unwrap();
+        let b = BlsKeyPair::from_ikm(&ikm).unwrap();
+        assert_eq!(a.public_key(), b.public_key());
+        assert!(BlsKeyPair::from_ikm(&[7u8; 31]).is_err());
+    }
+
+    #[test]
+    fn test_bls_rejects_invalid_points() {
+        // Compressed point at infinity
+        let mut infinity_pk = [0u8; 48];
+        infinity_pk[0] = 0xc0;
+        assert!(BlsPublicKey::from_bytes(&infinity_pk).is_err());
+        let mut infinity_sig = [0u8; 96];
+        infinity_sig[0] = 0xc0;
+        assert!(BlsSignature::from_bytes(&infinity_sig).is_err());
+
+        assert!(BlsPublicKey::from_slice(&[0u8; 47]).is_err());
+        assert!(BlsSignature::from_slice(&[0u8; 95]).is_err());
+    }
+
+    #[test]
+    fn test_bls_serde() {
+        let keypair = BlsKeyPair::generate();
+        let signature = keypair.sign(b"serde");
+
+        let json = serde_json::to_string(&keypair.public_key()).unwrap();
+        assert_eq!(json.len(), 2 + 2 + 96);
+        assert!(json.starts_with("\"0x"));
+        let pk: BlsPublicKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(pk, keypair.public_key());
+
+        let json = serde_json::to_string(&signature).unwrap();
+        let sig: BlsSignature = serde_json::from_str(&json).unwrap();
+        assert_eq!(sig, signature);
+
+        assert!(serde_json::from_str::<BlsPublicKey>("\"0x00\"").is_err());
+    }
+
     #[test]
     fn test_bls_from_secret_bytes() {
         let keypair1 = BlsKeyPair::generate();
@@ -0,0 +1,178 @@
+//! # Envelope Signing
+//!
+//! Per-subsystem Ed25519 signatures for `AuthenticatedMessage` envelopes.
+//!
+//! With a single shared HMAC secret, any subsystem holding it can forge
+//! messages from every other subsystem. Ed25519 gives each subsystem its own
+//! signing key (loaded from the keystore); receivers only hold public keys.
+//!
+//! ## Rollout
+//!
+//! `EnvelopeKeyRing` serves both the legacy HMAC secret and the Ed25519
+//! verifying keys, so a `MessageVerifier` in `EnvelopeAuthMode::Migration`
+//! can switch subsystems over one at a time: a subsystem with a verifying
+//! key must sign with Ed25519, the rest may still use HMAC.
+//!
+//! ```rust,ignore
+//! let keys = EnvelopeKeyRing::new()
+//!     .with_hmac_secret(master_secret)
+//!     .with_stored_key(SubsystemId::Consensus.as_u8(), &consensus_key)?;
+//! let verifier = MessageVerifier::new(recipient, nonce_cache, keys)
+//!     .with_auth_mode(EnvelopeAuthMode::Migration);
+//! ```
+
+use crate::{CryptoError, Ed25519KeyPair, Ed25519PublicKey, SecretBytes, StoredKey};
+use shared_types::security::{DerivedKeyProvider, KeyProvider};
+use std::collections::HashMap;
+
+/// Sign serialized envelope bytes (signature field zeroed) with Ed25519.
+///
+/// The result goes straight into `AuthenticatedMessage::signature`.
+pub fn sign_envelope(message_bytes: &[u8], keypair: &Ed25519KeyPair) -> [u8; 64] {
+    *keypair.sign(message_bytes).as_bytes()
+}
+
+/// Key provider holding the HMAC master secret and per-subsystem Ed25519 keys.
+#[derive(Debug, Default)]
+pub struct EnvelopeKeyRing {
+    hmac: Option<DerivedKeyProvider>,
+    verifying_keys: HashMap<u8, Ed25519PublicKey>,
+}
+
+impl EnvelopeKeyRing {
+    /// Create an empty key ring (no HMAC secret, no Ed25519 keys).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept HMAC signatures derived from `master_secret`.
+    pub fn with_hmac_secret(mut self, master_secret: impl Into<SecretBytes>) -> Self {
+        self.hmac = Some(DerivedKeyProvider::new(master_secret));
+        self
+    }
+
+    /// Register the Ed25519 verifying key of a subsystem.
+    pub fn with_subsystem_key(mut self, subsystem_id: u8, public_key: Ed25519PublicKey) -> Self {
+        self.verifying_keys.insert(subsystem_id, public_key);
+        self
+    }
+
+    /// Register the public half of a keystore key (must be Ed25519).
+    pub fn with_stored_key(self, subsystem_id: u8, key: &StoredKey) -> Result<Self, CryptoError> {
+        match key {
+            StoredKey::Ed25519(keypair) => {
+                Ok(self.with_subsystem_key(subsystem_id, keypair.public_key()))
+            }
+            StoredKey::Secp256k1(_) => Err(CryptoError::InvalidInput(
+                "envelope signing keys must be Ed25519".into(),
+            )),
+        }
+    }
+}
+
+impl KeyProvider for EnvelopeKeyRing {
+    fn get_shared_secret(&self, sender_id: u8) -> Option<Vec<u8>> {
+        self.hmac.as_ref()?.get_shared_secret(sender_id)
+    }
+
+    fn get_verifying_key(&self, sender_id: u8) -> Option<[u8; 32]> {
+        self.verifying_keys
+            .get(&sender_id)
+            .map(|key| *key.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KeyType;
+    use shared_types::envelope::AuthenticatedMessage;
+    use shared_types::security::{
+        current_timestamp, sign_message, EnvelopeAuthMode, MessageVerifier, NonceCache,
+    };
+    use uuid::Uuid;
+
+    const CONSENSUS: u8 = 8;
+    const MEMPOOL: u8 = 6;
+
+    fn message(sender_id: u8) -> (AuthenticatedMessage<String>, Vec<u8>) {
+        let message = AuthenticatedMessage {
+            version: AuthenticatedMessage::<String>::CURRENT_VERSION,
+            sender_id,
+            recipient_id: 2,
+            correlation_id: Uuid::new_v4(),
+            reply_to: None,
+            timestamp: current_timestamp(),
+            nonce: Uuid::new_v4(),
+            signature: [0u8; 64],
+            payload: "block".to_string(),
+        };
+        let bytes = serde_json::to_vec(&message).unwrap();
+        (message, bytes)
+    }
+
+    #[test]
+    fn test_migration_accepts_both_schemes() {
+        let consensus_key = StoredKey::generate(KeyType::Ed25519);
+        let keys = || {
+            EnvelopeKeyRing::new()
+                .with_hmac_secret(b"master".to_vec())
+                .with_stored_key(CONSENSUS, &consensus_key)
+                .unwrap()
+        };
+        let verifier =
+            |mode| MessageVerifier::new(2, NonceCache::new_shared(), keys()).with_auth_mode(mode);
+
+        // Consensus has migrated to Ed25519
+        let StoredKey::Ed25519(keypair) = &consensus_key else {
+            unreachable!()
+        };
+        let (mut signed, bytes) = message(CONSENSUS);
+        signed.signature = sign_envelope(&bytes, keypair);
+        assert!(verifier(EnvelopeAuthMode::Migration)
+            .verify(&signed, &bytes)
+            .is_valid());
+        assert!(verifier(EnvelopeAuthMode::Ed25519)
+            .verify(&signed, &bytes)
+            .is_valid());
+
+        // ...so the shared secret can no longer sign as Consensus
+        let (mut forged, bytes) = message(CONSENSUS);
+        let secret = keys().get_shared_secret(CONSENSUS).unwrap();
+        forged.signature = sign_message(&bytes, &secret);
+        assert!(verifier(EnvelopeAuthMode::Migration)
+            .verify(&forged, &bytes)
+            .is_error());
+
+        // Mempool still signs with HMAC
+        let (mut legacy, bytes) = message(MEMPOOL);
+        let secret = keys().get_shared_secret(MEMPOOL).unwrap();
+        legacy.signature = sign_message(&bytes, &secret);
+        assert!(verifier(EnvelopeAuthMode::Migration)
+            .verify(&legacy, &bytes)
+            .is_valid());
+        assert!(verifier(EnvelopeAuthMode::Ed25519)
+            .verify(&legacy, &bytes)
+            .is_error());
+    }
+
+    #[test]
+    fn test_impersonation_rejected() {
+        // Mempool's own key cannot sign as Consensus
+        let consensus = Ed25519KeyPair::generate();
+        let mempool = Ed25519KeyPair::generate();
+        let keys = EnvelopeKeyRing::new()
+            .with_subsystem_key(CONSENSUS, consensus.public_key())
+            .with_subsystem_key(MEMPOOL, mempool.public_key());
+        let verifier = MessageVerifier::new(2, NonceCache::new_shared(), keys)
+            .with_auth_mode(EnvelopeAuthMode::Ed25519);
+
+        let (mut forged, bytes) = message(CONSENSUS);
+        forged.signature = sign_envelope(&bytes, &mempool);
+        assert!(verifier.verify(&forged, &bytes).is_error());
+
+        assert!(EnvelopeKeyRing::new()
+            .with_stored_key(CONSENSUS, &StoredKey::generate(KeyType::Secp256k1))
+            .is_err());
+    }
+}
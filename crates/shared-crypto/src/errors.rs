@@ -62,4 +62,32 @@ pub enum CryptoError {
     /// Invalid input for cryptographic operation
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    /// Keystore password does not match its checksum
+    #[error("Invalid keystore password")]
+    InvalidPassword,
+
+    /// Keystore file is malformed or uses an unsupported scheme
+    #[error("Invalid keystore: {0}")]
+    InvalidKeystore(String),
+
+    /// Mnemonic phrase has unknown words, a bad length, or a bad checksum
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+
+    /// HD derivation path is malformed or not usable for the curve
+    #[error("Invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+
+    /// Secret shares are missing, inconsistent, or malformed
+    #[error("Invalid secret shares: {0}")]
+    InvalidShares(String),
+
+    /// Remote signer was unreachable or refused the request
+    #[error("Remote signer error: {0}")]
+    RemoteSigner(String),
+
+    /// Reading or writing a key file failed
+    #[error("Key file I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
@@ -6,8 +6,16 @@
 //!
 //! - 5-10x faster than SHA-256
 //! - Exploits AVX-512/NEON via internal Merkle tree
+//!
+//! ## Streaming
+//!
+//! `Blake3Hasher` and `Blake3Mac` implement `io::Write`, so large blocks
+//! and snapshot files can be hashed with `io::copy` or `update_reader`
+//! without buffering them in memory.
 
 use blake3::Hasher;
+use std::io;
+use std::path::Path;
 
 /// BLAKE3 hash output (256-bit).
 pub type Hash = [u8; 32];
@@ -44,12 +52,75 @@ impl Blake3Hasher {
         *hash.as_bytes()
     }
 
+    /// Update with everything read from `reader`.
+    pub fn update_reader(&mut self, reader: impl io::Read) -> io::Result<&mut Self> {
+        self.inner.update_reader(reader)?;
+        Ok(self)
+    }
+
+    /// Finalize into an arbitrary-length output (extendable output mode).
+    pub fn finalize_xof(&self, output: &mut [u8]) {
+        self.inner.finalize_xof().fill(output);
+    }
+
     /// Reset hasher for reuse.
     pub fn reset(&mut self) {
         self.inner.reset();
     }
 }
 
+impl io::Write for Blake3Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Streaming keyed BLAKE3 MAC.
+pub struct Blake3Mac {
+    inner: Hasher,
+}
+
+impl Blake3Mac {
+    /// Create a MAC with a 256-bit key.
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self {
+            inner: Hasher::new_keyed(key),
+        }
+    }
+
+    /// Update with data.
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.inner.update(data);
+        self
+    }
+
+    /// Finalize and return the tag.
+    pub fn finalize(&self) -> Hash {
+        *self.inner.finalize().as_bytes()
+    }
+
+    /// Check `tag` against the data seen so far (constant-time).
+    pub fn verify(&self, tag: &Hash) -> bool {
+        self.inner.finalize() == blake3::Hash::from(*tag)
+    }
+}
+
+impl io::Write for Blake3Mac {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 impl Default for Blake3Hasher {
     fn default() -> Self {
         Self::new()
@@ -61,6 +132,16 @@ pub fn blake3_hash(data: &[u8]) -> Hash {
     *blake3::hash(data).as_bytes()
 }
 
+/// Hash everything read from `reader`.
+pub fn blake3_hash_reader(reader: impl io::Read) -> io::Result<Hash> {
+    Ok(Blake3Hasher::new().update_reader(reader)?.finalize())
+}
+
+/// Hash a file without loading it into memory.
+pub fn blake3_hash_file(path: impl AsRef<Path>) -> io::Result<Hash> {
+    blake3_hash_reader(io::BufReader::new(std::fs::File::open(path)?))
+}
+
 /// Hash multiple inputs.
 pub fn blake3_hash_many(inputs: &[&[u8]]) -> Hash {
     let mut hasher = Blake3Hasher::new();
@@ -75,6 +156,11 @@ pub fn blake3_keyed_hash(key: &[u8; 32], data: &[u8]) -> Hash {
     *blake3::keyed_hash(key, data).as_bytes()
 }
 
+/// Verify a keyed hash (MAC) tag in constant time.
+pub fn blake3_keyed_verify(key: &[u8; 32], data: &[u8], tag: &Hash) -> bool {
+    blake3::keyed_hash(key, data) == blake3::Hash::from(*tag)
+}
+
 /// Derive key from context and input key material.
 pub fn blake3_derive_key(context: &str, key_material: &[u8]) -> [u8; 32] {
     let mut output = [0u8; 32];
@@ -132,6 +218,40 @@ mod tests {
         assert_ne!(h1, h3);
     }
 
+    #[test]
+    fn test_write_and_reader_adapters() {
+        let data: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+        let expected = blake3_hash(&data);
+
+        let mut hasher = Blake3Hasher::new();
+        io::copy(&mut data.as_slice(), &mut hasher).unwrap();
+        assert_eq!(hasher.finalize(), expected);
+        assert_eq!(blake3_hash_reader(data.as_slice()).unwrap(), expected);
+
+        let path = std::env::temp_dir().join(format!("qc-hash-{}", std::process::id()));
+        std::fs::write(&path, &data).unwrap();
+        assert_eq!(blake3_hash_file(&path).unwrap(), expected);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut xof = [0u8; 64];
+        hasher.finalize_xof(&mut xof);
+        assert_eq!(xof[..32], expected);
+    }
+
+    #[test]
+    fn test_streaming_mac() {
+        let key = [0x42u8; 32];
+        let mut mac = Blake3Mac::new(&key);
+        mac.update(b"snapshot ").update(b"chunk");
+        let tag = mac.finalize();
+
+        assert_eq!(tag, blake3_keyed_hash(&key, b"snapshot chunk"));
+        assert!(mac.verify(&tag));
+        assert!(blake3_keyed_verify(&key, b"snapshot chunk", &tag));
+        assert!(!blake3_keyed_verify(&key, b"snapshot chunK", &tag));
+        assert!(!blake3_keyed_verify(&[0u8; 32], b"snapshot chunk", &tag));
+    }
+
     #[test]
     fn test_derive_key() {
         let key = blake3_derive_key("quantum-chain encryption", b"master secret");
@@ -0,0 +1,603 @@
+//! # Hierarchical Deterministic Keys
+//!
+//! Mnemonic seed phrases and deterministic key derivation, so a wallet or
+//! validator can recover every account from one backed-up phrase.
+//!
+//! ## Standards
+//!
+//! - **BIP-39**: English mnemonics of 12–24 words, stretched into a 64-byte
+//!   seed with PBKDF2-HMAC-SHA512 and an optional passphrase
+//! - **BIP-32**: secp256k1 child keys (hardened and normal) from the seed
+//! - **SLIP-10**: Ed25519 child keys; Ed25519 only supports hardened
+//!   derivation, so every path component must be hardened
+//!
+//! ## Paths
+//!
+//! Paths use the usual notation, e.g. `m/44'/60'/0'/0/0`. A trailing `'`
+//! or `h` marks a hardened index. `DerivationPath::bip44` builds the
+//! standard account path for a coin type.
+//!
+//! Only private derivation is implemented; extended public keys and
+//! xprv/xpub serialization are not.
+
+use crate::{CryptoError, Ed25519KeyPair, Secp256k1KeyPair};
+use hmac::{Hmac, Mac};
+use k256::elliptic_curve::PrimeField;
+use k256::{FieldBytes, Scalar};
+use sha2::Sha512;
+use std::fmt;
+use std::str::FromStr;
+use zeroize::{Zeroize, Zeroizing};
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// BIP-32 master key HMAC key for secp256k1.
+const SECP256K1_SEED_KEY: &[u8] = b"Bitcoin seed";
+
+/// SLIP-10 master key HMAC key for Ed25519.
+const ED25519_SEED_KEY: &[u8] = b"ed25519 seed";
+
+/// Offset of hardened child indices.
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// BIP-44 purpose field.
+const BIP44_PURPOSE: u32 = 44;
+
+/// SLIP-44 coin type for Ethereum-style secp256k1 accounts.
+pub const COIN_TYPE_ETHEREUM: u32 = 60;
+
+// =============================================================================
+// MNEMONIC
+// =============================================================================
+
+/// BIP-39 mnemonic phrase (English word list).
+#[derive(Clone)]
+pub struct Mnemonic(bip39::Mnemonic);
+
+impl Mnemonic {
+    /// Generate a new random mnemonic of 12, 15, 18, 21 or 24 words.
+    pub fn generate(word_count: usize) -> Result<Self, CryptoError> {
+        if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+            return Err(CryptoError::InvalidMnemonic(format!(
+                "unsupported word count {word_count}"
+            )));
+        }
+        let mut entropy = Zeroizing::new([0u8; 32]);
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), entropy.as_mut());
+        Self::from_entropy(&entropy[..word_count / 3 * 4])
+    }
+
+    /// Build the mnemonic encoding the given entropy (16–32 bytes).
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, CryptoError> {
+        bip39::Mnemonic::from_entropy(entropy)
+            .map(Self)
+            .map_err(|e| CryptoError::InvalidMnemonic(e.to_string()))
+    }
+
+    /// Recover a mnemonic from its phrase, checking words and checksum.
+    ///
+    /// Words are NFKD-normalized and may be separated by any whitespace.
+    pub fn parse(phrase: &str) -> Result<Self, CryptoError> {
+        bip39::Mnemonic::parse_in(bip39::Language::English, phrase)
+            .map(Self)
+            .map_err(|e| CryptoError::InvalidMnemonic(e.to_string()))
+    }
+
+    /// Number of words in the phrase.
+    pub fn word_count(&self) -> usize {
+        self.0.word_count()
+    }
+
+    /// The phrase as space-separated words.
+    pub fn phrase(&self) -> Zeroizing<String> {
+        Zeroizing::new(self.0.to_string())
+    }
+
+    /// Stretch the phrase into a seed (empty passphrase for none).
+    pub fn to_seed(&self, passphrase: &str) -> Seed {
+        Seed(Zeroizing::new(self.0.to_seed(passphrase)))
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mnemonic")
+            .field("word_count", &self.word_count())
+            .finish_non_exhaustive()
+    }
+}
+
+/// 64-byte BIP-39 seed, the root of all derived keys.
+pub struct Seed(Zeroizing<[u8; 64]>);
+
+impl Seed {
+    /// Wrap raw seed bytes.
+    pub fn from_bytes(bytes: [u8; 64]) -> Self {
+        Self(Zeroizing::new(bytes))
+    }
+
+    /// Raw seed bytes.
+    pub fn as_bytes(&self) -> &[u8; 64] {
+        &self.0
+    }
+
+    /// Derive the secp256k1 keypair at `path` (BIP-32).
+    pub fn derive_secp256k1(&self, path: &DerivationPath) -> Result<Secp256k1KeyPair, CryptoError> {
+        Secp256k1ExtendedKey::from_seed(self.as_bytes())?
+            .derive_path(path)?
+            .keypair()
+    }
+
+    /// Derive the Ed25519 keypair at `path` (SLIP-10).
+    pub fn derive_ed25519(&self, path: &DerivationPath) -> Result<Ed25519KeyPair, CryptoError> {
+        Ok(Ed25519ExtendedKey::from_seed(self.as_bytes())
+            .derive_path(path)?
+            .keypair())
+    }
+}
+
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Seed(..)")
+    }
+}
+
+// =============================================================================
+// DERIVATION PATH
+// =============================================================================
+
+/// One component of a derivation path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChildIndex(u32);
+
+impl ChildIndex {
+    /// Normal (non-hardened) index; `index` must be below 2^31.
+    pub fn normal(index: u32) -> Result<Self, CryptoError> {
+        if index >= HARDENED_OFFSET {
+            return Err(CryptoError::InvalidDerivationPath(format!(
+                "index {index} out of range"
+            )));
+        }
+        Ok(Self(index))
+    }
+
+    /// Hardened index; `index` must be below 2^31.
+    pub fn hardened(index: u32) -> Result<Self, CryptoError> {
+        Self::normal(index).map(|c| Self(c.0 | HARDENED_OFFSET))
+    }
+
+    /// Whether this index uses hardened derivation.
+    pub fn is_hardened(&self) -> bool {
+        self.0 >= HARDENED_OFFSET
+    }
+
+    /// Raw 32-bit index, including the hardened bit.
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Display for ChildIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_hardened() {
+            write!(f, "{}'", self.0 - HARDENED_OFFSET)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+/// Derivation path from the master key, e.g. `m/44'/60'/0'/0/0`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<ChildIndex>);
+
+impl DerivationPath {
+    /// Path of the master key itself (`m`).
+    pub fn master() -> Self {
+        Self::default()
+    }
+
+    /// BIP-44 path `m/44'/coin_type'/account'/0/index`.
+    ///
+    /// The change and address levels are normal indices, so this path only
+    /// works for secp256k1; use `bip44_hardened` for Ed25519.
+    pub fn bip44(coin_type: u32, account: u32, index: u32) -> Result<Self, CryptoError> {
+        Ok(Self(vec![
+            ChildIndex::hardened(BIP44_PURPOSE)?,
+            ChildIndex::hardened(coin_type)?,
+            ChildIndex::hardened(account)?,
+            ChildIndex::normal(0)?,
+            ChildIndex::normal(index)?,
+        ]))
+    }
+
+    /// BIP-44 path with every level hardened, as SLIP-10 Ed25519 requires:
+    /// `m/44'/coin_type'/account'/0'/index'`.
+    pub fn bip44_hardened(coin_type: u32, account: u32, index: u32) -> Result<Self, CryptoError> {
+        Ok(Self(vec![
+            ChildIndex::hardened(BIP44_PURPOSE)?,
+            ChildIndex::hardened(coin_type)?,
+            ChildIndex::hardened(account)?,
+            ChildIndex::hardened(0)?,
+            ChildIndex::hardened(index)?,
+        ]))
+    }
+
+    /// Append a child index.
+    pub fn child(mut self, index: ChildIndex) -> Self {
+        self.0.push(index);
+        self
+    }
+
+    /// Path components, from the master key down.
+    pub fn indices(&self) -> &[ChildIndex] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CryptoError::InvalidDerivationPath(s.to_string());
+        let mut parts = s.trim().split('/');
+        if parts.next() != Some("m") {
+            return Err(invalid());
+        }
+        let mut indices = Vec::new();
+        for part in parts {
+            let (digits, hardened) = match part.strip_suffix(['\'', 'h', 'H']) {
+                Some(digits) => (digits, true),
+                None => (part, false),
+            };
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            let index = digits.parse::<u32>().map_err(|_| invalid())?;
+            indices.push(if hardened {
+                ChildIndex::hardened(index)?
+            } else {
+                ChildIndex::normal(index)?
+            });
+        }
+        Ok(Self(indices))
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("m")?;
+        for index in &self.0 {
+            write!(f, "/{index}")?;
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// EXTENDED KEYS
+// =============================================================================
+
+/// HMAC-SHA512, split into the left (key) and right (chain code) halves.
+fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>) {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts any key length");
+    for part in parts {
+        mac.update(part);
+    }
+    let mut output = mac.finalize().into_bytes();
+    let mut left = Zeroizing::new([0u8; 32]);
+    let mut right = Zeroizing::new([0u8; 32]);
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    output.zeroize();
+    (left, right)
+}
+
+/// Parse 32 bytes as a non-zero secp256k1 scalar below the curve order.
+fn secp256k1_scalar(bytes: &[u8; 32]) -> Option<Scalar> {
+    let scalar: Option<Scalar> = Scalar::from_repr(FieldBytes::from(*bytes)).into();
+    scalar.filter(|s| !bool::from(s.is_zero()))
+}
+
+/// BIP-32 extended private key on secp256k1.
+pub struct Secp256k1ExtendedKey {
+    key: Zeroizing<[u8; 32]>,
+    chain_code: Zeroizing<[u8; 32]>,
+    depth: u8,
+}
+
+impl Secp256k1ExtendedKey {
+    /// Master key from a 16–64 byte seed.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, CryptoError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(CryptoError::InvalidInput(format!(
+                "seed must be 16-64 bytes, got {}",
+                seed.len()
+            )));
+        }
+        let (key, chain_code) = hmac_sha512(SECP256K1_SEED_KEY, &[seed]);
+        // Probability ~2^-127; BIP-32 says to treat the seed as unusable.
+        secp256k1_scalar(&key).ok_or(CryptoError::InvalidPrivateKey)?;
+        Ok(Self {
+            key,
+            chain_code,
+            depth: 0,
+        })
+    }
+
+    /// Derive the direct child at `index`.
+    ///
+    /// In the ~2^-127 case where the index yields an invalid key, the error
+    /// is returned and the caller should move on to the next index.
+    pub fn derive_child(&self, index: ChildIndex) -> Result<Self, CryptoError> {
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or_else(|| CryptoError::InvalidDerivationPath("depth exceeds 255".into()))?;
+        let index_bytes = index.value().to_be_bytes();
+        let (tweak, chain_code) = if index.is_hardened() {
+            hmac_sha512(
+                self.chain_code.as_ref(),
+                &[&[0], self.key.as_ref(), &index_bytes],
+            )
+        } else {
+            let public = self.keypair()?.public_key();
+            hmac_sha512(self.chain_code.as_ref(), &[public.as_bytes(), &index_bytes])
+        };
+
+        let parent = secp256k1_scalar(&self.key).ok_or(CryptoError::InvalidPrivateKey)?;
+        let tweak = secp256k1_scalar(&tweak).ok_or(CryptoError::InvalidPrivateKey)?;
+        let child = parent + tweak;
+        if bool::from(child.is_zero()) {
+            return Err(CryptoError::InvalidPrivateKey);
+        }
+        Ok(Self {
+            key: Zeroizing::new(child.to_bytes().into()),
+            chain_code,
+            depth,
+        })
+    }
+
+    /// Derive the descendant at `path`, relative to this key.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, CryptoError> {
+        let mut key = self.clone();
+        for index in path.indices() {
+            key = key.derive_child(*index)?;
+        }
+        Ok(key)
+    }
+
+    /// Signing keypair for this node.
+    pub fn keypair(&self) -> Result<Secp256k1KeyPair, CryptoError> {
+        Secp256k1KeyPair::from_bytes(*self.key)
+    }
+
+    /// Chain code for this node.
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Depth below the master key.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+}
+
+impl Clone for Secp256k1ExtendedKey {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            chain_code: self.chain_code.clone(),
+            depth: self.depth,
+        }
+    }
+}
+
+/// SLIP-10 extended private key on Ed25519 (hardened derivation only).
+pub struct Ed25519ExtendedKey {
+    key: Zeroizing<[u8; 32]>,
+    chain_code: Zeroizing<[u8; 32]>,
+    depth: u8,
+}
+
+impl Ed25519ExtendedKey {
+    /// Master key from a seed.
+    pub fn from_seed(seed: &[u8]) -> Self {
+        let (key, chain_code) = hmac_sha512(ED25519_SEED_KEY, &[seed]);
+        Self {
+            key,
+            chain_code,
+            depth: 0,
+        }
+    }
+
+    /// Derive the direct child at a hardened `index`.
+    pub fn derive_child(&self, index: ChildIndex) -> Result<Self, CryptoError> {
+        if !index.is_hardened() {
+            return Err(CryptoError::InvalidDerivationPath(format!(
+                "Ed25519 requires hardened indices, got {index}"
+            )));
+        }
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or_else(|| CryptoError::InvalidDerivationPath("depth exceeds 255".into()))?;
+        let index_bytes = index.value().to_be_bytes();
+        let (key, chain_code) = hmac_sha512(
+            self.chain_code.as_ref(),
+            &[&[0], self.key.as_ref(), &index_bytes],
+        );
+        Ok(Self {
+            key,
+            chain_code,
+            depth,
+        })
+    }
+
+    /// Derive the descendant at `path`, relative to this key.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, CryptoError> {
+        let mut key = self.clone();
+        for index in path.indices() {
+            key = key.derive_child(*index)?;
+        }
+        Ok(key)
+    }
+
+    /// Signing keypair for this node.
+    pub fn keypair(&self) -> Ed25519KeyPair {
+        Ed25519KeyPair::from_seed(*self.key)
+    }
+
+    /// Chain code for this node.
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Depth below the master key.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+}
+
+impl Clone for Ed25519ExtendedKey {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            chain_code: self.chain_code.clone(),
+            depth: self.depth,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// BIP-32 / SLIP-10 test vector 1 seed.
+    const VECTOR_SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    fn path(s: &str) -> DerivationPath {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_bip39_vector() {
+        let mnemonic = Mnemonic::from_entropy(&[0u8; 16]).unwrap();
+        assert_eq!(
+            mnemonic.phrase().as_str(),
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        );
+        let seed = Mnemonic::parse(&mnemonic.phrase())
+            .unwrap()
+            .to_seed("TREZOR");
+        assert_eq!(
+            hex::encode(seed.as_bytes()),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+
+        assert!(Mnemonic::parse("abandon abandon abandon").is_err());
+        assert!(Mnemonic::parse(&"abandon ".repeat(12)).is_err()); // bad checksum
+        assert_eq!(Mnemonic::generate(24).unwrap().word_count(), 24);
+        assert!(Mnemonic::generate(13).is_err());
+    }
+
+    #[test]
+    fn test_bip32_secp256k1_vector() {
+        let seed = hex::decode(VECTOR_SEED).unwrap();
+        let master = Secp256k1ExtendedKey::from_seed(&seed).unwrap();
+        let cases = [
+            (
+                "m",
+                "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+                "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+            ),
+            (
+                "m/0'",
+                "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+                "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            ),
+            (
+                "m/0'/1",
+                "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+                "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            ),
+            (
+                "m/0'/1/2'/2/1000000000",
+                "c783e67b921d2beb8f6b389cc646d7263b4145701dadd2161548a8b078e65e9e",
+                "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+            ),
+        ];
+        for (p, chain_code, key) in cases {
+            let node = master.derive_path(&path(p)).unwrap();
+            assert_eq!(hex::encode(node.chain_code()), chain_code, "{p}");
+            assert_eq!(hex::encode(node.keypair().unwrap().to_bytes()), key, "{p}");
+        }
+    }
+
+    #[test]
+    fn test_slip10_ed25519_vector() {
+        let seed = hex::decode(VECTOR_SEED).unwrap();
+        let master = Ed25519ExtendedKey::from_seed(&seed);
+        let cases = [
+            (
+                "m",
+                "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+                "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            ),
+            (
+                "m/0'",
+                "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+                "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            ),
+            (
+                "m/0'/1'",
+                "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+                "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+            ),
+        ];
+        for (p, chain_code, key) in cases {
+            let node = master.derive_path(&path(p)).unwrap();
+            assert_eq!(hex::encode(node.chain_code()), chain_code, "{p}");
+            assert_eq!(hex::encode(node.keypair().to_seed()), key, "{p}");
+        }
+        assert!(master.derive_path(&path("m/0'/1")).is_err());
+    }
+
+    #[test]
+    fn test_derivation_path_parsing() {
+        let p = path("m/44'/60'/0'/0/7");
+        assert_eq!(p, DerivationPath::bip44(COIN_TYPE_ETHEREUM, 0, 7).unwrap());
+        assert_eq!(p.to_string(), "m/44'/60'/0'/0/7");
+        assert_eq!(path("m/1h/2H").to_string(), "m/1'/2'");
+        assert_eq!(path("m"), DerivationPath::master());
+
+        for bad in ["", "44'/0", "m/", "m/x", "m/-1", "m/2147483648", "m//1"] {
+            assert!(bad.parse::<DerivationPath>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_seed_derives_distinct_accounts() {
+        let mnemonic = Mnemonic::generate(12).unwrap();
+        let seed = Mnemonic::parse(&mnemonic.phrase()).unwrap().to_seed("");
+        let a = seed
+            .derive_secp256k1(&DerivationPath::bip44(COIN_TYPE_ETHEREUM, 0, 0).unwrap())
+            .unwrap();
+        let b = seed
+            .derive_secp256k1(&DerivationPath::bip44(COIN_TYPE_ETHEREUM, 0, 1).unwrap())
+            .unwrap();
+        assert_ne!(a.public_key(), b.public_key());
+
+        let again = mnemonic
+            .to_seed("")
+            .derive_secp256k1(&DerivationPath::bip44(COIN_TYPE_ETHEREUM, 0, 0).unwrap())
+            .unwrap();
+        assert_eq!(a.to_bytes(), again.to_bytes());
+
+        let validator = DerivationPath::bip44_hardened(COIN_TYPE_ETHEREUM, 0, 0).unwrap();
+        assert!(seed.derive_ed25519(&validator).is_ok());
+        assert!(seed
+            .derive_ed25519(&DerivationPath::bip44(COIN_TYPE_ETHEREUM, 0, 0).unwrap())
+            .is_err());
+    }
+}
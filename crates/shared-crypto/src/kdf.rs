@@ -0,0 +1,112 @@
+//! # Key Derivation (HKDF)
+//!
+//! HKDF-SHA256 (RFC 5869) for turning one master secret into independent
+//! per-purpose keys, e.g. a separate encryption key for each P2P channel
+//! or storage domain.
+//!
+//! ## Usage
+//!
+//! Extract once with `KeyDeriver::new`, then expand as many keys as needed.
+//! Distinct `info` labels yield independent keys; compromising one derived
+//! key reveals nothing about the master secret or its siblings.
+
+use crate::{CryptoError, SecretKey};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+/// Label prefix for channel encryption keys.
+const CHANNEL_KEY_INFO: &str = "quantum-chain/channel-key/v1/";
+
+/// Largest output HKDF-SHA256 can produce (255 * 32 bytes).
+pub const MAX_OUTPUT_LEN: usize = 255 * 32;
+
+/// HKDF-SHA256 pseudorandom key, ready to expand.
+pub struct KeyDeriver {
+    hkdf: Hkdf<Sha256>,
+}
+
+impl KeyDeriver {
+    /// Extract from input key material and an optional salt.
+    pub fn new(ikm: &[u8], salt: Option<&[u8]>) -> Self {
+        Self {
+            hkdf: Hkdf::new(salt, ikm),
+        }
+    }
+
+    /// Expand `info` into `output` (at most `MAX_OUTPUT_LEN` bytes).
+    pub fn expand(&self, info: &[u8], output: &mut [u8]) -> Result<(), CryptoError> {
+        self.hkdf.expand(info, output).map_err(|_| {
+            CryptoError::InvalidInput(format!(
+                "HKDF output of {} bytes exceeds {MAX_OUTPUT_LEN}",
+                output.len()
+            ))
+        })
+    }
+
+    /// Derive a 256-bit key for `info`.
+    pub fn derive_key(&self, info: &[u8]) -> SecretKey {
+        let mut key = Zeroizing::new([0u8; 32]);
+        self.expand(info, key.as_mut())
+            .expect("32 bytes is within HKDF output limit");
+        SecretKey::from_bytes(*key)
+    }
+
+    /// Derive the encryption key for a named channel.
+    pub fn channel_key(&self, channel: &str) -> SecretKey {
+        self.derive_key(format!("{CHANNEL_KEY_INFO}{channel}").as_bytes())
+    }
+}
+
+/// One-shot HKDF-SHA256.
+pub fn hkdf_sha256(
+    ikm: &[u8],
+    salt: Option<&[u8]>,
+    info: &[u8],
+    output: &mut [u8],
+) -> Result<(), CryptoError> {
+    KeyDeriver::new(ikm, salt).expand(info, output)
+}
+
+/// Derive the encryption key for `channel` from a master secret.
+pub fn derive_channel_key(master_secret: &[u8], channel: &str) -> SecretKey {
+    KeyDeriver::new(master_secret, None).channel_key(channel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decrypt, encrypt};
+
+    #[test]
+    fn test_rfc5869_case_1() {
+        let ikm = [0x0bu8; 22];
+        let salt = hex::decode("000102030405060708090a0b0c").unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        let mut okm = [0u8; 42];
+        hkdf_sha256(&ikm, Some(&salt), &info, &mut okm).unwrap();
+        assert_eq!(
+            hex::encode(okm),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        );
+
+        let mut too_long = vec![0u8; MAX_OUTPUT_LEN + 1];
+        assert!(hkdf_sha256(&ikm, None, &info, &mut too_long).is_err());
+    }
+
+    #[test]
+    fn test_channel_keys_are_independent() {
+        let master = b"node master secret";
+        let gossip = derive_channel_key(master, "gossip");
+        let sync = derive_channel_key(master, "sync");
+        assert_ne!(gossip.as_bytes(), sync.as_bytes());
+        assert_eq!(
+            gossip.as_bytes(),
+            derive_channel_key(master, "gossip").as_bytes()
+        );
+
+        let (ciphertext, nonce) = encrypt(&gossip, b"block").unwrap();
+        assert_eq!(decrypt(&gossip, &ciphertext, &nonce).unwrap(), b"block");
+        assert!(decrypt(&sync, &ciphertext, &nonce).is_err());
+    }
+}
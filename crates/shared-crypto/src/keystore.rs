@@ -0,0 +1,787 @@
+//! # Encrypted Keystore
+//!
+//! Password-protected key files, so node identity and validator keys are
+//! never stored as raw bytes.
+//!
+//! ## Format
+//!
+//! Key files are written in the EIP-2335 (version 4) layout:
+//!
+//! - **KDF**: scrypt (default), PBKDF2-HMAC-SHA256, or Argon2id
+//! - **Cipher**: AES-128-CTR keyed with the first half of the derived key
+//! - **Checksum**: SHA-256 over the second half of the derived key and the
+//!   ciphertext, checked before decrypting
+//!
+//! Web3 secret storage (version 3) files, as written by Ethereum clients,
+//! can also be read; they hold secp256k1 keys. Argon2id is an extension
+//! (`"function": "argon2id"`) that other EIP-2335 tools will not read.
+//!
+//! ## Passwords
+//!
+//! Control characters are stripped from passwords as EIP-2335 requires.
+//! NFKD normalization is not applied, so non-ASCII passwords must already
+//! be normalized to interoperate with other tools.
+//!
+//! ## Key Lifecycle
+//!
+//! `save_key`, `load_key`, `rotate_key` and `change_password` manage one
+//! Ed25519 or secp256k1 key per file. Decrypted secrets and derived keys
+//! are zeroized when dropped.
+
+use crate::{constant_time_eq, CryptoError, Ed25519KeyPair, Secp256k1KeyPair};
+use aes::cipher::{KeyIvInit, StreamCipher};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// Keystore layout version written by this module (EIP-2335).
+pub const KEYSTORE_VERSION: u32 = 4;
+
+/// Default scrypt cost, as in the EIP-2335 reference keystores (N = 2^18).
+pub const DEFAULT_SCRYPT_LOG_N: u8 = 18;
+
+/// Derived key length in bytes.
+const DKLEN: usize = 32;
+
+/// Random salt length in bytes.
+const SALT_LEN: usize = 32;
+
+/// AES-CTR IV length in bytes.
+const IV_LEN: usize = 16;
+
+/// Password-based key derivation function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    /// scrypt with cost `2^log_n`, block size `r` and parallelism `p`.
+    Scrypt {
+        /// Base-2 log of the CPU/memory cost.
+        log_n: u8,
+        /// Block size.
+        r: u32,
+        /// Parallelism.
+        p: u32,
+    },
+    /// PBKDF2-HMAC-SHA256.
+    Pbkdf2 {
+        /// Iteration count.
+        iterations: u32,
+    },
+    /// Argon2id (version 0x13).
+    Argon2id {
+        /// Memory cost in KiB.
+        memory_kib: u32,
+        /// Number of passes.
+        iterations: u32,
+        /// Degree of parallelism.
+        parallelism: u32,
+    },
+}
+
+impl Default for Kdf {
+    fn default() -> Self {
+        Self::Scrypt {
+            log_n: DEFAULT_SCRYPT_LOG_N,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl Kdf {
+    /// Argon2id with 64 MiB of memory and 3 passes.
+    pub fn argon2id() -> Self {
+        Self::Argon2id {
+            memory_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 1,
+        }
+    }
+
+    /// Derive the 32-byte key for `password`.
+    fn derive(&self, password: &[u8], salt: &[u8]) -> Result<Zeroizing<[u8; DKLEN]>, CryptoError> {
+        let mut key = Zeroizing::new([0u8; DKLEN]);
+        match *self {
+            Self::Scrypt { log_n, r, p } => {
+                let params = scrypt::Params::new(log_n, r, p, DKLEN)
+                    .map_err(|e| CryptoError::InvalidKeystore(format!("scrypt params: {e}")))?;
+                scrypt::scrypt(password, salt, &params, key.as_mut())
+                    .map_err(|e| CryptoError::InvalidKeystore(format!("scrypt: {e}")))?;
+            }
+            Self::Pbkdf2 { iterations } => {
+                pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, iterations, key.as_mut());
+            }
+            Self::Argon2id {
+                memory_kib,
+                iterations,
+                parallelism,
+            } => {
+                let params = argon2::Params::new(memory_kib, iterations, parallelism, Some(DKLEN))
+                    .map_err(|e| CryptoError::InvalidKeystore(format!("argon2 params: {e}")))?;
+                argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+                    .hash_password_into(password, salt, key.as_mut())
+                    .map_err(|e| CryptoError::InvalidKeystore(format!("argon2: {e}")))?;
+            }
+        }
+        Ok(key)
+    }
+
+    /// The KDF module recording these parameters and `salt`.
+    fn to_module(self, salt: &[u8]) -> Module {
+        let mut params = Map::new();
+        params.insert("dklen".into(), DKLEN.into());
+        let function = match self {
+            Self::Scrypt { log_n, r, p } => {
+                params.insert("n".into(), (1u64 << log_n).into());
+                params.insert("r".into(), r.into());
+                params.insert("p".into(), p.into());
+                "scrypt"
+            }
+            Self::Pbkdf2 { iterations } => {
+                params.insert("c".into(), iterations.into());
+                params.insert("prf".into(), "hmac-sha256".into());
+                "pbkdf2"
+            }
+            Self::Argon2id {
+                memory_kib,
+                iterations,
+                parallelism,
+            } => {
+                params.insert("m".into(), memory_kib.into());
+                params.insert("t".into(), iterations.into());
+                params.insert("p".into(), parallelism.into());
+                "argon2id"
+            }
+        };
+        params.insert("salt".into(), hex::encode(salt).into());
+        Module {
+            function: function.into(),
+            params,
+            message: String::new(),
+        }
+    }
+
+    /// Parse a KDF module into its parameters and salt.
+    fn from_module(module: &Module) -> Result<(Self, Vec<u8>), CryptoError> {
+        let params = &module.params;
+        let dklen = param_u32(params, "dklen")?;
+        if dklen as usize != DKLEN {
+            return Err(CryptoError::InvalidKeystore(format!(
+                "unsupported dklen {dklen}"
+            )));
+        }
+
+        let kdf = match module.function.as_str() {
+            "scrypt" => {
+                let n = param_u64(params, "n")?;
+                if !n.is_power_of_two() {
+                    return Err(CryptoError::InvalidKeystore(format!(
+                        "scrypt n {n} is not a power of two"
+                    )));
+                }
+                Self::Scrypt {
+                    log_n: n.trailing_zeros() as u8,
+                    r: param_u32(params, "r")?,
+                    p: param_u32(params, "p")?,
+                }
+            }
+            "pbkdf2" => {
+                match params.get("prf").and_then(Value::as_str) {
+                    Some("hmac-sha256") => {}
+                    prf => {
+                        return Err(CryptoError::InvalidKeystore(format!(
+                            "unsupported pbkdf2 prf {prf:?}"
+                        )))
+                    }
+                }
+                Self::Pbkdf2 {
+                    iterations: param_u32(params, "c")?,
+                }
+            }
+            "argon2id" => Self::Argon2id {
+                memory_kib: param_u32(params, "m")?,
+                iterations: param_u32(params, "t")?,
+                parallelism: param_u32(params, "p")?,
+            },
+            other => {
+                return Err(CryptoError::InvalidKeystore(format!(
+                    "unsupported kdf {other}"
+                )))
+            }
+        };
+        Ok((kdf, param_hex(params, "salt")?))
+    }
+}
+
+/// A keystore module: a function, its parameters and its output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Module {
+    /// Function name (e.g. `scrypt`, `sha256`, `aes-128-ctr`).
+    pub function: String,
+    /// Function parameters.
+    #[serde(default)]
+    pub params: Map<String, Value>,
+    /// Hex-encoded output (empty for the KDF).
+    #[serde(default)]
+    pub message: String,
+}
+
+/// The modules protecting the secret.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CryptoModules {
+    /// Password-based key derivation.
+    pub kdf: Module,
+    /// Password check over the ciphertext.
+    pub checksum: Module,
+    /// Encrypted secret.
+    pub cipher: Module,
+}
+
+/// Kind of key held in a keystore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyType {
+    /// Ed25519 signing key (32-byte seed).
+    Ed25519,
+    /// secp256k1 ECDSA key (32-byte scalar).
+    Secp256k1,
+}
+
+/// An encrypted secret in the EIP-2335 layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keystore {
+    /// Encryption of the secret.
+    pub crypto: CryptoModules,
+    /// Free-form description.
+    #[serde(default)]
+    pub description: String,
+    /// Hex-encoded public key.
+    #[serde(default)]
+    pub pubkey: String,
+    /// Key derivation path, empty if the key is not derived.
+    #[serde(default)]
+    pub path: String,
+    /// Unique keystore ID.
+    pub uuid: Uuid,
+    /// Layout version.
+    pub version: u32,
+    /// Kind of key, absent in keystores written by other tools.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_type: Option<KeyType>,
+}
+
+/// Web3 secret storage (version 3) layout.
+#[derive(Deserialize)]
+struct V3Keystore {
+    #[serde(alias = "Crypto")]
+    crypto: V3Crypto,
+    id: Uuid,
+    #[serde(default)]
+    address: String,
+}
+
+#[derive(Deserialize)]
+struct V3Crypto {
+    cipher: String,
+    cipherparams: Map<String, Value>,
+    ciphertext: String,
+    kdf: String,
+    kdfparams: Map<String, Value>,
+    mac: String,
+}
+
+impl From<V3Keystore> for Keystore {
+    fn from(v3: V3Keystore) -> Self {
+        let crypto = v3.crypto;
+        Self {
+            crypto: CryptoModules {
+                kdf: Module {
+                    function: crypto.kdf,
+                    params: crypto.kdfparams,
+                    message: String::new(),
+                },
+                // Version 3 checks the password with Keccak-256
+                checksum: Module {
+                    function: "keccak256".into(),
+                    params: Map::new(),
+                    message: crypto.mac,
+                },
+                cipher: Module {
+                    function: crypto.cipher,
+                    params: crypto.cipherparams,
+                    message: crypto.ciphertext,
+                },
+            },
+            description: v3.address,
+            pubkey: String::new(),
+            path: String::new(),
+            uuid: v3.id,
+            version: KEYSTORE_VERSION,
+            key_type: Some(KeyType::Secp256k1),
+        }
+    }
+}
+
+impl Keystore {
+    /// Encrypt `secret` under `password`.
+    pub fn encrypt(secret: &[u8], password: &str, kdf: Kdf) -> Result<Self, CryptoError> {
+        let salt: [u8; SALT_LEN] = rand::random();
+        let iv: [u8; IV_LEN] = rand::random();
+        let key = kdf.derive(&normalize_password(password), &salt)?;
+
+        let mut ciphertext = secret.to_vec();
+        apply_aes_ctr(&key, &iv, &mut ciphertext);
+
+        let mut cipher_params = Map::new();
+        cipher_params.insert("iv".into(), hex::encode(iv).into());
+        Ok(Self {
+            crypto: CryptoModules {
+                kdf: kdf.to_module(&salt),
+                checksum: Module {
+                    function: "sha256".into(),
+                    params: Map::new(),
+                    message: hex::encode(checksum("sha256", &key, &ciphertext)?),
+                },
+                cipher: Module {
+                    function: "aes-128-ctr".into(),
+                    params: cipher_params,
+                    message: hex::encode(ciphertext),
+                },
+            },
+            description: String::new(),
+            pubkey: String::new(),
+            path: String::new(),
+            uuid: Uuid::new_v4(),
+            version: KEYSTORE_VERSION,
+            key_type: None,
+        })
+    }
+
+    /// Decrypt the secret.
+    ///
+    /// # Errors
+    ///
+    /// `CryptoError::InvalidPassword` if the checksum does not match.
+    pub fn decrypt(&self, password: &str) -> Result<Zeroizing<Vec<u8>>, CryptoError> {
+        let (kdf, salt) = Kdf::from_module(&self.crypto.kdf)?;
+        let key = kdf.derive(&normalize_password(password), &salt)?;
+
+        let ciphertext = decode_hex(&self.crypto.cipher.message, "cipher message")?;
+        let expected = decode_hex(&self.crypto.checksum.message, "checksum")?;
+        let actual = checksum(&self.crypto.checksum.function, &key, &ciphertext)?;
+        if !constant_time_eq(&actual, &expected) {
+            return Err(CryptoError::InvalidPassword);
+        }
+
+        if self.crypto.cipher.function != "aes-128-ctr" {
+            return Err(CryptoError::InvalidKeystore(format!(
+                "unsupported cipher {}",
+                self.crypto.cipher.function
+            )));
+        }
+        let iv = param_hex(&self.crypto.cipher.params, "iv")?;
+        let iv: [u8; IV_LEN] = iv.try_into().map_err(|iv: Vec<u8>| {
+            CryptoError::InvalidKeystore(format!("iv of {} bytes", iv.len()))
+        })?;
+
+        let mut secret = Zeroizing::new(ciphertext);
+        apply_aes_ctr(&key, &iv, &mut secret);
+        Ok(secret)
+    }
+
+    /// Parse a version 4 (EIP-2335) or version 3 (web3) keystore.
+    pub fn from_json(json: &str) -> Result<Self, CryptoError> {
+        let value: Value = serde_json::from_str(json).map_err(invalid_json)?;
+        match value.get("version").and_then(Value::as_u64) {
+            Some(4) => serde_json::from_value(value).map_err(invalid_json),
+            Some(3) => serde_json::from_value::<V3Keystore>(value)
+                .map(Self::from)
+                .map_err(invalid_json),
+            version => Err(CryptoError::InvalidKeystore(format!(
+                "unsupported version {version:?}"
+            ))),
+        }
+    }
+
+    /// Serialize in the EIP-2335 layout.
+    pub fn to_json(&self) -> Result<String, CryptoError> {
+        serde_json::to_string_pretty(self).map_err(invalid_json)
+    }
+}
+
+/// A decrypted key.
+pub enum StoredKey {
+    /// Ed25519 keypair.
+    Ed25519(Ed25519KeyPair),
+    /// secp256k1 keypair.
+    Secp256k1(Secp256k1KeyPair),
+}
+
+impl StoredKey {
+    /// Generate a random key.
+    pub fn generate(key_type: KeyType) -> Self {
+        match key_type {
+            KeyType::Ed25519 => Self::Ed25519(Ed25519KeyPair::generate()),
+            KeyType::Secp256k1 => Self::Secp256k1(Secp256k1KeyPair::generate()),
+        }
+    }
+
+    /// Kind of key.
+    pub fn key_type(&self) -> KeyType {
+        match self {
+            Self::Ed25519(_) => KeyType::Ed25519,
+            Self::Secp256k1(_) => KeyType::Secp256k1,
+        }
+    }
+
+    /// Public key bytes (32 for Ed25519, 33 compressed for secp256k1).
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Ed25519(key) => key.public_key().as_bytes().to_vec(),
+            Self::Secp256k1(key) => key.public_key().as_bytes().to_vec(),
+        }
+    }
+
+    fn secret(&self) -> Zeroizing<[u8; 32]> {
+        match self {
+            Self::Ed25519(key) => Zeroizing::new(key.to_seed()),
+            Self::Secp256k1(key) => Zeroizing::new(key.to_bytes()),
+        }
+    }
+
+    fn from_secret(key_type: KeyType, secret: &[u8]) -> Result<Self, CryptoError> {
+        let bytes: Zeroizing<[u8; 32]> =
+            Zeroizing::new(
+                secret
+                    .try_into()
+                    .map_err(|_| CryptoError::InvalidKeyLength {
+                        expected: 32,
+                        actual: secret.len(),
+                    })?,
+            );
+        match key_type {
+            KeyType::Ed25519 => Ok(Self::Ed25519(Ed25519KeyPair::from_seed(*bytes))),
+            KeyType::Secp256k1 => Secp256k1KeyPair::from_bytes(*bytes).map(Self::Secp256k1),
+        }
+    }
+
+    /// Encrypt this key under `password`.
+    pub fn to_keystore(&self, password: &str, kdf: Kdf) -> Result<Keystore, CryptoError> {
+        let mut keystore = Keystore::encrypt(self.secret().as_ref(), password, kdf)?;
+        keystore.pubkey = hex::encode(self.public_key_bytes());
+        keystore.key_type = Some(self.key_type());
+        Ok(keystore)
+    }
+
+    /// Decrypt a keystore holding an Ed25519 or secp256k1 key.
+    pub fn from_keystore(keystore: &Keystore, password: &str) -> Result<Self, CryptoError> {
+        let key_type = keystore
+            .key_type
+            .ok_or_else(|| CryptoError::InvalidKeystore("unknown key type".into()))?;
+        let secret = keystore.decrypt(password)?;
+        Self::from_secret(key_type, &secret)
+    }
+}
+
+/// Encrypt `key` to a new key file at `path`, readable only by its owner.
+///
+/// An existing file is replaced atomically.
+pub fn save_key(
+    path: impl AsRef<Path>,
+    key: &StoredKey,
+    password: &str,
+    kdf: Kdf,
+) -> Result<Keystore, CryptoError> {
+    let keystore = key.to_keystore(password, kdf)?;
+    let tmp = write_temp(path.as_ref(), &keystore)?;
+    fs::rename(tmp, path)?;
+    Ok(keystore)
+}
+
+/// Decrypt the key file at `path`.
+pub fn load_key(path: impl AsRef<Path>, password: &str) -> Result<StoredKey, CryptoError> {
+    let keystore = Keystore::from_json(&fs::read_to_string(path)?)?;
+    StoredKey::from_keystore(&keystore, password)
+}
+
+/// Replace the key at `path` with a new key of the same type.
+///
+/// The old file is kept next to it as `<path>.retired-<unix seconds>`, so
+/// signatures made with the old key can still be checked.
+pub fn rotate_key(
+    path: impl AsRef<Path>,
+    password: &str,
+    kdf: Kdf,
+) -> Result<StoredKey, CryptoError> {
+    let path = path.as_ref();
+    let old = load_key(path, password)?;
+    let new = StoredKey::generate(old.key_type());
+
+    let tmp = write_temp(path, &new.to_keystore(password, kdf)?)?;
+    fs::rename(path, retired_path(path))?;
+    fs::rename(tmp, path)?;
+    Ok(new)
+}
+
+/// Re-encrypt the key at `path` under a new password.
+pub fn change_password(
+    path: impl AsRef<Path>,
+    old_password: &str,
+    new_password: &str,
+    kdf: Kdf,
+) -> Result<(), CryptoError> {
+    let path = path.as_ref();
+    let key = load_key(path, old_password)?;
+    save_key(path, &key, new_password, kdf).map(|_| ())
+}
+
+/// Write `keystore` to a temporary file next to `path`.
+fn write_temp(path: &Path, keystore: &Keystore) -> Result<PathBuf, CryptoError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(&tmp)?;
+    file.write_all(keystore.to_json()?.as_bytes())?;
+    file.sync_all()?;
+    Ok(tmp)
+}
+
+fn retired_path(path: &Path) -> PathBuf {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".retired-{secs}"));
+    PathBuf::from(name)
+}
+
+/// Strip control characters (C0, DEL and C1), per EIP-2335.
+fn normalize_password(password: &str) -> Zeroizing<Vec<u8>> {
+    let stripped: String = password
+        .chars()
+        .filter(|c| !matches!(*c, '\u{00}'..='\u{1F}' | '\u{7F}'..='\u{9F}'))
+        .collect();
+    Zeroizing::new(stripped.into_bytes())
+}
+
+/// Password checksum over the second half of the derived key and the
+/// ciphertext.
+fn checksum(function: &str, key: &[u8; DKLEN], ciphertext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    match function {
+        "sha256" => Ok(Sha256::new()
+            .chain_update(&key[16..])
+            .chain_update(ciphertext)
+            .finalize()
+            .to_vec()),
+        "keccak256" => Ok(Keccak256::new()
+            .chain_update(&key[16..])
+            .chain_update(ciphertext)
+            .finalize()
+            .to_vec()),
+        other => Err(CryptoError::InvalidKeystore(format!(
+            "unsupported checksum {other}"
+        ))),
+    }
+}
+
+/// AES-128-CTR with the first half of the derived key, in place.
+fn apply_aes_ctr(key: &[u8; DKLEN], iv: &[u8; IV_LEN], data: &mut [u8]) {
+    let mut cipher = Aes128Ctr::new(key[..16].into(), iv.into());
+    cipher.apply_keystream(data);
+}
+
+fn decode_hex(value: &str, what: &str) -> Result<Vec<u8>, CryptoError> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| CryptoError::InvalidKeystore(format!("{what}: {e}")))
+}
+
+fn param_hex(params: &Map<String, Value>, name: &str) -> Result<Vec<u8>, CryptoError> {
+    let value = params
+        .get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| CryptoError::InvalidKeystore(format!("missing {name}")))?;
+    decode_hex(value, name)
+}
+
+fn param_u64(params: &Map<String, Value>, name: &str) -> Result<u64, CryptoError> {
+    params
+        .get(name)
+        .and_then(Value::as_u64)
+        .ok_or_else(|| CryptoError::InvalidKeystore(format!("missing {name}")))
+}
+
+fn param_u32(params: &Map<String, Value>, name: &str) -> Result<u32, CryptoError> {
+    u32::try_from(param_u64(params, name)?)
+        .map_err(|_| CryptoError::InvalidKeystore(format!("{name} out of range")))
+}
+
+fn invalid_json(e: serde_json::Error) -> CryptoError {
+    CryptoError::InvalidKeystore(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap scrypt parameters, so tests run quickly.
+    const FAST: Kdf = Kdf::Scrypt {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    /// EIP-2335 PBKDF2 test vector.
+    const EIP2335_PBKDF2: &str = r#"{
+        "crypto": {
+            "kdf": {
+                "function": "pbkdf2",
+                "params": {
+                    "dklen": 32,
+                    "c": 262144,
+                    "prf": "hmac-sha256",
+                    "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                },
+                "message": ""
+            },
+            "checksum": {
+                "function": "sha256",
+                "params": {},
+                "message": "8a9f5d9912ed7e75ea794bc5a89bca5f193721d30868ade6f73043c6ea6febf1"
+            },
+            "cipher": {
+                "function": "aes-128-ctr",
+                "params": {"iv": "264daa3f303d7259501c93d997d84fe6"},
+                "message": "cee03fde2af33149775b7223e7845e4fb2c8ae1792e5f99fe9ecf474cc8c16ad"
+            }
+        },
+        "description": "This is a test keystore that uses PBKDF2 to secure the secret.",
+        "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+        "path": "m/12381/60/0/0",
+        "uuid": "64625def-3331-4eea-ab6f-782f3ed16a83",
+        "version": 4
+    }"#;
+
+    /// Web3 secret storage PBKDF2 test vector.
+    const WEB3_V3_PBKDF2: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": {"iv": "6087dab2f9fdbbfaddc31a909735c1e6"},
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    #[test]
+    fn test_decrypt_eip2335_vector() {
+        let keystore = Keystore::from_json(EIP2335_PBKDF2).unwrap();
+        // NFKD form of the vector's password
+        let secret = keystore.decrypt("testpassword\u{1F511}").unwrap();
+        assert_eq!(
+            hex::encode(&*secret),
+            "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f"
+        );
+        assert!(matches!(
+            keystore.decrypt("wrong"),
+            Err(CryptoError::InvalidPassword)
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_web3_v3_vector() {
+        let keystore = Keystore::from_json(WEB3_V3_PBKDF2).unwrap();
+        assert_eq!(keystore.key_type, Some(KeyType::Secp256k1));
+        let StoredKey::Secp256k1(key) =
+            StoredKey::from_keystore(&keystore, "testpassword").unwrap()
+        else {
+            panic!("expected a secp256k1 key");
+        };
+        assert_eq!(
+            hex::encode(key.to_bytes()),
+            "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"
+        );
+    }
+
+    #[test]
+    fn test_roundtrip_with_each_kdf() {
+        let secret = [7u8; 32];
+        let kdfs = [
+            FAST,
+            Kdf::Pbkdf2 { iterations: 1000 },
+            Kdf::Argon2id {
+                memory_kib: 64,
+                iterations: 1,
+                parallelism: 1,
+            },
+        ];
+        for kdf in kdfs {
+            let keystore = Keystore::encrypt(&secret, "pass\u{7}word", kdf).unwrap();
+            let parsed = Keystore::from_json(&keystore.to_json().unwrap()).unwrap();
+            assert_eq!(parsed, keystore);
+            // Control characters are not part of the password
+            assert_eq!(*parsed.decrypt("password").unwrap(), secret.to_vec());
+        }
+    }
+
+    #[test]
+    fn test_save_load_rotate_and_change_password() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("validator.json");
+
+        let key = StoredKey::generate(KeyType::Ed25519);
+        save_key(&path, &key, "first", FAST).unwrap();
+        let loaded = load_key(&path, "first").unwrap();
+        assert_eq!(loaded.public_key_bytes(), key.public_key_bytes());
+        assert!(matches!(
+            load_key(&path, "second"),
+            Err(CryptoError::InvalidPassword)
+        ));
+
+        change_password(&path, "first", "second", FAST).unwrap();
+        assert_eq!(
+            load_key(&path, "second").unwrap().public_key_bytes(),
+            key.public_key_bytes()
+        );
+
+        let rotated = rotate_key(&path, "second", FAST).unwrap();
+        assert_eq!(rotated.key_type(), KeyType::Ed25519);
+        assert_ne!(rotated.public_key_bytes(), key.public_key_bytes());
+        assert_eq!(
+            load_key(&path, "second").unwrap().public_key_bytes(),
+            rotated.public_key_bytes()
+        );
+        let retired = fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(Result::ok)
+            .find(|entry| entry.file_name().to_string_lossy().contains(".retired-"))
+            .unwrap();
+        assert_eq!(
+            load_key(retired.path(), "second")
+                .unwrap()
+                .public_key_bytes(),
+            key.public_key_bytes()
+        );
+    }
+}
@@ -7,10 +7,16 @@
 //! | Module | Algorithm | Use Case |
 //! |--------|-----------|----------|
 //! | `symmetric` | XChaCha20-Poly1305, AES-GCM | Encryption |
-//! | `hashing` | BLAKE3 | Fast hashing |
+//! | `hashing` | BLAKE3 | Fast hashing, streaming hashes, keyed MACs |
+//! | `kdf` | HKDF-SHA256 | Per-channel key derivation |
 //! | `signatures` | Ed25519 | Digital signatures (future P2P) |
+//! | `envelope` | Ed25519 | Per-subsystem IPC envelope signatures |
 //! | `ecdsa` | secp256k1 | Transaction/Node identity signing |
-//! | `bls` | BLS12-381 | Attestation signatures (qc-09-finality) |
+//! | `bls` | BLS12-381 | Attestation signatures, aggregation, proof-of-possession |
+//! | `hd` | BIP-39, BIP-32, SLIP-10 | Mnemonic backup, HD key derivation |
+//! | `threshold` | Shamir, Feldman VSS | Splitting keys across operators/HSMs |
+//! | `signer` | Ed25519, secp256k1, BLS | Signing port for HSMs / remote signers |
+//! | `keystore` | scrypt/Argon2id + AES-128-CTR | Encrypted key files (EIP-2335) |
 //!
 //! ## Security Properties
 //!
@@ -24,18 +30,37 @@
 
 pub mod bls;
 pub mod ecdsa;
+pub mod envelope;
 pub mod errors;
 pub mod hashing;
+pub mod hd;
+pub mod kdf;
+pub mod keystore;
+#[cfg(feature = "remote-signer")]
+pub mod remote_signer;
 pub mod signatures;
+pub mod signer;
 pub mod symmetric;
+pub mod threshold;
 
 // Re-exports
 pub use bls::{BlsKeyPair, BlsPublicKey, BlsSignature};
 pub use ecdsa::{Secp256k1KeyPair, Secp256k1PublicKey, Secp256k1Signature};
+pub use envelope::{sign_envelope, EnvelopeKeyRing};
 pub use errors::CryptoError;
-pub use hashing::{blake3_hash, Blake3Hasher};
+pub use hashing::{blake3_hash, Blake3Hasher, Blake3Mac};
+pub use hd::{
+    ChildIndex, DerivationPath, Ed25519ExtendedKey, Mnemonic, Secp256k1ExtendedKey, Seed,
+};
+pub use kdf::{derive_channel_key, hkdf_sha256, KeyDeriver};
+pub use keystore::{Kdf, KeyType, Keystore, StoredKey};
+#[cfg(feature = "remote-signer")]
+pub use remote_signer::{RemoteSigner, RemoteSignerConfig};
 pub use signatures::{Ed25519KeyPair, Ed25519PublicKey, Ed25519Signature};
+pub use signer::{verify_signature, SignatureScheme, Signer, SoftwareSigner};
+pub use shared_types::secret::{constant_time_eq, SecretBytes};
 pub use symmetric::{decrypt, encrypt, Cipher, Nonce, SecretKey};
+pub use threshold::{FeldmanCommitments, Share, VerifiableShare};
 
 /// Crate version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,272 @@
+//! # Remote Signer Adapter
+//!
+//! `Signer` implementation that forwards signing requests over HTTP to a
+//! remote signer or HSM gateway (protocol described in `signer`).
+//!
+//! Requires the `remote-signer` feature.
+
+use crate::signer::{
+    request_mac, verify_signature, RemoteSignRequest, RemoteSignResponse, SignatureScheme, Signer,
+    NONCE_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use crate::{CryptoError, SecretBytes};
+use async_trait::async_trait;
+use rand::RngCore;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default request timeout.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Remote signer connection settings.
+#[derive(Clone, Debug)]
+pub struct RemoteSignerConfig {
+    /// Base URL of the signer service, e.g. `https://signer.internal:9000`
+    pub endpoint: String,
+    /// Key identifier known to the signer service
+    pub key_id: String,
+    /// Signature scheme of the remote key
+    pub scheme: SignatureScheme,
+    /// Expected public key; every returned signature is checked against it
+    pub public_key: Vec<u8>,
+    /// Shared secret for request authentication
+    pub auth_secret: SecretBytes,
+    /// Per-request timeout
+    pub timeout: Duration,
+}
+
+impl RemoteSignerConfig {
+    /// Create a config with the default timeout.
+    pub fn new(
+        endpoint: impl Into<String>,
+        key_id: impl Into<String>,
+        scheme: SignatureScheme,
+        public_key: Vec<u8>,
+        auth_secret: impl Into<SecretBytes>,
+    ) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            key_id: key_id.into(),
+            scheme,
+            public_key,
+            auth_secret: auth_secret.into(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Set the per-request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Signer that delegates to a remote signing service.
+pub struct RemoteSigner {
+    config: RemoteSignerConfig,
+    client: reqwest::Client,
+}
+
+impl RemoteSigner {
+    /// Create a remote signer.
+    pub fn new(config: RemoteSignerConfig) -> Result<Self, CryptoError> {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| CryptoError::RemoteSigner(e.to_string()))?;
+        Ok(Self { config, client })
+    }
+
+    async fn request(&self, message: &[u8]) -> Result<RemoteSignResponse, CryptoError> {
+        let body = serde_json::to_vec(&RemoteSignRequest {
+            key_id: self.config.key_id.clone(),
+            scheme: self.config.scheme,
+            message: hex::encode(message),
+        })
+        .map_err(|e| CryptoError::RemoteSigner(e.to_string()))?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut nonce = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let nonce = hex::encode(nonce);
+        let mac = request_mac(
+            self.config.auth_secret.expose_secret(),
+            timestamp,
+            &nonce,
+            &body,
+        );
+
+        let url = format!("{}/sign", self.config.endpoint.trim_end_matches('/'));
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(NONCE_HEADER, nonce)
+            .header(SIGNATURE_HEADER, hex::encode(mac))
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| CryptoError::RemoteSigner(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(CryptoError::RemoteSigner(format!(
+                "signer returned HTTP {status}"
+            )));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| CryptoError::RemoteSigner(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn scheme(&self) -> SignatureScheme {
+        self.config.scheme
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        self.config.public_key.clone()
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let response = self.request(message).await?;
+        let signature = hex::decode(
+            response
+                .signature
+                .strip_prefix("0x")
+                .unwrap_or(&response.signature),
+        )
+        .map_err(|_| CryptoError::InvalidSignatureFormat)?;
+        verify_signature(
+            self.config.scheme,
+            &self.config.public_key,
+            message,
+            &signature,
+        )?;
+        Ok(signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::{verify_request_mac, SoftwareSigner};
+    use crate::Ed25519KeyPair;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    const SECRET: &[u8] = b"shared-signer-secret";
+
+    /// Read one HTTP/1.1 request, returning (headers, body).
+    async fn read_request(stream: &mut TcpStream) -> (String, Vec<u8>) {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        loop {
+            let n = stream.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+            let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") else {
+                continue;
+            };
+            let headers = String::from_utf8_lossy(&buf[..end]).to_lowercase();
+            let len: usize = header(&headers, "content-length").parse().unwrap();
+            if buf.len() >= end + 4 + len {
+                return (headers, buf[end + 4..end + 4 + len].to_vec());
+            }
+        }
+    }
+
+    fn header<'a>(headers: &'a str, name: &str) -> &'a str {
+        headers
+            .lines()
+            .find_map(|line| line.strip_prefix(&format!("{name}: ")))
+            .unwrap_or_default()
+            .trim()
+    }
+
+    /// Serve signing requests with `signer`; `tamper` corrupts responses.
+    async fn serve(listener: TcpListener, signer: Arc<SoftwareSigner>, tamper: bool) {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let (headers, body) = read_request(&mut stream).await;
+            let authenticated = verify_request_mac(
+                SECRET,
+                header(&headers, "x-qc-timestamp").parse().unwrap(),
+                header(&headers, "x-qc-nonce"),
+                &body,
+                header(&headers, "x-qc-signature"),
+            );
+            let response = if authenticated {
+                let request: RemoteSignRequest = serde_json::from_slice(&body).unwrap();
+                let mut signature = signer
+                    .sign(&hex::decode(request.message).unwrap())
+                    .await
+                    .unwrap();
+                signature[0] ^= tamper as u8;
+                let json = serde_json::to_string(&RemoteSignResponse {
+                    signature: format!("0x{}", hex::encode(signature)),
+                })
+                .unwrap();
+                format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{json}",
+                    json.len()
+                )
+            } else {
+                "HTTP/1.1 401 Unauthorized\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                    .to_string()
+            };
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    }
+
+    async fn remote_signer(tamper: bool, secret: &[u8]) -> RemoteSigner {
+        let local = Arc::new(SoftwareSigner::ed25519(Ed25519KeyPair::generate()));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let public_key = local.public_key();
+        tokio::spawn(serve(listener, local, tamper));
+
+        RemoteSigner::new(RemoteSignerConfig::new(
+            endpoint,
+            "validator-0",
+            SignatureScheme::Ed25519,
+            public_key,
+            secret.to_vec(),
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_remote_signature_verifies() {
+        let signer = remote_signer(false, SECRET).await;
+        let signature = signer.sign(b"block header").await.unwrap();
+        assert!(verify_signature(
+            SignatureScheme::Ed25519,
+            &signer.public_key(),
+            b"block header",
+            &signature
+        )
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_failures() {
+        let tampered = remote_signer(true, SECRET).await;
+        assert!(matches!(
+            tampered.sign(b"block header").await,
+            Err(CryptoError::SignatureVerificationFailed)
+        ));
+
+        let unauthenticated = remote_signer(false, b"wrong-secret").await;
+        assert!(matches!(
+            unauthenticated.sign(b"block header").await,
+            Err(CryptoError::RemoteSigner(_))
+        ));
+    }
+}
@@ -0,0 +1,272 @@
+//! # Signer Port
+//!
+//! Signing abstracted away from raw private keys, so validator and node
+//! identity keys can live in an HSM or a remote signer instead of process
+//! memory.
+//!
+//! ## Implementations
+//!
+//! | Type | Key location | Feature |
+//! |------|--------------|---------|
+//! | `SoftwareSigner` | In-process keypair (fallback) | always |
+//! | `RemoteSigner` | HTTP remote signer / HSM gateway | `remote-signer` |
+//!
+//! ## Remote Signing Protocol
+//!
+//! `POST {endpoint}/sign` with a JSON `RemoteSignRequest` body; the signer
+//! replies with a `RemoteSignResponse`. Each request is authenticated with
+//! HMAC-SHA256 under a shared secret (see `request_mac`) over the timestamp,
+//! a random nonce and the body, sent in the `X-QC-Timestamp`, `X-QC-Nonce`
+//! and `X-QC-Signature` headers. Signer services should reject stale
+//! timestamps and repeated nonces.
+//!
+//! The client never trusts the returned signature: it is verified against
+//! the configured public key before being handed to the caller.
+
+use crate::{
+    BlsKeyPair, BlsPublicKey, BlsSignature, CryptoError, Ed25519KeyPair, Ed25519PublicKey,
+    Ed25519Signature, Secp256k1KeyPair, Secp256k1PublicKey, Secp256k1Signature, StoredKey,
+};
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// Header carrying the request timestamp (unix seconds).
+pub const TIMESTAMP_HEADER: &str = "X-QC-Timestamp";
+
+/// Header carrying the hex request nonce.
+pub const NONCE_HEADER: &str = "X-QC-Nonce";
+
+/// Header carrying the hex HMAC-SHA256 request signature.
+pub const SIGNATURE_HEADER: &str = "X-QC-Signature";
+
+/// Signature algorithm produced by a signer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureScheme {
+    /// Ed25519 (32-byte keys, 64-byte signatures)
+    Ed25519,
+    /// secp256k1 ECDSA (33-byte compressed keys, 64-byte signatures)
+    Secp256k1,
+    /// BLS12-381 (48-byte keys, 96-byte signatures)
+    Bls12381,
+}
+
+/// Port: produce signatures without exposing the private key.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Signature algorithm of this signer.
+    fn scheme(&self) -> SignatureScheme;
+
+    /// Public key in the scheme's canonical encoding.
+    fn public_key(&self) -> Vec<u8>;
+
+    /// Sign a message.
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, CryptoError>;
+}
+
+/// Verify a signature produced by any `Signer`.
+pub fn verify_signature(
+    scheme: SignatureScheme,
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), CryptoError> {
+    match scheme {
+        SignatureScheme::Ed25519 => {
+            let key = Ed25519PublicKey::from_bytes(
+                public_key
+                    .try_into()
+                    .map_err(|_| CryptoError::InvalidPublicKey)?,
+            )?;
+            let sig = Ed25519Signature::from_bytes(
+                signature
+                    .try_into()
+                    .map_err(|_| CryptoError::InvalidSignatureFormat)?,
+            );
+            key.verify(message, &sig)
+        }
+        SignatureScheme::Secp256k1 => {
+            let key = Secp256k1PublicKey::from_bytes(
+                public_key
+                    .try_into()
+                    .map_err(|_| CryptoError::InvalidPublicKey)?,
+            )?;
+            let sig = Secp256k1Signature::from_bytes(
+                signature
+                    .try_into()
+                    .map_err(|_| CryptoError::InvalidSignatureFormat)?,
+            );
+            key.verify(message, &sig)
+        }
+        SignatureScheme::Bls12381 => {
+            let key = BlsPublicKey::from_slice(public_key)?;
+            let sig = BlsSignature::from_slice(signature)?;
+            key.verify(message, &sig)
+                .then_some(())
+                .ok_or(CryptoError::SignatureVerificationFailed)
+        }
+    }
+}
+
+// =============================================================================
+// SOFTWARE SIGNER
+// =============================================================================
+
+enum SoftwareKey {
+    Ed25519(Ed25519KeyPair),
+    Secp256k1(Secp256k1KeyPair),
+    Bls(BlsKeyPair),
+}
+
+/// Signer backed by an in-process keypair.
+///
+/// Used when no HSM or remote signer is configured, and in tests.
+pub struct SoftwareSigner(SoftwareKey);
+
+impl SoftwareSigner {
+    /// Sign with an Ed25519 keypair.
+    pub fn ed25519(keypair: Ed25519KeyPair) -> Self {
+        Self(SoftwareKey::Ed25519(keypair))
+    }
+
+    /// Sign with a secp256k1 keypair.
+    pub fn secp256k1(keypair: Secp256k1KeyPair) -> Self {
+        Self(SoftwareKey::Secp256k1(keypair))
+    }
+
+    /// Sign with a BLS keypair.
+    pub fn bls(keypair: BlsKeyPair) -> Self {
+        Self(SoftwareKey::Bls(keypair))
+    }
+}
+
+impl From<StoredKey> for SoftwareSigner {
+    fn from(key: StoredKey) -> Self {
+        match key {
+            StoredKey::Ed25519(keypair) => Self::ed25519(keypair),
+            StoredKey::Secp256k1(keypair) => Self::secp256k1(keypair),
+        }
+    }
+}
+
+#[async_trait]
+impl Signer for SoftwareSigner {
+    fn scheme(&self) -> SignatureScheme {
+        match &self.0 {
+            SoftwareKey::Ed25519(_) => SignatureScheme::Ed25519,
+            SoftwareKey::Secp256k1(_) => SignatureScheme::Secp256k1,
+            SoftwareKey::Bls(_) => SignatureScheme::Bls12381,
+        }
+    }
+
+    fn public_key(&self) -> Vec<u8> {
+        match &self.0 {
+            SoftwareKey::Ed25519(kp) => kp.public_key().as_bytes().to_vec(),
+            SoftwareKey::Secp256k1(kp) => kp.public_key().as_bytes().to_vec(),
+            SoftwareKey::Bls(kp) => kp.public_key().to_bytes().to_vec(),
+        }
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        Ok(match &self.0 {
+            SoftwareKey::Ed25519(kp) => kp.sign(message).as_bytes().to_vec(),
+            SoftwareKey::Secp256k1(kp) => kp.sign(message).as_bytes().to_vec(),
+            SoftwareKey::Bls(kp) => kp.sign(message).to_bytes().to_vec(),
+        })
+    }
+}
+
+// =============================================================================
+// REMOTE SIGNING PROTOCOL
+// =============================================================================
+
+/// Body of a remote signing request.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteSignRequest {
+    /// Key identifier known to the signer service
+    pub key_id: String,
+    /// Expected signature scheme
+    pub scheme: SignatureScheme,
+    /// Hex-encoded message to sign
+    pub message: String,
+}
+
+/// Body of a remote signing response.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteSignResponse {
+    /// Hex-encoded signature
+    pub signature: String,
+}
+
+/// Keyed HMAC over `timestamp || "\n" || nonce || "\n" || body`.
+fn request_hmac(secret: &[u8], timestamp: u64, nonce: &str, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b"\n");
+    mac.update(nonce.as_bytes());
+    mac.update(b"\n");
+    mac.update(body);
+    mac
+}
+
+/// HMAC-SHA256 authenticating a remote signing request.
+pub fn request_mac(secret: &[u8], timestamp: u64, nonce: &str, body: &[u8]) -> [u8; 32] {
+    request_hmac(secret, timestamp, nonce, body)
+        .finalize()
+        .into_bytes()
+        .into()
+}
+
+/// Check a request MAC in constant time (for signer services).
+pub fn verify_request_mac(
+    secret: &[u8],
+    timestamp: u64,
+    nonce: &str,
+    body: &[u8],
+    mac_hex: &str,
+) -> bool {
+    hex::decode(mac_hex).is_ok_and(|tag| {
+        request_hmac(secret, timestamp, nonce, body)
+            .verify_slice(&tag)
+            .is_ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_software_signers_verify() {
+        let signers = [
+            SoftwareSigner::ed25519(Ed25519KeyPair::generate()),
+            SoftwareSigner::secp256k1(Secp256k1KeyPair::generate()),
+            SoftwareSigner::bls(BlsKeyPair::generate()),
+        ];
+        for signer in &signers {
+            let signature = signer.sign(b"header").await.unwrap();
+            let public_key = signer.public_key();
+            assert!(verify_signature(signer.scheme(), &public_key, b"header", &signature).is_ok());
+            assert!(verify_signature(signer.scheme(), &public_key, b"other", &signature).is_err());
+        }
+    }
+
+    #[test]
+    fn test_stored_key_conversion() {
+        let signer = SoftwareSigner::from(StoredKey::generate(crate::KeyType::Secp256k1));
+        assert_eq!(signer.scheme(), SignatureScheme::Secp256k1);
+        assert_eq!(signer.public_key().len(), 33);
+    }
+
+    #[test]
+    fn test_request_mac() {
+        let mac = hex::encode(request_mac(b"secret", 100, "abcd", b"{}"));
+        assert!(verify_request_mac(b"secret", 100, "abcd", b"{}", &mac));
+        assert!(!verify_request_mac(b"secret", 101, "abcd", b"{}", &mac));
+        assert!(!verify_request_mac(b"secret", 100, "abce", b"{}", &mac));
+        assert!(!verify_request_mac(b"other", 100, "abcd", b"{}", &mac));
+        assert!(!verify_request_mac(b"secret", 100, "abcd", b"{}", "zz"));
+    }
+}
@@ -12,9 +12,12 @@ use chacha20poly1305::{
     aead::{Aead, KeyInit},
     XChaCha20Poly1305, XNonce,
 };
+use shared_types::constant_time_eq;
 use zeroize::Zeroize;
 
 /// Secret key (256-bit).
+///
+/// Zeroized on drop, compared in constant time, and redacted in `Debug`.
 #[derive(Clone, Zeroize)]
 #[zeroize(drop)]
 pub struct SecretKey([u8; 32]);
@@ -38,6 +41,20 @@ impl SecretKey {
     }
 }
 
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SecretKey {}
+
+impl std::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretKey(<redacted>)")
+    }
+}
+
 /// Nonce for encryption.
 #[derive(Clone)]
 pub struct Nonce([u8; 24]); // XChaCha20 uses 24-byte nonce
@@ -147,4 +164,12 @@ mod tests {
         let n2 = Nonce::generate();
         assert_ne!(n1.as_bytes(), n2.as_bytes());
     }
+
+    #[test]
+    fn test_secret_key_redacted_and_comparable() {
+        let key = SecretKey::from_bytes([0xAA; 32]);
+        assert_eq!(format!("{key:?}"), "SecretKey(<redacted>)");
+        assert_eq!(key, SecretKey::from_bytes([0xAA; 32]));
+        assert_ne!(key, SecretKey::generate());
+    }
 }
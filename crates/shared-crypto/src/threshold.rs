@@ -0,0 +1,436 @@
+//! # Threshold Secret Sharing
+//!
+//! Split a secret into `n` shares so that any `t` of them recover it and
+//! fewer reveal nothing. Lets a validator key or the node HMAC master secret
+//! be held across several operators or HSMs.
+//!
+//! ## Schemes
+//!
+//! | Function | Field | Secret | Verifiable |
+//! |----------|-------|--------|------------|
+//! | `split` / `combine` | GF(2^8), byte-wise | Any bytes | No |
+//! | `split_verifiable` / `combine_verifiable` | secp256k1 scalars | 32-byte key | Yes (Feldman) |
+//!
+//! Feldman commitments let each holder check their share against the
+//! dealer's published commitments, and `FeldmanCommitments::public_key`
+//! is the public key of the shared secp256k1 key, so a distributed
+//! validator can be addressed without ever reassembling its secret.
+//!
+//! ## Security Notes
+//!
+//! - GF(2^8) arithmetic is constant-time (no lookup tables)
+//! - Share values are zeroized when dropped
+//! - `combine` cannot detect a corrupted share; use the verifiable scheme
+//!   where a dealer or holder may be dishonest
+
+use crate::{CryptoError, Secp256k1PublicKey};
+use k256::elliptic_curve::group::GroupEncoding;
+use k256::elliptic_curve::{Field, PrimeField};
+use k256::{FieldBytes, ProjectivePoint, Scalar};
+use std::collections::HashSet;
+use zeroize::{Zeroize, Zeroizing};
+
+// =============================================================================
+// GF(2^8) SHAMIR
+// =============================================================================
+
+/// Multiply in GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(2^8) (a^254); `a` must be non-zero.
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exp = 254u8;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// One share of a byte secret.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Share {
+    index: u8,
+    value: Zeroizing<Vec<u8>>,
+}
+
+impl Share {
+    /// Share index (x-coordinate, 1–255).
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Encode as `index || value`.
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(1 + self.value.len()));
+        bytes.push(self.index);
+        bytes.extend_from_slice(&self.value);
+        bytes
+    }
+
+    /// Decode from `to_bytes` output.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+        match bytes.split_first() {
+            Some((&index, value)) if index != 0 && !value.is_empty() => Ok(Self {
+                index,
+                value: Zeroizing::new(value.to_vec()),
+            }),
+            _ => Err(CryptoError::InvalidShares("malformed share".into())),
+        }
+    }
+}
+
+impl std::fmt::Debug for Share {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Share")
+            .field("index", &self.index)
+            .field("len", &self.value.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Check `1 <= threshold <= count`.
+fn check_parameters(threshold: usize, count: usize, max: usize) -> Result<(), CryptoError> {
+    if threshold == 0 || threshold > count || count > max {
+        return Err(CryptoError::InvalidInput(format!(
+            "need 1 <= threshold ({threshold}) <= shares ({count}) <= {max}"
+        )));
+    }
+    Ok(())
+}
+
+/// Split `secret` into `count` shares, any `threshold` of which recover it.
+pub fn split(secret: &[u8], threshold: u8, count: u8) -> Result<Vec<Share>, CryptoError> {
+    check_parameters(threshold.into(), count.into(), u8::MAX.into())?;
+    if secret.is_empty() {
+        return Err(CryptoError::InvalidInput("empty secret".into()));
+    }
+
+    let mut shares: Vec<Share> = (1..=count)
+        .map(|index| Share {
+            index,
+            value: Zeroizing::new(vec![0u8; secret.len()]),
+        })
+        .collect();
+
+    // One random polynomial per byte, constant term = secret byte
+    let mut coefficients = Zeroizing::new(vec![0u8; threshold as usize]);
+    for (pos, &byte) in secret.iter().enumerate() {
+        coefficients[0] = byte;
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut coefficients[1..]);
+        for share in &mut shares {
+            // Horner's rule
+            share.value[pos] = coefficients
+                .iter()
+                .rev()
+                .fold(0, |acc, &c| gf_mul(acc, share.index) ^ c);
+        }
+    }
+    Ok(shares)
+}
+
+/// Recover the secret from at least `threshold` shares.
+///
+/// Passing fewer shares than the threshold yields a wrong secret rather
+/// than an error; the threshold is not recorded in the shares.
+pub fn combine(shares: &[Share]) -> Result<Zeroizing<Vec<u8>>, CryptoError> {
+    let first = shares
+        .first()
+        .ok_or_else(|| CryptoError::InvalidShares("no shares".into()))?;
+    let len = first.value.len();
+    if shares.iter().any(|s| s.value.len() != len) {
+        return Err(CryptoError::InvalidShares("share lengths differ".into()));
+    }
+    let mut seen = HashSet::new();
+    if !shares.iter().all(|s| s.index != 0 && seen.insert(s.index)) {
+        return Err(CryptoError::InvalidShares("duplicate or zero index".into()));
+    }
+
+    // Lagrange basis at x = 0: l_i = prod_{j != i} x_j / (x_j - x_i)
+    let basis: Vec<u8> = shares
+        .iter()
+        .map(|si| {
+            shares
+                .iter()
+                .filter(|sj| sj.index != si.index)
+                .fold(1, |acc, sj| {
+                    gf_mul(acc, gf_mul(sj.index, gf_inv(sj.index ^ si.index)))
+                })
+        })
+        .collect();
+
+    let mut secret = Zeroizing::new(vec![0u8; len]);
+    for (share, &l) in shares.iter().zip(&basis) {
+        for (out, &y) in secret.iter_mut().zip(share.value.iter()) {
+            *out ^= gf_mul(y, l);
+        }
+    }
+    Ok(secret)
+}
+
+// =============================================================================
+// FELDMAN VERIFIABLE SHARING (secp256k1)
+// =============================================================================
+
+/// Parse 32 bytes as a secp256k1 scalar below the curve order.
+fn scalar_from_bytes(bytes: &[u8; 32]) -> Option<Scalar> {
+    Option::from(Scalar::from_repr(FieldBytes::from(*bytes)))
+}
+
+/// One share of a secp256k1 secret key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct VerifiableShare {
+    index: u32,
+    value: Zeroizing<[u8; 32]>,
+}
+
+impl VerifiableShare {
+    /// Share index (x-coordinate, non-zero).
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Encode as big-endian `index (4 bytes) || value (32 bytes)`.
+    pub fn to_bytes(&self) -> Zeroizing<[u8; 36]> {
+        let mut bytes = Zeroizing::new([0u8; 36]);
+        bytes[..4].copy_from_slice(&self.index.to_be_bytes());
+        bytes[4..].copy_from_slice(self.value.as_ref());
+        bytes
+    }
+
+    /// Decode from `to_bytes` output.
+    pub fn from_bytes(bytes: &[u8; 36]) -> Result<Self, CryptoError> {
+        let index = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        let mut value = Zeroizing::new([0u8; 32]);
+        value.copy_from_slice(&bytes[4..]);
+        if index == 0 || scalar_from_bytes(&value).is_none() {
+            return Err(CryptoError::InvalidShares("malformed share".into()));
+        }
+        Ok(Self { index, value })
+    }
+
+    fn scalar(&self) -> Scalar {
+        scalar_from_bytes(&self.value).expect("share values are reduced scalars")
+    }
+}
+
+impl std::fmt::Debug for VerifiableShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VerifiableShare")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Feldman commitments `C_j = a_j * G` to the dealer's polynomial.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeldmanCommitments(Vec<ProjectivePoint>);
+
+impl FeldmanCommitments {
+    /// Number of shares needed to recover the secret.
+    pub fn threshold(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Public key of the shared secret (`C_0`).
+    pub fn public_key(&self) -> Result<Secp256k1PublicKey, CryptoError> {
+        let bytes: [u8; 33] = self.0[0]
+            .to_bytes()
+            .as_slice()
+            .try_into()
+            .map_err(|_| CryptoError::InvalidPublicKey)?;
+        Secp256k1PublicKey::from_bytes(bytes)
+    }
+
+    /// Check a share against the commitments: `s_i * G == sum C_j * i^j`.
+    pub fn verify(&self, share: &VerifiableShare) -> bool {
+        let x = Scalar::from(u64::from(share.index));
+        let expected = self
+            .0
+            .iter()
+            .rev()
+            .fold(ProjectivePoint::IDENTITY, |acc, c| acc * x + c);
+        ProjectivePoint::GENERATOR * share.scalar() == expected
+    }
+
+    /// Encode as concatenated 33-byte compressed points.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.iter().flat_map(|p| p.to_bytes()).collect()
+    }
+
+    /// Decode from `to_bytes` output.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+        if bytes.is_empty() || bytes.len() % 33 != 0 {
+            return Err(CryptoError::InvalidShares("malformed commitments".into()));
+        }
+        bytes
+            .chunks_exact(33)
+            .map(|chunk| {
+                let encoded = k256::CompressedPoint::clone_from_slice(chunk);
+                Option::<ProjectivePoint>::from(ProjectivePoint::from_bytes(&encoded))
+                    .ok_or(CryptoError::InvalidPublicKey)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+/// Split a secp256k1 secret key into `count` verifiable shares.
+pub fn split_verifiable(
+    secret: &[u8; 32],
+    threshold: usize,
+    count: usize,
+) -> Result<(Vec<VerifiableShare>, FeldmanCommitments), CryptoError> {
+    check_parameters(threshold, count, u32::MAX as usize)?;
+    let secret = scalar_from_bytes(secret)
+        .filter(|s| !bool::from(s.is_zero()))
+        .ok_or(CryptoError::InvalidPrivateKey)?;
+
+    let mut rng = rand::thread_rng();
+    let mut coefficients: Vec<Scalar> = std::iter::once(secret)
+        .chain((1..threshold).map(|_| Scalar::random(&mut rng)))
+        .collect();
+    let commitments = coefficients
+        .iter()
+        .map(|a| ProjectivePoint::GENERATOR * a)
+        .collect();
+
+    let shares = (1..=count as u32)
+        .map(|index| {
+            let x = Scalar::from(u64::from(index));
+            let y = coefficients
+                .iter()
+                .rev()
+                .fold(Scalar::ZERO, |acc, a| acc * x + a);
+            VerifiableShare {
+                index,
+                value: Zeroizing::new(y.to_bytes().into()),
+            }
+        })
+        .collect();
+    coefficients.zeroize();
+    Ok((shares, FeldmanCommitments(commitments)))
+}
+
+/// Recover a secp256k1 secret key from at least `threshold` shares.
+pub fn combine_verifiable(shares: &[VerifiableShare]) -> Result<Zeroizing<[u8; 32]>, CryptoError> {
+    if shares.is_empty() {
+        return Err(CryptoError::InvalidShares("no shares".into()));
+    }
+    let mut seen = HashSet::new();
+    if !shares.iter().all(|s| s.index != 0 && seen.insert(s.index)) {
+        return Err(CryptoError::InvalidShares("duplicate or zero index".into()));
+    }
+
+    let mut secret = Scalar::ZERO;
+    for si in shares {
+        let xi = Scalar::from(u64::from(si.index));
+        let (num, den) = shares.iter().filter(|sj| sj.index != si.index).fold(
+            (Scalar::ONE, Scalar::ONE),
+            |(num, den), sj| {
+                let xj = Scalar::from(u64::from(sj.index));
+                (num * xj, den * (xj - xi))
+            },
+        );
+        let den_inv = Option::<Scalar>::from(den.invert())
+            .ok_or_else(|| CryptoError::InvalidShares("degenerate share indices".into()))?;
+        secret += si.scalar() * num * den_inv;
+    }
+    let bytes = Zeroizing::new(secret.to_bytes().into());
+    secret.zeroize();
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Secp256k1KeyPair;
+
+    #[test]
+    fn test_gf256_arithmetic() {
+        // FIPS-197 section 4.2 example
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn test_split_combine_any_subset() {
+        let secret = b"node hmac master secret material";
+        let shares = split(secret, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        for subset in [[0, 1, 2], [0, 2, 4], [4, 3, 1]] {
+            let picked: Vec<_> = subset.iter().map(|&i| shares[i].clone()).collect();
+            assert_eq!(combine(&picked).unwrap().as_slice(), secret);
+        }
+        assert_eq!(combine(&shares).unwrap().as_slice(), secret);
+        assert_ne!(combine(&shares[..2]).unwrap().as_slice(), secret);
+
+        let decoded = Share::from_bytes(&shares[0].to_bytes()).unwrap();
+        assert_eq!(decoded, shares[0]);
+    }
+
+    #[test]
+    fn test_split_rejects_bad_parameters() {
+        assert!(split(b"s", 0, 3).is_err());
+        assert!(split(b"s", 4, 3).is_err());
+        assert!(split(b"", 2, 3).is_err());
+
+        let shares = split(b"secret", 2, 3).unwrap();
+        assert!(combine(&[shares[0].clone(), shares[0].clone()]).is_err());
+        assert!(combine(&[]).is_err());
+        assert!(Share::from_bytes(&[0, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_feldman_split_verify_combine() {
+        let keypair = Secp256k1KeyPair::generate();
+        let (shares, commitments) = split_verifiable(&keypair.to_bytes(), 3, 5).unwrap();
+
+        assert_eq!(commitments.threshold(), 3);
+        assert_eq!(commitments.public_key().unwrap(), keypair.public_key());
+        assert!(shares.iter().all(|s| commitments.verify(s)));
+
+        let recovered = combine_verifiable(&shares[1..4]).unwrap();
+        assert_eq!(*recovered, keypair.to_bytes());
+
+        // Commitments survive encoding and still verify
+        let decoded = FeldmanCommitments::from_bytes(&commitments.to_bytes()).unwrap();
+        assert_eq!(decoded, commitments);
+        let share = VerifiableShare::from_bytes(&shares[0].to_bytes()).unwrap();
+        assert!(decoded.verify(&share));
+    }
+
+    #[test]
+    fn test_feldman_detects_tampered_share() {
+        let keypair = Secp256k1KeyPair::generate();
+        let (shares, commitments) = split_verifiable(&keypair.to_bytes(), 2, 3).unwrap();
+
+        let mut tampered = shares[0].clone();
+        tampered.value[31] ^= 1;
+        assert!(!commitments.verify(&tampered));
+
+        let mut moved = shares[1].clone();
+        moved.index = 3;
+        assert!(!commitments.verify(&moved));
+
+        assert!(split_verifiable(&[0u8; 32], 2, 3).is_err());
+        assert!(split_verifiable(&[0xffu8; 32], 2, 3).is_err());
+    }
+}
@@ -0,0 +1,606 @@
+//! # Canonical Binary Codec
+//!
+//! One byte-stable encoding for chain entities and IPC payloads, so block
+//! hashes and network messages agree across subsystems regardless of which
+//! serde format an adapter happens to use.
+//!
+//! ## Format
+//!
+//! ```text
+//! CODEC_VERSION (1 byte) || RLP(value)
+//! ```
+//!
+//! - Structs are RLP lists of their fields in declaration order
+//! - Integers use minimal big-endian RLP encoding (`U256` included)
+//! - Fixed-size arrays (hashes, keys, signatures) and `Vec<u8>` are byte strings
+//! - `Option<T>` is a list of zero or one items
+//! - Fieldless enums are their discriminant as an integer
+//!
+//! Decoding is strict: the input must be exactly the canonical encoding of
+//! the decoded value (no trailing bytes, no non-minimal integers), so every
+//! value has one encoding and `canonical_hash` is well defined.
+//!
+//! Adding, removing or reordering a field changes the format and requires
+//! bumping `CODEC_VERSION`; the golden vectors in the tests guard this.
+
+use crate::entities::{
+    AccountState, Attestation, BlockHeader, CoinbaseTransaction, ConsensusProof, FinalityProof,
+    GenesisConfig, Hash, NodeId, PeerInfo, PeerList, SignedTransaction, StorageMetadata,
+    StoredBlock, Transaction, TransactionType, ValidatedBlock, ValidatedTransaction, Validator,
+};
+use crate::errors::CodecError;
+use crate::ipc::{
+    BlockStorageConfirmationPayload, BlockStoredPayload, BlockValidatedPayload,
+    MarkFinalizedPayload, MerkleRootComputedPayload, PeerListRequestPayload,
+    PeerListResponsePayload, ProposeTransactionBatchPayload, ReadBlockRangeRequestPayload,
+    ReadBlockRequestPayload, ReadBlockResponsePayload, StateRootComputedPayload,
+    StorageCriticalError, StorageCriticalPayload, VerifyNodeIdentityPayload,
+    VerifyNodeIdentityResponse, VerifySignatureRequestPayload, VerifySignatureResponsePayload,
+};
+use primitive_types::U256;
+use rlp::{Rlp, RlpStream};
+use sha2::{Digest, Sha256};
+
+/// Current canonical codec version (first byte of every encoding).
+pub const CODEC_VERSION: u8 = 1;
+
+/// A type with a canonical binary encoding.
+pub trait CanonicalEncode: Sized {
+    /// Append this value to an RLP stream.
+    fn encode_to(&self, stream: &mut RlpStream);
+
+    /// Decode this value from an RLP item.
+    fn decode_from(rlp: &Rlp<'_>) -> Result<Self, CodecError>;
+}
+
+/// Marker for types encoded as RLP lists (structs), so `Vec<T>` of them is
+/// a list of lists while `Vec<u8>` stays a byte string.
+pub trait CanonicalRecord: CanonicalEncode {}
+
+/// Encode a value canonically (version byte + RLP).
+pub fn encode<T: CanonicalEncode>(value: &T) -> Vec<u8> {
+    let mut stream = RlpStream::new();
+    value.encode_to(&mut stream);
+    let body = stream.out();
+    let mut out = Vec::with_capacity(1 + body.len());
+    out.push(CODEC_VERSION);
+    out.extend_from_slice(&body);
+    out
+}
+
+/// Decode a canonically encoded value, rejecting any non-canonical input.
+pub fn decode<T: CanonicalEncode>(bytes: &[u8]) -> Result<T, CodecError> {
+    let (&version, body) = bytes.split_first().ok_or(CodecError::Empty)?;
+    if version != CODEC_VERSION {
+        return Err(CodecError::UnsupportedVersion(version));
+    }
+    let rlp = Rlp::new(body);
+    let value = T::decode_from(&rlp)?;
+    // Re-encoding must reproduce the input exactly: rejects trailing bytes
+    // and every non-minimal length or integer form.
+    if encode(&value) != bytes {
+        return Err(CodecError::NonCanonical);
+    }
+    Ok(value)
+}
+
+/// SHA-256 of the canonical encoding.
+pub fn canonical_hash<T: CanonicalEncode>(value: &T) -> Hash {
+    Sha256::digest(encode(value)).into()
+}
+
+// =============================================================================
+// PRIMITIVES
+// =============================================================================
+
+fn data<'a>(rlp: &Rlp<'a>) -> Result<&'a [u8], CodecError> {
+    if !rlp.is_data() {
+        return Err(CodecError::Malformed("expected byte string".into()));
+    }
+    Ok(rlp.data()?)
+}
+
+fn list_len(rlp: &Rlp<'_>) -> Result<usize, CodecError> {
+    if !rlp.is_list() {
+        return Err(CodecError::Malformed("expected list".into()));
+    }
+    Ok(rlp.item_count()?)
+}
+
+macro_rules! canonical_rlp_value {
+    ($($ty:ty),*) => {$(
+        impl CanonicalEncode for $ty {
+            fn encode_to(&self, stream: &mut RlpStream) {
+                stream.append(self);
+            }
+
+            fn decode_from(rlp: &Rlp<'_>) -> Result<Self, CodecError> {
+                Ok(rlp.as_val()?)
+            }
+        }
+    )*};
+}
+
+canonical_rlp_value!(u8, u16, u32, u64, u128, bool, String);
+
+impl CanonicalEncode for U256 {
+    fn encode_to(&self, stream: &mut RlpStream) {
+        let mut bytes = [0u8; 32];
+        self.to_big_endian(&mut bytes);
+        let start = bytes.iter().position(|&b| b != 0).unwrap_or(32);
+        stream.append(&&bytes[start..]);
+    }
+
+    fn decode_from(rlp: &Rlp<'_>) -> Result<Self, CodecError> {
+        let bytes = data(rlp)?;
+        if bytes.len() > 32 || bytes.first() == Some(&0) {
+            return Err(CodecError::Malformed("invalid U256".into()));
+        }
+        Ok(U256::from_big_endian(bytes))
+    }
+}
+
+impl<const N: usize> CanonicalEncode for [u8; N] {
+    fn encode_to(&self, stream: &mut RlpStream) {
+        stream.append(&self.as_slice());
+    }
+
+    fn decode_from(rlp: &Rlp<'_>) -> Result<Self, CodecError> {
+        data(rlp)?
+            .try_into()
+            .map_err(|_| CodecError::Malformed(format!("expected {N} bytes")))
+    }
+}
+
+impl CanonicalEncode for Vec<u8> {
+    fn encode_to(&self, stream: &mut RlpStream) {
+        stream.append(&self.as_slice());
+    }
+
+    fn decode_from(rlp: &Rlp<'_>) -> Result<Self, CodecError> {
+        Ok(data(rlp)?.to_vec())
+    }
+}
+
+impl<T: CanonicalRecord> CanonicalEncode for Vec<T> {
+    fn encode_to(&self, stream: &mut RlpStream) {
+        stream.begin_list(self.len());
+        for item in self {
+            item.encode_to(stream);
+        }
+    }
+
+    fn decode_from(rlp: &Rlp<'_>) -> Result<Self, CodecError> {
+        list_len(rlp)?;
+        rlp.iter().map(|item| T::decode_from(&item)).collect()
+    }
+}
+
+/// Lists of hashes (e.g. included transaction hashes).
+impl CanonicalRecord for Hash {}
+
+impl<T: CanonicalEncode> CanonicalEncode for Option<T> {
+    fn encode_to(&self, stream: &mut RlpStream) {
+        match self {
+            Some(value) => {
+                stream.begin_list(1);
+                value.encode_to(stream);
+            }
+            None => {
+                stream.begin_list(0);
+            }
+        }
+    }
+
+    fn decode_from(rlp: &Rlp<'_>) -> Result<Self, CodecError> {
+        match list_len(rlp)? {
+            0 => Ok(None),
+            1 => T::decode_from(&rlp.at(0)?).map(Some),
+            n => Err(CodecError::Malformed(format!("option with {n} items"))),
+        }
+    }
+}
+
+impl<A: CanonicalEncode, B: CanonicalEncode> CanonicalEncode for (A, B) {
+    fn encode_to(&self, stream: &mut RlpStream) {
+        stream.begin_list(2);
+        self.0.encode_to(stream);
+        self.1.encode_to(stream);
+    }
+
+    fn decode_from(rlp: &Rlp<'_>) -> Result<Self, CodecError> {
+        let mut fields = RecordReader::new(rlp, 2)?;
+        Ok((fields.next()?, fields.next()?))
+    }
+}
+
+impl<A: CanonicalEncode, B: CanonicalEncode> CanonicalRecord for (A, B) {}
+
+impl CanonicalEncode for NodeId {
+    fn encode_to(&self, stream: &mut RlpStream) {
+        self.0.encode_to(stream);
+    }
+
+    fn decode_from(rlp: &Rlp<'_>) -> Result<Self, CodecError> {
+        CanonicalEncode::decode_from(rlp).map(NodeId)
+    }
+}
+
+// =============================================================================
+// RECORDS
+// =============================================================================
+
+/// Sequential field reader for a struct's RLP list.
+struct RecordReader<'a> {
+    rlp: Rlp<'a>,
+    index: usize,
+}
+
+impl<'a> RecordReader<'a> {
+    fn new(rlp: &Rlp<'a>, fields: usize) -> Result<Self, CodecError> {
+        let count = list_len(rlp)?;
+        if count != fields {
+            return Err(CodecError::Malformed(format!(
+                "expected {fields} fields, found {count}"
+            )));
+        }
+        Ok(Self {
+            rlp: rlp.clone(),
+            index: 0,
+        })
+    }
+
+    fn next<T: CanonicalEncode>(&mut self) -> Result<T, CodecError> {
+        let item = self.rlp.at(self.index)?;
+        self.index += 1;
+        T::decode_from(&item)
+    }
+}
+
+/// Encode a struct as the RLP list of the given fields, in order.
+macro_rules! canonical_record {
+    ($ty:ident { $($field:ident),* $(,)? }) => {
+        impl CanonicalEncode for $ty {
+            fn encode_to(&self, stream: &mut RlpStream) {
+                stream.begin_list(canonical_record!(@count $($field)*));
+                $(self.$field.encode_to(stream);)*
+            }
+
+            fn decode_from(rlp: &Rlp<'_>) -> Result<Self, CodecError> {
+                let mut _fields = RecordReader::new(rlp, canonical_record!(@count $($field)*))?;
+                Ok(Self { $($field: _fields.next()?,)* })
+            }
+        }
+
+        impl CanonicalRecord for $ty {}
+    };
+    (@count) => { 0usize };
+    (@count $head:ident $($tail:ident)*) => { 1usize + canonical_record!(@count $($tail)*) };
+}
+
+/// Encode a fieldless enum as its discriminant.
+macro_rules! canonical_enum {
+    ($ty:ident { $($variant:ident = $tag:literal),* $(,)? }) => {
+        impl CanonicalEncode for $ty {
+            fn encode_to(&self, stream: &mut RlpStream) {
+                let tag: u8 = match self {
+                    $($ty::$variant => $tag,)*
+                };
+                stream.append(&tag);
+            }
+
+            fn decode_from(rlp: &Rlp<'_>) -> Result<Self, CodecError> {
+                match rlp.as_val::<u8>()? {
+                    $($tag => Ok($ty::$variant),)*
+                    tag => Err(CodecError::Malformed(format!(
+                        concat!("unknown ", stringify!($ty), " tag {}"),
+                        tag
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+// Chain
+canonical_record!(BlockHeader {
+    version,
+    height,
+    parent_hash,
+    merkle_root,
+    state_root,
+    timestamp,
+    proposer,
+    difficulty,
+    nonce,
+});
+canonical_record!(ValidatedBlock {
+    header,
+    transactions,
+    consensus_proof,
+});
+canonical_record!(Transaction {
+    from,
+    to,
+    value,
+    nonce,
+    data,
+    signature,
+});
+canonical_record!(SignedTransaction {
+    from,
+    to,
+    value,
+    nonce,
+    gas_price,
+    gas_limit,
+    data,
+    signature,
+});
+canonical_record!(ValidatedTransaction { inner, tx_hash });
+canonical_enum!(TransactionType {
+    Regular = 0,
+    Genesis = 1,
+    Coinbase = 2,
+});
+canonical_record!(CoinbaseTransaction {
+    block_height,
+    miner_address,
+    reward,
+    fees,
+    timestamp,
+});
+
+// Consensus & finality
+canonical_record!(Validator {
+    public_key,
+    stake,
+    active,
+});
+canonical_record!(Attestation {
+    block_hash,
+    epoch,
+    validator,
+    signature,
+});
+canonical_record!(ConsensusProof {
+    block_hash,
+    attestations,
+    total_stake,
+});
+canonical_record!(FinalityProof {
+    checkpoint_hash,
+    epoch,
+    attestations,
+    total_stake,
+    required_stake,
+});
+
+// State & storage
+canonical_record!(AccountState {
+    balance,
+    nonce,
+    code_hash,
+    storage_root,
+});
+canonical_record!(StoredBlock { block, checksum });
+canonical_record!(StorageMetadata {
+    genesis_hash,
+    finalized_height,
+    chain_tip_height,
+});
+canonical_record!(GenesisConfig {
+    chain_id,
+    timestamp,
+    allocations,
+    validators,
+    total_supply,
+});
+
+// Networking
+canonical_record!(PeerInfo {
+    node_id,
+    address,
+    reputation,
+    last_seen,
+    protocol_version,
+});
+canonical_record!(PeerList { peers });
+
+// IPC payloads
+canonical_record!(VerifyNodeIdentityPayload {
+    node_id,
+    public_key,
+    signature,
+});
+canonical_record!(VerifyNodeIdentityResponse { valid, reason });
+canonical_record!(PeerListRequestPayload {
+    max_peers,
+    min_reputation,
+});
+canonical_record!(PeerListResponsePayload { peers });
+canonical_record!(BlockValidatedPayload { block });
+canonical_record!(MerkleRootComputedPayload {
+    block_hash,
+    merkle_root,
+});
+canonical_record!(StateRootComputedPayload {
+    block_hash,
+    state_root,
+});
+canonical_record!(BlockStoredPayload {
+    block_height,
+    block_hash,
+});
+canonical_record!(ReadBlockRequestPayload { block_hash });
+canonical_record!(ReadBlockRangeRequestPayload {
+    start_height,
+    limit,
+});
+canonical_record!(ReadBlockResponsePayload { block });
+canonical_record!(MarkFinalizedPayload {
+    block_height,
+    proof
+});
+canonical_record!(ProposeTransactionBatchPayload { transactions });
+canonical_record!(BlockStorageConfirmationPayload {
+    block_hash,
+    included_transactions,
+});
+canonical_record!(VerifySignatureRequestPayload {
+    public_key,
+    message,
+    signature,
+});
+canonical_record!(VerifySignatureResponsePayload { valid });
+canonical_enum!(StorageCriticalError {
+    DataCorruption = 0,
+    DiskFull = 1,
+    WriteFailed = 2,
+    ParentNotFound = 3,
+});
+canonical_record!(StorageCriticalPayload {
+    error_type,
+    block_hash,
+    description,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn header() -> BlockHeader {
+        BlockHeader {
+            version: 1,
+            height: 42,
+            parent_hash: [0x11; 32],
+            merkle_root: [0x22; 32],
+            state_root: [0x33; 32],
+            timestamp: 1_700_000_000,
+            proposer: [0x44; 32],
+            difficulty: U256::from(0x1d00_ffffu64),
+            nonce: 7,
+        }
+    }
+
+    fn transaction() -> Transaction {
+        Transaction {
+            from: [0xaa; 32],
+            to: None,
+            value: 1_000,
+            nonce: 3,
+            data: vec![0xde, 0xad],
+            signature: [0x55; 64],
+        }
+    }
+
+    #[test]
+    fn test_golden_block_header() {
+        let bytes = encode(&header());
+        assert_eq!(to_hex(&bytes), GOLDEN_HEADER);
+        assert_eq!(to_hex(&canonical_hash(&header())), GOLDEN_HEADER_HASH);
+        let decoded: BlockHeader = decode(&bytes).unwrap();
+        assert_eq!(encode(&decoded), bytes);
+    }
+
+    #[test]
+    fn test_golden_transaction() {
+        let bytes = encode(&transaction());
+        assert_eq!(to_hex(&bytes), GOLDEN_TRANSACTION);
+        let decoded: Transaction = decode(&bytes).unwrap();
+        assert_eq!(decoded.to, None);
+        assert_eq!(decoded.data, vec![0xde, 0xad]);
+    }
+
+    #[test]
+    fn test_golden_ipc_payload() {
+        let payload = BlockStorageConfirmationPayload {
+            block_hash: [0x01; 32],
+            included_transactions: vec![[0x02; 32], [0x03; 32]],
+        };
+        let bytes = encode(&payload);
+        assert_eq!(to_hex(&bytes), GOLDEN_CONFIRMATION);
+        let decoded: BlockStorageConfirmationPayload = decode(&bytes).unwrap();
+        assert_eq!(decoded.included_transactions, payload.included_transactions);
+    }
+
+    #[test]
+    fn test_block_roundtrip() {
+        let block = ValidatedBlock {
+            header: header(),
+            transactions: vec![ValidatedTransaction {
+                tx_hash: [0x66; 32],
+                inner: Transaction {
+                    to: Some([0xbb; 32]),
+                    ..transaction()
+                },
+            }],
+            consensus_proof: ConsensusProof {
+                block_hash: [0x77; 32],
+                attestations: vec![Attestation {
+                    block_hash: [0x77; 32],
+                    epoch: 9,
+                    validator: [0x88; 32],
+                    signature: [0x99; 64],
+                }],
+                total_stake: 100,
+            },
+        };
+        let stored = StoredBlock {
+            block,
+            checksum: 0xdead_beef,
+        };
+        let bytes = encode(&stored);
+        let decoded: StoredBlock = decode(&bytes).unwrap();
+        assert_eq!(encode(&decoded), bytes);
+        assert_eq!(decoded.block.transactions[0].inner.to, Some([0xbb; 32]));
+
+        let response = ReadBlockResponsePayload {
+            block: Some(stored),
+        };
+        let decoded: ReadBlockResponsePayload = decode(&encode(&response)).unwrap();
+        assert_eq!(decoded.block.unwrap().checksum, 0xdead_beef);
+    }
+
+    #[test]
+    fn test_rejects_non_canonical_input() {
+        let bytes = encode(&header());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(decode::<BlockHeader>(&trailing).is_err());
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = 2;
+        assert_eq!(
+            decode::<BlockHeader>(&wrong_version).unwrap_err(),
+            CodecError::UnsupportedVersion(2)
+        );
+        assert_eq!(decode::<BlockHeader>(&[]).unwrap_err(), CodecError::Empty);
+
+        // u64 7 written with a leading zero byte (0x82 0x00 0x07)
+        let mut payload = RlpStream::new_list(2);
+        payload.append_raw(&[0x82, 0x00, 0x07], 1).append(&10u64);
+        let mut non_minimal = vec![CODEC_VERSION];
+        non_minimal.extend_from_slice(&payload.out());
+        assert!(decode::<ReadBlockRangeRequestPayload>(&non_minimal).is_err());
+
+        // Wrong field count
+        assert!(
+            decode::<BlockStoredPayload>(&encode(&ReadBlockRequestPayload {
+                block_hash: [0; 32]
+            }))
+            .is_err()
+        );
+    }
+
+    const GOLDEN_HEADER: &str = "01f891012aa01111111111111111111111111111111111111111111111111111111111111111a02222222222222222222222222222222222222222222222222222222222222222a03333333333333333333333333333333333333333333333333333333333333333846553f100a04444444444444444444444444444444444444444444444444444444444444444841d00ffff07";
+    const GOLDEN_HEADER_HASH: &str =
+        "562930a935f3f5a5669d9d4a70b0073671588d03fb306b760edc7f707c8054a3";
+    const GOLDEN_TRANSACTION: &str = "01f86ba0aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaac08203e80382deadb84055555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555555";
+    const GOLDEN_CONFIRMATION: &str = "01f865a00101010101010101010101010101010101010101010101010101010101010101f842a00202020202020202020202020202020202020202020202020202020202020202a00303030303030303030303030303030303030303030303030303030303030303";
+}
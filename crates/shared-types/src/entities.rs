@@ -275,6 +275,54 @@ pub struct CoinbaseTransaction {
     pub timestamp: u64,
 }
 
+impl CoinbaseTransaction {
+    /// Newly minted part of the reward (reward minus collected fees)
+    pub fn subsidy(&self) -> U256 {
+        self.reward.saturating_sub(self.fees)
+    }
+}
+
+/// Block subsidy schedule shared by block production and consensus
+///
+/// The subsidy starts at `initial_coins` whole coins and halves (rounding
+/// down to whole coins) every `halving_interval` blocks until it is zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardSchedule {
+    /// Subsidy of the first block, in whole coins
+    pub initial_coins: u64,
+    /// Decimal places of a coin (base units per coin = 10^decimals)
+    pub decimals: u32,
+    /// Blocks between halvings
+    pub halving_interval: u64,
+}
+
+impl Default for RewardSchedule {
+    /// 50 coins with 8 decimals, halving every 210,000 blocks
+    fn default() -> Self {
+        Self {
+            initial_coins: 50,
+            decimals: 8,
+            halving_interval: 210_000,
+        }
+    }
+}
+
+impl RewardSchedule {
+    /// Subsidy minted by the block at `height`, in base units
+    pub fn subsidy_at(&self, height: u64) -> U256 {
+        let halvings = height / self.halving_interval.max(1);
+        if halvings >= 64 {
+            return U256::zero();
+        }
+        U256::from(self.initial_coins >> halvings) * U256::from(10u64).pow(self.decimals.into())
+    }
+
+    /// Largest coinbase reward allowed at `height` given the collected fees
+    pub fn max_reward(&self, height: u64, fees: U256) -> U256 {
+        self.subsidy_at(height).saturating_add(fees)
+    }
+}
+
 // =============================================================================
 // CLUSTER B: CONSENSUS & FINALITY
 // =============================================================================
@@ -69,8 +69,9 @@ pub struct AuthenticatedMessage<T> {
     /// Nonces are garbage-collected after the timestamp expires.
     pub nonce: Uuid,
 
-    /// Ed25519 signature over the serialized header + payload.
-    /// Verified using the sender's public key.
+    /// Signature over the serialized header + payload (signature zeroed).
+    /// Either HMAC-SHA256 (first 32 bytes, rest zero) or Ed25519 under the
+    /// sender's public key, as accepted by the verifier's `EnvelopeAuthMode`.
     #[serde_as(as = "Bytes")]
     pub signature: [u8; 64],
 
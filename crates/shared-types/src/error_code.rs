@@ -0,0 +1,247 @@
+//! # Error Taxonomy
+//!
+//! Stable, machine-readable error codes shared by all subsystems, so a
+//! caller can decide whether to retry, back off, or alert without parsing
+//! per-crate error messages.
+//!
+//! ## Code Ranges
+//!
+//! | Range | Class | Caller action |
+//! |-------|-------|---------------|
+//! | 1xxx | Request errors | Fix the request; never retry |
+//! | 2xxx | Transient errors | Retry, usually with backoff |
+//! | 3xxx | Internal errors | Alert an operator |
+//!
+//! `ErrorInfo` carries a code together with its origin subsystem. It is
+//! sent inside IPC envelopes and surfaced unchanged as the `data` member of
+//! API gateway JSON-RPC errors.
+
+use crate::entities::SubsystemId;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Shared error code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[repr(u16)]
+pub enum ErrorCode {
+    // Request errors
+    /// Malformed or invalid request parameters.
+    InvalidRequest = 1000,
+    /// Signature missing or invalid.
+    InvalidSignature = 1001,
+    /// Sender not authorized for this operation.
+    Unauthorized = 1002,
+    /// Requested resource does not exist.
+    NotFound = 1003,
+    /// Request conflicts with current state (e.g. nonce too low, duplicate).
+    Conflict = 1004,
+    /// Request was valid but rejected by policy.
+    Rejected = 1005,
+
+    // Transient errors
+    /// Operation timed out.
+    Timeout = 2000,
+    /// Target subsystem is unavailable.
+    Unavailable = 2001,
+    /// Caller exceeded a rate limit.
+    RateLimited = 2002,
+    /// Node is still syncing.
+    Syncing = 2003,
+    /// Queue or resource limit reached.
+    Overloaded = 2004,
+
+    // Internal errors
+    /// Unexpected internal failure.
+    Internal = 3000,
+    /// Invalid node configuration.
+    ConfigurationError = 3001,
+    /// Storage read/write failure.
+    StorageFailure = 3002,
+    /// Persisted data failed an integrity check.
+    DataCorruption = 3003,
+    /// A protocol invariant was violated.
+    InvariantViolation = 3004,
+}
+
+/// How serious an error is for operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Expected during normal operation (bad client input).
+    Info,
+    /// Degraded but self-healing.
+    Warning,
+    /// Needs investigation.
+    Error,
+    /// Needs immediate operator action.
+    Critical,
+}
+
+/// Whether and how a caller should retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Retry {
+    /// Retrying the same request cannot succeed.
+    Never,
+    /// Retry right away.
+    Immediately,
+    /// Retry with exponential backoff.
+    Backoff,
+}
+
+impl ErrorCode {
+    /// Numeric code.
+    pub fn as_u16(self) -> u16 {
+        self as u16
+    }
+
+    /// Default severity for this code.
+    pub fn severity(self) -> Severity {
+        match self.as_u16() {
+            1000..=1999 => Severity::Info,
+            2000..=2999 => Severity::Warning,
+            _ => match self {
+                Self::DataCorruption | Self::InvariantViolation => Severity::Critical,
+                _ => Severity::Error,
+            },
+        }
+    }
+
+    /// Default retry policy for this code.
+    pub fn retry(self) -> Retry {
+        match self {
+            Self::Timeout => Retry::Immediately,
+            Self::Unavailable | Self::RateLimited | Self::Syncing | Self::Overloaded => {
+                Retry::Backoff
+            }
+            _ => Retry::Never,
+        }
+    }
+
+    /// Closest JSON-RPC error code (SPEC-16 Section 9).
+    pub fn json_rpc_code(self) -> i32 {
+        match self {
+            Self::InvalidRequest => -32602,
+            Self::Unauthorized => -32010,
+            Self::NotFound => -32001,
+            Self::InvalidSignature | Self::Conflict | Self::Rejected => -32003,
+            Self::Timeout => -32006,
+            Self::Unavailable | Self::Syncing => -32002,
+            Self::RateLimited => -32029,
+            Self::Overloaded => -32005,
+            Self::Internal
+            | Self::ConfigurationError
+            | Self::StorageFailure
+            | Self::DataCorruption
+            | Self::InvariantViolation => -32603,
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "QC-{}", self.as_u16())
+    }
+}
+
+/// A coded error with its origin, as carried in IPC envelopes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorInfo {
+    /// Error code.
+    pub code: ErrorCode,
+    /// Severity (defaults to the code's severity).
+    pub severity: Severity,
+    /// Retry policy (defaults to the code's policy).
+    pub retry: Retry,
+    /// Subsystem where the error originated.
+    pub origin: SubsystemId,
+    /// Human-readable message.
+    pub message: String,
+}
+
+impl ErrorInfo {
+    /// Create an error with the code's default severity and retry policy.
+    pub fn new(code: ErrorCode, origin: SubsystemId, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            severity: code.severity(),
+            retry: code.retry(),
+            origin,
+            message: message.into(),
+        }
+    }
+
+    /// Override the severity.
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Override the retry policy.
+    pub fn with_retry(mut self, retry: Retry) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Whether the caller may retry.
+    pub fn is_retryable(&self) -> bool {
+        self.retry != Retry::Never
+    }
+}
+
+impl fmt::Display for ErrorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} from {:?}] {}", self.code, self.origin, self.message)
+    }
+}
+
+impl std::error::Error for ErrorInfo {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_classes() {
+        assert_eq!(ErrorCode::NotFound.severity(), Severity::Info);
+        assert_eq!(ErrorCode::NotFound.retry(), Retry::Never);
+        assert_eq!(ErrorCode::RateLimited.retry(), Retry::Backoff);
+        assert_eq!(ErrorCode::Timeout.severity(), Severity::Warning);
+        assert_eq!(ErrorCode::DataCorruption.severity(), Severity::Critical);
+        assert_eq!(ErrorCode::StorageFailure.severity(), Severity::Error);
+        assert_eq!(ErrorCode::Internal.retry(), Retry::Never);
+    }
+
+    #[test]
+    fn test_error_info_wire_format() {
+        let info = ErrorInfo::new(
+            ErrorCode::Unavailable,
+            SubsystemId::BlockStorage,
+            "database locked",
+        );
+        assert!(info.is_retryable());
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "code": "UNAVAILABLE",
+                "severity": "warning",
+                "retry": "backoff",
+                "origin": "BlockStorage",
+                "message": "database locked",
+            })
+        );
+        assert_eq!(serde_json::from_value::<ErrorInfo>(json).unwrap(), info);
+
+        let fatal = info
+            .with_retry(Retry::Never)
+            .with_severity(Severity::Critical);
+        assert!(!fatal.is_retryable());
+        assert_eq!(
+            fatal.to_string(),
+            "[QC-2001 from BlockStorage] database locked"
+        );
+    }
+}
@@ -56,6 +56,32 @@ pub enum MessageError {
     Unauthorized { sender: u8, message_type: String },
 }
 
+/// Errors from the canonical binary codec.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CodecError {
+    /// Input was empty (no version byte).
+    #[error("Empty input")]
+    Empty,
+
+    /// Encoding version not supported by this build.
+    #[error("Unsupported codec version: {0}")]
+    UnsupportedVersion(u8),
+
+    /// Structurally invalid encoding.
+    #[error("Malformed encoding: {0}")]
+    Malformed(String),
+
+    /// Valid RLP, but not the canonical encoding of the decoded value.
+    #[error("Non-canonical encoding")]
+    NonCanonical,
+}
+
+impl From<rlp::DecoderError> for CodecError {
+    fn from(err: rlp::DecoderError) -> Self {
+        Self::Malformed(err.to_string())
+    }
+}
+
 /// Node operational states.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NodeState {
@@ -0,0 +1,286 @@
+//! # Typed IPC Authorization Matrix
+//!
+//! Declarative form of the sender allowlists in IPC-MATRIX.md: each message
+//! type maps to the subsystems allowed to send it and the subsystems that
+//! accept it.
+//!
+//! ## Usage
+//!
+//! Message types opt in by implementing `IpcMessage`, which binds the type
+//! to its matrix entry at compile time. Handlers then call
+//! `check_authorized::<M>(sender_id)` instead of comparing against
+//! hand-written constants:
+//!
+//! ```rust,ignore
+//! impl IpcMessage for AttestationBatch {
+//!     const AUTHORIZATION: MatrixEntry = ipc_matrix::ATTESTATION_BATCH;
+//! }
+//!
+//! check_authorized::<AttestationBatch>(message.sender_id)?;
+//! ```
+//!
+//! The tests below re-derive the allowlists from the "Security Boundaries"
+//! sections of IPC-MATRIX.md and fail if this table drifts from the spec.
+
+use crate::entities::SubsystemId;
+use crate::errors::MessageError;
+use crate::ipc::{
+    BlockStorageConfirmationPayload, BlockValidatedPayload, MarkFinalizedPayload,
+    MerkleRootComputedPayload, PeerListRequestPayload, ProposeTransactionBatchPayload,
+    StateRootComputedPayload, VerifyNodeIdentityPayload, VerifyNodeIdentityResponse,
+    VerifySignatureRequestPayload,
+};
+
+/// Authorization rule for one message type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatrixEntry {
+    /// Message type name as used in IPC-MATRIX.md
+    pub message_type: &'static str,
+    /// Subsystems allowed to send this message
+    pub senders: &'static [SubsystemId],
+    /// Subsystems that accept this message
+    pub recipients: &'static [SubsystemId],
+}
+
+impl MatrixEntry {
+    /// Whether `sender_id` may send this message.
+    pub fn permits(&self, sender_id: u8) -> bool {
+        self.senders.iter().any(|s| s.as_u8() == sender_id)
+    }
+
+    /// Whether `recipient_id` accepts this message.
+    pub fn delivers_to(&self, recipient_id: u8) -> bool {
+        self.recipients.iter().any(|r| r.as_u8() == recipient_id)
+    }
+}
+
+/// A message type with a compile-time authorization rule.
+pub trait IpcMessage {
+    /// The matrix entry governing this message.
+    const AUTHORIZATION: MatrixEntry;
+}
+
+/// Check that `sender_id` may send message type `M`.
+pub fn check_authorized<M: IpcMessage>(sender_id: u8) -> Result<(), MessageError> {
+    if M::AUTHORIZATION.permits(sender_id) {
+        Ok(())
+    } else {
+        Err(MessageError::Unauthorized {
+            sender: sender_id,
+            message_type: M::AUTHORIZATION.message_type.to_string(),
+        })
+    }
+}
+
+/// Look up the entry for a message type by name.
+pub fn lookup(message_type: &str) -> Option<&'static MatrixEntry> {
+    IPC_MATRIX.iter().find(|e| e.message_type == message_type)
+}
+
+/// Declare matrix entries and the `IPC_MATRIX` table listing them.
+macro_rules! ipc_matrix {
+    ($(
+        $name:ident = $message:literal : [$($sender:ident),+] => [$($recipient:ident),+];
+    )*) => {
+        $(
+            #[doc = concat!("`", $message, "` authorization rule.")]
+            pub const $name: MatrixEntry = MatrixEntry {
+                message_type: $message,
+                senders: &[$(SubsystemId::$sender),+],
+                recipients: &[$(SubsystemId::$recipient),+],
+            };
+        )*
+
+        /// Every authorization rule, grouped by recipient.
+        pub const IPC_MATRIX: &[MatrixEntry] = &[$($name),*];
+    };
+}
+
+ipc_matrix! {
+    // Peer Discovery (1)
+    PEER_LIST_REQUEST = "PeerListRequest": [BlockPropagation, BloomFilters, LightClient] => [PeerDiscovery];
+    NODE_IDENTITY_VERIFICATION_RESULT = "NodeIdentityVerificationResult": [SignatureVerification] => [PeerDiscovery];
+
+    // Block Storage (2)
+    MERKLE_ROOT_COMPUTED = "MerkleRootComputed": [TransactionIndexing] => [BlockStorage];
+    STATE_ROOT_COMPUTED = "StateRootComputed": [StateManagement] => [BlockStorage];
+    MARK_FINALIZED_REQUEST = "MarkFinalizedRequest": [Finality] => [BlockStorage];
+    GET_TRANSACTION_LOCATION_REQUEST = "GetTransactionLocationRequest": [TransactionIndexing] => [BlockStorage];
+    GET_TRANSACTION_HASHES_REQUEST = "GetTransactionHashesRequest": [TransactionIndexing] => [BlockStorage];
+
+    // Transaction Indexing (3)
+    BLOCK_VALIDATED_EVENT = "BlockValidatedEvent": [Consensus] => [BlockStorage, TransactionIndexing, StateManagement];
+    MERKLE_PROOF_REQUEST = "MerkleProofRequest": [LightClient] => [TransactionIndexing];
+    TRANSACTION_HASH_REQUEST = "TransactionHashRequest": [BloomFilters] => [TransactionIndexing];
+    TRANSACTION_LOCATION_RESPONSE = "TransactionLocationResponse": [BlockStorage] => [TransactionIndexing];
+
+    // State Management (4)
+    STATE_READ_REQUEST = "StateReadRequest": [Mempool, SmartContracts, TransactionOrdering, Sharding] => [StateManagement];
+    STATE_WRITE_REQUEST = "StateWriteRequest": [SmartContracts] => [StateManagement];
+    BALANCE_CHECK_REQUEST = "BalanceCheckRequest": [Mempool] => [StateManagement];
+    CONFLICT_DETECTION_REQUEST = "ConflictDetectionRequest": [TransactionOrdering] => [StateManagement];
+
+    // Block Propagation (5)
+    PROPAGATE_BLOCK_REQUEST = "PropagateBlockRequest": [Consensus] => [BlockPropagation];
+
+    // Mempool (6)
+    ADD_TRANSACTION_REQUEST = "AddTransactionRequest": [SignatureVerification] => [Mempool];
+    GET_TRANSACTIONS_REQUEST = "GetTransactionsRequest": [Consensus] => [Mempool];
+    REMOVE_TRANSACTIONS_REQUEST = "RemoveTransactionsRequest": [Consensus] => [Mempool];
+    BLOCK_STORAGE_CONFIRMATION = "BlockStorageConfirmation": [BlockStorage] => [Mempool];
+    BLOCK_REJECTED_NOTIFICATION = "BlockRejectedNotification": [BlockStorage, Consensus] => [Mempool];
+
+    // Bloom Filters (7)
+    BUILD_FILTER_REQUEST = "BuildFilterRequest": [LightClient] => [BloomFilters];
+    UPDATE_FILTER_REQUEST = "UpdateFilterRequest": [LightClient] => [BloomFilters];
+    TRANSACTION_HASH_UPDATE = "TransactionHashUpdate": [TransactionIndexing] => [BloomFilters];
+
+    // Consensus (8)
+    PROPOSE_TRANSACTION_BATCH = "ProposeTransactionBatch": [Mempool] => [Consensus];
+    VALIDATE_BLOCK_REQUEST = "ValidateBlockRequest": [BlockPropagation] => [Consensus];
+    ATTESTATION_RECEIVED = "AttestationReceived": [SignatureVerification] => [Consensus];
+    PBFT_MESSAGE = "PBFTMessage": [SignatureVerification] => [Consensus];
+
+    // Finality (9)
+    ATTESTATION_BATCH = "AttestationBatch": [Consensus] => [Finality];
+    FINALITY_CHECK_REQUEST = "FinalityCheckRequest": [Consensus] => [Finality];
+    FINALITY_PROOF_REQUEST = "FinalityProofRequest": [CrossChain] => [Finality];
+
+    // Signature Verification (10)
+    VERIFY_TRANSACTION_REQUEST = "VerifyTransactionRequest": [PeerDiscovery, BlockPropagation, Mempool, Consensus, Finality] => [SignatureVerification];
+    VERIFY_NODE_IDENTITY_REQUEST = "VerifyNodeIdentityRequest": [PeerDiscovery] => [SignatureVerification];
+    VERIFY_SIGNATURE_REQUEST = "VerifySignatureRequest": [PeerDiscovery, BlockPropagation, Consensus, Finality] => [SignatureVerification];
+    BATCH_VERIFY_REQUEST = "BatchVerifyRequest": [Consensus] => [SignatureVerification];
+
+    // Smart Contracts (11)
+    EXECUTE_TRANSACTION_REQUEST = "ExecuteTransactionRequest": [Consensus, TransactionOrdering] => [SmartContracts];
+    EXECUTE_HTLC_REQUEST = "ExecuteHTLCRequest": [CrossChain] => [SmartContracts];
+
+    // Transaction Ordering (12)
+    ORDER_TRANSACTIONS_REQUEST = "OrderTransactionsRequest": [Consensus] => [TransactionOrdering];
+
+    // Light Client (13)
+    MERKLE_PROOF_RECEIVED = "MerkleProofReceived": [TransactionIndexing] => [LightClient];
+    FILTER_CREATED = "FilterCreated": [BloomFilters] => [LightClient];
+
+    // Sharding (14)
+    ASSIGN_SHARD_REQUEST = "AssignShardRequest": [Consensus] => [Sharding];
+    CROSS_SHARD_COMMIT_REQUEST = "CrossShardCommitRequest": [Consensus] => [Sharding];
+    REBALANCE_REQUEST = "RebalanceRequest": [Consensus] => [Sharding];
+
+    // Cross-Chain (15)
+    FINALITY_PROOF_RECEIVED = "FinalityProofReceived": [Finality] => [CrossChain];
+
+    // Block Production (17)
+    PENDING_TRANSACTIONS_RESPONSE = "PendingTransactionsResponse": [Mempool] => [BlockProduction];
+    STATE_PREFETCH_RESPONSE = "StatePrefetchResponse": [StateManagement] => [BlockProduction];
+    BLOCK_FINALIZED_EVENT = "BlockFinalizedEvent": [Finality] => [BlockProduction];
+    SLOT_ASSIGNED_EVENT = "SlotAssignedEvent": [Consensus] => [BlockProduction];
+}
+
+macro_rules! impl_ipc_message {
+    ($($ty:ty => $entry:ident),* $(,)?) => {$(
+        impl IpcMessage for $ty {
+            const AUTHORIZATION: MatrixEntry = $entry;
+        }
+    )*};
+}
+
+impl_ipc_message! {
+    PeerListRequestPayload => PEER_LIST_REQUEST,
+    VerifyNodeIdentityResponse => NODE_IDENTITY_VERIFICATION_RESULT,
+    MerkleRootComputedPayload => MERKLE_ROOT_COMPUTED,
+    StateRootComputedPayload => STATE_ROOT_COMPUTED,
+    MarkFinalizedPayload => MARK_FINALIZED_REQUEST,
+    BlockValidatedPayload => BLOCK_VALIDATED_EVENT,
+    BlockStorageConfirmationPayload => BLOCK_STORAGE_CONFIRMATION,
+    ProposeTransactionBatchPayload => PROPOSE_TRANSACTION_BATCH,
+    VerifyNodeIdentityPayload => VERIFY_NODE_IDENTITY_REQUEST,
+    VerifySignatureRequestPayload => VERIFY_SIGNATURE_REQUEST,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashSet};
+
+    const SPEC: &str = include_str!("../../../Documentation/IPC-MATRIX.md");
+
+    /// One `✅ Accept: X from Subsystem(s) N, M only` line of the spec.
+    struct SpecRule {
+        recipient: u8,
+        message_type: String,
+        senders: BTreeSet<u8>,
+    }
+
+    /// Extract every subsystem-to-subsystem accept rule from IPC-MATRIX.md.
+    fn spec_rules() -> Vec<SpecRule> {
+        let mut recipient = 0;
+        let mut rules = Vec::new();
+        for line in SPEC.lines() {
+            if let Some(rest) = line.strip_prefix("## SUBSYSTEM ") {
+                recipient = rest.split(':').next().unwrap().parse().unwrap_or(0);
+                continue;
+            }
+            let Some(rule) = line.strip_prefix("- ✅ Accept: ") else {
+                continue;
+            };
+            let Some((message_type, senders)) = rule.split_once(" from Subsystem") else {
+                continue;
+            };
+            let senders = senders
+                .trim_start_matches('s')
+                .split_whitespace()
+                .map_while(|token| token.trim_end_matches(',').parse().ok())
+                .collect();
+            rules.push(SpecRule {
+                recipient,
+                message_type: message_type.trim_matches('`').to_string(),
+                senders,
+            });
+        }
+        rules
+    }
+
+    #[test]
+    fn test_matrix_matches_spec() {
+        let rules = spec_rules();
+        assert!(rules.len() >= 40, "spec parser found only {}", rules.len());
+
+        for rule in rules {
+            let entry = lookup(&rule.message_type)
+                .unwrap_or_else(|| panic!("{} missing from IPC_MATRIX", rule.message_type));
+            let senders: BTreeSet<u8> = entry.senders.iter().map(|s| s.as_u8()).collect();
+            assert_eq!(senders, rule.senders, "senders of {}", rule.message_type);
+            assert!(
+                entry.delivers_to(rule.recipient),
+                "{} not delivered to {}",
+                rule.message_type,
+                rule.recipient
+            );
+        }
+    }
+
+    #[test]
+    fn test_message_types_unique() {
+        let mut seen = HashSet::new();
+        for entry in IPC_MATRIX {
+            assert!(seen.insert(entry.message_type), "{}", entry.message_type);
+        }
+    }
+
+    #[test]
+    fn test_check_authorized() {
+        assert!(check_authorized::<BlockValidatedPayload>(8).is_ok());
+        assert!(check_authorized::<VerifySignatureRequestPayload>(9).is_ok());
+
+        let err = check_authorized::<MarkFinalizedPayload>(8).unwrap_err();
+        assert!(matches!(
+            err,
+            MessageError::Unauthorized { sender: 8, ref message_type }
+                if message_type == "MarkFinalizedRequest"
+        ));
+        assert!(check_authorized::<VerifySignatureRequestPayload>(6).is_err());
+        assert!(check_authorized::<PeerListRequestPayload>(0).is_err());
+    }
+}
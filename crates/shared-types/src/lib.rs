@@ -10,16 +10,22 @@
 //!   for all IPC communication.
 //! - **No Redundant Identity**: Payloads MUST NOT contain `requester_id` fields;
 //!   the envelope's `sender_id` is authoritative.
+//! - **Byte-Stable Encoding**: `codec` defines the canonical, versioned binary
+//!   form used for hashing and network messages.
 //! - **Plug-and-Play**: Subsystems implement the `Subsystem` trait for runtime discovery.
 
 #![warn(missing_docs)]
 #![allow(missing_docs)] // TODO: Add documentation for all public items
 
+pub mod codec;
 pub mod entities;
 pub mod envelope;
+pub mod error_code;
 pub mod errors;
 pub mod ipc;
+pub mod ipc_matrix;
 pub mod rate_limiter;
+pub mod secret;
 pub mod security;
 pub mod subsystem_registry;
 pub mod subsystem_trait;
@@ -31,13 +37,16 @@ pub mod subsystem {
 
 pub use entities::*;
 pub use envelope::AuthenticatedMessage;
+pub use error_code::{ErrorCode, ErrorInfo, Retry, Severity};
 pub use errors::*;
 pub use ipc::*;
+pub use ipc_matrix::{check_authorized, IpcMessage, MatrixEntry, IPC_MATRIX};
+pub use secret::{constant_time_eq, SecretBytes};
 pub use security::*;
 
 // Re-export the plug-and-play architecture types
-pub use subsystem_registry::SubsystemRegistry;
+pub use subsystem_registry::{AdvertisedSubsystem, CapabilityReport, SubsystemRegistry};
 pub use subsystem_trait::{
-    DynSubsystem, Subsystem, SubsystemError, SubsystemErrorKind, SubsystemFactory, SubsystemInfo,
-    SubsystemStatus,
+    DynSubsystem, Subsystem, SubsystemCapabilities, SubsystemError, SubsystemErrorKind,
+    SubsystemFactory, SubsystemInfo, SubsystemStatus,
 };
@@ -0,0 +1,141 @@
+//! # Secret Material
+//!
+//! Wrappers for key material and credentials (HMAC master secrets, API
+//! keys, passwords) that must not leak through logs or timing.
+//!
+//! ## Guarantees
+//!
+//! - **Zeroize on drop**: the backing buffer is wiped when the value dies
+//! - **Constant-time equality**: `==` never short-circuits on the first
+//!   differing byte
+//! - **Redacted Debug**: `{:?}` prints `SecretBytes(<redacted>)`, so a
+//!   secret inside a derived-`Debug` config struct stays out of logs
+//! - **No `Serialize`**: secrets can be loaded from config, but never
+//!   written back out by accident
+//!
+//! Access to the raw bytes is explicit via `expose_secret`, which keeps
+//! every use greppable.
+
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Compare two byte strings in constant time.
+///
+/// Only the lengths may leak; equal-length inputs take the same time
+/// regardless of where (or whether) they differ.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// Variable-length secret bytes.
+#[derive(Clone, Default, Zeroize, ZeroizeOnDrop)]
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    /// Wrap secret bytes, taking ownership of the buffer.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Borrow the raw secret.
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Borrow the secret as UTF-8 text (API keys, passwords).
+    pub fn expose_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.0).ok()
+    }
+
+    /// Length of the secret in bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the secret is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Compare against presented bytes in constant time.
+    pub fn ct_eq_bytes(&self, other: &[u8]) -> bool {
+        constant_time_eq(&self.0, other)
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&[u8]> for SecretBytes {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl From<String> for SecretBytes {
+    fn from(text: String) -> Self {
+        Self(text.into_bytes())
+    }
+}
+
+impl From<&str> for SecretBytes {
+    fn from(text: &str) -> Self {
+        Self(text.as_bytes().to_vec())
+    }
+}
+
+impl PartialEq for SecretBytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq_bytes(&other.0)
+    }
+}
+
+impl Eq for SecretBytes {}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretBytes(<redacted>)")
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretBytes {
+    /// Deserialize from a string (config files, environment).
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let secret = SecretBytes::from("hunter2");
+        let printed = format!("{secret:?}");
+        assert_eq!(printed, "SecretBytes(<redacted>)");
+        assert!(!printed.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_equality() {
+        let a = SecretBytes::from("api-key-1");
+        assert_eq!(a, SecretBytes::from(b"api-key-1".to_vec()));
+        assert_ne!(a, SecretBytes::from("api-key-2"));
+        assert_ne!(a, SecretBytes::from("api-key"));
+        assert!(a.ct_eq_bytes(b"api-key-1"));
+        assert!(!a.ct_eq_bytes(b""));
+        assert_eq!(a.expose_str(), Some("api-key-1"));
+    }
+
+    #[test]
+    fn test_deserialize_from_string() {
+        let secret: SecretBytes = serde_json::from_str("\"from-config\"").unwrap();
+        assert_eq!(secret.expose_secret(), b"from-config");
+    }
+}
@@ -19,11 +19,15 @@
 //! ## Security Properties
 //!
 //! - **HMAC-SHA256 Signatures**: All messages are signed with subsystem-specific keys
+//! - **Ed25519 Signatures (optional)**: Per-subsystem asymmetric keys, so a
+//!   compromised subsystem cannot impersonate the others (see `EnvelopeAuthMode`)
 //! - **Time-Bounded Validity**: Messages expire after 60 seconds
 //! - **Nonce Replay Prevention**: Each nonce is valid only once within the time window
 //! - **Sender Authorization**: Messages are checked against IPC-MATRIX.md rules
 
 use crate::envelope::{AuthenticatedMessage, VerificationResult};
+use crate::secret::SecretBytes;
+use ed25519_dalek::{Signature as Ed25519Signature, VerifyingKey};
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
 use std::collections::HashMap;
@@ -223,6 +227,56 @@ pub fn sign_message(message_bytes: &[u8], shared_secret: &[u8]) -> [u8; 64] {
     signature
 }
 
+// =============================================================================
+// ED25519 VALIDATION
+// =============================================================================
+
+/// Validates an Ed25519 envelope signature.
+///
+/// # Arguments
+///
+/// - `message_bytes`: The canonically serialized message (header + payload)
+/// - `signature`: The 64-byte signature from the message envelope
+/// - `public_key`: The sender subsystem's Ed25519 verifying key
+///
+/// # Returns
+///
+/// - `true` if the signature is valid
+/// - `false` if the key is malformed or the signature does not verify
+///
+/// Uses strict verification (rejects small-order keys and malleable signatures).
+pub fn validate_ed25519_signature(
+    message_bytes: &[u8],
+    signature: &[u8; 64],
+    public_key: &[u8; 32],
+) -> bool {
+    let Ok(key) = VerifyingKey::from_bytes(public_key) else {
+        return false;
+    };
+    key.verify_strict(message_bytes, &Ed25519Signature::from_bytes(signature))
+        .is_ok()
+}
+
+/// How envelope signatures are authenticated.
+///
+/// ## Rollout
+///
+/// 1. `Hmac`: every subsystem signs with the shared HMAC scheme (legacy)
+/// 2. `Migration`: subsystems switch to Ed25519 one at a time; a sender
+///    with a registered Ed25519 key must sign with it, and only senders
+///    without one may still use HMAC
+/// 3. `Ed25519`: HMAC is no longer accepted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EnvelopeAuthMode {
+    /// HMAC-SHA256 only.
+    #[default]
+    Hmac,
+    /// Ed25519 for senders with a verifying key, HMAC for the rest.
+    Migration,
+    /// Ed25519 only.
+    Ed25519,
+}
+
 // =============================================================================
 // TIMESTAMP VALIDATION
 // =============================================================================
@@ -306,6 +360,8 @@ pub struct MessageVerifier<K: KeyProvider> {
     key_provider: K,
     /// Authorization matrix checker
     auth_matrix: AuthorizationMatrix,
+    /// Accepted envelope signature schemes
+    auth_mode: EnvelopeAuthMode,
 }
 
 /// Trait for retrieving shared secrets for HMAC validation.
@@ -322,6 +378,13 @@ pub trait KeyProvider: Send + Sync {
     /// - `Some(secret)` if the sender is known
     /// - `None` if the sender is unknown (reject message)
     fn get_shared_secret(&self, sender_id: u8) -> Option<Vec<u8>>;
+
+    /// Returns the Ed25519 verifying key for a given sender subsystem.
+    ///
+    /// Defaults to `None` (no Ed25519 key registered).
+    fn get_verifying_key(&self, _sender_id: u8) -> Option<[u8; 32]> {
+        None
+    }
 }
 
 impl<K: KeyProvider> MessageVerifier<K> {
@@ -332,9 +395,16 @@ impl<K: KeyProvider> MessageVerifier<K> {
             nonce_cache,
             key_provider,
             auth_matrix: AuthorizationMatrix::new(),
+            auth_mode: EnvelopeAuthMode::default(),
         }
     }
 
+    /// Sets the accepted envelope signature schemes.
+    pub fn with_auth_mode(mut self, auth_mode: EnvelopeAuthMode) -> Self {
+        self.auth_mode = auth_mode;
+        self
+    }
+
     /// Verifies an authenticated message.
     ///
     /// # Arguments
@@ -371,12 +441,7 @@ impl<K: KeyProvider> MessageVerifier<K> {
         }
 
         // 4. Signature check
-        let shared_secret = match self.key_provider.get_shared_secret(message.sender_id) {
-            Some(s) => s,
-            None => return VerificationResult::InvalidSignature,
-        };
-
-        if !validate_hmac_signature(message_bytes, &message.signature, &shared_secret) {
+        if !self.verify_signature(message.sender_id, &message.signature, message_bytes) {
             return VerificationResult::InvalidSignature;
         }
 
@@ -393,6 +458,30 @@ impl<K: KeyProvider> MessageVerifier<K> {
         VerificationResult::Valid
     }
 
+    /// Checks the envelope signature according to the configured auth mode.
+    fn verify_signature(&self, sender_id: u8, signature: &[u8; 64], message_bytes: &[u8]) -> bool {
+        let ed25519_valid = || {
+            self.key_provider
+                .get_verifying_key(sender_id)
+                .is_some_and(|key| validate_ed25519_signature(message_bytes, signature, &key))
+        };
+        let hmac_valid = || {
+            self.key_provider
+                .get_shared_secret(sender_id)
+                .is_some_and(|secret| validate_hmac_signature(message_bytes, signature, &secret))
+        };
+
+        match self.auth_mode {
+            EnvelopeAuthMode::Hmac => hmac_valid(),
+            // A migrated sender must not be impersonable with the shared secret
+            EnvelopeAuthMode::Migration => match self.key_provider.get_verifying_key(sender_id) {
+                Some(_) => ed25519_valid(),
+                None => hmac_valid(),
+            },
+            EnvelopeAuthMode::Ed25519 => ed25519_valid(),
+        }
+    }
+
     /// Checks if a sender is authorized to send a specific message type to this recipient.
     ///
     /// # Arguments
@@ -509,23 +598,25 @@ impl Default for AuthorizationMatrix {
 /// A simple key provider that derives keys from a master secret.
 ///
 /// For production, replace with a proper key management implementation.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct DerivedKeyProvider {
-    master_secret: Vec<u8>,
+    master_secret: SecretBytes,
 }
 
 impl DerivedKeyProvider {
     /// Creates a new key provider with the given master secret.
-    pub fn new(master_secret: Vec<u8>) -> Self {
-        Self { master_secret }
+    pub fn new(master_secret: impl Into<SecretBytes>) -> Self {
+        Self {
+            master_secret: master_secret.into(),
+        }
     }
 
     /// Derives a subsystem-specific key from the master secret.
     fn derive_key(&self, subsystem_id: u8) -> Vec<u8> {
         // SAFETY: HMAC-SHA256 can take a key of any size, so this never fails
         #[allow(clippy::expect_used)]
-        let mut mac =
-            HmacSha256::new_from_slice(&self.master_secret).expect("HMAC can take key of any size");
+        let mut mac = HmacSha256::new_from_slice(self.master_secret.expose_secret())
+            .expect("HMAC can take key of any size");
         mac.update(&[subsystem_id]);
         mac.finalize().into_bytes().to_vec()
     }
@@ -648,4 +739,111 @@ mod tests {
         let key1_again = provider.get_shared_secret(1).unwrap();
         assert_eq!(key1, key1_again);
     }
+
+    struct TestKeys {
+        hmac: DerivedKeyProvider,
+        ed25519: HashMap<u8, [u8; 32]>,
+    }
+
+    impl KeyProvider for TestKeys {
+        fn get_shared_secret(&self, sender_id: u8) -> Option<Vec<u8>> {
+            self.hmac.get_shared_secret(sender_id)
+        }
+
+        fn get_verifying_key(&self, sender_id: u8) -> Option<[u8; 32]> {
+            self.ed25519.get(&sender_id).copied()
+        }
+    }
+
+    /// Build a message from `sender_id` and return it with its signing bytes.
+    fn envelope(sender_id: u8) -> (AuthenticatedMessage<u64>, Vec<u8>) {
+        let message = AuthenticatedMessage {
+            version: AuthenticatedMessage::<u64>::CURRENT_VERSION,
+            sender_id,
+            recipient_id: 2,
+            correlation_id: Uuid::new_v4(),
+            reply_to: None,
+            timestamp: current_timestamp(),
+            nonce: Uuid::new_v4(),
+            signature: [0u8; 64],
+            payload: 42,
+        };
+        let bytes = serde_json::to_vec(&message).unwrap();
+        (message, bytes)
+    }
+
+    #[test]
+    fn test_envelope_auth_modes() {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let keys = || TestKeys {
+            hmac: DerivedKeyProvider::new(b"master_secret".to_vec()),
+            ed25519: HashMap::from([(8, signing_key.verifying_key().to_bytes())]),
+        };
+        let hmac_secret = keys().get_shared_secret(8).unwrap();
+        let verifier =
+            |mode| MessageVerifier::new(2, NonceCache::new_shared(), keys()).with_auth_mode(mode);
+
+        let signed = |ed25519: bool| {
+            let (mut message, bytes) = envelope(8);
+            message.signature = if ed25519 {
+                ed25519_dalek::Signer::sign(&signing_key, &bytes).to_bytes()
+            } else {
+                sign_message(&bytes, &hmac_secret)
+            };
+            (message, bytes)
+        };
+
+        let cases = [
+            (EnvelopeAuthMode::Hmac, false, true),
+            (EnvelopeAuthMode::Hmac, true, false),
+            // Sender 8 has migrated, so its HMAC is no longer accepted
+            (EnvelopeAuthMode::Migration, false, false),
+            (EnvelopeAuthMode::Migration, true, true),
+            (EnvelopeAuthMode::Ed25519, false, false),
+            (EnvelopeAuthMode::Ed25519, true, true),
+        ];
+        for (mode, ed25519, accepted) in cases {
+            let (message, bytes) = signed(ed25519);
+            assert_eq!(
+                verifier(mode).verify(&message, &bytes).is_valid(),
+                accepted,
+                "{mode:?} with ed25519={ed25519}"
+            );
+        }
+
+        // A sender without a registered Ed25519 key cannot pass in Ed25519 mode,
+        // even with a valid signature from another subsystem's key.
+        let (mut message, bytes) = envelope(6);
+        message.signature = ed25519_dalek::Signer::sign(&signing_key, &bytes).to_bytes();
+        assert_eq!(
+            verifier(EnvelopeAuthMode::Ed25519).verify(&message, &bytes),
+            VerificationResult::InvalidSignature
+        );
+    }
+
+    #[test]
+    fn test_migration_mode_hmac_only_for_unmigrated_senders() {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let keys = TestKeys {
+            hmac: DerivedKeyProvider::new(b"master_secret".to_vec()),
+            ed25519: HashMap::from([(8, signing_key.verifying_key().to_bytes())]),
+        };
+        let hmac = |sender_id: u8| {
+            let (mut message, bytes) = envelope(sender_id);
+            let secret = keys.get_shared_secret(sender_id).unwrap();
+            message.signature = sign_message(&bytes, &secret);
+            (message, bytes)
+        };
+        let (migrated, migrated_bytes) = hmac(8);
+        let (unmigrated, unmigrated_bytes) = hmac(6);
+
+        let verifier = MessageVerifier::new(2, NonceCache::new_shared(), keys)
+            .with_auth_mode(EnvelopeAuthMode::Migration);
+        // The shared secret cannot impersonate a sender that has an Ed25519 key
+        assert_eq!(
+            verifier.verify(&migrated, &migrated_bytes),
+            VerificationResult::InvalidSignature
+        );
+        assert!(verifier.verify(&unmigrated, &unmigrated_bytes).is_valid());
+    }
 }
@@ -9,6 +9,9 @@
 //! - **Dependency ordering**: Starts subsystems in correct order
 //! - **Graceful degradation**: Missing optional subsystems logged as warnings
 //! - **Health monitoring**: Periodic health checks on all subsystems
+//! - **Capability advertisement**: Aggregated `CapabilityReport` for the bus
+//! - **Runtime control**: Non-core subsystems can be stopped, started and
+//!   restarted while the node runs, subject to dependency checks
 //!
 //! ## Usage
 //!
@@ -26,16 +29,13 @@
 //! registry.stop_all().await?;
 //! ```
 
-// TODO: Refactor to use tokio::sync::RwLock to properly handle async lock guards.
-// Current implementation uses parking_lot::RwLock which warns about holding locks across await.
-// This is safe because subsystem operations are short-lived, but should be refactored.
-#![allow(clippy::await_holding_lock)]
-
 use crate::entities::SubsystemId;
 use crate::subsystem_trait::{
-    DynSubsystem, SubsystemError, SubsystemErrorKind, SubsystemInfo, SubsystemStatus,
+    DynSubsystem, Subsystem, SubsystemCapabilities, SubsystemError, SubsystemErrorKind,
+    SubsystemInfo, SubsystemStatus,
 };
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{error, info, warn};
@@ -55,13 +55,66 @@ pub struct SubsystemRegistry {
 /// Entry for a registered subsystem.
 struct SubsystemEntry {
     /// The subsystem instance.
-    subsystem: DynSubsystem,
+    subsystem: Arc<dyn Subsystem>,
     /// Current status.
     status: SubsystemStatus,
     /// Cached info.
     info: SubsystemInfo,
 }
 
+/// Capabilities of one registered subsystem, as advertised on the bus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdvertisedSubsystem {
+    /// Subsystem identifier.
+    pub id: SubsystemId,
+    /// Human-readable name.
+    pub name: String,
+    /// Version string.
+    pub version: String,
+    /// Status when the report was taken.
+    pub status: SubsystemStatus,
+    /// Supported message types and feature flags.
+    pub capabilities: SubsystemCapabilities,
+}
+
+/// Capabilities of every registered subsystem.
+///
+/// Subsystems missing from the report are not registered (disabled or not
+/// built), so callers can fail fast instead of waiting for a timeout.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityReport {
+    /// Registered subsystems, ordered by ID.
+    pub subsystems: Vec<AdvertisedSubsystem>,
+}
+
+impl CapabilityReport {
+    /// Get the entry for a subsystem, if registered.
+    pub fn get(&self, id: SubsystemId) -> Option<&AdvertisedSubsystem> {
+        self.subsystems.iter().find(|s| s.id == id)
+    }
+
+    /// Check if a subsystem is registered and able to serve requests.
+    pub fn is_available(&self, id: SubsystemId) -> bool {
+        self.get(id).is_some_and(|s| is_running(s.status))
+    }
+
+    /// Check if an available subsystem accepts a message type.
+    pub fn supports(&self, id: SubsystemId, message_type: &str) -> bool {
+        self.is_available(id)
+            && self
+                .get(id)
+                .is_some_and(|s| s.capabilities.supports(message_type))
+    }
+
+    /// Check if an available subsystem has a feature flag enabled.
+    pub fn has_feature(&self, id: SubsystemId, feature: &str) -> bool {
+        self.is_available(id)
+            && self
+                .get(id)
+                .is_some_and(|s| s.capabilities.has_feature(feature))
+    }
+}
+
 impl SubsystemRegistry {
     /// Create a new empty registry.
     pub fn new() -> Self {
@@ -96,7 +149,7 @@ impl SubsystemRegistry {
         self.subsystems.insert(
             id,
             Arc::new(RwLock::new(SubsystemEntry {
-                subsystem,
+                subsystem: Arc::from(subsystem),
                 status: SubsystemStatus::Stopped,
                 info,
             })),
@@ -177,19 +230,35 @@ impl SubsystemRegistry {
         // Check dependencies before starting
         self.check_dependencies(id, &entry_arc.read().info.dependencies)?;
 
-        // Start the subsystem
-        let mut entry = entry_arc.write();
-        info!("[Registry] Starting {:?} ({})", id, entry.info.name);
-        entry.status = SubsystemStatus::Starting;
-
-        if let Err(e) = entry.subsystem.start().await {
-            entry.status = SubsystemStatus::Error;
-            return self.handle_start_failure(id, e);
+        match Self::launch(id, entry_arc).await {
+            Ok(()) => Ok(()),
+            Err(e) => self.handle_start_failure(id, e),
         }
+    }
 
-        entry.status = SubsystemStatus::Healthy;
-        info!("[Registry] ✓ {:?} started successfully", id);
-        Ok(())
+    /// Call `start()` on a subsystem and record the resulting status.
+    ///
+    /// The entry lock is not held while the subsystem starts.
+    async fn launch(
+        id: &SubsystemId,
+        entry_arc: &Arc<RwLock<SubsystemEntry>>,
+    ) -> Result<(), SubsystemError> {
+        let subsystem = {
+            let mut entry = entry_arc.write();
+            info!("[Registry] Starting {:?} ({})", id, entry.info.name);
+            entry.status = SubsystemStatus::Starting;
+            Arc::clone(&entry.subsystem)
+        };
+
+        let result = subsystem.start().await;
+        entry_arc.write().status = match result {
+            Ok(()) => SubsystemStatus::Healthy,
+            Err(_) => SubsystemStatus::Error,
+        };
+        if result.is_ok() {
+            info!("[Registry] ✓ {:?} started successfully", id);
+        }
+        result
     }
 
     /// Handle a subsystem start failure.
@@ -225,27 +294,191 @@ impl SubsystemRegistry {
             return;
         };
 
-        let mut entry = entry_arc.write();
-        let should_stop =
-            entry.status == SubsystemStatus::Healthy || entry.status == SubsystemStatus::Degraded;
+        let status = entry_arc.read().status;
+        let should_stop = status == SubsystemStatus::Healthy || status == SubsystemStatus::Degraded;
 
-        if !should_stop {
-            return;
+        if should_stop {
+            // Failures are logged by `shutdown`; stopping the rest continues
+            let _ = Self::shutdown(id, entry_arc).await;
         }
+    }
 
-        info!("[Registry] Stopping {:?}", id);
-        entry.status = SubsystemStatus::ShuttingDown;
+    /// Call `stop()` on a subsystem and record the resulting status.
+    ///
+    /// The entry lock is not held while the subsystem stops.
+    async fn shutdown(
+        id: &SubsystemId,
+        entry_arc: &Arc<RwLock<SubsystemEntry>>,
+    ) -> Result<(), SubsystemError> {
+        let subsystem = {
+            let mut entry = entry_arc.write();
+            info!("[Registry] Stopping {:?}", id);
+            entry.status = SubsystemStatus::ShuttingDown;
+            Arc::clone(&entry.subsystem)
+        };
 
-        match entry.subsystem.stop().await {
+        let result = subsystem.stop().await;
+        match &result {
             Ok(()) => {
-                entry.status = SubsystemStatus::Stopped;
+                entry_arc.write().status = SubsystemStatus::Stopped;
                 info!("[Registry] ✓ {:?} stopped", id);
             }
             Err(e) => {
-                entry.status = SubsystemStatus::Error;
+                entry_arc.write().status = SubsystemStatus::Error;
                 error!("[Registry] ✗ {:?} failed to stop cleanly: {}", id, e);
             }
         }
+        result
+    }
+
+    // =========================================================================
+    // RUNTIME CONTROL
+    // =========================================================================
+
+    /// Check if a subsystem is core (required) and so cannot be stopped
+    /// while the node runs.
+    pub fn is_core(&self, id: SubsystemId) -> bool {
+        self.required.contains(&id)
+            || self
+                .subsystems
+                .get(&id)
+                .is_some_and(|entry| entry.read().info.required)
+    }
+
+    /// Get the running subsystems that depend on `id`.
+    pub fn dependents(&self, id: SubsystemId) -> Vec<SubsystemId> {
+        let mut dependents: Vec<SubsystemId> = self
+            .subsystems
+            .iter()
+            .filter(|(_, entry)| {
+                let entry = entry.read();
+                is_running(entry.status) && entry.info.dependencies.contains(&id)
+            })
+            .map(|(dependent, _)| *dependent)
+            .collect();
+        dependents.sort_by_key(|d| d.as_u8());
+        dependents
+    }
+
+    /// Stop a non-core subsystem while the node runs.
+    ///
+    /// Refused while a running subsystem depends on it. Stopping a subsystem
+    /// that is not running is a no-op. Returns the new status.
+    pub async fn stop(&self, id: SubsystemId) -> Result<SubsystemStatus, SubsystemError> {
+        let entry_arc = self.stoppable_entry(id)?;
+        if is_running(entry_arc.read().status) {
+            Self::shutdown(&id, entry_arc).await?;
+        }
+        Ok(entry_arc.read().status)
+    }
+
+    /// Start a stopped non-core subsystem while the node runs.
+    ///
+    /// Every registered dependency must be running. Starting a running
+    /// subsystem is a no-op. Returns the new status.
+    pub async fn start(&self, id: SubsystemId) -> Result<SubsystemStatus, SubsystemError> {
+        let entry_arc = self.controllable_entry(id)?;
+        if is_running(entry_arc.read().status) {
+            return Ok(entry_arc.read().status);
+        }
+
+        self.check_dependencies_running(id, entry_arc)?;
+        Self::launch(&id, entry_arc).await?;
+        Ok(entry_arc.read().status)
+    }
+
+    /// Stop and start a non-core subsystem while the node runs.
+    pub async fn restart(&self, id: SubsystemId) -> Result<SubsystemStatus, SubsystemError> {
+        self.stop(id).await?;
+        self.start(id).await
+    }
+
+    /// Check that `stop` would be allowed now, without stopping anything.
+    pub fn check_stop(&self, id: SubsystemId) -> Result<(), SubsystemError> {
+        self.stoppable_entry(id).map(|_| ())
+    }
+
+    /// Check that `start` would be allowed now, without starting anything.
+    pub fn check_start(&self, id: SubsystemId) -> Result<(), SubsystemError> {
+        let entry_arc = self.controllable_entry(id)?;
+        if is_running(entry_arc.read().status) {
+            return Ok(());
+        }
+        self.check_dependencies_running(id, entry_arc)
+    }
+
+    /// Check that `restart` would be allowed now, without touching the
+    /// subsystem.
+    pub fn check_restart(&self, id: SubsystemId) -> Result<(), SubsystemError> {
+        let entry_arc = self.stoppable_entry(id)?;
+        self.check_dependencies_running(id, entry_arc)
+    }
+
+    /// Entry for a non-core subsystem no running subsystem depends on.
+    fn stoppable_entry(
+        &self,
+        id: SubsystemId,
+    ) -> Result<&Arc<RwLock<SubsystemEntry>>, SubsystemError> {
+        let entry_arc = self.controllable_entry(id)?;
+        let dependents = self.dependents(id);
+        if !dependents.is_empty() {
+            return Err(SubsystemError {
+                subsystem_id: id,
+                kind: SubsystemErrorKind::Refused,
+                message: format!("Running subsystems depend on it: {:?}", dependents),
+            });
+        }
+        Ok(entry_arc)
+    }
+
+    /// Check that every registered dependency of `id` is running.
+    fn check_dependencies_running(
+        &self,
+        id: SubsystemId,
+        entry_arc: &Arc<RwLock<SubsystemEntry>>,
+    ) -> Result<(), SubsystemError> {
+        let dependencies = entry_arc.read().info.dependencies.clone();
+        for dep_id in &dependencies {
+            let dep_status = self.subsystems.get(dep_id).map(|dep| dep.read().status);
+            let satisfied = match dep_status {
+                Some(status) => is_running(status),
+                None => !self.required.contains(dep_id),
+            };
+            if !satisfied {
+                return Err(SubsystemError {
+                    subsystem_id: id,
+                    kind: SubsystemErrorKind::MissingDependency,
+                    message: format!(
+                        "Dependency {:?} is {}",
+                        dep_id,
+                        dep_status.map_or("not registered".to_string(), |s| format!("{:?}", s))
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Entry for a registered, non-core subsystem.
+    fn controllable_entry(
+        &self,
+        id: SubsystemId,
+    ) -> Result<&Arc<RwLock<SubsystemEntry>>, SubsystemError> {
+        let Some(entry_arc) = self.subsystems.get(&id) else {
+            return Err(SubsystemError {
+                subsystem_id: id,
+                kind: SubsystemErrorKind::NotAvailable,
+                message: "Subsystem is not registered".to_string(),
+            });
+        };
+        if self.is_core(id) {
+            return Err(SubsystemError {
+                subsystem_id: id,
+                kind: SubsystemErrorKind::Refused,
+                message: "Core subsystems cannot be stopped or started at runtime".to_string(),
+            });
+        }
+        Ok(entry_arc)
     }
 
     /// Run health checks on all subsystems.
@@ -253,9 +486,8 @@ impl SubsystemRegistry {
         let mut results = HashMap::new();
 
         for (id, entry) in &self.subsystems {
-            let entry = entry.read();
-            let status = entry.subsystem.health_check().await;
-            results.insert(*id, status);
+            let subsystem = Arc::clone(&entry.read().subsystem);
+            results.insert(*id, subsystem.health_check().await);
         }
 
         results
@@ -274,6 +506,29 @@ impl SubsystemRegistry {
         serde_json::Value::Object(metrics)
     }
 
+    /// Aggregate the capabilities of all registered subsystems.
+    ///
+    /// The runtime publishes this on the bus as `CapabilitiesAdvertised`.
+    pub fn capabilities(&self) -> CapabilityReport {
+        let mut subsystems: Vec<AdvertisedSubsystem> = self
+            .subsystems
+            .values()
+            .map(|entry| {
+                let entry = entry.read();
+                AdvertisedSubsystem {
+                    id: entry.info.id,
+                    name: entry.info.name.clone(),
+                    version: entry.info.version.clone(),
+                    status: entry.status,
+                    capabilities: entry.info.capabilities.clone(),
+                }
+            })
+            .collect();
+        subsystems.sort_by_key(|s| s.id.as_u8());
+
+        CapabilityReport { subsystems }
+    }
+
     /// Check if all dependencies for a subsystem are healthy.
     /// Returns Ok if all required deps are healthy, Err otherwise.
     fn check_dependencies(
@@ -370,6 +625,14 @@ impl SubsystemRegistry {
     }
 }
 
+/// Whether a subsystem is up (or coming up) and serving.
+fn is_running(status: SubsystemStatus) -> bool {
+    matches!(
+        status,
+        SubsystemStatus::Healthy | SubsystemStatus::Degraded | SubsystemStatus::Starting
+    )
+}
+
 impl Default for SubsystemRegistry {
     fn default() -> Self {
         Self::new()
@@ -387,6 +650,12 @@ mod tests {
         name: &'static str,
     }
 
+    impl MockSubsystem {
+        fn boxed(id: SubsystemId, name: &'static str) -> DynSubsystem {
+            Box::new(Self { id, name })
+        }
+    }
+
     #[async_trait]
     impl Subsystem for MockSubsystem {
         fn id(&self) -> SubsystemId {
@@ -395,6 +664,11 @@ mod tests {
         fn name(&self) -> &'static str {
             self.name
         }
+        fn info(&self) -> SubsystemInfo {
+            SubsystemInfo::new(self.id, self.name)
+                .accepts_messages(vec!["Ping"])
+                .with_features(vec!["mock"])
+        }
         async fn start(&self) -> Result<(), SubsystemError> {
             Ok(())
         }
@@ -429,4 +703,141 @@ mod tests {
         let result = registry.validate_required();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_capability_report() {
+        let mut registry = SubsystemRegistry::new();
+        for (id, name) in [
+            (SubsystemId::SignatureVerification, "Signature Verification"),
+            (SubsystemId::BlockStorage, "Block Storage"),
+            (SubsystemId::Consensus, "Consensus"),
+        ] {
+            registry.register(MockSubsystem::boxed(id, name)).unwrap();
+        }
+
+        // Registered but not started yet
+        let report = registry.capabilities();
+        let ids: Vec<_> = report.subsystems.iter().map(|s| s.id).collect();
+        assert_eq!(
+            ids,
+            vec![
+                SubsystemId::BlockStorage,
+                SubsystemId::Consensus,
+                SubsystemId::SignatureVerification
+            ]
+        );
+        assert!(!report.is_available(SubsystemId::Consensus));
+
+        for entry in registry.subsystems.values() {
+            entry.write().status = SubsystemStatus::Healthy;
+        }
+        let report = registry.capabilities();
+        assert!(report.supports(SubsystemId::Consensus, "Ping"));
+        assert!(!report.supports(SubsystemId::Consensus, "GetBlock"));
+        assert!(report.has_feature(SubsystemId::BlockStorage, "mock"));
+
+        // Optional subsystem that was never registered (e.g. disabled qc-07)
+        assert!(report.get(SubsystemId::BloomFilters).is_none());
+        assert!(!report.is_available(SubsystemId::BloomFilters));
+        assert!(!report.supports(SubsystemId::BloomFilters, "Ping"));
+    }
+
+    /// Optional subsystem with dependencies, for runtime control tests.
+    struct DependentSubsystem {
+        id: SubsystemId,
+        dependencies: Vec<SubsystemId>,
+    }
+
+    #[async_trait]
+    impl Subsystem for DependentSubsystem {
+        fn id(&self) -> SubsystemId {
+            self.id
+        }
+        fn name(&self) -> &'static str {
+            "Dependent"
+        }
+        fn info(&self) -> SubsystemInfo {
+            SubsystemInfo::new(self.id, self.name()).depends_on(self.dependencies.clone())
+        }
+        async fn start(&self) -> Result<(), SubsystemError> {
+            Ok(())
+        }
+        async fn stop(&self) -> Result<(), SubsystemError> {
+            Ok(())
+        }
+        async fn health_check(&self) -> SubsystemStatus {
+            SubsystemStatus::Healthy
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runtime_control() {
+        let mut registry = SubsystemRegistry::new();
+        for (id, name) in [
+            (SubsystemId::SignatureVerification, "Signature Verification"),
+            (SubsystemId::BlockStorage, "Block Storage"),
+            (SubsystemId::Consensus, "Consensus"),
+        ] {
+            registry.register(MockSubsystem::boxed(id, name)).unwrap();
+        }
+        for (id, dependencies) in [
+            (SubsystemId::PeerDiscovery, vec![]),
+            (
+                SubsystemId::BlockPropagation,
+                vec![SubsystemId::PeerDiscovery],
+            ),
+        ] {
+            registry
+                .register(Box::new(DependentSubsystem { id, dependencies }))
+                .unwrap();
+        }
+        registry.start_all().await.unwrap();
+
+        // Core subsystems and unregistered ones cannot be controlled
+        let core = registry.stop(SubsystemId::Consensus).await.unwrap_err();
+        assert_eq!(core.kind, SubsystemErrorKind::Refused);
+        let missing = registry.start(SubsystemId::BloomFilters).await.unwrap_err();
+        assert_eq!(missing.kind, SubsystemErrorKind::NotAvailable);
+
+        // A running dependent blocks the stop
+        assert_eq!(
+            registry.dependents(SubsystemId::PeerDiscovery),
+            vec![SubsystemId::BlockPropagation]
+        );
+        let blocked = registry.stop(SubsystemId::PeerDiscovery).await.unwrap_err();
+        assert_eq!(blocked.kind, SubsystemErrorKind::Refused);
+        let checked = registry.check_stop(SubsystemId::PeerDiscovery).unwrap_err();
+        assert_eq!(checked.kind, SubsystemErrorKind::Refused);
+        assert!(registry
+            .check_restart(SubsystemId::BlockPropagation)
+            .is_ok());
+
+        let stopped = registry.stop(SubsystemId::BlockPropagation).await.unwrap();
+        assert_eq!(stopped, SubsystemStatus::Stopped);
+        let stopped = registry.stop(SubsystemId::PeerDiscovery).await.unwrap();
+        assert_eq!(stopped, SubsystemStatus::Stopped);
+
+        // Cannot start before its dependency is back
+        let early = registry
+            .start(SubsystemId::BlockPropagation)
+            .await
+            .unwrap_err();
+        assert_eq!(early.kind, SubsystemErrorKind::MissingDependency);
+        let checked = registry
+            .check_start(SubsystemId::BlockPropagation)
+            .unwrap_err();
+        assert_eq!(checked.kind, SubsystemErrorKind::MissingDependency);
+        assert!(registry.check_start(SubsystemId::PeerDiscovery).is_ok());
+        assert_eq!(
+            registry.status(SubsystemId::PeerDiscovery),
+            Some(SubsystemStatus::Stopped)
+        );
+
+        registry.start(SubsystemId::PeerDiscovery).await.unwrap();
+        let started = registry.restart(SubsystemId::BlockPropagation).await;
+        assert_eq!(started.unwrap(), SubsystemStatus::Healthy);
+        assert!(registry
+            .capabilities()
+            .is_available(SubsystemId::BlockPropagation));
+    }
 }
@@ -29,6 +29,7 @@
 //! ```
 
 use crate::entities::SubsystemId;
+use crate::error_code::{ErrorCode, ErrorInfo};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -56,6 +57,18 @@ impl fmt::Display for SubsystemError {
 
 impl std::error::Error for SubsystemError {}
 
+impl SubsystemError {
+    /// Shared error code for this error's kind.
+    pub fn code(&self) -> ErrorCode {
+        self.kind.code()
+    }
+
+    /// Coded form of this error, for IPC envelopes and API responses.
+    pub fn to_error_info(&self) -> ErrorInfo {
+        ErrorInfo::new(self.code(), self.subsystem_id, self.message.clone())
+    }
+}
+
 /// Categories of subsystem errors.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SubsystemErrorKind {
@@ -71,6 +84,22 @@ pub enum SubsystemErrorKind {
     MissingDependency,
     /// Configuration error.
     ConfigurationError,
+    /// Lifecycle operation refused (e.g. stopping a core subsystem).
+    Refused,
+}
+
+impl SubsystemErrorKind {
+    /// Shared error code for this kind.
+    pub fn code(self) -> ErrorCode {
+        match self {
+            Self::NotAvailable | Self::MissingDependency => ErrorCode::Unavailable,
+            Self::ConfigurationError => ErrorCode::ConfigurationError,
+            Self::Refused => ErrorCode::Rejected,
+            Self::InitializationFailed | Self::RuntimeError | Self::ShutdownFailed => {
+                ErrorCode::Internal
+            }
+        }
+    }
 }
 
 impl fmt::Display for SubsystemErrorKind {
@@ -82,6 +111,7 @@ impl fmt::Display for SubsystemErrorKind {
             Self::ShutdownFailed => write!(f, "ShutdownFailed"),
             Self::MissingDependency => write!(f, "MissingDependency"),
             Self::ConfigurationError => write!(f, "ConfigurationError"),
+            Self::Refused => write!(f, "Refused"),
         }
     }
 }
@@ -105,6 +135,32 @@ pub enum SubsystemStatus {
     Disabled,
 }
 
+/// Machine-readable description of what a subsystem supports.
+///
+/// Advertised through the registry so other components can check for a
+/// message type or feature instead of assuming every subsystem is enabled.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubsystemCapabilities {
+    /// Capability version, bumped when supported messages change meaning.
+    pub version: u32,
+    /// Message types this subsystem accepts (IPC message or API query names).
+    pub message_types: Vec<String>,
+    /// Optional feature flags that are enabled.
+    pub features: Vec<String>,
+}
+
+impl SubsystemCapabilities {
+    /// Check if a message type is accepted.
+    pub fn supports(&self, message_type: &str) -> bool {
+        self.message_types.iter().any(|m| m == message_type)
+    }
+
+    /// Check if a feature flag is enabled.
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+}
+
 /// Metadata about a subsystem for discovery and monitoring.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubsystemInfo {
@@ -124,6 +180,9 @@ pub struct SubsystemInfo {
     pub subscribes: Vec<String>,
     /// Whether this subsystem is required for basic operation.
     pub required: bool,
+    /// Supported message types and feature flags.
+    #[serde(default)]
+    pub capabilities: SubsystemCapabilities,
 }
 
 impl SubsystemInfo {
@@ -138,6 +197,7 @@ impl SubsystemInfo {
             publishes: Vec::new(),
             subscribes: Vec::new(),
             required: false,
+            capabilities: SubsystemCapabilities::default(),
         }
     }
 
@@ -164,6 +224,24 @@ impl SubsystemInfo {
         self.subscribes = events.into_iter().map(String::from).collect();
         self
     }
+
+    /// Set message types this subsystem accepts.
+    pub fn accepts_messages(mut self, message_types: Vec<&str>) -> Self {
+        self.capabilities.message_types = message_types.into_iter().map(String::from).collect();
+        self
+    }
+
+    /// Set enabled feature flags.
+    pub fn with_features(mut self, features: Vec<&str>) -> Self {
+        self.capabilities.features = features.into_iter().map(String::from).collect();
+        self
+    }
+
+    /// Set the capability version.
+    pub fn with_capability_version(mut self, version: u32) -> Self {
+        self.capabilities.version = version;
+        self
+    }
 }
 
 /// The core trait that ALL subsystems must implement.
@@ -252,6 +330,26 @@ mod tests {
         assert_eq!(info.dependencies, vec![SubsystemId::SignatureVerification]);
         assert_eq!(info.publishes.len(), 2);
         assert_eq!(info.subscribes.len(), 1);
+        assert_eq!(info.capabilities, SubsystemCapabilities::default());
+    }
+
+    #[test]
+    fn test_subsystem_capabilities() {
+        let info = SubsystemInfo::new(SubsystemId::BloomFilters, "Bloom Filters")
+            .accepts_messages(vec!["BuildFilterRequest", "get_logs"])
+            .with_features(vec!["light-client-filters"])
+            .with_capability_version(2);
+
+        assert!(info.capabilities.supports("get_logs"));
+        assert!(!info.capabilities.supports("get_balance"));
+        assert!(info.capabilities.has_feature("light-client-filters"));
+        assert_eq!(info.capabilities.version, 2);
+
+        // Info serialized before capabilities existed still loads
+        let mut json = serde_json::to_value(&info).unwrap();
+        json.as_object_mut().unwrap().remove("capabilities");
+        let legacy: SubsystemInfo = serde_json::from_value(json).unwrap();
+        assert_eq!(legacy.capabilities, SubsystemCapabilities::default());
     }
 
     #[test]
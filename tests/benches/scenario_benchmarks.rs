@@ -0,0 +1,189 @@
+//! # Quantum-Chain Scenario Benchmarks
+//!
+//! Workload-driven end-to-end runs of an in-process node. Each scenario
+//! reports throughput, block-validation latency, block-commit latency and
+//! event-bus lag percentiles; the run is written as one JSON report.
+//!
+//! ## Usage
+//!
+//! Run every preset and write `target/scenario-reports/latest.json`:
+//! ```bash
+//! cargo bench -p qc-tests --bench scenario_benchmarks
+//! ```
+//!
+//! Run some presets, save the report, and fail on regressions against an
+//! earlier report (throughput drop or p50/p99 latency rise above 10%):
+//! ```bash
+//! cargo bench -p qc-tests --bench scenario_benchmarks -- \
+//!     --scenario transfers_steady --scenario contract_heavy \
+//!     --out main.json --baseline target/scenario-reports/latest.json --tolerance 10
+//! ```
+//!
+//! `QC_COMMIT` (or `git rev-parse HEAD`) is recorded as the report's commit.
+//!
+//! The node is the scenario module's hand-wired choreography over the real
+//! subsystem domains, not the node-runtime container; the report's `wiring`
+//! field records this.
+//!
+//! ## Presets
+//!
+//! | Scenario | Rate | Mix (transfer/call/deploy) | Interval | Blocks |
+//! |----------|------|----------------------------|----------|--------|
+//! | transfers_steady | 200/s | 1/0/0 | 1s | 10 |
+//! | contract_heavy | 150/s | 2/7/1 | 1s | 10 |
+//! | fast_blocks | 300/s | 6/4/0 | 250ms | 40 |
+//! | saturated | 1000/s, 500 txs/block cap | 1/0/0 | 2s | 5 |
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use qc_tests::benchmarks::scenario::{self, compare, RunReport, Scenario, ScenarioReport};
+
+struct Args {
+    scenarios: Vec<String>,
+    out: PathBuf,
+    baseline: Option<PathBuf>,
+    tolerance: f64,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        scenarios: Vec::new(),
+        out: PathBuf::from("target/scenario-reports/latest.json"),
+        baseline: None,
+        tolerance: 10.0,
+    };
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or_else(|| format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--scenario" => args.scenarios.push(value()?),
+            "--out" => args.out = value()?.into(),
+            "--baseline" => args.baseline = Some(value()?.into()),
+            "--tolerance" => {
+                args.tolerance = value()?.parse().map_err(|e| format!("--tolerance: {e}"))?
+            }
+            // Passed by `cargo bench`
+            "--bench" => {}
+            other => return Err(format!("unknown argument {other}")),
+        }
+    }
+    Ok(args)
+}
+
+fn commit() -> Option<String> {
+    if let Ok(commit) = std::env::var("QC_COMMIT") {
+        return Some(commit);
+    }
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn print_report(report: &ScenarioReport) {
+    println!(
+        "{:<18} {:>8.1} tx/s  confirmed {}/{} (rejected {}) in {} blocks, {} ms",
+        report.scenario.name,
+        report.throughput_tps,
+        report.confirmed,
+        report.submitted,
+        report.rejected,
+        report.blocks_stored,
+        report.elapsed_ms
+    );
+    for (label, p) in [
+        ("block validation", &report.block_validation),
+        ("block commit", &report.block_commit),
+        ("event-bus lag", &report.bus_lag),
+    ] {
+        println!(
+            "    {:<17} p50 {:>7} us  p90 {:>7} us  p99 {:>7} us  max {:>7} us",
+            label, p.p50_us, p.p90_us, p.p99_us, p.max_us
+        );
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("scenario_benchmarks: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let scenarios = if args.scenarios.is_empty() {
+        Scenario::presets()
+    } else {
+        let mut selected = Vec::new();
+        for name in &args.scenarios {
+            match Scenario::preset(name) {
+                Some(scenario) => selected.push(scenario),
+                None => {
+                    eprintln!("scenario_benchmarks: no scenario named {name}");
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        selected
+    };
+
+    let runtime = tokio::runtime::Runtime::new().expect("tokio runtime");
+    let mut run = RunReport {
+        commit: commit(),
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        wiring: scenario::WIRING.to_string(),
+        scenarios: Vec::new(),
+    };
+    println!("node: {}", run.wiring);
+    for scenario in &scenarios {
+        let report = runtime.block_on(scenario::run(scenario));
+        print_report(&report);
+        run.scenarios.push(report);
+    }
+
+    // Read the baseline first: it may be the file about to be overwritten
+    let baseline = match args.baseline.as_deref().map(RunReport::read).transpose() {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("scenario_benchmarks: baseline: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(e) = run.write(&args.out) {
+        eprintln!("scenario_benchmarks: {}: {e}", args.out.display());
+        return ExitCode::FAILURE;
+    }
+    println!("report written to {}", args.out.display());
+
+    let Some(baseline) = baseline else {
+        return ExitCode::SUCCESS;
+    };
+    let regressions = compare(&baseline, &run, args.tolerance);
+    if regressions.is_empty() {
+        println!(
+            "no regressions against {} (tolerance {}%)",
+            baseline.commit.as_deref().unwrap_or("baseline"),
+            args.tolerance
+        );
+        return ExitCode::SUCCESS;
+    }
+    eprintln!(
+        "{} regression(s) against {}:",
+        regressions.len(),
+        baseline.commit.as_deref().unwrap_or("baseline")
+    );
+    for regression in &regressions {
+        eprintln!("    {regression}");
+    }
+    ExitCode::FAILURE
+}
@@ -2,6 +2,9 @@
 //!
 //! Performance benchmarks per subsystem.
 //! All benchmarks are "brutal" stress tests validating SPEC claims.
+//!
+//! `scenario` drives a whole in-process node with a workload instead of a
+//! single subsystem; see `benches/scenario_benchmarks.rs`.
 
 pub mod qc_01_peer_discovery;
 pub mod qc_02_block_storage;
@@ -11,6 +14,7 @@ pub mod qc_06_mempool;
 pub mod qc_07_bloom_filters;
 pub mod qc_08_consensus;
 pub mod qc_10_signature;
+pub mod scenario;
 
 /// Re-export all benchmarks under the "brutal" namespace for the bench harness.
 pub mod brutal {
@@ -0,0 +1,250 @@
+//! # Workload Scenarios
+//!
+//! End-to-end benchmarks that drive a full in-process node with a
+//! synthetic workload and report what an operator would watch:
+//!
+//! - **Throughput**: confirmed transactions per second of wall time
+//! - **Block validation**: consensus checking a proposed block's signatures
+//!   and sealing it, up to `BlockValidated`
+//! - **Block commit**: `BlockValidated` → `BlockStored`, i.e. the whole
+//!   choreography (Merkle root, state root, assembly)
+//! - **Event-bus lag**: publish → receive for every choreography event
+//!
+//! A [`Scenario`] fixes the submission rate, contract mix and block
+//! interval. The workload is generated and signed from the scenario's seed
+//! before the clock starts, then replayed in real time against a
+//! [`node::ScenarioNode`] built from the real subsystem domains wired over
+//! `shared_bus::InMemoryEventBus`.
+//!
+//! The scenario node hand-wires its own choreography instead of starting
+//! the node-runtime container, so the router, IPC authentication, storage
+//! backend and P2P are not measured. Each report's `wiring` field says so,
+//! and results are for comparing commits, not for sizing a deployment.
+//!
+//! Reports serialize to JSON so runs on two commits can be compared with
+//! [`report::compare`]:
+//!
+//! ```bash
+//! cargo bench -p qc-tests --bench scenario_benchmarks -- --out before.json
+//! # ...switch commits...
+//! cargo bench -p qc-tests --bench scenario_benchmarks -- --baseline before.json
+//! ```
+
+pub mod node;
+pub mod report;
+pub mod workload;
+
+pub use node::{ScenarioNode, WIRING};
+pub use report::{compare, Percentiles, Regression, RunReport, ScenarioReport};
+pub use workload::{TxKind, Workload};
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Relative weights of the transaction kinds in a workload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractMix {
+    /// Plain value transfers
+    pub transfer: u32,
+    /// Calls writing one storage slot of a genesis contract
+    pub call: u32,
+    /// Contract deployments writing initial storage
+    pub deploy: u32,
+}
+
+impl ContractMix {
+    /// Transfers only.
+    pub fn transfers() -> Self {
+        Self {
+            transfer: 1,
+            call: 0,
+            deploy: 0,
+        }
+    }
+}
+
+/// One benchmark workload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scenario {
+    /// Name used in reports and on the command line
+    pub name: String,
+    /// Transactions submitted per second
+    pub tx_rate: u32,
+    /// Mix of transaction kinds
+    pub mix: ContractMix,
+    /// Time between block proposals
+    pub block_interval_ms: u64,
+    /// Blocks proposed while the workload is submitted
+    pub blocks: u32,
+    /// Funded sender accounts the workload is spread over
+    pub senders: u32,
+    /// Transaction cap per block
+    pub max_txs_per_block: usize,
+    /// Gas cap per block
+    pub block_gas_limit: u64,
+    /// Seed for workload generation
+    pub seed: u64,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            name: "default".into(),
+            tx_rate: 200,
+            mix: ContractMix::transfers(),
+            block_interval_ms: 1_000,
+            blocks: 10,
+            senders: 128,
+            max_txs_per_block: 1_000,
+            block_gas_limit: 30_000_000,
+            seed: 42,
+        }
+    }
+}
+
+impl Scenario {
+    /// Time the workload is submitted over.
+    pub fn duration(&self) -> Duration {
+        self.block_interval() * self.blocks
+    }
+
+    /// Time between block proposals.
+    pub fn block_interval(&self) -> Duration {
+        Duration::from_millis(self.block_interval_ms)
+    }
+
+    /// Transactions the workload submits.
+    pub fn total_transactions(&self) -> usize {
+        (self.tx_rate as u64 * self.duration().as_millis() as u64 / 1_000) as usize
+    }
+
+    /// Built-in scenarios run by `scenario_benchmarks`.
+    pub fn presets() -> Vec<Scenario> {
+        vec![
+            Scenario {
+                name: "transfers_steady".into(),
+                ..Scenario::default()
+            },
+            Scenario {
+                name: "contract_heavy".into(),
+                tx_rate: 150,
+                mix: ContractMix {
+                    transfer: 2,
+                    call: 7,
+                    deploy: 1,
+                },
+                ..Scenario::default()
+            },
+            Scenario {
+                name: "fast_blocks".into(),
+                tx_rate: 300,
+                mix: ContractMix {
+                    transfer: 6,
+                    call: 4,
+                    deploy: 0,
+                },
+                block_interval_ms: 250,
+                blocks: 40,
+                ..Scenario::default()
+            },
+            Scenario {
+                name: "saturated".into(),
+                tx_rate: 1_000,
+                block_interval_ms: 2_000,
+                blocks: 5,
+                senders: 512,
+                max_txs_per_block: 500,
+                ..Scenario::default()
+            },
+        ]
+    }
+
+    /// Look up a preset by name.
+    pub fn preset(name: &str) -> Option<Scenario> {
+        Self::presets().into_iter().find(|s| s.name == name)
+    }
+}
+
+/// Generate the scenario's workload, run it on a fresh node and report.
+pub async fn run(scenario: &Scenario) -> ScenarioReport {
+    let workload = Workload::generate(scenario);
+    ScenarioNode::new(scenario, &workload)
+        .run(scenario, workload)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiny(name: &str, mix: ContractMix) -> Scenario {
+        Scenario {
+            name: name.into(),
+            tx_rate: 60,
+            mix,
+            block_interval_ms: 100,
+            blocks: 4,
+            senders: 8,
+            ..Scenario::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_scenario_confirms_workload() {
+        let scenario = tiny("tiny_transfers", ContractMix::transfers());
+        let report = run(&scenario).await;
+
+        assert_eq!(report.submitted, scenario.total_transactions() as u64);
+        assert_eq!(report.rejected, 0);
+        assert_eq!(report.confirmed, report.submitted);
+        assert!(report.blocks_stored >= 1);
+        assert!(report.throughput_tps > 0.0);
+        assert_eq!(
+            report.block_validation.samples, report.blocks_stored,
+            "one validation sample per stored block"
+        );
+        // BlockValidated reaches three stages; each root reaches storage once
+        assert_eq!(report.bus_lag.samples, report.blocks_stored * 6);
+    }
+
+    #[tokio::test]
+    async fn test_contract_mix_scenario_confirms_workload() {
+        let scenario = tiny(
+            "tiny_contracts",
+            ContractMix {
+                transfer: 1,
+                call: 2,
+                deploy: 1,
+            },
+        );
+        let report = run(&scenario).await;
+
+        assert_eq!(report.confirmed, report.submitted);
+        assert!(report.block_commit.p99_us >= report.block_commit.p50_us);
+    }
+
+    #[tokio::test]
+    async fn test_block_cap_leaves_backlog() {
+        let scenario = Scenario {
+            max_txs_per_block: 5,
+            ..tiny("tiny_capped", ContractMix::transfers())
+        };
+        let report = run(&scenario).await;
+
+        assert!(report.confirmed <= 5 * report.blocks_stored);
+        assert!(report.confirmed < report.submitted);
+    }
+
+    #[test]
+    fn test_presets_are_unique_and_nonempty() {
+        let presets = Scenario::presets();
+        for scenario in &presets {
+            assert!(scenario.total_transactions() > 0, "{}", scenario.name);
+            assert_eq!(Scenario::preset(&scenario.name).as_ref(), Some(scenario));
+        }
+        let mut names: Vec<_> = presets.iter().map(|s| &s.name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), presets.len());
+    }
+}
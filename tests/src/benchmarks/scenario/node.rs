@@ -0,0 +1,588 @@
+//! # In-Process Node
+//!
+//! The block choreography with every stage in its own task, talking over
+//! `shared_bus::InMemoryEventBus`:
+//!
+//! ```text
+//! submit ──► qc-10 verify ──► qc-06 mempool
+//!                                  │ every block interval
+//!                                  ▼
+//!                 qc-08 consensus (verify block signatures, seal)
+//!                                  │ BlockValidated
+//!               ┌──────────────────┼──────────────────┐
+//!               ▼                  ▼                  ▼
+//!        qc-03 Merkle root   qc-04 state root   qc-02 assembler
+//!               │ MerkleRootComputed │ StateRootComputed ▲
+//!               └────────────────────┴──────────────────┘
+//!                                  │ BlockStored
+//!                                  ▼
+//!                         qc-06 confirm included
+//! ```
+//!
+//! The domains are the real ones, but the choreography is hand-wired here:
+//! node-runtime is not a dependency of this crate, so its container,
+//! choreography router, IPC authentication, persistent storage and P2P are
+//! not in the path. The numbers measure the domains and the bus, not a
+//! deployed node; [`WIRING`] records this in every report. Every publish is
+//! timestamped so each receiving stage records its event-bus lag.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use qc_02_block_storage::{AssemblyConfig, BlockAssemblyBuffer};
+use qc_03_transaction_indexing::{IndexConfig, MerkleTree, TransactionIndex, TransactionLocation};
+use qc_04_state_management::{AccountState, PatriciaMerkleTrie};
+use qc_06_mempool::{MempoolConfig, MempoolTransaction, TransactionPool};
+use qc_10_signature_verification::{keccak256, EcdsaSignature, EcdsaVerifier};
+use shared_bus::{
+    BlockchainEvent, EventFilter, EventPublisher, EventTopic, InMemoryEventBus, Subscription,
+};
+use shared_types::{
+    Address, BlockHeader, ConsensusProof, Hash, SignedTransaction, Transaction, ValidatedBlock,
+    ValidatedTransaction,
+};
+
+use super::report::{Percentiles, ScenarioReport};
+use super::workload::{contract_address, TxKind, Workload, DEPLOY_STORAGE_SLOTS, GENESIS_BALANCE};
+use super::Scenario;
+
+/// What a run measures, recorded as [`RunReport::wiring`].
+///
+/// [`RunReport::wiring`]: super::RunReport::wiring
+pub const WIRING: &str = "hand-wired in-process choreography over InMemoryEventBus; \
+     real subsystem domains, not the node-runtime container (no choreography router, \
+     IPC authentication, persistent storage or P2P)";
+
+/// How long a run waits for proposed blocks to be stored.
+const SETTLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Measurements shared by every stage.
+#[derive(Default)]
+struct Metrics {
+    /// Publish time per (block, topic)
+    published: Mutex<HashMap<(Hash, EventTopic), Instant>>,
+    validation: Mutex<Vec<Duration>>,
+    commit: Mutex<Vec<Duration>>,
+    bus_lag: Mutex<Vec<Duration>>,
+    rejected: AtomicU64,
+    confirmed: AtomicU64,
+    blocks_stored: AtomicU64,
+    blocks_confirmed: AtomicU64,
+    last_stored: Mutex<Option<Instant>>,
+}
+
+impl Metrics {
+    /// Record the lag of the `topic` event for `block_hash`.
+    fn received(&self, block_hash: Hash, topic: EventTopic) {
+        if let Some(at) = self.published.lock().get(&(block_hash, topic)) {
+            self.bus_lag.lock().push(at.elapsed());
+        }
+    }
+}
+
+/// Subsystem state shared by the stage tasks.
+struct NodeState {
+    bus: Arc<InMemoryEventBus>,
+    verifier: EcdsaVerifier,
+    mempool: Mutex<TransactionPool>,
+    index: Mutex<TransactionIndex>,
+    trie: Mutex<PatriciaMerkleTrie>,
+    assembly: Mutex<BlockAssemblyBuffer>,
+    contracts: Vec<Address>,
+    /// Transactions proposed per block, confirmed once it is stored
+    proposed: Mutex<HashMap<Hash, Vec<Hash>>>,
+    metrics: Metrics,
+}
+
+impl NodeState {
+    async fn publish(&self, block_hash: Hash, event: BlockchainEvent) {
+        self.metrics
+            .published
+            .lock()
+            .insert((block_hash, event.topic()), Instant::now());
+        self.bus.publish(event).await;
+    }
+
+    /// qc-10: verify a submission's signature against its sender.
+    ///
+    /// The signature carries no recovery ID, so both are tried.
+    fn verify(&self, tx: &SignedTransaction) -> bool {
+        let hash = tx.hash();
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&tx.signature[..32]);
+        s.copy_from_slice(&tx.signature[32..]);
+        [27u8, 28].into_iter().any(|v| {
+            self.verifier
+                .verify_ecdsa_signer(&hash, &EcdsaSignature { r, s, v }, tx.from)
+                .valid
+        })
+    }
+
+    /// qc-10 then qc-06: admit a submission to the mempool.
+    fn submit(&self, tx: SignedTransaction) -> bool {
+        if !self.verify(&tx) {
+            return false;
+        }
+        self.mempool
+            .lock()
+            .add(MempoolTransaction::new(tx, now_ms()))
+            .is_ok()
+    }
+
+    /// qc-08: propose, validate and publish the block at `height`.
+    ///
+    /// Returns the block hash, or `None` if the mempool had nothing to offer.
+    async fn propose(&self, scenario: &Scenario, height: u64, parent_hash: Hash) -> Option<Hash> {
+        let txs: Vec<SignedTransaction> = {
+            let mut pool = self.mempool.lock();
+            let (hashes, txs): (Vec<Hash>, Vec<SignedTransaction>) = pool
+                .get_for_block(scenario.max_txs_per_block, scenario.block_gas_limit)
+                .into_iter()
+                .map(|tx| (tx.hash, tx.transaction.clone()))
+                .unzip();
+            pool.propose(&hashes, height, now_ms());
+            txs
+        };
+        if txs.is_empty() {
+            return None;
+        }
+
+        let started = Instant::now();
+        let transactions: Vec<ValidatedTransaction> = txs
+            .iter()
+            .filter(|tx| self.verify(tx))
+            .map(validated_tx)
+            .collect();
+        let block = seal(height, parent_hash, transactions);
+        self.metrics.validation.lock().push(started.elapsed());
+
+        let block_hash = block.consensus_proof.block_hash;
+        self.proposed.lock().insert(
+            block_hash,
+            block.transactions.iter().map(|tx| tx.tx_hash).collect(),
+        );
+        self.publish(block_hash, BlockchainEvent::BlockValidated(block))
+            .await;
+        Some(block_hash)
+    }
+
+    /// qc-03: Merkle root and transaction locations of a validated block.
+    async fn index_block(&self, block: &ValidatedBlock) {
+        let block_hash = block.consensus_proof.block_hash;
+        let hashes: Vec<Hash> = block.transactions.iter().map(|tx| tx.tx_hash).collect();
+        let tree = MerkleTree::build(hashes.clone());
+        let merkle_root = tree.root();
+        {
+            let mut index = self.index.lock();
+            for (tx_index, tx_hash) in hashes.into_iter().enumerate() {
+                index.put_location(
+                    tx_hash,
+                    TransactionLocation {
+                        block_height: block.header.height,
+                        block_hash,
+                        tx_index,
+                        merkle_root,
+                    },
+                );
+            }
+            index.cache_tree(block_hash, tree);
+        }
+        self.publish(
+            block_hash,
+            BlockchainEvent::MerkleRootComputed {
+                block_hash,
+                merkle_root,
+            },
+        )
+        .await;
+    }
+
+    /// qc-04: apply a validated block and publish the state root.
+    async fn apply_block(&self, block: &ValidatedBlock) {
+        let block_hash = block.consensus_proof.block_hash;
+        let state_root = {
+            let mut trie = self.trie.lock();
+            for tx in &block.transactions {
+                apply_transaction(&mut trie, &tx.inner, &self.contracts);
+            }
+            trie.root_hash()
+        };
+        self.publish(
+            block_hash,
+            BlockchainEvent::StateRootComputed {
+                block_hash,
+                state_root,
+            },
+        )
+        .await;
+    }
+
+    /// qc-02: collect the three components and store the block when complete.
+    async fn assemble(&self, event: BlockchainEvent) {
+        let now = now_ms() / 1000;
+        let block_hash = {
+            let mut assembly = self.assembly.lock();
+            match event {
+                BlockchainEvent::BlockValidated(block) => {
+                    let block_hash = block.consensus_proof.block_hash;
+                    assembly.add_block_validated(block_hash, block, now);
+                    block_hash
+                }
+                BlockchainEvent::MerkleRootComputed {
+                    block_hash,
+                    merkle_root,
+                } => {
+                    assembly.add_merkle_root(block_hash, merkle_root, now);
+                    block_hash
+                }
+                BlockchainEvent::StateRootComputed {
+                    block_hash,
+                    state_root,
+                } => {
+                    assembly.add_state_root(block_hash, state_root, now);
+                    block_hash
+                }
+                _ => return,
+            }
+        };
+
+        let Some(stored) = self.assembly.lock().take_complete(&block_hash) else {
+            return;
+        };
+        let metrics = &self.metrics;
+        if let Some(at) = metrics
+            .published
+            .lock()
+            .get(&(block_hash, EventTopic::Consensus))
+        {
+            metrics.commit.lock().push(at.elapsed());
+        }
+        let tx_count = stored
+            .validated_block
+            .as_ref()
+            .map_or(0, |block| block.transactions.len());
+        metrics
+            .confirmed
+            .fetch_add(tx_count as u64, Ordering::SeqCst);
+        metrics.blocks_stored.fetch_add(1, Ordering::SeqCst);
+        *metrics.last_stored.lock() = Some(Instant::now());
+
+        self.publish(
+            block_hash,
+            BlockchainEvent::BlockStored {
+                block_height: stored.block_height,
+                block_hash,
+            },
+        )
+        .await;
+    }
+
+    /// qc-06: drop a stored block's transactions from the pool.
+    fn confirm(&self, block_hash: &Hash) {
+        if let Some(hashes) = self.proposed.lock().remove(block_hash) {
+            self.mempool.lock().confirm(&hashes);
+        }
+        self.metrics.blocks_confirmed.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// A node ready to run one scenario.
+pub struct ScenarioNode {
+    state: Arc<NodeState>,
+}
+
+impl ScenarioNode {
+    /// Build a node whose genesis funds the workload's senders and deploys
+    /// its contracts.
+    pub fn new(scenario: &Scenario, workload: &Workload) -> Self {
+        let mut trie = PatriciaMerkleTrie::new();
+        for sender in &workload.senders {
+            trie.set_balance(*sender, GENESIS_BALANCE)
+                .expect("genesis balance");
+        }
+        for contract in &workload.contracts {
+            let account = AccountState {
+                code_hash: keccak256(contract),
+                ..AccountState::default()
+            };
+            trie.insert_account(*contract, &account)
+                .expect("genesis contract");
+        }
+
+        let per_sender = scenario.total_transactions() / workload.senders.len().max(1);
+        let mempool = MempoolConfig {
+            max_per_account: (per_sender * 2).max(16),
+            ..MempoolConfig::default()
+        };
+
+        Self {
+            state: Arc::new(NodeState {
+                bus: Arc::new(InMemoryEventBus::new()),
+                verifier: EcdsaVerifier::new(),
+                mempool: Mutex::new(TransactionPool::new(mempool)),
+                index: Mutex::new(TransactionIndex::new(IndexConfig::default())),
+                trie: Mutex::new(trie),
+                assembly: Mutex::new(BlockAssemblyBuffer::new(AssemblyConfig::default())),
+                contracts: workload.contracts.clone(),
+                proposed: Mutex::new(HashMap::new()),
+                metrics: Metrics::default(),
+            }),
+        }
+    }
+
+    /// Replay `workload` in real time and report once every proposed block
+    /// is stored.
+    pub async fn run(self, scenario: &Scenario, workload: Workload) -> ScenarioReport {
+        let stages = self.spawn_stages();
+        let state = &self.state;
+        let started = Instant::now();
+        let submitted = workload.transactions.len() as u64;
+
+        let submitter = tokio::spawn(submit_paced(
+            Arc::clone(state),
+            workload.transactions,
+            scenario.tx_rate,
+        ));
+
+        let mut ticks = tokio::time::interval_at(
+            tokio::time::Instant::now() + scenario.block_interval(),
+            scenario.block_interval(),
+        );
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut submitter = Some(submitter);
+        let mut parent_hash = [0u8; 32];
+        let mut height = 1;
+        for block in 1..=scenario.blocks {
+            ticks.tick().await;
+            // The last block waits for the whole workload to be offered
+            if block == scenario.blocks {
+                if let Some(submitter) = submitter.take() {
+                    let _ = submitter.await;
+                }
+            }
+            if let Some(block_hash) = state.propose(scenario, height, parent_hash).await {
+                parent_hash = block_hash;
+                height += 1;
+            }
+        }
+
+        let proposed = height - 1;
+        let deadline = Instant::now() + SETTLE_TIMEOUT;
+        while state.metrics.blocks_confirmed.load(Ordering::SeqCst) < proposed
+            && Instant::now() < deadline
+        {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        for stage in stages {
+            stage.abort();
+        }
+
+        let metrics = &state.metrics;
+        let finished = metrics.last_stored.lock().unwrap_or_else(Instant::now);
+        let elapsed = finished.duration_since(started);
+        let confirmed = metrics.confirmed.load(Ordering::SeqCst);
+        ScenarioReport {
+            scenario: scenario.clone(),
+            elapsed_ms: elapsed.as_millis() as u64,
+            submitted,
+            rejected: metrics.rejected.load(Ordering::SeqCst),
+            confirmed,
+            blocks_stored: metrics.blocks_stored.load(Ordering::SeqCst),
+            throughput_tps: confirmed as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+            block_validation: Percentiles::from_samples(metrics.validation.lock().clone()),
+            block_commit: Percentiles::from_samples(metrics.commit.lock().clone()),
+            bus_lag: Percentiles::from_samples(metrics.bus_lag.lock().clone()),
+        }
+    }
+
+    /// Subscribe every stage before anything is published, then spawn them.
+    fn spawn_stages(&self) -> Vec<JoinHandle<()>> {
+        let bus = &self.state.bus;
+        let subscribe = |topics: Vec<EventTopic>| bus.subscribe(EventFilter::topics(topics));
+
+        vec![
+            self.spawn_stage(
+                subscribe(vec![EventTopic::Consensus]),
+                |state, event| async move {
+                    if let BlockchainEvent::BlockValidated(block) = &event {
+                        state.index_block(block).await;
+                    }
+                },
+            ),
+            self.spawn_stage(
+                subscribe(vec![EventTopic::Consensus]),
+                |state, event| async move {
+                    if let BlockchainEvent::BlockValidated(block) = &event {
+                        state.apply_block(block).await;
+                    }
+                },
+            ),
+            self.spawn_stage(
+                subscribe(vec![
+                    EventTopic::Consensus,
+                    EventTopic::TransactionIndexing,
+                    EventTopic::StateManagement,
+                ]),
+                |state, event| async move { state.assemble(event).await },
+            ),
+            self.spawn_stage(
+                subscribe(vec![EventTopic::BlockStorage]),
+                |state, event| async move {
+                    if let BlockchainEvent::BlockStored { block_hash, .. } = event {
+                        state.confirm(&block_hash);
+                    }
+                },
+            ),
+        ]
+    }
+
+    fn spawn_stage<F, Fut>(&self, mut subscription: Subscription, handle: F) -> JoinHandle<()>
+    where
+        F: Fn(Arc<NodeState>, BlockchainEvent) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send,
+    {
+        let state = Arc::clone(&self.state);
+        tokio::spawn(async move {
+            while let Some(event) = subscription.recv().await {
+                if let Some(block_hash) = block_hash_of(&event) {
+                    state.metrics.received(block_hash, event.topic());
+                }
+                handle(Arc::clone(&state), event).await;
+            }
+        })
+    }
+}
+
+/// Submit `transactions` at `rate` per second from the run's start.
+async fn submit_paced(state: Arc<NodeState>, transactions: Vec<SignedTransaction>, rate: u32) {
+    let started = tokio::time::Instant::now();
+    let spacing = Duration::from_secs(1) / rate.max(1);
+    for (i, tx) in transactions.into_iter().enumerate() {
+        tokio::time::sleep_until(started + spacing * i as u32).await;
+        if !state.submit(tx) {
+            state.metrics.rejected.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Block a choreography event belongs to.
+fn block_hash_of(event: &BlockchainEvent) -> Option<Hash> {
+    match event {
+        BlockchainEvent::BlockValidated(block) => Some(block.consensus_proof.block_hash),
+        BlockchainEvent::MerkleRootComputed { block_hash, .. }
+        | BlockchainEvent::StateRootComputed { block_hash, .. }
+        | BlockchainEvent::BlockStored { block_hash, .. } => Some(*block_hash),
+        _ => None,
+    }
+}
+
+/// Apply one transaction the way its kind dictates. Failures leave state as
+/// is, like a reverted transaction.
+fn apply_transaction(trie: &mut PatriciaMerkleTrie, tx: &Transaction, contracts: &[Address]) {
+    let from = address_of(&tx.from);
+    let to = tx.to.as_ref().map(address_of);
+    if trie.apply_nonce_increment(from, tx.nonce).is_err() {
+        return;
+    }
+
+    match TxKind::classify(to.as_ref(), contracts) {
+        TxKind::Transfer => {
+            let to = to.expect("transfers have a recipient");
+            if trie.apply_balance_change(from, -(tx.value as i128)).is_ok() {
+                let _ = trie.apply_balance_change(to, tx.value as i128);
+            }
+        }
+        TxKind::Call => {
+            let to = to.expect("calls have a recipient");
+            if tx.data.len() >= 68 {
+                let mut key = [0u8; 32];
+                let mut value = [0u8; 32];
+                key.copy_from_slice(&tx.data[4..36]);
+                value.copy_from_slice(&tx.data[36..68]);
+                let _ = trie.set_storage(to, key, value);
+            }
+        }
+        TxKind::Deploy => {
+            let address = contract_address(&from, tx.nonce);
+            let code_hash = keccak256(&tx.data);
+            let account = AccountState {
+                code_hash,
+                ..AccountState::default()
+            };
+            if trie.insert_account(address, &account).is_ok() {
+                for slot in 0..DEPLOY_STORAGE_SLOTS {
+                    let mut key = [0u8; 32];
+                    key[31] = slot as u8;
+                    let mut preimage = code_hash.to_vec();
+                    preimage.push(slot as u8);
+                    let _ = trie.set_storage(address, key, keccak256(&preimage));
+                }
+            }
+        }
+    }
+}
+
+/// Seal a block the way chaos tests' consensus does.
+fn seal(height: u64, parent_hash: Hash, transactions: Vec<ValidatedTransaction>) -> ValidatedBlock {
+    let mut preimage = parent_hash.to_vec();
+    preimage.extend_from_slice(&height.to_le_bytes());
+    for tx in &transactions {
+        preimage.extend_from_slice(&tx.tx_hash);
+    }
+    let block_hash = keccak256(&preimage);
+
+    ValidatedBlock {
+        header: BlockHeader {
+            version: 1,
+            height,
+            parent_hash,
+            timestamp: now_ms() / 1000,
+            ..BlockHeader::default()
+        },
+        transactions,
+        consensus_proof: ConsensusProof {
+            block_hash,
+            attestations: vec![],
+            total_stake: 0,
+        },
+    }
+}
+
+fn validated_tx(tx: &SignedTransaction) -> ValidatedTransaction {
+    let widen = |address: &Address| {
+        let mut key = [0u8; 32];
+        key[..20].copy_from_slice(address);
+        key
+    };
+
+    ValidatedTransaction {
+        inner: Transaction {
+            from: widen(&tx.from),
+            to: tx.to.as_ref().map(widen),
+            value: tx.value.low_u64(),
+            nonce: tx.nonce,
+            data: tx.data.clone(),
+            signature: tx.signature,
+        },
+        tx_hash: tx.hash(),
+    }
+}
+
+fn address_of(key: &[u8; 32]) -> Address {
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&key[..20]);
+    address
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
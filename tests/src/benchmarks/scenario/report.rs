@@ -0,0 +1,287 @@
+//! # Scenario Reports
+//!
+//! JSON-serializable results of a scenario run, and the comparison that
+//! flags regressions between two runs (typically two commits).
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use super::Scenario;
+
+/// Latency distribution in microseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Percentiles {
+    /// Number of samples
+    pub samples: u64,
+    /// Median
+    pub p50_us: u64,
+    /// 90th percentile
+    pub p90_us: u64,
+    /// 99th percentile
+    pub p99_us: u64,
+    /// Slowest sample
+    pub max_us: u64,
+}
+
+impl Percentiles {
+    /// Nearest-rank percentiles of `samples`.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+        let rank = |p: u64| {
+            let index = (p as usize * samples.len()).div_ceil(100).max(1) - 1;
+            samples[index].as_micros() as u64
+        };
+        Self {
+            samples: samples.len() as u64,
+            p50_us: rank(50),
+            p90_us: rank(90),
+            p99_us: rank(99),
+            max_us: rank(100),
+        }
+    }
+}
+
+/// Result of one scenario run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioReport {
+    /// The scenario as run
+    pub scenario: Scenario,
+    /// Wall time from the first submission until the last block was stored
+    pub elapsed_ms: u64,
+    /// Transactions submitted
+    pub submitted: u64,
+    /// Submissions refused by signature verification or the mempool
+    pub rejected: u64,
+    /// Transactions in stored blocks
+    pub confirmed: u64,
+    /// Blocks stored
+    pub blocks_stored: u64,
+    /// Confirmed transactions per second of wall time
+    pub throughput_tps: f64,
+    /// Proposal → `BlockValidated`
+    pub block_validation: Percentiles,
+    /// `BlockValidated` → `BlockStored`
+    pub block_commit: Percentiles,
+    /// Publish → receive of every choreography event, including time the
+    /// receiving stage waits for a runtime worker
+    pub bus_lag: Percentiles,
+}
+
+/// Reports of one benchmark run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunReport {
+    /// Commit the run was built from, if known
+    pub commit: Option<String>,
+    /// Seconds since the Unix epoch
+    pub created_at: u64,
+    /// What the node under test is; see [`super::node::WIRING`]
+    #[serde(default)]
+    pub wiring: String,
+    /// One report per scenario
+    pub scenarios: Vec<ScenarioReport>,
+}
+
+impl RunReport {
+    /// Read a report written by [`RunReport::write`].
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        serde_json::from_str(&text)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Write as pretty-printed JSON, creating parent directories.
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let text = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, text)
+    }
+}
+
+/// A metric that got worse by more than the tolerance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    /// Scenario name
+    pub scenario: String,
+    /// Metric path, e.g. `block_commit.p99_us`
+    pub metric: String,
+    /// Baseline value
+    pub baseline: f64,
+    /// Current value
+    pub current: f64,
+}
+
+impl Regression {
+    /// Change relative to the baseline, in percent.
+    pub fn change_percent(&self) -> f64 {
+        (self.current - self.baseline) / self.baseline * 100.0
+    }
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} {:.1} -> {:.1} ({:+.1}%)",
+            self.scenario,
+            self.metric,
+            self.baseline,
+            self.current,
+            self.change_percent()
+        )
+    }
+}
+
+/// Compare `current` against `baseline` scenario by scenario.
+///
+/// Throughput regresses when it drops by more than `tolerance_percent`;
+/// latencies regress when p50 or p99 rise by more than it. Scenarios
+/// missing from either side, or whose definition changed, are skipped.
+pub fn compare(
+    baseline: &RunReport,
+    current: &RunReport,
+    tolerance_percent: f64,
+) -> Vec<Regression> {
+    let tolerance = tolerance_percent / 100.0;
+    let mut regressions = Vec::new();
+
+    for now in &current.scenarios {
+        let Some(before) = baseline
+            .scenarios
+            .iter()
+            .find(|b| b.scenario == now.scenario)
+        else {
+            continue;
+        };
+        let name = &now.scenario.name;
+
+        if before.throughput_tps > 0.0
+            && now.throughput_tps < before.throughput_tps * (1.0 - tolerance)
+        {
+            regressions.push(Regression {
+                scenario: name.clone(),
+                metric: "throughput_tps".into(),
+                baseline: before.throughput_tps,
+                current: now.throughput_tps,
+            });
+        }
+
+        let latencies = [
+            (
+                "block_validation",
+                &before.block_validation,
+                &now.block_validation,
+            ),
+            ("block_commit", &before.block_commit, &now.block_commit),
+            ("bus_lag", &before.bus_lag, &now.bus_lag),
+        ];
+        for (metric, before, now) in latencies {
+            for (percentile, was, is) in [
+                ("p50_us", before.p50_us, now.p50_us),
+                ("p99_us", before.p99_us, now.p99_us),
+            ] {
+                if was > 0 && is as f64 > was as f64 * (1.0 + tolerance) {
+                    regressions.push(Regression {
+                        scenario: name.clone(),
+                        metric: format!("{metric}.{percentile}"),
+                        baseline: was as f64,
+                        current: is as f64,
+                    });
+                }
+            }
+        }
+    }
+
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(throughput_tps: f64, commit_p99_us: u64) -> ScenarioReport {
+        ScenarioReport {
+            scenario: Scenario::default(),
+            elapsed_ms: 10_000,
+            submitted: 2_000,
+            rejected: 0,
+            confirmed: 2_000,
+            blocks_stored: 10,
+            throughput_tps,
+            block_validation: Percentiles::default(),
+            block_commit: Percentiles {
+                samples: 10,
+                p50_us: 1_000,
+                p90_us: 1_500,
+                p99_us: commit_p99_us,
+                max_us: commit_p99_us,
+            },
+            bus_lag: Percentiles::default(),
+        }
+    }
+
+    fn run(scenarios: Vec<ScenarioReport>) -> RunReport {
+        RunReport {
+            commit: Some("abc123".into()),
+            created_at: 0,
+            wiring: super::super::node::WIRING.to_string(),
+            scenarios,
+        }
+    }
+
+    #[test]
+    fn test_percentiles_nearest_rank() {
+        let samples = (1..=100).map(Duration::from_micros).collect();
+        let p = Percentiles::from_samples(samples);
+        assert_eq!(
+            (p.samples, p.p50_us, p.p90_us, p.p99_us, p.max_us),
+            (100, 50, 90, 99, 100)
+        );
+
+        let single = Percentiles::from_samples(vec![Duration::from_micros(7)]);
+        assert_eq!((single.p50_us, single.p99_us, single.max_us), (7, 7, 7));
+        assert_eq!(Percentiles::from_samples(vec![]), Percentiles::default());
+    }
+
+    #[test]
+    fn test_report_json_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reports/run.json");
+        let original = run(vec![report(200.0, 2_000)]);
+        original.write(&path).unwrap();
+        assert_eq!(RunReport::read(&path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_compare_flags_regressions_beyond_tolerance() {
+        let baseline = run(vec![report(200.0, 2_000)]);
+
+        let within = run(vec![report(190.0, 2_100)]);
+        assert!(compare(&baseline, &within, 10.0).is_empty());
+
+        let worse = run(vec![report(150.0, 3_000)]);
+        let regressions = compare(&baseline, &worse, 10.0);
+        let metrics: Vec<_> = regressions.iter().map(|r| r.metric.as_str()).collect();
+        assert_eq!(metrics, vec!["throughput_tps", "block_commit.p99_us"]);
+        assert_eq!(regressions[0].change_percent(), -25.0);
+        assert_eq!(
+            regressions[1].to_string(),
+            "default: block_commit.p99_us 2000.0 -> 3000.0 (+50.0%)"
+        );
+    }
+
+    #[test]
+    fn test_compare_skips_changed_scenarios() {
+        let baseline = run(vec![report(200.0, 2_000)]);
+        let mut changed = report(100.0, 9_000);
+        changed.scenario.tx_rate += 1;
+        assert!(compare(&baseline, &run(vec![changed]), 10.0).is_empty());
+    }
+}
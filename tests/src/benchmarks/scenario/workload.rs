@@ -0,0 +1,222 @@
+//! # Workload Generation
+//!
+//! Deterministic, pre-signed transaction streams for a [`Scenario`].
+//!
+//! Signing happens here, before a run starts, so the node's measurements
+//! only include verification. Every transaction is signed by its sender's
+//! secp256k1 key over `SignedTransaction::hash()`; like qc-10, the node
+//! does not get the recovery ID and tries both.
+
+use k256::ecdsa::SigningKey;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+
+use qc_10_signature_verification::{address_from_pubkey, keccak256};
+use shared_types::{Address, SignedTransaction, U256};
+
+use super::Scenario;
+
+/// Contracts present at genesis that `Call` transactions write to.
+pub const GENESIS_CONTRACTS: usize = 8;
+
+/// Storage slots a deployment initializes.
+pub const DEPLOY_STORAGE_SLOTS: usize = 4;
+
+/// Balance minted to every sender at genesis.
+pub const GENESIS_BALANCE: u128 = 1_000_000_000_000_000_000_000_000;
+
+/// Gas price of every workload transaction (1 gwei above the mempool floor).
+const GAS_PRICE: u64 = 2_000_000_000;
+
+/// Init code size of a deployment.
+const INIT_CODE_LEN: usize = 256;
+
+/// Kind of workload transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TxKind {
+    /// Value transfer between senders
+    Transfer,
+    /// `set(bytes32,bytes32)` on a genesis contract
+    Call,
+    /// Contract creation
+    Deploy,
+}
+
+impl TxKind {
+    /// Gas limit the workload gives this kind.
+    pub fn gas_limit(&self) -> u64 {
+        match self {
+            Self::Transfer => 21_000,
+            Self::Call => 60_000,
+            Self::Deploy => 250_000,
+        }
+    }
+
+    /// Classify by recipient, the way state management dispatches.
+    pub fn classify(to: Option<&Address>, contracts: &[Address]) -> Self {
+        match to {
+            None => Self::Deploy,
+            Some(to) if contracts.contains(to) => Self::Call,
+            Some(_) => Self::Transfer,
+        }
+    }
+}
+
+/// Selector of the storage-writing call.
+pub fn call_selector() -> [u8; 4] {
+    let hash = keccak256(b"set(bytes32,bytes32)");
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Address a deployment by `from` at `nonce` creates.
+pub fn contract_address(from: &Address, nonce: u64) -> Address {
+    let mut preimage = Vec::with_capacity(28);
+    preimage.extend_from_slice(from);
+    preimage.extend_from_slice(&nonce.to_be_bytes());
+    let hash = keccak256(&preimage);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// A scenario's accounts and signed transactions in submission order.
+pub struct Workload {
+    /// Funded sender accounts
+    pub senders: Vec<Address>,
+    /// Contracts deployed at genesis
+    pub contracts: Vec<Address>,
+    /// Transactions in submission order
+    pub transactions: Vec<SignedTransaction>,
+    /// Count per kind
+    pub kinds: HashMap<TxKind, usize>,
+}
+
+impl Workload {
+    /// Generate and sign the scenario's workload from its seed.
+    pub fn generate(scenario: &Scenario) -> Self {
+        let mut rng = StdRng::seed_from_u64(scenario.seed);
+
+        let keys: Vec<SigningKey> = (0..scenario.senders.max(2))
+            .map(|_| SigningKey::random(&mut rng))
+            .collect();
+        let senders: Vec<Address> = keys
+            .iter()
+            .map(|key| address_from_pubkey(key.verifying_key()))
+            .collect();
+        let contracts: Vec<Address> = (0..GENESIS_CONTRACTS)
+            .map(|i| {
+                let mut address = [0xC0; 20];
+                address[19] = i as u8;
+                address
+            })
+            .collect();
+
+        let mix = scenario.mix;
+        let weight = (mix.transfer + mix.call + mix.deploy).max(1);
+        let mut nonces = vec![0u64; keys.len()];
+        let mut kinds = HashMap::new();
+        let mut transactions = Vec::with_capacity(scenario.total_transactions());
+
+        for _ in 0..scenario.total_transactions() {
+            let sender = rng.gen_range(0..keys.len());
+            let roll = rng.gen_range(0..weight);
+            let kind = if roll < mix.transfer {
+                TxKind::Transfer
+            } else if roll < mix.transfer + mix.call {
+                TxKind::Call
+            } else {
+                TxKind::Deploy
+            };
+
+            let (to, data) = match kind {
+                TxKind::Transfer => {
+                    let recipient = (sender + rng.gen_range(1..senders.len())) % senders.len();
+                    (Some(senders[recipient]), Vec::new())
+                }
+                TxKind::Call => {
+                    let mut data = call_selector().to_vec();
+                    data.extend_from_slice(&rng.gen::<[u8; 32]>());
+                    data.extend_from_slice(&rng.gen::<[u8; 32]>());
+                    (Some(contracts[rng.gen_range(0..contracts.len())]), data)
+                }
+                TxKind::Deploy => {
+                    let mut code = vec![0u8; INIT_CODE_LEN];
+                    rng.fill(&mut code[..]);
+                    (None, code)
+                }
+            };
+
+            let mut tx = SignedTransaction {
+                from: senders[sender],
+                to,
+                value: U256::from(if kind == TxKind::Transfer {
+                    1_000u64
+                } else {
+                    0
+                }),
+                nonce: nonces[sender],
+                gas_price: U256::from(GAS_PRICE),
+                gas_limit: kind.gas_limit(),
+                data,
+                signature: [0u8; 64],
+            };
+            let (signature, _) = keys[sender]
+                .sign_prehash_recoverable(&tx.hash())
+                .expect("prehash is 32 bytes");
+            tx.signature.copy_from_slice(&signature.to_bytes());
+
+            nonces[sender] += 1;
+            *kinds.entry(kind).or_insert(0) += 1;
+            transactions.push(tx);
+        }
+
+        Self {
+            senders,
+            contracts,
+            transactions,
+            kinds,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::scenario::ContractMix;
+
+    #[test]
+    fn test_workload_is_deterministic_and_follows_mix() {
+        let scenario = Scenario {
+            tx_rate: 100,
+            blocks: 2,
+            senders: 4,
+            mix: ContractMix {
+                transfer: 1,
+                call: 1,
+                deploy: 1,
+            },
+            ..Scenario::default()
+        };
+        let a = Workload::generate(&scenario);
+        let b = Workload::generate(&scenario);
+
+        assert_eq!(a.transactions.len(), scenario.total_transactions());
+        let hashes = |w: &Workload| {
+            w.transactions
+                .iter()
+                .map(|tx| tx.hash())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(hashes(&a), hashes(&b));
+        for kind in [TxKind::Transfer, TxKind::Call, TxKind::Deploy] {
+            assert!(a.kinds[&kind] > 0, "{kind:?} missing from the mix");
+        }
+        for tx in &a.transactions {
+            assert_eq!(
+                TxKind::classify(tx.to.as_ref(), &a.contracts).gas_limit(),
+                tx.gas_limit
+            );
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! # Merkle Tree Differential Tests
+//!
+//! Compares qc-03's `MerkleTree` with a reference written from SPEC-03:
+//!
+//! - leaves are the transaction hashes, in block order
+//! - padded with `SENTINEL_HASH` to a power of two, and to at least two
+//! - each parent is `SHA3-256(0x01 || left || right)`
+//! - an empty block has the sentinel root
+//!
+//! The reference folds one `Vec` per level instead of indexing a flat
+//! array, so a layout slip in either of qc-03's builders shows up as a root
+//! or proof-path mismatch.
+//!
+//! Update sequences edit a block's transaction list and rebuild; reorg
+//! sequences replace the chain tip in a `TransactionIndex` and check that
+//! cached trees follow the canonical chain and orphaned proofs stop
+//! verifying.
+
+use sha3::{Digest, Sha3_256};
+
+use qc_03_transaction_indexing::{MerkleTree, SiblingPosition, SENTINEL_HASH};
+use shared_types::Hash;
+
+// =============================================================================
+// REFERENCE TREE
+// =============================================================================
+
+fn hash_pair(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha3_256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Every level of the reference tree, padded leaves first, root last.
+pub fn reference_levels(leaves: &[Hash]) -> Vec<Vec<Hash>> {
+    let width = leaves.len().next_power_of_two().max(2);
+    let mut level = leaves.to_vec();
+    level.resize(width, SENTINEL_HASH);
+
+    let mut levels = vec![level];
+    while levels[levels.len() - 1].len() > 1 {
+        let next = levels[levels.len() - 1]
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// Reference Merkle root.
+pub fn reference_root(leaves: &[Hash]) -> Hash {
+    if leaves.is_empty() {
+        return SENTINEL_HASH;
+    }
+    reference_levels(leaves).last().unwrap()[0]
+}
+
+/// Reference proof path for leaf `index` in `levels`: sibling hash, and
+/// whether the sibling sits on the left.
+pub fn reference_path(levels: &[Vec<Hash>], mut index: usize) -> Vec<(Hash, bool)> {
+    let mut path = Vec::with_capacity(levels.len() - 1);
+    for level in &levels[..levels.len() - 1] {
+        path.push((level[index ^ 1], index % 2 == 1));
+        index /= 2;
+    }
+    path
+}
+
+// =============================================================================
+// OPERATIONS
+// =============================================================================
+
+/// An edit to a block's transaction list before it is re-committed.
+#[derive(Debug, Clone)]
+pub enum BlockEdit {
+    /// Append a transaction.
+    Push(Hash),
+    /// Replace the transaction at `index % len`.
+    Replace(usize, Hash),
+    /// Remove the transaction at `index % len`.
+    Remove(usize),
+    /// Re-sort with `sort_canonically`.
+    Canonicalize,
+}
+
+/// Apply `edit` to `txs`.
+pub fn apply_edit(txs: &mut Vec<Hash>, edit: &BlockEdit) {
+    match *edit {
+        BlockEdit::Push(hash) => txs.push(hash),
+        BlockEdit::Replace(i, hash) if !txs.is_empty() => {
+            let i = i % txs.len();
+            txs[i] = hash;
+        }
+        BlockEdit::Remove(i) if !txs.is_empty() => {
+            txs.remove(i % txs.len());
+        }
+        BlockEdit::Canonicalize => qc_03_transaction_indexing::sort_canonically(txs),
+        _ => {}
+    }
+}
+
+/// Block hash committing to the parent and the transaction list.
+pub fn block_hash(parent: &Hash, txs: &[Hash]) -> Hash {
+    let mut hasher = Sha3_256::new();
+    hasher.update(parent);
+    for tx in txs {
+        hasher.update(tx);
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use qc_03_transaction_indexing::{IndexConfig, TransactionIndex};
+
+    fn txs(max: usize) -> impl Strategy<Value = Vec<Hash>> {
+        prop::collection::vec(any::<Hash>(), 0..max)
+    }
+
+    fn block_edit() -> impl Strategy<Value = BlockEdit> {
+        prop_oneof![
+            3 => any::<Hash>().prop_map(BlockEdit::Push),
+            2 => (any::<usize>(), any::<Hash>()).prop_map(|(i, h)| BlockEdit::Replace(i, h)),
+            2 => any::<usize>().prop_map(BlockEdit::Remove),
+            1 => Just(BlockEdit::Canonicalize),
+        ]
+    }
+
+    /// Root, leaf count and every proof path match the reference, and every
+    /// proof verifies against the reference root.
+    fn check_tree(tree: &MerkleTree, leaves: &[Hash]) -> Result<(), TestCaseError> {
+        let root = reference_root(leaves);
+        prop_assert_eq!(tree.root(), root);
+        prop_assert_eq!(tree.transaction_count(), leaves.len());
+        if leaves.is_empty() {
+            return Ok(());
+        }
+        let levels = reference_levels(leaves);
+        prop_assert_eq!(tree.leaf_count(), levels[0].len());
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.generate_proof(i, 1, [0; 32]).unwrap();
+            let path: Vec<_> = proof
+                .path
+                .iter()
+                .map(|node| (node.hash, node.position == SiblingPosition::Left))
+                .collect();
+            prop_assert_eq!(proof.leaf_hash, *leaf);
+            prop_assert_eq!(path, reference_path(&levels, i));
+            prop_assert!(MerkleTree::verify_proof_static(leaf, &proof.path, &root));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_reference_small_trees() {
+        let a = [0xAA; 32];
+        let b = [0xBB; 32];
+        let c = [0xCC; 32];
+
+        assert_eq!(reference_root(&[]), SENTINEL_HASH);
+        assert_eq!(reference_root(&[a]), hash_pair(&a, &SENTINEL_HASH));
+        assert_eq!(
+            reference_root(&[a, b, c]),
+            hash_pair(&hash_pair(&a, &b), &hash_pair(&c, &SENTINEL_HASH))
+        );
+        assert_eq!(
+            reference_path(&reference_levels(&[a, b, c]), 2),
+            vec![(SENTINEL_HASH, false), (hash_pair(&a, &b), true)]
+        );
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(128))]
+
+        /// Any transaction list builds the reference tree.
+        #[test]
+        fn prop_tree_matches_reference(leaves in txs(70)) {
+            check_tree(&MerkleTree::build(leaves.clone()), &leaves)?;
+        }
+
+        /// A tampered sibling or leaf never verifies against the root.
+        #[test]
+        fn prop_tampered_proof_rejected(
+            leaves in prop::collection::vec(any::<Hash>(), 1..40),
+            pick in any::<usize>(),
+            flip in any::<usize>(),
+        ) {
+            let tree = MerkleTree::build(leaves.clone());
+            let index = pick % leaves.len();
+            let proof = tree.generate_proof(index, 1, [0; 32]).unwrap();
+
+            let mut path = proof.path.clone();
+            let node = flip % path.len();
+            path[node].hash[flip % 32] ^= 0x01;
+            prop_assert!(!MerkleTree::verify_proof_static(&proof.leaf_hash, &path, &tree.root()));
+
+            let mut leaf = proof.leaf_hash;
+            leaf[flip % 32] ^= 0x01;
+            prop_assert!(!MerkleTree::verify_proof_static(&leaf, &proof.path, &tree.root()));
+        }
+
+        /// Editing a block's transactions and rebuilding tracks the
+        /// reference after every edit.
+        #[test]
+        fn prop_update_sequence_matches_reference(
+            initial in txs(24),
+            edits in prop::collection::vec(block_edit(), 1..32),
+        ) {
+            let mut leaves = initial;
+            for edit in &edits {
+                apply_edit(&mut leaves, edit);
+                check_tree(&MerkleTree::build(leaves.clone()), &leaves)?;
+            }
+        }
+
+        /// Reorg: replacing the tip blocks leaves the index serving the
+        /// canonical block's tree for every height, and proofs from an
+        /// orphaned block no longer verify against the canonical root.
+        #[test]
+        fn prop_reorg_matches_reference(
+            chain in prop::collection::vec(txs(12), 1..8),
+            depth in 1usize..8,
+            replacement in prop::collection::vec(txs(12), 1..8),
+        ) {
+            let mut index = TransactionIndex::new(IndexConfig::default());
+            let mut canonical: Vec<(Hash, Vec<Hash>)> = Vec::new();
+            let mut commit = |index: &mut TransactionIndex,
+                              canonical: &mut Vec<(Hash, Vec<Hash>)>,
+                              leaves: &[Hash]| {
+                let parent = canonical.last().map_or([0; 32], |(hash, _)| *hash);
+                let hash = block_hash(&parent, leaves);
+                index.cache_tree(hash, MerkleTree::build(leaves.to_vec()));
+                canonical.push((hash, leaves.to_vec()));
+            };
+
+            for leaves in &chain {
+                commit(&mut index, &mut canonical, leaves);
+            }
+            let fork = canonical.len().saturating_sub(depth);
+            let orphaned = canonical.split_off(fork);
+            for leaves in &replacement {
+                commit(&mut index, &mut canonical, leaves);
+            }
+
+            for (hash, leaves) in &canonical {
+                let tree = index.get_tree(hash).unwrap().clone();
+                check_tree(&tree, leaves)?;
+            }
+
+            for (height, (hash, leaves)) in orphaned.iter().enumerate() {
+                let Some((_, new_leaves)) = canonical.get(fork + height) else {
+                    continue;
+                };
+                let new_root = reference_root(new_leaves);
+                if leaves.is_empty() || reference_root(leaves) == new_root {
+                    continue;
+                }
+                let tree = index.get_tree(hash).unwrap().clone();
+                for (i, leaf) in leaves.iter().enumerate() {
+                    let proof = tree.generate_proof(i, 1, *hash).unwrap();
+                    prop_assert!(!MerkleTree::verify_proof_static(leaf, &proof.path, &new_root));
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(8))]
+
+        /// Above `PARALLEL_THRESHOLD` the parallel builder matches the
+        /// reference and the serial builder.
+        #[test]
+        fn prop_parallel_build_matches_reference(
+            leaves in prop::collection::vec(any::<Hash>(), 1024..1400),
+        ) {
+            let parallel = MerkleTree::build_parallel(leaves.clone());
+            prop_assert_eq!(parallel.root(), reference_root(&leaves));
+            prop_assert_eq!(&parallel, &MerkleTree::build(leaves));
+        }
+    }
+}
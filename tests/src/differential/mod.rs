@@ -0,0 +1,29 @@
+//! # Differential Tests
+//!
+//! Property-based suites that run the production commitment structures
+//! side by side with small, independent reference implementations written
+//! straight from the spec, over random inputs and random update/reorg
+//! sequences. Unit tests pin a handful of hand-picked cases; these catch
+//! canonical-encoding divergences that only show up for some shapes.
+//!
+//! ## Suites
+//!
+//! - `merkle` - qc-03 `MerkleTree` against a level-by-level SHA3-256 fold
+//!   (SPEC-03: sentinel padding, `0x01` node domain)
+//! - `trie` - qc-04 `PatriciaMerkleTrie` against a textbook Modified Merkle
+//!   Patricia Trie (Yellow Paper Appendix D: RLP, hex-prefix, Keccak256)
+//!
+//! The references are checked against published vectors before they are
+//! trusted as an oracle.
+//!
+//! ## Running
+//!
+//! ```bash
+//! cargo test -p qc-tests differential::
+//!
+//! # More cases
+//! PROPTEST_CASES=2000 cargo test -p qc-tests differential::
+//! ```
+
+pub mod merkle;
+pub mod trie;
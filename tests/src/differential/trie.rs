@@ -0,0 +1,478 @@
+//! # Patricia Trie Differential Tests
+//!
+//! Compares qc-04's `PatriciaMerkleTrie` with a reference Modified Merkle
+//! Patricia Trie built from Yellow Paper Appendix D:
+//!
+//! - keys are `keccak256(address)` as nibbles (secure trie)
+//! - values are `RLP([nonce, balance, storage_root, code_hash])`
+//! - nodes are RLP lists with hex-prefix paths; a child is referenced by
+//!   `keccak256(rlp(child))`, or embedded when its RLP is under 32 bytes
+//! - an empty branch slot is the RLP empty string `0x80`
+//!
+//! The reference shares no code with qc-04: its RLP, hex-prefix and node
+//! layout are written here, and it is checked against the Ethereum trie
+//! test vectors first.
+//!
+//! Random operation sequences drive both sides through the trie's public
+//! API, including reorgs: the trie is restored from a snapshot taken at the
+//! fork point and a competing branch is applied.
+
+use std::collections::BTreeMap;
+
+use sha3::{Digest, Keccak256};
+
+use qc_04_state_management::{AccountState, Address, Hash, PatriciaMerkleTrie, StateError};
+
+// =============================================================================
+// REFERENCE TRIE
+// =============================================================================
+
+/// Keccak256 of `data`.
+pub fn keccak(data: &[u8]) -> Hash {
+    Keccak256::digest(data).into()
+}
+
+/// RLP string item.
+pub fn rlp_string(data: &[u8]) -> Vec<u8> {
+    if data.len() == 1 && data[0] < 0x80 {
+        return data.to_vec();
+    }
+    let mut out = rlp_header(0x80, data.len());
+    out.extend_from_slice(data);
+    out
+}
+
+/// RLP list of already-encoded items.
+pub fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut out = rlp_header(0xc0, payload.len());
+    out.extend(payload);
+    out
+}
+
+/// RLP scalar: minimal big-endian bytes, zero is the empty string.
+pub fn rlp_uint(value: u128) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    rlp_string(&bytes[start..])
+}
+
+fn rlp_header(offset: u8, len: usize) -> Vec<u8> {
+    if len < 56 {
+        return vec![offset + len as u8];
+    }
+    let len_bytes = len.to_be_bytes();
+    let start = len_bytes.iter().position(|&b| b != 0).unwrap_or(0);
+    let mut out = vec![offset + 55 + (len_bytes.len() - start) as u8];
+    out.extend_from_slice(&len_bytes[start..]);
+    out
+}
+
+/// Hex-prefix encoding of a nibble path.
+pub fn hex_prefix(nibbles: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = if leaf { 0x20 } else { 0x00 };
+    let (mut out, rest) = if nibbles.len() % 2 == 1 {
+        (vec![flag | 0x10 | nibbles[0]], &nibbles[1..])
+    } else {
+        (vec![flag], nibbles)
+    };
+    out.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    out
+}
+
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+/// Canonical account value: `RLP([nonce, balance, storage_root, code_hash])`.
+pub fn encode_account(account: &AccountState) -> Vec<u8> {
+    rlp_list(&[
+        rlp_uint(u128::from(account.nonce)),
+        rlp_uint(account.balance),
+        rlp_string(&account.storage_root),
+        rlp_string(&account.code_hash),
+    ])
+}
+
+/// RLP of the node holding `items` (sorted, unique nibble keys) below `depth`.
+fn encode_node(items: &[(Vec<u8>, Vec<u8>)], depth: usize) -> Vec<u8> {
+    if let [(key, value)] = items {
+        return rlp_list(&[
+            rlp_string(&hex_prefix(&key[depth..], true)),
+            rlp_string(value),
+        ]);
+    }
+
+    let shared = shared_prefix(items, depth);
+    if shared > 0 {
+        let child = encode_node(items, depth + shared);
+        return rlp_list(&[
+            rlp_string(&hex_prefix(&items[0].0[depth..depth + shared], false)),
+            child_ref(child),
+        ]);
+    }
+
+    let mut slots: Vec<Vec<u8>> = (0..16u8)
+        .map(|nibble| {
+            let group = branch_group(items, depth, nibble);
+            if group.is_empty() {
+                rlp_string(&[])
+            } else {
+                child_ref(encode_node(&group, depth + 1))
+            }
+        })
+        .collect();
+    let value = items
+        .iter()
+        .find(|(key, _)| key.len() == depth)
+        .map_or_else(|| rlp_string(&[]), |(_, value)| rlp_string(value));
+    slots.push(value);
+    rlp_list(&slots)
+}
+
+/// Shared nibble prefix of all `items` below `depth` (0 for a single item).
+fn shared_prefix(items: &[(Vec<u8>, Vec<u8>)], depth: usize) -> usize {
+    let first = &items[0].0[depth..];
+    items[1..]
+        .iter()
+        .map(|(key, _)| {
+            first
+                .iter()
+                .zip(&key[depth..])
+                .take_while(|(a, b)| a == b)
+                .count()
+        })
+        .min()
+        .unwrap_or(0)
+}
+
+/// Items continuing through branch slot `nibble` at `depth`.
+fn branch_group(items: &[(Vec<u8>, Vec<u8>)], depth: usize, nibble: u8) -> Vec<(Vec<u8>, Vec<u8>)> {
+    items
+        .iter()
+        .filter(|(key, _)| key.len() > depth && key[depth] == nibble)
+        .cloned()
+        .collect()
+}
+
+/// Reference to a child node: embedded under 32 bytes, hashed otherwise.
+fn child_ref(encoded: Vec<u8>) -> Vec<u8> {
+    if encoded.len() < 32 {
+        encoded
+    } else {
+        rlp_string(&keccak(&encoded))
+    }
+}
+
+/// Root of a plain (non-secure) trie over raw byte keys.
+pub fn reference_root(entries: &BTreeMap<Vec<u8>, Vec<u8>>) -> Hash {
+    if entries.is_empty() {
+        return keccak(&rlp_string(&[]));
+    }
+    let items: Vec<_> = entries
+        .iter()
+        .map(|(key, value)| (to_nibbles(key), value.clone()))
+        .collect();
+    keccak(&encode_node(&items, 0))
+}
+
+fn state_items(accounts: &BTreeMap<Address, AccountState>) -> BTreeMap<Vec<u8>, Vec<u8>> {
+    accounts
+        .iter()
+        .map(|(address, account)| (keccak(address).to_vec(), encode_account(account)))
+        .collect()
+}
+
+/// State root of `accounts`: a secure trie keyed by `keccak256(address)`.
+pub fn reference_state_root(accounts: &BTreeMap<Address, AccountState>) -> Hash {
+    reference_root(&state_items(accounts))
+}
+
+/// RLP of every node on the path from the root towards `address`.
+///
+/// State-trie nodes are never under 32 bytes (keys are 32-byte hashes and
+/// values full account records), so none is embedded in its parent and
+/// each appears as its own proof item.
+pub fn reference_state_proof(
+    accounts: &BTreeMap<Address, AccountState>,
+    address: &Address,
+) -> Vec<Vec<u8>> {
+    let mut items: Vec<_> = state_items(accounts)
+        .into_iter()
+        .map(|(key, value)| (to_nibbles(&key), value))
+        .collect();
+    let key = to_nibbles(&keccak(address));
+    let mut depth = 0;
+    let mut proof = Vec::new();
+    while !items.is_empty() {
+        proof.push(encode_node(&items, depth));
+        if items.len() == 1 {
+            break;
+        }
+        let shared = shared_prefix(&items, depth);
+        if shared > 0 {
+            if key[depth..depth + shared] != items[0].0[depth..depth + shared] {
+                break;
+            }
+            depth += shared;
+        } else {
+            items = branch_group(&items, depth, key[depth]);
+            depth += 1;
+        }
+    }
+    proof
+}
+
+// =============================================================================
+// OPERATIONS
+// =============================================================================
+
+/// A state change, applied to the trie and to the reference model.
+///
+/// Accounts are picked by index into a small address pool so later
+/// operations keep hitting existing accounts.
+#[derive(Debug, Clone)]
+pub enum StateOp {
+    /// `insert_account`
+    Insert(usize, AccountState),
+    /// `set_balance`
+    SetBalance(usize, u128),
+    /// `increment_nonce` (fails at `u64::MAX`)
+    IncrementNonce(usize),
+    /// `mint` (saturating)
+    Mint(usize, u128),
+    /// `apply_balance_change` with a negative delta (fails on overdraft)
+    Debit(usize, u64),
+}
+
+/// Plain map of the accounts the trie should commit to.
+pub type Model = BTreeMap<Address, AccountState>;
+
+/// Apply `op` to the trie and mirror the expected outcome in `model`.
+///
+/// Returns whether the trie accepted it; a rejected op must leave both
+/// sides unchanged.
+pub fn apply(
+    trie: &mut PatriciaMerkleTrie,
+    model: &mut Model,
+    pool: &[Address],
+    op: &StateOp,
+) -> Result<(), StateError> {
+    let pick = |i: usize| pool[i % pool.len()];
+    match *op {
+        StateOp::Insert(i, ref account) => {
+            trie.insert_account(pick(i), account)?;
+            model.insert(pick(i), account.clone());
+        }
+        StateOp::SetBalance(i, balance) => {
+            trie.set_balance(pick(i), balance)?;
+            model.entry(pick(i)).or_default().balance = balance;
+        }
+        StateOp::IncrementNonce(i) => {
+            trie.increment_nonce(pick(i))?;
+            model.entry(pick(i)).or_default().nonce += 1;
+        }
+        StateOp::Mint(i, amount) => {
+            trie.mint(pick(i), amount)?;
+            let account = model.entry(pick(i)).or_default();
+            account.balance = account.balance.saturating_add(amount);
+        }
+        StateOp::Debit(i, amount) => {
+            trie.apply_balance_change(pick(i), -i128::from(amount))?;
+            // Succeeded, so the account existed with enough balance.
+            model.entry(pick(i)).or_default().balance -= u128::from(amount);
+        }
+    }
+    Ok(())
+}
+
+/// Apply `ops` in order, ignoring rejected ones.
+pub fn apply_all(
+    trie: &mut PatriciaMerkleTrie,
+    model: &mut Model,
+    pool: &[Address],
+    ops: &[StateOp],
+) {
+    for op in ops {
+        let _ = apply(trie, model, pool, op);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use qc_04_state_management::EMPTY_TRIE_ROOT;
+
+    fn hex32(s: &str) -> Hash {
+        hex::decode(s).unwrap().try_into().unwrap()
+    }
+
+    fn plain(pairs: &[(&str, &str)]) -> BTreeMap<Vec<u8>, Vec<u8>> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.as_bytes().to_vec(), v.as_bytes().to_vec()))
+            .collect()
+    }
+
+    fn account() -> impl Strategy<Value = AccountState> {
+        (any::<u128>(), any::<u64>(), any::<Hash>(), any::<Hash>()).prop_map(
+            |(balance, nonce, code_hash, storage_root)| AccountState {
+                balance,
+                nonce,
+                code_hash,
+                storage_root,
+            },
+        )
+    }
+
+    fn state_op() -> impl Strategy<Value = StateOp> {
+        let index = 0usize..16;
+        prop_oneof![
+            (index.clone(), account()).prop_map(|(i, a)| StateOp::Insert(i, a)),
+            (index.clone(), any::<u128>()).prop_map(|(i, b)| StateOp::SetBalance(i, b)),
+            index.clone().prop_map(StateOp::IncrementNonce),
+            (index.clone(), any::<u128>()).prop_map(|(i, a)| StateOp::Mint(i, a)),
+            (index, 1u64..u64::MAX).prop_map(|(i, a)| StateOp::Debit(i, a)),
+        ]
+    }
+
+    fn pool() -> impl Strategy<Value = Vec<Address>> {
+        prop::collection::vec(any::<Address>(), 1..12)
+    }
+
+    // =========================================================================
+    // REFERENCE SANITY (published vectors)
+    // =========================================================================
+
+    #[test]
+    fn test_reference_empty_root() {
+        assert_eq!(reference_root(&BTreeMap::new()), EMPTY_TRIE_ROOT);
+        assert_eq!(reference_state_root(&Model::new()), EMPTY_TRIE_ROOT);
+    }
+
+    #[test]
+    fn test_reference_matches_ethereum_vectors() {
+        // ethereum/tests TrieTests: "dogs" and the Yellow Paper wiki example.
+        let puppy = plain(&[
+            ("do", "verb"),
+            ("dog", "puppy"),
+            ("doge", "coin"),
+            ("horse", "stallion"),
+        ]);
+        assert_eq!(
+            reference_root(&puppy),
+            hex32("5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84")
+        );
+
+        let wiki = plain(&[
+            ("doe", "reindeer"),
+            ("dog", "puppy"),
+            ("dogglesworth", "cat"),
+        ]);
+        assert_eq!(
+            reference_root(&wiki),
+            hex32("8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3")
+        );
+    }
+
+    // =========================================================================
+    // DIFFERENTIAL PROPERTIES
+    // =========================================================================
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// Any set of accounts commits to the reference state root,
+        /// whatever order it was inserted in.
+        #[test]
+        fn prop_root_matches_reference(
+            accounts in prop::collection::btree_map(any::<Address>(), account(), 0..24),
+        ) {
+            let mut forward = PatriciaMerkleTrie::new();
+            for (address, state) in &accounts {
+                forward.insert_account(*address, state).unwrap();
+            }
+            let mut backward = PatriciaMerkleTrie::new();
+            for (address, state) in accounts.iter().rev() {
+                backward.insert_account(*address, state).unwrap();
+            }
+
+            let expected = reference_state_root(&accounts);
+            prop_assert_eq!(forward.root_hash(), expected);
+            prop_assert_eq!(backward.root_hash(), expected);
+        }
+
+        /// After every operation in a random update sequence the trie's root
+        /// matches the reference root of the model.
+        #[test]
+        fn prop_update_sequence_matches_reference(
+            pool in pool(),
+            ops in prop::collection::vec(state_op(), 1..48),
+        ) {
+            let mut trie = PatriciaMerkleTrie::new();
+            let mut model = Model::new();
+            for op in &ops {
+                let before = trie.root_hash();
+                if apply(&mut trie, &mut model, &pool, op).is_err() {
+                    prop_assert_eq!(trie.root_hash(), before, "rejected {:?} changed root", op);
+                }
+                prop_assert_eq!(trie.root_hash(), reference_state_root(&model), "after {:?}", op);
+            }
+            for (address, state) in &model {
+                prop_assert_eq!(trie.get_account(*address).unwrap(), Some(state.clone()));
+            }
+        }
+
+        /// Reorg: restore the snapshot taken at the fork point, apply the
+        /// competing branch, and land on the same root as a node that only
+        /// ever saw that branch.
+        #[test]
+        fn prop_reorg_matches_reference(
+            pool in pool(),
+            common in prop::collection::vec(state_op(), 0..24),
+            orphaned in prop::collection::vec(state_op(), 1..24),
+            canonical in prop::collection::vec(state_op(), 1..24),
+        ) {
+            let mut trie = PatriciaMerkleTrie::new();
+            let mut model = Model::new();
+            apply_all(&mut trie, &mut model, &pool, &common);
+            let snapshot = trie.serialize().unwrap();
+            let fork_model = model.clone();
+
+            apply_all(&mut trie, &mut model, &pool, &orphaned);
+            prop_assert_eq!(trie.root_hash(), reference_state_root(&model));
+
+            let mut trie = PatriciaMerkleTrie::deserialize(&snapshot).unwrap();
+            let mut model = fork_model;
+            prop_assert_eq!(trie.root_hash(), reference_state_root(&model));
+
+            apply_all(&mut trie, &mut model, &pool, &canonical);
+            prop_assert_eq!(trie.root_hash(), reference_state_root(&model));
+
+            let mut fresh = PatriciaMerkleTrie::new();
+            let mut fresh_model = Model::new();
+            apply_all(&mut fresh, &mut fresh_model, &pool, &common);
+            apply_all(&mut fresh, &mut fresh_model, &pool, &canonical);
+            prop_assert_eq!(fresh_model, model);
+            prop_assert_eq!(fresh.root_hash(), trie.root_hash());
+        }
+
+        /// Proofs carry exactly the canonical nodes from the root down to
+        /// the account's leaf, and the first one hashes to the root.
+        #[test]
+        fn prop_proof_matches_reference(
+            accounts in prop::collection::btree_map(any::<Address>(), account(), 1..16),
+        ) {
+            let mut trie = PatriciaMerkleTrie::new();
+            for (address, state) in &accounts {
+                trie.insert_account(*address, state).unwrap();
+            }
+            for address in accounts.keys() {
+                let proof = trie.generate_proof(*address).unwrap();
+                prop_assert_eq!(keccak(&proof.proof_nodes[0]), trie.root_hash());
+                prop_assert_eq!(proof.proof_nodes, reference_state_proof(&accounts, address));
+            }
+        }
+    }
+}
@@ -5,8 +5,10 @@
 //! the honest network.
 
 use crate::exploits::helpers::keccak256;
+use crate::simulation::{sim_node_id, DiscoveryNode, LinkConfig, NodeIndex, SimConfig, Simulation};
 use qc_01_peer_discovery::{
-    IpAddr, KademliaConfig, NodeId, PeerInfo, RoutingTable, SocketAddr, Timestamp,
+    calculate_bucket_index, DiscoveryMessage, IpAddr, KademliaConfig, NodeId, PeerInfo,
+    RoutingTable, SocketAddr, Timestamp,
 };
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -111,3 +113,107 @@ fn test_defense_proof_of_work_simulation() {
 
     println!("✅ PoW makes Sybil attack expensive");
 }
+
+// =============================================================================
+// SIMULATED NETWORK: eviction challenges under loss and partition
+// =============================================================================
+
+/// Victim (node 0) with bucket 0 full of honest peers, plus sybils aimed at
+/// the same bucket. Returns the simulation, honest peers and sybils.
+fn eclipse_setup(
+    link: LinkConfig,
+    seed: u64,
+) -> (
+    Simulation<DiscoveryNode, DiscoveryMessage>,
+    Vec<NodeIndex>,
+    Vec<NodeIndex>,
+) {
+    let config = KademliaConfig {
+        eviction_challenge_timeout_secs: 5,
+        ..KademliaConfig::for_testing()
+    };
+    let victim_id = sim_node_id(0);
+    let bucket_zero: Vec<NodeIndex> = (1..)
+        .filter(|&i| calculate_bucket_index(&victim_id, &sim_node_id(i)) == 0)
+        .take(config.k + 6)
+        .collect();
+
+    let mut sim = Simulation::new(SimConfig {
+        seed,
+        link,
+        ..SimConfig::default()
+    });
+    for _ in 0..=*bucket_zero.last().unwrap() {
+        sim.add_node(|i, clock| DiscoveryNode::new(i, clock, config.clone()));
+    }
+
+    let (honest, sybils) = bucket_zero.split_at(config.k);
+    for &peer in honest {
+        sim.node_mut(0).introduce(peer).unwrap();
+    }
+    (sim, honest.to_vec(), sybils.to_vec())
+}
+
+/// Each sybil passes verification and challenges the oldest honest peer;
+/// the challenge runs to completion before the next sybil tries.
+fn run_sybil_wave(sim: &mut Simulation<DiscoveryNode, DiscoveryMessage>, sybils: &[NodeIndex]) {
+    for &sybil in sybils {
+        sim.node_mut(0).introduce(sybil).unwrap();
+        sim.run_for(7_000);
+    }
+}
+
+/// Test: Defense - honest peers answer challenges through a lossy network
+#[test]
+fn test_sim_eviction_challenges_survive_packet_loss() {
+    let link = LinkConfig::new(80).with_jitter(120).with_loss(0.2);
+    let (mut sim, honest, sybils) = eclipse_setup(link, 2016);
+
+    run_sybil_wave(&mut sim, &sybils);
+
+    let victim = sim.node(0);
+    assert!(honest.iter().all(|&p| victim.knows(p)));
+    assert!(sybils.iter().all(|&p| !victim.knows(p)));
+    assert!(sim.stats().lost > 0);
+    println!("✅ INVARIANT-10 holds at 20% loss: {:?}", sim.stats());
+}
+
+/// Test: Eclipse precondition - cutting the victim off lets sybils in
+///
+/// Eviction-on-Failure cannot tell a partitioned peer from a dead one, so
+/// an attacker able to partition the victim can take over the bucket. The
+/// honest peers come back as soon as a new slot opens after healing.
+#[test]
+fn test_sim_partition_lets_sybils_evict_honest_peers() {
+    let (mut sim, honest, sybils) = eclipse_setup(LinkConfig::new(50), 2016);
+    let attacker_side: Vec<NodeIndex> = std::iter::once(0).chain(sybils.clone()).collect();
+    sim.network_mut().partition(&[&attacker_side]);
+
+    run_sybil_wave(&mut sim, &sybils);
+
+    let victim = sim.node(0);
+    assert!(honest.iter().all(|&p| !victim.knows(p)));
+    assert_eq!(
+        sybils.iter().filter(|&&p| victim.knows(p)).count(),
+        honest.len()
+    );
+    println!("⚠️  Partitioned victim eclipsed: {:?}", sim.stats());
+}
+
+/// Test: Same seed, same partition scenario, same outcome
+#[test]
+fn test_sim_eclipse_scenario_is_deterministic() {
+    let run = || {
+        let link = LinkConfig::new(60).with_jitter(200).with_loss(0.35);
+        let (mut sim, honest, sybils) = eclipse_setup(link, 99);
+        run_sybil_wave(&mut sim, &sybils);
+        let known: Vec<bool> = honest
+            .iter()
+            .chain(&sybils)
+            .map(|&p| sim.node(0).knows(p))
+            .collect();
+        (sim.stats().clone(), known, sim.clock().now_ms())
+    };
+
+    assert_eq!(run(), run());
+}
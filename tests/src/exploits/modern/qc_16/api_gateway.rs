@@ -176,6 +176,7 @@ fn test_batch_request_bomb_within_limits() {
         max_response_size: 10 * 1024 * 1024,
         max_log_block_range: 1000,
         max_log_results: 10000,
+        log_chunk_blocks: 1000,
     };
 
     let heavy_requests: Vec<String> = (0..100)
@@ -787,6 +788,7 @@ fn brutal_batch_integer_overflow() {
         max_response_size: 10 * 1024 * 1024,
         max_log_block_range: 1000,
         max_log_results: 10000,
+        log_chunk_blocks: 1000,
     };
 
     // Attack: Try to make size calculation overflow
@@ -1040,6 +1042,7 @@ fn brutal_eth_getlogs_range_attack() {
         max_response_size: 10 * 1024 * 1024,
         max_log_block_range: 1000, // 1000 block limit
         max_log_results: 10000,
+        log_chunk_blocks: 1000,
     };
 
     // Attack ranges that might bypass validation
@@ -0,0 +1,1396 @@
+//! # Chaos Tests
+//!
+//! Randomly stops, starts and restarts subsystems in the middle of the block
+//! choreography and checks that nothing falls through the cracks:
+//!
+//! 1. **No block lost**: heights are stored once each, in order, on one
+//!    parent chain, and a stored block stays stored
+//! 2. **No tx lost**: an accepted transaction stays in the mempool (or its
+//!    `mempool.dat` while the mempool is down) until a stored block has it
+//! 3. **Assembler converges or times out**: no assembly outlives
+//!    `assembly_timeout_secs`, and every proposed block is either stored or
+//!    purged by the assembler's timeout
+//!
+//! Subsystems implement `shared_types::Subsystem` and are registered with
+//! the real `SubsystemRegistry`; every fault is a registry `stop`, `start`
+//! or `restart`, so its runtime-control rules apply. Block Storage,
+//! Consensus and Signature Verification are core and the registry refuses
+//! to stop them; refusals are counted. Transaction Indexing and State
+//! Management are registered as optional here (node-runtime marks them
+//! core) so the run can take them down mid-assembly.
+//!
+//! Events flow over a `shared_bus::InMemoryEventBus`. Each subsystem reads
+//! from its own `Block`-policy subscription, so events published while it
+//! is stopped wait in its queue and are delivered once it runs again. State
+//! a real subsystem only keeps in memory is dropped on stop: the mempool,
+//! apart from what it writes to `mempool.dat`.
+//!
+//! All randomness comes from one seeded RNG and all time from a
+//! `VirtualClock`, and the harness delivers one event at a time, so a
+//! failing seed replays exactly.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sha3::{Digest, Keccak256};
+
+use qc_02_block_storage::{AssemblyConfig, BlockAssemblyBuffer};
+use qc_03_transaction_indexing::MerkleTree;
+use qc_04_state_management::PatriciaMerkleTrie;
+use qc_06_mempool::{MempoolConfig, MempoolTransaction, PersistedTransaction, TransactionPool};
+use shared_bus::{
+    BackpressurePolicy, BlockchainEvent, EventFilter, EventPublisher, EventTopic, InMemoryEventBus,
+    Subscription, SubscriptionOptions,
+};
+use shared_types::{
+    BlockHeader, ConsensusProof, Hash, SignedTransaction, Subsystem, SubsystemError,
+    SubsystemErrorKind, SubsystemId, SubsystemInfo, SubsystemRegistry, SubsystemStatus,
+    Transaction, ValidatedBlock, ValidatedTransaction, U256,
+};
+
+use crate::simulation::VirtualClock;
+
+// =============================================================================
+// SUBSYSTEM CONTRACT
+// =============================================================================
+
+/// Events a stopped subsystem's subscription holds before publishers wait.
+const HELD_EVENT_CAPACITY: usize = 1 << 16;
+
+/// Choreography work of a registered subsystem.
+///
+/// The registry drives the lifecycle through `Subsystem`; the harness hands
+/// each running participant the events from its bus subscription and runs
+/// its timers.
+pub trait Participant: Subsystem {
+    /// Subscription the participant's events arrive on.
+    fn subscription(&self) -> &Mutex<Subscription>;
+
+    /// Handle an event; returns the events to publish.
+    fn on_event(&self, event: &BlockchainEvent, now_ms: u64) -> Vec<BlockchainEvent>;
+
+    /// Timer work; returns the events to publish.
+    fn on_tick(&self, _now_ms: u64) -> Vec<BlockchainEvent> {
+        Vec::new()
+    }
+}
+
+/// Registry entry for a participant the harness also drives.
+struct Registered(Arc<dyn Participant>);
+
+#[async_trait]
+impl Subsystem for Registered {
+    fn id(&self) -> SubsystemId {
+        self.0.id()
+    }
+
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    fn info(&self) -> SubsystemInfo {
+        self.0.info()
+    }
+
+    async fn start(&self) -> Result<(), SubsystemError> {
+        self.0.start().await
+    }
+
+    async fn stop(&self) -> Result<(), SubsystemError> {
+        self.0.stop().await
+    }
+
+    async fn health_check(&self) -> SubsystemStatus {
+        self.0.health_check().await
+    }
+}
+
+/// Running flag behind a subsystem's `start`, `stop` and `health_check`.
+#[derive(Default)]
+struct Lifecycle(AtomicBool);
+
+impl Lifecycle {
+    fn set_running(&self, running: bool) {
+        self.0.store(running, Ordering::SeqCst);
+    }
+
+    fn health(&self) -> SubsystemStatus {
+        if self.0.load(Ordering::SeqCst) {
+            SubsystemStatus::Healthy
+        } else {
+            SubsystemStatus::Stopped
+        }
+    }
+}
+
+/// Subscription that holds events while its subsystem is stopped.
+fn subscribe(bus: &InMemoryEventBus, name: &str, topics: Vec<EventTopic>) -> Mutex<Subscription> {
+    let options = SubscriptionOptions::new(name)
+        .with_policy(BackpressurePolicy::Block)
+        .with_capacity(HELD_EVENT_CAPACITY);
+    Mutex::new(bus.subscribe_with(EventFilter::topics(topics), options))
+}
+
+fn not_running(subsystem_id: SubsystemId) -> SubsystemError {
+    SubsystemError {
+        subsystem_id,
+        kind: SubsystemErrorKind::NotAvailable,
+        message: "not running".to_string(),
+    }
+}
+
+// =============================================================================
+// SUBSYSTEMS
+// =============================================================================
+
+/// Signature Verification (qc-10): registered because the registry requires
+/// it; this choreography carries no signature traffic.
+#[derive(Default)]
+pub struct SignatureVerificationSubsystem {
+    lifecycle: Lifecycle,
+}
+
+#[async_trait]
+impl Subsystem for SignatureVerificationSubsystem {
+    fn id(&self) -> SubsystemId {
+        SubsystemId::SignatureVerification
+    }
+
+    fn name(&self) -> &'static str {
+        "qc-10-signature-verification"
+    }
+
+    fn info(&self) -> SubsystemInfo {
+        SubsystemInfo::new(self.id(), self.name()).required()
+    }
+
+    async fn start(&self) -> Result<(), SubsystemError> {
+        self.lifecycle.set_running(true);
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), SubsystemError> {
+        self.lifecycle.set_running(false);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> SubsystemStatus {
+        self.lifecycle.health()
+    }
+}
+
+/// Consensus (qc-08): proposes one block at a time from the mempool.
+///
+/// The shared bus has no assembly-timeout event, so a purged proposal is
+/// noticed on the next tick by asking Block Storage.
+pub struct ConsensusSubsystem {
+    lifecycle: Lifecycle,
+    subscription: Mutex<Subscription>,
+    state: Mutex<ConsensusState>,
+    mempool: Arc<MempoolSubsystem>,
+    storage: Arc<BlockStorageSubsystem>,
+    max_txs_per_block: usize,
+}
+
+#[derive(Default)]
+struct ConsensusState {
+    next_height: u64,
+    parent_hash: Hash,
+    attempt: u64,
+    in_flight: Option<Hash>,
+    proposed: Vec<Hash>,
+}
+
+impl ConsensusSubsystem {
+    pub fn new(
+        bus: &InMemoryEventBus,
+        mempool: Arc<MempoolSubsystem>,
+        storage: Arc<BlockStorageSubsystem>,
+        max_txs_per_block: usize,
+    ) -> Self {
+        Self {
+            lifecycle: Lifecycle::default(),
+            subscription: subscribe(bus, "qc-08-consensus", vec![EventTopic::BlockStorage]),
+            state: Mutex::new(ConsensusState {
+                next_height: 1,
+                ..ConsensusState::default()
+            }),
+            mempool,
+            storage,
+            max_txs_per_block,
+        }
+    }
+
+    /// Every block hash proposed so far, including retries.
+    pub fn proposed(&self) -> Vec<Hash> {
+        self.state.lock().proposed.clone()
+    }
+
+    /// True while a proposed block is neither stored nor timed out.
+    pub fn has_block_in_flight(&self) -> bool {
+        self.state.lock().in_flight.is_some()
+    }
+
+    fn build_block(
+        state: &mut ConsensusState,
+        txs: &[SignedTransaction],
+        now_ms: u64,
+    ) -> ValidatedBlock {
+        let transactions: Vec<ValidatedTransaction> = txs.iter().map(validated_tx).collect();
+
+        let mut hasher = Keccak256::new();
+        hasher.update(state.parent_hash);
+        hasher.update(state.next_height.to_le_bytes());
+        hasher.update(state.attempt.to_le_bytes());
+        for tx in &transactions {
+            hasher.update(tx.tx_hash);
+        }
+        let block_hash: Hash = hasher.finalize().into();
+        state.attempt += 1;
+
+        ValidatedBlock {
+            header: BlockHeader {
+                version: 1,
+                height: state.next_height,
+                parent_hash: state.parent_hash,
+                timestamp: now_ms / 1000,
+                ..BlockHeader::default()
+            },
+            transactions,
+            consensus_proof: ConsensusProof {
+                block_hash,
+                attestations: vec![],
+                total_stake: 0,
+            },
+        }
+    }
+}
+
+#[async_trait]
+impl Subsystem for ConsensusSubsystem {
+    fn id(&self) -> SubsystemId {
+        SubsystemId::Consensus
+    }
+
+    fn name(&self) -> &'static str {
+        "qc-08-consensus"
+    }
+
+    fn info(&self) -> SubsystemInfo {
+        SubsystemInfo::new(self.id(), self.name())
+            .required()
+            .depends_on(vec![SubsystemId::SignatureVerification])
+    }
+
+    /// The chain tip is rebuilt from storage on a real restart, so it is
+    /// kept across stop/start.
+    async fn start(&self) -> Result<(), SubsystemError> {
+        self.lifecycle.set_running(true);
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), SubsystemError> {
+        self.lifecycle.set_running(false);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> SubsystemStatus {
+        self.lifecycle.health()
+    }
+}
+
+impl Participant for ConsensusSubsystem {
+    fn subscription(&self) -> &Mutex<Subscription> {
+        &self.subscription
+    }
+
+    fn on_event(&self, event: &BlockchainEvent, _now_ms: u64) -> Vec<BlockchainEvent> {
+        let mut state = self.state.lock();
+        if let BlockchainEvent::BlockStored {
+            block_hash,
+            block_height,
+        } = event
+        {
+            if state.in_flight == Some(*block_hash) {
+                state.next_height = block_height + 1;
+                state.parent_hash = *block_hash;
+                state.in_flight = None;
+            }
+        }
+        Vec::new()
+    }
+
+    fn on_tick(&self, now_ms: u64) -> Vec<BlockchainEvent> {
+        let mut state = self.state.lock();
+        if let Some(block_hash) = state.in_flight {
+            // The height is retried with a fresh block on the next tick
+            if self.storage.timed_out().contains(&block_hash) {
+                state.in_flight = None;
+            }
+            return Vec::new();
+        }
+
+        let Ok(txs) = self
+            .mempool
+            .propose(self.max_txs_per_block, state.next_height, now_ms)
+        else {
+            return Vec::new();
+        };
+        if txs.is_empty() {
+            return Vec::new();
+        }
+
+        let block = Self::build_block(&mut state, &txs, now_ms);
+        let block_hash = block.consensus_proof.block_hash;
+        state.in_flight = Some(block_hash);
+        state.proposed.push(block_hash);
+        vec![BlockchainEvent::BlockValidated(block)]
+    }
+}
+
+fn validated_tx(tx: &SignedTransaction) -> ValidatedTransaction {
+    let mut from = [0u8; 32];
+    from[..20].copy_from_slice(&tx.from);
+    let to = tx.to.map(|addr| {
+        let mut key = [0u8; 32];
+        key[..20].copy_from_slice(&addr);
+        key
+    });
+
+    ValidatedTransaction {
+        inner: Transaction {
+            from,
+            to,
+            value: tx.value.low_u64(),
+            nonce: tx.nonce,
+            data: tx.data.clone(),
+            signature: tx.signature,
+        },
+        tx_hash: tx.hash(),
+    }
+}
+
+/// Mempool (qc-06): the pool lives in memory; stop writes `mempool.dat`
+/// and start restores it.
+///
+/// `BlockStored` carries no transactions, so included ones are looked up in
+/// Block Storage.
+pub struct MempoolSubsystem {
+    lifecycle: Lifecycle,
+    subscription: Mutex<Subscription>,
+    config: MempoolConfig,
+    pool: Mutex<Option<TransactionPool>>,
+    disk: Mutex<Vec<PersistedTransaction>>,
+    storage: Arc<BlockStorageSubsystem>,
+}
+
+impl MempoolSubsystem {
+    pub fn new(
+        bus: &InMemoryEventBus,
+        config: MempoolConfig,
+        storage: Arc<BlockStorageSubsystem>,
+    ) -> Self {
+        Self {
+            lifecycle: Lifecycle::default(),
+            subscription: subscribe(bus, "qc-06-mempool", vec![EventTopic::BlockStorage]),
+            config,
+            pool: Mutex::new(None),
+            disk: Mutex::new(Vec::new()),
+            storage,
+        }
+    }
+
+    /// Submit a transaction; fails while stopped or if the pool rejects it.
+    pub fn submit(&self, tx: SignedTransaction, now_ms: u64) -> Result<Hash, SubsystemError> {
+        let mut pool = self.pool.lock();
+        let pool = pool.as_mut().ok_or_else(|| not_running(self.id()))?;
+        let tx = MempoolTransaction::new(tx, now_ms);
+        let hash = tx.hash;
+        pool.add(tx).map_err(|e| SubsystemError {
+            subsystem_id: self.id(),
+            kind: SubsystemErrorKind::RuntimeError,
+            message: e.to_string(),
+        })?;
+        Ok(hash)
+    }
+
+    /// Move up to `max` pending transactions into PENDING_INCLUSION.
+    pub fn propose(
+        &self,
+        max: usize,
+        block_height: u64,
+        now_ms: u64,
+    ) -> Result<Vec<SignedTransaction>, SubsystemError> {
+        let mut pool = self.pool.lock();
+        let pool = pool.as_mut().ok_or_else(|| not_running(self.id()))?;
+        let (hashes, txs): (Vec<Hash>, Vec<SignedTransaction>) = pool
+            .get_for_block(max, u64::MAX)
+            .into_iter()
+            .map(|tx| (tx.hash, tx.transaction.clone()))
+            .unzip();
+        pool.propose(&hashes, block_height, now_ms);
+        Ok(txs)
+    }
+
+    /// True if `hash` is in the pool, or in `mempool.dat` while stopped.
+    pub fn holds(&self, hash: &Hash) -> bool {
+        match self.pool.lock().as_ref() {
+            Some(pool) => pool.contains(hash),
+            None => self.disk.lock().iter().any(|tx| &tx.hash == hash),
+        }
+    }
+
+    /// Transactions held, in memory or on disk.
+    pub fn len(&self) -> usize {
+        match self.pool.lock().as_ref() {
+            Some(pool) => pool.len(),
+            None => self.disk.lock().len(),
+        }
+    }
+
+    /// True if no transactions are held.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl Subsystem for MempoolSubsystem {
+    fn id(&self) -> SubsystemId {
+        SubsystemId::Mempool
+    }
+
+    fn name(&self) -> &'static str {
+        "qc-06-mempool"
+    }
+
+    fn info(&self) -> SubsystemInfo {
+        SubsystemInfo::new(self.id(), self.name())
+            .depends_on(vec![SubsystemId::SignatureVerification])
+    }
+
+    async fn start(&self) -> Result<(), SubsystemError> {
+        let mut pool = TransactionPool::new(self.config.clone());
+        let saved = std::mem::take(&mut *self.disk.lock());
+        pool.restore(saved);
+        *self.pool.lock() = Some(pool);
+        self.lifecycle.set_running(true);
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), SubsystemError> {
+        if let Some(pool) = self.pool.lock().take() {
+            *self.disk.lock() = pool.persisted_transactions(0);
+        }
+        self.lifecycle.set_running(false);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> SubsystemStatus {
+        self.lifecycle.health()
+    }
+}
+
+impl Participant for MempoolSubsystem {
+    fn subscription(&self) -> &Mutex<Subscription> {
+        &self.subscription
+    }
+
+    fn on_event(&self, event: &BlockchainEvent, _now_ms: u64) -> Vec<BlockchainEvent> {
+        if let (BlockchainEvent::BlockStored { block_hash, .. }, Some(pool)) =
+            (event, self.pool.lock().as_mut())
+        {
+            if let Some(block) = self.storage.get(block_hash) {
+                pool.confirm(&block.tx_hashes);
+            }
+        }
+        Vec::new()
+    }
+
+    fn on_tick(&self, now_ms: u64) -> Vec<BlockchainEvent> {
+        // INVARIANT-5: proposals whose block never lands go back to pending
+        if let Some(pool) = self.pool.lock().as_mut() {
+            pool.cleanup_timeouts(now_ms);
+        }
+        Vec::new()
+    }
+}
+
+/// Transaction Indexing (qc-03): computes the Merkle root of each block.
+pub struct TxIndexingSubsystem {
+    lifecycle: Lifecycle,
+    subscription: Mutex<Subscription>,
+}
+
+impl TxIndexingSubsystem {
+    pub fn new(bus: &InMemoryEventBus) -> Self {
+        Self {
+            lifecycle: Lifecycle::default(),
+            subscription: subscribe(
+                bus,
+                "qc-03-transaction-indexing",
+                vec![EventTopic::Consensus],
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl Subsystem for TxIndexingSubsystem {
+    fn id(&self) -> SubsystemId {
+        SubsystemId::TransactionIndexing
+    }
+
+    fn name(&self) -> &'static str {
+        "qc-03-transaction-indexing"
+    }
+
+    async fn start(&self) -> Result<(), SubsystemError> {
+        self.lifecycle.set_running(true);
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), SubsystemError> {
+        self.lifecycle.set_running(false);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> SubsystemStatus {
+        self.lifecycle.health()
+    }
+}
+
+impl Participant for TxIndexingSubsystem {
+    fn subscription(&self) -> &Mutex<Subscription> {
+        &self.subscription
+    }
+
+    fn on_event(&self, event: &BlockchainEvent, _now_ms: u64) -> Vec<BlockchainEvent> {
+        let BlockchainEvent::BlockValidated(block) = event else {
+            return Vec::new();
+        };
+        let tx_hashes = block.transactions.iter().map(|tx| tx.tx_hash).collect();
+        vec![BlockchainEvent::MerkleRootComputed {
+            block_hash: block.consensus_proof.block_hash,
+            merkle_root: MerkleTree::build(tx_hashes).root(),
+        }]
+    }
+}
+
+/// State Management (qc-04): credits recipients and reports the state root.
+///
+/// The trie stands in for the state database and survives a stop.
+pub struct StateMgmtSubsystem {
+    lifecycle: Lifecycle,
+    subscription: Mutex<Subscription>,
+    trie: Mutex<PatriciaMerkleTrie>,
+}
+
+impl StateMgmtSubsystem {
+    pub fn new(bus: &InMemoryEventBus) -> Self {
+        Self {
+            lifecycle: Lifecycle::default(),
+            subscription: subscribe(bus, "qc-04-state-management", vec![EventTopic::Consensus]),
+            trie: Mutex::new(PatriciaMerkleTrie::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Subsystem for StateMgmtSubsystem {
+    fn id(&self) -> SubsystemId {
+        SubsystemId::StateManagement
+    }
+
+    fn name(&self) -> &'static str {
+        "qc-04-state-management"
+    }
+
+    async fn start(&self) -> Result<(), SubsystemError> {
+        self.lifecycle.set_running(true);
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<(), SubsystemError> {
+        self.lifecycle.set_running(false);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> SubsystemStatus {
+        self.lifecycle.health()
+    }
+}
+
+impl Participant for StateMgmtSubsystem {
+    fn subscription(&self) -> &Mutex<Subscription> {
+        &self.subscription
+    }
+
+    fn on_event(&self, event: &BlockchainEvent, _now_ms: u64) -> Vec<BlockchainEvent> {
+        let BlockchainEvent::BlockValidated(block) = event else {
+            return Vec::new();
+        };
+
+        let mut trie = self.trie.lock();
+        for tx in &block.transactions {
+            if let Some(to) = tx.inner.to {
+                let mut address = [0u8; 20];
+                address.copy_from_slice(&to[..20]);
+                let _ = trie.apply_balance_change(address, i128::from(tx.inner.value));
+            }
+        }
+        vec![BlockchainEvent::StateRootComputed {
+            block_hash: block.consensus_proof.block_hash,
+            state_root: trie.root_hash(),
+        }]
+    }
+}
+
+/// A block written by Block Storage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredBlock {
+    pub block_hash: Hash,
+    pub block_height: u64,
+    pub parent_hash: Hash,
+    pub merkle_root: Hash,
+    pub state_root: Hash,
+    pub tx_hashes: Vec<Hash>,
+}
+
+/// Block Storage (qc-02): the stateful assembler.
+///
+/// Core, so never stopped by the run; partial assemblies are purged by the
+/// assembler's timeout.
+pub struct BlockStorageSubsystem {
+    lifecycle: Lifecycle,
+    subscription: Mutex<Subscription>,
+    config: AssemblyConfig,
+    buffer: Mutex<BlockAssemblyBuffer>,
+    stored: Mutex<Vec<StoredBlock>>,
+    timed_out: Mutex<HashSet<Hash>>,
+}
+
+impl BlockStorageSubsystem {
+    pub fn new(bus: &InMemoryEventBus, config: AssemblyConfig) -> Self {
+        Self {
+            lifecycle: Lifecycle::default(),
+            subscription: subscribe(
+                bus,
+                "qc-02-block-storage",
+                vec![
+                    EventTopic::Consensus,
+                    EventTopic::TransactionIndexing,
+                    EventTopic::StateManagement,
+                ],
+            ),
+            buffer: Mutex::new(BlockAssemblyBuffer::new(config.clone())),
+            config,
+            stored: Mutex::new(Vec::new()),
+            timed_out: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Blocks in the order they were written.
+    pub fn stored(&self) -> Vec<StoredBlock> {
+        self.stored.lock().clone()
+    }
+
+    /// A stored block by hash.
+    pub fn get(&self, block_hash: &Hash) -> Option<StoredBlock> {
+        self.stored
+            .lock()
+            .iter()
+            .find(|block| &block.block_hash == block_hash)
+            .cloned()
+    }
+
+    /// Block hashes whose assembly was purged by timeout.
+    pub fn timed_out(&self) -> HashSet<Hash> {
+        self.timed_out.lock().clone()
+    }
+
+    /// When the pending assembly for `block_hash` started, in seconds.
+    pub fn pending_since(&self, block_hash: &Hash) -> Option<u64> {
+        self.buffer.lock().get(block_hash).map(|a| a.started_at)
+    }
+
+    /// Number of pending assemblies.
+    pub fn pending_count(&self) -> usize {
+        self.buffer.lock().len()
+    }
+
+    /// Assembly timeout from the config.
+    pub fn assembly_timeout_secs(&self) -> u64 {
+        self.config.assembly_timeout_secs
+    }
+
+    fn try_store(
+        &self,
+        buffer: &mut BlockAssemblyBuffer,
+        block_hash: Hash,
+    ) -> Vec<BlockchainEvent> {
+        let Some((block, merkle_root, state_root)) = buffer
+            .take_complete(&block_hash)
+            .and_then(|assembly| assembly.take_components())
+        else {
+            return Vec::new();
+        };
+
+        self.stored.lock().push(StoredBlock {
+            block_hash,
+            block_height: block.header.height,
+            parent_hash: block.header.parent_hash,
+            merkle_root,
+            state_root,
+            tx_hashes: block.transactions.iter().map(|tx| tx.tx_hash).collect(),
+        });
+        vec![BlockchainEvent::BlockStored {
+            block_height: block.header.height,
+            block_hash,
+        }]
+    }
+}
+
+#[async_trait]
+impl Subsystem for BlockStorageSubsystem {
+    fn id(&self) -> SubsystemId {
+        SubsystemId::BlockStorage
+    }
+
+    fn name(&self) -> &'static str {
+        "qc-02-block-storage"
+    }
+
+    fn info(&self) -> SubsystemInfo {
+        SubsystemInfo::new(self.id(), self.name()).required()
+    }
+
+    async fn start(&self) -> Result<(), SubsystemError> {
+        self.lifecycle.set_running(true);
+        Ok(())
+    }
+
+    /// The assembly buffer is in memory and is lost on stop; stored blocks
+    /// are on disk and survive.
+    async fn stop(&self) -> Result<(), SubsystemError> {
+        *self.buffer.lock() = BlockAssemblyBuffer::new(self.config.clone());
+        self.lifecycle.set_running(false);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> SubsystemStatus {
+        self.lifecycle.health()
+    }
+}
+
+impl Participant for BlockStorageSubsystem {
+    fn subscription(&self) -> &Mutex<Subscription> {
+        &self.subscription
+    }
+
+    fn on_event(&self, event: &BlockchainEvent, now_ms: u64) -> Vec<BlockchainEvent> {
+        let now = now_ms / 1000;
+        let mut buffer = self.buffer.lock();
+        let block_hash = match event {
+            BlockchainEvent::BlockValidated(block) => {
+                let block_hash = block.consensus_proof.block_hash;
+                buffer.add_block_validated(block_hash, block.clone(), now);
+                block_hash
+            }
+            BlockchainEvent::MerkleRootComputed {
+                block_hash,
+                merkle_root,
+            } => {
+                buffer.add_merkle_root(*block_hash, *merkle_root, now);
+                *block_hash
+            }
+            BlockchainEvent::StateRootComputed {
+                block_hash,
+                state_root,
+            } => {
+                buffer.add_state_root(*block_hash, *state_root, now);
+                *block_hash
+            }
+            _ => return Vec::new(),
+        };
+        self.try_store(&mut buffer, block_hash)
+    }
+
+    fn on_tick(&self, now_ms: u64) -> Vec<BlockchainEvent> {
+        // INVARIANT-7: partial assemblies are purged
+        let expired = self.buffer.lock().gc_expired(now_ms / 1000);
+        self.timed_out.lock().extend(expired);
+        Vec::new()
+    }
+}
+
+// =============================================================================
+// CHAOS HARNESS
+// =============================================================================
+
+/// Settings for a chaos run.
+#[derive(Clone, Debug)]
+pub struct ChaosConfig {
+    /// Seed for fault injection.
+    pub seed: u64,
+    /// Simulated time per round, in milliseconds.
+    pub round_ms: u64,
+    /// Interval between timer ticks, in milliseconds.
+    pub tick_interval_ms: u64,
+    /// Chance of stopping, starting or restarting a random subsystem before
+    /// each event delivery.
+    pub fault_rate: f64,
+    /// New transactions submitted per round.
+    pub txs_per_round: usize,
+    /// Block size limit for Consensus.
+    pub max_txs_per_block: usize,
+    /// Assembler settings.
+    pub assembly: AssemblyConfig,
+    /// Mempool settings.
+    pub mempool: MempoolConfig,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            round_ms: 250,
+            tick_interval_ms: 1_000,
+            fault_rate: 0.05,
+            txs_per_round: 2,
+            max_txs_per_block: 8,
+            assembly: AssemblyConfig {
+                assembly_timeout_secs: 3,
+                max_pending_assemblies: 100,
+            },
+            mempool: MempoolConfig::for_testing(),
+        }
+    }
+}
+
+/// What a chaos run did.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChaosReport {
+    /// Subsystems stopped through the registry (restarts included).
+    pub stops: u64,
+    /// Subsystems started through the registry (restarts included).
+    pub restarts: u64,
+    /// Faults the registry refused (core subsystems).
+    pub refused: u64,
+    /// Events published while a subscriber was stopped, delivered later.
+    pub held_events: u64,
+    /// Transactions the mempool accepted.
+    pub txs_accepted: u64,
+    /// Blocks written.
+    pub blocks_stored: u64,
+    /// Assemblies purged by timeout.
+    pub assembly_timeouts: u64,
+    /// Hash of the last stored block.
+    pub tip: Hash,
+}
+
+/// Drives the choreography while injecting registry faults.
+pub struct ChaosHarness {
+    config: ChaosConfig,
+    rng: StdRng,
+    clock: VirtualClock,
+    event_bus: Arc<InMemoryEventBus>,
+    registry: SubsystemRegistry,
+    /// Registered participants in ID order.
+    participants: Vec<Arc<dyn Participant>>,
+    /// Where the next delivery starts looking, for round-robin fairness.
+    next_participant: usize,
+    consensus: Arc<ConsensusSubsystem>,
+    mempool: Arc<MempoolSubsystem>,
+    storage: Arc<BlockStorageSubsystem>,
+    accepted: Vec<Hash>,
+    retry: Vec<SignedTransaction>,
+    next_sender: u64,
+    next_tick_ms: u64,
+    blocks_seen: usize,
+    report: ChaosReport,
+}
+
+impl ChaosHarness {
+    /// Register and start all subsystems.
+    pub async fn new(config: ChaosConfig) -> Self {
+        let clock = VirtualClock::new(1_000_000);
+        let event_bus = Arc::new(InMemoryEventBus::new());
+        let storage = Arc::new(BlockStorageSubsystem::new(
+            &event_bus,
+            config.assembly.clone(),
+        ));
+        let mempool = Arc::new(MempoolSubsystem::new(
+            &event_bus,
+            config.mempool.clone(),
+            Arc::clone(&storage),
+        ));
+        let consensus = Arc::new(ConsensusSubsystem::new(
+            &event_bus,
+            Arc::clone(&mempool),
+            Arc::clone(&storage),
+            config.max_txs_per_block,
+        ));
+        let participants: Vec<Arc<dyn Participant>> = vec![
+            Arc::clone(&storage) as Arc<dyn Participant>,
+            Arc::new(TxIndexingSubsystem::new(&event_bus)),
+            Arc::new(StateMgmtSubsystem::new(&event_bus)),
+            Arc::clone(&mempool) as Arc<dyn Participant>,
+            Arc::clone(&consensus) as Arc<dyn Participant>,
+        ];
+
+        let mut registry = SubsystemRegistry::new();
+        registry
+            .register(Box::new(SignatureVerificationSubsystem::default()))
+            .expect("registers");
+        for participant in &participants {
+            registry
+                .register(Box::new(Registered(Arc::clone(participant))))
+                .expect("registers");
+        }
+        registry.start_all().await.expect("subsystems start");
+
+        Self {
+            rng: StdRng::seed_from_u64(config.seed),
+            next_tick_ms: clock.now_ms() + config.tick_interval_ms,
+            clock,
+            event_bus,
+            registry,
+            participants,
+            next_participant: 0,
+            consensus,
+            mempool,
+            storage,
+            accepted: Vec::new(),
+            retry: Vec::new(),
+            next_sender: 0,
+            blocks_seen: 0,
+            report: ChaosReport::default(),
+            config,
+        }
+    }
+
+    /// The registry, for status checks.
+    pub fn registry(&self) -> &SubsystemRegistry {
+        &self.registry
+    }
+
+    /// Block Storage, for assertions.
+    pub fn storage(&self) -> &BlockStorageSubsystem {
+        &self.storage
+    }
+
+    /// Counters so far.
+    pub fn report(&self) -> &ChaosReport {
+        &self.report
+    }
+
+    /// Run `rounds` rounds with fault injection, checking invariants after
+    /// each one.
+    pub async fn run_chaos(&mut self, rounds: usize) {
+        for _ in 0..rounds {
+            self.round(true, self.config.txs_per_round).await;
+        }
+    }
+
+    /// Run `rounds` rounds without faults or new transactions, leaving
+    /// stopped subsystems stopped.
+    pub async fn run_quiet(&mut self, rounds: usize) {
+        for _ in 0..rounds {
+            self.round(false, 0).await;
+        }
+    }
+
+    /// Start everything, stop injecting faults and run until every
+    /// accepted transaction is in a stored block.
+    ///
+    /// Returns false if that does not happen within `max_ms`.
+    pub async fn settle(&mut self, max_ms: u64) -> bool {
+        let stopped: Vec<SubsystemId> = self
+            .participants
+            .iter()
+            .map(|participant| participant.id())
+            .filter(|id| !self.is_running(*id))
+            .collect();
+        for id in stopped {
+            self.start(id).await.expect("start succeeds");
+        }
+        self.drain(false).await;
+
+        let deadline = self.clock.now_ms() + max_ms;
+        while !self.is_settled() {
+            if self.clock.now_ms() >= deadline {
+                return false;
+            }
+            self.round(false, 0).await;
+        }
+        true
+    }
+
+    /// Submit `count` new transactions now.
+    pub fn submit_transactions(&mut self, count: usize) {
+        let now_ms = self.clock.now_ms();
+        let retries = std::mem::take(&mut self.retry);
+        let fresh: Vec<_> = (0..count).map(|_| self.next_transaction()).collect();
+        for tx in retries.into_iter().chain(fresh) {
+            match self.mempool.submit(tx.clone(), now_ms) {
+                Ok(hash) => {
+                    self.accepted.push(hash);
+                    self.report.txs_accepted += 1;
+                }
+                // The client retries anything the mempool turned away
+                Err(_) => self.retry.push(tx),
+            }
+        }
+    }
+
+    /// Run the timers of every running subsystem.
+    pub async fn tick(&mut self) {
+        let now_ms = self.clock.now_ms();
+        let events: Vec<_> = self
+            .participants
+            .iter()
+            .filter(|participant| self.is_running(participant.id()))
+            .flat_map(|participant| participant.on_tick(now_ms))
+            .collect();
+        self.publish(events).await;
+    }
+
+    /// Deliver one waiting event, maybe injecting a fault first.
+    ///
+    /// Running subsystems take turns; returns false if none had an event.
+    pub async fn deliver_next(&mut self, chaos: bool) -> bool {
+        if chaos && self.rng.gen_bool(self.config.fault_rate) {
+            self.inject_fault().await;
+        }
+
+        let count = self.participants.len();
+        for offset in 0..count {
+            let index = (self.next_participant + offset) % count;
+            let participant = Arc::clone(&self.participants[index]);
+            if !self.is_running(participant.id()) {
+                continue;
+            }
+            let Ok(Some(event)) = participant.subscription().lock().try_recv() else {
+                continue;
+            };
+
+            self.next_participant = index + 1;
+            let published = participant.on_event(&event, self.clock.now_ms());
+            self.publish(published).await;
+            return true;
+        }
+        false
+    }
+
+    /// Stop a subsystem through the registry; its events wait on the bus.
+    pub async fn stop(&mut self, id: SubsystemId) -> Result<(), SubsystemError> {
+        self.registry.stop(id).await?;
+        self.report.stops += 1;
+        Ok(())
+    }
+
+    /// Start a subsystem through the registry; it then receives the events
+    /// it missed.
+    pub async fn start(&mut self, id: SubsystemId) -> Result<(), SubsystemError> {
+        self.registry.start(id).await?;
+        self.report.restarts += 1;
+        Ok(())
+    }
+
+    /// Restart a subsystem through the registry.
+    pub async fn restart(&mut self, id: SubsystemId) -> Result<(), SubsystemError> {
+        self.registry.restart(id).await?;
+        self.report.stops += 1;
+        self.report.restarts += 1;
+        Ok(())
+    }
+
+    /// Assert every invariant that must hold at any point in the run.
+    pub fn check_invariants(&mut self) {
+        let seed = self.config.seed;
+        let stored = self.storage.stored();
+
+        // No block lost: a stored block is never dropped
+        assert!(
+            stored.len() >= self.blocks_seen,
+            "seed {seed}: stored blocks went from {} to {}",
+            self.blocks_seen,
+            stored.len()
+        );
+        self.blocks_seen = stored.len();
+
+        // No block lost: one block per height, on a single chain
+        let mut parent = Hash::default();
+        for (i, block) in stored.iter().enumerate() {
+            assert_eq!(
+                block.block_height,
+                i as u64 + 1,
+                "seed {seed}: height gap or duplicate"
+            );
+            assert_eq!(block.parent_hash, parent, "seed {seed}: broken parent link");
+            parent = block.block_hash;
+        }
+
+        // No tx lost: accepted transactions are pooled until included, once
+        let mut included = HashSet::new();
+        for hash in stored.iter().flat_map(|b| &b.tx_hashes) {
+            assert!(included.insert(*hash), "seed {seed}: tx included twice");
+        }
+        for hash in &self.accepted {
+            assert!(
+                included.contains(hash) || self.mempool.holds(hash),
+                "seed {seed}: accepted tx lost"
+            );
+        }
+
+        // Assembler: no assembly outlives its timeout by more than a tick
+        let now = self.clock.now_ms() / 1000;
+        let limit = self.storage.assembly_timeout_secs() + self.config.tick_interval_ms / 1000;
+        for hash in self.consensus.proposed() {
+            if let Some(started_at) = self.storage.pending_since(&hash) {
+                assert!(
+                    now.saturating_sub(started_at) <= limit,
+                    "seed {seed}: assembly stuck past its timeout"
+                );
+            }
+        }
+
+        self.report.blocks_stored = stored.len() as u64;
+        self.report.assembly_timeouts = self.storage.timed_out().len() as u64;
+        self.report.tip = parent;
+    }
+
+    /// After `settle`: every proposed block was stored or timed out.
+    pub fn check_converged(&mut self) {
+        self.check_invariants();
+        let seed = self.config.seed;
+        let stored: HashSet<Hash> = self.storage.stored().iter().map(|b| b.block_hash).collect();
+        let timed_out = self.storage.timed_out();
+        for hash in self.consensus.proposed() {
+            assert!(
+                stored.contains(&hash) || timed_out.contains(&hash),
+                "seed {seed}: block neither stored nor timed out"
+            );
+        }
+        assert_eq!(
+            self.storage.pending_count(),
+            0,
+            "seed {seed}: assembly left pending"
+        );
+    }
+
+    async fn round(&mut self, chaos: bool, new_txs: usize) {
+        self.clock.advance(self.config.round_ms);
+        self.submit_transactions(new_txs);
+        if self.clock.now_ms() >= self.next_tick_ms {
+            self.next_tick_ms += self.config.tick_interval_ms;
+            self.tick().await;
+        }
+        self.drain(chaos).await;
+        self.check_invariants();
+    }
+
+    async fn drain(&mut self, chaos: bool) {
+        while self.deliver_next(chaos).await {}
+    }
+
+    /// Stop or restart a running subsystem, or start a stopped one, picked
+    /// at random; the registry refuses core subsystems.
+    async fn inject_fault(&mut self) {
+        let id = self.participants[self.rng.gen_range(0..self.participants.len())].id();
+        let result = if !self.is_running(id) {
+            self.start(id).await
+        } else if self.rng.gen_bool(0.5) {
+            self.stop(id).await
+        } else {
+            self.restart(id).await
+        };
+        match result {
+            Ok(()) => {}
+            Err(e) if e.kind == SubsystemErrorKind::Refused => self.report.refused += 1,
+            Err(e) => panic!("seed {}: {e}", self.config.seed),
+        }
+    }
+
+    async fn publish(&mut self, events: Vec<BlockchainEvent>) {
+        for event in events {
+            let held = self
+                .participants
+                .iter()
+                .filter(|participant| !self.is_running(participant.id()))
+                .filter(|participant| participant.subscription().lock().filter().matches(&event))
+                .count();
+            self.report.held_events += held as u64;
+            self.event_bus.publish(event).await;
+        }
+    }
+
+    fn is_running(&self, id: SubsystemId) -> bool {
+        matches!(
+            self.registry.status(id),
+            Some(SubsystemStatus::Healthy | SubsystemStatus::Degraded)
+        )
+    }
+
+    fn is_settled(&self) -> bool {
+        self.event_bus.pending_events() == 0
+            && self.retry.is_empty()
+            && self.mempool.is_empty()
+            && !self.consensus.has_block_in_flight()
+            && self.storage.pending_count() == 0
+    }
+
+    fn next_transaction(&mut self) -> SignedTransaction {
+        let sender = self.next_sender;
+        self.next_sender += 1;
+        let mut from = [0u8; 20];
+        from[..8].copy_from_slice(&sender.to_be_bytes());
+
+        SignedTransaction {
+            from,
+            to: Some([0xBB; 20]),
+            value: U256::from(1_000u64),
+            nonce: 0,
+            gas_price: U256::from(20_000_000_000u64),
+            gas_limit: 21_000,
+            data: vec![],
+            signature: [0u8; 64],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn run(seed: u64) -> ChaosReport {
+        let mut harness = ChaosHarness::new(ChaosConfig {
+            seed,
+            ..ChaosConfig::default()
+        })
+        .await;
+        harness.run_chaos(200).await;
+        assert!(harness.settle(60_000).await, "seed {seed}: did not settle");
+        harness.check_converged();
+        harness.report().clone()
+    }
+
+    #[tokio::test]
+    async fn test_chaos_keeps_blocks_and_transactions() {
+        let mut refused = 0;
+        let mut held_events = 0;
+        let mut assembly_timeouts = 0;
+        for seed in 0..8 {
+            let report = run(seed).await;
+
+            assert!(report.stops > 0, "seed {seed}: no faults injected");
+            assert!(report.blocks_stored > 0);
+            assert_eq!(
+                report.txs_accepted, 400,
+                "seed {seed}: client gave up on a tx"
+            );
+            refused += report.refused;
+            held_events += report.held_events;
+            assembly_timeouts += report.assembly_timeouts;
+        }
+
+        // The seeds hit the registry's refusals, replay and the timeout path,
+        // not just clean restarts
+        assert!(refused > 0);
+        assert!(held_events > 0);
+        assert!(assembly_timeouts > 0);
+    }
+
+    #[tokio::test]
+    async fn test_chaos_run_is_deterministic() {
+        assert_eq!(run(42).await, run(42).await);
+    }
+
+    #[tokio::test]
+    async fn test_registry_refuses_core_faults() {
+        let mut harness = ChaosHarness::new(ChaosConfig {
+            fault_rate: 0.0,
+            ..ChaosConfig::default()
+        })
+        .await;
+
+        for id in [
+            SubsystemId::BlockStorage,
+            SubsystemId::Consensus,
+            SubsystemId::SignatureVerification,
+        ] {
+            let refused = harness.stop(id).await.unwrap_err();
+            assert_eq!(refused.kind, SubsystemErrorKind::Refused);
+            assert_eq!(
+                harness.registry().status(id),
+                Some(SubsystemStatus::Healthy)
+            );
+        }
+
+        harness.stop(SubsystemId::Mempool).await.unwrap();
+        assert_eq!(
+            harness.registry().status(SubsystemId::Mempool),
+            Some(SubsystemStatus::Stopped)
+        );
+        harness.restart(SubsystemId::StateManagement).await.unwrap();
+        assert_eq!(harness.report().stops, 2);
+    }
+
+    #[tokio::test]
+    async fn test_tx_indexing_outage_times_out_assembly_and_retries() {
+        let mut harness = ChaosHarness::new(ChaosConfig {
+            fault_rate: 0.0,
+            ..ChaosConfig::default()
+        })
+        .await;
+        harness.submit_transactions(3);
+
+        // The block's Merkle root waits on the bus until indexing is back
+        harness
+            .stop(SubsystemId::TransactionIndexing)
+            .await
+            .unwrap();
+        harness.tick().await;
+        assert!(harness.report().held_events > 0);
+        harness.run_quiet(20).await;
+        assert!(harness.report().assembly_timeouts >= 1);
+        assert_eq!(harness.report().blocks_stored, 0);
+
+        harness
+            .start(SubsystemId::TransactionIndexing)
+            .await
+            .unwrap();
+        assert!(harness.settle(30_000).await);
+        harness.check_converged();
+
+        let report = harness.report();
+        assert_eq!(report.blocks_stored, 1);
+        assert_eq!(harness.storage().stored()[0].tx_hashes.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_mempool_restart_keeps_proposed_transactions() {
+        let mut harness = ChaosHarness::new(ChaosConfig {
+            fault_rate: 0.0,
+            ..ChaosConfig::default()
+        })
+        .await;
+        harness.submit_transactions(5);
+        harness.tick().await;
+
+        // The batch is PENDING_INCLUSION when the mempool goes down
+        harness.stop(SubsystemId::Mempool).await.unwrap();
+        harness.check_invariants();
+        harness.submit_transactions(2);
+
+        assert!(harness.settle(30_000).await);
+        harness.check_converged();
+        assert_eq!(harness.report().txs_accepted, 7);
+    }
+}
@@ -98,6 +98,18 @@ fn now_secs() -> u64 {
         .as_secs()
 }
 
+/// Coinbase claim paying the default block subsidy to the zero address
+#[cfg(test)]
+fn coinbase_for(block_height: u64) -> shared_types::CoinbaseTransaction {
+    shared_types::CoinbaseTransaction {
+        block_height,
+        miner_address: [0u8; 20],
+        reward: shared_types::RewardSchedule::default().subsidy_at(block_height),
+        fees: shared_types::U256::zero(),
+        timestamp: now_secs(),
+    }
+}
+
 /// Test orchestrator that coordinates subsystem instances
 #[cfg(test)]
 struct ChoreographyTestHarness {
@@ -440,6 +452,7 @@ mod tests {
             nonce: 123456789,
             timestamp,
             parent_hash,
+            coinbase: coinbase_for(block_height),
         };
 
         let receivers = event_bus.publish(event.clone()).await;
@@ -484,6 +497,7 @@ mod tests {
             nonce: 1,
             timestamp: now_secs(),
             parent_hash: [0u8; 32],
+            coinbase: coinbase_for(1),
         };
 
         let receivers = event_bus.publish(event).await;
@@ -1,4 +1,5 @@
 //! Integration tests
+pub mod chaos;
 pub mod e2e_choreography;
 pub mod flows;
 pub mod runtime_simulation;
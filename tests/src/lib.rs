@@ -9,7 +9,12 @@
 //! ├── benchmarks/       # Performance tests per subsystem
 //! │   ├── qc_01_peer_discovery.rs
 //! │   ├── qc_02_block_storage.rs
-//! │   └── ...
+//! │   ├── ...
+//! │   └── scenario/     # Workload-driven end-to-end runs, JSON reports
+//! │
+//! ├── differential/     # Property-based checks against reference impls
+//! │   ├── merkle.rs     # qc-03 MerkleTree
+//! │   └── trie.rs       # qc-04 Patricia trie
 //! │
 //! ├── exploits/         # Attack simulations
 //! │   ├── historical/   # Famous past attacks
@@ -19,7 +24,9 @@
 //! │   └── architectural/# System-level attacks
 //! │       └── qc_XX/
 //! │
-//! └── integration/      # Cross-subsystem choreography
+//! ├── integration/      # Cross-subsystem choreography
+//! │
+//! └── simulation/       # Deterministic in-process network simulation
 //! ```
 //!
 //! ## Running Tests
@@ -30,6 +37,7 @@
 //!
 //! # By category
 //! cargo test -p qc-tests integration::
+//! cargo test -p qc-tests differential::
 //! cargo test -p qc-tests exploits::historical::
 //! cargo test -p qc-tests exploits::modern::
 //! cargo test -p qc-tests exploits::architectural::
@@ -84,5 +92,7 @@
 #![allow(clippy::manual_repeat_n)]
 
 pub mod benchmarks;
+pub mod differential;
 pub mod exploits;
 pub mod integration;
+pub mod simulation;
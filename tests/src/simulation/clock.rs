@@ -0,0 +1,49 @@
+//! # Virtual Clock
+//!
+//! Simulated time in milliseconds. Only the simulation advances it, so a
+//! test that waits "ten minutes" finishes instantly and always sees the
+//! same timestamps.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use qc_01_peer_discovery::{TimeSource, Timestamp};
+
+/// Shared handle to simulated time.
+///
+/// Clones see the same time, so one clock can be handed to every node's
+/// `TimeSource` and to the network.
+#[derive(Clone, Debug, Default)]
+pub struct VirtualClock {
+    now_ms: Arc<AtomicU64>,
+}
+
+impl VirtualClock {
+    /// Create a clock starting at `start_ms`.
+    pub fn new(start_ms: u64) -> Self {
+        Self {
+            now_ms: Arc::new(AtomicU64::new(start_ms)),
+        }
+    }
+
+    /// Current simulated time in milliseconds.
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms.load(Ordering::SeqCst)
+    }
+
+    /// Move time forward to `ms`. Time never goes backwards.
+    pub fn advance_to(&self, ms: u64) {
+        self.now_ms.fetch_max(ms, Ordering::SeqCst);
+    }
+
+    /// Move time forward by `ms`.
+    pub fn advance(&self, ms: u64) {
+        self.now_ms.fetch_add(ms, Ordering::SeqCst);
+    }
+}
+
+impl TimeSource for VirtualClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::new(self.now_ms() / 1000)
+    }
+}
@@ -0,0 +1,270 @@
+//! # Simulated Discovery Node
+//!
+//! A qc-01 `PeerDiscoveryService` running on the virtual clock and speaking
+//! `DiscoveryMessage` over the simulated network. It does what the async
+//! `DiscoveryDriver` does, but on simulation ticks instead of Tokio timers:
+//!
+//! - PING is answered with PONG
+//! - PONG from a challenged peer keeps it (INVARIANT-10)
+//! - Each tick settles expired challenges, PINGs challenged peers and runs GC
+
+use qc_01_peer_discovery::{
+    calculate_bucket_index, DiscoveryMessage, IpAddr, KademliaConfig, NodeId, PeerDiscoveryApi,
+    PeerDiscoveryError, PeerDiscoveryService, PeerInfo, SocketAddr, TimeSource, Timestamp,
+};
+
+use super::clock::VirtualClock;
+use super::harness::{NodeContext, SimNode};
+use super::network::NodeIndex;
+use crate::exploits::helpers::keccak256;
+
+/// Port every simulated node listens on.
+const SIM_PORT: u16 = 30303;
+
+/// NodeId of the node at `index`; hashed so nodes spread over buckets.
+pub fn sim_node_id(index: NodeIndex) -> NodeId {
+    NodeId::new(keccak256(&(index as u64).to_be_bytes()))
+}
+
+/// Address of the node at `index`; every node gets its own /24.
+pub fn sim_addr(index: NodeIndex) -> SocketAddr {
+    let [.., hi, lo] = (index as u32).to_be_bytes();
+    SocketAddr::new(IpAddr::v4(10, hi, lo, 1), SIM_PORT)
+}
+
+/// Inverse of `sim_addr`.
+pub fn sim_index(addr: &SocketAddr) -> Option<NodeIndex> {
+    match addr.ip {
+        IpAddr::V4([10, hi, lo, 1]) if addr.port == SIM_PORT => {
+            Some(usize::from(u16::from_be_bytes([hi, lo])))
+        }
+        _ => None,
+    }
+}
+
+/// `PeerInfo` for the node at `index`, last seen at `clock`'s time.
+pub fn sim_peer(index: NodeIndex, clock: &VirtualClock) -> PeerInfo {
+    PeerInfo::new(sim_node_id(index), sim_addr(index), clock.now())
+}
+
+/// A peer discovery node inside the simulation.
+pub struct DiscoveryNode {
+    index: NodeIndex,
+    clock: VirtualClock,
+    service: PeerDiscoveryService,
+}
+
+impl DiscoveryNode {
+    /// Create the node at `index` with an empty routing table.
+    pub fn new(index: NodeIndex, clock: VirtualClock, config: KademliaConfig) -> Self {
+        let service =
+            PeerDiscoveryService::new(sim_node_id(index), config, Box::new(clock.clone()));
+        Self {
+            index,
+            clock,
+            service,
+        }
+    }
+
+    /// This node's NodeId.
+    pub fn node_id(&self) -> NodeId {
+        sim_node_id(self.index)
+    }
+
+    /// The wrapped service.
+    pub fn service(&self) -> &PeerDiscoveryService {
+        &self.service
+    }
+
+    /// The wrapped service, for setup.
+    pub fn service_mut(&mut self) -> &mut PeerDiscoveryService {
+        &mut self.service
+    }
+
+    /// Stage `peer` and pass Subsystem 10 verification straight away.
+    ///
+    /// Returns the NodeId challenged if the peer's bucket was full.
+    pub fn introduce(&mut self, peer: NodeIndex) -> Result<Option<NodeId>, PeerDiscoveryError> {
+        let info = sim_peer(peer, &self.clock);
+        let node_id = info.node_id;
+        self.service.add_peer(info)?;
+        self.service.on_verification_result(&node_id, true)
+    }
+
+    /// True if `peer` is in the routing table.
+    pub fn knows(&self, peer: NodeIndex) -> bool {
+        let node_id = sim_node_id(peer);
+        self.find(&node_id).is_some()
+    }
+
+    /// Number of peers in the routing table.
+    pub fn peer_count(&self) -> usize {
+        self.service.routing_table().total_peer_count()
+    }
+
+    fn now(&self) -> Timestamp {
+        self.clock.now()
+    }
+
+    fn find(&self, node_id: &NodeId) -> Option<&PeerInfo> {
+        let table = self.service.routing_table();
+        let index = calculate_bucket_index(table.local_node_id(), node_id);
+        table
+            .get_bucket(index)?
+            .peers()
+            .iter()
+            .find(|p| &p.node_id == node_id)
+    }
+
+    /// Refresh a peer known at `from`, settling its challenge on a PONG.
+    fn touch_if_known(&mut self, node_id: &NodeId, from: NodeIndex, is_pong: bool) {
+        let known = self
+            .find(node_id)
+            .is_some_and(|p| sim_index(&p.socket_addr) == Some(from));
+        if !known {
+            return;
+        }
+
+        if is_pong {
+            let _ = self.service.on_challenge_response(node_id, true);
+        }
+        let now = self.now();
+        let _ = self.service.routing_table_mut().touch_peer(node_id, now);
+    }
+}
+
+impl SimNode<DiscoveryMessage> for DiscoveryNode {
+    fn on_message(
+        &mut self,
+        ctx: &mut NodeContext<'_, DiscoveryMessage>,
+        from: NodeIndex,
+        msg: DiscoveryMessage,
+    ) {
+        match msg {
+            DiscoveryMessage::Ping { from: node_id } => {
+                self.touch_if_known(&node_id, from, false);
+                ctx.send(
+                    from,
+                    DiscoveryMessage::Pong {
+                        from: self.node_id(),
+                    },
+                );
+            }
+            DiscoveryMessage::Pong { from: node_id } => {
+                self.touch_if_known(&node_id, from, true);
+            }
+            DiscoveryMessage::FindNode { from: node_id, .. } => {
+                self.touch_if_known(&node_id, from, false);
+            }
+        }
+    }
+
+    fn on_tick(&mut self, ctx: &mut NodeContext<'_, DiscoveryMessage>) {
+        self.service.check_expired_challenges();
+        for peer in self.service.routing_table().pending_challenges() {
+            if let Some(to) = sim_index(&peer.socket_addr) {
+                ctx.send(
+                    to,
+                    DiscoveryMessage::Ping {
+                        from: self.node_id(),
+                    },
+                );
+            }
+        }
+        self.service.gc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{LinkConfig, SimConfig, Simulation};
+
+    type DiscoverySim = Simulation<DiscoveryNode, DiscoveryMessage>;
+
+    /// `count` node indices (after 0) that land in bucket 0 of node 0.
+    fn bucket_zero_peers(count: usize) -> Vec<NodeIndex> {
+        let local = sim_node_id(0);
+        (1..)
+            .filter(|&i| calculate_bucket_index(&local, &sim_node_id(i)) == 0)
+            .take(count)
+            .collect()
+    }
+
+    /// Node 0 with a full bucket 0 (k = 3) and a fourth peer challenging
+    /// the oldest. Returns the sim, the challenged peer and the newcomer.
+    fn challenge_setup(config: SimConfig) -> (DiscoverySim, NodeIndex, NodeIndex) {
+        let kademlia = KademliaConfig {
+            eviction_challenge_timeout_secs: 5,
+            ..KademliaConfig::for_testing()
+        };
+        let peers = bucket_zero_peers(4);
+        let mut sim = Simulation::new(config);
+        for _ in 0..=peers[3] {
+            sim.add_node(|i, clock| DiscoveryNode::new(i, clock, kademlia.clone()));
+        }
+
+        let victim = sim.node_mut(0);
+        for &peer in &peers[..3] {
+            assert_eq!(victim.introduce(peer).unwrap(), None);
+        }
+        assert_eq!(
+            victim.introduce(peers[3]).unwrap(),
+            Some(sim_node_id(peers[0]))
+        );
+        (sim, peers[0], peers[3])
+    }
+
+    #[test]
+    fn test_sim_addr_round_trip() {
+        for index in [0, 1, 255, 256, 65_535] {
+            assert_eq!(sim_index(&sim_addr(index)), Some(index));
+        }
+    }
+
+    #[test]
+    fn test_live_peer_survives_challenge() {
+        let (mut sim, oldest, newcomer) = challenge_setup(SimConfig::default());
+
+        let settled = sim.run_until(10_000, |sim| {
+            sim.node(0)
+                .service()
+                .routing_table()
+                .pending_challenges()
+                .is_empty()
+        });
+
+        assert!(settled);
+        assert!(sim.node(0).knows(oldest));
+        assert!(!sim.node(0).knows(newcomer));
+    }
+
+    #[test]
+    fn test_partitioned_peer_is_evicted() {
+        let (mut sim, oldest, newcomer) = challenge_setup(SimConfig::default());
+        sim.network_mut().partition(&[&[oldest]]);
+
+        sim.run_for(7_000);
+
+        // INVARIANT-10: an unreachable peer loses its slot once the challenge expires
+        assert!(!sim.node(0).knows(oldest));
+        assert!(sim.node(0).knows(newcomer));
+        assert!(sim.stats().partitioned > 0);
+    }
+
+    #[test]
+    fn test_same_seed_same_run() {
+        let run = |seed| {
+            let config = SimConfig {
+                seed,
+                link: LinkConfig::new(300).with_jitter(400).with_loss(0.4),
+                ..SimConfig::default()
+            };
+            let (mut sim, oldest, _) = challenge_setup(config);
+            sim.run_for(5_000);
+            (sim.stats().clone(), sim.node(0).knows(oldest))
+        };
+
+        assert_eq!(run(42), run(42));
+    }
+}
@@ -0,0 +1,195 @@
+//! # Simulation Harness
+//!
+//! Runs in-process nodes over a `SimNetwork` as a discrete-event loop.
+//! Each step advances the `VirtualClock` straight to the next event, which
+//! is either a message delivery or the periodic tick, so no test sleeps.
+
+use super::clock::VirtualClock;
+use super::network::{LinkConfig, NetworkStats, NodeIndex, SimNetwork};
+
+/// A node that can run inside a `Simulation`.
+pub trait SimNode<M> {
+    /// Handle a message delivered by the network.
+    fn on_message(&mut self, ctx: &mut NodeContext<'_, M>, from: NodeIndex, msg: M);
+
+    /// Periodic timer work (challenges, GC, refresh).
+    fn on_tick(&mut self, _ctx: &mut NodeContext<'_, M>) {}
+}
+
+/// What a node can see and do while handling an event.
+pub struct NodeContext<'a, M> {
+    index: NodeIndex,
+    now_ms: u64,
+    outbox: &'a mut Vec<(NodeIndex, M)>,
+}
+
+impl<M> NodeContext<'_, M> {
+    /// This node's index.
+    pub fn index(&self) -> NodeIndex {
+        self.index
+    }
+
+    /// Simulated time in milliseconds.
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+
+    /// Queue `msg` for `to`; it enters the network when the handler returns.
+    pub fn send(&mut self, to: NodeIndex, msg: M) {
+        self.outbox.push((to, msg));
+    }
+}
+
+/// Settings for a `Simulation`.
+#[derive(Clone, Debug)]
+pub struct SimConfig {
+    /// Seed for loss and jitter.
+    pub seed: u64,
+    /// Clock value at the start, in milliseconds.
+    pub start_ms: u64,
+    /// Interval between `on_tick` calls, in milliseconds.
+    pub tick_interval_ms: u64,
+    /// Default link between any two nodes.
+    pub link: LinkConfig,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            start_ms: 1_000_000,
+            tick_interval_ms: 1_000,
+            link: LinkConfig::default(),
+        }
+    }
+}
+
+/// Deterministic run of `N` nodes exchanging `M` messages.
+pub struct Simulation<N, M> {
+    clock: VirtualClock,
+    network: SimNetwork<M>,
+    nodes: Vec<N>,
+    tick_interval_ms: u64,
+    next_tick_ms: u64,
+    outbox: Vec<(NodeIndex, M)>,
+}
+
+impl<N: SimNode<M>, M> Simulation<N, M> {
+    /// Create an empty simulation.
+    pub fn new(config: SimConfig) -> Self {
+        let tick_interval_ms = config.tick_interval_ms.max(1);
+        Self {
+            clock: VirtualClock::new(config.start_ms),
+            network: SimNetwork::new(config.seed, config.link),
+            nodes: Vec::new(),
+            tick_interval_ms,
+            next_tick_ms: config.start_ms + tick_interval_ms,
+            outbox: Vec::new(),
+        }
+    }
+
+    /// The simulation clock; hand clones to nodes as their time source.
+    pub fn clock(&self) -> &VirtualClock {
+        &self.clock
+    }
+
+    /// Add a node built from its index and the shared clock.
+    pub fn add_node(&mut self, build: impl FnOnce(NodeIndex, VirtualClock) -> N) -> NodeIndex {
+        let index = self.nodes.len();
+        self.nodes.push(build(index, self.clock.clone()));
+        index
+    }
+
+    /// A node by index.
+    pub fn node(&self, index: NodeIndex) -> &N {
+        &self.nodes[index]
+    }
+
+    /// A node by index, for setup and assertions.
+    pub fn node_mut(&mut self, index: NodeIndex) -> &mut N {
+        &mut self.nodes[index]
+    }
+
+    /// All nodes in index order.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// The network, for links and partitions.
+    pub fn network_mut(&mut self) -> &mut SimNetwork<M> {
+        &mut self.network
+    }
+
+    /// Network counters so far.
+    pub fn stats(&self) -> &NetworkStats {
+        self.network.stats()
+    }
+
+    /// Send a message as if `from` had sent it now.
+    pub fn inject(&mut self, from: NodeIndex, to: NodeIndex, msg: M) {
+        self.network.send(self.clock.now_ms(), from, to, msg);
+    }
+
+    /// Handle the next event and return the time it happened.
+    ///
+    /// A delivery due at the same time as a tick goes first.
+    pub fn step(&mut self) -> u64 {
+        let next = self.next_event_ms();
+        self.clock.advance_to(next);
+
+        if let Some((from, to, msg)) = self.network.pop_due(next) {
+            self.dispatch(to, |node, ctx| node.on_message(ctx, from, msg));
+        } else if next >= self.next_tick_ms {
+            self.next_tick_ms += self.tick_interval_ms;
+            for index in 0..self.nodes.len() {
+                self.dispatch(index, |node, ctx| node.on_tick(ctx));
+            }
+        }
+        next
+    }
+
+    /// Run every event up to `ms` from now, then set the clock there.
+    pub fn run_for(&mut self, ms: u64) {
+        let deadline = self.clock.now_ms() + ms;
+        while self.next_event_ms() <= deadline {
+            self.step();
+        }
+        self.clock.advance_to(deadline);
+    }
+
+    /// Run until `done` holds or `max_ms` has passed.
+    ///
+    /// Returns whether `done` held.
+    pub fn run_until(&mut self, max_ms: u64, mut done: impl FnMut(&Self) -> bool) -> bool {
+        let deadline = self.clock.now_ms() + max_ms;
+        while !done(self) {
+            if self.next_event_ms() > deadline {
+                self.clock.advance_to(deadline);
+                return false;
+            }
+            self.step();
+        }
+        true
+    }
+
+    fn next_event_ms(&self) -> u64 {
+        self.network
+            .next_delivery_ms()
+            .map_or(self.next_tick_ms, |at| at.min(self.next_tick_ms))
+    }
+
+    fn dispatch(&mut self, index: NodeIndex, handle: impl FnOnce(&mut N, &mut NodeContext<'_, M>)) {
+        let now_ms = self.clock.now_ms();
+        let mut ctx = NodeContext {
+            index,
+            now_ms,
+            outbox: &mut self.outbox,
+        };
+        if let Some(node) = self.nodes.get_mut(index) {
+            handle(node, &mut ctx);
+        }
+        for (to, msg) in self.outbox.drain(..) {
+            self.network.send(now_ms, index, to, msg);
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! # Simulated Network
+//!
+//! Point-to-point message delivery between simulated nodes. Every send is
+//! scheduled on an event queue at `now + latency + jitter`, unless it is
+//! lost or crosses a partition. All randomness comes from one seeded RNG,
+//! so the same seed and the same sends give the same deliveries.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Position of a node in the simulation.
+pub type NodeIndex = usize;
+
+/// Delivery characteristics of a link.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinkConfig {
+    /// Base one-way latency in milliseconds.
+    pub latency_ms: u64,
+    /// Extra latency drawn uniformly from `0..=jitter_ms`.
+    pub jitter_ms: u64,
+    /// Probability in `[0, 1]` that a message is dropped.
+    pub loss_rate: f64,
+}
+
+impl Default for LinkConfig {
+    fn default() -> Self {
+        Self::new(50)
+    }
+}
+
+impl LinkConfig {
+    /// A lossless link with fixed latency.
+    pub fn new(latency_ms: u64) -> Self {
+        Self {
+            latency_ms,
+            jitter_ms: 0,
+            loss_rate: 0.0,
+        }
+    }
+
+    /// Add up to `jitter_ms` of random extra latency.
+    pub fn with_jitter(mut self, jitter_ms: u64) -> Self {
+        self.jitter_ms = jitter_ms;
+        self
+    }
+
+    /// Drop messages with probability `loss_rate`.
+    pub fn with_loss(mut self, loss_rate: f64) -> Self {
+        self.loss_rate = loss_rate.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// Counters for everything the network has done.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NetworkStats {
+    /// Messages handed to `send`.
+    pub sent: u64,
+    /// Messages handed to their recipient.
+    pub delivered: u64,
+    /// Messages dropped by `loss_rate`.
+    pub lost: u64,
+    /// Messages dropped because sender and recipient were partitioned.
+    pub partitioned: u64,
+}
+
+/// A message on its way to a node.
+struct InFlight<M> {
+    deliver_at: u64,
+    seq: u64,
+    from: NodeIndex,
+    to: NodeIndex,
+    msg: M,
+}
+
+impl<M> PartialEq for InFlight<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.seq == other.seq
+    }
+}
+
+impl<M> Eq for InFlight<M> {}
+
+impl<M> PartialOrd for InFlight<M> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<M> Ord for InFlight<M> {
+    /// Earliest delivery first; ties go to the earlier send.
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.deliver_at, other.seq).cmp(&(self.deliver_at, self.seq))
+    }
+}
+
+/// Deterministic simulated network carrying messages of type `M`.
+pub struct SimNetwork<M> {
+    rng: StdRng,
+    default_link: LinkConfig,
+    links: HashMap<(NodeIndex, NodeIndex), LinkConfig>,
+    /// Partition group per node; unlisted nodes share the default group.
+    groups: HashMap<NodeIndex, usize>,
+    in_flight: BinaryHeap<InFlight<M>>,
+    seq: u64,
+    stats: NetworkStats,
+}
+
+impl<M> SimNetwork<M> {
+    /// Create a fully connected network where every link is `default_link`.
+    pub fn new(seed: u64, default_link: LinkConfig) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            default_link,
+            links: HashMap::new(),
+            groups: HashMap::new(),
+            in_flight: BinaryHeap::new(),
+            seq: 0,
+            stats: NetworkStats::default(),
+        }
+    }
+
+    /// Override the link between `a` and `b`, in both directions.
+    pub fn set_link(&mut self, a: NodeIndex, b: NodeIndex, link: LinkConfig) {
+        self.links.insert((a, b), link);
+        self.links.insert((b, a), link);
+    }
+
+    /// Split the network into `groups`.
+    ///
+    /// Nodes can only reach nodes in the same group. Nodes not listed form
+    /// one more group together. Messages already in flight across the new
+    /// boundary are dropped on arrival.
+    pub fn partition(&mut self, groups: &[&[NodeIndex]]) {
+        self.groups.clear();
+        for (group, nodes) in groups.iter().enumerate() {
+            for &node in *nodes {
+                self.groups.insert(node, group + 1);
+            }
+        }
+    }
+
+    /// Remove every partition.
+    pub fn heal(&mut self) {
+        self.groups.clear();
+    }
+
+    /// True if `a` and `b` are on the same side of every partition.
+    pub fn can_reach(&self, a: NodeIndex, b: NodeIndex) -> bool {
+        self.groups.get(&a).unwrap_or(&0) == self.groups.get(&b).unwrap_or(&0)
+    }
+
+    /// Send `msg` from `from` to `to` at simulated time `now_ms`.
+    pub fn send(&mut self, now_ms: u64, from: NodeIndex, to: NodeIndex, msg: M) {
+        self.stats.sent += 1;
+
+        if !self.can_reach(from, to) {
+            self.stats.partitioned += 1;
+            return;
+        }
+
+        let link = self
+            .links
+            .get(&(from, to))
+            .copied()
+            .unwrap_or(self.default_link);
+        if link.loss_rate > 0.0 && self.rng.gen_bool(link.loss_rate) {
+            self.stats.lost += 1;
+            return;
+        }
+
+        let jitter = match link.jitter_ms {
+            0 => 0,
+            max => self.rng.gen_range(0..=max),
+        };
+        self.seq += 1;
+        self.in_flight.push(InFlight {
+            deliver_at: now_ms + link.latency_ms + jitter,
+            seq: self.seq,
+            from,
+            to,
+            msg,
+        });
+    }
+
+    /// When the next message arrives, if any is in flight.
+    pub fn next_delivery_ms(&self) -> Option<u64> {
+        self.in_flight.peek().map(|m| m.deliver_at)
+    }
+
+    /// Take the next message due at or before `now_ms`.
+    ///
+    /// Returns `(from, to, msg)`. Messages that now cross a partition are
+    /// dropped and skipped.
+    pub fn pop_due(&mut self, now_ms: u64) -> Option<(NodeIndex, NodeIndex, M)> {
+        while self.next_delivery_ms()? <= now_ms {
+            let m = self.in_flight.pop()?;
+            if !self.can_reach(m.from, m.to) {
+                self.stats.partitioned += 1;
+                continue;
+            }
+            self.stats.delivered += 1;
+            return Some((m.from, m.to, m.msg));
+        }
+        None
+    }
+
+    /// Number of messages still in flight.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Counters so far.
+    pub fn stats(&self) -> &NetworkStats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delivery_order_follows_latency() {
+        let mut net = SimNetwork::new(1, LinkConfig::new(100));
+        net.set_link(0, 2, LinkConfig::new(10));
+        net.send(0, 0, 1, "slow");
+        net.send(0, 0, 2, "fast");
+
+        assert_eq!(net.pop_due(9), None);
+        assert_eq!(net.pop_due(10), Some((0, 2, "fast")));
+        assert_eq!(net.pop_due(99), None);
+        assert_eq!(net.pop_due(100), Some((0, 1, "slow")));
+        assert_eq!(net.stats().delivered, 2);
+    }
+
+    #[test]
+    fn test_partition_drops_sent_and_in_flight_messages() {
+        let mut net = SimNetwork::new(1, LinkConfig::new(10));
+        net.send(0, 0, 1, 1);
+        net.partition(&[&[0], &[1, 2]]);
+        net.send(0, 0, 1, 2);
+        net.send(0, 1, 2, 3);
+
+        // The message sent before the split is dropped on arrival
+        assert_eq!(net.pop_due(10), Some((1, 2, 3)));
+        assert_eq!(net.stats().partitioned, 2);
+
+        net.heal();
+        net.send(10, 0, 1, 4);
+        assert_eq!(net.pop_due(20), Some((0, 1, 4)));
+    }
+
+    #[test]
+    fn test_loss_and_jitter_are_reproducible() {
+        let run = |seed| {
+            let link = LinkConfig::new(20).with_jitter(30).with_loss(0.25);
+            let mut net = SimNetwork::new(seed, link);
+            for i in 0..200u32 {
+                net.send(0, 0, 1, i);
+            }
+            let mut arrivals = Vec::new();
+            while let Some(at) = net.next_delivery_ms() {
+                let (_, _, msg) = net.pop_due(at).unwrap();
+                arrivals.push((at, msg));
+            }
+            (arrivals, net.stats().clone())
+        };
+
+        let (arrivals, stats) = run(7);
+        assert_eq!(run(7), (arrivals.clone(), stats.clone()));
+        assert_ne!(run(8).0, arrivals);
+
+        assert_eq!(stats.sent, 200);
+        assert_eq!(stats.delivered + stats.lost, 200);
+        assert!(stats.lost > 20 && stats.lost < 80);
+        assert!(arrivals.iter().all(|(at, _)| (20..=50).contains(at)));
+    }
+}